@@ -0,0 +1,107 @@
+//! Latency of listing a user's 100 URLs with 3 tags each
+//!
+//! Compares `PostgresUrlRepository::find_by_user_id`, which joins the tags, with loading
+//! them through one query per URL. Needs a database created from init.sql:
+//! `TEST_DATABASE_URL=postgres://... cargo bench --bench find_by_user_id`
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use sqlx::PgPool;
+use tokio::runtime::Runtime;
+use url_shortner::domain::repositories::UrlRepository;
+use url_shortner::infrastructure::PostgresUrlRepository;
+
+const URL_COUNT: i32 = 100;
+const TAGS_PER_URL: i32 = 3;
+
+/// Create a user owning `URL_COUNT` URLs with `TAGS_PER_URL` tags each, returning its ID
+async fn seed(pool: &PgPool) -> i32 {
+    let prefix = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+    let user_id: i32 = sqlx::query_scalar(
+        "INSERT INTO users (username, email, password_hash) VALUES ($1, $1 || '@example.com', 'x') RETURNING id",
+    )
+    .bind(&prefix)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO urls (short_code, original_url, user_id)
+         SELECT $2 || g, 'https://example.com/' || g, $1 FROM generate_series(1, $3) g",
+    )
+    .bind(user_id)
+    .bind(&prefix)
+    .bind(URL_COUNT)
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO tags (name) SELECT $1 || '-' || g FROM generate_series(0, 9) g")
+        .bind(&prefix)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO url_tags (url_id, tag_id)
+         SELECT u.id, t.id FROM urls u
+         CROSS JOIN generate_series(0, $3 - 1) k
+         JOIN tags t ON t.name = $2 || '-' || ((u.id + k) % 10)
+         WHERE u.user_id = $1",
+    )
+    .bind(user_id)
+    .bind(&prefix)
+    .bind(TAGS_PER_URL)
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query("ANALYZE").execute(pool).await.unwrap();
+    user_id
+}
+
+/// The URLs, then the tags of each URL in its own query
+async fn find_with_tag_query_per_url(pool: &PgPool, user_id: i32) -> Vec<(i32, Vec<String>)> {
+    let ids: Vec<i32> = sqlx::query_scalar(
+        "SELECT id FROM urls WHERE user_id = $1 AND deleted_at IS NULL ORDER BY created_at DESC",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .unwrap();
+
+    let mut urls = Vec::with_capacity(ids.len());
+    for id in ids {
+        let tags: Vec<String> = sqlx::query_scalar(
+            "SELECT t.name FROM url_tags ut JOIN tags t ON t.id = ut.tag_id WHERE ut.url_id = $1 ORDER BY t.name",
+        )
+        .bind(id)
+        .fetch_all(pool)
+        .await
+        .unwrap();
+        urls.push((id, tags));
+    }
+    urls
+}
+
+fn bench_find_by_user_id(c: &mut Criterion) {
+    let Ok(database_url) = std::env::var("TEST_DATABASE_URL") else {
+        eprintln!("TEST_DATABASE_URL is not set, skipping the find_by_user_id benchmark");
+        return;
+    };
+    let runtime = Runtime::new().unwrap();
+    let pool = runtime.block_on(PgPool::connect(&database_url)).unwrap();
+    let user_id = runtime.block_on(seed(&pool));
+    let repository = PostgresUrlRepository::new(pool.clone());
+
+    let mut group = c.benchmark_group("find_by_user_id");
+    group.bench_function("tag_query_per_url", |b| {
+        b.iter(|| runtime.block_on(find_with_tag_query_per_url(&pool, user_id)))
+    });
+    group.bench_function("joined_tags", |b| {
+        b.iter(|| {
+            runtime
+                .block_on(repository.find_by_user_id(user_id, None))
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_find_by_user_id);
+criterion_main!(benches);
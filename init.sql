@@ -13,20 +13,103 @@ CREATE TABLE IF NOT EXISTS users (
     website VARCHAR(500),
     location VARCHAR(200),
     privacy VARCHAR(20) DEFAULT 'public' CHECK (privacy IN ('public', 'private', 'friends_only')),
-    updated_at TIMESTAMPTZ
+    updated_at TIMESTAMPTZ,
+    -- Account status fields
+    account_status VARCHAR(30) NOT NULL DEFAULT 'active' CHECK (account_status IN ('active', 'suspended', 'pending_verification', 'deactivated')),
+    suspension_reason TEXT,
+    suspended_until TIMESTAMPTZ,
+    tier VARCHAR(20) NOT NULL DEFAULT 'free' CHECK (tier IN ('free', 'premium')),
+    -- Session tokens issued before this time are rejected
+    password_changed_at TIMESTAMPTZ,
+    -- Details shown on the public profile
+    show_url_count BOOLEAN NOT NULL DEFAULT FALSE,
+    show_click_count BOOLEAN NOT NULL DEFAULT FALSE,
+    show_join_date BOOLEAN NOT NULL DEFAULT TRUE,
+    show_bio BOOLEAN NOT NULL DEFAULT TRUE,
+    show_website BOOLEAN NOT NULL DEFAULT TRUE,
+    show_social_links BOOLEAN NOT NULL DEFAULT TRUE,
+    show_recent_urls BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Links to the user's accounts elsewhere: twitter, github, linkedin, website and custom
+    social_links JSONB,
+    -- Linked social login account; accounts created through one have an empty password hash
+    oauth_provider VARCHAR(20) CHECK (oauth_provider IN ('google', 'github')),
+    oauth_provider_id VARCHAR(255),
+    -- FCM registration tokens of the user's mobile devices, oldest first
+    device_tokens TEXT[] NOT NULL DEFAULT '{}',
+    -- Address the user is changing their email to, until both addresses confirmed
+    pending_email VARCHAR(255),
+    UNIQUE (oauth_provider, oauth_provider_id)
+);
+
+-- Create the organizations table (team workspaces)
+CREATE TABLE IF NOT EXISTS organizations (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    slug VARCHAR(50) UNIQUE NOT NULL,
+    owner_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create the organization_members table
+CREATE TABLE IF NOT EXISTS organization_members (
+    org_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role VARCHAR(20) NOT NULL DEFAULT 'member' CHECK (role IN ('owner', 'admin', 'member')),
+    joined_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (org_id, user_id)
 );
 
 -- Create the urls table
 CREATE TABLE IF NOT EXISTS urls (
     id SERIAL PRIMARY KEY,
-    short_code VARCHAR(50) UNIQUE NOT NULL,
+    -- Unique among URLs that are not deleted, see idx_urls_short_code_live
+    short_code VARCHAR(50) NOT NULL,
     original_url TEXT NOT NULL,
     created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
     expiration_date TIMESTAMPTZ,
     user_id INTEGER REFERENCES users(id),
-    status VARCHAR(20) DEFAULT 'active' CHECK (status IN ('active', 'inactive'))
+    status VARCHAR(20) DEFAULT 'active' CHECK (status IN ('active', 'inactive', 'archived', 'deleted')),
+    -- URLs owned by an organization are shared with all of its members
+    organization_id INTEGER REFERENCES organizations(id) ON DELETE SET NULL,
+    -- Optimistic locking: incremented on every UPDATE
+    version BIGINT NOT NULL DEFAULT 1,
+    -- Maintained by the urls_updated_at trigger
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- When visitors see the preview interstitial before being redirected
+    preview_mode VARCHAR(10) NOT NULL DEFAULT 'none'
+        CHECK (preview_mode IN ('none', 'always', 'high_risk')),
+    -- Every click seen by the click tracker, including repeats dropped by deduplication
+    deduplicated_click_count BIGINT NOT NULL DEFAULT 0,
+    -- Fixed-size key for looking URLs up by destination; compare original_url too
+    url_hash TEXT GENERATED ALWAYS AS (md5(original_url)) STORED,
+    -- Set when the URL is deleted; the cleanup removes the row after the deleted URL retention
+    deleted_at TIMESTAMPTZ DEFAULT NULL,
+    -- Label chosen by the owner
+    title VARCHAR(255),
+    -- Permanent (308) or temporary (307) redirect
+    redirect_type VARCHAR(10) NOT NULL DEFAULT 'permanent'
+        CHECK (redirect_type IN ('permanent', 'temporary')),
+    -- Bcrypt hash of the password visitors must give; NULL for public links
+    password_hash VARCHAR(255),
+    -- Clicks after which the URL stops redirecting; NULL for no limit
+    max_clicks BIGINT CHECK (max_clicks > 0)
 );
 
+-- Stamp updated_at on every change so callers never have to set it; click counter
+-- increments are not changes to the URL
+CREATE OR REPLACE FUNCTION update_updated_at_column() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = CURRENT_TIMESTAMP;
+    RETURN NEW;
+END
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS urls_updated_at ON urls;
+CREATE TRIGGER urls_updated_at BEFORE UPDATE ON urls
+    FOR EACH ROW
+    WHEN (OLD.deduplicated_click_count IS NOT DISTINCT FROM NEW.deduplicated_click_count)
+    EXECUTE PROCEDURE update_updated_at_column();
+
 -- Create the clicks table for analytics tracking
 CREATE TABLE IF NOT EXISTS clicks (
     id SERIAL PRIMARY KEY,
@@ -36,20 +119,185 @@ CREATE TABLE IF NOT EXISTS clicks (
     user_agent TEXT,
     referer TEXT,
     country_code VARCHAR(2),
-    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    -- Value of the visitor's click cookie, used to attribute conversions to this click
+    click_token VARCHAR(64),
+    -- The visitor's IP had an abuse score over the configured threshold
+    suspicious BOOLEAN NOT NULL DEFAULT FALSE,
+    -- UTM parameters of the destination URL, or else of the referring page
+    utm_source VARCHAR(255),
+    utm_medium VARCHAR(255),
+    utm_campaign VARCHAR(255)
+);
+
+-- Daily HyperLogLog sketches of click IPs for approximate unique visitor counts.
+-- The hll extension is optional; without it unique visitors are counted from clicks.
+DO $$
+BEGIN
+    CREATE EXTENSION IF NOT EXISTS hll;
+EXCEPTION WHEN OTHERS THEN
+    RAISE NOTICE 'hll extension not available, unique visitors use COUNT(DISTINCT ip_address)';
+END
+$$;
+
+CREATE TABLE IF NOT EXISTS click_hll (
+    url_id INTEGER NOT NULL REFERENCES urls(id) ON DELETE CASCADE,
+    date DATE NOT NULL,
+    hll_state BYTEA NOT NULL,
+    PRIMARY KEY (url_id, date)
+);
+
+-- URL and click counters of each user, read by the dashboard instead of aggregating
+CREATE TABLE IF NOT EXISTS user_url_stats (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    total_urls BIGINT NOT NULL DEFAULT 0,
+    active_urls BIGINT NOT NULL DEFAULT 0,
+    inactive_urls BIGINT NOT NULL DEFAULT 0,
+    archived_urls BIGINT NOT NULL DEFAULT 0,
+    total_clicks BIGINT NOT NULL DEFAULT 0,
+    last_updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
 );
 
 -- Create indexes for faster lookups
 CREATE INDEX IF NOT EXISTS idx_urls_short_code ON urls(short_code);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_urls_short_code_live ON urls(short_code) WHERE deleted_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_urls_deleted_at ON urls(deleted_at) WHERE deleted_at IS NOT NULL;
 CREATE INDEX IF NOT EXISTS idx_urls_expiration_date ON urls(expiration_date);
-CREATE INDEX IF NOT EXISTS idx_urls_status ON urls(status);
+-- Status filters, optionally for one user (see migrations/add_missing_indexes.sql)
+CREATE INDEX IF NOT EXISTS idx_urls_status_user_id ON urls(status, user_id);
+CREATE INDEX IF NOT EXISTS idx_urls_status_id ON urls(status, id);
+-- Keyset pagination of a user's URLs by each sort field (see migrations/add_missing_indexes.sql)
+CREATE INDEX IF NOT EXISTS idx_urls_user_created_at_id ON urls(user_id, created_at, id);
+CREATE INDEX IF NOT EXISTS idx_urls_user_short_code_id ON urls(user_id, short_code, id);
+CREATE INDEX IF NOT EXISTS idx_urls_organization_id ON urls(organization_id);
+-- Activity feed of a user's recently modified URLs
+CREATE INDEX IF NOT EXISTS idx_urls_user_updated_at ON urls(user_id, updated_at);
+-- URLs by destination, for one user or across users (see migrations/add_urls_url_hash.sql)
+CREATE INDEX IF NOT EXISTS idx_urls_url_hash ON urls(url_hash);
+CREATE INDEX IF NOT EXISTS idx_organization_members_user_id ON organization_members(user_id);
 CREATE INDEX IF NOT EXISTS idx_users_username ON users(username);
 CREATE INDEX IF NOT EXISTS idx_users_email ON users(email);
+-- Emails are unique regardless of case
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_email_lower ON users(lower(email));
+-- Admin user search by partial username or email and by display name
+-- (see migrations/add_user_search_indexes.sql)
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+CREATE INDEX IF NOT EXISTS idx_users_username_trgm ON users USING GIN (lower(username) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_users_email_trgm ON users USING GIN (lower(email) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_users_display_name_fts ON users USING GIN (
+    to_tsvector('simple', coalesce(first_name, '') || ' ' || coalesce(last_name, ''))
+);
 
 -- Create indexes for analytics performance
 CREATE INDEX IF NOT EXISTS idx_clicks_url_id ON clicks(url_id);
 CREATE INDEX IF NOT EXISTS idx_clicks_clicked_at ON clicks(clicked_at);
+-- Also serves newest-first queries with a backward scan
 CREATE INDEX IF NOT EXISTS idx_clicks_url_clicked_at ON clicks(url_id, clicked_at);
+CREATE INDEX IF NOT EXISTS idx_clicks_url_country ON clicks(url_id, country_code);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_clicks_click_token ON clicks(click_token);
+
+-- Create the conversion_goals table (pages that count as a conversion for a URL)
+CREATE TABLE IF NOT EXISTS conversion_goals (
+    id SERIAL PRIMARY KEY,
+    url_id INTEGER NOT NULL REFERENCES urls(id) ON DELETE CASCADE,
+    goal_url_pattern TEXT NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create the conversion_events table (visitors arriving from a click who reached a goal)
+CREATE TABLE IF NOT EXISTS conversion_events (
+    id SERIAL PRIMARY KEY,
+    click_id INTEGER REFERENCES clicks(id) ON DELETE SET NULL,
+    goal_id INTEGER NOT NULL REFERENCES conversion_goals(id) ON DELETE CASCADE,
+    converted_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (click_id, goal_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_conversion_goals_url_id ON conversion_goals(url_id);
+CREATE INDEX IF NOT EXISTS idx_conversion_events_goal_id ON conversion_events(goal_id);
+
+-- Create the url_configs table (per-URL click tracking settings; missing rows use the defaults)
+CREATE TABLE IF NOT EXISTS url_configs (
+    url_id INTEGER PRIMARY KEY REFERENCES urls(id) ON DELETE CASCADE,
+    -- Seconds a repeat click from the same IP is ignored; NULL uses click_dedup_window_seconds
+    click_dedup_window_seconds INTEGER CHECK (click_dedup_window_seconds >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create the short_code_aliases table (old short codes that keep resolving after a re-encode
+-- or, flagged legacy, after their owner renamed the URL)
+CREATE TABLE IF NOT EXISTS short_code_aliases (
+    short_code VARCHAR(50) PRIMARY KEY,
+    url_id INTEGER NOT NULL REFERENCES urls(id) ON DELETE CASCADE,
+    is_legacy BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_short_code_aliases_url_id ON short_code_aliases(url_id);
+
+-- Create the tags and url_tags tables (labels users put on their URLs)
+CREATE TABLE IF NOT EXISTS tags (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(50) NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS url_tags (
+    url_id INTEGER NOT NULL REFERENCES urls(id) ON DELETE CASCADE,
+    tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (url_id, tag_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_url_tags_tag_id ON url_tags(tag_id);
+
+-- Counter encoded by the sequential short code strategy
+CREATE SEQUENCE IF NOT EXISTS short_code_seq;
+
+-- Create the email_outbox table (emails are queued here and sent by a background poller)
+CREATE TABLE IF NOT EXISTS email_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    recipient VARCHAR(255) NOT NULL,
+    subject TEXT NOT NULL,
+    body_html TEXT,
+    body_text TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- Earliest time of the next sending attempt; pushed back by the retry backoff
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    sent_at TIMESTAMPTZ,
+    -- Set once every attempt failed
+    failed_at TIMESTAMPTZ,
+    attempt_count INTEGER NOT NULL DEFAULT 0,
+    error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_outbox_pending ON email_outbox(next_attempt_at)
+    WHERE sent_at IS NULL AND failed_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_email_outbox_sent_at ON email_outbox(sent_at)
+    WHERE sent_at IS NOT NULL;
+
+-- Create the push_outbox table (push notifications are queued here and sent by a background poller)
+CREATE TABLE IF NOT EXISTS push_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    device_token TEXT NOT NULL,
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    -- App link opened when the notification is tapped, e.g. shortener://urls/42
+    deep_link TEXT NOT NULL,
+    -- Notifications with the same key are queued once per device
+    dedup_key VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    sent_at TIMESTAMPTZ,
+    failed_at TIMESTAMPTZ,
+    attempt_count INTEGER NOT NULL DEFAULT 0,
+    error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_push_outbox_pending ON push_outbox(next_attempt_at)
+    WHERE sent_at IS NULL AND failed_at IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_push_outbox_dedup ON push_outbox(device_token, dedup_key)
+    WHERE dedup_key IS NOT NULL;
 
 -- Create the password_reset_tokens table
 CREATE TABLE IF NOT EXISTS password_reset_tokens (
@@ -67,6 +315,50 @@ CREATE INDEX IF NOT EXISTS idx_password_reset_tokens_user_id ON password_reset_t
 CREATE INDEX IF NOT EXISTS idx_password_reset_tokens_token ON password_reset_tokens(token);
 CREATE INDEX IF NOT EXISTS idx_password_reset_tokens_expires_at ON password_reset_tokens(expires_at);
 CREATE INDEX IF NOT EXISTS idx_password_reset_tokens_user_active ON password_reset_tokens(user_id, is_used, expires_at);
+-- At most one pending token per user; issuing a new one invalidates the previous
+CREATE UNIQUE INDEX IF NOT EXISTS idx_password_reset_tokens_one_active ON password_reset_tokens(user_id) WHERE is_used = false;
+
+-- Create the magic_link_tokens table (passwordless login; only token hashes are stored)
+CREATE TABLE IF NOT EXISTS magic_link_tokens (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) UNIQUE NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMPTZ NOT NULL,
+    used BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS idx_magic_link_tokens_user_id ON magic_link_tokens(user_id);
+CREATE INDEX IF NOT EXISTS idx_magic_link_tokens_expires_at ON magic_link_tokens(expires_at);
+
+-- Create the email_change_tokens table (only token hashes are stored; one change per user)
+CREATE TABLE IF NOT EXISTS email_change_tokens (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER UNIQUE NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    new_email VARCHAR(255) NOT NULL,
+    old_token_hash VARCHAR(64) UNIQUE NOT NULL,
+    new_token_hash VARCHAR(64) UNIQUE NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMPTZ NOT NULL,
+    old_confirmed_at TIMESTAMPTZ,
+    new_confirmed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_change_tokens_expires_at ON email_change_tokens(expires_at);
+
+-- Create the sessions table (one row per login; tokens carry the session id)
+CREATE TABLE IF NOT EXISTS sessions (
+    id UUID PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    device_name VARCHAR(100),
+    ip_address VARCHAR(45),
+    user_agent TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    revoked BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_user_last_used ON sessions(user_id, last_used_at);
 
 -- Create the account_deletion_tokens table
 CREATE TABLE IF NOT EXISTS account_deletion_tokens (
@@ -85,3 +377,93 @@ CREATE INDEX IF NOT EXISTS idx_account_deletion_tokens_user_id ON account_deleti
 CREATE INDEX IF NOT EXISTS idx_account_deletion_tokens_token ON account_deletion_tokens(token);
 CREATE INDEX IF NOT EXISTS idx_account_deletion_tokens_expires_at ON account_deletion_tokens(expires_at);
 CREATE INDEX IF NOT EXISTS idx_account_deletion_tokens_user_active ON account_deletion_tokens(user_id, is_confirmed, is_cancelled, expires_at);
+
+-- Create the domain_blacklist table (URLs to these domains and their subdomains cannot be shortened)
+CREATE TABLE IF NOT EXISTS domain_blacklist (
+    id SERIAL PRIMARY KEY,
+    domain VARCHAR(253) UNIQUE NOT NULL,
+    reason TEXT NOT NULL DEFAULT '',
+    -- NULL for entries loaded from the blacklist file at startup
+    added_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create the service_accounts table (machine accounts for service-to-service calls)
+CREATE TABLE IF NOT EXISTS service_accounts (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(100) UNIQUE NOT NULL,
+    -- SHA-256 of the API key; the key itself is only shown once at creation
+    api_key_hash VARCHAR(64) UNIQUE NOT NULL,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create the url_metadata table (Open Graph data of destination pages for link previews)
+CREATE TABLE IF NOT EXISTS url_metadata (
+    url_id INTEGER PRIMARY KEY REFERENCES urls(id) ON DELETE CASCADE,
+    title TEXT,
+    description TEXT,
+    image_url TEXT,
+    favicon_url TEXT,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create the notification_preferences table (users without a row get the defaults)
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    digest_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    -- 0 = Sunday
+    digest_day_of_week SMALLINT NOT NULL DEFAULT 1 CHECK (digest_day_of_week BETWEEN 0 AND 6),
+    last_digest_sent_at TIMESTAMPTZ,
+    -- Send push notifications to the user's registered devices
+    push_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create the audit_log table (administrative actions, kept for the audit log retention)
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    -- No foreign keys: entries outlive the users and records they mention
+    actor_user_id INTEGER NOT NULL,
+    action VARCHAR(50) NOT NULL,
+    entity_type VARCHAR(50) NOT NULL,
+    entity_id INTEGER NOT NULL,
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity ON audit_log(entity_type, entity_id, created_at);
+
+-- Create the archive_records table (copies of records removed for good, kept for the archive retention)
+CREATE TABLE IF NOT EXISTS archive_records (
+    id BIGSERIAL PRIMARY KEY,
+    -- No foreign keys: the archived records are gone
+    entity_type VARCHAR(50) NOT NULL,
+    entity_id INTEGER NOT NULL,
+    data_snapshot JSONB NOT NULL DEFAULT '{}',
+    deleted_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    deletion_reason VARCHAR(100) NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_archive_records_entity ON archive_records(entity_type, deleted_at);
+CREATE INDEX IF NOT EXISTS idx_archive_records_deleted_at ON archive_records(deleted_at);
+
+-- Create the analytics_export_jobs table (raw click exports, removed with their files after 24 hours)
+CREATE TABLE IF NOT EXISTS analytics_export_jobs (
+    -- ID of the bulk operation producing the export
+    id VARCHAR(64) PRIMARY KEY,
+    -- No foreign key: the export file is removed by the cleanup even after its user is gone
+    user_id INTEGER NOT NULL,
+    format VARCHAR(16) NOT NULL,
+    start_date TIMESTAMPTZ,
+    end_date TIMESTAMPTZ,
+    status VARCHAR(16) NOT NULL DEFAULT 'pending',
+    object_key TEXT,
+    click_count BIGINT NOT NULL DEFAULT 0,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_analytics_export_jobs_user ON analytics_export_jobs(user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_analytics_export_jobs_expiry ON analytics_export_jobs(COALESCE(completed_at, created_at));
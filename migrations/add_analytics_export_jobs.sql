@@ -0,0 +1,23 @@
+-- add_analytics_export_jobs: raw click exports, tracked until the cleanup removes their files
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_analytics_export_jobs.sql
+
+CREATE TABLE IF NOT EXISTS analytics_export_jobs (
+    id VARCHAR(64) PRIMARY KEY,
+    user_id INTEGER NOT NULL,
+    format VARCHAR(16) NOT NULL,
+    start_date TIMESTAMPTZ,
+    end_date TIMESTAMPTZ,
+    status VARCHAR(16) NOT NULL DEFAULT 'pending',
+    object_key TEXT,
+    click_count BIGINT NOT NULL DEFAULT 0,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_analytics_export_jobs_user ON analytics_export_jobs(user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_analytics_export_jobs_expiry ON analytics_export_jobs(COALESCE(completed_at, created_at));
@@ -0,0 +1,18 @@
+-- add_archive_records: copies of records removed for good, kept for compliance audits
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_archive_records.sql
+
+CREATE TABLE IF NOT EXISTS archive_records (
+    id BIGSERIAL PRIMARY KEY,
+    entity_type VARCHAR(50) NOT NULL,
+    entity_id INTEGER NOT NULL,
+    data_snapshot JSONB NOT NULL DEFAULT '{}',
+    deleted_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    deletion_reason VARCHAR(100) NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_archive_records_entity ON archive_records(entity_type, deleted_at);
+CREATE INDEX IF NOT EXISTS idx_archive_records_deleted_at ON archive_records(deleted_at);
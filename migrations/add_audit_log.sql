@@ -0,0 +1,18 @@
+-- add_audit_log: administrative actions such as URL transfers
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_audit_log.sql
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    actor_user_id INTEGER NOT NULL,
+    action VARCHAR(50) NOT NULL,
+    entity_type VARCHAR(50) NOT NULL,
+    entity_id INTEGER NOT NULL,
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity ON audit_log(entity_type, entity_id, created_at);
@@ -0,0 +1,36 @@
+-- add_click_deduplication: raw click counter and per-URL click deduplication settings
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_click_deduplication.sql
+--
+-- Existing URLs start their raw counter at the clicks already recorded, so the dedup ratio
+-- does not report fewer raw clicks than unique ones.
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'urls' AND column_name = 'deduplicated_click_count'
+    ) THEN
+        ALTER TABLE urls ADD COLUMN deduplicated_click_count BIGINT NOT NULL DEFAULT 0;
+        UPDATE urls SET deduplicated_click_count = counts.clicks
+        FROM (SELECT url_id, COUNT(*) AS clicks FROM clicks GROUP BY url_id) AS counts
+        WHERE counts.url_id = urls.id;
+    END IF;
+END
+$$;
+
+-- Counter increments must not move updated_at
+DROP TRIGGER IF EXISTS urls_updated_at ON urls;
+CREATE TRIGGER urls_updated_at BEFORE UPDATE ON urls
+    FOR EACH ROW
+    WHEN (OLD.deduplicated_click_count IS NOT DISTINCT FROM NEW.deduplicated_click_count)
+    EXECUTE PROCEDURE update_updated_at_column();
+
+CREATE TABLE IF NOT EXISTS url_configs (
+    url_id INTEGER PRIMARY KEY REFERENCES urls(id) ON DELETE CASCADE,
+    click_dedup_window_seconds INTEGER CHECK (click_dedup_window_seconds >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
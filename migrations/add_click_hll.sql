@@ -0,0 +1,41 @@
+-- add_click_hll: daily HyperLogLog sketches for approximate unique visitor counts
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_click_hll.sql
+--
+-- The hll extension is optional; without it unique visitors are counted from clicks. With it,
+-- sketches are built from the clicks already recorded. Merging a sketch with itself changes
+-- nothing, so running the script again does not inflate the estimates.
+
+DO $$
+BEGIN
+    CREATE EXTENSION IF NOT EXISTS hll;
+EXCEPTION WHEN OTHERS THEN
+    RAISE NOTICE 'hll extension not available, unique visitors use COUNT(DISTINCT ip_address)';
+END
+$$;
+
+CREATE TABLE IF NOT EXISTS click_hll (
+    url_id INTEGER NOT NULL REFERENCES urls(id) ON DELETE CASCADE,
+    date DATE NOT NULL,
+    hll_state BYTEA NOT NULL,
+    PRIMARY KEY (url_id, date)
+);
+
+-- host() gives the address without a netmask, as the application hashes it
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'hll') THEN
+        INSERT INTO click_hll (url_id, date, hll_state)
+        SELECT url_id, (clicked_at AT TIME ZONE 'UTC')::date,
+               hll_add_agg(hll_hash_text(host(ip_address)))::bytea
+        FROM clicks
+        WHERE ip_address IS NOT NULL AND clicked_at IS NOT NULL
+        GROUP BY 1, 2
+        ON CONFLICT (url_id, date) DO UPDATE
+        SET hll_state = hll_union(click_hll.hll_state::hll, EXCLUDED.hll_state::hll)::bytea;
+    END IF;
+END
+$$;
@@ -0,0 +1,8 @@
+-- add_clicks_suspicious: flag clicks from IPs with a bad AbuseIPDB reputation
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql before enabling abuse_ipdb; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_clicks_suspicious.sql
+
+ALTER TABLE clicks ADD COLUMN IF NOT EXISTS suspicious BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,12 @@
+-- add_clicks_utm: UTM campaign parameters clicks are attributed to
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_clicks_utm.sql
+--
+-- Existing clicks are left unattributed.
+
+ALTER TABLE clicks ADD COLUMN IF NOT EXISTS utm_source VARCHAR(255);
+ALTER TABLE clicks ADD COLUMN IF NOT EXISTS utm_medium VARCHAR(255);
+ALTER TABLE clicks ADD COLUMN IF NOT EXISTS utm_campaign VARCHAR(255);
@@ -0,0 +1,31 @@
+-- add_conversions: goal pages of a URL and the clicks that reached them
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_conversions.sql
+--
+-- Existing clicks have no click token, so only clicks recorded after the upgrade can be
+-- attributed a conversion.
+
+ALTER TABLE clicks ADD COLUMN IF NOT EXISTS click_token VARCHAR(64);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_clicks_click_token ON clicks(click_token);
+
+CREATE TABLE IF NOT EXISTS conversion_goals (
+    id SERIAL PRIMARY KEY,
+    url_id INTEGER NOT NULL REFERENCES urls(id) ON DELETE CASCADE,
+    goal_url_pattern TEXT NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS conversion_events (
+    id SERIAL PRIMARY KEY,
+    click_id INTEGER REFERENCES clicks(id) ON DELETE SET NULL,
+    goal_id INTEGER NOT NULL REFERENCES conversion_goals(id) ON DELETE CASCADE,
+    converted_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (click_id, goal_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_conversion_goals_url_id ON conversion_goals(url_id);
+CREATE INDEX IF NOT EXISTS idx_conversion_events_goal_id ON conversion_events(goal_id);
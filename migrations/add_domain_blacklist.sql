@@ -0,0 +1,18 @@
+-- add_domain_blacklist: domains whose URLs, subdomains included, cannot be shortened
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_domain_blacklist.sql
+--
+-- The list starts empty; entries from APP_DOMAIN_BLACKLIST_FILE are loaded at the next startup.
+-- URLs shortened before a domain is listed keep redirecting.
+
+CREATE TABLE IF NOT EXISTS domain_blacklist (
+    id SERIAL PRIMARY KEY,
+    domain VARCHAR(253) UNIQUE NOT NULL,
+    reason TEXT NOT NULL DEFAULT '',
+    -- NULL for entries loaded from the blacklist file at startup
+    added_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,43 @@
+-- add_missing_indexes: indexes for the most frequent URL and click queries
+--
+-- Fresh databases get these from init.sql. For an existing database run this file once with
+-- psql (not inside a transaction, CONCURRENTLY needs its own); it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_missing_indexes.sql
+--
+-- Not added here:
+-- - urls(expiration_date) already exists as idx_urls_expiration_date.
+-- - clicks(url_id, clicked_at DESC): idx_clicks_url_clicked_at on (url_id, clicked_at) is
+--   scanned backwards for newest-first queries, so a DESC copy would only slow down inserts.
+-- - A GIN index on urls(tags): URLs have no tags column.
+--
+-- Expected plans (EXPLAIN ANALYZE, 50k URLs and 300k clicks) are asserted by
+-- tests/schema_indexes_test.rs; run them against a scratch database with
+-- TEST_DATABASE_URL=... cargo test --test schema_indexes_test -- --ignored
+
+-- Keyset pagination of a user's URLs (GET /urls), in either direction
+--   SELECT ... FROM urls WHERE user_id = $1 AND (created_at, id) < ($2, $3)
+--   ORDER BY created_at DESC, id DESC LIMIT $4
+-- Limit -> Index Scan Backward using idx_urls_user_created_at_id
+--   (Index Cond: user_id = $1 AND ROW(created_at, id) < ROW($2, $3)), no sort
+-- Also serves plain newest-first listings, so it replaces idx_urls_user_created_at.
+CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_urls_user_created_at_id ON urls(user_id, created_at, id);
+DROP INDEX CONCURRENTLY IF EXISTS idx_urls_user_created_at;
+
+-- The same listing ordered by short code
+--   SELECT ... FROM urls WHERE user_id = $1 AND (short_code, id) > ($2, $3)
+--   ORDER BY short_code ASC, id ASC LIMIT $4
+-- Limit -> Index Scan using idx_urls_user_short_code_id, no sort
+CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_urls_user_short_code_id ON urls(user_id, short_code, id);
+
+-- Filtering by status, optionally for one user
+--   SELECT ... FROM urls WHERE status = $1 AND user_id = $2 ORDER BY created_at DESC
+-- Sort -> Index Scan using idx_urls_status_user_id (Index Cond: status = $1 AND user_id = $2)
+-- Leads with status, so it replaces the single-column status index.
+CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_urls_status_user_id ON urls(status, user_id);
+DROP INDEX CONCURRENTLY IF EXISTS idx_urls_status;
+
+-- Country breakdown of a URL's clicks
+--   SELECT country_code, COUNT(*) FROM clicks WHERE url_id = $1 GROUP BY country_code
+-- GroupAggregate -> Index Only Scan using idx_clicks_url_country once the table is vacuumed
+CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_clicks_url_country ON clicks(url_id, country_code);
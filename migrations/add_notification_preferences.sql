@@ -0,0 +1,18 @@
+-- add_notification_preferences: per-user settings of the weekly expiry digest
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_notification_preferences.sql
+--
+-- Users without a row get the digest on Mondays. Run this before
+-- migrations/add_push_notifications.sql, which adds a column to the table.
+
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    digest_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    -- 0 = Sunday
+    digest_day_of_week SMALLINT NOT NULL DEFAULT 1 CHECK (digest_day_of_week BETWEEN 0 AND 6),
+    last_digest_sent_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,25 @@
+-- add_password_reset_tokens_one_active: one pending password reset token per user
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_password_reset_tokens_one_active.sql
+--
+-- Users with several pending tokens keep only the newest; the others are marked used, the
+-- same as issuing a new token does from now on.
+
+-- Expired token cleanup (CleanupService) scans by expiry
+CREATE INDEX IF NOT EXISTS idx_password_reset_tokens_expires_at ON password_reset_tokens(expires_at);
+
+UPDATE password_reset_tokens AS t
+SET is_used = true, used_at = NOW()
+WHERE is_used = false
+  AND EXISTS (
+      SELECT 1 FROM password_reset_tokens AS newer
+      WHERE newer.user_id = t.user_id
+        AND newer.is_used = false
+        AND (newer.created_at, newer.id) > (t.created_at, t.id)
+  );
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_password_reset_tokens_one_active
+    ON password_reset_tokens(user_id) WHERE is_used = false;
@@ -0,0 +1,17 @@
+-- add_service_accounts: machine accounts for service-to-service calls
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_service_accounts.sql
+--
+-- Only SHA-256 hashes of the API keys are stored.
+
+CREATE TABLE IF NOT EXISTS service_accounts (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(100) UNIQUE NOT NULL,
+    -- SHA-256 of the API key; the key itself is only shown once at creation
+    api_key_hash VARCHAR(64) UNIQUE NOT NULL,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
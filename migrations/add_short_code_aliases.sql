@@ -0,0 +1,14 @@
+-- add_short_code_aliases: old short codes that keep resolving after a re-encode
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql before calling POST /admin/short-codes/reencode; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_short_code_aliases.sql
+
+CREATE TABLE IF NOT EXISTS short_code_aliases (
+    short_code VARCHAR(50) PRIMARY KEY,
+    url_id INTEGER NOT NULL REFERENCES urls(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_short_code_aliases_url_id ON short_code_aliases(url_id);
@@ -0,0 +1,10 @@
+-- add_short_code_aliases_is_legacy: tell aliases left by renames from re-encoded short codes
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql before calling PATCH /urls/:id/short-code; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_short_code_aliases_is_legacy.sql
+--
+-- Existing aliases were all left by re-encodes and stay non-legacy.
+
+ALTER TABLE short_code_aliases ADD COLUMN IF NOT EXISTS is_legacy BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,8 @@
+-- add_short_code_sequence: counter of the sequential short code strategy
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql before setting short_code_strategy = "sequential"; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_short_code_sequence.sql
+
+CREATE SEQUENCE IF NOT EXISTS short_code_seq;
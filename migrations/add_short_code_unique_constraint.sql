@@ -0,0 +1,24 @@
+-- add_short_code_unique_constraint: make urls.short_code unique
+--
+-- URL creation relies on INSERT ... ON CONFLICT (short_code) DO NOTHING, which needs a unique
+-- constraint or index on exactly that column. Fresh databases get it from init.sql. For an
+-- existing database run this file once with psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_short_code_unique_constraint.sql
+--
+-- Fails if duplicate short codes are already stored; resolve those first.
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1
+        FROM pg_index i
+        JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = i.indkey[0]
+        WHERE i.indrelid = 'urls'::regclass
+          AND i.indisunique
+          AND i.indnatts = 1
+          AND a.attname = 'short_code'
+    ) THEN
+        ALTER TABLE urls ADD CONSTRAINT urls_short_code_key UNIQUE (short_code);
+    END IF;
+END $$;
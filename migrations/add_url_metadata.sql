@@ -0,0 +1,18 @@
+-- add_url_metadata: Open Graph data of destination pages, shown in link previews
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_url_metadata.sql
+--
+-- Metadata is fetched when a URL is created, so previews of existing URLs show no title,
+-- description or image.
+
+CREATE TABLE IF NOT EXISTS url_metadata (
+    url_id INTEGER PRIMARY KEY REFERENCES urls(id) ON DELETE CASCADE,
+    title TEXT,
+    description TEXT,
+    image_url TEXT,
+    favicon_url TEXT,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,26 @@
+-- add_url_tags: labels users put on their URLs
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_url_tags.sql
+--
+-- find_by_user_id loads the tags of all the URLs in the same query instead of one query per
+-- URL. Expected plan, asserted by test_find_by_user_id_loads_tags_in_one_query in
+-- src/infrastructure/database/postgres_repository.rs:
+--   GroupAggregate (Group Key: u.id)
+--   -> Hash Left Join (Hash Cond: ut.tag_id = t.id), tags is never looked up per row
+--      -> url_tags joined to the user's URLs, by hash or through url_tags_pkey
+
+CREATE TABLE IF NOT EXISTS tags (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(50) NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS url_tags (
+    url_id INTEGER NOT NULL REFERENCES urls(id) ON DELETE CASCADE,
+    tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (url_id, tag_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_url_tags_tag_id ON url_tags(tag_id);
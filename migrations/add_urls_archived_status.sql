@@ -0,0 +1,14 @@
+-- add_urls_archived_status: let the cleanup archive expired URLs instead of deleting them
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_urls_archived_status.sql
+--
+-- Only the allowed values change; no existing rows are touched. The list matches init.sql,
+-- including the 'deleted' status of soft-deleted URLs, so it accepts every existing row
+-- whichever migrations ran before.
+
+ALTER TABLE urls DROP CONSTRAINT IF EXISTS urls_status_check;
+ALTER TABLE urls ADD CONSTRAINT urls_status_check
+    CHECK (status IN ('active', 'inactive', 'archived', 'deleted'));
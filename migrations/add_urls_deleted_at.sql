@@ -0,0 +1,20 @@
+-- add_urls_deleted_at: keep deleted URLs for the deleted URL retention instead of removing them
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_urls_deleted_at.sql
+--
+-- Short codes only need to be unique among URLs that are not deleted, so the plain unique
+-- constraint is replaced by a partial unique index.
+
+ALTER TABLE urls ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ DEFAULT NULL;
+
+ALTER TABLE urls DROP CONSTRAINT IF EXISTS urls_status_check;
+ALTER TABLE urls ADD CONSTRAINT urls_status_check
+    CHECK (status IN ('active', 'inactive', 'archived', 'deleted'));
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_urls_short_code_live ON urls(short_code) WHERE deleted_at IS NULL;
+ALTER TABLE urls DROP CONSTRAINT IF EXISTS urls_short_code_key;
+
+CREATE INDEX IF NOT EXISTS idx_urls_deleted_at ON urls(deleted_at) WHERE deleted_at IS NOT NULL;
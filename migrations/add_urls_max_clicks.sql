@@ -0,0 +1,13 @@
+-- add_urls_max_clicks: click limit after which a URL stops redirecting
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_urls_max_clicks.sql
+--
+-- Existing rows get no limit.
+
+ALTER TABLE urls ADD COLUMN IF NOT EXISTS max_clicks BIGINT;
+
+ALTER TABLE urls DROP CONSTRAINT IF EXISTS urls_max_clicks_check;
+ALTER TABLE urls ADD CONSTRAINT urls_max_clicks_check CHECK (max_clicks > 0);
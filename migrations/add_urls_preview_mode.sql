@@ -0,0 +1,14 @@
+-- add_urls_preview_mode: per-URL choice of when to show the preview interstitial
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_urls_preview_mode.sql
+--
+-- Existing rows get 'none', which keeps redirecting straight to the destination.
+
+ALTER TABLE urls ADD COLUMN IF NOT EXISTS preview_mode VARCHAR(10) NOT NULL DEFAULT 'none';
+
+ALTER TABLE urls DROP CONSTRAINT IF EXISTS urls_preview_mode_check;
+ALTER TABLE urls ADD CONSTRAINT urls_preview_mode_check
+    CHECK (preview_mode IN ('none', 'always', 'high_risk'));
@@ -0,0 +1,10 @@
+-- add_urls_status_id_index: page through the URLs of one status for admin moderation
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql (not inside a transaction, CONCURRENTLY needs its own); it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_urls_status_id_index.sql
+
+--   SELECT ... FROM urls WHERE status = $1 AND id > $2 ORDER BY id LIMIT $3
+-- Limit -> Index Scan using idx_urls_status_id
+CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_urls_status_id ON urls(status, id);
@@ -0,0 +1,16 @@
+-- add_urls_title_redirect_type_password: URL settings editable through PATCH /urls/:id
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_urls_title_redirect_type_password.sql
+--
+-- Existing rows get no title or password and keep redirecting permanently.
+
+ALTER TABLE urls ADD COLUMN IF NOT EXISTS title VARCHAR(255);
+ALTER TABLE urls ADD COLUMN IF NOT EXISTS redirect_type VARCHAR(10) NOT NULL DEFAULT 'permanent';
+ALTER TABLE urls ADD COLUMN IF NOT EXISTS password_hash VARCHAR(255);
+
+ALTER TABLE urls DROP CONSTRAINT IF EXISTS urls_redirect_type_check;
+ALTER TABLE urls ADD CONSTRAINT urls_redirect_type_check
+    CHECK (redirect_type IN ('permanent', 'temporary'));
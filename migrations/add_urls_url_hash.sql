@@ -0,0 +1,18 @@
+-- add_urls_url_hash: index URLs by destination
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql (not inside a transaction, CONCURRENTLY needs its own); it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_urls_url_hash.sql
+--
+-- original_url is unbounded TEXT, so the index is built on its MD5 instead. Adding a stored
+-- generated column rewrites the table once; schedule it outside peak hours on large tables.
+-- The column is TEXT like md5()'s result: with CHAR(32), `url_hash = md5($1)` would cast the
+-- column and skip the index.
+
+ALTER TABLE urls ADD COLUMN IF NOT EXISTS url_hash TEXT
+    GENERATED ALWAYS AS (md5(original_url)) STORED;
+
+--   SELECT ... FROM urls WHERE url_hash = md5($1) AND original_url = $1 ORDER BY created_at DESC
+-- Sort -> Index Scan using idx_urls_url_hash (Filter: original_url = $1)
+CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_urls_url_hash ON urls(url_hash);
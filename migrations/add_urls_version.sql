@@ -0,0 +1,11 @@
+-- add_urls_version: version numbers for optimistic locking of URL updates
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_urls_version.sql
+--
+-- Existing URLs start at version 1. Adding a column with a constant default does not
+-- rewrite the table.
+
+ALTER TABLE urls ADD COLUMN IF NOT EXISTS version BIGINT NOT NULL DEFAULT 1;
@@ -0,0 +1,17 @@
+-- add_user_search_indexes: indexes behind the admin user search
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_user_search_indexes.sql
+
+-- Trigram indexes serve the substring (LIKE '%…%') matches on username and email
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_users_username_trgm ON users USING GIN (lower(username) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_users_email_trgm ON users USING GIN (lower(email) gin_trgm_ops);
+
+-- Full-text index over the display name (first and last name)
+CREATE INDEX IF NOT EXISTS idx_users_display_name_fts ON users USING GIN (
+    to_tsvector('simple', coalesce(first_name, '') || ' ' || coalesce(last_name, ''))
+);
@@ -0,0 +1,18 @@
+-- add_user_url_stats: per-user URL and click counters read by the dashboard
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_user_url_stats.sql
+--
+-- The counters of existing users are filled in by POST /admin/stats/rebuild.
+
+CREATE TABLE IF NOT EXISTS user_url_stats (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    total_urls BIGINT NOT NULL DEFAULT 0,
+    active_urls BIGINT NOT NULL DEFAULT 0,
+    inactive_urls BIGINT NOT NULL DEFAULT 0,
+    archived_urls BIGINT NOT NULL DEFAULT 0,
+    total_clicks BIGINT NOT NULL DEFAULT 0,
+    last_updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
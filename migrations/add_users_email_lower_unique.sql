@@ -0,0 +1,15 @@
+-- add_users_email_lower_unique: emails are unique regardless of case
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_users_email_lower_unique.sql
+--
+-- The index cannot be built while two accounts share an email in different case. List them
+-- with the query below and merge or rename them first; the script then fails without
+-- changing anything.
+--
+--   SELECT lower(email), array_agg(id ORDER BY id) FROM users
+--   GROUP BY lower(email) HAVING COUNT(*) > 1;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_email_lower ON users(lower(email));
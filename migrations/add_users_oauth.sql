@@ -0,0 +1,19 @@
+-- add_users_oauth: Google and GitHub accounts linked for social login
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_users_oauth.sql
+--
+-- Existing users have no linked account; each provider account can be linked to one user.
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS oauth_provider VARCHAR(20);
+ALTER TABLE users ADD COLUMN IF NOT EXISTS oauth_provider_id VARCHAR(255);
+
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_oauth_provider_check;
+ALTER TABLE users ADD CONSTRAINT users_oauth_provider_check
+    CHECK (oauth_provider IN ('google', 'github'));
+
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_oauth_provider_oauth_provider_id_key;
+ALTER TABLE users ADD CONSTRAINT users_oauth_provider_oauth_provider_id_key
+    UNIQUE (oauth_provider, oauth_provider_id);
@@ -0,0 +1,10 @@
+-- add_users_password_changed_at: session tokens issued before a password change are rejected
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_users_password_changed_at.sql
+--
+-- Existing users have no recorded change, so their current tokens keep working.
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS password_changed_at TIMESTAMPTZ;
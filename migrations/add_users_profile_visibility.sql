@@ -0,0 +1,17 @@
+-- add_users_profile_visibility: which details each user shows on their public profile
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_users_profile_visibility.sql
+--
+-- Existing users get the defaults: join date, bio, website and social links are shown; URL
+-- count, click count and recent URLs are hidden.
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS show_url_count BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS show_click_count BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS show_join_date BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS show_bio BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS show_website BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS show_social_links BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS show_recent_urls BOOLEAN NOT NULL DEFAULT FALSE;
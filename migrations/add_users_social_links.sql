@@ -0,0 +1,10 @@
+-- add_users_social_links: links to a user's accounts elsewhere, shown on their profile
+--
+-- Fresh databases get this from init.sql. For an existing database run this file once with
+-- psql; it is safe to run again:
+--
+--   psql "$APP_DATABASE_URL" -f migrations/add_users_social_links.sql
+--
+-- Existing users get no links.
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS social_links JSONB;
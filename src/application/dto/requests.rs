@@ -1,25 +1,213 @@
+use crate::domain::entities::RedirectType;
+use crate::domain::services::click_tracking_service::TimelineGranularity;
+use crate::domain::services::BatchOperation;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use validator::{Validate, ValidationError, ValidationErrors};
+
+/// Most URLs a single bulk shortening request may contain
+pub const MAX_BULK_ITEMS: usize = 1000;
 
 /// Request DTO for shortening a URL
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+///
+/// Custom short codes must also satisfy the configured short code rules, which apply within
+/// these bounds.
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct ShortenUrlRequest {
+    #[validate(
+        url(message = "must be a valid URL"),
+        length(max = 2048, message = "must be at most 2048 characters")
+    )]
     pub url: String,
+    #[validate(
+        length(min = 4, max = 50, message = "must be between 4 and 50 characters"),
+        custom(function = "validate_short_code_chars")
+    )]
     pub custom_short_code: Option<String>,
+    #[validate(custom(function = "validate_future_date"))]
     pub expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+    /// Organization that will own the URL (requires membership)
+    #[serde(default)]
+    pub organization_id: Option<i32>,
 }
 
-/// Request DTO for updating a URL
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+/// Request DTO for updating a URL; omitted fields are left unchanged
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema, Validate)]
 pub struct UpdateUrlRequest {
+    #[validate(length(max = 255, message = "must be at most 255 characters"))]
+    pub title: Option<String>,
+    #[validate(
+        url(message = "must be a valid URL"),
+        length(max = 2048, message = "must be at most 2048 characters")
+    )]
     pub original_url: Option<String>,
+    /// New expiration date, which must be in the future; `null` removes the expiration
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[schema(value_type = Option<chrono::DateTime<chrono::Utc>>, nullable)]
+    pub expiration_date: Option<Option<chrono::DateTime<chrono::Utc>>>,
+    /// `permanent` or `temporary`
+    #[schema(value_type = Option<String>, example = "temporary")]
+    pub redirect_type: Option<RedirectType>,
+    #[validate(
+        length(min = 4, max = 50, message = "must be between 4 and 50 characters"),
+        custom(function = "validate_short_code_chars")
+    )]
     pub custom_short_code: Option<String>,
+    /// Password visitors must give to be redirected; `null` removes the password
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[schema(value_type = Option<String>, nullable)]
+    #[validate(length(min = 4, max = 72, message = "must be between 4 and 72 characters"))]
+    pub password: Option<Option<String>>,
+    /// Clicks after which the URL stops redirecting; `null` removes the limit
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[schema(value_type = Option<i64>, nullable, minimum = 1)]
+    #[validate(range(min = 1, message = "must be at least 1"))]
+    pub max_clicks: Option<Option<i64>>,
+}
+
+/// Request DTO for giving a URL a new short code
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct RenameShortCodeRequest {
+    #[validate(
+        length(min = 4, max = 50, message = "must be between 4 and 50 characters"),
+        custom(function = "validate_short_code_chars")
+    )]
+    pub short_code: String,
+}
+
+/// Request DTO for duplicating a URL; omitted fields are copied from the original
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema, Validate)]
+pub struct DuplicateUrlRequest {
+    #[validate(
+        url(message = "must be a valid URL"),
+        length(max = 2048, message = "must be at most 2048 characters")
+    )]
+    pub original_url: Option<String>,
+    #[validate(
+        length(min = 4, max = 50, message = "must be between 4 and 50 characters"),
+        custom(function = "validate_short_code_chars")
+    )]
+    pub custom_short_code: Option<String>,
+}
+
+/// Query parameters for short link redirects
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct RedirectQuery {
+    /// Show an HTML preview of the destination before redirecting (default false)
+    #[serde(default)]
+    pub preview: bool,
+    /// Set by the preview interstitial when the visitor chose to proceed
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Form posted by the preview interstitial to continue to the destination
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ConfirmRedirectForm {
+    /// Signed confirmation token embedded in the interstitial page
+    pub token: String,
+}
+
+/// Request DTO for changing when a URL shows the preview interstitial
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct UpdatePreviewSettingsRequest {
+    /// One of `none`, `always` or `high_risk`
+    pub preview_mode: String,
+}
+
+/// Request DTO for changing the click tracking settings of a URL
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct UpdateUrlConfigRequest {
+    /// Seconds a repeat click from the same IP is not recorded, at most 86400; 0 records
+    /// every click and `null` restores the server default. The field must be present
+    #[serde(deserialize_with = "Option::deserialize")]
+    pub click_dedup_window_seconds: Option<u32>,
+}
+
+/// Query parameters for limited URL listings
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ListLimitQuery {
+    pub limit: Option<usize>,
+}
+
+/// Query parameters for cursor-paginated URL listings
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct ListUrlsQuery {
+    /// Field to order by: `created_at` (default) or `short_code`
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub sort: crate::domain::repositories::UrlSortField,
+    /// `asc` or `desc` (default)
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub direction: crate::domain::repositories::SortDirection,
+    /// Only list URLs with this status: `active`, `inactive` or `archived`;
+    /// archived URLs are left out when omitted
+    pub status: Option<String>,
+}
+
+/// Request DTO for restoring an archived URL
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema, Validate)]
+pub struct RestoreUrlRequest {
+    /// New expiration date; the URL never expires when omitted
     pub expiration_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// Largest page size clients may request from a collection endpoint
+pub const MAX_PAGE_LIMIT: usize = 100;
+
+/// Query parameters shared by paginated collection endpoints
+///
+/// At most one of `after` and `before` may be given.
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct PaginationRequest {
+    /// `next_cursor` of the previous page; `cursor` is accepted as an older name
+    #[serde(alias = "cursor")]
+    pub after: Option<String>,
+    /// `prev_cursor` of the following page
+    pub before: Option<String>,
+    /// Maximum number of items to return, at most 100
+    pub limit: Option<usize>,
+}
+
+impl PaginationRequest {
+    /// The requested page size, or `default`, clamped to `1..=MAX_PAGE_LIMIT`
+    pub fn limit_or(&self, default: usize) -> usize {
+        self.limit.unwrap_or(default).clamp(1, MAX_PAGE_LIMIT)
+    }
+}
+
+/// Query parameters for the URL analytics summary
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct AnalyticsSummaryQuery {
+    /// Count clicks from crawlers and scripted clients (default false)
+    #[serde(default)]
+    pub include_bots: bool,
+}
+
+/// Query parameters for the analytics of a URL over a time range
+#[derive(Debug, Default, Clone, Deserialize, Serialize, ToSchema)]
+pub struct GetUrlAnalyticsRequest {
+    /// Width of the timeseries buckets: `hour`, `day` (default), `week` or `month`
+    #[serde(default)]
+    #[schema(value_type = String, example = "day")]
+    pub granularity: TimelineGranularity,
+    /// Start of the range; defaults to the 24 hours, 30 days, 12 weeks or 12 months before `end`
+    pub start: Option<chrono::DateTime<chrono::Utc>>,
+    /// End of the range; defaults to now
+    pub end: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Query parameters for the click patterns of a URL
+#[derive(Debug, Default, Clone, Deserialize, Serialize, ToSchema)]
+pub struct ClickPatternsQuery {
+    /// Minutes the click velocity is measured over (default 60, at most 10080)
+    pub window_minutes: Option<u32>,
+}
+
 /// Request DTO for user authentication
 #[allow(dead_code)]
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct LoginRequest {
     pub email: String,
     pub password: String,
@@ -27,7 +215,7 @@ pub struct LoginRequest {
 
 /// Request DTO for user registration
 #[allow(dead_code)]
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct RegisterRequest {
     pub email: String,
     pub password: String,
@@ -35,76 +223,225 @@ pub struct RegisterRequest {
 }
 
 /// Request DTO for setting URL expiration
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct SetExpirationRequest {
     pub expiration_date: chrono::DateTime<chrono::Utc>,
 }
 
 /// Request DTO for extending URL expiration
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+///
+/// Exactly one of the fields must be set.
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct ExtendExpirationRequest {
-    pub additional_days: u32,
+    /// New absolute expiration date
+    pub expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+    /// Relative extension from the current expiration (or now), e.g. `30m`, `24h`, `7d`, `1y`
+    pub extend_by: Option<String>,
+    /// Number of days to extend by; kept for older clients, prefer `extend_by`
+    pub additional_days: Option<u32>,
 }
 
 /// Request DTO for bulk URL shortening
+///
+/// Holds at most [`MAX_BULK_ITEMS`] items.
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct BulkShortenUrlsRequest {
     pub items: Vec<ShortenUrlRequest>,
+    /// Queue priority for async processing; premium users always get `high`
+    #[serde(default)]
+    pub priority: OperationPriority,
+}
+
+// Written out because validator panics when a field fails both a length rule and `nested`
+impl Validate for BulkShortenUrlsRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if self.items.len() > MAX_BULK_ITEMS {
+            errors.add(
+                "items",
+                ValidationError::new("length")
+                    .with_message(format!("must contain at most {} items", MAX_BULK_ITEMS).into()),
+            );
+            return Err(errors);
+        }
+
+        errors.merge_self("items", self.items.validate());
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
 }
 
 /// Request DTO for batch URL operations
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct BatchUrlOperationRequest {
-    pub operation: BatchOperationType,
+    /// One of `deactivate`, `reactivate`, `delete`, `update_status` or `update_expiration`
+    #[serde(with = "batch_operation_name")]
+    #[schema(value_type = String, example = "deactivate")]
+    pub operation: Box<dyn BatchOperation>,
     pub url_ids: Vec<i32>,
-    pub data: Option<BatchOperationData>,
+    /// Settings of the operation: `update_status` reads `status` and `update_expiration`
+    /// reads `expiration_date`
+    #[schema(value_type = Option<BatchOperationData>)]
+    pub data: Option<serde_json::Value>,
+    /// Queue priority for async processing; premium users always get `high`
+    #[serde(default)]
+    pub priority: OperationPriority,
 }
 
-/// Types of batch operations that can be performed
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
-pub enum BatchOperationType {
-    #[serde(rename = "deactivate")]
-    Deactivate,
-    #[serde(rename = "reactivate")]
-    Reactivate,
-    #[serde(rename = "delete")]
-    Delete,
-    #[serde(rename = "update_status")]
-    UpdateStatus,
-    #[serde(rename = "update_expiration")]
-    UpdateExpiration,
+/// Request DTO for running a batch operation once another operation has completed
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct ChainedOperationRequest {
+    /// One of `deactivate`, `reactivate`, `delete`, `update_status` or `update_expiration`
+    #[serde(with = "batch_operation_name")]
+    #[schema(value_type = String, example = "update_expiration")]
+    pub operation: Box<dyn BatchOperation>,
+    /// URLs to apply the operation to; the URLs the other operation created when missing
+    pub url_ids: Option<Vec<i32>>,
+    /// Settings of the operation, as for batch operations
+    #[schema(value_type = Option<BatchOperationData>)]
+    pub data: Option<serde_json::Value>,
+}
+
+/// Batch operations (de)serialized by the name clients use
+mod batch_operation_name {
+    use crate::domain::services::batch_operations::BATCH_OPERATION_NAMES;
+    use crate::domain::services::{batch_operation, BatchOperation};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    // serde hands `with` modules a reference to the field itself
+    #[allow(clippy::borrowed_box)]
+    pub fn serialize<S: Serializer>(
+        operation: &Box<dyn BatchOperation>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(operation.name())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Box<dyn BatchOperation>, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        batch_operation(&name)
+            .ok_or_else(|| D::Error::unknown_variant(&name, BATCH_OPERATION_NAMES))
+    }
+}
+
+/// Queue priority of an async bulk operation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize, ToSchema)]
+pub enum OperationPriority {
+    #[serde(rename = "low")]
+    Low,
+    #[serde(rename = "normal")]
+    #[default]
+    Normal,
+    #[serde(rename = "high")]
+    High,
+}
+
+impl OperationPriority {
+    /// All priorities, from the first to be processed to the last
+    pub const ALL: [OperationPriority; 3] = [
+        OperationPriority::High,
+        OperationPriority::Normal,
+        OperationPriority::Low,
+    ];
+
+    /// Label used in metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OperationPriority::Low => "low",
+            OperationPriority::Normal => "normal",
+            OperationPriority::High => "high",
+        }
+    }
+}
+
+/// File format of an analytics export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Parquet,
+    Json,
+}
+
+impl ExportFormat {
+    /// File extension of exports in this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Parquet => "parquet",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    /// Format with the given file extension
+    pub fn parse(extension: &str) -> Option<Self> {
+        match extension {
+            "csv" => Some(ExportFormat::Csv),
+            "parquet" => Some(ExportFormat::Parquet),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Parquet => "application/vnd.apache.parquet",
+            ExportFormat::Json => "application/json",
+        }
+    }
+}
+
+/// Request DTO for changing the priority of a queued bulk operation
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct ReprioritizeOperationRequest {
+    pub priority: OperationPriority,
 }
 
 /// Data for batch operations
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct BatchOperationData {
     pub status: Option<String>,
     pub expiration_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Request DTO for bulk URL status updates
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct BulkStatusUpdateRequest {
     pub url_ids: Vec<i32>,
     pub status: String,
 }
 
 /// Request DTO for bulk URL expiration updates
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct BulkExpirationUpdateRequest {
     pub url_ids: Vec<i32>,
-    pub expiration_date: chrono::DateTime<chrono::Utc>,
+    /// New expiration; `null` removes it. The field must be present so a forgotten value
+    /// does not clear expirations
+    #[serde(deserialize_with = "Option::deserialize")]
+    pub expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Request DTO for removing the expiration of several URLs
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct BulkExpirationClearRequest {
+    pub url_ids: Vec<i32>,
 }
 
 /// Request DTO for bulk URL deletion
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct BulkDeleteRequest {
     pub url_ids: Vec<i32>,
     pub force: Option<bool>,
 }
 
 /// Request DTO for updating user profile
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct UpdateProfileRequest {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
@@ -113,6 +450,28 @@ pub struct UpdateProfileRequest {
     pub website: Option<String>,
     pub location: Option<String>,
     pub privacy: Option<ProfilePrivacyRequest>,
+    /// JSON Merge Patch of the social links: omitted or `null` members are left alone or
+    /// removed respectively, and `custom` is replaced as a whole; `null` removes every link
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<crate::application::dto::responses::SocialLinksDto>)]
+    pub social_links: Option<serde_json::Value>,
+}
+
+/// Deserialize a field that may be `null`, keeping `null` apart from an omitted field
+fn deserialize_present<'de, D>(deserializer: D) -> Result<Option<serde_json::Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    serde_json::Value::deserialize(deserializer).map(Some)
+}
+
+/// Deserialize a present field into `Some`, so an explicit `null` becomes `Some(None)`
+fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
 }
 
 /// Privacy settings for profile requests
@@ -127,15 +486,199 @@ pub enum ProfilePrivacyRequest {
 }
 
 /// Request DTO for account deletion
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct DeleteAccountRequest {
     /// User's current password for confirmation
     pub password: String,
 }
 
-/// Request DTO for confirming account deletion
+/// Request DTO for changing the current user's password
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct ChangePasswordRequest {
+    /// User's current password for confirmation
+    pub current_password: String,
+    /// New password (at least 8 characters)
+    pub new_password: String,
+}
+
+/// Request DTO for changing the current user's email address
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct ChangeEmailRequest {
+    /// Address to change to; it has to be confirmed along with the current one
+    #[validate(email(message = "must be a valid email address"))]
+    pub new_email: String,
+}
+
+/// Query parameters for confirming an email change
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct EmailChangeConfirmQuery {
+    /// Token from the confirmation link
+    pub token: String,
+}
+
+/// Request DTO for registering a device for push notifications
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct RegisterDeviceTokenRequest {
+    /// Registration token the device got from Firebase Cloud Messaging
+    #[validate(length(min = 1, max = 4096, message = "must be between 1 and 4096 characters"))]
+    pub token: String,
+}
+
+/// Request DTO for confirming account deletion
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct ConfirmAccountDeletionRequest {
     /// Confirmation token from email
     pub token: String,
 }
+
+/// Custom short codes are letters, digits, hyphens and underscores, like generated ones
+fn validate_short_code_chars(code: &str) -> Result<(), ValidationError> {
+    if code
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(ValidationError::new("short_code_chars")
+            .with_message("must contain only letters, digits, '-' and '_'".into()))
+    }
+}
+
+fn validate_future_date(date: &chrono::DateTime<chrono::Utc>) -> Result<(), ValidationError> {
+    if *date > chrono::Utc::now() {
+        Ok(())
+    } else {
+        Err(ValidationError::new("future_date").with_message("must be in the future".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn shorten_request(url: &str) -> ShortenUrlRequest {
+        ShortenUrlRequest {
+            url: url.to_string(),
+            custom_short_code: None,
+            expiration_date: None,
+            organization_id: None,
+        }
+    }
+
+    fn failed_fields(result: Result<(), ValidationErrors>) -> Vec<&'static str> {
+        let mut fields: Vec<_> = result.unwrap_err().errors().keys().copied().collect();
+        fields.sort();
+        fields
+    }
+
+    #[test]
+    fn test_shorten_request_url_rules() {
+        assert!(shorten_request("https://example.com/path")
+            .validate()
+            .is_ok());
+        assert_eq!(failed_fields(shorten_request("").validate()), ["url"]);
+        assert_eq!(
+            failed_fields(shorten_request("not a url").validate()),
+            ["url"]
+        );
+
+        let long_url = format!("https://example.com/{}", "a".repeat(2048));
+        assert_eq!(
+            failed_fields(shorten_request(&long_url).validate()),
+            ["url"]
+        );
+    }
+
+    #[test]
+    fn test_shorten_request_custom_short_code_rules() {
+        let with_code = |code: &str| ShortenUrlRequest {
+            custom_short_code: Some(code.to_string()),
+            ..shorten_request("https://example.com")
+        };
+
+        assert!(with_code("abcd").validate().is_ok());
+        assert!(with_code("my-link_2").validate().is_ok());
+        assert!(with_code(&"a".repeat(50)).validate().is_ok());
+        assert_eq!(
+            failed_fields(with_code("abc").validate()),
+            ["custom_short_code"]
+        );
+        assert_eq!(
+            failed_fields(with_code(&"a".repeat(51)).validate()),
+            ["custom_short_code"]
+        );
+        assert_eq!(
+            failed_fields(with_code("has space").validate()),
+            ["custom_short_code"]
+        );
+        assert_eq!(
+            failed_fields(with_code("abc/d").validate()),
+            ["custom_short_code"]
+        );
+    }
+
+    #[test]
+    fn test_shorten_request_expiration_must_be_in_the_future() {
+        let expiring = |date| ShortenUrlRequest {
+            expiration_date: Some(date),
+            ..shorten_request("https://example.com")
+        };
+
+        assert!(expiring(Utc::now() + Duration::days(1)).validate().is_ok());
+        assert_eq!(
+            failed_fields(expiring(Utc::now() - Duration::days(1)).validate()),
+            ["expiration_date"]
+        );
+    }
+
+    #[test]
+    fn test_bulk_request_item_rules() {
+        let bulk = |items| BulkShortenUrlsRequest {
+            items,
+            priority: OperationPriority::Normal,
+        };
+
+        let items = (0..MAX_BULK_ITEMS)
+            .map(|i| shorten_request(&format!("https://example.com/{}", i)))
+            .collect();
+        assert!(bulk(items).validate().is_ok());
+
+        let items = (0..=MAX_BULK_ITEMS)
+            .map(|_| shorten_request("not a url"))
+            .collect();
+        assert_eq!(failed_fields(bulk(items).validate()), ["items"]);
+
+        let result = bulk(vec![
+            shorten_request("https://example.com"),
+            shorten_request("not a url"),
+        ])
+        .validate();
+        match &result.unwrap_err().errors()["items"] {
+            validator::ValidationErrorsKind::List(items) => {
+                assert_eq!(items.keys().copied().collect::<Vec<_>>(), [1]);
+            }
+            other => panic!("unexpected errors: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_update_and_duplicate_requests_share_url_rules() {
+        let update = UpdateUrlRequest {
+            original_url: Some("javascript".to_string()),
+            custom_short_code: Some("ab".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            failed_fields(update.validate()),
+            ["custom_short_code", "original_url"]
+        );
+
+        assert!(DuplicateUrlRequest::default().validate().is_ok());
+        let duplicate = DuplicateUrlRequest {
+            original_url: Some("https://example.com".to_string()),
+            custom_short_code: Some("no spaces".to_string()),
+        };
+        assert_eq!(failed_fields(duplicate.validate()), ["custom_short_code"]);
+    }
+}
@@ -1,4 +1,8 @@
+use super::requests::PaginationRequest;
+use crate::domain::entities::SocialLinks;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use utoipa::ToSchema;
 
 /// Response DTO for successful URL shortening
@@ -8,9 +12,18 @@ pub struct ShortenUrlResponse {
     pub original_url: String,
     pub short_code: String,
     pub created_at: String,
+    pub updated_at: String,
     pub expiration_date: Option<String>,
+    /// Version to send in `If-Match` when updating the URL
+    pub version: i64,
+    /// Related endpoints by relation: `self`, `redirect`, `analytics` and `deactivate`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub links: HashMap<String, String>,
 }
 
+/// Response DTO for an updated URL, shaped like a newly shortened one
+pub type UpdateUrlResponse = ShortenUrlResponse;
+
 /// Response DTO for URL information
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UrlInfoResponse {
@@ -19,9 +32,136 @@ pub struct UrlInfoResponse {
     pub original_url: String,
     pub short_url: String,
     pub created_at: String,
+    pub updated_at: String,
     pub expiration_date: Option<String>,
     pub is_expired: bool,
     pub click_count: Option<i64>,
+    /// Version to send in `If-Match` when updating the URL
+    pub version: i64,
+}
+
+/// Response DTO for a URL in the `GET /urls` listing
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UrlSummaryResponse {
+    pub id: i32,
+    pub short_code: String,
+    pub short_url: String,
+    pub original_url: String,
+    pub title: Option<String>,
+    /// One of `active`, `inactive` or `archived`
+    pub status: String,
+    pub created_at: String,
+    pub expires_at: Option<String>,
+    /// Recorded clicks
+    pub click_count: i64,
+    /// Names of the URL's tags, sorted
+    pub tags: Vec<String>,
+}
+
+/// Response DTO for the full details of a single URL
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UrlDetailResponse {
+    pub id: i32,
+    pub short_code: String,
+    pub original_url: String,
+    pub short_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub expiration_date: Option<String>,
+    pub is_expired: bool,
+    /// One of `active`, `inactive`, `archived` or `deleted`
+    pub status: String,
+    pub user_id: Option<i32>,
+    pub organization_id: Option<i32>,
+    /// One of `none`, `always` or `high_risk`
+    pub preview_mode: String,
+    /// Recorded clicks
+    pub click_count: i64,
+    /// Every click seen, including repeats that were not recorded
+    pub deduplicated_click_count: i64,
+    /// Version to send in `If-Match` when updating the URL
+    pub version: i64,
+    /// Related endpoints by relation: `self`, `redirect`, `analytics` and `deactivate`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub links: HashMap<String, String>,
+}
+
+/// Response DTO for the preview interstitial settings of a URL
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PreviewSettingsResponse {
+    pub url_id: i32,
+    /// One of `none`, `always` or `high_risk`
+    pub preview_mode: String,
+    /// Whether visitors currently see the interstitial, including the global policy
+    pub preview_required: bool,
+}
+
+/// Response DTO for the click tracking settings of a URL
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UrlConfigResponse {
+    pub url_id: i32,
+    /// Override of the deduplication window in seconds; `null` uses the server default
+    pub click_dedup_window_seconds: Option<i32>,
+    /// Deduplication window currently applied to the URL's clicks, in seconds
+    pub effective_click_dedup_window_seconds: u64,
+}
+
+/// Response DTO comparing the clicks of a URL before and after deduplication
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ClickDedupRatioResponse {
+    /// Every click seen, including repeats from the same IP that were not recorded
+    pub raw_clicks: i64,
+    /// Recorded clicks from browsers
+    pub unique_clicks: i64,
+    /// Recorded clicks from crawlers and scripted clients
+    pub bot_clicks: i64,
+}
+
+/// Response DTO describing when a URL is clicked and how fast it is clicked right now
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ClickPatternsResponse {
+    pub url_id: i32,
+    /// 24 click counts by hour of day (UTC), midnight first
+    pub hourly_distribution: Vec<u32>,
+    /// 7 click counts by day of week (UTC), Sunday first
+    pub daily_distribution: Vec<u32>,
+    /// Hour of day (UTC) with the most clicks, 0 for URLs never clicked
+    pub peak_hour: u8,
+    /// Clicks per minute over the last `velocity_window_minutes`
+    pub click_velocity: f64,
+    pub velocity_window_minutes: u32,
+}
+
+/// Clicks attributed to one combination of UTM parameters
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UtmAttributionEntry {
+    pub source: Option<String>,
+    pub medium: Option<String>,
+    pub campaign: Option<String>,
+    pub count: i64,
+}
+
+/// Response DTO breaking the clicks of a URL down by UTM campaign
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UtmAttributionResponse {
+    pub url_id: i32,
+    /// Most clicked first; clicks without UTM parameters are left out
+    pub attributions: Vec<UtmAttributionEntry>,
+}
+
+/// Response DTO for the link preview of a short URL
+///
+/// Metadata fields are null until the destination page has been fetched.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LinkPreviewResponse {
+    pub short_code: String,
+    pub short_url: String,
+    pub original_url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub favicon_url: Option<String>,
+    pub fetched_at: Option<String>,
 }
 
 /// Response DTO for user URLs list
@@ -31,6 +171,229 @@ pub struct UserUrlsResponse {
     pub total_count: i64,
 }
 
+/// Response DTO for a user's most clicked URLs
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TopUrlsResponse {
+    pub urls: Vec<UrlInfoResponse>,
+    pub total_count: i64,
+}
+
+/// Response DTO for one page of a paginated collection
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[aliases(
+    UrlSummaryPage = Page<UrlSummaryResponse>,
+    BulkOperationProgressPage = Page<BulkOperationProgress>,
+    UserSummaryPage = Page<UserSummary>
+)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    /// Pass as `after` to fetch the next page; absent on the last page
+    pub next_cursor: Option<String>,
+    /// Pass as `before` to fetch the previous page; absent on the first page
+    pub prev_cursor: Option<String>,
+    /// Size of the whole collection, only given where it is cheap to count
+    pub total: Option<i64>,
+}
+
+impl<T> Page<T> {
+    /// Paginate a collection held in memory, using positions in it as cursors
+    ///
+    /// Returns `None` when a cursor is malformed or both `after` and `before` are given.
+    pub fn paginate_in_memory(
+        items: Vec<T>,
+        pagination: &PaginationRequest,
+        default_limit: usize,
+    ) -> Option<Self> {
+        let limit = pagination.limit_or(default_limit);
+        let total = items.len();
+        let (start, end) = match (&pagination.after, &pagination.before) {
+            (Some(_), Some(_)) => return None,
+            (Some(after), None) => {
+                let start = decode_position(after)?.min(total);
+                (start, (start + limit).min(total))
+            }
+            (None, Some(before)) => {
+                let end = decode_position(before)?.min(total);
+                (end.saturating_sub(limit), end)
+            }
+            (None, None) => (0, limit.min(total)),
+        };
+
+        Some(Self {
+            next_cursor: (end < total).then(|| encode_position(end)),
+            prev_cursor: (start > 0).then(|| encode_position(start)),
+            total: Some(total as i64),
+            data: items.into_iter().skip(start).take(end - start).collect(),
+        })
+    }
+
+    /// Page of a collection read from storage at `offset`, with the same cursors as
+    /// `paginate_in_memory`
+    ///
+    /// `items` holds up to `limit + 1` rows; the extra row only shows that a next page exists.
+    pub fn from_offset(mut items: Vec<T>, offset: usize, limit: usize) -> Self {
+        let has_next = items.len() > limit;
+        items.truncate(limit);
+        Self {
+            next_cursor: has_next.then(|| encode_position(offset + items.len())),
+            prev_cursor: (offset > 0).then(|| encode_position(offset)),
+            total: None,
+            data: items,
+        }
+    }
+}
+
+/// Offset of the page requested by `pagination`, for collections paged with `Page::from_offset`
+///
+/// Returns `None` when a cursor is malformed or both `after` and `before` are given.
+pub fn page_offset(pagination: &PaginationRequest, limit: usize) -> Option<usize> {
+    match (&pagination.after, &pagination.before) {
+        (Some(_), Some(_)) => None,
+        (Some(after), None) => decode_position(after),
+        (None, Some(before)) => Some(decode_position(before)?.saturating_sub(limit)),
+        (None, None) => Some(0),
+    }
+}
+
+fn encode_position(position: usize) -> String {
+    URL_SAFE_NO_PAD.encode(position.to_string())
+}
+
+fn decode_position(cursor: &str) -> Option<usize> {
+    let decoded = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    std::str::from_utf8(&decoded).ok()?.parse().ok()
+}
+
+/// Response DTO for a user in admin user listings, without credentials or profile details
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserSummary {
+    pub id: i32,
+    pub username: String,
+    pub email: String,
+    /// First and last name, when the user has set either
+    pub display_name: Option<String>,
+    pub account_status: String,
+    pub created_at: String,
+}
+
+/// Response DTO for a user's URL statistics
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserUrlStatsResponse {
+    /// URLs that were not deleted, whatever their status
+    pub total_urls: i64,
+    pub active_urls: i64,
+    pub inactive_urls: i64,
+    pub total_clicks: i64,
+    /// Short codes of those URLs; no two live URLs share one, so this equals `total_urls`
+    pub unique_short_codes: i64,
+    /// Approximate number of distinct visitors across the URLs
+    pub estimated_unique_visitors: i64,
+    /// URLs archived after expiring; included in `total_urls`
+    pub archived_url_count: i64,
+    /// When the counters last changed
+    pub last_updated_at: String,
+}
+
+/// Response DTO for the analytics summary of a single URL
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UrlAnalyticsSummaryResponse {
+    pub url_id: i32,
+    pub total_clicks: i64,
+    /// Approximate number of distinct visitors
+    pub unique_visitors: i64,
+    pub clicks_today: i64,
+    pub clicks_this_week: i64,
+    pub clicks_this_month: i64,
+    pub top_countries: Vec<CountryClicks>,
+    pub top_referrers: Vec<ReferrerClicks>,
+    pub device_breakdown: DeviceBreakdownResponse,
+    /// Share of the counted clicks that led to at least one conversion, from 0 to 1
+    pub conversion_rate: f64,
+    /// Most recent clicks, newest first
+    pub recent_clicks: Vec<ClickSummary>,
+}
+
+/// Click count for one country
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CountryClicks {
+    pub country_code: String,
+    pub count: i64,
+}
+
+/// Click count for one referrer
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReferrerClicks {
+    pub referrer: String,
+    pub count: i64,
+}
+
+/// Clicks broken down by device type
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeviceBreakdownResponse {
+    pub desktop: i64,
+    pub mobile: i64,
+    pub tablet: i64,
+    pub bot: i64,
+}
+
+/// Clicks in one bucket of a URL's analytics timeseries
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ClickTimeseriesPoint {
+    /// Start of the bucket
+    pub start: String,
+    pub clicks: i64,
+}
+
+/// Response DTO for the analytics of a URL over a time range
+///
+/// Clicks from crawlers and scripted clients only count in `device_breakdown.bot`.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UrlAnalyticsResponse {
+    pub url_id: i32,
+    /// One of `hour`, `day`, `week` or `month`
+    pub granularity: String,
+    pub start: String,
+    pub end: String,
+    /// Clicks within the range
+    pub clicks: i64,
+    /// Approximate distinct visitors over the days of the range
+    pub unique_visitors: i64,
+    /// Clicks since the URL was created, including bots
+    pub total_clicks: i64,
+    /// Clicks per bucket, oldest first, including empty buckets
+    pub timeseries: Vec<ClickTimeseriesPoint>,
+    pub top_countries: Vec<CountryClicks>,
+    pub top_referrers: Vec<ReferrerClicks>,
+    pub device_breakdown: DeviceBreakdownResponse,
+}
+
+/// A single click in an analytics summary
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ClickSummary {
+    pub clicked_at: String,
+    pub country_code: Option<String>,
+    pub referrer: Option<String>,
+    /// desktop, mobile, tablet or bot
+    pub device: String,
+}
+
+/// Response DTO for the user dashboard
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DashboardResponse {
+    pub top_urls: Vec<UrlInfoResponse>,
+    pub recent_urls: Vec<UrlInfoResponse>,
+    pub stats: UserUrlStatsResponse,
+}
+
+/// Response DTO for health, liveness and readiness probes
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HealthResponse {
+    pub status: String,
+    /// Result of each dependency check ("ok" or "error: ...")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checks: Option<BTreeMap<String, String>>,
+}
+
 /// Response DTO for authentication
 #[allow(dead_code)]
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -49,6 +412,13 @@ pub struct UserResponse {
     pub created_at: String,
 }
 
+/// Response DTO listing the devices registered for push notifications
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeviceTokensResponse {
+    /// Registration tokens, oldest first
+    pub device_tokens: Vec<String>,
+}
+
 /// Response DTO for user profile information
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UserProfileResponse {
@@ -63,10 +433,83 @@ pub struct UserProfileResponse {
     pub website: Option<String>,
     pub location: Option<String>,
     pub privacy: ProfilePrivacyResponse,
+    pub social_links: Option<SocialLinksDto>,
+    /// Address the user is changing their email to, until both addresses confirmed
+    pub pending_email: Option<String>,
     pub created_at: String,
     pub updated_at: Option<String>,
 }
 
+/// Response DTO for a requested email change
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct EmailChangeRequestResponse {
+    pub message: String,
+    pub pending_email: String,
+    /// Both addresses have to confirm before this time
+    pub expires_at: String,
+}
+
+/// Response DTO for a confirmed side of an email change
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct EmailChangeConfirmResponse {
+    pub message: String,
+    /// Whether both sides confirmed and the email was changed
+    pub completed: bool,
+    /// Address still waiting for the other confirmation; `None` once completed
+    pub pending_email: Option<String>,
+}
+
+/// A user's links to their accounts elsewhere
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SocialLinksDto {
+    pub twitter: Option<String>,
+    pub github: Option<String>,
+    pub linkedin: Option<String>,
+    pub website: Option<String>,
+    /// Up to five other links
+    #[serde(default)]
+    pub custom: Vec<CustomSocialLinkDto>,
+}
+
+/// A labelled link of a profile's social links
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CustomSocialLinkDto {
+    pub label: String,
+    pub url: String,
+}
+
+impl From<SocialLinks> for SocialLinksDto {
+    fn from(links: SocialLinks) -> Self {
+        Self {
+            twitter: links.twitter,
+            github: links.github,
+            linkedin: links.linkedin,
+            website: links.website,
+            custom: links
+                .custom
+                .into_iter()
+                .map(|(label, url)| CustomSocialLinkDto { label, url })
+                .collect(),
+        }
+    }
+}
+
+impl From<SocialLinksDto> for SocialLinks {
+    fn from(links: SocialLinksDto) -> Self {
+        Self {
+            twitter: links.twitter,
+            github: links.github,
+            linkedin: links.linkedin,
+            website: links.website,
+            custom: links
+                .custom
+                .into_iter()
+                .map(|link| (link.label, link.url))
+                .collect(),
+        }
+    }
+}
+
 /// Privacy settings for profile responses
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub enum ProfilePrivacyResponse {
@@ -79,6 +522,8 @@ pub enum ProfilePrivacyResponse {
 }
 
 /// Response DTO for public user profile (limited fields)
+///
+/// Fields the owner chose to hide are omitted.
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PublicUserProfileResponse {
     pub id: i32,
@@ -86,13 +531,67 @@ pub struct PublicUserProfileResponse {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub full_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub bio: Option<String>,
     pub avatar_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub website: Option<String>,
     pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub social_links: Option<SocialLinksDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub click_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recent_urls: Option<Vec<PublicUrlResponse>>,
+}
+
+/// A URL listed on a public profile
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PublicUrlResponse {
+    pub short_code: String,
+    pub short_url: String,
+    pub original_url: String,
     pub created_at: String,
 }
 
+/// A URL as it appears in a personal data export
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ExportedUrlResponse {
+    pub id: i32,
+    pub short_code: String,
+    pub original_url: String,
+    pub status: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub expiration_date: Option<String>,
+    pub organization_id: Option<i32>,
+    /// Total clicks; individual clicks and their IP addresses are not exported
+    pub click_count: i64,
+}
+
+/// Response DTO for a personal data export (GDPR data portability)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserDataExportResponse {
+    pub exported_at: String,
+    pub profile: UserProfileResponse,
+    pub account_status: String,
+    pub tier: String,
+    pub profile_picture_url: Option<String>,
+    pub urls: Vec<ExportedUrlResponse>,
+    pub total_clicks: i64,
+}
+
+/// Response DTO for a data export generated in the background
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DataExportQueuedResponse {
+    pub operation_id: String,
+    pub message: String,
+}
+
 /// Generic error response DTO
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
@@ -101,6 +600,36 @@ pub struct ErrorResponse {
     pub status_code: u16,
 }
 
+/// A request body field that failed validation
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FieldErrorResponse {
+    /// Path of the field, e.g. `url` or `items[2].custom_short_code`
+    pub field: String,
+    pub message: String,
+}
+
+/// Error response DTO for a request body that failed validation
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ValidationErrorResponse {
+    pub error: String,
+    pub message: String,
+    pub status_code: u16,
+    pub errors: Vec<FieldErrorResponse>,
+}
+
+/// Error response DTO for a custom short code outside the allowed length
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ShortCodeLengthErrorResponse {
+    pub error: String,
+    pub message: String,
+    pub status_code: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    pub actual_length: usize,
+}
+
 /// Success response DTO
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SuccessResponse {
@@ -114,6 +643,7 @@ pub struct ExpirationInfoResponse {
     pub expiration_date: Option<String>,
     pub is_expired: bool,
     pub expires_in_days: Option<i64>,
+    pub version: i64,
 }
 
 /// Response DTO for URLs expiring soon
@@ -152,6 +682,27 @@ pub struct BulkOperationProgress {
     pub successful_items: usize,
     pub failed_items: usize,
     pub progress_percentage: f32,
+    pub priority: crate::application::dto::requests::OperationPriority,
+    /// Items that failed with a transient error and wait to be retried
+    pub pending_retries: usize,
+    /// Items left unprocessed because the operation was cancelled
+    pub cancelled_items: usize,
+    /// Operation started once this one completes
+    pub next_operation_id: Option<String>,
+}
+
+/// Response DTO for an operation chained to another one
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ChainedOperationResponse {
+    pub parent_operation_id: String,
+    pub child_operation_id: String,
+}
+
+/// Response DTO for the operations of a chain
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OperationChainResponse {
+    /// Progress of each operation, first to last
+    pub operations: Vec<BulkOperationProgress>,
 }
 
 /// Status of a bulk operation
@@ -169,6 +720,31 @@ pub enum BulkOperationStatus {
     Cancelled,
 }
 
+impl BulkOperationStatus {
+    /// Whether the operation has stopped and will not change any more
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            BulkOperationStatus::Completed
+                | BulkOperationStatus::Failed
+                | BulkOperationStatus::Cancelled
+        )
+    }
+}
+
+/// Outcome of one item of a bulk URL creation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BulkItemResult {
+    /// Position of the item in the request, starting at 0
+    pub index: usize,
+    pub url: String,
+    pub success: bool,
+    /// Short code of the created URL
+    pub short_code: Option<String>,
+    /// Why the item failed
+    pub error: Option<String>,
+}
+
 /// Response DTO for account deletion request
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AccountDeletionRequestResponse {
@@ -0,0 +1,418 @@
+use crate::application::dto::{
+    requests::GetUrlAnalyticsRequest,
+    responses::{
+        ClickTimeseriesPoint, CountryClicks, DeviceBreakdownResponse, ReferrerClicks,
+        UrlAnalyticsResponse,
+    },
+};
+use crate::domain::entities::click::DeviceType;
+use crate::domain::repositories::{
+    ClickRepository, ClickRepositoryError, RepositoryError, UrlRepository,
+};
+use crate::domain::services::click_tracking_service::TimelineGranularity;
+use crate::infrastructure::analytics_cache::{AnalyticsCache, KEY_PREFIX};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+/// How long computed analytics are served from the cache
+pub const ANALYTICS_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Most buckets a timeseries may have; longer ranges need a coarser granularity
+pub const MAX_TIMESERIES_BUCKETS: usize = 1000;
+
+/// Most countries and referrers listed
+const TOP_ENTRIES_LIMIT: usize = 5;
+
+/// Use case for the analytics of a URL over a time range
+#[derive(Clone)]
+pub struct GetUrlAnalyticsUseCase<R, C>
+where
+    R: UrlRepository + Clone,
+    C: ClickRepository + Clone,
+{
+    url_repository: R,
+    click_repository: C,
+    cache: Option<Arc<dyn AnalyticsCache>>,
+}
+
+impl<R, C> GetUrlAnalyticsUseCase<R, C>
+where
+    R: UrlRepository + Clone,
+    C: ClickRepository + Clone,
+{
+    pub fn new(url_repository: R, click_repository: C) -> Self {
+        Self {
+            url_repository,
+            click_repository,
+            cache: None,
+        }
+    }
+
+    /// Serve results from `cache` for `ANALYTICS_CACHE_TTL` after computing them
+    pub fn with_cache(mut self, cache: Arc<dyn AnalyticsCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Execute the URL analytics use case
+    ///
+    /// Callers check that the user may see the URL first, since cached analytics are served
+    /// without another check. Cache failures are only logged; the analytics are then computed
+    /// from the database.
+    pub async fn execute(
+        &self,
+        url_id: i32,
+        request: GetUrlAnalyticsRequest,
+    ) -> Result<UrlAnalyticsResponse, GetUrlAnalyticsError> {
+        if self.url_repository.find_by_id(url_id).await?.is_none() {
+            return Err(GetUrlAnalyticsError::NotFound);
+        }
+
+        let key = cache_key(url_id, &request);
+        if let Some(cache) = &self.cache {
+            match cache.get(&key).await {
+                Ok(Some(cached)) => match serde_json::from_str(&cached) {
+                    Ok(response) => return Ok(response),
+                    Err(e) => warn!("Ignoring unreadable cached analytics {}: {}", key, e),
+                },
+                Ok(None) => {}
+                Err(e) => warn!("Failed to read cached analytics {}: {}", key, e),
+            }
+        }
+
+        let response = self.compute(url_id, &request).await?;
+
+        if let Some(cache) = &self.cache {
+            let json = serde_json::to_string(&response).expect("analytics serialize to JSON");
+            if let Err(e) = cache.set(&key, &json, ANALYTICS_CACHE_TTL).await {
+                warn!("Failed to cache analytics {}: {}", key, e);
+            }
+        }
+        Ok(response)
+    }
+
+    /// Query the clicks, unique visitors and totals of a URL at once and assemble them
+    async fn compute(
+        &self,
+        url_id: i32,
+        request: &GetUrlAnalyticsRequest,
+    ) -> Result<UrlAnalyticsResponse, GetUrlAnalyticsError> {
+        let granularity = request.granularity;
+        let end = request.end.unwrap_or_else(Utc::now);
+        let start = request
+            .start
+            .unwrap_or_else(|| default_start(granularity, end));
+        let bucket_starts = bucket_starts(granularity, start, end)?;
+
+        let (clicks, unique_visitors, stats) = tokio::join!(
+            self.click_repository
+                .get_clicks_for_url(url_id, Some(start), Some(end)),
+            self.click_repository.get_unique_visitors_estimate(
+                url_id,
+                start.date_naive(),
+                end.date_naive()
+            ),
+            self.click_repository.get_url_click_stats(url_id),
+        );
+        let clicks = clicks?;
+
+        let mut timeseries: Vec<ClickTimeseriesPoint> = bucket_starts
+            .iter()
+            .map(|start| ClickTimeseriesPoint {
+                start: start.to_rfc3339(),
+                clicks: 0,
+            })
+            .collect();
+        let mut countries = HashMap::new();
+        let mut referrers = HashMap::new();
+        let mut device_breakdown = DeviceBreakdownResponse {
+            desktop: 0,
+            mobile: 0,
+            tablet: 0,
+            bot: 0,
+        };
+        let mut counted = 0;
+        for click in &clicks {
+            match click.device_type() {
+                DeviceType::Bot => {
+                    device_breakdown.bot += 1;
+                    continue;
+                }
+                DeviceType::Desktop => device_breakdown.desktop += 1,
+                DeviceType::Mobile => device_breakdown.mobile += 1,
+                DeviceType::Tablet => device_breakdown.tablet += 1,
+            }
+            counted += 1;
+            let bucket = granularity.bucket_start(click.clicked_at);
+            if let Ok(index) = bucket_starts.binary_search(&bucket) {
+                timeseries[index].clicks += 1;
+            }
+            count_key(&mut countries, click.country_code.as_deref());
+            count_key(&mut referrers, click.referer.as_deref());
+        }
+
+        Ok(UrlAnalyticsResponse {
+            url_id,
+            granularity: granularity.as_str().to_string(),
+            start: start.to_rfc3339(),
+            end: end.to_rfc3339(),
+            clicks: counted,
+            unique_visitors: unique_visitors?,
+            total_clicks: stats?.total_clicks,
+            timeseries,
+            top_countries: top_entries(countries)
+                .into_iter()
+                .map(|(country_code, count)| CountryClicks {
+                    country_code,
+                    count,
+                })
+                .collect(),
+            top_referrers: top_entries(referrers)
+                .into_iter()
+                .map(|(referrer, count)| ReferrerClicks { referrer, count })
+                .collect(),
+            device_breakdown,
+        })
+    }
+}
+
+/// Cache key of a request: `analytics:<url_id>:<granularity>:<start>:<end>`
+///
+/// Open range ends are keyed as `-`, so requests relative to now share an entry.
+fn cache_key(url_id: i32, request: &GetUrlAnalyticsRequest) -> String {
+    let format = |at: Option<DateTime<Utc>>| at.map_or("-".to_string(), |at| at.to_rfc3339());
+    format!(
+        "{}{}:{}:{}:{}",
+        KEY_PREFIX,
+        url_id,
+        request.granularity.as_str(),
+        format(request.start),
+        format(request.end)
+    )
+}
+
+/// Start of the usual window of a granularity ending at `end`, e.g. 30 days for `day`
+fn default_start(granularity: TimelineGranularity, end: DateTime<Utc>) -> DateTime<Utc> {
+    let mut start = granularity.bucket_start(end);
+    for _ in 1..granularity.bucket_count() {
+        start = granularity.previous(start);
+    }
+    start
+}
+
+/// Starts of the buckets overlapping `start..=end`, oldest first
+fn bucket_starts(
+    granularity: TimelineGranularity,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<DateTime<Utc>>, GetUrlAnalyticsError> {
+    if start > end {
+        return Err(GetUrlAnalyticsError::InvalidRange(
+            "start must not be after end".to_string(),
+        ));
+    }
+    let mut starts = Vec::new();
+    let mut bucket = granularity.bucket_start(start);
+    while bucket <= end {
+        if starts.len() == MAX_TIMESERIES_BUCKETS {
+            return Err(GetUrlAnalyticsError::InvalidRange(format!(
+                "range spans more than {} {}s; use a coarser granularity",
+                MAX_TIMESERIES_BUCKETS,
+                granularity.as_str()
+            )));
+        }
+        starts.push(bucket);
+        bucket = granularity.next(bucket);
+    }
+    Ok(starts)
+}
+
+fn count_key(counts: &mut HashMap<String, i64>, key: Option<&str>) {
+    if let Some(key) = key.filter(|key| !key.is_empty()) {
+        *counts.entry(key.to_string()).or_default() += 1;
+    }
+}
+
+/// The most frequent keys, ties broken alphabetically
+fn top_entries(counts: HashMap<String, i64>) -> Vec<(String, i64)> {
+    let mut entries: Vec<(String, i64)> = counts.into_iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    entries.truncate(TOP_ENTRIES_LIMIT);
+    entries
+}
+
+/// Errors of the URL analytics use case
+#[derive(Debug, thiserror::Error)]
+pub enum GetUrlAnalyticsError {
+    /// The URL does not exist
+    #[error("URL not found")]
+    NotFound,
+
+    #[error("Invalid range: {0}")]
+    InvalidRange(String),
+
+    #[error("URL repository error: {0}")]
+    Url(#[from] RepositoryError),
+
+    #[error("Click repository error: {0}")]
+    Click(#[from] ClickRepositoryError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Click, ShortCode, UrlStatus};
+    use crate::infrastructure::test_utils::{
+        MockAnalyticsCache, MockClickRepository, MockUrlRepository,
+    };
+    use chrono::TimeZone;
+
+    const BROWSER: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64)";
+
+    async fn setup() -> (
+        GetUrlAnalyticsUseCase<MockUrlRepository, MockClickRepository>,
+        MockClickRepository,
+        MockAnalyticsCache,
+        i32,
+    ) {
+        let urls = MockUrlRepository::new();
+        let url = urls
+            .create_url(
+                &ShortCode::new("stats1".to_string()).unwrap(),
+                "https://example.com",
+                None,
+                Some(1),
+                None,
+                UrlStatus::Active,
+            )
+            .await
+            .unwrap();
+        let clicks = MockClickRepository::new();
+        let cache = MockAnalyticsCache::new();
+        let use_case =
+            GetUrlAnalyticsUseCase::new(urls, clicks.clone()).with_cache(Arc::new(cache.clone()));
+        (use_case, clicks, cache, url.id)
+    }
+
+    fn click(url_id: i32, at: DateTime<Utc>, user_agent: &str, country: &str) -> Click {
+        let mut click = Click::new_for_tracking(
+            url_id,
+            Some("10.0.0.1".to_string()),
+            Some(user_agent.to_string()),
+            None,
+            Some(country.to_string()),
+        );
+        click.clicked_at = at;
+        click
+    }
+
+    fn day(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, day, 12, 0, 0).unwrap()
+    }
+
+    fn request(start: u32, end: u32) -> GetUrlAnalyticsRequest {
+        GetUrlAnalyticsRequest {
+            granularity: TimelineGranularity::Day,
+            start: Some(day(start)),
+            end: Some(day(end)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_analytics_over_range() {
+        let (use_case, clicks, _, url_id) = setup().await;
+        for click in [
+            click(url_id, day(1), BROWSER, "PT"),
+            click(url_id, day(3), BROWSER, "PT"),
+            click(url_id, day(3), BROWSER, "ES"),
+            click(url_id, day(3), "curl/8.0", "US"),
+            click(url_id, day(9), BROWSER, "PT"),
+        ] {
+            clicks.record_click(&click).await.unwrap();
+        }
+
+        let response = use_case.execute(url_id, request(2, 4)).await.unwrap();
+        assert_eq!(response.clicks, 2);
+        assert_eq!(response.total_clicks, 5);
+        assert_eq!(response.timeseries.len(), 3);
+        assert_eq!(
+            response
+                .timeseries
+                .iter()
+                .map(|p| p.clicks)
+                .collect::<Vec<_>>(),
+            vec![0, 2, 0]
+        );
+        assert_eq!(response.top_countries.len(), 2);
+        assert_eq!(response.device_breakdown.desktop, 2);
+        assert_eq!(response.device_breakdown.bot, 1);
+    }
+
+    #[tokio::test]
+    async fn test_analytics_are_cached_per_range_until_invalidated() {
+        let (use_case, clicks, cache, url_id) = setup().await;
+        clicks
+            .record_click(&click(url_id, day(3), BROWSER, "PT"))
+            .await
+            .unwrap();
+        let first = use_case.execute(url_id, request(2, 4)).await.unwrap();
+        assert_eq!(first.clicks, 1);
+        assert!(cache
+            .entries
+            .lock()
+            .unwrap()
+            .contains_key(&cache_key(url_id, &request(2, 4))));
+
+        clicks
+            .record_click(&click(url_id, day(3), BROWSER, "PT"))
+            .await
+            .unwrap();
+        let cached = use_case.execute(url_id, request(2, 4)).await.unwrap();
+        assert_eq!(cached.clicks, 1);
+        let other_range = use_case.execute(url_id, request(3, 4)).await.unwrap();
+        assert_eq!(other_range.clicks, 2);
+
+        cache.invalidate_url(url_id).await.unwrap();
+        let fresh = use_case.execute(url_id, request(2, 4)).await.unwrap();
+        assert_eq!(fresh.clicks, 2);
+    }
+
+    #[tokio::test]
+    async fn test_analytics_require_existing_url_and_valid_range() {
+        let (use_case, _, cache, url_id) = setup().await;
+        assert!(matches!(
+            use_case.execute(url_id + 1, request(2, 4)).await,
+            Err(GetUrlAnalyticsError::NotFound)
+        ));
+        assert!(matches!(
+            use_case.execute(url_id, request(4, 2)).await,
+            Err(GetUrlAnalyticsError::InvalidRange(_))
+        ));
+
+        let hourly_year = GetUrlAnalyticsRequest {
+            granularity: TimelineGranularity::Hour,
+            start: Some(Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap()),
+            end: Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+        };
+        assert!(matches!(
+            use_case.execute(url_id, hourly_year).await,
+            Err(GetUrlAnalyticsError::InvalidRange(_))
+        ));
+        assert!(cache.entries.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cache_key() {
+        assert_eq!(
+            cache_key(7, &GetUrlAnalyticsRequest::default()),
+            "analytics:7:day:-:-"
+        );
+        assert_eq!(
+            cache_key(7, &request(2, 4)),
+            "analytics:7:day:2024-03-02T12:00:00+00:00:2024-03-04T12:00:00+00:00"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use crate::application::dto::responses::{Page, UrlSummaryResponse};
+use crate::domain::entities::{Url, UrlStatus};
+use crate::domain::repositories::{SortDirection, UrlFilter, UrlRepository, UrlSortField};
+use crate::domain::services::{ServiceError, UrlService};
+
+/// Parameters of one page of a user's URL listing
+#[derive(Debug, Clone)]
+pub struct ListUrlsRequest {
+    pub user_id: i32,
+    /// Only URLs with this status; archived URLs are left out when omitted
+    pub status: Option<UrlStatus>,
+    pub sort: UrlSortField,
+    pub direction: SortDirection,
+    /// `next_cursor` of the previous page
+    pub after: Option<String>,
+    /// `prev_cursor` of the following page
+    pub before: Option<String>,
+    pub limit: usize,
+}
+
+/// Use case for listing a user's URLs with their click counts
+///
+/// Pagination and cursor checks are left to `UrlService::list_urls`; this adds the total
+/// number of matching URLs and the clicks of every URL on the page.
+#[derive(Clone)]
+pub struct ListUrlsUseCase<R>
+where
+    R: UrlRepository + Clone,
+{
+    url_service: UrlService<R>,
+    repository: R,
+    base_url: String,
+}
+
+impl<R> ListUrlsUseCase<R>
+where
+    R: UrlRepository + Clone,
+{
+    pub fn new(url_service: UrlService<R>, repository: R, base_url: String) -> Self {
+        Self {
+            url_service,
+            repository,
+            base_url,
+        }
+    }
+
+    /// List the page of URLs described by `request`
+    ///
+    /// The page and the total are fetched concurrently, then the click counts of the whole
+    /// page are loaded in one query. Invalid cursors are reported as
+    /// `ServiceError::InvalidData`.
+    pub async fn execute(
+        &self,
+        request: ListUrlsRequest,
+    ) -> Result<Page<UrlSummaryResponse>, ServiceError> {
+        let filter = UrlFilter {
+            status: request.status,
+        };
+        let (page, total) = tokio::join!(
+            self.url_service.list_urls(
+                request.user_id,
+                request.status,
+                request.sort,
+                request.direction,
+                request.after.as_deref(),
+                request.before.as_deref(),
+                request.limit,
+            ),
+            self.repository.count_by_filter(&filter, request.user_id),
+        );
+        let (page, total) = (page?, total?);
+
+        let url_ids: Vec<i32> = page.urls.iter().map(|url| url.id).collect();
+        let click_counts = self.repository.count_clicks_for_urls(&url_ids).await?;
+        let data = page
+            .urls
+            .into_iter()
+            .map(|url| {
+                let click_count = click_counts.get(&url.id).copied().unwrap_or(0);
+                self.summarize(url, click_count)
+            })
+            .collect();
+
+        Ok(Page {
+            data,
+            next_cursor: page.next_cursor,
+            prev_cursor: page.prev_cursor,
+            total: Some(total),
+        })
+    }
+
+    fn summarize(&self, url: Url, click_count: i64) -> UrlSummaryResponse {
+        UrlSummaryResponse {
+            id: url.id,
+            short_url: url.short_url(&self.base_url),
+            short_code: url.short_code,
+            original_url: url.original_url,
+            title: url.title,
+            status: url.status.to_string(),
+            created_at: url.created_at.to_rfc3339(),
+            expires_at: url.expiration_date.map(|d| d.to_rfc3339()),
+            click_count,
+            tags: url.tags,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::ShortCode;
+    use crate::infrastructure::test_utils::MockUrlRepository;
+
+    fn request(user_id: i32, status: Option<UrlStatus>, limit: usize) -> ListUrlsRequest {
+        ListUrlsRequest {
+            user_id,
+            status,
+            sort: UrlSortField::ShortCode,
+            direction: SortDirection::Asc,
+            after: None,
+            before: None,
+            limit,
+        }
+    }
+
+    async fn create(repository: &MockUrlRepository, code: &str, user_id: i32) -> Url {
+        repository
+            .create_url(
+                &ShortCode::new(code.to_string()).unwrap(),
+                "https://example.com",
+                None,
+                Some(user_id),
+                None,
+                UrlStatus::Active,
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_lists_page_with_total_and_click_counts() {
+        let repository = MockUrlRepository::new();
+        let first = create(&repository, "code01", 1).await;
+        create(&repository, "code02", 1).await;
+        create(&repository, "code03", 1).await;
+        create(&repository, "other1", 2).await;
+        repository.set_click_count(first.id, 7);
+        let use_case = ListUrlsUseCase::new(
+            UrlService::new(repository.clone()),
+            repository,
+            "https://sho.rt".to_string(),
+        );
+
+        let page = use_case.execute(request(1, None, 2)).await.unwrap();
+        assert_eq!(page.total, Some(3));
+        assert!(page.next_cursor.is_some());
+        let codes: Vec<&str> = page.data.iter().map(|u| u.short_code.as_str()).collect();
+        assert_eq!(codes, vec!["code01", "code02"]);
+        assert_eq!(page.data[0].click_count, 7);
+        assert_eq!(page.data[0].short_url, "https://sho.rt/code01");
+        assert_eq!(page.data[0].status, "active");
+        assert_eq!(page.data[1].click_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_total_follows_status_filter() {
+        let repository = MockUrlRepository::new();
+        create(&repository, "code01", 1).await;
+        let use_case = ListUrlsUseCase::new(
+            UrlService::new(repository.clone()),
+            repository,
+            "https://sho.rt".to_string(),
+        );
+
+        let page = use_case
+            .execute(request(1, Some(UrlStatus::Archived), 10))
+            .await
+            .unwrap();
+        assert!(page.data.is_empty());
+        assert_eq!(page.total, Some(0));
+    }
+}
@@ -1,3 +1,9 @@
+pub mod get_url_analytics;
+pub mod list_urls;
 pub mod shorten_url;
+pub mod update_url;
 
+pub use get_url_analytics::{GetUrlAnalyticsError, GetUrlAnalyticsUseCase};
+pub use list_urls::{ListUrlsRequest, ListUrlsUseCase};
 pub use shorten_url::ShortenUrlUseCase;
+pub use update_url::UpdateUrlUseCase;
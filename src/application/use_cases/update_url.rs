@@ -0,0 +1,368 @@
+use crate::application::dto::{requests::UpdateUrlRequest, responses::UpdateUrlResponse};
+use crate::application::use_cases::shorten_url::{ShortenUrlUseCase, UseCaseError};
+use crate::domain::entities::Url;
+use crate::domain::repositories::{RepositoryError, UrlRepository};
+use crate::domain::services::ServiceError;
+use bcrypt::{hash, DEFAULT_COST};
+use chrono::Utc;
+
+/// Use case for changing a URL after it was created
+///
+/// Destinations and custom short codes are validated with the rules of `ShortenUrlUseCase`,
+/// so an update cannot produce a URL that could not have been shortened.
+#[derive(Clone)]
+pub struct UpdateUrlUseCase<R>
+where
+    R: UrlRepository + Clone,
+{
+    shorten_url_use_case: ShortenUrlUseCase<R>,
+}
+
+impl<R> UpdateUrlUseCase<R>
+where
+    R: UrlRepository + Clone,
+{
+    pub fn new(shorten_url_use_case: ShortenUrlUseCase<R>) -> Self {
+        Self {
+            shorten_url_use_case,
+        }
+    }
+
+    /// Apply the fields set in `request` to `url`, which the caller checked the user may change
+    ///
+    /// The update is rejected with `RepositoryError::ConflictingUpdate` when the URL is no
+    /// longer at `expected_version`.
+    pub async fn execute(
+        &self,
+        mut url: Url,
+        request: UpdateUrlRequest,
+        expected_version: i64,
+    ) -> Result<UpdateUrlResponse, UseCaseError> {
+        let url_service = self.shorten_url_use_case.url_service();
+        if url.version != expected_version {
+            return Err(
+                ServiceError::Repository(RepositoryError::ConflictingUpdate {
+                    current_version: url.version,
+                })
+                .into(),
+            );
+        }
+
+        if let Some(title) = request.title {
+            let title = title.trim();
+            url.title = (!title.is_empty()).then(|| title.to_string());
+        }
+
+        if let Some(original_url) = request.original_url {
+            self.shorten_url_use_case
+                .check_destination(&original_url)
+                .await?;
+            url.original_url = original_url;
+        }
+
+        if let Some(expiration_date) = request.expiration_date {
+            if expiration_date.is_some_and(|date| date <= Utc::now()) {
+                return Err(UseCaseError::Validation(
+                    "Expiration date must be in the future".to_string(),
+                ));
+            }
+            url.expiration_date = expiration_date;
+        }
+
+        if let Some(redirect_type) = request.redirect_type {
+            url.redirect_type = redirect_type;
+        }
+
+        if let Some(custom_short_code) = request.custom_short_code {
+            let short_code = self
+                .shorten_url_use_case
+                .validate_custom_short_code(custom_short_code)?;
+            if short_code.value() != url.short_code {
+                if url_service.short_code_exists(&short_code).await? {
+                    return Err(ServiceError::ShortCodeAlreadyExists.into());
+                }
+                url.short_code = short_code.value().to_string();
+            }
+        }
+
+        if let Some(password) = request.password {
+            url.password_hash = password
+                .map(|password| hash(password, DEFAULT_COST))
+                .transpose()
+                .map_err(|e| UseCaseError::Internal(format!("Failed to hash password: {}", e)))?;
+        }
+
+        if let Some(max_clicks) = request.max_clicks {
+            url.max_clicks = max_clicks;
+        }
+
+        let updated = url_service.update_url(&url, expected_version).await?;
+        Ok(self.shorten_url_use_case.to_response(updated))
+    }
+
+    /// Give `url` a new short code, validated like a custom short code
+    ///
+    /// The caller checks the user may change the URL. The old short code keeps redirecting
+    /// as an alias.
+    pub async fn rename_short_code(
+        &self,
+        url: &Url,
+        short_code: String,
+    ) -> Result<Url, UseCaseError> {
+        let short_code = self
+            .shorten_url_use_case
+            .validate_custom_short_code(short_code)?;
+        Ok(self
+            .shorten_url_use_case
+            .url_service()
+            .rename_short_code(url.id, &short_code)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::dto::requests::ShortenUrlRequest;
+    use crate::domain::entities::{RedirectType, ShortCode, UrlStatus};
+    use crate::domain::services::url_service::MAX_SHORT_CODE_ALIASES;
+    use crate::domain::services::UrlService;
+    use crate::infrastructure::test_utils::MockUrlRepository;
+
+    const OWNER_ID: i32 = 1;
+
+    async fn setup() -> (UpdateUrlUseCase<MockUrlRepository>, MockUrlRepository, i32) {
+        let repository = MockUrlRepository::new();
+        let url = repository
+            .create_url(
+                &ShortCode::new("update1".to_string()).unwrap(),
+                "https://example.com/original",
+                None,
+                Some(OWNER_ID),
+                None,
+                UrlStatus::Active,
+            )
+            .await
+            .unwrap();
+        let shorten_url_use_case = ShortenUrlUseCase::new(
+            UrlService::new(repository.clone()),
+            "http://localhost:8000".to_string(),
+        );
+        (
+            UpdateUrlUseCase::new(shorten_url_use_case),
+            repository,
+            url.id,
+        )
+    }
+
+    async fn load(repository: &MockUrlRepository, id: i32) -> Url {
+        repository.find_by_id(id).await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_title_only_update_keeps_other_fields() {
+        let (use_case, repository, id) = setup().await;
+        let request: UpdateUrlRequest = serde_json::from_str(r#"{"title": "Launch"}"#).unwrap();
+
+        let response = use_case
+            .execute(load(&repository, id).await, request, 1)
+            .await
+            .unwrap();
+
+        assert_eq!(response.original_url, "https://example.com/original");
+        assert_eq!(response.short_code, "update1");
+        assert_eq!(response.version, 2);
+        let url = repository.find_by_id(id).await.unwrap().unwrap();
+        assert_eq!(url.title.as_deref(), Some("Launch"));
+        assert_eq!(url.redirect_type, RedirectType::Permanent);
+    }
+
+    #[tokio::test]
+    async fn test_null_password_removes_protection() {
+        let (use_case, repository, id) = setup().await;
+
+        let request: UpdateUrlRequest = serde_json::from_str(r#"{"password": "s3cret"}"#).unwrap();
+        use_case
+            .execute(load(&repository, id).await, request, 1)
+            .await
+            .unwrap();
+        let url = repository.find_by_id(id).await.unwrap().unwrap();
+        assert!(url.is_password_protected());
+        assert!(url.verify_password("s3cret"));
+        assert!(!url.verify_password("wrong"));
+
+        // An explicit null clears the password, so visitors are redirected without one
+        let request: UpdateUrlRequest = serde_json::from_str(r#"{"password": null}"#).unwrap();
+        use_case
+            .execute(load(&repository, id).await, request, 2)
+            .await
+            .unwrap();
+        let url = repository.find_by_id(id).await.unwrap().unwrap();
+        assert!(!url.is_password_protected());
+        assert!(url.verify_password(""));
+    }
+
+    #[tokio::test]
+    async fn test_click_limit_can_be_set_and_removed() {
+        let (use_case, repository, id) = setup().await;
+
+        let request: UpdateUrlRequest = serde_json::from_str(r#"{"max_clicks": 100}"#).unwrap();
+        use_case
+            .execute(load(&repository, id).await, request, 1)
+            .await
+            .unwrap();
+        let url = repository.find_by_id(id).await.unwrap().unwrap();
+        assert_eq!(url.max_clicks, Some(100));
+
+        let request: UpdateUrlRequest = serde_json::from_str(r#"{"title": "Launch"}"#).unwrap();
+        use_case
+            .execute(load(&repository, id).await, request, 2)
+            .await
+            .unwrap();
+        let url = repository.find_by_id(id).await.unwrap().unwrap();
+        assert_eq!(url.max_clicks, Some(100));
+
+        let request: UpdateUrlRequest = serde_json::from_str(r#"{"max_clicks": null}"#).unwrap();
+        use_case
+            .execute(load(&repository, id).await, request, 3)
+            .await
+            .unwrap();
+        let url = repository.find_by_id(id).await.unwrap().unwrap();
+        assert_eq!(url.max_clicks, None);
+    }
+
+    #[tokio::test]
+    async fn test_expiration_can_be_cleared_but_not_set_in_the_past() {
+        let (use_case, repository, id) = setup().await;
+
+        let request = UpdateUrlRequest {
+            expiration_date: Some(Some(Utc::now() - chrono::Duration::hours(1))),
+            ..Default::default()
+        };
+        let result = use_case
+            .execute(load(&repository, id).await, request, 1)
+            .await;
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+
+        let tomorrow = Utc::now() + chrono::Duration::days(1);
+        let request = UpdateUrlRequest {
+            expiration_date: Some(Some(tomorrow)),
+            ..Default::default()
+        };
+        let response = use_case
+            .execute(load(&repository, id).await, request, 1)
+            .await
+            .unwrap();
+        assert_eq!(response.expiration_date, Some(tomorrow.to_rfc3339()));
+
+        let request: UpdateUrlRequest =
+            serde_json::from_str(r#"{"expiration_date": null}"#).unwrap();
+        let response = use_case
+            .execute(load(&repository, id).await, request, 2)
+            .await
+            .unwrap();
+        assert_eq!(response.expiration_date, None);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_stale_and_invalid_updates() {
+        let (use_case, repository, id) = setup().await;
+        repository
+            .create_url(
+                &ShortCode::new("taken1".to_string()).unwrap(),
+                "https://example.com/other",
+                None,
+                Some(OWNER_ID),
+                None,
+                UrlStatus::Active,
+            )
+            .await
+            .unwrap();
+
+        let result = use_case
+            .execute(load(&repository, id).await, UpdateUrlRequest::default(), 5)
+            .await;
+        assert!(matches!(
+            result,
+            Err(UseCaseError::Service(ServiceError::Repository(
+                RepositoryError::ConflictingUpdate { current_version: 1 }
+            )))
+        ));
+
+        let request = UpdateUrlRequest {
+            custom_short_code: Some("taken1".to_string()),
+            ..Default::default()
+        };
+        let result = use_case
+            .execute(load(&repository, id).await, request, 1)
+            .await;
+        assert!(matches!(
+            result,
+            Err(UseCaseError::Service(ServiceError::ShortCodeAlreadyExists))
+        ));
+
+        let request = UpdateUrlRequest {
+            original_url: Some("http://127.0.0.1/admin".to_string()),
+            ..Default::default()
+        };
+        let result = use_case
+            .execute(load(&repository, id).await, request, 1)
+            .await;
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_renamed_short_code_keeps_redirecting() {
+        let (use_case, repository, id) = setup().await;
+
+        let url = use_case
+            .rename_short_code(&load(&repository, id).await, "memorable".to_string())
+            .await
+            .unwrap();
+        assert_eq!(url.short_code, "memorable");
+
+        let url_service = use_case.shorten_url_use_case.url_service();
+        for code in ["update1", "memorable"] {
+            let url = url_service
+                .get_url_for_redirect(&ShortCode::new(code.to_string()).unwrap())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(url.id, id);
+        }
+
+        // The old short code still belongs to the URL, so it cannot be shortened again
+        let request = ShortenUrlRequest {
+            url: "https://example.com/other".to_string(),
+            custom_short_code: Some("update1".to_string()),
+            expiration_date: None,
+            organization_id: None,
+        };
+        let result = use_case.shorten_url_use_case.execute(request, None).await;
+        assert!(matches!(
+            result,
+            Err(UseCaseError::Service(ServiceError::ShortCodeAlreadyExists))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rename_is_limited_by_alias_count() {
+        let (use_case, repository, id) = setup().await;
+
+        for i in 0..MAX_SHORT_CODE_ALIASES {
+            use_case
+                .rename_short_code(&load(&repository, id).await, format!("renamed{}", i))
+                .await
+                .unwrap();
+        }
+        let result = use_case
+            .rename_short_code(&load(&repository, id).await, "onetoomany".to_string())
+            .await;
+        assert!(matches!(
+            result,
+            Err(UseCaseError::Service(ServiceError::Repository(
+                RepositoryError::AliasLimitReached { limit: 3 }
+            )))
+        ));
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Deletion reason of URLs removed once the deleted URL retention passed
+pub const DELETED_URL_RETENTION_REASON: &str = "deleted_url_retention";
+
+/// Domain entity for a copy of a record kept after the record was removed for good
+///
+/// Archive records let deployments show what was deleted and why, long after the data is
+/// gone. They are purged themselves after the archive retention.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchiveRecord {
+    pub id: i64,
+    /// Kind of record removed, e.g. `url`
+    pub entity_type: String,
+    pub entity_id: i32,
+    /// The record as serialized just before it was removed, without sensitive fields
+    pub data_snapshot: serde_json::Value,
+    pub deleted_at: DateTime<Utc>,
+    /// Why the record was removed, e.g. `deleted_url_retention`
+    pub deletion_reason: String,
+}
+
+impl ArchiveRecord {
+    /// Record of `entity` about to be removed, leaving out the `sensitive_fields`
+    ///
+    /// Sensitive fields are top-level field names of the serialized entity. `id` and
+    /// `deleted_at` are assigned when the record is stored.
+    pub fn snapshot<T: Serialize>(
+        entity_type: &str,
+        entity_id: i32,
+        entity: &T,
+        deletion_reason: &str,
+        sensitive_fields: &[String],
+    ) -> Result<Self, serde_json::Error> {
+        let mut data_snapshot = serde_json::to_value(entity)?;
+        if let Some(fields) = data_snapshot.as_object_mut() {
+            for field in sensitive_fields {
+                fields.remove(field);
+            }
+        }
+
+        Ok(Self {
+            id: 0,
+            entity_type: entity_type.to_string(),
+            entity_id,
+            data_snapshot,
+            deleted_at: Utc::now(),
+            deletion_reason: deletion_reason.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Person {
+        name: String,
+        email: String,
+        age: u8,
+    }
+
+    #[test]
+    fn test_snapshot_leaves_out_sensitive_fields() {
+        let person = Person {
+            name: "Ada".to_string(),
+            email: "ada@example.com".to_string(),
+            age: 36,
+        };
+
+        let record = ArchiveRecord::snapshot(
+            "person",
+            7,
+            &person,
+            "test",
+            &["email".to_string(), "missing".to_string()],
+        )
+        .unwrap();
+        assert_eq!(record.entity_type, "person");
+        assert_eq!(record.entity_id, 7);
+        assert_eq!(record.deletion_reason, "test");
+        assert_eq!(
+            record.data_snapshot,
+            serde_json::json!({ "name": "Ada", "age": 36 })
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Audit log action of an administrator moving a URL to another user
+pub const URL_TRANSFER_ACTION: &str = "url.transfer";
+
+/// Audit log action of a redirect refused because of the client IP's reputation
+pub const SUSPICIOUS_IP_BLOCK_ACTION: &str = "ip.blocked";
+
+/// Actor of entries recorded by the server itself rather than a user
+pub const SYSTEM_ACTOR_ID: i32 = 0;
+
+/// Domain entity for an administrative action recorded in the audit log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditLogEntry {
+    pub id: i64,
+    /// User who performed the action
+    pub actor_user_id: i32,
+    /// What was done, e.g. `url.transfer`
+    pub action: String,
+    /// Kind of record acted on, e.g. `url`
+    pub entity_type: String,
+    pub entity_id: i32,
+    /// Action-specific details
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditLogEntry {
+    /// Entry for an administrator moving URL `url_id` from one user to another
+    ///
+    /// `id` and `created_at` are assigned when the entry is stored.
+    pub fn url_transfer(
+        admin_user_id: i32,
+        url_id: i32,
+        from_user_id: i32,
+        to_user_id: i32,
+    ) -> Self {
+        Self {
+            id: 0,
+            actor_user_id: admin_user_id,
+            action: URL_TRANSFER_ACTION.to_string(),
+            entity_type: "url".to_string(),
+            entity_id: url_id,
+            details: serde_json::json!({
+                "from_user_id": from_user_id,
+                "to_user_id": to_user_id,
+            }),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Entry for a redirect of URL `url_id` refused to `ip_address` for its abuse `score`
+    pub fn suspicious_ip_block(url_id: i32, ip_address: &str, score: u8) -> Self {
+        Self {
+            id: 0,
+            actor_user_id: SYSTEM_ACTOR_ID,
+            action: SUSPICIOUS_IP_BLOCK_ACTION.to_string(),
+            entity_type: "url".to_string(),
+            entity_id: url_id,
+            details: serde_json::json!({
+                "ip_address": ip_address,
+                "abuse_score": score,
+            }),
+            created_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Domain entity for a blacklisted domain
+///
+/// Blocking a domain also blocks all of its subdomains.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BlockedDomain {
+    pub id: i32,
+    /// Lowercase domain name without a trailing dot
+    pub domain: String,
+    pub reason: String,
+    /// Administrator who added the entry; `None` for entries loaded from the blacklist file
+    pub added_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BlockedDomain {
+    /// Check if `host` is this domain or one of its subdomains
+    ///
+    /// `host` must already be lowercase.
+    pub fn matches(&self, host: &str) -> bool {
+        host == self.domain
+            || host
+                .strip_suffix(self.domain.as_str())
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_domain_and_subdomains() {
+        let blocked = BlockedDomain {
+            id: 1,
+            domain: "malware.com".to_string(),
+            reason: "Malware".to_string(),
+            added_by: None,
+            created_at: Utc::now(),
+        };
+
+        assert!(blocked.matches("malware.com"));
+        assert!(blocked.matches("cdn.malware.com"));
+        assert!(blocked.matches("a.b.malware.com"));
+        assert!(!blocked.matches("notmalware.com"));
+        assert!(!blocked.matches("malware.com.example.org"));
+        assert!(!blocked.matches("com"));
+    }
+}
@@ -2,6 +2,129 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+/// User agent fragments identifying crawlers, link previewers and scripted clients
+///
+/// Matched case-insensitively; kept free of regex metacharacters so the same lists can be
+/// used in SQL pattern matches.
+pub const BOT_USER_AGENT_MARKERS: &[&str] = &[
+    "bot",
+    "crawl",
+    "spider",
+    "slurp",
+    "preview",
+    "facebookexternalhit",
+    "headless",
+    "curl",
+    "wget",
+    "python-requests",
+    "go-http-client",
+];
+
+/// User agent fragments identifying tablets
+pub const TABLET_USER_AGENT_MARKERS: &[&str] = &["ipad", "tablet", "kindle", "silk", "playbook"];
+
+/// User agent fragments identifying phones
+pub const MOBILE_USER_AGENT_MARKERS: &[&str] = &[
+    "mobi",
+    "iphone",
+    "ipod",
+    "android",
+    "blackberry",
+    "opera mini",
+    "windows phone",
+];
+
+/// Kind of device a click came from, derived from its user agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeviceType {
+    Desktop,
+    Mobile,
+    Tablet,
+    Bot,
+}
+
+impl DeviceType {
+    /// Classify a user agent; clicks without one are treated as bots
+    ///
+    /// Checked in order bot, tablet, mobile, so an Android tablet counts as a tablet.
+    pub fn from_user_agent(user_agent: Option<&str>) -> Self {
+        let Some(user_agent) = user_agent else {
+            return DeviceType::Bot;
+        };
+        let user_agent = user_agent.to_lowercase();
+        let matches = |markers: &[&str]| markers.iter().any(|m| user_agent.contains(m));
+
+        if matches(BOT_USER_AGENT_MARKERS) {
+            DeviceType::Bot
+        } else if matches(TABLET_USER_AGENT_MARKERS) {
+            DeviceType::Tablet
+        } else if matches(MOBILE_USER_AGENT_MARKERS) {
+            DeviceType::Mobile
+        } else {
+            DeviceType::Desktop
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeviceType::Desktop => "desktop",
+            DeviceType::Mobile => "mobile",
+            DeviceType::Tablet => "tablet",
+            DeviceType::Bot => "bot",
+        }
+    }
+}
+
+/// UTM campaign parameters a click is attributed to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UtmParameters {
+    pub source: Option<String>,
+    pub medium: Option<String>,
+    pub campaign: Option<String>,
+}
+
+impl UtmParameters {
+    /// Read the `utm_source`, `utm_medium` and `utm_campaign` query parameters of a URL
+    ///
+    /// URLs that don't parse and parameters left empty give nothing.
+    pub fn from_url(url: &str) -> Self {
+        let mut utm = Self::default();
+        let Ok(url) = ::url::Url::parse(url) else {
+            return utm;
+        };
+        for (name, value) in url.query_pairs() {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            let field = match name.as_ref() {
+                "utm_source" => &mut utm.source,
+                "utm_medium" => &mut utm.medium,
+                "utm_campaign" => &mut utm.campaign,
+                _ => continue,
+            };
+            field.get_or_insert_with(|| value.to_string());
+        }
+        utm
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.source.is_none() && self.medium.is_none() && self.campaign.is_none()
+    }
+
+    /// These parameters, or `other` when there are none
+    ///
+    /// Parameters are taken as a set so one click is never credited to two campaigns.
+    pub fn or(self, other: Self) -> Self {
+        if self.is_empty() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
 /// Domain entity representing a click/access event for analytics
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Click {
@@ -13,6 +136,18 @@ pub struct Click {
     pub referer: Option<String>,
     pub country_code: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// Opaque token handed to the visitor in a cookie so later conversions can be attributed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub click_token: Option<String>,
+    /// The click came from an IP with a bad reputation
+    #[serde(default)]
+    pub suspicious: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utm_source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utm_medium: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utm_campaign: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -38,6 +173,11 @@ impl Click {
             referer,
             country_code,
             created_at,
+            click_token: None,
+            suspicious: false,
+            utm_source: None,
+            utm_medium: None,
+            utm_campaign: None,
         }
     }
 
@@ -89,6 +229,27 @@ impl Click {
         self.country_code.is_some()
     }
 
+    /// Kind of device the click came from
+    pub fn device_type(&self) -> DeviceType {
+        DeviceType::from_user_agent(self.user_agent.as_deref())
+    }
+
+    /// UTM campaign parameters the click is attributed to
+    pub fn utm(&self) -> UtmParameters {
+        UtmParameters {
+            source: self.utm_source.clone(),
+            medium: self.utm_medium.clone(),
+            campaign: self.utm_campaign.clone(),
+        }
+    }
+
+    /// Attribute the click to a UTM campaign
+    pub fn set_utm(&mut self, utm: UtmParameters) {
+        self.utm_source = utm.source;
+        self.utm_medium = utm.medium;
+        self.utm_campaign = utm.campaign;
+    }
+
     /// Get a sanitized user agent (first 100 characters)
     pub fn sanitized_user_agent(&self) -> Option<String> {
         self.user_agent.as_ref().map(|ua| {
@@ -160,6 +321,77 @@ mod tests {
         assert!(sanitized.ends_with("..."));
     }
 
+    #[test]
+    fn test_device_type_from_user_agent() {
+        let cases = [
+            (
+                Some("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 Chrome/120.0"),
+                DeviceType::Desktop,
+            ),
+            (
+                Some("Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X) Mobile/15E148"),
+                DeviceType::Mobile,
+            ),
+            (
+                Some("Mozilla/5.0 (Linux; Android 14; Pixel 8) Mobile Safari/537.36"),
+                DeviceType::Mobile,
+            ),
+            (
+                Some("Mozilla/5.0 (iPad; CPU OS 17_0 like Mac OS X) Mobile/15E148"),
+                DeviceType::Tablet,
+            ),
+            (
+                Some("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"),
+                DeviceType::Bot,
+            ),
+            (Some("curl/8.4.0"), DeviceType::Bot),
+            (None, DeviceType::Bot),
+        ];
+
+        for (user_agent, expected) in cases {
+            assert_eq!(DeviceType::from_user_agent(user_agent), expected);
+        }
+    }
+
+    #[test]
+    fn test_user_agent_markers_are_sql_safe() {
+        let markers = BOT_USER_AGENT_MARKERS
+            .iter()
+            .chain(TABLET_USER_AGENT_MARKERS)
+            .chain(MOBILE_USER_AGENT_MARKERS);
+        for marker in markers {
+            assert!(marker
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c == ' ' || c == '-'));
+        }
+    }
+
+    #[test]
+    fn test_utm_parameters_from_url() {
+        let utm = UtmParameters::from_url(
+            "https://example.com/?utm_source=newsletter&utm_medium=email&utm_campaign=&ref=x",
+        );
+        assert_eq!(utm.source.as_deref(), Some("newsletter"));
+        assert_eq!(utm.medium.as_deref(), Some("email"));
+        assert_eq!(utm.campaign, None);
+
+        assert!(UtmParameters::from_url("https://example.com/?ref=x").is_empty());
+        assert!(UtmParameters::from_url("not a url").is_empty());
+    }
+
+    #[test]
+    fn test_utm_parameters_fall_back_as_a_set() {
+        let destination = UtmParameters::from_url("https://example.com/?utm_source=newsletter");
+        let referer =
+            UtmParameters::from_url("https://short.ly/?utm_source=ads&utm_campaign=spring");
+
+        let utm = destination.or(referer.clone());
+        assert_eq!(utm.source.as_deref(), Some("newsletter"));
+        assert_eq!(utm.campaign, None);
+
+        assert_eq!(UtmParameters::default().or(referer.clone()), referer);
+    }
+
     #[test]
     fn test_geographic_data_detection() {
         let click_with_geo =
@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A page the owner of a short URL counts as a conversion when a visitor reaches it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConversionGoal {
+    pub id: i32,
+    pub url_id: i32,
+    /// URL of the goal page; `*` matches any run of characters
+    pub goal_url_pattern: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ConversionGoal {
+    /// Check whether a page URL matches the goal pattern
+    ///
+    /// Matching is case-sensitive except for the scheme and host, which browsers normalize.
+    pub fn matches_url(&self, url: &str) -> bool {
+        wildcard_match(
+            &normalize_origin(&self.goal_url_pattern),
+            &normalize_origin(url),
+        )
+    }
+}
+
+/// A visitor who arrived through a short URL reaching one of its goals
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConversionEvent {
+    pub id: i32,
+    /// Click the visitor arrived with; cleared when old clicks are purged
+    pub click_id: Option<i32>,
+    pub goal_id: i32,
+    pub converted_at: DateTime<Utc>,
+}
+
+/// Lowercase the `scheme://host` part of a URL, leaving path and query untouched
+fn normalize_origin(url: &str) -> String {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return url.to_string();
+    };
+    let host_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    format!(
+        "{}://{}{}",
+        scheme.to_lowercase(),
+        rest[..host_end].to_lowercase(),
+        &rest[host_end..]
+    )
+}
+
+/// Glob match where `*` stands for any (possibly empty) run of characters
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard: the whole text must match
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn goal(pattern: &str) -> ConversionGoal {
+        ConversionGoal {
+            id: 1,
+            url_id: 1,
+            goal_url_pattern: pattern.to_string(),
+            name: "Signup".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_exact_pattern() {
+        let goal = goal("https://shop.example.com/thank-you");
+        assert!(goal.matches_url("https://shop.example.com/thank-you"));
+        assert!(goal.matches_url("HTTPS://Shop.Example.com/thank-you"));
+        assert!(!goal.matches_url("https://shop.example.com/thank-you/extra"));
+        assert!(!goal.matches_url("https://shop.example.com/Thank-You"));
+    }
+
+    #[test]
+    fn test_wildcard_pattern() {
+        let goal = goal("https://shop.example.com/orders/*/confirmation*");
+        assert!(goal.matches_url("https://shop.example.com/orders/42/confirmation"));
+        assert!(goal.matches_url("https://shop.example.com/orders/42/confirmation?ref=mail"));
+        assert!(!goal.matches_url("https://shop.example.com/orders/42"));
+        assert!(!goal.matches_url("https://evil.example/orders/42/confirmation"));
+    }
+
+    #[test]
+    fn test_wildcard_only_pattern() {
+        assert!(goal("*").matches_url("https://anything.example/"));
+        assert!(goal("https://*.example.com/done").matches_url("https://www.example.com/done"));
+    }
+}
@@ -0,0 +1,98 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Address a confirmation link of an email change is sent to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailChangeSide {
+    /// The address the account is moving away from
+    OldAddress,
+    /// The address the account is moving to
+    NewAddress,
+}
+
+/// Domain entity representing a pending change of a user's email address
+///
+/// The change needs a confirmation from both the current and the new address. Only hashes
+/// of the two tokens are stored; the raw tokens exist solely in the emailed links.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EmailChangeToken {
+    pub id: i32,
+    pub user_id: i32,
+    /// Address the email changes to once both sides confirmed
+    pub new_email: String,
+    /// Hash of the token sent to the current address
+    pub old_token_hash: String,
+    /// Hash of the token sent to the new address
+    pub new_token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub old_confirmed_at: Option<DateTime<Utc>>,
+    pub new_confirmed_at: Option<DateTime<Utc>>,
+}
+
+impl EmailChangeToken {
+    /// Create a new email change expiring after the given number of hours
+    pub fn new(
+        user_id: i32,
+        new_email: String,
+        old_token_hash: String,
+        new_token_hash: String,
+        expiration_hours: i64,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: 0,
+            user_id,
+            new_email,
+            old_token_hash,
+            new_token_hash,
+            created_at: now,
+            expires_at: now + Duration::hours(expiration_hours),
+            old_confirmed_at: None,
+            new_confirmed_at: None,
+        }
+    }
+
+    /// Check if the change expired before both sides confirmed it
+    pub fn is_expired(&self) -> bool {
+        Utc::now() > self.expires_at
+    }
+
+    /// Check if both the current and the new address confirmed the change
+    pub fn is_confirmed(&self) -> bool {
+        self.old_confirmed_at.is_some() && self.new_confirmed_at.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_change_needs_both_confirmations() {
+        let mut change = EmailChangeToken::new(
+            7,
+            "new@example.com".to_string(),
+            "old-hash".to_string(),
+            "new-hash".to_string(),
+            24,
+        );
+        assert!(!change.is_expired());
+        assert!(!change.is_confirmed());
+
+        change.new_confirmed_at = Some(Utc::now());
+        assert!(!change.is_confirmed());
+
+        change.old_confirmed_at = Some(Utc::now());
+        assert!(change.is_confirmed());
+
+        let expired = EmailChangeToken::new(
+            7,
+            "new@example.com".to_string(),
+            "old-hash".to_string(),
+            "new-hash".to_string(),
+            -1,
+        );
+        assert!(expired.is_expired());
+    }
+}
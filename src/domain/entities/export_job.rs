@@ -0,0 +1,128 @@
+use crate::application::dto::requests::ExportFormat;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Period of time an analytics export covers; open ends are unbounded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateRange {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl DateRange {
+    /// Whether the range ends no earlier than it starts
+    pub fn is_valid(&self) -> bool {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => start <= end,
+            _ => true,
+        }
+    }
+}
+
+/// Stage of an analytics export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportJobStatus {
+    Pending,
+    Processing,
+    Completed,
+    Failed,
+}
+
+impl ExportJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportJobStatus::Pending => "pending",
+            ExportJobStatus::Processing => "processing",
+            ExportJobStatus::Completed => "completed",
+            ExportJobStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(ExportJobStatus::Pending),
+            "processing" => Some(ExportJobStatus::Processing),
+            "completed" => Some(ExportJobStatus::Completed),
+            "failed" => Some(ExportJobStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// Domain entity representing a user's export of their raw click data
+///
+/// Its ID is the ID of the bulk operation producing the file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExportJob {
+    pub id: String,
+    pub user_id: i32,
+    pub format: ExportFormat,
+    pub date_range: DateRange,
+    pub status: ExportJobStatus,
+    /// Key of the export file in the private export storage, once uploaded
+    pub object_key: Option<String>,
+    pub click_count: u64,
+    /// Why the export failed
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl ExportJob {
+    /// A new export waiting to run
+    pub fn new(id: String, user_id: i32, format: ExportFormat, date_range: DateRange) -> Self {
+        Self {
+            id,
+            user_id,
+            format,
+            date_range,
+            status: ExportJobStatus::Pending,
+            object_key: None,
+            click_count: 0,
+            error: None,
+            created_at: Utc::now(),
+            completed_at: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_export_job_status_round_trip() {
+        for status in [
+            ExportJobStatus::Pending,
+            ExportJobStatus::Processing,
+            ExportJobStatus::Completed,
+            ExportJobStatus::Failed,
+        ] {
+            assert_eq!(ExportJobStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(ExportJobStatus::parse("done"), None);
+    }
+
+    #[test]
+    fn test_date_range_is_valid() {
+        let now = Utc::now();
+        assert!(DateRange::default().is_valid());
+        assert!(DateRange {
+            start: Some(now),
+            end: None
+        }
+        .is_valid());
+        assert!(DateRange {
+            start: Some(now - Duration::days(1)),
+            end: Some(now)
+        }
+        .is_valid());
+        assert!(!DateRange {
+            start: Some(now),
+            end: Some(now - Duration::days(1))
+        }
+        .is_valid());
+    }
+}
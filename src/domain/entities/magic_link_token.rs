@@ -0,0 +1,64 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Domain entity representing a passwordless login link
+///
+/// Only a hash of the token is stored; the raw token exists solely in the emailed link.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MagicLinkToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub used: bool,
+}
+
+impl MagicLinkToken {
+    /// Create a new magic link token expiring after the given number of minutes
+    pub fn new(user_id: i32, token_hash: String, expiration_minutes: i64) -> Self {
+        let now = Utc::now();
+        Self {
+            id: 0,
+            user_id,
+            token_hash,
+            created_at: now,
+            expires_at: now + Duration::minutes(expiration_minutes),
+            used: false,
+        }
+    }
+
+    /// Check if the token is expired
+    pub fn is_expired(&self) -> bool {
+        Utc::now() > self.expires_at
+    }
+
+    /// Check if the token can still be used to log in
+    #[allow(dead_code)]
+    pub fn is_valid(&self) -> bool {
+        !self.is_expired() && !self.used
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_magic_link_token_validity() {
+        let token = MagicLinkToken::new(7, "hash".to_string(), 15);
+        assert_eq!(token.user_id, 7);
+        assert!(!token.used);
+        assert!(token.is_valid());
+
+        let expired = MagicLinkToken::new(7, "hash".to_string(), -1);
+        assert!(expired.is_expired());
+        assert!(!expired.is_valid());
+
+        let used = MagicLinkToken {
+            used: true,
+            ..token
+        };
+        assert!(!used.is_valid());
+    }
+}
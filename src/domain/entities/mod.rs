@@ -1,13 +1,48 @@
 pub mod account_deletion_token;
+pub mod archive_record;
+pub mod audit_log_entry;
+pub mod blocked_domain;
 pub mod click;
+pub mod conversion;
+pub mod email_change_token;
+pub mod export_job;
+pub mod magic_link_token;
+pub mod notification_preferences;
+pub mod organization;
+pub mod outbox_email;
+pub mod outbox_push;
 pub mod password_reset_token;
+pub mod service_account;
+pub mod session;
 pub mod short_code;
 pub mod url;
+pub mod url_config;
+pub mod url_metadata;
 pub mod user;
+pub mod user_url_stats;
 
 pub use account_deletion_token::AccountDeletionToken;
-pub use click::Click;
+pub use archive_record::ArchiveRecord;
+pub use audit_log_entry::AuditLogEntry;
+pub use blocked_domain::BlockedDomain;
+pub use click::{Click, UtmParameters};
+pub use conversion::{ConversionEvent, ConversionGoal};
+pub use email_change_token::{EmailChangeSide, EmailChangeToken};
+pub use export_job::{DateRange, ExportJob, ExportJobStatus};
+pub use magic_link_token::MagicLinkToken;
+pub use notification_preferences::NotificationPreferences;
+pub use organization::{OrgRole, Organization, OrganizationMember, OrganizationWithMemberCount};
+pub use outbox_email::OutboxEmail;
+pub use outbox_push::OutboxPush;
 pub use password_reset_token::PasswordResetToken;
-pub use short_code::{ShortCode, ShortCodeError};
-pub use url::{Url, UrlStatus};
-pub use user::{ProfilePrivacy, User};
+pub use service_account::ServiceAccount;
+pub use session::{Session, SessionClient};
+pub use short_code::{ShortCode, ShortCodeAlphabet, ShortCodeError, ShortCodeValidator};
+pub use url::{AccessibilityStatus, PreviewMode, RedirectType, Url, UrlStatus, UrlWithClickCount};
+pub use url_config::UrlConfig;
+pub use url_metadata::UrlMetadata;
+pub use user::{
+    AccountStatus, OAuthProvider, ProfilePrivacy, ProfileVisibility, SocialLinks, User, UserTier,
+    MAX_CUSTOM_SOCIAL_LINKS, MAX_DEVICE_TOKENS,
+};
+pub use user_url_stats::{UrlCountChange, UserUrlStats};
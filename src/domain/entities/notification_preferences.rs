@@ -0,0 +1,66 @@
+use chrono::{DateTime, Datelike, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A digest is skipped if the previous one went out less than this long ago
+pub const DIGEST_MIN_INTERVAL_DAYS: i64 = 6;
+
+/// Per-user email and push notification settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NotificationPreferences {
+    pub user_id: i32,
+    /// Send the weekly digest of URLs about to expire
+    pub digest_enabled: bool,
+    /// Day the digest is sent on, 0 = Sunday
+    pub digest_day_of_week: u8,
+    pub last_digest_sent_at: Option<DateTime<Utc>>,
+    /// Send push notifications to the user's registered devices
+    pub push_enabled: bool,
+}
+
+impl NotificationPreferences {
+    /// Preferences of a user who never changed them: a digest every Monday and pushes
+    pub fn new(user_id: i32) -> Self {
+        Self {
+            user_id,
+            digest_enabled: true,
+            digest_day_of_week: 1,
+            last_digest_sent_at: None,
+            push_enabled: true,
+        }
+    }
+
+    /// Whether `now` falls on the user's digest day
+    pub fn is_digest_day(&self, now: DateTime<Utc>) -> bool {
+        now.weekday().num_days_from_sunday() == u32::from(self.digest_day_of_week)
+    }
+
+    /// Whether a digest was already sent within the minimum interval before `now`
+    pub fn digest_sent_recently(&self, now: DateTime<Utc>) -> bool {
+        self.last_digest_sent_at
+            .is_some_and(|sent_at| sent_at > now - Duration::days(DIGEST_MIN_INTERVAL_DAYS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_digest_day_and_interval() {
+        // 2024-01-07 is a Sunday
+        let sunday = Utc.with_ymd_and_hms(2024, 1, 7, 9, 0, 0).unwrap();
+        let mut preferences = NotificationPreferences::new(1);
+        assert!(!preferences.is_digest_day(sunday));
+        assert!(preferences.is_digest_day(sunday + Duration::days(1)));
+
+        preferences.digest_day_of_week = 0;
+        assert!(preferences.is_digest_day(sunday));
+
+        assert!(!preferences.digest_sent_recently(sunday));
+        preferences.last_digest_sent_at = Some(sunday - Duration::days(1));
+        assert!(preferences.digest_sent_recently(sunday));
+        preferences.last_digest_sent_at = Some(sunday - Duration::days(7));
+        assert!(!preferences.digest_sent_recently(sunday));
+    }
+}
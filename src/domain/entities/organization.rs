@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Role of a user within an organization
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OrgRole {
+    Owner,
+    Admin,
+    Member,
+}
+
+impl OrgRole {
+    /// Storage representation of the role
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrgRole::Owner => "owner",
+            OrgRole::Admin => "admin",
+            OrgRole::Member => "member",
+        }
+    }
+
+    /// Parse a stored or user-supplied role (case-insensitive)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "owner" => Some(OrgRole::Owner),
+            "admin" => Some(OrgRole::Admin),
+            "member" => Some(OrgRole::Member),
+            _ => None,
+        }
+    }
+
+    /// Whether this role can update the organization and manage its members
+    pub fn can_manage(&self) -> bool {
+        matches!(self, OrgRole::Owner | OrgRole::Admin)
+    }
+}
+
+impl fmt::Display for OrgRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Domain entity representing an organization (team workspace)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Organization {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub owner_id: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Organization {
+    /// Derive a URL-friendly slug from an organization name
+    ///
+    /// Lowercases ASCII letters and digits and joins everything else with single dashes.
+    pub fn slugify(name: &str) -> String {
+        let mut slug = String::with_capacity(name.len());
+        for c in name.trim().chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c.to_ascii_lowercase());
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        slug.trim_end_matches('-').to_string()
+    }
+
+    /// Check if a slug is well-formed (3-50 chars, lowercase letters, digits and inner dashes)
+    pub fn is_valid_slug(slug: &str) -> bool {
+        (3..=50).contains(&slug.len())
+            && slug
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            && !slug.starts_with('-')
+            && !slug.ends_with('-')
+    }
+}
+
+/// Membership of a user in an organization
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrganizationMember {
+    pub org_id: i32,
+    pub user_id: i32,
+    pub role: OrgRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// An organization together with its number of members
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrganizationWithMemberCount {
+    #[serde(flatten)]
+    pub organization: Organization,
+    pub member_count: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_org_role_round_trip() {
+        for role in [OrgRole::Owner, OrgRole::Admin, OrgRole::Member] {
+            assert_eq!(OrgRole::parse(role.as_str()), Some(role));
+        }
+        assert_eq!(OrgRole::parse("ADMIN"), Some(OrgRole::Admin));
+        assert_eq!(OrgRole::parse("guest"), None);
+        assert!(OrgRole::Admin.can_manage());
+        assert!(!OrgRole::Member.can_manage());
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(Organization::slugify("Acme Corp"), "acme-corp");
+        assert_eq!(Organization::slugify("  R&D -- Team!  "), "r-d-team");
+        assert!(Organization::is_valid_slug("acme-corp"));
+        assert!(!Organization::is_valid_slug("-acme"));
+        assert!(!Organization::is_valid_slug("Acme"));
+        assert!(!Organization::is_valid_slug("ab"));
+    }
+}
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Sending attempts before an outbox email is given up on
+pub const MAX_OUTBOX_ATTEMPTS: i32 = 5;
+
+/// Wait before the first retry; doubled after every further failure
+const FIRST_RETRY_DELAY_SECONDS: i64 = 30;
+
+/// Domain entity representing an email waiting in the outbox
+///
+/// Emails are stored before they are sent, so one queued together with a business change is
+/// delivered at least once even if the process stops before reaching the mail server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OutboxEmail {
+    pub id: i64,
+    pub recipient: String,
+    pub subject: String,
+    pub body_html: Option<String>,
+    pub body_text: String,
+    pub created_at: DateTime<Utc>,
+    /// Earliest time the next sending attempt may happen
+    pub next_attempt_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    /// Set once every attempt failed; the email is not retried afterwards
+    pub failed_at: Option<DateTime<Utc>>,
+    pub attempt_count: i32,
+    /// Error of the last failed attempt
+    pub error: Option<String>,
+}
+
+impl OutboxEmail {
+    /// Whether the email still waits to be sent
+    pub fn is_pending(&self) -> bool {
+        self.sent_at.is_none() && self.failed_at.is_none()
+    }
+
+    /// When to retry after the email failed `attempt_count` times, or `None` once it is given up
+    pub fn retry_at(attempt_count: i32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        (attempt_count < MAX_OUTBOX_ATTEMPTS).then(|| {
+            let doublings = (attempt_count - 1).clamp(0, MAX_OUTBOX_ATTEMPTS) as u32;
+            now + Duration::seconds(FIRST_RETRY_DELAY_SECONDS * 2i64.pow(doublings))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_backs_off_exponentially() {
+        let now = Utc::now();
+        let delays: Vec<_> = (1..MAX_OUTBOX_ATTEMPTS)
+            .map(|attempts| (OutboxEmail::retry_at(attempts, now).unwrap() - now).num_seconds())
+            .collect();
+        assert_eq!(delays, [30, 60, 120, 240]);
+        assert_eq!(OutboxEmail::retry_at(MAX_OUTBOX_ATTEMPTS, now), None);
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Domain entity representing a push notification waiting in the outbox
+///
+/// Like [`OutboxEmail`](super::OutboxEmail)s, pushes are stored before they are sent and
+/// retried on the same backoff, so every delivery attempt can be traced.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OutboxPush {
+    pub id: i64,
+    pub user_id: i32,
+    pub device_token: String,
+    pub title: String,
+    pub body: String,
+    pub deep_link: String,
+    pub dedup_key: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Earliest time the next sending attempt may happen
+    pub next_attempt_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    /// Set once every attempt failed or the device was unregistered
+    pub failed_at: Option<DateTime<Utc>>,
+    pub attempt_count: i32,
+    /// Error of the last failed attempt
+    pub error: Option<String>,
+}
+
+impl OutboxPush {
+    /// Whether the push still waits to be sent
+    pub fn is_pending(&self) -> bool {
+        self.sent_at.is_none() && self.failed_at.is_none()
+    }
+}
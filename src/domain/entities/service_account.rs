@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Machine account another service uses to call service-to-service endpoints
+///
+/// Authenticates with an API key; only the key's SHA-256 hash is stored.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ServiceAccount {
+    pub id: i32,
+    pub name: String,
+    #[serde(skip_serializing)]
+    pub api_key_hash: String,
+    /// Administrator who created the account
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Domain entity representing a logged-in device
+///
+/// Every issued token carries the id of its session, so revoking the session ends that
+/// login without touching the user's other devices.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: i32,
+    /// Readable description of the client, e.g. "Chrome on macOS"
+    pub device_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    pub revoked: bool,
+}
+
+/// Client a token is being issued to, as seen by the login request
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionClient {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl Session {
+    /// Start a new session for a user on the given client
+    pub fn new(user_id: i32, client: &SessionClient) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            device_name: client.user_agent.as_deref().and_then(device_name),
+            ip_address: client.ip_address.clone(),
+            user_agent: client.user_agent.clone(),
+            created_at: now,
+            last_used_at: now,
+            revoked: false,
+        }
+    }
+}
+
+/// Describe a `User-Agent` as "<browser> on <operating system>"
+///
+/// Returns whichever half is recognized when only one is, and `None` when neither is.
+pub fn device_name(user_agent: &str) -> Option<String> {
+    let user_agent = user_agent.to_lowercase();
+    let has = |marker: &str| user_agent.contains(marker);
+
+    // Order matters: Edge and Opera also claim to be Chrome, and Chrome claims to be Safari
+    let browser = if has("edg/") || has("edge/") {
+        Some("Edge")
+    } else if has("opr/") || has("opera") {
+        Some("Opera")
+    } else if has("firefox/") || has("fxios/") {
+        Some("Firefox")
+    } else if has("chrome/") || has("crios/") || has("chromium/") {
+        Some("Chrome")
+    } else if has("safari/") {
+        Some("Safari")
+    } else {
+        None
+    };
+
+    // iOS user agents mention "like Mac OS X", so check them before macOS
+    let os = if has("iphone") || has("ipad") {
+        Some("iOS")
+    } else if has("android") {
+        Some("Android")
+    } else if has("windows") {
+        Some("Windows")
+    } else if has("mac os x") || has("macintosh") {
+        Some("macOS")
+    } else if has("cros") {
+        Some("ChromeOS")
+    } else if has("linux") {
+        Some("Linux")
+    } else {
+        None
+    };
+
+    match (browser, os) {
+        (Some(browser), Some(os)) => Some(format!("{} on {}", browser, os)),
+        (Some(name), None) | (None, Some(name)) => Some(name.to_string()),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_name_from_user_agent() {
+        let cases = [
+            (
+                "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+                Some("Chrome on macOS"),
+            ),
+            (
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0",
+                Some("Edge on Windows"),
+            ),
+            (
+                "Mozilla/5.0 (iPhone; CPU iPhone OS 17_1 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1 Mobile/15E148 Safari/604.1",
+                Some("Safari on iOS"),
+            ),
+            (
+                "Mozilla/5.0 (X11; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
+                Some("Firefox on Linux"),
+            ),
+            (
+                "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36",
+                Some("Chrome on Android"),
+            ),
+            ("curl/8.4.0", None),
+        ];
+
+        for (user_agent, expected) in cases {
+            assert_eq!(
+                device_name(user_agent).as_deref(),
+                expected,
+                "{}",
+                user_agent
+            );
+        }
+    }
+
+    #[test]
+    fn test_new_session_describes_client() {
+        let client = SessionClient {
+            ip_address: Some("203.0.113.7".to_string()),
+            user_agent: Some("Mozilla/5.0 (X11; Linux x86_64; rv:121.0) Firefox/121.0".to_string()),
+        };
+        let session = Session::new(7, &client);
+
+        assert_eq!(session.user_id, 7);
+        assert_eq!(session.device_name.as_deref(), Some("Firefox on Linux"));
+        assert_eq!(session.ip_address.as_deref(), Some("203.0.113.7"));
+        assert!(!session.revoked);
+        assert_eq!(session.created_at, session.last_used_at);
+    }
+}
@@ -1,5 +1,25 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use uuid::Uuid;
+
+/// Shortest short code accepted when no validator is configured
+pub const DEFAULT_MIN_SHORT_CODE_LENGTH: usize = 4;
+
+/// Longest short code accepted when no validator is configured; matches the column size
+pub const DEFAULT_MAX_SHORT_CODE_LENGTH: usize = 50;
+
+/// Characters generated short codes are made of by default
+const ALPHANUMERIC_ALPHABET: &str =
+    "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Bitcoin's Base58: alphanumerics without the look-alikes `0`, `O`, `I` and `l`
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The common Base62 ordering, lowercase before uppercase
+const BASE62_ALPHABET: &str = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Characters allowed in short codes when no validator is configured
+const DEFAULT_ALPHABET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz-_";
 
 /// Domain entity representing a short code
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -8,25 +28,37 @@ pub struct ShortCode {
 }
 
 impl ShortCode {
-    /// Create a new short code with validation
+    /// Create a new short code, validated against the default [`ShortCodeValidator`]
     pub fn new(value: String) -> Result<Self, ShortCodeError> {
-        if value.is_empty() {
-            return Err(ShortCodeError::Empty);
-        }
+        ShortCodeValidator::default().validate(value)
+    }
 
-        if value.len() > 50 {
-            return Err(ShortCodeError::TooLong);
-        }
+    /// Generate an alphanumeric short code of exactly `length` characters from a hash
+    #[must_use]
+    pub fn generate(hash: u64, length: usize) -> Self {
+        Self::generate_with_config(hash, length, &ShortCodeAlphabet::default())
+    }
 
-        // Check for invalid characters
-        if !value
-            .chars()
-            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
-        {
-            return Err(ShortCodeError::InvalidCharacters);
-        }
+    /// Generate a short code of exactly `length` characters of `alphabet` from a hash
+    ///
+    /// Once the hash runs out of digits the code is padded with the alphabet's first character.
+    #[must_use]
+    pub fn generate_with_config(
+        mut hash: u64,
+        length: usize,
+        alphabet: &ShortCodeAlphabet,
+    ) -> Self {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        let base = alphabet.len() as u64;
+        let value = (0..length)
+            .map(|_| {
+                let c = alphabet[(hash % base) as usize];
+                hash /= base;
+                c
+            })
+            .collect();
 
-        Ok(ShortCode { value })
+        ShortCode { value }
     }
 
     /// Create a short code from a string without validation (for internal use)
@@ -34,6 +66,59 @@ impl ShortCode {
         ShortCode { value }
     }
 
+    /// Derive a short code deterministically from a UUID
+    ///
+    /// Encodes the UUID modulo `alphabet.len()^length` in base `alphabet.len()`, i.e. its
+    /// lowest `length * log2(alphabet.len())` bits. The same UUID always yields the same code,
+    /// so no collision check is needed as long as the key space fits: callers are responsible
+    /// for choosing an alphabet and length that give enough unique codes for their IDs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the alphabet has fewer than two characters.
+    #[must_use]
+    pub fn from_uuid(id: Uuid, alphabet: &str, length: usize) -> Self {
+        Self::encode(id.as_u128(), alphabet, length)
+    }
+
+    /// Derive a short code deterministically from a numeric ID
+    ///
+    /// See [`ShortCode::from_uuid`] for the encoding and its caveats.
+    #[must_use]
+    pub fn from_u64(id: u64, alphabet: &str, length: usize) -> Self {
+        Self::encode(u128::from(id), alphabet, length)
+    }
+
+    /// Base-encode the lowest digits of `value`, most significant digit first
+    fn encode(mut value: u128, alphabet: &str, length: usize) -> Self {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        assert!(
+            alphabet.len() >= 2,
+            "short code alphabet needs at least two characters"
+        );
+
+        let base = alphabet.len() as u128;
+        let mut digits = vec![alphabet[0]; length];
+        for digit in digits.iter_mut().rev() {
+            *digit = alphabet[(value % base) as usize];
+            value /= base;
+        }
+
+        ShortCode {
+            value: digits.into_iter().collect(),
+        }
+    }
+
+    /// Whether the code's letters and digits all belong to `alphabet`
+    ///
+    /// `-` and `_` are left to [`ShortCodeValidator`], so readable custom codes stay possible
+    /// with any generation alphabet.
+    pub fn is_valid_for_alphabet(&self, alphabet: &ShortCodeAlphabet) -> bool {
+        self.value
+            .chars()
+            .all(|c| !c.is_ascii_alphanumeric() || alphabet.contains(c))
+    }
+
     /// Get the string value
     pub fn value(&self) -> &str {
         &self.value
@@ -49,6 +134,105 @@ impl ShortCode {
     pub fn is_generated(&self) -> bool {
         !self.is_custom()
     }
+
+    /// How differently two short codes sound when read aloud
+    ///
+    /// The edit distance between the codes' phonetic keys divided by the longer key's length:
+    /// 0.0 for homophones such as `cl1ck` and `click`, 1.0 for codes sharing no sound.
+    pub fn phonetic_distance(&self, other: &ShortCode) -> f32 {
+        let key = phonetic_key(&self.value);
+        let other_key = phonetic_key(&other.value);
+        let longest = key.len().max(other_key.len());
+        if longest == 0 {
+            return 0.0;
+        }
+        edit_distance(&key, &other_key) as f32 / longest as f32
+    }
+}
+
+/// Letters digits are commonly read as when they stand in for them, as in `cl1ck`
+fn digit_as_letter(c: char) -> char {
+    match c {
+        '0' => 'o',
+        '1' => 'i',
+        '3' => 'e',
+        '4' => 'a',
+        '5' => 's',
+        '6' | '9' => 'g',
+        '7' => 't',
+        '8' => 'b',
+        other => other,
+    }
+}
+
+/// Metaphone-style key of how a short code sounds
+///
+/// Digits are read as the letters they resemble, then the usual Metaphone rules apply: vowels
+/// only count at the start, silent letters are dropped and letters sounding alike share a code
+/// (`ck`, `c`, `q` and hard `g` are all `K`; `ph` and `v` are `F`; `th` is `0`).
+fn phonetic_key(code: &str) -> Vec<char> {
+    let mut letters: Vec<char> = code
+        .chars()
+        .map(|c| digit_as_letter(c.to_ascii_lowercase()))
+        .filter(char::is_ascii_lowercase)
+        .collect();
+    letters.dedup_by(|next, previous| next == previous && *next != 'c');
+    if let [first, second, ..] = letters[..] {
+        if matches!(
+            (first, second),
+            ('k', 'n') | ('g', 'n') | ('p', 'n') | ('w', 'r')
+        ) {
+            letters.remove(0);
+        }
+    }
+
+    let is_vowel = |c: Option<&char>| matches!(c, Some('a' | 'e' | 'i' | 'o' | 'u'));
+    let is_soft = |c: Option<&char>| matches!(c, Some('e' | 'i' | 'y'));
+    let mut key = Vec::with_capacity(letters.len());
+    for (i, &c) in letters.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|p| letters[p]);
+        let next = letters.get(i + 1);
+        let code = match c {
+            'a' | 'e' | 'i' | 'o' | 'u' => (i == 0).then_some('A'),
+            'b' if previous == Some('m') && next.is_none() => None,
+            'c' if next == Some(&'h') => Some('X'),
+            'c' if is_soft(next) => Some('S'),
+            'd' if next == Some(&'g') && is_soft(letters.get(i + 2)) => Some('J'),
+            'g' if is_soft(next) => Some('J'),
+            'h' if matches!(previous, Some('c' | 's' | 'p' | 't' | 'g')) || !is_vowel(next) => None,
+            'k' if previous == Some('c') => None,
+            'p' if next == Some(&'h') => Some('F'),
+            's' if next == Some(&'h') => Some('X'),
+            't' if next == Some(&'h') => Some('0'),
+            'w' | 'y' if i > 0 || !is_vowel(next) => None,
+            'x' => {
+                key.push('K');
+                Some('S')
+            }
+            'c' | 'q' | 'g' => Some('K'),
+            'd' => Some('T'),
+            'v' => Some('F'),
+            'z' => Some('S'),
+            other => Some(other.to_ascii_uppercase()),
+        };
+        key.extend(code);
+    }
+    key
+}
+
+/// Levenshtein distance between two sequences
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, &a_char) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
 }
 
 impl fmt::Display for ShortCode {
@@ -63,11 +247,161 @@ impl From<ShortCode> for String {
     }
 }
 
+/// Characters generated short codes are made of
+///
+/// Configured as `alphanumeric`, `base58`, `base62` or `custom:<characters>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ShortCodeAlphabet {
+    /// Digits, then uppercase, then lowercase letters
+    #[default]
+    AlphaNumeric,
+    /// Bitcoin's Base58, without the easily confused `0`, `O`, `I` and `l`
+    Base58,
+    /// Digits, then lowercase, then uppercase letters
+    Base62,
+    /// Any set of at least two distinct characters
+    Custom(String),
+}
+
+impl ShortCodeAlphabet {
+    /// The alphabet's characters, in encoding order
+    pub fn chars(&self) -> std::str::Chars<'_> {
+        match self {
+            ShortCodeAlphabet::AlphaNumeric => ALPHANUMERIC_ALPHABET.chars(),
+            ShortCodeAlphabet::Base58 => BASE58_ALPHABET.chars(),
+            ShortCodeAlphabet::Base62 => BASE62_ALPHABET.chars(),
+            ShortCodeAlphabet::Custom(chars) => chars.chars(),
+        }
+    }
+
+    /// Whether `c` belongs to the alphabet
+    pub fn contains(&self, c: char) -> bool {
+        self.chars().any(|candidate| candidate == c)
+    }
+}
+
+impl fmt::Display for ShortCodeAlphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShortCodeAlphabet::AlphaNumeric => write!(f, "alphanumeric"),
+            ShortCodeAlphabet::Base58 => write!(f, "base58"),
+            ShortCodeAlphabet::Base62 => write!(f, "base62"),
+            ShortCodeAlphabet::Custom(chars) => write!(f, "custom:{}", chars),
+        }
+    }
+}
+
+impl std::str::FromStr for ShortCodeAlphabet {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "alphanumeric" => Ok(ShortCodeAlphabet::AlphaNumeric),
+            "base58" => Ok(ShortCodeAlphabet::Base58),
+            "base62" => Ok(ShortCodeAlphabet::Base62),
+            _ => match value.strip_prefix("custom:") {
+                Some(chars) => {
+                    let mut unique: Vec<char> = chars.chars().collect();
+                    unique.sort_unstable();
+                    unique.dedup();
+                    if unique.len() < 2 || unique.len() != chars.chars().count() {
+                        return Err(
+                            "a custom alphabet needs at least two distinct characters".to_string()
+                        );
+                    }
+                    Ok(ShortCodeAlphabet::Custom(chars.to_string()))
+                }
+                None => Err(format!(
+                    "unknown short code alphabet '{}', expected alphanumeric, base58, base62 or custom:<characters>",
+                    value
+                )),
+            },
+        }
+    }
+}
+
+impl TryFrom<String> for ShortCodeAlphabet {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<ShortCodeAlphabet> for String {
+    fn from(alphabet: ShortCodeAlphabet) -> Self {
+        alphabet.to_string()
+    }
+}
+
+/// Length and character rules for user-supplied short codes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortCodeValidator {
+    pub min: usize,
+    pub max: usize,
+    pub alphabet: Vec<char>,
+}
+
+impl Default for ShortCodeValidator {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_MIN_SHORT_CODE_LENGTH,
+            DEFAULT_MAX_SHORT_CODE_LENGTH,
+            DEFAULT_ALPHABET,
+        )
+    }
+}
+
+impl ShortCodeValidator {
+    pub fn new(min: usize, max: usize, alphabet: &str) -> Self {
+        Self {
+            min,
+            max,
+            alphabet: alphabet.chars().collect(),
+        }
+    }
+
+    /// Check `value` against the rules and wrap it in a short code
+    pub fn validate(&self, value: String) -> Result<ShortCode, ShortCodeError> {
+        if value.is_empty() {
+            return Err(ShortCodeError::Empty);
+        }
+
+        let length = value.chars().count();
+        if length < self.min {
+            return Err(ShortCodeError::TooShort {
+                min_length: self.min,
+                actual_length: length,
+            });
+        }
+        if length > self.max {
+            return Err(ShortCodeError::TooLong {
+                max_length: self.max,
+                actual_length: length,
+            });
+        }
+
+        if !value.chars().all(|c| self.alphabet.contains(&c)) {
+            return Err(ShortCodeError::InvalidCharacters);
+        }
+
+        Ok(ShortCode { value })
+    }
+}
+
 /// Errors that can occur when creating a short code
 #[derive(Debug, Clone, PartialEq)]
 pub enum ShortCodeError {
     Empty,
-    TooLong,
+    TooShort {
+        min_length: usize,
+        actual_length: usize,
+    },
+    TooLong {
+        max_length: usize,
+        actual_length: usize,
+    },
     InvalidCharacters,
 }
 
@@ -75,15 +409,21 @@ impl fmt::Display for ShortCodeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ShortCodeError::Empty => write!(f, "Short code cannot be empty"),
-            ShortCodeError::TooLong => write!(f, "Short code is too long (max 50 characters)"),
-            ShortCodeError::InvalidCharacters => write!(
-                f,
-                "Short code contains invalid characters (only alphanumeric, -, _ allowed)"
-            ),
+            ShortCodeError::TooShort { min_length, .. } => {
+                write!(f, "Short code is too short (min {} characters)", min_length)
+            }
+            ShortCodeError::TooLong { max_length, .. } => {
+                write!(f, "Short code is too long (max {} characters)", max_length)
+            }
+            ShortCodeError::InvalidCharacters => {
+                write!(f, "Short code contains characters that are not allowed")
+            }
         }
     }
 }
 
+impl std::error::Error for ShortCodeError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,7 +452,131 @@ mod tests {
     fn test_short_code_creation_too_long() {
         let long_code = "a".repeat(51);
         let result = ShortCode::new(long_code);
-        assert_eq!(result, Err(ShortCodeError::TooLong));
+        assert_eq!(
+            result,
+            Err(ShortCodeError::TooLong {
+                max_length: 50,
+                actual_length: 51
+            })
+        );
+    }
+
+    #[test]
+    fn test_short_code_creation_too_short() {
+        let result = ShortCode::new("abc".to_string());
+        assert_eq!(
+            result,
+            Err(ShortCodeError::TooShort {
+                min_length: 4,
+                actual_length: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_validator_length_boundaries() {
+        let validator = ShortCodeValidator::new(5, 8, DEFAULT_ALPHABET);
+
+        assert_eq!(
+            validator.validate("a".repeat(4)),
+            Err(ShortCodeError::TooShort {
+                min_length: 5,
+                actual_length: 4
+            })
+        );
+        assert!(validator.validate("a".repeat(5)).is_ok());
+        assert!(validator.validate("a".repeat(8)).is_ok());
+        assert_eq!(
+            validator.validate("a".repeat(9)),
+            Err(ShortCodeError::TooLong {
+                max_length: 8,
+                actual_length: 9
+            })
+        );
+    }
+
+    #[test]
+    fn test_validator_rejects_characters_outside_alphabet() {
+        let validator = ShortCodeValidator::new(4, 50, "abcdef");
+        assert!(validator.validate("face".to_string()).is_ok());
+        assert_eq!(
+            validator.validate("fade-".to_string()),
+            Err(ShortCodeError::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    fn test_generate_uses_requested_length() {
+        for length in [4, 6, 12, 50] {
+            let code = ShortCode::generate(u64::MAX, length);
+            assert_eq!(code.value().len(), length);
+            assert!(ShortCode::new(code.value().to_string()).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_base58_codes_have_no_ambiguous_characters() {
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        for _ in 0..10_000 {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let code = ShortCode::generate_with_config(state, 8, &ShortCodeAlphabet::Base58);
+            assert_eq!(code.value().len(), 8);
+            assert!(
+                !code.value().contains(['0', 'O', 'I', 'l']),
+                "ambiguous character in {}",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn test_phonetic_distance_flags_homophones() {
+        let code = |value: &str| ShortCode::from_string_unchecked(value.to_string());
+        for (a, b) in [("cl1ck", "click"), ("0ne", "one"), ("t00", "two")] {
+            assert_eq!(code(a).phonetic_distance(&code(b)), 0.0, "{} / {}", a, b);
+        }
+
+        assert_eq!(code("abc123").phonetic_distance(&code("abc123")), 0.0);
+        assert!(code("click").phonetic_distance(&code("slack")) > 0.0);
+        assert_eq!(code("bd7Fm").phonetic_distance(&code("xQrLn")), 1.0);
+    }
+
+    #[test]
+    fn test_is_valid_for_alphabet() {
+        let base58 = ShortCodeAlphabet::Base58;
+        assert!(ShortCode::new("abc123".to_string())
+            .unwrap()
+            .is_valid_for_alphabet(&base58));
+        assert!(ShortCode::new("my-code_2".to_string())
+            .unwrap()
+            .is_valid_for_alphabet(&base58));
+        assert!(!ShortCode::new("c0de".to_string())
+            .unwrap()
+            .is_valid_for_alphabet(&base58));
+        assert!(ShortCode::new("c0de".to_string())
+            .unwrap()
+            .is_valid_for_alphabet(&ShortCodeAlphabet::Base62));
+    }
+
+    #[test]
+    fn test_alphabet_parsing() {
+        assert_eq!("base58".parse(), Ok(ShortCodeAlphabet::Base58));
+        assert_eq!(
+            "custom:abc".parse(),
+            Ok(ShortCodeAlphabet::Custom("abc".to_string()))
+        );
+        assert!("custom:a".parse::<ShortCodeAlphabet>().is_err());
+        assert!("custom:aab".parse::<ShortCodeAlphabet>().is_err());
+        assert!("base64".parse::<ShortCodeAlphabet>().is_err());
+        for alphabet in [
+            ShortCodeAlphabet::AlphaNumeric,
+            ShortCodeAlphabet::Base62,
+            ShortCodeAlphabet::Custom("xyz".to_string()),
+        ] {
+            assert_eq!(alphabet.to_string().parse(), Ok(alphabet));
+        }
     }
 
     #[test]
@@ -121,6 +585,43 @@ mod tests {
         assert_eq!(result, Err(ShortCodeError::InvalidCharacters));
     }
 
+    const ALPHABET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+    #[test]
+    fn test_from_uuid_is_deterministic() {
+        let id = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        let first = ShortCode::from_uuid(id, ALPHABET, 6);
+        let second = ShortCode::from_uuid(id, ALPHABET, 6);
+
+        assert_eq!(first, second);
+        assert_eq!(first.value().len(), 6);
+        assert!(first.value().chars().all(|c| ALPHABET.contains(c)));
+    }
+
+    #[test]
+    fn test_from_uuid_no_collisions() {
+        // Fixed, well-spread set of UUIDs so the test is reproducible
+        let codes: std::collections::HashSet<_> = (1..=10_000u128)
+            .map(|i| Uuid::from_u128(i.wrapping_mul(0x9E37_79B9_7F4A_7C15_F39C_C060_5CED_C835)))
+            .map(|id| ShortCode::from_uuid(id, ALPHABET, 6))
+            .collect();
+
+        assert_eq!(codes.len(), 10_000);
+        assert!(codes
+            .iter()
+            .all(|code| code.value().chars().all(|c| ALPHABET.contains(c))));
+    }
+
+    #[test]
+    fn test_from_u64() {
+        assert_eq!(ShortCode::from_u64(0, ALPHABET, 4).value(), "0000");
+        assert_eq!(ShortCode::from_u64(61, ALPHABET, 4).value(), "000z");
+        assert_eq!(ShortCode::from_u64(62, ALPHABET, 4).value(), "0010");
+        assert_eq!(ShortCode::from_u64(255, "01", 8).value(), "11111111");
+        // Only the lowest digits are kept
+        assert_eq!(ShortCode::from_u64(256, "01", 8).value(), "00000000");
+    }
+
     #[test]
     fn test_short_code_display() {
         let short_code = ShortCode::new("abc123".to_string()).unwrap();
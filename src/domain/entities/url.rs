@@ -1,8 +1,9 @@
 use chrono::{DateTime, Utc};
+use percent_encoding::{percent_decode_str, percent_encode, AsciiSet, CONTROLS};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-/// Status of a URL - active or inactive (soft deleted)
+/// Status of a URL - active, inactive (soft deleted) or archived after expiring
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub enum UrlStatus {
     /// URL is active and can be accessed
@@ -10,6 +11,10 @@ pub enum UrlStatus {
     Active,
     /// URL is inactive (soft deleted) and should not redirect
     Inactive,
+    /// URL expired and was archived by the cleanup; it keeps its analytics but does not redirect
+    Archived,
+    /// URL was deleted by its owner; the row stays until the cleanup removes it for good
+    Deleted,
 }
 
 impl UrlStatus {
@@ -17,15 +22,131 @@ impl UrlStatus {
     pub fn is_active(&self) -> bool {
         matches!(self, UrlStatus::Active)
     }
+
+    /// Whether a listing filtered by `filter` shows URLs with this status
+    ///
+    /// Without a filter every URL except archived ones is listed. Deleted URLs never are.
+    pub fn is_listed_under(&self, filter: Option<UrlStatus>) -> bool {
+        match filter {
+            _ if matches!(self, UrlStatus::Deleted) => false,
+            Some(filter) => *self == filter,
+            None => !matches!(self, UrlStatus::Archived),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UrlStatus::Active => "active",
+            UrlStatus::Inactive => "inactive",
+            UrlStatus::Archived => "archived",
+            UrlStatus::Deleted => "deleted",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "active" => Some(UrlStatus::Active),
+            "inactive" => Some(UrlStatus::Inactive),
+            "archived" => Some(UrlStatus::Archived),
+            "deleted" => Some(UrlStatus::Deleted),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for UrlStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// When visitors see an interstitial page naming the destination before being redirected
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PreviewMode {
+    /// Redirect straight away
+    #[default]
+    None,
+    /// Always show the interstitial page
+    Always,
+    /// Show the interstitial page only while the link is unverified
+    HighRisk,
+}
+
+impl PreviewMode {
+    pub fn as_str(&self) -> &'static str {
         match self {
-            UrlStatus::Active => write!(f, "active"),
-            UrlStatus::Inactive => write!(f, "inactive"),
+            PreviewMode::None => "none",
+            PreviewMode::Always => "always",
+            PreviewMode::HighRisk => "high_risk",
         }
     }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "none" => Some(PreviewMode::None),
+            "always" => Some(PreviewMode::Always),
+            "high_risk" => Some(PreviewMode::HighRisk),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PreviewMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// HTTP redirect a URL answers visitors with
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RedirectType {
+    /// 308; browsers and crawlers may remember the destination
+    #[default]
+    Permanent,
+    /// 307; every visit comes back to the short link, e.g. for destinations that change
+    Temporary,
+}
+
+impl RedirectType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RedirectType::Permanent => "permanent",
+            RedirectType::Temporary => "temporary",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "permanent" => Some(RedirectType::Permanent),
+            "temporary" => Some(RedirectType::Temporary),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RedirectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Whether a URL redirects, and why not when it does not
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessibilityStatus {
+    /// Active, not expired and under its click limit
+    Accessible,
+    /// Deactivated by its owner; it may be reactivated
+    Inactive,
+    /// Past its expiration date, including URLs archived after expiring
+    Expired,
+    /// Clicked as many times as its owner allowed
+    ClickLimitReached,
+    /// Accessible to visitors who give its password
+    PasswordRequired,
+    /// Deleted by its owner
+    Deleted,
 }
 
 /// Domain entity representing a URL record
@@ -38,6 +159,39 @@ pub struct Url {
     pub expiration_date: Option<DateTime<Utc>>,
     pub user_id: Option<i32>, // For future user association
     pub status: UrlStatus,    // URL status (active/inactive)
+    /// Organization owning the URL; its members can all access it
+    #[serde(default)]
+    pub organization_id: Option<i32>,
+    /// Optimistic locking version, incremented on every update
+    #[serde(default = "Url::initial_version")]
+    pub version: i64,
+    /// Time of the last change, maintained by a database trigger
+    #[serde(default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+    /// Whether visitors see an interstitial page before the redirect
+    #[serde(default)]
+    pub preview_mode: PreviewMode,
+    /// Every click seen by the click tracker, including repeats it did not record
+    #[serde(default)]
+    pub deduplicated_click_count: i64,
+    /// When the URL was deleted; deleted URLs are hidden until the cleanup removes them
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Names of the URL's tags, sorted; only loaded by `UrlRepository::find_by_user_id` and
+    /// `UrlRepository::find_paginated`
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Label chosen by the owner
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub redirect_type: RedirectType,
+    /// Bcrypt hash of the password visitors must give to be redirected; never serialized
+    #[serde(default, skip_serializing)]
+    pub password_hash: Option<String>,
+    /// Clicks after which the URL stops redirecting, counting repeat clicks too
+    #[serde(default)]
+    pub max_clicks: Option<i64>,
 }
 
 #[allow(dead_code)]
@@ -60,9 +214,31 @@ impl Url {
             expiration_date,
             user_id,
             status,
+            organization_id: None,
+            version: Self::initial_version(),
+            updated_at: created_at,
+            preview_mode: PreviewMode::None,
+            deduplicated_click_count: 0,
+            deleted_at: None,
+            tags: Vec::new(),
+            title: None,
+            redirect_type: RedirectType::default(),
+            password_hash: None,
+            max_clicks: None,
         }
     }
 
+    /// Version of a newly created URL
+    pub fn initial_version() -> i64 {
+        1
+    }
+
+    /// Assign the URL to an organization
+    pub fn with_organization(mut self, organization_id: Option<i32>) -> Self {
+        self.organization_id = organization_id;
+        self
+    }
+
     /// Create a new URL with current timestamp
     pub fn new_with_timestamp(
         id: i32,
@@ -113,9 +289,53 @@ impl Url {
         }
     }
 
-    /// Check if the URL is accessible (active and not expired)
+    /// Check if the URL is accessible (active, not expired and under its click limit)
+    ///
+    /// Password protected URLs are accessible; visitors are asked for the password.
     pub fn is_accessible(&self) -> bool {
-        self.status.is_active() && !self.is_expired()
+        matches!(
+            self.accessibility_reason(),
+            AccessibilityStatus::Accessible | AccessibilityStatus::PasswordRequired
+        )
+    }
+
+    /// Whether the URL has been clicked as many times as its click limit allows
+    pub fn is_click_limit_reached(&self) -> bool {
+        self.max_clicks
+            .is_some_and(|max_clicks| self.deduplicated_click_count >= max_clicks)
+    }
+
+    /// Why the URL does or does not redirect
+    ///
+    /// A deactivated URL reports `Inactive` even once its expiration date has passed, and
+    /// an expired one `Expired` even when its click limit was reached too.
+    pub fn accessibility_reason(&self) -> AccessibilityStatus {
+        match self.status {
+            UrlStatus::Deleted => AccessibilityStatus::Deleted,
+            UrlStatus::Inactive => AccessibilityStatus::Inactive,
+            UrlStatus::Archived => AccessibilityStatus::Expired,
+            UrlStatus::Active if self.is_expired() => AccessibilityStatus::Expired,
+            UrlStatus::Active if self.is_click_limit_reached() => {
+                AccessibilityStatus::ClickLimitReached
+            }
+            UrlStatus::Active if self.is_password_protected() => {
+                AccessibilityStatus::PasswordRequired
+            }
+            UrlStatus::Active => AccessibilityStatus::Accessible,
+        }
+    }
+
+    /// Whether visitors must give a password to be redirected
+    pub fn is_password_protected(&self) -> bool {
+        self.password_hash.is_some()
+    }
+
+    /// Whether `password` opens a password protected URL; always true without a password
+    pub fn verify_password(&self, password: &str) -> bool {
+        match &self.password_hash {
+            Some(hash) => bcrypt::verify(password, hash).unwrap_or(false),
+            None => true,
+        }
     }
 
     /// Deactivate the URL (soft delete)
@@ -132,6 +352,128 @@ impl Url {
     pub fn is_deactivated(&self) -> bool {
         matches!(self.status, UrlStatus::Inactive)
     }
+
+    /// Check if the URL was archived after expiring
+    pub fn is_archived(&self) -> bool {
+        matches!(self.status, UrlStatus::Archived)
+    }
+
+    /// Delete the URL, keeping the record until the cleanup removes it
+    pub fn mark_deleted(&mut self, now: DateTime<Utc>) {
+        self.status = UrlStatus::Deleted;
+        self.deleted_at = Some(now);
+    }
+
+    /// Check if the URL was deleted
+    pub fn is_deleted(&self) -> bool {
+        matches!(self.status, UrlStatus::Deleted)
+    }
+
+    /// Bring an archived URL back, expiring at `expiration_date` or never
+    pub fn restore(&mut self, expiration_date: Option<DateTime<Utc>>) {
+        self.status = UrlStatus::Active;
+        self.expiration_date = expiration_date;
+    }
+
+    /// Whether the link was created by a registered user
+    ///
+    /// Anonymous links cannot be traced back to anyone and count as unverified.
+    pub fn is_verified(&self) -> bool {
+        self.user_id.is_some()
+    }
+
+    /// Normalize `raw` so that URLs pointing at the same resource are stored the same way
+    ///
+    /// The scheme and host are lowercased, default ports dropped, query parameters sorted by
+    /// name, trailing slashes removed from the path (the root keeps its slash) and percent
+    /// escapes rewritten in one consistent form. Applying it again changes nothing.
+    pub fn canonical_form(raw: &str) -> Result<String, UrlNormalizationError> {
+        let mut url = ::url::Url::parse(raw.trim())
+            .map_err(|e| UrlNormalizationError::Unparseable(e.to_string()))?;
+        if !url.has_host() || url.cannot_be_a_base() {
+            return Err(UrlNormalizationError::MissingHost);
+        }
+
+        let segments: Vec<String> = url
+            .path()
+            .trim_end_matches('/')
+            .split('/')
+            .map(|segment| {
+                let decoded: Vec<u8> = percent_decode_str(segment).collect();
+                percent_encode(&decoded, PATH_SEGMENT).to_string()
+            })
+            .collect();
+        let path = segments.join("/");
+        url.set_path(if path.is_empty() { "/" } else { &path });
+
+        let mut params: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(name, value)| (name.into_owned(), value.into_owned()))
+            .collect();
+        if params.is_empty() {
+            url.set_query(None);
+        } else {
+            // Stable, so repeated parameters keep their relative order
+            params.sort_by(|a, b| a.0.cmp(&b.0));
+            url.query_pairs_mut().clear().extend_pairs(params);
+        }
+
+        Ok(url.to_string())
+    }
+}
+
+/// Characters escaped in a canonical path segment: all but unreserved and sub-delimiters
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+/// Why a URL has no canonical form
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlNormalizationError {
+    /// Not a URL at all
+    Unparseable(String),
+    /// A URL without a host, such as `mailto:` or `data:` URLs
+    MissingHost,
+}
+
+impl fmt::Display for UrlNormalizationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlNormalizationError::Unparseable(reason) => write!(f, "URL is not valid: {}", reason),
+            UrlNormalizationError::MissingHost => write!(f, "URL must have a host"),
+        }
+    }
+}
+
+impl std::error::Error for UrlNormalizationError {}
+
+/// A URL together with the number of clicks it has received
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UrlWithClickCount {
+    #[serde(flatten)]
+    pub url: Url,
+    pub click_count: i64,
+}
+
+impl UrlWithClickCount {
+    /// Create a new URL with click count
+    pub fn new(url: Url, click_count: i64) -> Self {
+        Self { url, click_count }
+    }
 }
 
 impl fmt::Display for Url {
@@ -147,6 +489,111 @@ impl fmt::Display for Url {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_canonical_form_sorts_query_parameters() {
+        assert_eq!(
+            Url::canonical_form("https://example.com/path?b=2&a=1").unwrap(),
+            "https://example.com/path?a=1&b=2"
+        );
+        // Repeated parameters keep their order
+        assert_eq!(
+            Url::canonical_form("https://example.com/?tag=z&id=1&tag=a").unwrap(),
+            "https://example.com/?id=1&tag=z&tag=a"
+        );
+        assert_eq!(
+            Url::canonical_form("https://example.com/path?").unwrap(),
+            "https://example.com/path"
+        );
+    }
+
+    #[test]
+    fn test_canonical_form_normalizes_case() {
+        assert_eq!(
+            Url::canonical_form("HTTPS://Example.COM/Path?b=2&a=1").unwrap(),
+            "https://example.com/Path?a=1&b=2"
+        );
+        assert_eq!(
+            Url::canonical_form("https://Example.COM/path?b=2&a=1").unwrap(),
+            Url::canonical_form("https://example.com/path?a=1&b=2").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_canonical_form_removes_default_ports() {
+        assert_eq!(
+            Url::canonical_form("http://example.com:80/a").unwrap(),
+            "http://example.com/a"
+        );
+        assert_eq!(
+            Url::canonical_form("https://example.com:443/a").unwrap(),
+            "https://example.com/a"
+        );
+        assert_eq!(
+            Url::canonical_form("https://example.com:8443/a").unwrap(),
+            "https://example.com:8443/a"
+        );
+        assert_eq!(
+            Url::canonical_form("http://example.com:443/a").unwrap(),
+            "http://example.com:443/a"
+        );
+    }
+
+    #[test]
+    fn test_canonical_form_paths_and_escapes() {
+        assert_eq!(
+            Url::canonical_form("https://example.com/docs//").unwrap(),
+            "https://example.com/docs"
+        );
+        assert_eq!(
+            Url::canonical_form("https://example.com").unwrap(),
+            "https://example.com/"
+        );
+        assert_eq!(
+            Url::canonical_form("https://example.com/%7euser/a%2fb/caf%C3%A9").unwrap(),
+            "https://example.com/~user/a%2Fb/caf%C3%A9"
+        );
+        assert_eq!(
+            Url::canonical_form("https://example.com/?q=a%20b&r=x+y").unwrap(),
+            "https://example.com/?q=a+b&r=x+y"
+        );
+    }
+
+    #[test]
+    fn test_canonical_form_errors() {
+        assert!(matches!(
+            Url::canonical_form("not a url"),
+            Err(UrlNormalizationError::Unparseable(_))
+        ));
+        assert_eq!(
+            Url::canonical_form("mailto:someone@example.com"),
+            Err(UrlNormalizationError::MissingHost)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_canonical_form_is_idempotent(
+            scheme in prop::sample::select(vec!["http", "https", "HTTP", "Https"]),
+            host in "[a-zA-Z][a-zA-Z0-9-]{0,10}(\\.[a-zA-Z]{2,5}){1,2}",
+            port in prop::option::of(prop::sample::select(vec![80u16, 443, 8080])),
+            path in "(/([a-zA-Z0-9._~-]|%[0-9a-fA-F]{2}| ){0,8}){0,4}/?",
+            query in prop::collection::vec(("[a-z]{1,4}", "([a-zA-Z0-9 +]|%[0-9a-fA-F]{2}){0,6}"), 0..4),
+        ) {
+            let port = port.map(|port| format!(":{}", port)).unwrap_or_default();
+            let query = if query.is_empty() {
+                String::new()
+            } else {
+                let pairs: Vec<String> = query.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+                format!("?{}", pairs.join("&"))
+            };
+            let raw = format!("{}://{}{}{}{}", scheme, host, port, path, query);
+
+            let canonical = Url::canonical_form(&raw).unwrap();
+            prop_assert_eq!(Url::canonical_form(&canonical).unwrap(), canonical);
+        }
+    }
 
     #[test]
     fn test_url_creation() {
@@ -313,5 +760,200 @@ mod tests {
     fn test_url_status_display() {
         assert_eq!(UrlStatus::Active.to_string(), "active");
         assert_eq!(UrlStatus::Inactive.to_string(), "inactive");
+        assert_eq!(UrlStatus::Archived.to_string(), "archived");
+        assert_eq!(UrlStatus::Deleted.to_string(), "deleted");
+    }
+
+    #[test]
+    fn test_deleted_url_is_hidden_and_not_accessible() {
+        let now = Utc::now();
+        let mut url = Url::new_with_timestamp(
+            1,
+            "abc123".to_string(),
+            "https://example.com".to_string(),
+            None,
+            None,
+            UrlStatus::Active,
+        );
+        url.mark_deleted(now);
+
+        assert!(url.is_deleted());
+        assert_eq!(url.deleted_at, Some(now));
+        assert_eq!(url.accessibility_reason(), AccessibilityStatus::Deleted);
+        assert_eq!(UrlStatus::parse("deleted"), Some(UrlStatus::Deleted));
+        assert!(!UrlStatus::Deleted.is_listed_under(None));
+        assert!(!UrlStatus::Deleted.is_listed_under(Some(UrlStatus::Deleted)));
+    }
+
+    #[test]
+    fn test_archived_url_is_not_accessible() {
+        let mut url = Url::new_with_timestamp(
+            1,
+            "abc123".to_string(),
+            "https://example.com".to_string(),
+            Some(Utc::now() - chrono::Duration::days(1)),
+            None,
+            UrlStatus::Archived,
+        );
+        assert!(url.is_archived());
+        assert!(!url.is_accessible());
+        assert_eq!(UrlStatus::parse("archived"), Some(UrlStatus::Archived));
+        assert_eq!(UrlStatus::parse("removed"), None);
+
+        url.restore(None);
+        assert!(!url.is_archived());
+        assert!(url.is_accessible());
+    }
+
+    #[test]
+    fn test_accessibility_reason() {
+        let now = Utc::now();
+        let url = |expiration_date: Option<DateTime<Utc>>, status| {
+            Url::new_with_timestamp(
+                1,
+                "abc123".to_string(),
+                "https://example.com".to_string(),
+                expiration_date,
+                None,
+                status,
+            )
+        };
+        let past = Some(now - chrono::Duration::hours(1));
+        let future = Some(now + chrono::Duration::hours(1));
+
+        let cases = [
+            (None, UrlStatus::Active, AccessibilityStatus::Accessible),
+            (future, UrlStatus::Active, AccessibilityStatus::Accessible),
+            (past, UrlStatus::Active, AccessibilityStatus::Expired),
+            (None, UrlStatus::Inactive, AccessibilityStatus::Inactive),
+            (future, UrlStatus::Inactive, AccessibilityStatus::Inactive),
+            (past, UrlStatus::Inactive, AccessibilityStatus::Inactive),
+            (past, UrlStatus::Archived, AccessibilityStatus::Expired),
+            (None, UrlStatus::Archived, AccessibilityStatus::Expired),
+            (future, UrlStatus::Deleted, AccessibilityStatus::Deleted),
+            (past, UrlStatus::Deleted, AccessibilityStatus::Deleted),
+        ];
+        for (expiration_date, status, expected) in cases {
+            let url = url(expiration_date, status);
+            assert_eq!(
+                url.accessibility_reason(),
+                expected,
+                "{:?} expiring {:?}",
+                status,
+                expiration_date
+            );
+            assert_eq!(
+                url.is_accessible(),
+                expected == AccessibilityStatus::Accessible
+            );
+        }
+    }
+
+    #[test]
+    fn test_accessibility_reason_with_click_limit_and_password() {
+        let url = |status, clicks, max_clicks, password: Option<&str>| {
+            let mut url = Url::new_with_timestamp(
+                1,
+                "abc123".to_string(),
+                "https://example.com".to_string(),
+                None,
+                None,
+                status,
+            );
+            url.deduplicated_click_count = clicks;
+            url.max_clicks = max_clicks;
+            url.password_hash = password.map(|p| bcrypt::hash(p, 4).unwrap());
+            url
+        };
+
+        let cases = [
+            (
+                UrlStatus::Active,
+                9,
+                Some(10),
+                None,
+                AccessibilityStatus::Accessible,
+            ),
+            (
+                UrlStatus::Active,
+                10,
+                Some(10),
+                None,
+                AccessibilityStatus::ClickLimitReached,
+            ),
+            (
+                UrlStatus::Active,
+                11,
+                Some(10),
+                None,
+                AccessibilityStatus::ClickLimitReached,
+            ),
+            (
+                UrlStatus::Active,
+                500,
+                None,
+                None,
+                AccessibilityStatus::Accessible,
+            ),
+            (
+                UrlStatus::Active,
+                0,
+                None,
+                Some("s3cret"),
+                AccessibilityStatus::PasswordRequired,
+            ),
+            (
+                UrlStatus::Active,
+                9,
+                Some(10),
+                Some("s3cret"),
+                AccessibilityStatus::PasswordRequired,
+            ),
+            // Asking for the password is pointless once the link no longer redirects
+            (
+                UrlStatus::Active,
+                10,
+                Some(10),
+                Some("s3cret"),
+                AccessibilityStatus::ClickLimitReached,
+            ),
+            (
+                UrlStatus::Inactive,
+                10,
+                Some(10),
+                Some("s3cret"),
+                AccessibilityStatus::Inactive,
+            ),
+            (
+                UrlStatus::Deleted,
+                10,
+                Some(10),
+                None,
+                AccessibilityStatus::Deleted,
+            ),
+        ];
+        for (status, clicks, max_clicks, password, expected) in cases {
+            let url = url(status, clicks, max_clicks, password);
+            assert_eq!(
+                url.accessibility_reason(),
+                expected,
+                "{:?} with {} of {:?} clicks",
+                status,
+                clicks,
+                max_clicks
+            );
+            assert_eq!(
+                url.is_accessible(),
+                matches!(
+                    expected,
+                    AccessibilityStatus::Accessible | AccessibilityStatus::PasswordRequired
+                )
+            );
+        }
+
+        // Expiry wins over the click limit
+        let mut expired = url(UrlStatus::Active, 10, Some(10), None);
+        expired.expiration_date = Some(Utc::now() - chrono::Duration::hours(1));
+        assert_eq!(expired.accessibility_reason(), AccessibilityStatus::Expired);
     }
 }
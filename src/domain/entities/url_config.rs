@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Per-URL click tracking settings, overriding the service defaults
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UrlConfig {
+    pub url_id: i32,
+    /// Seconds a repeat click from the same IP is not recorded; `None` uses the default and
+    /// 0 records every click
+    pub click_dedup_window_seconds: Option<i32>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl UrlConfig {
+    /// Settings of a URL that has no overrides
+    pub fn default_for(url_id: i32) -> Self {
+        Self {
+            url_id,
+            click_dedup_window_seconds: None,
+            updated_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Link preview metadata of a URL's destination page, read from its Open Graph tags
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UrlMetadata {
+    pub url_id: i32,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub favicon_url: Option<String>,
+    pub fetched_at: DateTime<Utc>,
+}
@@ -11,6 +11,151 @@ pub enum ProfilePrivacy {
     FriendsOnly,
 }
 
+/// Which details appear on a public profile
+///
+/// Applies on top of [`ProfilePrivacy`]: a private profile shows nothing regardless.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProfileVisibility {
+    pub show_url_count: bool,
+    pub show_click_count: bool,
+    pub show_join_date: bool,
+    pub show_bio: bool,
+    pub show_website: bool,
+    pub show_social_links: bool,
+    pub show_recent_urls: bool,
+}
+
+impl Default for ProfileVisibility {
+    /// Details public profiles always showed stay visible; link activity is opt-in
+    fn default() -> Self {
+        Self {
+            show_url_count: false,
+            show_click_count: false,
+            show_join_date: true,
+            show_bio: true,
+            show_website: true,
+            show_social_links: true,
+            show_recent_urls: false,
+        }
+    }
+}
+
+/// Most custom links a profile may list next to the well-known networks
+pub const MAX_CUSTOM_SOCIAL_LINKS: usize = 5;
+
+/// Devices a user can receive push notifications on; registering another drops the oldest
+pub const MAX_DEVICE_TOKENS: usize = 10;
+
+/// Links to a user's accounts elsewhere, shown on their profile
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct SocialLinks {
+    pub twitter: Option<String>,
+    pub github: Option<String>,
+    pub linkedin: Option<String>,
+    pub website: Option<String>,
+    /// Other links as (label, URL) pairs, at most `MAX_CUSTOM_SOCIAL_LINKS`
+    #[serde(default)]
+    pub custom: Vec<(String, String)>,
+}
+
+impl SocialLinks {
+    /// Check if no link is set
+    pub fn is_empty(&self) -> bool {
+        self.twitter.is_none()
+            && self.github.is_none()
+            && self.linkedin.is_none()
+            && self.website.is_none()
+            && self.custom.is_empty()
+    }
+}
+
+/// Lifecycle status of a user account
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub enum AccountStatus {
+    #[default]
+    Active,
+    Suspended {
+        reason: String,
+        until: Option<DateTime<Utc>>,
+    },
+    PendingVerification,
+    Deactivated,
+}
+
+impl AccountStatus {
+    /// Reason recorded when an account is suspended by the login lockout
+    pub const LOCKOUT_REASON: &'static str = "Too many failed login attempts";
+
+    /// Suspension applied after too many failed login attempts (one hour)
+    pub fn lockout(now: DateTime<Utc>) -> Self {
+        AccountStatus::Suspended {
+            reason: Self::LOCKOUT_REASON.to_string(),
+            until: Some(now + chrono::Duration::hours(1)),
+        }
+    }
+
+    /// Check if the account is suspended at the given time
+    ///
+    /// A suspension whose `until` has passed no longer applies.
+    pub fn is_suspended_at(&self, now: DateTime<Utc>) -> bool {
+        match self {
+            AccountStatus::Suspended { until, .. } => until.is_none_or(|until| until > now),
+            _ => false,
+        }
+    }
+
+    /// Storage representation of the status
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountStatus::Active => "active",
+            AccountStatus::Suspended { .. } => "suspended",
+            AccountStatus::PendingVerification => "pending_verification",
+            AccountStatus::Deactivated => "deactivated",
+        }
+    }
+}
+
+/// Subscription tier of a user account (stored as `free` / `premium`)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum UserTier {
+    #[default]
+    Free,
+    Premium,
+}
+
+/// External identity provider a user can sign in with (stored as `google` / `github`)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum OAuthProvider {
+    Google,
+    GitHub,
+}
+
+impl OAuthProvider {
+    /// Storage and URL path representation of the provider
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "google",
+            OAuthProvider::GitHub => "github",
+        }
+    }
+
+    /// Parse the storage representation
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "google" => Some(OAuthProvider::Google),
+            "github" => Some(OAuthProvider::GitHub),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for OAuthProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Domain entity representing a User
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct User {
@@ -27,7 +172,24 @@ pub struct User {
     pub website: Option<String>,
     pub location: Option<String>,
     pub privacy: ProfilePrivacy,
+    /// Details shown on the public profile
+    pub profile_visibility: ProfileVisibility,
+    pub social_links: Option<SocialLinks>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub account_status: AccountStatus,
+    pub tier: UserTier,
+    /// When the password was last changed; earlier session tokens are no longer accepted
+    pub password_changed_at: Option<DateTime<Utc>>,
+    /// Provider of the linked social login, if any
+    pub oauth_provider: Option<OAuthProvider>,
+    /// The user's ID at `oauth_provider`
+    pub oauth_provider_id: Option<String>,
+    /// Push notification tokens of the user's mobile devices
+    #[serde(default)]
+    pub device_tokens: Vec<String>,
+    /// Address the user is changing their email to, until both addresses confirmed
+    #[serde(default)]
+    pub pending_email: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -54,7 +216,16 @@ impl User {
             website: None,
             location: None,
             privacy: ProfilePrivacy::default(),
+            profile_visibility: ProfileVisibility::default(),
+            social_links: None,
             updated_at: None,
+            account_status: AccountStatus::default(),
+            tier: UserTier::default(),
+            password_changed_at: None,
+            oauth_provider: None,
+            oauth_provider_id: None,
+            device_tokens: Vec::new(),
+            pending_email: None,
         }
     }
 
@@ -97,7 +268,16 @@ impl User {
             website,
             location,
             privacy,
+            profile_visibility: ProfileVisibility::default(),
+            social_links: None,
             updated_at: None,
+            account_status: AccountStatus::default(),
+            tier: UserTier::default(),
+            password_changed_at: None,
+            oauth_provider: None,
+            oauth_provider_id: None,
+            device_tokens: Vec::new(),
+            pending_email: None,
         }
     }
 
@@ -151,6 +331,18 @@ impl User {
     pub fn is_profile_public(&self) -> bool {
         matches!(self.privacy, ProfilePrivacy::Public)
     }
+
+    /// Check if the account can log in with a password
+    ///
+    /// Accounts created through a social login have no password until one is reset.
+    pub fn has_password(&self) -> bool {
+        !self.password_hash.is_empty()
+    }
+
+    /// Check if the account is currently suspended
+    pub fn is_suspended(&self) -> bool {
+        self.account_status.is_suspended_at(Utc::now())
+    }
 }
 
 impl fmt::Display for User {
@@ -303,4 +495,36 @@ mod tests {
         assert!(public_user.is_profile_public());
         assert!(!private_user.is_profile_public());
     }
+
+    #[test]
+    fn test_account_status_default() {
+        let user = User::new_with_timestamp(
+            1,
+            "testuser".to_string(),
+            "test@example.com".to_string(),
+            "hashed_password".to_string(),
+        );
+
+        assert_eq!(user.account_status, AccountStatus::Active);
+        assert!(!user.is_suspended());
+    }
+
+    #[test]
+    fn test_account_status_suspension_window() {
+        let now = Utc::now();
+        let indefinite = AccountStatus::Suspended {
+            reason: "Spam".to_string(),
+            until: None,
+        };
+        let expired = AccountStatus::Suspended {
+            reason: "Spam".to_string(),
+            until: Some(now - chrono::Duration::minutes(1)),
+        };
+
+        assert!(indefinite.is_suspended_at(now));
+        assert!(!expired.is_suspended_at(now));
+        assert!(AccountStatus::lockout(now).is_suspended_at(now));
+        assert!(!AccountStatus::lockout(now).is_suspended_at(now + chrono::Duration::hours(2)));
+        assert!(!AccountStatus::PendingVerification.is_suspended_at(now));
+    }
 }
@@ -0,0 +1,165 @@
+use crate::domain::entities::UrlStatus;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Domain entity holding a user's URL and click totals, kept up to date as counters
+///
+/// The counters are adjusted as URLs are created, deactivated and clicked instead of being
+/// aggregated on every read, and can be recomputed from scratch should they drift.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserUrlStats {
+    pub user_id: i32,
+    /// URLs the user has that were not deleted, whatever their status
+    pub total_urls: i64,
+    pub active_urls: i64,
+    pub inactive_urls: i64,
+    /// URLs archived after expiring; included in `total_urls`
+    pub archived_urls: i64,
+    pub total_clicks: i64,
+    pub last_updated_at: DateTime<Utc>,
+}
+
+impl UserUrlStats {
+    /// Stats of a user without any URL
+    pub fn empty(user_id: i32) -> Self {
+        Self {
+            user_id,
+            total_urls: 0,
+            active_urls: 0,
+            inactive_urls: 0,
+            archived_urls: 0,
+            total_clicks: 0,
+            last_updated_at: Utc::now(),
+        }
+    }
+}
+
+/// Amounts to add to the URL counters of a user's stats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UrlCountChange {
+    pub total_urls: i64,
+    pub active_urls: i64,
+    pub inactive_urls: i64,
+    pub archived_urls: i64,
+}
+
+impl UrlCountChange {
+    /// Counts of a single URL with the given status
+    fn of(status: &UrlStatus) -> Self {
+        Self {
+            total_urls: 1,
+            active_urls: i64::from(*status == UrlStatus::Active),
+            inactive_urls: i64::from(*status == UrlStatus::Inactive),
+            archived_urls: i64::from(*status == UrlStatus::Archived),
+        }
+    }
+
+    /// A URL was created with the given status
+    pub fn created(status: &UrlStatus) -> Self {
+        Self::of(status)
+    }
+
+    /// A URL went from one status to another
+    pub fn status_changed(from: &UrlStatus, to: &UrlStatus) -> Self {
+        let (from, to) = (Self::of(from), Self::of(to));
+        Self {
+            total_urls: 0,
+            active_urls: to.active_urls - from.active_urls,
+            inactive_urls: to.inactive_urls - from.inactive_urls,
+            archived_urls: to.archived_urls - from.archived_urls,
+        }
+    }
+
+    /// A URL with the given status was deleted
+    pub fn removed(status: &UrlStatus) -> Self {
+        let counts = Self::of(status);
+        Self {
+            total_urls: -counts.total_urls,
+            active_urls: -counts.active_urls,
+            inactive_urls: -counts.inactive_urls,
+            archived_urls: -counts.archived_urls,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Add up the changes of each owner, leaving out URLs without one
+    pub fn per_user(
+        changes: impl IntoIterator<Item = (Option<i32>, UrlCountChange)>,
+    ) -> HashMap<i32, UrlCountChange> {
+        let mut per_user: HashMap<i32, UrlCountChange> = HashMap::new();
+        for (user_id, change) in changes {
+            let Some(user_id) = user_id else { continue };
+            let total = per_user.entry(user_id).or_default();
+            total.total_urls += change.total_urls;
+            total.active_urls += change.active_urls;
+            total.inactive_urls += change.inactive_urls;
+            total.archived_urls += change.archived_urls;
+        }
+        per_user
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_count_changes() {
+        assert_eq!(
+            UrlCountChange::created(&UrlStatus::Active),
+            UrlCountChange {
+                total_urls: 1,
+                active_urls: 1,
+                inactive_urls: 0,
+                archived_urls: 0,
+            }
+        );
+        assert_eq!(
+            UrlCountChange::status_changed(&UrlStatus::Active, &UrlStatus::Inactive),
+            UrlCountChange {
+                total_urls: 0,
+                active_urls: -1,
+                inactive_urls: 1,
+                archived_urls: 0,
+            }
+        );
+        assert!(
+            UrlCountChange::status_changed(&UrlStatus::Inactive, &UrlStatus::Inactive).is_empty()
+        );
+        assert_eq!(
+            UrlCountChange::removed(&UrlStatus::Archived),
+            UrlCountChange {
+                total_urls: -1,
+                active_urls: 0,
+                inactive_urls: 0,
+                archived_urls: -1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_changes_per_user() {
+        let archived = |status| UrlCountChange::status_changed(status, &UrlStatus::Archived);
+        let per_user = UrlCountChange::per_user([
+            (Some(1), archived(&UrlStatus::Active)),
+            (Some(1), archived(&UrlStatus::Inactive)),
+            (Some(2), archived(&UrlStatus::Active)),
+            (None, archived(&UrlStatus::Active)),
+        ]);
+        assert_eq!(per_user.len(), 2);
+        assert_eq!(
+            per_user[&1],
+            UrlCountChange {
+                total_urls: 0,
+                active_urls: -1,
+                inactive_urls: -1,
+                archived_urls: 2,
+            }
+        );
+        assert_eq!(per_user[&2].archived_urls, 1);
+    }
+}
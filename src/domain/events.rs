@@ -0,0 +1,38 @@
+use crate::domain::entities::{Click, Url};
+
+/// Something that happened to a URL, published for services reacting to it
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum DomainEvent {
+    UrlCreated { url: Url },
+    UrlClicked { click: Click },
+    UrlExpired { url: Url },
+    UrlDeactivated { url: Url },
+}
+
+impl DomainEvent {
+    /// Name of the event, used as a metric label and in logs
+    pub fn name(&self) -> &'static str {
+        match self {
+            DomainEvent::UrlCreated { .. } => "url_created",
+            DomainEvent::UrlClicked { .. } => "url_clicked",
+            DomainEvent::UrlExpired { .. } => "url_expired",
+            DomainEvent::UrlDeactivated { .. } => "url_deactivated",
+        }
+    }
+}
+
+/// Event bus errors
+#[derive(Debug, thiserror::Error)]
+pub enum EventBusError {
+    #[error("No subscriber is listening for {0} events")]
+    NoSubscribers(&'static str),
+}
+
+/// Delivers domain events to the services subscribed to them
+///
+/// Publishing does not wait for subscribers, so side effects such as notifications never
+/// slow down or fail the operation that caused them.
+pub trait EventBus: Send + Sync {
+    fn publish(&self, event: DomainEvent) -> Result<(), EventBusError>;
+}
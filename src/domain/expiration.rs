@@ -0,0 +1,164 @@
+use chrono::{DateTime, Duration, Utc};
+use thiserror::Error;
+
+/// Errors raised while computing a new expiration date
+#[derive(Error, Debug, PartialEq)]
+pub enum ExpirationError {
+    #[error("Invalid duration '{0}'. Expected a positive amount followed by m, h, d, w or y (e.g. 30m, 24h, 7d, 1y)")]
+    InvalidDuration(String),
+
+    #[error("Expiration cannot be more than {0} days in the future")]
+    ExceedsMaximum(u32),
+}
+
+/// Parse a relative duration such as `30m`, `24h`, `7d`, `2w` or `1y`
+///
+/// A leading `+` is accepted. A year is 365 days. Zero, negative and overflowing amounts
+/// are rejected.
+pub fn parse_relative_duration(input: &str) -> Result<Duration, ExpirationError> {
+    let invalid = || ExpirationError::InvalidDuration(input.to_string());
+
+    let trimmed = input.trim();
+    let trimmed = trimmed.strip_prefix('+').unwrap_or(trimmed);
+    let unit = trimmed.chars().last().ok_or_else(invalid)?;
+    let amount = &trimmed[..trimmed.len() - unit.len_utf8()];
+
+    if amount.is_empty() || !amount.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    if amount == 0 {
+        return Err(invalid());
+    }
+
+    let duration = match unit.to_ascii_lowercase() {
+        'm' => Duration::try_minutes(amount),
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        'y' => amount.checked_mul(365).and_then(Duration::try_days),
+        _ => None,
+    };
+    duration.ok_or_else(invalid)
+}
+
+/// Compute the expiration after extending `current` (or `now`, if unset) by `extend_by`
+///
+/// The result may not lie more than `max_expiration_days` after `now`.
+pub fn extend_expiration(
+    current: Option<DateTime<Utc>>,
+    extend_by: Duration,
+    now: DateTime<Utc>,
+    max_expiration_days: u32,
+) -> Result<DateTime<Utc>, ExpirationError> {
+    let new_expiration = current
+        .unwrap_or(now)
+        .checked_add_signed(extend_by)
+        .ok_or(ExpirationError::ExceedsMaximum(max_expiration_days))?;
+    check_max_expiration(new_expiration, now, max_expiration_days)?;
+    Ok(new_expiration)
+}
+
+/// Reject expiration dates more than `max_expiration_days` after `now`
+pub fn check_max_expiration(
+    expiration: DateTime<Utc>,
+    now: DateTime<Utc>,
+    max_expiration_days: u32,
+) -> Result<(), ExpirationError> {
+    if expiration - now > Duration::days(max_expiration_days as i64) {
+        return Err(ExpirationError::ExceedsMaximum(max_expiration_days));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid_durations() {
+        assert_eq!(parse_relative_duration("30m"), Ok(Duration::minutes(30)));
+        assert_eq!(parse_relative_duration("24h"), Ok(Duration::hours(24)));
+        assert_eq!(parse_relative_duration("7d"), Ok(Duration::days(7)));
+        assert_eq!(parse_relative_duration("+30d"), Ok(Duration::days(30)));
+        assert_eq!(parse_relative_duration("2w"), Ok(Duration::days(14)));
+        assert_eq!(parse_relative_duration("1y"), Ok(Duration::days(365)));
+        assert_eq!(parse_relative_duration(" 12H "), Ok(Duration::hours(12)));
+    }
+
+    #[test]
+    fn test_parse_invalid_durations() {
+        for input in [
+            "",
+            "d",
+            "+",
+            "7",
+            "-7d",
+            "+-7d",
+            "0d",
+            "7x",
+            "7 d",
+            "1.5h",
+            "seven days",
+            "7dd",
+            "7é",
+        ] {
+            assert!(
+                matches!(
+                    parse_relative_duration(input),
+                    Err(ExpirationError::InvalidDuration(_))
+                ),
+                "expected '{}' to be rejected",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_overflowing_durations() {
+        assert!(parse_relative_duration("99999999999999999999d").is_err());
+        assert!(parse_relative_duration("9223372036854775807m").is_err());
+        assert!(parse_relative_duration("9223372036854775807y").is_err());
+        assert!(parse_relative_duration("999999999999999w").is_err());
+    }
+
+    #[test]
+    fn test_extend_from_current_or_now() {
+        let now = Utc::now();
+        let current = now + Duration::days(10);
+
+        assert_eq!(
+            extend_expiration(Some(current), Duration::days(7), now, 365),
+            Ok(current + Duration::days(7))
+        );
+        assert_eq!(
+            extend_expiration(None, Duration::hours(24), now, 365),
+            Ok(now + Duration::hours(24))
+        );
+    }
+
+    #[test]
+    fn test_extend_beyond_maximum_is_rejected() {
+        let now = Utc::now();
+        let current = now + Duration::days(360);
+
+        assert_eq!(
+            extend_expiration(Some(current), Duration::days(7), now, 365),
+            Err(ExpirationError::ExceedsMaximum(365))
+        );
+        assert!(extend_expiration(None, Duration::days(365), now, 365).is_ok());
+        assert!(extend_expiration(None, Duration::days(366), now, 365).is_err());
+    }
+
+    #[test]
+    fn test_extend_overflowing_date_is_rejected() {
+        let now = Utc::now();
+        assert!(extend_expiration(
+            Some(DateTime::<Utc>::MAX_UTC),
+            Duration::days(1),
+            now,
+            u32::MAX
+        )
+        .is_err());
+    }
+}
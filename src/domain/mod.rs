@@ -1,4 +1,6 @@
 pub mod entities;
+pub mod events;
+pub mod expiration;
 pub mod repositories;
 pub mod services;
 pub mod validation;
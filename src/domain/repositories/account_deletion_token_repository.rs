@@ -1,4 +1,5 @@
 use crate::domain::entities::AccountDeletionToken;
+use crate::infrastructure::email::EmailMessage;
 use async_trait::async_trait;
 use thiserror::Error;
 
@@ -7,9 +8,12 @@ use thiserror::Error;
 #[allow(dead_code)]
 pub trait AccountDeletionTokenRepository: Send + Sync {
     /// Create a new account deletion token
+    ///
+    /// `email` is queued in the email outbox in the same transaction as the token.
     async fn create_token(
         &self,
         token: AccountDeletionToken,
+        email: Option<&EmailMessage>,
     ) -> Result<AccountDeletionToken, Box<dyn std::error::Error + Send + Sync>>;
 
     /// Find an account deletion token by token string
@@ -42,6 +46,54 @@ pub trait AccountDeletionTokenRepository: Send + Sync {
     ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
 }
 
+/// Forwards to the shared repository
+#[async_trait]
+impl<T: AccountDeletionTokenRepository + ?Sized> AccountDeletionTokenRepository
+    for std::sync::Arc<T>
+{
+    async fn create_token(
+        &self,
+        token: AccountDeletionToken,
+        email: Option<&EmailMessage>,
+    ) -> Result<AccountDeletionToken, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).create_token(token, email).await
+    }
+
+    async fn find_by_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<AccountDeletionToken>, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).find_by_token(token).await
+    }
+
+    async fn find_active_token_for_user(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<AccountDeletionToken>, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).find_active_token_for_user(user_id).await
+    }
+
+    async fn update_token(
+        &self,
+        token: AccountDeletionToken,
+    ) -> Result<AccountDeletionToken, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).update_token(token).await
+    }
+
+    async fn delete_expired_tokens(
+        &self,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).delete_expired_tokens().await
+    }
+
+    async fn cancel_all_tokens_for_user(
+        &self,
+        user_id: i32,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).cancel_all_tokens_for_user(user_id).await
+    }
+}
+
 /// Repository errors
 #[allow(dead_code)]
 #[derive(Error, Debug)]
@@ -0,0 +1,25 @@
+use crate::domain::entities::ArchiveRecord;
+use crate::domain::repositories::RepositoryError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Repository trait for the archive of records removed for good
+#[async_trait]
+pub trait ArchiveRepository: Send + Sync {
+    /// Store a record, returning it with its assigned ID and deletion time
+    async fn archive(&self, record: &ArchiveRecord) -> Result<ArchiveRecord, RepositoryError>;
+
+    /// Records archived before `before`, optionally of one entity type, newest first
+    async fn find_records(
+        &self,
+        entity_type: Option<&str>,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<ArchiveRecord>, RepositoryError>;
+
+    /// Count records archived before the cutoff
+    async fn count_archived_before(&self, cutoff: DateTime<Utc>) -> Result<u64, RepositoryError>;
+
+    /// Delete records archived before the cutoff, returning how many were removed
+    async fn delete_archived_before(&self, cutoff: DateTime<Utc>) -> Result<u64, RepositoryError>;
+}
@@ -0,0 +1,10 @@
+use crate::domain::entities::AuditLogEntry;
+use crate::domain::repositories::RepositoryError;
+use async_trait::async_trait;
+
+/// Repository trait for the audit log of administrative actions
+#[async_trait]
+pub trait AuditLogRepository: Send + Sync {
+    /// Store an entry, returning it with its assigned ID and time
+    async fn record(&self, entry: &AuditLogEntry) -> Result<AuditLogEntry, RepositoryError>;
+}
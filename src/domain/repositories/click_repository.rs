@@ -1,4 +1,4 @@
-use crate::domain::entities::Click;
+use crate::domain::entities::{Click, ConversionEvent, ConversionGoal, UrlConfig};
 use async_trait::async_trait;
 
 /// Repository trait for click/analytics data operations
@@ -8,6 +8,17 @@ pub trait ClickRepository: Send + Sync {
     /// Record a new click event
     async fn record_click(&self, click: &Click) -> Result<Click, RepositoryError>;
 
+    /// Record several click events in a single write, returning the number stored
+    async fn record_clicks(&self, clicks: &[Click]) -> Result<u64, RepositoryError>;
+
+    /// Add clicks seen by the tracker to each URL's `deduplicated_click_count`, recorded or not
+    ///
+    /// Returns the new count of every URL that still exists.
+    async fn increment_deduplicated_click_counts(
+        &self,
+        counts: &[(i32, i64)],
+    ) -> Result<Vec<ClickCountTotal>, RepositoryError>;
+
     /// Get click count for a specific URL
     async fn get_click_count(&self, url_id: i32) -> Result<i64, RepositoryError>;
 
@@ -27,17 +38,288 @@ pub trait ClickRepository: Send + Sync {
         end_date: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<Vec<Click>, RepositoryError>;
 
+    /// Get up to `limit` clicks on a user's URLs within a time range, by ascending ID
+    ///
+    /// Only clicks with an ID above `after_id` are returned, so large histories can be read
+    /// one page at a time.
+    async fn get_clicks_for_user_page(
+        &self,
+        user_id: i32,
+        start_date: Option<chrono::DateTime<chrono::Utc>>,
+        end_date: Option<chrono::DateTime<chrono::Utc>>,
+        after_id: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<Click>, RepositoryError>;
+
     /// Get click statistics for a URL
     async fn get_url_click_stats(&self, url_id: i32) -> Result<ClickStats, RepositoryError>;
 
     /// Get click statistics for a user
     async fn get_user_click_stats(&self, user_id: i32) -> Result<ClickStats, RepositoryError>;
 
+    /// Approximate number of distinct visitors of a URL between two dates (inclusive)
+    async fn get_unique_visitors_estimate(
+        &self,
+        url_id: i32,
+        start: chrono::NaiveDate,
+        end: chrono::NaiveDate,
+    ) -> Result<i64, RepositoryError>;
+
+    /// Aggregate the analytics summary of a URL, optionally counting bot clicks
+    async fn get_url_analytics_summary(
+        &self,
+        url_id: i32,
+        include_bots: bool,
+    ) -> Result<UrlAnalyticsSummary, RepositoryError>;
+
+    /// Create a conversion goal for a URL
+    async fn create_conversion_goal(
+        &self,
+        url_id: i32,
+        goal_url_pattern: &str,
+        name: &str,
+    ) -> Result<ConversionGoal, RepositoryError>;
+
+    /// Find a conversion goal by ID
+    async fn find_conversion_goal(
+        &self,
+        id: i32,
+    ) -> Result<Option<ConversionGoal>, RepositoryError>;
+
+    /// Delete a conversion goal with its events, returning whether it existed
+    async fn delete_conversion_goal(&self, id: i32) -> Result<bool, RepositoryError>;
+
+    /// Record that the visitor holding a click token reached a goal
+    ///
+    /// Returns `None` when no click of the goal's URL carries the token. Reporting the same
+    /// click and goal again returns the existing event.
+    async fn record_conversion(
+        &self,
+        click_token: &str,
+        goal_id: i32,
+    ) -> Result<Option<ConversionEvent>, RepositoryError>;
+
+    /// Share of a URL's clicks that led to at least one conversion, from 0 to 1
+    async fn get_conversion_rate(&self, url_id: i32) -> Result<f64, RepositoryError>;
+
+    /// Raw, recorded non-bot and recorded bot clicks of a URL
+    async fn get_click_dedup_ratio(&self, url_id: i32) -> Result<ClickDedupRatio, RepositoryError>;
+
+    /// Find the click tracking settings of a URL, `None` when it has no overrides
+    async fn find_url_config(&self, url_id: i32) -> Result<Option<UrlConfig>, RepositoryError>;
+
+    /// Create or replace the click tracking settings of a URL
+    async fn save_url_config(&self, config: &UrlConfig) -> Result<UrlConfig, RepositoryError>;
+
+    /// Clicks of a URL by hour of day (UTC) across all time, midnight first
+    async fn get_hourly_distribution(&self, url_id: i32) -> Result<[u32; 24], RepositoryError>;
+
+    /// Clicks of a URL by day of week (UTC) across all time, Sunday first
+    async fn get_daily_distribution(&self, url_id: i32) -> Result<[u32; 7], RepositoryError>;
+
+    /// Hour of day (UTC) a URL is clicked most, the earliest on ties and 0 without clicks
+    async fn get_peak_hour(&self, url_id: i32) -> Result<u8, RepositoryError>;
+
+    /// Clicks per minute of a URL over the last `window_minutes`
+    async fn get_click_velocity(
+        &self,
+        url_id: i32,
+        window_minutes: u32,
+    ) -> Result<f64, RepositoryError>;
+
+    /// Clicks of a URL by UTM source, medium and campaign, most clicked first
+    ///
+    /// Clicks without UTM parameters are left out.
+    async fn get_utm_attribution_breakdown(
+        &self,
+        url_id: i32,
+    ) -> Result<Vec<UtmAttributionRow>, RepositoryError>;
+
     /// Delete old click records (for data retention)
     async fn delete_old_clicks(
         &self,
         older_than: chrono::DateTime<chrono::Utc>,
     ) -> Result<u64, RepositoryError>;
+
+    /// Count the click records `delete_old_clicks` would delete
+    async fn count_old_clicks(
+        &self,
+        older_than: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError>;
+}
+
+/// Forwards to the shared repository, so `Arc<dyn ClickRepository>` is a `ClickRepository` too
+#[async_trait]
+impl<T: ClickRepository + ?Sized> ClickRepository for std::sync::Arc<T> {
+    async fn record_click(&self, click: &Click) -> Result<Click, RepositoryError> {
+        (**self).record_click(click).await
+    }
+
+    async fn record_clicks(&self, clicks: &[Click]) -> Result<u64, RepositoryError> {
+        (**self).record_clicks(clicks).await
+    }
+
+    async fn increment_deduplicated_click_counts(
+        &self,
+        counts: &[(i32, i64)],
+    ) -> Result<Vec<ClickCountTotal>, RepositoryError> {
+        (**self).increment_deduplicated_click_counts(counts).await
+    }
+
+    async fn get_click_count(&self, url_id: i32) -> Result<i64, RepositoryError> {
+        (**self).get_click_count(url_id).await
+    }
+
+    async fn get_clicks_for_url(
+        &self,
+        url_id: i32,
+        start_date: Option<chrono::DateTime<chrono::Utc>>,
+        end_date: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<Click>, RepositoryError> {
+        (**self)
+            .get_clicks_for_url(url_id, start_date, end_date)
+            .await
+    }
+
+    async fn get_clicks_for_user(
+        &self,
+        user_id: i32,
+        start_date: Option<chrono::DateTime<chrono::Utc>>,
+        end_date: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<Click>, RepositoryError> {
+        (**self)
+            .get_clicks_for_user(user_id, start_date, end_date)
+            .await
+    }
+
+    async fn get_clicks_for_user_page(
+        &self,
+        user_id: i32,
+        start_date: Option<chrono::DateTime<chrono::Utc>>,
+        end_date: Option<chrono::DateTime<chrono::Utc>>,
+        after_id: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<Click>, RepositoryError> {
+        (**self)
+            .get_clicks_for_user_page(user_id, start_date, end_date, after_id, limit)
+            .await
+    }
+
+    async fn get_url_click_stats(&self, url_id: i32) -> Result<ClickStats, RepositoryError> {
+        (**self).get_url_click_stats(url_id).await
+    }
+
+    async fn get_user_click_stats(&self, user_id: i32) -> Result<ClickStats, RepositoryError> {
+        (**self).get_user_click_stats(user_id).await
+    }
+
+    async fn get_unique_visitors_estimate(
+        &self,
+        url_id: i32,
+        start: chrono::NaiveDate,
+        end: chrono::NaiveDate,
+    ) -> Result<i64, RepositoryError> {
+        (**self)
+            .get_unique_visitors_estimate(url_id, start, end)
+            .await
+    }
+
+    async fn get_url_analytics_summary(
+        &self,
+        url_id: i32,
+        include_bots: bool,
+    ) -> Result<UrlAnalyticsSummary, RepositoryError> {
+        (**self)
+            .get_url_analytics_summary(url_id, include_bots)
+            .await
+    }
+
+    async fn create_conversion_goal(
+        &self,
+        url_id: i32,
+        goal_url_pattern: &str,
+        name: &str,
+    ) -> Result<ConversionGoal, RepositoryError> {
+        (**self)
+            .create_conversion_goal(url_id, goal_url_pattern, name)
+            .await
+    }
+
+    async fn find_conversion_goal(
+        &self,
+        id: i32,
+    ) -> Result<Option<ConversionGoal>, RepositoryError> {
+        (**self).find_conversion_goal(id).await
+    }
+
+    async fn delete_conversion_goal(&self, id: i32) -> Result<bool, RepositoryError> {
+        (**self).delete_conversion_goal(id).await
+    }
+
+    async fn record_conversion(
+        &self,
+        click_token: &str,
+        goal_id: i32,
+    ) -> Result<Option<ConversionEvent>, RepositoryError> {
+        (**self).record_conversion(click_token, goal_id).await
+    }
+
+    async fn get_conversion_rate(&self, url_id: i32) -> Result<f64, RepositoryError> {
+        (**self).get_conversion_rate(url_id).await
+    }
+
+    async fn get_click_dedup_ratio(&self, url_id: i32) -> Result<ClickDedupRatio, RepositoryError> {
+        (**self).get_click_dedup_ratio(url_id).await
+    }
+
+    async fn find_url_config(&self, url_id: i32) -> Result<Option<UrlConfig>, RepositoryError> {
+        (**self).find_url_config(url_id).await
+    }
+
+    async fn save_url_config(&self, config: &UrlConfig) -> Result<UrlConfig, RepositoryError> {
+        (**self).save_url_config(config).await
+    }
+
+    async fn get_hourly_distribution(&self, url_id: i32) -> Result<[u32; 24], RepositoryError> {
+        (**self).get_hourly_distribution(url_id).await
+    }
+
+    async fn get_daily_distribution(&self, url_id: i32) -> Result<[u32; 7], RepositoryError> {
+        (**self).get_daily_distribution(url_id).await
+    }
+
+    async fn get_peak_hour(&self, url_id: i32) -> Result<u8, RepositoryError> {
+        (**self).get_peak_hour(url_id).await
+    }
+
+    async fn get_click_velocity(
+        &self,
+        url_id: i32,
+        window_minutes: u32,
+    ) -> Result<f64, RepositoryError> {
+        (**self).get_click_velocity(url_id, window_minutes).await
+    }
+
+    async fn get_utm_attribution_breakdown(
+        &self,
+        url_id: i32,
+    ) -> Result<Vec<UtmAttributionRow>, RepositoryError> {
+        (**self).get_utm_attribution_breakdown(url_id).await
+    }
+
+    async fn delete_old_clicks(
+        &self,
+        older_than: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        (**self).delete_old_clicks(older_than).await
+    }
+
+    async fn count_old_clicks(
+        &self,
+        older_than: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        (**self).count_old_clicks(older_than).await
+    }
 }
 
 /// Click statistics data structure
@@ -52,6 +334,64 @@ pub struct ClickStats {
     pub top_referers: Vec<(String, i64)>,
 }
 
+/// A URL's `deduplicated_click_count` after clicks were added to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClickCountTotal {
+    pub url_id: i32,
+    pub user_id: Option<i32>,
+    pub short_code: String,
+    pub total: i64,
+}
+
+/// Clicks of a URL before and after deduplication
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ClickDedupRatio {
+    /// Every click seen, including repeats that were not recorded
+    pub raw_clicks: i64,
+    /// Recorded clicks from browsers
+    pub unique_clicks: i64,
+    /// Recorded clicks from crawlers and scripted clients
+    pub bot_clicks: i64,
+}
+
+/// Clicks of one URL attributed to a combination of UTM parameters
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UtmAttributionRow {
+    pub source: Option<String>,
+    pub medium: Option<String>,
+    pub campaign: Option<String>,
+    pub count: i64,
+}
+
+/// Clicks of one URL broken down by device type
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DeviceBreakdown {
+    pub desktop: i64,
+    pub mobile: i64,
+    pub tablet: i64,
+    pub bot: i64,
+}
+
+/// Analytics summary of a single URL
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UrlAnalyticsSummary {
+    pub total_clicks: i64,
+    /// Approximate number of distinct visitors
+    pub unique_visitors: i64,
+    pub clicks_today: i64,
+    pub clicks_this_week: i64,
+    pub clicks_this_month: i64,
+    /// Up to five most frequent countries with their click counts
+    pub top_countries: Vec<(String, i64)>,
+    /// Up to five most frequent referrers with their click counts
+    pub top_referrers: Vec<(String, i64)>,
+    pub device_breakdown: DeviceBreakdown,
+    /// Share of the counted clicks that led to at least one conversion, from 0 to 1
+    pub conversion_rate: f64,
+    /// Up to ten most recent clicks, newest first
+    pub recent_clicks: Vec<Click>,
+}
+
 /// Repository errors
 #[allow(dead_code)]
 #[derive(Debug, thiserror::Error)]
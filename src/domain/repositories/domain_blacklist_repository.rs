@@ -0,0 +1,45 @@
+use crate::domain::entities::BlockedDomain;
+use async_trait::async_trait;
+use thiserror::Error;
+
+/// Repository trait for the domain blacklist
+///
+/// Domains are passed in already normalized (lowercase, no trailing dot).
+#[async_trait]
+pub trait DomainBlacklistRepository: Send + Sync {
+    /// Check if a host is blacklisted, either directly or through a parent domain
+    async fn is_blocked(&self, domain: &str) -> Result<bool, RepositoryError>;
+
+    /// Add a domain to the blacklist
+    async fn add_domain(
+        &self,
+        domain: &str,
+        reason: &str,
+        added_by: i32,
+    ) -> Result<BlockedDomain, RepositoryError>;
+
+    /// Add domains loaded from the blacklist file, skipping those already present
+    ///
+    /// Returns the number of domains added.
+    async fn import_domains(&self, domains: &[(String, String)]) -> Result<u64, RepositoryError>;
+
+    /// Remove a blacklist entry by ID
+    async fn remove_domain(&self, id: i32) -> Result<bool, RepositoryError>;
+
+    /// List all blacklisted domains, alphabetically
+    async fn list_domains(&self) -> Result<Vec<BlockedDomain>, RepositoryError>;
+}
+
+/// Repository errors
+#[allow(dead_code)]
+#[derive(Error, Debug)]
+pub enum RepositoryError {
+    #[error("Database connection error: {0}")]
+    Connection(#[from] sqlx::Error),
+
+    #[error("Domain is already blacklisted")]
+    DuplicateDomain,
+
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+}
@@ -0,0 +1,47 @@
+use crate::domain::entities::{EmailChangeSide, EmailChangeToken};
+use async_trait::async_trait;
+
+/// Repository trait for pending email changes
+#[async_trait]
+pub trait EmailChangeRepository: Send + Sync {
+    /// Store a new email change
+    ///
+    /// Any pending change of the same user is dropped first, so a user has at most one.
+    async fn create_token(
+        &self,
+        token: EmailChangeToken,
+    ) -> Result<EmailChangeToken, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Find the change whose link for the given side carries the token with this hash
+    async fn find_by_token_hash(
+        &self,
+        side: EmailChangeSide,
+        token_hash: &str,
+    ) -> Result<Option<EmailChangeToken>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Record the confirmation of one side, returning the updated change
+    ///
+    /// Confirming a side twice keeps the time of the first confirmation.
+    async fn confirm(
+        &self,
+        id: i32,
+        side: EmailChangeSide,
+    ) -> Result<Option<EmailChangeToken>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Delete a change once it was applied or expired
+    async fn delete_token(&self, id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Delete changes that expired before the given time
+    ///
+    /// The pending email of their users is cleared along with them.
+    async fn delete_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Count the changes `delete_expired_tokens` would delete
+    async fn count_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
+}
@@ -0,0 +1,53 @@
+use crate::domain::entities::OutboxEmail;
+use crate::infrastructure::email::EmailMessage;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Repository trait for the outbox of emails waiting to be sent
+#[async_trait]
+pub trait EmailOutboxRepository: Send + Sync {
+    /// Store an email to be sent by the outbox poller
+    async fn enqueue(
+        &self,
+        message: &EmailMessage,
+    ) -> Result<OutboxEmail, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Take up to `limit` pending emails due at `now`, oldest first
+    ///
+    /// Taken emails are not due again before `now + lease`, so several pollers never send the
+    /// same email at once; the lease runs out if the poller stops before recording the result.
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease: chrono::Duration,
+        limit: usize,
+    ) -> Result<Vec<OutboxEmail>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Record that an email was sent
+    async fn mark_sent(
+        &self,
+        id: i64,
+        sent_at: DateTime<Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Record a failed attempt; the email is retried at `retry_at`, or given up without one
+    async fn mark_attempt_failed(
+        &self,
+        id: i64,
+        error: &str,
+        failed_at: DateTime<Utc>,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Delete emails sent before the given time
+    async fn delete_sent_before(
+        &self,
+        sent_before: DateTime<Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Count the emails `delete_sent_before` would delete
+    async fn count_sent_before(
+        &self,
+        sent_before: DateTime<Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
+}
@@ -0,0 +1,28 @@
+use crate::domain::entities::ExportJob;
+use crate::domain::repositories::RepositoryError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Repository trait for analytics export jobs
+#[async_trait]
+pub trait ExportJobRepository: Send + Sync {
+    /// Store a new job; a job already stored under its ID is left as it is
+    async fn create(&self, job: &ExportJob) -> Result<(), RepositoryError>;
+
+    /// Store the job's current state, creating it if it is not stored yet
+    async fn save(&self, job: &ExportJob) -> Result<(), RepositoryError>;
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<ExportJob>, RepositoryError>;
+
+    /// The user's jobs, newest first
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<ExportJob>, RepositoryError>;
+
+    /// Jobs that finished, or were started if they never finished, before the cutoff
+    async fn find_expired(&self, cutoff: DateTime<Utc>) -> Result<Vec<ExportJob>, RepositoryError>;
+
+    /// Count the jobs `find_expired` would return
+    async fn count_expired(&self, cutoff: DateTime<Utc>) -> Result<u64, RepositoryError>;
+
+    /// Delete a job, returning whether it existed
+    async fn delete(&self, id: &str) -> Result<bool, RepositoryError>;
+}
@@ -0,0 +1,73 @@
+use crate::domain::entities::MagicLinkToken;
+use async_trait::async_trait;
+
+/// Repository trait for magic link token operations
+#[async_trait]
+#[allow(dead_code)]
+pub trait MagicLinkRepository: Send + Sync {
+    /// Store a new magic link token
+    async fn create_token(
+        &self,
+        token: MagicLinkToken,
+    ) -> Result<MagicLinkToken, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Find a magic link token by the hash of its raw value
+    async fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<MagicLinkToken>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Mark a token as used
+    ///
+    /// Returns `false` if the token was already used, so concurrent verifications of the
+    /// same link can only succeed once.
+    async fn mark_used(&self, id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Delete tokens that expired before the given time
+    async fn delete_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Count the tokens `delete_expired_tokens` would delete
+    async fn count_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Forwards to the shared repository
+#[async_trait]
+impl<T: MagicLinkRepository + ?Sized> MagicLinkRepository for std::sync::Arc<T> {
+    async fn create_token(
+        &self,
+        token: MagicLinkToken,
+    ) -> Result<MagicLinkToken, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).create_token(token).await
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<MagicLinkToken>, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).find_by_token_hash(token_hash).await
+    }
+
+    async fn mark_used(&self, id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).mark_used(id).await
+    }
+
+    async fn delete_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).delete_expired_tokens(expired_before).await
+    }
+
+    async fn count_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).count_expired_tokens(expired_before).await
+    }
+}
@@ -1,12 +1,49 @@
 pub mod account_deletion_token_repository;
+pub mod archive_repository;
+pub mod audit_log_repository;
 pub mod click_repository;
+pub mod domain_blacklist_repository;
+pub mod email_change_repository;
+pub mod email_outbox_repository;
+pub mod export_job_repository;
+pub mod magic_link_repository;
+pub mod notification_preferences_repository;
+pub mod organization_repository;
 pub mod password_reset_repository;
+pub mod push_outbox_repository;
+pub mod service_account_repository;
+pub mod session_repository;
+pub mod short_code_sequence_repository;
+pub mod url_cursor;
+pub mod url_metadata_repository;
 pub mod url_repository;
 pub mod user_repository;
+pub mod user_url_stats_repository;
 
 #[allow(unused_imports)]
 pub use account_deletion_token_repository::AccountDeletionTokenRepository;
-pub use click_repository::{ClickRepository, ClickStats, RepositoryError as ClickRepositoryError};
+pub use archive_repository::ArchiveRepository;
+pub use audit_log_repository::AuditLogRepository;
+pub use click_repository::{
+    ClickCountTotal, ClickDedupRatio, ClickRepository, ClickStats,
+    RepositoryError as ClickRepositoryError, UrlAnalyticsSummary, UtmAttributionRow,
+};
+pub use domain_blacklist_repository::DomainBlacklistRepository;
+pub use email_change_repository::EmailChangeRepository;
+pub use email_outbox_repository::EmailOutboxRepository;
+pub use export_job_repository::ExportJobRepository;
+pub use magic_link_repository::MagicLinkRepository;
+pub use notification_preferences_repository::{DigestRecipient, NotificationPreferencesRepository};
+pub use organization_repository::{
+    OrganizationRepository, RepositoryError as OrganizationRepositoryError,
+};
 pub use password_reset_repository::PasswordResetRepository;
-pub use url_repository::{RepositoryError, UrlRepository, UrlStats};
-pub use user_repository::UserRepository;
+pub use push_outbox_repository::PushOutboxRepository;
+pub use service_account_repository::ServiceAccountRepository;
+pub use session_repository::SessionRepository;
+pub use short_code_sequence_repository::ShortCodeSequenceRepository;
+pub use url_cursor::{CursorError, SortDirection, UrlCursor, UrlSortField};
+pub use url_metadata_repository::UrlMetadataRepository;
+pub use url_repository::{RepositoryError, UrlFilter, UrlPage, UrlRepository, UrlStats};
+pub use user_repository::{UserDataExport, UserRepository};
+pub use user_url_stats_repository::UserUrlStatsRepository;
@@ -0,0 +1,60 @@
+use crate::domain::entities::NotificationPreferences;
+use crate::infrastructure::email::EmailMessage;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+/// A user who may receive the expiry digest
+#[derive(Debug, Clone)]
+pub struct DigestRecipient {
+    pub username: String,
+    pub email: String,
+    pub preferences: NotificationPreferences,
+}
+
+/// Repository trait for notification preferences
+///
+/// Users who never saved preferences get [`NotificationPreferences::new`].
+#[async_trait]
+pub trait NotificationPreferencesRepository: Send + Sync {
+    /// Find a user's preferences
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Result<NotificationPreferences, RepositoryError>;
+
+    /// Store the digest and push settings of a user, keeping when the last digest was sent
+    async fn save(
+        &self,
+        preferences: &NotificationPreferences,
+    ) -> Result<NotificationPreferences, RepositoryError>;
+
+    /// Find the email address and preferences of the given users
+    async fn find_digest_recipients(
+        &self,
+        user_ids: &[i32],
+    ) -> Result<Vec<DigestRecipient>, RepositoryError>;
+
+    /// Find the push notification tokens of the devices a user registered
+    async fn find_device_tokens(&self, user_id: i32) -> Result<Vec<String>, RepositoryError>;
+
+    /// Record a digest as sent at `sent_at` unless one was already sent after `sent_after`
+    ///
+    /// The claimed digest is queued in the email outbox in the same transaction. Returns
+    /// `false`, queueing nothing, if another digest got there first, so concurrent runs send
+    /// it once.
+    async fn claim_digest(
+        &self,
+        user_id: i32,
+        sent_at: DateTime<Utc>,
+        sent_after: DateTime<Utc>,
+        digest: &EmailMessage,
+    ) -> Result<bool, RepositoryError>;
+}
+
+/// Repository errors
+#[derive(Error, Debug)]
+pub enum RepositoryError {
+    #[error("Database connection error: {0}")]
+    Connection(#[from] sqlx::Error),
+}
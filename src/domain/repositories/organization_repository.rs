@@ -0,0 +1,152 @@
+use crate::domain::entities::{
+    OrgRole, Organization, OrganizationMember, OrganizationWithMemberCount,
+};
+use async_trait::async_trait;
+use thiserror::Error;
+
+/// Repository trait for organization and membership operations
+#[async_trait]
+pub trait OrganizationRepository: Send + Sync {
+    /// Create an organization and add its owner as a member with the Owner role
+    async fn create_organization(
+        &self,
+        name: &str,
+        slug: &str,
+        owner_id: i32,
+    ) -> Result<Organization, RepositoryError>;
+
+    /// Find an organization by ID
+    async fn find_by_id(&self, id: i32) -> Result<Option<Organization>, RepositoryError>;
+
+    /// Find the organizations a user belongs to
+    async fn find_by_member(&self, user_id: i32) -> Result<Vec<Organization>, RepositoryError>;
+
+    /// Update an organization's name and slug
+    async fn update_organization(
+        &self,
+        id: i32,
+        name: &str,
+        slug: &str,
+    ) -> Result<Organization, RepositoryError>;
+
+    /// Delete an organization; its URLs stay with their creators
+    async fn delete_organization(&self, id: i32) -> Result<bool, RepositoryError>;
+
+    /// Find a user's membership in an organization
+    async fn find_member(
+        &self,
+        org_id: i32,
+        user_id: i32,
+    ) -> Result<Option<OrganizationMember>, RepositoryError>;
+
+    /// List the members of an organization
+    async fn list_members(&self, org_id: i32) -> Result<Vec<OrganizationMember>, RepositoryError>;
+
+    /// Add a user to an organization
+    async fn add_member(
+        &self,
+        org_id: i32,
+        user_id: i32,
+        role: OrgRole,
+    ) -> Result<OrganizationMember, RepositoryError>;
+
+    /// Remove a user from an organization
+    async fn remove_member(&self, org_id: i32, user_id: i32) -> Result<bool, RepositoryError>;
+
+    /// Count the URLs owned by an organization
+    async fn count_urls(&self, org_id: i32) -> Result<i64, RepositoryError>;
+
+    /// List all organizations with their member counts
+    async fn list_with_member_counts(
+        &self,
+    ) -> Result<Vec<OrganizationWithMemberCount>, RepositoryError>;
+}
+
+/// Forwards to the shared repository
+#[async_trait]
+impl<T: OrganizationRepository + ?Sized> OrganizationRepository for std::sync::Arc<T> {
+    async fn create_organization(
+        &self,
+        name: &str,
+        slug: &str,
+        owner_id: i32,
+    ) -> Result<Organization, RepositoryError> {
+        (**self).create_organization(name, slug, owner_id).await
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Organization>, RepositoryError> {
+        (**self).find_by_id(id).await
+    }
+
+    async fn find_by_member(&self, user_id: i32) -> Result<Vec<Organization>, RepositoryError> {
+        (**self).find_by_member(user_id).await
+    }
+
+    async fn update_organization(
+        &self,
+        id: i32,
+        name: &str,
+        slug: &str,
+    ) -> Result<Organization, RepositoryError> {
+        (**self).update_organization(id, name, slug).await
+    }
+
+    async fn delete_organization(&self, id: i32) -> Result<bool, RepositoryError> {
+        (**self).delete_organization(id).await
+    }
+
+    async fn find_member(
+        &self,
+        org_id: i32,
+        user_id: i32,
+    ) -> Result<Option<OrganizationMember>, RepositoryError> {
+        (**self).find_member(org_id, user_id).await
+    }
+
+    async fn list_members(&self, org_id: i32) -> Result<Vec<OrganizationMember>, RepositoryError> {
+        (**self).list_members(org_id).await
+    }
+
+    async fn add_member(
+        &self,
+        org_id: i32,
+        user_id: i32,
+        role: OrgRole,
+    ) -> Result<OrganizationMember, RepositoryError> {
+        (**self).add_member(org_id, user_id, role).await
+    }
+
+    async fn remove_member(&self, org_id: i32, user_id: i32) -> Result<bool, RepositoryError> {
+        (**self).remove_member(org_id, user_id).await
+    }
+
+    async fn count_urls(&self, org_id: i32) -> Result<i64, RepositoryError> {
+        (**self).count_urls(org_id).await
+    }
+
+    async fn list_with_member_counts(
+        &self,
+    ) -> Result<Vec<OrganizationWithMemberCount>, RepositoryError> {
+        (**self).list_with_member_counts().await
+    }
+}
+
+/// Repository errors
+#[allow(dead_code)]
+#[derive(Error, Debug)]
+pub enum RepositoryError {
+    #[error("Database connection error: {0}")]
+    Connection(#[from] sqlx::Error),
+
+    #[error("Organization not found")]
+    NotFound,
+
+    #[error("Organization slug already exists")]
+    DuplicateSlug,
+
+    #[error("User is already a member of this organization")]
+    AlreadyMember,
+
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+}
@@ -1,4 +1,5 @@
 use crate::domain::entities::PasswordResetToken;
+use crate::infrastructure::email::EmailMessage;
 use async_trait::async_trait;
 use thiserror::Error;
 
@@ -7,9 +8,14 @@ use thiserror::Error;
 #[allow(dead_code)]
 pub trait PasswordResetRepository: Send + Sync {
     /// Create a new password reset token
+    ///
+    /// Pending tokens of the same user are invalidated first, so a user has at most one usable
+    /// token; the schema enforces this with a partial unique index. `email` is queued in the
+    /// email outbox in the same transaction as the token.
     async fn create_token(
         &self,
         token: PasswordResetToken,
+        email: Option<&EmailMessage>,
     ) -> Result<PasswordResetToken, Box<dyn std::error::Error + Send + Sync>>;
 
     /// Find a password reset token by token string
@@ -36,18 +42,115 @@ pub trait PasswordResetRepository: Send + Sync {
         token: PasswordResetToken,
     ) -> Result<PasswordResetToken, Box<dyn std::error::Error + Send + Sync>>;
 
-    /// Delete expired tokens
+    /// Find tokens that expired before the given time
+    async fn find_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<PasswordResetToken>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Delete tokens that expired before the given time
     async fn delete_expired_tokens(
         &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Count the tokens `delete_expired_tokens` would delete
+    async fn count_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
     ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
 
-    /// Revoke all tokens for a user
-    async fn revoke_all_tokens_for_user(
+    /// Mark a token as used unless it already is
+    ///
+    /// Returns `false` if the token was already used, so only one of several concurrent
+    /// resets with the same token can succeed.
+    async fn mark_token_used(
+        &self,
+        token_id: i32,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Mark every pending token of a user as used, returning how many were invalidated
+    async fn invalidate_all_tokens_for_user(
         &self,
         user_id: i32,
     ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
 }
 
+/// Forwards to the shared repository
+#[async_trait]
+impl<T: PasswordResetRepository + ?Sized> PasswordResetRepository for std::sync::Arc<T> {
+    async fn create_token(
+        &self,
+        token: PasswordResetToken,
+        email: Option<&EmailMessage>,
+    ) -> Result<PasswordResetToken, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).create_token(token, email).await
+    }
+
+    async fn find_by_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<PasswordResetToken>, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).find_by_token(token).await
+    }
+
+    async fn find_active_tokens_for_user(
+        &self,
+        user_id: i32,
+    ) -> Result<Vec<PasswordResetToken>, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).find_active_tokens_for_user(user_id).await
+    }
+
+    async fn count_active_tokens_for_user(
+        &self,
+        user_id: i32,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).count_active_tokens_for_user(user_id).await
+    }
+
+    async fn update_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<PasswordResetToken, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).update_token(token).await
+    }
+
+    async fn find_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<PasswordResetToken>, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).find_expired_tokens(expired_before).await
+    }
+
+    async fn delete_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).delete_expired_tokens(expired_before).await
+    }
+
+    async fn count_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).count_expired_tokens(expired_before).await
+    }
+
+    async fn mark_token_used(
+        &self,
+        token_id: i32,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).mark_token_used(token_id).await
+    }
+
+    async fn invalidate_all_tokens_for_user(
+        &self,
+        user_id: i32,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).invalidate_all_tokens_for_user(user_id).await
+    }
+}
+
 /// Repository errors
 #[allow(dead_code)]
 #[derive(Error, Debug)]
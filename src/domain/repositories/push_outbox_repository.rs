@@ -0,0 +1,43 @@
+use crate::domain::entities::OutboxPush;
+use crate::infrastructure::push::PushMessage;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Repository trait for the outbox of push notifications waiting to be sent
+#[async_trait]
+pub trait PushOutboxRepository: Send + Sync {
+    /// Store a push to be sent by the outbox poller
+    ///
+    /// Returns `None` without storing anything when a push with the same `dedup_key` was
+    /// already queued for the device.
+    async fn enqueue(
+        &self,
+        message: &PushMessage,
+    ) -> Result<Option<OutboxPush>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Take up to `limit` pending pushes due at `now`, oldest first
+    ///
+    /// Taken pushes are not due again before `now + lease`, as for the email outbox.
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease: chrono::Duration,
+        limit: usize,
+    ) -> Result<Vec<OutboxPush>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Record that a push was sent
+    async fn mark_sent(
+        &self,
+        id: i64,
+        sent_at: DateTime<Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Record a failed attempt; the push is retried at `retry_at`, or given up without one
+    async fn mark_attempt_failed(
+        &self,
+        id: i64,
+        error: &str,
+        failed_at: DateTime<Utc>,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
@@ -0,0 +1,32 @@
+use crate::domain::entities::ServiceAccount;
+use async_trait::async_trait;
+use thiserror::Error;
+
+/// Repository trait for service accounts
+#[async_trait]
+pub trait ServiceAccountRepository: Send + Sync {
+    /// Create a service account authenticated by the API key with the given hash
+    async fn create_service_account(
+        &self,
+        name: &str,
+        api_key_hash: &str,
+        created_by: i32,
+    ) -> Result<ServiceAccount, RepositoryError>;
+
+    /// Find the service account owning an API key
+    async fn find_by_api_key_hash(
+        &self,
+        api_key_hash: &str,
+    ) -> Result<Option<ServiceAccount>, RepositoryError>;
+}
+
+/// Repository errors
+#[allow(dead_code)]
+#[derive(Error, Debug)]
+pub enum RepositoryError {
+    #[error("Database connection error: {0}")]
+    Connection(#[from] sqlx::Error),
+
+    #[error("A service account with this name already exists")]
+    DuplicateName,
+}
@@ -0,0 +1,52 @@
+use crate::domain::entities::Session;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Repository trait for login session operations
+#[async_trait]
+#[allow(dead_code)]
+pub trait SessionRepository: Send + Sync {
+    /// Store a new session
+    async fn create_session(
+        &self,
+        session: Session,
+    ) -> Result<Session, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Find a session by id, revoked or not
+    async fn find_by_id(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<Session>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Find a user's sessions that are not revoked and started after `created_after`,
+    /// most recently used first
+    async fn find_active_for_user(
+        &self,
+        user_id: i32,
+        created_after: DateTime<Utc>,
+    ) -> Result<Vec<Session>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Record that a session's token was used
+    async fn touch(
+        &self,
+        id: Uuid,
+        last_used_at: DateTime<Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Revoke one of a user's sessions
+    ///
+    /// Returns `false` if the user has no such active session.
+    async fn revoke(
+        &self,
+        id: Uuid,
+        user_id: i32,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Revoke every active session of a user except `keep`, returning how many were revoked
+    async fn revoke_all_except(
+        &self,
+        user_id: i32,
+        keep: Option<Uuid>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
+}
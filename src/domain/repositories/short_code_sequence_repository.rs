@@ -0,0 +1,9 @@
+use crate::domain::repositories::RepositoryError;
+use async_trait::async_trait;
+
+/// Counter behind sequential short codes
+#[async_trait]
+pub trait ShortCodeSequenceRepository: Send + Sync {
+    /// Next value of the counter, starting at 1; no value is returned twice
+    async fn next_value(&self) -> Result<u64, RepositoryError>;
+}
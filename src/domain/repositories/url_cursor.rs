@@ -0,0 +1,242 @@
+use crate::domain::entities::Url;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use thiserror::Error;
+
+/// Field a paginated URL listing is ordered by; ties are broken by URL id
+///
+/// Each field needs a `(user_id, <field>, id)` index for the keyset query to stay cheap.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UrlSortField {
+    #[default]
+    CreatedAt,
+    ShortCode,
+}
+
+impl UrlSortField {
+    /// Column holding the field in the `urls` table
+    pub fn column(self) -> &'static str {
+        match self {
+            UrlSortField::CreatedAt => "created_at",
+            UrlSortField::ShortCode => "short_code",
+        }
+    }
+
+    /// The field's value for a URL, as stored in a cursor
+    fn value_of(self, url: &Url) -> String {
+        match self {
+            UrlSortField::CreatedAt => url.created_at.to_rfc3339(),
+            UrlSortField::ShortCode => url.short_code.clone(),
+        }
+    }
+
+    /// Compare two URLs by this field, then by id
+    pub fn compare(self, a: &Url, b: &Url) -> Ordering {
+        let by_field = match self {
+            UrlSortField::CreatedAt => a.created_at.cmp(&b.created_at),
+            UrlSortField::ShortCode => a.short_code.cmp(&b.short_code),
+        };
+        by_field.then(a.id.cmp(&b.id))
+    }
+}
+
+/// Sort direction of a paginated listing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortDirection {
+    /// The opposite direction, used to walk a listing backwards from a cursor
+    pub fn reversed(self) -> Self {
+        match self {
+            SortDirection::Asc => SortDirection::Desc,
+            SortDirection::Desc => SortDirection::Asc,
+        }
+    }
+}
+
+/// Cursor errors
+#[derive(Error, Debug, PartialEq)]
+pub enum CursorError {
+    #[error("Malformed pagination cursor")]
+    Malformed,
+
+    #[error("Pagination cursor was issued for a different sort order")]
+    SortMismatch,
+}
+
+/// Position in a paginated URL listing: the sort value and id of the last URL returned
+///
+/// Clients receive it as opaque URL-safe base64 of its JSON form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UrlCursor {
+    pub sort: UrlSortField,
+    pub direction: SortDirection,
+    pub sort_value: String,
+    pub id: i32,
+}
+
+impl UrlCursor {
+    /// Cursor pointing just after `url` in a listing with the given order
+    pub fn after(url: &Url, sort: UrlSortField, direction: SortDirection) -> Self {
+        Self {
+            sort,
+            direction,
+            sort_value: sort.value_of(url),
+            id: url.id,
+        }
+    }
+
+    /// Encode the cursor pointing just after `url`
+    pub fn encode(url: &Url, sort: UrlSortField, direction: SortDirection) -> String {
+        let cursor = Self::after(url, sort, direction);
+        let json = serde_json::to_vec(&cursor).expect("cursor serializes to JSON");
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    /// Decode a cursor received from a client
+    pub fn decode(s: &str) -> Result<Self, CursorError> {
+        let json = URL_SAFE_NO_PAD
+            .decode(s)
+            .map_err(|_| CursorError::Malformed)?;
+        let cursor: Self = serde_json::from_slice(&json).map_err(|_| CursorError::Malformed)?;
+        if cursor.sort == UrlSortField::CreatedAt && cursor.created_at().is_none() {
+            return Err(CursorError::Malformed);
+        }
+        Ok(cursor)
+    }
+
+    /// Check that the cursor was issued for the same order it is used with
+    ///
+    /// A cursor from another order points at an unrelated position, so reusing it after the
+    /// client changed `sort` or `direction` would skip or repeat URLs.
+    pub fn ensure_order(
+        &self,
+        sort: UrlSortField,
+        direction: SortDirection,
+    ) -> Result<(), CursorError> {
+        if self.sort != sort || self.direction != direction {
+            return Err(CursorError::SortMismatch);
+        }
+        Ok(())
+    }
+
+    /// The same position in the listing read in the opposite direction
+    ///
+    /// URLs the cursor precedes in the reversed order are those before it in the original one.
+    pub fn reversed(mut self) -> Self {
+        self.direction = self.direction.reversed();
+        self
+    }
+
+    /// The sort value as a timestamp, for cursors of `created_at` listings
+    pub fn created_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.sort_value)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+
+    /// Whether `url` comes after the cursor position in the cursor's order
+    pub fn precedes(&self, url: &Url) -> bool {
+        let by_field = match self.sort {
+            UrlSortField::CreatedAt => self.created_at().map(|at| at.cmp(&url.created_at)),
+            UrlSortField::ShortCode => Some(self.sort_value.as_str().cmp(url.short_code.as_str())),
+        };
+        let ordering = by_field
+            .unwrap_or(Ordering::Less)
+            .then(self.id.cmp(&url.id));
+        match self.direction {
+            SortDirection::Asc => ordering == Ordering::Less,
+            SortDirection::Desc => ordering == Ordering::Greater,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::UrlStatus;
+
+    fn url(id: i32, short_code: &str) -> Url {
+        Url::new_with_timestamp(
+            id,
+            short_code.to_string(),
+            "https://example.com".to_string(),
+            None,
+            Some(1),
+            UrlStatus::Active,
+        )
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let url = url(42, "abc123");
+        let encoded = UrlCursor::encode(&url, UrlSortField::CreatedAt, SortDirection::Desc);
+        assert!(!encoded.contains('='));
+
+        let cursor = UrlCursor::decode(&encoded).unwrap();
+        assert_eq!(cursor.id, 42);
+        assert_eq!(cursor.created_at(), Some(url.created_at));
+        assert!(cursor
+            .ensure_order(UrlSortField::CreatedAt, SortDirection::Desc)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_cursor_rejects_other_order() {
+        let encoded =
+            UrlCursor::encode(&url(1, "abc"), UrlSortField::ShortCode, SortDirection::Asc);
+        let cursor = UrlCursor::decode(&encoded).unwrap();
+
+        assert_eq!(
+            cursor.ensure_order(UrlSortField::ShortCode, SortDirection::Desc),
+            Err(CursorError::SortMismatch)
+        );
+        assert_eq!(
+            cursor.ensure_order(UrlSortField::CreatedAt, SortDirection::Asc),
+            Err(CursorError::SortMismatch)
+        );
+    }
+
+    #[test]
+    fn test_malformed_cursor() {
+        assert_eq!(
+            UrlCursor::decode("not base64!"),
+            Err(CursorError::Malformed)
+        );
+        let not_a_cursor = URL_SAFE_NO_PAD.encode(b"{\"id\":1}");
+        assert_eq!(
+            UrlCursor::decode(&not_a_cursor),
+            Err(CursorError::Malformed)
+        );
+        let bad_timestamp = URL_SAFE_NO_PAD
+            .encode(br#"{"sort":"created_at","direction":"asc","sort_value":"yesterday","id":1}"#);
+        assert_eq!(
+            UrlCursor::decode(&bad_timestamp),
+            Err(CursorError::Malformed)
+        );
+    }
+
+    #[test]
+    fn test_precedes_breaks_ties_by_id() {
+        let cursor = UrlCursor::after(&url(5, "same"), UrlSortField::ShortCode, SortDirection::Asc);
+        assert!(cursor.precedes(&url(6, "same")));
+        assert!(!cursor.precedes(&url(4, "same")));
+        assert!(cursor.precedes(&url(1, "samf")));
+
+        let cursor = UrlCursor::after(
+            &url(5, "same"),
+            UrlSortField::ShortCode,
+            SortDirection::Desc,
+        );
+        assert!(cursor.precedes(&url(4, "same")));
+        assert!(!cursor.precedes(&url(6, "same")));
+    }
+}
@@ -0,0 +1,20 @@
+use crate::domain::entities::UrlMetadata;
+use async_trait::async_trait;
+use thiserror::Error;
+
+/// Repository trait for link preview metadata
+#[async_trait]
+pub trait UrlMetadataRepository: Send + Sync {
+    /// Store the metadata of a URL, replacing what was fetched before
+    async fn save_metadata(&self, metadata: &UrlMetadata) -> Result<(), RepositoryError>;
+
+    /// Find the metadata fetched for a URL
+    async fn find_by_url_id(&self, url_id: i32) -> Result<Option<UrlMetadata>, RepositoryError>;
+}
+
+/// Repository errors
+#[derive(Error, Debug)]
+pub enum RepositoryError {
+    #[error("Database connection error: {0}")]
+    Connection(#[from] sqlx::Error),
+}
@@ -1,5 +1,7 @@
-use crate::domain::entities::{ShortCode, Url, UrlStatus};
+use crate::domain::entities::{ShortCode, Url, UrlStatus, UrlWithClickCount};
+use crate::domain::repositories::url_cursor::{SortDirection, UrlCursor, UrlSortField};
 use async_trait::async_trait;
+use std::collections::HashMap;
 
 /// Repository trait for URL operations
 /// This defines the contract for URL data access without depending on specific implementations
@@ -7,32 +9,154 @@ use async_trait::async_trait;
 #[allow(dead_code)]
 pub trait UrlRepository: Send + Sync {
     /// Create a new URL record
+    ///
+    /// Fails with [`RepositoryError::DuplicateShortCode`] when the short code is taken.
     async fn create_url(
         &self,
         short_code: &ShortCode,
         original_url: &str,
         expiration_date: Option<chrono::DateTime<chrono::Utc>>,
         user_id: Option<i32>,
+        organization_id: Option<i32>,
         status: UrlStatus,
     ) -> Result<Url, RepositoryError>;
 
+    /// Create a new URL record, or return the URL already stored under the short code
+    ///
+    /// Concurrent inserts of the same short code all get the single stored row back; callers
+    /// decide whether that row is the link they asked for. The flag is true only for the call
+    /// that inserted the row.
+    ///
+    /// With `max_organization_urls`, fails with [`RepositoryError::OrganizationQuotaExceeded`]
+    /// when the organization already owns that many URLs. The URLs are counted under a lock
+    /// on the organization, so concurrent creations cannot go over the quota together.
+    #[allow(clippy::too_many_arguments)]
+    async fn create_url_idempotent(
+        &self,
+        short_code: &ShortCode,
+        original_url: &str,
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Option<i32>,
+        organization_id: Option<i32>,
+        max_organization_urls: Option<i64>,
+        status: UrlStatus,
+    ) -> Result<(Url, bool), RepositoryError>;
+
     /// Find a URL by short code
+    ///
+    /// Like every `find_*` method it skips deleted URLs unless it says otherwise.
+    ///
+    /// `force_primary` asks for the primary database when reads normally go to a replica
+    /// that may lag behind recent writes; implementations without replicas ignore it.
     async fn find_by_short_code(
         &self,
         short_code: &ShortCode,
+        force_primary: bool,
     ) -> Result<Option<Url>, RepositoryError>;
 
     /// Find URLs by user ID
-    async fn find_by_user_id(&self, user_id: i32) -> Result<Vec<Url>, RepositoryError>;
+    ///
+    /// With an organization, returns every URL owned by that organization instead; callers
+    /// must check that the user is a member.
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        organization_id: Option<i32>,
+    ) -> Result<Vec<Url>, RepositoryError>;
+
+    /// Find the URLs of one user pointing at exactly this destination, newest first
+    async fn find_by_original_url(
+        &self,
+        original_url: &str,
+        user_id: i32,
+    ) -> Result<Vec<Url>, RepositoryError>;
 
-    /// Check if a short code already exists
+    /// Find URLs of every user pointing at exactly this destination, newest first
+    ///
+    /// Administrative: shows whether a destination was shortened by several users.
+    async fn find_all_by_original_url(
+        &self,
+        original_url: &str,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError>;
+
+    /// Find the most recently deleted URL that had this short code
+    ///
+    /// Lets redirects tell a deleted link from one that never existed.
+    async fn find_deleted_by_short_code(
+        &self,
+        short_code: &ShortCode,
+    ) -> Result<Option<Url>, RepositoryError>;
+
+    /// Check if a short code is in use, as a URL's code or as an alias of one
+    ///
+    /// Codes of deleted URLs may be used again.
     async fn exists_by_short_code(&self, short_code: &ShortCode) -> Result<bool, RepositoryError>;
 
-    /// Delete a URL by ID
+    /// Find short codes in use that sound too much like `generated`
+    ///
+    /// Returns the URL codes and aliases whose [`ShortCode::phonetic_distance`] to `generated`
+    /// is below `threshold`.
+    async fn find_phonetically_similar_codes(
+        &self,
+        generated: &ShortCode,
+        threshold: f32,
+    ) -> Result<Vec<ShortCode>, RepositoryError>;
+
+    /// Give a URL a new short code, keeping the old one as an alias that still resolves
+    ///
+    /// Fails with `NotFound` when the URL does not exist.
+    async fn replace_short_code(
+        &self,
+        url_id: i32,
+        new_short_code: &ShortCode,
+    ) -> Result<(), RepositoryError>;
+
+    /// Rename a URL's short code, keeping the old one as a legacy alias that still resolves
+    ///
+    /// Fails with `AliasLimitReached` when the URL already has `max_aliases` aliases, with
+    /// `DuplicateShortCode` when the new short code is taken and with `NotFound` when the
+    /// URL does not exist.
+    async fn rename_short_code(
+        &self,
+        url_id: i32,
+        new_short_code: &ShortCode,
+        max_aliases: usize,
+    ) -> Result<Url, RepositoryError>;
+
+    /// Delete a URL by ID, keeping the row with `deleted_at` set and status `deleted`
+    ///
+    /// The row is removed for good by the cleanup after the deleted URL retention.
     async fn delete_by_id(&self, id: i32, user_id: Option<i32>) -> Result<bool, RepositoryError>;
 
-    /// Update a URL
-    async fn update_url(&self, url: &Url) -> Result<Url, RepositoryError>;
+    /// Remove a URL and its clicks for good, whether or not it was deleted first
+    ///
+    /// Administrative: not scoped to an owner.
+    async fn permanently_delete_by_id(&self, id: i32) -> Result<bool, RepositoryError>;
+
+    /// Find URLs deleted before the given time
+    async fn find_deleted_before(
+        &self,
+        deleted_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Url>, RepositoryError>;
+
+    /// Move a URL from one owner to another, keeping its clicks and conversions
+    ///
+    /// Returns `None` when the URL does not exist or is not owned by `from_user_id`.
+    async fn transfer_owner(
+        &self,
+        id: i32,
+        from_user_id: i32,
+        to_user_id: i32,
+    ) -> Result<Option<Url>, RepositoryError>;
+
+    /// Find a URL by ID
+    async fn find_by_id(&self, id: i32) -> Result<Option<Url>, RepositoryError>;
+
+    /// Update a URL if it is still at `expected_version`, incrementing its version
+    ///
+    /// Fails with `ConflictingUpdate` when another update got there first.
+    async fn update_url(&self, url: &Url, expected_version: i64) -> Result<Url, RepositoryError>;
 
     /// Get URL statistics
     async fn get_stats(&self, user_id: Option<i32>) -> Result<UrlStats, RepositoryError>;
@@ -46,10 +170,28 @@ pub trait UrlRepository: Send + Sync {
     /// Find expired URLs
     async fn find_expired_urls(&self) -> Result<Vec<Url>, RepositoryError>;
 
-    /// Delete expired URLs
-    async fn delete_expired_urls(&self) -> Result<u64, RepositoryError>;
+    /// Delete URLs that expired before the given time
+    async fn delete_expired_urls(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError>;
+
+    /// Archive URLs that expired before the given time, keeping their clicks
+    ///
+    /// URLs that are already archived are left alone. Returns the URLs archived, as they were
+    /// before, so callers know the status each one left.
+    async fn archive_expired_urls(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Url>, RepositoryError>;
+
+    /// Count the URLs `archive_expired_urls` would archive, without changing them
+    async fn count_expired_urls_to_archive(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError>;
 
-    /// Soft delete a URL by setting status to inactive
+    /// Deactivate a URL by setting status to inactive
     async fn soft_delete_by_id(
         &self,
         id: i32,
@@ -70,6 +212,25 @@ pub trait UrlRepository: Send + Sync {
         user_id: Option<i32>,
     ) -> Result<Vec<Url>, RepositoryError>;
 
+    /// Find one page of the URLs of every user with the given status, ordered by ID
+    ///
+    /// Keyset pagination: the page starts right after the URL with ID `after_id`. Also
+    /// returns the `after_id` of the next page, `None` on the last one.
+    async fn find_by_status_paginated(
+        &self,
+        status: UrlStatus,
+        after_id: Option<i32>,
+        limit: usize,
+    ) -> Result<(Vec<Url>, Option<i32>), RepositoryError>;
+
+    /// Find URLs created in the last `created_within_hours` hours that already have at least
+    /// `min_click_count` recorded clicks, most clicked first
+    async fn find_suspicious_urls(
+        &self,
+        min_click_count: i64,
+        created_within_hours: u8,
+    ) -> Result<Vec<Url>, RepositoryError>;
+
     /// Batch deactivate URLs by IDs
     async fn batch_deactivate_urls(
         &self,
@@ -84,7 +245,7 @@ pub trait UrlRepository: Send + Sync {
         user_id: Option<i32>,
     ) -> Result<BatchOperationResult, RepositoryError>;
 
-    /// Batch delete URLs by IDs
+    /// Batch delete URLs by IDs, keeping the rows like [`UrlRepository::delete_by_id`]
     async fn batch_delete_urls(
         &self,
         url_ids: &[i32],
@@ -99,13 +260,465 @@ pub trait UrlRepository: Send + Sync {
         user_id: Option<i32>,
     ) -> Result<BatchOperationResult, RepositoryError>;
 
-    /// Batch update URL expiration dates
+    /// Batch update URL expiration dates; `None` removes the expiration
     async fn batch_update_expiration(
         &self,
         url_ids: &[i32],
         expiration_date: Option<chrono::DateTime<chrono::Utc>>,
         user_id: Option<i32>,
     ) -> Result<BatchOperationResult, RepositoryError>;
+
+    /// Find a user's URLs ordered by click count, most clicked first
+    async fn find_most_clicked(
+        &self,
+        user_id: i32,
+        limit: usize,
+    ) -> Result<Vec<UrlWithClickCount>, RepositoryError>;
+
+    /// Find a user's most recently created URLs
+    async fn find_recently_created(
+        &self,
+        user_id: i32,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError>;
+
+    /// Find a user's URLs changed after `since`, most recently changed first
+    async fn find_recently_modified(
+        &self,
+        user_id: i32,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError>;
+
+    /// Find one page of URLs, optionally of one user, in the given order
+    ///
+    /// Only URLs with the given status are returned; without one, all but archived URLs are.
+    /// Keyset pagination: the page starts right after `after_cursor`, which callers must have
+    /// checked was issued for the same `sort` and `direction`.
+    async fn find_paginated(
+        &self,
+        user_id: Option<i32>,
+        status: Option<UrlStatus>,
+        sort: UrlSortField,
+        direction: SortDirection,
+        after_cursor: Option<&UrlCursor>,
+        limit: usize,
+    ) -> Result<UrlPage, RepositoryError>;
+
+    /// Count the URLs of a user a listing filtered by `filter` shows across all its pages
+    async fn count_by_filter(
+        &self,
+        filter: &UrlFilter,
+        user_id: i32,
+    ) -> Result<i64, RepositoryError>;
+
+    /// Count the recorded clicks of each of `url_ids` in one query
+    ///
+    /// URLs without clicks may be missing from the result.
+    async fn count_clicks_for_urls(
+        &self,
+        url_ids: &[i32],
+    ) -> Result<HashMap<i32, i64>, RepositoryError>;
+}
+
+/// Lets a shared repository such as `Arc<dyn UrlRepository>` be used wherever a `UrlRepository` is expected
+#[async_trait]
+impl<T: UrlRepository + ?Sized> UrlRepository for std::sync::Arc<T> {
+    async fn create_url(
+        &self,
+        short_code: &ShortCode,
+        original_url: &str,
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Option<i32>,
+        organization_id: Option<i32>,
+        status: UrlStatus,
+    ) -> Result<Url, RepositoryError> {
+        (**self)
+            .create_url(
+                short_code,
+                original_url,
+                expiration_date,
+                user_id,
+                organization_id,
+                status,
+            )
+            .await
+    }
+
+    async fn create_url_idempotent(
+        &self,
+        short_code: &ShortCode,
+        original_url: &str,
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Option<i32>,
+        organization_id: Option<i32>,
+        max_organization_urls: Option<i64>,
+        status: UrlStatus,
+    ) -> Result<(Url, bool), RepositoryError> {
+        (**self)
+            .create_url_idempotent(
+                short_code,
+                original_url,
+                expiration_date,
+                user_id,
+                organization_id,
+                max_organization_urls,
+                status,
+            )
+            .await
+    }
+
+    async fn find_by_short_code(
+        &self,
+        short_code: &ShortCode,
+        force_primary: bool,
+    ) -> Result<Option<Url>, RepositoryError> {
+        (**self).find_by_short_code(short_code, force_primary).await
+    }
+
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        organization_id: Option<i32>,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        (**self).find_by_user_id(user_id, organization_id).await
+    }
+
+    async fn find_by_original_url(
+        &self,
+        original_url: &str,
+        user_id: i32,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        (**self).find_by_original_url(original_url, user_id).await
+    }
+
+    async fn find_all_by_original_url(
+        &self,
+        original_url: &str,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        (**self).find_all_by_original_url(original_url, limit).await
+    }
+
+    async fn find_deleted_by_short_code(
+        &self,
+        short_code: &ShortCode,
+    ) -> Result<Option<Url>, RepositoryError> {
+        (**self).find_deleted_by_short_code(short_code).await
+    }
+
+    async fn exists_by_short_code(&self, short_code: &ShortCode) -> Result<bool, RepositoryError> {
+        (**self).exists_by_short_code(short_code).await
+    }
+
+    async fn find_phonetically_similar_codes(
+        &self,
+        generated: &ShortCode,
+        threshold: f32,
+    ) -> Result<Vec<ShortCode>, RepositoryError> {
+        (**self)
+            .find_phonetically_similar_codes(generated, threshold)
+            .await
+    }
+
+    async fn replace_short_code(
+        &self,
+        url_id: i32,
+        new_short_code: &ShortCode,
+    ) -> Result<(), RepositoryError> {
+        (**self).replace_short_code(url_id, new_short_code).await
+    }
+
+    async fn rename_short_code(
+        &self,
+        url_id: i32,
+        new_short_code: &ShortCode,
+        max_aliases: usize,
+    ) -> Result<Url, RepositoryError> {
+        (**self)
+            .rename_short_code(url_id, new_short_code, max_aliases)
+            .await
+    }
+
+    async fn delete_by_id(&self, id: i32, user_id: Option<i32>) -> Result<bool, RepositoryError> {
+        (**self).delete_by_id(id, user_id).await
+    }
+
+    async fn permanently_delete_by_id(&self, id: i32) -> Result<bool, RepositoryError> {
+        (**self).permanently_delete_by_id(id).await
+    }
+
+    async fn find_deleted_before(
+        &self,
+        deleted_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        (**self).find_deleted_before(deleted_before).await
+    }
+
+    async fn transfer_owner(
+        &self,
+        id: i32,
+        from_user_id: i32,
+        to_user_id: i32,
+    ) -> Result<Option<Url>, RepositoryError> {
+        (**self).transfer_owner(id, from_user_id, to_user_id).await
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Url>, RepositoryError> {
+        (**self).find_by_id(id).await
+    }
+
+    async fn update_url(&self, url: &Url, expected_version: i64) -> Result<Url, RepositoryError> {
+        (**self).update_url(url, expected_version).await
+    }
+
+    async fn get_stats(&self, user_id: Option<i32>) -> Result<UrlStats, RepositoryError> {
+        (**self).get_stats(user_id).await
+    }
+
+    async fn find_urls_expiring_soon(
+        &self,
+        duration: chrono::Duration,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        (**self).find_urls_expiring_soon(duration).await
+    }
+
+    async fn find_expired_urls(&self) -> Result<Vec<Url>, RepositoryError> {
+        (**self).find_expired_urls().await
+    }
+
+    async fn delete_expired_urls(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        (**self).delete_expired_urls(expired_before).await
+    }
+
+    async fn archive_expired_urls(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        (**self).archive_expired_urls(expired_before).await
+    }
+
+    async fn count_expired_urls_to_archive(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        (**self).count_expired_urls_to_archive(expired_before).await
+    }
+
+    async fn soft_delete_by_id(
+        &self,
+        id: i32,
+        user_id: Option<i32>,
+    ) -> Result<bool, RepositoryError> {
+        (**self).soft_delete_by_id(id, user_id).await
+    }
+
+    async fn reactivate_by_id(
+        &self,
+        id: i32,
+        user_id: Option<i32>,
+    ) -> Result<bool, RepositoryError> {
+        (**self).reactivate_by_id(id, user_id).await
+    }
+
+    async fn find_by_status(
+        &self,
+        status: UrlStatus,
+        user_id: Option<i32>,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        (**self).find_by_status(status, user_id).await
+    }
+
+    async fn find_by_status_paginated(
+        &self,
+        status: UrlStatus,
+        after_id: Option<i32>,
+        limit: usize,
+    ) -> Result<(Vec<Url>, Option<i32>), RepositoryError> {
+        (**self)
+            .find_by_status_paginated(status, after_id, limit)
+            .await
+    }
+
+    async fn find_suspicious_urls(
+        &self,
+        min_click_count: i64,
+        created_within_hours: u8,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        (**self)
+            .find_suspicious_urls(min_click_count, created_within_hours)
+            .await
+    }
+
+    async fn batch_deactivate_urls(
+        &self,
+        url_ids: &[i32],
+        user_id: Option<i32>,
+    ) -> Result<BatchOperationResult, RepositoryError> {
+        (**self).batch_deactivate_urls(url_ids, user_id).await
+    }
+
+    async fn batch_reactivate_urls(
+        &self,
+        url_ids: &[i32],
+        user_id: Option<i32>,
+    ) -> Result<BatchOperationResult, RepositoryError> {
+        (**self).batch_reactivate_urls(url_ids, user_id).await
+    }
+
+    async fn batch_delete_urls(
+        &self,
+        url_ids: &[i32],
+        user_id: Option<i32>,
+    ) -> Result<BatchOperationResult, RepositoryError> {
+        (**self).batch_delete_urls(url_ids, user_id).await
+    }
+
+    async fn batch_update_status(
+        &self,
+        url_ids: &[i32],
+        status: UrlStatus,
+        user_id: Option<i32>,
+    ) -> Result<BatchOperationResult, RepositoryError> {
+        (**self).batch_update_status(url_ids, status, user_id).await
+    }
+
+    async fn batch_update_expiration(
+        &self,
+        url_ids: &[i32],
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Option<i32>,
+    ) -> Result<BatchOperationResult, RepositoryError> {
+        (**self)
+            .batch_update_expiration(url_ids, expiration_date, user_id)
+            .await
+    }
+
+    async fn find_most_clicked(
+        &self,
+        user_id: i32,
+        limit: usize,
+    ) -> Result<Vec<UrlWithClickCount>, RepositoryError> {
+        (**self).find_most_clicked(user_id, limit).await
+    }
+
+    async fn find_recently_created(
+        &self,
+        user_id: i32,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        (**self).find_recently_created(user_id, limit).await
+    }
+
+    async fn find_recently_modified(
+        &self,
+        user_id: i32,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        (**self).find_recently_modified(user_id, since, limit).await
+    }
+
+    async fn find_paginated(
+        &self,
+        user_id: Option<i32>,
+        status: Option<UrlStatus>,
+        sort: UrlSortField,
+        direction: SortDirection,
+        after_cursor: Option<&UrlCursor>,
+        limit: usize,
+    ) -> Result<UrlPage, RepositoryError> {
+        (**self)
+            .find_paginated(user_id, status, sort, direction, after_cursor, limit)
+            .await
+    }
+
+    async fn count_by_filter(
+        &self,
+        filter: &UrlFilter,
+        user_id: i32,
+    ) -> Result<i64, RepositoryError> {
+        (**self).count_by_filter(filter, user_id).await
+    }
+
+    async fn count_clicks_for_urls(
+        &self,
+        url_ids: &[i32],
+    ) -> Result<HashMap<i32, i64>, RepositoryError> {
+        (**self).count_clicks_for_urls(url_ids).await
+    }
+}
+
+/// Conditions restricting which URLs a listing shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UrlFilter {
+    /// Only URLs with this status; without one, all but archived URLs
+    pub status: Option<UrlStatus>,
+}
+
+impl UrlFilter {
+    /// Whether a listing with this filter shows `url`
+    pub fn matches(&self, url: &Url) -> bool {
+        url.status.is_listed_under(self.status)
+    }
+}
+
+/// One page of a keyset-paginated URL listing
+#[derive(Debug, Clone)]
+pub struct UrlPage {
+    pub urls: Vec<Url>,
+    /// Cursor of the next page; `None` on the last page
+    pub next_cursor: Option<String>,
+    /// Cursor of the previous page; `None` on the first page
+    pub prev_cursor: Option<String>,
+}
+
+impl UrlPage {
+    /// Build a page from up to `limit + 1` URLs in listing order
+    ///
+    /// The extra URL only signals that another page exists and is dropped.
+    pub fn from_rows(
+        mut urls: Vec<Url>,
+        limit: usize,
+        sort: UrlSortField,
+        direction: SortDirection,
+    ) -> Self {
+        let next_cursor = if urls.len() > limit {
+            urls.truncate(limit);
+            urls.last()
+                .map(|url| UrlCursor::encode(url, sort, direction))
+        } else {
+            None
+        };
+        Self {
+            urls,
+            next_cursor,
+            prev_cursor: None,
+        }
+    }
+
+    /// Paginate URLs held in memory the same way the keyset query does
+    pub fn paginate(
+        urls: impl IntoIterator<Item = Url>,
+        sort: UrlSortField,
+        direction: SortDirection,
+        after_cursor: Option<&UrlCursor>,
+        limit: usize,
+    ) -> Self {
+        let mut urls: Vec<Url> = urls
+            .into_iter()
+            .filter(|url| after_cursor.is_none_or(|cursor| cursor.precedes(url)))
+            .collect();
+        urls.sort_by(|a, b| match direction {
+            SortDirection::Asc => sort.compare(a, b),
+            SortDirection::Desc => sort.compare(b, a),
+        });
+        urls.truncate(limit + 1);
+        Self::from_rows(urls, limit, sort, direction)
+    }
 }
 
 /// Statistics about URLs  
@@ -115,6 +728,10 @@ pub struct UrlStats {
     pub total_urls: i64,
     pub total_clicks: i64,
     pub unique_short_codes: i64,
+    /// Approximate number of distinct visitors across the URLs (HyperLogLog estimate)
+    pub estimated_unique_visitors: i64,
+    /// URLs archived after expiring; included in `total_urls`
+    pub archived_url_count: i64,
 }
 
 /// Result of a batch operation
@@ -138,8 +755,11 @@ pub struct BatchItemResult {
 #[allow(dead_code)]
 #[derive(Debug, thiserror::Error)]
 pub enum RepositoryError {
-    #[error("Database connection error: {0}")]
-    Connection(#[from] sqlx::Error),
+    #[error("Database connection error: {}", connection_error_message(.0))]
+    Connection(#[source] sqlx::Error),
+
+    #[error("Query exceeded its statement timeout")]
+    QueryTimeout,
 
     #[error("URL not found")]
     NotFound,
@@ -147,6 +767,15 @@ pub enum RepositoryError {
     #[error("Short code already exists")]
     DuplicateShortCode,
 
+    #[error("URL was modified concurrently (current version {current_version})")]
+    ConflictingUpdate { current_version: i64 },
+
+    #[error("URL already has {limit} short code aliases, the most allowed")]
+    AliasLimitReached { limit: usize },
+
+    #[error("Organization already owns {limit} URLs, the most allowed")]
+    OrganizationQuotaExceeded { limit: i64 },
+
     #[error("Permission denied: {0}")]
     PermissionDenied(String),
 
@@ -157,6 +786,33 @@ pub enum RepositoryError {
     Internal(String),
 }
 
+/// Describe a connection error, spelling out pool exhaustion
+fn connection_error_message(error: &sqlx::Error) -> String {
+    match error {
+        sqlx::Error::PoolTimedOut => "no pooled connection became free within \
+             database.pool_acquire_timeout_ms; the connection pool is exhausted"
+            .to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// SQLSTATE of a statement cancelled by `statement_timeout`
+const QUERY_CANCELED: &str = "57014";
+
+impl From<sqlx::Error> for RepositoryError {
+    fn from(error: sqlx::Error) -> Self {
+        let is_timeout = error
+            .as_database_error()
+            .and_then(|e| e.code())
+            .is_some_and(|code| code == QUERY_CANCELED);
+        if is_timeout {
+            RepositoryError::QueryTimeout
+        } else {
+            RepositoryError::Connection(error)
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -183,6 +839,7 @@ pub mod tests {
             original_url: &str,
             expiration_date: Option<chrono::DateTime<chrono::Utc>>,
             user_id: Option<i32>,
+            organization_id: Option<i32>,
             status: UrlStatus,
         ) -> Result<Url, RepositoryError> {
             let mut urls = self.urls.lock().unwrap();
@@ -194,14 +851,44 @@ pub mod tests {
                 expiration_date,
                 user_id,
                 status,
-            );
+            )
+            .with_organization(organization_id);
             urls.push(url.clone());
             Ok(url)
         }
 
+        async fn create_url_idempotent(
+            &self,
+            short_code: &ShortCode,
+            original_url: &str,
+            expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+            user_id: Option<i32>,
+            organization_id: Option<i32>,
+            _max_organization_urls: Option<i64>,
+            status: UrlStatus,
+        ) -> Result<(Url, bool), RepositoryError> {
+            let mut urls = self.urls.lock().unwrap();
+            if let Some(existing) = urls.iter().find(|url| url.short_code == short_code.value()) {
+                return Ok((existing.clone(), false));
+            }
+            let id = (urls.len() + 1) as i32;
+            let url = Url::new_with_timestamp(
+                id,
+                short_code.value().to_string(),
+                original_url.to_string(),
+                expiration_date,
+                user_id,
+                status,
+            )
+            .with_organization(organization_id);
+            urls.push(url.clone());
+            Ok((url, true))
+        }
+
         async fn find_by_short_code(
             &self,
             short_code: &ShortCode,
+            _force_primary: bool,
         ) -> Result<Option<Url>, RepositoryError> {
             let urls = self.urls.lock().unwrap();
             Ok(urls
@@ -210,11 +897,47 @@ pub mod tests {
                 .cloned())
         }
 
-        async fn find_by_user_id(&self, user_id: i32) -> Result<Vec<Url>, RepositoryError> {
+        async fn find_by_user_id(
+            &self,
+            user_id: i32,
+            organization_id: Option<i32>,
+        ) -> Result<Vec<Url>, RepositoryError> {
             let urls = self.urls.lock().unwrap();
             Ok(urls
                 .iter()
-                .filter(|u| u.user_id == Some(user_id))
+                .filter(|u| match organization_id {
+                    Some(org_id) => u.organization_id == Some(org_id),
+                    None => u.user_id == Some(user_id),
+                })
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_original_url(
+            &self,
+            original_url: &str,
+            user_id: i32,
+        ) -> Result<Vec<Url>, RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .rev()
+                .filter(|u| u.original_url == original_url && u.user_id == Some(user_id))
+                .cloned()
+                .collect())
+        }
+
+        async fn find_all_by_original_url(
+            &self,
+            original_url: &str,
+            limit: usize,
+        ) -> Result<Vec<Url>, RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .rev()
+                .filter(|u| u.original_url == original_url)
+                .take(limit)
                 .cloned()
                 .collect())
         }
@@ -227,6 +950,48 @@ pub mod tests {
             Ok(urls.iter().any(|u| u.short_code == short_code.value()))
         }
 
+        async fn find_phonetically_similar_codes(
+            &self,
+            generated: &ShortCode,
+            threshold: f32,
+        ) -> Result<Vec<ShortCode>, RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .map(|u| ShortCode::from_string_unchecked(u.short_code.clone()))
+                .filter(|code| generated.phonetic_distance(code) < threshold)
+                .collect())
+        }
+
+        async fn replace_short_code(
+            &self,
+            url_id: i32,
+            new_short_code: &ShortCode,
+        ) -> Result<(), RepositoryError> {
+            let mut urls = self.urls.lock().unwrap();
+            let url = urls
+                .iter_mut()
+                .find(|u| u.id == url_id)
+                .ok_or(RepositoryError::NotFound)?;
+            url.short_code = new_short_code.value().to_string();
+            Ok(())
+        }
+
+        async fn rename_short_code(
+            &self,
+            url_id: i32,
+            new_short_code: &ShortCode,
+            _max_aliases: usize,
+        ) -> Result<Url, RepositoryError> {
+            let mut urls = self.urls.lock().unwrap();
+            let url = urls
+                .iter_mut()
+                .find(|u| u.id == url_id)
+                .ok_or(RepositoryError::NotFound)?;
+            url.short_code = new_short_code.value().to_string();
+            Ok(url.clone())
+        }
+
         async fn delete_by_id(
             &self,
             id: i32,
@@ -241,10 +1006,63 @@ pub mod tests {
             }
         }
 
-        async fn update_url(&self, url: &Url) -> Result<Url, RepositoryError> {
+        async fn find_deleted_by_short_code(
+            &self,
+            _short_code: &ShortCode,
+        ) -> Result<Option<Url>, RepositoryError> {
+            Ok(None)
+        }
+
+        async fn permanently_delete_by_id(&self, id: i32) -> Result<bool, RepositoryError> {
+            let mut urls = self.urls.lock().unwrap();
+            let before = urls.len();
+            urls.retain(|u| u.id != id);
+            Ok(urls.len() < before)
+        }
+
+        async fn find_deleted_before(
+            &self,
+            _deleted_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<Url>, RepositoryError> {
+            Ok(vec![])
+        }
+
+        async fn transfer_owner(
+            &self,
+            id: i32,
+            from_user_id: i32,
+            to_user_id: i32,
+        ) -> Result<Option<Url>, RepositoryError> {
+            let mut urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter_mut()
+                .find(|u| u.id == id && u.user_id == Some(from_user_id))
+                .map(|url| {
+                    url.user_id = Some(to_user_id);
+                    url.clone()
+                }))
+        }
+
+        async fn find_by_id(&self, id: i32) -> Result<Option<Url>, RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls.iter().find(|u| u.id == id).cloned())
+        }
+
+        async fn update_url(
+            &self,
+            url: &Url,
+            expected_version: i64,
+        ) -> Result<Url, RepositoryError> {
             let mut urls = self.urls.lock().unwrap();
             if let Some(existing) = urls.iter_mut().find(|u| u.id == url.id) {
+                if existing.version != expected_version {
+                    return Err(RepositoryError::ConflictingUpdate {
+                        current_version: existing.version,
+                    });
+                }
                 *existing = url.clone();
+                existing.version = expected_version + 1;
+                existing.updated_at = chrono::Utc::now();
                 Ok(existing.clone())
             } else {
                 Err(RepositoryError::NotFound)
@@ -263,6 +1081,8 @@ pub mod tests {
                 total_urls: filtered_urls.len() as i64,
                 total_clicks: 0, // Mock value
                 unique_short_codes: filtered_urls.len() as i64,
+                estimated_unique_visitors: 0,
+                archived_url_count: 0,
             })
         }
 
@@ -301,16 +1121,47 @@ pub mod tests {
             Ok(expired)
         }
 
-        async fn delete_expired_urls(&self) -> Result<u64, RepositoryError> {
+        async fn delete_expired_urls(
+            &self,
+            expired_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<u64, RepositoryError> {
             let mut urls = self.urls.lock().unwrap();
             let initial_count = urls.len();
 
-            urls.retain(|url| !url.is_expired());
+            urls.retain(|url| url.expiration_date.is_none_or(|e| e > expired_before));
 
             let deleted_count = initial_count - urls.len();
             Ok(deleted_count as u64)
         }
 
+        async fn archive_expired_urls(
+            &self,
+            expired_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<Url>, RepositoryError> {
+            let mut urls = self.urls.lock().unwrap();
+            let mut archived = Vec::new();
+            for url in urls.iter_mut().filter(|url| {
+                !url.is_archived() && url.expiration_date.is_some_and(|e| e <= expired_before)
+            }) {
+                archived.push(url.clone());
+                url.status = UrlStatus::Archived;
+            }
+            Ok(archived)
+        }
+
+        async fn count_expired_urls_to_archive(
+            &self,
+            expired_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<u64, RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .filter(|url| {
+                    !url.is_archived() && url.expiration_date.is_some_and(|e| e <= expired_before)
+                })
+                .count() as u64)
+        }
+
         async fn soft_delete_by_id(
             &self,
             id: i32,
@@ -353,6 +1204,23 @@ pub mod tests {
             Ok(filtered_urls)
         }
 
+        async fn find_by_status_paginated(
+            &self,
+            _status: UrlStatus,
+            _after_id: Option<i32>,
+            _limit: usize,
+        ) -> Result<(Vec<Url>, Option<i32>), RepositoryError> {
+            Ok((Vec::new(), None))
+        }
+
+        async fn find_suspicious_urls(
+            &self,
+            _min_click_count: i64,
+            _created_within_hours: u8,
+        ) -> Result<Vec<Url>, RepositoryError> {
+            Ok(Vec::new())
+        }
+
         async fn batch_deactivate_urls(
             &self,
             url_ids: &[i32],
@@ -554,6 +1422,105 @@ pub mod tests {
                 results,
             })
         }
+
+        async fn find_most_clicked(
+            &self,
+            user_id: i32,
+            limit: usize,
+        ) -> Result<
+            Vec<crate::domain::entities::UrlWithClickCount>,
+            crate::domain::repositories::RepositoryError,
+        > {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .filter(|url| url.user_id == Some(user_id))
+                .take(limit)
+                .map(|url| crate::domain::entities::UrlWithClickCount::new(url.clone(), 0))
+                .collect())
+        }
+
+        async fn find_recently_created(
+            &self,
+            user_id: i32,
+            limit: usize,
+        ) -> Result<Vec<crate::domain::entities::Url>, crate::domain::repositories::RepositoryError>
+        {
+            let urls = self.urls.lock().unwrap();
+            let mut user_urls: Vec<_> = urls
+                .iter()
+                .filter(|url| url.user_id == Some(user_id))
+                .cloned()
+                .collect();
+            user_urls.sort_by_key(|url| std::cmp::Reverse(url.created_at));
+            user_urls.truncate(limit);
+            Ok(user_urls)
+        }
+
+        async fn find_recently_modified(
+            &self,
+            user_id: i32,
+            since: chrono::DateTime<chrono::Utc>,
+            limit: usize,
+        ) -> Result<Vec<crate::domain::entities::Url>, crate::domain::repositories::RepositoryError>
+        {
+            let urls = self.urls.lock().unwrap();
+            let mut user_urls: Vec<_> = urls
+                .iter()
+                .filter(|url| url.user_id == Some(user_id) && url.updated_at > since)
+                .cloned()
+                .collect();
+            user_urls.sort_by_key(|url| std::cmp::Reverse(url.updated_at));
+            user_urls.truncate(limit);
+            Ok(user_urls)
+        }
+
+        async fn find_paginated(
+            &self,
+            user_id: Option<i32>,
+            status: Option<UrlStatus>,
+            sort: crate::domain::repositories::UrlSortField,
+            direction: crate::domain::repositories::SortDirection,
+            after_cursor: Option<&crate::domain::repositories::UrlCursor>,
+            limit: usize,
+        ) -> Result<
+            crate::domain::repositories::UrlPage,
+            crate::domain::repositories::RepositoryError,
+        > {
+            let urls = self.urls.lock().unwrap();
+            let urls = urls
+                .iter()
+                .filter(|url| user_id.is_none_or(|id| url.user_id == Some(id)))
+                .filter(|url| url.status.is_listed_under(status))
+                .cloned();
+            Ok(crate::domain::repositories::UrlPage::paginate(
+                urls,
+                sort,
+                direction,
+                after_cursor,
+                limit,
+            ))
+        }
+
+        async fn count_by_filter(
+            &self,
+            filter: &crate::domain::repositories::UrlFilter,
+            user_id: i32,
+        ) -> Result<i64, crate::domain::repositories::RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .filter(|url| url.user_id == Some(user_id) && filter.matches(url))
+                .count() as i64)
+        }
+
+        async fn count_clicks_for_urls(
+            &self,
+            _url_ids: &[i32],
+        ) -> Result<std::collections::HashMap<i32, i64>, crate::domain::repositories::RepositoryError>
+        {
+            Ok(std::collections::HashMap::new())
+        }
     }
 
     #[tokio::test]
@@ -567,6 +1534,7 @@ pub mod tests {
                 "https://example.com",
                 None,
                 None,
+                None,
                 UrlStatus::Active,
             )
             .await
@@ -574,7 +1542,7 @@ pub mod tests {
         assert_eq!(url.short_code, "abc123");
         assert_eq!(url.original_url, "https://example.com");
 
-        let found = repo.find_by_short_code(&short_code).await.unwrap();
+        let found = repo.find_by_short_code(&short_code, false).await.unwrap();
         assert!(found.is_some());
         assert_eq!(found.unwrap().id, url.id);
     }
@@ -591,6 +1559,7 @@ pub mod tests {
             "https://example.com",
             None,
             None,
+            None,
             UrlStatus::Active,
         )
         .await
@@ -598,4 +1567,11 @@ pub mod tests {
 
         assert!(repo.exists_by_short_code(&short_code).await.unwrap());
     }
+
+    #[test]
+    fn test_pool_timeout_is_described_as_pool_exhaustion() {
+        let error = RepositoryError::from(sqlx::Error::PoolTimedOut);
+        assert!(matches!(error, RepositoryError::Connection(_)));
+        assert!(error.to_string().contains("connection pool is exhausted"));
+    }
 }
@@ -1,5 +1,10 @@
-use crate::domain::entities::{ProfilePrivacy, User};
+use crate::domain::entities::{
+    AccountStatus, OAuthProvider, ProfilePrivacy, ProfileVisibility, SocialLinks,
+    UrlWithClickCount, User,
+};
+use crate::infrastructure::email::EmailMessage;
 use async_trait::async_trait;
+use std::collections::HashMap;
 use thiserror::Error;
 
 /// Repository trait for User operations
@@ -13,19 +18,71 @@ pub trait UserRepository: Send + Sync {
         password_hash: &str,
     ) -> Result<User, RepositoryError>;
 
+    /// Create a user who has to verify their email address before the account is active
+    ///
+    /// `welcome_email` is queued in the email outbox in the same transaction as the user.
+    async fn create_unverified_user(
+        &self,
+        username: &str,
+        email: &str,
+        password_hash: &str,
+        welcome_email: &EmailMessage,
+    ) -> Result<User, RepositoryError>;
+
+    /// Create a user who signs in through a social login and has no password
+    async fn create_oauth_user(
+        &self,
+        username: &str,
+        email: &str,
+        provider: OAuthProvider,
+        provider_id: &str,
+    ) -> Result<User, RepositoryError>;
+
     /// Find a user by username
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, RepositoryError>;
 
-    /// Find a user by email
+    /// Find a user by email (case-insensitive)
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, RepositoryError>;
 
     /// Find a user by ID
     async fn find_by_id(&self, id: i32) -> Result<Option<User>, RepositoryError>;
 
+    /// Find the usernames of the given users in one query; unknown IDs are left out
+    async fn find_usernames_by_ids(
+        &self,
+        ids: &[i32],
+    ) -> Result<HashMap<i32, String>, RepositoryError>;
+
+    /// Find the user linked to an account at a social login provider
+    async fn find_by_oauth_account(
+        &self,
+        provider: OAuthProvider,
+        provider_id: &str,
+    ) -> Result<Option<User>, RepositoryError>;
+
+    /// Link a social login account to an existing user, replacing any previous link
+    async fn link_oauth_account(
+        &self,
+        user_id: i32,
+        provider: OAuthProvider,
+        provider_id: &str,
+    ) -> Result<User, RepositoryError>;
+
+    /// Search users for admin user management, ordered by username
+    ///
+    /// Matches users whose email or username contains `query` (case-insensitive) or whose
+    /// display name (first and last name) contains all of its words.
+    async fn search_users(
+        &self,
+        query: &str,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<User>, RepositoryError>;
+
     /// Check if username exists
     async fn exists_by_username(&self, username: &str) -> Result<bool, RepositoryError>;
 
-    /// Check if email exists
+    /// Check if email exists (case-insensitive)
     async fn exists_by_email(&self, email: &str) -> Result<bool, RepositoryError>;
 
     /// Update user profile
@@ -46,18 +103,276 @@ pub trait UserRepository: Send + Sync {
     async fn get_profile(&self, user_id: i32) -> Result<Option<User>, RepositoryError>;
 
     /// Delete a user account (hard delete)
-    /// Note: Consider using anonymize_account for GDPR compliance
+    /// Note: Consider using anonymize_user for GDPR compliance
     #[allow(dead_code)]
     async fn delete_account(&self, user_id: i32) -> Result<(), RepositoryError>;
 
-    /// Anonymize a user account (soft delete with data anonymization)
-    async fn anonymize_account(
+    /// Erase a user's personal data (GDPR right to erasure) in a single transaction
+    ///
+    /// Replaces the credentials and clears the profile, detaches the user's URLs, deletes
+    /// their older clicks and masks the IP of the rest; see `AnonymizationService` for the
+    /// retention rules.
+    async fn anonymize_user(&self, user_id: i32) -> Result<(), RepositoryError>;
+
+    /// Replace the password hash and record the change time in `password_changed_at`
+    async fn update_password(
+        &self,
+        user_id: i32,
+        password_hash: &str,
+    ) -> Result<User, RepositoryError>;
+
+    /// Replace which details the user's public profile shows
+    async fn update_profile_visibility(
+        &self,
+        user_id: i32,
+        visibility: &ProfileVisibility,
+    ) -> Result<User, RepositoryError>;
+
+    /// Replace the user's social links; `None` removes them all
+    async fn update_social_links(
         &self,
         user_id: i32,
-        anonymized_username: &str,
-        anonymized_email: &str,
-        anonymized_password_hash: &str,
-    ) -> Result<(), RepositoryError>;
+        social_links: Option<&SocialLinks>,
+    ) -> Result<User, RepositoryError>;
+
+    /// Set the address the user is changing their email to; `None` clears it
+    async fn set_pending_email(
+        &self,
+        user_id: i32,
+        pending_email: Option<&str>,
+    ) -> Result<User, RepositoryError>;
+
+    /// Change the user's email and clear their pending email
+    ///
+    /// Fails with `DuplicateEmail` if another account has the address.
+    async fn update_email(&self, user_id: i32, email: &str) -> Result<User, RepositoryError>;
+
+    /// Register the push notification token of one of the user's devices
+    ///
+    /// A token belongs to a single device, so it is taken away from any other user first.
+    /// Only the `MAX_DEVICE_TOKENS` most recently registered tokens are kept.
+    async fn add_device_token(&self, user_id: i32, token: &str) -> Result<User, RepositoryError>;
+
+    /// Forget a device token; returns whether the user had it
+    async fn remove_device_token(&self, user_id: i32, token: &str)
+        -> Result<bool, RepositoryError>;
+
+    /// Update the account status (suspension, verification, deactivation)
+    async fn update_account_status(
+        &self,
+        user_id: i32,
+        status: &AccountStatus,
+    ) -> Result<User, RepositoryError>;
+
+    /// Collect a user's personal data for export (GDPR right to data portability)
+    ///
+    /// URLs carry aggregated click counts only; raw click data such as IP addresses is not
+    /// part of the export. Returns `None` if the user does not exist.
+    async fn export_user_data(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<UserDataExport>, RepositoryError>;
+}
+
+/// Forwards to the shared repository, so `Arc<dyn UserRepository>` is a `UserRepository` too
+#[async_trait]
+impl<T: UserRepository + ?Sized> UserRepository for std::sync::Arc<T> {
+    async fn create_user(
+        &self,
+        username: &str,
+        email: &str,
+        password_hash: &str,
+    ) -> Result<User, RepositoryError> {
+        (**self).create_user(username, email, password_hash).await
+    }
+
+    async fn create_unverified_user(
+        &self,
+        username: &str,
+        email: &str,
+        password_hash: &str,
+        welcome_email: &EmailMessage,
+    ) -> Result<User, RepositoryError> {
+        (**self)
+            .create_unverified_user(username, email, password_hash, welcome_email)
+            .await
+    }
+
+    async fn create_oauth_user(
+        &self,
+        username: &str,
+        email: &str,
+        provider: OAuthProvider,
+        provider_id: &str,
+    ) -> Result<User, RepositoryError> {
+        (**self)
+            .create_oauth_user(username, email, provider, provider_id)
+            .await
+    }
+
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, RepositoryError> {
+        (**self).find_by_username(username).await
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, RepositoryError> {
+        (**self).find_by_email(email).await
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<User>, RepositoryError> {
+        (**self).find_by_id(id).await
+    }
+
+    async fn find_usernames_by_ids(
+        &self,
+        ids: &[i32],
+    ) -> Result<HashMap<i32, String>, RepositoryError> {
+        (**self).find_usernames_by_ids(ids).await
+    }
+
+    async fn find_by_oauth_account(
+        &self,
+        provider: OAuthProvider,
+        provider_id: &str,
+    ) -> Result<Option<User>, RepositoryError> {
+        (**self).find_by_oauth_account(provider, provider_id).await
+    }
+
+    async fn link_oauth_account(
+        &self,
+        user_id: i32,
+        provider: OAuthProvider,
+        provider_id: &str,
+    ) -> Result<User, RepositoryError> {
+        (**self)
+            .link_oauth_account(user_id, provider, provider_id)
+            .await
+    }
+
+    async fn search_users(
+        &self,
+        query: &str,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<User>, RepositoryError> {
+        (**self).search_users(query, limit, offset).await
+    }
+
+    async fn exists_by_username(&self, username: &str) -> Result<bool, RepositoryError> {
+        (**self).exists_by_username(username).await
+    }
+
+    async fn exists_by_email(&self, email: &str) -> Result<bool, RepositoryError> {
+        (**self).exists_by_email(email).await
+    }
+
+    async fn update_profile(
+        &self,
+        user_id: i32,
+        first_name: Option<&str>,
+        last_name: Option<&str>,
+        bio: Option<&str>,
+        avatar_url: Option<&str>,
+        website: Option<&str>,
+        location: Option<&str>,
+        privacy: Option<ProfilePrivacy>,
+    ) -> Result<User, RepositoryError> {
+        (**self)
+            .update_profile(
+                user_id, first_name, last_name, bio, avatar_url, website, location, privacy,
+            )
+            .await
+    }
+
+    async fn get_profile(&self, user_id: i32) -> Result<Option<User>, RepositoryError> {
+        (**self).get_profile(user_id).await
+    }
+
+    async fn delete_account(&self, user_id: i32) -> Result<(), RepositoryError> {
+        (**self).delete_account(user_id).await
+    }
+
+    async fn anonymize_user(&self, user_id: i32) -> Result<(), RepositoryError> {
+        (**self).anonymize_user(user_id).await
+    }
+
+    async fn update_password(
+        &self,
+        user_id: i32,
+        password_hash: &str,
+    ) -> Result<User, RepositoryError> {
+        (**self).update_password(user_id, password_hash).await
+    }
+
+    async fn update_profile_visibility(
+        &self,
+        user_id: i32,
+        visibility: &ProfileVisibility,
+    ) -> Result<User, RepositoryError> {
+        (**self)
+            .update_profile_visibility(user_id, visibility)
+            .await
+    }
+
+    async fn update_social_links(
+        &self,
+        user_id: i32,
+        social_links: Option<&SocialLinks>,
+    ) -> Result<User, RepositoryError> {
+        (**self).update_social_links(user_id, social_links).await
+    }
+
+    async fn set_pending_email(
+        &self,
+        user_id: i32,
+        pending_email: Option<&str>,
+    ) -> Result<User, RepositoryError> {
+        (**self).set_pending_email(user_id, pending_email).await
+    }
+
+    async fn update_email(&self, user_id: i32, email: &str) -> Result<User, RepositoryError> {
+        (**self).update_email(user_id, email).await
+    }
+
+    async fn add_device_token(&self, user_id: i32, token: &str) -> Result<User, RepositoryError> {
+        (**self).add_device_token(user_id, token).await
+    }
+
+    async fn remove_device_token(
+        &self,
+        user_id: i32,
+        token: &str,
+    ) -> Result<bool, RepositoryError> {
+        (**self).remove_device_token(user_id, token).await
+    }
+
+    async fn update_account_status(
+        &self,
+        user_id: i32,
+        status: &AccountStatus,
+    ) -> Result<User, RepositoryError> {
+        (**self).update_account_status(user_id, status).await
+    }
+
+    async fn export_user_data(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<UserDataExport>, RepositoryError> {
+        (**self).export_user_data(user_id).await
+    }
+}
+
+/// Snapshot of everything stored about a user, read in a single transaction
+#[derive(Debug, Clone)]
+pub struct UserDataExport {
+    pub user: User,
+    pub urls: Vec<UrlWithClickCount>,
+}
+
+/// Normalize an email address for storage and comparison
+///
+/// Emails are compared case-insensitively, so they are always persisted in lowercase.
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
 }
 
 /// Repository errors
@@ -79,6 +394,21 @@ pub enum RepositoryError {
     #[error("Invalid data: {0}")]
     InvalidData(String),
 
+    #[error("Anonymization failed: {0}")]
+    AnonymizationFailed(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_email() {
+        assert_eq!(normalize_email("User@Example.com"), "user@example.com");
+        assert_eq!(normalize_email("  USER@EXAMPLE.COM "), "user@example.com");
+        assert_eq!(normalize_email("user@example.com"), "user@example.com");
+    }
+}
@@ -0,0 +1,33 @@
+use crate::domain::entities::{UrlCountChange, UserUrlStats};
+use crate::domain::repositories::RepositoryError;
+use async_trait::async_trait;
+
+/// Repository trait for the URL and click counters of each user
+///
+/// Counters are changed with atomic increments, so concurrent updates never lose counts.
+#[async_trait]
+pub trait UserUrlStatsRepository: Send + Sync {
+    /// Get a user's counters; `None` if nothing was counted for the user yet
+    async fn find_by_user_id(&self, user_id: i32) -> Result<Option<UserUrlStats>, RepositoryError>;
+
+    /// Add to the URL counters of a user, creating the user's counters if needed
+    async fn adjust_url_counts(
+        &self,
+        user_id: i32,
+        change: UrlCountChange,
+    ) -> Result<(), RepositoryError>;
+
+    /// Estimate the distinct visitors across a user's URLs
+    ///
+    /// Not a counter: visitors cannot be added up, so this merges the daily visitor
+    /// estimates of the user's URLs.
+    async fn estimate_unique_visitors(&self, user_id: i32) -> Result<i64, RepositoryError>;
+
+    /// Add clicks to the totals of their users, given as `(user_id, clicks)` pairs
+    async fn increment_clicks(&self, clicks: &[(i32, i64)]) -> Result<(), RepositoryError>;
+
+    /// Recompute the counters of every user from the stored URLs and clicks
+    ///
+    /// Returns the number of users whose counters were written.
+    async fn rebuild_all(&self) -> Result<u64, RepositoryError>;
+}
@@ -0,0 +1,695 @@
+use crate::application::dto::requests::{ExportFormat, OperationPriority};
+use crate::domain::entities::{Click, DateRange, ExportJob, ExportJobStatus};
+use crate::domain::repositories::{
+    ClickRepository, ClickRepositoryError, ExportJobRepository, RepositoryError,
+};
+use crate::domain::services::bulk_processor::{BulkProcessorError, BulkTaskQueue};
+use crate::domain::services::ServiceError;
+use crate::infrastructure::object_storage::{
+    analytics_export_key, ObjectStorage, ObjectStorageError,
+};
+use chrono::{DateTime, Utc};
+use polars::prelude::{
+    BatchedWriter, Column, DataFrame, DataType, ParquetWriter, PlSmallStr, TimeUnit, TimeZone,
+};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{error, info};
+
+/// Clicks read from the repository, and written to the export file, at a time
+pub const EXPORT_BATCH_SIZE: i64 = 1000;
+
+/// How long a pre-signed download URL for an export stays valid
+pub const EXPORT_DOWNLOAD_URL_TTL: Duration = Duration::from_secs(3600);
+
+/// How long export files are kept after the export finished
+pub const EXPORT_RETENTION_HOURS: i64 = 24;
+
+/// Analytics export errors
+#[derive(Error, Debug)]
+pub enum AnalyticsExportError {
+    #[error("Export not found")]
+    NotFound,
+
+    #[error("Export is not ready for download")]
+    NotReady,
+
+    #[error("Failed to read clicks: {0}")]
+    Repository(#[from] ClickRepositoryError),
+
+    #[error("Failed to track export: {0}")]
+    Jobs(#[from] RepositoryError),
+
+    #[error("Failed to write export file: {0}")]
+    Serialization(String),
+
+    #[error("Object storage error: {0}")]
+    Storage(#[from] ObjectStorageError),
+}
+
+/// Service exporting the raw click data of a user's URLs
+///
+/// Exports run as bulk operations, writing clicks to a temporary file a batch at a time and
+/// uploading it to the private export storage, which is only handed out as pre-signed URLs.
+/// Jobs are stored in the export job repository, so the cleanup service still finds their
+/// files after a restart.
+#[derive(Clone)]
+pub struct AnalyticsExportService {
+    click_repository: Arc<dyn ClickRepository>,
+    export_job_repository: Arc<dyn ExportJobRepository>,
+    export_storage: Arc<dyn ObjectStorage>,
+    task_queue: Option<BulkTaskQueue>,
+}
+
+impl AnalyticsExportService {
+    pub fn new(
+        click_repository: Arc<dyn ClickRepository>,
+        export_job_repository: Arc<dyn ExportJobRepository>,
+        export_storage: Arc<dyn ObjectStorage>,
+    ) -> Self {
+        Self {
+            click_repository,
+            export_job_repository,
+            export_storage,
+            task_queue: None,
+        }
+    }
+
+    /// Run exports on the bulk processor the queue belongs to
+    pub fn with_task_queue(mut self, task_queue: BulkTaskQueue) -> Self {
+        self.task_queue = Some(task_queue);
+        self
+    }
+
+    /// Queue an export of the clicks on the user's URLs, returning its operation ID
+    pub async fn request_export(
+        &self,
+        user_id: i32,
+        format: ExportFormat,
+        date_range: DateRange,
+    ) -> Result<String, ServiceError> {
+        if !date_range.is_valid() {
+            return Err(ServiceError::InvalidData(
+                "The date range ends before it starts".to_string(),
+            ));
+        }
+        let Some(task_queue) = &self.task_queue else {
+            return Err(ServiceError::Repository(RepositoryError::Internal(
+                "Analytics exports are not processed".to_string(),
+            )));
+        };
+
+        let mut queued_job = None;
+        let queued = task_queue
+            .queue_task(OperationPriority::Normal, |operation_id| {
+                let job = ExportJob::new(operation_id.to_string(), user_id, format, date_range);
+                queued_job = Some(job.clone());
+
+                let service = self.clone();
+                Box::pin(async move { service.run_export(job).await })
+            })
+            .await
+            .map_err(|e| {
+                ServiceError::Repository(RepositoryError::Internal(format!(
+                    "Failed to queue analytics export: {}",
+                    e
+                )))
+            })?;
+
+        // The export may already be running; it stores its own progress over this
+        if let Some(job) = queued_job {
+            self.export_job_repository.create(&job).await?;
+        }
+        info!(
+            "Queued {} analytics export {} for user {}",
+            format.extension(),
+            queued,
+            user_id
+        );
+        Ok(queued)
+    }
+
+    /// The user's exports, newest first
+    pub async fn list_exports(&self, user_id: i32) -> Result<Vec<ExportJob>, AnalyticsExportError> {
+        Ok(self.export_job_repository.find_by_user(user_id).await?)
+    }
+
+    /// Pre-signed URL the user can download a completed export from for the next hour
+    ///
+    /// Exports of other users are reported as missing.
+    pub async fn download_url(
+        &self,
+        user_id: i32,
+        export_id: &str,
+    ) -> Result<String, AnalyticsExportError> {
+        let job = self
+            .export_job_repository
+            .find_by_id(export_id)
+            .await?
+            .filter(|job| job.user_id == user_id)
+            .ok_or(AnalyticsExportError::NotFound)?;
+        let object_key = match (job.status, job.object_key) {
+            (ExportJobStatus::Completed, Some(object_key)) => object_key,
+            _ => return Err(AnalyticsExportError::NotReady),
+        };
+
+        Ok(self
+            .export_storage
+            .presigned_url(&object_key, EXPORT_DOWNLOAD_URL_TTL)?)
+    }
+
+    /// Number of exports that finished longer ago than the export retention
+    pub async fn count_expired_exports(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<u64, AnalyticsExportError> {
+        Ok(self
+            .export_job_repository
+            .count_expired(retention_cutoff(now))
+            .await?)
+    }
+
+    /// Delete the exports, and their files, that finished longer ago than the export retention
+    ///
+    /// Exports that never finished are removed once they are as old. An export whose file
+    /// can't be deleted is kept for the next run.
+    pub async fn purge_expired_exports(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<u64, AnalyticsExportError> {
+        let mut purged = 0;
+        for job in self
+            .export_job_repository
+            .find_expired(retention_cutoff(now))
+            .await?
+        {
+            if let Some(object_key) = &job.object_key {
+                self.export_storage.delete(object_key).await?;
+            }
+            if self.export_job_repository.delete(&job.id).await? {
+                purged += 1;
+            }
+        }
+        Ok(purged)
+    }
+
+    /// Produce and upload the export, storing the outcome on its job
+    async fn run_export(&self, mut job: ExportJob) -> Result<(), BulkProcessorError> {
+        job.status = ExportJobStatus::Processing;
+        self.save_job(&job).await?;
+
+        let result = self.export(&job).await;
+        job.completed_at = Some(Utc::now());
+        match result {
+            Ok(exported) => {
+                info!(
+                    "Analytics export {} wrote {} clicks to {}",
+                    job.id, exported.click_count, exported.object_key
+                );
+                job.status = ExportJobStatus::Completed;
+                job.object_key = Some(exported.object_key);
+                job.click_count = exported.click_count;
+                self.save_job(&job).await
+            }
+            Err(e) => {
+                error!("Analytics export {} failed: {}", job.id, e);
+                job.status = ExportJobStatus::Failed;
+                job.error = Some(e.to_string());
+                self.save_job(&job).await?;
+                Err(BulkProcessorError::ProcessingFailed(e.to_string()))
+            }
+        }
+    }
+
+    async fn save_job(&self, job: &ExportJob) -> Result<(), BulkProcessorError> {
+        self.export_job_repository.save(job).await.map_err(|e| {
+            error!("Failed to store analytics export {}: {}", job.id, e);
+            BulkProcessorError::ProcessingFailed(e.to_string())
+        })
+    }
+
+    async fn export(&self, job: &ExportJob) -> Result<ExportedFile, AnalyticsExportError> {
+        // Removed when dropped, once uploaded or when the export fails
+        let (file, path) = tempfile::NamedTempFile::new()
+            .map_err(serialization_error)?
+            .into_parts();
+        let mut writer = ExportWriter::new(job.format, file)?;
+        let mut after_id = None;
+        let mut click_count = 0;
+        loop {
+            let clicks = self
+                .click_repository
+                .get_clicks_for_user_page(
+                    job.user_id,
+                    job.date_range.start,
+                    job.date_range.end,
+                    after_id,
+                    EXPORT_BATCH_SIZE,
+                )
+                .await?;
+            let Some(last) = clicks.last() else {
+                break;
+            };
+            after_id = Some(last.id);
+            writer.write(&clicks)?;
+            click_count += clicks.len() as u64;
+            if (clicks.len() as i64) < EXPORT_BATCH_SIZE {
+                break;
+            }
+        }
+        writer.finish()?;
+
+        let object_key = analytics_export_key(job.user_id, &job.id, job.format.extension());
+        self.export_storage
+            .upload_file(&object_key, &path, job.format.content_type())
+            .await?;
+        Ok(ExportedFile {
+            object_key,
+            click_count,
+        })
+    }
+}
+
+/// Exports finished before this are past the export retention
+fn retention_cutoff(now: DateTime<Utc>) -> DateTime<Utc> {
+    now - chrono::Duration::hours(EXPORT_RETENTION_HOURS)
+}
+
+/// Export file uploaded to the export storage
+struct ExportedFile {
+    object_key: String,
+    click_count: u64,
+}
+
+/// Exported click, with every field present so all rows have the same columns
+#[derive(Serialize)]
+struct ClickExportRow<'a> {
+    id: i32,
+    url_id: i32,
+    clicked_at: DateTime<Utc>,
+    ip_address: Option<&'a str>,
+    user_agent: Option<&'a str>,
+    referer: Option<&'a str>,
+    country_code: Option<&'a str>,
+    created_at: DateTime<Utc>,
+    click_token: Option<&'a str>,
+    suspicious: bool,
+    utm_source: Option<&'a str>,
+    utm_medium: Option<&'a str>,
+    utm_campaign: Option<&'a str>,
+}
+
+impl<'a> From<&'a Click> for ClickExportRow<'a> {
+    fn from(click: &'a Click) -> Self {
+        Self {
+            id: click.id,
+            url_id: click.url_id,
+            clicked_at: click.clicked_at,
+            ip_address: click.ip_address.as_deref(),
+            user_agent: click.user_agent.as_deref(),
+            referer: click.referer.as_deref(),
+            country_code: click.country_code.as_deref(),
+            created_at: click.created_at,
+            click_token: click.click_token.as_deref(),
+            suspicious: click.suspicious,
+            utm_source: click.utm_source.as_deref(),
+            utm_medium: click.utm_medium.as_deref(),
+            utm_campaign: click.utm_campaign.as_deref(),
+        }
+    }
+}
+
+/// Click fields collected column by column, as Parquet files are written
+#[derive(Default)]
+struct ClickColumns {
+    id: Vec<i32>,
+    url_id: Vec<i32>,
+    clicked_at: Vec<i64>,
+    ip_address: Vec<Option<String>>,
+    user_agent: Vec<Option<String>>,
+    referer: Vec<Option<String>>,
+    country_code: Vec<Option<String>>,
+    created_at: Vec<i64>,
+    click_token: Vec<Option<String>>,
+    suspicious: Vec<bool>,
+    utm_source: Vec<Option<String>>,
+    utm_medium: Vec<Option<String>>,
+    utm_campaign: Vec<Option<String>>,
+}
+
+impl ClickColumns {
+    fn push(&mut self, click: &Click) {
+        self.id.push(click.id);
+        self.url_id.push(click.url_id);
+        self.clicked_at.push(click.clicked_at.timestamp_millis());
+        self.ip_address.push(click.ip_address.clone());
+        self.user_agent.push(click.user_agent.clone());
+        self.referer.push(click.referer.clone());
+        self.country_code.push(click.country_code.clone());
+        self.created_at.push(click.created_at.timestamp_millis());
+        self.click_token.push(click.click_token.clone());
+        self.suspicious.push(click.suspicious);
+        self.utm_source.push(click.utm_source.clone());
+        self.utm_medium.push(click.utm_medium.clone());
+        self.utm_campaign.push(click.utm_campaign.clone());
+    }
+
+    fn into_data_frame(self) -> polars::prelude::PolarsResult<DataFrame> {
+        let timestamp = DataType::Datetime(TimeUnit::Milliseconds, Some(TimeZone::UTC));
+        let name = PlSmallStr::from_static;
+        DataFrame::new(vec![
+            Column::new(name("id"), self.id),
+            Column::new(name("url_id"), self.url_id),
+            Column::new(name("clicked_at"), self.clicked_at).cast(&timestamp)?,
+            Column::new(name("ip_address"), self.ip_address),
+            Column::new(name("user_agent"), self.user_agent),
+            Column::new(name("referer"), self.referer),
+            Column::new(name("country_code"), self.country_code),
+            Column::new(name("created_at"), self.created_at).cast(&timestamp)?,
+            Column::new(name("click_token"), self.click_token),
+            Column::new(name("suspicious"), self.suspicious),
+            Column::new(name("utm_source"), self.utm_source),
+            Column::new(name("utm_medium"), self.utm_medium),
+            Column::new(name("utm_campaign"), self.utm_campaign),
+        ])
+    }
+}
+
+fn serialization_error(error: impl std::fmt::Display) -> AnalyticsExportError {
+    AnalyticsExportError::Serialization(error.to_string())
+}
+
+/// Export file being written, one batch of clicks at a time
+enum ExportWriter {
+    Csv(Box<csv::Writer<File>>),
+    /// A JSON array, written without its closing bracket until finished
+    Json {
+        writer: BufWriter<File>,
+        empty: bool,
+    },
+    /// Each batch is written as a row group
+    Parquet(Box<BatchedWriter<File>>),
+}
+
+impl ExportWriter {
+    fn new(format: ExportFormat, file: File) -> Result<Self, AnalyticsExportError> {
+        Ok(match format {
+            ExportFormat::Csv => ExportWriter::Csv(Box::new(csv::Writer::from_writer(file))),
+            ExportFormat::Json => {
+                let mut writer = BufWriter::new(file);
+                writer.write_all(b"[").map_err(serialization_error)?;
+                ExportWriter::Json {
+                    writer,
+                    empty: true,
+                }
+            }
+            ExportFormat::Parquet => {
+                let empty = ClickColumns::default()
+                    .into_data_frame()
+                    .map_err(serialization_error)?;
+                ExportWriter::Parquet(Box::new(
+                    ParquetWriter::new(file)
+                        .batched(empty.schema())
+                        .map_err(serialization_error)?,
+                ))
+            }
+        })
+    }
+
+    fn write(&mut self, clicks: &[Click]) -> Result<(), AnalyticsExportError> {
+        match self {
+            ExportWriter::Csv(writer) => {
+                for click in clicks {
+                    writer
+                        .serialize(ClickExportRow::from(click))
+                        .map_err(serialization_error)?;
+                }
+            }
+            ExportWriter::Json { writer, empty } => {
+                for click in clicks {
+                    if !*empty {
+                        writer.write_all(b",").map_err(serialization_error)?;
+                    }
+                    serde_json::to_writer(&mut *writer, &ClickExportRow::from(click))
+                        .map_err(serialization_error)?;
+                    *empty = false;
+                }
+            }
+            ExportWriter::Parquet(writer) => {
+                let mut columns = ClickColumns::default();
+                for click in clicks {
+                    columns.push(click);
+                }
+                let batch = columns.into_data_frame().map_err(serialization_error)?;
+                writer.write_batch(&batch).map_err(serialization_error)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Complete the file and flush it to disk
+    fn finish(self) -> Result<(), AnalyticsExportError> {
+        match self {
+            ExportWriter::Csv(mut writer) => writer.flush().map_err(serialization_error),
+            ExportWriter::Json { mut writer, .. } => {
+                writer.write_all(b"]").map_err(serialization_error)?;
+                writer.flush().map_err(serialization_error)
+            }
+            ExportWriter::Parquet(writer) => {
+                writer.finish().map_err(serialization_error)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::{BulkProcessor, ProgressService, UrlService};
+    use crate::infrastructure::object_storage::LocalObjectStorage;
+    use crate::infrastructure::test_utils::{
+        MockClickRepository, MockExportJobRepository, MockUrlRepository, MockUserRepository,
+    };
+    use polars::prelude::{ParquetReader, SerReader};
+    use tempfile::TempDir;
+
+    const USER_ID: i32 = 7;
+
+    fn export_storage(storage_dir: &TempDir) -> Arc<dyn ObjectStorage> {
+        Arc::new(LocalObjectStorage::new(
+            storage_dir.path(),
+            "file:///exports",
+        ))
+    }
+
+    /// Service over clicks on user 7's URL 1 and another user's URL 2
+    async fn service_with_clicks(
+        storage_dir: &TempDir,
+        clicks_of_user: usize,
+    ) -> AnalyticsExportService {
+        service_with_jobs(storage_dir, clicks_of_user, MockExportJobRepository::new()).await
+    }
+
+    async fn service_with_jobs(
+        storage_dir: &TempDir,
+        clicks_of_user: usize,
+        jobs: MockExportJobRepository,
+    ) -> AnalyticsExportService {
+        let click_repository = MockClickRepository::new();
+        click_repository.set_url_owner(1, USER_ID);
+        click_repository.set_url_owner(2, USER_ID + 1);
+        for i in 0..clicks_of_user {
+            let mut click = Click::new_with_timestamp(
+                0,
+                1,
+                Some(format!("10.0.0.{}", i % 250)),
+                Some("Mozilla/5.0".to_string()),
+                None,
+                Some("PT".to_string()),
+            );
+            click.utm_source = Some("newsletter".to_string());
+            click_repository.record_click(&click).await.unwrap();
+        }
+        let other = Click::new_with_timestamp(0, 2, None, None, None, None);
+        click_repository.record_click(&other).await.unwrap();
+
+        let processor = BulkProcessor::new(
+            UrlService::new(MockUrlRepository::new()),
+            ProgressService::new(),
+            MockUserRepository::new(),
+        );
+        AnalyticsExportService::new(
+            Arc::new(click_repository),
+            Arc::new(jobs),
+            export_storage(storage_dir),
+        )
+        .with_task_queue(processor.task_queue())
+    }
+
+    /// Wait for the export to finish and return its job
+    async fn finished_export(service: &AnalyticsExportService, export_id: &str) -> ExportJob {
+        for _ in 0..200 {
+            let job = service
+                .list_exports(USER_ID)
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|job| job.id == export_id)
+                .unwrap();
+            if matches!(
+                job.status,
+                ExportJobStatus::Completed | ExportJobStatus::Failed
+            ) {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("export {} did not finish", export_id);
+    }
+
+    async fn export_file(
+        service: &AnalyticsExportService,
+        storage_dir: &TempDir,
+        format: ExportFormat,
+    ) -> (ExportJob, Vec<u8>) {
+        let export_id = service
+            .request_export(USER_ID, format, DateRange::default())
+            .await
+            .unwrap();
+        let job = finished_export(service, &export_id).await;
+        assert_eq!(job.status, ExportJobStatus::Completed, "{:?}", job.error);
+        let contents =
+            std::fs::read(storage_dir.path().join(job.object_key.as_ref().unwrap())).unwrap();
+        (job, contents)
+    }
+
+    #[tokio::test]
+    async fn test_csv_export_covers_every_batch() {
+        let storage_dir = TempDir::new().unwrap();
+        let service = service_with_clicks(&storage_dir, 2500).await;
+
+        let (job, contents) = export_file(&service, &storage_dir, ExportFormat::Csv).await;
+        assert_eq!(job.click_count, 2500);
+        assert_eq!(
+            job.object_key.as_deref(),
+            Some(format!("exports/{}/{}.csv", USER_ID, job.id).as_str())
+        );
+
+        let mut reader = csv::Reader::from_reader(contents.as_slice());
+        let headers = reader.headers().unwrap().clone();
+        assert_eq!(headers.len(), 13);
+        assert_eq!(&headers[0], "id");
+        assert_eq!(&headers[12], "utm_campaign");
+        let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(rows.len(), 2500);
+        // Clicks on the other user's URL are left out
+        assert!(rows.iter().all(|row| &row[1] == "1"));
+        assert_eq!(&rows[0][10], "newsletter");
+    }
+
+    #[tokio::test]
+    async fn test_json_and_parquet_exports() {
+        let storage_dir = TempDir::new().unwrap();
+        let service = service_with_clicks(&storage_dir, 3).await;
+
+        let (_, contents) = export_file(&service, &storage_dir, ExportFormat::Json).await;
+        let rows: Vec<serde_json::Value> = serde_json::from_slice(&contents).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0]["country_code"], "PT");
+        assert!(rows[0]["click_token"].is_null());
+
+        let (_, contents) = export_file(&service, &storage_dir, ExportFormat::Parquet).await;
+        let data_frame = ParquetReader::new(std::io::Cursor::new(contents))
+            .finish()
+            .unwrap();
+        assert_eq!(data_frame.height(), 3);
+        assert_eq!(data_frame.width(), 13);
+        assert!(matches!(
+            data_frame.column("clicked_at").unwrap().dtype(),
+            DataType::Datetime(TimeUnit::Milliseconds, _)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_empty_export_and_invalid_range() {
+        let storage_dir = TempDir::new().unwrap();
+        let service = service_with_clicks(&storage_dir, 0).await;
+
+        let (job, contents) = export_file(&service, &storage_dir, ExportFormat::Json).await;
+        assert_eq!(job.click_count, 0);
+        assert_eq!(contents, b"[]");
+
+        let now = Utc::now();
+        let result = service
+            .request_export(
+                USER_ID,
+                ExportFormat::Csv,
+                DateRange {
+                    start: Some(now),
+                    end: Some(now - chrono::Duration::days(1)),
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(ServiceError::InvalidData(_))));
+    }
+
+    #[tokio::test]
+    async fn test_download_url_is_only_given_to_the_owner() {
+        let storage_dir = TempDir::new().unwrap();
+        let service = service_with_clicks(&storage_dir, 1).await;
+        let (job, _) = export_file(&service, &storage_dir, ExportFormat::Csv).await;
+
+        assert_eq!(
+            service.download_url(USER_ID, &job.id).await.unwrap(),
+            format!("file:///exports/exports/{}/{}.csv", USER_ID, job.id)
+        );
+        assert!(matches!(
+            service.download_url(USER_ID + 1, &job.id).await,
+            Err(AnalyticsExportError::NotFound)
+        ));
+        assert!(service.list_exports(USER_ID + 1).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_expired_exports_are_purged_with_their_files() {
+        let storage_dir = TempDir::new().unwrap();
+        let service = service_with_clicks(&storage_dir, 1).await;
+        let (job, _) = export_file(&service, &storage_dir, ExportFormat::Csv).await;
+        let path = storage_dir.path().join(job.object_key.as_ref().unwrap());
+
+        let now = Utc::now();
+        assert_eq!(service.count_expired_exports(now).await.unwrap(), 0);
+        assert_eq!(service.purge_expired_exports(now).await.unwrap(), 0);
+
+        let later = now + chrono::Duration::hours(EXPORT_RETENTION_HOURS + 1);
+        assert_eq!(service.count_expired_exports(later).await.unwrap(), 1);
+        assert_eq!(service.purge_expired_exports(later).await.unwrap(), 1);
+        assert!(!path.exists());
+        assert!(service.list_exports(USER_ID).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_exports_outlive_a_restart() {
+        let storage_dir = TempDir::new().unwrap();
+        let jobs = MockExportJobRepository::new();
+        let service = service_with_jobs(&storage_dir, 1, jobs.clone()).await;
+        let (job, _) = export_file(&service, &storage_dir, ExportFormat::Csv).await;
+        let path = storage_dir.path().join(job.object_key.as_ref().unwrap());
+        drop(service);
+
+        // A new service over the same stored jobs still lists and cleans up the export
+        let restarted = service_with_jobs(&storage_dir, 0, jobs).await;
+        let exports = restarted.list_exports(USER_ID).await.unwrap();
+        assert_eq!(exports, vec![job.clone()]);
+        assert!(restarted.download_url(USER_ID, &job.id).await.is_ok());
+
+        let later = Utc::now() + chrono::Duration::hours(EXPORT_RETENTION_HOURS + 1);
+        assert_eq!(restarted.purge_expired_exports(later).await.unwrap(), 1);
+        assert!(!path.exists());
+    }
+}
@@ -2,7 +2,21 @@ use crate::domain::entities::User;
 use chrono::Utc;
 use thiserror::Error;
 
+/// Clicks younger than this many days survive account deletion with their IP masked;
+/// older clicks are deleted outright
+pub const ANONYMIZED_CLICK_RETENTION_DAYS: i64 = 30;
+
+/// IP address stored on the retained clicks of a deleted account
+pub const ANONYMIZED_IP_ADDRESS: &str = "0.0.0.0";
+
 /// Service for anonymizing user data upon account deletion
+///
+/// Data retention on deletion (right to erasure):
+/// - the user row is kept so foreign keys stay valid, but its username, email and password
+///   hash are replaced and every profile field is cleared
+/// - the user's URLs keep working but become anonymous (`user_id` is cleared)
+/// - clicks on those URLs older than `ANONYMIZED_CLICK_RETENTION_DAYS` are deleted; newer
+///   ones are kept for aggregate statistics with the IP replaced by `ANONYMIZED_IP_ADDRESS`
 #[derive(Clone, Default)]
 pub struct AnonymizationService;
 
@@ -24,19 +38,21 @@ impl AnonymizationService {
     }
 
     /// Generate anonymized username
-    fn anonymize_username(&self, user_id: i32) -> String {
-        format!("deleted_user_{}", user_id)
+    pub fn anonymize_username(&self, user_id: i32) -> String {
+        format!("deleted-{}", user_id)
     }
 
     /// Generate anonymized email
-    fn anonymize_email(&self, user_id: i32) -> String {
-        format!("deleted_{}@anonymized.local", user_id)
+    ///
+    /// `.invalid` is reserved (RFC 2606), so nothing can ever be delivered to it.
+    pub fn anonymize_email(&self, user_id: i32) -> String {
+        format!("deleted-{}@anonymized.invalid", user_id)
     }
 
     /// Generate a placeholder password hash
-    fn generate_anonymized_hash(&self) -> String {
-        // Use a fixed hash to indicate deleted account
-        "DELETED_ACCOUNT_HASH".to_string()
+    pub fn generate_anonymized_hash(&self) -> String {
+        // An empty hash never verifies, so the account cannot log in again
+        String::new()
     }
 }
 
@@ -92,30 +108,33 @@ mod tests {
         let anonymized = service.anonymize_user_data(&user);
 
         assert_eq!(anonymized.user_id, 123);
-        assert_eq!(anonymized.username, "deleted_user_123");
-        assert_eq!(anonymized.email, "deleted_123@anonymized.local");
-        assert_eq!(anonymized.password_hash, "DELETED_ACCOUNT_HASH");
+        assert_eq!(anonymized.username, "deleted-123");
+        assert_eq!(anonymized.email, "deleted-123@anonymized.invalid");
+        assert_eq!(anonymized.password_hash, "");
         assert!(anonymized.deleted_at <= Utc::now());
     }
 
     #[test]
     fn test_anonymize_username() {
         let service = AnonymizationService::new();
-        assert_eq!(service.anonymize_username(1), "deleted_user_1");
-        assert_eq!(service.anonymize_username(999), "deleted_user_999");
+        assert_eq!(service.anonymize_username(1), "deleted-1");
+        assert_eq!(service.anonymize_username(999), "deleted-999");
     }
 
     #[test]
     fn test_anonymize_email() {
         let service = AnonymizationService::new();
-        assert_eq!(service.anonymize_email(1), "deleted_1@anonymized.local");
-        assert_eq!(service.anonymize_email(999), "deleted_999@anonymized.local");
+        assert_eq!(service.anonymize_email(1), "deleted-1@anonymized.invalid");
+        assert_eq!(
+            service.anonymize_email(999),
+            "deleted-999@anonymized.invalid"
+        );
     }
 
     #[test]
     fn test_generate_anonymized_hash() {
         let service = AnonymizationService::new();
         let hash = service.generate_anonymized_hash();
-        assert_eq!(hash, "DELETED_ACCOUNT_HASH");
+        assert!(hash.is_empty());
     }
 }
@@ -1,10 +1,46 @@
-use crate::domain::entities::User;
-use crate::domain::repositories::user_repository::{RepositoryError, UserRepository};
+use crate::domain::entities::{AccountStatus, Session, SessionClient, User};
+use crate::domain::repositories::user_repository::{
+    normalize_email, RepositoryError, UserRepository,
+};
+use crate::domain::repositories::SessionRepository;
+use crate::infrastructure::email::{EmailMessage, WelcomeEmail};
+use crate::infrastructure::user_invalidation::UserInvalidationBroadcaster;
 use bcrypt::{hash, verify, DEFAULT_COST};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
-use std::time::{SystemTime, UNIX_EPOCH};
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Minimum time between two writes of a session's `last_used_at`
+pub const SESSION_TOUCH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Sessions whose last write is remembered before stale entries are pruned
+const MAX_TRACKED_SESSION_TOUCHES: usize = 10_000;
+
+/// How long a verified token is trusted before its user is loaded again
+pub const VERIFIED_TOKEN_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Verified tokens remembered before expired entries are pruned
+const MAX_CACHED_TOKENS: usize = 10_000;
+
+/// Wrong passwords within `LOGIN_LOCKOUT_WINDOW` after which an account is suspended
+pub const LOGIN_LOCKOUT_THRESHOLD: u32 = 5;
+
+/// Period over which failed logins are counted; the count starts over once it has passed
+pub const LOGIN_LOCKOUT_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+/// How long the link in a welcome email can verify the address
+pub const EMAIL_VERIFICATION_EXPIRATION_HOURS: usize = 72;
+
+/// `purpose` claim of email verification tokens
+const EMAIL_VERIFICATION_PURPOSE: &str = "email_verification";
 
 /// JWT Claims
 #[derive(Debug, Serialize, Deserialize)]
@@ -13,6 +49,118 @@ pub struct Claims {
     pub username: String,
     pub exp: usize,
     pub iat: usize,
+    /// The user's `password_changed_at` (Unix milliseconds) when the token was issued
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_changed_at: Option<i64>,
+    /// Session the token belongs to; absent for tokens issued without session tracking
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<Uuid>,
+}
+
+/// Claims of the token in an email verification link
+///
+/// They share no required claim with session tokens, so neither is accepted as the other.
+#[derive(Debug, Serialize, Deserialize)]
+struct EmailVerificationClaims {
+    /// Address being verified
+    email: String,
+    purpose: String,
+    exp: usize,
+    iat: usize,
+}
+
+/// Claims of an active token that are safe to share with other services
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenIntrospection {
+    pub user_id: i32,
+    pub username: String,
+    pub email: String,
+    pub exp: usize,
+    pub iat: usize,
+    pub scopes: Vec<String>,
+}
+
+/// Hits and misses of the verified token cache since startup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// A verified token's user and session, and when they were loaded
+struct CachedVerification {
+    user: User,
+    session_id: Option<Uuid>,
+    verified_at: Instant,
+}
+
+/// Recently verified tokens, keyed by the first 16 bytes of their SHA-256
+///
+/// Only hashes are kept so the cache never holds a usable token.
+struct TokenCache {
+    ttl: Duration,
+    entries: DashMap<[u8; 16], CachedVerification>,
+    /// Bumped on every invalidation, so verifications racing one are not cached
+    generation: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl TokenCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: DashMap::new(),
+            generation: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    fn key(token: &str) -> [u8; 16] {
+        let digest = Sha256::digest(token.as_bytes());
+        let mut key = [0; 16];
+        key.copy_from_slice(&digest[..16]);
+        key
+    }
+
+    fn get(&self, key: &[u8; 16]) -> Option<(User, Option<Uuid>)> {
+        let hit = self
+            .entries
+            .get(key)
+            .filter(|entry| entry.verified_at.elapsed() < self.ttl)
+            .map(|entry| (entry.user.clone(), entry.session_id));
+        let counter = if hit.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        hit
+    }
+
+    /// Remember a verification started at `generation`, unless a user was invalidated since
+    fn insert(&self, key: [u8; 16], user: User, session_id: Option<Uuid>, generation: u64) {
+        if self.entries.len() >= MAX_CACHED_TOKENS {
+            self.entries
+                .retain(|_, entry| entry.verified_at.elapsed() < self.ttl);
+        }
+        if self.generation.load(Ordering::SeqCst) == generation {
+            self.entries.insert(
+                key,
+                CachedVerification {
+                    user,
+                    session_id,
+                    verified_at: Instant::now(),
+                },
+            );
+        }
+    }
+
+    fn evict_user(&self, user_id: i32) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.entries.retain(|_, entry| entry.user.id != user_id);
+    }
 }
 
 /// Authentication service
@@ -23,6 +171,17 @@ where
 {
     user_repository: R,
     jwt_secret: String,
+    admin_user_ids: Vec<i32>,
+    token_expiration_hours: u32,
+    session_repository: Option<Arc<dyn SessionRepository>>,
+    /// When each session's `last_used_at` was last written by this instance
+    session_touches: Arc<DashMap<Uuid, Instant>>,
+    /// Recently verified tokens; `None` loads the user of every token
+    token_cache: Option<Arc<TokenCache>>,
+    /// Evicts users from the token caches of other instances
+    user_invalidation: Option<Arc<dyn UserInvalidationBroadcaster>>,
+    /// Wrong passwords per user and when their window started
+    failed_logins: Arc<DashMap<i32, (u32, Instant)>>,
 }
 
 impl<R> AuthService<R>
@@ -33,9 +192,94 @@ where
         Self {
             user_repository,
             jwt_secret,
+            admin_user_ids: Vec::new(),
+            token_expiration_hours: 24,
+            session_repository: None,
+            session_touches: Arc::new(DashMap::new()),
+            token_cache: None,
+            user_invalidation: None,
+            failed_logins: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Track a session per issued token so users can list and revoke their logins
+    pub fn with_session_repository(
+        mut self,
+        session_repository: Arc<dyn SessionRepository>,
+    ) -> Self {
+        self.session_repository = Some(session_repository);
+        self
+    }
+
+    /// Grant administrative access to the given user IDs
+    pub fn with_admin_user_ids(mut self, admin_user_ids: Vec<i32>) -> Self {
+        self.admin_user_ids = admin_user_ids;
+        self
+    }
+
+    /// Set how long issued tokens stay valid (24 hours by default)
+    pub fn with_token_expiration_hours(mut self, hours: u32) -> Self {
+        self.token_expiration_hours = hours;
+        self
+    }
+
+    /// Trust verified tokens for `ttl` instead of loading their user on every request
+    ///
+    /// Suspensions, password changes and revoked sessions evict the user's tokens at once.
+    pub fn with_token_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.token_cache = Some(Arc::new(TokenCache::new(ttl)));
+        self
+    }
+
+    /// Evict invalidated users from the token caches of other instances too
+    pub fn with_user_invalidation(
+        mut self,
+        user_invalidation: Arc<dyn UserInvalidationBroadcaster>,
+    ) -> Self {
+        self.user_invalidation = Some(user_invalidation);
+        self
+    }
+
+    /// Hits and misses of the verified token cache; zero when it is disabled
+    pub fn token_cache_stats(&self) -> TokenCacheStats {
+        self.token_cache
+            .as_ref()
+            .map(|cache| TokenCacheStats {
+                hits: cache.hits.load(Ordering::Relaxed),
+                misses: cache.misses.load(Ordering::Relaxed),
+            })
+            .unwrap_or_default()
+    }
+
+    /// Forget the cached tokens of a user on this instance only
+    ///
+    /// Called for invalidations received from other instances.
+    pub fn evict_cached_user(&self, user_id: i32) {
+        if let Some(cache) = &self.token_cache {
+            cache.evict_user(user_id);
+        }
+    }
+
+    /// Forget the cached tokens of a user on every instance
+    ///
+    /// Needed whenever the user's tokens may have stopped being valid.
+    pub async fn invalidate_user(&self, user_id: i32) {
+        self.evict_cached_user(user_id);
+        if let Some(user_invalidation) = &self.user_invalidation {
+            if let Err(e) = user_invalidation.broadcast(user_id).await {
+                warn!(
+                    "Failed to broadcast the invalidation of user {}: {}",
+                    user_id, e
+                );
+            }
         }
     }
 
+    /// Check if a user has administrative access
+    pub fn is_admin(&self, user: &User) -> bool {
+        self.admin_user_ids.contains(&user.id)
+    }
+
     /// Register a new user
     pub async fn register(
         &self,
@@ -43,16 +287,68 @@ where
         email: &str,
         password: &str,
     ) -> Result<User, ServiceError> {
+        let (email, password_hash) = self.prepare_registration(username, email, password).await?;
+
+        // Create user
+        let user = self
+            .user_repository
+            .create_user(username, &email, &password_hash)
+            .await
+            .map_err(ServiceError::Repository)?;
+
+        Ok(user)
+    }
+
+    /// Register a new user who has to verify their email address
+    ///
+    /// The account waits in `PendingVerification` until the link of its welcome email, under
+    /// `verify_url_base`, is opened. The email is queued in the outbox together with the
+    /// account, so neither is stored without the other.
+    pub async fn register_unverified(
+        &self,
+        username: &str,
+        email: &str,
+        password: &str,
+        verify_url_base: &str,
+    ) -> Result<User, ServiceError> {
+        let (email, password_hash) = self.prepare_registration(username, email, password).await?;
+
+        let template = WelcomeEmail {
+            username: username.to_string(),
+            verify_url: format!(
+                "{}/auth/verify-email?token={}",
+                verify_url_base,
+                self.email_verification_token(&email)?
+            ),
+        };
+        let welcome_email = EmailMessage::from_template(email.clone(), &template);
+
+        self.user_repository
+            .create_unverified_user(username, &email, &password_hash, &welcome_email)
+            .await
+            .map_err(ServiceError::Repository)
+    }
+
+    /// Validate a registration and hash its password, returning the normalized email and hash
+    async fn prepare_registration(
+        &self,
+        username: &str,
+        email: &str,
+        password: &str,
+    ) -> Result<(String, String), ServiceError> {
         // Validate input
         self.validate_registration_input(username, email, password)?;
 
+        // Emails are unique regardless of case
+        let email = normalize_email(email);
+
         // Check if username already exists
         if self.user_repository.exists_by_username(username).await? {
             return Err(ServiceError::UsernameAlreadyExists);
         }
 
         // Check if email already exists
-        if self.user_repository.exists_by_email(email).await? {
+        if self.user_repository.exists_by_email(&email).await? {
             return Err(ServiceError::EmailAlreadyExists);
         }
 
@@ -60,18 +356,68 @@ where
         let password_hash = hash(password, DEFAULT_COST)
             .map_err(|e| ServiceError::PasswordHashing(e.to_string()))?;
 
-        // Create user
+        Ok((email, password_hash))
+    }
+
+    /// Token for the link verifying `email`, valid for [`EMAIL_VERIFICATION_EXPIRATION_HOURS`]
+    pub fn email_verification_token(&self, email: &str) -> Result<String, ServiceError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as usize;
+
+        let claims = EmailVerificationClaims {
+            email: email.to_string(),
+            purpose: EMAIL_VERIFICATION_PURPOSE.to_string(),
+            exp: now + EMAIL_VERIFICATION_EXPIRATION_HOURS * 60 * 60,
+            iat: now,
+        };
+
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(self.jwt_secret.as_ref()),
+        )
+        .map_err(|e| ServiceError::TokenGeneration(e.to_string()))
+    }
+
+    /// Activate the account waiting for the address of an email verification link
+    ///
+    /// Accounts that are not waiting are returned unchanged, so opening a link twice is harmless.
+    pub async fn verify_email(&self, token: &str) -> Result<User, ServiceError> {
+        let claims = decode::<EmailVerificationClaims>(
+            token,
+            &DecodingKey::from_secret(self.jwt_secret.as_ref()),
+            &Validation::new(Algorithm::HS256),
+        )
+        .map_err(|e| ServiceError::TokenValidation(e.to_string()))?
+        .claims;
+        if claims.purpose != EMAIL_VERIFICATION_PURPOSE {
+            return Err(ServiceError::TokenValidation(
+                "Not an email verification token".to_string(),
+            ));
+        }
+
         let user = self
             .user_repository
-            .create_user(username, email, &password_hash)
+            .find_by_email(&claims.email)
+            .await
+            .map_err(ServiceError::Repository)?
+            .ok_or(ServiceError::UserNotFound)?;
+        if user.account_status != AccountStatus::PendingVerification {
+            return Ok(user);
+        }
+        self.update_account_status(user.id, &AccountStatus::Active)
             .await
-            .map_err(ServiceError::Repository)?;
-
-        Ok(user)
     }
 
-    /// Login a user
-    pub async fn login(&self, username: &str, password: &str) -> Result<String, ServiceError> {
+    /// Login a user, starting a session for the client
+    pub async fn login(
+        &self,
+        username: &str,
+        password: &str,
+        client: &SessionClient,
+    ) -> Result<String, ServiceError> {
         // Find user by username
         let user = self
             .user_repository
@@ -80,24 +426,157 @@ where
             .map_err(ServiceError::Repository)?
             .ok_or(ServiceError::InvalidCredentials)?;
 
+        // Anonymized (deleted) and social login accounts have no password hash
+        if !user.has_password() {
+            return Err(ServiceError::InvalidCredentials);
+        }
+
         // Verify password
         let is_valid = verify(password, &user.password_hash)
             .map_err(|e| ServiceError::PasswordVerification(e.to_string()))?;
 
         if !is_valid {
+            self.record_failed_login(&user).await;
             return Err(ServiceError::InvalidCredentials);
         }
 
+        Self::ensure_not_suspended(&user)?;
+        self.failed_logins.remove(&user.id);
+
         // Generate JWT token
-        let token = self.generate_jwt_token(&user)?;
+        let session_id = self.start_session(&user, client).await?;
+        let token = self.generate_jwt_token(&user, session_id)?;
 
         Ok(token)
     }
 
+    /// Issue a session token for a user authenticated without a password (e.g. a magic link)
+    pub async fn issue_token(
+        &self,
+        user: &User,
+        client: &SessionClient,
+    ) -> Result<String, ServiceError> {
+        Self::ensure_not_suspended(user)?;
+        let session_id = self.start_session(user, client).await?;
+        self.generate_jwt_token(user, session_id)
+    }
+
     /// Verify JWT token and return user
     pub async fn verify_token(&self, token: &str) -> Result<User, ServiceError> {
+        self.verify_token_with_session(token)
+            .await
+            .map(|(user, _)| user)
+    }
+
+    /// Verify JWT token and return the user and the session it belongs to, if any
+    pub async fn verify_token_with_session(
+        &self,
+        token: &str,
+    ) -> Result<(User, Option<Uuid>), ServiceError> {
         let claims = self.decode_jwt_token(token)?;
+        let Some(cache) = &self.token_cache else {
+            let user = self.user_for_claims(&claims).await?;
+            return Ok((user, claims.session_id));
+        };
+
+        let key = TokenCache::key(token);
+        if let Some(verified) = cache.get(&key) {
+            return Ok(verified);
+        }
+        let generation = cache.generation.load(Ordering::SeqCst);
+        let user = self.user_for_claims(&claims).await?;
+        cache.insert(key, user.clone(), claims.session_id, generation);
+        Ok((user, claims.session_id))
+    }
 
+    /// List a user's sessions that are neither revoked nor older than a token's lifetime
+    pub async fn list_sessions(&self, user_id: i32) -> Result<Vec<Session>, ServiceError> {
+        let Some(session_repository) = &self.session_repository else {
+            return Ok(Vec::new());
+        };
+        let created_after =
+            Utc::now() - chrono::Duration::hours(i64::from(self.token_expiration_hours));
+        session_repository
+            .find_active_for_user(user_id, created_after)
+            .await
+            .map_err(|e| ServiceError::SessionStore(e.to_string()))
+    }
+
+    /// Revoke one of a user's sessions, returning `false` if it is not theirs or not active
+    pub async fn revoke_session(
+        &self,
+        user_id: i32,
+        session_id: Uuid,
+    ) -> Result<bool, ServiceError> {
+        let Some(session_repository) = &self.session_repository else {
+            return Ok(false);
+        };
+        let revoked = session_repository
+            .revoke(session_id, user_id)
+            .await
+            .map_err(|e| ServiceError::SessionStore(e.to_string()))?;
+        if revoked {
+            self.invalidate_user(user_id).await;
+        }
+        Ok(revoked)
+    }
+
+    /// Revoke every session of a user except `current`, returning how many were revoked
+    pub async fn revoke_other_sessions(
+        &self,
+        user_id: i32,
+        current: Option<Uuid>,
+    ) -> Result<usize, ServiceError> {
+        let Some(session_repository) = &self.session_repository else {
+            return Ok(0);
+        };
+        let revoked = session_repository
+            .revoke_all_except(user_id, current)
+            .await
+            .map_err(|e| ServiceError::SessionStore(e.to_string()))?;
+        if revoked > 0 {
+            self.invalidate_user(user_id).await;
+        }
+        Ok(revoked)
+    }
+
+    /// Inspect a token on behalf of another service (RFC 7662)
+    ///
+    /// Returns `None` for any token `verify_token` would reject: bad signature, expired,
+    /// issued before a password change, or belonging to a missing or suspended user.
+    pub async fn introspect_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<TokenIntrospection>, ServiceError> {
+        let Ok(claims) = self.decode_jwt_token(token) else {
+            return Ok(None);
+        };
+
+        let user = match self.user_for_claims(&claims).await {
+            Ok(user) => user,
+            Err(e @ (ServiceError::Repository(_) | ServiceError::SessionStore(_))) => {
+                return Err(e)
+            }
+            Err(_) => return Ok(None),
+        };
+
+        let mut scopes = vec!["user".to_string()];
+        if self.is_admin(&user) {
+            scopes.push("admin".to_string());
+        }
+
+        Ok(Some(TokenIntrospection {
+            user_id: user.id,
+            username: user.username,
+            email: user.email,
+            exp: claims.exp,
+            iat: claims.iat,
+            scopes,
+        }))
+    }
+
+    /// Load the user a token was issued to, checking it is still valid for them
+    async fn user_for_claims(&self, claims: &Claims) -> Result<User, ServiceError> {
         let user = self
             .user_repository
             .find_by_id(claims.sub)
@@ -105,11 +584,228 @@ where
             .map_err(ServiceError::Repository)?
             .ok_or(ServiceError::UserNotFound)?;
 
+        // Changing the password ends every session started before the change
+        if claims.password_changed_at != Self::password_changed_claim(&user) {
+            return Err(ServiceError::TokenValidation(
+                "Token was issued before the last password change".to_string(),
+            ));
+        }
+
+        Self::ensure_not_suspended(&user)?;
+
+        if let Some(session_id) = claims.session_id {
+            self.check_session(session_id, user.id).await?;
+        }
+
+        Ok(user)
+    }
+
+    /// Record a new session for the user, if sessions are tracked
+    async fn start_session(
+        &self,
+        user: &User,
+        client: &SessionClient,
+    ) -> Result<Option<Uuid>, ServiceError> {
+        let Some(session_repository) = &self.session_repository else {
+            return Ok(None);
+        };
+        let session = session_repository
+            .create_session(Session::new(user.id, client))
+            .await
+            .map_err(|e| ServiceError::SessionStore(e.to_string()))?;
+        Ok(Some(session.id))
+    }
+
+    /// Reject tokens of revoked sessions and note that the session was used
+    async fn check_session(&self, session_id: Uuid, user_id: i32) -> Result<(), ServiceError> {
+        let Some(session_repository) = &self.session_repository else {
+            return Ok(());
+        };
+        let session = session_repository
+            .find_by_id(session_id)
+            .await
+            .map_err(|e| ServiceError::SessionStore(e.to_string()))?;
+        if !session.is_some_and(|session| !session.revoked && session.user_id == user_id) {
+            return Err(ServiceError::TokenValidation(
+                "Session has been revoked".to_string(),
+            ));
+        }
+
+        if self.claim_session_touch(session_id) {
+            if let Err(e) = session_repository.touch(session_id, Utc::now()).await {
+                warn!("Failed to record use of session {}: {}", session_id, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `last_used_at` of a session is due to be written again
+    ///
+    /// Throttled to once per [`SESSION_TOUCH_INTERVAL`] per instance, so busy clients do not
+    /// turn every request into a database write.
+    fn claim_session_touch(&self, session_id: Uuid) -> bool {
+        if self.session_touches.len() >= MAX_TRACKED_SESSION_TOUCHES {
+            self.session_touches
+                .retain(|_, touched_at| touched_at.elapsed() < SESSION_TOUCH_INTERVAL);
+        }
+
+        let mut due = true;
+        self.session_touches
+            .entry(session_id)
+            .and_modify(|touched_at| {
+                due = touched_at.elapsed() >= SESSION_TOUCH_INTERVAL;
+                if due {
+                    *touched_at = Instant::now();
+                }
+            })
+            .or_insert_with(Instant::now);
+        due
+    }
+
+    /// Change a user's password after checking the current one
+    ///
+    /// Session tokens issued before the change stop working; callers should also
+    /// invalidate any pending password reset tokens.
+    pub async fn change_password(
+        &self,
+        user: &User,
+        current_password: &str,
+        new_password: &str,
+    ) -> Result<User, ServiceError> {
+        let is_valid = verify(current_password, &user.password_hash)
+            .map_err(|e| ServiceError::PasswordVerification(e.to_string()))?;
+        if !is_valid {
+            return Err(ServiceError::InvalidCredentials);
+        }
+
+        if new_password.len() < 8 {
+            return Err(ServiceError::InvalidInput(
+                "Password must be at least 8 characters long".to_string(),
+            ));
+        }
+
+        let password_hash = hash(new_password, DEFAULT_COST)
+            .map_err(|e| ServiceError::PasswordHashing(e.to_string()))?;
+
+        let user = self
+            .user_repository
+            .update_password(user.id, &password_hash)
+            .await
+            .map_err(|e| match e {
+                RepositoryError::NotFound => ServiceError::UserNotFound,
+                e => ServiceError::Repository(e),
+            })?;
+        self.invalidate_user(user.id).await;
+        Ok(user)
+    }
+
+    /// Suspend a user account, optionally until a given time
+    pub async fn suspend_user(
+        &self,
+        user_id: i32,
+        reason: &str,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<User, ServiceError> {
+        if reason.trim().is_empty() {
+            return Err(ServiceError::InvalidInput(
+                "Suspension reason cannot be empty".to_string(),
+            ));
+        }
+
+        if until.is_some_and(|until| until <= Utc::now()) {
+            return Err(ServiceError::InvalidInput(
+                "Suspension end must be in the future".to_string(),
+            ));
+        }
+
+        let status = AccountStatus::Suspended {
+            reason: reason.trim().to_string(),
+            until,
+        };
+        self.update_account_status(user_id, &status).await
+    }
+
+    /// Lift a suspension and reactivate the account
+    pub async fn unsuspend_user(&self, user_id: i32) -> Result<User, ServiceError> {
+        self.update_account_status(user_id, &AccountStatus::Active)
+            .await
+    }
+
+    /// Count a wrong password, suspending the account once `LOGIN_LOCKOUT_THRESHOLD` is reached
+    async fn record_failed_login(&self, user: &User) {
+        if user.is_suspended() {
+            return;
+        }
+
+        let failures = {
+            let mut entry = self
+                .failed_logins
+                .entry(user.id)
+                .or_insert((0, Instant::now()));
+            let (count, started) = entry.value_mut();
+            if started.elapsed() >= LOGIN_LOCKOUT_WINDOW {
+                *count = 0;
+                *started = Instant::now();
+            }
+            *count += 1;
+            *count
+        };
+
+        if failures >= LOGIN_LOCKOUT_THRESHOLD {
+            self.failed_logins.remove(&user.id);
+            if let Err(e) = self.suspend_after_lockout(user.id).await {
+                warn!(
+                    "Failed to suspend user {} after login lockout: {}",
+                    user.id, e
+                );
+            }
+        }
+    }
+
+    /// Suspend an account for an hour after the login lockout threshold is reached
+    pub async fn suspend_after_lockout(&self, user_id: i32) -> Result<User, ServiceError> {
+        self.update_account_status(user_id, &AccountStatus::lockout(Utc::now()))
+            .await
+    }
+
+    /// Persist a new account status
+    async fn update_account_status(
+        &self,
+        user_id: i32,
+        status: &AccountStatus,
+    ) -> Result<User, ServiceError> {
+        let user = self
+            .user_repository
+            .update_account_status(user_id, status)
+            .await
+            .map_err(|e| match e {
+                RepositoryError::NotFound => ServiceError::UserNotFound,
+                e => ServiceError::Repository(e),
+            })?;
+        self.invalidate_user(user_id).await;
         Ok(user)
     }
 
+    /// Reject users whose account is currently suspended
+    fn ensure_not_suspended(user: &User) -> Result<(), ServiceError> {
+        if let AccountStatus::Suspended { reason, until } = &user.account_status {
+            if user.is_suspended() {
+                return Err(ServiceError::AccountSuspended {
+                    reason: reason.clone(),
+                    until: *until,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Generate JWT token for user
-    fn generate_jwt_token(&self, user: &User) -> Result<String, ServiceError> {
+    fn generate_jwt_token(
+        &self,
+        user: &User,
+        session_id: Option<Uuid>,
+    ) -> Result<String, ServiceError> {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
@@ -118,8 +814,10 @@ where
         let claims = Claims {
             sub: user.id,
             username: user.username.clone(),
-            exp: now + (24 * 60 * 60), // 24 hours
+            exp: now + self.token_expiration_hours as usize * 60 * 60,
             iat: now,
+            password_changed_at: Self::password_changed_claim(user),
+            session_id,
         };
 
         let token = encode(
@@ -132,6 +830,12 @@ where
         Ok(token)
     }
 
+    /// Value of the `password_changed_at` claim for a user
+    fn password_changed_claim(user: &User) -> Option<i64> {
+        user.password_changed_at
+            .map(|changed_at| changed_at.timestamp_millis())
+    }
+
     /// Decode JWT token
     fn decode_jwt_token(&self, token: &str) -> Result<Claims, ServiceError> {
         let validation = Validation::new(Algorithm::HS256);
@@ -218,4 +922,489 @@ pub enum ServiceError {
 
     #[error("Token validation error: {0}")]
     TokenValidation(String),
+
+    #[error("Session store error: {0}")]
+    SessionStore(String),
+
+    #[error("Account suspended: {reason}")]
+    AccountSuspended {
+        reason: String,
+        until: Option<DateTime<Utc>>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_utils::{MockSessionRepository, MockUserRepository};
+
+    #[tokio::test]
+    async fn test_register_rejects_email_in_different_case() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string());
+
+        let user = service
+            .register("mixedcase", "John.Doe@Example.com", "password123")
+            .await
+            .unwrap();
+        assert_eq!(user.email, "john.doe@example.com");
+
+        for email in [
+            "john.doe@example.com",
+            "JOHN.DOE@EXAMPLE.COM",
+            "John.Doe@example.COM",
+        ] {
+            let result = service.register("another", email, "password123").await;
+            assert!(matches!(result, Err(ServiceError::EmailAlreadyExists)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_token_rejects_suspended_user() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string());
+        let user = service
+            .register("suspended", "suspended@example.com", "password123")
+            .await
+            .unwrap();
+        let token = service
+            .login("suspended", "password123", &SessionClient::default())
+            .await
+            .unwrap();
+
+        service.suspend_user(user.id, "Spam", None).await.unwrap();
+
+        let result = service.verify_token(&token).await;
+        assert!(matches!(
+            result,
+            Err(ServiceError::AccountSuspended { ref reason, until: None }) if reason == "Spam"
+        ));
+        assert!(matches!(
+            service
+                .login("suspended", "password123", &SessionClient::default())
+                .await,
+            Err(ServiceError::AccountSuspended { .. })
+        ));
+
+        service.unsuspend_user(user.id).await.unwrap();
+        assert!(service.verify_token(&token).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_verify_email_activates_pending_account() {
+        let repository = MockUserRepository::new();
+        let service = AuthService::new(repository.clone(), "secret".to_string());
+        let user = service
+            .register_unverified(
+                "pending",
+                "Pending@example.com",
+                "password123",
+                "https://short.ly",
+            )
+            .await
+            .unwrap();
+        assert_eq!(user.account_status, AccountStatus::PendingVerification);
+
+        let queued = repository.outbox().emails();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].recipient, "pending@example.com");
+        let token = queued[0]
+            .body_text
+            .split("https://short.ly/auth/verify-email?token=")
+            .nth(1)
+            .and_then(|rest| rest.lines().next())
+            .unwrap();
+
+        let verified = service.verify_email(token).await.unwrap();
+        assert_eq!(verified.account_status, AccountStatus::Active);
+
+        // Opening the link again changes nothing
+        let verified = service.verify_email(token).await.unwrap();
+        assert_eq!(verified.account_status, AccountStatus::Active);
+    }
+
+    #[tokio::test]
+    async fn test_failed_registration_queues_no_welcome_email() {
+        let repository = MockUserRepository::new();
+        let service = AuthService::new(repository.clone(), "secret".to_string());
+        service
+            .register("taken", "taken@example.com", "password123")
+            .await
+            .unwrap();
+
+        let result = service
+            .register_unverified(
+                "taken",
+                "other@example.com",
+                "password123",
+                "https://short.ly",
+            )
+            .await;
+        assert!(matches!(result, Err(ServiceError::UsernameAlreadyExists)));
+        assert!(repository.outbox().emails().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verify_email_rejects_session_tokens() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string());
+        service
+            .register("session", "session@example.com", "password123")
+            .await
+            .unwrap();
+        let session_token = service
+            .login("session", "password123", &SessionClient::default())
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            service.verify_email(&session_token).await,
+            Err(ServiceError::TokenValidation(_))
+        ));
+        let verification_token = service
+            .email_verification_token("session@example.com")
+            .unwrap();
+        assert!(service.verify_token(&verification_token).await.is_err());
+    }
+
+    /// Records the users it is asked to invalidate
+    #[derive(Default)]
+    struct RecordingInvalidation {
+        user_ids: std::sync::Mutex<Vec<i32>>,
+    }
+
+    #[async_trait::async_trait]
+    impl UserInvalidationBroadcaster for RecordingInvalidation {
+        async fn broadcast(
+            &self,
+            user_id: i32,
+        ) -> Result<(), crate::infrastructure::user_invalidation::UserInvalidationError> {
+            self.user_ids.lock().unwrap().push(user_id);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_cache_is_invalidated_by_suspension() {
+        let invalidation = Arc::new(RecordingInvalidation::default());
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string())
+            .with_token_cache_ttl(VERIFIED_TOKEN_CACHE_TTL)
+            .with_user_invalidation(invalidation.clone());
+        let user = service
+            .register("cached", "cached@example.com", "password123")
+            .await
+            .unwrap();
+        let token = service
+            .login("cached", "password123", &SessionClient::default())
+            .await
+            .unwrap();
+
+        assert!(service.verify_token(&token).await.is_ok());
+        assert!(service.verify_token(&token).await.is_ok());
+        assert_eq!(
+            service.token_cache_stats(),
+            TokenCacheStats { hits: 1, misses: 1 }
+        );
+
+        service.suspend_user(user.id, "Spam", None).await.unwrap();
+        assert!(matches!(
+            service.verify_token(&token).await,
+            Err(ServiceError::AccountSuspended { .. })
+        ));
+        assert_eq!(*invalidation.user_ids.lock().unwrap(), vec![user.id]);
+    }
+
+    #[tokio::test]
+    async fn test_password_change_invalidates_existing_tokens() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string());
+        let user = service
+            .register("changer", "changer@example.com", "password123")
+            .await
+            .unwrap();
+        let old_token = service
+            .login("changer", "password123", &SessionClient::default())
+            .await
+            .unwrap();
+        assert!(service.verify_token(&old_token).await.is_ok());
+
+        assert!(matches!(
+            service
+                .change_password(&user, "wrong-password", "new-password-1")
+                .await,
+            Err(ServiceError::InvalidCredentials)
+        ));
+        assert!(matches!(
+            service.change_password(&user, "password123", "short").await,
+            Err(ServiceError::InvalidInput(_))
+        ));
+
+        service
+            .change_password(&user, "password123", "new-password-1")
+            .await
+            .unwrap();
+
+        // Rejected even though it was issued in the same second as the change
+        assert!(matches!(
+            service.verify_token(&old_token).await,
+            Err(ServiceError::TokenValidation(_))
+        ));
+        assert_eq!(service.introspect_token(&old_token).await.unwrap(), None);
+        assert!(service
+            .login("changer", "password123", &SessionClient::default())
+            .await
+            .is_err());
+
+        let new_token = service
+            .login("changer", "new-password-1", &SessionClient::default())
+            .await
+            .unwrap();
+        assert!(service.verify_token(&new_token).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_suspend_after_lockout() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string());
+        let user = service
+            .register("lockedout", "locked@example.com", "password123")
+            .await
+            .unwrap();
+
+        let user = service.suspend_after_lockout(user.id).await.unwrap();
+        match user.account_status {
+            AccountStatus::Suspended { reason, until } => {
+                assert_eq!(reason, AccountStatus::LOCKOUT_REASON);
+                assert!(until.unwrap() > Utc::now() + chrono::Duration::minutes(59));
+            }
+            other => panic!("expected suspension, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_wrong_passwords_suspend_the_account() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string());
+        service
+            .register("guessed", "guessed@example.com", "password123")
+            .await
+            .unwrap();
+        let client = SessionClient::default();
+
+        for _ in 0..LOGIN_LOCKOUT_THRESHOLD {
+            let result = service.login("guessed", "wrong-password", &client).await;
+            assert!(matches!(result, Err(ServiceError::InvalidCredentials)));
+        }
+
+        match service.login("guessed", "password123", &client).await {
+            Err(ServiceError::AccountSuspended { reason, until }) => {
+                assert_eq!(reason, AccountStatus::LOCKOUT_REASON);
+                assert!(until.is_some());
+            }
+            other => panic!("expected suspension, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_successful_login_resets_failed_logins() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string());
+        service
+            .register("forgetful", "forgetful@example.com", "password123")
+            .await
+            .unwrap();
+        let client = SessionClient::default();
+
+        for _ in 0..2 {
+            for _ in 1..LOGIN_LOCKOUT_THRESHOLD {
+                let _ = service.login("forgetful", "wrong-password", &client).await;
+            }
+            assert!(service
+                .login("forgetful", "password123", &client)
+                .await
+                .is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_suspend_unknown_user() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string());
+        let result = service.suspend_user(42, "Spam", None).await;
+        assert!(matches!(result, Err(ServiceError::UserNotFound)));
+    }
+
+    #[test]
+    fn test_is_admin() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string())
+            .with_admin_user_ids(vec![1]);
+        let admin = User::new_with_timestamp(1, "admin".into(), "a@example.com".into(), "h".into());
+        let user = User::new_with_timestamp(2, "user".into(), "u@example.com".into(), "h".into());
+
+        assert!(service.is_admin(&admin));
+        assert!(!service.is_admin(&user));
+    }
+
+    #[tokio::test]
+    async fn test_introspect_active_token() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string())
+            .with_admin_user_ids(vec![1]);
+        service
+            .register("alice", "alice@example.com", "password123")
+            .await
+            .unwrap();
+        let token = service
+            .login("alice", "password123", &SessionClient::default())
+            .await
+            .unwrap();
+
+        let introspection = service.introspect_token(&token).await.unwrap().unwrap();
+        assert_eq!(introspection.user_id, 1);
+        assert_eq!(introspection.email, "alice@example.com");
+        assert!(introspection.exp > introspection.iat);
+        assert_eq!(introspection.scopes, vec!["user", "admin"]);
+    }
+
+    #[tokio::test]
+    async fn test_introspect_expired_token() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string());
+        let user = service
+            .register("alice", "alice@example.com", "password123")
+            .await
+            .unwrap();
+
+        let issued_at = (Utc::now() - chrono::Duration::hours(3)).timestamp() as usize;
+        let claims = Claims {
+            sub: user.id,
+            username: user.username.clone(),
+            exp: issued_at + 60 * 60,
+            iat: issued_at,
+            password_changed_at: None,
+            session_id: None,
+        };
+        let token = encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret("secret".as_ref()),
+        )
+        .unwrap();
+
+        assert_eq!(service.introspect_token(&token).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_introspect_token_with_invalid_signature() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string());
+        let other = AuthService::new(MockUserRepository::new(), "other-secret".to_string());
+        other
+            .register("alice", "alice@example.com", "password123")
+            .await
+            .unwrap();
+        let forged = other
+            .login("alice", "password123", &SessionClient::default())
+            .await
+            .unwrap();
+
+        assert_eq!(service.introspect_token(&forged).await.unwrap(), None);
+        assert_eq!(service.introspect_token("not-a-jwt").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_introspect_token_of_suspended_user() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string());
+        let user = service
+            .register("alice", "alice@example.com", "password123")
+            .await
+            .unwrap();
+        let token = service
+            .login("alice", "password123", &SessionClient::default())
+            .await
+            .unwrap();
+
+        service.suspend_user(user.id, "Spam", None).await.unwrap();
+        assert_eq!(service.introspect_token(&token).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_revoked_session_rejects_its_token_only() {
+        let sessions = MockSessionRepository::new();
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string())
+            .with_session_repository(Arc::new(sessions.clone()));
+        let user = service
+            .register("alice", "alice@example.com", "password123")
+            .await
+            .unwrap();
+        let laptop = SessionClient {
+            ip_address: Some("203.0.113.7".to_string()),
+            user_agent: Some(
+                "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) Chrome/120.0".to_string(),
+            ),
+        };
+        let laptop_token = service
+            .login("alice", "password123", &laptop)
+            .await
+            .unwrap();
+        let phone_token = service
+            .login("alice", "password123", &SessionClient::default())
+            .await
+            .unwrap();
+        let tablet_token = service
+            .issue_token(&user, &SessionClient::default())
+            .await
+            .unwrap();
+
+        let listed = service.list_sessions(user.id).await.unwrap();
+        assert_eq!(listed.len(), 3);
+        assert!(listed
+            .iter()
+            .any(|s| s.device_name.as_deref() == Some("Chrome on macOS")));
+
+        let (_, phone_session) = service
+            .verify_token_with_session(&phone_token)
+            .await
+            .unwrap();
+        let phone_session = phone_session.expect("token carries its session");
+        assert!(service
+            .revoke_session(user.id, phone_session)
+            .await
+            .unwrap());
+        assert!(!service
+            .revoke_session(user.id, phone_session)
+            .await
+            .unwrap());
+        assert!(!service
+            .revoke_session(user.id + 1, phone_session)
+            .await
+            .unwrap());
+
+        assert!(matches!(
+            service.verify_token(&phone_token).await,
+            Err(ServiceError::TokenValidation(_))
+        ));
+        assert_eq!(service.introspect_token(&phone_token).await.unwrap(), None);
+        assert!(service.verify_token(&laptop_token).await.is_ok());
+
+        let (_, laptop_session) = service
+            .verify_token_with_session(&laptop_token)
+            .await
+            .unwrap();
+        assert_eq!(
+            service
+                .revoke_other_sessions(user.id, laptop_session)
+                .await
+                .unwrap(),
+            1
+        );
+        assert!(service.verify_token(&tablet_token).await.is_err());
+        assert!(service.verify_token(&laptop_token).await.is_ok());
+        assert_eq!(service.list_sessions(user.id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_session_last_used_is_throttled() {
+        let service = AuthService::new(MockUserRepository::new(), "secret".to_string())
+            .with_session_repository(Arc::new(MockSessionRepository::new()));
+        let session_id = Uuid::new_v4();
+
+        assert!(service.claim_session_touch(session_id));
+        assert!(!service.claim_session_touch(session_id));
+
+        service
+            .session_touches
+            .insert(session_id, Instant::now() - SESSION_TOUCH_INTERVAL);
+        assert!(service.claim_session_touch(session_id));
+        assert!(!service.claim_session_touch(session_id));
+    }
 }
@@ -0,0 +1,274 @@
+//! Operations the batch endpoints and the bulk processor apply to URLs
+//!
+//! The processor only knows the [`BatchOperation`] trait. Adding an operation, say archiving,
+//! takes a struct implementing it and a line in [`batch_operation`] so clients can name it:
+//!
+//! ```ignore
+//! pub struct ArchiveOperation;
+//!
+//! #[async_trait]
+//! impl BatchOperation for ArchiveOperation {
+//!     fn name(&self) -> &'static str {
+//!         "archive"
+//!     }
+//!
+//!     async fn execute(
+//!         &self,
+//!         url_id: i32,
+//!         user_id: Option<i32>,
+//!         _data: Option<&Value>,
+//!         repo: &dyn UrlRepository,
+//!     ) -> Result<(), ServiceError> {
+//!         let result = repo
+//!             .batch_update_status(&[url_id], UrlStatus::Archived, user_id)
+//!             .await?;
+//!         single_url_result(result)
+//!     }
+//! }
+//! ```
+//!
+//! Operations reading `data` should also override [`BatchOperation::check_data`], so bad
+//! settings are rejected before any URL changes.
+
+use crate::application::dto::requests::BatchOperationData;
+use crate::domain::entities::UrlStatus;
+use crate::domain::repositories::url_repository::BatchOperationResult;
+use crate::domain::repositories::UrlRepository;
+use crate::domain::services::ServiceError;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::fmt;
+
+/// Names clients can send as a batch `operation`
+pub const BATCH_OPERATION_NAMES: &[&str] = &[
+    "deactivate",
+    "reactivate",
+    "delete",
+    "update_status",
+    "update_expiration",
+];
+
+/// A change applied to each URL of a batch operation
+#[async_trait]
+pub trait BatchOperation: Send + Sync {
+    /// Name clients send as the batch `operation`
+    fn name(&self) -> &'static str;
+
+    /// Reject `data` this operation cannot work with
+    fn check_data(&self, _data: Option<&Value>) -> Result<(), ServiceError> {
+        Ok(())
+    }
+
+    /// Apply the operation to one URL; only URLs of `user_id` are changed when it is set
+    async fn execute(
+        &self,
+        url_id: i32,
+        user_id: Option<i32>,
+        data: Option<&Value>,
+        repo: &dyn UrlRepository,
+    ) -> Result<(), ServiceError>;
+}
+
+impl fmt::Debug for dyn BatchOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The operation clients call `name`, if there is one
+pub fn batch_operation(name: &str) -> Option<Box<dyn BatchOperation>> {
+    let operation: Box<dyn BatchOperation> = match name {
+        "deactivate" => Box::new(DeactivateOperation),
+        "reactivate" => Box::new(ReactivateOperation),
+        "delete" => Box::new(DeleteOperation),
+        "update_status" => Box::new(UpdateStatusOperation),
+        "update_expiration" => Box::new(UpdateExpirationOperation),
+        _ => return None,
+    };
+    Some(operation)
+}
+
+/// Turn the repository's result for a single URL into the operation's result
+pub fn single_url_result(result: BatchOperationResult) -> Result<(), ServiceError> {
+    match result.results.into_iter().next() {
+        Some(item) if item.success => Ok(()),
+        Some(item) => Err(ServiceError::PermissionDenied(
+            item.error
+                .unwrap_or_else(|| "URL not found or unauthorized".to_string()),
+        )),
+        None => Err(ServiceError::PermissionDenied(
+            "URL not found or unauthorized".to_string(),
+        )),
+    }
+}
+
+/// The operation settings in `data`; missing data has no settings
+fn operation_data(data: Option<&Value>) -> Result<BatchOperationData, ServiceError> {
+    data.map_or(Ok(BatchOperationData::default()), |data| {
+        serde_json::from_value(data.clone())
+            .map_err(|e| ServiceError::InvalidData(format!("Invalid operation data: {}", e)))
+    })
+}
+
+/// Stop URLs from redirecting
+pub struct DeactivateOperation;
+
+#[async_trait]
+impl BatchOperation for DeactivateOperation {
+    fn name(&self) -> &'static str {
+        "deactivate"
+    }
+
+    async fn execute(
+        &self,
+        url_id: i32,
+        user_id: Option<i32>,
+        _data: Option<&Value>,
+        repo: &dyn UrlRepository,
+    ) -> Result<(), ServiceError> {
+        single_url_result(repo.batch_deactivate_urls(&[url_id], user_id).await?)
+    }
+}
+
+/// Make deactivated URLs redirect again
+pub struct ReactivateOperation;
+
+#[async_trait]
+impl BatchOperation for ReactivateOperation {
+    fn name(&self) -> &'static str {
+        "reactivate"
+    }
+
+    async fn execute(
+        &self,
+        url_id: i32,
+        user_id: Option<i32>,
+        _data: Option<&Value>,
+        repo: &dyn UrlRepository,
+    ) -> Result<(), ServiceError> {
+        single_url_result(repo.batch_reactivate_urls(&[url_id], user_id).await?)
+    }
+}
+
+/// Delete URLs
+pub struct DeleteOperation;
+
+#[async_trait]
+impl BatchOperation for DeleteOperation {
+    fn name(&self) -> &'static str {
+        "delete"
+    }
+
+    async fn execute(
+        &self,
+        url_id: i32,
+        user_id: Option<i32>,
+        _data: Option<&Value>,
+        repo: &dyn UrlRepository,
+    ) -> Result<(), ServiceError> {
+        single_url_result(repo.batch_delete_urls(&[url_id], user_id).await?)
+    }
+}
+
+/// Set the status in `data.status`, `active` or `inactive`
+pub struct UpdateStatusOperation;
+
+impl UpdateStatusOperation {
+    fn status(data: Option<&Value>) -> Result<UrlStatus, ServiceError> {
+        match operation_data(data)?.status.as_deref() {
+            Some("active") => Ok(UrlStatus::Active),
+            Some("inactive") => Ok(UrlStatus::Inactive),
+            _ => Err(ServiceError::InvalidData(
+                "Invalid status provided".to_string(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl BatchOperation for UpdateStatusOperation {
+    fn name(&self) -> &'static str {
+        "update_status"
+    }
+
+    fn check_data(&self, data: Option<&Value>) -> Result<(), ServiceError> {
+        Self::status(data).map(|_| ())
+    }
+
+    async fn execute(
+        &self,
+        url_id: i32,
+        user_id: Option<i32>,
+        data: Option<&Value>,
+        repo: &dyn UrlRepository,
+    ) -> Result<(), ServiceError> {
+        let status = Self::status(data)?;
+        single_url_result(repo.batch_update_status(&[url_id], status, user_id).await?)
+    }
+}
+
+/// Set the expiration in `data.expiration_date`, removing it when unset
+pub struct UpdateExpirationOperation;
+
+#[async_trait]
+impl BatchOperation for UpdateExpirationOperation {
+    fn name(&self) -> &'static str {
+        "update_expiration"
+    }
+
+    fn check_data(&self, data: Option<&Value>) -> Result<(), ServiceError> {
+        operation_data(data).map(|_| ())
+    }
+
+    async fn execute(
+        &self,
+        url_id: i32,
+        user_id: Option<i32>,
+        data: Option<&Value>,
+        repo: &dyn UrlRepository,
+    ) -> Result<(), ServiceError> {
+        let expiration_date = operation_data(data)?.expiration_date;
+        single_url_result(
+            repo.batch_update_expiration(&[url_id], expiration_date, user_id)
+                .await?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_every_name_maps_to_its_operation() {
+        for name in BATCH_OPERATION_NAMES {
+            assert_eq!(batch_operation(name).unwrap().name(), *name);
+        }
+        assert!(batch_operation("archive").is_none());
+    }
+
+    #[test]
+    fn test_update_status_checks_data() {
+        let operation = UpdateStatusOperation;
+        assert!(operation
+            .check_data(Some(&json!({ "status": "inactive" })))
+            .is_ok());
+        assert!(operation
+            .check_data(Some(&json!({ "status": "archived" })))
+            .is_err());
+        assert!(operation.check_data(None).is_err());
+    }
+
+    #[test]
+    fn test_update_expiration_checks_data() {
+        let operation = UpdateExpirationOperation;
+        assert!(operation.check_data(None).is_ok());
+        assert!(operation
+            .check_data(Some(&json!({ "expiration_date": "2030-01-01T00:00:00Z" })))
+            .is_ok());
+        assert!(operation
+            .check_data(Some(&json!({ "expiration_date": "tomorrow" })))
+            .is_err());
+    }
+}
@@ -1,20 +1,147 @@
-use crate::application::dto::requests::{BatchOperationData, BatchOperationType};
-use crate::domain::repositories::{UrlRepository, UserRepository};
-use crate::domain::services::{ProgressService, UrlService};
-use std::sync::Arc;
-use tokio::task;
-use tracing::{error, info};
+use crate::application::dto::requests::{OperationPriority, ShortenUrlRequest};
+use crate::application::dto::responses::{BulkItemResult, BulkOperationStatus};
+use crate::domain::entities::{ShortCode, Url, User, UserTier};
+use crate::domain::repositories::{RepositoryError, UrlRepository, UserDataExport, UserRepository};
+use crate::domain::services::batch_operations::BatchOperation;
+use crate::domain::services::bulk_queue::BulkOperationQueue;
+use crate::domain::services::progress_service::ChainedOperation;
+use crate::domain::services::{
+    NotificationService, ProgressService, ProgressServiceError, ServiceError, UrlService,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, Semaphore};
+use tokio::task::{self, JoinSet};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+/// Maximum number of bulk operations processed at the same time
+///
+/// Further operations wait in the priority queue, where they can still be reprioritized.
+const MAX_CONCURRENT_OPERATIONS: usize = 4;
+
+/// Parameters of a chained operation, kept with it until its parent completes
+#[derive(Debug, Serialize, Deserialize)]
+struct ChainedOperationParams {
+    /// URLs to apply the operation to; the URLs the parent created when missing
+    url_ids: Option<Vec<i32>>,
+    data: Option<serde_json::Value>,
+    /// User who chained the operation, whose URLs it may change
+    user_id: Option<i32>,
+}
+
+/// Bulk processor settings
+#[derive(Debug, Clone, Copy)]
+pub struct BulkProcessorConfig {
+    /// Times an item failing with a transient error is retried before it counts as failed
+    pub max_item_retries: u8,
+    /// Delay before the first retry; it doubles with every further attempt
+    pub retry_base_delay_ms: u64,
+    /// URLs of one bulk creation that are created at the same time
+    pub max_concurrent_items: usize,
+}
+
+impl Default for BulkProcessorConfig {
+    fn default() -> Self {
+        Self {
+            max_item_retries: 3,
+            retry_base_delay_ms: 100,
+            max_concurrent_items: 4,
+        }
+    }
+}
+
+impl BulkProcessorConfig {
+    /// Delay before retry number `attempt` (0-based): 100ms, 200ms, 400ms, ... by default
+    fn retry_delay(&self, attempt: u8) -> Duration {
+        let factor = 1u64.checked_shl(attempt.into()).unwrap_or(u64::MAX);
+        Duration::from_millis(self.retry_base_delay_ms.saturating_mul(factor))
+    }
+}
+
+/// Work another service runs through the bulk processor
+pub type BulkTask = Pin<Box<dyn Future<Output = Result<(), BulkProcessorError>> + Send>>;
+
+/// A bulk operation waiting in the queue
+enum BulkJob {
+    Operation {
+        operation: Box<dyn BatchOperation>,
+        url_ids: Vec<i32>,
+        data: Option<serde_json::Value>,
+        user_id: Option<i32>,
+    },
+    UrlCreation {
+        urls: Vec<ShortenUrlRequest>,
+        user_id: Option<i32>,
+    },
+    DataExport {
+        user_id: i32,
+        reply: oneshot::Sender<Result<UserDataExport, BulkProcessorError>>,
+    },
+    Task {
+        task: BulkTask,
+    },
+}
+
+/// Handle for queueing [`BulkTask`]s on a bulk processor
+///
+/// Tasks share the processor's priorities and concurrency limit; each one is tracked as an
+/// operation with a single item.
+#[derive(Clone)]
+pub struct BulkTaskQueue {
+    queue: Arc<BulkOperationQueue<BulkJob>>,
+    progress_service: ProgressService,
+}
+
+impl BulkTaskQueue {
+    /// Queue the task built for a new operation, returning the operation's ID
+    pub async fn queue_task(
+        &self,
+        priority: OperationPriority,
+        task: impl FnOnce(&str) -> BulkTask,
+    ) -> Result<String, BulkProcessorError> {
+        let operation_id = self.progress_service.create_operation(1).await;
+        let task = task(&operation_id);
+        info!(
+            "Queueing task {} at {} priority",
+            operation_id,
+            priority.as_str()
+        );
+        enqueue(
+            &self.queue,
+            &self.progress_service,
+            operation_id.clone(),
+            priority,
+            BulkJob::Task { task },
+        )
+        .await?;
+        Ok(operation_id)
+    }
+}
 
 /// Service for processing bulk operations in the background
+///
+/// Operations are queued by priority and started highest priority first.
 #[derive(Clone)]
 pub struct BulkProcessor<R, U>
 where
     R: UrlRepository + Send + Sync + Clone + 'static,
     U: UserRepository + Send + Sync + Clone + 'static,
 {
-    url_service: UrlService<R>,
     progress_service: ProgressService,
+    queue: Arc<BulkOperationQueue<BulkJob>>,
+    url_service: UrlService<R>,
+    config: BulkProcessorConfig,
     _user_repository: Arc<U>,
+    /// Shared with the dispatcher, which is already running when it is set
+    notification_service: Arc<OnceLock<NotificationService>>,
 }
 
 impl<R, U> BulkProcessor<R, U>
@@ -22,277 +149,590 @@ where
     R: UrlRepository + Send + Sync + Clone + 'static,
     U: UserRepository + Send + Sync + Clone + 'static,
 {
+    /// Create the processor with default settings and start its dispatcher
     pub fn new(
         url_service: UrlService<R>,
         progress_service: ProgressService,
         user_repository: U,
     ) -> Self {
-        Self {
+        Self::with_config(
             url_service,
             progress_service,
-            _user_repository: Arc::new(user_repository),
+            user_repository,
+            BulkProcessorConfig::default(),
+        )
+    }
+
+    /// Create the processor and start its dispatcher
+    pub fn with_config(
+        url_service: UrlService<R>,
+        progress_service: ProgressService,
+        user_repository: U,
+        config: BulkProcessorConfig,
+    ) -> Self {
+        let queue = Arc::new(BulkOperationQueue::new());
+        let user_repository = Arc::new(user_repository);
+        let notification_service = Arc::new(OnceLock::new());
+        task::spawn(run_dispatcher(
+            queue.clone(),
+            url_service.clone(),
+            progress_service.clone(),
+            user_repository.clone(),
+            config,
+            notification_service.clone(),
+        ));
+
+        Self {
+            progress_service,
+            queue,
+            url_service,
+            config,
+            _user_repository: user_repository,
+            notification_service,
+        }
+    }
+
+    /// Notify users when a bulk operation or URL creation they started completes
+    ///
+    /// Only the first notification service given to a processor is used.
+    pub fn with_notification_service(self, notification_service: NotificationService) -> Self {
+        let _ = self.notification_service.set(notification_service);
+        self
+    }
+
+    /// Priority an operation is queued at: premium users always get `High`
+    pub fn effective_priority(
+        &self,
+        requested: OperationPriority,
+        user: &User,
+    ) -> OperationPriority {
+        match user.tier {
+            UserTier::Premium => OperationPriority::High,
+            UserTier::Free => requested,
         }
     }
 
-    /// Process a bulk operation in the background
+    /// Queue a bulk operation for background processing
+    ///
+    /// Any [`BatchOperation`] can be queued; `data` it rejects fails the call instead.
     pub async fn process_bulk_operation(
         &self,
         operation_id: String,
-        operation: BatchOperationType,
+        operation: Box<dyn BatchOperation>,
         url_ids: Vec<i32>,
-        data: Option<BatchOperationData>,
+        data: Option<serde_json::Value>,
         user_id: Option<i32>,
+        priority: OperationPriority,
     ) -> Result<(), BulkProcessorError> {
-        let total_items = url_ids.len();
+        operation
+            .check_data(data.as_ref())
+            .map_err(|e| BulkProcessorError::InvalidData(e.to_string()))?;
+        info!(
+            "Queueing bulk operation {} for {} URLs at {} priority",
+            operation_id,
+            url_ids.len(),
+            priority.as_str()
+        );
+        let job = BulkJob::Operation {
+            operation,
+            url_ids,
+            data,
+            user_id,
+        };
+        self.enqueue(operation_id, priority, job).await
+    }
+
+    /// Run a bulk operation once `depends_on` has completed, returning the new operation's ID
+    ///
+    /// Without `url_ids` the operation applies to the URLs `depends_on` created. It is
+    /// queued at the priority of `depends_on`; `data` it rejects fails the call instead.
+    pub async fn chain_bulk_operation(
+        &self,
+        depends_on: String,
+        operation: Box<dyn BatchOperation>,
+        url_ids: Option<Vec<i32>>,
+        data: Option<serde_json::Value>,
+        user_id: Option<i32>,
+    ) -> Result<String, BulkProcessorError> {
+        operation
+            .check_data(data.as_ref())
+            .map_err(|e| BulkProcessorError::InvalidData(e.to_string()))?;
+        let params = serde_json::to_value(ChainedOperationParams {
+            url_ids,
+            data,
+            user_id,
+        })
+        .map_err(|e| BulkProcessorError::ProcessingFailed(e.to_string()))?;
 
-        // Update status to processing
-        if let Err(e) = self
+        let operation_id = self
             .progress_service
-            .update_status(
-                &operation_id,
-                crate::application::dto::responses::BulkOperationStatus::Processing,
-            )
+            .create_chained_operation(depends_on.clone(), operation, params)
             .await
-        {
-            error!("Failed to update operation status to processing: {}", e);
-            return Err(BulkProcessorError::ProgressUpdateFailed(e.to_string()));
+            .map_err(BulkProcessorError::ChainRejected)?;
+        info!(
+            "Chained bulk operation {} to operation {}",
+            operation_id, depends_on
+        );
+        Ok(operation_id)
+    }
+
+    /// Queue a bulk URL creation for background processing
+    pub async fn process_bulk_url_creation(
+        &self,
+        operation_id: String,
+        urls: Vec<ShortenUrlRequest>,
+        user_id: Option<i32>,
+        priority: OperationPriority,
+    ) -> Result<(), BulkProcessorError> {
+        info!(
+            "Queueing bulk URL creation {} for {} URLs at {} priority",
+            operation_id,
+            urls.len(),
+            priority.as_str()
+        );
+        self.enqueue(
+            operation_id,
+            priority,
+            BulkJob::UrlCreation { urls, user_id },
+        )
+        .await
+    }
+
+    /// Create URLs right away, yielding each item's result as soon as it completes
+    ///
+    /// Unlike `process_bulk_url_creation` the items skip the queue and the progress of
+    /// `operation_id` is left to the caller; cancelling the operation still stops it.
+    /// Dropping the stream lets items already running finish without starting more.
+    pub fn process_bulk_url_creation_streaming(
+        &self,
+        operation_id: String,
+        urls: Vec<ShortenUrlRequest>,
+        user_id: Option<i32>,
+    ) -> impl Stream<Item = BulkItemResult> {
+        info!(
+            "Streaming bulk URL creation {} for {} URLs",
+            operation_id,
+            urls.len()
+        );
+        ReceiverStream::new(stream_url_creation(
+            self.url_service.clone(),
+            self.progress_service.clone(),
+            self.config,
+            operation_id,
+            urls,
+            user_id,
+        ))
+    }
+
+    /// Queue a personal data export for background processing
+    ///
+    /// The collected data is delivered on the returned channel once the job has run; the
+    /// channel closes without a value if the operation is cancelled while queued.
+    pub async fn process_data_export(
+        &self,
+        operation_id: String,
+        user_id: i32,
+        priority: OperationPriority,
+    ) -> Result<oneshot::Receiver<Result<UserDataExport, BulkProcessorError>>, BulkProcessorError>
+    {
+        info!(
+            "Queueing data export {} for user {} at {} priority",
+            operation_id,
+            user_id,
+            priority.as_str()
+        );
+        let (reply, receiver) = oneshot::channel();
+        self.enqueue(
+            operation_id,
+            priority,
+            BulkJob::DataExport { user_id, reply },
+        )
+        .await?;
+        Ok(receiver)
+    }
+
+    /// Move a queued operation to another priority
+    pub async fn reprioritize(
+        &self,
+        operation_id: &str,
+        priority: OperationPriority,
+    ) -> Result<(), BulkProcessorError> {
+        if !self.queue.reprioritize(operation_id, priority) {
+            return Err(BulkProcessorError::NotQueued(operation_id.to_string()));
         }
+        self.progress_service
+            .set_priority(operation_id, priority)
+            .await
+            .map_err(|e| BulkProcessorError::ProgressUpdateFailed(e.to_string()))?;
 
         info!(
-            "Starting bulk operation {} for {} URLs",
-            operation_id, total_items
+            "Moved bulk operation {} to {} priority",
+            operation_id,
+            priority.as_str()
         );
+        Ok(())
+    }
 
-        // Spawn background task
-        let url_service = self.url_service.clone();
-        let progress_service = self.progress_service.clone();
+    /// Number of queued operations at each priority level
+    pub fn queue_depths(&self) -> Vec<(OperationPriority, usize)> {
+        self.queue.depths()
+    }
 
-        task::spawn(async move {
-            let mut processed_items = 0;
-            let mut successful_items = 0;
-            let mut failed_items = 0;
-            let batch_size = 10; // Process in batches of 10
-
-            // Process URLs in batches
-            for chunk in url_ids.chunks(batch_size) {
-                // Check if operation was cancelled
-                if let Ok(progress) = progress_service.get_progress(&operation_id).await {
-                    if matches!(
-                        progress.status,
-                        crate::application::dto::responses::BulkOperationStatus::Cancelled
-                    ) {
-                        info!(
-                            "Operation {} was cancelled, stopping processing",
-                            operation_id
-                        );
-                        break;
-                    }
-                }
+    /// Handle for queueing other services' work on this processor
+    pub fn task_queue(&self) -> BulkTaskQueue {
+        BulkTaskQueue {
+            queue: self.queue.clone(),
+            progress_service: self.progress_service.clone(),
+        }
+    }
 
-                // Process current batch
-                let batch_result = match &operation {
-                    BatchOperationType::Deactivate => {
-                        url_service.batch_deactivate_urls(chunk, user_id).await
-                    }
-                    BatchOperationType::Reactivate => {
-                        url_service.batch_reactivate_urls(chunk, user_id).await
-                    }
-                    BatchOperationType::Delete => {
-                        url_service.batch_delete_urls(chunk, user_id).await
-                    }
-                    BatchOperationType::UpdateStatus => {
-                        if let Some(ref batch_data) = data {
-                            if let Some(ref status_str) = batch_data.status {
-                                let status = match status_str.as_str() {
-                                    "active" => crate::domain::entities::UrlStatus::Active,
-                                    "inactive" => crate::domain::entities::UrlStatus::Inactive,
-                                    _ => {
-                                        error!("Invalid status in batch operation: {}", status_str);
-                                        continue;
-                                    }
-                                };
-                                url_service
-                                    .batch_update_status(chunk, status, user_id)
-                                    .await
-                            } else {
-                                error!("No status provided for UpdateStatus operation");
-                                continue;
-                            }
-                        } else {
-                            error!("No data provided for UpdateStatus operation");
-                            continue;
-                        }
-                    }
-                    BatchOperationType::UpdateExpiration => {
-                        let expiration_date = data.as_ref().and_then(|d| d.expiration_date);
-                        url_service
-                            .batch_update_expiration(chunk, expiration_date, user_id)
-                            .await
-                    }
-                };
+    async fn enqueue(
+        &self,
+        operation_id: String,
+        priority: OperationPriority,
+        job: BulkJob,
+    ) -> Result<(), BulkProcessorError> {
+        enqueue(
+            &self.queue,
+            &self.progress_service,
+            operation_id,
+            priority,
+            job,
+        )
+        .await
+    }
+}
 
-                match batch_result {
-                    Ok(result) => {
-                        processed_items += result.total_processed;
-                        successful_items += result.successful;
-                        failed_items += result.failed;
-
-                        // Update progress
-                        if let Err(e) = progress_service
-                            .update_progress(
-                                &operation_id,
-                                processed_items,
-                                successful_items,
-                                failed_items,
-                            )
-                            .await
-                        {
-                            error!(
-                                "Failed to update progress for operation {}: {}",
-                                operation_id, e
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        error!(
-                            "Batch operation failed for operation {}: {}",
-                            operation_id, e
-                        );
-                        failed_items += chunk.len();
-                        processed_items += chunk.len();
-
-                        // Update progress even on failure
-                        if let Err(progress_err) = progress_service
-                            .update_progress(
-                                &operation_id,
-                                processed_items,
-                                successful_items,
-                                failed_items,
-                            )
-                            .await
-                        {
-                            error!(
-                                "Failed to update progress after batch failure: {}",
-                                progress_err
-                            );
-                        }
-                    }
-                }
+/// Record the priority of `operation_id` and queue its job
+async fn enqueue(
+    queue: &BulkOperationQueue<BulkJob>,
+    progress_service: &ProgressService,
+    operation_id: String,
+    priority: OperationPriority,
+    job: BulkJob,
+) -> Result<(), BulkProcessorError> {
+    if let Err(e) = progress_service.set_priority(&operation_id, priority).await {
+        error!(
+            "Failed to record priority of operation {}: {}",
+            operation_id, e
+        );
+        return Err(BulkProcessorError::ProgressUpdateFailed(e.to_string()));
+    }
+
+    queue.push(operation_id, priority, job);
+    Ok(())
+}
 
-                // Small delay between batches to prevent overwhelming the system
-                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+/// Start queued operations, highest priority first, with bounded concurrency
+async fn run_dispatcher<R, U>(
+    queue: Arc<BulkOperationQueue<BulkJob>>,
+    url_service: UrlService<R>,
+    progress_service: ProgressService,
+    user_repository: Arc<U>,
+    config: BulkProcessorConfig,
+    notification_service: Arc<OnceLock<NotificationService>>,
+) where
+    R: UrlRepository + Send + Sync + Clone + 'static,
+    U: UserRepository + Send + Sync + Clone + 'static,
+{
+    let slots = Arc::new(Semaphore::new(MAX_CONCURRENT_OPERATIONS));
+
+    loop {
+        // Wait for a free slot first so waiting operations stay reprioritizable
+        let Ok(permit) = slots.clone().acquire_owned().await else {
+            break;
+        };
+        let Some((operation_id, job)) = queue.pop().await else {
+            break;
+        };
+
+        match progress_service.get_progress(&operation_id).await {
+            Ok(progress) if matches!(progress.status, BulkOperationStatus::Cancelled) => {
+                info!("Operation {} was cancelled while queued", operation_id);
+                continue;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                error!("Skipping unknown bulk operation {}: {}", operation_id, e);
+                continue;
             }
+        }
+        if let Err(e) = progress_service
+            .update_status(&operation_id, BulkOperationStatus::Processing)
+            .await
+        {
+            error!("Failed to update operation status to processing: {}", e);
+            continue;
+        }
 
-            // Final status update
-            let final_status = if processed_items >= total_items {
-                if failed_items == 0 {
-                    crate::application::dto::responses::BulkOperationStatus::Completed
-                } else if successful_items == 0 {
-                    crate::application::dto::responses::BulkOperationStatus::Failed
-                } else {
-                    crate::application::dto::responses::BulkOperationStatus::Completed
+        let url_service = url_service.clone();
+        let progress_service = progress_service.clone();
+        let user_repository = user_repository.clone();
+        let notification_service = notification_service.clone();
+        let queue = queue.clone();
+        task::spawn(async move {
+            // Exports are delivered to the waiting request, which needs no notification
+            let notified_operation = operation_id.clone();
+            let notified_user = match &job {
+                BulkJob::Operation { user_id, .. } | BulkJob::UrlCreation { user_id, .. } => {
+                    *user_id
                 }
-            } else {
-                crate::application::dto::responses::BulkOperationStatus::Failed
+                BulkJob::DataExport { .. } | BulkJob::Task { .. } => None,
             };
-
-            if let Err(e) = progress_service
-                .update_status(&operation_id, final_status)
-                .await
+            match job {
+                BulkJob::Operation {
+                    operation,
+                    url_ids,
+                    data,
+                    user_id,
+                } => {
+                    run_bulk_operation(
+                        &url_service,
+                        &progress_service,
+                        operation_id,
+                        operation,
+                        url_ids,
+                        data,
+                        user_id,
+                    )
+                    .await
+                }
+                BulkJob::UrlCreation { urls, user_id } => {
+                    run_bulk_url_creation(
+                        &url_service,
+                        &progress_service,
+                        &config,
+                        operation_id,
+                        urls,
+                        user_id,
+                    )
+                    .await
+                }
+                BulkJob::DataExport { user_id, reply } => {
+                    run_data_export(
+                        user_repository.as_ref(),
+                        &progress_service,
+                        operation_id,
+                        user_id,
+                        reply,
+                    )
+                    .await
+                }
+                BulkJob::Task { task } => run_task(&progress_service, operation_id, task).await,
+            }
+            if let (Some(user_id), Some(notification_service)) =
+                (notified_user, notification_service.get())
             {
-                error!(
-                    "Failed to update final status for operation {}: {}",
-                    operation_id, e
-                );
+                notify_completion(
+                    notification_service,
+                    &progress_service,
+                    &notified_operation,
+                    user_id,
+                )
+                .await;
             }
+            start_next_operation(&queue, &url_service, &progress_service, &notified_operation)
+                .await;
+            drop(permit);
+        });
+    }
+}
 
-            info!(
-                "Completed bulk operation {}: {}/{} successful, {}/{} failed",
-                operation_id, successful_items, total_items, failed_items, total_items
+/// Queue the operation chained to `operation_id`, which has just finished
+///
+/// Operations chained after one that failed or was cancelled are cancelled instead.
+async fn start_next_operation<R>(
+    queue: &BulkOperationQueue<BulkJob>,
+    url_service: &UrlService<R>,
+    progress_service: &ProgressService,
+    operation_id: &str,
+) where
+    R: UrlRepository + Send + Sync + Clone + 'static,
+{
+    let next = match progress_service.take_next_operation(operation_id).await {
+        Ok(Some(next)) => next,
+        Ok(None) => return,
+        Err(e) => {
+            error!(
+                "Failed to look up the operation chained to {}: {}",
+                operation_id, e
             );
-        });
+            return;
+        }
+    };
+    let ChainedOperation {
+        operation_id: next_operation_id,
+        operation,
+        params,
+        priority,
+    } = next;
 
-        Ok(())
+    let params: ChainedOperationParams = match serde_json::from_value(params) {
+        Ok(params) => params,
+        Err(e) => {
+            error!(
+                "Invalid parameters of chained operation {}: {}",
+                next_operation_id, e
+            );
+            // Failing it cancels whatever was chained after it
+            let _ = progress_service
+                .update_status(&next_operation_id, BulkOperationStatus::Failed)
+                .await;
+            let _ = progress_service
+                .take_next_operation(&next_operation_id)
+                .await;
+            return;
+        }
+    };
+    let url_ids = match params.url_ids {
+        Some(url_ids) => url_ids,
+        None => created_url_ids(url_service, progress_service, operation_id).await,
+    };
+    if let Err(e) = progress_service
+        .set_total_items(&next_operation_id, url_ids.len())
+        .await
+    {
+        error!(
+            "Failed to start chained operation {}: {}",
+            next_operation_id, e
+        );
+        return;
     }
 
-    /// Process bulk URL creation in the background
-    pub async fn process_bulk_url_creation(
-        &self,
-        operation_id: String,
-        urls: Vec<crate::application::dto::requests::ShortenUrlRequest>,
-        user_id: Option<i32>,
-    ) -> Result<(), BulkProcessorError> {
-        let total_items = urls.len();
+    info!(
+        "Operation {} completed, queueing chained operation {} for {} URLs",
+        operation_id,
+        next_operation_id,
+        url_ids.len()
+    );
+    queue.push(
+        next_operation_id,
+        priority,
+        BulkJob::Operation {
+            operation,
+            url_ids,
+            data: params.data,
+            user_id: params.user_id,
+        },
+    );
+}
 
-        // Update status to processing
-        if let Err(e) = self
-            .progress_service
-            .update_status(
-                &operation_id,
-                crate::application::dto::responses::BulkOperationStatus::Processing,
-            )
+/// IDs of the URLs a bulk URL creation created
+async fn created_url_ids<R>(
+    url_service: &UrlService<R>,
+    progress_service: &ProgressService,
+    operation_id: &str,
+) -> Vec<i32>
+where
+    R: UrlRepository + Send + Sync + Clone + 'static,
+{
+    let recorded = match progress_service.subscribe_item_results(operation_id).await {
+        Ok(subscription) => subscription.recorded,
+        Err(e) => {
+            warn!(
+                "Failed to read the results of operation {}: {}",
+                operation_id, e
+            );
+            return Vec::new();
+        }
+    };
+
+    let mut url_ids = Vec::new();
+    for short_code in recorded.into_iter().filter_map(|result| result.short_code) {
+        match url_service
+            .get_url_by_short_code(&ShortCode::from_string_unchecked(short_code))
             .await
         {
-            error!("Failed to update operation status to processing: {}", e);
-            return Err(BulkProcessorError::ProgressUpdateFailed(e.to_string()));
+            Ok(Some(url)) => url_ids.push(url.id),
+            Ok(None) => {}
+            Err(e) => warn!(
+                "Failed to look up a URL created by operation {}: {}",
+                operation_id, e
+            ),
         }
+    }
+    url_ids
+}
 
-        info!(
-            "Starting bulk URL creation {} for {} URLs",
-            operation_id, total_items
+/// Push a notification to `user_id` if the operation completed rather than failed or was cancelled
+async fn notify_completion(
+    notification_service: &NotificationService,
+    progress_service: &ProgressService,
+    operation_id: &str,
+    user_id: i32,
+) {
+    let progress = match progress_service.get_progress(operation_id).await {
+        Ok(progress) if matches!(progress.status, BulkOperationStatus::Completed) => progress,
+        Ok(_) => return,
+        Err(e) => {
+            warn!(
+                "Failed to read progress of operation {}: {}",
+                operation_id, e
+            );
+            return;
+        }
+    };
+    if let Err(e) = notification_service
+        .notify_bulk_operation_completed(
+            user_id,
+            operation_id,
+            progress.successful_items,
+            progress.failed_items,
+        )
+        .await
+    {
+        warn!(
+            "Failed to notify user {} of completed operation {}: {}",
+            user_id, operation_id, e
         );
+    }
+}
 
-        // Spawn background task
-        let url_service = self.url_service.clone();
-        let progress_service = self.progress_service.clone();
+/// Apply a batch operation to the given URLs, in chunks
+async fn run_bulk_operation<R>(
+    url_service: &UrlService<R>,
+    progress_service: &ProgressService,
+    operation_id: String,
+    operation: Box<dyn BatchOperation>,
+    url_ids: Vec<i32>,
+    data: Option<serde_json::Value>,
+    user_id: Option<i32>,
+) where
+    R: UrlRepository + Send + Sync + Clone + 'static,
+{
+    let total_items = url_ids.len();
+    info!(
+        "Starting bulk operation {} for {} URLs",
+        operation_id, total_items
+    );
 
-        task::spawn(async move {
-            let mut processed_items = 0;
-            let mut successful_items = 0;
-            let mut failed_items = 0;
-
-            for url_request in urls {
-                // Check if operation was cancelled
-                if let Ok(progress) = progress_service.get_progress(&operation_id).await {
-                    if matches!(
-                        progress.status,
-                        crate::application::dto::responses::BulkOperationStatus::Cancelled
-                    ) {
-                        info!(
-                            "Operation {} was cancelled, stopping processing",
-                            operation_id
-                        );
-                        break;
-                    }
-                }
+    let cancellation = progress_service
+        .cancellation_token(&operation_id)
+        .unwrap_or_default();
+    let mut processed_items = 0;
+    let mut successful_items = 0;
+    let mut failed_items = 0;
+    let batch_size = 10; // Process in batches of 10
 
-                // Process individual URL creation
-                let custom_short_code = url_request
-                    .custom_short_code
-                    .and_then(|code| crate::domain::entities::ShortCode::new(code).ok());
+    // Process URLs in batches
+    for chunk in url_ids.chunks(batch_size) {
+        if cancellation.is_cancelled() {
+            info!(
+                "Operation {} was cancelled, stopping processing",
+                operation_id
+            );
+            break;
+        }
 
-                match url_service
-                    .create_url(
-                        &url_request.url,
-                        custom_short_code,
-                        url_request.expiration_date,
-                        user_id,
-                    )
-                    .await
-                {
-                    Ok(_) => {
-                        successful_items += 1;
-                    }
-                    Err(e) => {
-                        error!(
-                            "Failed to create URL in bulk operation {}: {}",
-                            operation_id, e
-                        );
-                        failed_items += 1;
-                    }
-                }
+        let batch_result = url_service
+            .process_batch_operations(operation.as_ref(), chunk, data.as_ref(), user_id)
+            .await;
 
-                processed_items += 1;
+        match batch_result {
+            Ok(result) => {
+                processed_items += result.total_processed;
+                successful_items += result.successful;
+                failed_items += result.failed;
 
                 // Update progress
                 if let Err(e) = progress_service
@@ -309,41 +749,501 @@ where
                         operation_id, e
                     );
                 }
+            }
+            Err(e) => {
+                error!(
+                    "Batch operation failed for operation {}: {}",
+                    operation_id, e
+                );
+                failed_items += chunk.len();
+                processed_items += chunk.len();
+
+                // Update progress even on failure
+                if let Err(progress_err) = progress_service
+                    .update_progress(
+                        &operation_id,
+                        processed_items,
+                        successful_items,
+                        failed_items,
+                    )
+                    .await
+                {
+                    error!(
+                        "Failed to update progress after batch failure: {}",
+                        progress_err
+                    );
+                }
+            }
+        }
 
-                // Small delay between operations
-                tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        // Small delay between batches to prevent overwhelming the system
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    if cancellation.is_cancelled() {
+        finish_cancelled(
+            progress_service,
+            &operation_id,
+            total_items - processed_items,
+        )
+        .await;
+        return;
+    }
+
+    // Final status update
+    let final_status = if processed_items >= total_items {
+        if failed_items == 0 {
+            BulkOperationStatus::Completed
+        } else if successful_items == 0 {
+            BulkOperationStatus::Failed
+        } else {
+            BulkOperationStatus::Completed
+        }
+    } else {
+        BulkOperationStatus::Failed
+    };
+
+    if let Err(e) = progress_service
+        .update_status(&operation_id, final_status)
+        .await
+    {
+        error!(
+            "Failed to update final status for operation {}: {}",
+            operation_id, e
+        );
+    }
+
+    info!(
+        "Completed bulk operation {}: {}/{} successful, {}/{} failed",
+        operation_id, successful_items, total_items, failed_items, total_items
+    );
+}
+
+/// Create the given URLs, up to `max_concurrent_items` at a time
+///
+/// Each item's result is sent as soon as the item finishes, so results arrive roughly in
+/// completion order. Once the operation is cancelled no further items are started; items
+/// already running finish and are sent. The channel closes when the last item is done.
+fn stream_url_creation<R>(
+    url_service: UrlService<R>,
+    progress_service: ProgressService,
+    config: BulkProcessorConfig,
+    operation_id: String,
+    urls: Vec<ShortenUrlRequest>,
+    user_id: Option<i32>,
+) -> mpsc::Receiver<BulkItemResult>
+where
+    R: UrlRepository + Send + Sync + Clone + 'static,
+{
+    let (results, receiver) = mpsc::channel(config.max_concurrent_items.max(1));
+
+    task::spawn(async move {
+        let cancellation = progress_service
+            .cancellation_token(&operation_id)
+            .unwrap_or_default();
+        let pending_retries = Arc::new(AtomicUsize::new(0));
+        let mut pending_items = urls.into_iter().enumerate();
+        let mut running = HashMap::new();
+        let mut tasks = JoinSet::new();
+
+        loop {
+            while tasks.len() < config.max_concurrent_items.max(1) && !cancellation.is_cancelled() {
+                let Some((index, url_request)) = pending_items.next() else {
+                    break;
+                };
+                let url = url_request.url.clone();
+                let handle = tasks.spawn(create_url_item(
+                    url_service.clone(),
+                    progress_service.clone(),
+                    config,
+                    operation_id.clone(),
+                    index,
+                    url_request,
+                    user_id,
+                    pending_retries.clone(),
+                    cancellation.clone(),
+                ));
+                running.insert(handle.id(), (index, url));
             }
 
-            // Final status update
-            let final_status = if processed_items >= total_items {
-                if failed_items == 0 {
-                    crate::application::dto::responses::BulkOperationStatus::Completed
-                } else if successful_items == 0 {
-                    crate::application::dto::responses::BulkOperationStatus::Failed
-                } else {
-                    crate::application::dto::responses::BulkOperationStatus::Completed
+            let Some(joined) = tasks.join_next_with_id().await else {
+                break;
+            };
+            let result = match joined {
+                // Cancelled before the item was started
+                Ok((id, None)) => {
+                    running.remove(&id);
+                    continue;
+                }
+                Ok((id, Some(result))) => {
+                    running.remove(&id);
+                    result
+                }
+                Err(e) => {
+                    error!(
+                        "URL creation task in bulk operation {} failed: {}",
+                        operation_id, e
+                    );
+                    let Some((index, url)) = running.remove(&e.id()) else {
+                        continue;
+                    };
+                    BulkItemResult {
+                        index,
+                        url,
+                        success: false,
+                        short_code: None,
+                        error: Some("Internal error".to_string()),
+                    }
                 }
-            } else {
-                crate::application::dto::responses::BulkOperationStatus::Failed
             };
 
-            if let Err(e) = progress_service
-                .update_status(&operation_id, final_status)
-                .await
-            {
-                error!(
-                    "Failed to update final status for operation {}: {}",
-                    operation_id, e
+            if results.send(result).await.is_err() {
+                // Nobody is listening any more; let running items finish unobserved
+                warn!(
+                    "Results of bulk operation {} are no longer consumed",
+                    operation_id
                 );
+                tasks.detach_all();
+                break;
             }
+        }
+    });
 
-            info!(
-                "Completed bulk URL creation {}: {}/{} successful, {}/{} failed",
-                operation_id, successful_items, total_items, failed_items, total_items
+    receiver
+}
+
+/// Create the given URLs, recording each item's result and the progress as items finish
+async fn run_bulk_url_creation<R>(
+    url_service: &UrlService<R>,
+    progress_service: &ProgressService,
+    config: &BulkProcessorConfig,
+    operation_id: String,
+    urls: Vec<ShortenUrlRequest>,
+    user_id: Option<i32>,
+) where
+    R: UrlRepository + Send + Sync + Clone + 'static,
+{
+    let total_items = urls.len();
+    info!(
+        "Starting bulk URL creation {} for {} URLs",
+        operation_id, total_items
+    );
+
+    let cancellation = progress_service
+        .cancellation_token(&operation_id)
+        .unwrap_or_default();
+    let mut results = stream_url_creation(
+        url_service.clone(),
+        progress_service.clone(),
+        *config,
+        operation_id.clone(),
+        urls,
+        user_id,
+    );
+    let mut processed_items = 0;
+    let mut successful_items = 0;
+    let mut failed_items = 0;
+
+    while let Some(result) = results.recv().await {
+        if result.success {
+            successful_items += 1;
+        } else {
+            error!(
+                "Failed to create {} in bulk operation {}: {}",
+                result.url,
+                operation_id,
+                result.error.as_deref().unwrap_or("unknown error")
             );
-        });
+            failed_items += 1;
+        }
+        processed_items += 1;
 
-        Ok(())
+        if let Err(e) = progress_service
+            .record_item_result(&operation_id, result)
+            .await
+        {
+            error!(
+                "Failed to record item result for operation {}: {}",
+                operation_id, e
+            );
+        }
+
+        // Update progress
+        if let Err(e) = progress_service
+            .update_progress(
+                &operation_id,
+                processed_items,
+                successful_items,
+                failed_items,
+            )
+            .await
+        {
+            error!(
+                "Failed to update progress for operation {}: {}",
+                operation_id, e
+            );
+        }
+    }
+
+    if cancellation.is_cancelled() {
+        info!(
+            "Bulk URL creation {} was cancelled after {}/{} URLs",
+            operation_id, processed_items, total_items
+        );
+        finish_cancelled(
+            progress_service,
+            &operation_id,
+            total_items - processed_items,
+        )
+        .await;
+        return;
+    }
+
+    // Final status update
+    let final_status = if processed_items >= total_items {
+        if failed_items == 0 {
+            BulkOperationStatus::Completed
+        } else if successful_items == 0 {
+            BulkOperationStatus::Failed
+        } else {
+            BulkOperationStatus::Completed
+        }
+    } else {
+        BulkOperationStatus::Failed
+    };
+
+    if let Err(e) = progress_service
+        .update_status(&operation_id, final_status)
+        .await
+    {
+        error!(
+            "Failed to update final status for operation {}: {}",
+            operation_id, e
+        );
+    }
+
+    info!(
+        "Completed bulk URL creation {}: {}/{} successful, {}/{} failed",
+        operation_id, successful_items, total_items, failed_items, total_items
+    );
+}
+
+/// Create one URL of a bulk creation, unless the operation was cancelled before it started
+#[allow(clippy::too_many_arguments)]
+async fn create_url_item<R>(
+    url_service: UrlService<R>,
+    progress_service: ProgressService,
+    config: BulkProcessorConfig,
+    operation_id: String,
+    index: usize,
+    url_request: ShortenUrlRequest,
+    user_id: Option<i32>,
+    pending_retries: Arc<AtomicUsize>,
+    cancellation: CancellationToken,
+) -> Option<BulkItemResult>
+where
+    R: UrlRepository + Send + Sync + Clone + 'static,
+{
+    if cancellation.is_cancelled() {
+        return None;
+    }
+
+    let custom_short_code = url_request
+        .custom_short_code
+        .clone()
+        .and_then(|code| ShortCode::new(code).ok());
+
+    let result = create_url_with_retries(
+        &url_service,
+        &progress_service,
+        &config,
+        &operation_id,
+        &url_request,
+        custom_short_code,
+        user_id,
+        &pending_retries,
+    )
+    .await;
+
+    Some(match result {
+        Ok(url) => BulkItemResult {
+            index,
+            url: url_request.url,
+            success: true,
+            short_code: Some(url.short_code),
+            error: None,
+        },
+        Err(e) => BulkItemResult {
+            index,
+            url: url_request.url,
+            success: false,
+            short_code: None,
+            error: Some(e.to_string()),
+        },
+    })
+}
+
+/// Record a cancelled operation with the number of items it left unprocessed
+async fn finish_cancelled(
+    progress_service: &ProgressService,
+    operation_id: &str,
+    cancelled_items: usize,
+) {
+    if let Err(e) = progress_service
+        .mark_cancelled(operation_id, cancelled_items)
+        .await
+    {
+        error!(
+            "Failed to record cancellation of operation {}: {}",
+            operation_id, e
+        );
+    }
+}
+
+/// Whether a failed item may succeed when tried again
+///
+/// Only lost database connections are transient; validation errors and taken short codes
+/// fail the same way every time.
+fn is_retryable(error: &ServiceError) -> bool {
+    matches!(
+        error,
+        ServiceError::Repository(RepositoryError::Connection(_))
+    )
+}
+
+/// Create one URL, retrying transient failures with exponential backoff
+///
+/// `pending_retries` counts the items of the operation currently waiting for a retry.
+#[allow(clippy::too_many_arguments)]
+async fn create_url_with_retries<R>(
+    url_service: &UrlService<R>,
+    progress_service: &ProgressService,
+    config: &BulkProcessorConfig,
+    operation_id: &str,
+    url_request: &ShortenUrlRequest,
+    custom_short_code: Option<ShortCode>,
+    user_id: Option<i32>,
+    pending_retries: &AtomicUsize,
+) -> Result<Url, ServiceError>
+where
+    R: UrlRepository + Send + Sync + Clone + 'static,
+{
+    let mut attempt = 0;
+    loop {
+        let result = url_service
+            .create_url_in_organization(
+                &url_request.url,
+                custom_short_code.clone(),
+                url_request.expiration_date,
+                user_id,
+                url_request.organization_id,
+            )
+            .await;
+        let error = match result {
+            Ok(url) => break Ok(url),
+            Err(e) if is_retryable(&e) && attempt < config.max_item_retries => e,
+            Err(e) => {
+                if attempt > 0 {
+                    error!(
+                        "Giving up on {} in bulk operation {} after {} retries",
+                        url_request.url, operation_id, attempt
+                    );
+                }
+                break Err(e);
+            }
+        };
+
+        let delay = config.retry_delay(attempt);
+        attempt += 1;
+        warn!(
+            "Transient failure creating {} in bulk operation {} ({}); retry {}/{} in {:?}",
+            url_request.url, operation_id, error, attempt, config.max_item_retries, delay
+        );
+        let waiting = pending_retries.fetch_add(1, Ordering::SeqCst) + 1;
+        let _ = progress_service
+            .set_pending_retries(operation_id, waiting)
+            .await;
+        tokio::time::sleep(delay).await;
+        let waiting = pending_retries.fetch_sub(1, Ordering::SeqCst) - 1;
+        let _ = progress_service
+            .set_pending_retries(operation_id, waiting)
+            .await;
+    }
+}
+
+/// Collect a user's data and hand it back to the caller waiting on `reply`
+async fn run_data_export<U>(
+    user_repository: &U,
+    progress_service: &ProgressService,
+    operation_id: String,
+    user_id: i32,
+    reply: oneshot::Sender<Result<UserDataExport, BulkProcessorError>>,
+) where
+    U: UserRepository + Send + Sync + Clone + 'static,
+{
+    info!("Starting data export {} for user {}", operation_id, user_id);
+
+    let result = match user_repository.export_user_data(user_id).await {
+        Ok(Some(export)) => Ok(export),
+        Ok(None) => Err(BulkProcessorError::InvalidData(format!(
+            "User {} not found",
+            user_id
+        ))),
+        Err(e) => Err(BulkProcessorError::ProcessingFailed(e.to_string())),
+    };
+
+    if let Err(e) = &result {
+        error!("Data export {} failed: {}", operation_id, e);
+    }
+    finish_single_item_operation(progress_service, &operation_id, result.is_ok()).await;
+
+    if reply.send(result).is_err() {
+        warn!(
+            "Data export {} finished but nobody is waiting for it",
+            operation_id
+        );
+    }
+}
+
+/// Run a task queued through a [`BulkTaskQueue`]
+async fn run_task(progress_service: &ProgressService, operation_id: String, task: BulkTask) {
+    info!("Starting task {}", operation_id);
+
+    let result = task.await;
+    if let Err(e) = &result {
+        error!("Task {} failed: {}", operation_id, e);
+    }
+    finish_single_item_operation(progress_service, &operation_id, result.is_ok()).await;
+}
+
+/// Record the outcome of an operation with a single item
+async fn finish_single_item_operation(
+    progress_service: &ProgressService,
+    operation_id: &str,
+    succeeded: bool,
+) {
+    let (successful_items, failed_items, final_status) = if succeeded {
+        (1, 0, BulkOperationStatus::Completed)
+    } else {
+        (0, 1, BulkOperationStatus::Failed)
+    };
+    if let Err(e) = progress_service
+        .update_progress(operation_id, 1, successful_items, failed_items)
+        .await
+    {
+        error!(
+            "Failed to update progress for operation {}: {}",
+            operation_id, e
+        );
+    }
+    if let Err(e) = progress_service
+        .update_status(operation_id, final_status)
+        .await
+    {
+        error!(
+            "Failed to update final status for operation {}: {}",
+            operation_id, e
+        );
     }
 }
 
@@ -359,4 +1259,724 @@ pub enum BulkProcessorError {
 
     #[error("Invalid operation data: {0}")]
     InvalidData(String),
+
+    #[error("Operation {0} is not waiting in the queue")]
+    NotQueued(String),
+
+    #[error("Cannot chain the operation: {0}")]
+    ChainRejected(ProgressServiceError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_utils::{MockUrlRepository, MockUserRepository};
+    use tokio_stream::StreamExt;
+
+    #[tokio::test]
+    async fn test_premium_users_get_high_priority() {
+        let processor = BulkProcessor::new(
+            UrlService::new(MockUrlRepository::new()),
+            ProgressService::new(),
+            MockUserRepository::new(),
+        );
+        let mut user = User::new_with_timestamp(
+            1,
+            "alice".to_string(),
+            "alice@example.com".to_string(),
+            "hash".to_string(),
+        );
+
+        assert_eq!(
+            processor.effective_priority(OperationPriority::Low, &user),
+            OperationPriority::Low
+        );
+
+        user.tier = UserTier::Premium;
+        assert_eq!(
+            processor.effective_priority(OperationPriority::Low, &user),
+            OperationPriority::High
+        );
+    }
+
+    #[tokio::test]
+    async fn test_data_export_delivers_user_data() {
+        let user_repository = MockUserRepository::new();
+        let user = user_repository
+            .create_user("alice", "alice@example.com", "hash")
+            .await
+            .unwrap();
+        let progress_service = ProgressService::new();
+        let processor = BulkProcessor::new(
+            UrlService::new(MockUrlRepository::new()),
+            progress_service.clone(),
+            user_repository,
+        );
+
+        let operation_id = progress_service.create_operation(1).await;
+        let receiver = processor
+            .process_data_export(operation_id.clone(), user.id, OperationPriority::Normal)
+            .await
+            .unwrap();
+
+        let export = receiver.await.unwrap().unwrap();
+        assert_eq!(export.user.username, "alice");
+        let progress = progress_service.get_progress(&operation_id).await.unwrap();
+        assert!(matches!(progress.status, BulkOperationStatus::Completed));
+    }
+
+    #[tokio::test]
+    async fn test_queued_task_runs_as_operation() {
+        let progress_service = ProgressService::new();
+        let processor = BulkProcessor::new(
+            UrlService::new(MockUrlRepository::new()),
+            progress_service.clone(),
+            MockUserRepository::new(),
+        );
+        let (done, ran) = oneshot::channel();
+
+        let operation_id = processor
+            .task_queue()
+            .queue_task(OperationPriority::Normal, |operation_id| {
+                let operation_id = operation_id.to_string();
+                Box::pin(async move {
+                    let _ = done.send(operation_id);
+                    Err(BulkProcessorError::ProcessingFailed("upload failed".into()))
+                })
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(ran.await.unwrap(), operation_id);
+        let mut progress = progress_service.get_progress(&operation_id).await.unwrap();
+        for _ in 0..50 {
+            if matches!(progress.status, BulkOperationStatus::Failed) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            progress = progress_service.get_progress(&operation_id).await.unwrap();
+        }
+        assert!(matches!(progress.status, BulkOperationStatus::Failed));
+        assert_eq!(progress.failed_items, 1);
+    }
+
+    #[tokio::test]
+    async fn test_data_export_for_unknown_user_fails() {
+        let progress_service = ProgressService::new();
+        let processor = BulkProcessor::new(
+            UrlService::new(MockUrlRepository::new()),
+            progress_service.clone(),
+            MockUserRepository::new(),
+        );
+
+        let operation_id = progress_service.create_operation(1).await;
+        let receiver = processor
+            .process_data_export(operation_id.clone(), 42, OperationPriority::Normal)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            receiver.await.unwrap(),
+            Err(BulkProcessorError::InvalidData(_))
+        ));
+        let progress = progress_service.get_progress(&operation_id).await.unwrap();
+        assert!(matches!(progress.status, BulkOperationStatus::Failed));
+    }
+
+    fn shorten_request(url: &str, custom_short_code: Option<&str>) -> ShortenUrlRequest {
+        ShortenUrlRequest {
+            url: url.to_string(),
+            custom_short_code: custom_short_code.map(str::to_string),
+            expiration_date: None,
+            organization_id: None,
+        }
+    }
+
+    #[test]
+    fn test_retry_delay_doubles() {
+        let config = BulkProcessorConfig::default();
+        assert_eq!(config.retry_delay(0), Duration::from_millis(100));
+        assert_eq!(config.retry_delay(1), Duration::from_millis(200));
+        assert_eq!(config.retry_delay(2), Duration::from_millis(400));
+        assert_eq!(config.retry_delay(200), Duration::from_millis(u64::MAX));
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_are_retried() {
+        let url_repository = MockUrlRepository::new();
+        url_repository.fail_next_creates(2);
+        let config = BulkProcessorConfig {
+            max_item_retries: 3,
+            retry_base_delay_ms: 1,
+            max_concurrent_items: 1,
+        };
+
+        let result = create_url_with_retries(
+            &UrlService::new(url_repository),
+            &ProgressService::new(),
+            &config,
+            "op",
+            &shorten_request("https://example.com", None),
+            None,
+            Some(1),
+            &AtomicUsize::new(0),
+        )
+        .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_item_fails_after_retries_exhausted() {
+        let url_repository = MockUrlRepository::new();
+        url_repository.fail_next_creates(3);
+        let config = BulkProcessorConfig {
+            max_item_retries: 2,
+            retry_base_delay_ms: 1,
+            max_concurrent_items: 1,
+        };
+        let url_service = UrlService::new(url_repository);
+
+        let result = create_url_with_retries(
+            &url_service,
+            &ProgressService::new(),
+            &config,
+            "op",
+            &shorten_request("https://example.com", None),
+            None,
+            Some(1),
+            &AtomicUsize::new(0),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ServiceError::Repository(RepositoryError::Connection(_)))
+        ));
+        assert!(url_service.get_urls_for_user(1).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_permanent_failures_are_not_retried() {
+        let url_repository = MockUrlRepository::new();
+        let url_service = UrlService::new(url_repository.clone());
+        let code = ShortCode::new("taken".to_string()).unwrap();
+        url_service
+            .create_url("https://example.com", Some(code.clone()), None, Some(1))
+            .await
+            .unwrap();
+        // A retry would consume this failure and then hit the duplicate code again
+        url_repository.fail_next_creates(1);
+
+        let result = create_url_with_retries(
+            &url_service,
+            &ProgressService::new(),
+            &BulkProcessorConfig::default(),
+            "op",
+            &shorten_request("https://example.org", Some("taken")),
+            Some(code),
+            Some(1),
+            &AtomicUsize::new(0),
+        )
+        .await;
+        assert!(matches!(result, Err(ServiceError::ShortCodeAlreadyExists)));
+    }
+
+    #[tokio::test]
+    async fn test_bulk_creation_recovers_from_transient_failures() {
+        let url_repository = MockUrlRepository::new();
+        url_repository.fail_next_creates(1);
+        let progress_service = ProgressService::new();
+        let processor = BulkProcessor::with_config(
+            UrlService::new(url_repository),
+            progress_service.clone(),
+            MockUserRepository::new(),
+            BulkProcessorConfig {
+                max_item_retries: 3,
+                retry_base_delay_ms: 1,
+                max_concurrent_items: 1,
+            },
+        );
+
+        let operation_id = progress_service.create_operation(2).await;
+        processor
+            .process_bulk_url_creation(
+                operation_id.clone(),
+                vec![
+                    shorten_request("https://example.com", None),
+                    shorten_request("https://example.org", None),
+                ],
+                Some(1),
+                OperationPriority::Normal,
+            )
+            .await
+            .unwrap();
+
+        let progress = loop {
+            let progress = progress_service.get_progress(&operation_id).await.unwrap();
+            if matches!(progress.status, BulkOperationStatus::Completed) {
+                break progress;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        assert_eq!(progress.successful_items, 2);
+        assert_eq!(progress.failed_items, 0);
+        assert_eq!(progress.pending_retries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_streamed_results_arrive_as_items_finish() {
+        let url_service = UrlService::new(MockUrlRepository::new());
+        let processor = BulkProcessor::with_config(
+            url_service.clone(),
+            ProgressService::new(),
+            MockUserRepository::new(),
+            BulkProcessorConfig {
+                max_concurrent_items: 1,
+                ..BulkProcessorConfig::default()
+            },
+        );
+        let urls: Vec<_> = (0..10)
+            .map(|i| shorten_request(&format!("https://example.com/{}", i), None))
+            .collect();
+
+        let mut results = Box::pin(processor.process_bulk_url_creation_streaming(
+            "op".to_string(),
+            urls,
+            Some(1),
+        ));
+        let first = results.next().await.unwrap();
+        assert_eq!(first.index, 0);
+        assert!(first.success);
+        assert!(first.short_code.is_some());
+        // The first result is delivered long before the last item is created
+        assert!(url_service.get_urls_for_user(1).await.unwrap().len() < 10);
+
+        let rest: Vec<usize> = results.map(|result| result.index).collect().await;
+        assert_eq!(rest, (1..10).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_streamed_results_are_roughly_in_start_order() {
+        let max_concurrent_items = 4;
+        let url_service = UrlService::new(MockUrlRepository::new());
+        url_service
+            .create_url(
+                "https://example.com",
+                Some(ShortCode::new("taken".to_string()).unwrap()),
+                None,
+                Some(1),
+            )
+            .await
+            .unwrap();
+        let processor = BulkProcessor::with_config(
+            url_service,
+            ProgressService::new(),
+            MockUserRepository::new(),
+            BulkProcessorConfig {
+                max_concurrent_items,
+                ..BulkProcessorConfig::default()
+            },
+        );
+        let mut urls: Vec<_> = (0..40)
+            .map(|i| shorten_request(&format!("https://example.com/{}", i), None))
+            .collect();
+        urls[7].custom_short_code = Some("taken".to_string());
+
+        let results: Vec<BulkItemResult> = processor
+            .process_bulk_url_creation_streaming("op".to_string(), urls, Some(1))
+            .collect()
+            .await;
+
+        assert_eq!(results.len(), 40);
+        // An item only starts once all but the last few before it have been delivered
+        for (position, result) in results.iter().enumerate() {
+            assert!(result.index < position + max_concurrent_items);
+        }
+        let failed: Vec<usize> = results
+            .iter()
+            .filter(|result| !result.success)
+            .map(|result| result.index)
+            .collect();
+        assert_eq!(failed, vec![7]);
+        let mut indexes: Vec<usize> = results.iter().map(|result| result.index).collect();
+        indexes.sort_unstable();
+        assert_eq!(indexes, (0..40).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_queued_creation_records_item_results() {
+        let progress_service = ProgressService::new();
+        let processor = BulkProcessor::new(
+            UrlService::new(MockUrlRepository::new()),
+            progress_service.clone(),
+            MockUserRepository::new(),
+        );
+        let operation_id = progress_service.create_operation(3).await;
+        let subscription = progress_service
+            .subscribe_item_results(&operation_id)
+            .await
+            .unwrap();
+        let mut live = subscription.live.unwrap();
+
+        processor
+            .process_bulk_url_creation(
+                operation_id.clone(),
+                vec![
+                    shorten_request("https://example.com/a", None),
+                    shorten_request("https://example.com/b", None),
+                    shorten_request("https://example.com/c", None),
+                ],
+                Some(1),
+                OperationPriority::Normal,
+            )
+            .await
+            .unwrap();
+
+        let mut streamed = Vec::new();
+        while let Ok(result) = live.recv().await {
+            streamed.push(result);
+        }
+        assert_eq!(streamed.len(), 3);
+        let recorded = progress_service
+            .subscribe_item_results(&operation_id)
+            .await
+            .unwrap();
+        assert_eq!(recorded.recorded, streamed);
+        assert!(recorded.live.is_none());
+    }
+
+    async fn wait_until_cancelled(
+        progress_service: &ProgressService,
+        operation_id: &str,
+        total_items: usize,
+    ) -> crate::application::dto::responses::BulkOperationProgress {
+        loop {
+            let progress = progress_service.get_progress(operation_id).await.unwrap();
+            if progress.processed_items + progress.cancelled_items == total_items
+                && matches!(progress.status, BulkOperationStatus::Cancelled)
+            {
+                break progress;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_stops_bulk_creation_mid_batch() {
+        let url_repository = MockUrlRepository::new();
+        let progress_service = ProgressService::new();
+        let operation_id = progress_service.create_operation(20).await;
+        let token = progress_service.cancellation_token(&operation_id).unwrap();
+        url_repository.on_create(move |created| {
+            if created == 5 {
+                token.cancel();
+            }
+        });
+        let url_service = UrlService::new(url_repository);
+        let processor = BulkProcessor::with_config(
+            url_service.clone(),
+            progress_service.clone(),
+            MockUserRepository::new(),
+            BulkProcessorConfig {
+                max_concurrent_items: 1,
+                ..BulkProcessorConfig::default()
+            },
+        );
+
+        let urls = (0..20)
+            .map(|i| shorten_request(&format!("https://example.com/{}", i), None))
+            .collect();
+        processor
+            .process_bulk_url_creation(
+                operation_id.clone(),
+                urls,
+                Some(1),
+                OperationPriority::Normal,
+            )
+            .await
+            .unwrap();
+
+        let progress = wait_until_cancelled(&progress_service, &operation_id, 20).await;
+        assert_eq!(progress.processed_items, 5);
+        assert_eq!(progress.successful_items, 5);
+        assert_eq!(progress.cancelled_items, 15);
+        assert_eq!(url_service.get_urls_for_user(1).await.unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_concurrent_creation_accounts_for_every_item() {
+        let url_repository = MockUrlRepository::new();
+        let progress_service = ProgressService::new();
+        let operation_id = progress_service.create_operation(50).await;
+        let token = progress_service.cancellation_token(&operation_id).unwrap();
+        url_repository.on_create(move |created| {
+            if created == 10 {
+                token.cancel();
+            }
+        });
+        let url_service = UrlService::new(url_repository);
+        let processor = BulkProcessor::new(
+            url_service.clone(),
+            progress_service.clone(),
+            MockUserRepository::new(),
+        );
+
+        let urls = (0..50)
+            .map(|i| shorten_request(&format!("https://example.com/{}", i), None))
+            .collect();
+        processor
+            .process_bulk_url_creation(
+                operation_id.clone(),
+                urls,
+                Some(1),
+                OperationPriority::Normal,
+            )
+            .await
+            .unwrap();
+
+        let progress = wait_until_cancelled(&progress_service, &operation_id, 50).await;
+        let created = url_service.get_urls_for_user(1).await.unwrap().len();
+        assert_eq!(progress.processed_items, created);
+        assert!((10..50).contains(&created));
+    }
+
+    /// URL and user IDs a [`TestOperation`] was applied to
+    type ExecutedOn = Arc<std::sync::Mutex<Vec<(i32, Option<i32>)>>>;
+
+    /// Operation recording the URLs it was applied to and changing nothing
+    struct TestOperation {
+        executed: ExecutedOn,
+    }
+
+    #[async_trait::async_trait]
+    impl BatchOperation for TestOperation {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        async fn execute(
+            &self,
+            url_id: i32,
+            user_id: Option<i32>,
+            _data: Option<&serde_json::Value>,
+            _repo: &dyn UrlRepository,
+        ) -> Result<(), ServiceError> {
+            self.executed.lock().unwrap().push((url_id, user_id));
+            Ok(())
+        }
+    }
+
+    async fn wait_until_finished(
+        progress_service: &ProgressService,
+        operation_id: &str,
+    ) -> crate::application::dto::responses::BulkOperationProgress {
+        loop {
+            let progress = progress_service.get_progress(operation_id).await.unwrap();
+            if matches!(
+                progress.status,
+                BulkOperationStatus::Completed | BulkOperationStatus::Failed
+            ) {
+                break progress;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    /// Operation failing for every URL
+    struct FailingOperation;
+
+    #[async_trait::async_trait]
+    impl BatchOperation for FailingOperation {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn execute(
+            &self,
+            _url_id: i32,
+            _user_id: Option<i32>,
+            _data: Option<&serde_json::Value>,
+            _repo: &dyn UrlRepository,
+        ) -> Result<(), ServiceError> {
+            Err(ServiceError::PermissionDenied("not allowed".to_string()))
+        }
+    }
+
+    async fn wait_until_status_is_final(
+        progress_service: &ProgressService,
+        operation_id: &str,
+    ) -> crate::application::dto::responses::BulkOperationProgress {
+        loop {
+            let progress = progress_service.get_progress(operation_id).await.unwrap();
+            if progress.status.is_finished() {
+                break progress;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chain_stops_at_failed_operation() {
+        let progress_service = ProgressService::new();
+        let processor = BulkProcessor::new(
+            UrlService::new(MockUrlRepository::new()),
+            progress_service.clone(),
+            MockUserRepository::new(),
+        );
+        let executed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let first = progress_service.create_operation(2).await;
+        let second = processor
+            .chain_bulk_operation(
+                first.clone(),
+                Box::new(FailingOperation),
+                Some(vec![1, 2]),
+                None,
+                Some(7),
+            )
+            .await
+            .unwrap();
+        let third = processor
+            .chain_bulk_operation(
+                second.clone(),
+                Box::new(TestOperation {
+                    executed: executed.clone(),
+                }),
+                Some(vec![3]),
+                None,
+                Some(7),
+            )
+            .await
+            .unwrap();
+
+        processor
+            .process_bulk_operation(
+                first.clone(),
+                Box::new(TestOperation {
+                    executed: executed.clone(),
+                }),
+                vec![1, 2],
+                None,
+                Some(7),
+                OperationPriority::Normal,
+            )
+            .await
+            .unwrap();
+
+        let progress = wait_until_status_is_final(&progress_service, &third).await;
+        assert!(matches!(progress.status, BulkOperationStatus::Cancelled));
+        let progress = progress_service.get_progress(&first).await.unwrap();
+        assert!(matches!(progress.status, BulkOperationStatus::Completed));
+        let progress = progress_service.get_progress(&second).await.unwrap();
+        assert!(matches!(progress.status, BulkOperationStatus::Failed));
+        assert_eq!(progress.failed_items, 2);
+
+        // The last operation never ran
+        assert_eq!(*executed.lock().unwrap(), vec![(1, Some(7)), (2, Some(7))]);
+        let chain = progress_service.get_chain(&second).await.unwrap();
+        let ids: Vec<_> = chain.into_iter().map(|p| p.operation_id).collect();
+        assert_eq!(ids, [first, second, third]);
+    }
+
+    #[tokio::test]
+    async fn test_chained_operation_applies_to_created_urls() {
+        let progress_service = ProgressService::new();
+        let processor = BulkProcessor::new(
+            UrlService::new(MockUrlRepository::new()),
+            progress_service.clone(),
+            MockUserRepository::new(),
+        );
+        let executed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let creation = progress_service.create_operation(2).await;
+        let next = processor
+            .chain_bulk_operation(
+                creation.clone(),
+                Box::new(TestOperation {
+                    executed: executed.clone(),
+                }),
+                None,
+                None,
+                Some(1),
+            )
+            .await
+            .unwrap();
+
+        processor
+            .process_bulk_url_creation(
+                creation,
+                vec![
+                    shorten_request("https://example.com/a", Some("chain-a")),
+                    shorten_request("https://example.com/b", Some("chain-b")),
+                ],
+                Some(1),
+                OperationPriority::Normal,
+            )
+            .await
+            .unwrap();
+
+        let progress = wait_until_status_is_final(&progress_service, &next).await;
+        assert!(matches!(progress.status, BulkOperationStatus::Completed));
+        assert_eq!(progress.total_items, 2);
+        let mut executed = executed.lock().unwrap().clone();
+        executed.sort();
+        assert_eq!(executed.len(), 2);
+        assert!(executed.iter().all(|(_, user_id)| *user_id == Some(1)));
+    }
+
+    #[tokio::test]
+    async fn test_custom_operation_runs_through_processor() {
+        let progress_service = ProgressService::new();
+        let processor = BulkProcessor::new(
+            UrlService::new(MockUrlRepository::new()),
+            progress_service.clone(),
+            MockUserRepository::new(),
+        );
+        let executed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let url_ids: Vec<i32> = (1..=12).collect();
+        let operation_id = progress_service.create_operation(url_ids.len()).await;
+
+        processor
+            .process_bulk_operation(
+                operation_id.clone(),
+                Box::new(TestOperation {
+                    executed: executed.clone(),
+                }),
+                url_ids.clone(),
+                None,
+                Some(7),
+                OperationPriority::Normal,
+            )
+            .await
+            .unwrap();
+
+        let progress = wait_until_finished(&progress_service, &operation_id).await;
+        assert!(matches!(progress.status, BulkOperationStatus::Completed));
+        assert_eq!(progress.successful_items, 12);
+        let executed = executed.lock().unwrap().clone();
+        assert_eq!(
+            executed,
+            url_ids.iter().map(|&id| (id, Some(7))).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_operation_data_is_checked_before_queueing() {
+        let progress_service = ProgressService::new();
+        let processor = BulkProcessor::new(
+            UrlService::new(MockUrlRepository::new()),
+            progress_service.clone(),
+            MockUserRepository::new(),
+        );
+        let operation_id = progress_service.create_operation(1).await;
+
+        let result = processor
+            .process_bulk_operation(
+                operation_id,
+                crate::domain::services::batch_operation("update_status").unwrap(),
+                vec![1],
+                Some(serde_json::json!({ "status": "deleted" })),
+                Some(1),
+                OperationPriority::Normal,
+            )
+            .await;
+        assert!(matches!(result, Err(BulkProcessorError::InvalidData(_))));
+    }
 }
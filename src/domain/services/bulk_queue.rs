@@ -0,0 +1,193 @@
+use crate::application::dto::requests::OperationPriority;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+/// Queue of pending bulk operations with one channel per priority level
+///
+/// `pop` always drains `High` before `Normal` before `Low`. Jobs stay in `pending` until
+/// popped, so a queued operation can still be moved to another priority: its ID is sent on
+/// the new channel and the entry left on the old one is skipped when it comes up.
+pub struct BulkOperationQueue<J> {
+    senders: HashMap<OperationPriority, UnboundedSender<String>>,
+    receivers: tokio::sync::Mutex<PriorityReceivers>,
+    pending: Mutex<HashMap<String, (OperationPriority, J)>>,
+}
+
+struct PriorityReceivers {
+    high: UnboundedReceiver<String>,
+    normal: UnboundedReceiver<String>,
+    low: UnboundedReceiver<String>,
+}
+
+impl<J> BulkOperationQueue<J> {
+    pub fn new() -> Self {
+        let (high_tx, high) = mpsc::unbounded_channel();
+        let (normal_tx, normal) = mpsc::unbounded_channel();
+        let (low_tx, low) = mpsc::unbounded_channel();
+
+        Self {
+            senders: HashMap::from([
+                (OperationPriority::High, high_tx),
+                (OperationPriority::Normal, normal_tx),
+                (OperationPriority::Low, low_tx),
+            ]),
+            receivers: tokio::sync::Mutex::new(PriorityReceivers { high, normal, low }),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Queue a job under the given priority
+    pub fn push(&self, operation_id: String, priority: OperationPriority, job: J) {
+        self.pending
+            .lock()
+            .unwrap()
+            .insert(operation_id.clone(), (priority, job));
+        self.send(operation_id, priority);
+    }
+
+    /// Move a queued job to another priority
+    ///
+    /// Returns `false` if the job is no longer queued (already started or unknown).
+    pub fn reprioritize(&self, operation_id: &str, priority: OperationPriority) -> bool {
+        let mut pending = self.pending.lock().unwrap();
+        match pending.get_mut(operation_id) {
+            Some((current, _)) => {
+                if *current != priority {
+                    *current = priority;
+                    self.send(operation_id.to_string(), priority);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Wait for the next job, highest priority first
+    pub async fn pop(&self) -> Option<(String, J)> {
+        let mut receivers = self.receivers.lock().await;
+        let PriorityReceivers { high, normal, low } = &mut *receivers;
+
+        loop {
+            let (priority, operation_id) = tokio::select! {
+                biased;
+                Some(id) = high.recv() => (OperationPriority::High, id),
+                Some(id) = normal.recv() => (OperationPriority::Normal, id),
+                Some(id) = low.recv() => (OperationPriority::Low, id),
+                else => return None,
+            };
+
+            // Skip entries superseded by a reprioritization
+            let job = {
+                let mut pending = self.pending.lock().unwrap();
+                match pending.get(&operation_id) {
+                    Some((current, _)) if *current == priority => {
+                        pending.remove(&operation_id).map(|(_, job)| job)
+                    }
+                    _ => None,
+                }
+            };
+            if let Some(job) = job {
+                return Some((operation_id, job));
+            }
+        }
+    }
+
+    /// Number of queued jobs at each priority level
+    pub fn depths(&self) -> Vec<(OperationPriority, usize)> {
+        let pending = self.pending.lock().unwrap();
+        OperationPriority::ALL
+            .iter()
+            .map(|priority| {
+                let depth = pending.values().filter(|(p, _)| p == priority).count();
+                (*priority, depth)
+            })
+            .collect()
+    }
+
+    fn send(&self, operation_id: String, priority: OperationPriority) {
+        // The queue owns the receivers, so the channels never close
+        let _ = self.senders[&priority].send(operation_id);
+    }
+}
+
+impl<J> Default for BulkOperationQueue<J> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_pop_drains_high_then_normal_then_low() {
+        let queue = BulkOperationQueue::new();
+        queue.push("low".to_string(), OperationPriority::Low, ());
+        queue.push("normal".to_string(), OperationPriority::Normal, ());
+        queue.push("high".to_string(), OperationPriority::High, ());
+
+        let mut order = Vec::new();
+        for _ in 0..3 {
+            order.push(queue.pop().await.unwrap().0);
+        }
+        assert_eq!(order, vec!["high", "normal", "low"]);
+    }
+
+    #[tokio::test]
+    async fn test_reprioritize_moves_queued_job() {
+        let queue = BulkOperationQueue::new();
+        queue.push("a".to_string(), OperationPriority::Normal, ());
+        queue.push("b".to_string(), OperationPriority::Low, ());
+
+        assert!(queue.reprioritize("b", OperationPriority::High));
+        assert_eq!(
+            queue.depths(),
+            vec![
+                (OperationPriority::High, 1),
+                (OperationPriority::Normal, 1),
+                (OperationPriority::Low, 0),
+            ]
+        );
+
+        assert_eq!(queue.pop().await.unwrap().0, "b");
+        assert_eq!(queue.pop().await.unwrap().0, "a");
+        assert!(!queue.reprioritize("a", OperationPriority::Low));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_submissions_keep_priority_order() {
+        let queue = Arc::new(BulkOperationQueue::new());
+
+        let mut handles = Vec::new();
+        for i in 0..300 {
+            let queue = queue.clone();
+            handles.push(tokio::spawn(async move {
+                let priority = OperationPriority::ALL[i % 3];
+                queue.push(format!("op-{}", i), priority, priority);
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        let mut popped = Vec::new();
+        for _ in 0..300 {
+            popped.push(queue.pop().await.unwrap().1);
+        }
+
+        // Every high job comes before every normal job, and those before every low job
+        let rank = |p: &OperationPriority| OperationPriority::ALL.iter().position(|x| x == p);
+        assert!(popped.windows(2).all(|w| rank(&w[0]) <= rank(&w[1])));
+        assert_eq!(
+            popped
+                .iter()
+                .filter(|p| **p == OperationPriority::High)
+                .count(),
+            100
+        );
+        assert!(queue.depths().iter().all(|(_, depth)| *depth == 0));
+    }
+}
@@ -1,17 +1,68 @@
 #![allow(dead_code)]
-use crate::domain::repositories::UrlRepository;
-use crate::domain::services::NotificationService;
+use crate::domain::entities::archive_record::{ArchiveRecord, DELETED_URL_RETENTION_REASON};
+use crate::domain::entities::{Url, UrlCountChange, UrlStatus};
+use crate::domain::repositories::{
+    ArchiveRepository, ClickRepository, EmailChangeRepository, EmailOutboxRepository,
+    MagicLinkRepository, PasswordResetRepository, UrlRepository, UserUrlStatsRepository,
+};
+use crate::domain::services::notification_service::{
+    DigestRunSummary, DigestSchedule, EXPIRY_DIGEST_WINDOW_DAYS,
+};
+use crate::domain::services::{AnalyticsExportService, NotificationService, ProgressService};
+use crate::infrastructure::config::{retention_cutoff, RetentionConfig};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::interval;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+
+/// Whether cleanup tasks remove data or only count what they would remove
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanupMode {
+    /// Count the affected rows without changing anything
+    DryRun,
+    /// Archive or delete the affected rows
+    Execute,
+}
+
+/// Rows each cleanup task affected, or would affect when run as a dry run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CleanupPreview {
+    pub expired_urls: u64,
+    pub deleted_urls: u64,
+    pub old_clicks: u64,
+    pub expired_password_reset_tokens: u64,
+    pub expired_magic_link_tokens: u64,
+    pub expired_email_changes: u64,
+    pub finished_bulk_operations: u64,
+    pub sent_emails: u64,
+    pub archive_records: u64,
+    pub expired_analytics_exports: u64,
+}
 
 /// Service for handling background cleanup tasks
+///
+/// Each kind of data is kept for the number of days set in its [`RetentionConfig`] entry.
+/// Data whose repository was not provided is left alone. With an archive repository, records
+/// removed for good are copied to the archive first.
 #[derive(Clone)]
 pub struct CleanupService<R>
 where
     R: UrlRepository + Clone,
 {
     url_repository: R,
+    retention: RetentionConfig,
+    click_repository: Option<Arc<dyn ClickRepository>>,
+    password_reset_repository: Option<Arc<dyn PasswordResetRepository>>,
+    magic_link_repository: Option<Arc<dyn MagicLinkRepository>>,
+    email_change_repository: Option<Arc<dyn EmailChangeRepository>>,
+    email_outbox_repository: Option<Arc<dyn EmailOutboxRepository>>,
+    archive_repository: Option<Arc<dyn ArchiveRepository>>,
+    user_url_stats: Option<Arc<dyn UserUrlStatsRepository>>,
+    /// Fields left out of archived copies
+    sensitive_fields: Vec<String>,
+    progress_service: Option<ProgressService>,
+    analytics_export_service: Option<AnalyticsExportService>,
     notification_service: NotificationService,
 }
 
@@ -19,19 +70,119 @@ impl<R> CleanupService<R>
 where
     R: UrlRepository + Clone,
 {
-    pub fn new(url_repository: R) -> Self {
+    pub fn new(url_repository: R, retention: RetentionConfig) -> Self {
         Self {
             url_repository,
+            retention,
+            click_repository: None,
+            password_reset_repository: None,
+            magic_link_repository: None,
+            email_change_repository: None,
+            email_outbox_repository: None,
+            archive_repository: None,
+            user_url_stats: None,
+            sensitive_fields: Vec::new(),
+            progress_service: None,
+            analytics_export_service: None,
             notification_service: NotificationService::new(),
         }
     }
 
+    /// Also delete click records older than the click data retention
+    pub fn with_click_repository(mut self, click_repository: Arc<dyn ClickRepository>) -> Self {
+        self.click_repository = Some(click_repository);
+        self
+    }
+
+    /// Also delete expired password reset tokens
+    pub fn with_password_reset_repository(
+        mut self,
+        password_reset_repository: Arc<dyn PasswordResetRepository>,
+    ) -> Self {
+        self.password_reset_repository = Some(password_reset_repository);
+        self
+    }
+
+    /// Also delete expired magic link tokens
+    pub fn with_magic_link_repository(
+        mut self,
+        magic_link_repository: Arc<dyn MagicLinkRepository>,
+    ) -> Self {
+        self.magic_link_repository = Some(magic_link_repository);
+        self
+    }
+
+    /// Also expire email changes not confirmed by both addresses in time
+    pub fn with_email_change_repository(
+        mut self,
+        email_change_repository: Arc<dyn EmailChangeRepository>,
+    ) -> Self {
+        self.email_change_repository = Some(email_change_repository);
+        self
+    }
+
+    /// Also delete emails sent longer ago than the sent email retention
+    pub fn with_email_outbox_repository(
+        mut self,
+        email_outbox_repository: Arc<dyn EmailOutboxRepository>,
+    ) -> Self {
+        self.email_outbox_repository = Some(email_outbox_repository);
+        self
+    }
+
+    /// Archive records before removing them for good, without the `sensitive_fields`
+    ///
+    /// Archive records older than the archive retention are deleted as well.
+    pub fn with_archive_repository(
+        mut self,
+        archive_repository: Arc<dyn ArchiveRepository>,
+        sensitive_fields: Vec<String>,
+    ) -> Self {
+        self.archive_repository = Some(archive_repository);
+        self.sensitive_fields = sensitive_fields;
+        self
+    }
+
+    /// Move the URLs archived after expiring to their owners' archived counts
+    pub fn with_user_url_stats(mut self, user_url_stats: Arc<dyn UserUrlStatsRepository>) -> Self {
+        self.user_url_stats = Some(user_url_stats);
+        self
+    }
+
+    /// Also forget the progress of finished bulk operations
+    pub fn with_progress_service(mut self, progress_service: ProgressService) -> Self {
+        self.progress_service = Some(progress_service);
+        self
+    }
+
+    /// Also delete analytics export files once their retention has passed
+    pub fn with_analytics_export_service(
+        mut self,
+        analytics_export_service: AnalyticsExportService,
+    ) -> Self {
+        self.analytics_export_service = Some(analytics_export_service);
+        self
+    }
+
+    /// Send notifications, including the weekly expiry digest, through the given service
+    pub fn with_notification_service(mut self, notification_service: NotificationService) -> Self {
+        self.notification_service = notification_service;
+        self
+    }
+
+    /// Retention periods applied by this service
+    pub fn retention(&self) -> &RetentionConfig {
+        &self.retention
+    }
+
     /// Start the cleanup service with the specified interval
+    ///
+    /// Scheduled runs always delete; previews are run on demand with [`Self::preview`].
     pub async fn start_cleanup_service(&self, cleanup_interval_hours: u64) {
         let mut interval = interval(Duration::from_secs(cleanup_interval_hours * 3600));
 
         info!(
-            "Starting URL cleanup service with {} hour interval",
+            "Starting cleanup service with {} hour interval",
             cleanup_interval_hours
         );
 
@@ -43,29 +194,329 @@ where
                 error!("Failed to send expiration warnings: {}", e);
             }
 
-            // Clean up expired URLs
-            match self.cleanup_expired_urls().await {
-                Ok(deleted_count) => {
-                    if deleted_count > 0 {
-                        info!("Cleaned up {} expired URLs", deleted_count);
-                    }
-                }
-                Err(e) => {
-                    error!("Failed to cleanup expired URLs: {}", e);
-                }
+            // Runs daily; each user only gets the digest on their chosen weekday
+            if let Err(e) = self.send_expiry_digests().await {
+                error!("Failed to send expiry digests: {}", e);
             }
+
+            self.run_cleanup().await;
         }
     }
 
-    /// Clean up expired URLs
-    pub async fn cleanup_expired_urls(&self) -> Result<u64, CleanupError> {
-        let deleted_count = self
+    /// Run every cleanup task once, logging what was removed
+    pub async fn run_cleanup(&self) {
+        let mode = CleanupMode::Execute;
+        log_cleanup(
+            "archived expired URLs",
+            self.cleanup_expired_urls(mode).await,
+        );
+        log_cleanup("deleted URLs", self.cleanup_deleted_urls(mode).await);
+        log_cleanup("old clicks", self.cleanup_old_clicks(mode).await);
+        log_cleanup(
+            "expired password reset tokens",
+            self.cleanup_password_reset_tokens(mode).await,
+        );
+        log_cleanup(
+            "expired magic link tokens",
+            self.cleanup_magic_link_tokens(mode).await,
+        );
+        log_cleanup(
+            "expired email changes",
+            self.cleanup_email_changes(mode).await,
+        );
+        log_cleanup(
+            "finished bulk operations",
+            self.cleanup_bulk_operations(mode).await,
+        );
+        log_cleanup("sent emails", self.cleanup_sent_emails(mode).await);
+        log_cleanup("archive records", self.cleanup_archive_records(mode).await);
+        log_cleanup(
+            "expired analytics exports",
+            self.cleanup_analytics_exports(mode).await,
+        );
+    }
+
+    /// Run every cleanup task once in the given mode, stopping at the first failure
+    pub async fn run(&self, mode: CleanupMode) -> Result<CleanupPreview, CleanupError> {
+        Ok(CleanupPreview {
+            expired_urls: self.cleanup_expired_urls(mode).await?,
+            deleted_urls: self.cleanup_deleted_urls(mode).await?,
+            old_clicks: self.cleanup_old_clicks(mode).await?,
+            expired_password_reset_tokens: self.cleanup_password_reset_tokens(mode).await?,
+            expired_magic_link_tokens: self.cleanup_magic_link_tokens(mode).await?,
+            expired_email_changes: self.cleanup_email_changes(mode).await?,
+            finished_bulk_operations: self.cleanup_bulk_operations(mode).await?,
+            sent_emails: self.cleanup_sent_emails(mode).await?,
+            archive_records: self.cleanup_archive_records(mode).await?,
+            expired_analytics_exports: self.cleanup_analytics_exports(mode).await?,
+        })
+    }
+
+    /// Count what a cleanup run would remove, without changing any data
+    pub async fn preview(&self) -> Result<CleanupPreview, CleanupError> {
+        self.run(CleanupMode::DryRun).await
+    }
+
+    /// Archive URLs that expired longer ago than the expired URL retention
+    ///
+    /// Archived URLs keep their click data for later analysis but no longer redirect.
+    pub async fn cleanup_expired_urls(&self, mode: CleanupMode) -> Result<u64, CleanupError> {
+        let Some(cutoff) = retention_cutoff(self.retention.expired_url_retention_days, Utc::now())
+        else {
+            return Ok(0);
+        };
+
+        match mode {
+            CleanupMode::DryRun => Ok(self
+                .url_repository
+                .count_expired_urls_to_archive(cutoff)
+                .await?),
+            CleanupMode::Execute => {
+                let archived = self.url_repository.archive_expired_urls(cutoff).await?;
+                self.count_archived_urls(&archived).await;
+                Ok(archived.len() as u64)
+            }
+        }
+    }
+
+    /// Adjust the counters of the owners of archived URLs; a failure only leaves them to the
+    /// next rebuild
+    async fn count_archived_urls(&self, archived: &[Url]) {
+        let Some(user_url_stats) = &self.user_url_stats else {
+            return;
+        };
+        let changes = UrlCountChange::per_user(archived.iter().map(|url| {
+            let change = UrlCountChange::status_changed(&url.status, &UrlStatus::Archived);
+            (url.user_id, change)
+        }));
+        for (user_id, change) in changes {
+            if let Err(e) = user_url_stats.adjust_url_counts(user_id, change).await {
+                warn!("Failed to update the URL stats of user {}: {}", user_id, e);
+            }
+        }
+    }
+
+    /// Remove URLs deleted longer ago than the deleted URL retention, with their clicks
+    ///
+    /// Each URL is archived first when an archive repository is set; a URL whose archiving
+    /// fails is kept for the next run.
+    pub async fn cleanup_deleted_urls(&self, mode: CleanupMode) -> Result<u64, CleanupError> {
+        let Some(cutoff) = retention_cutoff(self.retention.deleted_url_retention_days, Utc::now())
+        else {
+            return Ok(0);
+        };
+
+        let deleted_urls = self
             .url_repository
-            .delete_expired_urls()
+            .find_deleted_before(cutoff)
             .await
             .map_err(CleanupError::Repository)?;
+        if mode == CleanupMode::DryRun {
+            return Ok(deleted_urls.len() as u64);
+        }
 
-        Ok(deleted_count)
+        let mut removed_count = 0;
+        for url in deleted_urls {
+            if let Some(archive_repository) = &self.archive_repository {
+                let record = ArchiveRecord::snapshot(
+                    "url",
+                    url.id,
+                    &url,
+                    DELETED_URL_RETENTION_REASON,
+                    &self.sensitive_fields,
+                )
+                .map_err(|e| {
+                    CleanupError::TaskError(format!("Failed to archive URL {}: {}", url.id, e))
+                })?;
+                archive_repository
+                    .archive(&record)
+                    .await
+                    .map_err(CleanupError::Repository)?;
+            }
+
+            if self
+                .url_repository
+                .permanently_delete_by_id(url.id)
+                .await
+                .map_err(CleanupError::Repository)?
+            {
+                removed_count += 1;
+            }
+        }
+
+        Ok(removed_count)
+    }
+
+    /// Delete click records older than the click data retention
+    pub async fn cleanup_old_clicks(&self, mode: CleanupMode) -> Result<u64, CleanupError> {
+        let Some(click_repository) = &self.click_repository else {
+            return Ok(0);
+        };
+        let Some(cutoff) = retention_cutoff(self.retention.click_data_retention_days, Utc::now())
+        else {
+            return Ok(0);
+        };
+
+        match mode {
+            CleanupMode::DryRun => click_repository.count_old_clicks(cutoff).await,
+            CleanupMode::Execute => click_repository.delete_old_clicks(cutoff).await,
+        }
+        .map_err(|e| CleanupError::TaskError(format!("Failed to delete clicks: {}", e)))
+    }
+
+    /// Delete password reset tokens that expired longer ago than their retention
+    pub async fn cleanup_password_reset_tokens(
+        &self,
+        mode: CleanupMode,
+    ) -> Result<u64, CleanupError> {
+        let Some(password_reset_repository) = &self.password_reset_repository else {
+            return Ok(0);
+        };
+        let Some(cutoff) = retention_cutoff(
+            self.retention.password_reset_token_retention_days,
+            Utc::now(),
+        ) else {
+            return Ok(0);
+        };
+
+        match mode {
+            CleanupMode::DryRun => password_reset_repository.count_expired_tokens(cutoff).await,
+            CleanupMode::Execute => {
+                password_reset_repository
+                    .delete_expired_tokens(cutoff)
+                    .await
+            }
+        }
+        .map(|count| count as u64)
+        .map_err(|e| {
+            CleanupError::TaskError(format!("Failed to delete password reset tokens: {}", e))
+        })
+    }
+
+    /// Delete magic link tokens that expired longer ago than their retention
+    pub async fn cleanup_magic_link_tokens(&self, mode: CleanupMode) -> Result<u64, CleanupError> {
+        let Some(magic_link_repository) = &self.magic_link_repository else {
+            return Ok(0);
+        };
+        let Some(cutoff) =
+            retention_cutoff(self.retention.magic_link_token_retention_days, Utc::now())
+        else {
+            return Ok(0);
+        };
+
+        match mode {
+            CleanupMode::DryRun => magic_link_repository.count_expired_tokens(cutoff).await,
+            CleanupMode::Execute => magic_link_repository.delete_expired_tokens(cutoff).await,
+        }
+        .map(|count| count as u64)
+        .map_err(|e| CleanupError::TaskError(format!("Failed to delete magic link tokens: {}", e)))
+    }
+
+    /// Delete email changes that expired, clearing the pending email of their users
+    ///
+    /// Unlike tokens, changes are not kept for a retention period: the pending email they
+    /// set should go away as soon as the change can no longer complete.
+    pub async fn cleanup_email_changes(&self, mode: CleanupMode) -> Result<u64, CleanupError> {
+        let Some(email_change_repository) = &self.email_change_repository else {
+            return Ok(0);
+        };
+        let now = Utc::now();
+
+        match mode {
+            CleanupMode::DryRun => email_change_repository.count_expired_tokens(now).await,
+            CleanupMode::Execute => email_change_repository.delete_expired_tokens(now).await,
+        }
+        .map(|count| count as u64)
+        .map_err(|e| CleanupError::TaskError(format!("Failed to delete email changes: {}", e)))
+    }
+
+    /// Forget finished bulk operations not updated within the bulk operation retention
+    pub async fn cleanup_bulk_operations(&self, mode: CleanupMode) -> Result<u64, CleanupError> {
+        let Some(progress_service) = &self.progress_service else {
+            return Ok(0);
+        };
+        let Some(cutoff) =
+            retention_cutoff(self.retention.bulk_operation_retention_days, Utc::now())
+        else {
+            return Ok(0);
+        };
+
+        match mode {
+            CleanupMode::DryRun => progress_service.count_old_operations(cutoff).await,
+            CleanupMode::Execute => progress_service.cleanup_old_operations(cutoff).await,
+        }
+        .map(|count| count as u64)
+        .map_err(|e| CleanupError::TaskError(format!("Failed to clean up operations: {}", e)))
+    }
+
+    /// Delete outbox emails sent longer ago than the sent email retention
+    ///
+    /// Emails still pending or given up are kept, so failures can be looked into.
+    pub async fn cleanup_sent_emails(&self, mode: CleanupMode) -> Result<u64, CleanupError> {
+        let Some(email_outbox_repository) = &self.email_outbox_repository else {
+            return Ok(0);
+        };
+        let Some(cutoff) = retention_cutoff(self.retention.sent_email_retention_days, Utc::now())
+        else {
+            return Ok(0);
+        };
+
+        match mode {
+            CleanupMode::DryRun => email_outbox_repository.count_sent_before(cutoff).await,
+            CleanupMode::Execute => email_outbox_repository.delete_sent_before(cutoff).await,
+        }
+        .map(|count| count as u64)
+        .map_err(|e| CleanupError::TaskError(format!("Failed to delete sent emails: {}", e)))
+    }
+
+    /// Delete archive records older than the archive retention
+    pub async fn cleanup_archive_records(&self, mode: CleanupMode) -> Result<u64, CleanupError> {
+        let Some(archive_repository) = &self.archive_repository else {
+            return Ok(0);
+        };
+        let Some(cutoff) = retention_cutoff(self.retention.archive_retention_days, Utc::now())
+        else {
+            return Ok(0);
+        };
+
+        match mode {
+            CleanupMode::DryRun => archive_repository.count_archived_before(cutoff).await,
+            CleanupMode::Execute => archive_repository.delete_archived_before(cutoff).await,
+        }
+        .map_err(CleanupError::Repository)
+    }
+
+    /// Delete the files of analytics exports finished more than 24 hours ago
+    pub async fn cleanup_analytics_exports(&self, mode: CleanupMode) -> Result<u64, CleanupError> {
+        let Some(analytics_export_service) = &self.analytics_export_service else {
+            return Ok(0);
+        };
+        let now = Utc::now();
+
+        match mode {
+            CleanupMode::DryRun => analytics_export_service.count_expired_exports(now).await,
+            CleanupMode::Execute => analytics_export_service.purge_expired_exports(now).await,
+        }
+        .map_err(|e| CleanupError::TaskError(format!("Failed to delete analytics exports: {}", e)))
+    }
+
+    /// Archive records deleted before `before`, optionally of one entity type, newest first
+    ///
+    /// Without an archive repository nothing is archived, so the list is empty.
+    pub async fn list_archive_records(
+        &self,
+        entity_type: Option<&str>,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<ArchiveRecord>, CleanupError> {
+        let Some(archive_repository) = &self.archive_repository else {
+            return Ok(Vec::new());
+        };
+
+        archive_repository
+            .find_records(entity_type, before, limit)
+            .await
+            .map_err(CleanupError::Repository)
     }
 
     /// Get URLs that are expiring soon for notification purposes
@@ -89,6 +540,24 @@ where
             .map_err(CleanupError::Repository)
     }
 
+    /// Send the expiry digest to users whose digest day is today
+    pub async fn send_expiry_digests(&self) -> Result<DigestRunSummary, CleanupError> {
+        if !self.notification_service.can_send_digests() {
+            return Ok(DigestRunSummary::default());
+        }
+
+        let expiring_urls = self
+            .url_repository
+            .find_urls_expiring_soon(chrono::Duration::days(EXPIRY_DIGEST_WINDOW_DAYS))
+            .await
+            .map_err(CleanupError::Repository)?;
+
+        self.notification_service
+            .send_expiry_digest_for_all_users(&expiring_urls, DigestSchedule::Scheduled, Utc::now())
+            .await
+            .map_err(|e| CleanupError::TaskError(format!("Failed to send expiry digests: {}", e)))
+    }
+
     /// Send expiration warnings for URLs expiring soon
     pub async fn send_expiration_warnings(&self, warning_days: u32) -> Result<(), CleanupError> {
         let duration = chrono::Duration::days(warning_days as i64);
@@ -118,6 +587,14 @@ where
     }
 }
 
+fn log_cleanup(what: &str, result: Result<u64, CleanupError>) {
+    match result {
+        Ok(0) => {}
+        Ok(deleted_count) => info!("Cleaned up {} {}", deleted_count, what),
+        Err(e) => error!("Failed to clean up {}: {}", what, e),
+    }
+}
+
 /// Cleanup service errors
 #[derive(Debug, thiserror::Error)]
 pub enum CleanupError {
@@ -132,8 +609,11 @@ pub enum CleanupError {
 mod tests {
     use super::*;
     use crate::domain::{
-        entities::UrlStatus,
-        repositories::{RepositoryError, UrlRepository},
+        entities::{Click, ConversionEvent, ConversionGoal, UrlConfig, UrlStatus},
+        repositories::{
+            ClickCountTotal, ClickDedupRatio, ClickRepositoryError, ClickStats, RepositoryError,
+            UrlAnalyticsSummary, UrlRepository,
+        },
     };
     use async_trait::async_trait;
     use std::sync::{Arc, Mutex};
@@ -160,15 +640,33 @@ mod tests {
             _original_url: &str,
             _expiration_date: Option<chrono::DateTime<chrono::Utc>>,
             _user_id: Option<i32>,
+            _organization_id: Option<i32>,
             _status: crate::domain::entities::UrlStatus,
         ) -> Result<crate::domain::entities::Url, crate::domain::repositories::RepositoryError>
         {
             todo!()
         }
 
+        async fn create_url_idempotent(
+            &self,
+            _short_code: &crate::domain::entities::ShortCode,
+            _original_url: &str,
+            _expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+            _user_id: Option<i32>,
+            _organization_id: Option<i32>,
+            _max_organization_urls: Option<i64>,
+            _status: crate::domain::entities::UrlStatus,
+        ) -> Result<
+            (crate::domain::entities::Url, bool),
+            crate::domain::repositories::RepositoryError,
+        > {
+            todo!()
+        }
+
         async fn find_by_short_code(
             &self,
             _short_code: &crate::domain::entities::ShortCode,
+            _force_primary: bool,
         ) -> Result<
             Option<crate::domain::entities::Url>,
             crate::domain::repositories::RepositoryError,
@@ -179,6 +677,25 @@ mod tests {
         async fn find_by_user_id(
             &self,
             _user_id: i32,
+            _organization_id: Option<i32>,
+        ) -> Result<Vec<crate::domain::entities::Url>, crate::domain::repositories::RepositoryError>
+        {
+            todo!()
+        }
+
+        async fn find_by_original_url(
+            &self,
+            _original_url: &str,
+            _user_id: i32,
+        ) -> Result<Vec<crate::domain::entities::Url>, crate::domain::repositories::RepositoryError>
+        {
+            todo!()
+        }
+
+        async fn find_all_by_original_url(
+            &self,
+            _original_url: &str,
+            _limit: usize,
         ) -> Result<Vec<crate::domain::entities::Url>, crate::domain::repositories::RepositoryError>
         {
             todo!()
@@ -191,6 +708,35 @@ mod tests {
             todo!()
         }
 
+        async fn find_phonetically_similar_codes(
+            &self,
+            _generated: &crate::domain::entities::ShortCode,
+            _threshold: f32,
+        ) -> Result<
+            Vec<crate::domain::entities::ShortCode>,
+            crate::domain::repositories::RepositoryError,
+        > {
+            todo!()
+        }
+
+        async fn replace_short_code(
+            &self,
+            _url_id: i32,
+            _new_short_code: &crate::domain::entities::ShortCode,
+        ) -> Result<(), crate::domain::repositories::RepositoryError> {
+            todo!()
+        }
+
+        async fn rename_short_code(
+            &self,
+            _url_id: i32,
+            _new_short_code: &crate::domain::entities::ShortCode,
+            _max_aliases: usize,
+        ) -> Result<crate::domain::entities::Url, crate::domain::repositories::RepositoryError>
+        {
+            todo!()
+        }
+
         async fn delete_by_id(
             &self,
             _id: i32,
@@ -199,9 +745,65 @@ mod tests {
             todo!()
         }
 
+        async fn find_deleted_by_short_code(
+            &self,
+            _short_code: &crate::domain::entities::ShortCode,
+        ) -> Result<
+            Option<crate::domain::entities::Url>,
+            crate::domain::repositories::RepositoryError,
+        > {
+            todo!()
+        }
+
+        async fn permanently_delete_by_id(
+            &self,
+            id: i32,
+        ) -> Result<bool, crate::domain::repositories::RepositoryError> {
+            let mut urls = self.urls.lock().unwrap();
+            let before = urls.len();
+            urls.retain(|u| u.id != id);
+            Ok(urls.len() < before)
+        }
+
+        async fn find_deleted_before(
+            &self,
+            deleted_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<crate::domain::entities::Url>, crate::domain::repositories::RepositoryError>
+        {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .filter(|u| u.deleted_at.is_some_and(|d| d < deleted_before))
+                .cloned()
+                .collect())
+        }
+
+        async fn transfer_owner(
+            &self,
+            _id: i32,
+            _from_user_id: i32,
+            _to_user_id: i32,
+        ) -> Result<
+            Option<crate::domain::entities::Url>,
+            crate::domain::repositories::RepositoryError,
+        > {
+            todo!()
+        }
+
+        async fn find_by_id(
+            &self,
+            _id: i32,
+        ) -> Result<
+            Option<crate::domain::entities::Url>,
+            crate::domain::repositories::RepositoryError,
+        > {
+            todo!()
+        }
+
         async fn update_url(
             &self,
             _url: &crate::domain::entities::Url,
+            _expected_version: i64,
         ) -> Result<crate::domain::entities::Url, crate::domain::repositories::RepositoryError>
         {
             todo!()
@@ -257,16 +859,46 @@ mod tests {
 
         async fn delete_expired_urls(
             &self,
+            expired_before: chrono::DateTime<chrono::Utc>,
         ) -> Result<u64, crate::domain::repositories::RepositoryError> {
             let mut urls = self.urls.lock().unwrap();
             let initial_count = urls.len();
 
-            urls.retain(|url| !url.is_expired());
+            urls.retain(|url| url.expiration_date.is_none_or(|e| e > expired_before));
 
             let deleted_count = initial_count - urls.len();
             Ok(deleted_count as u64)
         }
 
+        async fn archive_expired_urls(
+            &self,
+            expired_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<crate::domain::entities::Url>, crate::domain::repositories::RepositoryError>
+        {
+            let mut urls = self.urls.lock().unwrap();
+            let mut archived = Vec::new();
+            for url in urls.iter_mut().filter(|url| {
+                !url.is_archived() && url.expiration_date.is_some_and(|e| e <= expired_before)
+            }) {
+                archived.push(url.clone());
+                url.status = crate::domain::entities::UrlStatus::Archived;
+            }
+            Ok(archived)
+        }
+
+        async fn count_expired_urls_to_archive(
+            &self,
+            expired_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<u64, crate::domain::repositories::RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .filter(|url| {
+                    !url.is_archived() && url.expiration_date.is_some_and(|e| e <= expired_before)
+                })
+                .count() as u64)
+        }
+
         async fn soft_delete_by_id(
             &self,
             _id: i32,
@@ -292,6 +924,27 @@ mod tests {
             todo!()
         }
 
+        async fn find_by_status_paginated(
+            &self,
+            _status: crate::domain::entities::UrlStatus,
+            _after_id: Option<i32>,
+            _limit: usize,
+        ) -> Result<
+            (Vec<crate::domain::entities::Url>, Option<i32>),
+            crate::domain::repositories::RepositoryError,
+        > {
+            todo!()
+        }
+
+        async fn find_suspicious_urls(
+            &self,
+            _min_click_count: i64,
+            _created_within_hours: u8,
+        ) -> Result<Vec<crate::domain::entities::Url>, crate::domain::repositories::RepositoryError>
+        {
+            todo!()
+        }
+
         async fn batch_deactivate_urls(
             &self,
             url_ids: &[i32],
@@ -455,15 +1108,643 @@ mod tests {
                 results,
             })
         }
+
+        async fn find_most_clicked(
+            &self,
+            _user_id: i32,
+            _limit: usize,
+        ) -> Result<
+            Vec<crate::domain::entities::UrlWithClickCount>,
+            crate::domain::repositories::RepositoryError,
+        > {
+            todo!()
+        }
+
+        async fn find_recently_created(
+            &self,
+            _user_id: i32,
+            _limit: usize,
+        ) -> Result<Vec<crate::domain::entities::Url>, crate::domain::repositories::RepositoryError>
+        {
+            todo!()
+        }
+
+        async fn find_recently_modified(
+            &self,
+            _user_id: i32,
+            _since: chrono::DateTime<chrono::Utc>,
+            _limit: usize,
+        ) -> Result<Vec<crate::domain::entities::Url>, crate::domain::repositories::RepositoryError>
+        {
+            todo!()
+        }
+
+        async fn find_paginated(
+            &self,
+            _user_id: Option<i32>,
+            _status: Option<crate::domain::entities::UrlStatus>,
+            _sort: crate::domain::repositories::UrlSortField,
+            _direction: crate::domain::repositories::SortDirection,
+            _after_cursor: Option<&crate::domain::repositories::UrlCursor>,
+            _limit: usize,
+        ) -> Result<
+            crate::domain::repositories::UrlPage,
+            crate::domain::repositories::RepositoryError,
+        > {
+            todo!()
+        }
+
+        async fn count_by_filter(
+            &self,
+            _filter: &crate::domain::repositories::UrlFilter,
+            _user_id: i32,
+        ) -> Result<i64, crate::domain::repositories::RepositoryError> {
+            todo!()
+        }
+
+        async fn count_clicks_for_urls(
+            &self,
+            _url_ids: &[i32],
+        ) -> Result<std::collections::HashMap<i32, i64>, crate::domain::repositories::RepositoryError>
+        {
+            todo!()
+        }
+    }
+
+    /// Click repository holding only click times
+    #[derive(Default)]
+    struct MockClickRepository {
+        clicked_at: Mutex<Vec<chrono::DateTime<chrono::Utc>>>,
+    }
+
+    #[async_trait]
+    impl ClickRepository for MockClickRepository {
+        async fn record_click(&self, _click: &Click) -> Result<Click, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn record_clicks(&self, _clicks: &[Click]) -> Result<u64, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn increment_deduplicated_click_counts(
+            &self,
+            _counts: &[(i32, i64)],
+        ) -> Result<Vec<ClickCountTotal>, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_click_count(&self, _url_id: i32) -> Result<i64, ClickRepositoryError> {
+            Ok(self.clicked_at.lock().unwrap().len() as i64)
+        }
+
+        async fn get_clicks_for_url(
+            &self,
+            _url_id: i32,
+            _start_date: Option<chrono::DateTime<chrono::Utc>>,
+            _end_date: Option<chrono::DateTime<chrono::Utc>>,
+        ) -> Result<Vec<Click>, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_clicks_for_user(
+            &self,
+            _user_id: i32,
+            _start_date: Option<chrono::DateTime<chrono::Utc>>,
+            _end_date: Option<chrono::DateTime<chrono::Utc>>,
+        ) -> Result<Vec<Click>, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_clicks_for_user_page(
+            &self,
+            _user_id: i32,
+            _start_date: Option<chrono::DateTime<chrono::Utc>>,
+            _end_date: Option<chrono::DateTime<chrono::Utc>>,
+            _after_id: Option<i32>,
+            _limit: i64,
+        ) -> Result<Vec<Click>, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_url_click_stats(
+            &self,
+            _url_id: i32,
+        ) -> Result<ClickStats, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_user_click_stats(
+            &self,
+            _user_id: i32,
+        ) -> Result<ClickStats, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_unique_visitors_estimate(
+            &self,
+            _url_id: i32,
+            _start: chrono::NaiveDate,
+            _end: chrono::NaiveDate,
+        ) -> Result<i64, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_url_analytics_summary(
+            &self,
+            _url_id: i32,
+            _include_bots: bool,
+        ) -> Result<UrlAnalyticsSummary, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn create_conversion_goal(
+            &self,
+            _url_id: i32,
+            _goal_url_pattern: &str,
+            _name: &str,
+        ) -> Result<ConversionGoal, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn find_conversion_goal(
+            &self,
+            _id: i32,
+        ) -> Result<Option<ConversionGoal>, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn delete_conversion_goal(&self, _id: i32) -> Result<bool, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn record_conversion(
+            &self,
+            _click_token: &str,
+            _goal_id: i32,
+        ) -> Result<Option<ConversionEvent>, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_conversion_rate(&self, _url_id: i32) -> Result<f64, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_click_dedup_ratio(
+            &self,
+            _url_id: i32,
+        ) -> Result<ClickDedupRatio, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn find_url_config(
+            &self,
+            _url_id: i32,
+        ) -> Result<Option<UrlConfig>, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn save_url_config(
+            &self,
+            _config: &UrlConfig,
+        ) -> Result<UrlConfig, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_hourly_distribution(
+            &self,
+            _url_id: i32,
+        ) -> Result<[u32; 24], ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_daily_distribution(
+            &self,
+            _url_id: i32,
+        ) -> Result<[u32; 7], ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_peak_hour(&self, _url_id: i32) -> Result<u8, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_click_velocity(
+            &self,
+            _url_id: i32,
+            _window_minutes: u32,
+        ) -> Result<f64, ClickRepositoryError> {
+            todo!()
+        }
+
+        async fn get_utm_attribution_breakdown(
+            &self,
+            _url_id: i32,
+        ) -> Result<Vec<crate::domain::repositories::UtmAttributionRow>, ClickRepositoryError>
+        {
+            todo!()
+        }
+
+        async fn delete_old_clicks(
+            &self,
+            older_than: chrono::DateTime<chrono::Utc>,
+        ) -> Result<u64, ClickRepositoryError> {
+            let mut clicked_at = self.clicked_at.lock().unwrap();
+            let initial_count = clicked_at.len();
+            clicked_at.retain(|at| *at >= older_than);
+            Ok((initial_count - clicked_at.len()) as u64)
+        }
+
+        async fn count_old_clicks(
+            &self,
+            older_than: chrono::DateTime<chrono::Utc>,
+        ) -> Result<u64, ClickRepositoryError> {
+            let clicked_at = self.clicked_at.lock().unwrap();
+            Ok(clicked_at.iter().filter(|at| **at < older_than).count() as u64)
+        }
+    }
+
+    fn click_repository_with_old_clicks() -> Arc<MockClickRepository> {
+        let now = chrono::Utc::now();
+        Arc::new(MockClickRepository {
+            clicked_at: Mutex::new(vec![
+                now - chrono::Duration::days(800),
+                now - chrono::Duration::days(400),
+                now - chrono::Duration::days(1),
+            ]),
+        })
     }
 
     #[tokio::test]
     async fn test_cleanup_expired_urls() {
         let repo = MockUrlRepository::new();
-        let service = CleanupService::new(repo);
+        let service = CleanupService::new(repo, RetentionConfig::default());
 
         // Test with no expired URLs
-        let deleted_count = service.cleanup_expired_urls().await.unwrap();
-        assert_eq!(deleted_count, 0);
+        let archived_count = service
+            .cleanup_expired_urls(CleanupMode::Execute)
+            .await
+            .unwrap();
+        assert_eq!(archived_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_archives_urls_past_retention() {
+        let repo = MockUrlRepository::new();
+        let now = chrono::Utc::now();
+        for (id, expired_days_ago) in [(1, 40), (2, 10)] {
+            repo.urls
+                .lock()
+                .unwrap()
+                .push(crate::domain::entities::Url::new_with_timestamp(
+                    id,
+                    format!("exp{}", id),
+                    "https://example.com".to_string(),
+                    Some(now - chrono::Duration::days(expired_days_ago)),
+                    Some(1),
+                    crate::domain::entities::UrlStatus::Active,
+                ));
+        }
+        let stats = crate::infrastructure::test_utils::MockUserUrlStatsRepository::new();
+        let service = CleanupService::new(repo.clone(), RetentionConfig::default())
+            .with_user_url_stats(Arc::new(stats.clone()));
+
+        assert_eq!(
+            service
+                .cleanup_expired_urls(CleanupMode::Execute)
+                .await
+                .unwrap(),
+            1
+        );
+        // The URLs were stored without being counted, so only the move shows: one URL left
+        // the owner's active count for the archived one
+        let user_stats = stats.find_by_user_id(1).await.unwrap().unwrap();
+        assert_eq!((user_stats.active_urls, user_stats.archived_urls), (-1, 1));
+        // Archived URLs are kept, and archiving again changes nothing
+        assert_eq!(
+            service
+                .cleanup_expired_urls(CleanupMode::Execute)
+                .await
+                .unwrap(),
+            0
+        );
+        let statuses: Vec<_> = repo.urls.lock().unwrap().iter().map(|u| u.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                crate::domain::entities::UrlStatus::Archived,
+                crate::domain::entities::UrlStatus::Active
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_removes_urls_deleted_past_retention() {
+        let repo = MockUrlRepository::new();
+        let now = chrono::Utc::now();
+        for (id, deleted_days_ago) in [(1, 100), (2, 10)] {
+            let mut url = crate::domain::entities::Url::new_with_timestamp(
+                id,
+                format!("del{}", id),
+                "https://example.com".to_string(),
+                None,
+                Some(1),
+                crate::domain::entities::UrlStatus::Active,
+            );
+            url.mark_deleted(now - chrono::Duration::days(deleted_days_ago));
+            repo.urls.lock().unwrap().push(url);
+        }
+        let service = CleanupService::new(repo.clone(), RetentionConfig::default());
+
+        assert_eq!(
+            service
+                .cleanup_deleted_urls(CleanupMode::Execute)
+                .await
+                .unwrap(),
+            1
+        );
+        let ids: Vec<_> = repo.urls.lock().unwrap().iter().map(|u| u.id).collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_removed_urls_are_archived_without_sensitive_fields() {
+        use crate::infrastructure::test_utils::MockArchiveRepository;
+
+        let repo = MockUrlRepository::new();
+        let now = chrono::Utc::now();
+        for (id, deleted_days_ago) in [(1, 100), (2, 10)] {
+            let mut url = crate::domain::entities::Url::new_with_timestamp(
+                id,
+                format!("del{}", id),
+                "https://example.com/private".to_string(),
+                None,
+                Some(42),
+                crate::domain::entities::UrlStatus::Active,
+            );
+            url.mark_deleted(now - chrono::Duration::days(deleted_days_ago));
+            repo.urls.lock().unwrap().push(url);
+        }
+        let archive = Arc::new(MockArchiveRepository::new());
+        let service = CleanupService::new(repo.clone(), RetentionConfig::default())
+            .with_archive_repository(
+                archive.clone(),
+                vec!["user_id".to_string(), "original_url".to_string()],
+            );
+
+        assert_eq!(
+            service
+                .cleanup_deleted_urls(CleanupMode::Execute)
+                .await
+                .unwrap(),
+            1
+        );
+
+        let records = archive.records.lock().unwrap().clone();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.entity_type, "url");
+        assert_eq!(record.entity_id, 1);
+        assert_eq!(record.deletion_reason, DELETED_URL_RETENTION_REASON);
+        assert_eq!(record.data_snapshot["short_code"], "del1");
+        assert_eq!(record.data_snapshot["id"], 1);
+        assert!(record.data_snapshot.get("user_id").is_none());
+        assert!(record.data_snapshot.get("original_url").is_none());
+        assert!(record.data_snapshot.get("password_hash").is_none());
+
+        let listed = service
+            .list_archive_records(Some("url"), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(listed, records);
+        assert!(service
+            .list_archive_records(Some("user"), None, 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_archive_records_are_purged_after_archive_retention() {
+        use crate::infrastructure::test_utils::MockArchiveRepository;
+
+        let archive = Arc::new(MockArchiveRepository::new());
+        let now = chrono::Utc::now();
+        for (entity_id, archived_days_ago) in [(1, 2600), (2, 30)] {
+            archive.records.lock().unwrap().push(ArchiveRecord {
+                id: entity_id.into(),
+                entity_type: "url".to_string(),
+                entity_id,
+                data_snapshot: serde_json::json!({}),
+                deleted_at: now - chrono::Duration::days(archived_days_ago),
+                deletion_reason: DELETED_URL_RETENTION_REASON.to_string(),
+            });
+        }
+        let service = CleanupService::new(MockUrlRepository::new(), RetentionConfig::default())
+            .with_archive_repository(archive.clone(), Vec::new());
+
+        assert_eq!(
+            service
+                .cleanup_archive_records(CleanupMode::DryRun)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            service
+                .cleanup_archive_records(CleanupMode::Execute)
+                .await
+                .unwrap(),
+            1
+        );
+        let ids: Vec<_> = archive
+            .records
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| r.entity_id)
+            .collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_counts_without_changing_data() {
+        let repo = MockUrlRepository::new();
+        let now = chrono::Utc::now();
+        let mut deleted = crate::domain::entities::Url::new_with_timestamp(
+            1,
+            "del1".to_string(),
+            "https://example.com".to_string(),
+            None,
+            Some(1),
+            crate::domain::entities::UrlStatus::Active,
+        );
+        deleted.mark_deleted(now - chrono::Duration::days(100));
+        let expired = crate::domain::entities::Url::new_with_timestamp(
+            2,
+            "exp2".to_string(),
+            "https://example.com".to_string(),
+            Some(now - chrono::Duration::days(40)),
+            Some(1),
+            crate::domain::entities::UrlStatus::Active,
+        );
+        repo.urls.lock().unwrap().extend([deleted, expired]);
+        let clicks = click_repository_with_old_clicks();
+        let service = CleanupService::new(repo.clone(), RetentionConfig::default())
+            .with_click_repository(clicks.clone());
+
+        let stored = || -> Vec<_> {
+            repo.urls
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|u| (u.id, u.status))
+                .collect()
+        };
+        let before = stored();
+
+        let preview = service.preview().await.unwrap();
+        assert_eq!(
+            preview,
+            CleanupPreview {
+                expired_urls: 1,
+                deleted_urls: 1,
+                old_clicks: 2,
+                ..CleanupPreview::default()
+            }
+        );
+
+        assert_eq!(stored(), before);
+        assert_eq!(clicks.get_click_count(1).await.unwrap(), 3);
+
+        // Executing removes exactly what the preview counted
+        assert_eq!(service.run(CleanupMode::Execute).await.unwrap(), preview);
+        assert_eq!(service.preview().await.unwrap(), CleanupPreview::default());
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_old_clicks_uses_click_retention() {
+        let clicks = click_repository_with_old_clicks();
+        let service = CleanupService::new(MockUrlRepository::new(), RetentionConfig::default())
+            .with_click_repository(clicks.clone());
+
+        let deleted_count = service
+            .cleanup_old_clicks(CleanupMode::Execute)
+            .await
+            .unwrap();
+        assert_eq!(deleted_count, 2);
+        assert_eq!(clicks.get_click_count(1).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_zero_click_retention_keeps_click_data() {
+        let clicks = click_repository_with_old_clicks();
+        let retention = RetentionConfig {
+            click_data_retention_days: 0,
+            ..RetentionConfig::default()
+        };
+        let service = CleanupService::new(MockUrlRepository::new(), retention)
+            .with_click_repository(clicks.clone());
+
+        service.run_cleanup().await;
+        assert_eq!(
+            service
+                .cleanup_old_clicks(CleanupMode::Execute)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(clicks.get_click_count(1).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_sent_emails_keeps_pending_ones() {
+        use crate::domain::repositories::EmailOutboxRepository;
+        use crate::infrastructure::email::EmailMessage;
+        use crate::infrastructure::test_utils::MockEmailOutboxRepository;
+
+        let outbox = Arc::new(MockEmailOutboxRepository::new());
+        let now = chrono::Utc::now();
+        for (to, sent_days_ago) in [
+            ("old@example.com", Some(31)),
+            ("new@example.com", Some(1)),
+            ("pending@example.com", None),
+        ] {
+            let email = outbox
+                .enqueue(&EmailMessage::new(
+                    to.to_string(),
+                    "Subject".to_string(),
+                    "Body".to_string(),
+                ))
+                .await
+                .unwrap();
+            if let Some(days) = sent_days_ago {
+                outbox
+                    .mark_sent(email.id, now - chrono::Duration::days(days))
+                    .await
+                    .unwrap();
+            }
+        }
+        let service = CleanupService::new(MockUrlRepository::new(), RetentionConfig::default())
+            .with_email_outbox_repository(outbox.clone());
+
+        assert_eq!(
+            service
+                .cleanup_sent_emails(CleanupMode::Execute)
+                .await
+                .unwrap(),
+            1
+        );
+        let recipients: Vec<_> = outbox
+            .emails()
+            .into_iter()
+            .map(|email| email.recipient)
+            .collect();
+        assert_eq!(recipients, ["new@example.com", "pending@example.com"]);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_email_changes_removes_expired_ones() {
+        use crate::domain::entities::EmailChangeToken;
+        use crate::domain::repositories::EmailChangeRepository;
+        use crate::infrastructure::test_utils::MockEmailChangeRepository;
+
+        let changes = Arc::new(MockEmailChangeRepository::new());
+        for (user_id, expiration_hours) in [(1, -1), (2, 24)] {
+            changes
+                .create_token(EmailChangeToken::new(
+                    user_id,
+                    format!("user{}@new.example", user_id),
+                    format!("old-{}", user_id),
+                    format!("new-{}", user_id),
+                    expiration_hours,
+                ))
+                .await
+                .unwrap();
+        }
+        let service = CleanupService::new(MockUrlRepository::new(), RetentionConfig::default())
+            .with_email_change_repository(changes.clone());
+
+        assert_eq!(
+            service
+                .cleanup_email_changes(CleanupMode::DryRun)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            service
+                .cleanup_email_changes(CleanupMode::Execute)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            changes
+                .count_expired_tokens(chrono::Utc::now() + chrono::Duration::days(2))
+                .await
+                .unwrap(),
+            1
+        );
     }
 }
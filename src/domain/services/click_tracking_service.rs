@@ -1,8 +1,24 @@
 #![allow(dead_code)]
-use crate::domain::entities::Click;
-use crate::domain::repositories::{ClickRepository, ClickRepositoryError, ClickStats};
-use tokio::sync::{mpsc, oneshot};
-use tokio::task;
+use crate::domain::entities::click::DeviceType;
+use crate::domain::entities::{Click, ConversionEvent, ConversionGoal, UrlConfig, UtmParameters};
+use crate::domain::events::{DomainEvent, EventBus};
+use crate::domain::repositories::{
+    ClickCountTotal, ClickDedupRatio, ClickRepository, ClickRepositoryError, ClickStats,
+    UrlAnalyticsSummary, UserUrlStatsRepository, UtmAttributionRow,
+};
+use crate::domain::services::notification_service::{
+    crossed_click_milestones, NotificationService,
+};
+use crate::infrastructure::analytics_cache::UrlAnalyticsCacheInvalidator;
+use crate::infrastructure::click_deduplication::ClickDeduplicator;
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Months, TimeZone, Timelike, Utc};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::task::{self, JoinHandle};
+use tokio::time::{interval, MissedTickBehavior};
 
 /// Click tracking information extracted from HTTP request
 #[derive(Debug, Clone)]
@@ -11,125 +27,459 @@ pub struct ClickInfo {
     pub user_agent: Option<String>,
     pub referer: Option<String>,
     pub country_code: Option<String>,
+    /// Token set in the visitor's click cookie, used to attribute conversions
+    pub click_token: Option<String>,
+    /// The IP's reputation score is over the configured threshold
+    pub suspicious: bool,
+    /// Campaign the click is attributed to
+    pub utm: UtmParameters,
+}
+
+/// A click waiting in the buffer to be written
+#[derive(Debug, Clone)]
+pub struct ClickRecord {
+    pub url_id: i32,
+    pub click_info: ClickInfo,
+    pub clicked_at: DateTime<Utc>,
+}
+
+impl ClickRecord {
+    /// Create a record for a click happening now
+    pub fn new(url_id: i32, click_info: ClickInfo) -> Self {
+        Self {
+            url_id,
+            click_info,
+            clicked_at: Utc::now(),
+        }
+    }
+
+    /// Convert into a Click entity ready for insertion
+    fn into_click(self) -> Click {
+        let mut click = Click::new_for_tracking(
+            self.url_id,
+            self.click_info.ip_address,
+            self.click_info.user_agent,
+            self.click_info.referer,
+            self.click_info.country_code,
+        );
+        click.clicked_at = self.clicked_at;
+        click.click_token = self.click_info.click_token;
+        click.suspicious = self.click_info.suspicious;
+        click.set_utm(self.click_info.utm);
+        click
+    }
+}
+
+/// Longest accepted conversion goal name, in characters
+pub const MAX_GOAL_NAME_LENGTH: usize = 100;
+
+/// Longest accepted conversion goal URL pattern, in characters
+pub const MAX_GOAL_URL_PATTERN_LENGTH: usize = 2048;
+
+/// How long an analytics summary is served from cache before it is recomputed
+pub const ANALYTICS_SUMMARY_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Cached analytics summaries keyed by `analytics:summary:<url_id>:<include_bots>`
+type SummaryCache = HashMap<String, (Instant, UrlAnalyticsSummary)>;
+
+/// Longest accepted per-URL click deduplication window, in seconds
+pub const MAX_CLICK_DEDUP_WINDOW_SECONDS: u32 = 86_400;
+
+/// Window the click velocity is measured over when none is requested, in minutes
+pub const DEFAULT_CLICK_VELOCITY_WINDOW_MINUTES: u32 = 60;
+
+/// Longest accepted click velocity window, in minutes
+pub const MAX_CLICK_VELOCITY_WINDOW_MINUTES: u32 = 10_080;
+
+/// How long the batch writer reuses a URL's deduplication window before reloading it
+const URL_CONFIG_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Per-URL deduplication window overrides keyed by URL ID, with the time they were loaded
+type DedupWindowCache = HashMap<i32, (Instant, Option<i32>)>;
+
+/// Width of the buckets of a click timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimelineGranularity {
+    /// The last 24 hours
+    Hour,
+    /// The last 30 days
+    #[default]
+    Day,
+    /// The last 12 weeks, starting on Mondays
+    Week,
+    /// The last 12 months
+    Month,
+}
+
+impl TimelineGranularity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TimelineGranularity::Hour => "hour",
+            TimelineGranularity::Day => "day",
+            TimelineGranularity::Week => "week",
+            TimelineGranularity::Month => "month",
+        }
+    }
+
+    /// Number of buckets in a timeline, including the current one
+    pub fn bucket_count(self) -> usize {
+        match self {
+            TimelineGranularity::Hour => 24,
+            TimelineGranularity::Day => 30,
+            TimelineGranularity::Week | TimelineGranularity::Month => 12,
+        }
+    }
+
+    /// Start of the bucket containing `at`
+    pub fn bucket_start(self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let date = at.date_naive();
+        let midnight =
+            |date: chrono::NaiveDate| Utc.from_utc_datetime(&date.and_time(Default::default()));
+        match self {
+            TimelineGranularity::Hour => {
+                midnight(date) + ChronoDuration::hours(i64::from(at.hour()))
+            }
+            TimelineGranularity::Day => midnight(date),
+            TimelineGranularity::Week => {
+                midnight(date)
+                    - ChronoDuration::days(i64::from(date.weekday().num_days_from_monday()))
+            }
+            TimelineGranularity::Month => midnight(date.with_day(1).unwrap_or(date)),
+        }
+    }
+
+    /// Start of the bucket before the one starting at `start`
+    pub fn previous(self, start: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            TimelineGranularity::Hour => start - ChronoDuration::hours(1),
+            TimelineGranularity::Day => start - ChronoDuration::days(1),
+            TimelineGranularity::Week => start - ChronoDuration::weeks(1),
+            TimelineGranularity::Month => start.checked_sub_months(Months::new(1)).unwrap_or(start),
+        }
+    }
+
+    /// Start of the bucket after the one starting at `start`
+    pub fn next(self, start: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            TimelineGranularity::Hour => start + ChronoDuration::hours(1),
+            TimelineGranularity::Day => start + ChronoDuration::days(1),
+            TimelineGranularity::Week => start + ChronoDuration::weeks(1),
+            TimelineGranularity::Month => start.checked_add_months(Months::new(1)).unwrap_or(start),
+        }
+    }
+}
+
+/// Clicks counted in one bucket of a timeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClickTimelinePoint {
+    pub start: DateTime<Utc>,
+    pub clicks: i64,
+}
+
+/// When during the day and week a URL is clicked, and how fast it is clicked right now
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClickPatterns {
+    /// Clicks by hour of day (UTC), midnight first
+    pub hourly_distribution: [u32; 24],
+    /// Clicks by day of week (UTC), Sunday first
+    pub daily_distribution: [u32; 7],
+    pub peak_hour: u8,
+    /// Clicks per minute over the velocity window
+    pub click_velocity: f64,
+}
+
+/// Configuration for buffered click writes
+#[derive(Debug, Clone)]
+pub struct ClickTrackingConfig {
+    /// Maximum number of clicks waiting to be written before new ones are dropped
+    pub buffer_size: usize,
+    /// Maximum number of clicks written per INSERT
+    pub batch_size: usize,
+    /// How often a partial batch is flushed
+    pub flush_interval: Duration,
+    /// How long repeat clicks from the same IP are not recorded, unless the URL overrides it
+    pub dedup_window: Duration,
+    /// Record clicks at all; when off `record_click` does nothing
+    pub enabled: bool,
+}
+
+impl Default for ClickTrackingConfig {
+    fn default() -> Self {
+        Self {
+            buffer_size: 1000,
+            batch_size: 100,
+            flush_interval: Duration::from_millis(500),
+            dedup_window: Duration::from_secs(60),
+            enabled: true,
+        }
+    }
 }
 
 /// Click tracking service for recording and analyzing URL clicks
-/// Uses async processing to avoid blocking URL resolution
+///
+/// Clicks are pushed into a bounded buffer and written in batches by a background task,
+/// so recording a click never waits on the database.
 #[derive(Clone)]
 pub struct ClickTrackingService<R>
 where
     R: ClickRepository + Clone + Send + Sync + 'static,
 {
     repository: R,
-    sender: mpsc::UnboundedSender<ClickTrackingTask>,
+    sender: mpsc::Sender<ClickRecord>,
+    dropped_clicks: Arc<AtomicU64>,
+    writer: Arc<Mutex<Option<BatchWriterHandle>>>,
+    summary_cache: Arc<std::sync::Mutex<SummaryCache>>,
+    dedup_window: Duration,
+    dedup_windows: Arc<std::sync::Mutex<DedupWindowCache>>,
+    enabled: bool,
+    analytics_invalidator: Option<Arc<UrlAnalyticsCacheInvalidator>>,
+    event_bus: Option<Arc<dyn EventBus>>,
+    /// Shared with the batch writer, which is already running when it is set
+    notification_service: Arc<OnceLock<NotificationService>>,
+    /// Shared with the batch writer like `notification_service`
+    user_url_stats: Arc<OnceLock<Arc<dyn UserUrlStatsRepository>>>,
 }
 
-/// Internal task for async click processing
-#[derive(Debug)]
-enum ClickTrackingTask {
-    RecordClick {
-        url_id: i32,
-        click_info: ClickInfo,
-    },
-    GetStats {
-        url_id: i32,
-        response_sender: oneshot::Sender<Result<ClickStats, ClickTrackingError>>,
-    },
-    GetUserStats {
-        user_id: i32,
-        response_sender: oneshot::Sender<Result<ClickStats, ClickTrackingError>>,
-    },
+/// Handle used to stop the background batch writer
+struct BatchWriterHandle {
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<()>,
 }
 
 impl<R> ClickTrackingService<R>
 where
     R: ClickRepository + Clone + Send + Sync + 'static,
 {
-    /// Create a new click tracking service
+    /// Create a new click tracking service with the default configuration
     pub fn new(repository: R) -> Self {
-        let (sender, mut receiver) = mpsc::unbounded_channel();
-
-        // Spawn background task for processing click events
-        let repo_clone = repository.clone();
-        task::spawn(async move {
-            while let Some(task) = receiver.recv().await {
-                match task {
-                    ClickTrackingTask::RecordClick { url_id, click_info } => {
-                        let click = Click::new_for_tracking(
-                            url_id,
-                            click_info.ip_address,
-                            click_info.user_agent,
-                            click_info.referer,
-                            click_info.country_code,
-                        );
-
-                        if let Err(e) = repo_clone.record_click(&click).await {
-                            tracing::warn!("Failed to record click for URL {}: {}", url_id, e);
-                        }
-                    }
-                    ClickTrackingTask::GetStats {
-                        url_id,
-                        response_sender,
-                    } => {
-                        let result = repo_clone.get_url_click_stats(url_id).await;
-                        let _ = response_sender.send(result.map_err(ClickTrackingError::from));
-                    }
-                    ClickTrackingTask::GetUserStats {
-                        user_id,
-                        response_sender,
-                    } => {
-                        let result = repo_clone.get_user_click_stats(user_id).await;
-                        let _ = response_sender.send(result.map_err(ClickTrackingError::from));
-                    }
-                }
-            }
-        });
+        Self::with_config(repository, ClickTrackingConfig::default())
+    }
+
+    /// Create a new click tracking service with a custom configuration
+    ///
+    /// Without a deduplicator every click is recorded.
+    pub fn with_config(repository: R, config: ClickTrackingConfig) -> Self {
+        Self::with_deduplicator(repository, config, None)
+    }
+
+    /// Create a click tracking service that skips repeat clicks marked in `deduplicator`
+    pub fn with_deduplicator(
+        repository: R,
+        config: ClickTrackingConfig,
+        deduplicator: Option<Arc<dyn ClickDeduplicator>>,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel(config.buffer_size.max(1));
+        let (shutdown, shutdown_receiver) = oneshot::channel();
+        let dedup_window = config.dedup_window;
+        let enabled = config.enabled;
+        let dedup_windows = Arc::new(std::sync::Mutex::new(HashMap::new()));
+        let notification_service = Arc::new(OnceLock::new());
+        let user_url_stats = Arc::new(OnceLock::new());
+
+        // Spawn background task that writes buffered clicks in batches
+        let task = task::spawn(run_batch_writer(
+            repository.clone(),
+            receiver,
+            shutdown_receiver,
+            config,
+            ClickDeduplication {
+                deduplicator,
+                default_window: dedup_window,
+                url_windows: dedup_windows.clone(),
+            },
+            notification_service.clone(),
+            user_url_stats.clone(),
+        ));
 
-        Self { repository, sender }
+        Self {
+            repository,
+            sender,
+            dropped_clicks: Arc::new(AtomicU64::new(0)),
+            writer: Arc::new(Mutex::new(Some(BatchWriterHandle { shutdown, task }))),
+            summary_cache: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            dedup_window,
+            dedup_windows,
+            enabled,
+            analytics_invalidator: None,
+            event_bus: None,
+            notification_service,
+            user_url_stats,
+        }
+    }
+
+    /// Notify URL owners when their URL reaches one of the `CLICK_MILESTONES`
+    ///
+    /// Only the first notification service given to a tracker is used.
+    pub fn with_notification_service(self, notification_service: NotificationService) -> Self {
+        let _ = self.notification_service.set(notification_service);
+        self
+    }
+
+    /// Add recorded clicks to the `total_clicks` counter of their URLs' owners
+    ///
+    /// Only the first stats repository given to a tracker is used.
+    pub fn with_user_url_stats(self, user_url_stats: Arc<dyn UserUrlStatsRepository>) -> Self {
+        let _ = self.user_url_stats.set(user_url_stats);
+        self
+    }
+
+    /// Drop a URL's cached analytics when it is clicked
+    pub fn with_analytics_invalidator(
+        mut self,
+        analytics_invalidator: Arc<UrlAnalyticsCacheInvalidator>,
+    ) -> Self {
+        self.analytics_invalidator = Some(analytics_invalidator);
+        self
+    }
+
+    /// Publish [`DomainEvent::UrlClicked`] on `event_bus` for every click accepted
+    ///
+    /// Events are published before deduplication, so repeat clicks are included.
+    pub fn with_event_bus(mut self, event_bus: Arc<dyn EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
     }
 
-    /// Record a click event asynchronously (non-blocking)
+    /// Record a click event without waiting for it to be written
+    ///
+    /// The batch writer counts the click in the URL's `deduplicated_click_count`. Unless the
+    /// same IP clicked the URL within its deduplication window, it then stores the click, adds
+    /// it to the owner's `total_clicks` and folds its IP into the URL's daily unique visitor
+    /// sketch. If the buffer is full the click is dropped and counted in
+    /// `dropped_clicks_total`. Nothing is recorded while click tracking is disabled.
+    #[tracing::instrument(skip(self, click_info))]
     pub fn record_click(
         &self,
         url_id: i32,
         click_info: ClickInfo,
     ) -> Result<(), ClickTrackingError> {
-        self.sender
-            .send(ClickTrackingTask::RecordClick { url_id, click_info })
-            .map_err(|_| ClickTrackingError::ServiceUnavailable)?;
-        Ok(())
+        if !self.enabled {
+            return Ok(());
+        }
+        tracing::debug!("Recording click");
+        let record = ClickRecord::new(url_id, click_info);
+        let click = self.event_bus.as_ref().map(|_| record.clone().into_click());
+        match self.sender.try_send(record) {
+            Ok(()) => {
+                if let Some(invalidator) = &self.analytics_invalidator {
+                    invalidator.click_recorded(url_id);
+                }
+                if let (Some(event_bus), Some(click)) = (&self.event_bus, click) {
+                    if let Err(e) = event_bus.publish(DomainEvent::UrlClicked { click }) {
+                        tracing::warn!("Failed to publish click on URL {}: {}", url_id, e);
+                    }
+                }
+                Ok(())
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                let dropped = self.dropped_clicks.fetch_add(1, Ordering::Relaxed) + 1;
+                tracing::warn!(
+                    "Click buffer full, dropped click for URL {} ({} dropped in total)",
+                    url_id,
+                    dropped
+                );
+                Err(ClickTrackingError::BufferFull)
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                Err(ClickTrackingError::ServiceUnavailable)
+            }
+        }
+    }
+
+    /// Number of clicks dropped because the buffer was full
+    pub fn dropped_clicks_total(&self) -> u64 {
+        self.dropped_clicks.load(Ordering::Relaxed)
+    }
+
+    /// Stop accepting clicks and write everything still buffered
+    ///
+    /// Safe to call more than once; later calls return immediately.
+    pub async fn shutdown(&self) {
+        let handle = self.writer.lock().await.take();
+        if let Some(BatchWriterHandle { shutdown, task }) = handle {
+            let _ = shutdown.send(());
+            if let Err(e) = task.await {
+                tracing::warn!("Click batch writer stopped abnormally: {}", e);
+            }
+        }
     }
 
     /// Get click statistics for a URL
     pub async fn get_url_stats(&self, url_id: i32) -> Result<ClickStats, ClickTrackingError> {
-        let (response_sender, response_receiver) = oneshot::channel();
+        self.repository
+            .get_url_click_stats(url_id)
+            .await
+            .map_err(ClickTrackingError::from)
+    }
 
-        self.sender
-            .send(ClickTrackingTask::GetStats {
-                url_id,
-                response_sender,
-            })
-            .map_err(|_| ClickTrackingError::ServiceUnavailable)?;
+    /// Get the raw, unique and bot clicks of a URL
+    pub async fn get_click_dedup_ratio(
+        &self,
+        url_id: i32,
+    ) -> Result<ClickDedupRatio, ClickTrackingError> {
+        self.repository
+            .get_click_dedup_ratio(url_id)
+            .await
+            .map_err(ClickTrackingError::from)
+    }
 
-        response_receiver
+    /// Get the clicks of a URL by UTM source, medium and campaign
+    pub async fn get_utm_attribution_breakdown(
+        &self,
+        url_id: i32,
+    ) -> Result<Vec<UtmAttributionRow>, ClickTrackingError> {
+        self.repository
+            .get_utm_attribution_breakdown(url_id)
             .await
-            .map_err(|_| ClickTrackingError::ServiceUnavailable)?
+            .map_err(ClickTrackingError::from)
     }
 
-    /// Get click statistics for a user
-    pub async fn get_user_stats(&self, user_id: i32) -> Result<ClickStats, ClickTrackingError> {
-        let (response_sender, response_receiver) = oneshot::channel();
+    /// Deduplication window of URLs without an override
+    pub fn default_click_dedup_window(&self) -> Duration {
+        self.dedup_window
+    }
 
-        self.sender
-            .send(ClickTrackingTask::GetUserStats {
-                user_id,
-                response_sender,
-            })
-            .map_err(|_| ClickTrackingError::ServiceUnavailable)?;
+    /// Get the click tracking settings of a URL, with no overrides if it has none stored
+    pub async fn get_url_config(&self, url_id: i32) -> Result<UrlConfig, ClickTrackingError> {
+        Ok(self
+            .repository
+            .find_url_config(url_id)
+            .await?
+            .unwrap_or_else(|| UrlConfig::default_for(url_id)))
+    }
+
+    /// Override the deduplication window of a URL; `None` restores the default
+    ///
+    /// The batch writer picks the new window up immediately on this instance and within
+    /// `URL_CONFIG_CACHE_TTL` on the others.
+    pub async fn set_click_dedup_window(
+        &self,
+        url_id: i32,
+        window_seconds: Option<u32>,
+    ) -> Result<UrlConfig, ClickTrackingError> {
+        if window_seconds.is_some_and(|seconds| seconds > MAX_CLICK_DEDUP_WINDOW_SECONDS) {
+            return Err(ClickTrackingError::InvalidData(format!(
+                "Click deduplication window must be at most {} seconds",
+                MAX_CLICK_DEDUP_WINDOW_SECONDS
+            )));
+        }
 
-        response_receiver
+        let config = UrlConfig {
+            click_dedup_window_seconds: window_seconds.map(|seconds| seconds as i32),
+            ..self.get_url_config(url_id).await?
+        };
+        let saved = self.repository.save_url_config(&config).await?;
+        self.dedup_windows.lock().unwrap().remove(&url_id);
+        Ok(saved)
+    }
+
+    /// Get click statistics for a user
+    pub async fn get_user_stats(&self, user_id: i32) -> Result<ClickStats, ClickTrackingError> {
+        self.repository
+            .get_user_click_stats(user_id)
             .await
-            .map_err(|_| ClickTrackingError::ServiceUnavailable)?
+            .map_err(ClickTrackingError::from)
     }
 
     /// Get click count for a URL (synchronous, for response enhancement)
@@ -140,6 +490,203 @@ where
             .map_err(ClickTrackingError::from)
     }
 
+    /// Get the approximate number of unique visitors of a URL between two dates (inclusive)
+    pub async fn get_unique_visitors_estimate(
+        &self,
+        url_id: i32,
+        start: chrono::NaiveDate,
+        end: chrono::NaiveDate,
+    ) -> Result<i64, ClickTrackingError> {
+        self.repository
+            .get_unique_visitors_estimate(url_id, start, end)
+            .await
+            .map_err(ClickTrackingError::from)
+    }
+
+    /// Get the analytics summary of a URL
+    ///
+    /// The aggregation is expensive, so results are cached for
+    /// `ANALYTICS_SUMMARY_CACHE_TTL` per URL and bot setting.
+    pub async fn get_url_analytics_summary(
+        &self,
+        url_id: i32,
+        include_bots: bool,
+    ) -> Result<UrlAnalyticsSummary, ClickTrackingError> {
+        let key = format!("analytics:summary:{}:{}", url_id, include_bots);
+        if let Some((cached_at, summary)) = self.summary_cache.lock().unwrap().get(&key) {
+            if cached_at.elapsed() < ANALYTICS_SUMMARY_CACHE_TTL {
+                return Ok(summary.clone());
+            }
+        }
+
+        let summary = self
+            .repository
+            .get_url_analytics_summary(url_id, include_bots)
+            .await
+            .map_err(ClickTrackingError::from)?;
+
+        let mut cache = self.summary_cache.lock().unwrap();
+        cache.retain(|_, (cached_at, _)| cached_at.elapsed() < ANALYTICS_SUMMARY_CACHE_TTL);
+        cache.insert(key, (Instant::now(), summary.clone()));
+        Ok(summary)
+    }
+
+    /// Count a URL's clicks per hour, day, week or month, oldest bucket first
+    ///
+    /// Covers the buckets up to and including the current one; empty buckets are kept so
+    /// clients can plot the result directly.
+    pub async fn get_click_timeline(
+        &self,
+        url_id: i32,
+        granularity: TimelineGranularity,
+        include_bots: bool,
+    ) -> Result<Vec<ClickTimelinePoint>, ClickTrackingError> {
+        let mut starts = vec![granularity.bucket_start(Utc::now())];
+        while starts.len() < granularity.bucket_count() {
+            let earliest = *starts.last().expect("starts is never empty");
+            starts.push(granularity.previous(earliest));
+        }
+        starts.reverse();
+
+        let clicks = self
+            .repository
+            .get_clicks_for_url(url_id, Some(starts[0]), None)
+            .await?;
+
+        let mut timeline: Vec<ClickTimelinePoint> = starts
+            .into_iter()
+            .map(|start| ClickTimelinePoint { start, clicks: 0 })
+            .collect();
+        for click in clicks {
+            if !include_bots && click.device_type() == DeviceType::Bot {
+                continue;
+            }
+            let start = granularity.bucket_start(click.clicked_at);
+            if let Ok(index) = timeline.binary_search_by_key(&start, |point| point.start) {
+                timeline[index].clicks += 1;
+            }
+        }
+        Ok(timeline)
+    }
+
+    /// Get the hourly and daily click distributions, peak hour and click velocity of a URL
+    ///
+    /// The velocity is measured over the last `window_minutes`, between 1 and
+    /// `MAX_CLICK_VELOCITY_WINDOW_MINUTES`.
+    pub async fn get_click_patterns(
+        &self,
+        url_id: i32,
+        window_minutes: u32,
+    ) -> Result<ClickPatterns, ClickTrackingError> {
+        if !(1..=MAX_CLICK_VELOCITY_WINDOW_MINUTES).contains(&window_minutes) {
+            return Err(ClickTrackingError::InvalidData(format!(
+                "Click velocity window must be between 1 and {} minutes",
+                MAX_CLICK_VELOCITY_WINDOW_MINUTES
+            )));
+        }
+
+        let (hourly_distribution, daily_distribution, peak_hour, click_velocity) = tokio::join!(
+            self.repository.get_hourly_distribution(url_id),
+            self.repository.get_daily_distribution(url_id),
+            self.repository.get_peak_hour(url_id),
+            self.repository.get_click_velocity(url_id, window_minutes),
+        );
+        Ok(ClickPatterns {
+            hourly_distribution: hourly_distribution?,
+            daily_distribution: daily_distribution?,
+            peak_hour: peak_hour?,
+            click_velocity: click_velocity?,
+        })
+    }
+
+    /// Create a conversion goal for a URL
+    ///
+    /// The pattern must be an http(s) URL; `*` matches any run of characters.
+    pub async fn create_conversion_goal(
+        &self,
+        url_id: i32,
+        goal_url_pattern: &str,
+        name: &str,
+    ) -> Result<ConversionGoal, ClickTrackingError> {
+        let goal_url_pattern = goal_url_pattern.trim();
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_GOAL_NAME_LENGTH {
+            return Err(ClickTrackingError::InvalidData(format!(
+                "Goal name must be between 1 and {} characters",
+                MAX_GOAL_NAME_LENGTH
+            )));
+        }
+        if !(goal_url_pattern.starts_with("http://") || goal_url_pattern.starts_with("https://"))
+            || goal_url_pattern.chars().count() > MAX_GOAL_URL_PATTERN_LENGTH
+        {
+            return Err(ClickTrackingError::InvalidData(format!(
+                "Goal URL pattern must start with http:// or https:// and be at most {} characters",
+                MAX_GOAL_URL_PATTERN_LENGTH
+            )));
+        }
+
+        self.repository
+            .create_conversion_goal(url_id, goal_url_pattern, name)
+            .await
+            .map_err(ClickTrackingError::from)
+    }
+
+    /// Get a conversion goal by ID
+    pub async fn get_conversion_goal(
+        &self,
+        goal_id: i32,
+    ) -> Result<Option<ConversionGoal>, ClickTrackingError> {
+        self.repository
+            .find_conversion_goal(goal_id)
+            .await
+            .map_err(ClickTrackingError::from)
+    }
+
+    /// Delete a conversion goal together with its recorded conversions
+    pub async fn delete_conversion_goal(&self, goal_id: i32) -> Result<(), ClickTrackingError> {
+        match self.repository.delete_conversion_goal(goal_id).await? {
+            true => Ok(()),
+            false => Err(ClickTrackingError::GoalNotFound),
+        }
+    }
+
+    /// Record a conversion reported by the goal page for the visitor's click cookie
+    ///
+    /// When the page URL is known it must match the goal pattern. The click must belong to
+    /// the goal's URL; clicks still in the write buffer are not found yet.
+    pub async fn report_conversion(
+        &self,
+        click_token: &str,
+        goal_id: i32,
+        page_url: Option<&str>,
+    ) -> Result<ConversionEvent, ClickTrackingError> {
+        let goal = self
+            .repository
+            .find_conversion_goal(goal_id)
+            .await?
+            .ok_or(ClickTrackingError::GoalNotFound)?;
+        if let Some(page_url) = page_url {
+            if !goal.matches_url(page_url) {
+                return Err(ClickTrackingError::InvalidData(
+                    "Page does not match the goal URL pattern".to_string(),
+                ));
+            }
+        }
+
+        self.repository
+            .record_conversion(click_token, goal.id)
+            .await?
+            .ok_or(ClickTrackingError::UnknownClick)
+    }
+
+    /// Get the share of a URL's clicks that led to at least one conversion
+    pub async fn get_conversion_rate(&self, url_id: i32) -> Result<f64, ClickTrackingError> {
+        self.repository
+            .get_conversion_rate(url_id)
+            .await
+            .map_err(ClickTrackingError::from)
+    }
+
     /// Get clicks for a URL within a time range
     pub async fn get_clicks_for_url(
         &self,
@@ -167,6 +714,292 @@ where
     }
 }
 
+/// Decides which clicks repeat an earlier click from the same IP
+struct ClickDeduplication {
+    deduplicator: Option<Arc<dyn ClickDeduplicator>>,
+    default_window: Duration,
+    url_windows: Arc<std::sync::Mutex<DedupWindowCache>>,
+}
+
+impl ClickDeduplication {
+    /// Whether the click repeats one within its URL's window
+    ///
+    /// Clicks without an IP, or that cannot be checked, are not repeats.
+    async fn is_repeat<R>(&self, repository: &R, record: &ClickRecord) -> bool
+    where
+        R: ClickRepository,
+    {
+        let Some(deduplicator) = &self.deduplicator else {
+            return false;
+        };
+        let Some(ip_address) = record.click_info.ip_address.as_deref() else {
+            return false;
+        };
+        let window = self.window_for(repository, record.url_id).await;
+        if window.is_zero() {
+            return false;
+        }
+
+        match deduplicator
+            .first_click(ip_address, record.url_id, window)
+            .await
+        {
+            Ok(first) => !first,
+            Err(e) => {
+                tracing::warn!(
+                    "Click deduplication unavailable, recording click for URL {}: {}",
+                    record.url_id,
+                    e
+                );
+                false
+            }
+        }
+    }
+
+    /// Deduplication window of a URL, reloaded at most every `URL_CONFIG_CACHE_TTL`
+    async fn window_for<R>(&self, repository: &R, url_id: i32) -> Duration
+    where
+        R: ClickRepository,
+    {
+        let cached = self
+            .url_windows
+            .lock()
+            .unwrap()
+            .get(&url_id)
+            .filter(|(loaded_at, _)| loaded_at.elapsed() < URL_CONFIG_CACHE_TTL)
+            .map(|(_, seconds)| *seconds);
+        let seconds = match cached {
+            Some(seconds) => seconds,
+            None => match repository.find_url_config(url_id).await {
+                Ok(config) => {
+                    let seconds = config.and_then(|config| config.click_dedup_window_seconds);
+                    let mut url_windows = self.url_windows.lock().unwrap();
+                    url_windows
+                        .retain(|_, (loaded_at, _)| loaded_at.elapsed() < URL_CONFIG_CACHE_TTL);
+                    url_windows.insert(url_id, (Instant::now(), seconds));
+                    seconds
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to load settings of URL {}: {}", url_id, e);
+                    None
+                }
+            },
+        };
+        seconds.map_or(self.default_window, |seconds| {
+            Duration::from_secs(seconds.max(0) as u64)
+        })
+    }
+}
+
+/// Clicks and raw click counts waiting to be written
+#[derive(Default)]
+struct PendingWrites {
+    clicks: Vec<Click>,
+    /// Clicks seen per URL, whether they are recorded or not
+    seen: HashMap<i32, i64>,
+}
+
+impl PendingWrites {
+    fn is_empty(&self) -> bool {
+        self.clicks.is_empty() && self.seen.is_empty()
+    }
+}
+
+/// Drain the click buffer, writing a batch whenever it is full or the flush interval elapses
+async fn run_batch_writer<R>(
+    repository: R,
+    mut receiver: mpsc::Receiver<ClickRecord>,
+    mut shutdown: oneshot::Receiver<()>,
+    config: ClickTrackingConfig,
+    deduplication: ClickDeduplication,
+    notification_service: Arc<OnceLock<NotificationService>>,
+    user_url_stats: Arc<OnceLock<Arc<dyn UserUrlStatsRepository>>>,
+) where
+    R: ClickRepository,
+{
+    let batch_size = config.batch_size.max(1);
+    let mut pending = PendingWrites::default();
+    let mut ticker = interval(config.flush_interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            record = receiver.recv() => match record {
+                Some(record) => {
+                    buffer_record(&repository, &deduplication, &mut pending, record).await;
+                    if pending.clicks.len() >= batch_size {
+                        flush_batch(
+                            &repository,
+                            &mut pending,
+                            &notification_service,
+                            &user_url_stats,
+                        )
+                        .await;
+                    }
+                }
+                // Every sender is gone; nothing more can arrive
+                None => break,
+            },
+            _ = ticker.tick() => {
+                if !pending.is_empty() {
+                    flush_batch(
+                        &repository,
+                        &mut pending,
+                        &notification_service,
+                        &user_url_stats,
+                    )
+                    .await;
+                }
+            }
+            _ = &mut shutdown => {
+                // Stop accepting new clicks, then drain what is already buffered
+                receiver.close();
+                while let Some(record) = receiver.recv().await {
+                    buffer_record(&repository, &deduplication, &mut pending, record).await;
+                    if pending.clicks.len() >= batch_size {
+                        flush_batch(
+                            &repository,
+                            &mut pending,
+                            &notification_service,
+                            &user_url_stats,
+                        )
+                        .await;
+                    }
+                }
+                break;
+            }
+        }
+    }
+
+    if !pending.is_empty() {
+        flush_batch(
+            &repository,
+            &mut pending,
+            &notification_service,
+            &user_url_stats,
+        )
+        .await;
+    }
+}
+
+/// Count a click as seen and queue it for insertion unless it is a repeat
+async fn buffer_record<R>(
+    repository: &R,
+    deduplication: &ClickDeduplication,
+    pending: &mut PendingWrites,
+    record: ClickRecord,
+) where
+    R: ClickRepository,
+{
+    *pending.seen.entry(record.url_id).or_default() += 1;
+    if !deduplication.is_repeat(repository, &record).await {
+        pending.clicks.push(record.into_click());
+    }
+}
+
+/// Write pending clicks with a single insert, add the seen clicks to the raw counters and
+/// clear both
+///
+/// The recorded clicks are added to their owners' `total_clicks`. Owners of URLs whose raw
+/// count passed a click milestone are notified in the background.
+async fn flush_batch<R>(
+    repository: &R,
+    pending: &mut PendingWrites,
+    notification_service: &OnceLock<NotificationService>,
+    user_url_stats: &OnceLock<Arc<dyn UserUrlStatsRepository>>,
+) where
+    R: ClickRepository,
+{
+    let mut recorded: HashMap<i32, i64> = HashMap::new();
+    if !pending.clicks.is_empty() {
+        match repository.record_clicks(&pending.clicks).await {
+            Ok(_) => {
+                for click in &pending.clicks {
+                    *recorded.entry(click.url_id).or_default() += 1;
+                }
+            }
+            Err(e) => tracing::warn!(
+                "Failed to record batch of {} clicks: {}",
+                pending.clicks.len(),
+                e
+            ),
+        }
+        pending.clicks.clear();
+    }
+
+    let counts: Vec<(i32, i64)> = pending.seen.drain().collect();
+    match repository
+        .increment_deduplicated_click_counts(&counts)
+        .await
+    {
+        Ok(totals) => {
+            if let Some(user_url_stats) = user_url_stats.get() {
+                count_user_clicks(user_url_stats.as_ref(), &recorded, &totals).await;
+            }
+            if let Some(notification_service) = notification_service.get() {
+                notify_click_milestones(notification_service, &counts, totals);
+            }
+        }
+        Err(e) => tracing::warn!("Failed to count clicks of {} URLs: {}", counts.len(), e),
+    }
+}
+
+/// Add the clicks recorded per URL to the `total_clicks` of the URLs' owners
+///
+/// `totals` tells who owns each URL; clicks on anonymous URLs are not counted for anyone.
+async fn count_user_clicks(
+    user_url_stats: &dyn UserUrlStatsRepository,
+    recorded: &HashMap<i32, i64>,
+    totals: &[ClickCountTotal],
+) {
+    let mut per_user: HashMap<i32, i64> = HashMap::new();
+    for total in totals {
+        if let (Some(user_id), Some(clicks)) = (total.user_id, recorded.get(&total.url_id)) {
+            *per_user.entry(user_id).or_default() += clicks;
+        }
+    }
+    if per_user.is_empty() {
+        return;
+    }
+    let per_user: Vec<(i32, i64)> = per_user.into_iter().collect();
+    if let Err(e) = user_url_stats.increment_clicks(&per_user).await {
+        tracing::warn!("Failed to count clicks of {} users: {}", per_user.len(), e);
+    }
+}
+
+/// Notify the owner of every URL whose count passed a milestone with the clicks just added
+fn notify_click_milestones(
+    notification_service: &NotificationService,
+    counts: &[(i32, i64)],
+    totals: Vec<ClickCountTotal>,
+) {
+    let added: HashMap<i32, i64> = counts.iter().copied().collect();
+    for total in totals {
+        let Some(user_id) = total.user_id else {
+            continue;
+        };
+        let added = added.get(&total.url_id).copied().unwrap_or_default();
+        for milestone in crossed_click_milestones(total.total, added) {
+            let notification_service = notification_service.clone();
+            let short_code = total.short_code.clone();
+            task::spawn(async move {
+                if let Err(e) = notification_service
+                    .notify_click_milestone(user_id, total.url_id, &short_code, milestone)
+                    .await
+                {
+                    tracing::warn!(
+                        "Failed to notify user {} of {} clicks on URL {}: {}",
+                        user_id,
+                        milestone,
+                        total.url_id,
+                        e
+                    );
+                }
+            });
+        }
+    }
+}
+
 /// Click tracking service errors
 #[derive(Debug, thiserror::Error)]
 pub enum ClickTrackingError {
@@ -176,6 +1009,15 @@ pub enum ClickTrackingError {
     #[error("Service unavailable")]
     ServiceUnavailable,
 
+    #[error("Click buffer is full")]
+    BufferFull,
+
+    #[error("Conversion goal not found")]
+    GoalNotFound,
+
+    #[error("No click of this URL matches the click cookie")]
+    UnknownClick,
+
     #[error("Invalid data: {0}")]
     InvalidData(String),
 }
@@ -183,19 +1025,42 @@ pub enum ClickTrackingError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::repositories::click_repository::DeviceBreakdown;
     use crate::domain::repositories::ClickRepository;
     use std::sync::{Arc, Mutex};
 
+    /// Owner of every URL in the mock repository
+    const URL_OWNER_ID: i32 = 1;
+
     // Mock repository for testing
     #[derive(Clone)]
     struct MockClickRepository {
         clicks: Arc<Mutex<Vec<Click>>>,
+        batch_sizes: Arc<Mutex<Vec<usize>>>,
+        summary_queries: Arc<Mutex<usize>>,
+        goals: Arc<Mutex<Vec<ConversionGoal>>>,
+        conversions: Arc<Mutex<Vec<ConversionEvent>>>,
+        deduplicated_click_counts: Arc<Mutex<HashMap<i32, i64>>>,
+        url_configs: Arc<Mutex<HashMap<i32, UrlConfig>>>,
+        write_latency: Duration,
     }
 
     impl MockClickRepository {
         fn new() -> Self {
+            Self::with_write_latency(Duration::ZERO)
+        }
+
+        /// Simulate the round-trip cost of a database write
+        fn with_write_latency(write_latency: Duration) -> Self {
             Self {
                 clicks: Arc::new(Mutex::new(Vec::new())),
+                batch_sizes: Arc::new(Mutex::new(Vec::new())),
+                summary_queries: Arc::new(Mutex::new(0)),
+                goals: Arc::new(Mutex::new(Vec::new())),
+                conversions: Arc::new(Mutex::new(Vec::new())),
+                deduplicated_click_counts: Arc::new(Mutex::new(HashMap::new())),
+                url_configs: Arc::new(Mutex::new(HashMap::new())),
+                write_latency,
             }
         }
     }
@@ -203,6 +1068,7 @@ mod tests {
     #[async_trait::async_trait]
     impl ClickRepository for MockClickRepository {
         async fn record_click(&self, click: &Click) -> Result<Click, ClickRepositoryError> {
+            tokio::time::sleep(self.write_latency).await;
             let mut clicks = self.clicks.lock().unwrap();
             let mut new_click = click.clone();
             new_click.id = (clicks.len() + 1) as i32;
@@ -210,7 +1076,35 @@ mod tests {
             Ok(new_click)
         }
 
-        async fn get_click_count(&self, url_id: i32) -> Result<i64, ClickRepositoryError> {
+        async fn record_clicks(&self, batch: &[Click]) -> Result<u64, ClickRepositoryError> {
+            tokio::time::sleep(self.write_latency).await;
+            self.batch_sizes.lock().unwrap().push(batch.len());
+            let mut clicks = self.clicks.lock().unwrap();
+            clicks.extend(batch.iter().cloned());
+            Ok(batch.len() as u64)
+        }
+
+        async fn increment_deduplicated_click_counts(
+            &self,
+            counts: &[(i32, i64)],
+        ) -> Result<Vec<ClickCountTotal>, ClickRepositoryError> {
+            let mut totals = self.deduplicated_click_counts.lock().unwrap();
+            Ok(counts
+                .iter()
+                .map(|(url_id, clicks)| {
+                    let total = totals.entry(*url_id).or_default();
+                    *total += clicks;
+                    ClickCountTotal {
+                        url_id: *url_id,
+                        user_id: Some(URL_OWNER_ID),
+                        short_code: format!("code{}", url_id),
+                        total: *total,
+                    }
+                })
+                .collect())
+        }
+
+        async fn get_click_count(&self, url_id: i32) -> Result<i64, ClickRepositoryError> {
             let clicks = self.clicks.lock().unwrap();
             Ok(clicks.iter().filter(|c| c.url_id == url_id).count() as i64)
         }
@@ -238,6 +1132,17 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn get_clicks_for_user_page(
+            &self,
+            _user_id: i32,
+            _start_date: Option<chrono::DateTime<chrono::Utc>>,
+            _end_date: Option<chrono::DateTime<chrono::Utc>>,
+            _after_id: Option<i32>,
+            _limit: i64,
+        ) -> Result<Vec<Click>, ClickRepositoryError> {
+            Ok(vec![])
+        }
+
         async fn get_url_click_stats(
             &self,
             url_id: i32,
@@ -271,12 +1176,285 @@ mod tests {
             })
         }
 
+        async fn get_unique_visitors_estimate(
+            &self,
+            url_id: i32,
+            start: chrono::NaiveDate,
+            end: chrono::NaiveDate,
+        ) -> Result<i64, ClickRepositoryError> {
+            let clicks = self.clicks.lock().unwrap();
+            let visitors: std::collections::HashSet<_> = clicks
+                .iter()
+                .filter(|c| c.url_id == url_id)
+                .filter(|c| (start..=end).contains(&c.clicked_at.date_naive()))
+                .filter_map(|c| c.ip_address.as_deref())
+                .collect();
+            Ok(visitors.len() as i64)
+        }
+
+        async fn get_url_analytics_summary(
+            &self,
+            url_id: i32,
+            include_bots: bool,
+        ) -> Result<UrlAnalyticsSummary, ClickRepositoryError> {
+            *self.summary_queries.lock().unwrap() += 1;
+            let clicks = self.clicks.lock().unwrap();
+            let url_clicks: Vec<_> = clicks
+                .iter()
+                .filter(|c| c.url_id == url_id)
+                .filter(|c| include_bots || c.device_type() != DeviceType::Bot)
+                .collect();
+
+            let mut device_breakdown = DeviceBreakdown::default();
+            for click in &url_clicks {
+                match click.device_type() {
+                    DeviceType::Desktop => device_breakdown.desktop += 1,
+                    DeviceType::Mobile => device_breakdown.mobile += 1,
+                    DeviceType::Tablet => device_breakdown.tablet += 1,
+                    DeviceType::Bot => device_breakdown.bot += 1,
+                }
+            }
+            let visitors: std::collections::HashSet<_> = url_clicks
+                .iter()
+                .filter_map(|c| c.ip_address.as_deref())
+                .collect();
+
+            Ok(UrlAnalyticsSummary {
+                total_clicks: url_clicks.len() as i64,
+                unique_visitors: visitors.len() as i64,
+                clicks_today: url_clicks.len() as i64,
+                clicks_this_week: url_clicks.len() as i64,
+                clicks_this_month: url_clicks.len() as i64,
+                top_countries: vec![],
+                top_referrers: vec![],
+                device_breakdown,
+                conversion_rate: 0.0,
+                recent_clicks: url_clicks.iter().rev().take(10).cloned().cloned().collect(),
+            })
+        }
+
+        async fn create_conversion_goal(
+            &self,
+            url_id: i32,
+            goal_url_pattern: &str,
+            name: &str,
+        ) -> Result<ConversionGoal, ClickRepositoryError> {
+            let mut goals = self.goals.lock().unwrap();
+            let goal = ConversionGoal {
+                id: goals.len() as i32 + 1,
+                url_id,
+                goal_url_pattern: goal_url_pattern.to_string(),
+                name: name.to_string(),
+                created_at: Utc::now(),
+            };
+            goals.push(goal.clone());
+            Ok(goal)
+        }
+
+        async fn find_conversion_goal(
+            &self,
+            id: i32,
+        ) -> Result<Option<ConversionGoal>, ClickRepositoryError> {
+            let goals = self.goals.lock().unwrap();
+            Ok(goals.iter().find(|g| g.id == id).cloned())
+        }
+
+        async fn delete_conversion_goal(&self, id: i32) -> Result<bool, ClickRepositoryError> {
+            let mut goals = self.goals.lock().unwrap();
+            let before = goals.len();
+            goals.retain(|g| g.id != id);
+            self.conversions.lock().unwrap().retain(|c| c.goal_id != id);
+            Ok(goals.len() < before)
+        }
+
+        async fn record_conversion(
+            &self,
+            click_token: &str,
+            goal_id: i32,
+        ) -> Result<Option<ConversionEvent>, ClickRepositoryError> {
+            let Some(goal) = self.find_conversion_goal(goal_id).await? else {
+                return Ok(None);
+            };
+            let clicks = self.clicks.lock().unwrap();
+            let Some(click) = clicks
+                .iter()
+                .find(|c| c.url_id == goal.url_id && c.click_token.as_deref() == Some(click_token))
+            else {
+                return Ok(None);
+            };
+
+            let mut conversions = self.conversions.lock().unwrap();
+            if let Some(existing) = conversions
+                .iter()
+                .find(|c| c.click_id == Some(click.id) && c.goal_id == goal_id)
+            {
+                return Ok(Some(existing.clone()));
+            }
+            let event = ConversionEvent {
+                id: conversions.len() as i32 + 1,
+                click_id: Some(click.id),
+                goal_id,
+                converted_at: Utc::now(),
+            };
+            conversions.push(event.clone());
+            Ok(Some(event))
+        }
+
+        async fn get_conversion_rate(&self, url_id: i32) -> Result<f64, ClickRepositoryError> {
+            let clicks = self.clicks.lock().unwrap();
+            let conversions = self.conversions.lock().unwrap();
+            let url_clicks: Vec<_> = clicks.iter().filter(|c| c.url_id == url_id).collect();
+            if url_clicks.is_empty() {
+                return Ok(0.0);
+            }
+            let converted = url_clicks
+                .iter()
+                .filter(|click| conversions.iter().any(|c| c.click_id == Some(click.id)))
+                .count();
+            Ok(converted as f64 / url_clicks.len() as f64)
+        }
+
+        async fn get_click_dedup_ratio(
+            &self,
+            url_id: i32,
+        ) -> Result<ClickDedupRatio, ClickRepositoryError> {
+            let clicks = self.clicks.lock().unwrap();
+            let url_clicks: Vec<_> = clicks.iter().filter(|c| c.url_id == url_id).collect();
+            let bot_clicks = url_clicks
+                .iter()
+                .filter(|c| c.device_type() == DeviceType::Bot)
+                .count() as i64;
+            Ok(ClickDedupRatio {
+                raw_clicks: self
+                    .deduplicated_click_counts
+                    .lock()
+                    .unwrap()
+                    .get(&url_id)
+                    .copied()
+                    .unwrap_or_default(),
+                unique_clicks: url_clicks.len() as i64 - bot_clicks,
+                bot_clicks,
+            })
+        }
+
+        async fn find_url_config(
+            &self,
+            url_id: i32,
+        ) -> Result<Option<UrlConfig>, ClickRepositoryError> {
+            Ok(self.url_configs.lock().unwrap().get(&url_id).cloned())
+        }
+
+        async fn save_url_config(
+            &self,
+            config: &UrlConfig,
+        ) -> Result<UrlConfig, ClickRepositoryError> {
+            self.url_configs
+                .lock()
+                .unwrap()
+                .insert(config.url_id, config.clone());
+            Ok(config.clone())
+        }
+
+        async fn get_hourly_distribution(
+            &self,
+            url_id: i32,
+        ) -> Result<[u32; 24], ClickRepositoryError> {
+            let mut distribution = [0u32; 24];
+            for click in self.clicks.lock().unwrap().iter() {
+                if click.url_id == url_id {
+                    distribution[click.clicked_at.hour() as usize] += 1;
+                }
+            }
+            Ok(distribution)
+        }
+
+        async fn get_daily_distribution(
+            &self,
+            url_id: i32,
+        ) -> Result<[u32; 7], ClickRepositoryError> {
+            let mut distribution = [0u32; 7];
+            for click in self.clicks.lock().unwrap().iter() {
+                if click.url_id == url_id {
+                    distribution[click.clicked_at.weekday().num_days_from_sunday() as usize] += 1;
+                }
+            }
+            Ok(distribution)
+        }
+
+        async fn get_peak_hour(&self, url_id: i32) -> Result<u8, ClickRepositoryError> {
+            let distribution = self.get_hourly_distribution(url_id).await?;
+            let peak = (0..24).fold(0, |peak, hour| {
+                if distribution[hour] > distribution[peak] {
+                    hour
+                } else {
+                    peak
+                }
+            });
+            Ok(peak as u8)
+        }
+
+        async fn get_click_velocity(
+            &self,
+            url_id: i32,
+            window_minutes: u32,
+        ) -> Result<f64, ClickRepositoryError> {
+            let since = Utc::now() - ChronoDuration::minutes(window_minutes as i64);
+            let clicks = self
+                .clicks
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|c| c.url_id == url_id && c.clicked_at >= since)
+                .count();
+            Ok(clicks as f64 / window_minutes as f64)
+        }
+
+        async fn get_utm_attribution_breakdown(
+            &self,
+            url_id: i32,
+        ) -> Result<Vec<UtmAttributionRow>, ClickRepositoryError> {
+            let mut counts: HashMap<(Option<String>, Option<String>, Option<String>), i64> =
+                HashMap::new();
+            for click in self
+                .clicks
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|c| c.url_id == url_id)
+            {
+                let utm = click.utm();
+                if !utm.is_empty() {
+                    *counts
+                        .entry((utm.source, utm.medium, utm.campaign))
+                        .or_default() += 1;
+                }
+            }
+            let mut rows: Vec<UtmAttributionRow> = counts
+                .into_iter()
+                .map(|((source, medium, campaign), count)| UtmAttributionRow {
+                    source,
+                    medium,
+                    campaign,
+                    count,
+                })
+                .collect();
+            rows.sort_by_key(|row| std::cmp::Reverse(row.count));
+            Ok(rows)
+        }
+
         async fn delete_old_clicks(
             &self,
             _older_than: chrono::DateTime<chrono::Utc>,
         ) -> Result<u64, ClickRepositoryError> {
             Ok(0)
         }
+
+        async fn count_old_clicks(
+            &self,
+            _older_than: chrono::DateTime<chrono::Utc>,
+        ) -> Result<u64, ClickRepositoryError> {
+            Ok(0)
+        }
     }
 
     #[tokio::test]
@@ -289,13 +1467,16 @@ mod tests {
             user_agent: Some("Mozilla/5.0...".to_string()),
             referer: Some("https://google.com".to_string()),
             country_code: Some("US".to_string()),
+            click_token: None,
+            suspicious: false,
+            utm: UtmParameters::default(),
         };
 
         // Record click (non-blocking)
         service.record_click(42, click_info).unwrap();
 
-        // Give async task time to process
-        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        // Flush buffered clicks
+        service.shutdown().await;
 
         // Check click count
         let count = service.get_click_count(42).await.unwrap();
@@ -310,4 +1491,553 @@ mod tests {
         let stats = service.get_url_stats(42).await.unwrap();
         assert_eq!(stats.total_clicks, 0);
     }
+
+    #[tokio::test]
+    async fn test_unique_visitors_estimate() {
+        let repo = MockClickRepository::new();
+        let service = ClickTrackingService::new(repo);
+
+        for ip in ["10.0.0.1", "10.0.0.2", "10.0.0.1"] {
+            let click_info = ClickInfo {
+                ip_address: Some(ip.to_string()),
+                ..test_click_info()
+            };
+            service.record_click(7, click_info).unwrap();
+        }
+        service.shutdown().await;
+
+        let today = chrono::Utc::now().date_naive();
+        let estimate = service
+            .get_unique_visitors_estimate(7, today, today)
+            .await
+            .unwrap();
+        assert_eq!(estimate, 2);
+    }
+
+    #[tokio::test]
+    async fn test_analytics_summary_is_cached_per_bot_setting() {
+        let repository = MockClickRepository::new();
+        let service = ClickTrackingService::new(repository.clone());
+        for user_agent in ["Mozilla/5.0 (iPhone) Mobile", "Googlebot/2.1"] {
+            repository
+                .record_click(&Click::new_for_tracking(
+                    1,
+                    Some("10.0.0.1".to_string()),
+                    Some(user_agent.to_string()),
+                    None,
+                    None,
+                ))
+                .await
+                .unwrap();
+        }
+
+        let humans = service.get_url_analytics_summary(1, false).await.unwrap();
+        assert_eq!(humans.total_clicks, 1);
+        assert_eq!(humans.device_breakdown.mobile, 1);
+        assert_eq!(humans.device_breakdown.bot, 0);
+
+        let everyone = service.get_url_analytics_summary(1, true).await.unwrap();
+        assert_eq!(everyone.total_clicks, 2);
+        assert_eq!(everyone.device_breakdown.bot, 1);
+
+        // Served from cache: new clicks are not visible and the repository is not queried
+        repository
+            .record_click(&Click::new_for_tracking(1, None, None, None, None))
+            .await
+            .unwrap();
+        let cached = service.get_url_analytics_summary(1, true).await.unwrap();
+        assert_eq!(cached.total_clicks, 2);
+        assert_eq!(*repository.summary_queries.lock().unwrap(), 2);
+    }
+
+    /// Store a click of a URL carrying the given click cookie token
+    async fn record_tokened_click(repository: &MockClickRepository, url_id: i32, token: &str) {
+        let mut click = Click::new_for_tracking(url_id, None, None, None, None);
+        click.click_token = Some(token.to_string());
+        repository.record_click(&click).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_conversion_is_attributed_to_click() {
+        let repository = MockClickRepository::new();
+        let service = ClickTrackingService::new(repository.clone());
+        record_tokened_click(&repository, 1, "token-a").await;
+        record_tokened_click(&repository, 1, "token-b").await;
+        let goal = service
+            .create_conversion_goal(1, "https://shop.example.com/thanks*", "Purchase")
+            .await
+            .unwrap();
+
+        let event = service
+            .report_conversion(
+                "token-a",
+                goal.id,
+                Some("https://shop.example.com/thanks?order=7"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(event.goal_id, goal.id);
+        assert_eq!(event.click_id, Some(1));
+
+        // Reporting again does not count the click twice
+        let repeated = service
+            .report_conversion("token-a", goal.id, None)
+            .await
+            .unwrap();
+        assert_eq!(repeated.id, event.id);
+        assert_eq!(service.get_conversion_rate(1).await.unwrap(), 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_conversion_report_is_validated() {
+        let repository = MockClickRepository::new();
+        let service = ClickTrackingService::new(repository.clone());
+        record_tokened_click(&repository, 2, "other-url").await;
+        let goal = service
+            .create_conversion_goal(1, "https://shop.example.com/thanks", "Purchase")
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            service.report_conversion("other-url", goal.id, None).await,
+            Err(ClickTrackingError::UnknownClick)
+        ));
+        assert!(matches!(
+            service
+                .report_conversion("other-url", goal.id, Some("https://evil.example/"))
+                .await,
+            Err(ClickTrackingError::InvalidData(_))
+        ));
+        assert!(matches!(
+            service.report_conversion("other-url", 99, None).await,
+            Err(ClickTrackingError::GoalNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_conversion_goal_validation() {
+        let service = ClickTrackingService::new(MockClickRepository::new());
+
+        for (pattern, name) in [
+            ("https://example.com/done", "  "),
+            ("ftp://example.com/done", "Signup"),
+            ("/done", "Signup"),
+        ] {
+            assert!(matches!(
+                service.create_conversion_goal(1, pattern, name).await,
+                Err(ClickTrackingError::InvalidData(_))
+            ));
+        }
+        assert!(matches!(
+            service.delete_conversion_goal(1).await,
+            Err(ClickTrackingError::GoalNotFound)
+        ));
+    }
+
+    fn test_click_info() -> ClickInfo {
+        ClickInfo {
+            ip_address: Some("10.0.0.1".to_string()),
+            user_agent: None,
+            referer: None,
+            country_code: None,
+            click_token: None,
+            suspicious: false,
+            utm: UtmParameters::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_click_is_attributed_to_destination_utm() {
+        let repo = MockClickRepository::new();
+        let service = ClickTrackingService::new(repo.clone());
+
+        let click_info = ClickInfo {
+            utm: UtmParameters::from_url("https://example.com?utm_source=newsletter"),
+            ..test_click_info()
+        };
+        service.record_click(1, click_info).unwrap();
+        service.record_click(1, test_click_info()).unwrap();
+        service.shutdown().await;
+
+        let clicks = repo.clicks.lock().unwrap().clone();
+        assert_eq!(clicks.len(), 2);
+        assert_eq!(clicks[0].utm_source.as_deref(), Some("newsletter"));
+        assert_eq!(clicks[1].utm_source, None);
+
+        let breakdown = service.get_utm_attribution_breakdown(1).await.unwrap();
+        assert_eq!(
+            breakdown,
+            vec![UtmAttributionRow {
+                source: Some("newsletter".to_string()),
+                medium: None,
+                campaign: None,
+                count: 1,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clicks_are_written_in_batches() {
+        let repo = MockClickRepository::new();
+        let config = ClickTrackingConfig {
+            buffer_size: 1000,
+            batch_size: 100,
+            flush_interval: Duration::from_secs(60),
+            ..ClickTrackingConfig::default()
+        };
+        let service = ClickTrackingService::with_config(repo.clone(), config);
+
+        for _ in 0..250 {
+            service.record_click(1, test_click_info()).unwrap();
+        }
+        service.shutdown().await;
+
+        assert_eq!(repo.clicks.lock().unwrap().len(), 250);
+        assert_eq!(*repo.batch_sizes.lock().unwrap(), vec![100, 100, 50]);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_tracking_records_nothing() {
+        let repo = MockClickRepository::new();
+        let config = ClickTrackingConfig {
+            enabled: false,
+            ..ClickTrackingConfig::default()
+        };
+        let service = ClickTrackingService::with_config(repo.clone(), config);
+
+        service.record_click(1, test_click_info()).unwrap();
+        service.shutdown().await;
+
+        assert!(repo.clicks.lock().unwrap().is_empty());
+        assert_eq!(service.dropped_clicks_total(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_partial_batch_flushed_on_interval() {
+        let repo = MockClickRepository::new();
+        let config = ClickTrackingConfig {
+            flush_interval: Duration::from_millis(20),
+            ..ClickTrackingConfig::default()
+        };
+        let service = ClickTrackingService::with_config(repo.clone(), config);
+
+        service.record_click(1, test_click_info()).unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(service.get_click_count(1).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_full_buffer_drops_clicks() {
+        let repo = MockClickRepository::with_write_latency(Duration::from_millis(200));
+        let config = ClickTrackingConfig {
+            buffer_size: 2,
+            batch_size: 1,
+            flush_interval: Duration::from_secs(60),
+            ..ClickTrackingConfig::default()
+        };
+        let service = ClickTrackingService::with_config(repo, config);
+
+        let results: Vec<_> = (0..10)
+            .map(|_| service.record_click(1, test_click_info()))
+            .collect();
+
+        assert!(results
+            .iter()
+            .any(|r| matches!(r, Err(ClickTrackingError::BufferFull))));
+        assert!(service.dropped_clicks_total() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_record_after_shutdown_fails() {
+        let service = ClickTrackingService::new(MockClickRepository::new());
+        service.shutdown().await;
+        service.shutdown().await;
+
+        let result = service.record_click(1, test_click_info());
+        assert!(matches!(
+            result,
+            Err(ClickTrackingError::ServiceUnavailable)
+        ));
+    }
+
+    fn deduplicating_service(
+        repo: MockClickRepository,
+        deduplicator: &crate::infrastructure::test_utils::MockClickDeduplicator,
+    ) -> ClickTrackingService<MockClickRepository> {
+        ClickTrackingService::with_deduplicator(
+            repo,
+            ClickTrackingConfig::default(),
+            Some(Arc::new(deduplicator.clone())),
+        )
+    }
+
+    fn click_from(ip_address: &str) -> ClickInfo {
+        ClickInfo {
+            ip_address: Some(ip_address.to_string()),
+            user_agent: Some("Mozilla/5.0 (X11; Linux x86_64)".to_string()),
+            ..test_click_info()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeat_clicks_are_counted_but_not_recorded() {
+        let repo = MockClickRepository::new();
+        let deduplicator = crate::infrastructure::test_utils::MockClickDeduplicator::new();
+        let service = deduplicating_service(repo.clone(), &deduplicator);
+
+        for ip_address in ["10.0.0.1", "10.0.0.1", "10.0.0.1", "10.0.0.2"] {
+            service.record_click(1, click_from(ip_address)).unwrap();
+        }
+        service.record_click(2, click_from("10.0.0.1")).unwrap();
+        service.shutdown().await;
+
+        assert_eq!(service.get_click_count(1).await.unwrap(), 2);
+        assert_eq!(service.get_click_count(2).await.unwrap(), 1);
+        let ratio = service.get_click_dedup_ratio(1).await.unwrap();
+        assert_eq!(
+            ratio,
+            ClickDedupRatio {
+                raw_clicks: 4,
+                unique_clicks: 2,
+                bot_clicks: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_click_milestone_notifies_owner_once() {
+        use crate::infrastructure::test_utils::{
+            MockNotificationPreferencesRepository, MockPushSender,
+        };
+
+        let preferences = MockNotificationPreferencesRepository::new();
+        preferences.add_device_token(URL_OWNER_ID, "owner-phone");
+        let push_sender = MockPushSender::new();
+        let notification_service = NotificationService::new()
+            .with_preferences_repository(Arc::new(preferences))
+            .with_push_sender(Some(Arc::new(push_sender.clone())));
+        let config = ClickTrackingConfig {
+            batch_size: 30,
+            ..ClickTrackingConfig::default()
+        };
+        let service = ClickTrackingService::with_config(MockClickRepository::new(), config)
+            .with_notification_service(notification_service);
+
+        // Crossing 100 in the middle of a batch notifies once; 150 crosses nothing else
+        for _ in 0..150 {
+            service.record_click(5, test_click_info()).unwrap();
+        }
+        service.shutdown().await;
+
+        for _ in 0..100 {
+            if !push_sender.sent().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let sent = push_sender.sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].device_token, "owner-phone");
+        assert_eq!(sent[0].deep_link, "shortener://urls/5");
+        assert_eq!(sent[0].dedup_key.as_deref(), Some("milestone:5:100"));
+        assert!(sent[0].body.contains("code5"));
+    }
+
+    #[tokio::test]
+    async fn test_recorded_clicks_are_added_to_owner_stats() {
+        use crate::infrastructure::test_utils::MockUserUrlStatsRepository;
+
+        let stats = MockUserUrlStatsRepository::new();
+        let service = ClickTrackingService::new(MockClickRepository::new())
+            .with_user_url_stats(Arc::new(stats.clone()));
+
+        for url_id in [5, 5, 6] {
+            service.record_click(url_id, test_click_info()).unwrap();
+        }
+        service.shutdown().await;
+
+        let owner_stats = stats.find_by_user_id(URL_OWNER_ID).await.unwrap().unwrap();
+        assert_eq!(owner_stats.total_clicks, 3);
+        assert_eq!(owner_stats.total_urls, 0);
+    }
+
+    #[tokio::test]
+    async fn test_clicks_recorded_when_deduplicator_fails() {
+        let repo = MockClickRepository::new();
+        let deduplicator = crate::infrastructure::test_utils::MockClickDeduplicator::new();
+        deduplicator.set_failing(true);
+        let service = deduplicating_service(repo.clone(), &deduplicator);
+
+        for _ in 0..3 {
+            service.record_click(1, click_from("10.0.0.1")).unwrap();
+        }
+        service.shutdown().await;
+
+        assert_eq!(service.get_click_count(1).await.unwrap(), 3);
+        assert_eq!(
+            service.get_click_dedup_ratio(1).await.unwrap().raw_clicks,
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_url_config_overrides_dedup_window() {
+        let repo = MockClickRepository::new();
+        let deduplicator = crate::infrastructure::test_utils::MockClickDeduplicator::new();
+        let service = deduplicating_service(repo.clone(), &deduplicator);
+
+        assert_eq!(
+            service
+                .get_url_config(1)
+                .await
+                .unwrap()
+                .click_dedup_window_seconds,
+            None
+        );
+        let config = service.set_click_dedup_window(1, Some(0)).await.unwrap();
+        assert_eq!(config.click_dedup_window_seconds, Some(0));
+        assert!(matches!(
+            service
+                .set_click_dedup_window(1, Some(MAX_CLICK_DEDUP_WINDOW_SECONDS + 1))
+                .await,
+            Err(ClickTrackingError::InvalidData(_))
+        ));
+
+        // A window of zero records every click
+        for _ in 0..3 {
+            service.record_click(1, click_from("10.0.0.1")).unwrap();
+        }
+        service.shutdown().await;
+        assert_eq!(service.get_click_count(1).await.unwrap(), 3);
+    }
+
+    /// Compares writing each click inline (the old redirect path) with buffering it
+    #[tokio::test]
+    async fn bench_synchronous_vs_batched_click_recording() {
+        const CLICKS: usize = 50;
+        let write_latency = Duration::from_millis(2);
+
+        let sync_repo = MockClickRepository::with_write_latency(write_latency);
+        let started = std::time::Instant::now();
+        for _ in 0..CLICKS {
+            let click = ClickRecord::new(1, test_click_info()).into_click();
+            sync_repo.record_click(&click).await.unwrap();
+        }
+        let synchronous = started.elapsed();
+
+        let batched_repo = MockClickRepository::with_write_latency(write_latency);
+        let service = ClickTrackingService::new(batched_repo.clone());
+        let started = std::time::Instant::now();
+        for _ in 0..CLICKS {
+            service.record_click(1, test_click_info()).unwrap();
+        }
+        let batched = started.elapsed();
+        service.shutdown().await;
+
+        println!(
+            "{} clicks: synchronous {:?}, batched {:?} ({} INSERTs)",
+            CLICKS,
+            synchronous,
+            batched,
+            batched_repo.batch_sizes.lock().unwrap().len()
+        );
+        assert_eq!(batched_repo.clicks.lock().unwrap().len(), CLICKS);
+        assert!(synchronous >= write_latency * CLICKS as u32);
+        assert!(batched < synchronous);
+    }
+
+    #[test]
+    fn test_timeline_bucket_start() {
+        let at = Utc.with_ymd_and_hms(2024, 3, 14, 15, 42, 7).unwrap();
+        assert_eq!(
+            TimelineGranularity::Hour.bucket_start(at),
+            Utc.with_ymd_and_hms(2024, 3, 14, 15, 0, 0).unwrap()
+        );
+        assert_eq!(
+            TimelineGranularity::Day.bucket_start(at),
+            Utc.with_ymd_and_hms(2024, 3, 14, 0, 0, 0).unwrap()
+        );
+        // 2024-03-14 is a Thursday
+        assert_eq!(
+            TimelineGranularity::Week.bucket_start(at),
+            Utc.with_ymd_and_hms(2024, 3, 11, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            TimelineGranularity::Month.bucket_start(at),
+            Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_click_patterns_of_morning_clicks() {
+        let repository = MockClickRepository::new();
+        let service = ClickTrackingService::new(repository.clone());
+        // A Monday, with every click between 8 and 10 in the morning
+        let monday = Utc.with_ymd_and_hms(2024, 3, 4, 0, 0, 0).unwrap();
+        for (hour, clicks) in [(8, 2), (9, 5), (10, 1)] {
+            for _ in 0..clicks {
+                let mut click = Click::new_for_tracking(1, None, None, None, None);
+                click.clicked_at = monday + ChronoDuration::hours(hour);
+                repository.record_click(&click).await.unwrap();
+            }
+        }
+
+        let patterns = service.get_click_patterns(1, 60).await.unwrap();
+        let mut expected = [0u32; 24];
+        expected[8] = 2;
+        expected[9] = 5;
+        expected[10] = 1;
+        assert_eq!(patterns.hourly_distribution, expected);
+        assert_eq!(patterns.peak_hour, 9);
+        assert_eq!(patterns.daily_distribution, [0, 8, 0, 0, 0, 0, 0]);
+        assert_eq!(patterns.click_velocity, 0.0);
+
+        for minutes_ago in [1, 5, 30] {
+            let mut click = Click::new_for_tracking(2, None, None, None, None);
+            click.clicked_at = Utc::now() - ChronoDuration::minutes(minutes_ago);
+            repository.record_click(&click).await.unwrap();
+        }
+        let patterns = service.get_click_patterns(2, 10).await.unwrap();
+        assert!((patterns.click_velocity - 0.2).abs() < 1e-9);
+
+        assert!(matches!(
+            service.get_click_patterns(1, 0).await,
+            Err(ClickTrackingError::InvalidData(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_click_timeline_counts_per_bucket() {
+        let repository = MockClickRepository::new();
+        let service = ClickTrackingService::new(repository.clone());
+        let browser = Some("Mozilla/5.0 (X11; Linux x86_64) Firefox/121.0".to_string());
+        for days_ago in [0, 0, 2, 45] {
+            let mut click = Click::new_for_tracking(1, None, browser.clone(), None, None);
+            click.clicked_at = Utc::now() - ChronoDuration::days(days_ago);
+            repository.record_click(&click).await.unwrap();
+        }
+        repository
+            .record_click(&Click::new_for_tracking(1, None, None, None, None))
+            .await
+            .unwrap();
+
+        let timeline = service
+            .get_click_timeline(1, TimelineGranularity::Day, false)
+            .await
+            .unwrap();
+        assert_eq!(timeline.len(), 30);
+        assert!(timeline
+            .windows(2)
+            .all(|pair| pair[0].start < pair[1].start));
+        assert_eq!(timeline[29].clicks, 2);
+        assert_eq!(timeline[27].clicks, 1);
+        assert_eq!(timeline.iter().map(|point| point.clicks).sum::<i64>(), 3);
+
+        let with_bots = service
+            .get_click_timeline(1, TimelineGranularity::Day, true)
+            .await
+            .unwrap();
+        assert_eq!(with_bots[29].clicks, 3);
+    }
 }
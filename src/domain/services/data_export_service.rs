@@ -0,0 +1,266 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// How long a download link for an asynchronous export stays valid
+pub const EXPORT_LINK_TTL_HOURS: i64 = 48;
+
+/// Minimum time between two exports requested by the same user
+pub const EXPORT_COOLDOWN_HOURS: i64 = 24;
+
+/// Exports with more URLs than this are generated in the background and sent by email
+pub const LARGE_EXPORT_URL_THRESHOLD: i64 = 1000;
+
+/// Data export errors
+#[derive(Error, Debug)]
+pub enum DataExportError {
+    #[error("A data export was already requested in the last {EXPORT_COOLDOWN_HOURS} hours; try again in {retry_after_seconds} seconds")]
+    RateLimited { retry_after_seconds: i64 },
+
+    #[error("Export not found or expired")]
+    NotFound,
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// Download link for a stored export
+#[derive(Debug, Clone)]
+pub struct StoredExport {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Export file waiting to be downloaded
+struct ExportFile {
+    path: PathBuf,
+    filename: String,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct ExportState {
+    /// When each user last requested an export
+    last_requests: HashMap<i32, DateTime<Utc>>,
+    /// Stored exports by download token
+    files: HashMap<String, ExportFile>,
+}
+
+/// Service for personal data exports (GDPR right to data portability)
+///
+/// Enforces the per-user export limit and keeps large exports on disk until their download
+/// link expires. Both are tracked in memory, so they reset when the server restarts.
+#[derive(Clone)]
+pub struct DataExportService {
+    export_dir: PathBuf,
+    state: Arc<Mutex<ExportState>>,
+}
+
+impl DataExportService {
+    /// Create a service storing export files in `export_dir`
+    pub fn new(export_dir: PathBuf) -> Self {
+        Self {
+            export_dir,
+            state: Arc::new(Mutex::new(ExportState::default())),
+        }
+    }
+
+    /// Record an export request, failing if the user already exported in the cooldown period
+    pub fn reserve(&self, user_id: i32) -> Result<(), DataExportError> {
+        self.reserve_at(user_id, Utc::now())
+    }
+
+    fn reserve_at(&self, user_id: i32, now: DateTime<Utc>) -> Result<(), DataExportError> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(last_request) = state.last_requests.get(&user_id) {
+            let next_allowed = *last_request + Duration::hours(EXPORT_COOLDOWN_HOURS);
+            if next_allowed > now {
+                return Err(DataExportError::RateLimited {
+                    retry_after_seconds: (next_allowed - now).num_seconds().max(1),
+                });
+            }
+        }
+        state.last_requests.insert(user_id, now);
+        Ok(())
+    }
+
+    /// Give back a reservation whose export failed, so the user can retry right away
+    pub fn release(&self, user_id: i32) {
+        self.state.lock().unwrap().last_requests.remove(&user_id);
+    }
+
+    /// Write an export to disk and return a download link valid for 48 hours
+    pub async fn store(
+        &self,
+        filename: String,
+        contents: Vec<u8>,
+    ) -> Result<StoredExport, DataExportError> {
+        self.purge_expired().await;
+
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let path = self.export_dir.join(format!("{}.json", token));
+        tokio::fs::create_dir_all(&self.export_dir).await?;
+        tokio::fs::write(&path, contents).await?;
+
+        let expires_at = Utc::now() + Duration::hours(EXPORT_LINK_TTL_HOURS);
+        self.state.lock().unwrap().files.insert(
+            token.clone(),
+            ExportFile {
+                path,
+                filename,
+                expires_at,
+            },
+        );
+
+        Ok(StoredExport { token, expires_at })
+    }
+
+    /// Read a stored export, returning its download filename and contents
+    pub async fn load(&self, token: &str) -> Result<(String, Vec<u8>), DataExportError> {
+        let (path, filename) = {
+            let state = self.state.lock().unwrap();
+            match state.files.get(token) {
+                Some(file) if file.expires_at > Utc::now() => {
+                    (file.path.clone(), file.filename.clone())
+                }
+                _ => return Err(DataExportError::NotFound),
+            }
+        };
+
+        match tokio::fs::read(&path).await {
+            Ok(contents) => Ok((filename, contents)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(DataExportError::NotFound),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Delete exports whose download link has expired, returning how many were removed
+    pub async fn purge_expired(&self) -> usize {
+        let now = Utc::now();
+        let expired: Vec<ExportFile> = {
+            let mut state = self.state.lock().unwrap();
+            let tokens: Vec<String> = state
+                .files
+                .iter()
+                .filter(|(_, file)| file.expires_at <= now)
+                .map(|(token, _)| token.clone())
+                .collect();
+            tokens
+                .iter()
+                .filter_map(|token| state.files.remove(token))
+                .collect()
+        };
+
+        for file in &expired {
+            if let Err(e) = tokio::fs::remove_file(&file.path).await {
+                tracing::warn!("Failed to delete expired export {:?}: {}", file.path, e);
+            }
+        }
+        expired.len()
+    }
+}
+
+/// Download filename for an export: `export-<username>-<date>.json`
+///
+/// The username is reduced to characters that are safe inside a quoted header value.
+pub fn export_filename(username: &str, date: NaiveDate) -> String {
+    let username: String = username
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("export-{}-{}.json", username, date.format("%Y-%m-%d"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_rate_limited_for_24_hours() {
+        let service = DataExportService::new(std::env::temp_dir());
+        let now = Utc::now();
+
+        assert!(service.reserve_at(1, now).is_ok());
+        assert!(matches!(
+            service.reserve_at(1, now + Duration::hours(23)),
+            Err(DataExportError::RateLimited { retry_after_seconds }) if retry_after_seconds == 3600
+        ));
+        assert!(service.reserve_at(2, now).is_ok());
+        assert!(service.reserve_at(1, now + Duration::hours(24)).is_ok());
+    }
+
+    #[test]
+    fn test_release_allows_retry() {
+        let service = DataExportService::new(std::env::temp_dir());
+        service.reserve(1).unwrap();
+        service.release(1);
+        assert!(service.reserve(1).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_store_and_load_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = DataExportService::new(dir.path().to_path_buf());
+
+        let stored = service
+            .store("export-alice-2024-01-01.json".to_string(), b"{}".to_vec())
+            .await
+            .unwrap();
+        assert!(stored.expires_at > Utc::now() + Duration::hours(47));
+
+        let (filename, contents) = service.load(&stored.token).await.unwrap();
+        assert_eq!(filename, "export-alice-2024-01-01.json");
+        assert_eq!(contents, b"{}");
+        assert!(matches!(
+            service.load("unknown").await,
+            Err(DataExportError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_expired_export_is_purged() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = DataExportService::new(dir.path().to_path_buf());
+        let stored = service
+            .store("export.json".to_string(), b"{}".to_vec())
+            .await
+            .unwrap();
+
+        service
+            .state
+            .lock()
+            .unwrap()
+            .files
+            .get_mut(&stored.token)
+            .unwrap()
+            .expires_at = Utc::now() - Duration::seconds(1);
+
+        assert!(matches!(
+            service.load(&stored.token).await,
+            Err(DataExportError::NotFound)
+        ));
+        assert_eq!(service.purge_expired().await, 1);
+        assert!(!dir.path().join(format!("{}.json", stored.token)).exists());
+    }
+
+    #[test]
+    fn test_export_filename_is_sanitized() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 9).unwrap();
+        assert_eq!(
+            export_filename("alice", date),
+            "export-alice-2024-03-09.json"
+        );
+        assert_eq!(
+            export_filename("bob\"; x=\r\n", date),
+            "export-bob___x___-2024-03-09.json"
+        );
+    }
+}
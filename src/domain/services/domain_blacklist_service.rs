@@ -0,0 +1,201 @@
+use crate::domain::entities::BlockedDomain;
+use crate::domain::repositories::domain_blacklist_repository::RepositoryError;
+use crate::domain::repositories::DomainBlacklistRepository;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Domain service for the blacklist of domains that may not be shortened
+///
+/// Matching is case-insensitive and covers subdomains: blocking `malware.com` also blocks
+/// `cdn.malware.com`. Internationalized names are compared in their punycode form.
+#[derive(Clone)]
+pub struct DomainBlacklist {
+    repository: Arc<dyn DomainBlacklistRepository>,
+}
+
+impl DomainBlacklist {
+    pub fn new(repository: Arc<dyn DomainBlacklistRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Check if URLs pointing at `host` are blocked
+    pub async fn is_blocked(&self, host: &str) -> Result<bool, DomainBlacklistError> {
+        let host = Self::normalize_domain(host)?;
+        Ok(self.repository.is_blocked(&host).await?)
+    }
+
+    /// Block a domain and its subdomains
+    pub async fn add_domain(
+        &self,
+        domain: &str,
+        reason: &str,
+        added_by: i32,
+    ) -> Result<BlockedDomain, DomainBlacklistError> {
+        let domain = Self::normalize_domain(domain)?;
+        self.repository
+            .add_domain(&domain, reason.trim(), added_by)
+            .await
+            .map_err(|e| match e {
+                RepositoryError::DuplicateDomain => DomainBlacklistError::AlreadyBlocked(domain),
+                e => DomainBlacklistError::Repository(e),
+            })
+    }
+
+    /// Remove a blacklist entry
+    pub async fn remove_domain(&self, id: i32) -> Result<(), DomainBlacklistError> {
+        if self.repository.remove_domain(id).await? {
+            Ok(())
+        } else {
+            Err(DomainBlacklistError::NotFound)
+        }
+    }
+
+    /// List all blacklisted domains
+    pub async fn list_domains(&self) -> Result<Vec<BlockedDomain>, DomainBlacklistError> {
+        Ok(self.repository.list_domains().await?)
+    }
+
+    /// Load entries from the contents of a blacklist file, keeping existing ones
+    ///
+    /// Each line holds a domain optionally followed by a reason; blank lines and lines
+    /// starting with `#` are ignored. Returns the number of domains added.
+    pub async fn import(&self, contents: &str) -> Result<u64, DomainBlacklistError> {
+        let domains = Self::parse_blacklist_file(contents)?;
+        Ok(self.repository.import_domains(&domains).await?)
+    }
+
+    /// Parse a blacklist file into (domain, reason) pairs
+    pub fn parse_blacklist_file(
+        contents: &str,
+    ) -> Result<Vec<(String, String)>, DomainBlacklistError> {
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (domain, reason) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+                Ok((Self::normalize_domain(domain)?, reason.trim().to_string()))
+            })
+            .collect()
+    }
+
+    /// Lowercase a domain, drop a trailing dot and convert it to punycode
+    pub fn normalize_domain(domain: &str) -> Result<String, DomainBlacklistError> {
+        let invalid = || DomainBlacklistError::InvalidDomain(domain.to_string());
+
+        let trimmed = domain.trim().trim_end_matches('.');
+        if trimmed.is_empty()
+            || trimmed
+                .chars()
+                .any(|c| c.is_whitespace() || "/:@?#[]\\".contains(c))
+        {
+            return Err(invalid());
+        }
+
+        match url::Host::parse(trimmed).map_err(|_| invalid())? {
+            url::Host::Domain(domain) => Ok(domain),
+            host => Ok(host.to_string()),
+        }
+    }
+}
+
+/// Domain blacklist errors
+#[derive(Error, Debug)]
+pub enum DomainBlacklistError {
+    #[error("Invalid domain: {0}")]
+    InvalidDomain(String),
+
+    #[error("Domain '{0}' is already blacklisted")]
+    AlreadyBlocked(String),
+
+    #[error("Blacklist entry not found")]
+    NotFound,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_utils::MockDomainBlacklistRepository;
+
+    fn blacklist() -> DomainBlacklist {
+        DomainBlacklist::new(Arc::new(MockDomainBlacklistRepository::new()))
+    }
+
+    #[test]
+    fn test_normalize_domain() {
+        assert_eq!(
+            DomainBlacklist::normalize_domain(" Malware.COM. ").unwrap(),
+            "malware.com"
+        );
+        assert_eq!(
+            DomainBlacklist::normalize_domain("bücher.de").unwrap(),
+            "xn--bcher-kva.de"
+        );
+        for invalid in [
+            "",
+            "malware.com/path",
+            "user@malware.com",
+            "a b.com",
+            "host:80",
+        ] {
+            assert!(
+                DomainBlacklist::normalize_domain(invalid).is_err(),
+                "expected '{}' to be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_blocks_domain_and_subdomains_case_insensitively() {
+        let blacklist = blacklist();
+        blacklist
+            .add_domain("Malware.com", "Malware distribution", 1)
+            .await
+            .unwrap();
+
+        assert!(blacklist.is_blocked("malware.com").await.unwrap());
+        assert!(blacklist.is_blocked("CDN.Malware.COM").await.unwrap());
+        assert!(!blacklist.is_blocked("notmalware.com").await.unwrap());
+        assert!(!blacklist.is_blocked("example.com").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_and_missing_entries() {
+        let blacklist = blacklist();
+        let entry = blacklist.add_domain("malware.com", "", 1).await.unwrap();
+
+        assert!(matches!(
+            blacklist.add_domain("MALWARE.com", "", 1).await,
+            Err(DomainBlacklistError::AlreadyBlocked(_))
+        ));
+
+        blacklist.remove_domain(entry.id).await.unwrap();
+        assert!(!blacklist.is_blocked("malware.com").await.unwrap());
+        assert!(matches!(
+            blacklist.remove_domain(entry.id).await,
+            Err(DomainBlacklistError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_import_blacklist_file() {
+        let blacklist = blacklist();
+        blacklist.add_domain("phishing.net", "", 1).await.unwrap();
+
+        let contents = "# Known bad domains\n\nmalware.com   Malware distribution\nPhishing.NET\n";
+        assert_eq!(blacklist.import(contents).await.unwrap(), 1);
+        assert!(blacklist.is_blocked("cdn.malware.com").await.unwrap());
+
+        let domains = blacklist.list_domains().await.unwrap();
+        assert_eq!(domains.len(), 2);
+        assert_eq!(domains[0].domain, "malware.com");
+        assert_eq!(domains[0].reason, "Malware distribution");
+        assert_eq!(domains[0].added_by, None);
+
+        assert!(DomainBlacklist::parse_blacklist_file("bad/domain Phishing").is_err());
+    }
+}
@@ -0,0 +1,330 @@
+use crate::domain::entities::{EmailChangeSide, EmailChangeToken, User};
+use crate::domain::repositories::user_repository::{normalize_email, RepositoryError};
+use crate::domain::repositories::{EmailChangeRepository, UserRepository};
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Hours both addresses have to confirm an email change
+pub const EMAIL_CHANGE_EXPIRATION_HOURS: i64 = 24;
+
+/// Length of the raw tokens sent in the confirmation links
+const EMAIL_CHANGE_TOKEN_LENGTH: usize = 48;
+
+/// Service for changing a user's email address once the old and the new address confirmed
+///
+/// Until both confirmed, the new address is only shown as the user's pending email.
+pub struct EmailChangeService<U>
+where
+    U: UserRepository,
+{
+    email_change_repository: Arc<dyn EmailChangeRepository>,
+    user_repository: U,
+}
+
+/// Email change service errors
+#[derive(Error, Debug)]
+pub enum EmailChangeError {
+    #[error("User not found")]
+    UserNotFound,
+
+    #[error("The new email address is the current one")]
+    SameEmail,
+
+    #[error("The email address is already in use")]
+    EmailTaken,
+
+    #[error("Invalid token")]
+    InvalidToken,
+
+    #[error("Token expired")]
+    TokenExpired,
+
+    #[error("Repository error: {0}")]
+    RepositoryError(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl From<RepositoryError> for EmailChangeError {
+    fn from(error: RepositoryError) -> Self {
+        match error {
+            RepositoryError::NotFound => EmailChangeError::UserNotFound,
+            RepositoryError::DuplicateEmail => EmailChangeError::EmailTaken,
+            e => EmailChangeError::RepositoryError(Box::new(e)),
+        }
+    }
+}
+
+/// A freshly requested email change
+#[derive(Debug, Clone)]
+pub struct EmailChangeRequest {
+    /// The user, with the new address as their pending email
+    pub user: User,
+    /// Raw token for the link sent to the current address; never stored
+    pub old_address_token: String,
+    /// Raw token for the link sent to the new address; never stored
+    pub new_address_token: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Result of confirming one side of an email change
+#[derive(Debug, Clone)]
+pub enum EmailChangeConfirmation {
+    /// The other address has yet to confirm
+    Pending(EmailChangeToken),
+    /// Both addresses confirmed and the user's email was changed
+    Completed(Box<User>),
+}
+
+impl<U> EmailChangeService<U>
+where
+    U: UserRepository,
+{
+    pub fn new(
+        email_change_repository: Arc<dyn EmailChangeRepository>,
+        user_repository: U,
+    ) -> Self {
+        Self {
+            email_change_repository,
+            user_repository,
+        }
+    }
+
+    /// Hash a raw token for storage and lookup
+    pub fn hash_token(token: &str) -> String {
+        format!("{:x}", Sha256::digest(token.as_bytes()))
+    }
+
+    fn generate_token() -> String {
+        thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(EMAIL_CHANGE_TOKEN_LENGTH)
+            .map(char::from)
+            .collect()
+    }
+
+    /// Start changing the email of `user_id` to `new_email`
+    ///
+    /// Replaces any change the user requested before.
+    pub async fn request_change(
+        &self,
+        user_id: i32,
+        new_email: &str,
+    ) -> Result<EmailChangeRequest, EmailChangeError> {
+        let new_email = normalize_email(new_email);
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or(EmailChangeError::UserNotFound)?;
+        if user.email == new_email {
+            return Err(EmailChangeError::SameEmail);
+        }
+        if self.user_repository.exists_by_email(&new_email).await? {
+            return Err(EmailChangeError::EmailTaken);
+        }
+
+        let old_address_token = Self::generate_token();
+        let new_address_token = Self::generate_token();
+        let stored = self
+            .email_change_repository
+            .create_token(EmailChangeToken::new(
+                user.id,
+                new_email.clone(),
+                Self::hash_token(&old_address_token),
+                Self::hash_token(&new_address_token),
+                EMAIL_CHANGE_EXPIRATION_HOURS,
+            ))
+            .await?;
+        let user = self
+            .user_repository
+            .set_pending_email(user.id, Some(&new_email))
+            .await?;
+
+        Ok(EmailChangeRequest {
+            user,
+            old_address_token,
+            new_address_token,
+            expires_at: stored.expires_at,
+        })
+    }
+
+    /// Confirm one side of an email change with the token from its link
+    ///
+    /// The email changes with the second confirmation. A change that expired is dropped
+    /// and the user's pending email cleared.
+    pub async fn confirm(
+        &self,
+        side: EmailChangeSide,
+        token: &str,
+    ) -> Result<EmailChangeConfirmation, EmailChangeError> {
+        let change = self
+            .email_change_repository
+            .find_by_token_hash(side, &Self::hash_token(token))
+            .await?
+            .ok_or(EmailChangeError::InvalidToken)?;
+
+        if change.is_expired() {
+            self.drop_change(&change).await?;
+            return Err(EmailChangeError::TokenExpired);
+        }
+
+        let change = self
+            .email_change_repository
+            .confirm(change.id, side)
+            .await?
+            .ok_or(EmailChangeError::InvalidToken)?;
+        if !change.is_confirmed() {
+            return Ok(EmailChangeConfirmation::Pending(change));
+        }
+
+        match self
+            .user_repository
+            .update_email(change.user_id, &change.new_email)
+            .await
+        {
+            Ok(user) => {
+                self.email_change_repository.delete_token(change.id).await?;
+                Ok(EmailChangeConfirmation::Completed(Box::new(user)))
+            }
+            Err(RepositoryError::DuplicateEmail) => {
+                // Another account took the address while the change was pending
+                self.drop_change(&change).await?;
+                Err(EmailChangeError::EmailTaken)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Delete a change and clear the pending email it set
+    async fn drop_change(&self, change: &EmailChangeToken) -> Result<(), EmailChangeError> {
+        self.email_change_repository.delete_token(change.id).await?;
+        self.user_repository
+            .set_pending_email(change.user_id, None)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_utils::{MockEmailChangeRepository, MockUserRepository};
+
+    async fn service_with_user() -> (
+        EmailChangeService<MockUserRepository>,
+        MockUserRepository,
+        User,
+    ) {
+        let users = MockUserRepository::new();
+        let user = users
+            .create_user("alice", "alice@example.com", "hash")
+            .await
+            .unwrap();
+        let service =
+            EmailChangeService::new(Arc::new(MockEmailChangeRepository::new()), users.clone());
+        (service, users, user)
+    }
+
+    #[tokio::test]
+    async fn test_email_changes_after_both_confirmations() {
+        let (service, users, user) = service_with_user().await;
+
+        let request = service
+            .request_change(user.id, "Alice@New.example")
+            .await
+            .unwrap();
+        assert_eq!(request.user.email, "alice@example.com");
+        assert_eq!(
+            request.user.pending_email.as_deref(),
+            Some("alice@new.example")
+        );
+
+        // Tokens only work for their own side
+        assert!(matches!(
+            service
+                .confirm(EmailChangeSide::OldAddress, &request.new_address_token)
+                .await,
+            Err(EmailChangeError::InvalidToken)
+        ));
+
+        let confirmation = service
+            .confirm(EmailChangeSide::NewAddress, &request.new_address_token)
+            .await
+            .unwrap();
+        assert!(matches!(confirmation, EmailChangeConfirmation::Pending(_)));
+        let pending = users.find_by_id(user.id).await.unwrap().unwrap();
+        assert_eq!(pending.email, "alice@example.com");
+
+        let confirmation = service
+            .confirm(EmailChangeSide::OldAddress, &request.old_address_token)
+            .await
+            .unwrap();
+        let EmailChangeConfirmation::Completed(changed) = confirmation else {
+            panic!("expected the change to complete");
+        };
+        assert_eq!(changed.email, "alice@new.example");
+        assert_eq!(changed.pending_email, None);
+
+        // The links stop working once the change is applied
+        assert!(matches!(
+            service
+                .confirm(EmailChangeSide::OldAddress, &request.old_address_token)
+                .await,
+            Err(EmailChangeError::InvalidToken)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_expired_change_clears_pending_email() {
+        let (service, users, user) = service_with_user().await;
+        let request = service
+            .request_change(user.id, "alice@new.example")
+            .await
+            .unwrap();
+
+        // Move the change past its expiry
+        let repository = MockEmailChangeRepository::new();
+        let change = EmailChangeToken {
+            expires_at: chrono::Utc::now() - chrono::Duration::hours(1),
+            ..EmailChangeToken::new(
+                user.id,
+                "alice@new.example".to_string(),
+                EmailChangeService::<MockUserRepository>::hash_token(&request.old_address_token),
+                EmailChangeService::<MockUserRepository>::hash_token(&request.new_address_token),
+                EMAIL_CHANGE_EXPIRATION_HOURS,
+            )
+        };
+        repository.create_token(change).await.unwrap();
+        let service = EmailChangeService::new(Arc::new(repository), users.clone());
+
+        assert!(matches!(
+            service
+                .confirm(EmailChangeSide::NewAddress, &request.new_address_token)
+                .await,
+            Err(EmailChangeError::TokenExpired)
+        ));
+        let user = users.find_by_id(user.id).await.unwrap().unwrap();
+        assert_eq!(user.email, "alice@example.com");
+        assert_eq!(user.pending_email, None);
+    }
+
+    #[tokio::test]
+    async fn test_request_rejects_current_and_taken_addresses() {
+        let (service, users, user) = service_with_user().await;
+        users
+            .create_user("bob", "bob@example.com", "hash")
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            service.request_change(user.id, " ALICE@example.com").await,
+            Err(EmailChangeError::SameEmail)
+        ));
+        assert!(matches!(
+            service.request_change(user.id, "bob@example.com").await,
+            Err(EmailChangeError::EmailTaken)
+        ));
+    }
+}
@@ -3,8 +3,9 @@ use std::path::Path;
 use thiserror::Error;
 
 /// File upload service for handling profile pictures
+///
+/// It validates and processes uploads; storing them is up to the object storage.
 pub struct FileUploadService {
-    upload_dir: String,
     max_file_size: usize,
     allowed_extensions: Vec<String>,
     allowed_mime_types: Vec<String>,
@@ -36,15 +37,16 @@ pub enum FileUploadError {
     InvalidFileName(String),
 }
 
-/// File upload result
+/// Validated upload, processed and ready to be stored
 #[derive(Debug, Clone)]
-pub struct FileUploadResult {
-    pub filename: String,
-    #[allow(dead_code)]
-    pub file_path: String,
-    pub file_size: usize,
-    #[allow(dead_code)]
+pub struct ProcessedFile {
+    pub data: Vec<u8>,
+    /// Extension matching `data`; images are re-encoded as JPEG
+    pub extension: String,
+    /// MIME type matching `data`
     pub mime_type: String,
+    /// Size of the file as uploaded, in bytes
+    pub file_size: usize,
     pub width: Option<u32>,
     pub height: Option<u32>,
 }
@@ -52,13 +54,11 @@ pub struct FileUploadResult {
 impl FileUploadService {
     /// Create a new file upload service
     pub fn new(
-        upload_dir: String,
         max_file_size: usize,
         allowed_extensions: Vec<String>,
         allowed_mime_types: Vec<String>,
     ) -> Self {
         Self {
-            upload_dir,
             max_file_size,
             allowed_extensions,
             allowed_mime_types,
@@ -66,9 +66,8 @@ impl FileUploadService {
     }
 
     /// Create a default file upload service for profile pictures
-    pub fn new_profile_picture_service(upload_dir: String) -> Self {
+    pub fn new_profile_picture_service() -> Self {
         Self::new(
-            upload_dir,
             5 * 1024 * 1024, // 5MB max file size
             vec![
                 "jpg".to_string(),
@@ -135,45 +134,42 @@ impl FileUploadService {
         Ok(())
     }
 
-    /// Process and save uploaded file
-    pub async fn process_and_save_file(
+    /// Validate and process an uploaded file
+    pub fn process_file(
         &self,
         filename: &str,
         content_type: &str,
         file_data: Vec<u8>,
-    ) -> Result<FileUploadResult, FileUploadError> {
+    ) -> Result<ProcessedFile, FileUploadError> {
         // Validate file
         self.validate_file(filename, content_type, file_data.len())?;
 
-        // Create upload directory if it doesn't exist
-        tokio::fs::create_dir_all(&self.upload_dir).await?;
-
-        // Generate unique filename
-        let file_extension = Path::new(filename)
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .unwrap_or("jpg");
-        let unique_filename = format!("{}.{}", uuid::Uuid::new_v4(), file_extension);
-        let file_path = Path::new(&self.upload_dir).join(&unique_filename);
-
         // Process image if it's an image file
         let file_size = file_data.len();
-        let (processed_data, width, height) = if content_type.starts_with("image/") {
-            self.process_image(file_data)?
-        } else {
-            (file_data, None, None)
-        };
-
-        // Save file
-        tokio::fs::write(&file_path, processed_data).await?;
+        if content_type.starts_with("image/") {
+            let (data, width, height) = self.process_image(file_data)?;
+            return Ok(ProcessedFile {
+                data,
+                extension: "jpg".to_string(),
+                mime_type: "image/jpeg".to_string(),
+                file_size,
+                width,
+                height,
+            });
+        }
 
-        Ok(FileUploadResult {
-            filename: unique_filename,
-            file_path: file_path.to_string_lossy().to_string(),
-            file_size,
+        let extension = Path::new(filename)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .unwrap_or_default();
+        Ok(ProcessedFile {
+            data: file_data,
+            extension,
             mime_type: content_type.to_string(),
-            width,
-            height,
+            file_size,
+            width: None,
+            height: None,
         })
     }
 
@@ -209,30 +205,15 @@ impl FileUploadService {
             Some(processed_img.height()),
         ))
     }
-
-    /// Delete file
-    pub async fn delete_file(&self, filename: &str) -> Result<(), FileUploadError> {
-        let file_path = Path::new(&self.upload_dir).join(filename);
-        if file_path.exists() {
-            tokio::fs::remove_file(file_path).await?;
-        }
-        Ok(())
-    }
-
-    /// Get file URL (for serving files)
-    pub fn get_file_url(&self, filename: &str, base_url: &str) -> String {
-        format!("{}/uploads/{}", base_url, filename)
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tempfile::TempDir;
 
     #[tokio::test]
     async fn test_validate_file() {
-        let service = FileUploadService::new_profile_picture_service("/tmp".to_string());
+        let service = FileUploadService::new_profile_picture_service();
 
         // Valid file
         assert!(service
@@ -267,35 +248,32 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_process_and_save_file() {
+    async fn test_process_file() {
         use image::{ImageBuffer, Rgb};
         use std::io::Cursor;
 
-        let temp_dir = TempDir::new().unwrap();
-        let service = FileUploadService::new_profile_picture_service(
-            temp_dir.path().to_string_lossy().to_string(),
-        );
+        let service = FileUploadService::new_profile_picture_service();
 
         // Create a valid 10x10 pixel RGB image
         let img: ImageBuffer<Rgb<u8>, Vec<u8>> =
             ImageBuffer::from_fn(10, 10, |_, _| Rgb([255, 0, 0]));
 
-        // Encode to JPEG format
+        // Encode to PNG format
         let mut buffer = Cursor::new(Vec::new());
-        img.write_to(&mut buffer, image::ImageFormat::Jpeg).unwrap();
+        img.write_to(&mut buffer, image::ImageFormat::Png).unwrap();
         let test_image_data = buffer.into_inner();
 
-        let result = service
-            .process_and_save_file("test.jpg", "image/jpeg", test_image_data)
-            .await;
+        let result = service.process_file("test.png", "image/png", test_image_data);
 
         if let Err(ref e) = result {
             eprintln!("Error processing file: {:?}", e);
         }
         assert!(result.is_ok(), "File processing failed: {:?}", result.err());
         let result = result.unwrap();
-        assert!(result.filename.ends_with(".jpg"));
+        // Images are stored as JPEG whatever format they were uploaded in
+        assert_eq!(result.extension, "jpg");
         assert!(result.file_size > 0);
         assert_eq!(result.mime_type, "image/jpeg");
+        assert_eq!((result.width, result.height), (Some(10), Some(10)));
     }
 }
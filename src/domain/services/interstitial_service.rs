@@ -0,0 +1,145 @@
+use crate::domain::entities::{PreviewMode, Url};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Minutes a visitor has to confirm the preview page before it must be reloaded
+pub const CONFIRMATION_EXPIRATION_MINUTES: i64 = 10;
+
+/// Decides when visitors see the preview interstitial and signs their confirmations
+///
+/// A confirmation token is `<expiry>.<signature>`, where the signature is an HMAC over the
+/// short code and the expiry, so it cannot be reused for another link or after it expires.
+#[derive(Clone)]
+pub struct InterstitialService {
+    signing_key: Vec<u8>,
+    require_preview_for_unverified: bool,
+}
+
+impl InterstitialService {
+    pub fn new(signing_key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            signing_key: signing_key.into(),
+            require_preview_for_unverified: false,
+        }
+    }
+
+    /// Show the interstitial for every link without a verified owner, whatever its mode
+    pub fn with_require_preview_for_unverified(mut self, require: bool) -> Self {
+        self.require_preview_for_unverified = require;
+        self
+    }
+
+    /// Whether visitors of this URL must confirm before being redirected
+    pub fn requires_preview(&self, url: &Url) -> bool {
+        match url.preview_mode {
+            PreviewMode::Always => true,
+            PreviewMode::HighRisk => !url.is_verified(),
+            PreviewMode::None => self.require_preview_for_unverified && !url.is_verified(),
+        }
+    }
+
+    /// Issue a token the preview page posts back to continue to the destination
+    pub fn confirmation_token(&self, short_code: &str) -> String {
+        let expires_at =
+            (Utc::now() + Duration::minutes(CONFIRMATION_EXPIRATION_MINUTES)).timestamp();
+        format!("{}.{}", expires_at, self.sign(short_code, expires_at))
+    }
+
+    /// Check a token issued for this short code that has not expired yet
+    pub fn verify_confirmation(&self, short_code: &str, token: &str) -> bool {
+        let Some((expires_at, signature)) = token.split_once('.') else {
+            return false;
+        };
+        let Ok(expires_at) = expires_at.parse::<i64>() else {
+            return false;
+        };
+        let Ok(signature) = URL_SAFE_NO_PAD.decode(signature) else {
+            return false;
+        };
+        if expires_at < Utc::now().timestamp() {
+            return false;
+        }
+
+        self.mac(short_code, expires_at)
+            .verify_slice(&signature)
+            .is_ok()
+    }
+
+    fn sign(&self, short_code: &str, expires_at: i64) -> String {
+        URL_SAFE_NO_PAD.encode(self.mac(short_code, expires_at).finalize().into_bytes())
+    }
+
+    fn mac(&self, short_code: &str, expires_at: i64) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.signing_key).expect("HMAC accepts keys of any length");
+        mac.update(short_code.as_bytes());
+        mac.update(b".");
+        mac.update(expires_at.to_string().as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::UrlStatus;
+
+    fn url(preview_mode: PreviewMode, user_id: Option<i32>) -> Url {
+        let mut url = Url::new_with_timestamp(
+            1,
+            "abc123".to_string(),
+            "https://example.com".to_string(),
+            None,
+            user_id,
+            UrlStatus::Active,
+        );
+        url.preview_mode = preview_mode;
+        url
+    }
+
+    #[test]
+    fn test_requires_preview() {
+        let service = InterstitialService::new("secret");
+        assert!(!service.requires_preview(&url(PreviewMode::None, None)));
+        assert!(service.requires_preview(&url(PreviewMode::Always, Some(1))));
+        assert!(service.requires_preview(&url(PreviewMode::HighRisk, None)));
+        assert!(!service.requires_preview(&url(PreviewMode::HighRisk, Some(1))));
+
+        let service = service.with_require_preview_for_unverified(true);
+        assert!(service.requires_preview(&url(PreviewMode::None, None)));
+        assert!(!service.requires_preview(&url(PreviewMode::None, Some(1))));
+    }
+
+    #[test]
+    fn test_confirmation_token_round_trip() {
+        let service = InterstitialService::new("secret");
+        let token = service.confirmation_token("abc123");
+
+        assert!(service.verify_confirmation("abc123", &token));
+        assert!(!service.verify_confirmation("other", &token));
+        assert!(!InterstitialService::new("other-secret").verify_confirmation("abc123", &token));
+        assert!(!service.verify_confirmation("abc123", "garbage"));
+    }
+
+    #[test]
+    fn test_tampered_or_expired_token_rejected() {
+        let service = InterstitialService::new("secret");
+        let token = service.confirmation_token("abc123");
+        let (expires_at, signature) = token.split_once('.').unwrap();
+
+        let extended = format!(
+            "{}.{}",
+            expires_at.parse::<i64>().unwrap() + 3600,
+            signature
+        );
+        assert!(!service.verify_confirmation("abc123", &extended));
+
+        let expired_at = Utc::now().timestamp() - 1;
+        let expired = format!("{}.{}", expired_at, service.sign("abc123", expired_at));
+        assert!(!service.verify_confirmation("abc123", &expired));
+    }
+}
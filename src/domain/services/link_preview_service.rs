@@ -0,0 +1,273 @@
+use crate::domain::entities::UrlMetadata;
+use crate::domain::repositories::url_metadata_repository::RepositoryError;
+use crate::domain::repositories::UrlMetadataRepository;
+use chrono::Utc;
+use regex::Regex;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Give up on destination pages that take longer than this to answer
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Only the start of a page is read; Open Graph tags live in `<head>`
+const MAX_PAGE_BYTES: usize = 512 * 1024;
+
+/// Longest title and description kept, in characters
+const MAX_TITLE_CHARS: usize = 300;
+const MAX_DESCRIPTION_CHARS: usize = 1000;
+
+/// Link preview errors
+#[derive(Error, Debug)]
+pub enum LinkPreviewError {
+    #[error("Failed to fetch page: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Page is not HTML")]
+    NotHtml,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+}
+
+/// Domain service fetching link preview metadata of destination pages
+#[derive(Clone)]
+pub struct LinkPreviewService {
+    repository: Arc<dyn UrlMetadataRepository>,
+    client: reqwest::Client,
+}
+
+impl LinkPreviewService {
+    pub fn new(repository: Arc<dyn UrlMetadataRepository>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::limited(3))
+            .user_agent("url-shortener-preview/1.0")
+            .build()
+            .expect("HTTP client configuration is valid");
+        Self { repository, client }
+    }
+
+    /// Fetch a URL's destination page and store its metadata
+    pub async fn fetch_and_store(
+        &self,
+        url_id: i32,
+        page_url: &str,
+    ) -> Result<UrlMetadata, LinkPreviewError> {
+        let html = self.fetch_page(page_url).await?;
+        let metadata = parse_metadata(url_id, &html, page_url);
+        self.repository.save_metadata(&metadata).await?;
+        Ok(metadata)
+    }
+
+    /// Metadata fetched for a URL, if any
+    pub async fn get_metadata(&self, url_id: i32) -> Result<Option<UrlMetadata>, LinkPreviewError> {
+        Ok(self.repository.find_by_url_id(url_id).await?)
+    }
+
+    /// Download the start of an HTML page
+    async fn fetch_page(&self, page_url: &str) -> Result<String, LinkPreviewError> {
+        let mut response = self.client.get(page_url).send().await?.error_for_status()?;
+
+        let is_html = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.to_ascii_lowercase().contains("html"));
+        if !is_html {
+            return Err(LinkPreviewError::NotHtml);
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            body.extend_from_slice(&chunk);
+            if body.len() >= MAX_PAGE_BYTES {
+                body.truncate(MAX_PAGE_BYTES);
+                break;
+            }
+        }
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+}
+
+fn meta_tag_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"(?is)<(meta|link)\b([^>]*)>").unwrap())
+}
+
+fn attribute_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r#"(?is)([a-z:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).unwrap()
+    })
+}
+
+fn title_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap())
+}
+
+/// Extract preview metadata from a page
+///
+/// Open Graph tags win over the plain `<title>` and description meta tags. Image and favicon
+/// URLs are resolved against the page URL; the favicon defaults to `/favicon.ico`.
+pub fn parse_metadata(url_id: i32, html: &str, page_url: &str) -> UrlMetadata {
+    let mut og_title = None;
+    let mut og_description = None;
+    let mut og_image = None;
+    let mut description = None;
+    let mut favicon = None;
+
+    for tag in meta_tag_regex().captures_iter(html) {
+        let attributes: Vec<(String, String)> = attribute_regex()
+            .captures_iter(&tag[2])
+            .map(|a| {
+                let value = a
+                    .get(2)
+                    .or(a.get(3))
+                    .or(a.get(4))
+                    .map_or("", |v| v.as_str());
+                (a[1].to_ascii_lowercase(), decode_entities(value))
+            })
+            .collect();
+        let attribute = |name: &str| {
+            attributes
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+        };
+
+        if tag[1].eq_ignore_ascii_case("link") {
+            let is_icon = attribute("rel").is_some_and(|rel| {
+                rel.split_whitespace()
+                    .any(|r| r.eq_ignore_ascii_case("icon"))
+            });
+            if is_icon && favicon.is_none() {
+                favicon = attribute("href");
+            }
+            continue;
+        }
+
+        let Some(content) = attribute("content").filter(|c| !c.trim().is_empty()) else {
+            continue;
+        };
+        let key = attribute("property")
+            .or_else(|| attribute("name"))
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let slot = match key.as_str() {
+            "og:title" => &mut og_title,
+            "og:description" => &mut og_description,
+            "og:image" => &mut og_image,
+            "description" => &mut description,
+            _ => continue,
+        };
+        slot.get_or_insert(content);
+    }
+
+    let title = og_title.or_else(|| {
+        title_regex()
+            .captures(html)
+            .map(|c| decode_entities(&c[1]))
+            .filter(|t| !t.trim().is_empty())
+    });
+
+    UrlMetadata {
+        url_id,
+        title: title.map(|t| clean_text(&t, MAX_TITLE_CHARS)),
+        description: og_description
+            .or(description)
+            .map(|d| clean_text(&d, MAX_DESCRIPTION_CHARS)),
+        image_url: og_image.and_then(|i| resolve_url(page_url, &i)),
+        favicon_url: resolve_url(page_url, favicon.as_deref().unwrap_or("/favicon.ico")),
+        fetched_at: Utc::now(),
+    }
+}
+
+/// Collapse whitespace and cut to `max_chars`
+fn clean_text(text: &str, max_chars: usize) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(max_chars)
+        .collect()
+}
+
+/// Resolve a possibly relative link against the page URL, keeping only web URLs
+fn resolve_url(page_url: &str, link: &str) -> Option<String> {
+    let resolved = url::Url::parse(page_url).ok()?.join(link.trim()).ok()?;
+    matches!(resolved.scheme(), "http" | "https").then(|| resolved.to_string())
+}
+
+/// Decode the HTML entities commonly found in attribute values and titles
+fn decode_entities(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_open_graph_tags() {
+        let html = r#"<html><head>
+            <title>Fallback title</title>
+            <meta property="og:title" content="Rust &amp; Friends">
+            <meta content='A blog about Rust' property='og:description'>
+            <meta property="og:image" content="/images/cover.png" />
+            <link rel="shortcut icon" href="https://cdn.example.com/icon.png">
+        </head></html>"#;
+
+        let metadata = parse_metadata(7, html, "https://example.com/posts/1");
+        assert_eq!(metadata.url_id, 7);
+        assert_eq!(metadata.title.as_deref(), Some("Rust & Friends"));
+        assert_eq!(metadata.description.as_deref(), Some("A blog about Rust"));
+        assert_eq!(
+            metadata.image_url.as_deref(),
+            Some("https://example.com/images/cover.png")
+        );
+        assert_eq!(
+            metadata.favicon_url.as_deref(),
+            Some("https://cdn.example.com/icon.png")
+        );
+    }
+
+    #[test]
+    fn test_parse_falls_back_to_plain_tags() {
+        let html = r#"<head><title>
+            Plain   title
+        </title><meta name="Description" content="Plain description"></head>"#;
+
+        let metadata = parse_metadata(1, html, "https://example.com/a/b");
+        assert_eq!(metadata.title.as_deref(), Some("Plain title"));
+        assert_eq!(metadata.description.as_deref(), Some("Plain description"));
+        assert_eq!(metadata.image_url, None);
+        assert_eq!(
+            metadata.favicon_url.as_deref(),
+            Some("https://example.com/favicon.ico")
+        );
+    }
+
+    #[test]
+    fn test_script_image_urls_dropped() {
+        let html = r#"<meta property="og:image" content="javascript:alert(1)">"#;
+        let metadata = parse_metadata(1, html, "https://example.com/");
+        assert_eq!(metadata.image_url, None);
+        assert_eq!(metadata.title, None);
+    }
+
+    #[test]
+    fn test_long_title_truncated() {
+        let html = format!("<title>{}</title>", "a".repeat(500));
+        let metadata = parse_metadata(1, &html, "https://example.com/");
+        assert_eq!(metadata.title.map(|t| t.len()), Some(MAX_TITLE_CHARS));
+    }
+}
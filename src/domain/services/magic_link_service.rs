@@ -0,0 +1,185 @@
+use crate::domain::entities::{MagicLinkToken, User};
+use crate::domain::repositories::user_repository::normalize_email;
+use crate::domain::repositories::{MagicLinkRepository, UserRepository};
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Minutes a magic link stays valid
+pub const MAGIC_LINK_EXPIRATION_MINUTES: i64 = 15;
+
+/// Length of the raw token sent in the magic link
+const MAGIC_LINK_TOKEN_LENGTH: usize = 48;
+
+/// Service for passwordless login through emailed one-time links
+pub struct MagicLinkService<M, U>
+where
+    M: MagicLinkRepository,
+    U: UserRepository,
+{
+    magic_link_repository: M,
+    user_repository: U,
+}
+
+/// Magic link service errors
+#[derive(Error, Debug)]
+pub enum MagicLinkError {
+    #[error("User not found")]
+    UserNotFound,
+
+    #[error("Invalid token")]
+    InvalidToken,
+
+    #[error("Token expired")]
+    TokenExpired,
+
+    #[error("Token already used")]
+    TokenAlreadyUsed,
+
+    #[error("Repository error: {0}")]
+    RepositoryError(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// A freshly issued magic link
+#[derive(Debug, Clone)]
+pub struct MagicLinkRequest {
+    pub user: User,
+    /// Raw token to embed in the link; never stored
+    pub token: String,
+    #[allow(dead_code)]
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl<M, U> MagicLinkService<M, U>
+where
+    M: MagicLinkRepository,
+    U: UserRepository,
+{
+    pub fn new(magic_link_repository: M, user_repository: U) -> Self {
+        Self {
+            magic_link_repository,
+            user_repository,
+        }
+    }
+
+    /// Hash a raw token for storage and lookup
+    pub fn hash_token(token: &str) -> String {
+        format!("{:x}", Sha256::digest(token.as_bytes()))
+    }
+
+    /// Issue a magic link for the account registered under `email`
+    ///
+    /// Works for every account, including ones that log in with a password.
+    pub async fn create_link(&self, email: &str) -> Result<MagicLinkRequest, MagicLinkError> {
+        let user = self
+            .user_repository
+            .find_by_email(&normalize_email(email))
+            .await
+            .map_err(|e| MagicLinkError::RepositoryError(Box::new(e)))?
+            .ok_or(MagicLinkError::UserNotFound)?;
+
+        let token: String = thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(MAGIC_LINK_TOKEN_LENGTH)
+            .map(char::from)
+            .collect();
+
+        let stored = self
+            .magic_link_repository
+            .create_token(MagicLinkToken::new(
+                user.id,
+                Self::hash_token(&token),
+                MAGIC_LINK_EXPIRATION_MINUTES,
+            ))
+            .await?;
+
+        Ok(MagicLinkRequest {
+            user,
+            token,
+            expires_at: stored.expires_at,
+        })
+    }
+
+    /// Consume a magic link token and return the user it logs in
+    pub async fn verify_link(&self, token: &str) -> Result<User, MagicLinkError> {
+        let magic_link = self
+            .magic_link_repository
+            .find_by_token_hash(&Self::hash_token(token))
+            .await?
+            .ok_or(MagicLinkError::InvalidToken)?;
+
+        if magic_link.used {
+            return Err(MagicLinkError::TokenAlreadyUsed);
+        }
+        if magic_link.is_expired() {
+            return Err(MagicLinkError::TokenExpired);
+        }
+        if !self.magic_link_repository.mark_used(magic_link.id).await? {
+            return Err(MagicLinkError::TokenAlreadyUsed);
+        }
+
+        self.user_repository
+            .find_by_id(magic_link.user_id)
+            .await
+            .map_err(|e| MagicLinkError::RepositoryError(Box::new(e)))?
+            .ok_or(MagicLinkError::UserNotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_utils::{MockMagicLinkRepository, MockUserRepository};
+
+    #[tokio::test]
+    async fn test_expired_link_is_rejected() {
+        let users = MockUserRepository::new();
+        let user = users
+            .create_user("alice", "alice@example.com", "hash")
+            .await
+            .unwrap();
+        let links = MockMagicLinkRepository::new();
+        links
+            .create_token(MagicLinkToken::new(
+                user.id,
+                MagicLinkService::<MockMagicLinkRepository, MockUserRepository>::hash_token(
+                    "expired",
+                ),
+                -1,
+            ))
+            .await
+            .unwrap();
+
+        let service = MagicLinkService::new(links, users);
+        assert!(matches!(
+            service.verify_link("expired").await,
+            Err(MagicLinkError::TokenExpired)
+        ));
+        assert!(matches!(
+            service.verify_link("unknown").await,
+            Err(MagicLinkError::InvalidToken)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_email() {
+        let service =
+            MagicLinkService::new(MockMagicLinkRepository::new(), MockUserRepository::new());
+        assert!(matches!(
+            service.create_link("nobody@example.com").await,
+            Err(MagicLinkError::UserNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_hash_token_is_stable_hex() {
+        let hash =
+            MagicLinkService::<MockMagicLinkRepository, MockUserRepository>::hash_token("abc");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(
+            hash,
+            MagicLinkService::<MockMagicLinkRepository, MockUserRepository>::hash_token("abc")
+        );
+    }
+}
@@ -1,25 +1,51 @@
+pub mod analytics_export_service;
 pub mod anonymization_service;
 pub mod auth_service;
+pub mod batch_operations;
 pub mod bulk_processor;
+pub mod bulk_queue;
 pub mod cleanup_service;
 pub mod click_tracking_service;
+pub mod data_export_service;
+pub mod domain_blacklist_service;
+pub mod email_change_service;
 pub mod file_upload_service;
+pub mod interstitial_service;
+pub mod link_preview_service;
+pub mod magic_link_service;
 pub mod notification_service;
+pub mod oauth_service;
+pub mod org_service;
 pub mod password_reset_service;
 pub mod privacy_service;
 pub mod profile_validation_service;
 pub mod progress_service;
+pub mod service_account_service;
+pub mod short_code_strategy;
 pub mod token_validation_service;
 pub mod url_service;
 
+pub use analytics_export_service::{AnalyticsExportError, AnalyticsExportService};
 pub use anonymization_service::AnonymizationService;
 pub use auth_service::{AuthService, ServiceError as AuthServiceError};
+pub use batch_operations::{batch_operation, BatchOperation};
 pub use bulk_processor::BulkProcessor;
+pub use data_export_service::{DataExportError, DataExportService};
+pub use domain_blacklist_service::{DomainBlacklist, DomainBlacklistError};
 pub use file_upload_service::{FileUploadError, FileUploadService};
+pub use interstitial_service::InterstitialService;
+pub use link_preview_service::LinkPreviewService;
+pub use magic_link_service::{MagicLinkError, MagicLinkService};
 pub use notification_service::NotificationService;
+pub use oauth_service::{OAuthClientConfig, OAuthError, OAuthService};
+pub use org_service::{OrgLimits, OrgService, OrgServiceError};
 pub use password_reset_service::{PasswordResetError, PasswordResetService};
-pub use privacy_service::{DataPrivacyLevel, PrivacyService};
+pub use privacy_service::{DataPrivacyLevel, PrivacyService, VisibilityRecommendation};
 pub use profile_validation_service::ProfileValidationService;
 pub use progress_service::{ProgressService, ProgressServiceError};
+pub use service_account_service::{ServiceAccountError, ServiceAccountService};
+pub use short_code_strategy::{
+    HashStrategy, RandomStrategy, SequentialStrategy, ShortCodeStrategy,
+};
 pub use token_validation_service::TokenValidationService;
-pub use url_service::{ServiceError, UrlService};
+pub use url_service::{DuplicateOverrides, ServiceError, UrlService};
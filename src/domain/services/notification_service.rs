@@ -1,17 +1,360 @@
 #![allow(dead_code)]
-use crate::domain::entities::Url;
+use crate::domain::entities::notification_preferences::DIGEST_MIN_INTERVAL_DAYS;
+use crate::domain::entities::{NotificationPreferences, Url, UrlStatus, User};
+use crate::domain::events::DomainEvent;
+use crate::domain::repositories::notification_preferences_repository::RepositoryError;
+use crate::domain::repositories::NotificationPreferencesRepository;
+use crate::infrastructure::email::{EmailMessage, EmailSender, ExpiringUrlInfo, ExpiryDigestEmail};
+use crate::infrastructure::push::{
+    bulk_operation_deep_link, url_deep_link, PushMessage, PushNotificationSender,
+};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::BTreeMap;
+use std::sync::Arc;
 use tracing::{info, warn};
 
+/// URLs expiring within this many days are listed in the digest
+pub const EXPIRY_DIGEST_WINDOW_DAYS: i64 = 14;
+
+/// Click counts a URL owner gets a push notification for
+pub const CLICK_MILESTONES: [i64; 3] = [100, 1_000, 10_000];
+
+/// Milestones reached when a URL's click count went from `total - added` to `total`
+pub fn crossed_click_milestones(total: i64, added: i64) -> impl Iterator<Item = i64> {
+    CLICK_MILESTONES
+        .into_iter()
+        .filter(move |&milestone| total - added < milestone && milestone <= total)
+}
+
+/// Which users an expiry digest run sends to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestSchedule {
+    /// Users whose digest day is today
+    Scheduled,
+    /// Every user with the digest enabled, whatever their digest day
+    Immediate,
+}
+
+/// Outcome of an expiry digest run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DigestRunSummary {
+    /// Users with URLs expiring within the digest window
+    pub recipients: usize,
+    /// Digests queued in the email outbox
+    pub sent: usize,
+    /// Digest disabled, not the user's digest day, or already sent
+    pub skipped: usize,
+    pub failed: usize,
+}
+
 /// Service for handling notifications and warnings
 #[derive(Clone, Default)]
-pub struct NotificationService;
+pub struct NotificationService {
+    email_sender: Option<Arc<dyn EmailSender>>,
+    push_sender: Option<Arc<dyn PushNotificationSender>>,
+    preferences_repository: Option<Arc<dyn NotificationPreferencesRepository>>,
+    /// Base URL of the UI, used for links in emails
+    base_url: String,
+}
 
 impl NotificationService {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Send emails through the given sender
+    pub fn with_email_sender(mut self, email_sender: Option<Arc<dyn EmailSender>>) -> Self {
+        self.email_sender = email_sender;
+        self
+    }
+
+    /// Send push notifications through the given sender
+    pub fn with_push_sender(
+        mut self,
+        push_sender: Option<Arc<dyn PushNotificationSender>>,
+    ) -> Self {
+        self.push_sender = push_sender;
+        self
+    }
+
+    /// Read and store per-user notification preferences in the given repository
+    pub fn with_preferences_repository(
+        mut self,
+        preferences_repository: Arc<dyn NotificationPreferencesRepository>,
+    ) -> Self {
+        self.preferences_repository = Some(preferences_repository);
+        self
+    }
+
+    /// Build links in emails from the given base URL
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Whether expiry digests can be sent, i.e. email and preferences are configured
+    pub fn can_send_digests(&self) -> bool {
+        self.email_sender.is_some() && self.preferences_repository.is_some()
+    }
+
+    fn preferences_repository(
+        &self,
+    ) -> Result<&Arc<dyn NotificationPreferencesRepository>, NotificationError> {
+        self.preferences_repository.as_ref().ok_or_else(|| {
+            NotificationError::Internal("Notification preferences are not configured".to_string())
+        })
+    }
+
+    /// Get a user's notification preferences
+    pub async fn get_preferences(
+        &self,
+        user_id: i32,
+    ) -> Result<NotificationPreferences, NotificationError> {
+        Ok(self
+            .preferences_repository()?
+            .find_by_user_id(user_id)
+            .await?)
+    }
+
+    /// Update a user's digest and push settings; `None` keeps the current value
+    pub async fn update_preferences(
+        &self,
+        user_id: i32,
+        digest_enabled: Option<bool>,
+        digest_day_of_week: Option<u8>,
+        push_enabled: Option<bool>,
+    ) -> Result<NotificationPreferences, NotificationError> {
+        if digest_day_of_week.is_some_and(|day| day > 6) {
+            return Err(NotificationError::InvalidPreferences(
+                "digest_day_of_week must be between 0 (Sunday) and 6 (Saturday)".to_string(),
+            ));
+        }
+
+        let repository = self.preferences_repository()?;
+        let mut preferences = repository.find_by_user_id(user_id).await?;
+        if let Some(enabled) = digest_enabled {
+            preferences.digest_enabled = enabled;
+        }
+        if let Some(day) = digest_day_of_week {
+            preferences.digest_day_of_week = day;
+        }
+        if let Some(enabled) = push_enabled {
+            preferences.push_enabled = enabled;
+        }
+
+        Ok(repository.save(&preferences).await?)
+    }
+
+    /// Email every owner of the given URLs a digest of those expiring within the digest window
+    ///
+    /// A user gets at most one digest per [`DIGEST_MIN_INTERVAL_DAYS`], however often this runs.
+    pub async fn send_expiry_digest_for_all_users(
+        &self,
+        expiring_urls: &[Url],
+        schedule: DigestSchedule,
+        now: DateTime<Utc>,
+    ) -> Result<DigestRunSummary, NotificationError> {
+        let repository = self.preferences_repository()?;
+        if self.email_sender.is_none() {
+            return Err(NotificationError::EmailService(
+                "Email is not configured".to_string(),
+            ));
+        }
+
+        let window_end = now + Duration::days(EXPIRY_DIGEST_WINDOW_DAYS);
+        let mut urls_by_user: BTreeMap<i32, Vec<&Url>> = BTreeMap::new();
+        for url in expiring_urls {
+            let expiring = url
+                .expiration_date
+                .is_some_and(|expires_at| expires_at > now && expires_at <= window_end);
+            if let (true, Some(user_id), UrlStatus::Active) = (expiring, url.user_id, &url.status) {
+                urls_by_user.entry(user_id).or_default().push(url);
+            }
+        }
+
+        let mut summary = DigestRunSummary::default();
+        if urls_by_user.is_empty() {
+            return Ok(summary);
+        }
+
+        let user_ids: Vec<i32> = urls_by_user.keys().copied().collect();
+        let recipients = repository.find_digest_recipients(&user_ids).await?;
+        summary.recipients = recipients.len();
+
+        for recipient in recipients {
+            let preferences = &recipient.preferences;
+            let due = preferences.digest_enabled
+                && (schedule == DigestSchedule::Immediate || preferences.is_digest_day(now))
+                && !preferences.digest_sent_recently(now);
+            if !due {
+                summary.skipped += 1;
+                continue;
+            }
+
+            let template = ExpiryDigestEmail {
+                username: recipient.username.clone(),
+                urls: urls_by_user[&preferences.user_id]
+                    .iter()
+                    .map(|url| self.digest_entry(url, now))
+                    .collect(),
+            };
+            let message = EmailMessage::from_template(recipient.email.clone(), &template);
+
+            // Claiming keeps a concurrent run from sending the same digest; the digest is
+            // queued in the same transaction, so a failed claim leaves the next run to retry
+            match repository
+                .claim_digest(
+                    preferences.user_id,
+                    now,
+                    now - Duration::days(DIGEST_MIN_INTERVAL_DAYS),
+                    &message,
+                )
+                .await
+            {
+                Ok(true) => summary.sent += 1,
+                Ok(false) => summary.skipped += 1,
+                Err(e) => {
+                    warn!(
+                        "Failed to queue expiry digest for user {}: {}",
+                        preferences.user_id, e
+                    );
+                    summary.failed += 1;
+                }
+            }
+        }
+
+        info!(
+            "Expiry digest: {} sent, {} skipped, {} failed",
+            summary.sent, summary.skipped, summary.failed
+        );
+        Ok(summary)
+    }
+
+    fn digest_entry(&self, url: &Url, now: DateTime<Utc>) -> ExpiringUrlInfo {
+        let days_remaining = url
+            .expiration_date
+            .map(|expires_at| (expires_at - now).num_days())
+            .unwrap_or_default();
+        ExpiringUrlInfo {
+            short_code: url.short_code.clone(),
+            original_url: url.original_url.clone(),
+            days_remaining,
+            extend_link: format!("{}/urls/{}/extend", self.base_url, url.short_code),
+        }
+    }
+
+    /// Email the previous and the new owner of a URL an administrator transferred
+    ///
+    /// Does nothing without an email sender; failures are logged and not returned, as the
+    /// transfer already happened.
+    pub async fn send_url_transfer_notifications(
+        &self,
+        url: &Url,
+        previous_owner: &User,
+        new_owner: &User,
+    ) {
+        let Some(email_sender) = &self.email_sender else {
+            return;
+        };
+        for (user, received) in [(previous_owner, false), (new_owner, true)] {
+            let message = EmailMessage::url_transferred(
+                user.email.clone(),
+                &url.short_code,
+                &url.original_url,
+                received,
+            );
+            if let Err(e) = email_sender.send_email(message).await {
+                warn!(
+                    "Failed to notify user {} of the transfer of URL {}: {}",
+                    user.id, url.id, e
+                );
+            }
+        }
+    }
+
+    /// Push a notification to every device of a user who has pushes enabled
+    ///
+    /// Returns the number of devices notified. Does nothing without a push sender or
+    /// preferences repository; `dedup_key` keeps a device from getting the same push twice.
+    async fn send_push(
+        &self,
+        user_id: i32,
+        title: String,
+        body: String,
+        deep_link: String,
+        dedup_key: String,
+    ) -> Result<usize, NotificationError> {
+        let (Some(push_sender), Some(repository)) =
+            (&self.push_sender, &self.preferences_repository)
+        else {
+            return Ok(0);
+        };
+        if !repository.find_by_user_id(user_id).await?.push_enabled {
+            return Ok(0);
+        }
+
+        let device_tokens = repository.find_device_tokens(user_id).await?;
+        for device_token in &device_tokens {
+            let message = PushMessage {
+                user_id,
+                device_token: device_token.clone(),
+                title: title.clone(),
+                body: body.clone(),
+                deep_link: deep_link.clone(),
+                dedup_key: Some(dedup_key.clone()),
+            };
+            push_sender
+                .send_push(message)
+                .await
+                .map_err(|e| NotificationError::PushNotification(e.to_string()))?;
+        }
+        Ok(device_tokens.len())
+    }
+
+    /// Tell the owner of a URL it reached `milestone` clicks
+    pub async fn notify_click_milestone(
+        &self,
+        user_id: i32,
+        url_id: i32,
+        short_code: &str,
+        milestone: i64,
+    ) -> Result<usize, NotificationError> {
+        self.send_push(
+            user_id,
+            format!("{} clicks!", milestone),
+            format!("Your link {} reached {} clicks", short_code, milestone),
+            url_deep_link(url_id),
+            format!("milestone:{}:{}", url_id, milestone),
+        )
+        .await
+    }
+
+    /// Tell a user their bulk operation finished
+    pub async fn notify_bulk_operation_completed(
+        &self,
+        user_id: i32,
+        operation_id: &str,
+        succeeded: usize,
+        failed: usize,
+    ) -> Result<usize, NotificationError> {
+        let body = if failed == 0 {
+            format!("All {} items were processed", succeeded)
+        } else {
+            format!("{} items processed, {} failed", succeeded, failed)
+        };
+        self.send_push(
+            user_id,
+            "Bulk operation completed".to_string(),
+            body,
+            bulk_operation_deep_link(operation_id),
+            format!("bulk:{}", operation_id),
+        )
+        .await
     }
 
     /// Send expiration warning for a URL
+    ///
+    /// The owner gets a push notification once per expiration date, however often this runs.
     pub async fn send_expiration_warning(
         &self,
         url: &Url,
@@ -22,15 +365,34 @@ impl NotificationService {
             url.short_code, days_until_expiry, url.expiration_date
         );
 
-        // TODO: In a real implementation, this would:
-        // 1. Send email to the user who created the URL
-        // 2. Send push notification if user has mobile app
-        // 3. Send webhook notification to external systems
-        // 4. Log to monitoring systems
+        if let (Some(user_id), Some(expires_at)) = (url.user_id, url.expiration_date) {
+            let body = match days_until_expiry {
+                0 => format!("Your link {} expires today", url.short_code),
+                1 => format!("Your link {} expires tomorrow", url.short_code),
+                days => format!("Your link {} expires in {} days", url.short_code, days),
+            };
+            self.send_push(
+                user_id,
+                "Link about to expire".to_string(),
+                body,
+                url_deep_link(url.id),
+                format!("expiry:{}:{}", url.id, expires_at.timestamp()),
+            )
+            .await?;
+        }
 
         Ok(())
     }
 
+    /// React to an event published on the event bus
+    pub async fn handle_event(&self, event: DomainEvent) {
+        if let DomainEvent::UrlExpired { url } = event {
+            if let Err(e) = self.send_expiration_notification(&url).await {
+                warn!("Failed to notify the expiry of {}: {}", url.short_code, e);
+            }
+        }
+    }
+
     /// Send notification that a URL has expired
     pub async fn send_expiration_notification(&self, url: &Url) -> Result<(), NotificationError> {
         warn!(
@@ -72,6 +434,12 @@ pub enum NotificationError {
     #[error("Webhook error: {0}")]
     Webhook(String),
 
+    #[error("Invalid notification preferences: {0}")]
+    InvalidPreferences(String),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+
     #[error("Internal notification error: {0}")]
     Internal(String),
 }
@@ -79,8 +447,10 @@ pub enum NotificationError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::entities::UrlStatus;
-    use chrono::Utc;
+    use crate::infrastructure::test_utils::{
+        MockEmailSender, MockNotificationPreferencesRepository, MockPushSender,
+    };
+    use chrono::Datelike;
 
     #[tokio::test]
     async fn test_send_expiration_warning() {
@@ -139,4 +509,181 @@ mod tests {
         let result = service.send_bulk_expiration_warnings(&urls, 2).await;
         assert!(result.is_ok());
     }
+
+    fn digest_service(
+        repository: &MockNotificationPreferencesRepository,
+        email_sender: &MockEmailSender,
+    ) -> NotificationService {
+        NotificationService::new()
+            .with_preferences_repository(Arc::new(repository.clone()))
+            .with_email_sender(Some(Arc::new(email_sender.clone())))
+            .with_base_url("https://short.ly")
+    }
+
+    fn expiring_url(id: i32, user_id: i32, days: i64, now: DateTime<Utc>) -> Url {
+        Url::new_with_timestamp(
+            id,
+            format!("code{}", id),
+            format!("https://example.com/{}", id),
+            Some(now + Duration::days(days) + Duration::hours(1)),
+            Some(user_id),
+            UrlStatus::Active,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_expiry_digest_is_sent_once() {
+        let repository = MockNotificationPreferencesRepository::new();
+        repository.add_user(1, "one@example.com");
+        repository.add_user(2, "two@example.com");
+        let email_sender = MockEmailSender::new();
+        let service = digest_service(&repository, &email_sender);
+
+        let now = Utc::now();
+        let urls = vec![
+            expiring_url(1, 1, 3, now),
+            expiring_url(2, 1, 10, now),
+            expiring_url(3, 2, 1, now),
+            // Outside the digest window
+            expiring_url(4, 2, 30, now),
+        ];
+
+        let summary = service
+            .send_expiry_digest_for_all_users(&urls, DigestSchedule::Immediate, now)
+            .await
+            .unwrap();
+        assert_eq!(summary.recipients, 2);
+        assert_eq!(summary.sent, 2);
+
+        let queued = repository.outbox().emails();
+        let first = queued
+            .iter()
+            .find(|m| m.recipient == "one@example.com")
+            .unwrap();
+        assert!(first.body_text.contains("code1"));
+        assert!(first
+            .body_text
+            .contains("https://short.ly/urls/code2/extend"));
+        let second = queued
+            .iter()
+            .find(|m| m.recipient == "two@example.com")
+            .unwrap();
+        assert!(!second.body_text.contains("code4"));
+
+        // Running again in the same week sends nothing
+        let summary = service
+            .send_expiry_digest_for_all_users(&urls, DigestSchedule::Immediate, now)
+            .await
+            .unwrap();
+        assert_eq!(summary.sent, 0);
+        assert_eq!(summary.skipped, 2);
+        assert_eq!(repository.outbox().emails().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_digest_respects_preferences() {
+        let repository = MockNotificationPreferencesRepository::new();
+        repository.add_user(1, "one@example.com");
+        repository.add_user(2, "two@example.com");
+        let email_sender = MockEmailSender::new();
+        let service = digest_service(&repository, &email_sender);
+
+        let now = Utc::now();
+        let today = now.weekday().num_days_from_sunday() as u8;
+        service
+            .update_preferences(1, None, Some(today), None)
+            .await
+            .unwrap();
+        service
+            .update_preferences(2, Some(false), Some(today), None)
+            .await
+            .unwrap();
+
+        let urls = vec![expiring_url(1, 1, 2, now), expiring_url(2, 2, 2, now)];
+        let summary = service
+            .send_expiry_digest_for_all_users(&urls, DigestSchedule::Scheduled, now)
+            .await
+            .unwrap();
+        assert_eq!(summary.sent, 1);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(repository.outbox().emails()[0].recipient, "one@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_digest_is_queued_while_the_mail_server_is_down() {
+        let repository = MockNotificationPreferencesRepository::new();
+        repository.add_user(1, "one@example.com");
+        let email_sender = MockEmailSender::new();
+        let service = digest_service(&repository, &email_sender);
+        let now = Utc::now();
+        let urls = vec![expiring_url(1, 1, 2, now)];
+
+        // Sending is left to the outbox poller, which retries failed emails
+        email_sender.set_failing(true);
+        let summary = service
+            .send_expiry_digest_for_all_users(&urls, DigestSchedule::Immediate, now)
+            .await
+            .unwrap();
+        assert_eq!(summary.sent, 1);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(repository.outbox().emails().len(), 1);
+        assert!(email_sender.sent().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_preferences_rejects_invalid_day() {
+        let repository = MockNotificationPreferencesRepository::new();
+        let service = digest_service(&repository, &MockEmailSender::new());
+
+        let result = service.update_preferences(1, None, Some(7), None).await;
+        assert!(matches!(
+            result,
+            Err(NotificationError::InvalidPreferences(_))
+        ));
+    }
+
+    #[test]
+    fn test_crossed_click_milestones() {
+        assert_eq!(crossed_click_milestones(99, 99).count(), 0);
+        assert_eq!(crossed_click_milestones(100, 1).collect::<Vec<_>>(), [100]);
+        assert_eq!(crossed_click_milestones(150, 40).count(), 0);
+        assert_eq!(
+            crossed_click_milestones(1_200, 1_150).collect::<Vec<_>>(),
+            [100, 1_000]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_push_goes_to_every_device_unless_disabled() {
+        let repository = MockNotificationPreferencesRepository::new();
+        repository.add_device_token(1, "phone");
+        repository.add_device_token(1, "tablet");
+        let push_sender = MockPushSender::new();
+        let service = NotificationService::new()
+            .with_preferences_repository(Arc::new(repository.clone()))
+            .with_push_sender(Some(Arc::new(push_sender.clone())));
+
+        let notified = service
+            .notify_bulk_operation_completed(1, "op-1", 10, 2)
+            .await
+            .unwrap();
+        assert_eq!(notified, 2);
+        let sent = push_sender.sent();
+        assert_eq!(sent[0].deep_link, "shortener://bulk-operations/op-1");
+        assert_eq!(sent[0].body, "10 items processed, 2 failed");
+        assert_eq!(sent[1].device_token, "tablet");
+
+        service
+            .update_preferences(1, None, None, Some(false))
+            .await
+            .unwrap();
+        let url = expiring_url(7, 1, 2, Utc::now());
+        service.send_expiration_warning(&url, 2).await.unwrap();
+        let notified = service
+            .notify_click_milestone(1, 7, "code7", 100)
+            .await
+            .unwrap();
+        assert_eq!(notified, 0);
+        assert_eq!(push_sender.sent().len(), 2);
+    }
 }
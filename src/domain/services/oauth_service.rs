@@ -0,0 +1,717 @@
+use crate::domain::entities::{OAuthProvider, User};
+use crate::domain::repositories::user_repository::{normalize_email, RepositoryError};
+use crate::domain::repositories::UserRepository;
+use chrono::{DateTime, Duration, Utc};
+use oauth2::basic::BasicClient;
+use oauth2::{
+    AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, EndpointNotSet, EndpointSet,
+    PkceCodeChallenge, PkceCodeVerifier, RedirectUrl, Scope, TokenResponse, TokenUrl,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// Minutes a started login may take before its state token expires
+pub const OAUTH_STATE_EXPIRATION_MINUTES: i64 = 10;
+
+/// Give up on provider requests that take longer than this
+const PROVIDER_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Longest username derived from a provider profile, leaving room for a numeric suffix
+const MAX_DERIVED_USERNAME_LEN: usize = 40;
+
+/// Longest stored name part and avatar URL, matching the `users` columns
+const MAX_NAME_LEN: usize = 100;
+const MAX_AVATAR_URL_LEN: usize = 500;
+
+/// Client with the authorization and token endpoints set
+type ConfiguredClient =
+    BasicClient<EndpointSet, EndpointNotSet, EndpointNotSet, EndpointNotSet, EndpointSet>;
+
+/// Endpoints of an OAuth2 provider
+#[derive(Debug, Clone)]
+pub struct OAuthEndpoints {
+    pub authorize_url: String,
+    pub token_url: String,
+    /// Returns the signed-in user's profile
+    pub userinfo_url: String,
+    /// Lists the user's email addresses with their verification status (GitHub only)
+    pub emails_url: Option<String>,
+}
+
+impl OAuthEndpoints {
+    /// Public endpoints of a provider
+    pub fn for_provider(provider: OAuthProvider) -> Self {
+        match provider {
+            OAuthProvider::Google => Self {
+                authorize_url: "https://accounts.google.com/o/oauth2/v2/auth".to_string(),
+                token_url: "https://oauth2.googleapis.com/token".to_string(),
+                userinfo_url: "https://openidconnect.googleapis.com/v1/userinfo".to_string(),
+                emails_url: None,
+            },
+            OAuthProvider::GitHub => Self {
+                authorize_url: "https://github.com/login/oauth/authorize".to_string(),
+                token_url: "https://github.com/login/oauth/access_token".to_string(),
+                userinfo_url: "https://api.github.com/user".to_string(),
+                emails_url: Some("https://api.github.com/user/emails".to_string()),
+            },
+        }
+    }
+}
+
+/// OAuth2 client registered with a provider
+#[derive(Debug, Clone)]
+pub struct OAuthClientConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub endpoints: OAuthEndpoints,
+}
+
+impl OAuthClientConfig {
+    /// Client using the provider's public endpoints
+    pub fn new(provider: OAuthProvider, client_id: String, client_secret: String) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            endpoints: OAuthEndpoints::for_provider(provider),
+        }
+    }
+}
+
+/// Scopes needed to read the user's profile and verified email
+fn scopes(provider: OAuthProvider) -> &'static [&'static str] {
+    match provider {
+        OAuthProvider::Google => &["openid", "email", "profile"],
+        OAuthProvider::GitHub => &["read:user", "user:email"],
+    }
+}
+
+/// OAuth service errors
+#[derive(Error, Debug)]
+pub enum OAuthError {
+    #[error("Login with {0} is not configured")]
+    ProviderNotConfigured(OAuthProvider),
+
+    #[error("Invalid or expired login state")]
+    InvalidState,
+
+    #[error("Failed to exchange authorization code: {0}")]
+    TokenExchange(String),
+
+    #[error("Failed to fetch profile: {0}")]
+    Profile(String),
+
+    #[error("Provider did not return a verified email address")]
+    EmailUnavailable,
+
+    #[error("Could not find a free username")]
+    UsernameUnavailable,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+}
+
+/// The user's profile at a provider
+#[derive(Debug, Clone, PartialEq)]
+pub struct OAuthProfile {
+    pub provider_id: String,
+    /// Verified email address
+    pub email: String,
+    pub name: Option<String>,
+    pub avatar_url: Option<String>,
+    /// Username the provider knows the user by, if any (GitHub login)
+    pub login: Option<String>,
+}
+
+/// Authorization URL of a started login
+#[derive(Debug, Clone)]
+pub struct OAuthAuthorization {
+    pub url: String,
+    /// CSRF token the provider echoes back to the callback
+    pub state: String,
+}
+
+/// Outcome of a completed social login
+#[derive(Debug, Clone)]
+pub struct OAuthLogin {
+    pub user: User,
+    /// Whether a new account was created for the provider profile
+    pub created: bool,
+}
+
+/// A started login waiting for the provider's callback
+#[derive(Debug)]
+struct PendingLogin {
+    provider: OAuthProvider,
+    pkce_verifier: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Service for logging in through Google and GitHub (OAuth2 authorization code flow)
+///
+/// Started logins are kept in memory until their callback arrives, so a callback must reach
+/// the instance that issued its state.
+#[derive(Clone)]
+pub struct OAuthService {
+    user_repository: Arc<dyn UserRepository>,
+    clients: HashMap<OAuthProvider, OAuthClientConfig>,
+    base_url: String,
+    pending: Arc<Mutex<HashMap<String, PendingLogin>>>,
+    http_client: reqwest::Client,
+}
+
+impl OAuthService {
+    pub fn new(user_repository: Arc<dyn UserRepository>, base_url: String) -> Self {
+        // Following redirects from the token endpoint would leak the authorization code
+        let http_client = reqwest::Client::builder()
+            .timeout(PROVIDER_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .user_agent("url-shortener-oauth/1.0")
+            .build()
+            .expect("HTTP client configuration is valid");
+        Self {
+            user_repository,
+            clients: HashMap::new(),
+            base_url,
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            http_client,
+        }
+    }
+
+    /// Enable login with a provider
+    pub fn with_client(mut self, provider: OAuthProvider, client: OAuthClientConfig) -> Self {
+        self.clients.insert(provider, client);
+        self
+    }
+
+    /// Callback URL registered with the provider
+    pub fn redirect_url(&self, provider: OAuthProvider) -> String {
+        format!(
+            "{}/auth/oauth/{}/callback",
+            self.base_url.trim_end_matches('/'),
+            provider
+        )
+    }
+
+    /// Start a login: remember a fresh state and PKCE verifier and build the provider URL
+    pub fn start_login(&self, provider: OAuthProvider) -> Result<OAuthAuthorization, OAuthError> {
+        let client = self.client(provider)?;
+        let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+
+        let mut request = client
+            .authorize_url(CsrfToken::new_random)
+            .set_pkce_challenge(pkce_challenge);
+        for scope in scopes(provider) {
+            request = request.add_scope(Scope::new(scope.to_string()));
+        }
+        let (url, state) = request.url();
+
+        let now = Utc::now();
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, login| login.expires_at > now);
+        pending.insert(
+            state.secret().clone(),
+            PendingLogin {
+                provider,
+                pkce_verifier: pkce_verifier.secret().clone(),
+                expires_at: now + Duration::minutes(OAUTH_STATE_EXPIRATION_MINUTES),
+            },
+        );
+
+        Ok(OAuthAuthorization {
+            url: url.to_string(),
+            state: state.secret().clone(),
+        })
+    }
+
+    /// Finish a login from the provider's callback and return the user it logs in
+    ///
+    /// Each state is accepted once, for the provider it was issued for.
+    pub async fn complete_login(
+        &self,
+        provider: OAuthProvider,
+        code: &str,
+        state: &str,
+    ) -> Result<OAuthLogin, OAuthError> {
+        let pending = self
+            .pending
+            .lock()
+            .unwrap()
+            .remove(state)
+            .filter(|login| login.provider == provider && login.expires_at > Utc::now())
+            .ok_or(OAuthError::InvalidState)?;
+
+        let token = self
+            .client(provider)?
+            .exchange_code(AuthorizationCode::new(code.to_string()))
+            .set_pkce_verifier(PkceCodeVerifier::new(pending.pkce_verifier))
+            .request_async(&self.http_client)
+            .await
+            .map_err(|e| OAuthError::TokenExchange(e.to_string()))?;
+
+        let profile = self
+            .fetch_profile(provider, token.access_token().secret())
+            .await?;
+        self.find_or_create_user(provider, &profile).await
+    }
+
+    /// Log in the user linked to a provider profile, linking or creating an account if needed
+    ///
+    /// An existing account with the same email (with or without a password) is linked rather
+    /// than duplicated.
+    pub async fn find_or_create_user(
+        &self,
+        provider: OAuthProvider,
+        profile: &OAuthProfile,
+    ) -> Result<OAuthLogin, OAuthError> {
+        if let Some(user) = self
+            .user_repository
+            .find_by_oauth_account(provider, &profile.provider_id)
+            .await?
+        {
+            return Ok(OAuthLogin {
+                user,
+                created: false,
+            });
+        }
+
+        let email = normalize_email(&profile.email);
+        if let Some(user) = self.user_repository.find_by_email(&email).await? {
+            let user = self
+                .user_repository
+                .link_oauth_account(user.id, provider, &profile.provider_id)
+                .await?;
+            return Ok(OAuthLogin {
+                user,
+                created: false,
+            });
+        }
+
+        let username = self.free_username(profile).await?;
+        let user = self
+            .user_repository
+            .create_oauth_user(&username, &email, provider, &profile.provider_id)
+            .await?;
+
+        let (first_name, last_name) = split_name(profile.name.as_deref());
+        let avatar_url = profile
+            .avatar_url
+            .as_deref()
+            .filter(|url| url.len() <= MAX_AVATAR_URL_LEN);
+        let user = if first_name.is_some() || avatar_url.is_some() {
+            self.user_repository
+                .update_profile(
+                    user.id,
+                    first_name.as_deref(),
+                    last_name.as_deref(),
+                    None,
+                    avatar_url,
+                    None,
+                    None,
+                    None,
+                )
+                .await?
+        } else {
+            user
+        };
+
+        Ok(OAuthLogin {
+            user,
+            created: true,
+        })
+    }
+
+    fn client(&self, provider: OAuthProvider) -> Result<ConfiguredClient, OAuthError> {
+        let config = self
+            .clients
+            .get(&provider)
+            .ok_or(OAuthError::ProviderNotConfigured(provider))?;
+        let invalid = |e: url::ParseError| OAuthError::TokenExchange(e.to_string());
+
+        Ok(BasicClient::new(ClientId::new(config.client_id.clone()))
+            .set_client_secret(ClientSecret::new(config.client_secret.clone()))
+            .set_auth_uri(AuthUrl::new(config.endpoints.authorize_url.clone()).map_err(invalid)?)
+            .set_token_uri(TokenUrl::new(config.endpoints.token_url.clone()).map_err(invalid)?)
+            .set_redirect_uri(RedirectUrl::new(self.redirect_url(provider)).map_err(invalid)?))
+    }
+
+    /// Fetch the user's profile and verified email from the provider
+    async fn fetch_profile(
+        &self,
+        provider: OAuthProvider,
+        access_token: &str,
+    ) -> Result<OAuthProfile, OAuthError> {
+        let endpoints = &self
+            .clients
+            .get(&provider)
+            .ok_or(OAuthError::ProviderNotConfigured(provider))?
+            .endpoints;
+
+        match provider {
+            OAuthProvider::Google => {
+                let info: GoogleUserInfo =
+                    self.get_json(&endpoints.userinfo_url, access_token).await?;
+                let email = info
+                    .email
+                    .filter(|_| info.email_verified)
+                    .ok_or(OAuthError::EmailUnavailable)?;
+                Ok(OAuthProfile {
+                    provider_id: info.sub,
+                    email,
+                    name: info.name,
+                    avatar_url: info.picture,
+                    login: None,
+                })
+            }
+            OAuthProvider::GitHub => {
+                let user: GitHubUser = self.get_json(&endpoints.userinfo_url, access_token).await?;
+                // The profile email may be missing or unverified; the email list says which
+                let emails_url = endpoints
+                    .emails_url
+                    .as_deref()
+                    .ok_or(OAuthError::EmailUnavailable)?;
+                let emails: Vec<GitHubEmail> = self.get_json(emails_url, access_token).await?;
+                let email = emails
+                    .iter()
+                    .filter(|e| e.verified)
+                    .max_by_key(|e| e.primary)
+                    .map(|e| e.email.clone())
+                    .ok_or(OAuthError::EmailUnavailable)?;
+                Ok(OAuthProfile {
+                    provider_id: user.id.to_string(),
+                    email,
+                    name: user.name,
+                    avatar_url: user.avatar_url,
+                    login: Some(user.login),
+                })
+            }
+        }
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        access_token: &str,
+    ) -> Result<T, OAuthError> {
+        let body = self
+            .http_client
+            .get(url)
+            .bearer_auth(access_token)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| OAuthError::Profile(e.to_string()))?
+            .bytes()
+            .await
+            .map_err(|e| OAuthError::Profile(e.to_string()))?;
+        serde_json::from_slice(&body).map_err(|e| OAuthError::Profile(e.to_string()))
+    }
+
+    /// Username for a new account: the provider login or the email's local part, with a
+    /// numeric suffix if it is taken
+    async fn free_username(&self, profile: &OAuthProfile) -> Result<String, OAuthError> {
+        let source = profile
+            .login
+            .as_deref()
+            .unwrap_or_else(|| profile.email.split('@').next().unwrap_or_default());
+        let mut base: String = source
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+            .take(MAX_DERIVED_USERNAME_LEN)
+            .collect();
+        if base.len() < 3 {
+            base = format!("user{}", base);
+        }
+
+        if !self.user_repository.exists_by_username(&base).await? {
+            return Ok(base);
+        }
+        for suffix in 2..100 {
+            let candidate = format!("{}{}", base, suffix);
+            if !self.user_repository.exists_by_username(&candidate).await? {
+                return Ok(candidate);
+            }
+        }
+        Err(OAuthError::UsernameUnavailable)
+    }
+}
+
+/// Split a display name into first and last name at the first space
+fn split_name(name: Option<&str>) -> (Option<String>, Option<String>) {
+    let Some(name) = name.map(str::trim).filter(|n| !n.is_empty()) else {
+        return (None, None);
+    };
+    let truncate = |part: &str| part.chars().take(MAX_NAME_LEN).collect::<String>();
+    match name.split_once(char::is_whitespace) {
+        Some((first, last)) => (Some(truncate(first)), Some(truncate(last.trim()))),
+        None => (Some(truncate(name)), None),
+    }
+}
+
+/// Google OpenID Connect userinfo response
+#[derive(Debug, Deserialize)]
+struct GoogleUserInfo {
+    sub: String,
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
+    name: Option<String>,
+    picture: Option<String>,
+}
+
+/// GitHub `GET /user` response
+#[derive(Debug, Deserialize)]
+struct GitHubUser {
+    id: i64,
+    login: String,
+    name: Option<String>,
+    avatar_url: Option<String>,
+}
+
+/// Entry of GitHub's `GET /user/emails` response
+#[derive(Debug, Deserialize)]
+struct GitHubEmail {
+    email: String,
+    primary: bool,
+    verified: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_utils::MockUserRepository;
+    use axum::{
+        http::{HeaderMap, StatusCode},
+        routing::{get, post},
+        Json, Router,
+    };
+    use serde_json::{json, Value};
+
+    /// Serve a provider's token and profile endpoints on a local port
+    async fn mock_provider(profile: Value, emails: Value) -> OAuthEndpoints {
+        let authorized = |headers: &HeaderMap| {
+            headers
+                .get("authorization")
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|v| v.eq_ignore_ascii_case("bearer mock-access-token"))
+        };
+        let app = Router::new()
+            .route(
+                "/token",
+                post(|body: String| async move {
+                    // The code and the verifier from the authorization request are both sent
+                    if !body.contains("code=good-code") || !body.contains("code_verifier=") {
+                        return (
+                            StatusCode::BAD_REQUEST,
+                            Json(json!({"error": "invalid_grant"})),
+                        );
+                    }
+                    (
+                        StatusCode::OK,
+                        Json(json!({"access_token": "mock-access-token", "token_type": "bearer"})),
+                    )
+                }),
+            )
+            .route(
+                "/user",
+                get(move |headers: HeaderMap| async move {
+                    if !authorized(&headers) {
+                        return (StatusCode::UNAUTHORIZED, Json(json!({})));
+                    }
+                    (StatusCode::OK, Json(profile))
+                }),
+            )
+            .route(
+                "/user/emails",
+                get(move |headers: HeaderMap| async move {
+                    if !authorized(&headers) {
+                        return (StatusCode::UNAUTHORIZED, Json(json!([])));
+                    }
+                    (StatusCode::OK, Json(emails))
+                }),
+            );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let base = format!("http://{}", address);
+        OAuthEndpoints {
+            authorize_url: format!("{}/authorize", base),
+            token_url: format!("{}/token", base),
+            userinfo_url: format!("{}/user", base),
+            emails_url: Some(format!("{}/user/emails", base)),
+        }
+    }
+
+    fn service(
+        users: MockUserRepository,
+        provider: OAuthProvider,
+        endpoints: OAuthEndpoints,
+    ) -> OAuthService {
+        OAuthService::new(Arc::new(users), "http://short.test".to_string()).with_client(
+            provider,
+            OAuthClientConfig {
+                client_id: "client".to_string(),
+                client_secret: "secret".to_string(),
+                endpoints,
+            },
+        )
+    }
+
+    fn github_profile() -> (Value, Value) {
+        (
+            json!({"id": 42, "login": "octo-cat", "name": "Mona Lisa", "avatar_url": "https://avatars.test/42"}),
+            json!([
+                {"email": "old@example.com", "primary": false, "verified": true},
+                {"email": "Mona@Example.com", "primary": true, "verified": true}
+            ]),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_github_login_creates_then_reuses_account() {
+        let (profile, emails) = github_profile();
+        let endpoints = mock_provider(profile, emails).await;
+        let users = MockUserRepository::new();
+        let service = service(users.clone(), OAuthProvider::GitHub, endpoints);
+
+        let authorization = service.start_login(OAuthProvider::GitHub).unwrap();
+        assert!(authorization.url.contains("code_challenge="));
+        assert!(authorization
+            .url
+            .contains(&format!("state={}", authorization.state)));
+
+        let login = service
+            .complete_login(OAuthProvider::GitHub, "good-code", &authorization.state)
+            .await
+            .unwrap();
+        assert!(login.created);
+        assert_eq!(login.user.username, "octo-cat");
+        assert_eq!(login.user.email, "mona@example.com");
+        assert_eq!(login.user.oauth_provider, Some(OAuthProvider::GitHub));
+        assert_eq!(login.user.oauth_provider_id.as_deref(), Some("42"));
+        assert_eq!(login.user.first_name.as_deref(), Some("Mona"));
+        assert_eq!(login.user.last_name.as_deref(), Some("Lisa"));
+        assert!(!login.user.has_password());
+
+        // A state is only accepted once
+        let replay = service
+            .complete_login(OAuthProvider::GitHub, "good-code", &authorization.state)
+            .await;
+        assert!(matches!(replay, Err(OAuthError::InvalidState)));
+
+        let authorization = service.start_login(OAuthProvider::GitHub).unwrap();
+        let again = service
+            .complete_login(OAuthProvider::GitHub, "good-code", &authorization.state)
+            .await
+            .unwrap();
+        assert!(!again.created);
+        assert_eq!(again.user.id, login.user.id);
+    }
+
+    #[tokio::test]
+    async fn test_login_links_existing_password_account() {
+        let (profile, emails) = github_profile();
+        let endpoints = mock_provider(profile, emails).await;
+        let users = MockUserRepository::new();
+        let existing = users
+            .create_user("mona", "mona@example.com", "bcrypt-hash")
+            .await
+            .unwrap();
+        let service = service(users.clone(), OAuthProvider::GitHub, endpoints);
+
+        let authorization = service.start_login(OAuthProvider::GitHub).unwrap();
+        let login = service
+            .complete_login(OAuthProvider::GitHub, "good-code", &authorization.state)
+            .await
+            .unwrap();
+
+        assert!(!login.created);
+        assert_eq!(login.user.id, existing.id);
+        assert_eq!(login.user.password_hash, "bcrypt-hash");
+        assert_eq!(login.user.oauth_provider_id.as_deref(), Some("42"));
+        assert!(!users.exists_by_username("octo-cat").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_google_login_requires_verified_email() {
+        let endpoints = mock_provider(
+            json!({"sub": "g-1", "email": "someone@example.com", "email_verified": false}),
+            json!([]),
+        )
+        .await;
+        let service = service(MockUserRepository::new(), OAuthProvider::Google, endpoints);
+
+        let authorization = service.start_login(OAuthProvider::Google).unwrap();
+        let result = service
+            .complete_login(OAuthProvider::Google, "good-code", &authorization.state)
+            .await;
+        assert!(matches!(result, Err(OAuthError::EmailUnavailable)));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_code_and_foreign_state() {
+        let (profile, emails) = github_profile();
+        let endpoints = mock_provider(profile, emails).await;
+        let service = service(MockUserRepository::new(), OAuthProvider::GitHub, endpoints);
+
+        let authorization = service.start_login(OAuthProvider::GitHub).unwrap();
+        let result = service
+            .complete_login(OAuthProvider::GitHub, "bad-code", &authorization.state)
+            .await;
+        assert!(matches!(result, Err(OAuthError::TokenExchange(_))));
+
+        // A state issued for one provider is not accepted by another
+        let authorization = service.start_login(OAuthProvider::GitHub).unwrap();
+        let result = service
+            .complete_login(OAuthProvider::Google, "good-code", &authorization.state)
+            .await;
+        assert!(matches!(result, Err(OAuthError::InvalidState)));
+
+        assert!(matches!(
+            service.start_login(OAuthProvider::Google),
+            Err(OAuthError::ProviderNotConfigured(OAuthProvider::Google))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_derived_username_avoids_taken_names() {
+        let users = MockUserRepository::new();
+        users
+            .create_user("jo", "taken@example.com", "hash")
+            .await
+            .unwrap();
+        users
+            .create_user("userjo", "taken2@example.com", "hash")
+            .await
+            .unwrap();
+        let service = OAuthService::new(Arc::new(users), "http://short.test".to_string());
+
+        let profile = OAuthProfile {
+            provider_id: "g-7".to_string(),
+            email: "jo@example.com".to_string(),
+            name: None,
+            avatar_url: None,
+            login: None,
+        };
+        let login = service
+            .find_or_create_user(OAuthProvider::Google, &profile)
+            .await
+            .unwrap();
+        assert_eq!(login.user.username, "userjo2");
+    }
+
+    #[test]
+    fn test_split_name() {
+        assert_eq!(
+            split_name(Some("Ada  Lovelace King")),
+            (Some("Ada".to_string()), Some("Lovelace King".to_string()))
+        );
+        assert_eq!(split_name(Some("Cher")), (Some("Cher".to_string()), None));
+        assert_eq!(split_name(Some("  ")), (None, None));
+        assert_eq!(split_name(None), (None, None));
+    }
+}
@@ -0,0 +1,530 @@
+use crate::domain::entities::{
+    OrgRole, Organization, OrganizationMember, OrganizationWithMemberCount, Url,
+};
+use crate::domain::repositories::{OrganizationRepository, OrganizationRepositoryError};
+use governor::{clock::DefaultClock, state::keyed::DefaultKeyedStateStore, Quota, RateLimiter};
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+/// Limits applied per organization rather than per user
+#[derive(Debug, Clone)]
+pub struct OrgLimits {
+    /// Maximum number of URLs an organization can own
+    pub max_urls_per_organization: i64,
+    /// URLs the members of one organization can create per minute, combined
+    pub url_creations_per_minute: u32,
+}
+
+impl Default for OrgLimits {
+    fn default() -> Self {
+        Self {
+            max_urls_per_organization: 10_000,
+            url_creations_per_minute: 120,
+        }
+    }
+}
+
+type OrgRateLimiter = RateLimiter<i32, DefaultKeyedStateStore<i32>, DefaultClock>;
+
+/// Domain service for organizations, memberships and organization-level limits
+#[derive(Clone)]
+pub struct OrgService<O>
+where
+    O: OrganizationRepository + Clone,
+{
+    repository: O,
+    limits: OrgLimits,
+    url_rate_limiter: Arc<OrgRateLimiter>,
+}
+
+impl<O> OrgService<O>
+where
+    O: OrganizationRepository + Clone,
+{
+    /// Create a new organization service with default limits
+    pub fn new(repository: O) -> Self {
+        Self::with_limits(repository, OrgLimits::default())
+    }
+
+    /// Create a new organization service with custom limits
+    pub fn with_limits(repository: O, limits: OrgLimits) -> Self {
+        let per_minute = NonZeroU32::new(limits.url_creations_per_minute.max(1)).unwrap();
+        let url_rate_limiter = Arc::new(RateLimiter::keyed(Quota::per_minute(per_minute)));
+
+        Self {
+            repository,
+            limits,
+            url_rate_limiter,
+        }
+    }
+
+    /// Create an organization owned by `owner_id`
+    ///
+    /// The slug is derived from the name when not given.
+    pub async fn create_organization(
+        &self,
+        owner_id: i32,
+        name: &str,
+        slug: Option<&str>,
+    ) -> Result<Organization, OrgServiceError> {
+        let name = Self::validate_name(name)?;
+        let slug = Self::resolve_slug(name, slug)?;
+
+        Ok(self
+            .repository
+            .create_organization(name, &slug, owner_id)
+            .await?)
+    }
+
+    /// Get an organization the user belongs to, with the user's membership
+    pub async fn get_organization(
+        &self,
+        org_id: i32,
+        user_id: i32,
+    ) -> Result<(Organization, OrganizationMember), OrgServiceError> {
+        let organization = self.find_organization(org_id).await?;
+        let membership = self.require_member(org_id, user_id).await?;
+        Ok((organization, membership))
+    }
+
+    /// List the organizations a user belongs to
+    pub async fn list_for_user(&self, user_id: i32) -> Result<Vec<Organization>, OrgServiceError> {
+        Ok(self.repository.find_by_member(user_id).await?)
+    }
+
+    /// Rename an organization or change its slug (owners and admins)
+    pub async fn update_organization(
+        &self,
+        org_id: i32,
+        actor_id: i32,
+        name: Option<&str>,
+        slug: Option<&str>,
+    ) -> Result<Organization, OrgServiceError> {
+        let organization = self.find_organization(org_id).await?;
+        self.require_role(org_id, actor_id, OrgRole::can_manage)
+            .await?;
+
+        let name = match name {
+            Some(name) => Self::validate_name(name)?.to_string(),
+            None => organization.name,
+        };
+        let slug = match slug {
+            Some(slug) => Self::resolve_slug(&name, Some(slug))?,
+            None => organization.slug,
+        };
+
+        Ok(self
+            .repository
+            .update_organization(org_id, &name, &slug)
+            .await?)
+    }
+
+    /// Delete an organization (owner only)
+    pub async fn delete_organization(
+        &self,
+        org_id: i32,
+        actor_id: i32,
+    ) -> Result<(), OrgServiceError> {
+        self.find_organization(org_id).await?;
+        self.require_role(org_id, actor_id, |role| *role == OrgRole::Owner)
+            .await?;
+
+        if !self.repository.delete_organization(org_id).await? {
+            return Err(OrgServiceError::NotFound);
+        }
+        Ok(())
+    }
+
+    /// List the members of an organization (members only)
+    pub async fn list_members(
+        &self,
+        org_id: i32,
+        actor_id: i32,
+    ) -> Result<Vec<OrganizationMember>, OrgServiceError> {
+        self.find_organization(org_id).await?;
+        self.require_member(org_id, actor_id).await?;
+        Ok(self.repository.list_members(org_id).await?)
+    }
+
+    /// Add a user to an organization
+    ///
+    /// Owners and admins can add members; only the owner can add admins.
+    pub async fn add_member(
+        &self,
+        org_id: i32,
+        actor_id: i32,
+        user_id: i32,
+        role: OrgRole,
+    ) -> Result<OrganizationMember, OrgServiceError> {
+        self.find_organization(org_id).await?;
+        let actor = self
+            .require_role(org_id, actor_id, OrgRole::can_manage)
+            .await?;
+
+        match role {
+            OrgRole::Owner => return Err(OrgServiceError::CannotAssignOwner),
+            OrgRole::Admin if actor.role != OrgRole::Owner => {
+                return Err(OrgServiceError::InsufficientRole)
+            }
+            _ => {}
+        }
+
+        Ok(self.repository.add_member(org_id, user_id, role).await?)
+    }
+
+    /// Remove a user from an organization
+    ///
+    /// Members can always leave; otherwise owners and admins remove members and only the
+    /// owner removes admins. The owner cannot be removed.
+    pub async fn remove_member(
+        &self,
+        org_id: i32,
+        actor_id: i32,
+        user_id: i32,
+    ) -> Result<(), OrgServiceError> {
+        self.find_organization(org_id).await?;
+        let actor = self.require_member(org_id, actor_id).await?;
+        let target = self
+            .repository
+            .find_member(org_id, user_id)
+            .await?
+            .ok_or(OrgServiceError::NotMember)?;
+
+        if target.role == OrgRole::Owner {
+            return Err(OrgServiceError::CannotRemoveOwner);
+        }
+        if actor_id != user_id {
+            let allowed = match target.role {
+                OrgRole::Admin => actor.role == OrgRole::Owner,
+                _ => actor.role.can_manage(),
+            };
+            if !allowed {
+                return Err(OrgServiceError::InsufficientRole);
+            }
+        }
+
+        self.repository.remove_member(org_id, user_id).await?;
+        Ok(())
+    }
+
+    /// Check that a user may create a URL owned by an organization
+    ///
+    /// Requires membership and applies the organization's rate limit and URL quota. The
+    /// quota is checked again by the insert, which concurrent creations cannot race past.
+    pub async fn authorize_url_creation(
+        &self,
+        org_id: i32,
+        user_id: i32,
+    ) -> Result<(), OrgServiceError> {
+        self.find_organization(org_id).await?;
+        self.require_member(org_id, user_id).await?;
+
+        if self.url_rate_limiter.check_key(&org_id).is_err() {
+            return Err(OrgServiceError::RateLimited);
+        }
+
+        let url_count = self.repository.count_urls(org_id).await?;
+        if url_count >= self.limits.max_urls_per_organization {
+            return Err(OrgServiceError::QuotaExceeded {
+                limit: self.limits.max_urls_per_organization,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check if a user can access a URL: they created it or belong to its organization
+    pub async fn can_access_url(&self, user_id: i32, url: &Url) -> Result<bool, OrgServiceError> {
+        if url.user_id == Some(user_id) {
+            return Ok(true);
+        }
+        match url.organization_id {
+            Some(org_id) => Ok(self
+                .repository
+                .find_member(org_id, user_id)
+                .await?
+                .is_some()),
+            None => Ok(false),
+        }
+    }
+
+    /// Require that a user belongs to an organization
+    pub async fn require_member(
+        &self,
+        org_id: i32,
+        user_id: i32,
+    ) -> Result<OrganizationMember, OrgServiceError> {
+        self.repository
+            .find_member(org_id, user_id)
+            .await?
+            .ok_or(OrgServiceError::NotMember)
+    }
+
+    /// List all organizations with their member counts (administration)
+    pub async fn list_with_member_counts(
+        &self,
+    ) -> Result<Vec<OrganizationWithMemberCount>, OrgServiceError> {
+        Ok(self.repository.list_with_member_counts().await?)
+    }
+
+    async fn find_organization(&self, org_id: i32) -> Result<Organization, OrgServiceError> {
+        self.repository
+            .find_by_id(org_id)
+            .await?
+            .ok_or(OrgServiceError::NotFound)
+    }
+
+    async fn require_role(
+        &self,
+        org_id: i32,
+        user_id: i32,
+        allowed: impl Fn(&OrgRole) -> bool,
+    ) -> Result<OrganizationMember, OrgServiceError> {
+        let member = self.require_member(org_id, user_id).await?;
+        if !allowed(&member.role) {
+            return Err(OrgServiceError::InsufficientRole);
+        }
+        Ok(member)
+    }
+
+    fn validate_name(name: &str) -> Result<&str, OrgServiceError> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > 100 {
+            return Err(OrgServiceError::InvalidName(
+                "Organization name must be between 1 and 100 characters".to_string(),
+            ));
+        }
+        Ok(name)
+    }
+
+    fn resolve_slug(name: &str, slug: Option<&str>) -> Result<String, OrgServiceError> {
+        let slug = match slug {
+            Some(slug) => slug.trim().to_string(),
+            None => Organization::slugify(name),
+        };
+        if !Organization::is_valid_slug(&slug) {
+            return Err(OrgServiceError::InvalidSlug(slug));
+        }
+        Ok(slug)
+    }
+}
+
+/// Organization service errors
+#[derive(Debug, thiserror::Error)]
+pub enum OrgServiceError {
+    #[error("Organization not found")]
+    NotFound,
+
+    #[error("You are not a member of this organization")]
+    NotMember,
+
+    #[error("Your role in this organization does not allow this action")]
+    InsufficientRole,
+
+    #[error("{0}")]
+    InvalidName(String),
+
+    #[error("Invalid slug '{0}': use 3-50 lowercase letters, digits and dashes")]
+    InvalidSlug(String),
+
+    #[error("Organization slug already exists")]
+    DuplicateSlug,
+
+    #[error("User is already a member of this organization")]
+    AlreadyMember,
+
+    #[error("The organization owner cannot be removed")]
+    CannotRemoveOwner,
+
+    #[error("Ownership cannot be granted through membership changes")]
+    CannotAssignOwner,
+
+    #[error("Organization URL quota of {limit} reached")]
+    QuotaExceeded { limit: i64 },
+
+    #[error("Organization is creating URLs too quickly, try again later")]
+    RateLimited,
+
+    #[error("Repository error: {0}")]
+    Repository(String),
+}
+
+impl From<OrganizationRepositoryError> for OrgServiceError {
+    fn from(error: OrganizationRepositoryError) -> Self {
+        match error {
+            OrganizationRepositoryError::NotFound => OrgServiceError::NotFound,
+            OrganizationRepositoryError::DuplicateSlug => OrgServiceError::DuplicateSlug,
+            OrganizationRepositoryError::AlreadyMember => OrgServiceError::AlreadyMember,
+            other => OrgServiceError::Repository(other.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::UrlStatus;
+    use crate::infrastructure::test_utils::MockOrganizationRepository;
+
+    const OWNER: i32 = 1;
+    const ADMIN: i32 = 2;
+    const MEMBER: i32 = 3;
+    const OUTSIDER: i32 = 4;
+
+    async fn service_with_org() -> (OrgService<MockOrganizationRepository>, Organization) {
+        let service = OrgService::new(MockOrganizationRepository::default());
+        let org = service
+            .create_organization(OWNER, "Acme Corp", None)
+            .await
+            .unwrap();
+        service
+            .add_member(org.id, OWNER, ADMIN, OrgRole::Admin)
+            .await
+            .unwrap();
+        service
+            .add_member(org.id, ADMIN, MEMBER, OrgRole::Member)
+            .await
+            .unwrap();
+        (service, org)
+    }
+
+    #[tokio::test]
+    async fn test_create_organization_adds_owner() {
+        let (service, org) = service_with_org().await;
+        assert_eq!(org.slug, "acme-corp");
+
+        let (_, membership) = service.get_organization(org.id, OWNER).await.unwrap();
+        assert_eq!(membership.role, OrgRole::Owner);
+
+        let result = service
+            .create_organization(OUTSIDER, "Acme Corp", None)
+            .await;
+        assert!(matches!(result, Err(OrgServiceError::DuplicateSlug)));
+
+        let result = service
+            .create_organization(OUTSIDER, "Other", Some("Bad Slug"))
+            .await;
+        assert!(matches!(result, Err(OrgServiceError::InvalidSlug(_))));
+    }
+
+    #[tokio::test]
+    async fn test_non_members_cannot_read() {
+        let (service, org) = service_with_org().await;
+        assert!(service.list_members(org.id, MEMBER).await.is_ok());
+        assert!(matches!(
+            service.get_organization(org.id, OUTSIDER).await,
+            Err(OrgServiceError::NotMember)
+        ));
+        assert!(matches!(
+            service.get_organization(99, OWNER).await,
+            Err(OrgServiceError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_role_permissions() {
+        let (service, org) = service_with_org().await;
+
+        // Members cannot manage, admins cannot add admins or delete
+        assert!(matches!(
+            service
+                .update_organization(org.id, MEMBER, Some("New"), None)
+                .await,
+            Err(OrgServiceError::InsufficientRole)
+        ));
+        assert!(matches!(
+            service
+                .add_member(org.id, ADMIN, OUTSIDER, OrgRole::Admin)
+                .await,
+            Err(OrgServiceError::InsufficientRole)
+        ));
+        assert!(matches!(
+            service
+                .add_member(org.id, OWNER, OUTSIDER, OrgRole::Owner)
+                .await,
+            Err(OrgServiceError::CannotAssignOwner)
+        ));
+        assert!(matches!(
+            service.delete_organization(org.id, ADMIN).await,
+            Err(OrgServiceError::InsufficientRole)
+        ));
+
+        let updated = service
+            .update_organization(org.id, ADMIN, Some("Acme Inc"), Some("acme-inc"))
+            .await
+            .unwrap();
+        assert_eq!(updated.slug, "acme-inc");
+    }
+
+    #[tokio::test]
+    async fn test_remove_member_rules() {
+        let (service, org) = service_with_org().await;
+
+        assert!(matches!(
+            service.remove_member(org.id, ADMIN, OWNER).await,
+            Err(OrgServiceError::CannotRemoveOwner)
+        ));
+        assert!(matches!(
+            service.remove_member(org.id, MEMBER, ADMIN).await,
+            Err(OrgServiceError::InsufficientRole)
+        ));
+
+        // Members can leave on their own
+        service.remove_member(org.id, MEMBER, MEMBER).await.unwrap();
+        service.remove_member(org.id, OWNER, ADMIN).await.unwrap();
+        assert_eq!(service.list_members(org.id, OWNER).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_url_creation_quota_and_rate_limit() {
+        let repository = MockOrganizationRepository::default();
+        let service = OrgService::with_limits(
+            repository.clone(),
+            OrgLimits {
+                max_urls_per_organization: 5,
+                url_creations_per_minute: 2,
+            },
+        );
+        let org = service
+            .create_organization(OWNER, "Acme Corp", None)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            service.authorize_url_creation(org.id, OUTSIDER).await,
+            Err(OrgServiceError::NotMember)
+        ));
+
+        service.authorize_url_creation(org.id, OWNER).await.unwrap();
+        repository.set_url_count(org.id, 5);
+        assert!(matches!(
+            service.authorize_url_creation(org.id, OWNER).await,
+            Err(OrgServiceError::QuotaExceeded { limit: 5 })
+        ));
+        // Third attempt in the same minute exceeds the organization's rate limit
+        assert!(matches!(
+            service.authorize_url_creation(org.id, OWNER).await,
+            Err(OrgServiceError::RateLimited)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_can_access_url() {
+        let (service, org) = service_with_org().await;
+        let url = Url::new_with_timestamp(
+            1,
+            "abc123".to_string(),
+            "https://example.com".to_string(),
+            None,
+            Some(OWNER),
+            UrlStatus::Active,
+        );
+
+        assert!(service.can_access_url(OWNER, &url).await.unwrap());
+        assert!(!service.can_access_url(MEMBER, &url).await.unwrap());
+
+        let org_url = url.with_organization(Some(org.id));
+        assert!(service.can_access_url(MEMBER, &org_url).await.unwrap());
+        assert!(!service.can_access_url(OUTSIDER, &org_url).await.unwrap());
+    }
+}
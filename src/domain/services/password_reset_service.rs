@@ -1,6 +1,8 @@
 use crate::domain::entities::{PasswordResetToken, User};
 use crate::domain::repositories::password_reset_repository::PasswordResetRepository;
 use crate::domain::repositories::user_repository::UserRepository;
+use crate::infrastructure::email::{EmailMessage, PasswordResetEmail};
+use bcrypt::{hash, DEFAULT_COST};
 use chrono::{Duration, Utc};
 use rand::distributions::Alphanumeric;
 use rand::{thread_rng, Rng};
@@ -106,9 +108,13 @@ where
     }
 
     /// Create a password reset request for a user
+    ///
+    /// With a `reset_url_base`, the email with the reset link is queued in the outbox together
+    /// with the token.
     pub async fn create_reset_request(
         &self,
         email: &str,
+        reset_url_base: Option<&str>,
     ) -> Result<PasswordResetRequest, PasswordResetError> {
         // Find user by email
         let user = self
@@ -142,9 +148,18 @@ where
             expires_at,
         );
 
+        let reset_email = reset_url_base.map(|base_url| {
+            let template = PasswordResetEmail {
+                username: user.username.clone(),
+                reset_url: format!("{}/reset-password?token={}", base_url, token),
+                expires_in_minutes: (self.token_expiration_hours * 60) as u32,
+            };
+            EmailMessage::from_template(user.email.clone(), &template)
+        });
+
         // Save token to repository
         self.password_reset_repository
-            .create_token(reset_token)
+            .create_token(reset_token, reset_email.as_ref())
             .await
             .map_err(|e| PasswordResetError::Internal(e.to_string()))?;
 
@@ -180,34 +195,43 @@ where
     }
 
     /// Reset password using a valid token
+    ///
+    /// The token is consumed atomically before the password changes, and every other
+    /// pending token of the user is invalidated afterwards, so an intercepted link stops
+    /// working once the password has been reset.
     pub async fn reset_password(
         &self,
         token: &str,
         new_password: &str,
     ) -> Result<User, PasswordResetError> {
         // Validate token
-        let mut reset_token = self.validate_token(token).await?;
+        let reset_token = self.validate_token(token).await?;
 
-        // Get user
-        let user = self
-            .user_repository
-            .find_by_id(reset_token.user_id)
+        // Claim the token; a concurrent reset with the same token loses here
+        let claimed = self
+            .password_reset_repository
+            .mark_token_used(reset_token.id)
             .await
-            .map_err(|e| PasswordResetError::Internal(e.to_string()))?
-            .ok_or(PasswordResetError::UserNotFound)?;
+            .map_err(|e| PasswordResetError::Internal(e.to_string()))?;
+        if !claimed {
+            return Err(PasswordResetError::TokenAlreadyUsed);
+        }
 
-        // Hash new password (in a real implementation, you would hash the password)
-        // For now, we'll just store it as-is (this should be replaced with proper hashing)
-        let _hashed_password = new_password.to_string(); // TODO: Implement proper password hashing
+        let password_hash = hash(new_password, DEFAULT_COST)
+            .map_err(|e| PasswordResetError::Internal(e.to_string()))?;
 
-        // Update user password
-        // Note: This would require a new method in UserRepository to update password
-        // For now, we'll mark the token as used
-        reset_token.mark_as_used();
-        self.password_reset_repository
-            .update_token(reset_token)
+        let user = self
+            .user_repository
+            .update_password(reset_token.user_id, &password_hash)
             .await
-            .map_err(|e| PasswordResetError::Internal(e.to_string()))?;
+            .map_err(|e| match e {
+                crate::domain::repositories::user_repository::RepositoryError::NotFound => {
+                    PasswordResetError::UserNotFound
+                }
+                e => PasswordResetError::Internal(e.to_string()),
+            })?;
+
+        self.invalidate_all_tokens_for_user(user.id).await?;
 
         Ok(user)
     }
@@ -215,7 +239,7 @@ where
     /// Clean up expired tokens
     pub async fn cleanup_expired_tokens(&self) -> Result<usize, PasswordResetError> {
         self.password_reset_repository
-            .delete_expired_tokens()
+            .delete_expired_tokens(chrono::Utc::now())
             .await
             .map_err(|e| PasswordResetError::Internal(e.to_string()))
     }
@@ -231,13 +255,13 @@ where
             .map_err(|e| PasswordResetError::Internal(e.to_string()))
     }
 
-    /// Revoke all tokens for a user
-    pub async fn revoke_all_tokens_for_user(
+    /// Invalidate all pending tokens for a user, e.g. after a password change
+    pub async fn invalidate_all_tokens_for_user(
         &self,
         user_id: i32,
     ) -> Result<usize, PasswordResetError> {
         self.password_reset_repository
-            .revoke_all_tokens_for_user(user_id)
+            .invalidate_all_tokens_for_user(user_id)
             .await
             .map_err(|e| PasswordResetError::Internal(e.to_string()))
     }
@@ -256,6 +280,7 @@ mod tests {
         async fn create_token(
             &self,
             _token: PasswordResetToken,
+            _email: Option<&EmailMessage>,
         ) -> Result<PasswordResetToken, Box<dyn std::error::Error + Send + Sync>> {
             Ok(PasswordResetToken::new_with_timestamp(
                 1,
@@ -303,13 +328,35 @@ mod tests {
             ))
         }
 
+        async fn find_expired_tokens(
+            &self,
+            _expired_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<PasswordResetToken>, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(vec![])
+        }
+
         async fn delete_expired_tokens(
             &self,
+            _expired_before: chrono::DateTime<chrono::Utc>,
         ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
             Ok(0)
         }
 
-        async fn revoke_all_tokens_for_user(
+        async fn count_expired_tokens(
+            &self,
+            _expired_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(0)
+        }
+
+        async fn mark_token_used(
+            &self,
+            _token_id: i32,
+        ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(true)
+        }
+
+        async fn invalidate_all_tokens_for_user(
             &self,
             _user_id: i32,
         ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
@@ -333,6 +380,69 @@ mod tests {
             ))
         }
 
+        async fn create_unverified_user(
+            &self,
+            _username: &str,
+            _email: &str,
+            _password_hash: &str,
+            _welcome_email: &EmailMessage,
+        ) -> Result<User, crate::domain::repositories::user_repository::RepositoryError> {
+            Ok(User::new_with_timestamp(
+                1,
+                "test".to_string(),
+                "test@example.com".to_string(),
+                "hash".to_string(),
+            ))
+        }
+
+        async fn create_oauth_user(
+            &self,
+            _username: &str,
+            _email: &str,
+            _provider: crate::domain::entities::OAuthProvider,
+            _provider_id: &str,
+        ) -> Result<User, crate::domain::repositories::user_repository::RepositoryError> {
+            Ok(User::new_with_timestamp(
+                1,
+                "test".to_string(),
+                "test@example.com".to_string(),
+                "hash".to_string(),
+            ))
+        }
+
+        async fn find_usernames_by_ids(
+            &self,
+            _ids: &[i32],
+        ) -> Result<
+            std::collections::HashMap<i32, String>,
+            crate::domain::repositories::user_repository::RepositoryError,
+        > {
+            Ok(std::collections::HashMap::new())
+        }
+
+        async fn find_by_oauth_account(
+            &self,
+            _provider: crate::domain::entities::OAuthProvider,
+            _provider_id: &str,
+        ) -> Result<Option<User>, crate::domain::repositories::user_repository::RepositoryError>
+        {
+            Ok(None)
+        }
+
+        async fn link_oauth_account(
+            &self,
+            _user_id: i32,
+            _provider: crate::domain::entities::OAuthProvider,
+            _provider_id: &str,
+        ) -> Result<User, crate::domain::repositories::user_repository::RepositoryError> {
+            Ok(User::new_with_timestamp(
+                1,
+                "test".to_string(),
+                "test@example.com".to_string(),
+                "hash".to_string(),
+            ))
+        }
+
         async fn find_by_username(
             &self,
             _username: &str,
@@ -376,6 +486,16 @@ mod tests {
             )))
         }
 
+        async fn search_users(
+            &self,
+            _query: &str,
+            _limit: usize,
+            _offset: usize,
+        ) -> Result<Vec<User>, crate::domain::repositories::user_repository::RepositoryError>
+        {
+            Ok(Vec::new())
+        }
+
         async fn exists_by_username(
             &self,
             _username: &str,
@@ -429,15 +549,133 @@ mod tests {
             Ok(())
         }
 
-        async fn anonymize_account(
+        async fn anonymize_user(
             &self,
             _user_id: i32,
-            _anonymized_username: &str,
-            _anonymized_email: &str,
-            _anonymized_password_hash: &str,
         ) -> Result<(), crate::domain::repositories::user_repository::RepositoryError> {
             Ok(())
         }
+
+        async fn update_profile_visibility(
+            &self,
+            _user_id: i32,
+            visibility: &crate::domain::entities::ProfileVisibility,
+        ) -> Result<User, crate::domain::repositories::user_repository::RepositoryError> {
+            let mut user = User::new_with_timestamp(
+                1,
+                "test".to_string(),
+                "test@example.com".to_string(),
+                "hash".to_string(),
+            );
+            user.profile_visibility = *visibility;
+            Ok(user)
+        }
+
+        async fn update_social_links(
+            &self,
+            _user_id: i32,
+            social_links: Option<&crate::domain::entities::SocialLinks>,
+        ) -> Result<User, crate::domain::repositories::user_repository::RepositoryError> {
+            let mut user = User::new_with_timestamp(
+                1,
+                "test".to_string(),
+                "test@example.com".to_string(),
+                "hash".to_string(),
+            );
+            user.social_links = social_links.cloned();
+            Ok(user)
+        }
+
+        async fn set_pending_email(
+            &self,
+            user_id: i32,
+            pending_email: Option<&str>,
+        ) -> Result<User, crate::domain::repositories::user_repository::RepositoryError> {
+            let mut user = User::new_with_timestamp(
+                user_id,
+                "test".to_string(),
+                "test@example.com".to_string(),
+                "hash".to_string(),
+            );
+            user.pending_email = pending_email.map(str::to_string);
+            Ok(user)
+        }
+
+        async fn update_email(
+            &self,
+            user_id: i32,
+            email: &str,
+        ) -> Result<User, crate::domain::repositories::user_repository::RepositoryError> {
+            Ok(User::new_with_timestamp(
+                user_id,
+                "test".to_string(),
+                email.to_string(),
+                "hash".to_string(),
+            ))
+        }
+
+        async fn add_device_token(
+            &self,
+            user_id: i32,
+            token: &str,
+        ) -> Result<User, crate::domain::repositories::user_repository::RepositoryError> {
+            let mut user = User::new_with_timestamp(
+                user_id,
+                "test".to_string(),
+                "test@example.com".to_string(),
+                "hash".to_string(),
+            );
+            user.device_tokens = vec![token.to_string()];
+            Ok(user)
+        }
+
+        async fn remove_device_token(
+            &self,
+            _user_id: i32,
+            _token: &str,
+        ) -> Result<bool, crate::domain::repositories::user_repository::RepositoryError> {
+            Ok(false)
+        }
+
+        async fn update_account_status(
+            &self,
+            _user_id: i32,
+            status: &crate::domain::entities::AccountStatus,
+        ) -> Result<User, crate::domain::repositories::user_repository::RepositoryError> {
+            let mut user = User::new_with_timestamp(
+                1,
+                "test".to_string(),
+                "test@example.com".to_string(),
+                "hash".to_string(),
+            );
+            user.account_status = status.clone();
+            Ok(user)
+        }
+
+        async fn update_password(
+            &self,
+            _user_id: i32,
+            password_hash: &str,
+        ) -> Result<User, crate::domain::repositories::user_repository::RepositoryError> {
+            let mut user = User::new_with_timestamp(
+                1,
+                "test".to_string(),
+                "test@example.com".to_string(),
+                password_hash.to_string(),
+            );
+            user.password_changed_at = Some(Utc::now());
+            Ok(user)
+        }
+
+        async fn export_user_data(
+            &self,
+            _user_id: i32,
+        ) -> Result<
+            Option<crate::domain::repositories::UserDataExport>,
+            crate::domain::repositories::user_repository::RepositoryError,
+        > {
+            Ok(None)
+        }
     }
 
     #[tokio::test]
@@ -445,7 +683,7 @@ mod tests {
         let service =
             PasswordResetService::new_default(MockPasswordResetRepository, MockUserRepository);
 
-        let result = service.create_reset_request("test@example.com").await;
+        let result = service.create_reset_request("test@example.com", None).await;
         assert!(result.is_ok());
 
         let request = result.unwrap();
@@ -460,7 +698,7 @@ mod tests {
             PasswordResetService::new_default(MockPasswordResetRepository, MockUserRepository);
 
         let result = service
-            .create_reset_request("nonexistent@example.com")
+            .create_reset_request("nonexistent@example.com", None)
             .await;
         assert!(matches!(result, Err(PasswordResetError::UserNotFound)));
     }
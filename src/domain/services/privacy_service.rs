@@ -1,4 +1,4 @@
-use crate::domain::entities::{ProfilePrivacy, User};
+use crate::domain::entities::{ProfilePrivacy, ProfileVisibility, User};
 use thiserror::Error;
 
 /// Privacy service for handling user profile privacy settings
@@ -38,6 +38,31 @@ pub struct FieldPrivacySettings {
     pub email: DataPrivacyLevel,
 }
 
+/// Suggested change to a public profile visibility setting
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisibilityRecommendation {
+    pub setting: &'static str,
+    pub recommended: bool,
+    pub reason: &'static str,
+}
+
+impl VisibilityRecommendation {
+    /// Apply the recommended value to a visibility configuration
+    pub fn apply(&self, visibility: &mut ProfileVisibility) {
+        let field = match self.setting {
+            "show_url_count" => &mut visibility.show_url_count,
+            "show_click_count" => &mut visibility.show_click_count,
+            "show_join_date" => &mut visibility.show_join_date,
+            "show_bio" => &mut visibility.show_bio,
+            "show_website" => &mut visibility.show_website,
+            "show_social_links" => &mut visibility.show_social_links,
+            "show_recent_urls" => &mut visibility.show_recent_urls,
+            _ => return,
+        };
+        *field = self.recommended;
+    }
+}
+
 #[allow(dead_code)]
 impl PrivacyService {
     /// Create a new privacy service
@@ -225,6 +250,50 @@ impl PrivacyService {
         matches!(privacy, ProfilePrivacy::Public)
     }
 
+    /// Suggest hiding public profile details the user still shares by default
+    pub fn recommend_visibility_changes(
+        &self,
+        visibility: &ProfileVisibility,
+    ) -> Vec<VisibilityRecommendation> {
+        let defaults = ProfileVisibility::default();
+        let candidates = [
+            (
+                "show_join_date",
+                visibility.show_join_date,
+                defaults.show_join_date,
+                "Your join date reveals how long you have had an account",
+            ),
+            (
+                "show_bio",
+                visibility.show_bio,
+                defaults.show_bio,
+                "Your bio may contain personal details",
+            ),
+            (
+                "show_website",
+                visibility.show_website,
+                defaults.show_website,
+                "Your website can link your profile to your real identity",
+            ),
+            (
+                "show_social_links",
+                visibility.show_social_links,
+                defaults.show_social_links,
+                "Social links make it easy to track you across services",
+            ),
+        ];
+
+        candidates
+            .into_iter()
+            .filter(|(_, current, default, _)| *current && current == default)
+            .map(|(setting, _, _, reason)| VisibilityRecommendation {
+                setting,
+                recommended: false,
+                reason,
+            })
+            .collect()
+    }
+
     /// Get recommended privacy settings based on user preferences
     pub fn get_recommended_privacy_settings(
         &self,
@@ -392,4 +461,37 @@ mod tests {
         assert_eq!(personal_settings.last_name, DataPrivacyLevel::FriendsOnly);
         assert_eq!(personal_settings.email, DataPrivacyLevel::Private);
     }
+
+    #[test]
+    fn test_recommend_visibility_changes() {
+        let service = PrivacyService::new();
+
+        let recommendations = service.recommend_visibility_changes(&ProfileVisibility::default());
+        let settings: Vec<_> = recommendations.iter().map(|r| r.setting).collect();
+        assert_eq!(
+            settings,
+            vec![
+                "show_join_date",
+                "show_bio",
+                "show_website",
+                "show_social_links"
+            ]
+        );
+        assert!(recommendations.iter().all(|r| !r.recommended));
+
+        // Settings the user already changed are left alone
+        let visibility = ProfileVisibility {
+            show_bio: false,
+            show_website: false,
+            show_social_links: false,
+            ..ProfileVisibility::default()
+        };
+        let recommendations = service.recommend_visibility_changes(&visibility);
+        assert_eq!(recommendations.len(), 1);
+        assert_eq!(recommendations[0].setting, "show_join_date");
+
+        let mut recommended = visibility;
+        recommendations[0].apply(&mut recommended);
+        assert!(!recommended.show_join_date);
+    }
 }
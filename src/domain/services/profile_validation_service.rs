@@ -1,4 +1,4 @@
-use crate::domain::entities::ProfilePrivacy;
+use crate::domain::entities::{ProfilePrivacy, SocialLinks, MAX_CUSTOM_SOCIAL_LINKS};
 use regex::Regex;
 use thiserror::Error;
 use url::Url;
@@ -33,6 +33,9 @@ pub enum ProfileValidationError {
     #[error("Invalid avatar URL: {0}")]
     InvalidAvatarUrl(String),
 
+    #[error("Invalid social link: {0}")]
+    InvalidSocialLink(String),
+
     #[error("Profile data too long: {0}")]
     DataTooLong(String),
 
@@ -269,6 +272,74 @@ impl ProfileValidationService {
         Ok(url)
     }
 
+    /// Validate and sanitize social links
+    ///
+    /// Every link must be an HTTPS URL and custom links need a label; blank named links are
+    /// dropped.
+    pub fn validate_social_links(
+        &self,
+        links: SocialLinks,
+    ) -> Result<SocialLinks, ProfileValidationError> {
+        if links.custom.len() > MAX_CUSTOM_SOCIAL_LINKS {
+            return Err(ProfileValidationError::InvalidSocialLink(format!(
+                "At most {} custom links are allowed",
+                MAX_CUSTOM_SOCIAL_LINKS
+            )));
+        }
+
+        let named = |url: Option<String>, name: &str| {
+            url.map(|url| url.trim().to_string())
+                .filter(|url| !url.is_empty())
+                .map(|url| self.validate_social_link_url(url, name))
+                .transpose()
+        };
+
+        let custom = links
+            .custom
+            .into_iter()
+            .map(|(label, url)| {
+                let label = label.trim().to_string();
+                if label.is_empty() || label.chars().count() > 50 {
+                    return Err(ProfileValidationError::InvalidSocialLink(
+                        "Custom link labels must be 1 to 50 characters".to_string(),
+                    ));
+                }
+                let url = self.validate_social_link_url(url.trim().to_string(), &label)?;
+                Ok((label, url))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(SocialLinks {
+            twitter: named(links.twitter, "twitter")?,
+            github: named(links.github, "github")?,
+            linkedin: named(links.linkedin, "linkedin")?,
+            website: named(links.website, "website")?,
+            custom,
+        })
+    }
+
+    /// Check that a social link is an HTTPS URL of reasonable length
+    fn validate_social_link_url(
+        &self,
+        url: String,
+        name: &str,
+    ) -> Result<String, ProfileValidationError> {
+        if url.len() > self.website_max_length {
+            return Err(ProfileValidationError::DataTooLong(format!(
+                "{} link cannot exceed {} characters",
+                name, self.website_max_length
+            )));
+        }
+
+        match Url::parse(&url) {
+            Ok(parsed) if parsed.scheme() == "https" && parsed.host_str().is_some() => Ok(url),
+            _ => Err(ProfileValidationError::InvalidSocialLink(format!(
+                "{} link must be a valid https:// URL",
+                name
+            ))),
+        }
+    }
+
     /// Validate and sanitize location
     fn validate_and_sanitize_location(
         &self,
@@ -372,6 +443,51 @@ impl Default for ProfileValidationService {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_validate_social_links() {
+        let service = ProfileValidationService::new();
+
+        let links = service
+            .validate_social_links(SocialLinks {
+                github: Some(" https://github.com/jane ".to_string()),
+                twitter: Some("  ".to_string()),
+                custom: vec![("Blog".to_string(), "https://jane.dev".to_string())],
+                ..SocialLinks::default()
+            })
+            .unwrap();
+        assert_eq!(links.github.as_deref(), Some("https://github.com/jane"));
+        assert_eq!(links.twitter, None);
+        assert_eq!(links.custom.len(), 1);
+
+        for invalid in [
+            "http://github.com/jane",
+            "javascript:alert(1)",
+            "github.com/jane",
+        ] {
+            assert!(matches!(
+                service.validate_social_links(SocialLinks {
+                    github: Some(invalid.to_string()),
+                    ..SocialLinks::default()
+                }),
+                Err(ProfileValidationError::InvalidSocialLink(_))
+            ));
+        }
+
+        let too_many = SocialLinks {
+            custom: (0..=MAX_CUSTOM_SOCIAL_LINKS)
+                .map(|i| (format!("Link {}", i), format!("https://example.com/{}", i)))
+                .collect(),
+            ..SocialLinks::default()
+        };
+        assert!(service.validate_social_links(too_many).is_err());
+
+        let unlabeled = SocialLinks {
+            custom: vec![(" ".to_string(), "https://example.com".to_string())],
+            ..SocialLinks::default()
+        };
+        assert!(service.validate_social_links(unlabeled).is_err());
+    }
+
     #[test]
     fn test_validate_name() {
         let service = ProfileValidationService::new();
@@ -1,13 +1,68 @@
-use crate::application::dto::responses::{BulkOperationProgress, BulkOperationStatus};
+use crate::application::dto::requests::OperationPriority;
+use crate::application::dto::responses::{
+    BulkItemResult, BulkOperationProgress, BulkOperationStatus,
+};
+use crate::domain::services::BatchOperation;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+/// Item results a subscriber may fall behind by before missing some
+const ITEM_RESULT_BUFFER: usize = 256;
+
+/// Most operations one chain may hold, the first one included
+pub const MAX_CHAIN_DEPTH: usize = 5;
+
+/// Progress of an operation along with the time it last changed
+struct TrackedOperation {
+    progress: BulkOperationProgress,
+    updated_at: DateTime<Utc>,
+    /// Results of the items processed so far, in the order they finished
+    item_results: Vec<BulkItemResult>,
+    /// Sends new item results to subscribers; dropped once the operation has finished
+    item_results_sender: Option<broadcast::Sender<BulkItemResult>>,
+    /// Operation that has to complete before this one starts
+    depends_on: Option<String>,
+    /// What to run once `depends_on` completes; taken when the operation is started
+    chained_job: Option<ChainedJob>,
+}
+
+/// Work of a chained operation waiting for the operation it depends on
+struct ChainedJob {
+    operation: Box<dyn BatchOperation>,
+    params: serde_json::Value,
+}
+
+/// A chained operation whose parent has completed, ready to be queued
+pub struct ChainedOperation {
+    pub operation_id: String,
+    pub operation: Box<dyn BatchOperation>,
+    /// Parameters given when the operation was chained
+    pub params: serde_json::Value,
+    /// Priority inherited from the operation it was chained to
+    pub priority: OperationPriority,
+}
+
+/// Item results of an operation for a new subscriber
+pub struct ItemResultsSubscription {
+    /// Results recorded before subscribing
+    pub recorded: Vec<BulkItemResult>,
+    /// Results recorded from now on; `None` when the operation has already finished
+    pub live: Option<broadcast::Receiver<BulkItemResult>>,
+}
+
 /// Service for tracking progress of bulk operations
+///
+/// Every operation gets a cancellation token that `cancel_operation` triggers, so the
+/// task processing it can stop between items.
 #[derive(Clone)]
 pub struct ProgressService {
-    operations: Arc<RwLock<HashMap<String, BulkOperationProgress>>>,
+    operations: Arc<RwLock<HashMap<String, TrackedOperation>>>,
+    cancellation_tokens: Arc<DashMap<String, CancellationToken>>,
 }
 
 #[allow(dead_code)]
@@ -15,25 +70,144 @@ impl ProgressService {
     pub fn new() -> Self {
         Self {
             operations: Arc::new(RwLock::new(HashMap::new())),
+            cancellation_tokens: Arc::new(DashMap::new()),
         }
     }
 
     /// Create a new bulk operation and return its ID
     pub async fn create_operation(&self, total_items: usize) -> String {
         let operation_id = Uuid::new_v4().to_string();
-        let progress = BulkOperationProgress {
-            operation_id: operation_id.clone(),
-            status: BulkOperationStatus::Pending,
-            total_items,
-            processed_items: 0,
-            successful_items: 0,
-            failed_items: 0,
-            progress_percentage: 0.0,
+        self.cancellation_tokens
+            .insert(operation_id.clone(), CancellationToken::new());
+        let mut operations = self.operations.write().await;
+        operations.insert(
+            operation_id.clone(),
+            TrackedOperation::new(&operation_id, total_items),
+        );
+        operation_id
+    }
+
+    /// Create an operation running `next_operation` once `depends_on` has completed
+    ///
+    /// The new operation stays pending until then. If `depends_on` fails or is cancelled
+    /// instead, it is cancelled along with the rest of the chain. An operation has at most
+    /// one successor and a chain holds at most [`MAX_CHAIN_DEPTH`] operations.
+    pub async fn create_chained_operation(
+        &self,
+        depends_on: String,
+        next_operation: Box<dyn BatchOperation>,
+        params: serde_json::Value,
+    ) -> Result<String, ProgressServiceError> {
+        let mut operations = self.operations.write().await;
+        let parent = operations
+            .get(&depends_on)
+            .ok_or(ProgressServiceError::OperationNotFound)?;
+        // A finished operation would never start its successor
+        if parent.progress.status.is_finished() || parent.progress.next_operation_id.is_some() {
+            return Err(ProgressServiceError::InvalidOperationState);
+        }
+        if chain_length(&operations, &depends_on) >= MAX_CHAIN_DEPTH {
+            return Err(ProgressServiceError::ChainTooLong(MAX_CHAIN_DEPTH));
+        }
+
+        let operation_id = Uuid::new_v4().to_string();
+        let mut operation = TrackedOperation::new(&operation_id, 0);
+        operation.progress.priority = parent.progress.priority;
+        operation.depends_on = Some(depends_on.clone());
+        operation.chained_job = Some(ChainedJob {
+            operation: next_operation,
+            params,
+        });
+        if let Some(parent) = operations.get_mut(&depends_on) {
+            parent.touch().next_operation_id = Some(operation_id.clone());
+        }
+        self.cancellation_tokens
+            .insert(operation_id.clone(), CancellationToken::new());
+        operations.insert(operation_id.clone(), operation);
+        Ok(operation_id)
+    }
+
+    /// Take the operation chained to `operation_id`, which has just finished
+    ///
+    /// The chained operation is returned for queueing if `operation_id` completed. If it
+    /// failed or was cancelled, the operations chained after it are cancelled instead. Nothing
+    /// happens while `operation_id` is still running.
+    pub async fn take_next_operation(
+        &self,
+        operation_id: &str,
+    ) -> Result<Option<ChainedOperation>, ProgressServiceError> {
+        let mut operations = self.operations.write().await;
+        let operation = operations
+            .get(operation_id)
+            .ok_or(ProgressServiceError::OperationNotFound)?;
+        let Some(next_operation_id) = operation.progress.next_operation_id.clone() else {
+            return Ok(None);
         };
+        if !operation.progress.status.is_finished() {
+            return Ok(None);
+        }
+        if !matches!(operation.progress.status, BulkOperationStatus::Completed) {
+            self.cancel_chain(&mut operations, Some(next_operation_id));
+            return Ok(None);
+        }
+
+        // A successor cancelled while waiting has cancelled the rest of the chain already
+        let Some(next) = operations
+            .get_mut(&next_operation_id)
+            .filter(|next| matches!(next.progress.status, BulkOperationStatus::Pending))
+        else {
+            return Ok(None);
+        };
+        Ok(next.chained_job.take().map(|job| ChainedOperation {
+            operation_id: next_operation_id,
+            operation: job.operation,
+            params: job.params,
+            priority: next.progress.priority,
+        }))
+    }
+
+    /// Every operation of the chain `operation_id` belongs to, first to last
+    pub async fn get_chain(
+        &self,
+        operation_id: &str,
+    ) -> Result<Vec<BulkOperationProgress>, ProgressServiceError> {
+        let operations = self.operations.read().await;
+        let mut first = operations
+            .get(operation_id)
+            .ok_or(ProgressServiceError::OperationNotFound)?;
+        while let Some(parent) = first
+            .depends_on
+            .as_deref()
+            .and_then(|parent_id| operations.get(parent_id))
+        {
+            first = parent;
+        }
 
+        let mut chain = vec![first.progress.clone()];
+        let mut next_operation_id = first.progress.next_operation_id.as_deref();
+        while let Some(next) = next_operation_id.and_then(|next_id| operations.get(next_id)) {
+            chain.push(next.progress.clone());
+            next_operation_id = next.progress.next_operation_id.as_deref();
+        }
+        Ok(chain)
+    }
+
+    /// Record how many items a chained operation works on, known once it is started
+    pub async fn set_total_items(
+        &self,
+        operation_id: &str,
+        total_items: usize,
+    ) -> Result<(), ProgressServiceError> {
         let mut operations = self.operations.write().await;
-        operations.insert(operation_id.clone(), progress);
-        operation_id
+        if let Some(progress) = operations
+            .get_mut(operation_id)
+            .map(TrackedOperation::touch)
+        {
+            progress.total_items = total_items;
+            Ok(())
+        } else {
+            Err(ProgressServiceError::OperationNotFound)
+        }
     }
 
     /// Update operation status
@@ -43,8 +217,45 @@ impl ProgressService {
         status: BulkOperationStatus,
     ) -> Result<(), ProgressServiceError> {
         let mut operations = self.operations.write().await;
-        if let Some(progress) = operations.get_mut(operation_id) {
-            progress.status = status;
+        if let Some(operation) = operations.get_mut(operation_id) {
+            operation.touch().status = status;
+            operation.close_if_finished();
+            Ok(())
+        } else {
+            Err(ProgressServiceError::OperationNotFound)
+        }
+    }
+
+    /// Record the queue priority of an operation
+    pub async fn set_priority(
+        &self,
+        operation_id: &str,
+        priority: OperationPriority,
+    ) -> Result<(), ProgressServiceError> {
+        let mut operations = self.operations.write().await;
+        if let Some(progress) = operations
+            .get_mut(operation_id)
+            .map(TrackedOperation::touch)
+        {
+            progress.priority = priority;
+            Ok(())
+        } else {
+            Err(ProgressServiceError::OperationNotFound)
+        }
+    }
+
+    /// Record how many items are waiting to be retried
+    pub async fn set_pending_retries(
+        &self,
+        operation_id: &str,
+        pending_retries: usize,
+    ) -> Result<(), ProgressServiceError> {
+        let mut operations = self.operations.write().await;
+        if let Some(progress) = operations
+            .get_mut(operation_id)
+            .map(TrackedOperation::touch)
+        {
+            progress.pending_retries = pending_retries;
             Ok(())
         } else {
             Err(ProgressServiceError::OperationNotFound)
@@ -60,7 +271,8 @@ impl ProgressService {
         failed_items: usize,
     ) -> Result<(), ProgressServiceError> {
         let mut operations = self.operations.write().await;
-        if let Some(progress) = operations.get_mut(operation_id) {
+        if let Some(operation) = operations.get_mut(operation_id) {
+            let progress = operation.touch();
             progress.processed_items = processed_items;
             progress.successful_items = successful_items;
             progress.failed_items = failed_items;
@@ -70,7 +282,11 @@ impl ProgressService {
                 0.0
             };
 
-            // Update status based on progress
+            // Update status based on progress; items still finishing after a cancellation
+            // are counted without reviving the operation
+            if matches!(progress.status, BulkOperationStatus::Cancelled) {
+                return Ok(());
+            }
             if progress.processed_items >= progress.total_items {
                 if progress.failed_items == 0 {
                     progress.status = BulkOperationStatus::Completed;
@@ -82,6 +298,7 @@ impl ProgressService {
             } else if progress.processed_items > 0 {
                 progress.status = BulkOperationStatus::Processing;
             }
+            operation.close_if_finished();
             Ok(())
         } else {
             Err(ProgressServiceError::OperationNotFound)
@@ -96,50 +313,209 @@ impl ProgressService {
         let operations = self.operations.read().await;
         operations
             .get(operation_id)
-            .cloned()
+            .map(|operation| operation.progress.clone())
             .ok_or(ProgressServiceError::OperationNotFound)
     }
 
-    /// Cancel an operation
+    /// Record the result of one item and send it to subscribers
+    pub async fn record_item_result(
+        &self,
+        operation_id: &str,
+        result: BulkItemResult,
+    ) -> Result<(), ProgressServiceError> {
+        let mut operations = self.operations.write().await;
+        let operation = operations
+            .get_mut(operation_id)
+            .ok_or(ProgressServiceError::OperationNotFound)?;
+        if let Some(sender) = &operation.item_results_sender {
+            // Sending only fails when nobody is subscribed
+            let _ = sender.send(result.clone());
+        }
+        operation.item_results.push(result);
+        Ok(())
+    }
+
+    /// Item results recorded so far and, while the operation runs, a receiver for the rest
+    ///
+    /// No result falls between the two: recording waits for the subscription to complete.
+    pub async fn subscribe_item_results(
+        &self,
+        operation_id: &str,
+    ) -> Result<ItemResultsSubscription, ProgressServiceError> {
+        let operations = self.operations.read().await;
+        let operation = operations
+            .get(operation_id)
+            .ok_or(ProgressServiceError::OperationNotFound)?;
+        Ok(ItemResultsSubscription {
+            recorded: operation.item_results.clone(),
+            live: operation
+                .item_results_sender
+                .as_ref()
+                .map(broadcast::Sender::subscribe),
+        })
+    }
+
+    /// Token that is cancelled once the operation is cancelled
+    pub fn cancellation_token(&self, operation_id: &str) -> Option<CancellationToken> {
+        self.cancellation_tokens
+            .get(operation_id)
+            .map(|token| token.clone())
+    }
+
+    /// Cancel an operation and signal the task processing it to stop
     pub async fn cancel_operation(&self, operation_id: &str) -> Result<(), ProgressServiceError> {
         let mut operations = self.operations.write().await;
-        if let Some(progress) = operations.get_mut(operation_id) {
+        if let Some(operation) = operations.get_mut(operation_id) {
+            let progress = operation.touch();
+            // Nothing will record results for an operation that never started
+            let started = !matches!(progress.status, BulkOperationStatus::Pending);
+            progress.status = BulkOperationStatus::Cancelled;
+            if let Some(token) = self.cancellation_tokens.get(operation_id) {
+                token.cancel();
+            }
+            if !started {
+                operation.item_results_sender = None;
+                operation.chained_job = None;
+                // Nothing will finish this operation and start the ones chained after it
+                let next_operation_id = operation.progress.next_operation_id.clone();
+                self.cancel_chain(&mut operations, next_operation_id);
+            }
+            Ok(())
+        } else {
+            Err(ProgressServiceError::OperationNotFound)
+        }
+    }
+
+    /// Record that processing stopped after a cancellation, leaving `cancelled_items` undone
+    pub async fn mark_cancelled(
+        &self,
+        operation_id: &str,
+        cancelled_items: usize,
+    ) -> Result<(), ProgressServiceError> {
+        let mut operations = self.operations.write().await;
+        if let Some(operation) = operations.get_mut(operation_id) {
+            let progress = operation.touch();
             progress.status = BulkOperationStatus::Cancelled;
+            progress.cancelled_items = cancelled_items;
+            operation.close_if_finished();
             Ok(())
         } else {
             Err(ProgressServiceError::OperationNotFound)
         }
     }
 
-    /// Clean up old completed operations (older than 1 hour)
-    pub async fn cleanup_old_operations(&self) -> Result<usize, ProgressServiceError> {
+    /// Clean up completed, failed or cancelled operations last updated before `finished_before`
+    pub async fn cleanup_old_operations(
+        &self,
+        finished_before: DateTime<Utc>,
+    ) -> Result<usize, ProgressServiceError> {
         let mut operations = self.operations.write().await;
         let initial_count = operations.len();
 
-        // Remove operations that are completed, failed, or cancelled and older than 1 hour
-        operations.retain(|_, progress| {
-            match progress.status {
-                BulkOperationStatus::Completed
-                | BulkOperationStatus::Failed
-                | BulkOperationStatus::Cancelled => {
-                    // For simplicity, we'll keep operations for now
-                    // In a real implementation, you'd check timestamps here
-                    false
-                }
-                _ => true,
-            }
+        operations.retain(|_, operation| {
+            !operation.progress.status.is_finished() || operation.updated_at >= finished_before
         });
+        self.cancellation_tokens
+            .retain(|operation_id, _| operations.contains_key(operation_id));
 
         Ok(initial_count - operations.len())
     }
 
+    /// Count the operations `cleanup_old_operations` would forget
+    pub async fn count_old_operations(
+        &self,
+        finished_before: DateTime<Utc>,
+    ) -> Result<usize, ProgressServiceError> {
+        let operations = self.operations.read().await;
+        Ok(operations
+            .values()
+            .filter(|operation| {
+                operation.progress.status.is_finished() && operation.updated_at < finished_before
+            })
+            .count())
+    }
+
+    /// Cancel `next_operation_id` and every operation chained after it, none of which started
+    fn cancel_chain(
+        &self,
+        operations: &mut HashMap<String, TrackedOperation>,
+        mut next_operation_id: Option<String>,
+    ) {
+        while let Some(operation_id) = next_operation_id {
+            let Some(operation) = operations.get_mut(&operation_id) else {
+                break;
+            };
+            let progress = operation.touch();
+            progress.status = BulkOperationStatus::Cancelled;
+            progress.cancelled_items = progress.total_items;
+            next_operation_id = progress.next_operation_id.clone();
+            operation.chained_job = None;
+            operation.item_results_sender = None;
+            if let Some(token) = self.cancellation_tokens.get(&operation_id) {
+                token.cancel();
+            }
+        }
+    }
+
     /// Get all operations for a user (if we add user association later)
     pub async fn get_user_operations(
         &self,
         _user_id: i32,
     ) -> Result<Vec<BulkOperationProgress>, ProgressServiceError> {
         let operations = self.operations.read().await;
-        Ok(operations.values().cloned().collect())
+        Ok(operations
+            .values()
+            .map(|operation| operation.progress.clone())
+            .collect())
+    }
+}
+
+/// Number of operations from the start of the chain up to `operation_id`
+fn chain_length(operations: &HashMap<String, TrackedOperation>, operation_id: &str) -> usize {
+    let mut length = 0;
+    let mut current = Some(operation_id);
+    while let Some(operation) = current.and_then(|id| operations.get(id)) {
+        length += 1;
+        current = operation.depends_on.as_deref();
+    }
+    length
+}
+
+impl TrackedOperation {
+    fn new(operation_id: &str, total_items: usize) -> Self {
+        Self {
+            progress: BulkOperationProgress {
+                operation_id: operation_id.to_string(),
+                status: BulkOperationStatus::Pending,
+                total_items,
+                processed_items: 0,
+                successful_items: 0,
+                failed_items: 0,
+                progress_percentage: 0.0,
+                priority: OperationPriority::default(),
+                pending_retries: 0,
+                cancelled_items: 0,
+                next_operation_id: None,
+            },
+            updated_at: Utc::now(),
+            item_results: Vec::new(),
+            item_results_sender: Some(broadcast::channel(ITEM_RESULT_BUFFER).0),
+            depends_on: None,
+            chained_job: None,
+        }
+    }
+
+    /// Mark the operation as changed now and return its progress for updating
+    fn touch(&mut self) -> &mut BulkOperationProgress {
+        self.updated_at = Utc::now();
+        &mut self.progress
+    }
+
+    /// End the subscriptions to item results once the operation has finished
+    fn close_if_finished(&mut self) {
+        if self.progress.status.is_finished() {
+            self.item_results_sender = None;
+        }
     }
 }
 
@@ -159,6 +535,9 @@ pub enum ProgressServiceError {
     #[error("Invalid operation state")]
     InvalidOperationState,
 
+    #[error("Operation chains hold at most {0} operations")]
+    ChainTooLong(usize),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -212,6 +591,92 @@ mod tests {
         assert!(matches!(progress.status, BulkOperationStatus::Completed));
     }
 
+    fn item_result(index: usize) -> BulkItemResult {
+        BulkItemResult {
+            index,
+            url: format!("https://example.com/{}", index),
+            success: true,
+            short_code: Some(format!("code{}", index)),
+            error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_item_results_are_replayed_then_streamed_until_finished() {
+        let service = ProgressService::new();
+        let operation_id = service.create_operation(2).await;
+        service
+            .record_item_result(&operation_id, item_result(0))
+            .await
+            .unwrap();
+
+        let subscription = service.subscribe_item_results(&operation_id).await.unwrap();
+        assert_eq!(subscription.recorded, vec![item_result(0)]);
+        let mut live = subscription.live.unwrap();
+
+        service
+            .record_item_result(&operation_id, item_result(1))
+            .await
+            .unwrap();
+        service
+            .update_progress(&operation_id, 2, 2, 0)
+            .await
+            .unwrap();
+        assert_eq!(live.recv().await.unwrap(), item_result(1));
+        assert!(matches!(
+            live.recv().await,
+            Err(broadcast::error::RecvError::Closed)
+        ));
+
+        // Once finished, everything is replayed and there is nothing left to follow
+        let subscription = service.subscribe_item_results(&operation_id).await.unwrap();
+        assert_eq!(subscription.recorded.len(), 2);
+        assert!(subscription.live.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_set_priority() {
+        let service = ProgressService::new();
+        let operation_id = service.create_operation(10).await;
+        assert_eq!(
+            service.get_progress(&operation_id).await.unwrap().priority,
+            OperationPriority::Normal
+        );
+
+        service
+            .set_priority(&operation_id, OperationPriority::High)
+            .await
+            .unwrap();
+        assert_eq!(
+            service.get_progress(&operation_id).await.unwrap().priority,
+            OperationPriority::High
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_pending_retries() {
+        let service = ProgressService::new();
+        let operation_id = service.create_operation(10).await;
+        assert_eq!(
+            service
+                .get_progress(&operation_id)
+                .await
+                .unwrap()
+                .pending_retries,
+            0
+        );
+
+        service.set_pending_retries(&operation_id, 2).await.unwrap();
+        assert_eq!(
+            service
+                .get_progress(&operation_id)
+                .await
+                .unwrap()
+                .pending_retries,
+            2
+        );
+    }
+
     #[tokio::test]
     async fn test_cancel_operation() {
         let service = ProgressService::new();
@@ -221,6 +686,162 @@ mod tests {
 
         let progress = service.get_progress(&operation_id).await.unwrap();
         assert!(matches!(progress.status, BulkOperationStatus::Cancelled));
+        assert!(service
+            .cancellation_token(&operation_id)
+            .unwrap()
+            .is_cancelled());
+
+        // Items finishing after the cancellation do not revive the operation
+        service
+            .update_progress(&operation_id, 100, 100, 0)
+            .await
+            .unwrap();
+        let progress = service.get_progress(&operation_id).await.unwrap();
+        assert!(matches!(progress.status, BulkOperationStatus::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_old_operations_keeps_recent_and_running() {
+        let service = ProgressService::new();
+        let running = service.create_operation(10).await;
+        let finished = service.create_operation(10).await;
+        service.cancel_operation(&finished).await.unwrap();
+
+        let removed = service
+            .cleanup_old_operations(Utc::now() - chrono::Duration::days(1))
+            .await
+            .unwrap();
+        assert_eq!(removed, 0);
+
+        let removed = service
+            .cleanup_old_operations(Utc::now() + chrono::Duration::seconds(1))
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+        assert!(service.get_progress(&running).await.is_ok());
+        assert!(service.get_progress(&finished).await.is_err());
+        assert!(service.cancellation_token(&running).is_some());
+        assert!(service.cancellation_token(&finished).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_chained_operations_start_after_their_parent_completes() {
+        let service = ProgressService::new();
+        let first = service.create_operation(1).await;
+        let second = service
+            .create_chained_operation(
+                first.clone(),
+                crate::domain::services::batch_operation("deactivate").unwrap(),
+                serde_json::json!({ "url_ids": [1] }),
+            )
+            .await
+            .unwrap();
+
+        // Only one operation may follow another
+        let result = service
+            .create_chained_operation(
+                first.clone(),
+                crate::domain::services::batch_operation("delete").unwrap(),
+                serde_json::Value::Null,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(ProgressServiceError::InvalidOperationState)
+        ));
+
+        // Nothing starts while the parent runs
+        assert!(service.take_next_operation(&first).await.unwrap().is_none());
+        service.update_progress(&first, 1, 1, 0).await.unwrap();
+        let next = service.take_next_operation(&first).await.unwrap().unwrap();
+        assert_eq!(next.operation_id, second);
+        assert_eq!(next.operation.name(), "deactivate");
+        assert_eq!(next.params["url_ids"], serde_json::json!([1]));
+        // The job is handed out once
+        assert!(service.take_next_operation(&first).await.unwrap().is_none());
+
+        let chain = service.get_chain(&second).await.unwrap();
+        let ids: Vec<_> = chain.iter().map(|p| p.operation_id.clone()).collect();
+        assert_eq!(ids, [first.clone(), second]);
+        assert_eq!(chain[0].next_operation_id, Some(ids[1].clone()));
+
+        // Finished operations never start a successor
+        let result = service
+            .create_chained_operation(
+                first,
+                crate::domain::services::batch_operation("delete").unwrap(),
+                serde_json::Value::Null,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(ProgressServiceError::InvalidOperationState)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_chain_depth_is_limited() {
+        let service = ProgressService::new();
+        let mut last = service.create_operation(1).await;
+        for _ in 1..MAX_CHAIN_DEPTH {
+            last = service
+                .create_chained_operation(
+                    last,
+                    crate::domain::services::batch_operation("deactivate").unwrap(),
+                    serde_json::Value::Null,
+                )
+                .await
+                .unwrap();
+        }
+
+        let result = service
+            .create_chained_operation(
+                last.clone(),
+                crate::domain::services::batch_operation("deactivate").unwrap(),
+                serde_json::Value::Null,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(ProgressServiceError::ChainTooLong(MAX_CHAIN_DEPTH))
+        ));
+        assert_eq!(
+            service.get_chain(&last).await.unwrap().len(),
+            MAX_CHAIN_DEPTH
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_parent_cancels_the_rest_of_the_chain() {
+        let service = ProgressService::new();
+        let first = service.create_operation(1).await;
+        let second = service
+            .create_chained_operation(
+                first.clone(),
+                crate::domain::services::batch_operation("deactivate").unwrap(),
+                serde_json::Value::Null,
+            )
+            .await
+            .unwrap();
+        let third = service
+            .create_chained_operation(
+                second.clone(),
+                crate::domain::services::batch_operation("delete").unwrap(),
+                serde_json::Value::Null,
+            )
+            .await
+            .unwrap();
+
+        service.update_progress(&first, 1, 0, 1).await.unwrap();
+        assert!(service.take_next_operation(&first).await.unwrap().is_none());
+        for operation_id in [&second, &third] {
+            let progress = service.get_progress(operation_id).await.unwrap();
+            assert!(matches!(progress.status, BulkOperationStatus::Cancelled));
+            assert!(service
+                .cancellation_token(operation_id)
+                .unwrap()
+                .is_cancelled());
+        }
     }
 
     #[tokio::test]
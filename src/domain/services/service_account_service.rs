@@ -0,0 +1,148 @@
+use crate::domain::entities::ServiceAccount;
+use crate::domain::repositories::service_account_repository::RepositoryError;
+use crate::domain::repositories::ServiceAccountRepository;
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Prefix of every API key, so leaked keys are easy to recognize
+const API_KEY_PREFIX: &str = "sk_";
+
+/// Number of random characters in an API key
+const API_KEY_RANDOM_LENGTH: usize = 48;
+
+/// Domain service for machine accounts calling service-to-service endpoints
+#[derive(Clone)]
+pub struct ServiceAccountService {
+    repository: Arc<dyn ServiceAccountRepository>,
+}
+
+impl ServiceAccountService {
+    pub fn new(repository: Arc<dyn ServiceAccountRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Create a service account and return it with its API key
+    ///
+    /// The key is not stored and cannot be retrieved again.
+    pub async fn create_account(
+        &self,
+        name: &str,
+        created_by: i32,
+    ) -> Result<(ServiceAccount, String), ServiceAccountError> {
+        let name = name.trim();
+        if name.is_empty() || name.len() > 100 {
+            return Err(ServiceAccountError::InvalidInput(
+                "Name must be between 1 and 100 characters".to_string(),
+            ));
+        }
+
+        let api_key = Self::generate_api_key();
+        let account = self
+            .repository
+            .create_service_account(name, &Self::hash_api_key(&api_key), created_by)
+            .await
+            .map_err(|e| match e {
+                RepositoryError::DuplicateName => {
+                    ServiceAccountError::AlreadyExists(name.to_string())
+                }
+                e => ServiceAccountError::Repository(e),
+            })?;
+
+        Ok((account, api_key))
+    }
+
+    /// Find the service account an API key belongs to
+    pub async fn authenticate(&self, api_key: &str) -> Result<ServiceAccount, ServiceAccountError> {
+        if !api_key.starts_with(API_KEY_PREFIX) {
+            return Err(ServiceAccountError::InvalidApiKey);
+        }
+
+        self.repository
+            .find_by_api_key_hash(&Self::hash_api_key(api_key))
+            .await?
+            .ok_or(ServiceAccountError::InvalidApiKey)
+    }
+
+    /// Hash an API key for storage and lookup
+    pub fn hash_api_key(api_key: &str) -> String {
+        format!("{:x}", Sha256::digest(api_key.as_bytes()))
+    }
+
+    fn generate_api_key() -> String {
+        let random_part: String = thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(API_KEY_RANDOM_LENGTH)
+            .map(char::from)
+            .collect();
+        format!("{}{}", API_KEY_PREFIX, random_part)
+    }
+}
+
+/// Service account errors
+#[derive(Error, Debug)]
+pub enum ServiceAccountError {
+    #[error("Invalid API key")]
+    InvalidApiKey,
+
+    #[error("Service account '{0}' already exists")]
+    AlreadyExists(String),
+
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_utils::MockServiceAccountRepository;
+
+    fn service() -> ServiceAccountService {
+        ServiceAccountService::new(Arc::new(MockServiceAccountRepository::new()))
+    }
+
+    #[tokio::test]
+    async fn test_create_and_authenticate() {
+        let service = service();
+        let (account, api_key) = service.create_account(" analytics ", 1).await.unwrap();
+        assert_eq!(account.name, "analytics");
+        assert!(api_key.starts_with("sk_"));
+        assert_ne!(account.api_key_hash, api_key);
+
+        let authenticated = service.authenticate(&api_key).await.unwrap();
+        assert_eq!(authenticated.id, account.id);
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_rejects_unknown_key() {
+        let service = service();
+        service.create_account("analytics", 1).await.unwrap();
+
+        for api_key in ["sk_unknown", "not-a-key", ""] {
+            assert!(matches!(
+                service.authenticate(api_key).await,
+                Err(ServiceAccountError::InvalidApiKey)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_duplicate_and_invalid_names() {
+        let service = service();
+        service.create_account("analytics", 1).await.unwrap();
+
+        assert!(matches!(
+            service.create_account("analytics", 1).await,
+            Err(ServiceAccountError::AlreadyExists(_))
+        ));
+        assert!(matches!(
+            service.create_account("  ", 1).await,
+            Err(ServiceAccountError::InvalidInput(_))
+        ));
+    }
+}
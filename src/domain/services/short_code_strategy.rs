@@ -0,0 +1,193 @@
+//! How [`UrlService`](super::UrlService) picks the short code of a URL without a custom one
+//!
+//! The service still checks every generated code is free, and derives another from it when
+//! it is not, so strategies only have to produce valid codes.
+
+use crate::domain::entities::{ShortCode, ShortCodeAlphabet};
+use crate::domain::repositories::ShortCodeSequenceRepository;
+use crate::domain::services::ServiceError;
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// Shape of the codes a strategy generates
+#[derive(Debug, Clone)]
+pub struct GenerationConfig {
+    /// Characters of the code; sequential codes grow past it once the counter needs more
+    pub length: usize,
+    pub alphabet: ShortCodeAlphabet,
+}
+
+/// A way of generating short codes
+#[async_trait]
+pub trait ShortCodeStrategy: Send + Sync {
+    /// Generate a short code for `original_url`
+    async fn generate(
+        &self,
+        original_url: &str,
+        config: &GenerationConfig,
+    ) -> Result<ShortCode, ServiceError>;
+}
+
+/// Codes drawn at random; the default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomStrategy;
+
+#[async_trait]
+impl ShortCodeStrategy for RandomStrategy {
+    async fn generate(
+        &self,
+        _original_url: &str,
+        config: &GenerationConfig,
+    ) -> Result<ShortCode, ServiceError> {
+        Ok(ShortCode::generate_with_config(
+            rand::random(),
+            config.length,
+            &config.alphabet,
+        ))
+    }
+}
+
+/// Codes derived from the SHA-256 of the URL
+///
+/// The same URL always gets the same code first, so shortening it again finds the code in use
+/// and falls back to a derived one; callers can look the existing URL up instead.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashStrategy;
+
+#[async_trait]
+impl ShortCodeStrategy for HashStrategy {
+    async fn generate(
+        &self,
+        original_url: &str,
+        config: &GenerationConfig,
+    ) -> Result<ShortCode, ServiceError> {
+        let digest = Sha256::digest(original_url.as_bytes());
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&digest[..8]);
+        Ok(ShortCode::generate_with_config(
+            u64::from_be_bytes(prefix),
+            config.length,
+            &config.alphabet,
+        ))
+    }
+}
+
+/// Codes encoding the next value of a database counter
+///
+/// Two generated codes never collide, but codes are guessable and reveal how many URLs were
+/// shortened.
+#[derive(Clone)]
+pub struct SequentialStrategy {
+    sequence: Arc<dyn ShortCodeSequenceRepository>,
+}
+
+impl SequentialStrategy {
+    pub fn new(sequence: Arc<dyn ShortCodeSequenceRepository>) -> Self {
+        Self { sequence }
+    }
+}
+
+#[async_trait]
+impl ShortCodeStrategy for SequentialStrategy {
+    async fn generate(
+        &self,
+        _original_url: &str,
+        config: &GenerationConfig,
+    ) -> Result<ShortCode, ServiceError> {
+        let value = self.sequence.next_value().await?;
+        let alphabet: String = config.alphabet.chars().collect();
+        let base = alphabet.chars().count() as u64;
+
+        // Grow the code rather than wrap around once the counter outgrows the length
+        let mut digits = 1;
+        let mut remaining = value / base;
+        while remaining > 0 {
+            digits += 1;
+            remaining /= base;
+        }
+        Ok(ShortCode::from_u64(
+            value,
+            &alphabet,
+            config.length.max(digits),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::RepositoryError;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    struct MockSequence(AtomicU64);
+
+    #[async_trait]
+    impl ShortCodeSequenceRepository for MockSequence {
+        async fn next_value(&self) -> Result<u64, RepositoryError> {
+            Ok(self.0.fetch_add(1, Ordering::SeqCst) + 1)
+        }
+    }
+
+    fn config() -> GenerationConfig {
+        GenerationConfig {
+            length: 6,
+            alphabet: ShortCodeAlphabet::Base62,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hash_strategy_is_deterministic() {
+        let first = HashStrategy
+            .generate("https://example.com/page", &config())
+            .await
+            .unwrap();
+        let second = HashStrategy
+            .generate("https://example.com/page", &config())
+            .await
+            .unwrap();
+        let other = HashStrategy
+            .generate("https://example.com/other", &config())
+            .await
+            .unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_eq!(first.value().len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_sequential_strategy_counts_up() {
+        let strategy = SequentialStrategy::new(Arc::new(MockSequence(AtomicU64::new(0))));
+        let first = strategy.generate("https://a.com", &config()).await.unwrap();
+        let second = strategy.generate("https://a.com", &config()).await.unwrap();
+        assert_eq!(first.value(), "000001");
+        assert_eq!(second.value(), "000002");
+
+        // 62^6 no longer fits in six characters
+        let strategy =
+            SequentialStrategy::new(Arc::new(MockSequence(AtomicU64::new(62u64.pow(6) - 1))));
+        let code = strategy.generate("https://a.com", &config()).await.unwrap();
+        assert_eq!(code.value(), "1000000");
+    }
+
+    #[tokio::test]
+    async fn test_strategies_generate_valid_codes() {
+        let sequential = SequentialStrategy::new(Arc::new(MockSequence(AtomicU64::new(41))));
+        let strategies: [&dyn ShortCodeStrategy; 3] = [&RandomStrategy, &HashStrategy, &sequential];
+        for strategy in strategies {
+            for alphabet in [ShortCodeAlphabet::default(), ShortCodeAlphabet::Base58] {
+                let config = GenerationConfig {
+                    length: 6,
+                    alphabet,
+                };
+                let code = strategy
+                    .generate("https://example.com", &config)
+                    .await
+                    .unwrap();
+                assert!(ShortCode::new(code.value().to_string()).is_ok());
+                assert!(code.is_valid_for_alphabet(&config.alphabet));
+            }
+        }
+    }
+}
@@ -1,7 +1,46 @@
-use crate::domain::entities::{ShortCode, Url, UrlStatus};
-use crate::domain::repositories::{RepositoryError, UrlRepository};
+use crate::domain::entities::{
+    AuditLogEntry, ShortCode, ShortCodeAlphabet, Url, UrlCountChange, UrlStatus, UrlWithClickCount,
+    UserUrlStats,
+};
+use crate::domain::events::{DomainEvent, EventBus};
+use crate::domain::repositories::url_repository::{BatchItemResult, BatchOperationResult};
+use crate::domain::repositories::{
+    AuditLogRepository, CursorError, RepositoryError, SortDirection, UrlCursor, UrlPage,
+    UrlRepository, UrlSortField, UrlStats, UserRepository, UserUrlStatsRepository,
+};
+use crate::domain::services::batch_operations::BatchOperation;
+use crate::domain::services::short_code_strategy::{
+    GenerationConfig, RandomStrategy, ShortCodeStrategy,
+};
 use seahash::SeaHasher;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Default number of URLs returned by dashboard listings
+pub const DEFAULT_LISTING_LIMIT: usize = 10;
+
+/// Maximum number of URLs returned by dashboard listings
+pub const MAX_LISTING_LIMIT: usize = 100;
+
+/// Maximum number of old short codes that keep resolving to a renamed URL
+pub const MAX_SHORT_CODE_ALIASES: usize = 3;
+
+/// Length of generated short codes unless configured otherwise
+const DEFAULT_SHORT_CODE_LENGTH: usize = 6;
+
+/// Canonical form of a URL to look up; URLs that have none can only match themselves
+fn canonical_or_raw(original_url: &str) -> String {
+    Url::canonical_form(original_url).unwrap_or_else(|_| original_url.to_string())
+}
+
+/// Fields to change when duplicating a URL; everything else is copied from the original
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateOverrides {
+    pub original_url: Option<String>,
+    pub custom_short_code: Option<ShortCode>,
+}
 
 /// Domain service for URL operations
 /// Contains business logic that doesn't belong to a specific entity
@@ -11,6 +50,34 @@ where
     R: UrlRepository + Clone,
 {
     repository: R,
+    short_code_length: usize,
+    short_code_alphabet: ShortCodeAlphabet,
+    /// Picks the first code tried for a URL
+    short_code_strategy: Arc<dyn ShortCodeStrategy + Send + Sync>,
+    /// Generated codes must sound at least this different from codes in use; 0 disables the check
+    min_phonetic_distance: f32,
+    /// Looks up the recipients of URL transfers
+    user_repository: Option<Arc<dyn UserRepository>>,
+    audit_log: Option<Arc<dyn AuditLogRepository>>,
+    /// URLs a user may own before transfers to them are refused; 0 means no limit
+    max_urls_per_user: u32,
+    /// URLs an organization may own, checked as its URLs are created; 0 means no limit
+    max_urls_per_organization: i64,
+    /// Tells other services about created URLs
+    event_bus: Option<Arc<dyn EventBus>>,
+    /// Per-user URL counters adjusted as URLs are created and change status
+    user_url_stats: Option<Arc<dyn UserUrlStatsRepository>>,
+}
+
+/// Outcome of re-encoding stored short codes to the configured alphabet
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReencodeSummary {
+    /// URLs looked at
+    pub checked: usize,
+    /// URLs given a new short code; their old code keeps redirecting
+    pub reencoded: usize,
+    /// URLs whose new code could not be stored
+    pub failed: usize,
 }
 
 #[allow(dead_code)]
@@ -19,26 +86,123 @@ where
     R: UrlRepository + Clone,
 {
     pub fn new(repository: R) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            short_code_length: DEFAULT_SHORT_CODE_LENGTH,
+            short_code_alphabet: ShortCodeAlphabet::default(),
+            short_code_strategy: Arc::new(RandomStrategy),
+            min_phonetic_distance: 0.0,
+            user_repository: None,
+            audit_log: None,
+            max_urls_per_user: 0,
+            max_urls_per_organization: 0,
+            event_bus: None,
+            user_url_stats: None,
+        }
+    }
+
+    /// Generate short codes of this many characters
+    pub fn with_short_code_length(mut self, short_code_length: usize) -> Self {
+        self.short_code_length = short_code_length;
+        self
+    }
+
+    /// Generate short codes from the characters of `short_code_alphabet`
+    pub fn with_short_code_alphabet(mut self, short_code_alphabet: ShortCodeAlphabet) -> Self {
+        self.short_code_alphabet = short_code_alphabet;
+        self
+    }
+
+    /// Pick generated short codes with `short_code_strategy` instead of at random
+    pub fn with_short_code_strategy(
+        mut self,
+        short_code_strategy: Box<dyn ShortCodeStrategy + Send + Sync>,
+    ) -> Self {
+        self.short_code_strategy = Arc::from(short_code_strategy);
+        self
+    }
+
+    /// Look up users through `user_repository`; needed to transfer URLs
+    pub fn with_user_repository(mut self, user_repository: Arc<dyn UserRepository>) -> Self {
+        self.user_repository = Some(user_repository);
+        self
+    }
+
+    /// Record administrative actions such as URL transfers in `audit_log`
+    pub fn with_audit_log(mut self, audit_log: Arc<dyn AuditLogRepository>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Refuse transfers to users already owning this many URLs; 0 means no limit
+    pub fn with_max_urls_per_user(mut self, max_urls_per_user: u32) -> Self {
+        self.max_urls_per_user = max_urls_per_user;
+        self
+    }
+
+    /// Refuse to create URLs for organizations already owning this many; 0 means no limit
+    pub fn with_max_urls_per_organization(mut self, max_urls_per_organization: i64) -> Self {
+        self.max_urls_per_organization = max_urls_per_organization;
+        self
+    }
+
+    /// Publish events such as [`DomainEvent::UrlCreated`] on `event_bus`
+    pub fn with_event_bus(mut self, event_bus: Arc<dyn EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Keep the URL counters of each owner in `user_url_stats` up to date; needed to read
+    /// and rebuild them
+    pub fn with_user_url_stats(mut self, user_url_stats: Arc<dyn UserUrlStatsRepository>) -> Self {
+        self.user_url_stats = Some(user_url_stats);
+        self
+    }
+
+    /// Regenerate short codes sounding too much like one in use, see
+    /// [`ShortCode::phonetic_distance`]
+    pub fn with_min_phonetic_distance(mut self, min_phonetic_distance: f32) -> Self {
+        self.min_phonetic_distance = min_phonetic_distance;
+        self
+    }
+
+    /// Alphabet generated short codes are made of
+    pub fn short_code_alphabet(&self) -> &ShortCodeAlphabet {
+        &self.short_code_alphabet
     }
 
     /// Generate a unique short code for a URL
     pub async fn generate_short_code(&self, original_url: &str) -> Result<ShortCode, ServiceError> {
-        // Start with a hash-based approach
-        let mut hasher = SeaHasher::new();
-        original_url.hash(&mut hasher);
-        let hash = hasher.finish();
-
-        // Convert to base62-like encoding
-        let short_code = self.hash_to_short_code(hash);
-        let short_code = ShortCode::new(short_code)?;
+        let config = GenerationConfig {
+            length: self.short_code_length,
+            alphabet: self.short_code_alphabet.clone(),
+        };
+        let short_code = self
+            .short_code_strategy
+            .generate(original_url, &config)
+            .await?;
 
-        // Check if it already exists, if so, append a suffix
-        if self.repository.exists_by_short_code(&short_code).await? {
-            self.generate_unique_short_code(&short_code).await
-        } else {
+        // Check if it already exists, if so, rehash until a free code is found
+        if self.is_generated_code_available(&short_code).await? {
             Ok(short_code)
+        } else {
+            self.generate_unique_short_code(&short_code).await
+        }
+    }
+
+    /// Whether a generated code is unused and, when enabled, sounds unlike the codes in use
+    async fn is_generated_code_available(&self, code: &ShortCode) -> Result<bool, ServiceError> {
+        if self.repository.exists_by_short_code(code).await? {
+            return Ok(false);
         }
+        if self.min_phonetic_distance <= 0.0 {
+            return Ok(true);
+        }
+        let similar = self
+            .repository
+            .find_phonetically_similar_codes(code, self.min_phonetic_distance)
+            .await?;
+        Ok(similar.is_empty())
     }
 
     /// Generate a unique short code with collision handling
@@ -50,23 +214,16 @@ where
         let base_value = base_code.value();
 
         loop {
-            let candidate = if counter < 10 {
-                format!("{}{}", base_value, counter)
-            } else {
-                // Use a more sophisticated approach for higher numbers
-                let mut hasher = SeaHasher::new();
-                (base_value, counter).hash(&mut hasher);
-                let hash = hasher.finish();
-                self.hash_to_short_code(hash)
-            };
-
-            let candidate_code = ShortCode::new(candidate)?;
+            // Rehash instead of appending a suffix so every code has the configured length
+            let mut hasher = SeaHasher::new();
+            (base_value, counter).hash(&mut hasher);
+            let candidate_code = ShortCode::generate_with_config(
+                hasher.finish(),
+                self.short_code_length,
+                &self.short_code_alphabet,
+            );
 
-            if !self
-                .repository
-                .exists_by_short_code(&candidate_code)
-                .await?
-            {
+            if self.is_generated_code_available(&candidate_code).await? {
                 return Ok(candidate_code);
             }
 
@@ -77,21 +234,6 @@ where
         }
     }
 
-    /// Convert a hash to a short code string
-    fn hash_to_short_code(&self, hash: u64) -> String {
-        const CHARS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
-        let mut result = String::new();
-        let mut value = hash;
-
-        // Generate 6-8 character code
-        for _ in 0..6 {
-            result.push(CHARS[(value % 62) as usize] as char);
-            value /= 62;
-        }
-
-        result
-    }
-
     /// Create a URL with auto-generated short code
     pub async fn create_url(
         &self,
@@ -99,28 +241,221 @@ where
         custom_short_code: Option<ShortCode>,
         expiration_date: Option<chrono::DateTime<chrono::Utc>>,
         user_id: Option<i32>,
+    ) -> Result<Url, ServiceError> {
+        self.create_url_in_organization(
+            original_url,
+            custom_short_code,
+            expiration_date,
+            user_id,
+            None,
+        )
+        .await
+    }
+
+    /// Create a URL, optionally owned by an organization
+    ///
+    /// Membership and the organization's rate limit are checked by `OrgService` before
+    /// calling this. The organization's URL quota is enforced by the insert, failing with
+    /// `RepositoryError::OrganizationQuotaExceeded`.
+    pub async fn create_url_in_organization(
+        &self,
+        original_url: &str,
+        custom_short_code: Option<ShortCode>,
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Option<i32>,
+        organization_id: Option<i32>,
     ) -> Result<Url, ServiceError> {
         let short_code = match custom_short_code {
             Some(code) => {
-                // Validate custom short code
-                if self.repository.exists_by_short_code(&code).await? {
-                    return Err(ServiceError::ShortCodeAlreadyExists);
+                // Resubmitting the same link under its custom code returns the stored URL
+                if let Some(existing) = self.repository.find_by_short_code(&code, false).await? {
+                    return Self::same_link(existing, original_url, user_id, organization_id);
                 }
                 code
             }
             None => self.generate_short_code(original_url).await?,
         };
 
-        self.repository
-            .create_url(
+        // Requests racing past the lookup above all get the single stored row back
+        let max_organization_urls =
+            (self.max_urls_per_organization > 0).then_some(self.max_urls_per_organization);
+        let (url, inserted) = self
+            .repository
+            .create_url_idempotent(
                 &short_code,
                 original_url,
                 expiration_date,
                 user_id,
+                organization_id,
+                max_organization_urls,
                 UrlStatus::Active,
             )
+            .await?;
+        let url = Self::same_link(url, original_url, user_id, organization_id)?;
+        // Only the request that inserted the row counts it and announces it
+        if inserted {
+            self.adjust_url_counts(url.user_id, UrlCountChange::created(&url.status))
+                .await;
+            self.publish(DomainEvent::UrlCreated { url: url.clone() });
+        }
+        Ok(url)
+    }
+
+    /// Add `change` to the URL counters of `user_id`, if the URL has an owner
+    ///
+    /// Like events, the counters never fail the operation; `rebuild_user_url_stats`
+    /// corrects any drift.
+    async fn adjust_url_counts(&self, user_id: Option<i32>, change: UrlCountChange) {
+        let (Some(user_url_stats), Some(user_id)) = (&self.user_url_stats, user_id) else {
+            return;
+        };
+        if change.is_empty() {
+            return;
+        }
+        if let Err(e) = user_url_stats.adjust_url_counts(user_id, change).await {
+            warn!("Failed to update the URL stats of user {}: {}", user_id, e);
+        }
+    }
+
+    /// Move the URLs `archive_expired_urls` archived to their owners' archived counts
+    async fn count_archived_urls(&self, archived: &[Url]) {
+        let changes = UrlCountChange::per_user(archived.iter().map(|url| {
+            let change = UrlCountChange::status_changed(&url.status, &UrlStatus::Archived);
+            (url.user_id, change)
+        }));
+        for (user_id, change) in changes {
+            self.adjust_url_counts(Some(user_id), change).await;
+        }
+    }
+
+    /// The URL as it was before a change, when its owner's counters need adjusting
+    async fn url_before_change(&self, id: i32) -> Result<Option<Url>, ServiceError> {
+        if self.user_url_stats.is_none() {
+            return Ok(None);
+        }
+        Ok(self.repository.find_by_id(id).await?)
+    }
+
+    /// The URLs a batch operation is about to change, when their owners' counters need adjusting
+    async fn urls_before_batch(&self, url_ids: &[i32]) -> Result<Vec<Url>, ServiceError> {
+        let mut urls = Vec::new();
+        if self.user_url_stats.is_none() {
+            return Ok(urls);
+        }
+        for &id in url_ids {
+            if let Some(url) = self.repository.find_by_id(id).await? {
+                urls.push(url);
+            }
+        }
+        Ok(urls)
+    }
+
+    /// Adjust the owners' counters for the URLs a batch operation changed
+    ///
+    /// Each changed URL is read again, so any operation is counted by the status it left.
+    async fn count_batch_changes(&self, before: Vec<Url>, result: &BatchOperationResult) {
+        let changed: HashSet<i32> = result
+            .results
+            .iter()
+            .filter(|item| item.success)
+            .map(|item| item.url_id)
+            .collect();
+        let mut changes = Vec::new();
+        for url in before.into_iter().filter(|url| changed.contains(&url.id)) {
+            let change = match self.repository.find_by_id(url.id).await {
+                Ok(Some(after)) => UrlCountChange::status_changed(&url.status, &after.status),
+                Ok(None) => UrlCountChange::removed(&url.status),
+                Err(e) => {
+                    warn!(
+                        "Failed to read URL {} after a batch operation: {}",
+                        url.id, e
+                    );
+                    continue;
+                }
+            };
+            changes.push((url.user_id, change));
+        }
+        for (user_id, change) in UrlCountChange::per_user(changes) {
+            self.adjust_url_counts(Some(user_id), change).await;
+        }
+    }
+
+    /// Publish `event` if an event bus is configured; subscribers never fail the operation
+    fn publish(&self, event: DomainEvent) {
+        if let Some(event_bus) = &self.event_bus {
+            if let Err(e) = event_bus.publish(event) {
+                warn!("Failed to publish domain event: {}", e);
+            }
+        }
+    }
+
+    /// `url` if it links `original_url` for the same owner, otherwise the short code is taken
+    fn same_link(
+        url: Url,
+        original_url: &str,
+        user_id: Option<i32>,
+        organization_id: Option<i32>,
+    ) -> Result<Url, ServiceError> {
+        if url.original_url == original_url
+            && url.user_id == user_id
+            && url.organization_id == organization_id
+        {
+            Ok(url)
+        } else {
+            Err(ServiceError::ShortCodeAlreadyExists)
+        }
+    }
+
+    /// Create a copy of a URL owned by `user_id`
+    ///
+    /// The copy keeps the expiration date, status and organization of the original and gets
+    /// a new short code unless one is given. Clicks are not copied.
+    pub async fn duplicate_url(
+        &self,
+        id: i32,
+        user_id: i32,
+        overrides: DuplicateOverrides,
+    ) -> Result<Url, ServiceError> {
+        let original = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or(ServiceError::Repository(RepositoryError::NotFound))?;
+        if original.user_id != Some(user_id) {
+            return Err(ServiceError::PermissionDenied(
+                "URL belongs to another user".to_string(),
+            ));
+        }
+
+        let original_url = overrides.original_url.unwrap_or(original.original_url);
+        let short_code = match overrides.custom_short_code {
+            Some(code) => {
+                if self.repository.exists_by_short_code(&code).await? {
+                    return Err(ServiceError::ShortCodeAlreadyExists);
+                }
+                code
+            }
+            None => self.generate_short_code(&original_url).await?,
+        };
+
+        let url = self
+            .repository
+            .create_url(
+                &short_code,
+                &original_url,
+                original.expiration_date,
+                Some(user_id),
+                original.organization_id,
+                original.status,
+            )
             .await
-            .map_err(ServiceError::from)
+            .map_err(|error| match error {
+                RepositoryError::DuplicateShortCode => ServiceError::ShortCodeAlreadyExists,
+                error => ServiceError::from(error),
+            })?;
+        self.adjust_url_counts(url.user_id, UrlCountChange::created(&url.status))
+            .await;
+        Ok(url)
     }
 
     /// Get URL by short code
@@ -129,7 +464,7 @@ where
         short_code: &ShortCode,
     ) -> Result<Option<Url>, ServiceError> {
         self.repository
-            .find_by_short_code(short_code)
+            .find_by_short_code(short_code, false)
             .await
             .map_err(ServiceError::from)
     }
@@ -137,230 +472,756 @@ where
     /// Get URLs for a specific user
     pub async fn get_urls_for_user(&self, user_id: i32) -> Result<Vec<Url>, ServiceError> {
         self.repository
-            .find_by_user_id(user_id)
+            .find_by_user_id(user_id, None)
             .await
             .map_err(ServiceError::from)
     }
 
-    /// Delete a URL (with ownership check)
-    pub async fn delete_url(&self, id: i32, user_id: Option<i32>) -> Result<bool, ServiceError> {
+    /// Get URLs owned by an organization
+    ///
+    /// Membership must be checked by the caller.
+    pub async fn get_urls_for_organization(
+        &self,
+        user_id: i32,
+        organization_id: i32,
+    ) -> Result<Vec<Url>, ServiceError> {
         self.repository
-            .delete_by_id(id, user_id)
+            .find_by_user_id(user_id, Some(organization_id))
             .await
             .map_err(ServiceError::from)
     }
 
-    /// Update a URL (with ownership check)
-    pub async fn update_url(&self, url: &Url) -> Result<Url, ServiceError> {
+    /// Get a user's most clicked URLs
+    ///
+    /// The limit is clamped to `1..=MAX_LISTING_LIMIT`.
+    pub async fn get_most_clicked_urls(
+        &self,
+        user_id: i32,
+        limit: usize,
+    ) -> Result<Vec<UrlWithClickCount>, ServiceError> {
         self.repository
-            .update_url(url)
+            .find_most_clicked(user_id, limit.clamp(1, MAX_LISTING_LIMIT))
             .await
             .map_err(ServiceError::from)
     }
 
-    /// Get URL by short code with validation (expiration and status)
-    pub async fn get_url_by_short_code_with_validation(
+    /// Get a user's most recently created URLs
+    ///
+    /// The limit is clamped to `1..=MAX_LISTING_LIMIT`.
+    pub async fn get_recent_urls(
         &self,
-        short_code: &ShortCode,
-    ) -> Result<Option<Url>, ServiceError> {
-        match self.repository.find_by_short_code(short_code).await? {
-            Some(url) => {
-                if !url.is_accessible() {
-                    // URL is either expired or inactive
-                    Ok(None)
-                } else {
-                    Ok(Some(url))
-                }
-            }
-            None => Ok(None),
-        }
+        user_id: i32,
+        limit: usize,
+    ) -> Result<Vec<Url>, ServiceError> {
+        self.repository
+            .find_recently_created(user_id, limit.clamp(1, MAX_LISTING_LIMIT))
+            .await
+            .map_err(ServiceError::from)
     }
 
-    /// Get URLs that are expiring soon
-    pub async fn get_urls_expiring_soon(
+    /// Find a user's URLs pointing at `original_url`, newest first
+    ///
+    /// URLs are compared in canonical form, see [`Url::canonical_form`].
+    pub async fn find_user_urls_by_original_url(
         &self,
-        duration: chrono::Duration,
+        original_url: &str,
+        user_id: i32,
     ) -> Result<Vec<Url>, ServiceError> {
         self.repository
-            .find_urls_expiring_soon(duration)
+            .find_by_original_url(&canonical_or_raw(original_url), user_id)
             .await
             .map_err(ServiceError::from)
     }
 
-    /// Get expired URLs
-    pub async fn get_expired_urls(&self) -> Result<Vec<Url>, ServiceError> {
+    /// Find URLs of every user pointing at `original_url` in canonical form, newest first
+    ///
+    /// Administrative only. The limit is clamped to `1..=MAX_LISTING_LIMIT`.
+    pub async fn find_all_urls_by_original_url(
+        &self,
+        original_url: &str,
+        limit: usize,
+    ) -> Result<Vec<Url>, ServiceError> {
         self.repository
-            .find_expired_urls()
+            .find_all_by_original_url(
+                &canonical_or_raw(original_url),
+                limit.clamp(1, MAX_LISTING_LIMIT),
+            )
             .await
             .map_err(ServiceError::from)
     }
 
-    /// Clean up expired URLs
-    pub async fn cleanup_expired_urls(&self) -> Result<u64, ServiceError> {
+    /// Get one page of the URLs of every user with the given status, for moderation
+    ///
+    /// Administrative only. The limit is clamped to `1..=MAX_LISTING_LIMIT`; the returned ID
+    /// is the `after_id` of the next page, `None` on the last one.
+    pub async fn list_urls_by_status(
+        &self,
+        status: UrlStatus,
+        after_id: Option<i32>,
+        limit: usize,
+    ) -> Result<(Vec<Url>, Option<i32>), ServiceError> {
         self.repository
-            .delete_expired_urls()
+            .find_by_status_paginated(status, after_id, limit.clamp(1, MAX_LISTING_LIMIT))
             .await
             .map_err(ServiceError::from)
     }
 
-    /// Soft delete a URL (deactivate)
-    pub async fn deactivate_url(
+    /// Find URLs created in the last `created_within_hours` hours that already have at least
+    /// `min_click_count` clicks, which hints at bot traffic
+    ///
+    /// Administrative only.
+    pub async fn find_suspicious_urls(
         &self,
-        id: i32,
-        user_id: Option<i32>,
-    ) -> Result<bool, ServiceError> {
+        min_click_count: i64,
+        created_within_hours: u8,
+    ) -> Result<Vec<Url>, ServiceError> {
         self.repository
-            .soft_delete_by_id(id, user_id)
+            .find_suspicious_urls(min_click_count, created_within_hours)
             .await
             .map_err(ServiceError::from)
     }
 
-    /// Reactivate a URL
-    pub async fn reactivate_url(
+    /// Count the recorded clicks of each of `url_ids`; URLs without clicks may be missing
+    pub async fn count_clicks_for_urls(
         &self,
-        id: i32,
-        user_id: Option<i32>,
-    ) -> Result<bool, ServiceError> {
+        url_ids: &[i32],
+    ) -> Result<HashMap<i32, i64>, ServiceError> {
         self.repository
-            .reactivate_by_id(id, user_id)
+            .count_clicks_for_urls(url_ids)
             .await
             .map_err(ServiceError::from)
     }
 
-    /// Get URLs by status
-    pub async fn get_urls_by_status(
+    /// Get a user's URLs changed after `since`, for activity feeds
+    ///
+    /// The limit is clamped to `1..=MAX_LISTING_LIMIT`.
+    pub async fn get_recently_modified_urls(
         &self,
-        status: UrlStatus,
-        user_id: Option<i32>,
+        user_id: i32,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: usize,
     ) -> Result<Vec<Url>, ServiceError> {
         self.repository
-            .find_by_status(status, user_id)
+            .find_recently_modified(user_id, since, limit.clamp(1, MAX_LISTING_LIMIT))
             .await
             .map_err(ServiceError::from)
     }
 
-    /// Get deactivated URLs for a user
-    pub async fn get_deactivated_urls(
+    /// List a user's URLs one page at a time
+    ///
+    /// Only URLs with `status` are listed; without it archived URLs are left out. `after` is the `next_cursor` and `before` the `prev_cursor` of a previous page; at most
+    /// one may be given and it must have been issued for the same sort order. The limit is
+    /// clamped to `1..=MAX_LISTING_LIMIT`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn list_urls(
         &self,
-        user_id: Option<i32>,
-    ) -> Result<Vec<Url>, ServiceError> {
-        self.get_urls_by_status(UrlStatus::Inactive, user_id).await
+        user_id: i32,
+        status: Option<UrlStatus>,
+        sort: UrlSortField,
+        direction: SortDirection,
+        after: Option<&str>,
+        before: Option<&str>,
+        limit: usize,
+    ) -> Result<UrlPage, ServiceError> {
+        let decode = |encoded: &str| {
+            let cursor = UrlCursor::decode(encoded)?;
+            cursor.ensure_order(sort, direction)?;
+            Ok(cursor)
+        };
+        let limit = limit.clamp(1, MAX_LISTING_LIMIT);
+
+        match (after, before) {
+            (Some(_), Some(_)) => Err(ServiceError::InvalidData(
+                "Only one of after and before may be given".to_string(),
+            )),
+            (None, Some(before)) => {
+                let cursor = decode(before)
+                    .map_err(|e: CursorError| ServiceError::InvalidData(e.to_string()))?;
+
+                // Walk backwards from the cursor, then put the URLs back in listing order
+                let reversed = self
+                    .repository
+                    .find_paginated(
+                        Some(user_id),
+                        status,
+                        sort,
+                        direction.reversed(),
+                        Some(&cursor.reversed()),
+                        limit,
+                    )
+                    .await?;
+                let mut urls = reversed.urls;
+                urls.reverse();
+
+                let prev_cursor = reversed
+                    .next_cursor
+                    .and(urls.first())
+                    .map(|url| UrlCursor::encode(url, sort, direction));
+                let next_cursor = urls
+                    .last()
+                    .map(|url| UrlCursor::encode(url, sort, direction));
+                Ok(UrlPage {
+                    urls,
+                    next_cursor,
+                    prev_cursor,
+                })
+            }
+            (after, None) => {
+                let cursor = after
+                    .map(decode)
+                    .transpose()
+                    .map_err(|e: CursorError| ServiceError::InvalidData(e.to_string()))?;
+
+                let mut page = self
+                    .repository
+                    .find_paginated(
+                        Some(user_id),
+                        status,
+                        sort,
+                        direction,
+                        cursor.as_ref(),
+                        limit,
+                    )
+                    .await?;
+                // Only a page reached through a cursor has anything before it
+                if cursor.is_some() {
+                    page.prev_cursor = page
+                        .urls
+                        .first()
+                        .map(|url| UrlCursor::encode(url, sort, direction));
+                }
+                Ok(page)
+            }
+        }
     }
 
-    /// Batch deactivate URLs
-    pub async fn batch_deactivate_urls(
-        &self,
-        url_ids: &[i32],
-        user_id: Option<i32>,
-    ) -> Result<crate::domain::repositories::url_repository::BatchOperationResult, ServiceError>
-    {
+    /// Get a user's URL and click totals from the counters kept in `user_url_stats`
+    ///
+    /// Cheap for any number of URLs, unlike `get_stats`, which aggregates them.
+    pub async fn get_user_url_stats(&self, user_id: i32) -> Result<UserUrlStats, ServiceError> {
+        let user_url_stats = self.user_url_stats.as_ref().ok_or_else(|| {
+            RepositoryError::Internal("User URL stats need a stats repository".to_string())
+        })?;
+        Ok(user_url_stats
+            .find_by_user_id(user_id)
+            .await?
+            .unwrap_or_else(|| UserUrlStats::empty(user_id)))
+    }
+
+    /// Estimate the distinct visitors across a user's URLs
+    pub async fn estimate_unique_visitors(&self, user_id: i32) -> Result<i64, ServiceError> {
+        let user_url_stats = self.user_url_stats.as_ref().ok_or_else(|| {
+            RepositoryError::Internal("User URL stats need a stats repository".to_string())
+        })?;
+        Ok(user_url_stats.estimate_unique_visitors(user_id).await?)
+    }
+
+    /// Recompute the URL and click counters of every user, returning how many were written
+    pub async fn rebuild_user_url_stats(&self) -> Result<u64, ServiceError> {
+        let user_url_stats = self.user_url_stats.as_ref().ok_or_else(|| {
+            RepositoryError::Internal("User URL stats need a stats repository".to_string())
+        })?;
+        Ok(user_url_stats.rebuild_all().await?)
+    }
+
+    /// Get URL statistics, optionally scoped to a user
+    pub async fn get_stats(&self, user_id: Option<i32>) -> Result<UrlStats, ServiceError> {
         self.repository
-            .batch_deactivate_urls(url_ids, user_id)
+            .get_stats(user_id)
             .await
             .map_err(ServiceError::from)
     }
 
-    /// Batch reactivate URLs
-    pub async fn batch_reactivate_urls(
-        &self,
-        url_ids: &[i32],
-        user_id: Option<i32>,
-    ) -> Result<crate::domain::repositories::url_repository::BatchOperationResult, ServiceError>
-    {
+    /// Delete a URL (with ownership check)
+    ///
+    /// The URL stops resolving at once; the cleanup removes it for good after the deleted URL
+    /// retention.
+    pub async fn delete_url(&self, id: i32, user_id: Option<i32>) -> Result<bool, ServiceError> {
+        let before = self.url_before_change(id).await?;
+        let deleted = self.repository.delete_by_id(id, user_id).await?;
+        if let (true, Some(before)) = (deleted, before) {
+            self.adjust_url_counts(before.user_id, UrlCountChange::removed(&before.status))
+                .await;
+        }
+        Ok(deleted)
+    }
+
+    /// Find a URL by ID
+    pub async fn get_url_by_id(&self, id: i32) -> Result<Option<Url>, ServiceError> {
         self.repository
-            .batch_reactivate_urls(url_ids, user_id)
+            .find_by_id(id)
             .await
             .map_err(ServiceError::from)
     }
 
-    /// Batch delete URLs
-    pub async fn batch_delete_urls(
-        &self,
-        url_ids: &[i32],
-        user_id: Option<i32>,
-    ) -> Result<crate::domain::repositories::url_repository::BatchOperationResult, ServiceError>
-    {
+    /// Check whether a short code is already used by any URL
+    pub async fn short_code_exists(&self, short_code: &ShortCode) -> Result<bool, ServiceError> {
         self.repository
-            .batch_delete_urls(url_ids, user_id)
+            .exists_by_short_code(short_code)
             .await
             .map_err(ServiceError::from)
     }
 
-    /// Batch update URL status
-    pub async fn batch_update_status(
+    /// Give a URL a new short code, keeping the old one as an alias that still redirects
+    ///
+    /// Fails with `RepositoryError::AliasLimitReached` once the URL has
+    /// `MAX_SHORT_CODE_ALIASES` aliases.
+    pub async fn rename_short_code(
         &self,
-        url_ids: &[i32],
-        status: UrlStatus,
-        user_id: Option<i32>,
-    ) -> Result<crate::domain::repositories::url_repository::BatchOperationResult, ServiceError>
-    {
+        url_id: i32,
+        new_short_code: &ShortCode,
+    ) -> Result<Url, ServiceError> {
+        if self.repository.exists_by_short_code(new_short_code).await? {
+            return Err(ServiceError::ShortCodeAlreadyExists);
+        }
         self.repository
-            .batch_update_status(url_ids, status, user_id)
+            .rename_short_code(url_id, new_short_code, MAX_SHORT_CODE_ALIASES)
             .await
-            .map_err(ServiceError::from)
+            .map_err(|e| match e {
+                RepositoryError::DuplicateShortCode => ServiceError::ShortCodeAlreadyExists,
+                other => ServiceError::from(other),
+            })
     }
 
-    /// Batch update URL expiration dates
-    pub async fn batch_update_expiration(
-        &self,
-        url_ids: &[i32],
-        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
-        user_id: Option<i32>,
-    ) -> Result<crate::domain::repositories::url_repository::BatchOperationResult, ServiceError>
-    {
+    /// Update a URL if nobody changed it since `expected_version` was read
+    pub async fn update_url(&self, url: &Url, expected_version: i64) -> Result<Url, ServiceError> {
         self.repository
-            .batch_update_expiration(url_ids, expiration_date, user_id)
+            .update_url(url, expected_version)
             .await
             .map_err(ServiceError::from)
     }
 
-    /// Process batch URL operations
-    pub async fn process_batch_operations(
+    /// Get the URL a short code redirects to, whether or not it is accessible
+    ///
+    /// A miss is retried once against the primary database, so a link is found right after
+    /// it was created even if a read replica has not caught up yet. A code left only by a
+    /// deleted URL gives that URL, so callers can tell it apart from an unknown code.
+    #[tracing::instrument(skip(self), fields(short_code = %short_code))]
+    pub async fn get_url_for_redirect(
         &self,
-        operation: &crate::application::dto::requests::BatchOperationType,
-        url_ids: &[i32],
-        data: Option<&crate::application::dto::requests::BatchOperationData>,
-        user_id: Option<i32>,
-    ) -> Result<crate::domain::repositories::url_repository::BatchOperationResult, ServiceError>
-    {
-        match operation {
-            crate::application::dto::requests::BatchOperationType::Deactivate => {
-                self.batch_deactivate_urls(url_ids, user_id).await
-            }
-            crate::application::dto::requests::BatchOperationType::Reactivate => {
-                self.batch_reactivate_urls(url_ids, user_id).await
-            }
-            crate::application::dto::requests::BatchOperationType::Delete => {
-                self.batch_delete_urls(url_ids, user_id).await
-            }
-            crate::application::dto::requests::BatchOperationType::UpdateStatus => {
-                let status = data
-                    .and_then(|d| d.status.as_ref())
-                    .and_then(|s| match s.as_str() {
-                        "active" => Some(UrlStatus::Active),
-                        "inactive" => Some(UrlStatus::Inactive),
-                        _ => None,
-                    })
-                    .ok_or_else(|| {
-                        ServiceError::InvalidData("Invalid status provided".to_string())
-                    })?;
-                self.batch_update_status(url_ids, status, user_id).await
-            }
-            crate::application::dto::requests::BatchOperationType::UpdateExpiration => {
-                let expiration_date = data.and_then(|d| d.expiration_date);
-                self.batch_update_expiration(url_ids, expiration_date, user_id)
-                    .await
-            }
+        short_code: &ShortCode,
+    ) -> Result<Option<Url>, ServiceError> {
+        debug!("Looking up URL for redirect");
+        match self
+            .repository
+            .find_by_short_code(short_code, false)
+            .await?
+        {
+            Some(url) => Ok(Some(url)),
+            None => match self.repository.find_by_short_code(short_code, true).await? {
+                Some(url) => Ok(Some(url)),
+                None => Ok(self
+                    .repository
+                    .find_deleted_by_short_code(short_code)
+                    .await?),
+            },
         }
     }
-}
 
-/// Service errors
-#[allow(dead_code)]
-#[derive(Debug, thiserror::Error)]
-pub enum ServiceError {
-    #[error("Repository error: {0}")]
-    Repository(#[from] RepositoryError),
+    /// Get URL by short code, only if it is accessible (see [`Url::is_accessible`])
+    pub async fn get_url_by_short_code_with_validation(
+        &self,
+        short_code: &ShortCode,
+    ) -> Result<Option<Url>, ServiceError> {
+        Ok(self
+            .get_url_for_redirect(short_code)
+            .await?
+            .filter(Url::is_accessible))
+    }
+
+    /// Get URLs that are expiring soon
+    pub async fn get_urls_expiring_soon(
+        &self,
+        duration: chrono::Duration,
+    ) -> Result<Vec<Url>, ServiceError> {
+        self.repository
+            .find_urls_expiring_soon(duration)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Get expired URLs
+    pub async fn get_expired_urls(&self) -> Result<Vec<Url>, ServiceError> {
+        self.repository
+            .find_expired_urls()
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Archive expired URLs, keeping their analytics
+    ///
+    /// With an event bus, [`DomainEvent::UrlExpired`] is published for each URL archived.
+    pub async fn cleanup_expired_urls(&self) -> Result<u64, ServiceError> {
+        let archived = self
+            .repository
+            .archive_expired_urls(chrono::Utc::now())
+            .await?;
+        self.count_archived_urls(&archived).await;
+        let archived_count = archived.len() as u64;
+        for url in archived {
+            self.publish(DomainEvent::UrlExpired { url });
+        }
+        Ok(archived_count)
+    }
+
+    /// Move a URL from one user to another on behalf of an administrator
+    ///
+    /// The URL keeps its clicks and conversions. Fails when `from_user_id` does not own the
+    /// URL, or the recipient does not exist, is suspended or already owns the most URLs
+    /// allowed. The transfer is recorded in the audit log.
+    pub async fn transfer_url(
+        &self,
+        url_id: i32,
+        from_user_id: i32,
+        to_user_id: i32,
+        admin_user_id: i32,
+    ) -> Result<Url, ServiceError> {
+        if from_user_id == to_user_id {
+            return Err(ServiceError::InvalidData(
+                "URL already belongs to this user".to_string(),
+            ));
+        }
+
+        let url = self
+            .repository
+            .find_by_id(url_id)
+            .await?
+            .ok_or(RepositoryError::NotFound)?;
+        if url.user_id != Some(from_user_id) {
+            return Err(ServiceError::PermissionDenied(format!(
+                "URL {} does not belong to user {}",
+                url_id, from_user_id
+            )));
+        }
+
+        let user_repository = self.user_repository.as_ref().ok_or_else(|| {
+            RepositoryError::Internal("URL transfers need a user repository".to_string())
+        })?;
+        let recipient = user_repository
+            .find_by_id(to_user_id)
+            .await
+            .map_err(|e| RepositoryError::Internal(e.to_string()))?
+            .ok_or(ServiceError::UserNotFound(to_user_id))?;
+        if recipient.account_status.is_suspended_at(chrono::Utc::now()) {
+            return Err(ServiceError::UserSuspended(to_user_id));
+        }
+
+        if self.max_urls_per_user > 0 {
+            let owned = self
+                .repository
+                .get_stats(Some(to_user_id))
+                .await?
+                .total_urls;
+            if owned >= i64::from(self.max_urls_per_user) {
+                return Err(ServiceError::UrlQuotaExceeded {
+                    user_id: to_user_id,
+                    limit: self.max_urls_per_user,
+                });
+            }
+        }
+
+        // The owner is checked again in the update in case the URL moved in the meantime
+        let transferred = self
+            .repository
+            .transfer_owner(url_id, from_user_id, to_user_id)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::PermissionDenied(format!(
+                    "URL {} does not belong to user {}",
+                    url_id, from_user_id
+                ))
+            })?;
+
+        // The URL leaves the counts of its old owner for those of its new one
+        self.adjust_url_counts(
+            Some(from_user_id),
+            UrlCountChange::removed(&transferred.status),
+        )
+        .await;
+        self.adjust_url_counts(
+            Some(to_user_id),
+            UrlCountChange::created(&transferred.status),
+        )
+        .await;
+
+        if let Some(audit_log) = &self.audit_log {
+            let entry =
+                AuditLogEntry::url_transfer(admin_user_id, url_id, from_user_id, to_user_id);
+            if let Err(e) = audit_log.record(&entry).await {
+                warn!(
+                    "URL {} was transferred from user {} to user {} but the audit log entry failed: {}",
+                    url_id, from_user_id, to_user_id, e
+                );
+            }
+        }
+
+        Ok(transferred)
+    }
+
+    /// Bring back one of a user's archived URLs
+    ///
+    /// The URL then expires at `expiration_date`, which must be in the future, or never.
+    /// Returns `None` when the user has no such URL.
+    pub async fn restore_url(
+        &self,
+        id: i32,
+        user_id: i32,
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Option<Url>, ServiceError> {
+        if expiration_date.is_some_and(|date| date <= chrono::Utc::now()) {
+            return Err(ServiceError::InvalidData(
+                "Expiration date must be in the future".to_string(),
+            ));
+        }
+
+        let url = self.repository.find_by_id(id).await?;
+        let Some(mut url) = url.filter(|url| url.user_id == Some(user_id)) else {
+            return Ok(None);
+        };
+        if !url.is_archived() {
+            return Err(ServiceError::InvalidData("URL is not archived".to_string()));
+        }
+
+        let (expected_version, status) = (url.version, url.status);
+        url.restore(expiration_date);
+        let restored = self.repository.update_url(&url, expected_version).await?;
+        let change = UrlCountChange::status_changed(&status, &restored.status);
+        self.adjust_url_counts(restored.user_id, change).await;
+        Ok(Some(restored))
+    }
+
+    /// Soft delete a URL (deactivate)
+    pub async fn deactivate_url(
+        &self,
+        id: i32,
+        user_id: Option<i32>,
+    ) -> Result<bool, ServiceError> {
+        let before = self.url_before_change(id).await?;
+        let deactivated = self.repository.soft_delete_by_id(id, user_id).await?;
+        if let (true, Some(before)) = (deactivated, &before) {
+            let change = UrlCountChange::status_changed(&before.status, &UrlStatus::Inactive);
+            self.adjust_url_counts(before.user_id, change).await;
+        }
+        if deactivated && self.event_bus.is_some() {
+            if let Some(url) = self.repository.find_by_id(id).await? {
+                self.publish(DomainEvent::UrlDeactivated { url });
+            }
+        }
+        Ok(deactivated)
+    }
+
+    /// Reactivate a URL
+    pub async fn reactivate_url(
+        &self,
+        id: i32,
+        user_id: Option<i32>,
+    ) -> Result<bool, ServiceError> {
+        let before = self.url_before_change(id).await?;
+        let reactivated = self.repository.reactivate_by_id(id, user_id).await?;
+        if let (true, Some(before)) = (reactivated, before) {
+            let change = UrlCountChange::status_changed(&before.status, &UrlStatus::Active);
+            self.adjust_url_counts(before.user_id, change).await;
+        }
+        Ok(reactivated)
+    }
+
+    /// Get URLs by status
+    pub async fn get_urls_by_status(
+        &self,
+        status: UrlStatus,
+        user_id: Option<i32>,
+    ) -> Result<Vec<Url>, ServiceError> {
+        self.repository
+            .find_by_status(status, user_id)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Get deactivated URLs for a user
+    pub async fn get_deactivated_urls(
+        &self,
+        user_id: Option<i32>,
+    ) -> Result<Vec<Url>, ServiceError> {
+        self.get_urls_by_status(UrlStatus::Inactive, user_id).await
+    }
+
+    /// Batch deactivate URLs
+    pub async fn batch_deactivate_urls(
+        &self,
+        url_ids: &[i32],
+        user_id: Option<i32>,
+    ) -> Result<crate::domain::repositories::url_repository::BatchOperationResult, ServiceError>
+    {
+        let before = self.urls_before_batch(url_ids).await?;
+        let result = self
+            .repository
+            .batch_deactivate_urls(url_ids, user_id)
+            .await?;
+        self.count_batch_changes(before, &result).await;
+        Ok(result)
+    }
+
+    /// Batch reactivate URLs
+    pub async fn batch_reactivate_urls(
+        &self,
+        url_ids: &[i32],
+        user_id: Option<i32>,
+    ) -> Result<crate::domain::repositories::url_repository::BatchOperationResult, ServiceError>
+    {
+        let before = self.urls_before_batch(url_ids).await?;
+        let result = self
+            .repository
+            .batch_reactivate_urls(url_ids, user_id)
+            .await?;
+        self.count_batch_changes(before, &result).await;
+        Ok(result)
+    }
+
+    /// Batch delete URLs
+    pub async fn batch_delete_urls(
+        &self,
+        url_ids: &[i32],
+        user_id: Option<i32>,
+    ) -> Result<crate::domain::repositories::url_repository::BatchOperationResult, ServiceError>
+    {
+        let before = self.urls_before_batch(url_ids).await?;
+        let result = self.repository.batch_delete_urls(url_ids, user_id).await?;
+        self.count_batch_changes(before, &result).await;
+        Ok(result)
+    }
+
+    /// Batch update URL status
+    pub async fn batch_update_status(
+        &self,
+        url_ids: &[i32],
+        status: UrlStatus,
+        user_id: Option<i32>,
+    ) -> Result<crate::domain::repositories::url_repository::BatchOperationResult, ServiceError>
+    {
+        let before = self.urls_before_batch(url_ids).await?;
+        let result = self
+            .repository
+            .batch_update_status(url_ids, status, user_id)
+            .await?;
+        self.count_batch_changes(before, &result).await;
+        Ok(result)
+    }
+
+    /// Batch update URL expiration dates
+    pub async fn batch_update_expiration(
+        &self,
+        url_ids: &[i32],
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Option<i32>,
+    ) -> Result<crate::domain::repositories::url_repository::BatchOperationResult, ServiceError>
+    {
+        self.repository
+            .batch_update_expiration(url_ids, expiration_date, user_id)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Apply a batch operation to each of the given URLs
+    ///
+    /// Fails without changing any URL when the operation rejects `data`.
+    pub async fn process_batch_operations(
+        &self,
+        operation: &dyn BatchOperation,
+        url_ids: &[i32],
+        data: Option<&serde_json::Value>,
+        user_id: Option<i32>,
+    ) -> Result<BatchOperationResult, ServiceError> {
+        operation.check_data(data)?;
+
+        let before = self.urls_before_batch(url_ids).await?;
+        let mut results = Vec::with_capacity(url_ids.len());
+        for &url_id in url_ids {
+            let error = operation
+                .execute(url_id, user_id, data, &self.repository)
+                .await
+                .err();
+            results.push(BatchItemResult {
+                url_id,
+                success: error.is_none(),
+                error: error.map(|e| e.to_string()),
+            });
+        }
+
+        let successful = results.iter().filter(|r| r.success).count();
+        let result = BatchOperationResult {
+            total_processed: results.len(),
+            successful,
+            failed: results.len() - successful,
+            results,
+        };
+        self.count_batch_changes(before, &result).await;
+        Ok(result)
+    }
+
+    /// Give every URL whose short code uses characters outside the configured alphabet a new
+    /// generated code
+    ///
+    /// One-time migration after changing the alphabet. The old code is kept as an alias, so
+    /// links already shared keep redirecting.
+    pub async fn reencode_short_codes(&self) -> Result<ReencodeSummary, ServiceError> {
+        const PAGE_SIZE: usize = 500;
+        let (sort, direction) = (UrlSortField::CreatedAt, SortDirection::Asc);
+        let mut summary = ReencodeSummary::default();
+
+        // Listings without a status leave out archived URLs, so those get their own pass
+        for status in [None, Some(UrlStatus::Archived)] {
+            let mut cursor: Option<UrlCursor> = None;
+            loop {
+                let page = self
+                    .repository
+                    .find_paginated(None, status, sort, direction, cursor.as_ref(), PAGE_SIZE)
+                    .await?;
+
+                for url in &page.urls {
+                    summary.checked += 1;
+                    let current = ShortCode::from_string_unchecked(url.short_code.clone());
+                    if current.is_valid_for_alphabet(&self.short_code_alphabet) {
+                        continue;
+                    }
+                    let replaced = match self.generate_unique_short_code(&current).await {
+                        Ok(new_code) => self.repository.replace_short_code(url.id, &new_code).await,
+                        Err(error) => {
+                            warn!("Could not generate a code for URL {}: {}", url.id, error);
+                            summary.failed += 1;
+                            continue;
+                        }
+                    };
+                    match replaced {
+                        Ok(()) => summary.reencoded += 1,
+                        Err(error) => {
+                            warn!("Could not re-encode URL {}: {}", url.id, error);
+                            summary.failed += 1;
+                        }
+                    }
+                }
+
+                match page.next_cursor {
+                    Some(next) => {
+                        cursor = Some(
+                            UrlCursor::decode(&next)
+                                .map_err(|e| ServiceError::InvalidData(e.to_string()))?,
+                        )
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+/// Service errors
+#[allow(dead_code)]
+#[derive(Debug, thiserror::Error)]
+pub enum ServiceError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
 
     #[error("Invalid short code: {0}")]
     InvalidShortCode(String),
@@ -376,6 +1237,15 @@ pub enum ServiceError {
 
     #[error("Invalid data: {0}")]
     InvalidData(String),
+
+    #[error("User {0} not found")]
+    UserNotFound(i32),
+
+    #[error("User {0} is suspended")]
+    UserSuspended(i32),
+
+    #[error("User {user_id} already owns {limit} URLs, the most allowed")]
+    UrlQuotaExceeded { user_id: i32, limit: u32 },
 }
 
 impl From<crate::domain::entities::ShortCodeError> for ServiceError {
@@ -388,6 +1258,8 @@ impl From<crate::domain::entities::ShortCodeError> for ServiceError {
 mod tests {
     use super::*;
     use crate::domain::repositories::UrlRepository;
+    use crate::domain::services::short_code_strategy::HashStrategy;
+    use crate::infrastructure::event_bus::InProcessEventBus;
     use async_trait::async_trait;
     use std::sync::{Arc, Mutex};
 
@@ -413,6 +1285,7 @@ mod tests {
             original_url: &str,
             expiration_date: Option<chrono::DateTime<chrono::Utc>>,
             user_id: Option<i32>,
+            organization_id: Option<i32>,
             status: UrlStatus,
         ) -> Result<Url, RepositoryError> {
             let mut urls = self.urls.lock().unwrap();
@@ -424,14 +1297,44 @@ mod tests {
                 expiration_date,
                 user_id,
                 status,
-            );
+            )
+            .with_organization(organization_id);
             urls.push(url.clone());
             Ok(url)
         }
 
+        async fn create_url_idempotent(
+            &self,
+            short_code: &ShortCode,
+            original_url: &str,
+            expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+            user_id: Option<i32>,
+            organization_id: Option<i32>,
+            _max_organization_urls: Option<i64>,
+            status: UrlStatus,
+        ) -> Result<(Url, bool), RepositoryError> {
+            let mut urls = self.urls.lock().unwrap();
+            if let Some(existing) = urls.iter().find(|url| url.short_code == short_code.value()) {
+                return Ok((existing.clone(), false));
+            }
+            let id = (urls.len() + 1) as i32;
+            let url = Url::new_with_timestamp(
+                id,
+                short_code.value().to_string(),
+                original_url.to_string(),
+                expiration_date,
+                user_id,
+                status,
+            )
+            .with_organization(organization_id);
+            urls.push(url.clone());
+            Ok((url, true))
+        }
+
         async fn find_by_short_code(
             &self,
             short_code: &ShortCode,
+            _force_primary: bool,
         ) -> Result<Option<Url>, RepositoryError> {
             let urls = self.urls.lock().unwrap();
             Ok(urls
@@ -440,11 +1343,47 @@ mod tests {
                 .cloned())
         }
 
-        async fn find_by_user_id(&self, user_id: i32) -> Result<Vec<Url>, RepositoryError> {
+        async fn find_by_user_id(
+            &self,
+            user_id: i32,
+            organization_id: Option<i32>,
+        ) -> Result<Vec<Url>, RepositoryError> {
             let urls = self.urls.lock().unwrap();
             Ok(urls
                 .iter()
-                .filter(|u| u.user_id == Some(user_id))
+                .filter(|u| match organization_id {
+                    Some(org_id) => u.organization_id == Some(org_id),
+                    None => u.user_id == Some(user_id),
+                })
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_original_url(
+            &self,
+            original_url: &str,
+            user_id: i32,
+        ) -> Result<Vec<Url>, RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .rev()
+                .filter(|u| u.original_url == original_url && u.user_id == Some(user_id))
+                .cloned()
+                .collect())
+        }
+
+        async fn find_all_by_original_url(
+            &self,
+            original_url: &str,
+            limit: usize,
+        ) -> Result<Vec<Url>, RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .rev()
+                .filter(|u| u.original_url == original_url)
+                .take(limit)
                 .cloned()
                 .collect())
         }
@@ -457,6 +1396,48 @@ mod tests {
             Ok(urls.iter().any(|u| u.short_code == short_code.value()))
         }
 
+        async fn find_phonetically_similar_codes(
+            &self,
+            generated: &ShortCode,
+            threshold: f32,
+        ) -> Result<Vec<ShortCode>, RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .map(|u| ShortCode::from_string_unchecked(u.short_code.clone()))
+                .filter(|code| generated.phonetic_distance(code) < threshold)
+                .collect())
+        }
+
+        async fn replace_short_code(
+            &self,
+            url_id: i32,
+            new_short_code: &ShortCode,
+        ) -> Result<(), RepositoryError> {
+            let mut urls = self.urls.lock().unwrap();
+            let url = urls
+                .iter_mut()
+                .find(|u| u.id == url_id)
+                .ok_or(RepositoryError::NotFound)?;
+            url.short_code = new_short_code.value().to_string();
+            Ok(())
+        }
+
+        async fn rename_short_code(
+            &self,
+            url_id: i32,
+            new_short_code: &ShortCode,
+            _max_aliases: usize,
+        ) -> Result<Url, RepositoryError> {
+            let mut urls = self.urls.lock().unwrap();
+            let url = urls
+                .iter_mut()
+                .find(|u| u.id == url_id)
+                .ok_or(RepositoryError::NotFound)?;
+            url.short_code = new_short_code.value().to_string();
+            Ok(url.clone())
+        }
+
         async fn delete_by_id(
             &self,
             id: i32,
@@ -471,10 +1452,63 @@ mod tests {
             }
         }
 
-        async fn update_url(&self, url: &Url) -> Result<Url, RepositoryError> {
+        async fn find_deleted_by_short_code(
+            &self,
+            _short_code: &ShortCode,
+        ) -> Result<Option<Url>, RepositoryError> {
+            Ok(None)
+        }
+
+        async fn permanently_delete_by_id(&self, id: i32) -> Result<bool, RepositoryError> {
+            let mut urls = self.urls.lock().unwrap();
+            let before = urls.len();
+            urls.retain(|u| u.id != id);
+            Ok(urls.len() < before)
+        }
+
+        async fn find_deleted_before(
+            &self,
+            _deleted_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<Url>, RepositoryError> {
+            Ok(vec![])
+        }
+
+        async fn transfer_owner(
+            &self,
+            id: i32,
+            from_user_id: i32,
+            to_user_id: i32,
+        ) -> Result<Option<Url>, RepositoryError> {
+            let mut urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter_mut()
+                .find(|u| u.id == id && u.user_id == Some(from_user_id))
+                .map(|url| {
+                    url.user_id = Some(to_user_id);
+                    url.clone()
+                }))
+        }
+
+        async fn find_by_id(&self, id: i32) -> Result<Option<Url>, RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls.iter().find(|u| u.id == id).cloned())
+        }
+
+        async fn update_url(
+            &self,
+            url: &Url,
+            expected_version: i64,
+        ) -> Result<Url, RepositoryError> {
             let mut urls = self.urls.lock().unwrap();
             if let Some(existing) = urls.iter_mut().find(|u| u.id == url.id) {
+                if existing.version != expected_version {
+                    return Err(RepositoryError::ConflictingUpdate {
+                        current_version: existing.version,
+                    });
+                }
                 *existing = url.clone();
+                existing.version = expected_version + 1;
+                existing.updated_at = chrono::Utc::now();
                 Ok(existing.clone())
             } else {
                 Err(RepositoryError::NotFound)
@@ -496,24 +1530,68 @@ mod tests {
                 total_urls: filtered_urls.len() as i64,
                 total_clicks: 0,
                 unique_short_codes: filtered_urls.len() as i64,
+                estimated_unique_visitors: 0,
+                archived_url_count: 0,
             })
         }
 
         async fn find_urls_expiring_soon(
             &self,
-            _duration: chrono::Duration,
+            duration: chrono::Duration,
         ) -> Result<Vec<Url>, RepositoryError> {
-            Ok(vec![])
+            let now = chrono::Utc::now();
+            Ok(self
+                .urls
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|url| {
+                    url.expiration_date
+                        .is_some_and(|e| e > now && e <= now + duration)
+                })
+                .cloned()
+                .collect())
         }
 
         async fn find_expired_urls(&self) -> Result<Vec<Url>, RepositoryError> {
             Ok(vec![])
         }
 
-        async fn delete_expired_urls(&self) -> Result<u64, RepositoryError> {
+        async fn delete_expired_urls(
+            &self,
+            _expired_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<u64, RepositoryError> {
             Ok(0)
         }
 
+        async fn archive_expired_urls(
+            &self,
+            expired_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<Url>, RepositoryError> {
+            let mut urls = self.urls.lock().unwrap();
+            let mut archived = Vec::new();
+            for url in urls.iter_mut().filter(|url| {
+                !url.is_archived() && url.expiration_date.is_some_and(|e| e <= expired_before)
+            }) {
+                archived.push(url.clone());
+                url.status = UrlStatus::Archived;
+            }
+            Ok(archived)
+        }
+
+        async fn count_expired_urls_to_archive(
+            &self,
+            expired_before: chrono::DateTime<chrono::Utc>,
+        ) -> Result<u64, RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .filter(|url| {
+                    !url.is_archived() && url.expiration_date.is_some_and(|e| e <= expired_before)
+                })
+                .count() as u64)
+        }
+
         async fn soft_delete_by_id(
             &self,
             id: i32,
@@ -556,6 +1634,37 @@ mod tests {
             Ok(filtered_urls)
         }
 
+        async fn find_by_status_paginated(
+            &self,
+            status: UrlStatus,
+            after_id: Option<i32>,
+            limit: usize,
+        ) -> Result<(Vec<Url>, Option<i32>), RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            let mut page: Vec<Url> = urls
+                .iter()
+                .filter(|url| url.status == status && after_id.is_none_or(|after| url.id > after))
+                .cloned()
+                .collect();
+            page.sort_by_key(|url| url.id);
+            let has_more = page.len() > limit;
+            page.truncate(limit);
+            let next_after_id = if has_more {
+                page.last().map(|url| url.id)
+            } else {
+                None
+            };
+            Ok((page, next_after_id))
+        }
+
+        async fn find_suspicious_urls(
+            &self,
+            _min_click_count: i64,
+            _created_within_hours: u8,
+        ) -> Result<Vec<Url>, RepositoryError> {
+            Ok(Vec::new())
+        }
+
         async fn batch_deactivate_urls(
             &self,
             url_ids: &[i32],
@@ -802,46 +1911,457 @@ mod tests {
                 },
             )
         }
+
+        async fn find_most_clicked(
+            &self,
+            user_id: i32,
+            limit: usize,
+        ) -> Result<
+            Vec<crate::domain::entities::UrlWithClickCount>,
+            crate::domain::repositories::RepositoryError,
+        > {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .filter(|url| url.user_id == Some(user_id))
+                .take(limit)
+                .map(|url| crate::domain::entities::UrlWithClickCount::new(url.clone(), 0))
+                .collect())
+        }
+
+        async fn find_recently_created(
+            &self,
+            user_id: i32,
+            limit: usize,
+        ) -> Result<Vec<crate::domain::entities::Url>, crate::domain::repositories::RepositoryError>
+        {
+            let urls = self.urls.lock().unwrap();
+            let mut user_urls: Vec<_> = urls
+                .iter()
+                .filter(|url| url.user_id == Some(user_id))
+                .cloned()
+                .collect();
+            user_urls.sort_by_key(|url| std::cmp::Reverse(url.created_at));
+            user_urls.truncate(limit);
+            Ok(user_urls)
+        }
+
+        async fn find_recently_modified(
+            &self,
+            user_id: i32,
+            since: chrono::DateTime<chrono::Utc>,
+            limit: usize,
+        ) -> Result<Vec<crate::domain::entities::Url>, crate::domain::repositories::RepositoryError>
+        {
+            let urls = self.urls.lock().unwrap();
+            let mut user_urls: Vec<_> = urls
+                .iter()
+                .filter(|url| url.user_id == Some(user_id) && url.updated_at > since)
+                .cloned()
+                .collect();
+            user_urls.sort_by_key(|url| std::cmp::Reverse(url.updated_at));
+            user_urls.truncate(limit);
+            Ok(user_urls)
+        }
+
+        async fn find_paginated(
+            &self,
+            user_id: Option<i32>,
+            status: Option<crate::domain::entities::UrlStatus>,
+            sort: crate::domain::repositories::UrlSortField,
+            direction: crate::domain::repositories::SortDirection,
+            after_cursor: Option<&crate::domain::repositories::UrlCursor>,
+            limit: usize,
+        ) -> Result<
+            crate::domain::repositories::UrlPage,
+            crate::domain::repositories::RepositoryError,
+        > {
+            let urls = self.urls.lock().unwrap();
+            let urls = urls
+                .iter()
+                .filter(|url| user_id.is_none_or(|id| url.user_id == Some(id)))
+                .filter(|url| url.status.is_listed_under(status))
+                .cloned();
+            Ok(crate::domain::repositories::UrlPage::paginate(
+                urls,
+                sort,
+                direction,
+                after_cursor,
+                limit,
+            ))
+        }
+
+        async fn count_by_filter(
+            &self,
+            filter: &crate::domain::repositories::UrlFilter,
+            user_id: i32,
+        ) -> Result<i64, crate::domain::repositories::RepositoryError> {
+            let urls = self.urls.lock().unwrap();
+            Ok(urls
+                .iter()
+                .filter(|url| url.user_id == Some(user_id) && filter.matches(url))
+                .count() as i64)
+        }
+
+        async fn count_clicks_for_urls(
+            &self,
+            _url_ids: &[i32],
+        ) -> Result<std::collections::HashMap<i32, i64>, crate::domain::repositories::RepositoryError>
+        {
+            Ok(std::collections::HashMap::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_short_code() {
+        let repo = MockUrlRepository::new();
+        let service = UrlService::new(repo);
+
+        let short_code = service
+            .generate_short_code("https://example.com")
+            .await
+            .unwrap();
+        assert!(!short_code.value().is_empty());
+        assert!(short_code.value().len() >= 6);
+    }
+
+    #[tokio::test]
+    async fn test_generate_short_code_avoids_homophones_when_enabled() {
+        let code = |value: &str| ShortCode::from_string_unchecked(value.to_string());
+        let repo = MockUrlRepository::new();
+        // Hashing gives the same first candidate each time
+        let service =
+            UrlService::new(repo.clone()).with_short_code_strategy(Box::new(HashStrategy));
+        let first = service
+            .generate_short_code("https://example.com")
+            .await
+            .unwrap();
+
+        // The same code in another case is a different code that reads the same
+        let homophone: String = first
+            .value()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_lowercase() {
+                    c.to_ascii_uppercase()
+                } else {
+                    c.to_ascii_lowercase()
+                }
+            })
+            .collect();
+        assert_ne!(homophone, first.value());
+        service
+            .create_url("https://other.example", Some(code(&homophone)), None, None)
+            .await
+            .unwrap();
+
+        let unchecked = service
+            .generate_short_code("https://example.com")
+            .await
+            .unwrap();
+        assert_eq!(unchecked, first);
+
+        let service = service.with_min_phonetic_distance(0.5);
+        let checked = service
+            .generate_short_code("https://example.com")
+            .await
+            .unwrap();
+        assert_ne!(checked, first);
+        assert!(checked.phonetic_distance(&code(&homophone)) >= 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_create_url_with_generated_code() {
+        let repo = MockUrlRepository::new();
+        let service = UrlService::new(repo);
+
+        let url = service
+            .create_url("https://example.com", None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(url.original_url, "https://example.com");
+        assert!(!url.short_code.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_url_with_custom_code() {
+        let repo = MockUrlRepository::new();
+        let service = UrlService::new(repo);
+        let custom_code = ShortCode::new("mycode".to_string()).unwrap();
+
+        let url = service
+            .create_url("https://example.com", Some(custom_code.clone()), None, None)
+            .await
+            .unwrap();
+        assert_eq!(url.short_code, custom_code.value());
+        assert_eq!(url.original_url, "https://example.com");
+    }
+
+    #[tokio::test]
+    async fn test_create_url_publishes_url_created() {
+        let event_bus = InProcessEventBus::default();
+        let mut subscriber = event_bus.subscribe();
+        let service = UrlService::new(MockUrlRepository::new()).with_event_bus(Arc::new(event_bus));
+
+        let url = service
+            .create_url("https://example.com", None, None, Some(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            subscriber.recv().await.unwrap(),
+            DomainEvent::UrlCreated { url }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_only_the_inserting_request_counts_and_announces_the_url() {
+        use crate::infrastructure::test_utils::MockUserUrlStatsRepository;
+
+        let repository = crate::infrastructure::test_utils::MockUrlRepository::new();
+        let event_bus = InProcessEventBus::default();
+        let mut subscriber = event_bus.subscribe();
+        let service = UrlService::new(repository.clone())
+            .with_event_bus(Arc::new(event_bus))
+            .with_user_url_stats(Arc::new(MockUserUrlStatsRepository::new()));
+        let custom_code = ShortCode::new("samelink".to_string()).unwrap();
+
+        let url = service
+            .create_url(
+                "https://example.com",
+                Some(custom_code.clone()),
+                None,
+                Some(1),
+            )
+            .await
+            .unwrap();
+        // A resubmission that raced past the lookup gets the stored row from the insert
+        repository.miss_next_lookups(1);
+        let again = service
+            .create_url("https://example.com", Some(custom_code), None, Some(1))
+            .await
+            .unwrap();
+        assert_eq!(again.id, url.id);
+
+        assert_eq!(service.get_user_url_stats(1).await.unwrap().total_urls, 1);
+        assert_eq!(
+            subscriber.recv().await.unwrap(),
+            DomainEvent::UrlCreated { url }
+        );
+        assert!(subscriber.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_organization_url_quota_is_enforced_by_the_insert() {
+        let service = UrlService::new(crate::infrastructure::test_utils::MockUrlRepository::new())
+            .with_max_urls_per_organization(1);
+
+        service
+            .create_url_in_organization("https://example.com/1", None, None, Some(1), Some(7))
+            .await
+            .unwrap();
+        let result = service
+            .create_url_in_organization("https://example.com/2", None, None, Some(1), Some(7))
+            .await;
+        assert!(matches!(
+            result,
+            Err(ServiceError::Repository(
+                RepositoryError::OrganizationQuotaExceeded { limit: 1 }
+            ))
+        ));
+
+        // Other organizations and personal URLs do not count towards it
+        service
+            .create_url_in_organization("https://example.com/3", None, None, Some(1), Some(8))
+            .await
+            .unwrap();
+        service
+            .create_url("https://example.com/4", None, None, Some(1))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_url_changes_adjust_user_url_stats() {
+        use crate::infrastructure::test_utils::MockUserUrlStatsRepository;
+
+        let stats = MockUserUrlStatsRepository::new();
+        let service =
+            UrlService::new(MockUrlRepository::new()).with_user_url_stats(Arc::new(stats.clone()));
+
+        let first = service
+            .create_url("https://example.com/1", None, None, Some(1))
+            .await
+            .unwrap();
+        service
+            .create_url("https://example.com/2", None, None, Some(1))
+            .await
+            .unwrap();
+        // Anonymous URLs have nobody to count them for
+        service
+            .create_url("https://example.com/3", None, None, None)
+            .await
+            .unwrap();
+        assert!(service.deactivate_url(first.id, Some(1)).await.unwrap());
+        // Deactivating again changes nothing
+        assert!(service.deactivate_url(first.id, Some(1)).await.unwrap());
+
+        let user_stats = service.get_user_url_stats(1).await.unwrap();
+        assert_eq!(user_stats.total_urls, 2);
+        assert_eq!(user_stats.active_urls, 1);
+        assert_eq!(user_stats.inactive_urls, 1);
+        assert_eq!(stats.stats.lock().unwrap().len(), 1);
+
+        assert!(service.reactivate_url(first.id, Some(1)).await.unwrap());
+        let user_stats = service.get_user_url_stats(1).await.unwrap();
+        assert_eq!((user_stats.active_urls, user_stats.inactive_urls), (2, 0));
+
+        // Users without URLs get zeros
+        assert_eq!(service.get_user_url_stats(2).await.unwrap().total_urls, 0);
+    }
+
+    #[tokio::test]
+    async fn test_batches_transfers_and_expiry_adjust_user_url_stats() {
+        use crate::infrastructure::test_utils::{MockUserRepository, MockUserUrlStatsRepository};
+
+        let users = MockUserRepository::new();
+        for name in ["alice", "bob"] {
+            users
+                .create_user(name, &format!("{}@example.com", name), "hash")
+                .await
+                .unwrap();
+        }
+        let service = UrlService::new(crate::infrastructure::test_utils::MockUrlRepository::new())
+            .with_user_repository(Arc::new(users))
+            .with_user_url_stats(Arc::new(MockUserUrlStatsRepository::new()));
+        let mut ids = Vec::new();
+        for i in 0..4 {
+            let url = service
+                .create_url(&format!("https://example.com/{}", i), None, None, Some(1))
+                .await
+                .unwrap();
+            ids.push(url.id);
+        }
+        let counts = |user_id| {
+            let service = service.clone();
+            async move {
+                let stats = service.get_user_url_stats(user_id).await.unwrap();
+                (
+                    stats.total_urls,
+                    stats.active_urls,
+                    stats.inactive_urls,
+                    stats.archived_urls,
+                )
+            }
+        };
+
+        service
+            .batch_update_status(&ids[..2], UrlStatus::Inactive, Some(1))
+            .await
+            .unwrap();
+        assert_eq!(counts(1).await, (4, 2, 2, 0));
+        // URLs of other users are not changed, so not counted
+        service
+            .batch_delete_urls(&[ids[0], 999], Some(1))
+            .await
+            .unwrap();
+        assert_eq!(counts(1).await, (3, 2, 1, 0));
+
+        service.transfer_url(ids[2], 1, 2, 99).await.unwrap();
+        assert_eq!(counts(1).await, (2, 1, 1, 0));
+        assert_eq!(counts(2).await, (1, 1, 0, 0));
+
+        let mut expiring = service.get_url_by_id(ids[3]).await.unwrap().unwrap();
+        expiring.expiration_date = Some(chrono::Utc::now() - chrono::Duration::hours(1));
+        service
+            .update_url(&expiring, expiring.version)
+            .await
+            .unwrap();
+        assert_eq!(service.cleanup_expired_urls().await.unwrap(), 1);
+        assert_eq!(counts(1).await, (2, 0, 1, 1));
+
+        service.restore_url(ids[3], 1, None).await.unwrap().unwrap();
+        assert_eq!(counts(1).await, (2, 1, 1, 0));
     }
 
     #[tokio::test]
-    async fn test_generate_short_code() {
+    async fn test_duplicate_url_copies_fields() {
         let repo = MockUrlRepository::new();
         let service = UrlService::new(repo);
+        let expiration = chrono::Utc::now() + chrono::Duration::days(7);
+        let original = service
+            .create_url_in_organization(
+                "https://example.com",
+                None,
+                Some(expiration),
+                Some(1),
+                Some(3),
+            )
+            .await
+            .unwrap();
 
-        let short_code = service
-            .generate_short_code("https://example.com")
+        let copy = service
+            .duplicate_url(original.id, 1, DuplicateOverrides::default())
             .await
             .unwrap();
-        assert!(!short_code.value().is_empty());
-        assert!(short_code.value().len() >= 6);
+        assert_ne!(copy.id, original.id);
+        assert_ne!(copy.short_code, original.short_code);
+        assert_eq!(copy.original_url, "https://example.com");
+        assert_eq!(copy.expiration_date, Some(expiration));
+        assert_eq!(copy.organization_id, Some(3));
+        assert_eq!(copy.user_id, Some(1));
     }
 
     #[tokio::test]
-    async fn test_create_url_with_generated_code() {
+    async fn test_duplicate_url_with_overrides() {
         let repo = MockUrlRepository::new();
         let service = UrlService::new(repo);
+        let original = service
+            .create_url("https://example.com", None, None, Some(1))
+            .await
+            .unwrap();
 
-        let url = service
-            .create_url("https://example.com", None, None, None)
+        let overrides = DuplicateOverrides {
+            original_url: Some("https://example.org".to_string()),
+            custom_short_code: Some(ShortCode::new("copy1".to_string()).unwrap()),
+        };
+        let copy = service
+            .duplicate_url(original.id, 1, overrides)
             .await
             .unwrap();
-        assert_eq!(url.original_url, "https://example.com");
-        assert!(!url.short_code.is_empty());
+        assert_eq!(copy.original_url, "https://example.org");
+        assert_eq!(copy.short_code, "copy1");
+
+        let taken = DuplicateOverrides {
+            custom_short_code: Some(ShortCode::new("copy1".to_string()).unwrap()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            service.duplicate_url(original.id, 1, taken).await,
+            Err(ServiceError::ShortCodeAlreadyExists)
+        ));
     }
 
     #[tokio::test]
-    async fn test_create_url_with_custom_code() {
+    async fn test_duplicate_url_requires_ownership() {
         let repo = MockUrlRepository::new();
         let service = UrlService::new(repo);
-        let custom_code = ShortCode::new("mycode".to_string()).unwrap();
-
-        let url = service
-            .create_url("https://example.com", Some(custom_code.clone()), None, None)
+        let original = service
+            .create_url("https://example.com", None, None, Some(1))
             .await
             .unwrap();
-        assert_eq!(url.short_code, custom_code.value());
-        assert_eq!(url.original_url, "https://example.com");
+
+        assert!(matches!(
+            service
+                .duplicate_url(original.id, 2, DuplicateOverrides::default())
+                .await,
+            Err(ServiceError::PermissionDenied(_))
+        ));
+        assert!(matches!(
+            service
+                .duplicate_url(999, 1, DuplicateOverrides::default())
+                .await,
+            Err(ServiceError::Repository(RepositoryError::NotFound))
+        ));
     }
 
     #[tokio::test]
@@ -971,4 +2491,647 @@ mod tests {
         assert_eq!(result.successful, 2);
         assert_eq!(result.failed, 0);
     }
+
+    #[tokio::test]
+    async fn test_batch_clear_expiration() {
+        let repo = MockUrlRepository::new();
+        let service = UrlService::new(repo.clone());
+        let expiration = chrono::Utc::now() + chrono::Duration::days(3);
+
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let url = service
+                .create_url(
+                    &format!("https://example{}.com", i),
+                    None,
+                    Some(expiration),
+                    Some(1),
+                )
+                .await
+                .unwrap();
+            ids.push(url.id);
+        }
+        let expiring = service
+            .get_urls_expiring_soon(chrono::Duration::days(7))
+            .await
+            .unwrap();
+        assert_eq!(expiring.len(), 3);
+
+        // Only the owner's URLs are touched
+        let result = service
+            .batch_update_expiration(&ids[..2], None, Some(2))
+            .await
+            .unwrap();
+        assert_eq!(result.failed, 2);
+
+        let result = service
+            .batch_update_expiration(&ids[..2], None, Some(1))
+            .await
+            .unwrap();
+        assert_eq!(result.successful, 2);
+
+        let expiring = service
+            .get_urls_expiring_soon(chrono::Duration::days(7))
+            .await
+            .unwrap();
+        assert_eq!(
+            expiring.iter().map(|url| url.id).collect::<Vec<_>>(),
+            vec![ids[2]]
+        );
+        let cleared = service.get_url_by_id(ids[0]).await.unwrap().unwrap();
+        assert_eq!(cleared.expiration_date, None);
+    }
+
+    #[tokio::test]
+    async fn test_dashboard_listings_are_scoped_and_limited() {
+        let repo = MockUrlRepository::new();
+        let service = UrlService::new(repo.clone());
+
+        for i in 0..3 {
+            service
+                .create_url(&format!("https://example{}.com", i), None, None, Some(1))
+                .await
+                .unwrap();
+        }
+        service
+            .create_url("https://other.com", None, None, Some(2))
+            .await
+            .unwrap();
+
+        let top = service.get_most_clicked_urls(1, 10).await.unwrap();
+        assert_eq!(top.len(), 3);
+        assert!(top.iter().all(|u| u.url.user_id == Some(1)));
+
+        let recent = service.get_recent_urls(1, 2).await.unwrap();
+        assert_eq!(recent.len(), 2);
+
+        // A zero limit is clamped to a single result
+        let recent = service.get_recent_urls(1, 0).await.unwrap();
+        assert_eq!(recent.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_urls_by_status_pages_are_consistent() {
+        let repo = MockUrlRepository::new();
+        let service = UrlService::new(repo.clone());
+        let mut active = Vec::new();
+        for i in 0..7 {
+            let url = service
+                .create_url(
+                    &format!("https://example.com/{}", i),
+                    None,
+                    None,
+                    Some(i % 2 + 1),
+                )
+                .await
+                .unwrap();
+            active.push(url.id);
+        }
+        assert!(service
+            .deactivate_url(active.remove(2), Some(1))
+            .await
+            .unwrap());
+
+        let (first, mut after) = service
+            .list_urls_by_status(UrlStatus::Active, None, 4)
+            .await
+            .unwrap();
+        assert_eq!(after, first.last().map(|url| url.id));
+        let mut seen: Vec<i32> = first.iter().map(|url| url.id).collect();
+
+        // A URL created while paging shows up on a later page, nothing is repeated or skipped
+        let late = service
+            .create_url("https://example.com/late", None, None, Some(3))
+            .await
+            .unwrap();
+        active.push(late.id);
+        while let Some(cursor) = after {
+            let (page, next) = service
+                .list_urls_by_status(UrlStatus::Active, Some(cursor), 4)
+                .await
+                .unwrap();
+            assert!(page.iter().all(|url| url.id > cursor));
+            seen.extend(page.iter().map(|url| url.id));
+            after = next;
+        }
+        assert_eq!(seen, active);
+
+        let (inactive, after) = service
+            .list_urls_by_status(UrlStatus::Inactive, None, 0)
+            .await
+            .unwrap();
+        assert_eq!(inactive.len(), 1);
+        assert_eq!(after, None);
+    }
+
+    #[tokio::test]
+    async fn test_find_urls_by_original_url() {
+        let repo = MockUrlRepository::new();
+        let service = UrlService::new(repo.clone());
+        for user_id in [1, 2, 2] {
+            service
+                .create_url("https://example.com/shared", None, None, Some(user_id))
+                .await
+                .unwrap();
+        }
+        service
+            .create_url("https://example.com/shared/other", None, None, Some(1))
+            .await
+            .unwrap();
+
+        let own = service
+            .find_user_urls_by_original_url("https://example.com/shared", 2)
+            .await
+            .unwrap();
+        assert_eq!(own.len(), 2);
+        assert!(own.iter().all(|u| u.user_id == Some(2)));
+
+        let all = service
+            .find_all_urls_by_original_url("https://example.com/shared", 10)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert!(all
+            .iter()
+            .all(|u| u.original_url == "https://example.com/shared"));
+
+        let limited = service
+            .find_all_urls_by_original_url("https://example.com/shared", 1)
+            .await
+            .unwrap();
+        assert_eq!(limited.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_urls_pages_are_disjoint_and_complete() {
+        let repo = MockUrlRepository::new();
+        let service = UrlService::new(repo.clone());
+
+        for i in 0..100 {
+            service
+                .create_url(&format!("https://example{}.com", i), None, None, Some(1))
+                .await
+                .unwrap();
+        }
+        service
+            .create_url("https://other.com", None, None, Some(2))
+            .await
+            .unwrap();
+
+        for sort in [UrlSortField::CreatedAt, UrlSortField::ShortCode] {
+            for direction in [SortDirection::Asc, SortDirection::Desc] {
+                let mut seen = Vec::new();
+                let mut cursor: Option<String> = None;
+                let mut pages = 0;
+                loop {
+                    let page = service
+                        .list_urls(1, None, sort, direction, cursor.as_deref(), None, 30)
+                        .await
+                        .unwrap();
+                    pages += 1;
+                    seen.extend(page.urls);
+                    match page.next_cursor {
+                        Some(next) => cursor = Some(next),
+                        None => break,
+                    }
+                }
+
+                assert_eq!(pages, 4);
+                assert_eq!(seen.len(), 100);
+                let mut ids: Vec<i32> = seen.iter().map(|u| u.id).collect();
+                ids.sort_unstable();
+                ids.dedup();
+                assert_eq!(
+                    ids.len(),
+                    100,
+                    "pages overlap for {:?} {:?}",
+                    sort,
+                    direction
+                );
+                assert!(seen.iter().all(|u| u.user_id == Some(1)));
+                assert!(seen.windows(2).all(|pair| match direction {
+                    SortDirection::Asc => sort.compare(&pair[0], &pair[1]).is_lt(),
+                    SortDirection::Desc => sort.compare(&pair[0], &pair[1]).is_gt(),
+                }));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archived_urls_are_listed_only_on_request_and_can_be_restored() {
+        let repo = MockUrlRepository::new();
+        let service = UrlService::new(repo.clone());
+        let now = chrono::Utc::now();
+        let expired = service
+            .create_url(
+                "https://example.com/old",
+                None,
+                Some(now - chrono::Duration::days(1)),
+                Some(1),
+            )
+            .await
+            .unwrap();
+        let current = service
+            .create_url("https://example.com/new", None, None, Some(1))
+            .await
+            .unwrap();
+        assert_eq!(service.cleanup_expired_urls().await.unwrap(), 1);
+
+        let list = |status| {
+            let service = service.clone();
+            async move {
+                let page = service
+                    .list_urls(
+                        1,
+                        status,
+                        UrlSortField::default(),
+                        SortDirection::default(),
+                        None,
+                        None,
+                        10,
+                    )
+                    .await
+                    .unwrap();
+                page.urls.iter().map(|u| u.id).collect::<Vec<i32>>()
+            }
+        };
+        assert_eq!(list(None).await, vec![current.id]);
+        assert_eq!(list(Some(UrlStatus::Archived)).await, vec![expired.id]);
+
+        // Only the owner can restore, and only archived URLs
+        assert!(service
+            .restore_url(expired.id, 2, None)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            service.restore_url(current.id, 1, None).await,
+            Err(ServiceError::InvalidData(_))
+        ));
+        assert!(matches!(
+            service
+                .restore_url(expired.id, 1, Some(now - chrono::Duration::hours(1)))
+                .await,
+            Err(ServiceError::InvalidData(_))
+        ));
+
+        let extended = now + chrono::Duration::days(30);
+        let restored = service
+            .restore_url(expired.id, 1, Some(extended))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(restored.status, UrlStatus::Active);
+        assert_eq!(restored.expiration_date, Some(extended));
+        assert!(restored.is_accessible());
+        assert_eq!(list(None).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_urls_rejects_foreign_cursors() {
+        let repo = MockUrlRepository::new();
+        let service = UrlService::new(repo.clone());
+        for i in 0..3 {
+            service
+                .create_url(&format!("https://example{}.com", i), None, None, Some(1))
+                .await
+                .unwrap();
+        }
+
+        let page = service
+            .list_urls(
+                1,
+                None,
+                UrlSortField::CreatedAt,
+                SortDirection::Desc,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+        let cursor = page.next_cursor.unwrap();
+
+        let result = service
+            .list_urls(
+                1,
+                None,
+                UrlSortField::ShortCode,
+                SortDirection::Desc,
+                Some(&cursor),
+                None,
+                1,
+            )
+            .await;
+        assert!(matches!(result, Err(ServiceError::InvalidData(_))));
+
+        let result = service
+            .list_urls(
+                1,
+                None,
+                UrlSortField::CreatedAt,
+                SortDirection::Desc,
+                Some("not-a-cursor"),
+                None,
+                1,
+            )
+            .await;
+        assert!(matches!(result, Err(ServiceError::InvalidData(_))));
+
+        let result = service
+            .list_urls(
+                1,
+                None,
+                UrlSortField::CreatedAt,
+                SortDirection::Desc,
+                Some(&cursor),
+                Some(&cursor),
+                1,
+            )
+            .await;
+        assert!(matches!(result, Err(ServiceError::InvalidData(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_urls_pages_backwards_with_prev_cursor() {
+        let repo = MockUrlRepository::new();
+        let service = UrlService::new(repo.clone());
+        for i in 0..25 {
+            service
+                .create_url(&format!("https://example{}.com", i), None, None, Some(1))
+                .await
+                .unwrap();
+        }
+        let (sort, direction) = (UrlSortField::ShortCode, SortDirection::Asc);
+
+        let mut pages = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = service
+                .list_urls(1, None, sort, direction, cursor.as_deref(), None, 10)
+                .await
+                .unwrap();
+            assert_eq!(page.prev_cursor.is_none(), pages.is_empty());
+            cursor = page.next_cursor.clone();
+            pages.push(page);
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(pages.len(), 3);
+
+        // Walking back from the last page returns the same pages in the same order
+        let mut cursor = pages[2].prev_cursor.clone();
+        for expected in pages[..2].iter().rev() {
+            let page = service
+                .list_urls(1, None, sort, direction, None, cursor.as_deref(), 10)
+                .await
+                .unwrap();
+            let ids: Vec<i32> = page.urls.iter().map(|u| u.id).collect();
+            let expected_ids: Vec<i32> = expected.urls.iter().map(|u| u.id).collect();
+            assert_eq!(ids, expected_ids);
+            assert!(page.next_cursor.is_some());
+            cursor = page.prev_cursor;
+        }
+        assert!(cursor.is_none(), "first page has no previous page");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_updates_only_one_wins() {
+        let repo = MockUrlRepository::new();
+        let service = UrlService::new(repo.clone());
+        let url = service
+            .create_url("https://example.com", None, None, Some(1))
+            .await
+            .unwrap();
+        assert_eq!(url.version, 1);
+
+        // Two tasks read version 1 and race to update it
+        let tasks: Vec<_> = ["https://first.com", "https://second.com"]
+            .into_iter()
+            .map(|target| {
+                let service = service.clone();
+                let mut url = url.clone();
+                tokio::spawn(async move {
+                    url.original_url = target.to_string();
+                    service.update_url(&url, 1).await
+                })
+            })
+            .collect();
+
+        let mut results = Vec::new();
+        for task in tasks {
+            results.push(task.await.unwrap());
+        }
+
+        let winners: Vec<_> = results.iter().filter_map(|r| r.as_ref().ok()).collect();
+        assert_eq!(winners.len(), 1);
+        assert_eq!(winners[0].version, 2);
+        assert!(results.iter().any(|r| matches!(
+            r,
+            Err(ServiceError::Repository(
+                RepositoryError::ConflictingUpdate { current_version: 2 }
+            ))
+        )));
+
+        // The stored URL is the winner's, and a stale version keeps failing
+        let stored = service.get_url_by_id(url.id).await.unwrap().unwrap();
+        assert_eq!(stored.original_url, winners[0].original_url);
+        assert!(service.update_url(&stored, 1).await.is_err());
+        assert_eq!(service.update_url(&stored, 2).await.unwrap().version, 3);
+    }
+
+    #[tokio::test]
+    async fn test_update_bumps_updated_at() {
+        let service = UrlService::new(MockUrlRepository::new());
+        let url = service
+            .create_url("https://example.com", None, None, Some(1))
+            .await
+            .unwrap();
+        assert_eq!(url.updated_at, url.created_at);
+
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let mut changed = url.clone();
+        changed.original_url = "https://example.org".to_string();
+        let updated = service.update_url(&changed, url.version).await.unwrap();
+        assert!(updated.updated_at > updated.created_at);
+
+        let feed = service
+            .get_recently_modified_urls(1, url.created_at, 10)
+            .await
+            .unwrap();
+        assert_eq!(feed.len(), 1);
+        assert_eq!(feed[0].original_url, "https://example.org");
+    }
+
+    #[tokio::test]
+    async fn test_reencode_short_codes_keeps_old_codes_resolving() {
+        use crate::infrastructure::test_utils::MockUrlRepository as AliasingUrlRepository;
+
+        let repo = AliasingUrlRepository::new();
+        let code = |value: &str| ShortCode::new(value.to_string()).unwrap();
+        for (short_code, status) in [
+            ("c0de", UrlStatus::Active),
+            ("abcd", UrlStatus::Active),
+            ("OldIl", UrlStatus::Archived),
+        ] {
+            repo.create_url(
+                &code(short_code),
+                "https://example.com",
+                None,
+                Some(1),
+                None,
+                status,
+            )
+            .await
+            .unwrap();
+        }
+        let service =
+            UrlService::new(repo.clone()).with_short_code_alphabet(ShortCodeAlphabet::Base58);
+
+        let summary = service.reencode_short_codes().await.unwrap();
+        assert_eq!(
+            summary,
+            ReencodeSummary {
+                checked: 3,
+                reencoded: 2,
+                failed: 0,
+            }
+        );
+
+        for (old_code, id) in [("c0de", 1), ("abcd", 2), ("OldIl", 3)] {
+            let url = repo
+                .find_by_short_code(&code(old_code), false)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(url.id, id);
+            assert!(ShortCode::from_string_unchecked(url.short_code.clone())
+                .is_valid_for_alphabet(&ShortCodeAlphabet::Base58));
+            assert!(repo.exists_by_short_code(&code(old_code)).await.unwrap());
+        }
+        assert_eq!(
+            repo.find_by_id(2).await.unwrap().unwrap().short_code,
+            "abcd"
+        );
+
+        // A second run finds nothing left to do
+        let summary = service.reencode_short_codes().await.unwrap();
+        assert_eq!(summary.reencoded, 0);
+    }
+
+    /// Service with users 1 and 2, and URL `abc123` owned by user 1
+    async fn transfer_setup(
+        max_urls_per_user: u32,
+    ) -> (
+        UrlService<MockUrlRepository>,
+        Url,
+        crate::infrastructure::test_utils::MockUserRepository,
+        crate::infrastructure::test_utils::MockAuditLogRepository,
+    ) {
+        use crate::infrastructure::test_utils::{MockAuditLogRepository, MockUserRepository};
+
+        let users = MockUserRepository::new();
+        for name in ["alice", "bob"] {
+            users
+                .create_user(name, &format!("{}@example.com", name), "hash")
+                .await
+                .unwrap();
+        }
+        let audit_log = MockAuditLogRepository::new();
+        let service = UrlService::new(MockUrlRepository::new())
+            .with_user_repository(Arc::new(users.clone()))
+            .with_audit_log(Arc::new(audit_log.clone()))
+            .with_max_urls_per_user(max_urls_per_user);
+        let url = service
+            .create_url(
+                "https://example.com",
+                Some(ShortCode::new("abc123".to_string()).unwrap()),
+                None,
+                Some(1),
+            )
+            .await
+            .unwrap();
+        (service, url, users, audit_log)
+    }
+
+    #[tokio::test]
+    async fn test_transfer_url_changes_owner_and_records_audit_entry() {
+        let (service, url, _, audit_log) = transfer_setup(0).await;
+
+        let transferred = service.transfer_url(url.id, 1, 2, 99).await.unwrap();
+        assert_eq!(transferred.id, url.id);
+        assert_eq!(transferred.user_id, Some(2));
+        assert_eq!(service.get_urls_for_user(2).await.unwrap().len(), 1);
+        assert!(service.get_urls_for_user(1).await.unwrap().is_empty());
+
+        let entries = audit_log.entries.lock().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            (entries[0].entity_type.as_str(), entries[0].entity_id),
+            ("url", url.id)
+        );
+        assert_eq!(entries[0].actor_user_id, 99);
+        assert_eq!(entries[0].action, "url.transfer");
+        assert_eq!(entries[0].details["from_user_id"], 1);
+        assert_eq!(entries[0].details["to_user_id"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_url_checks_current_owner_and_recipient() {
+        let (service, url, users, audit_log) = transfer_setup(0).await;
+
+        let result = service.transfer_url(url.id, 2, 1, 99).await;
+        assert!(matches!(result, Err(ServiceError::PermissionDenied(_))));
+
+        let result = service.transfer_url(url.id, 1, 42, 99).await;
+        assert!(matches!(result, Err(ServiceError::UserNotFound(42))));
+
+        users
+            .update_account_status(
+                2,
+                &crate::domain::entities::AccountStatus::Suspended {
+                    reason: "Spam".to_string(),
+                    until: None,
+                },
+            )
+            .await
+            .unwrap();
+        let result = service.transfer_url(url.id, 1, 2, 99).await;
+        assert!(matches!(result, Err(ServiceError::UserSuspended(2))));
+
+        assert_eq!(
+            service
+                .get_url_by_id(url.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .user_id,
+            Some(1)
+        );
+        assert!(audit_log.entries.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_url_refuses_recipient_at_quota() {
+        let (service, url, _, audit_log) = transfer_setup(1).await;
+        service
+            .create_url("https://example.org", None, None, Some(2))
+            .await
+            .unwrap();
+
+        let result = service.transfer_url(url.id, 1, 2, 99).await;
+        assert!(matches!(
+            result,
+            Err(ServiceError::UrlQuotaExceeded {
+                user_id: 2,
+                limit: 1
+            })
+        ));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("already owns 1 URLs"));
+        assert!(audit_log.entries.lock().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,186 @@
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Prefix of the Redis keys holding cached URL analytics
+pub const KEY_PREFIX: &str = "analytics:";
+
+/// How long a URL's cache is left alone after being invalidated, however often it is clicked
+pub const INVALIDATION_DEBOUNCE: Duration = Duration::from_secs(60);
+
+/// Errors of the URL analytics cache
+#[derive(Error, Debug)]
+pub enum AnalyticsCacheError {
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+}
+
+/// Cached URL analytics, stored as JSON under keys starting with `analytics:<url_id>:`
+#[async_trait]
+pub trait AnalyticsCache: Send + Sync {
+    /// Cached value of `key`, if it has not expired
+    async fn get(&self, key: &str) -> Result<Option<String>, AnalyticsCacheError>;
+
+    /// Cache `value` under `key` for `ttl`
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> Result<(), AnalyticsCacheError>;
+
+    /// Drop everything cached for a URL
+    async fn invalidate_url(&self, url_id: i32) -> Result<(), AnalyticsCacheError>;
+}
+
+/// URL analytics cached in Redis, shared by every instance
+#[derive(Clone)]
+pub struct RedisAnalyticsCache {
+    connection: ConnectionManager,
+}
+
+impl RedisAnalyticsCache {
+    /// Connect to the Redis server at `url`
+    pub async fn connect(url: &str) -> Result<Self, AnalyticsCacheError> {
+        let client = redis::Client::open(url)?;
+        let connection = ConnectionManager::new(client).await?;
+        Ok(Self { connection })
+    }
+}
+
+#[async_trait]
+impl AnalyticsCache for RedisAnalyticsCache {
+    async fn get(&self, key: &str) -> Result<Option<String>, AnalyticsCacheError> {
+        let mut connection = self.connection.clone();
+        let value: Option<String> = redis::cmd("GET")
+            .arg(key)
+            .query_async(&mut connection)
+            .await?;
+        Ok(value)
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> Result<(), AnalyticsCacheError> {
+        let mut connection = self.connection.clone();
+        redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .query_async::<_, ()>(&mut connection)
+            .await?;
+        Ok(())
+    }
+
+    async fn invalidate_url(&self, url_id: i32) -> Result<(), AnalyticsCacheError> {
+        // A URL has one key per requested range, so they are found with SCAN rather than KEYS
+        let mut connection = self.connection.clone();
+        let pattern = format!("{}{}:*", KEY_PREFIX, url_id);
+        let mut cursor: u64 = 0;
+        loop {
+            let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(100)
+                .query_async(&mut connection)
+                .await?;
+            if !keys.is_empty() {
+                redis::cmd("DEL")
+                    .arg(keys)
+                    .query_async::<_, ()>(&mut connection)
+                    .await?;
+            }
+            if next == 0 {
+                return Ok(());
+            }
+            cursor = next;
+        }
+    }
+}
+
+/// Drops a URL's cached analytics when it is clicked, at most once per debounce period
+///
+/// Clicks arriving within the period after an invalidation leave the cache alone; the entries
+/// cached meanwhile expire on their own soon after.
+pub struct UrlAnalyticsCacheInvalidator {
+    cache: Arc<dyn AnalyticsCache>,
+    debounce: Duration,
+    last_invalidated: Mutex<HashMap<i32, Instant>>,
+}
+
+impl UrlAnalyticsCacheInvalidator {
+    /// Invalidate `cache` at most once per `INVALIDATION_DEBOUNCE` for each URL
+    pub fn new(cache: Arc<dyn AnalyticsCache>) -> Self {
+        Self::with_debounce(cache, INVALIDATION_DEBOUNCE)
+    }
+
+    pub fn with_debounce(cache: Arc<dyn AnalyticsCache>, debounce: Duration) -> Self {
+        Self {
+            cache,
+            debounce,
+            last_invalidated: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Note a click of `url_id`, invalidating its cached analytics in the background if the
+    /// debounce period since the last invalidation has passed
+    pub fn click_recorded(&self, url_id: i32) {
+        if !self.should_invalidate(url_id, Instant::now()) {
+            return;
+        }
+        let cache = self.cache.clone();
+        tokio::spawn(async move {
+            if let Err(e) = cache.invalidate_url(url_id).await {
+                tracing::warn!("Failed to invalidate analytics of URL {}: {}", url_id, e);
+            }
+        });
+    }
+
+    /// Whether a click at `now` invalidates the URL's cache, remembering it if so
+    fn should_invalidate(&self, url_id: i32, now: Instant) -> bool {
+        let mut last_invalidated = self.last_invalidated.lock().unwrap();
+        if let Some(at) = last_invalidated.get(&url_id) {
+            if now.duration_since(*at) < self.debounce {
+                return false;
+            }
+        }
+        last_invalidated.retain(|_, at| now.duration_since(*at) < self.debounce);
+        last_invalidated.insert(url_id, now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_utils::MockAnalyticsCache;
+
+    #[tokio::test]
+    async fn test_invalidation_is_debounced_per_url() {
+        let invalidator = UrlAnalyticsCacheInvalidator::new(Arc::new(MockAnalyticsCache::new()));
+        let now = Instant::now();
+
+        assert!(invalidator.should_invalidate(1, now));
+        assert!(!invalidator.should_invalidate(1, now + Duration::from_secs(30)));
+        assert!(invalidator.should_invalidate(2, now + Duration::from_secs(30)));
+        assert!(invalidator.should_invalidate(1, now + INVALIDATION_DEBOUNCE));
+    }
+
+    /// Needs a Redis server: `REDIS_URL=redis://localhost:6379 cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_analytics_cache() {
+        let url = std::env::var("REDIS_URL").unwrap_or("redis://localhost:6379".to_string());
+        let cache = RedisAnalyticsCache::connect(&url).await.unwrap();
+        let url_id = (uuid::Uuid::new_v4().as_u128() % 1_000_000_000) as i32;
+        let key = format!("{}{}:day:-:-", KEY_PREFIX, url_id);
+
+        cache
+            .set(&key, "{}", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(cache.get(&key).await.unwrap().as_deref(), Some("{}"));
+
+        cache.invalidate_url(url_id).await.unwrap();
+        assert_eq!(cache.get(&key).await.unwrap(), None);
+    }
+}
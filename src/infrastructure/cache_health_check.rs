@@ -0,0 +1,62 @@
+use std::time::Duration;
+use thiserror::Error;
+
+/// Longest a readiness probe waits for Redis to answer
+const PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Errors of the Redis connectivity check
+#[derive(Error, Debug)]
+pub enum CacheHealthError {
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+    #[error("no answer within {0:?}")]
+    Timeout(Duration),
+}
+
+/// Connectivity check against Redis, used by readiness probes
+///
+/// Each check opens its own connection, so a server that was down at startup is reported
+/// healthy as soon as it comes back.
+#[derive(Clone)]
+pub struct CacheHealthCheck {
+    client: redis::Client,
+}
+
+impl CacheHealthCheck {
+    /// Check the Redis server at `url`; nothing is connected until the first check
+    pub fn new(url: &str) -> Result<Self, CacheHealthError> {
+        Ok(Self {
+            client: redis::Client::open(url)?,
+        })
+    }
+
+    /// Send `PING` to confirm Redis accepts commands
+    pub async fn check(&self) -> Result<(), CacheHealthError> {
+        let ping = async {
+            let mut connection = self.client.get_multiplexed_async_connection().await?;
+            redis::cmd("PING")
+                .query_async::<_, String>(&mut connection)
+                .await?;
+            Ok(())
+        };
+        tokio::time::timeout(PING_TIMEOUT, ping)
+            .await
+            .map_err(|_| CacheHealthError::Timeout(PING_TIMEOUT))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_check_fails_when_redis_is_unreachable() {
+        let health = CacheHealthCheck::new("redis://127.0.0.1:1").unwrap();
+        assert!(health.check().await.is_err());
+    }
+
+    #[test]
+    fn test_invalid_url_is_rejected() {
+        assert!(CacheHealthCheck::new("not a url").is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Prefix of the Redis keys marking recent clicks
+const KEY_PREFIX: &str = "click_dedup:";
+
+/// Errors of the store remembering recent clicks
+#[derive(Error, Debug)]
+pub enum ClickDeduplicationError {
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+}
+
+/// Remembers which IPs clicked which URLs recently
+#[async_trait]
+pub trait ClickDeduplicator: Send + Sync {
+    /// Mark a click of `url_id` from `ip_address` for `window`, returning whether no earlier
+    /// click of the pair was still marked
+    async fn first_click(
+        &self,
+        ip_address: &str,
+        url_id: i32,
+        window: Duration,
+    ) -> Result<bool, ClickDeduplicationError>;
+}
+
+/// Recent clicks as expiring Redis keys, shared by every instance
+#[derive(Clone)]
+pub struct RedisClickDeduplicator {
+    connection: ConnectionManager,
+}
+
+impl RedisClickDeduplicator {
+    /// Connect to the Redis server at `url`
+    pub async fn connect(url: &str) -> Result<Self, ClickDeduplicationError> {
+        let client = redis::Client::open(url)?;
+        let connection = ConnectionManager::new(client).await?;
+        Ok(Self { connection })
+    }
+}
+
+#[async_trait]
+impl ClickDeduplicator for RedisClickDeduplicator {
+    async fn first_click(
+        &self,
+        ip_address: &str,
+        url_id: i32,
+        window: Duration,
+    ) -> Result<bool, ClickDeduplicationError> {
+        // SET ... NX replies nil when the key exists, so the check and the mark are atomic
+        let mut connection = self.connection.clone();
+        let reply: Option<String> = redis::cmd("SET")
+            .arg(format!("{}{}:{}", KEY_PREFIX, ip_address, url_id))
+            .arg(1)
+            .arg("EX")
+            .arg(window.as_secs().max(1))
+            .arg("NX")
+            .query_async(&mut connection)
+            .await?;
+        Ok(reply.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Needs a Redis server: `REDIS_URL=redis://localhost:6379 cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_deduplicator() {
+        let url = std::env::var("REDIS_URL").unwrap_or("redis://localhost:6379".to_string());
+        let deduplicator = RedisClickDeduplicator::connect(&url).await.unwrap();
+        let ip = format!("test-{}", uuid::Uuid::new_v4());
+        let window = Duration::from_secs(1);
+
+        assert!(deduplicator.first_click(&ip, 1, window).await.unwrap());
+        assert!(!deduplicator.first_click(&ip, 1, window).await.unwrap());
+        assert!(deduplicator.first_click(&ip, 2, window).await.unwrap());
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        assert!(deduplicator.first_click(&ip, 1, window).await.unwrap());
+    }
+}
@@ -0,0 +1,30 @@
+use serde::Deserialize;
+
+/// IP reputation checks of redirecting clients against AbuseIPDB
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AbuseIpDbConfig {
+    /// Key of the AbuseIPDB API; must come from the environment
+    pub api_key: String,
+    /// Abuse confidence score (0-100) above which a client IP is suspicious
+    pub score_threshold: u8,
+    /// Check client IPs on redirects; also needs `api_key` and `rate_limit.redis_url`
+    pub enabled: bool,
+}
+
+impl Default for AbuseIpDbConfig {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            score_threshold: 75,
+            enabled: false,
+        }
+    }
+}
+
+impl AbuseIpDbConfig {
+    /// Whether client IPs are checked, i.e. the checks are enabled and a key is set
+    pub fn is_active(&self) -> bool {
+        self.enabled && !self.api_key.is_empty()
+    }
+}
@@ -0,0 +1,100 @@
+use serde::Deserialize;
+
+/// Name of the cookie identifying the click a visitor arrived with
+pub const CLICK_COOKIE_NAME: &str = "url_shortener_click_id";
+
+/// SameSite attribute of the click cookie
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CookieSameSite {
+    Strict,
+    #[default]
+    Lax,
+    None,
+}
+
+impl CookieSameSite {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CookieSameSite::Strict => "Strict",
+            CookieSameSite::Lax => "Lax",
+            CookieSameSite::None => "None",
+        }
+    }
+}
+
+/// Click cookie configuration
+///
+/// Goal pages read the cookie to report conversions, so `domain` must cover both the short
+/// link host and the destination site (e.g. `.example.com`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ClickCookieConfig {
+    /// Domain attribute; omitted when unset, limiting the cookie to the short link host
+    pub domain: Option<String>,
+    pub same_site: CookieSameSite,
+    /// How long a click can still be converted, in days
+    pub max_age_days: u32,
+}
+
+impl Default for ClickCookieConfig {
+    fn default() -> Self {
+        Self {
+            domain: None,
+            same_site: CookieSameSite::Lax,
+            max_age_days: 30,
+        }
+    }
+}
+
+impl ClickCookieConfig {
+    /// `Set-Cookie` header value carrying a click token
+    ///
+    /// Not `HttpOnly`: the goal page script has to read it. Browsers reject `SameSite=None`
+    /// without `Secure`, so that combination always adds it.
+    pub fn set_cookie_value(&self, click_token: &str) -> String {
+        let mut cookie = format!(
+            "{}={}; Path=/; Max-Age={}; SameSite={}",
+            CLICK_COOKIE_NAME,
+            click_token,
+            u64::from(self.max_age_days) * 24 * 60 * 60,
+            self.same_site.as_str()
+        );
+        if let Some(domain) = self.domain.as_deref().filter(|d| !d.is_empty()) {
+            cookie.push_str("; Domain=");
+            cookie.push_str(domain);
+        }
+        if self.same_site == CookieSameSite::None {
+            cookie.push_str("; Secure");
+        }
+        cookie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_cookie() {
+        let cookie = ClickCookieConfig::default().set_cookie_value("abc");
+        assert_eq!(
+            cookie,
+            "url_shortener_click_id=abc; Path=/; Max-Age=2592000; SameSite=Lax"
+        );
+    }
+
+    #[test]
+    fn test_cross_site_cookie_with_domain() {
+        let config = ClickCookieConfig {
+            domain: Some(".example.com".to_string()),
+            same_site: CookieSameSite::None,
+            max_age_days: 1,
+        };
+        let cookie = config.set_cookie_value("abc");
+        assert!(cookie.contains("; Domain=.example.com"));
+        assert!(cookie.contains("; SameSite=None"));
+        assert!(cookie.ends_with("; Secure"));
+        assert!(cookie.contains("Max-Age=86400"));
+    }
+}
@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+/// CORS configuration
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CorsConfig {
+    /// Origins allowed to call the API; empty allows any origin
+    pub allowed_origins: Vec<String>,
+}
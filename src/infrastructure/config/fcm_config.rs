@@ -0,0 +1,47 @@
+use serde::Deserialize;
+
+/// Google endpoint exchanging a signed service account JWT for an access token
+pub const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+
+/// Firebase Cloud Messaging API
+pub const FCM_API_URL: &str = "https://fcm.googleapis.com";
+
+/// Push notifications through Firebase Cloud Messaging (HTTP v1 API)
+///
+/// The service account fields come from the JSON key file of a Firebase service account.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct FcmConfig {
+    /// Send push notifications; also needs the project and service account
+    pub enabled: bool,
+    pub project_id: String,
+    /// `client_email` of the service account
+    pub client_email: String,
+    /// PEM `private_key` of the service account; must come from the environment
+    pub private_key: String,
+    pub token_url: String,
+    pub api_url: String,
+}
+
+impl Default for FcmConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            project_id: String::new(),
+            client_email: String::new(),
+            private_key: String::new(),
+            token_url: GOOGLE_TOKEN_URL.to_string(),
+            api_url: FCM_API_URL.to_string(),
+        }
+    }
+}
+
+impl FcmConfig {
+    /// Whether pushes are sent, i.e. they are enabled and the service account is set
+    pub fn is_active(&self) -> bool {
+        self.enabled
+            && !self.project_id.is_empty()
+            && !self.client_email.is_empty()
+            && !self.private_key.is_empty()
+    }
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Features an instance offers, switched on and off by configuration
+///
+/// Handlers of a disabled feature answer `501 FEATURE_DISABLED`. Features this server does
+/// not implement yet default to off.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, ToSchema)]
+#[serde(default)]
+pub struct FeatureFlags {
+    /// Login with Google and GitHub
+    pub enable_social_login: bool,
+    /// Passwordless login through emailed links
+    pub enable_magic_link: bool,
+    /// Two-factor authentication; not implemented yet
+    pub enable_2fa: bool,
+    /// A/B testing of link destinations; not implemented yet
+    pub enable_ab_testing: bool,
+    /// Webhooks to external systems; not implemented yet
+    pub enable_webhooks: bool,
+    /// Recording of clicks on short links; redirects still work while it is off
+    pub enable_click_tracking: bool,
+    /// QR codes of short links; not implemented yet
+    pub enable_qr_codes: bool,
+}
+
+impl Default for FeatureFlags {
+    fn default() -> Self {
+        Self {
+            enable_social_login: true,
+            enable_magic_link: true,
+            enable_2fa: false,
+            enable_ab_testing: false,
+            enable_webhooks: false,
+            enable_click_tracking: true,
+            enable_qr_codes: false,
+        }
+    }
+}
+
+/// A feature whose handlers answer `501 FEATURE_DISABLED` while it is off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    SocialLogin,
+    MagicLink,
+}
+
+impl Feature {
+    /// Name of the feature in messages to clients
+    pub fn description(&self) -> &'static str {
+        match self {
+            Feature::SocialLogin => "Social login",
+            Feature::MagicLink => "Magic link login",
+        }
+    }
+}
+
+impl FeatureFlags {
+    /// Whether `feature` is enabled on this instance
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        match feature {
+            Feature::SocialLogin => self.enable_social_login,
+            Feature::MagicLink => self.enable_magic_link,
+        }
+    }
+}
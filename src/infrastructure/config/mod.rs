@@ -1,3 +1,25 @@
+pub mod abuse_ipdb_config;
 pub mod app_config;
+pub mod click_cookie_config;
+pub mod cors_config;
 pub mod database_config;
+pub mod fcm_config;
+pub mod feature_flags;
+pub mod object_storage_config;
 pub mod rate_limit_config;
+pub mod retention_config;
+pub mod short_code_config;
+
+pub use abuse_ipdb_config::AbuseIpDbConfig;
+#[allow(unused_imports)]
+pub use app_config::{env_var, AppConfig, ConfigError, Environment};
+pub use click_cookie_config::ClickCookieConfig;
+pub use cors_config::CorsConfig;
+#[allow(unused_imports)]
+pub use database_config::{DatabaseConfig, DbSslMode};
+pub use fcm_config::FcmConfig;
+pub use feature_flags::{Feature, FeatureFlags};
+pub use object_storage_config::ObjectStorageConfig;
+pub use rate_limit_config::{RateLimitAlgorithm, RateLimitConfig};
+pub use retention_config::{retention_cutoff, RetentionConfig};
+pub use short_code_config::{ShortCodeConfig, ShortCodeStrategyType};
@@ -0,0 +1,80 @@
+use serde::Deserialize;
+use std::env;
+use std::path::PathBuf;
+
+/// Where uploaded files such as profile pictures are stored
+///
+/// Files go to an S3-compatible bucket (AWS S3, MinIO, ...) when `bucket` is set, and to
+/// `local_dir` otherwise. Analytics exports hold visitor IPs and are kept apart from the
+/// public uploads, in `exports_bucket` or `exports_dir`.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ObjectStorageConfig {
+    /// S3 API endpoint; defaults to AWS S3 in `region`. Set it to the server URL for MinIO
+    pub endpoint: Option<String>,
+    /// Bucket holding uploaded files
+    pub bucket: Option<String>,
+    pub region: String,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+    /// Base URL objects are publicly served from; defaults to `<endpoint>/<bucket>`
+    pub public_url: Option<String>,
+    /// Directory uploads are stored in when no bucket is configured
+    pub local_dir: PathBuf,
+    /// Private bucket for analytics exports, which are only handed out as pre-signed URLs;
+    /// required along with `bucket`
+    pub exports_bucket: Option<String>,
+    /// Directory analytics exports are stored in when no bucket is configured
+    pub exports_dir: PathBuf,
+}
+
+impl Default for ObjectStorageConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            bucket: None,
+            region: "us-east-1".to_string(),
+            access_key_id: None,
+            secret_access_key: None,
+            public_url: None,
+            local_dir: env::temp_dir().join("url-shortener-uploads"),
+            exports_bucket: None,
+            exports_dir: env::temp_dir().join("url-shortener-exports"),
+        }
+    }
+}
+
+impl ObjectStorageConfig {
+    /// Whether uploads go to an S3-compatible bucket instead of the local directory
+    pub fn s3_enabled(&self) -> bool {
+        self.bucket.is_some()
+    }
+
+    /// Endpoint of the S3 API, falling back to AWS S3 in the configured region
+    pub fn resolved_endpoint(&self) -> String {
+        match &self.endpoint {
+            Some(endpoint) => endpoint.trim_end_matches('/').to_string(),
+            None => format!("https://s3.{}.amazonaws.com", self.region),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolved_endpoint() {
+        let mut config = ObjectStorageConfig {
+            region: "eu-west-1".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.resolved_endpoint(),
+            "https://s3.eu-west-1.amazonaws.com"
+        );
+
+        config.endpoint = Some("http://localhost:9000/".to_string());
+        assert_eq!(config.resolved_endpoint(), "http://localhost:9000");
+    }
+}
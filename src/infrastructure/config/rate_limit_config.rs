@@ -1,45 +1,73 @@
 #![allow(dead_code)]
-use std::env;
+use serde::Deserialize;
+
+/// How requests are counted against the rate limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitAlgorithm {
+    /// Counter reset at the start of every window; allows up to twice the limit across a
+    /// window boundary
+    #[default]
+    FixedWindow,
+    /// Log of request times over the last window; requires Redis and falls back to
+    /// `FixedWindow` without it
+    SlidingWindow,
+}
 
 /// Rate limiting configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
 pub struct RateLimitConfig {
     pub requests_per_minute: u32,
     pub burst_size: u32,
+    /// Length of the counting window, in seconds; the limit scales with it
     pub window_size: u64,
+    pub algorithm: RateLimitAlgorithm,
+    /// Redis holding the request log of the sliding window, shared by every instance
+    pub redis_url: Option<String>,
 }
 
 impl Default for RateLimitConfig {
     fn default() -> Self {
         Self {
-            requests_per_minute: env::var("RATE_LIMIT_REQUESTS_PER_MINUTE")
-                .unwrap_or_else(|_| "60".to_string())
-                .parse()
-                .unwrap_or(60),
-            burst_size: env::var("RATE_LIMIT_BURST_SIZE")
-                .unwrap_or_else(|_| "10".to_string())
-                .parse()
-                .unwrap_or(10),
-            window_size: env::var("RATE_LIMIT_WINDOW_SIZE")
-                .unwrap_or_else(|_| "60".to_string())
-                .parse()
-                .unwrap_or(60),
+            requests_per_minute: 60,
+            burst_size: 10,
+            window_size: 60,
+            algorithm: RateLimitAlgorithm::default(),
+            redis_url: None,
         }
     }
 }
 
 impl RateLimitConfig {
-    pub fn from_env() -> Result<Self, env::VarError> {
-        Ok(RateLimitConfig {
-            requests_per_minute: env::var("RATE_LIMIT_REQUESTS_PER_MINUTE")?
-                .parse()
-                .map_err(|_| env::VarError::NotPresent)?,
-            burst_size: env::var("RATE_LIMIT_BURST_SIZE")?
-                .parse()
-                .map_err(|_| env::VarError::NotPresent)?,
-            window_size: env::var("RATE_LIMIT_WINDOW_SIZE")?
-                .parse()
-                .map_err(|_| env::VarError::NotPresent)?,
-        })
+    /// Requests allowed per window: `requests_per_minute` scaled to `window_size`
+    pub fn requests_per_window(&self) -> u32 {
+        let scaled = u64::from(self.requests_per_minute) * self.window_size / 60;
+        scaled.clamp(1, u64::from(u32::MAX)) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_per_window() {
+        let config = RateLimitConfig::default();
+        assert_eq!(config.requests_per_window(), 60);
+
+        let config = RateLimitConfig {
+            requests_per_minute: 100,
+            window_size: 1,
+            ..Default::default()
+        };
+        assert_eq!(config.requests_per_window(), 1);
+
+        let config = RateLimitConfig {
+            requests_per_minute: 100,
+            window_size: 3600,
+            ..Default::default()
+        };
+        assert_eq!(config.requests_per_window(), 6000);
     }
 }
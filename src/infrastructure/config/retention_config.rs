@@ -0,0 +1,62 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+/// How long cleanup keeps each kind of data, in days
+///
+/// A value of 0 disables cleanup for that kind of data.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RetentionConfig {
+    /// Days after expiring before a URL is archived
+    pub expired_url_retention_days: u32,
+    /// Days a deleted URL is kept before it is removed for good with its clicks
+    pub deleted_url_retention_days: u32,
+    /// Days a password reset token is kept after it expired
+    pub password_reset_token_retention_days: u32,
+    /// Days click records are kept
+    pub click_data_retention_days: u32,
+    /// Days audit log entries are kept; reserved for the audit log
+    pub audit_log_retention_days: u32,
+    /// Days a finished bulk operation's progress stays available
+    pub bulk_operation_retention_days: u32,
+    /// Days a magic link token is kept after it expired
+    pub magic_link_token_retention_days: u32,
+    /// Days a sent email is kept in the outbox
+    pub sent_email_retention_days: u32,
+    /// Days the archived copy of a record removed for good is kept
+    pub archive_retention_days: u32,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            expired_url_retention_days: 30,
+            deleted_url_retention_days: 90,
+            password_reset_token_retention_days: 7,
+            click_data_retention_days: 365,
+            audit_log_retention_days: 365,
+            bulk_operation_retention_days: 7,
+            magic_link_token_retention_days: 7,
+            sent_email_retention_days: 30,
+            // 7 years, as audits of deletions commonly require
+            archive_retention_days: 2555,
+        }
+    }
+}
+
+/// Oldest time kept by a retention period, or `None` when cleanup is disabled
+pub fn retention_cutoff(retention_days: u32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    (retention_days > 0).then(|| now - Duration::days(retention_days.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retention_cutoff() {
+        let now = Utc::now();
+        assert_eq!(retention_cutoff(0, now), None);
+        assert_eq!(retention_cutoff(30, now), Some(now - Duration::days(30)));
+    }
+}
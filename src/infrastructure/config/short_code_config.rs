@@ -0,0 +1,50 @@
+use crate::domain::entities::ShortCodeAlphabet;
+use serde::Deserialize;
+
+/// How generated short codes are picked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShortCodeStrategyType {
+    /// At random
+    #[default]
+    Random,
+    /// From the SHA-256 of the URL, so the same URL gets the same code first
+    Hash,
+    /// From a database counter; never collides but reveals how many URLs were shortened
+    Sequential,
+}
+
+/// Short code generation and validation configuration
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ShortCodeConfig {
+    /// Length of generated short codes
+    pub length: usize,
+    /// Shortest custom short code users may choose
+    pub min_length: usize,
+    /// Longest custom short code users may choose
+    pub max_length: usize,
+    /// Characters allowed in custom short codes
+    pub alphabet: String,
+    /// Characters generated short codes are made of
+    pub generated_alphabet: ShortCodeAlphabet,
+    /// How differently a generated code must sound from every code in use, from 0.0 to 1.0
+    ///
+    /// Codes closer than this, such as `cl1ck` to an existing `click`, are regenerated.
+    /// 0.0 disables the check, which reads every short code in use for each generated one.
+    pub min_phonetic_distance: f32,
+}
+
+impl Default for ShortCodeConfig {
+    fn default() -> Self {
+        Self {
+            length: 6,
+            min_length: 4,
+            max_length: 50,
+            alphabet: "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz-_"
+                .to_string(),
+            generated_alphabet: ShortCodeAlphabet::default(),
+            min_phonetic_distance: 0.0,
+        }
+    }
+}
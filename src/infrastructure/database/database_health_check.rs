@@ -0,0 +1,180 @@
+use crate::infrastructure::metrics;
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// How often the pool monitor refreshes the `db_pool_connections_*` gauges
+pub const POOL_STATS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Share of the pool in use above which the pool monitor logs a warning
+const POOL_UTILIZATION_WARNING: f64 = 0.8;
+
+/// SQLSTATE of a missing table, here `pg_stat_statements` without its extension
+const UNDEFINED_TABLE: &str = "42P01";
+
+/// Connections held by the pool at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Open connections, idle or in use
+    pub size: u32,
+    pub idle: u32,
+    pub max_connections: u32,
+}
+
+impl PoolStats {
+    /// Connections currently running a query or held by a transaction
+    pub fn active(&self) -> u32 {
+        self.size.saturating_sub(self.idle)
+    }
+
+    /// Active connections as a share of the maximum pool size, from 0.0 to 1.0
+    pub fn utilization(&self) -> f64 {
+        if self.max_connections == 0 {
+            return 0.0;
+        }
+        f64::from(self.active()) / f64::from(self.max_connections)
+    }
+}
+
+/// A statement from `pg_stat_statements` with its execution times
+#[derive(Debug, Clone)]
+pub struct SlowQuery {
+    pub query: String,
+    pub calls: i64,
+    pub mean_exec_time_ms: f64,
+    pub total_exec_time_ms: f64,
+}
+
+/// Connectivity check against the PostgreSQL pool, used by readiness probes
+#[derive(Clone)]
+pub struct DatabaseHealthCheck {
+    pool: PgPool,
+}
+
+impl DatabaseHealthCheck {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Run `SELECT 1` to confirm the database accepts queries
+    pub async fn check(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Bytes used on disk by each of the given tables, including indexes and TOAST data
+    ///
+    /// Tables that don't exist are left out.
+    pub async fn table_sizes(&self, tables: &[&str]) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        let tables: Vec<String> = tables.iter().map(|t| t.to_string()).collect();
+        sqlx::query_as(
+            "SELECT c.relname::TEXT, pg_total_relation_size(c.oid)
+             FROM pg_class c
+             JOIN pg_namespace n ON n.oid = c.relnamespace
+             WHERE c.relkind = 'r' AND n.nspname = current_schema() AND c.relname = ANY($1)",
+        )
+        .bind(tables)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Current size of the connection pool
+    pub fn pool_stats(&self) -> PoolStats {
+        PoolStats {
+            size: self.pool.size(),
+            idle: u32::try_from(self.pool.num_idle()).unwrap_or(u32::MAX),
+            max_connections: self.pool.options().get_max_connections(),
+        }
+    }
+
+    /// The `limit` statements with the highest mean execution time
+    ///
+    /// Gives `None` when the `pg_stat_statements` extension is not installed.
+    pub async fn slowest_queries(&self, limit: i64) -> Result<Option<Vec<SlowQuery>>, sqlx::Error> {
+        let rows: Result<Vec<(String, i64, f64, f64)>, sqlx::Error> = sqlx::query_as(
+            "SELECT query, calls, mean_exec_time, total_exec_time
+             FROM pg_stat_statements
+             ORDER BY mean_exec_time DESC
+             LIMIT $1",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await;
+
+        match rows {
+            Ok(rows) => Ok(Some(
+                rows.into_iter()
+                    .map(
+                        |(query, calls, mean_exec_time_ms, total_exec_time_ms)| SlowQuery {
+                            query,
+                            calls,
+                            mean_exec_time_ms,
+                            total_exec_time_ms,
+                        },
+                    )
+                    .collect(),
+            )),
+            Err(e)
+                if e.as_database_error()
+                    .and_then(|e| e.code())
+                    .is_some_and(|code| code == UNDEFINED_TABLE) =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Refresh the pool gauges every [`POOL_STATS_INTERVAL`] until the runtime stops
+    ///
+    /// Logs a warning whenever more than 80% of the pool is in use, as requests start waiting
+    /// for connections once it is exhausted.
+    pub fn spawn_pool_monitor(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(POOL_STATS_INTERVAL);
+            loop {
+                interval.tick().await;
+                let stats = self.pool_stats();
+                metrics::sync_db_pool(&stats);
+                if stats.utilization() > POOL_UTILIZATION_WARNING {
+                    tracing::warn!(
+                        "Database pool {:.0}% in use: {} of {} connections active",
+                        stats.utilization() * 100.0,
+                        stats.active(),
+                        stats.max_connections
+                    );
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_stats_utilization() {
+        let stats = PoolStats {
+            size: 10,
+            idle: 1,
+            max_connections: 10,
+        };
+        assert_eq!(stats.active(), 9);
+        assert!(stats.utilization() > POOL_UTILIZATION_WARNING);
+
+        let quiet = PoolStats {
+            size: 4,
+            idle: 3,
+            max_connections: 10,
+        };
+        assert_eq!(quiet.active(), 1);
+        assert!(quiet.utilization() < POOL_UTILIZATION_WARNING);
+
+        let empty = PoolStats {
+            size: 0,
+            idle: 0,
+            max_connections: 0,
+        };
+        assert_eq!(empty.utilization(), 0.0);
+    }
+}
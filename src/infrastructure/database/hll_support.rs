@@ -0,0 +1,48 @@
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+use tracing::{info, warn};
+
+/// Detects whether the `hll` (HyperLogLog) extension is installed
+///
+/// The result is cached once known; without the extension unique visitor counts fall
+/// back to `COUNT(DISTINCT ip_address)` over the raw clicks.
+#[derive(Clone, Default)]
+pub struct HllSupport {
+    available: Arc<OnceCell<bool>>,
+}
+
+impl HllSupport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if the HLL functions can be used on this database
+    pub async fn is_available(&self, pool: &PgPool) -> bool {
+        let detected = self
+            .available
+            .get_or_try_init(|| async {
+                let available: bool = sqlx::query_scalar(
+                    "SELECT EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'hll')",
+                )
+                .fetch_one(pool)
+                .await?;
+                if available {
+                    info!("HyperLogLog extension available for unique visitor estimates");
+                } else {
+                    warn!("HyperLogLog extension not installed, counting distinct IPs instead");
+                }
+                Ok::<bool, sqlx::Error>(available)
+            })
+            .await;
+
+        match detected {
+            Ok(available) => *available,
+            Err(e) => {
+                // Not cached, so the next call retries the detection
+                warn!("Failed to detect HyperLogLog extension: {}", e);
+                false
+            }
+        }
+    }
+}
@@ -1,10 +1,48 @@
+pub mod database_health_check;
+pub mod hll_support;
 pub mod postgres_account_deletion_token_repository;
+pub mod postgres_archive_repository;
+pub mod postgres_audit_log_repository;
+pub mod postgres_click_repository;
+pub mod postgres_domain_blacklist_repository;
+pub mod postgres_email_change_repository;
+pub mod postgres_email_outbox_repository;
+pub mod postgres_export_job_repository;
+pub mod postgres_magic_link_repository;
+pub mod postgres_notification_preferences_repository;
+pub mod postgres_organization_repository;
 pub mod postgres_password_reset_repository;
+pub mod postgres_push_outbox_repository;
 pub mod postgres_repository;
+pub mod postgres_service_account_repository;
+pub mod postgres_session_repository;
+pub mod postgres_short_code_sequence_repository;
+pub mod postgres_url_metadata_repository;
 pub mod postgres_user_repository;
+pub mod postgres_user_url_stats_repository;
+pub mod query_builders;
+pub mod replica_aware_pool;
 
+pub use database_health_check::{DatabaseHealthCheck, PoolStats, SlowQuery};
 #[allow(unused_imports)]
 pub use postgres_account_deletion_token_repository::PostgresAccountDeletionTokenRepository;
+pub use postgres_archive_repository::PostgresArchiveRepository;
+pub use postgres_audit_log_repository::PostgresAuditLogRepository;
+pub use postgres_click_repository::PostgresClickRepository;
+pub use postgres_domain_blacklist_repository::PostgresDomainBlacklistRepository;
+pub use postgres_email_change_repository::PostgresEmailChangeRepository;
+pub use postgres_email_outbox_repository::PostgresEmailOutboxRepository;
+pub use postgres_export_job_repository::PostgresExportJobRepository;
+pub use postgres_magic_link_repository::PostgresMagicLinkRepository;
+pub use postgres_notification_preferences_repository::PostgresNotificationPreferencesRepository;
+pub use postgres_organization_repository::PostgresOrganizationRepository;
 pub use postgres_password_reset_repository::PostgresPasswordResetRepository;
+pub use postgres_push_outbox_repository::PostgresPushOutboxRepository;
 pub use postgres_repository::PostgresUrlRepository;
+pub use postgres_service_account_repository::PostgresServiceAccountRepository;
+pub use postgres_session_repository::PostgresSessionRepository;
+pub use postgres_short_code_sequence_repository::PostgresShortCodeSequenceRepository;
+pub use postgres_url_metadata_repository::PostgresUrlMetadataRepository;
 pub use postgres_user_repository::PostgresUserRepository;
+pub use postgres_user_url_stats_repository::PostgresUserUrlStatsRepository;
+pub use replica_aware_pool::{ReplicaAwarePool, ReplicaLag};
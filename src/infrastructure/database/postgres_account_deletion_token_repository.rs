@@ -1,5 +1,7 @@
 use crate::domain::entities::AccountDeletionToken;
 use crate::domain::repositories::account_deletion_token_repository::AccountDeletionTokenRepository;
+use crate::infrastructure::database::PostgresEmailOutboxRepository;
+use crate::infrastructure::email::EmailMessage;
 use async_trait::async_trait;
 use sqlx::{PgPool, Row};
 
@@ -36,7 +38,10 @@ impl AccountDeletionTokenRepository for PostgresAccountDeletionTokenRepository {
     async fn create_token(
         &self,
         token: AccountDeletionToken,
+        email: Option<&EmailMessage>,
     ) -> Result<AccountDeletionToken, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tx = self.pool.begin().await?;
+
         let row = sqlx::query(
             "INSERT INTO account_deletion_tokens (user_id, token, created_at, expires_at, is_confirmed, is_cancelled) 
              VALUES ($1, $2, $3, $4, $5, $6) 
@@ -48,9 +53,13 @@ impl AccountDeletionTokenRepository for PostgresAccountDeletionTokenRepository {
         .bind(token.expires_at)
         .bind(token.is_confirmed)
         .bind(token.is_cancelled)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await?;
 
+        if let Some(email) = email {
+            PostgresEmailOutboxRepository::enqueue_in(&mut tx, email).await?;
+        }
+        tx.commit().await?;
         Ok(self.row_to_token(&row))
     }
 
@@ -0,0 +1,91 @@
+use crate::domain::entities::ArchiveRecord;
+use crate::domain::repositories::{ArchiveRepository, RepositoryError};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+
+/// PostgreSQL implementation of the ArchiveRepository trait
+#[derive(Clone)]
+pub struct PostgresArchiveRepository {
+    pool: PgPool,
+}
+
+impl PostgresArchiveRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert a database row to an ArchiveRecord entity
+    fn row_to_record(row: &sqlx::postgres::PgRow) -> ArchiveRecord {
+        let data_snapshot: String = row.get("data_snapshot");
+        ArchiveRecord {
+            id: row.get("id"),
+            entity_type: row.get("entity_type"),
+            entity_id: row.get("entity_id"),
+            data_snapshot: serde_json::from_str(&data_snapshot).unwrap_or_default(),
+            deleted_at: row.get("deleted_at"),
+            deletion_reason: row.get("deletion_reason"),
+        }
+    }
+}
+
+#[async_trait]
+impl ArchiveRepository for PostgresArchiveRepository {
+    async fn archive(&self, record: &ArchiveRecord) -> Result<ArchiveRecord, RepositoryError> {
+        let row = sqlx::query(
+            "INSERT INTO archive_records (entity_type, entity_id, data_snapshot, deletion_reason)
+             VALUES ($1, $2, $3::JSONB, $4)
+             RETURNING id, entity_type, entity_id, data_snapshot::TEXT, deleted_at, deletion_reason",
+        )
+        .bind(&record.entity_type)
+        .bind(record.entity_id)
+        .bind(record.data_snapshot.to_string())
+        .bind(&record.deletion_reason)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(Self::row_to_record(&row))
+    }
+
+    async fn find_records(
+        &self,
+        entity_type: Option<&str>,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<ArchiveRecord>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT id, entity_type, entity_id, data_snapshot::TEXT, deleted_at, deletion_reason
+             FROM archive_records
+             WHERE ($1::TEXT IS NULL OR entity_type = $1)
+               AND ($2::TIMESTAMPTZ IS NULL OR deleted_at < $2)
+             ORDER BY deleted_at DESC, id DESC
+             LIMIT $3",
+        )
+        .bind(entity_type)
+        .bind(before)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_record).collect())
+    }
+
+    async fn count_archived_before(&self, cutoff: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM archive_records WHERE deleted_at < $1")
+                .bind(cutoff)
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(count as u64)
+    }
+
+    async fn delete_archived_before(&self, cutoff: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        let result = sqlx::query("DELETE FROM archive_records WHERE deleted_at < $1")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,50 @@
+use crate::domain::entities::AuditLogEntry;
+use crate::domain::repositories::{AuditLogRepository, RepositoryError};
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+/// PostgreSQL implementation of the AuditLogRepository trait
+#[derive(Clone)]
+pub struct PostgresAuditLogRepository {
+    pool: PgPool,
+}
+
+impl PostgresAuditLogRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert a database row to an AuditLogEntry entity
+    fn row_to_entry(row: &sqlx::postgres::PgRow) -> AuditLogEntry {
+        let details: String = row.get("details");
+        AuditLogEntry {
+            id: row.get("id"),
+            actor_user_id: row.get("actor_user_id"),
+            action: row.get("action"),
+            entity_type: row.get("entity_type"),
+            entity_id: row.get("entity_id"),
+            details: serde_json::from_str(&details).unwrap_or_default(),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for PostgresAuditLogRepository {
+    async fn record(&self, entry: &AuditLogEntry) -> Result<AuditLogEntry, RepositoryError> {
+        let row = sqlx::query(
+            "INSERT INTO audit_log (actor_user_id, action, entity_type, entity_id, details)
+             VALUES ($1, $2, $3, $4, $5::JSONB)
+             RETURNING id, actor_user_id, action, entity_type, entity_id, details::TEXT, created_at",
+        )
+        .bind(entry.actor_user_id)
+        .bind(&entry.action)
+        .bind(&entry.entity_type)
+        .bind(entry.entity_id)
+        .bind(entry.details.to_string())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(Self::row_to_entry(&row))
+    }
+}
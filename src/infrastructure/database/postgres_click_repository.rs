@@ -0,0 +1,836 @@
+use super::hll_support::HllSupport;
+use crate::domain::entities::click::{
+    BOT_USER_AGENT_MARKERS, MOBILE_USER_AGENT_MARKERS, TABLET_USER_AGENT_MARKERS,
+};
+use crate::domain::entities::{Click, ConversionEvent, ConversionGoal, UrlConfig};
+use crate::domain::repositories::click_repository::{
+    ClickCountTotal, ClickDedupRatio, ClickRepository, ClickStats, DeviceBreakdown,
+    RepositoryError, UrlAnalyticsSummary, UtmAttributionRow,
+};
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, QueryBuilder, Row};
+use std::net::IpAddr;
+
+/// Columns selected when loading clicks; INET is returned as text
+const CLICK_COLUMNS: &str = "clicks.id, clicks.url_id, clicks.clicked_at, \
+     host(clicks.ip_address) AS ip_address, clicks.user_agent, clicks.referer, \
+     clicks.country_code, clicks.created_at, clicks.click_token, clicks.suspicious, \
+     clicks.utm_source, clicks.utm_medium, clicks.utm_campaign";
+
+/// Columns of `conversion_goals` selected into a ConversionGoal
+const GOAL_COLUMNS: &str = "id, url_id, goal_url_pattern, name, created_at";
+
+/// Number of entries returned for top countries and referers
+const TOP_ENTRIES_LIMIT: i64 = 5;
+
+/// Number of clicks listed in an analytics summary
+const RECENT_CLICKS_LIMIT: i64 = 10;
+
+/// PostgreSQL implementation of the ClickRepository trait
+#[derive(Clone)]
+pub struct PostgresClickRepository {
+    pool: PgPool,
+    hll: HllSupport,
+}
+
+impl PostgresClickRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            hll: HllSupport::new(),
+        }
+    }
+
+    /// Fold the visitors of `clicks` into the daily HyperLogLog sketches
+    ///
+    /// Only a hash of each IP ends up in `click_hll`, so the estimates outlive the
+    /// retention of the raw clicks.
+    async fn add_to_daily_hll<'e, E>(executor: E, clicks: &[Click]) -> Result<(), RepositoryError>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        let (url_ids, (clicked_at, ip_addresses)): (Vec<i32>, (Vec<_>, Vec<_>)) = clicks
+            .iter()
+            .filter_map(|click| {
+                Self::valid_ip(&click.ip_address).map(|ip| (click.url_id, (click.clicked_at, ip)))
+            })
+            .unzip();
+        if url_ids.is_empty() {
+            return Ok(());
+        }
+
+        sqlx::query(
+            "INSERT INTO click_hll (url_id, date, hll_state)
+             SELECT url_id, (clicked_at AT TIME ZONE 'UTC')::date,
+                    hll_add_agg(hll_hash_text(ip_address))::bytea
+             FROM UNNEST($1::int[], $2::timestamptz[], $3::text[])
+                  AS batch(url_id, clicked_at, ip_address)
+             GROUP BY 1, 2
+             ON CONFLICT (url_id, date) DO UPDATE
+             SET hll_state = hll_union(click_hll.hll_state::hll, EXCLUDED.hll_state::hll)::bytea",
+        )
+        .bind(url_ids)
+        .bind(clicked_at)
+        .bind(ip_addresses)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Convert a database row to a Click entity
+    fn row_to_click(row: &sqlx::postgres::PgRow) -> Click {
+        Click {
+            id: row.get("id"),
+            url_id: row.get("url_id"),
+            clicked_at: row.get("clicked_at"),
+            ip_address: row.get("ip_address"),
+            user_agent: row.get("user_agent"),
+            referer: row.get("referer"),
+            country_code: row.get("country_code"),
+            created_at: row.get("created_at"),
+            click_token: row.get("click_token"),
+            suspicious: row.get("suspicious"),
+            utm_source: row.get("utm_source"),
+            utm_medium: row.get("utm_medium"),
+            utm_campaign: row.get("utm_campaign"),
+        }
+    }
+
+    fn row_to_url_config(row: &sqlx::postgres::PgRow) -> UrlConfig {
+        UrlConfig {
+            url_id: row.get("url_id"),
+            click_dedup_window_seconds: row.get("click_dedup_window_seconds"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+
+    fn row_to_goal(row: &sqlx::postgres::PgRow) -> ConversionGoal {
+        ConversionGoal {
+            id: row.get("id"),
+            url_id: row.get("url_id"),
+            goal_url_pattern: row.get("goal_url_pattern"),
+            name: row.get("name"),
+            created_at: row.get("created_at"),
+        }
+    }
+
+    /// SQL expression classifying `column` (a user agent) as desktop, mobile, tablet or bot
+    ///
+    /// Mirrors `DeviceType::from_user_agent` using the same marker lists.
+    fn device_type_sql(column: &str) -> String {
+        let pattern = |markers: &[&str]| markers.join("|");
+        format!(
+            "CASE WHEN {column} IS NULL OR {column} ~* '{bot}' THEN 'bot' \
+                  WHEN {column} ~* '{tablet}' THEN 'tablet' \
+                  WHEN {column} ~* '{mobile}' THEN 'mobile' \
+                  ELSE 'desktop' END",
+            bot = pattern(BOT_USER_AGENT_MARKERS),
+            tablet = pattern(TABLET_USER_AGENT_MARKERS),
+            mobile = pattern(MOBILE_USER_AGENT_MARKERS),
+        )
+    }
+
+    /// Decode a JSON column produced by `json_agg`
+    fn json_column<T: serde::de::DeserializeOwned>(
+        row: &sqlx::postgres::PgRow,
+        column: &str,
+    ) -> Result<T, RepositoryError> {
+        let json: String = row.get(column);
+        serde_json::from_str(&json)
+            .map_err(|e| RepositoryError::InvalidData(format!("{}: {}", column, e)))
+    }
+
+    /// Keep only addresses Postgres can store in an INET column
+    fn valid_ip(ip_address: &Option<String>) -> Option<String> {
+        ip_address
+            .as_deref()
+            .and_then(|ip| ip.trim().parse::<IpAddr>().ok())
+            .map(|ip| ip.to_string())
+    }
+
+    /// Aggregate click statistics for clicks matching `scope`
+    ///
+    /// `scope` is a fixed SQL fragment (joins and WHERE clause) whose only parameter is `$1`.
+    async fn stats_for(&self, scope: &str, id: i32) -> Result<ClickStats, RepositoryError> {
+        let totals = sqlx::query(&format!(
+            "SELECT COUNT(*) AS total_clicks,
+                    COUNT(DISTINCT clicks.ip_address) AS unique_ips,
+                    COUNT(*) FILTER (WHERE clicks.clicked_at >= date_trunc('day', NOW())) AS clicks_today,
+                    COUNT(*) FILTER (WHERE clicks.clicked_at >= NOW() - INTERVAL '7 days') AS clicks_this_week,
+                    COUNT(*) FILTER (WHERE clicks.clicked_at >= NOW() - INTERVAL '30 days') AS clicks_this_month
+             FROM clicks {}",
+            scope
+        ))
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let top_countries = self
+            .top_values_for(scope, "clicks.country_code", id)
+            .await?;
+        let top_referers = self.top_values_for(scope, "clicks.referer", id).await?;
+
+        Ok(ClickStats {
+            total_clicks: totals.get("total_clicks"),
+            unique_ips: totals.get("unique_ips"),
+            clicks_today: totals.get("clicks_today"),
+            clicks_this_week: totals.get("clicks_this_week"),
+            clicks_this_month: totals.get("clicks_this_month"),
+            top_countries,
+            top_referers,
+        })
+    }
+
+    /// Most frequent non-null values of `column` for clicks matching `scope`
+    async fn top_values_for(
+        &self,
+        scope: &str,
+        column: &str,
+        id: i32,
+    ) -> Result<Vec<(String, i64)>, RepositoryError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {column} AS value, COUNT(*) AS count
+             FROM clicks {scope} AND {column} IS NOT NULL
+             GROUP BY {column}
+             ORDER BY count DESC
+             LIMIT $2"
+        ))
+        .bind(id)
+        .bind(TOP_ENTRIES_LIMIT)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| (row.get("value"), row.get("count")))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ClickRepository for PostgresClickRepository {
+    async fn record_click(&self, click: &Click) -> Result<Click, RepositoryError> {
+        let use_hll = self.hll.is_available(&self.pool).await;
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query(&format!(
+            "INSERT INTO clicks (url_id, clicked_at, ip_address, user_agent, referer, country_code, click_token, suspicious, utm_source, utm_medium, utm_campaign)
+             VALUES ($1, $2, $3::inet, $4, $5, $6, $7, $8, $9, $10, $11)
+             RETURNING {}",
+            CLICK_COLUMNS
+        ))
+        .bind(click.url_id)
+        .bind(click.clicked_at)
+        .bind(Self::valid_ip(&click.ip_address))
+        .bind(&click.user_agent)
+        .bind(&click.referer)
+        .bind(&click.country_code)
+        .bind(&click.click_token)
+        .bind(click.suspicious)
+        .bind(&click.utm_source)
+        .bind(&click.utm_medium)
+        .bind(&click.utm_campaign)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if use_hll {
+            Self::add_to_daily_hll(&mut *tx, std::slice::from_ref(click)).await?;
+        }
+        tx.commit().await?;
+
+        Ok(Self::row_to_click(&row))
+    }
+
+    async fn record_clicks(&self, clicks: &[Click]) -> Result<u64, RepositoryError> {
+        if clicks.is_empty() {
+            return Ok(0);
+        }
+        let use_hll = self.hll.is_available(&self.pool).await;
+        let mut tx = self.pool.begin().await?;
+
+        // Single multi-row INSERT ... VALUES (...), (...), ...
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+            "INSERT INTO clicks (url_id, clicked_at, ip_address, user_agent, referer, country_code, click_token, suspicious, utm_source, utm_medium, utm_campaign) ",
+        );
+        query_builder.push_values(clicks, |mut row, click| {
+            row.push_bind(click.url_id)
+                .push_bind(click.clicked_at)
+                .push_bind(Self::valid_ip(&click.ip_address))
+                .push_unseparated("::inet")
+                .push_bind(click.user_agent.clone())
+                .push_bind(click.referer.clone())
+                .push_bind(click.country_code.clone())
+                .push_bind(click.click_token.clone())
+                .push_bind(click.suspicious)
+                .push_bind(click.utm_source.clone())
+                .push_bind(click.utm_medium.clone())
+                .push_bind(click.utm_campaign.clone());
+        });
+
+        let result = query_builder.build().execute(&mut *tx).await?;
+
+        if use_hll {
+            Self::add_to_daily_hll(&mut *tx, clicks).await?;
+        }
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn increment_deduplicated_click_counts(
+        &self,
+        counts: &[(i32, i64)],
+    ) -> Result<Vec<ClickCountTotal>, RepositoryError> {
+        if counts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let (url_ids, clicks): (Vec<i32>, Vec<i64>) = counts.iter().copied().unzip();
+
+        let rows = sqlx::query(
+            "UPDATE urls SET deduplicated_click_count = urls.deduplicated_click_count + batch.clicks
+             FROM UNNEST($1::int[], $2::bigint[]) AS batch(url_id, clicks)
+             WHERE urls.id = batch.url_id
+             RETURNING urls.id, urls.user_id, urls.short_code, urls.deduplicated_click_count",
+        )
+        .bind(url_ids)
+        .bind(clicks)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ClickCountTotal {
+                url_id: row.get("id"),
+                user_id: row.get("user_id"),
+                short_code: row.get("short_code"),
+                total: row.get("deduplicated_click_count"),
+            })
+            .collect())
+    }
+
+    async fn get_click_count(&self, url_id: i32) -> Result<i64, RepositoryError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM clicks WHERE url_id = $1")
+            .bind(url_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn get_clicks_for_url(
+        &self,
+        url_id: i32,
+        start_date: Option<chrono::DateTime<chrono::Utc>>,
+        end_date: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<Click>, RepositoryError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM clicks
+             WHERE clicks.url_id = $1
+             AND ($2::timestamptz IS NULL OR clicks.clicked_at >= $2)
+             AND ($3::timestamptz IS NULL OR clicks.clicked_at <= $3)
+             ORDER BY clicks.clicked_at DESC",
+            CLICK_COLUMNS
+        ))
+        .bind(url_id)
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_click).collect())
+    }
+
+    async fn get_clicks_for_user(
+        &self,
+        user_id: i32,
+        start_date: Option<chrono::DateTime<chrono::Utc>>,
+        end_date: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<Click>, RepositoryError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM clicks
+             JOIN urls ON urls.id = clicks.url_id
+             WHERE urls.user_id = $1
+             AND ($2::timestamptz IS NULL OR clicks.clicked_at >= $2)
+             AND ($3::timestamptz IS NULL OR clicks.clicked_at <= $3)
+             ORDER BY clicks.clicked_at DESC",
+            CLICK_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_click).collect())
+    }
+
+    async fn get_clicks_for_user_page(
+        &self,
+        user_id: i32,
+        start_date: Option<chrono::DateTime<chrono::Utc>>,
+        end_date: Option<chrono::DateTime<chrono::Utc>>,
+        after_id: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<Click>, RepositoryError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM clicks
+             JOIN urls ON urls.id = clicks.url_id
+             WHERE urls.user_id = $1
+             AND ($2::timestamptz IS NULL OR clicks.clicked_at >= $2)
+             AND ($3::timestamptz IS NULL OR clicks.clicked_at <= $3)
+             AND ($4::int IS NULL OR clicks.id > $4)
+             ORDER BY clicks.id
+             LIMIT $5",
+            CLICK_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(start_date)
+        .bind(end_date)
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_click).collect())
+    }
+
+    async fn get_url_click_stats(&self, url_id: i32) -> Result<ClickStats, RepositoryError> {
+        self.stats_for("WHERE clicks.url_id = $1", url_id).await
+    }
+
+    async fn get_user_click_stats(&self, user_id: i32) -> Result<ClickStats, RepositoryError> {
+        self.stats_for(
+            "JOIN urls ON urls.id = clicks.url_id WHERE urls.user_id = $1",
+            user_id,
+        )
+        .await
+    }
+
+    async fn get_unique_visitors_estimate(
+        &self,
+        url_id: i32,
+        start: chrono::NaiveDate,
+        end: chrono::NaiveDate,
+    ) -> Result<i64, RepositoryError> {
+        let query = if self.hll.is_available(&self.pool).await {
+            "SELECT COALESCE(ROUND(hll_cardinality(hll_union_agg(hll_state::hll))), 0)::BIGINT
+             FROM click_hll
+             WHERE url_id = $1 AND date BETWEEN $2 AND $3"
+        } else {
+            "SELECT COUNT(DISTINCT ip_address)
+             FROM clicks
+             WHERE url_id = $1 AND (clicked_at AT TIME ZONE 'UTC')::date BETWEEN $2 AND $3"
+        };
+
+        let estimate: i64 = sqlx::query_scalar(query)
+            .bind(url_id)
+            .bind(start)
+            .bind(end)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(estimate)
+    }
+
+    async fn get_url_analytics_summary(
+        &self,
+        url_id: i32,
+        include_bots: bool,
+    ) -> Result<UrlAnalyticsSummary, RepositoryError> {
+        // HLL sketches cover every click, so they can only stand in when bots are counted
+        let (sketch_cte, unique_visitors) =
+            if include_bots && self.hll.is_available(&self.pool).await {
+                (
+                    "sketch AS (
+                        SELECT hll_cardinality(hll_union_agg(click_hll.hll_state::hll)) AS estimate
+                        FROM click_hll JOIN url ON url.id = click_hll.url_id
+                    ),",
+                    "COALESCE(ROUND((SELECT estimate FROM sketch)), 0)::BIGINT",
+                )
+            } else {
+                ("", "totals.distinct_ips")
+            };
+
+        let query = format!(
+            "WITH url AS (
+                SELECT id FROM urls WHERE id = $1
+            ),
+            classified AS (
+                SELECT {CLICK_COLUMNS}, {device} AS device
+                FROM clicks JOIN url ON url.id = clicks.url_id
+            ),
+            filtered AS (
+                SELECT * FROM classified WHERE $2 OR device <> 'bot'
+            ),
+            totals AS (
+                SELECT COUNT(*) AS total_clicks,
+                       COUNT(DISTINCT ip_address) AS distinct_ips,
+                       COUNT(*) FILTER (WHERE clicked_at >= date_trunc('day', NOW())) AS clicks_today,
+                       COUNT(*) FILTER (WHERE clicked_at >= NOW() - INTERVAL '7 days') AS clicks_this_week,
+                       COUNT(*) FILTER (WHERE clicked_at >= NOW() - INTERVAL '30 days') AS clicks_this_month,
+                       COUNT(*) FILTER (WHERE device = 'desktop') AS desktop,
+                       COUNT(*) FILTER (WHERE device = 'mobile') AS mobile,
+                       COUNT(*) FILTER (WHERE device = 'tablet') AS tablet,
+                       COUNT(*) FILTER (WHERE device = 'bot') AS bot,
+                       COUNT(*) FILTER (WHERE EXISTS (
+                           SELECT 1 FROM conversion_events WHERE conversion_events.click_id = filtered.id
+                       )) AS converted_clicks
+                FROM filtered
+            ),
+            {sketch_cte}
+            top_countries AS (
+                SELECT country_code AS value, COUNT(*) AS count
+                FROM filtered WHERE country_code IS NOT NULL
+                GROUP BY country_code ORDER BY count DESC, value LIMIT $3
+            ),
+            top_referrers AS (
+                SELECT referer AS value, COUNT(*) AS count
+                FROM filtered WHERE referer IS NOT NULL
+                GROUP BY referer ORDER BY count DESC, value LIMIT $3
+            ),
+            recent AS (
+                SELECT * FROM filtered ORDER BY clicked_at DESC, id DESC LIMIT $4
+            )
+            SELECT totals.*,
+                   {unique_visitors} AS unique_visitors,
+                   COALESCE(totals.converted_clicks::float8 / NULLIF(totals.total_clicks, 0), 0) AS conversion_rate,
+                   (SELECT COALESCE(json_agg(json_build_array(value, count) ORDER BY count DESC, value), '[]')
+                    FROM top_countries)::text AS top_countries,
+                   (SELECT COALESCE(json_agg(json_build_array(value, count) ORDER BY count DESC, value), '[]')
+                    FROM top_referrers)::text AS top_referrers,
+                   (SELECT COALESCE(json_agg(json_build_object(
+                        'id', id, 'url_id', url_id, 'clicked_at', clicked_at,
+                        'ip_address', ip_address, 'user_agent', user_agent, 'referer', referer,
+                        'country_code', country_code, 'created_at', created_at
+                    ) ORDER BY clicked_at DESC, id DESC), '[]')
+                    FROM recent)::text AS recent_clicks
+            FROM totals",
+            device = Self::device_type_sql("clicks.user_agent"),
+        );
+
+        let row = sqlx::query(&query)
+            .bind(url_id)
+            .bind(include_bots)
+            .bind(TOP_ENTRIES_LIMIT)
+            .bind(RECENT_CLICKS_LIMIT)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(UrlAnalyticsSummary {
+            total_clicks: row.get("total_clicks"),
+            unique_visitors: row.get("unique_visitors"),
+            clicks_today: row.get("clicks_today"),
+            clicks_this_week: row.get("clicks_this_week"),
+            clicks_this_month: row.get("clicks_this_month"),
+            top_countries: Self::json_column(&row, "top_countries")?,
+            top_referrers: Self::json_column(&row, "top_referrers")?,
+            device_breakdown: DeviceBreakdown {
+                desktop: row.get("desktop"),
+                mobile: row.get("mobile"),
+                tablet: row.get("tablet"),
+                bot: row.get("bot"),
+            },
+            conversion_rate: row.get("conversion_rate"),
+            recent_clicks: Self::json_column(&row, "recent_clicks")?,
+        })
+    }
+
+    async fn create_conversion_goal(
+        &self,
+        url_id: i32,
+        goal_url_pattern: &str,
+        name: &str,
+    ) -> Result<ConversionGoal, RepositoryError> {
+        let row = sqlx::query(&format!(
+            "INSERT INTO conversion_goals (url_id, goal_url_pattern, name)
+             VALUES ($1, $2, $3)
+             RETURNING {}",
+            GOAL_COLUMNS
+        ))
+        .bind(url_id)
+        .bind(goal_url_pattern)
+        .bind(name)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(Self::row_to_goal(&row))
+    }
+
+    async fn find_conversion_goal(
+        &self,
+        id: i32,
+    ) -> Result<Option<ConversionGoal>, RepositoryError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM conversion_goals WHERE id = $1",
+            GOAL_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(Self::row_to_goal))
+    }
+
+    async fn delete_conversion_goal(&self, id: i32) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM conversion_goals WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn record_conversion(
+        &self,
+        click_token: &str,
+        goal_id: i32,
+    ) -> Result<Option<ConversionEvent>, RepositoryError> {
+        // The no-op update makes a repeated report return the existing event
+        let row = sqlx::query(
+            "INSERT INTO conversion_events (click_id, goal_id)
+             SELECT clicks.id, conversion_goals.id
+             FROM clicks
+             JOIN conversion_goals ON conversion_goals.url_id = clicks.url_id
+             WHERE clicks.click_token = $1 AND conversion_goals.id = $2
+             ON CONFLICT (click_id, goal_id)
+             DO UPDATE SET converted_at = conversion_events.converted_at
+             RETURNING id, click_id, goal_id, converted_at",
+        )
+        .bind(click_token)
+        .bind(goal_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| ConversionEvent {
+            id: row.get("id"),
+            click_id: row.get("click_id"),
+            goal_id: row.get("goal_id"),
+            converted_at: row.get("converted_at"),
+        }))
+    }
+
+    async fn get_conversion_rate(&self, url_id: i32) -> Result<f64, RepositoryError> {
+        let rate: f64 = sqlx::query_scalar(
+            "SELECT COALESCE(
+                 COUNT(*) FILTER (WHERE EXISTS (
+                     SELECT 1 FROM conversion_events WHERE conversion_events.click_id = clicks.id
+                 ))::float8 / NULLIF(COUNT(*), 0),
+                 0
+             )
+             FROM clicks WHERE url_id = $1",
+        )
+        .bind(url_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(rate)
+    }
+
+    async fn get_click_dedup_ratio(&self, url_id: i32) -> Result<ClickDedupRatio, RepositoryError> {
+        let row = sqlx::query(&format!(
+            "SELECT urls.deduplicated_click_count AS raw_clicks,
+                    COUNT(clicks.id) FILTER (WHERE {device} <> 'bot') AS unique_clicks,
+                    COUNT(clicks.id) FILTER (WHERE {device} = 'bot') AS bot_clicks
+             FROM urls
+             LEFT JOIN clicks ON clicks.url_id = urls.id
+             WHERE urls.id = $1
+             GROUP BY urls.id",
+            device = Self::device_type_sql("clicks.user_agent"),
+        ))
+        .bind(url_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(RepositoryError::NotFound)?;
+
+        Ok(ClickDedupRatio {
+            raw_clicks: row.get("raw_clicks"),
+            unique_clicks: row.get("unique_clicks"),
+            bot_clicks: row.get("bot_clicks"),
+        })
+    }
+
+    async fn get_utm_attribution_breakdown(
+        &self,
+        url_id: i32,
+    ) -> Result<Vec<UtmAttributionRow>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT utm_source, utm_medium, utm_campaign, COUNT(*) AS clicks
+             FROM clicks
+             WHERE url_id = $1
+               AND (utm_source IS NOT NULL OR utm_medium IS NOT NULL OR utm_campaign IS NOT NULL)
+             GROUP BY utm_source, utm_medium, utm_campaign
+             ORDER BY clicks DESC, utm_source, utm_medium, utm_campaign",
+        )
+        .bind(url_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| UtmAttributionRow {
+                source: row.get("utm_source"),
+                medium: row.get("utm_medium"),
+                campaign: row.get("utm_campaign"),
+                count: row.get("clicks"),
+            })
+            .collect())
+    }
+
+    async fn find_url_config(&self, url_id: i32) -> Result<Option<UrlConfig>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT url_id, click_dedup_window_seconds, updated_at FROM url_configs WHERE url_id = $1",
+        )
+        .bind(url_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(Self::row_to_url_config))
+    }
+
+    async fn save_url_config(&self, config: &UrlConfig) -> Result<UrlConfig, RepositoryError> {
+        let row = sqlx::query(
+            "INSERT INTO url_configs (url_id, click_dedup_window_seconds)
+             VALUES ($1, $2)
+             ON CONFLICT (url_id) DO UPDATE
+             SET click_dedup_window_seconds = EXCLUDED.click_dedup_window_seconds,
+                 updated_at = CURRENT_TIMESTAMP
+             RETURNING url_id, click_dedup_window_seconds, updated_at",
+        )
+        .bind(config.url_id)
+        .bind(config.click_dedup_window_seconds)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(Self::row_to_url_config(&row))
+    }
+
+    async fn get_hourly_distribution(&self, url_id: i32) -> Result<[u32; 24], RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT date_part('hour', clicked_at AT TIME ZONE 'UTC')::int AS hour, COUNT(*) AS count
+             FROM clicks WHERE url_id = $1
+             GROUP BY hour",
+        )
+        .bind(url_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut distribution = [0u32; 24];
+        for row in rows {
+            let hour: i32 = row.get("hour");
+            let count: i64 = row.get("count");
+            distribution[hour as usize] = count as u32;
+        }
+        Ok(distribution)
+    }
+
+    async fn get_daily_distribution(&self, url_id: i32) -> Result<[u32; 7], RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT date_part('dow', clicked_at AT TIME ZONE 'UTC')::int AS day, COUNT(*) AS count
+             FROM clicks WHERE url_id = $1
+             GROUP BY day",
+        )
+        .bind(url_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut distribution = [0u32; 7];
+        for row in rows {
+            let day: i32 = row.get("day");
+            let count: i64 = row.get("count");
+            distribution[day as usize] = count as u32;
+        }
+        Ok(distribution)
+    }
+
+    async fn get_peak_hour(&self, url_id: i32) -> Result<u8, RepositoryError> {
+        let hour: Option<i32> = sqlx::query_scalar(
+            "SELECT date_part('hour', clicked_at AT TIME ZONE 'UTC')::int AS hour
+             FROM clicks WHERE url_id = $1
+             GROUP BY hour
+             ORDER BY COUNT(*) DESC, hour
+             LIMIT 1",
+        )
+        .bind(url_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(hour.unwrap_or(0) as u8)
+    }
+
+    async fn get_click_velocity(
+        &self,
+        url_id: i32,
+        window_minutes: u32,
+    ) -> Result<f64, RepositoryError> {
+        if window_minutes == 0 {
+            return Ok(0.0);
+        }
+        let clicks: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM clicks
+             WHERE url_id = $1 AND clicked_at >= NOW() - make_interval(mins => $2)",
+        )
+        .bind(url_id)
+        .bind(window_minutes as i32)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(clicks as f64 / window_minutes as f64)
+    }
+
+    async fn delete_old_clicks(
+        &self,
+        older_than: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        let result = sqlx::query("DELETE FROM clicks WHERE clicked_at < $1")
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn count_old_clicks(
+        &self,
+        older_than: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM clicks WHERE clicked_at < $1")
+            .bind(older_than)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_ip() {
+        assert_eq!(
+            PostgresClickRepository::valid_ip(&Some("192.168.1.1".to_string())),
+            Some("192.168.1.1".to_string())
+        );
+        assert_eq!(
+            PostgresClickRepository::valid_ip(&Some(" 2001:db8::1 ".to_string())),
+            Some("2001:db8::1".to_string())
+        );
+        assert_eq!(
+            PostgresClickRepository::valid_ip(&Some("unknown".to_string())),
+            None
+        );
+        assert_eq!(PostgresClickRepository::valid_ip(&None), None);
+    }
+
+    #[test]
+    fn test_device_type_sql_checks_bots_first() {
+        let sql = PostgresClickRepository::device_type_sql("ua");
+        assert!(sql.starts_with("CASE WHEN ua IS NULL OR ua ~* 'bot|crawl|"));
+        let bot = sql.find("'bot'").unwrap();
+        let tablet = sql.find("'tablet'").unwrap();
+        let mobile = sql.find("'mobile'").unwrap();
+        assert!(bot < tablet && tablet < mobile);
+    }
+}
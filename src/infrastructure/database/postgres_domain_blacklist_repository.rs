@@ -0,0 +1,109 @@
+use crate::domain::entities::BlockedDomain;
+use crate::domain::repositories::domain_blacklist_repository::{
+    DomainBlacklistRepository, RepositoryError,
+};
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+/// PostgreSQL implementation of the DomainBlacklistRepository trait
+#[derive(Clone)]
+pub struct PostgresDomainBlacklistRepository {
+    pool: PgPool,
+}
+
+impl PostgresDomainBlacklistRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert a database row to a BlockedDomain entity
+    fn row_to_blocked_domain(row: &sqlx::postgres::PgRow) -> BlockedDomain {
+        BlockedDomain {
+            id: row.get("id"),
+            domain: row.get("domain"),
+            reason: row.get("reason"),
+            added_by: row.get("added_by"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl DomainBlacklistRepository for PostgresDomainBlacklistRepository {
+    async fn is_blocked(&self, domain: &str) -> Result<bool, RepositoryError> {
+        // Matches the domain itself and any parent domain on the blacklist
+        let blocked: bool = sqlx::query_scalar(
+            "SELECT EXISTS (
+                 SELECT 1 FROM domain_blacklist
+                 WHERE domain = $1 OR right($1, length(domain) + 1) = '.' || domain
+             )",
+        )
+        .bind(domain)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(blocked)
+    }
+
+    async fn add_domain(
+        &self,
+        domain: &str,
+        reason: &str,
+        added_by: i32,
+    ) -> Result<BlockedDomain, RepositoryError> {
+        let row = sqlx::query(
+            "INSERT INTO domain_blacklist (domain, reason, added_by) VALUES ($1, $2, $3)
+             RETURNING id, domain, reason, added_by, created_at",
+        )
+        .bind(domain)
+        .bind(reason)
+        .bind(added_by)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                RepositoryError::DuplicateDomain
+            }
+            _ => RepositoryError::Connection(e),
+        })?;
+
+        Ok(Self::row_to_blocked_domain(&row))
+    }
+
+    async fn import_domains(&self, domains: &[(String, String)]) -> Result<u64, RepositoryError> {
+        let (names, reasons): (Vec<String>, Vec<String>) = domains.iter().cloned().unzip();
+
+        let result = sqlx::query(
+            "INSERT INTO domain_blacklist (domain, reason)
+             SELECT * FROM UNNEST($1::VARCHAR[], $2::TEXT[])
+             ON CONFLICT (domain) DO NOTHING",
+        )
+        .bind(&names)
+        .bind(&reasons)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn remove_domain(&self, id: i32) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM domain_blacklist WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_domains(&self) -> Result<Vec<BlockedDomain>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT id, domain, reason, added_by, created_at
+             FROM domain_blacklist
+             ORDER BY domain",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_blocked_domain).collect())
+    }
+}
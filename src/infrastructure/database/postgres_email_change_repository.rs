@@ -0,0 +1,157 @@
+use crate::domain::entities::{EmailChangeSide, EmailChangeToken};
+use crate::domain::repositories::EmailChangeRepository;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+/// Columns of a stored email change, in `row_to_token` order
+const EMAIL_CHANGE_COLUMNS: &str = "id, user_id, new_email, old_token_hash, new_token_hash, \
+     created_at, expires_at, old_confirmed_at, new_confirmed_at";
+
+/// PostgreSQL implementation of the EmailChangeRepository trait
+#[derive(Clone)]
+pub struct PostgresEmailChangeRepository {
+    pool: PgPool,
+}
+
+impl PostgresEmailChangeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert a database row to an EmailChangeToken entity
+    fn row_to_token(&self, row: &sqlx::postgres::PgRow) -> EmailChangeToken {
+        EmailChangeToken {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            new_email: row.get("new_email"),
+            old_token_hash: row.get("old_token_hash"),
+            new_token_hash: row.get("new_token_hash"),
+            created_at: row.get("created_at"),
+            expires_at: row.get("expires_at"),
+            old_confirmed_at: row.get("old_confirmed_at"),
+            new_confirmed_at: row.get("new_confirmed_at"),
+        }
+    }
+
+    /// Columns holding the token hash and the confirmation time of one side
+    fn side_columns(side: EmailChangeSide) -> (&'static str, &'static str) {
+        match side {
+            EmailChangeSide::OldAddress => ("old_token_hash", "old_confirmed_at"),
+            EmailChangeSide::NewAddress => ("new_token_hash", "new_confirmed_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl EmailChangeRepository for PostgresEmailChangeRepository {
+    async fn create_token(
+        &self,
+        token: EmailChangeToken,
+    ) -> Result<EmailChangeToken, Box<dyn std::error::Error + Send + Sync>> {
+        // One pending change per user: a new request replaces the previous one
+        let row = sqlx::query(&format!(
+            "INSERT INTO email_change_tokens
+                 (user_id, new_email, old_token_hash, new_token_hash, created_at, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (user_id) DO UPDATE
+             SET new_email = EXCLUDED.new_email,
+                 old_token_hash = EXCLUDED.old_token_hash,
+                 new_token_hash = EXCLUDED.new_token_hash,
+                 created_at = EXCLUDED.created_at,
+                 expires_at = EXCLUDED.expires_at,
+                 old_confirmed_at = NULL,
+                 new_confirmed_at = NULL
+             RETURNING {}",
+            EMAIL_CHANGE_COLUMNS
+        ))
+        .bind(token.user_id)
+        .bind(&token.new_email)
+        .bind(&token.old_token_hash)
+        .bind(&token.new_token_hash)
+        .bind(token.created_at)
+        .bind(token.expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(self.row_to_token(&row))
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        side: EmailChangeSide,
+        token_hash: &str,
+    ) -> Result<Option<EmailChangeToken>, Box<dyn std::error::Error + Send + Sync>> {
+        let (hash_column, _) = Self::side_columns(side);
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM email_change_tokens WHERE {} = $1",
+            EMAIL_CHANGE_COLUMNS, hash_column
+        ))
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| self.row_to_token(&row)))
+    }
+
+    async fn confirm(
+        &self,
+        id: i32,
+        side: EmailChangeSide,
+    ) -> Result<Option<EmailChangeToken>, Box<dyn std::error::Error + Send + Sync>> {
+        let (_, confirmed_column) = Self::side_columns(side);
+        let row = sqlx::query(&format!(
+            "UPDATE email_change_tokens
+             SET {column} = COALESCE({column}, CURRENT_TIMESTAMP)
+             WHERE id = $1
+             RETURNING {columns}",
+            column = confirmed_column,
+            columns = EMAIL_CHANGE_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| self.row_to_token(&row)))
+    }
+
+    async fn delete_token(&self, id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query("DELETE FROM email_change_tokens WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        // Each user has at most one change, so one user row is updated per deleted change
+        let result = sqlx::query(
+            "WITH expired AS (
+                 DELETE FROM email_change_tokens WHERE expires_at < $1 RETURNING user_id
+             )
+             UPDATE users SET pending_email = NULL
+             WHERE id IN (SELECT user_id FROM expired)",
+        )
+        .bind(expired_before)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    async fn count_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM email_change_tokens WHERE expires_at < $1")
+                .bind(expired_before)
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(count as usize)
+    }
+}
@@ -0,0 +1,168 @@
+use crate::domain::entities::OutboxEmail;
+use crate::domain::repositories::EmailOutboxRepository;
+use crate::infrastructure::email::EmailMessage;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool, Row};
+
+const OUTBOX_COLUMNS: &str = "id, recipient, subject, body_html, body_text, created_at, next_attempt_at, sent_at, failed_at, attempt_count, error";
+
+/// PostgreSQL implementation of the EmailOutboxRepository trait
+#[derive(Clone)]
+pub struct PostgresEmailOutboxRepository {
+    pool: PgPool,
+}
+
+impl PostgresEmailOutboxRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Queue an email on a connection already in a transaction
+    ///
+    /// Repositories call this with the transaction of their business change, so the email is
+    /// stored if and only if the change is committed; e.g. new unverified users, password
+    /// reset and account deletion tokens and claimed expiry digests.
+    pub async fn enqueue_in(
+        connection: &mut PgConnection,
+        message: &EmailMessage,
+    ) -> Result<OutboxEmail, sqlx::Error> {
+        let row = sqlx::query(&format!(
+            "INSERT INTO email_outbox (recipient, subject, body_html, body_text)
+             VALUES ($1, $2, $3, $4)
+             RETURNING {}",
+            OUTBOX_COLUMNS
+        ))
+        .bind(&message.to)
+        .bind(&message.subject)
+        .bind(&message.html_body)
+        .bind(&message.body)
+        .fetch_one(connection)
+        .await?;
+
+        Ok(Self::row_to_email(&row))
+    }
+
+    /// Convert a database row to an OutboxEmail entity
+    fn row_to_email(row: &sqlx::postgres::PgRow) -> OutboxEmail {
+        OutboxEmail {
+            id: row.get("id"),
+            recipient: row.get("recipient"),
+            subject: row.get("subject"),
+            body_html: row.get("body_html"),
+            body_text: row.get("body_text"),
+            created_at: row.get("created_at"),
+            next_attempt_at: row.get("next_attempt_at"),
+            sent_at: row.get("sent_at"),
+            failed_at: row.get("failed_at"),
+            attempt_count: row.get("attempt_count"),
+            error: row.get("error"),
+        }
+    }
+}
+
+#[async_trait]
+impl EmailOutboxRepository for PostgresEmailOutboxRepository {
+    async fn enqueue(
+        &self,
+        message: &EmailMessage,
+    ) -> Result<OutboxEmail, Box<dyn std::error::Error + Send + Sync>> {
+        let mut connection = self.pool.acquire().await?;
+        Ok(Self::enqueue_in(&mut connection, message).await?)
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease: chrono::Duration,
+        limit: usize,
+    ) -> Result<Vec<OutboxEmail>, Box<dyn std::error::Error + Send + Sync>> {
+        // SKIP LOCKED lets concurrent pollers take disjoint batches
+        let rows = sqlx::query(&format!(
+            "UPDATE email_outbox SET next_attempt_at = $2
+             WHERE id IN (
+                 SELECT id FROM email_outbox
+                 WHERE sent_at IS NULL AND failed_at IS NULL AND next_attempt_at <= $1
+                 ORDER BY next_attempt_at, id
+                 LIMIT $3
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING {}",
+            OUTBOX_COLUMNS
+        ))
+        .bind(now)
+        .bind(now + lease)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut emails: Vec<OutboxEmail> = rows.iter().map(Self::row_to_email).collect();
+        emails.sort_by_key(|email| (email.created_at, email.id));
+        Ok(emails)
+    }
+
+    async fn mark_sent(
+        &self,
+        id: i64,
+        sent_at: DateTime<Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query(
+            "UPDATE email_outbox SET sent_at = $2, attempt_count = attempt_count + 1, error = NULL WHERE id = $1",
+        )
+        .bind(id)
+        .bind(sent_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_attempt_failed(
+        &self,
+        id: i64,
+        error: &str,
+        failed_at: DateTime<Utc>,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query(
+            "UPDATE email_outbox
+             SET attempt_count = attempt_count + 1,
+                 error = $2,
+                 next_attempt_at = COALESCE($3, next_attempt_at),
+                 failed_at = CASE WHEN $3::timestamptz IS NULL THEN $4 END
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(error)
+        .bind(retry_at)
+        .bind(failed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_sent_before(
+        &self,
+        sent_before: DateTime<Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let result = sqlx::query("DELETE FROM email_outbox WHERE sent_at < $1")
+            .bind(sent_before)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    async fn count_sent_before(
+        &self,
+        sent_before: DateTime<Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM email_outbox WHERE sent_at < $1")
+            .bind(sent_before)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count as usize)
+    }
+}
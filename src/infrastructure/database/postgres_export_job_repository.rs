@@ -0,0 +1,157 @@
+use crate::application::dto::requests::ExportFormat;
+use crate::domain::entities::{DateRange, ExportJob, ExportJobStatus};
+use crate::domain::repositories::{ExportJobRepository, RepositoryError};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+
+/// Columns of a stored export job, in `row_to_job` order
+const EXPORT_JOB_COLUMNS: &str = "id, user_id, format, start_date, end_date, status, object_key, \
+     click_count, error, created_at, completed_at";
+
+/// PostgreSQL implementation of the ExportJobRepository trait
+#[derive(Clone)]
+pub struct PostgresExportJobRepository {
+    pool: PgPool,
+}
+
+impl PostgresExportJobRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert a database row to an ExportJob entity
+    fn row_to_job(row: &sqlx::postgres::PgRow) -> Result<ExportJob, RepositoryError> {
+        let format: String = row.get("format");
+        let status: String = row.get("status");
+        let click_count: i64 = row.get("click_count");
+        Ok(ExportJob {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            format: ExportFormat::parse(&format).ok_or_else(|| {
+                RepositoryError::InvalidData(format!("Unknown export format {}", format))
+            })?,
+            date_range: DateRange {
+                start: row.get("start_date"),
+                end: row.get("end_date"),
+            },
+            status: ExportJobStatus::parse(&status).ok_or_else(|| {
+                RepositoryError::InvalidData(format!("Unknown export status {}", status))
+            })?,
+            object_key: row.get("object_key"),
+            click_count: click_count as u64,
+            error: row.get("error"),
+            created_at: row.get("created_at"),
+            completed_at: row.get("completed_at"),
+        })
+    }
+}
+
+#[async_trait]
+impl ExportJobRepository for PostgresExportJobRepository {
+    async fn create(&self, job: &ExportJob) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO analytics_export_jobs
+                 (id, user_id, format, start_date, end_date, status, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             ON CONFLICT (id) DO NOTHING",
+        )
+        .bind(&job.id)
+        .bind(job.user_id)
+        .bind(job.format.extension())
+        .bind(job.date_range.start)
+        .bind(job.date_range.end)
+        .bind(job.status.as_str())
+        .bind(job.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn save(&self, job: &ExportJob) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO analytics_export_jobs
+                 (id, user_id, format, start_date, end_date, status, object_key, click_count,
+                  error, created_at, completed_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             ON CONFLICT (id) DO UPDATE
+             SET status = EXCLUDED.status,
+                 object_key = EXCLUDED.object_key,
+                 click_count = EXCLUDED.click_count,
+                 error = EXCLUDED.error,
+                 completed_at = EXCLUDED.completed_at",
+        )
+        .bind(&job.id)
+        .bind(job.user_id)
+        .bind(job.format.extension())
+        .bind(job.date_range.start)
+        .bind(job.date_range.end)
+        .bind(job.status.as_str())
+        .bind(&job.object_key)
+        .bind(job.click_count as i64)
+        .bind(&job.error)
+        .bind(job.created_at)
+        .bind(job.completed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<ExportJob>, RepositoryError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM analytics_export_jobs WHERE id = $1",
+            EXPORT_JOB_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_job).transpose()
+    }
+
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<ExportJob>, RepositoryError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM analytics_export_jobs WHERE user_id = $1 ORDER BY created_at DESC",
+            EXPORT_JOB_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_job).collect()
+    }
+
+    async fn find_expired(&self, cutoff: DateTime<Utc>) -> Result<Vec<ExportJob>, RepositoryError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM analytics_export_jobs WHERE COALESCE(completed_at, created_at) < $1",
+            EXPORT_JOB_COLUMNS
+        ))
+        .bind(cutoff)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_job).collect()
+    }
+
+    async fn count_expired(&self, cutoff: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM analytics_export_jobs WHERE COALESCE(completed_at, created_at) < $1",
+        )
+        .bind(cutoff)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count as u64)
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM analytics_export_jobs WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
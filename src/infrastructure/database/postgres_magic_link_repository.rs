@@ -0,0 +1,102 @@
+use crate::domain::entities::MagicLinkToken;
+use crate::domain::repositories::MagicLinkRepository;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+/// PostgreSQL implementation of the MagicLinkRepository trait
+#[derive(Clone)]
+pub struct PostgresMagicLinkRepository {
+    pool: PgPool,
+}
+
+impl PostgresMagicLinkRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert a database row to a MagicLinkToken entity
+    fn row_to_token(&self, row: &sqlx::postgres::PgRow) -> MagicLinkToken {
+        MagicLinkToken {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            token_hash: row.get("token_hash"),
+            created_at: row.get("created_at"),
+            expires_at: row.get("expires_at"),
+            used: row.get("used"),
+        }
+    }
+}
+
+#[async_trait]
+impl MagicLinkRepository for PostgresMagicLinkRepository {
+    async fn create_token(
+        &self,
+        token: MagicLinkToken,
+    ) -> Result<MagicLinkToken, Box<dyn std::error::Error + Send + Sync>> {
+        let row = sqlx::query(
+            "INSERT INTO magic_link_tokens (user_id, token_hash, created_at, expires_at, used)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id, user_id, token_hash, created_at, expires_at, used",
+        )
+        .bind(token.user_id)
+        .bind(&token.token_hash)
+        .bind(token.created_at)
+        .bind(token.expires_at)
+        .bind(token.used)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(self.row_to_token(&row))
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<MagicLinkToken>, Box<dyn std::error::Error + Send + Sync>> {
+        let row = sqlx::query(
+            "SELECT id, user_id, token_hash, created_at, expires_at, used
+             FROM magic_link_tokens
+             WHERE token_hash = $1",
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| self.row_to_token(&row)))
+    }
+
+    async fn mark_used(&self, id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let result =
+            sqlx::query("UPDATE magic_link_tokens SET used = true WHERE id = $1 AND used = false")
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn delete_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let result = sqlx::query("DELETE FROM magic_link_tokens WHERE expires_at < $1")
+            .bind(expired_before)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    async fn count_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM magic_link_tokens WHERE expires_at < $1")
+                .bind(expired_before)
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(count as usize)
+    }
+}
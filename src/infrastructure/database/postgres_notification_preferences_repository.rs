@@ -0,0 +1,149 @@
+use crate::domain::entities::NotificationPreferences;
+use crate::domain::repositories::notification_preferences_repository::{
+    DigestRecipient, NotificationPreferencesRepository, RepositoryError,
+};
+use crate::infrastructure::database::PostgresEmailOutboxRepository;
+use crate::infrastructure::email::EmailMessage;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, PgPool, Row};
+
+/// PostgreSQL implementation of the NotificationPreferencesRepository trait
+#[derive(Clone)]
+pub struct PostgresNotificationPreferencesRepository {
+    pool: PgPool,
+}
+
+impl PostgresNotificationPreferencesRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_preferences(row: &PgRow) -> NotificationPreferences {
+    NotificationPreferences {
+        user_id: row.get("user_id"),
+        digest_enabled: row.get("digest_enabled"),
+        digest_day_of_week: row.get::<i16, _>("digest_day_of_week") as u8,
+        last_digest_sent_at: row.get("last_digest_sent_at"),
+        push_enabled: row.get("push_enabled"),
+    }
+}
+
+#[async_trait]
+impl NotificationPreferencesRepository for PostgresNotificationPreferencesRepository {
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Result<NotificationPreferences, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT user_id, digest_enabled, digest_day_of_week, last_digest_sent_at, push_enabled
+             FROM notification_preferences WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row
+            .map(|row| row_to_preferences(&row))
+            .unwrap_or_else(|| NotificationPreferences::new(user_id)))
+    }
+
+    async fn save(
+        &self,
+        preferences: &NotificationPreferences,
+    ) -> Result<NotificationPreferences, RepositoryError> {
+        let row = sqlx::query(
+            "INSERT INTO notification_preferences (user_id, digest_enabled, digest_day_of_week, push_enabled)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (user_id) DO UPDATE SET
+                 digest_enabled = EXCLUDED.digest_enabled,
+                 digest_day_of_week = EXCLUDED.digest_day_of_week,
+                 push_enabled = EXCLUDED.push_enabled,
+                 updated_at = NOW()
+             RETURNING user_id, digest_enabled, digest_day_of_week, last_digest_sent_at, push_enabled",
+        )
+        .bind(preferences.user_id)
+        .bind(preferences.digest_enabled)
+        .bind(i16::from(preferences.digest_day_of_week))
+        .bind(preferences.push_enabled)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row_to_preferences(&row))
+    }
+
+    async fn find_digest_recipients(
+        &self,
+        user_ids: &[i32],
+    ) -> Result<Vec<DigestRecipient>, RepositoryError> {
+        let defaults = NotificationPreferences::new(0);
+        let rows = sqlx::query(
+            "SELECT u.id AS user_id, u.username, u.email,
+                    COALESCE(p.digest_enabled, $2) AS digest_enabled,
+                    COALESCE(p.digest_day_of_week, $3) AS digest_day_of_week,
+                    p.last_digest_sent_at,
+                    COALESCE(p.push_enabled, $4) AS push_enabled
+             FROM users u
+             LEFT JOIN notification_preferences p ON p.user_id = u.id
+             WHERE u.id = ANY($1)
+             ORDER BY u.id",
+        )
+        .bind(user_ids)
+        .bind(defaults.digest_enabled)
+        .bind(i16::from(defaults.digest_day_of_week))
+        .bind(defaults.push_enabled)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| DigestRecipient {
+                username: row.get("username"),
+                email: row.get("email"),
+                preferences: row_to_preferences(row),
+            })
+            .collect())
+    }
+
+    async fn find_device_tokens(&self, user_id: i32) -> Result<Vec<String>, RepositoryError> {
+        let tokens: Option<Vec<String>> =
+            sqlx::query_scalar("SELECT device_tokens FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(tokens.unwrap_or_default())
+    }
+
+    async fn claim_digest(
+        &self,
+        user_id: i32,
+        sent_at: DateTime<Utc>,
+        sent_after: DateTime<Utc>,
+        digest: &EmailMessage,
+    ) -> Result<bool, RepositoryError> {
+        let mut tx = self.pool.begin().await?;
+
+        let claimed = sqlx::query(
+            "INSERT INTO notification_preferences (user_id, last_digest_sent_at)
+             VALUES ($1, $2)
+             ON CONFLICT (user_id) DO UPDATE SET last_digest_sent_at = EXCLUDED.last_digest_sent_at
+             WHERE notification_preferences.last_digest_sent_at IS NULL
+                OR notification_preferences.last_digest_sent_at <= $3
+             RETURNING user_id",
+        )
+        .bind(user_id)
+        .bind(sent_at)
+        .bind(sent_after)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if claimed.is_none() {
+            return Ok(false);
+        }
+
+        PostgresEmailOutboxRepository::enqueue_in(&mut tx, digest).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,234 @@
+use crate::domain::entities::{
+    OrgRole, Organization, OrganizationMember, OrganizationWithMemberCount,
+};
+use crate::domain::repositories::organization_repository::{
+    OrganizationRepository, RepositoryError,
+};
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+/// PostgreSQL implementation of the OrganizationRepository trait
+#[derive(Clone)]
+pub struct PostgresOrganizationRepository {
+    pool: PgPool,
+}
+
+impl PostgresOrganizationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert a database row to an Organization entity
+    fn row_to_organization(row: &sqlx::postgres::PgRow) -> Organization {
+        Organization {
+            id: row.get("id"),
+            name: row.get("name"),
+            slug: row.get("slug"),
+            owner_id: row.get("owner_id"),
+            created_at: row.get("created_at"),
+        }
+    }
+
+    /// Convert a database row to an OrganizationMember entity
+    fn row_to_member(row: &sqlx::postgres::PgRow) -> OrganizationMember {
+        let role: String = row.get("role");
+        OrganizationMember {
+            org_id: row.get("org_id"),
+            user_id: row.get("user_id"),
+            role: OrgRole::parse(&role).unwrap_or(OrgRole::Member),
+            joined_at: row.get("joined_at"),
+        }
+    }
+
+    /// Map a unique violation to the given error, passing other errors through
+    fn map_unique_violation(error: sqlx::Error, duplicate: RepositoryError) -> RepositoryError {
+        match &error {
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => duplicate,
+            _ => RepositoryError::Connection(error),
+        }
+    }
+}
+
+#[async_trait]
+impl OrganizationRepository for PostgresOrganizationRepository {
+    async fn create_organization(
+        &self,
+        name: &str,
+        slug: &str,
+        owner_id: i32,
+    ) -> Result<Organization, RepositoryError> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query(
+            "INSERT INTO organizations (name, slug, owner_id) VALUES ($1, $2, $3)
+             RETURNING id, name, slug, owner_id, created_at",
+        )
+        .bind(name)
+        .bind(slug)
+        .bind(owner_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| Self::map_unique_violation(e, RepositoryError::DuplicateSlug))?;
+        let organization = Self::row_to_organization(&row);
+
+        sqlx::query("INSERT INTO organization_members (org_id, user_id, role) VALUES ($1, $2, $3)")
+            .bind(organization.id)
+            .bind(owner_id)
+            .bind(OrgRole::Owner.as_str())
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(organization)
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Organization>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT id, name, slug, owner_id, created_at FROM organizations WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(Self::row_to_organization))
+    }
+
+    async fn find_by_member(&self, user_id: i32) -> Result<Vec<Organization>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT o.id, o.name, o.slug, o.owner_id, o.created_at
+             FROM organizations o
+             JOIN organization_members m ON m.org_id = o.id
+             WHERE m.user_id = $1
+             ORDER BY o.name",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_organization).collect())
+    }
+
+    async fn update_organization(
+        &self,
+        id: i32,
+        name: &str,
+        slug: &str,
+    ) -> Result<Organization, RepositoryError> {
+        let row = sqlx::query(
+            "UPDATE organizations SET name = $1, slug = $2 WHERE id = $3
+             RETURNING id, name, slug, owner_id, created_at",
+        )
+        .bind(name)
+        .bind(slug)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Self::map_unique_violation(e, RepositoryError::DuplicateSlug))?;
+
+        row.as_ref()
+            .map(Self::row_to_organization)
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn delete_organization(&self, id: i32) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM organizations WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_member(
+        &self,
+        org_id: i32,
+        user_id: i32,
+    ) -> Result<Option<OrganizationMember>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT org_id, user_id, role, joined_at FROM organization_members
+             WHERE org_id = $1 AND user_id = $2",
+        )
+        .bind(org_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(Self::row_to_member))
+    }
+
+    async fn list_members(&self, org_id: i32) -> Result<Vec<OrganizationMember>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT org_id, user_id, role, joined_at FROM organization_members
+             WHERE org_id = $1
+             ORDER BY joined_at",
+        )
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_member).collect())
+    }
+
+    async fn add_member(
+        &self,
+        org_id: i32,
+        user_id: i32,
+        role: OrgRole,
+    ) -> Result<OrganizationMember, RepositoryError> {
+        let row = sqlx::query(
+            "INSERT INTO organization_members (org_id, user_id, role) VALUES ($1, $2, $3)
+             RETURNING org_id, user_id, role, joined_at",
+        )
+        .bind(org_id)
+        .bind(user_id)
+        .bind(role.as_str())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Self::map_unique_violation(e, RepositoryError::AlreadyMember))?;
+
+        Ok(Self::row_to_member(&row))
+    }
+
+    async fn remove_member(&self, org_id: i32, user_id: i32) -> Result<bool, RepositoryError> {
+        let result =
+            sqlx::query("DELETE FROM organization_members WHERE org_id = $1 AND user_id = $2")
+                .bind(org_id)
+                .bind(user_id)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn count_urls(&self, org_id: i32) -> Result<i64, RepositoryError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM urls WHERE organization_id = $1")
+            .bind(org_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn list_with_member_counts(
+        &self,
+    ) -> Result<Vec<OrganizationWithMemberCount>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT o.id, o.name, o.slug, o.owner_id, o.created_at, COUNT(m.user_id) AS member_count
+             FROM organizations o
+             LEFT JOIN organization_members m ON m.org_id = o.id
+             GROUP BY o.id
+             ORDER BY o.id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| OrganizationWithMemberCount {
+                organization: Self::row_to_organization(row),
+                member_count: row.get("member_count"),
+            })
+            .collect())
+    }
+}
@@ -1,5 +1,7 @@
 use crate::domain::entities::PasswordResetToken;
 use crate::domain::repositories::password_reset_repository::PasswordResetRepository;
+use crate::infrastructure::database::PostgresEmailOutboxRepository;
+use crate::infrastructure::email::EmailMessage;
 use async_trait::async_trait;
 use sqlx::{PgPool, Row};
 
@@ -33,7 +35,20 @@ impl PasswordResetRepository for PostgresPasswordResetRepository {
     async fn create_token(
         &self,
         token: PasswordResetToken,
+        email: Option<&EmailMessage>,
     ) -> Result<PasswordResetToken, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tx = self.pool.begin().await?;
+
+        // idx_password_reset_tokens_one_active allows a single pending token per user
+        sqlx::query(
+            "UPDATE password_reset_tokens 
+             SET is_used = true, used_at = NOW() 
+             WHERE user_id = $1 AND is_used = false",
+        )
+        .bind(token.user_id)
+        .execute(&mut *tx)
+        .await?;
+
         let row = sqlx::query(
             "INSERT INTO password_reset_tokens (user_id, token, created_at, expires_at, is_used) 
              VALUES ($1, $2, $3, $4, $5) 
@@ -44,9 +59,13 @@ impl PasswordResetRepository for PostgresPasswordResetRepository {
         .bind(token.created_at)
         .bind(token.expires_at)
         .bind(token.is_used)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await?;
 
+        if let Some(email) = email {
+            PostgresEmailOutboxRepository::enqueue_in(&mut tx, email).await?;
+        }
+        tx.commit().await?;
         Ok(self.row_to_token(&row))
     }
 
@@ -122,20 +141,70 @@ impl PasswordResetRepository for PostgresPasswordResetRepository {
         Ok(self.row_to_token(&row))
     }
 
+    async fn find_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<PasswordResetToken>, Box<dyn std::error::Error + Send + Sync>> {
+        let rows = sqlx::query(
+            "SELECT id, user_id, token, created_at, expires_at, used_at, is_used 
+             FROM password_reset_tokens 
+             WHERE expires_at < $1 
+             ORDER BY expires_at",
+        )
+        .bind(expired_before)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|row| self.row_to_token(row)).collect())
+    }
+
     async fn delete_expired_tokens(
         &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
     ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
         let result = sqlx::query(
             "DELETE FROM password_reset_tokens 
-             WHERE expires_at < NOW()",
+             WHERE expires_at < $1",
         )
+        .bind(expired_before)
         .execute(&self.pool)
         .await?;
 
         Ok(result.rows_affected() as usize)
     }
 
-    async fn revoke_all_tokens_for_user(
+    async fn count_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM password_reset_tokens
+             WHERE expires_at < $1",
+        )
+        .bind(expired_before)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count as usize)
+    }
+
+    async fn mark_token_used(
+        &self,
+        token_id: i32,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let result = sqlx::query(
+            "UPDATE password_reset_tokens 
+             SET is_used = true, used_at = NOW() 
+             WHERE id = $1 AND is_used = false",
+        )
+        .bind(token_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn invalidate_all_tokens_for_user(
         &self,
         user_id: i32,
     ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
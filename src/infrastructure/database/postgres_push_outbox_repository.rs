@@ -0,0 +1,137 @@
+use crate::domain::entities::OutboxPush;
+use crate::domain::repositories::PushOutboxRepository;
+use crate::infrastructure::push::PushMessage;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+
+const OUTBOX_COLUMNS: &str = "id, user_id, device_token, title, body, deep_link, dedup_key, created_at, next_attempt_at, sent_at, failed_at, attempt_count, error";
+
+/// PostgreSQL implementation of the PushOutboxRepository trait
+#[derive(Clone)]
+pub struct PostgresPushOutboxRepository {
+    pool: PgPool,
+}
+
+impl PostgresPushOutboxRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert a database row to an OutboxPush entity
+    fn row_to_push(row: &sqlx::postgres::PgRow) -> OutboxPush {
+        OutboxPush {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            device_token: row.get("device_token"),
+            title: row.get("title"),
+            body: row.get("body"),
+            deep_link: row.get("deep_link"),
+            dedup_key: row.get("dedup_key"),
+            created_at: row.get("created_at"),
+            next_attempt_at: row.get("next_attempt_at"),
+            sent_at: row.get("sent_at"),
+            failed_at: row.get("failed_at"),
+            attempt_count: row.get("attempt_count"),
+            error: row.get("error"),
+        }
+    }
+}
+
+#[async_trait]
+impl PushOutboxRepository for PostgresPushOutboxRepository {
+    async fn enqueue(
+        &self,
+        message: &PushMessage,
+    ) -> Result<Option<OutboxPush>, Box<dyn std::error::Error + Send + Sync>> {
+        // The partial unique index on (device_token, dedup_key) drops duplicates
+        let row = sqlx::query(&format!(
+            "INSERT INTO push_outbox (user_id, device_token, title, body, deep_link, dedup_key)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (device_token, dedup_key) WHERE dedup_key IS NOT NULL DO NOTHING
+             RETURNING {}",
+            OUTBOX_COLUMNS
+        ))
+        .bind(message.user_id)
+        .bind(&message.device_token)
+        .bind(&message.title)
+        .bind(&message.body)
+        .bind(&message.deep_link)
+        .bind(&message.dedup_key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(Self::row_to_push))
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease: chrono::Duration,
+        limit: usize,
+    ) -> Result<Vec<OutboxPush>, Box<dyn std::error::Error + Send + Sync>> {
+        // SKIP LOCKED lets concurrent pollers take disjoint batches
+        let rows = sqlx::query(&format!(
+            "UPDATE push_outbox SET next_attempt_at = $2
+             WHERE id IN (
+                 SELECT id FROM push_outbox
+                 WHERE sent_at IS NULL AND failed_at IS NULL AND next_attempt_at <= $1
+                 ORDER BY next_attempt_at, id
+                 LIMIT $3
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING {}",
+            OUTBOX_COLUMNS
+        ))
+        .bind(now)
+        .bind(now + lease)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut pushes: Vec<OutboxPush> = rows.iter().map(Self::row_to_push).collect();
+        pushes.sort_by_key(|push| (push.created_at, push.id));
+        Ok(pushes)
+    }
+
+    async fn mark_sent(
+        &self,
+        id: i64,
+        sent_at: DateTime<Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query(
+            "UPDATE push_outbox SET sent_at = $2, attempt_count = attempt_count + 1, error = NULL WHERE id = $1",
+        )
+        .bind(id)
+        .bind(sent_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_attempt_failed(
+        &self,
+        id: i64,
+        error: &str,
+        failed_at: DateTime<Utc>,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query(
+            "UPDATE push_outbox
+             SET attempt_count = attempt_count + 1,
+                 error = $2,
+                 next_attempt_at = COALESCE($3, next_attempt_at),
+                 failed_at = CASE WHEN $3::timestamptz IS NULL THEN $4 END
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(error)
+        .bind(retry_at)
+        .bind(failed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
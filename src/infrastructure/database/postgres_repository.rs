@@ -1,30 +1,87 @@
-use crate::domain::entities::{ShortCode, Url, UrlStatus};
-use crate::domain::repositories::{RepositoryError, UrlRepository, UrlStats};
+use super::hll_support::HllSupport;
+use super::query_builders::UrlQueryBuilder;
+use super::replica_aware_pool::ReplicaLag;
+use crate::domain::entities::{
+    PreviewMode, RedirectType, ShortCode, Url, UrlStatus, UrlWithClickCount,
+};
+use crate::domain::repositories::{
+    RepositoryError, SortDirection, UrlCursor, UrlFilter, UrlPage, UrlRepository, UrlSortField,
+    UrlStats,
+};
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{Executor, PgPool, Postgres, Row, Transaction};
+use std::collections::HashMap;
+use tokio_stream::StreamExt;
+use tracing::debug;
+
+/// Advisory lock on one short code, released when the guard is committed or dropped
+///
+/// The lock is transaction-scoped (`pg_try_advisory_xact_lock`), so ending the transaction
+/// releases it: [`ShortCodeLock::commit`] commits the work done under it and dropping the
+/// guard rolls that work back. Short codes are locked by `hashtext`, so two codes sharing a
+/// hash also share a lock.
+pub struct ShortCodeLock {
+    tx: Transaction<'static, Postgres>,
+}
+
+impl ShortCodeLock {
+    /// Commit the statements run under the lock, then release it
+    pub async fn commit(self) -> Result<(), RepositoryError> {
+        self.tx.commit().await?;
+        Ok(())
+    }
+}
 
 /// PostgreSQL implementation of the UrlRepository trait
 #[derive(Clone)]
 pub struct PostgresUrlRepository {
     pool: PgPool,
+    hll: HllSupport,
+    /// Statement timeout of quick lookups, in milliseconds
+    short_query_timeout_ms: u64,
+    /// Statement timeout of aggregations and bulk deletes, in milliseconds
+    long_query_timeout_ms: u64,
 }
 
 impl PostgresUrlRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            hll: HllSupport::new(),
+            short_query_timeout_ms: 1000,
+            long_query_timeout_ms: 10000,
+        }
+    }
+
+    /// Set the statement timeouts of quick lookups and of long-running queries
+    pub fn with_query_timeouts(mut self, short_ms: u64, long_ms: u64) -> Self {
+        self.short_query_timeout_ms = short_ms;
+        self.long_query_timeout_ms = long_ms;
+        self
+    }
+
+    /// Begin a transaction whose statements are cancelled after `timeout_ms`
+    ///
+    /// `SET LOCAL` keeps the timeout from leaking to the pooled connection once the
+    /// transaction ends. Cancelled statements surface as [`RepositoryError::QueryTimeout`].
+    async fn begin_with_timeout(
+        &self,
+        timeout_ms: u64,
+    ) -> Result<Transaction<'static, Postgres>, RepositoryError> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(&format!("SET LOCAL statement_timeout = '{}ms'", timeout_ms))
+            .execute(&mut *tx)
+            .await?;
+        Ok(tx)
     }
 
     /// Helper function to convert string status to UrlStatus
     fn status_from_string(status: String) -> UrlStatus {
-        match status.as_str() {
-            "active" => UrlStatus::Active,
-            "inactive" => UrlStatus::Inactive,
-            _ => UrlStatus::Active, // Default fallback
-        }
+        UrlStatus::parse(&status).unwrap_or(UrlStatus::Active) // Default fallback
     }
 
     /// Helper function to create Url from database row
-    fn url_from_row(row: &sqlx::postgres::PgRow) -> Url {
+    pub(crate) fn url_from_row(row: &sqlx::postgres::PgRow) -> Url {
         Url {
             id: row.get("id"),
             short_code: row.get("short_code"),
@@ -33,44 +90,219 @@ impl PostgresUrlRepository {
             expiration_date: row.get("expiration_date"),
             user_id: row.get("user_id"),
             status: Self::status_from_string(row.get("status")),
+            organization_id: row.get("organization_id"),
+            version: row.get("version"),
+            updated_at: row.get("updated_at"),
+            preview_mode: PreviewMode::parse(row.get("preview_mode")).unwrap_or_default(),
+            deduplicated_click_count: row.get("deduplicated_click_count"),
+            deleted_at: row.get("deleted_at"),
+            // Only selected by queries loading tags
+            tags: row.try_get("tags").unwrap_or_default(),
+            title: row.get("title"),
+            redirect_type: RedirectType::parse(row.get("redirect_type")).unwrap_or_default(),
+            password_hash: row.get("password_hash"),
+            max_clicks: row.get("max_clicks"),
         }
     }
-}
 
-#[async_trait]
-impl UrlRepository for PostgresUrlRepository {
-    async fn create_url(
+    /// Query listing the live URLs whose `owner_column` is `$1`, newest first, with their tags
+    ///
+    /// Tags are joined and aggregated per URL in the same query rather than fetched for each
+    /// URL.
+    fn find_by_owner_query(owner_column: &str) -> String {
+        format!(
+            "SELECT u.id, u.short_code, u.original_url, u.created_at, u.expiration_date, u.user_id, u.status, u.organization_id, u.version, u.updated_at, u.preview_mode, u.deduplicated_click_count, u.deleted_at, u.title, u.redirect_type, u.password_hash, u.max_clicks,
+                    COALESCE(array_agg(t.name ORDER BY t.name) FILTER (WHERE t.name IS NOT NULL), '{{}}') AS tags
+             FROM urls u
+             LEFT JOIN url_tags ut ON ut.url_id = u.id
+             LEFT JOIN tags t ON t.id = ut.tag_id
+             WHERE u.{} = $1 AND u.deleted_at IS NULL
+             GROUP BY u.id
+             ORDER BY u.created_at DESC",
+            owner_column
+        )
+    }
+
+    /// Lock `short_code` for the transaction of the returned guard
+    ///
+    /// Fails with [`RepositoryError::DuplicateShortCode`] without waiting when another
+    /// request holds the lock, as that request is creating a URL with the same short code.
+    pub async fn acquire_short_code_lock(
         &self,
+        short_code: &str,
+    ) -> Result<ShortCodeLock, RepositoryError> {
+        let mut tx = self.pool.begin().await?;
+        let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_xact_lock(hashtext($1))")
+            .bind(short_code)
+            .fetch_one(&mut *tx)
+            .await?;
+        if !locked {
+            return Err(RepositoryError::DuplicateShortCode);
+        }
+        Ok(ShortCodeLock { tx })
+    }
+
+    /// Insert a URL, returning `None` instead of failing when the short code is taken
+    ///
+    /// `ON CONFLICT DO NOTHING` lets concurrent inserts of the same short code settle in the
+    /// database instead of racing on an earlier existence check.
+    async fn insert_url<'e>(
+        executor: impl Executor<'e, Database = Postgres>,
         short_code: &ShortCode,
         original_url: &str,
         expiration_date: Option<chrono::DateTime<chrono::Utc>>,
         user_id: Option<i32>,
+        organization_id: Option<i32>,
         status: UrlStatus,
-    ) -> Result<Url, RepositoryError> {
+    ) -> Result<Option<Url>, RepositoryError> {
         let row = sqlx::query(
-            "INSERT INTO urls (short_code, original_url, expiration_date, user_id, status) VALUES ($1, $2, $3, $4, $5) RETURNING id, short_code, original_url, created_at, expiration_date, user_id, status"
+            "INSERT INTO urls (short_code, original_url, expiration_date, user_id, organization_id, status) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (short_code) WHERE deleted_at IS NULL DO NOTHING RETURNING id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks"
         )
         .bind(short_code.value())
         .bind(original_url)
         .bind(expiration_date)
         .bind(user_id)
+        .bind(organization_id)
         .bind(status.to_string())
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(row.as_ref().map(Self::url_from_row))
+    }
+}
+
+#[async_trait]
+impl ReplicaLag for PostgresUrlRepository {
+    async fn replica_lag_ms(&self) -> Result<Option<f64>, RepositoryError> {
+        let lag_ms = sqlx::query_scalar(
+            "SELECT (EXTRACT(epoch FROM now() - pg_last_xact_replay_timestamp()) * 1000)::float8",
+        )
         .fetch_one(&self.pool)
         .await?;
+        Ok(lag_ms)
+    }
+}
 
-        Ok(Self::url_from_row(&row))
+#[async_trait]
+impl UrlRepository for PostgresUrlRepository {
+    async fn create_url(
+        &self,
+        short_code: &ShortCode,
+        original_url: &str,
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Option<i32>,
+        organization_id: Option<i32>,
+        status: UrlStatus,
+    ) -> Result<Url, RepositoryError> {
+        // Checking and inserting under the short code's advisory lock turns concurrent
+        // requests for the same code away before they insert. ON CONFLICT alone would also
+        // settle the race, but only after each loser's insert round-trip; it stays as the
+        // backstop for writers that do not take the lock.
+        let mut lock = self.acquire_short_code_lock(short_code.value()).await?;
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM urls WHERE short_code = $1 AND deleted_at IS NULL) OR EXISTS (SELECT 1 FROM short_code_aliases WHERE short_code = $1)",
+        )
+        .bind(short_code.value())
+        .fetch_one(&mut *lock.tx)
+        .await?;
+        if exists {
+            return Err(RepositoryError::DuplicateShortCode);
+        }
+
+        let url = Self::insert_url(
+            &mut *lock.tx,
+            short_code,
+            original_url,
+            expiration_date,
+            user_id,
+            organization_id,
+            status,
+        )
+        .await?
+        .ok_or(RepositoryError::DuplicateShortCode)?;
+        lock.commit().await?;
+        Ok(url)
+    }
+
+    async fn create_url_idempotent(
+        &self,
+        short_code: &ShortCode,
+        original_url: &str,
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Option<i32>,
+        organization_id: Option<i32>,
+        max_organization_urls: Option<i64>,
+        status: UrlStatus,
+    ) -> Result<(Url, bool), RepositoryError> {
+        let inserted = match organization_id.zip(max_organization_urls) {
+            Some((org_id, limit)) => {
+                // Creations for the same organization queue on its row, so each one counts
+                // the URLs the ones before it inserted
+                let mut tx = self.pool.begin().await?;
+                sqlx::query("SELECT id FROM organizations WHERE id = $1 FOR UPDATE")
+                    .bind(org_id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+                let owned: i64 =
+                    sqlx::query_scalar("SELECT COUNT(*) FROM urls WHERE organization_id = $1")
+                        .bind(org_id)
+                        .fetch_one(&mut *tx)
+                        .await?;
+                if owned >= limit {
+                    return Err(RepositoryError::OrganizationQuotaExceeded { limit });
+                }
+                let inserted = Self::insert_url(
+                    &mut *tx,
+                    short_code,
+                    original_url,
+                    expiration_date,
+                    user_id,
+                    organization_id,
+                    status,
+                )
+                .await?;
+                tx.commit().await?;
+                inserted
+            }
+            None => {
+                Self::insert_url(
+                    &self.pool,
+                    short_code,
+                    original_url,
+                    expiration_date,
+                    user_id,
+                    organization_id,
+                    status,
+                )
+                .await?
+            }
+        };
+        match inserted {
+            Some(url) => Ok((url, true)),
+            // The row that won the conflict; it may have been deleted again since
+            None => self
+                .find_by_short_code(short_code, true)
+                .await?
+                .map(|url| (url, false))
+                .ok_or(RepositoryError::DuplicateShortCode),
+        }
     }
 
+    #[tracing::instrument(skip(self, _force_primary), fields(short_code = %short_code))]
     async fn find_by_short_code(
         &self,
         short_code: &ShortCode,
+        _force_primary: bool,
     ) -> Result<Option<Url>, RepositoryError> {
+        debug!("Querying URL by short code");
+        let mut tx = self.begin_with_timeout(self.short_query_timeout_ms).await?;
         let row = sqlx::query(
-            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status FROM urls WHERE short_code = $1"
+            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks FROM urls WHERE (short_code = $1 OR id = (SELECT url_id FROM short_code_aliases WHERE short_code = $1)) AND deleted_at IS NULL ORDER BY short_code = $1 DESC LIMIT 1"
         )
         .bind(short_code.value())
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *tx)
         .await?;
+        tx.commit().await?;
 
         match row {
             Some(row) => Ok(Some(Self::url_from_row(&row))),
@@ -78,14 +310,40 @@ impl UrlRepository for PostgresUrlRepository {
         }
     }
 
-    async fn find_by_user_id(&self, user_id: i32) -> Result<Vec<Url>, RepositoryError> {
-        let rows = sqlx::query(
-            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status FROM urls WHERE user_id = $1 ORDER BY created_at DESC"
+    async fn find_deleted_by_short_code(
+        &self,
+        short_code: &ShortCode,
+    ) -> Result<Option<Url>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks
+             FROM urls
+             WHERE short_code = $1 AND deleted_at IS NOT NULL
+             ORDER BY deleted_at DESC
+             LIMIT 1",
         )
-        .bind(user_id)
-        .fetch_all(&self.pool)
+        .bind(short_code.value())
+        .fetch_optional(&self.pool)
         .await?;
 
+        Ok(row.as_ref().map(Self::url_from_row))
+    }
+
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        organization_id: Option<i32>,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let (owner_column, owner_id) = match organization_id {
+            Some(org_id) => ("organization_id", org_id),
+            None => ("user_id", user_id),
+        };
+        let mut tx = self.begin_with_timeout(self.short_query_timeout_ms).await?;
+        let rows = sqlx::query(&Self::find_by_owner_query(owner_column))
+            .bind(owner_id)
+            .fetch_all(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
         let urls = rows
             .into_iter()
             .map(|row| Self::url_from_row(&row))
@@ -94,70 +352,347 @@ impl UrlRepository for PostgresUrlRepository {
         Ok(urls)
     }
 
+    async fn find_by_original_url(
+        &self,
+        original_url: &str,
+        user_id: i32,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        // url_hash narrows the lookup through its index; comparing the URL itself rules out
+        // MD5 collisions
+        let rows = sqlx::query(
+            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks 
+             FROM urls 
+             WHERE url_hash = md5($1) AND original_url = $1 AND user_id = $2 AND deleted_at IS NULL 
+             ORDER BY created_at DESC, id DESC",
+        )
+        .bind(original_url)
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::url_from_row).collect())
+    }
+
+    async fn find_all_by_original_url(
+        &self,
+        original_url: &str,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks 
+             FROM urls 
+             WHERE url_hash = md5($1) AND original_url = $1 AND deleted_at IS NULL 
+             ORDER BY created_at DESC, id DESC 
+             LIMIT $2",
+        )
+        .bind(original_url)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::url_from_row).collect())
+    }
+
     async fn exists_by_short_code(&self, short_code: &ShortCode) -> Result<bool, RepositoryError> {
-        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM urls WHERE short_code = $1")
-            .bind(short_code.value())
-            .fetch_one(&self.pool)
-            .await?;
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM urls WHERE short_code = $1 AND deleted_at IS NULL) OR EXISTS (SELECT 1 FROM short_code_aliases WHERE short_code = $1)",
+        )
+        .bind(short_code.value())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    async fn find_phonetically_similar_codes(
+        &self,
+        generated: &ShortCode,
+        threshold: f32,
+    ) -> Result<Vec<ShortCode>, RepositoryError> {
+        // Phonetic keys are computed in Rust, so every code in use is read; the check is opt-in
+        let mut rows = sqlx::query_scalar::<_, String>(
+            "SELECT short_code FROM urls WHERE deleted_at IS NULL UNION ALL SELECT short_code FROM short_code_aliases",
+        )
+        .fetch(&self.pool);
+
+        let mut similar = Vec::new();
+        while let Some(short_code) = rows.next().await {
+            let short_code = ShortCode::from_string_unchecked(short_code?);
+            if generated.phonetic_distance(&short_code) < threshold {
+                similar.push(short_code);
+            }
+        }
+        Ok(similar)
+    }
+
+    async fn replace_short_code(
+        &self,
+        url_id: i32,
+        new_short_code: &ShortCode,
+    ) -> Result<(), RepositoryError> {
+        let mut tx = self.pool.begin().await?;
+        let old_short_code: Option<String> = sqlx::query_scalar(
+            "SELECT short_code FROM urls WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+        )
+        .bind(url_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let old_short_code = old_short_code.ok_or(RepositoryError::NotFound)?;
+
+        sqlx::query(
+            "INSERT INTO short_code_aliases (short_code, url_id) VALUES ($1, $2) ON CONFLICT (short_code) DO NOTHING",
+        )
+        .bind(&old_short_code)
+        .bind(url_id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("UPDATE urls SET short_code = $1, version = version + 1 WHERE id = $2")
+            .bind(new_short_code.value())
+            .bind(url_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| match &e {
+                sqlx::Error::Database(db) if db.is_unique_violation() => {
+                    RepositoryError::DuplicateShortCode
+                }
+                _ => RepositoryError::from(e),
+            })?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn rename_short_code(
+        &self,
+        url_id: i32,
+        new_short_code: &ShortCode,
+        max_aliases: usize,
+    ) -> Result<Url, RepositoryError> {
+        let mut tx = self.pool.begin().await?;
+        let old_short_code: Option<String> = sqlx::query_scalar(
+            "SELECT short_code FROM urls WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+        )
+        .bind(url_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let old_short_code = old_short_code.ok_or(RepositoryError::NotFound)?;
+
+        // The row lock above serializes renames of the URL, so the count stays accurate
+        let alias_count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM short_code_aliases WHERE url_id = $1")
+                .bind(url_id)
+                .fetch_one(&mut *tx)
+                .await?;
+        if alias_count >= max_aliases as i64 {
+            return Err(RepositoryError::AliasLimitReached { limit: max_aliases });
+        }
+        let taken_by_alias: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM short_code_aliases WHERE short_code = $1)",
+        )
+        .bind(new_short_code.value())
+        .fetch_one(&mut *tx)
+        .await?;
+        if taken_by_alias {
+            return Err(RepositoryError::DuplicateShortCode);
+        }
+
+        sqlx::query(
+            "INSERT INTO short_code_aliases (short_code, url_id, is_legacy) VALUES ($1, $2, true)",
+        )
+        .bind(&old_short_code)
+        .bind(url_id)
+        .execute(&mut *tx)
+        .await?;
+        let row = sqlx::query(
+            "UPDATE urls SET short_code = $1, version = version + 1 WHERE id = $2 RETURNING id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks",
+        )
+        .bind(new_short_code.value())
+        .bind(url_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                RepositoryError::DuplicateShortCode
+            }
+            _ => RepositoryError::from(e),
+        })?;
 
-        Ok(count > 0)
+        tx.commit().await?;
+        Ok(Self::url_from_row(&row))
     }
 
     async fn delete_by_id(&self, id: i32, user_id: Option<i32>) -> Result<bool, RepositoryError> {
         let query = if let Some(uid) = user_id {
-            sqlx::query("DELETE FROM urls WHERE id = $1 AND user_id = $2")
+            sqlx::query("UPDATE urls SET status = 'deleted', deleted_at = now(), version = version + 1 WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL")
                 .bind(id)
                 .bind(uid)
         } else {
-            sqlx::query("DELETE FROM urls WHERE id = $1 AND user_id IS NULL").bind(id)
+            sqlx::query("UPDATE urls SET status = 'deleted', deleted_at = now(), version = version + 1 WHERE id = $1 AND user_id IS NULL AND deleted_at IS NULL")
+                .bind(id)
         };
 
         let result = query.execute(&self.pool).await?;
         Ok(result.rows_affected() > 0)
     }
 
-    async fn update_url(&self, url: &Url) -> Result<Url, RepositoryError> {
+    async fn permanently_delete_by_id(&self, id: i32) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM urls WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_deleted_before(
+        &self,
+        deleted_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks
+             FROM urls
+             WHERE deleted_at < $1
+             ORDER BY deleted_at ASC",
+        )
+        .bind(deleted_before)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::url_from_row).collect())
+    }
+
+    async fn transfer_owner(
+        &self,
+        id: i32,
+        from_user_id: i32,
+        to_user_id: i32,
+    ) -> Result<Option<Url>, RepositoryError> {
+        // Clicks and conversions reference the URL by ID, so they move along with it
+        let row = sqlx::query(
+            "UPDATE urls SET user_id = $3, version = version + 1
+             WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
+             RETURNING id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks",
+        )
+        .bind(id)
+        .bind(from_user_id)
+        .bind(to_user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(Self::url_from_row))
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Url>, RepositoryError> {
         let row = sqlx::query(
-            "UPDATE urls SET short_code = $1, original_url = $2, expiration_date = $3, status = $4 WHERE id = $5 RETURNING id, short_code, original_url, created_at, expiration_date, user_id, status"
+            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks FROM urls WHERE id = $1 AND deleted_at IS NULL"
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(Self::url_from_row))
+    }
+
+    async fn update_url(&self, url: &Url, expected_version: i64) -> Result<Url, RepositoryError> {
+        let row = sqlx::query(
+            "UPDATE urls SET short_code = $1, original_url = $2, expiration_date = $3, status = $4, preview_mode = $5, title = $8, redirect_type = $9, password_hash = $10, max_clicks = $11, version = version + 1 WHERE id = $6 AND version = $7 AND deleted_at IS NULL RETURNING id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks"
         )
         .bind(&url.short_code)
         .bind(&url.original_url)
         .bind(url.expiration_date)
         .bind(url.status.to_string())
+        .bind(url.preview_mode.as_str())
         .bind(url.id)
-        .fetch_one(&self.pool)
+        .bind(expected_version)
+        .bind(&url.title)
+        .bind(url.redirect_type.as_str())
+        .bind(&url.password_hash)
+        .bind(url.max_clicks)
+        .fetch_optional(&self.pool)
         .await?;
 
-        Ok(Self::url_from_row(&row))
+        if let Some(row) = row {
+            return Ok(Self::url_from_row(&row));
+        }
+
+        // Nothing updated: either the URL is gone or another update bumped its version
+        let current_version: Option<i64> =
+            sqlx::query_scalar("SELECT version FROM urls WHERE id = $1 AND deleted_at IS NULL")
+                .bind(url.id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        match current_version {
+            Some(current_version) => Err(RepositoryError::ConflictingUpdate { current_version }),
+            None => Err(RepositoryError::NotFound),
+        }
     }
 
     async fn get_stats(&self, user_id: Option<i32>) -> Result<UrlStats, RepositoryError> {
-        let (total_urls, unique_short_codes) = if let Some(uid) = user_id {
+        let hll_available = self.hll.is_available(&self.pool).await;
+        let mut tx = self.begin_with_timeout(self.long_query_timeout_ms).await?;
+
+        let (total_urls, unique_short_codes, archived_url_count) = if let Some(uid) = user_id {
             let row = sqlx::query(
-                "SELECT COUNT(*) as total_urls, COUNT(DISTINCT short_code) as unique_short_codes FROM urls WHERE user_id = $1"
+                "SELECT COUNT(*) as total_urls, COUNT(DISTINCT short_code) as unique_short_codes, COUNT(*) FILTER (WHERE status = 'archived') as archived_url_count FROM urls WHERE user_id = $1 AND deleted_at IS NULL"
             )
             .bind(uid)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *tx)
             .await?;
 
-            (row.get("total_urls"), row.get("unique_short_codes"))
+            (
+                row.get("total_urls"),
+                row.get("unique_short_codes"),
+                row.get("archived_url_count"),
+            )
         } else {
             let row = sqlx::query(
-                "SELECT COUNT(*) as total_urls, COUNT(DISTINCT short_code) as unique_short_codes FROM urls"
+                "SELECT COUNT(*) as total_urls, COUNT(DISTINCT short_code) as unique_short_codes, COUNT(*) FILTER (WHERE status = 'archived') as archived_url_count FROM urls WHERE deleted_at IS NULL"
             )
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *tx)
             .await?;
 
-            (row.get("total_urls"), row.get("unique_short_codes"))
+            (
+                row.get("total_urls"),
+                row.get("unique_short_codes"),
+                row.get("archived_url_count"),
+            )
+        };
+
+        let total_clicks: i64 = if let Some(uid) = user_id {
+            sqlx::query_scalar(
+                "SELECT COUNT(*) FROM clicks JOIN urls ON urls.id = clicks.url_id WHERE urls.user_id = $1",
+            )
+            .bind(uid)
+            .fetch_one(&mut *tx)
+            .await?
+        } else {
+            sqlx::query_scalar("SELECT COUNT(*) FROM clicks")
+                .fetch_one(&mut *tx)
+                .await?
         };
 
-        // TODO: Add click tracking when analytics are implemented
-        let total_clicks = 0;
+        let unique_visitors_query = if hll_available {
+            "SELECT COALESCE(ROUND(hll_cardinality(hll_union_agg(click_hll.hll_state::hll))), 0)::BIGINT
+             FROM click_hll JOIN urls ON urls.id = click_hll.url_id
+             WHERE $1::int IS NULL OR urls.user_id = $1"
+        } else {
+            "SELECT COUNT(DISTINCT clicks.ip_address)
+             FROM clicks JOIN urls ON urls.id = clicks.url_id
+             WHERE $1::int IS NULL OR urls.user_id = $1"
+        };
+        let estimated_unique_visitors: i64 = sqlx::query_scalar(unique_visitors_query)
+            .bind(user_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
 
         Ok(UrlStats {
             total_urls,
             total_clicks,
             unique_short_codes,
+            estimated_unique_visitors,
+            archived_url_count,
         })
     }
 
@@ -169,11 +704,12 @@ impl UrlRepository for PostgresUrlRepository {
         let warning_time = now + duration;
 
         let rows = sqlx::query(
-            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status 
+            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks 
              FROM urls 
              WHERE expiration_date IS NOT NULL 
              AND expiration_date > $1 
              AND expiration_date <= $2 
+             AND deleted_at IS NULL 
              ORDER BY expiration_date ASC",
         )
         .bind(now)
@@ -193,10 +729,11 @@ impl UrlRepository for PostgresUrlRepository {
         let now = chrono::Utc::now();
 
         let rows = sqlx::query(
-            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status 
+            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks 
              FROM urls 
              WHERE expiration_date IS NOT NULL 
              AND expiration_date <= $1 
+             AND deleted_at IS NULL 
              ORDER BY expiration_date ASC",
         )
         .bind(now)
@@ -211,30 +748,77 @@ impl UrlRepository for PostgresUrlRepository {
         Ok(urls)
     }
 
-    async fn delete_expired_urls(&self) -> Result<u64, RepositoryError> {
-        let now = chrono::Utc::now();
-
+    async fn delete_expired_urls(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        let mut tx = self.begin_with_timeout(self.long_query_timeout_ms).await?;
         let result = sqlx::query(
             "DELETE FROM urls WHERE expiration_date IS NOT NULL AND expiration_date <= $1",
         )
-        .bind(now)
-        .execute(&self.pool)
+        .bind(expired_before)
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
 
         Ok(result.rows_affected())
     }
 
+    async fn archive_expired_urls(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let mut tx = self.begin_with_timeout(self.long_query_timeout_ms).await?;
+        // The locked rows are returned as they were before the update
+        let rows = sqlx::query(
+            "WITH expired AS (
+                 SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks
+                 FROM urls
+                 WHERE expiration_date IS NOT NULL AND expiration_date <= $1
+                   AND status <> 'archived' AND deleted_at IS NULL
+                 FOR UPDATE
+             )
+             UPDATE urls SET status = 'archived', version = urls.version + 1
+             FROM expired WHERE urls.id = expired.id
+             RETURNING expired.*",
+        )
+        .bind(expired_before)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(rows.iter().map(Self::url_from_row).collect())
+    }
+
+    async fn count_expired_urls_to_archive(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        let mut tx = self.begin_with_timeout(self.long_query_timeout_ms).await?;
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM urls
+             WHERE expiration_date IS NOT NULL AND expiration_date <= $1 AND status <> 'archived'
+               AND deleted_at IS NULL",
+        )
+        .bind(expired_before)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(count as u64)
+    }
+
     async fn soft_delete_by_id(
         &self,
         id: i32,
         user_id: Option<i32>,
     ) -> Result<bool, RepositoryError> {
         let query = if let Some(uid) = user_id {
-            sqlx::query("UPDATE urls SET status = 'inactive' WHERE id = $1 AND user_id = $2")
+            sqlx::query("UPDATE urls SET status = 'inactive', version = version + 1 WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL")
                 .bind(id)
                 .bind(uid)
         } else {
-            sqlx::query("UPDATE urls SET status = 'inactive' WHERE id = $1 AND user_id IS NULL")
+            sqlx::query("UPDATE urls SET status = 'inactive', version = version + 1 WHERE id = $1 AND user_id IS NULL AND deleted_at IS NULL")
                 .bind(id)
         };
 
@@ -248,11 +832,11 @@ impl UrlRepository for PostgresUrlRepository {
         user_id: Option<i32>,
     ) -> Result<bool, RepositoryError> {
         let query = if let Some(uid) = user_id {
-            sqlx::query("UPDATE urls SET status = 'active' WHERE id = $1 AND user_id = $2")
+            sqlx::query("UPDATE urls SET status = 'active', version = version + 1 WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL")
                 .bind(id)
                 .bind(uid)
         } else {
-            sqlx::query("UPDATE urls SET status = 'active' WHERE id = $1 AND user_id IS NULL")
+            sqlx::query("UPDATE urls SET status = 'active', version = version + 1 WHERE id = $1 AND user_id IS NULL AND deleted_at IS NULL")
                 .bind(id)
         };
 
@@ -267,8 +851,8 @@ impl UrlRepository for PostgresUrlRepository {
     ) -> Result<Vec<Url>, RepositoryError> {
         let rows = if let Some(uid) = user_id {
             sqlx::query(
-                "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status 
-                 FROM urls WHERE status = $1 AND user_id = $2 ORDER BY created_at DESC",
+                "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks 
+                 FROM urls WHERE status = $1 AND user_id = $2 AND deleted_at IS NULL ORDER BY created_at DESC",
             )
             .bind(status.to_string())
             .bind(uid)
@@ -276,8 +860,8 @@ impl UrlRepository for PostgresUrlRepository {
             .await?
         } else {
             sqlx::query(
-                "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status 
-                 FROM urls WHERE status = $1 ORDER BY created_at DESC",
+                "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks 
+                 FROM urls WHERE status = $1 AND deleted_at IS NULL ORDER BY created_at DESC",
             )
             .bind(status.to_string())
             .fetch_all(&self.pool)
@@ -292,6 +876,62 @@ impl UrlRepository for PostgresUrlRepository {
         Ok(urls)
     }
 
+    async fn find_by_status_paginated(
+        &self,
+        status: UrlStatus,
+        after_id: Option<i32>,
+        limit: usize,
+    ) -> Result<(Vec<Url>, Option<i32>), RepositoryError> {
+        // One row past the page tells whether another page follows
+        let rows = sqlx::query(
+            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks 
+             FROM urls 
+             WHERE status = $1 AND ($2::int IS NULL OR id > $2) 
+             ORDER BY id 
+             LIMIT $3",
+        )
+        .bind(status.to_string())
+        .bind(after_id)
+        .bind(limit as i64 + 1)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let has_more = rows.len() > limit;
+        let urls: Vec<Url> = rows.iter().take(limit).map(Self::url_from_row).collect();
+        let next_after_id = if has_more {
+            urls.last().map(|url| url.id)
+        } else {
+            None
+        };
+
+        Ok((urls, next_after_id))
+    }
+
+    async fn find_suspicious_urls(
+        &self,
+        min_click_count: i64,
+        created_within_hours: u8,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let mut tx = self.begin_with_timeout(self.long_query_timeout_ms).await?;
+        let rows = sqlx::query(
+            "SELECT urls.id, urls.short_code, urls.original_url, urls.created_at, 
+                    urls.expiration_date, urls.user_id, urls.status, urls.organization_id, urls.version, urls.updated_at, urls.preview_mode, urls.deduplicated_click_count, urls.deleted_at, urls.title, urls.redirect_type, urls.password_hash, urls.max_clicks 
+             FROM urls 
+             JOIN clicks ON clicks.url_id = urls.id 
+             WHERE urls.created_at > NOW() - make_interval(hours => $2) AND urls.deleted_at IS NULL 
+             GROUP BY urls.id 
+             HAVING COUNT(clicks.id) >= $1 
+             ORDER BY COUNT(clicks.id) DESC, urls.id",
+        )
+        .bind(min_click_count)
+        .bind(i32::from(created_within_hours))
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(rows.iter().map(Self::url_from_row).collect())
+    }
+
     async fn batch_deactivate_urls(
         &self,
         url_ids: &[i32],
@@ -323,13 +963,13 @@ impl UrlRepository for PostgresUrlRepository {
         let mut results = Vec::new();
         for &url_id in url_ids {
             let result = if let Some(uid) = user_id {
-                sqlx::query("DELETE FROM urls WHERE id = $1 AND user_id = $2")
+                sqlx::query("UPDATE urls SET status = 'deleted', deleted_at = now(), version = version + 1 WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL")
                     .bind(url_id)
                     .bind(uid)
                     .execute(&self.pool)
                     .await
             } else {
-                sqlx::query("DELETE FROM urls WHERE id = $1")
+                sqlx::query("UPDATE urls SET status = 'deleted', deleted_at = now(), version = version + 1 WHERE id = $1 AND deleted_at IS NULL")
                     .bind(url_id)
                     .execute(&self.pool)
                     .await
@@ -377,14 +1017,14 @@ impl UrlRepository for PostgresUrlRepository {
         let mut results = Vec::new();
         for &url_id in url_ids {
             let result = if let Some(uid) = user_id {
-                sqlx::query("UPDATE urls SET status = $1 WHERE id = $2 AND user_id = $3")
+                sqlx::query("UPDATE urls SET status = $1, version = version + 1 WHERE id = $2 AND user_id = $3 AND deleted_at IS NULL")
                     .bind(status.to_string())
                     .bind(url_id)
                     .bind(uid)
                     .execute(&self.pool)
                     .await
             } else {
-                sqlx::query("UPDATE urls SET status = $1 WHERE id = $2")
+                sqlx::query("UPDATE urls SET status = $1, version = version + 1 WHERE id = $2 AND deleted_at IS NULL")
                     .bind(status.to_string())
                     .bind(url_id)
                     .execute(&self.pool)
@@ -430,21 +1070,24 @@ impl UrlRepository for PostgresUrlRepository {
     {
         use crate::domain::repositories::url_repository::{BatchItemResult, BatchOperationResult};
 
+        // A `None` expiration binds NULL, which removes the expiration
         let mut results = Vec::new();
         for &url_id in url_ids {
             let result = if let Some(uid) = user_id {
-                sqlx::query("UPDATE urls SET expiration_date = $1 WHERE id = $2 AND user_id = $3")
+                sqlx::query("UPDATE urls SET expiration_date = $1, version = version + 1 WHERE id = $2 AND user_id = $3 AND deleted_at IS NULL")
                     .bind(expiration_date)
                     .bind(url_id)
                     .bind(uid)
                     .execute(&self.pool)
                     .await
             } else {
-                sqlx::query("UPDATE urls SET expiration_date = $1 WHERE id = $2")
-                    .bind(expiration_date)
-                    .bind(url_id)
-                    .execute(&self.pool)
-                    .await
+                sqlx::query(
+                    "UPDATE urls SET expiration_date = $1, version = version + 1 WHERE id = $2 AND deleted_at IS NULL",
+                )
+                .bind(expiration_date)
+                .bind(url_id)
+                .execute(&self.pool)
+                .await
             };
 
             match result {
@@ -476,4 +1119,412 @@ impl UrlRepository for PostgresUrlRepository {
             results,
         })
     }
+
+    async fn find_most_clicked(
+        &self,
+        user_id: i32,
+        limit: usize,
+    ) -> Result<Vec<UrlWithClickCount>, RepositoryError> {
+        let mut tx = self.begin_with_timeout(self.long_query_timeout_ms).await?;
+        let rows = sqlx::query(
+            "SELECT urls.id, urls.short_code, urls.original_url, urls.created_at, 
+                    urls.expiration_date, urls.user_id, urls.status, urls.organization_id, urls.version, urls.updated_at, urls.preview_mode, urls.deduplicated_click_count, urls.deleted_at, urls.title, urls.redirect_type, urls.password_hash, urls.max_clicks, 
+                    COUNT(clicks.id) AS click_count 
+             FROM urls 
+             LEFT JOIN clicks ON clicks.url_id = urls.id 
+             WHERE urls.user_id = $1 AND urls.deleted_at IS NULL 
+             GROUP BY urls.id 
+             ORDER BY click_count DESC, urls.created_at DESC 
+             LIMIT $2",
+        )
+        .bind(user_id)
+        .bind(limit as i64)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        let urls = rows
+            .into_iter()
+            .map(|row| UrlWithClickCount::new(Self::url_from_row(&row), row.get("click_count")))
+            .collect();
+
+        Ok(urls)
+    }
+
+    async fn find_recently_created(
+        &self,
+        user_id: i32,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks 
+             FROM urls 
+             WHERE user_id = $1 AND deleted_at IS NULL 
+             ORDER BY created_at DESC 
+             LIMIT $2",
+        )
+        .bind(user_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let urls = rows
+            .into_iter()
+            .map(|row| Self::url_from_row(&row))
+            .collect();
+
+        Ok(urls)
+    }
+
+    async fn find_recently_modified(
+        &self,
+        user_id: i32,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let mut tx = self.begin_with_timeout(self.short_query_timeout_ms).await?;
+        let rows = sqlx::query(
+            "SELECT id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks
+             FROM urls
+             WHERE user_id = $1 AND updated_at > $2 AND deleted_at IS NULL
+             ORDER BY updated_at DESC
+             LIMIT $3",
+        )
+        .bind(user_id)
+        .bind(since)
+        .bind(limit as i64)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(rows.iter().map(Self::url_from_row).collect())
+    }
+
+    async fn find_paginated(
+        &self,
+        user_id: Option<i32>,
+        status: Option<UrlStatus>,
+        sort: UrlSortField,
+        direction: SortDirection,
+        after_cursor: Option<&UrlCursor>,
+        limit: usize,
+    ) -> Result<UrlPage, RepositoryError> {
+        let mut query_builder = UrlQueryBuilder::new(UrlFilter { status })
+            .owned_by(user_id)
+            .page(sort, direction, after_cursor, limit)?;
+
+        let mut tx = self.begin_with_timeout(self.short_query_timeout_ms).await?;
+        let rows = query_builder.build().fetch_all(&mut *tx).await?;
+        tx.commit().await?;
+
+        let urls = rows.iter().map(Self::url_from_row).collect();
+        Ok(UrlPage::from_rows(urls, limit, sort, direction))
+    }
+
+    async fn count_by_filter(
+        &self,
+        filter: &UrlFilter,
+        user_id: i32,
+    ) -> Result<i64, RepositoryError> {
+        let mut query_builder = UrlQueryBuilder::new(*filter)
+            .owned_by(Some(user_id))
+            .count();
+
+        let mut tx = self.begin_with_timeout(self.short_query_timeout_ms).await?;
+        let count: i64 = query_builder
+            .build_query_scalar()
+            .fetch_one(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(count)
+    }
+
+    async fn count_clicks_for_urls(
+        &self,
+        url_ids: &[i32],
+    ) -> Result<HashMap<i32, i64>, RepositoryError> {
+        if url_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let mut tx = self.begin_with_timeout(self.short_query_timeout_ms).await?;
+        let counts: Vec<(i32, i64)> = sqlx::query_as(
+            "SELECT url_id, COUNT(*) FROM clicks WHERE url_id = ANY($1) GROUP BY url_id",
+        )
+        .bind(url_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(counts.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create a user owning `url_count` URLs with `tags_per_url` of 10 tags each
+    ///
+    /// Returns the user's ID and the prefix of its short codes and tag names.
+    async fn seed_tagged_urls(pool: &PgPool, url_count: i32, tags_per_url: i32) -> (i32, String) {
+        let prefix = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        let user_id: i32 = sqlx::query_scalar(
+            "INSERT INTO users (username, email, password_hash) VALUES ($1, $1 || '@example.com', 'x') RETURNING id",
+        )
+        .bind(&prefix)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO urls (short_code, original_url, user_id)
+             SELECT $2 || g, 'https://example.com/' || g, $1 FROM generate_series(1, $3) g",
+        )
+        .bind(user_id)
+        .bind(&prefix)
+        .bind(url_count)
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO tags (name) SELECT $1 || '-' || g FROM generate_series(0, 9) g")
+            .bind(&prefix)
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO url_tags (url_id, tag_id)
+             SELECT u.id, t.id FROM urls u
+             CROSS JOIN generate_series(0, $3 - 1) k
+             JOIN tags t ON t.name = $2 || '-' || ((u.id + k) % 10)
+             WHERE u.user_id = $1",
+        )
+        .bind(user_id)
+        .bind(&prefix)
+        .bind(tags_per_url)
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query("ANALYZE").execute(pool).await.unwrap();
+        (user_id, prefix)
+    }
+
+    /// Whether the node of an EXPLAIN plan whose line contains `scan` runs inside a nested loop
+    ///
+    /// Caching nodes such as `Memoize` between the loop and the node are looked through.
+    fn in_nested_loop(plan: &[String], scan: &str) -> bool {
+        let indent = |line: &String| line.len() - line.trim_start().len();
+        let Some(mut position) = plan.iter().position(|line| line.contains(scan)) else {
+            return false;
+        };
+        loop {
+            let parent = (0..position).rev().find(|&i| {
+                (i == 0 || plan[i].trim_start().starts_with("->"))
+                    && indent(&plan[i]) < indent(&plan[position])
+            });
+            match parent {
+                Some(i) if plan[i].contains("Memoize") || plan[i].contains("Materialize") => {
+                    position = i
+                }
+                Some(i) => return plan[i].contains("Nested Loop"),
+                None => return false,
+            }
+        }
+    }
+
+    /// Needs a database created from init.sql:
+    /// `TEST_DATABASE_URL=postgres://... cargo test --lib concurrent_creates -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_concurrent_creates_of_one_short_code() {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .max_connections(20)
+            .connect(&url)
+            .await
+            .unwrap();
+        let repository = PostgresUrlRepository::new(pool);
+        let short_code = ShortCode::from_string_unchecked(format!(
+            "lock{}",
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        ));
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for i in 0..100 {
+            let repository = repository.clone();
+            let short_code = short_code.clone();
+            tasks.spawn(async move {
+                repository
+                    .create_url(
+                        &short_code,
+                        &format!("https://example.com/{}", i),
+                        None,
+                        None,
+                        None,
+                        UrlStatus::Active,
+                    )
+                    .await
+            });
+        }
+
+        let (mut created, mut duplicates) = (0, 0);
+        while let Some(result) = tasks.join_next().await {
+            match result.unwrap() {
+                Ok(_) => created += 1,
+                Err(RepositoryError::DuplicateShortCode) => duplicates += 1,
+                Err(e) => panic!("unexpected error: {}", e),
+            }
+        }
+        assert_eq!((created, duplicates), (1, 99));
+
+        // The lock was released with its transaction
+        let lock = repository
+            .acquire_short_code_lock(short_code.value())
+            .await
+            .unwrap();
+        drop(lock);
+    }
+
+    /// Needs a database created from init.sql:
+    /// `TEST_DATABASE_URL=postgres://... cargo test --lib organization_quota -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_concurrent_creates_respect_the_organization_quota() {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .max_connections(20)
+            .connect(&url)
+            .await
+            .unwrap();
+        let prefix = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        let user_id: i32 = sqlx::query_scalar(
+            "INSERT INTO users (username, email, password_hash) VALUES ($1, $1 || '@example.com', 'x') RETURNING id",
+        )
+        .bind(&prefix)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let organization_id: i32 = sqlx::query_scalar(
+            "INSERT INTO organizations (name, slug, owner_id) VALUES ($1, $1, $2) RETURNING id",
+        )
+        .bind(&prefix)
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let repository = PostgresUrlRepository::new(pool);
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for i in 0..20 {
+            let repository = repository.clone();
+            let short_code = ShortCode::from_string_unchecked(format!("{}q{}", prefix, i));
+            tasks.spawn(async move {
+                repository
+                    .create_url_idempotent(
+                        &short_code,
+                        &format!("https://example.com/{}", i),
+                        None,
+                        Some(user_id),
+                        Some(organization_id),
+                        Some(5),
+                        UrlStatus::Active,
+                    )
+                    .await
+            });
+        }
+
+        let (mut created, mut refused) = (0, 0);
+        while let Some(result) = tasks.join_next().await {
+            match result.unwrap() {
+                Ok(_) => created += 1,
+                Err(RepositoryError::OrganizationQuotaExceeded { limit: 5 }) => refused += 1,
+                Err(e) => panic!("unexpected error: {}", e),
+            }
+        }
+        assert_eq!((created, refused), (5, 15));
+    }
+
+    /// Needs a database created from init.sql:
+    /// `TEST_DATABASE_URL=postgres://... cargo test --lib archive_returns -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_archive_returns_urls_as_they_were() {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let repository = PostgresUrlRepository::new(pool);
+        let expired_at = chrono::Utc::now() - chrono::Duration::days(1);
+        let short_code = ShortCode::from_string_unchecked(format!(
+            "arch{}",
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        ));
+        let created = repository
+            .create_url(
+                &short_code,
+                "https://example.com",
+                Some(expired_at),
+                None,
+                None,
+                UrlStatus::Inactive,
+            )
+            .await
+            .unwrap();
+
+        let archived = repository
+            .archive_expired_urls(expired_at + chrono::Duration::seconds(1))
+            .await
+            .unwrap();
+        let ours = archived.iter().find(|url| url.id == created.id).unwrap();
+        assert_eq!(ours.status, UrlStatus::Inactive);
+        let stored = repository.find_by_id(created.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, UrlStatus::Archived);
+        assert_eq!(stored.version, created.version + 1);
+    }
+
+    /// Needs a database created from init.sql:
+    /// `TEST_DATABASE_URL=postgres://... cargo test --lib find_by_user_id_loads_tags -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_find_by_user_id_loads_tags_in_one_query() {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let pool = PgPool::connect(&url).await.unwrap();
+        let (user_id, prefix) = seed_tagged_urls(&pool, 100, 3).await;
+        let repository = PostgresUrlRepository::new(pool.clone());
+
+        let urls = repository.find_by_user_id(user_id, None).await.unwrap();
+        assert_eq!(urls.len(), 100);
+        for url in &urls {
+            assert_eq!(url.tags.len(), 3, "{:?}", url);
+            assert!(url.tags.is_sorted());
+            assert!(url.tags.iter().all(|tag| tag.starts_with(&prefix)));
+        }
+
+        // Lookups by short code skip the tags
+        let short_code = ShortCode::from_string_unchecked(urls[0].short_code.clone());
+        let url = repository
+            .find_by_short_code(&short_code, false)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(url.tags.is_empty());
+
+        let plan: Vec<String> = sqlx::query_scalar(&format!(
+            "EXPLAIN {}",
+            PostgresUrlRepository::find_by_owner_query("user_id")
+        ))
+        .bind(user_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        println!("{}", plan.join("\n"));
+        // No subquery runs per URL, and tags are not looked up one row at a time. The URLs'
+        // url_tags rows may still be probed through the primary key within the one query.
+        assert!(
+            plan.iter().all(|line| !line.contains("SubPlan")),
+            "{:?}",
+            plan
+        );
+        assert!(
+            plan.iter().any(|line| line.contains(" on tags t")),
+            "{:?}",
+            plan
+        );
+        assert!(!in_nested_loop(&plan, " on tags t"), "{:?}", plan);
+    }
 }
@@ -0,0 +1,72 @@
+use crate::domain::entities::ServiceAccount;
+use crate::domain::repositories::service_account_repository::{
+    RepositoryError, ServiceAccountRepository,
+};
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+/// PostgreSQL implementation of the ServiceAccountRepository trait
+#[derive(Clone)]
+pub struct PostgresServiceAccountRepository {
+    pool: PgPool,
+}
+
+impl PostgresServiceAccountRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert a database row to a ServiceAccount entity
+    fn row_to_service_account(row: &sqlx::postgres::PgRow) -> ServiceAccount {
+        ServiceAccount {
+            id: row.get("id"),
+            name: row.get("name"),
+            api_key_hash: row.get("api_key_hash"),
+            created_by: row.get("created_by"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl ServiceAccountRepository for PostgresServiceAccountRepository {
+    async fn create_service_account(
+        &self,
+        name: &str,
+        api_key_hash: &str,
+        created_by: i32,
+    ) -> Result<ServiceAccount, RepositoryError> {
+        let row = sqlx::query(
+            "INSERT INTO service_accounts (name, api_key_hash, created_by) VALUES ($1, $2, $3)
+             RETURNING id, name, api_key_hash, created_by, created_at",
+        )
+        .bind(name)
+        .bind(api_key_hash)
+        .bind(created_by)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                RepositoryError::DuplicateName
+            }
+            _ => RepositoryError::Connection(e),
+        })?;
+
+        Ok(Self::row_to_service_account(&row))
+    }
+
+    async fn find_by_api_key_hash(
+        &self,
+        api_key_hash: &str,
+    ) -> Result<Option<ServiceAccount>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT id, name, api_key_hash, created_by, created_at
+             FROM service_accounts WHERE api_key_hash = $1",
+        )
+        .bind(api_key_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(Self::row_to_service_account))
+    }
+}
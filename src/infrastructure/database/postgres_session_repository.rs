@@ -0,0 +1,141 @@
+use crate::domain::entities::Session;
+use crate::domain::repositories::SessionRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+/// PostgreSQL implementation of the SessionRepository trait
+#[derive(Clone)]
+pub struct PostgresSessionRepository {
+    pool: PgPool,
+}
+
+impl PostgresSessionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert a database row to a Session entity
+    fn row_to_session(&self, row: &sqlx::postgres::PgRow) -> Session {
+        Session {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            device_name: row.get("device_name"),
+            ip_address: row.get("ip_address"),
+            user_agent: row.get("user_agent"),
+            created_at: row.get("created_at"),
+            last_used_at: row.get("last_used_at"),
+            revoked: row.get("revoked"),
+        }
+    }
+}
+
+#[async_trait]
+impl SessionRepository for PostgresSessionRepository {
+    async fn create_session(
+        &self,
+        session: Session,
+    ) -> Result<Session, Box<dyn std::error::Error + Send + Sync>> {
+        let row = sqlx::query(
+            "INSERT INTO sessions (id, user_id, device_name, ip_address, user_agent, created_at, last_used_at, revoked)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING id, user_id, device_name, ip_address, user_agent, created_at, last_used_at, revoked",
+        )
+        .bind(session.id)
+        .bind(session.user_id)
+        .bind(&session.device_name)
+        .bind(&session.ip_address)
+        .bind(&session.user_agent)
+        .bind(session.created_at)
+        .bind(session.last_used_at)
+        .bind(session.revoked)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(self.row_to_session(&row))
+    }
+
+    async fn find_by_id(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<Session>, Box<dyn std::error::Error + Send + Sync>> {
+        let row = sqlx::query(
+            "SELECT id, user_id, device_name, ip_address, user_agent, created_at, last_used_at, revoked
+             FROM sessions
+             WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| self.row_to_session(&row)))
+    }
+
+    async fn find_active_for_user(
+        &self,
+        user_id: i32,
+        created_after: DateTime<Utc>,
+    ) -> Result<Vec<Session>, Box<dyn std::error::Error + Send + Sync>> {
+        let rows = sqlx::query(
+            "SELECT id, user_id, device_name, ip_address, user_agent, created_at, last_used_at, revoked
+             FROM sessions
+             WHERE user_id = $1 AND revoked = false AND created_at > $2
+             ORDER BY last_used_at DESC",
+        )
+        .bind(user_id)
+        .bind(created_after)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|row| self.row_to_session(row)).collect())
+    }
+
+    async fn touch(
+        &self,
+        id: Uuid,
+        last_used_at: DateTime<Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query("UPDATE sessions SET last_used_at = $2 WHERE id = $1 AND last_used_at < $2")
+            .bind(id)
+            .bind(last_used_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn revoke(
+        &self,
+        id: Uuid,
+        user_id: i32,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let result = sqlx::query(
+            "UPDATE sessions SET revoked = true
+             WHERE id = $1 AND user_id = $2 AND revoked = false",
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn revoke_all_except(
+        &self,
+        user_id: i32,
+        keep: Option<Uuid>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let result = sqlx::query(
+            "UPDATE sessions SET revoked = true
+             WHERE user_id = $1 AND revoked = false AND id IS DISTINCT FROM $2",
+        )
+        .bind(user_id)
+        .bind(keep)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::domain::repositories::{RepositoryError, ShortCodeSequenceRepository};
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+/// PostgreSQL implementation of the ShortCodeSequenceRepository trait, on `short_code_seq`
+#[derive(Clone)]
+pub struct PostgresShortCodeSequenceRepository {
+    pool: PgPool,
+}
+
+impl PostgresShortCodeSequenceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ShortCodeSequenceRepository for PostgresShortCodeSequenceRepository {
+    async fn next_value(&self) -> Result<u64, RepositoryError> {
+        let value: i64 = sqlx::query_scalar("SELECT nextval('short_code_seq')")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(value as u64)
+    }
+}
@@ -0,0 +1,63 @@
+use crate::domain::entities::UrlMetadata;
+use crate::domain::repositories::url_metadata_repository::{
+    RepositoryError, UrlMetadataRepository,
+};
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+/// PostgreSQL implementation of the UrlMetadataRepository trait
+#[derive(Clone)]
+pub struct PostgresUrlMetadataRepository {
+    pool: PgPool,
+}
+
+impl PostgresUrlMetadataRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UrlMetadataRepository for PostgresUrlMetadataRepository {
+    async fn save_metadata(&self, metadata: &UrlMetadata) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO url_metadata (url_id, title, description, image_url, favicon_url, fetched_at)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (url_id) DO UPDATE SET
+                 title = EXCLUDED.title,
+                 description = EXCLUDED.description,
+                 image_url = EXCLUDED.image_url,
+                 favicon_url = EXCLUDED.favicon_url,
+                 fetched_at = EXCLUDED.fetched_at",
+        )
+        .bind(metadata.url_id)
+        .bind(&metadata.title)
+        .bind(&metadata.description)
+        .bind(&metadata.image_url)
+        .bind(&metadata.favicon_url)
+        .bind(metadata.fetched_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_url_id(&self, url_id: i32) -> Result<Option<UrlMetadata>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT url_id, title, description, image_url, favicon_url, fetched_at
+             FROM url_metadata WHERE url_id = $1",
+        )
+        .bind(url_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| UrlMetadata {
+            url_id: row.get("url_id"),
+            title: row.get("title"),
+            description: row.get("description"),
+            image_url: row.get("image_url"),
+            favicon_url: row.get("favicon_url"),
+            fetched_at: row.get("fetched_at"),
+        }))
+    }
+}
@@ -1,7 +1,21 @@
-use crate::domain::entities::{ProfilePrivacy, User};
-use crate::domain::repositories::user_repository::{RepositoryError, UserRepository};
+use crate::domain::entities::UrlWithClickCount;
+use crate::domain::entities::{
+    AccountStatus, OAuthProvider, ProfilePrivacy, ProfileVisibility, SocialLinks, User, UserTier,
+    MAX_DEVICE_TOKENS,
+};
+use crate::domain::repositories::user_repository::normalize_email;
+use crate::domain::repositories::user_repository::{
+    RepositoryError, UserDataExport, UserRepository,
+};
+use crate::domain::services::anonymization_service::{
+    AnonymizationService, ANONYMIZED_CLICK_RETENTION_DAYS, ANONYMIZED_IP_ADDRESS,
+};
+use crate::infrastructure::database::{PostgresEmailOutboxRepository, PostgresUrlRepository};
+use crate::infrastructure::email::EmailMessage;
 use async_trait::async_trait;
+use sqlx::types::Json;
 use sqlx::{PgPool, Row};
+use std::collections::HashMap;
 
 /// PostgreSQL implementation of the UserRepository trait
 #[derive(Clone)]
@@ -14,6 +28,92 @@ impl PostgresUserRepository {
         Self { pool }
     }
 
+    /// Run every step of `anonymize_user` in one transaction, rolling back on any error
+    async fn erase_user_data(&self, user_id: i32) -> Result<(), RepositoryError> {
+        let anonymization = AnonymizationService::new();
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            "UPDATE users
+             SET username = $1,
+                 email = $2,
+                 password_hash = $3,
+                 first_name = NULL,
+                 last_name = NULL,
+                 bio = NULL,
+                 avatar_url = NULL,
+                 website = NULL,
+                 location = NULL,
+                 social_links = NULL,
+                 privacy = 'private',
+                 account_status = 'deactivated',
+                 suspension_reason = NULL,
+                 suspended_until = NULL,
+                 password_changed_at = CURRENT_TIMESTAMP,
+                 oauth_provider = NULL,
+                 oauth_provider_id = NULL,
+                 device_tokens = '{}',
+                 pending_email = NULL,
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE id = $4",
+        )
+        .bind(anonymization.anonymize_username(user_id))
+        .bind(anonymization.anonymize_email(user_id))
+        .bind(anonymization.generate_anonymized_hash())
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        // Pending tokens could otherwise still log in to (or reset) the erased account
+        sqlx::query("DELETE FROM password_reset_tokens WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM magic_link_tokens WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let url_ids: Vec<i32> =
+            sqlx::query_scalar("UPDATE urls SET user_id = NULL WHERE user_id = $1 RETURNING id")
+                .bind(user_id)
+                .fetch_all(&mut *tx)
+                .await?;
+
+        if !url_ids.is_empty() {
+            sqlx::query(
+                "DELETE FROM clicks
+                 WHERE url_id = ANY($1) AND clicked_at < NOW() - make_interval(days => $2)",
+            )
+            .bind(&url_ids)
+            .bind(ANONYMIZED_CLICK_RETENTION_DAYS as i32)
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query("UPDATE clicks SET ip_address = $1::inet WHERE url_id = ANY($2)")
+                .bind(ANONYMIZED_IP_ADDRESS)
+                .bind(&url_ids)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// LIKE pattern matching values that contain `query`, with its wildcards escaped
+    fn contains_pattern(query: &str) -> String {
+        let escaped = query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        format!("%{}%", escaped)
+    }
+
     /// Convert a database row to a User entity
     fn row_to_user(&self, row: &sqlx::postgres::PgRow) -> User {
         let privacy_str: String = row.get("privacy");
@@ -24,6 +124,25 @@ impl PostgresUserRepository {
             _ => ProfilePrivacy::Public, // Default fallback
         };
 
+        let status_str: String = row.get("account_status");
+        let account_status = match status_str.as_str() {
+            "suspended" => AccountStatus::Suspended {
+                reason: row
+                    .get::<Option<String>, _>("suspension_reason")
+                    .unwrap_or_default(),
+                until: row.get("suspended_until"),
+            },
+            "pending_verification" => AccountStatus::PendingVerification,
+            "deactivated" => AccountStatus::Deactivated,
+            _ => AccountStatus::Active, // Default fallback
+        };
+
+        let tier_str: String = row.get("tier");
+        let tier = match tier_str.as_str() {
+            "premium" => UserTier::Premium,
+            _ => UserTier::Free,
+        };
+
         User {
             id: row.get("id"),
             username: row.get("username"),
@@ -38,6 +157,28 @@ impl PostgresUserRepository {
             location: row.get("location"),
             privacy,
             updated_at: row.get("updated_at"),
+            account_status,
+            tier,
+            password_changed_at: row.get("password_changed_at"),
+            oauth_provider: row
+                .get::<Option<String>, _>("oauth_provider")
+                .as_deref()
+                .and_then(OAuthProvider::parse),
+            oauth_provider_id: row.get("oauth_provider_id"),
+            device_tokens: row.get("device_tokens"),
+            pending_email: row.get("pending_email"),
+            profile_visibility: ProfileVisibility {
+                show_url_count: row.get("show_url_count"),
+                show_click_count: row.get("show_click_count"),
+                show_join_date: row.get("show_join_date"),
+                show_bio: row.get("show_bio"),
+                show_website: row.get("show_website"),
+                show_social_links: row.get("show_social_links"),
+                show_recent_urls: row.get("show_recent_urls"),
+            },
+            social_links: row
+                .get::<Option<Json<SocialLinks>>, _>("social_links")
+                .map(|links| links.0),
         }
     }
 }
@@ -53,10 +194,13 @@ impl UserRepository for PostgresUserRepository {
         let row = sqlx::query(
             "INSERT INTO users (username, email, password_hash) VALUES ($1, $2, $3) 
              RETURNING id, username, email, password_hash, created_at, first_name, last_name, 
-             bio, avatar_url, website, location, privacy, updated_at",
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email",
         )
         .bind(username)
-        .bind(email)
+        .bind(normalize_email(email))
         .bind(password_hash)
         .fetch_one(&self.pool)
         .await?;
@@ -64,10 +208,69 @@ impl UserRepository for PostgresUserRepository {
         Ok(self.row_to_user(&row))
     }
 
+    async fn create_unverified_user(
+        &self,
+        username: &str,
+        email: &str,
+        password_hash: &str,
+        welcome_email: &EmailMessage,
+    ) -> Result<User, RepositoryError> {
+        let mut tx = self.pool.begin().await?;
+
+        PostgresEmailOutboxRepository::enqueue_in(&mut tx, welcome_email).await?;
+        let row = sqlx::query(
+            "INSERT INTO users (username, email, password_hash, account_status)
+             VALUES ($1, $2, $3, 'pending_verification')
+             RETURNING id, username, email, password_hash, created_at, first_name, last_name, 
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email",
+        )
+        .bind(username)
+        .bind(normalize_email(email))
+        .bind(password_hash)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(self.row_to_user(&row))
+    }
+
+    async fn create_oauth_user(
+        &self,
+        username: &str,
+        email: &str,
+        provider: OAuthProvider,
+        provider_id: &str,
+    ) -> Result<User, RepositoryError> {
+        // The empty password hash never verifies, so only the provider can log the user in
+        let row = sqlx::query(
+            "INSERT INTO users (username, email, password_hash, oauth_provider, oauth_provider_id)
+             VALUES ($1, $2, '', $3, $4)
+             RETURNING id, username, email, password_hash, created_at, first_name, last_name, 
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email",
+        )
+        .bind(username)
+        .bind(normalize_email(email))
+        .bind(provider.as_str())
+        .bind(provider_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(self.row_to_user(&row))
+    }
+
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, RepositoryError> {
         let row = sqlx::query(
             "SELECT id, username, email, password_hash, created_at, first_name, last_name, 
-             bio, avatar_url, website, location, privacy, updated_at FROM users WHERE username = $1"
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email FROM users WHERE username = $1",
         )
         .bind(username)
         .fetch_optional(&self.pool)
@@ -82,7 +285,11 @@ impl UserRepository for PostgresUserRepository {
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, RepositoryError> {
         let row = sqlx::query(
             "SELECT id, username, email, password_hash, created_at, first_name, last_name, 
-             bio, avatar_url, website, location, privacy, updated_at FROM users WHERE email = $1",
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email FROM users
+             WHERE lower(email) = lower($1)",
         )
         .bind(email)
         .fetch_optional(&self.pool)
@@ -97,7 +304,10 @@ impl UserRepository for PostgresUserRepository {
     async fn find_by_id(&self, id: i32) -> Result<Option<User>, RepositoryError> {
         let row = sqlx::query(
             "SELECT id, username, email, password_hash, created_at, first_name, last_name, 
-             bio, avatar_url, website, location, privacy, updated_at FROM users WHERE id = $1",
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email FROM users WHERE id = $1",
         )
         .bind(id)
         .fetch_optional(&self.pool)
@@ -109,6 +319,102 @@ impl UserRepository for PostgresUserRepository {
         }
     }
 
+    async fn find_usernames_by_ids(
+        &self,
+        ids: &[i32],
+    ) -> Result<HashMap<i32, String>, RepositoryError> {
+        let rows = sqlx::query("SELECT id, username FROM users WHERE id = ANY($1)")
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| (row.get("id"), row.get("username")))
+            .collect())
+    }
+
+    async fn find_by_oauth_account(
+        &self,
+        provider: OAuthProvider,
+        provider_id: &str,
+    ) -> Result<Option<User>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT id, username, email, password_hash, created_at, first_name, last_name, 
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email
+             FROM users WHERE oauth_provider = $1 AND oauth_provider_id = $2",
+        )
+        .bind(provider.as_str())
+        .bind(provider_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| self.row_to_user(&row)))
+    }
+
+    async fn search_users(
+        &self,
+        query: &str,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<User>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT id, username, email, password_hash, created_at, first_name, last_name,
+             bio, avatar_url, website, location, privacy, updated_at,
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email
+             FROM users
+             WHERE lower(email) LIKE lower($1)
+                OR lower(username) LIKE lower($1)
+                OR to_tsvector('simple', coalesce(first_name, '') || ' ' || coalesce(last_name, ''))
+                   @@ plainto_tsquery('simple', $2)
+             ORDER BY username
+             LIMIT $3 OFFSET $4",
+        )
+        .bind(Self::contains_pattern(query))
+        .bind(query)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|row| self.row_to_user(row)).collect())
+    }
+
+    async fn link_oauth_account(
+        &self,
+        user_id: i32,
+        provider: OAuthProvider,
+        provider_id: &str,
+    ) -> Result<User, RepositoryError> {
+        let row = sqlx::query(
+            "UPDATE users
+             SET oauth_provider = $1,
+                 oauth_provider_id = $2,
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE id = $3
+             RETURNING id, username, email, password_hash, created_at, first_name, last_name, 
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email",
+        )
+        .bind(provider.as_str())
+        .bind(provider_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(self.row_to_user(&row)),
+            None => Err(RepositoryError::NotFound),
+        }
+    }
+
     async fn exists_by_username(&self, username: &str) -> Result<bool, RepositoryError> {
         let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE username = $1")
             .bind(username)
@@ -119,10 +425,11 @@ impl UserRepository for PostgresUserRepository {
     }
 
     async fn exists_by_email(&self, email: &str) -> Result<bool, RepositoryError> {
-        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE email = $1")
-            .bind(email)
-            .fetch_one(&self.pool)
-            .await?;
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE lower(email) = lower($1)")
+                .bind(email)
+                .fetch_one(&self.pool)
+                .await?;
 
         Ok(count > 0)
     }
@@ -182,7 +489,10 @@ impl UserRepository for PostgresUserRepository {
         let query = format!(
             "UPDATE users SET {} WHERE id = ${} 
              RETURNING id, username, email, password_hash, created_at, first_name, last_name, 
-             bio, avatar_url, website, location, privacy, updated_at",
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email",
             query_parts.join(", "),
             param_count
         );
@@ -226,7 +536,10 @@ impl UserRepository for PostgresUserRepository {
     async fn get_profile(&self, user_id: i32) -> Result<Option<User>, RepositoryError> {
         let row = sqlx::query(
             "SELECT id, username, email, password_hash, created_at, first_name, last_name, 
-             bio, avatar_url, website, location, privacy, updated_at FROM users WHERE id = $1",
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email FROM users WHERE id = $1",
         )
         .bind(user_id)
         .fetch_optional(&self.pool)
@@ -251,39 +564,329 @@ impl UserRepository for PostgresUserRepository {
         Ok(())
     }
 
-    async fn anonymize_account(
+    async fn anonymize_user(&self, user_id: i32) -> Result<(), RepositoryError> {
+        self.erase_user_data(user_id).await.map_err(|e| match e {
+            RepositoryError::NotFound => RepositoryError::NotFound,
+            other => RepositoryError::AnonymizationFailed(other.to_string()),
+        })
+    }
+
+    async fn update_password(
         &self,
         user_id: i32,
-        anonymized_username: &str,
-        anonymized_email: &str,
-        anonymized_password_hash: &str,
-    ) -> Result<(), RepositoryError> {
-        let result = sqlx::query(
+        password_hash: &str,
+    ) -> Result<User, RepositoryError> {
+        let row = sqlx::query(
             "UPDATE users 
-             SET username = $1,
-                 email = $2,
-                 password_hash = $3,
-                 first_name = NULL,
-                 last_name = NULL,
-                 bio = NULL,
-                 avatar_url = NULL,
-                 website = NULL,
-                 location = NULL,
-                 privacy = 'private',
+             SET password_hash = $1,
+                 password_changed_at = CURRENT_TIMESTAMP,
                  updated_at = CURRENT_TIMESTAMP
-             WHERE id = $4",
+             WHERE id = $2
+             RETURNING id, username, email, password_hash, created_at, first_name, last_name, 
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email",
         )
-        .bind(anonymized_username)
-        .bind(anonymized_email)
-        .bind(anonymized_password_hash)
+        .bind(password_hash)
         .bind(user_id)
-        .execute(&self.pool)
+        .fetch_optional(&self.pool)
         .await?;
 
-        if result.rows_affected() == 0 {
+        match row {
+            Some(row) => Ok(self.row_to_user(&row)),
+            None => Err(RepositoryError::NotFound),
+        }
+    }
+
+    async fn update_profile_visibility(
+        &self,
+        user_id: i32,
+        visibility: &ProfileVisibility,
+    ) -> Result<User, RepositoryError> {
+        let row = sqlx::query(
+            "UPDATE users 
+             SET show_url_count = $1,
+                 show_click_count = $2,
+                 show_join_date = $3,
+                 show_bio = $4,
+                 show_website = $5,
+                 show_social_links = $6,
+                 show_recent_urls = $7,
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE id = $8
+             RETURNING id, username, email, password_hash, created_at, first_name, last_name, 
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email",
+        )
+        .bind(visibility.show_url_count)
+        .bind(visibility.show_click_count)
+        .bind(visibility.show_join_date)
+        .bind(visibility.show_bio)
+        .bind(visibility.show_website)
+        .bind(visibility.show_social_links)
+        .bind(visibility.show_recent_urls)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(self.row_to_user(&row)),
+            None => Err(RepositoryError::NotFound),
+        }
+    }
+
+    async fn update_social_links(
+        &self,
+        user_id: i32,
+        social_links: Option<&SocialLinks>,
+    ) -> Result<User, RepositoryError> {
+        let row = sqlx::query(
+            "UPDATE users
+             SET social_links = $1,
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE id = $2
+             RETURNING id, username, email, password_hash, created_at, first_name, last_name,
+             bio, avatar_url, website, location, privacy, updated_at,
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email",
+        )
+        .bind(social_links.map(Json))
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(self.row_to_user(&row)),
+            None => Err(RepositoryError::NotFound),
+        }
+    }
+
+    async fn set_pending_email(
+        &self,
+        user_id: i32,
+        pending_email: Option<&str>,
+    ) -> Result<User, RepositoryError> {
+        let row = sqlx::query(
+            "UPDATE users
+             SET pending_email = $1,
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE id = $2
+             RETURNING id, username, email, password_hash, created_at, first_name, last_name,
+             bio, avatar_url, website, location, privacy, updated_at,
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email",
+        )
+        .bind(pending_email.map(normalize_email))
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(self.row_to_user(&row)),
+            None => Err(RepositoryError::NotFound),
+        }
+    }
+
+    async fn update_email(&self, user_id: i32, email: &str) -> Result<User, RepositoryError> {
+        let row = sqlx::query(
+            "UPDATE users
+             SET email = $1,
+                 pending_email = NULL,
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE id = $2
+             RETURNING id, username, email, password_hash, created_at, first_name, last_name,
+             bio, avatar_url, website, location, privacy, updated_at,
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email",
+        )
+        .bind(normalize_email(email))
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                RepositoryError::DuplicateEmail
+            }
+            e => RepositoryError::Connection(e),
+        })?;
+
+        match row {
+            Some(row) => Ok(self.row_to_user(&row)),
+            None => Err(RepositoryError::NotFound),
+        }
+    }
+
+    async fn add_device_token(&self, user_id: i32, token: &str) -> Result<User, RepositoryError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "UPDATE users SET device_tokens = array_remove(device_tokens, $1)
+             WHERE $1 = ANY(device_tokens) AND id <> $2",
+        )
+        .bind(token)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        // Moves an already registered token to the end, then keeps the newest tokens
+        let row = sqlx::query(
+            "UPDATE users
+             SET device_tokens = registered.tokens[GREATEST(cardinality(registered.tokens) - $3 + 1, 1):],
+                 updated_at = CURRENT_TIMESTAMP
+             FROM (
+                 SELECT array_append(array_remove(device_tokens, $1), $1) AS tokens
+                 FROM users WHERE id = $2
+             ) registered
+             WHERE id = $2
+             RETURNING id, username, email, password_hash, created_at, first_name, last_name,
+             bio, avatar_url, website, location, privacy, updated_at,
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email",
+        )
+        .bind(token)
+        .bind(user_id)
+        .bind(MAX_DEVICE_TOKENS as i32)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(row) = row else {
             return Err(RepositoryError::NotFound);
+        };
+        tx.commit().await?;
+        Ok(self.row_to_user(&row))
+    }
+
+    async fn remove_device_token(
+        &self,
+        user_id: i32,
+        token: &str,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE users
+             SET device_tokens = array_remove(device_tokens, $1),
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE id = $2 AND $1 = ANY(device_tokens)",
+        )
+        .bind(token)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn update_account_status(
+        &self,
+        user_id: i32,
+        status: &AccountStatus,
+    ) -> Result<User, RepositoryError> {
+        let (reason, until) = match status {
+            AccountStatus::Suspended { reason, until } => (Some(reason.as_str()), *until),
+            _ => (None, None),
+        };
+
+        let row = sqlx::query(
+            "UPDATE users 
+             SET account_status = $1,
+                 suspension_reason = $2,
+                 suspended_until = $3,
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE id = $4
+             RETURNING id, username, email, password_hash, created_at, first_name, last_name, 
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email",
+        )
+        .bind(status.as_str())
+        .bind(reason)
+        .bind(until)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(self.row_to_user(&row)),
+            None => Err(RepositoryError::NotFound),
         }
+    }
 
-        Ok(())
+    async fn export_user_data(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<UserDataExport>, RepositoryError> {
+        // Repeatable read keeps the profile and URL list from the same snapshot
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(&mut *tx)
+            .await?;
+
+        let user_row = sqlx::query(
+            "SELECT id, username, email, password_hash, created_at, first_name, last_name, 
+             bio, avatar_url, website, location, privacy, updated_at, 
+             account_status, suspension_reason, suspended_until, tier,
+             password_changed_at, show_url_count, show_click_count, show_join_date, show_bio,
+             show_website, show_social_links, show_recent_urls, social_links, oauth_provider, oauth_provider_id, device_tokens, pending_email
+             FROM users WHERE id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(user_row) = user_row else {
+            return Ok(None);
+        };
+
+        let url_rows = sqlx::query(
+            "SELECT urls.id, urls.short_code, urls.original_url, urls.created_at, 
+                    urls.expiration_date, urls.user_id, urls.status, urls.organization_id, urls.version, urls.updated_at, urls.preview_mode, urls.deduplicated_click_count, urls.deleted_at, urls.title, urls.redirect_type, urls.password_hash, urls.max_clicks, 
+                    COUNT(clicks.id) AS click_count 
+             FROM urls 
+             LEFT JOIN clicks ON clicks.url_id = urls.id 
+             WHERE urls.user_id = $1 
+             GROUP BY urls.id 
+             ORDER BY urls.created_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        let urls = url_rows
+            .iter()
+            .map(|row| {
+                UrlWithClickCount::new(
+                    PostgresUrlRepository::url_from_row(row),
+                    row.get("click_count"),
+                )
+            })
+            .collect();
+
+        Ok(Some(UserDataExport {
+            user: self.row_to_user(&user_row),
+            urls,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains_pattern_escapes_wildcards() {
+        assert_eq!(PostgresUserRepository::contains_pattern("alice"), "%alice%");
+        assert_eq!(
+            PostgresUserRepository::contains_pattern("a_b%c\\d"),
+            "%a\\_b\\%c\\\\d%"
+        );
     }
 }
@@ -0,0 +1,182 @@
+use super::hll_support::HllSupport;
+use crate::domain::entities::{UrlCountChange, UserUrlStats};
+use crate::domain::repositories::{RepositoryError, UserUrlStatsRepository};
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+/// PostgreSQL implementation of the UserUrlStatsRepository trait
+#[derive(Clone)]
+pub struct PostgresUserUrlStatsRepository {
+    pool: PgPool,
+    hll: HllSupport,
+}
+
+impl PostgresUserUrlStatsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            hll: HllSupport::new(),
+        }
+    }
+
+    /// Convert a database row to a UserUrlStats entity
+    fn row_to_stats(row: &sqlx::postgres::PgRow) -> UserUrlStats {
+        UserUrlStats {
+            user_id: row.get("user_id"),
+            total_urls: row.get("total_urls"),
+            active_urls: row.get("active_urls"),
+            inactive_urls: row.get("inactive_urls"),
+            archived_urls: row.get("archived_urls"),
+            total_clicks: row.get("total_clicks"),
+            last_updated_at: row.get("last_updated_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl UserUrlStatsRepository for PostgresUserUrlStatsRepository {
+    async fn find_by_user_id(&self, user_id: i32) -> Result<Option<UserUrlStats>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT user_id, total_urls, active_urls, inactive_urls, archived_urls, total_clicks,
+                    last_updated_at
+             FROM user_url_stats WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(Self::row_to_stats))
+    }
+
+    async fn adjust_url_counts(
+        &self,
+        user_id: i32,
+        change: UrlCountChange,
+    ) -> Result<(), RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE user_url_stats
+             SET total_urls = total_urls + $2,
+                 active_urls = active_urls + $3,
+                 inactive_urls = inactive_urls + $4,
+                 archived_urls = archived_urls + $5,
+                 last_updated_at = CURRENT_TIMESTAMP
+             WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .bind(change.total_urls)
+        .bind(change.active_urls)
+        .bind(change.inactive_urls)
+        .bind(change.archived_urls)
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() > 0 {
+            return Ok(());
+        }
+
+        // First count for this user; a concurrent first count is added to instead
+        sqlx::query(
+            "INSERT INTO user_url_stats
+                 (user_id, total_urls, active_urls, inactive_urls, archived_urls)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (user_id) DO UPDATE
+             SET total_urls = user_url_stats.total_urls + EXCLUDED.total_urls,
+                 active_urls = user_url_stats.active_urls + EXCLUDED.active_urls,
+                 inactive_urls = user_url_stats.inactive_urls + EXCLUDED.inactive_urls,
+                 archived_urls = user_url_stats.archived_urls + EXCLUDED.archived_urls,
+                 last_updated_at = CURRENT_TIMESTAMP",
+        )
+        .bind(user_id)
+        .bind(change.total_urls)
+        .bind(change.active_urls)
+        .bind(change.inactive_urls)
+        .bind(change.archived_urls)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn estimate_unique_visitors(&self, user_id: i32) -> Result<i64, RepositoryError> {
+        // Same estimate as UrlRepository::get_stats
+        let query = if self.hll.is_available(&self.pool).await {
+            "SELECT COALESCE(ROUND(hll_cardinality(hll_union_agg(click_hll.hll_state::hll))), 0)::BIGINT
+             FROM click_hll JOIN urls ON urls.id = click_hll.url_id
+             WHERE urls.user_id = $1"
+        } else {
+            "SELECT COUNT(DISTINCT clicks.ip_address)
+             FROM clicks JOIN urls ON urls.id = clicks.url_id
+             WHERE urls.user_id = $1"
+        };
+
+        Ok(sqlx::query_scalar(query)
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await?)
+    }
+
+    async fn increment_clicks(&self, clicks: &[(i32, i64)]) -> Result<(), RepositoryError> {
+        if clicks.is_empty() {
+            return Ok(());
+        }
+        let (user_ids, counts): (Vec<i32>, Vec<i64>) = clicks.iter().copied().unzip();
+
+        sqlx::query(
+            "INSERT INTO user_url_stats (user_id, total_clicks)
+             SELECT user_id, SUM(clicks)::BIGINT
+             FROM UNNEST($1::INTEGER[], $2::BIGINT[]) AS added(user_id, clicks)
+             GROUP BY user_id
+             ON CONFLICT (user_id) DO UPDATE
+             SET total_clicks = user_url_stats.total_clicks + EXCLUDED.total_clicks,
+                 last_updated_at = CURRENT_TIMESTAMP",
+        )
+        .bind(&user_ids)
+        .bind(&counts)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn rebuild_all(&self) -> Result<u64, RepositoryError> {
+        // Same definitions as UrlRepository::get_stats: deleted URLs are not counted, while
+        // their clicks are
+        let result = sqlx::query(
+            "INSERT INTO user_url_stats
+                 (user_id, total_urls, active_urls, inactive_urls, archived_urls, total_clicks,
+                  last_updated_at)
+             SELECT users.id,
+                    COALESCE(url_counts.total_urls, 0),
+                    COALESCE(url_counts.active_urls, 0),
+                    COALESCE(url_counts.inactive_urls, 0),
+                    COALESCE(url_counts.archived_urls, 0),
+                    COALESCE(click_counts.total_clicks, 0),
+                    CURRENT_TIMESTAMP
+             FROM users
+             LEFT JOIN (
+                 SELECT user_id,
+                        COUNT(*) AS total_urls,
+                        COUNT(*) FILTER (WHERE status = 'active') AS active_urls,
+                        COUNT(*) FILTER (WHERE status = 'inactive') AS inactive_urls,
+                        COUNT(*) FILTER (WHERE status = 'archived') AS archived_urls
+                 FROM urls WHERE deleted_at IS NULL
+                 GROUP BY user_id
+             ) url_counts ON url_counts.user_id = users.id
+             LEFT JOIN (
+                 SELECT urls.user_id, COUNT(*) AS total_clicks
+                 FROM clicks JOIN urls ON urls.id = clicks.url_id
+                 GROUP BY urls.user_id
+             ) click_counts ON click_counts.user_id = users.id
+             ON CONFLICT (user_id) DO UPDATE
+             SET total_urls = EXCLUDED.total_urls,
+                 active_urls = EXCLUDED.active_urls,
+                 inactive_urls = EXCLUDED.inactive_urls,
+                 archived_urls = EXCLUDED.archived_urls,
+                 total_clicks = EXCLUDED.total_clicks,
+                 last_updated_at = EXCLUDED.last_updated_at",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod url_query_builder;
+
+pub use url_query_builder::UrlQueryBuilder;
@@ -0,0 +1,183 @@
+use crate::domain::repositories::{
+    RepositoryError, SortDirection, UrlCursor, UrlFilter, UrlSortField,
+};
+use sqlx::{Postgres, QueryBuilder};
+
+/// Columns selected for a listed URL, including its sorted tag names
+const LISTING_COLUMNS: &str = "id, short_code, original_url, created_at, expiration_date, user_id, status, organization_id, version, updated_at, preview_mode, deduplicated_click_count, deleted_at, title, redirect_type, password_hash, max_clicks,
+        COALESCE((SELECT array_agg(t.name ORDER BY t.name) FROM url_tags ut JOIN tags t ON t.id = ut.tag_id WHERE ut.url_id = urls.id), '{}') AS tags";
+
+/// Builds the SQL of URL listings from a `UrlFilter`
+///
+/// Filter values are always bound as parameters; only column names and keywords chosen from
+/// fixed lists are written into the SQL itself.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UrlQueryBuilder {
+    filter: UrlFilter,
+    user_id: Option<i32>,
+}
+
+impl UrlQueryBuilder {
+    pub fn new(filter: UrlFilter) -> Self {
+        Self {
+            filter,
+            user_id: None,
+        }
+    }
+
+    /// Only list URLs owned by `user_id`; `None` lists the URLs of every user
+    pub fn owned_by(mut self, user_id: Option<i32>) -> Self {
+        self.user_id = user_id;
+        self
+    }
+
+    /// Query counting the URLs matching the filter
+    pub fn count(&self) -> QueryBuilder<'static, Postgres> {
+        let mut query = QueryBuilder::new("SELECT COUNT(*) FROM urls");
+        self.push_where(&mut query);
+        query
+    }
+
+    /// Query for one page of matching URLs in the given order, starting after `after_cursor`
+    ///
+    /// Selects one URL more than `limit` so callers can tell whether another page follows.
+    /// Fails when a `created_at` cursor does not hold a timestamp.
+    pub fn page(
+        &self,
+        sort: UrlSortField,
+        direction: SortDirection,
+        after_cursor: Option<&UrlCursor>,
+        limit: usize,
+    ) -> Result<QueryBuilder<'static, Postgres>, RepositoryError> {
+        let column = sort.column();
+        let (comparison, order) = match direction {
+            SortDirection::Asc => (">", "ASC"),
+            SortDirection::Desc => ("<", "DESC"),
+        };
+
+        let mut query = QueryBuilder::new("SELECT ");
+        query.push(LISTING_COLUMNS).push(" FROM urls");
+        self.push_where(&mut query);
+        // Row comparison lets the (user_id, <column>, id) index seek straight to the cursor
+        if let Some(cursor) = after_cursor {
+            query.push(format!(" AND ({}, id) {} (", column, comparison));
+            match sort {
+                UrlSortField::CreatedAt => {
+                    let created_at = cursor.created_at().ok_or_else(|| {
+                        RepositoryError::InvalidData("Malformed pagination cursor".to_string())
+                    })?;
+                    query.push_bind(created_at);
+                }
+                UrlSortField::ShortCode => {
+                    query.push_bind(cursor.sort_value.clone());
+                }
+            }
+            query.push(", ").push_bind(cursor.id).push(")");
+        }
+        query
+            .push(format!(
+                " ORDER BY {} {}, id {} LIMIT ",
+                column, order, order
+            ))
+            .push_bind((limit + 1) as i64);
+        Ok(query)
+    }
+
+    /// Restrict `query` to live URLs matching the filter
+    fn push_where(&self, query: &mut QueryBuilder<'static, Postgres>) {
+        query.push(" WHERE deleted_at IS NULL");
+        if let Some(user_id) = self.user_id {
+            query.push(" AND user_id = ").push_bind(user_id);
+        }
+        match self.filter.status {
+            Some(status) => {
+                query.push(" AND status = ").push_bind(status.as_str());
+            }
+            None => {
+                query.push(" AND status <> 'archived'");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::UrlStatus;
+
+    #[test]
+    fn test_where_clause_for_every_filter_combination() {
+        let statuses = [
+            None,
+            Some(UrlStatus::Active),
+            Some(UrlStatus::Inactive),
+            Some(UrlStatus::Archived),
+        ];
+        for user_id in [None, Some(7)] {
+            for status in statuses {
+                let builder = UrlQueryBuilder::new(UrlFilter { status }).owned_by(user_id);
+                let count = builder.count();
+                let page = builder
+                    .page(UrlSortField::CreatedAt, SortDirection::Desc, None, 10)
+                    .unwrap();
+                let status_param = if user_id.is_some() { "$2" } else { "$1" };
+
+                for sql in [count.sql(), page.sql()] {
+                    assert!(sql.contains(" WHERE deleted_at IS NULL"), "{}", sql);
+                    assert_eq!(sql.contains(" AND user_id = $1"), user_id.is_some());
+                    assert_eq!(
+                        sql.contains(&format!(" AND status = {}", status_param)),
+                        status.is_some()
+                    );
+                    assert_eq!(sql.contains(" AND status <> 'archived'"), status.is_none());
+                }
+                assert!(count.sql().starts_with("SELECT COUNT(*) FROM urls WHERE"));
+                let limit_param =
+                    1 + usize::from(user_id.is_some()) + usize::from(status.is_some());
+                assert!(page.sql().ends_with(&format!(
+                    " ORDER BY created_at DESC, id DESC LIMIT ${}",
+                    limit_param
+                )));
+            }
+        }
+    }
+
+    #[test]
+    fn test_keyset_clause_follows_sort_order() {
+        let builder = UrlQueryBuilder::new(UrlFilter::default()).owned_by(Some(7));
+        let cursor = UrlCursor {
+            sort: UrlSortField::ShortCode,
+            direction: SortDirection::Asc,
+            sort_value: "abc123".to_string(),
+            id: 42,
+        };
+        let page = builder
+            .page(
+                UrlSortField::ShortCode,
+                SortDirection::Asc,
+                Some(&cursor),
+                10,
+            )
+            .unwrap();
+        assert!(page.sql().contains(" AND (short_code, id) > ($2, $3)"));
+        assert!(page
+            .sql()
+            .ends_with(" ORDER BY short_code ASC, id ASC LIMIT $4"));
+
+        let malformed = UrlCursor {
+            sort: UrlSortField::CreatedAt,
+            direction: SortDirection::Desc,
+            sort_value: "yesterday".to_string(),
+            id: 42,
+        };
+        assert!(matches!(
+            builder.page(
+                UrlSortField::CreatedAt,
+                SortDirection::Desc,
+                Some(&malformed),
+                10
+            ),
+            Err(RepositoryError::InvalidData(_))
+        ));
+    }
+}
@@ -0,0 +1,678 @@
+use crate::domain::entities::{ShortCode, Url, UrlStatus, UrlWithClickCount};
+use crate::domain::repositories::url_repository::BatchOperationResult;
+use crate::domain::repositories::{
+    RepositoryError, SortDirection, UrlCursor, UrlFilter, UrlPage, UrlRepository, UrlSortField,
+    UrlStats,
+};
+use crate::infrastructure::database::PostgresUrlRepository;
+use crate::infrastructure::metrics::{
+    DB_REPLICA_AVAILABLE, DB_REPLICA_LAG_MS, PRIMARY_FALLBACK_TOTAL,
+};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::trace;
+
+/// How long after a replica miss a primary lookup may still be explained by replication lag
+pub const PRIMARY_FALLBACK_WINDOW: Duration = Duration::from_secs(5);
+
+/// Replica misses tracked before expired entries are pruned
+const MAX_TRACKED_MISSES: usize = 10_000;
+
+/// Replication lag reported by a read replica
+#[async_trait]
+pub trait ReplicaLag: Send + Sync {
+    /// Milliseconds the replica trails the primary by; `None` until it replayed a transaction
+    async fn replica_lag_ms(&self) -> Result<Option<f64>, RepositoryError>;
+}
+
+/// URL repository over the primary database and an optional read replica
+///
+/// Writes, and the reads write paths depend on (`exists_by_short_code`, `find_by_id`), go to
+/// the primary. Other reads go to the replica after checking its replication lag, and to the
+/// primary when the replica lags more than `replica_lag_max_ms` or cannot be reached. A short
+/// code lookup with `force_primary` is only sent to the primary within
+/// [`PRIMARY_FALLBACK_WINDOW`] of the replica missing the same code; later misses cannot be
+/// blamed on replication lag and keep the replica's answer.
+#[derive(Clone)]
+pub struct ReplicaAwarePool<R = PostgresUrlRepository> {
+    primary: R,
+    replica: Option<R>,
+    replica_lag_max_ms: u64,
+    replica_misses: Arc<DashMap<String, Instant>>,
+}
+
+impl<R> ReplicaAwarePool<R>
+where
+    R: UrlRepository + ReplicaLag,
+{
+    pub fn new(primary: R, replica: Option<R>) -> Self {
+        Self {
+            primary,
+            replica,
+            replica_lag_max_ms: 1000,
+            replica_misses: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Send reads to the primary while the replica lags more than this, in milliseconds
+    pub fn with_replica_lag_max_ms(mut self, replica_lag_max_ms: u64) -> Self {
+        self.replica_lag_max_ms = replica_lag_max_ms;
+        self
+    }
+
+    fn record_replica_miss(&self, short_code: &ShortCode) {
+        if self.replica_misses.len() >= MAX_TRACKED_MISSES {
+            self.replica_misses
+                .retain(|_, missed_at| missed_at.elapsed() < PRIMARY_FALLBACK_WINDOW);
+        }
+        self.replica_misses
+            .insert(short_code.value().to_string(), Instant::now());
+    }
+
+    /// Whether the replica missed this short code recently enough to ask the primary
+    fn take_recent_miss(&self, short_code: &ShortCode) -> bool {
+        self.replica_misses
+            .remove(short_code.value())
+            .is_some_and(|(_, missed_at)| missed_at.elapsed() < PRIMARY_FALLBACK_WINDOW)
+    }
+
+    fn primary_for(&self, operation: &str) -> &R {
+        trace!(operation, "Routing query to the primary");
+        &self.primary
+    }
+
+    /// Where a read goes: the replica unless there is none or it is lagging or unreachable
+    async fn reader_for(&self, operation: &str) -> &R {
+        let Some(replica) = &self.replica else {
+            return self.primary_for(operation);
+        };
+        match replica.replica_lag_ms().await {
+            Ok(lag_ms) => {
+                let lag_ms = lag_ms.unwrap_or(0.0).max(0.0);
+                DB_REPLICA_AVAILABLE.set(1);
+                DB_REPLICA_LAG_MS.set(lag_ms as i64);
+                if lag_ms <= self.replica_lag_max_ms as f64 {
+                    trace!(operation, lag_ms, "Routing query to the replica");
+                    replica
+                } else {
+                    trace!(
+                        operation,
+                        lag_ms,
+                        "Routing query to the primary, the replica is lagging"
+                    );
+                    &self.primary
+                }
+            }
+            Err(e) => {
+                DB_REPLICA_AVAILABLE.set(0);
+                trace!(
+                    operation,
+                    error = %e,
+                    "Routing query to the primary, the replica is unavailable"
+                );
+                &self.primary
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<R> UrlRepository for ReplicaAwarePool<R>
+where
+    R: UrlRepository + ReplicaLag,
+{
+    async fn create_url(
+        &self,
+        short_code: &ShortCode,
+        original_url: &str,
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Option<i32>,
+        organization_id: Option<i32>,
+        status: UrlStatus,
+    ) -> Result<Url, RepositoryError> {
+        self.primary_for("create_url")
+            .create_url(
+                short_code,
+                original_url,
+                expiration_date,
+                user_id,
+                organization_id,
+                status,
+            )
+            .await
+    }
+
+    async fn create_url_idempotent(
+        &self,
+        short_code: &ShortCode,
+        original_url: &str,
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Option<i32>,
+        organization_id: Option<i32>,
+        max_organization_urls: Option<i64>,
+        status: UrlStatus,
+    ) -> Result<(Url, bool), RepositoryError> {
+        self.primary_for("create_url_idempotent")
+            .create_url_idempotent(
+                short_code,
+                original_url,
+                expiration_date,
+                user_id,
+                organization_id,
+                max_organization_urls,
+                status,
+            )
+            .await
+    }
+
+    #[tracing::instrument(skip(self), fields(short_code = %short_code))]
+    async fn find_by_short_code(
+        &self,
+        short_code: &ShortCode,
+        force_primary: bool,
+    ) -> Result<Option<Url>, RepositoryError> {
+        if self.replica.is_none() {
+            return self
+                .primary_for("find_by_short_code")
+                .find_by_short_code(short_code, force_primary)
+                .await;
+        }
+        if force_primary && self.take_recent_miss(short_code) {
+            PRIMARY_FALLBACK_TOTAL.inc();
+            return self
+                .primary_for("find_by_short_code")
+                .find_by_short_code(short_code, true)
+                .await;
+        }
+
+        let reader = self.reader_for("find_by_short_code").await;
+        let url = reader.find_by_short_code(short_code, false).await?;
+        if url.is_none() && !force_primary && !std::ptr::eq(reader, &self.primary) {
+            self.record_replica_miss(short_code);
+        }
+        Ok(url)
+    }
+
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        organization_id: Option<i32>,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        self.reader_for("find_by_user_id")
+            .await
+            .find_by_user_id(user_id, organization_id)
+            .await
+    }
+
+    async fn find_by_original_url(
+        &self,
+        original_url: &str,
+        user_id: i32,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        self.reader_for("find_by_original_url")
+            .await
+            .find_by_original_url(original_url, user_id)
+            .await
+    }
+
+    async fn find_all_by_original_url(
+        &self,
+        original_url: &str,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        self.reader_for("find_all_by_original_url")
+            .await
+            .find_all_by_original_url(original_url, limit)
+            .await
+    }
+
+    async fn find_deleted_by_short_code(
+        &self,
+        short_code: &ShortCode,
+    ) -> Result<Option<Url>, RepositoryError> {
+        self.primary_for("find_deleted_by_short_code")
+            .find_deleted_by_short_code(short_code)
+            .await
+    }
+
+    async fn exists_by_short_code(&self, short_code: &ShortCode) -> Result<bool, RepositoryError> {
+        self.primary_for("exists_by_short_code")
+            .exists_by_short_code(short_code)
+            .await
+    }
+
+    async fn find_phonetically_similar_codes(
+        &self,
+        generated: &ShortCode,
+        threshold: f32,
+    ) -> Result<Vec<ShortCode>, RepositoryError> {
+        self.primary_for("find_phonetically_similar_codes")
+            .find_phonetically_similar_codes(generated, threshold)
+            .await
+    }
+
+    async fn replace_short_code(
+        &self,
+        url_id: i32,
+        new_short_code: &ShortCode,
+    ) -> Result<(), RepositoryError> {
+        self.primary_for("replace_short_code")
+            .replace_short_code(url_id, new_short_code)
+            .await
+    }
+
+    async fn rename_short_code(
+        &self,
+        url_id: i32,
+        new_short_code: &ShortCode,
+        max_aliases: usize,
+    ) -> Result<Url, RepositoryError> {
+        self.primary_for("rename_short_code")
+            .rename_short_code(url_id, new_short_code, max_aliases)
+            .await
+    }
+
+    async fn delete_by_id(&self, id: i32, user_id: Option<i32>) -> Result<bool, RepositoryError> {
+        self.primary_for("delete_by_id")
+            .delete_by_id(id, user_id)
+            .await
+    }
+
+    async fn permanently_delete_by_id(&self, id: i32) -> Result<bool, RepositoryError> {
+        self.primary_for("permanently_delete_by_id")
+            .permanently_delete_by_id(id)
+            .await
+    }
+
+    async fn find_deleted_before(
+        &self,
+        deleted_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        self.primary_for("find_deleted_before")
+            .find_deleted_before(deleted_before)
+            .await
+    }
+
+    async fn transfer_owner(
+        &self,
+        id: i32,
+        from_user_id: i32,
+        to_user_id: i32,
+    ) -> Result<Option<Url>, RepositoryError> {
+        self.primary_for("transfer_owner")
+            .transfer_owner(id, from_user_id, to_user_id)
+            .await
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Url>, RepositoryError> {
+        self.primary_for("find_by_id").find_by_id(id).await
+    }
+
+    async fn update_url(&self, url: &Url, expected_version: i64) -> Result<Url, RepositoryError> {
+        self.primary_for("update_url")
+            .update_url(url, expected_version)
+            .await
+    }
+
+    async fn get_stats(&self, user_id: Option<i32>) -> Result<UrlStats, RepositoryError> {
+        self.reader_for("get_stats").await.get_stats(user_id).await
+    }
+
+    async fn find_urls_expiring_soon(
+        &self,
+        duration: chrono::Duration,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        self.reader_for("find_urls_expiring_soon")
+            .await
+            .find_urls_expiring_soon(duration)
+            .await
+    }
+
+    async fn find_expired_urls(&self) -> Result<Vec<Url>, RepositoryError> {
+        self.reader_for("find_expired_urls")
+            .await
+            .find_expired_urls()
+            .await
+    }
+
+    async fn delete_expired_urls(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        self.primary_for("delete_expired_urls")
+            .delete_expired_urls(expired_before)
+            .await
+    }
+
+    async fn archive_expired_urls(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        self.primary_for("archive_expired_urls")
+            .archive_expired_urls(expired_before)
+            .await
+    }
+
+    async fn count_expired_urls_to_archive(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        self.primary_for("count_expired_urls_to_archive")
+            .count_expired_urls_to_archive(expired_before)
+            .await
+    }
+
+    async fn soft_delete_by_id(
+        &self,
+        id: i32,
+        user_id: Option<i32>,
+    ) -> Result<bool, RepositoryError> {
+        self.primary_for("soft_delete_by_id")
+            .soft_delete_by_id(id, user_id)
+            .await
+    }
+
+    async fn reactivate_by_id(
+        &self,
+        id: i32,
+        user_id: Option<i32>,
+    ) -> Result<bool, RepositoryError> {
+        self.primary_for("reactivate_by_id")
+            .reactivate_by_id(id, user_id)
+            .await
+    }
+
+    async fn find_by_status(
+        &self,
+        status: UrlStatus,
+        user_id: Option<i32>,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        self.reader_for("find_by_status")
+            .await
+            .find_by_status(status, user_id)
+            .await
+    }
+
+    async fn find_by_status_paginated(
+        &self,
+        status: UrlStatus,
+        after_id: Option<i32>,
+        limit: usize,
+    ) -> Result<(Vec<Url>, Option<i32>), RepositoryError> {
+        self.reader_for("find_by_status_paginated")
+            .await
+            .find_by_status_paginated(status, after_id, limit)
+            .await
+    }
+
+    async fn find_suspicious_urls(
+        &self,
+        min_click_count: i64,
+        created_within_hours: u8,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        self.reader_for("find_suspicious_urls")
+            .await
+            .find_suspicious_urls(min_click_count, created_within_hours)
+            .await
+    }
+
+    async fn batch_deactivate_urls(
+        &self,
+        url_ids: &[i32],
+        user_id: Option<i32>,
+    ) -> Result<BatchOperationResult, RepositoryError> {
+        self.primary_for("batch_deactivate_urls")
+            .batch_deactivate_urls(url_ids, user_id)
+            .await
+    }
+
+    async fn batch_reactivate_urls(
+        &self,
+        url_ids: &[i32],
+        user_id: Option<i32>,
+    ) -> Result<BatchOperationResult, RepositoryError> {
+        self.primary_for("batch_reactivate_urls")
+            .batch_reactivate_urls(url_ids, user_id)
+            .await
+    }
+
+    async fn batch_delete_urls(
+        &self,
+        url_ids: &[i32],
+        user_id: Option<i32>,
+    ) -> Result<BatchOperationResult, RepositoryError> {
+        self.primary_for("batch_delete_urls")
+            .batch_delete_urls(url_ids, user_id)
+            .await
+    }
+
+    async fn batch_update_status(
+        &self,
+        url_ids: &[i32],
+        status: UrlStatus,
+        user_id: Option<i32>,
+    ) -> Result<BatchOperationResult, RepositoryError> {
+        self.primary_for("batch_update_status")
+            .batch_update_status(url_ids, status, user_id)
+            .await
+    }
+
+    async fn batch_update_expiration(
+        &self,
+        url_ids: &[i32],
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Option<i32>,
+    ) -> Result<BatchOperationResult, RepositoryError> {
+        self.primary_for("batch_update_expiration")
+            .batch_update_expiration(url_ids, expiration_date, user_id)
+            .await
+    }
+
+    async fn find_most_clicked(
+        &self,
+        user_id: i32,
+        limit: usize,
+    ) -> Result<Vec<UrlWithClickCount>, RepositoryError> {
+        self.reader_for("find_most_clicked")
+            .await
+            .find_most_clicked(user_id, limit)
+            .await
+    }
+
+    async fn find_recently_created(
+        &self,
+        user_id: i32,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        self.reader_for("find_recently_created")
+            .await
+            .find_recently_created(user_id, limit)
+            .await
+    }
+
+    async fn find_recently_modified(
+        &self,
+        user_id: i32,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        self.reader_for("find_recently_modified")
+            .await
+            .find_recently_modified(user_id, since, limit)
+            .await
+    }
+
+    async fn find_paginated(
+        &self,
+        user_id: Option<i32>,
+        status: Option<UrlStatus>,
+        sort: UrlSortField,
+        direction: SortDirection,
+        after_cursor: Option<&UrlCursor>,
+        limit: usize,
+    ) -> Result<UrlPage, RepositoryError> {
+        self.reader_for("find_paginated")
+            .await
+            .find_paginated(user_id, status, sort, direction, after_cursor, limit)
+            .await
+    }
+
+    async fn count_by_filter(
+        &self,
+        filter: &UrlFilter,
+        user_id: i32,
+    ) -> Result<i64, RepositoryError> {
+        self.reader_for("count_by_filter")
+            .await
+            .count_by_filter(filter, user_id)
+            .await
+    }
+
+    async fn count_clicks_for_urls(
+        &self,
+        url_ids: &[i32],
+    ) -> Result<HashMap<i32, i64>, RepositoryError> {
+        self.reader_for("count_clicks_for_urls")
+            .await
+            .count_clicks_for_urls(url_ids)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::UrlService;
+    use crate::infrastructure::test_utils::MockUrlRepository;
+
+    async fn create(repository: &impl UrlRepository, code: &str) -> ShortCode {
+        let short_code = ShortCode::new(code.to_string()).unwrap();
+        repository
+            .create_url(
+                &short_code,
+                "https://example.com",
+                None,
+                Some(1),
+                None,
+                UrlStatus::Active,
+            )
+            .await
+            .unwrap();
+        short_code
+    }
+
+    async fn finds(repository: &impl UrlRepository, short_code: &ShortCode) -> bool {
+        repository
+            .find_by_short_code(short_code, false)
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    #[tokio::test]
+    async fn test_writes_go_to_primary_and_reads_to_replica() {
+        let primary = MockUrlRepository::new();
+        let replica = MockUrlRepository::new();
+        let pool = ReplicaAwarePool::new(primary.clone(), Some(replica.clone()));
+
+        let written = create(&pool, "write1").await;
+        assert!(finds(&primary, &written).await);
+        assert!(!finds(&replica, &written).await);
+
+        // Not replicated yet: plain reads miss it, forced ones find it on the primary
+        assert!(!finds(&pool, &written).await);
+        assert!(pool
+            .find_by_short_code(&written, true)
+            .await
+            .unwrap()
+            .is_some());
+
+        let replicated = create(&replica, "read1").await;
+        assert!(finds(&pool, &replicated).await);
+        let urls = pool.find_by_user_id(1, None).await.unwrap();
+        assert_eq!(urls.len(), 1);
+        assert_eq!(urls[0].short_code, "read1");
+    }
+
+    #[tokio::test]
+    async fn test_lagging_replica_falls_back_to_primary() {
+        let primary = MockUrlRepository::new();
+        let replica = MockUrlRepository::new();
+        let pool = ReplicaAwarePool::new(primary.clone(), Some(replica.clone()))
+            .with_replica_lag_max_ms(500);
+        let written = create(&primary, "lag1").await;
+
+        replica.set_replica_lag_ms(Some(2000.0));
+        assert!(finds(&pool, &written).await);
+
+        replica.set_replica_lag_ms(Some(100.0));
+        assert!(!finds(&pool, &written).await);
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_or_missing_replica_reads_from_primary() {
+        let primary = MockUrlRepository::new();
+        let replica = MockUrlRepository::new();
+        let written = create(&primary, "down1").await;
+
+        replica.set_replica_lag_ms(None);
+        let pool = ReplicaAwarePool::new(primary.clone(), Some(replica));
+        assert!(finds(&pool, &written).await);
+
+        let pool = ReplicaAwarePool::new(primary, None);
+        assert!(finds(&pool, &written).await);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_falls_back_to_primary_after_replica_miss() {
+        let primary = MockUrlRepository::new();
+        let written = create(&primary, "fresh1").await;
+        let pool = ReplicaAwarePool::new(primary, Some(MockUrlRepository::new()));
+        let fallbacks = PRIMARY_FALLBACK_TOTAL.get();
+
+        let url = UrlService::new(pool)
+            .get_url_by_short_code_with_validation(&written)
+            .await
+            .unwrap()
+            .expect("found on the primary");
+        assert_eq!(url.short_code, "fresh1");
+        assert!(PRIMARY_FALLBACK_TOTAL.get() > fallbacks);
+    }
+
+    #[tokio::test]
+    async fn test_forced_lookup_without_recent_miss_stays_on_replica() {
+        let primary = MockUrlRepository::new();
+        let written = create(&primary, "fresh2").await;
+        let pool = ReplicaAwarePool::new(primary, Some(MockUrlRepository::new()));
+        assert!(pool
+            .find_by_short_code(&written, true)
+            .await
+            .unwrap()
+            .is_none());
+
+        pool.replica_misses.insert(
+            written.value().to_string(),
+            Instant::now() - PRIMARY_FALLBACK_WINDOW,
+        );
+        assert!(pool
+            .find_by_short_code(&written, true)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_missing_everywhere_is_not_found() {
+        let pool = ReplicaAwarePool::new(MockUrlRepository::new(), Some(MockUrlRepository::new()));
+        let short_code = ShortCode::new("nothere".to_string()).unwrap();
+
+        let url = UrlService::new(pool)
+            .get_url_by_short_code_with_validation(&short_code)
+            .await
+            .unwrap();
+        assert!(url.is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use super::templates::EmailTemplate;
 use async_trait::async_trait;
 use thiserror::Error;
 
@@ -32,199 +33,99 @@ impl EmailMessage {
         }
     }
 
-    /// Create a password reset email
-    pub fn password_reset(to: String, reset_link: String, expires_in_hours: i64) -> Self {
-        let subject = "Password Reset Request".to_string();
+    /// Create an email from a template, with both text and HTML bodies
+    pub fn from_template<T: EmailTemplate>(to: String, template: &T) -> Self {
+        Self::new_with_html(
+            to,
+            template.subject(),
+            template.render_text(),
+            template.render_html(),
+        )
+    }
+
+    /// Create an email notifying a user they were added to an organization
+    pub fn organization_invitation(
+        to: String,
+        organization_name: &str,
+        invited_by: &str,
+        role: &str,
+    ) -> Self {
+        let subject = format!("You have been added to {}", organization_name);
 
         let body = format!(
-            "You have requested to reset your password.\n\n\
-             Click the link below to reset your password:\n\
-             {}\n\n\
-             This link will expire in {} hours.\n\n\
-             If you did not request this password reset, please ignore this email.\n\n\
+            "{} has added you to the organization \"{}\" as {}.\n\n\
+             URLs shortened for this organization are now shared with you.\n\n\
              Best regards,\n\
              URL Shortener Team",
-            reset_link, expires_in_hours
-        );
-
-        let html_body = format!(
-            r#"
-            <!DOCTYPE html>
-            <html>
-            <head>
-                <meta charset="UTF-8">
-                <title>Password Reset Request</title>
-                <style>
-                    body {{
-                        font-family: Arial, sans-serif;
-                        line-height: 1.6;
-                        color: #333;
-                        max-width: 600px;
-                        margin: 0 auto;
-                        padding: 20px;
-                    }}
-                    .container {{
-                        background-color: #f9f9f9;
-                        border-radius: 5px;
-                        padding: 30px;
-                        border: 1px solid #ddd;
-                    }}
-                    .button {{
-                        display: inline-block;
-                        padding: 12px 24px;
-                        background-color: #007bff;
-                        color: white;
-                        text-decoration: none;
-                        border-radius: 4px;
-                        margin: 20px 0;
-                    }}
-                    .button:hover {{
-                        background-color: #0056b3;
-                    }}
-                    .warning {{
-                        color: #856404;
-                        background-color: #fff3cd;
-                        border: 1px solid #ffeaa7;
-                        padding: 10px;
-                        border-radius: 4px;
-                        margin: 20px 0;
-                    }}
-                    .footer {{
-                        margin-top: 30px;
-                        padding-top: 20px;
-                        border-top: 1px solid #ddd;
-                        font-size: 12px;
-                        color: #666;
-                    }}
-                </style>
-            </head>
-            <body>
-                <div class="container">
-                    <h2>Password Reset Request</h2>
-                    <p>You have requested to reset your password.</p>
-                    <p>Click the button below to reset your password:</p>
-                    <a href="{}" class="button">Reset Password</a>
-                    <div class="warning">
-                        <strong>⚠️ Important:</strong> This link will expire in {} hours.
-                    </div>
-                    <p>If you did not request this password reset, please ignore this email.</p>
-                    <div class="footer">
-                        <p>Best regards,<br>URL Shortener Team</p>
-                        <p><small>This is an automated message. Please do not reply to this email.</small></p>
-                    </div>
-                </div>
-            </body>
-            </html>
-            "#,
-            reset_link, expires_in_hours
+            invited_by, organization_name, role
         );
 
-        Self::new_with_html(to, subject, body, html_body)
+        Self::new(to, subject, body)
     }
 
-    /// Create an account deletion confirmation email
-    pub fn account_deletion_confirmation(
+    /// Create an email telling a user an administrator moved one of their URLs to another
+    /// account, or moved a URL to theirs
+    pub fn url_transferred(
         to: String,
-        confirmation_link: String,
-        expires_in_hours: i64,
+        short_code: &str,
+        original_url: &str,
+        received: bool,
     ) -> Self {
-        let subject = "Confirm Account Deletion".to_string();
+        let (subject, change) = if received {
+            (
+                format!("The short link {} was moved to your account", short_code),
+                "was moved to your account by an administrator. You now own it, along with its click statistics.",
+            )
+        } else {
+            (
+                format!("The short link {} was moved to another account", short_code),
+                "was moved to another account by an administrator. It keeps redirecting but no longer appears in your links.",
+            )
+        };
+
+        let body = format!(
+            "The short link {} to {} {}\n\n\
+             Best regards,\n\
+             URL Shortener Team",
+            short_code, original_url, change
+        );
+
+        Self::new(to, subject, body)
+    }
+
+    /// Create an email with the download link for a personal data export
+    pub fn data_export_ready(to: String, download_link: String, expires_in_hours: i64) -> Self {
+        let subject = "Your data export is ready".to_string();
 
         let body = format!(
-            "You have requested to delete your account.\n\n\
-             This is a permanent action and cannot be undone. All your data including URLs and analytics will be permanently deleted.\n\n\
-             Click the link below to confirm account deletion:\n\
+            "The export of your account data you requested is ready.\n\n\
+             Download it here:\n\
              {}\n\n\
              This link will expire in {} hours.\n\n\
-             If you did not request account deletion, please ignore this email and your account will remain active.\n\n\
+             If you didn't request this, please change your password.\n\n\
              Best regards,\n\
              URL Shortener Team",
-            confirmation_link, expires_in_hours
+            download_link, expires_in_hours
         );
 
-        let html_body = format!(
-            r#"
-            <!DOCTYPE html>
-            <html>
-            <head>
-                <meta charset="UTF-8">
-                <title>Confirm Account Deletion</title>
-                <style>
-                    body {{
-                        font-family: Arial, sans-serif;
-                        line-height: 1.6;
-                        color: #333;
-                        max-width: 600px;
-                        margin: 0 auto;
-                        padding: 20px;
-                    }}
-                    .container {{
-                        background-color: #f9f9f9;
-                        border-radius: 5px;
-                        padding: 30px;
-                        border: 1px solid #ddd;
-                    }}
-                    .button {{
-                        display: inline-block;
-                        padding: 12px 24px;
-                        background-color: #dc3545;
-                        color: white;
-                        text-decoration: none;
-                        border-radius: 4px;
-                        margin: 20px 0;
-                    }}
-                    .button:hover {{
-                        background-color: #c82333;
-                    }}
-                    .warning {{
-                        color: #721c24;
-                        background-color: #f8d7da;
-                        border: 1px solid #f5c6cb;
-                        padding: 15px;
-                        border-radius: 4px;
-                        margin: 20px 0;
-                    }}
-                    .footer {{
-                        margin-top: 30px;
-                        padding-top: 20px;
-                        border-top: 1px solid #ddd;
-                        font-size: 12px;
-                        color: #666;
-                    }}
-                </style>
-            </head>
-            <body>
-                <div class="container">
-                    <h2>⚠️ Confirm Account Deletion</h2>
-                    <p>You have requested to delete your account.</p>
-                    <div class="warning">
-                        <strong>Warning:</strong> This is a permanent action and cannot be undone.<br>
-                        All your data including:
-                        <ul>
-                            <li>Shortened URLs</li>
-                            <li>Analytics data</li>
-                            <li>Profile information</li>
-                        </ul>
-                        will be permanently deleted.
-                    </div>
-                    <p>Click the button below to confirm account deletion:</p>
-                    <a href="{}" class="button">Confirm Account Deletion</a>
-                    <div class="warning">
-                        <strong>⏱️ Important:</strong> This link will expire in {} hours.
-                    </div>
-                    <p>If you did not request account deletion, please ignore this email and your account will remain active.</p>
-                    <div class="footer">
-                        <p>Best regards,<br>URL Shortener Team</p>
-                        <p><small>This is an automated message. Please do not reply to this email.</small></p>
-                    </div>
-                </div>
-            </body>
-            </html>
-            "#,
-            confirmation_link, expires_in_hours
+        Self::new(to, subject, body)
+    }
+
+    /// Create a passwordless login email
+    pub fn magic_link(to: String, login_link: String, expires_in_minutes: i64) -> Self {
+        let subject = "Your login link".to_string();
+
+        let body = format!(
+            "Click the link below to log in to your account:\n\
+             {}\n\n\
+             This link can be used once and will expire in {} minutes.\n\n\
+             If you didn't request this, ignore this email.\n\n\
+             Best regards,\n\
+             URL Shortener Team",
+            login_link, expires_in_minutes
         );
 
-        Self::new_with_html(to, subject, body, html_body)
+        Self::new(to, subject, body)
     }
 }
 
@@ -267,6 +168,7 @@ pub enum EmailError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::infrastructure::email::templates::PasswordResetEmail;
 
     #[test]
     fn test_email_message_creation() {
@@ -283,21 +185,83 @@ mod tests {
     }
 
     #[test]
-    fn test_password_reset_email() {
-        let message = EmailMessage::password_reset(
+    fn test_organization_invitation_email() {
+        let message = EmailMessage::organization_invitation(
+            "member@example.com".to_string(),
+            "Acme Corp",
+            "alice",
+            "admin",
+        );
+
+        assert_eq!(message.subject, "You have been added to Acme Corp");
+        assert!(message.body.contains("alice has added you"));
+        assert!(message.body.contains("as admin"));
+        assert!(message.html_body.is_none());
+    }
+
+    #[test]
+    fn test_url_transferred_email() {
+        let received = EmailMessage::url_transferred(
+            "bob@example.com".to_string(),
+            "abc123",
+            "https://example.com",
+            true,
+        );
+        assert_eq!(received.to, "bob@example.com");
+        assert!(received.subject.contains("to your account"));
+        assert!(received.body.contains("https://example.com"));
+
+        let lost = EmailMessage::url_transferred(
+            "alice@example.com".to_string(),
+            "abc123",
+            "https://example.com",
+            false,
+        );
+        assert!(lost.subject.contains("another account"));
+    }
+
+    #[test]
+    fn test_magic_link_email() {
+        let message = EmailMessage::magic_link(
             "user@example.com".to_string(),
-            "https://example.com/reset?token=abc123".to_string(),
-            24,
+            "https://example.com/auth/magic-link/verify?token=abc123".to_string(),
+            15,
         );
 
+        assert_eq!(message.subject, "Your login link");
+        assert!(message.body.contains("verify?token=abc123"));
+        assert!(message.body.contains("15 minutes"));
+        assert!(message
+            .body
+            .contains("If you didn't request this, ignore this email."));
+    }
+
+    #[test]
+    fn test_data_export_ready_email() {
+        let message = EmailMessage::data_export_ready(
+            "user@example.com".to_string(),
+            "https://example.com/api/profile/export/download/abc123".to_string(),
+            48,
+        );
+
+        assert_eq!(message.subject, "Your data export is ready");
+        assert!(message.body.contains("export/download/abc123"));
+        assert!(message.body.contains("48 hours"));
+        assert!(message.html_body.is_none());
+    }
+
+    #[test]
+    fn test_email_from_template() {
+        let template = PasswordResetEmail {
+            username: "alice".to_string(),
+            reset_url: "https://example.com/reset?token=abc123".to_string(),
+            expires_in_minutes: 60,
+        };
+        let message = EmailMessage::from_template("user@example.com".to_string(), &template);
+
         assert_eq!(message.to, "user@example.com");
         assert_eq!(message.subject, "Password Reset Request");
-        assert!(message.body.contains("reset your password"));
-        assert!(message.body.contains("24 hours"));
-        assert!(message.html_body.is_some());
-
-        let html = message.html_body.unwrap();
-        assert!(html.contains("Reset Password"));
-        assert!(html.contains("24 hours"));
+        assert!(message.body.contains("expire in 1 hour."));
+        assert!(message.html_body.unwrap().contains("Reset Password</a>"));
     }
 }
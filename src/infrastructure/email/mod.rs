@@ -1,5 +1,14 @@
 pub mod email_sender;
+pub mod outbox_email_sender;
 pub mod smtp_email_sender;
+pub mod templates;
 
 pub use email_sender::{EmailError, EmailMessage, EmailSender};
+pub use outbox_email_sender::{
+    drain_outbox, OutboxDrainSummary, OutboxEmailSender, OUTBOX_POLL_INTERVAL,
+};
 pub use smtp_email_sender::SmtpEmailSender;
+pub use templates::{
+    AccountDeletionConfirmEmail, EmailChangeConfirmEmail, EmailTemplate, ExpiringUrlInfo,
+    ExpiryDigestEmail, PasswordResetEmail, WelcomeEmail,
+};
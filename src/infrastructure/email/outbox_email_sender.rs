@@ -0,0 +1,200 @@
+use crate::domain::entities::OutboxEmail;
+use crate::domain::repositories::EmailOutboxRepository;
+use crate::infrastructure::email::{EmailError, EmailMessage, EmailSender};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, warn};
+
+/// Time between two drains of the outbox
+pub const OUTBOX_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Emails sent per drain at most
+const OUTBOX_BATCH_SIZE: usize = 50;
+
+/// How long a claimed email is kept from other pollers
+const OUTBOX_LEASE_MINUTES: i64 = 5;
+
+/// Email sender queueing messages in the outbox instead of sending them
+///
+/// The outbox poller started by [`SmtpEmailSender::spawn_outbox_poller`] sends them later, so a
+/// slow or unreachable mail server no longer holds up requests.
+///
+/// [`SmtpEmailSender::spawn_outbox_poller`]: crate::infrastructure::email::SmtpEmailSender::spawn_outbox_poller
+pub struct OutboxEmailSender {
+    outbox: Arc<dyn EmailOutboxRepository>,
+}
+
+impl OutboxEmailSender {
+    pub fn new(outbox: Arc<dyn EmailOutboxRepository>) -> Self {
+        Self { outbox }
+    }
+}
+
+#[async_trait]
+impl EmailSender for OutboxEmailSender {
+    async fn send_email(&self, message: EmailMessage) -> Result<(), EmailError> {
+        self.outbox
+            .enqueue(&message)
+            .await
+            .map_err(|e| EmailError::Internal(format!("Failed to queue email: {}", e)))?;
+        Ok(())
+    }
+}
+
+impl From<OutboxEmail> for EmailMessage {
+    fn from(email: OutboxEmail) -> Self {
+        Self {
+            to: email.recipient,
+            subject: email.subject,
+            body: email.body_text,
+            html_body: email.body_html,
+        }
+    }
+}
+
+/// Outcome of one drain of the outbox
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutboxDrainSummary {
+    pub sent: usize,
+    /// Failed emails that will be tried again later
+    pub retried: usize,
+    /// Emails that failed their last attempt
+    pub given_up: usize,
+}
+
+/// Send the outbox emails due at `now` with `sender`, recording each result
+pub async fn drain_outbox(
+    outbox: &dyn EmailOutboxRepository,
+    sender: &dyn EmailSender,
+    now: DateTime<Utc>,
+) -> Result<OutboxDrainSummary, Box<dyn std::error::Error + Send + Sync>> {
+    let emails = outbox
+        .claim_due(
+            now,
+            chrono::Duration::minutes(OUTBOX_LEASE_MINUTES),
+            OUTBOX_BATCH_SIZE,
+        )
+        .await?;
+
+    let mut summary = OutboxDrainSummary::default();
+    for email in emails {
+        let (id, recipient, attempt_count) =
+            (email.id, email.recipient.clone(), email.attempt_count);
+        match sender.send_email(email.into()).await {
+            Ok(()) => {
+                outbox.mark_sent(id, Utc::now()).await?;
+                summary.sent += 1;
+            }
+            Err(e) => {
+                let retry_at = OutboxEmail::retry_at(attempt_count + 1, now);
+                outbox
+                    .mark_attempt_failed(id, &e.to_string(), Utc::now(), retry_at)
+                    .await?;
+                match retry_at {
+                    Some(retry_at) => {
+                        warn!(
+                            "Failed to send outbox email {} to {}, retrying at {}: {}",
+                            id, recipient, retry_at, e
+                        );
+                        summary.retried += 1;
+                    }
+                    None => {
+                        error!(
+                            "ALERT: giving up on outbox email {} to {} after {} attempts: {}",
+                            id,
+                            recipient,
+                            attempt_count + 1,
+                            e
+                        );
+                        summary.given_up += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::outbox_email::MAX_OUTBOX_ATTEMPTS;
+    use crate::infrastructure::test_utils::{MockEmailOutboxRepository, MockEmailSender};
+
+    fn message(to: &str) -> EmailMessage {
+        EmailMessage::new(to.to_string(), "Subject".to_string(), "Body".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_queued_emails_are_sent_once() {
+        let outbox = Arc::new(MockEmailOutboxRepository::new());
+        let sender = MockEmailSender::new();
+        let queue = OutboxEmailSender::new(outbox.clone());
+        queue.send_email(message("one@example.com")).await.unwrap();
+        queue.send_email(message("two@example.com")).await.unwrap();
+        assert!(sender.sent().is_empty());
+
+        let summary = drain_outbox(outbox.as_ref(), &sender, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(summary.sent, 2);
+        let recipients: Vec<_> = sender.sent().into_iter().map(|m| m.to).collect();
+        assert_eq!(recipients, ["one@example.com", "two@example.com"]);
+        assert!(outbox.emails().iter().all(|email| email.sent_at.is_some()));
+
+        let summary = drain_outbox(outbox.as_ref(), &sender, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(summary, OutboxDrainSummary::default());
+        assert_eq!(sender.sent().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_emails_back_off_then_give_up() {
+        let outbox = MockEmailOutboxRepository::new();
+        let sender = MockEmailSender::new();
+        sender.set_failing(true);
+        outbox.enqueue(&message("user@example.com")).await.unwrap();
+
+        let mut now = Utc::now();
+        for attempt in 1..MAX_OUTBOX_ATTEMPTS {
+            let summary = drain_outbox(&outbox, &sender, now).await.unwrap();
+            assert_eq!(summary.retried, 1, "attempt {}", attempt);
+
+            // Not retried before its backoff is over
+            let email = outbox.emails().remove(0);
+            assert_eq!(email.attempt_count, attempt);
+            assert_eq!(
+                email.next_attempt_at,
+                OutboxEmail::retry_at(attempt, now).unwrap()
+            );
+            let early = drain_outbox(
+                &outbox,
+                &sender,
+                email.next_attempt_at - chrono::Duration::seconds(1),
+            )
+            .await
+            .unwrap();
+            assert_eq!(early, OutboxDrainSummary::default());
+            now = email.next_attempt_at;
+        }
+
+        let summary = drain_outbox(&outbox, &sender, now).await.unwrap();
+        assert_eq!(summary.given_up, 1);
+        let email = outbox.emails().remove(0);
+        assert_eq!(email.attempt_count, MAX_OUTBOX_ATTEMPTS);
+        assert!(email.failed_at.is_some());
+        assert_eq!(
+            email.error.as_deref(),
+            Some("Email sending failed: mock failure")
+        );
+
+        sender.set_failing(false);
+        let far_future = now + chrono::Duration::days(1);
+        let summary = drain_outbox(&outbox, &sender, far_future).await.unwrap();
+        assert_eq!(summary, OutboxDrainSummary::default());
+    }
+}
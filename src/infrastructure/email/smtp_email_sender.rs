@@ -1,11 +1,16 @@
-use crate::infrastructure::email::{EmailError, EmailMessage, EmailSender};
+use crate::domain::repositories::EmailOutboxRepository;
+use crate::infrastructure::config::env_var;
+use crate::infrastructure::email::{
+    drain_outbox, EmailError, EmailMessage, EmailSender, EmailTemplate, OUTBOX_POLL_INTERVAL,
+};
 use async_trait::async_trait;
 use lettre::{
-    message::{header::ContentType, Mailbox},
+    message::{header::ContentType, Mailbox, MessageBuilder, MultiPart},
     transport::smtp::authentication::Credentials,
     Message, SmtpTransport, Transport,
 };
 use std::str::FromStr;
+use std::sync::Arc;
 
 /// SMTP email sender configuration
 #[derive(Debug, Clone)]
@@ -42,18 +47,16 @@ impl SmtpConfig {
     /// Create SMTP config from environment variables
     pub fn from_env() -> Result<Self, EmailError> {
         Ok(Self {
-            host: std::env::var("SMTP_HOST").unwrap_or_else(|_| "localhost".to_string()),
-            port: std::env::var("SMTP_PORT")
-                .unwrap_or_else(|_| "587".to_string())
+            host: env_var("SMTP_HOST").unwrap_or_else(|| "localhost".to_string()),
+            port: env_var("SMTP_PORT")
+                .unwrap_or_else(|| "587".to_string())
                 .parse()
                 .map_err(|e| EmailError::ConfigError(format!("Invalid SMTP_PORT: {}", e)))?,
-            username: std::env::var("SMTP_USERNAME")
-                .unwrap_or_else(|_| "user@example.com".to_string()),
-            password: std::env::var("SMTP_PASSWORD").unwrap_or_else(|_| "password".to_string()),
-            from_email: std::env::var("SMTP_FROM_EMAIL")
-                .unwrap_or_else(|_| "noreply@example.com".to_string()),
-            from_name: std::env::var("SMTP_FROM_NAME")
-                .unwrap_or_else(|_| "URL Shortener".to_string()),
+            username: env_var("SMTP_USERNAME").unwrap_or_else(|| "user@example.com".to_string()),
+            password: env_var("SMTP_PASSWORD").unwrap_or_else(|| "password".to_string()),
+            from_email: env_var("SMTP_FROM_EMAIL")
+                .unwrap_or_else(|| "noreply@example.com".to_string()),
+            from_name: env_var("SMTP_FROM_NAME").unwrap_or_else(|| "URL Shortener".to_string()),
         })
     }
 }
@@ -75,23 +78,52 @@ impl SmtpEmailSender {
         Ok(Self::new(config))
     }
 
-    /// Build the SMTP transport
-    fn build_transport(&self) -> Result<SmtpTransport, EmailError> {
-        let creds = Credentials::new(self.config.username.clone(), self.config.password.clone());
-
-        let transport = SmtpTransport::relay(&self.config.host)
-            .map_err(|e| EmailError::SmtpError(format!("Failed to create SMTP transport: {}", e)))?
-            .port(self.config.port)
-            .credentials(creds)
-            .build();
+    /// Send the emails queued in `outbox` every [`OUTBOX_POLL_INTERVAL`] until the runtime stops
+    ///
+    /// Failed emails are retried with exponential backoff and given up after their last
+    /// attempt, see [`OutboxEmail::retry_at`](crate::domain::entities::OutboxEmail::retry_at).
+    pub fn spawn_outbox_poller(
+        self: Arc<Self>,
+        outbox: Arc<dyn EmailOutboxRepository>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(OUTBOX_POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                match drain_outbox(outbox.as_ref(), self.as_ref(), chrono::Utc::now()).await {
+                    Ok(summary) if summary.sent + summary.retried + summary.given_up > 0 => {
+                        tracing::info!(
+                            "Email outbox: {} sent, {} to retry, {} given up",
+                            summary.sent,
+                            summary.retried,
+                            summary.given_up
+                        );
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Failed to drain the email outbox: {}", e),
+                }
+            }
+        })
+    }
 
-        Ok(transport)
+    /// Render both versions of `template` and send them as a multipart email
+    pub async fn send_templated<T: EmailTemplate>(
+        &self,
+        to: String,
+        template: &T,
+    ) -> Result<(), EmailError> {
+        let body =
+            MultiPart::alternative_plain_html(template.render_text(), template.render_html());
+        self.deliver(&to, template.subject(), |builder| builder.multipart(body))
     }
-}
 
-#[async_trait]
-impl EmailSender for SmtpEmailSender {
-    async fn send_email(&self, message: EmailMessage) -> Result<(), EmailError> {
+    /// Send an email to `to`, letting `body` add the body to the message
+    fn deliver(
+        &self,
+        to: &str,
+        subject: String,
+        body: impl FnOnce(MessageBuilder) -> Result<Message, lettre::error::Error>,
+    ) -> Result<(), EmailError> {
         // Parse from mailbox
         let from_mailbox = Mailbox::from_str(&format!(
             "{} <{}>",
@@ -100,23 +132,16 @@ impl EmailSender for SmtpEmailSender {
         .map_err(|e| EmailError::InvalidEmail(format!("Invalid from email: {}", e)))?;
 
         // Parse to mailbox
-        let to_mailbox = Mailbox::from_str(&message.to)
+        let to_mailbox = Mailbox::from_str(to)
             .map_err(|e| EmailError::InvalidEmail(format!("Invalid to email: {}", e)))?;
 
         // Build email message
-        let email_builder = Message::builder()
-            .from(from_mailbox)
-            .to(to_mailbox)
-            .subject(message.subject);
-
-        // Add body (HTML if available, otherwise plain text)
-        let email = if let Some(html_body) = message.html_body {
-            email_builder.header(ContentType::TEXT_HTML).body(html_body)
-        } else {
-            email_builder
-                .header(ContentType::TEXT_PLAIN)
-                .body(message.body)
-        }
+        let email = body(
+            Message::builder()
+                .from(from_mailbox)
+                .to(to_mailbox)
+                .subject(subject),
+        )
         .map_err(|e| EmailError::SendingFailed(format!("Failed to build email: {}", e)))?;
 
         // Build transport and send
@@ -126,9 +151,63 @@ impl EmailSender for SmtpEmailSender {
             .send(&email)
             .map_err(|e| EmailError::SendingFailed(format!("Failed to send email: {}", e)))?;
 
-        tracing::info!("Email sent successfully to: {}", message.to);
+        tracing::info!("Email sent successfully to: {}", to);
         Ok(())
     }
+
+    /// Build the SMTP transport
+    fn build_transport(&self) -> Result<SmtpTransport, EmailError> {
+        let creds = Credentials::new(self.config.username.clone(), self.config.password.clone());
+
+        let transport = SmtpTransport::relay(&self.config.host)
+            .map_err(|e| EmailError::SmtpError(format!("Failed to create SMTP transport: {}", e)))?
+            .port(self.config.port)
+            .credentials(creds)
+            .build();
+
+        Ok(transport)
+    }
+}
+
+#[async_trait]
+impl EmailSender for SmtpEmailSender {
+    async fn send_email(&self, message: EmailMessage) -> Result<(), EmailError> {
+        // Emails with an HTML version were rendered from a template before they were queued
+        match &message.html_body {
+            Some(html) => {
+                let rendered = RenderedEmail {
+                    subject: &message.subject,
+                    text: &message.body,
+                    html,
+                };
+                self.send_templated(message.to.clone(), &rendered).await
+            }
+            None => self.deliver(&message.to, message.subject, |builder| {
+                builder.header(ContentType::TEXT_PLAIN).body(message.body)
+            }),
+        }
+    }
+}
+
+/// An email whose template was rendered when it was queued in the outbox
+struct RenderedEmail<'a> {
+    subject: &'a str,
+    text: &'a str,
+    html: &'a str,
+}
+
+impl EmailTemplate for RenderedEmail<'_> {
+    fn subject(&self) -> String {
+        self.subject.to_string()
+    }
+
+    fn render_html(&self) -> String {
+        self.html.to_string()
+    }
+
+    fn render_text(&self) -> String {
+        self.text.to_string()
+    }
 }
 
 #[cfg(test)]
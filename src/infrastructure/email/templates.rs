@@ -0,0 +1,402 @@
+use askama::Template;
+
+/// An email with HTML and plain text versions of the same content
+///
+/// Templates live in `templates/email/`, one `.html` and one `.txt` file per email.
+pub trait EmailTemplate {
+    /// Subject line of the email
+    fn subject(&self) -> String;
+
+    /// HTML version of the body; values are escaped
+    fn render_html(&self) -> String;
+
+    /// Plain text version of the body
+    fn render_text(&self) -> String;
+}
+
+/// Render an email template
+///
+/// Email templates only display strings and numbers, which cannot fail to format.
+fn render(template: &impl Template) -> String {
+    template
+        .render()
+        .expect("email templates only display strings and numbers")
+}
+
+/// Email greeting a new user and asking them to verify their address
+#[derive(Debug, Clone)]
+pub struct WelcomeEmail {
+    pub username: String,
+    pub verify_url: String,
+}
+
+#[derive(Template)]
+#[template(path = "email/welcome.html")]
+struct WelcomeHtml<'a> {
+    email: &'a WelcomeEmail,
+}
+
+#[derive(Template)]
+#[template(path = "email/welcome.txt")]
+struct WelcomeText<'a> {
+    email: &'a WelcomeEmail,
+}
+
+impl EmailTemplate for WelcomeEmail {
+    fn subject(&self) -> String {
+        "Welcome to URL Shortener".to_string()
+    }
+
+    fn render_html(&self) -> String {
+        render(&WelcomeHtml { email: self })
+    }
+
+    fn render_text(&self) -> String {
+        render(&WelcomeText { email: self })
+    }
+}
+
+/// Email with the link for resetting a forgotten password
+#[derive(Debug, Clone)]
+pub struct PasswordResetEmail {
+    pub username: String,
+    pub reset_url: String,
+    pub expires_in_minutes: u32,
+}
+
+impl PasswordResetEmail {
+    /// How long the link stays valid, in whole hours when possible
+    fn expires_in(&self) -> String {
+        match self.expires_in_minutes {
+            60 => "1 hour".to_string(),
+            m if m % 60 == 0 => format!("{} hours", m / 60),
+            1 => "1 minute".to_string(),
+            m => format!("{} minutes", m),
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "email/password_reset.html")]
+struct PasswordResetHtml<'a> {
+    email: &'a PasswordResetEmail,
+}
+
+#[derive(Template)]
+#[template(path = "email/password_reset.txt")]
+struct PasswordResetText<'a> {
+    email: &'a PasswordResetEmail,
+}
+
+impl EmailTemplate for PasswordResetEmail {
+    fn subject(&self) -> String {
+        "Password Reset Request".to_string()
+    }
+
+    fn render_html(&self) -> String {
+        render(&PasswordResetHtml { email: self })
+    }
+
+    fn render_text(&self) -> String {
+        render(&PasswordResetText { email: self })
+    }
+}
+
+/// A URL listed in the expiry digest email
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiringUrlInfo {
+    pub short_code: String,
+    pub original_url: String,
+    /// Whole days left before the URL expires
+    pub days_remaining: i64,
+    /// Link to the UI with the URL's expiration ready to be extended
+    pub extend_link: String,
+}
+
+impl ExpiringUrlInfo {
+    fn expires_in(&self) -> String {
+        match self.days_remaining {
+            d if d < 1 => "less than a day".to_string(),
+            1 => "1 day".to_string(),
+            d => format!("{} days", d),
+        }
+    }
+}
+
+/// Weekly digest of a user's URLs about to expire
+#[derive(Debug, Clone)]
+pub struct ExpiryDigestEmail {
+    pub username: String,
+    pub urls: Vec<ExpiringUrlInfo>,
+}
+
+/// URLs of the digest expiring the same number of days from now
+struct ExpiryGroup<'a> {
+    expires_in: String,
+    urls: Vec<&'a ExpiringUrlInfo>,
+}
+
+impl ExpiryDigestEmail {
+    /// URLs grouped by days remaining, soonest first
+    fn groups(&self) -> Vec<ExpiryGroup<'_>> {
+        let mut urls: Vec<&ExpiringUrlInfo> = self.urls.iter().collect();
+        urls.sort_by_key(|url| url.days_remaining);
+        urls.chunk_by(|a, b| a.days_remaining == b.days_remaining)
+            .map(|group| ExpiryGroup {
+                expires_in: group[0].expires_in(),
+                urls: group.to_vec(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Template)]
+#[template(path = "email/expiry_digest.html")]
+struct ExpiryDigestHtml<'a> {
+    email: &'a ExpiryDigestEmail,
+}
+
+#[derive(Template)]
+#[template(path = "email/expiry_digest.txt")]
+struct ExpiryDigestText<'a> {
+    email: &'a ExpiryDigestEmail,
+}
+
+impl EmailTemplate for ExpiryDigestEmail {
+    fn subject(&self) -> String {
+        match self.urls.len() {
+            1 => "1 of your URLs expires soon".to_string(),
+            n => format!("{} of your URLs expire soon", n),
+        }
+    }
+
+    fn render_html(&self) -> String {
+        render(&ExpiryDigestHtml { email: self })
+    }
+
+    fn render_text(&self) -> String {
+        render(&ExpiryDigestText { email: self })
+    }
+}
+
+/// Email asking a user to confirm, or cancel, the deletion of their account
+#[derive(Debug, Clone)]
+pub struct AccountDeletionConfirmEmail {
+    pub username: String,
+    pub confirm_url: String,
+    pub cancel_url: String,
+    pub expires_in_hours: u32,
+}
+
+#[derive(Template)]
+#[template(path = "email/account_deletion_confirm.html")]
+struct AccountDeletionConfirmHtml<'a> {
+    email: &'a AccountDeletionConfirmEmail,
+}
+
+#[derive(Template)]
+#[template(path = "email/account_deletion_confirm.txt")]
+struct AccountDeletionConfirmText<'a> {
+    email: &'a AccountDeletionConfirmEmail,
+}
+
+impl EmailTemplate for AccountDeletionConfirmEmail {
+    fn subject(&self) -> String {
+        "Confirm Account Deletion".to_string()
+    }
+
+    fn render_html(&self) -> String {
+        render(&AccountDeletionConfirmHtml { email: self })
+    }
+
+    fn render_text(&self) -> String {
+        render(&AccountDeletionConfirmText { email: self })
+    }
+}
+
+/// Email asking a user to confirm one side of a change of their email address
+///
+/// Sent to both the current and the new address, each with its own link.
+#[derive(Debug, Clone)]
+pub struct EmailChangeConfirmEmail {
+    pub username: String,
+    /// Address the account is changing to
+    pub new_email: String,
+    pub confirm_url: String,
+    pub expires_in_hours: u32,
+    /// Whether this email goes to the new address rather than the current one
+    pub to_new_address: bool,
+}
+
+#[derive(Template)]
+#[template(path = "email/email_change_confirm.html")]
+struct EmailChangeConfirmHtml<'a> {
+    email: &'a EmailChangeConfirmEmail,
+}
+
+#[derive(Template)]
+#[template(path = "email/email_change_confirm.txt")]
+struct EmailChangeConfirmText<'a> {
+    email: &'a EmailChangeConfirmEmail,
+}
+
+impl EmailTemplate for EmailChangeConfirmEmail {
+    fn subject(&self) -> String {
+        if self.to_new_address {
+            "Confirm your new address".to_string()
+        } else {
+            "Confirm you're changing away from this address".to_string()
+        }
+    }
+
+    fn render_html(&self) -> String {
+        render(&EmailChangeConfirmHtml { email: self })
+    }
+
+    fn render_text(&self) -> String {
+        render(&EmailChangeConfirmText { email: self })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_welcome_email() {
+        let email = WelcomeEmail {
+            username: "alice".to_string(),
+            verify_url: "https://short.ly/verify?token=abc".to_string(),
+        };
+
+        let html = email.render_html();
+        assert!(html.contains("<h2>Welcome, alice!</h2>"));
+        assert!(html.contains(r#"<a href="https://short.ly/verify?token=abc" class="button">"#));
+        assert!(email
+            .render_text()
+            .contains("started:\nhttps://short.ly/verify?token=abc\n"));
+    }
+
+    #[test]
+    fn test_password_reset_email() {
+        let email = PasswordResetEmail {
+            username: "alice".to_string(),
+            reset_url: "https://example.com/reset?token=abc123".to_string(),
+            expires_in_minutes: 24 * 60,
+        };
+
+        assert_eq!(email.subject(), "Password Reset Request");
+        let html = email.render_html();
+        assert!(html.contains("<title>Password Reset Request</title>"));
+        assert!(html.contains(
+            r#"<a href="https://example.com/reset?token=abc123" class="button">Reset Password</a>"#
+        ));
+        assert!(html.contains("This link will expire in 24 hours."));
+
+        let text = email.render_text();
+        assert!(text.starts_with("Hi alice,\n"));
+        assert!(text.contains("https://example.com/reset?token=abc123\n"));
+        assert!(!text.contains('<'));
+
+        let short = PasswordResetEmail {
+            expires_in_minutes: 30,
+            ..email
+        };
+        assert!(short.render_text().contains("expire in 30 minutes."));
+    }
+
+    #[test]
+    fn test_html_escapes_values() {
+        let email = WelcomeEmail {
+            username: "<script>alert(1)</script>".to_string(),
+            verify_url: "https://short.ly/verify?a=1&b=2".to_string(),
+        };
+
+        let html = email.render_html();
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("verify?a=1&amp;b=2"));
+        // Plain text is sent as is
+        assert!(email.render_text().contains("<script>alert(1)</script>"));
+    }
+
+    #[test]
+    fn test_expiry_digest_email() {
+        let url = |code: &str, days| ExpiringUrlInfo {
+            short_code: code.to_string(),
+            original_url: format!("https://example.com/?a=1&b={}", code),
+            days_remaining: days,
+            extend_link: format!("https://short.ly/urls/{}/extend", code),
+        };
+        let email = ExpiryDigestEmail {
+            username: "alice".to_string(),
+            urls: vec![url("later", 9), url("soon1", 2), url("soon2", 2)],
+        };
+
+        assert_eq!(email.subject(), "3 of your URLs expire soon");
+        let text = email.render_text();
+        let soon = text.find("Expiring in 2 days").unwrap();
+        let later = text.find("Expiring in 9 days").unwrap();
+        assert!(soon < later);
+        assert_eq!(text.matches("Expiring in 2 days").count(), 1);
+        assert!(text.contains(
+            "soon1 | https://example.com/?a=1&b=soon1 | 2 days | https://short.ly/urls/soon1/extend\n"
+        ));
+
+        let html = email.render_html();
+        assert!(html.contains("<h3>Expiring in 2 days</h3>"));
+        assert!(html.contains("https://example.com/?a=1&amp;b=later"));
+        assert!(html.contains(r#"<a href="https://short.ly/urls/later/extend">Extend</a>"#));
+        assert!(html.contains("You can turn off this digest"));
+    }
+
+    #[test]
+    fn test_account_deletion_confirm_email() {
+        let email = AccountDeletionConfirmEmail {
+            username: "alice".to_string(),
+            confirm_url: "https://short.ly/account/deletion/confirm?token=abc".to_string(),
+            cancel_url: "https://short.ly/account/deletion/cancel?token=abc".to_string(),
+            expires_in_hours: 24,
+        };
+
+        let html = email.render_html();
+        assert!(html.contains(
+            r#"<a href="https://short.ly/account/deletion/confirm?token=abc" class="button danger">"#
+        ));
+        assert!(html.contains(
+            r#"<a href="https://short.ly/account/deletion/cancel?token=abc">Keep my account</a>"#
+        ));
+        let text = email.render_text();
+        assert!(text.contains("expire in 24 hours."));
+        assert!(text.contains("https://short.ly/account/deletion/cancel?token=abc\n"));
+    }
+
+    #[test]
+    fn test_email_change_confirm_email() {
+        let email = EmailChangeConfirmEmail {
+            username: "alice".to_string(),
+            new_email: "alice@new.example".to_string(),
+            confirm_url: "https://short.ly/profile/change-email/confirm-old?token=abc".to_string(),
+            expires_in_hours: 24,
+            to_new_address: false,
+        };
+
+        assert_eq!(
+            email.subject(),
+            "Confirm you're changing away from this address"
+        );
+        assert!(email.render_html().contains(
+            r#"<a href="https://short.ly/profile/change-email/confirm-old?token=abc" class="button">"#
+        ));
+        let text = email.render_text();
+        assert!(text.contains("change the email address of your account to alice@new.example."));
+        assert!(text.contains("within 24 hours."));
+
+        let to_new = EmailChangeConfirmEmail {
+            to_new_address: true,
+            ..email
+        };
+        assert_eq!(to_new.subject(), "Confirm your new address");
+        assert!(to_new.render_text().contains("confirm your new address:\n"));
+    }
+}
@@ -0,0 +1,61 @@
+use crate::domain::entities::User;
+use crate::infrastructure::config::{AppConfig, Environment};
+use sentry::{ClientInitGuard, ClientOptions};
+
+/// Start reporting errors to Sentry when `sentry_dsn` is configured
+///
+/// Events are sent until the returned guard is dropped, which flushes the ones still queued.
+pub fn init_sentry(config: &AppConfig) -> Option<ClientInitGuard> {
+    let dsn = config.sentry_dsn.as_deref()?;
+    Some(sentry::init(client_options(dsn, &config.environment)))
+}
+
+/// Client options tagging events with the environment and the crate version as release
+///
+/// A DSN failing to parse leaves the client disabled; `AppConfig::validate` rejects those.
+pub fn client_options(dsn: &str, environment: &Environment) -> ClientOptions {
+    ClientOptions {
+        dsn: dsn.parse().ok(),
+        release: Some(env!("CARGO_PKG_VERSION").into()),
+        environment: Some(environment.name().into()),
+        ..Default::default()
+    }
+}
+
+/// Attach the authenticated user to the Sentry events of the current request
+///
+/// Requests run on their own hub (see `sentry_error_middleware`), so the user does not leak
+/// into the events of other requests. Does nothing when Sentry is not configured.
+pub fn set_sentry_user(user: &User) {
+    sentry::configure_scope(|scope| {
+        scope.set_user(Some(sentry::User {
+            id: Some(user.id.to_string()),
+            email: Some(user.email.clone()),
+            ..Default::default()
+        }))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_options() {
+        let options = client_options(
+            "https://public@sentry.example.com/42",
+            &Environment::Staging,
+        );
+        assert_eq!(options.dsn.unwrap().project_id().value(), "42");
+        assert_eq!(options.environment.as_deref(), Some("staging"));
+        assert_eq!(options.release.as_deref(), Some(env!("CARGO_PKG_VERSION")));
+        assert!(client_options("not a dsn", &Environment::Test)
+            .dsn
+            .is_none());
+    }
+
+    #[test]
+    fn test_init_sentry_needs_a_dsn() {
+        assert!(init_sentry(&AppConfig::default()).is_none());
+    }
+}
@@ -0,0 +1,112 @@
+use crate::domain::events::{DomainEvent, EventBus, EventBusError};
+use std::future::Future;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+/// Events a subscriber may fall behind by before it misses some
+pub const DEFAULT_EVENT_BUS_CAPACITY: usize = 1024;
+
+/// Event bus delivering events to subscribers within this process
+#[derive(Clone)]
+pub struct InProcessEventBus {
+    sender: broadcast::Sender<DomainEvent>,
+}
+
+impl Default for InProcessEventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_BUS_CAPACITY)
+    }
+}
+
+impl InProcessEventBus {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// Receive every event published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Call `handler` with every event published from now on, in a background task
+    ///
+    /// A subscriber falling more than the bus capacity behind skips the events it missed.
+    pub fn spawn_subscriber<F, Fut>(&self, name: &'static str, handler: F) -> JoinHandle<()>
+    where
+        F: Fn(DomainEvent) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        let mut receiver = self.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => handler(event).await,
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!("Event subscriber {} missed {} events", name, missed);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+impl EventBus for InProcessEventBus {
+    fn publish(&self, event: DomainEvent) -> Result<(), EventBusError> {
+        let name = event.name();
+        self.sender
+            .send(event)
+            .map(|_| ())
+            .map_err(|_| EventBusError::NoSubscribers(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Url, UrlStatus};
+    use std::sync::{Arc, Mutex};
+    use tokio::sync::Notify;
+
+    fn url() -> Url {
+        Url::new_with_timestamp(
+            1,
+            "abc123".to_string(),
+            "https://example.com".to_string(),
+            None,
+            None,
+            UrlStatus::Active,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_publish_without_subscribers() {
+        let bus = InProcessEventBus::default();
+        let result = bus.publish(DomainEvent::UrlCreated { url: url() });
+        assert!(matches!(
+            result,
+            Err(EventBusError::NoSubscribers("url_created"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_spawned_subscriber_receives_events() {
+        let bus = InProcessEventBus::default();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let delivered = Arc::new(Notify::new());
+        let (events, notify) = (received.clone(), delivered.clone());
+        bus.spawn_subscriber("test", move |event| {
+            let (events, notify) = (events.clone(), notify.clone());
+            async move {
+                events.lock().unwrap().push(event.name());
+                notify.notify_one();
+            }
+        });
+
+        bus.publish(DomainEvent::UrlExpired { url: url() }).unwrap();
+        delivered.notified().await;
+        assert_eq!(*received.lock().unwrap(), vec!["url_expired"]);
+    }
+}
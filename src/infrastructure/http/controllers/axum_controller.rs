@@ -73,7 +73,7 @@ impl AxumUrlController {
         // Find the URL
         match app_state
             .url_repository
-            .find_by_short_code(&short_code)
+            .find_by_short_code(&short_code, false)
             .await
         {
             Ok(Some(url)) => {
@@ -0,0 +1,119 @@
+use crate::application::dto::ErrorResponse;
+use crate::domain::entities::User;
+use crate::infrastructure::error_tracking::set_sentry_user;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState};
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, StatusCode},
+    Json,
+};
+use tracing::{debug, warn};
+
+/// User authenticated by the `Authorization: Bearer <token>` header of the request
+///
+/// Requests without a bearer token get `401 UNAUTHORIZED`; tokens failing verification are
+/// rejected like [`token_error_response`] describes.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser(pub User);
+
+/// User authenticated by the request, if it carries a valid bearer token
+///
+/// For endpoints serving anonymous clients too: a missing or invalid token is never rejected.
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct OptionalAuthenticatedUser(pub Option<User>);
+
+/// Bearer token of the request's `Authorization` header, if any
+fn bearer_token(parts: &Parts) -> Option<&str> {
+    parts
+        .headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .filter(|t| !t.is_empty())
+}
+
+fn missing_token_response() -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: "UNAUTHORIZED".to_string(),
+        message: "Missing or invalid Authorization header".to_string(),
+        status_code: StatusCode::UNAUTHORIZED.as_u16(),
+    };
+    (StatusCode::UNAUTHORIZED, Json(error_response))
+}
+
+#[async_trait]
+impl FromRequestParts<ConcreteAppState> for AuthenticatedUser {
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        app_state: &ConcreteAppState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = bearer_token(parts).ok_or_else(missing_token_response)?;
+        let user = app_state
+            .auth_service
+            .verify_token(token)
+            .await
+            .map_err(|e| {
+                warn!("Token verification failed: {}", e);
+                token_error_response(&e)
+            })?;
+        set_sentry_user(&user);
+        Ok(Self(user))
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<ConcreteAppState> for OptionalAuthenticatedUser {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        app_state: &ConcreteAppState,
+    ) -> Result<Self, Self::Rejection> {
+        let Some(token) = bearer_token(parts) else {
+            return Ok(Self(None));
+        };
+        match app_state.auth_service.verify_token(token).await {
+            Ok(user) => {
+                set_sentry_user(&user);
+                Ok(Self(Some(user)))
+            }
+            Err(e) => {
+                debug!("Ignoring bearer token failing verification: {}", e);
+                Ok(Self(None))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    fn parts(authorization: Option<&str>) -> Parts {
+        let mut builder = Request::builder().uri("/shorten");
+        if let Some(value) = authorization {
+            builder = builder.header(header::AUTHORIZATION, value);
+        }
+        builder.body(()).unwrap().into_parts().0
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        assert_eq!(bearer_token(&parts(Some("Bearer abc"))), Some("abc"));
+        assert_eq!(bearer_token(&parts(Some("Bearer "))), None);
+        assert_eq!(bearer_token(&parts(Some("Basic abc"))), None);
+        assert_eq!(bearer_token(&parts(None)), None);
+    }
+
+    #[test]
+    fn test_missing_token_response() {
+        let (status, Json(body)) = missing_token_response();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body.error, "UNAUTHORIZED");
+    }
+}
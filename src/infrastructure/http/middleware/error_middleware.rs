@@ -1,11 +1,15 @@
 #![allow(dead_code)]
 use axum::{
-    extract::rejection::JsonRejection,
-    http::StatusCode,
+    extract::{rejection::JsonRejection, MatchedPath, Request},
+    http::{Method, StatusCode},
+    middleware::Next,
     response::{IntoResponse, Response},
     Json,
 };
+use sentry::protocol::{Event, Level};
+use sentry::{Hub, SentryFutureExt};
 use serde_json::json;
+use std::sync::Arc;
 
 /// Custom error response for middleware
 #[derive(Debug)]
@@ -51,3 +55,43 @@ pub fn handle_json_error(err: JsonRejection) -> MiddlewareError {
         },
     }
 }
+
+/// Report responses with a 5xx status to Sentry
+///
+/// Each request runs on a hub of its own so that the user set during authentication only
+/// tags the events of that request. 4xx responses are client mistakes and are not reported.
+pub async fn sentry_error_middleware(request: Request, next: Next) -> Response {
+    if Hub::main().client().is_none() {
+        return next.run(request).await;
+    }
+
+    let method = request.method().clone();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let hub = Arc::new(Hub::new_from_top(Hub::main()));
+    let response = next.run(request).bind_hub(hub.clone()).await;
+
+    if response.status().is_server_error() {
+        hub.capture_event(server_error_event(&method, &path, response.status()));
+    }
+    response
+}
+
+/// Sentry event of a request answered with a server error
+///
+/// The matched route is reported instead of the actual path, which may hold tokens.
+fn server_error_event(method: &Method, path: &str, status: StatusCode) -> Event<'static> {
+    let mut event = Event {
+        level: Level::Error,
+        message: Some(format!("{} {} responded with {}", method, path, status)),
+        ..Default::default()
+    };
+    event.tags.insert("http.method".into(), method.to_string());
+    event
+        .tags
+        .insert("http.status_code".into(), status.as_u16().to_string());
+    event
+}
@@ -0,0 +1,656 @@
+use crate::application::dto::ErrorResponse;
+use crate::domain::repositories::UserRepository;
+use crate::domain::services::AuthService;
+use async_trait::async_trait;
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, HeaderMap, HeaderValue, Request, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use dashmap::DashMap;
+use redis::aio::ConnectionManager;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tower::{Layer, Service};
+use tracing::warn;
+use uuid::Uuid;
+
+/// Request header carrying the client's idempotency key, a UUID
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response header set on responses replayed from the store
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "x-idempotent-replayed";
+
+/// How long a response is replayed for its idempotency key
+pub const IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long a request may hold its key before another request may take over
+const LOCK_TTL: Duration = Duration::from_secs(60);
+
+/// Largest response body stored for replay
+const MAX_STORED_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Prefix of the Redis keys holding stored responses
+const KEY_PREFIX: &str = "idempotency:";
+
+/// Prefix of the Redis keys locking an idempotency key while its request runs
+const LOCK_PREFIX: &str = "idempotency_lock:";
+
+/// Errors of the store keeping responses by idempotency key
+#[derive(Error, Debug)]
+pub enum IdempotencyError {
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+
+    #[error("Stored response is corrupt: {0}")]
+    Corrupt(String),
+}
+
+/// Response kept to be replayed to retries of a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+/// Keeps responses by idempotency key and locks keys while their request runs
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Response stored for `key`, if it has not expired
+    async fn get(&self, key: &str) -> Result<Option<StoredResponse>, IdempotencyError>;
+
+    /// Lock `key` for `ttl`, returning whether no other request held it
+    async fn try_lock(&self, key: &str, ttl: Duration) -> Result<bool, IdempotencyError>;
+
+    /// Store the response of `key` for `ttl` and release its lock
+    async fn complete(
+        &self,
+        key: &str,
+        response: &StoredResponse,
+        ttl: Duration,
+    ) -> Result<(), IdempotencyError>;
+
+    /// Release the lock of `key` without storing a response, so the request can be retried
+    async fn unlock(&self, key: &str) -> Result<(), IdempotencyError>;
+}
+
+/// Stored responses as expiring Redis hashes, shared by every instance
+#[derive(Clone)]
+pub struct RedisIdempotencyStore {
+    connection: ConnectionManager,
+}
+
+impl RedisIdempotencyStore {
+    /// Connect to the Redis server at `url`
+    pub async fn connect(url: &str) -> Result<Self, IdempotencyError> {
+        let client = redis::Client::open(url)?;
+        let connection = ConnectionManager::new(client).await?;
+        Ok(Self { connection })
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for RedisIdempotencyStore {
+    async fn get(&self, key: &str) -> Result<Option<StoredResponse>, IdempotencyError> {
+        let mut connection = self.connection.clone();
+        let mut fields: HashMap<String, Vec<u8>> = redis::cmd("HGETALL")
+            .arg(format!("{}{}", KEY_PREFIX, key))
+            .query_async(&mut connection)
+            .await?;
+        if fields.is_empty() {
+            return Ok(None);
+        }
+
+        let status = fields
+            .get("status")
+            .and_then(|status| std::str::from_utf8(status).ok())
+            .and_then(|status| status.parse().ok())
+            .ok_or_else(|| IdempotencyError::Corrupt(format!("no status for {}", key)))?;
+        let content_type = fields
+            .remove("content_type")
+            .filter(|content_type| !content_type.is_empty())
+            .map(|content_type| String::from_utf8_lossy(&content_type).into_owned());
+        Ok(Some(StoredResponse {
+            status,
+            content_type,
+            body: fields.remove("body").unwrap_or_default(),
+        }))
+    }
+
+    async fn try_lock(&self, key: &str, ttl: Duration) -> Result<bool, IdempotencyError> {
+        // SET ... NX replies nil when another request holds the lock
+        let mut connection = self.connection.clone();
+        let reply: Option<String> = redis::cmd("SET")
+            .arg(format!("{}{}", LOCK_PREFIX, key))
+            .arg(1)
+            .arg("PX")
+            .arg(ttl.as_millis().max(1) as u64)
+            .arg("NX")
+            .query_async(&mut connection)
+            .await?;
+        Ok(reply.is_some())
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        response: &StoredResponse,
+        ttl: Duration,
+    ) -> Result<(), IdempotencyError> {
+        let stored_key = format!("{}{}", KEY_PREFIX, key);
+        let mut connection = self.connection.clone();
+        redis::pipe()
+            .atomic()
+            .cmd("HSET")
+            .arg(&stored_key)
+            .arg("status")
+            .arg(response.status)
+            .arg("content_type")
+            .arg(response.content_type.as_deref().unwrap_or_default())
+            .arg("body")
+            .arg(response.body.as_slice())
+            .ignore()
+            .cmd("PEXPIRE")
+            .arg(&stored_key)
+            .arg(ttl.as_millis().max(1) as u64)
+            .ignore()
+            .cmd("DEL")
+            .arg(format!("{}{}", LOCK_PREFIX, key))
+            .ignore()
+            .query_async::<_, ()>(&mut connection)
+            .await?;
+        Ok(())
+    }
+
+    async fn unlock(&self, key: &str) -> Result<(), IdempotencyError> {
+        let mut connection = self.connection.clone();
+        redis::cmd("DEL")
+            .arg(format!("{}{}", LOCK_PREFIX, key))
+            .query_async::<_, ()>(&mut connection)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Stored responses in process memory, for single instances without Redis
+#[derive(Default)]
+pub struct InMemoryIdempotencyStore {
+    responses: DashMap<String, (StoredResponse, Instant)>,
+    locks: DashMap<String, Instant>,
+}
+
+impl InMemoryIdempotencyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn get(&self, key: &str) -> Result<Option<StoredResponse>, IdempotencyError> {
+        let now = Instant::now();
+        self.responses
+            .remove_if(key, |_, (_, expires_at)| *expires_at <= now);
+        Ok(self.responses.get(key).map(|entry| entry.value().0.clone()))
+    }
+
+    async fn try_lock(&self, key: &str, ttl: Duration) -> Result<bool, IdempotencyError> {
+        let now = Instant::now();
+        let mut lock = self.locks.entry(key.to_string()).or_insert(now);
+        if *lock > now {
+            return Ok(false);
+        }
+        *lock = now + ttl;
+        Ok(true)
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        response: &StoredResponse,
+        ttl: Duration,
+    ) -> Result<(), IdempotencyError> {
+        self.responses
+            .insert(key.to_string(), (response.clone(), Instant::now() + ttl));
+        self.locks.remove(key);
+        Ok(())
+    }
+
+    async fn unlock(&self, key: &str) -> Result<(), IdempotencyError> {
+        self.locks.remove(key);
+        Ok(())
+    }
+}
+
+/// Works out which user sent a request, so users never share idempotency keys
+#[async_trait]
+pub trait RequestUserResolver: Send + Sync {
+    /// Id of the user authenticated by `headers`; `None` for anonymous requests
+    async fn user_id(&self, headers: &HeaderMap) -> Option<i32>;
+}
+
+#[async_trait]
+impl<U> RequestUserResolver for AuthService<U>
+where
+    U: UserRepository + Clone + Send + Sync,
+{
+    async fn user_id(&self, headers: &HeaderMap) -> Option<i32> {
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|h| h.strip_prefix("Bearer "))
+            .filter(|t| !t.is_empty())?;
+        self.verify_token(token).await.ok().map(|user| user.id)
+    }
+}
+
+/// Key of a request in the store: method, path, user and idempotency key, hashed
+fn cache_key(method: &str, path: &str, user_id: Option<i32>, idempotency_key: &Uuid) -> String {
+    let user = user_id.map_or_else(|| "anonymous".to_string(), |id| id.to_string());
+    format!(
+        "{:x}",
+        Sha256::digest(format!(
+            "{}\n{}\n{}\n{}",
+            method, path, user, idempotency_key
+        ))
+    )
+}
+
+fn error_response(status: StatusCode, error: &str, message: &str) -> Response {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message: message.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response)).into_response()
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let mut response = Response::new(Body::from(stored.body));
+    *response.status_mut() = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let headers = response.headers_mut();
+    if let Some(content_type) = stored
+        .content_type
+        .and_then(|content_type| HeaderValue::from_str(&content_type).ok())
+    {
+        headers.insert(header::CONTENT_TYPE, content_type);
+    }
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+/// Whether a client may retry after this status, so the response must not be replayed
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Replays the stored response to retries of a request sent with an `Idempotency-Key`
+///
+/// Keys are scoped to the method, path and user of the request. While the first request with
+/// a key runs, others with the same key get `409 IDEMPOTENCY_KEY_IN_USE` instead of running
+/// it again. Responses are kept for [`IDEMPOTENCY_TTL`], except 5xx and 429 ones, which the
+/// client may retry. Requests without the header are passed through untouched, and so are
+/// requests when the store is unreachable.
+#[derive(Clone)]
+pub struct IdempotencyLayer {
+    store: Arc<dyn IdempotencyStore>,
+    users: Arc<dyn RequestUserResolver>,
+}
+
+impl IdempotencyLayer {
+    pub fn new(store: Arc<dyn IdempotencyStore>, users: Arc<dyn RequestUserResolver>) -> Self {
+        Self { store, users }
+    }
+
+    async fn handle<S>(&self, request: Request<Body>, mut inner: S) -> Response
+    where
+        S: Service<Request<Body>, Response = Response, Error = Infallible>,
+    {
+        let Some(value) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+            return forward(&mut inner, request).await;
+        };
+        let Some(idempotency_key) = value
+            .to_str()
+            .ok()
+            .and_then(|value| Uuid::parse_str(value.trim()).ok())
+        else {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                "INVALID_IDEMPOTENCY_KEY",
+                "Idempotency-Key must be a UUID",
+            );
+        };
+
+        let user_id = self.users.user_id(request.headers()).await;
+        let key = cache_key(
+            request.method().as_str(),
+            request.uri().path(),
+            user_id,
+            &idempotency_key,
+        );
+
+        match self.store.get(&key).await {
+            Ok(Some(stored)) => return replay(stored),
+            Ok(None) => {}
+            Err(e) => {
+                warn!(
+                    "Idempotency store unavailable, handling the request anyway: {}",
+                    e
+                );
+                return forward(&mut inner, request).await;
+            }
+        }
+        match self.store.try_lock(&key, LOCK_TTL).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return error_response(
+                    StatusCode::CONFLICT,
+                    "IDEMPOTENCY_KEY_IN_USE",
+                    "A request with this Idempotency-Key is still being processed",
+                );
+            }
+            Err(e) => {
+                warn!(
+                    "Idempotency store unavailable, handling the request anyway: {}",
+                    e
+                );
+                return forward(&mut inner, request).await;
+            }
+        }
+
+        let response = forward(&mut inner, request).await;
+        if is_retryable(response.status()) {
+            self.release(&key).await;
+            return response;
+        }
+
+        let (parts, body) = response.into_parts();
+        let body = match to_bytes(body, MAX_STORED_BODY_BYTES).await {
+            Ok(body) => body,
+            Err(e) => {
+                warn!(
+                    "Failed to buffer the response of an idempotent request: {}",
+                    e
+                );
+                self.release(&key).await;
+                return error_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "INTERNAL_ERROR",
+                    "Failed to read the response",
+                );
+            }
+        };
+        let stored = StoredResponse {
+            status: parts.status.as_u16(),
+            content_type: parts
+                .headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string),
+            body: body.to_vec(),
+        };
+        if let Err(e) = self.store.complete(&key, &stored, IDEMPOTENCY_TTL).await {
+            warn!(
+                "Failed to store the response of an idempotent request: {}",
+                e
+            );
+            self.release(&key).await;
+        }
+        Response::from_parts(parts, Body::from(body))
+    }
+
+    async fn release(&self, key: &str) {
+        if let Err(e) = self.store.unlock(key).await {
+            warn!("Failed to release an idempotency key: {}", e);
+        }
+    }
+}
+
+async fn forward<S>(inner: &mut S, request: Request<Body>) -> Response
+where
+    S: Service<Request<Body>, Response = Response, Error = Infallible>,
+{
+    match inner.call(request).await {
+        Ok(response) => response,
+        Err(infallible) => match infallible {},
+    }
+}
+
+impl<S> Layer<S> for IdempotencyLayer {
+    type Service = IdempotencyService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        IdempotencyService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service applying [`IdempotencyLayer`] to the requests of `S`
+#[derive(Clone)]
+pub struct IdempotencyService<S> {
+    inner: S,
+    layer: IdempotencyLayer,
+}
+
+impl<S> Service<Request<Body>> for IdempotencyService<S>
+where
+    S: Service<Request<Body>, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        // Keep the service that was polled ready; the clone takes its place
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+        Box::pin(async move { Ok(layer.handle(request, inner).await) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::Notify;
+    use tower::ServiceExt;
+
+    const KEY: &str = "6f1c2a8e-3b7d-4e0f-9a51-2c4d8e7f1b30";
+
+    /// Takes the user from an `x-user-id` header instead of a token
+    struct HeaderUserResolver;
+
+    #[async_trait]
+    impl RequestUserResolver for HeaderUserResolver {
+        async fn user_id(&self, headers: &HeaderMap) -> Option<i32> {
+            headers.get("x-user-id")?.to_str().ok()?.parse().ok()
+        }
+    }
+
+    /// Router whose handler answers with `respond(call number)`, counting its calls
+    fn app(
+        calls: Arc<AtomicUsize>,
+        release: Option<Arc<Notify>>,
+        respond: fn(usize) -> StatusCode,
+    ) -> Router {
+        let layer = IdempotencyLayer::new(
+            Arc::new(InMemoryIdempotencyStore::new()),
+            Arc::new(HeaderUserResolver),
+        );
+        let handler = move || async move {
+            let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if let Some(release) = release {
+                release.notified().await;
+            }
+            (respond(call), Json(serde_json::json!({ "call": call })))
+        };
+        Router::new().route("/shorten", post(handler).layer(layer))
+    }
+
+    fn request(key: Option<&str>, user_id: Option<i32>) -> Request<Body> {
+        let mut request = Request::builder().method("POST").uri("/shorten");
+        if let Some(key) = key {
+            request = request.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
+        if let Some(user_id) = user_id {
+            request = request.header("x-user-id", user_id);
+        }
+        request.body(Body::empty()).unwrap()
+    }
+
+    async fn call_number(response: Response) -> u64 {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        body["call"].as_u64().unwrap()
+    }
+
+    fn is_replayed(response: &Response) -> bool {
+        response.headers().get(IDEMPOTENT_REPLAYED_HEADER)
+            == Some(&HeaderValue::from_static("true"))
+    }
+
+    #[tokio::test]
+    async fn test_retries_replay_the_stored_response() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), None, |_| StatusCode::CREATED);
+
+        let response = app
+            .clone()
+            .oneshot(request(Some(KEY), Some(1)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert!(!is_replayed(&response));
+        assert_eq!(call_number(response).await, 1);
+
+        let response = app
+            .clone()
+            .oneshot(request(Some(KEY), Some(1)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert!(is_replayed(&response));
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(call_number(response).await, 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // The same key of another user is a different request
+        let response = app
+            .clone()
+            .oneshot(request(Some(KEY), Some(2)))
+            .await
+            .unwrap();
+        assert!(!is_replayed(&response));
+        assert_eq!(call_number(response).await, 2);
+
+        // Requests without a key always run
+        for expected in [3, 4] {
+            let response = app.clone().oneshot(request(None, Some(1))).await.unwrap();
+            assert_eq!(call_number(response).await, expected);
+        }
+
+        let response = app
+            .oneshot(request(Some("not-a-uuid"), Some(1)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_request_with_same_key_waits_for_the_first() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let release = Arc::new(Notify::new());
+        let app = app(calls.clone(), Some(release.clone()), |_| {
+            StatusCode::CREATED
+        });
+
+        let first = tokio::spawn(app.clone().oneshot(request(Some(KEY), None)));
+        while calls.load(Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        // The key is locked while the first request runs
+        let response = app.clone().oneshot(request(Some(KEY), None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        release.notify_one();
+        let response = first.await.unwrap().unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let response = app.oneshot(request(Some(KEY), None)).await.unwrap();
+        assert!(is_replayed(&response));
+        assert_eq!(call_number(response).await, 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_are_not_replayed() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), None, |call| {
+            if call == 1 {
+                StatusCode::SERVICE_UNAVAILABLE
+            } else {
+                StatusCode::CREATED
+            }
+        });
+
+        let response = app.clone().oneshot(request(Some(KEY), None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let response = app.clone().oneshot(request(Some(KEY), None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert!(!is_replayed(&response));
+
+        let response = app.oneshot(request(Some(KEY), None)).await.unwrap();
+        assert!(is_replayed(&response));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    /// Needs a Redis server: `REDIS_URL=redis://localhost:6379 cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_store() {
+        let url = std::env::var("REDIS_URL").unwrap_or("redis://localhost:6379".to_string());
+        let store = RedisIdempotencyStore::connect(&url).await.unwrap();
+        let key = format!("test-{}", Uuid::new_v4());
+        let response = StoredResponse {
+            status: 201,
+            content_type: Some("application/json".to_string()),
+            body: b"{\"id\":1}".to_vec(),
+        };
+
+        assert_eq!(store.get(&key).await.unwrap(), None);
+        assert!(store.try_lock(&key, LOCK_TTL).await.unwrap());
+        assert!(!store.try_lock(&key, LOCK_TTL).await.unwrap());
+
+        store
+            .complete(&key, &response, Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(store.get(&key).await.unwrap(), Some(response));
+        assert!(store.try_lock(&key, LOCK_TTL).await.unwrap());
+        store.unlock(&key).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        assert_eq!(store.get(&key).await.unwrap(), None);
+    }
+}
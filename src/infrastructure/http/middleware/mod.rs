@@ -1,9 +1,10 @@
 // HTTP Middleware implementations
 // This allows us to organize middleware by functionality
 
+pub mod auth_middleware;
 pub mod cors_middleware;
 pub mod error_middleware;
+pub mod idempotency_middleware;
 pub mod logging_middleware;
 
-// Future: pub mod auth_middleware;
 // Future: pub mod metrics_middleware;
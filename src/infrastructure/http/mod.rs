@@ -1,2 +1,7 @@
 pub mod controllers;
 pub mod middleware;
+pub mod real_ip_extractor;
+
+#[allow(unused_imports)]
+pub use middleware::auth_middleware::{AuthenticatedUser, OptionalAuthenticatedUser};
+pub use real_ip_extractor::RealIpExtractor;
@@ -0,0 +1,170 @@
+use axum::http::HeaderMap;
+use ipnetwork::IpNetwork;
+use std::net::IpAddr;
+
+/// Forwarding headers checked for the client IP, in priority order
+const FORWARDED_HEADERS: &[&str] = &["x-forwarded-for", "x-real-ip", "cf-connecting-ip"];
+
+/// Resolves the real client IP of a request behind load balancers or Cloudflare
+///
+/// Forwarding headers are only trusted when the direct connection comes from one of the
+/// configured proxies; otherwise any client could spoof its address.
+#[derive(Debug, Clone, Default)]
+pub struct RealIpExtractor {
+    trusted_proxies: Vec<IpNetwork>,
+}
+
+impl RealIpExtractor {
+    pub fn new(trusted_proxies: Vec<IpNetwork>) -> Self {
+        Self { trusted_proxies }
+    }
+
+    /// Check if an address belongs to a trusted proxy
+    pub fn is_trusted_proxy(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.trusted_proxies
+            .iter()
+            .any(|network| network.contains(ip))
+    }
+
+    /// Resolve the client IP from the direct peer address and the request headers
+    ///
+    /// Falls back to the peer address when it is not a trusted proxy or no header holds a
+    /// public IP. Returns `None` only when the peer address is unknown and untrusted.
+    pub fn extract(&self, peer: Option<IpAddr>, headers: &HeaderMap) -> Option<IpAddr> {
+        let peer = peer.map(|ip| ip.to_canonical());
+        match peer {
+            Some(ip) if self.is_trusted_proxy(ip) => self.forwarded_client_ip(headers).or(Some(ip)),
+            _ => peer,
+        }
+    }
+
+    /// First public client IP found in the forwarding headers
+    fn forwarded_client_ip(&self, headers: &HeaderMap) -> Option<IpAddr> {
+        FORWARDED_HEADERS.iter().find_map(|name| {
+            let value = headers.get(*name)?.to_str().ok()?;
+            // X-Forwarded-For is "client, proxy1, proxy2": walk back past our own proxies
+            value
+                .split(',')
+                .rev()
+                .filter_map(|entry| entry.trim().parse::<IpAddr>().ok())
+                .map(|ip| ip.to_canonical())
+                .find(|ip| !self.is_trusted_proxy(*ip))
+                .filter(|ip| is_public_ip(*ip))
+        })
+    }
+}
+
+/// Check that an address is publicly routable (not private, loopback, link-local, ...)
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip.to_canonical() {
+        IpAddr::V4(ip) => {
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                // Shared address space (carrier-grade NAT), 100.64.0.0/10
+                || (ip.octets()[0] == 100 && (ip.octets()[1] & 0xc0) == 64))
+        }
+        IpAddr::V6(ip) => {
+            let first_segment = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // Unique local, fc00::/7
+                || (first_segment & 0xfe00) == 0xfc00
+                // Link-local, fe80::/10
+                || (first_segment & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extractor() -> RealIpExtractor {
+        RealIpExtractor::new(vec![
+            "10.0.0.0/8".parse().unwrap(),
+            "173.245.48.0/20".parse().unwrap(),
+        ])
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_forwarded_headers_from_trusted_proxy() {
+        let extractor = extractor();
+
+        let forwarded = headers(&[("x-forwarded-for", "203.0.113.7, 10.0.0.2")]);
+        assert_eq!(
+            extractor.extract(Some(ip("10.0.0.1")), &forwarded),
+            Some(ip("203.0.113.7"))
+        );
+
+        let cloudflare = headers(&[("cf-connecting-ip", "198.51.100.4")]);
+        assert_eq!(
+            extractor.extract(Some(ip("173.245.48.10")), &cloudflare),
+            Some(ip("198.51.100.4"))
+        );
+    }
+
+    #[test]
+    fn test_forwarded_headers_ignored_from_untrusted_client() {
+        let spoofed = headers(&[("x-forwarded-for", "203.0.113.7")]);
+        assert_eq!(
+            extractor().extract(Some(ip("198.51.100.9")), &spoofed),
+            Some(ip("198.51.100.9"))
+        );
+        assert_eq!(extractor().extract(None, &spoofed), None);
+    }
+
+    #[test]
+    fn test_header_priority_and_private_addresses() {
+        let extractor = extractor();
+
+        // Private X-Forwarded-For entries are skipped in favour of the next header
+        let pairs = headers(&[
+            ("x-forwarded-for", "192.168.1.20"),
+            ("x-real-ip", "203.0.113.8"),
+            ("cf-connecting-ip", "198.51.100.4"),
+        ]);
+        assert_eq!(
+            extractor.extract(Some(ip("10.0.0.1")), &pairs),
+            Some(ip("203.0.113.8"))
+        );
+
+        // No usable header: fall back to the proxy address
+        let private_only = headers(&[("x-forwarded-for", "127.0.0.1")]);
+        assert_eq!(
+            extractor.extract(Some(ip("10.0.0.1")), &private_only),
+            Some(ip("10.0.0.1"))
+        );
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        assert!(is_public_ip(ip("203.0.113.7")));
+        assert!(is_public_ip(ip("2001:db8::1")));
+        assert!(is_public_ip(ip("::ffff:8.8.8.8")));
+        assert!(!is_public_ip(ip("10.1.2.3")));
+        assert!(!is_public_ip(ip("100.64.0.1")));
+        assert!(!is_public_ip(ip("169.254.0.1")));
+        assert!(!is_public_ip(ip("::1")));
+        assert!(!is_public_ip(ip("fd00::1")));
+        assert!(!is_public_ip(ip("fe80::1")));
+        assert!(!is_public_ip(ip("::ffff:192.168.0.1")));
+    }
+}
@@ -0,0 +1,237 @@
+use crate::application::dto::requests::OperationPriority;
+use crate::domain::events::DomainEvent;
+use crate::domain::services::auth_service::TokenCacheStats;
+use crate::infrastructure::database::PoolStats;
+use prometheus::{
+    Encoder, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use std::sync::LazyLock;
+
+/// Registry holding all application metrics exposed on `/metrics`
+pub static REGISTRY: LazyLock<Registry> = LazyLock::new(Registry::new);
+
+/// Clicks dropped because the click tracking buffer was full
+pub static CLICKS_DROPPED_TOTAL: LazyLock<IntCounter> = LazyLock::new(|| {
+    let counter = IntCounter::new(
+        "clicks_dropped_total",
+        "Clicks dropped because the click tracking buffer was full",
+    )
+    .expect("valid metric definition");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric registered once");
+    counter
+});
+
+/// Bring `clicks_dropped_total` up to date with the click tracking service's count
+pub fn sync_clicks_dropped(total: u64) {
+    let current = CLICKS_DROPPED_TOTAL.get();
+    if total > current {
+        CLICKS_DROPPED_TOTAL.inc_by(total - current);
+    }
+}
+
+/// Bulk operations waiting in the queue, per priority level
+pub static BULK_QUEUE_DEPTH: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    let gauge = IntGaugeVec::new(
+        Opts::new(
+            "bulk_queue_depth",
+            "Bulk operations waiting in the queue, per priority level",
+        ),
+        &["priority"],
+    )
+    .expect("valid metric definition");
+    REGISTRY
+        .register(Box::new(gauge.clone()))
+        .expect("metric registered once");
+    gauge
+});
+
+/// Set `bulk_queue_depth` from the bulk processor's current queue depths
+pub fn sync_bulk_queue_depth(depths: &[(OperationPriority, usize)]) {
+    for (priority, depth) in depths {
+        BULK_QUEUE_DEPTH
+            .with_label_values(&[priority.as_str()])
+            .set(*depth as i64);
+    }
+}
+
+/// Short code lookups retried on the primary after the read replica missed them
+pub static PRIMARY_FALLBACK_TOTAL: LazyLock<IntCounter> = LazyLock::new(|| {
+    let counter = IntCounter::new(
+        "primary_fallback_total",
+        "Short code lookups retried on the primary after the read replica missed them",
+    )
+    .expect("valid metric definition");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric registered once");
+    counter
+});
+
+/// Register a gauge under `name`
+fn register_gauge(name: &str, help: &str) -> IntGauge {
+    let gauge = IntGauge::new(name, help).expect("valid metric definition");
+    REGISTRY
+        .register(Box::new(gauge.clone()))
+        .expect("metric registered once");
+    gauge
+}
+
+/// Connections open in the database pool, idle or in use
+pub static DB_POOL_CONNECTIONS_TOTAL: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_gauge(
+        "db_pool_connections_total",
+        "Connections open in the database pool, idle or in use",
+    )
+});
+
+/// Open database connections waiting to be used
+pub static DB_POOL_CONNECTIONS_IDLE: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_gauge(
+        "db_pool_connections_idle",
+        "Open database connections waiting to be used",
+    )
+});
+
+/// Database connections in use
+pub static DB_POOL_CONNECTIONS_ACTIVE: LazyLock<IntGauge> =
+    LazyLock::new(|| register_gauge("db_pool_connections_active", "Database connections in use"));
+
+/// Milliseconds the read replica trailed the primary by when last checked
+pub static DB_REPLICA_LAG_MS: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_gauge(
+        "db_replica_lag_ms",
+        "Milliseconds the read replica trailed the primary by when last checked",
+    )
+});
+
+/// Whether the read replica answered its last lag check (1) or not (0)
+pub static DB_REPLICA_AVAILABLE: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_gauge(
+        "db_replica_available",
+        "Whether the read replica answered its last lag check (1) or not (0)",
+    )
+});
+
+/// Set the `db_pool_connections_*` gauges from a snapshot of the pool
+pub fn sync_db_pool(stats: &PoolStats) {
+    DB_POOL_CONNECTIONS_TOTAL.set(i64::from(stats.size));
+    DB_POOL_CONNECTIONS_IDLE.set(i64::from(stats.idle));
+    DB_POOL_CONNECTIONS_ACTIVE.set(i64::from(stats.active()));
+}
+
+/// Token verifications answered from the verified token cache
+pub static VERIFY_TOKEN_CACHE_HITS_TOTAL: LazyLock<IntCounter> = LazyLock::new(|| {
+    register_counter(
+        "verify_token_cache_hits_total",
+        "Token verifications answered from the verified token cache",
+    )
+});
+
+/// Token verifications that had to load the token's user
+pub static VERIFY_TOKEN_CACHE_MISSES_TOTAL: LazyLock<IntCounter> = LazyLock::new(|| {
+    register_counter(
+        "verify_token_cache_misses_total",
+        "Token verifications that had to load the token's user",
+    )
+});
+
+/// Register a counter under `name`
+fn register_counter(name: &str, help: &str) -> IntCounter {
+    let counter = IntCounter::new(name, help).expect("valid metric definition");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric registered once");
+    counter
+}
+
+/// Bring a counter up to date with a running total kept elsewhere
+fn sync_counter(counter: &IntCounter, total: u64) {
+    let current = counter.get();
+    if total > current {
+        counter.inc_by(total - current);
+    }
+}
+
+/// Bring the `verify_token_cache_*` counters up to date with the auth service's cache
+pub fn sync_verify_token_cache(stats: TokenCacheStats) {
+    sync_counter(&VERIFY_TOKEN_CACHE_HITS_TOTAL, stats.hits);
+    sync_counter(&VERIFY_TOKEN_CACHE_MISSES_TOTAL, stats.misses);
+}
+
+/// Domain events published, per event
+pub static DOMAIN_EVENTS_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    let counter = IntCounterVec::new(
+        Opts::new("domain_events_total", "Domain events published, per event"),
+        &["event"],
+    )
+    .expect("valid metric definition");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric registered once");
+    counter
+});
+
+/// Count an event received from the event bus in `domain_events_total`
+pub fn record_domain_event(event: &DomainEvent) {
+    DOMAIN_EVENTS_TOTAL.with_label_values(&[event.name()]).inc();
+}
+
+/// Render all registered metrics in the Prometheus text format
+pub fn render() -> String {
+    // Make sure lazily created metrics show up even before their first increment
+    LazyLock::force(&CLICKS_DROPPED_TOTAL);
+    LazyLock::force(&BULK_QUEUE_DEPTH);
+    LazyLock::force(&PRIMARY_FALLBACK_TOTAL);
+    LazyLock::force(&DB_POOL_CONNECTIONS_TOTAL);
+    LazyLock::force(&DB_POOL_CONNECTIONS_IDLE);
+    LazyLock::force(&DB_POOL_CONNECTIONS_ACTIVE);
+    LazyLock::force(&DB_REPLICA_LAG_MS);
+    LazyLock::force(&DB_REPLICA_AVAILABLE);
+    LazyLock::force(&DOMAIN_EVENTS_TOTAL);
+    LazyLock::force(&VERIFY_TOKEN_CACHE_HITS_TOTAL);
+    LazyLock::force(&VERIFY_TOKEN_CACHE_MISSES_TOTAL);
+
+    let mut buffer = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&REGISTRY.gather(), &mut buffer) {
+        tracing::warn!("Failed to encode metrics: {}", e);
+    }
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_clicks_dropped() {
+        sync_clicks_dropped(3);
+        sync_clicks_dropped(2);
+        assert!(CLICKS_DROPPED_TOTAL.get() >= 3);
+        assert!(render().contains("clicks_dropped_total"));
+    }
+
+    #[test]
+    fn test_sync_bulk_queue_depth() {
+        sync_bulk_queue_depth(&[
+            (OperationPriority::High, 2),
+            (OperationPriority::Normal, 0),
+            (OperationPriority::Low, 5),
+        ]);
+        assert_eq!(BULK_QUEUE_DEPTH.with_label_values(&["high"]).get(), 2);
+        assert!(render().contains("bulk_queue_depth{priority=\"low\"} 5"));
+    }
+
+    #[test]
+    fn test_sync_db_pool() {
+        sync_db_pool(&PoolStats {
+            size: 7,
+            idle: 2,
+            max_connections: 10,
+        });
+        assert_eq!(DB_POOL_CONNECTIONS_TOTAL.get(), 7);
+        assert_eq!(DB_POOL_CONNECTIONS_IDLE.get(), 2);
+        assert!(render().contains("db_pool_connections_active 5"));
+    }
+}
@@ -1,12 +1,24 @@
+pub mod analytics_cache;
+pub mod cache_health_check;
+pub mod click_deduplication;
 pub mod config;
 pub mod database;
 pub mod email;
+pub mod error_tracking;
+pub mod event_bus;
 pub mod http;
+pub mod metrics;
+pub mod object_storage;
 pub mod password_reset_rate_limiter;
+pub mod push;
 pub mod rate_limiting;
 pub mod server;
+pub mod server_info;
 pub mod test_utils;
+pub mod tls;
+pub mod trace_context;
+pub mod user_invalidation;
 
 pub use database::*;
 pub use email::*;
-pub use password_reset_rate_limiter::PasswordResetRateLimiter;
+pub use password_reset_rate_limiter::{PasswordResetRateLimitConfig, PasswordResetRateLimiter};
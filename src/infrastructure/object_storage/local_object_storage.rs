@@ -0,0 +1,135 @@
+use super::{validate_key, ObjectStorage, ObjectStorageError};
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::path::{Path, PathBuf};
+
+/// Object storage in a local directory, for development
+///
+/// Objects are written to `<root>/<key>` and served from `<public_base_url>/<key>`.
+#[derive(Debug, Clone)]
+pub struct LocalObjectStorage {
+    root: PathBuf,
+    public_base_url: String,
+}
+
+impl LocalObjectStorage {
+    pub fn new(root: impl Into<PathBuf>, public_base_url: impl Into<String>) -> Self {
+        Self {
+            root: root.into(),
+            public_base_url: public_base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf, ObjectStorageError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for LocalObjectStorage {
+    async fn upload(
+        &self,
+        key: &str,
+        data: Bytes,
+        _content_type: &str,
+    ) -> Result<String, ObjectStorageError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, data).await?;
+        Ok(self.public_url(key))
+    }
+
+    async fn upload_file(
+        &self,
+        key: &str,
+        path: &Path,
+        _content_type: &str,
+    ) -> Result<(), ObjectStorageError> {
+        let target = self.path_for(key)?;
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::copy(path, &target).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), ObjectStorageError> {
+        match tokio::fs::remove_file(self.path_for(key)?).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_base_url, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_upload_and_delete() {
+        let dir = TempDir::new().unwrap();
+        let storage = LocalObjectStorage::new(dir.path(), "http://localhost:8000/uploads/");
+
+        let url = storage
+            .upload(
+                "profiles/1/avatar.jpg",
+                Bytes::from_static(b"jpeg"),
+                "image/jpeg",
+            )
+            .await
+            .unwrap();
+        assert_eq!(url, "http://localhost:8000/uploads/profiles/1/avatar.jpg");
+        let path = dir.path().join("profiles/1/avatar.jpg");
+        assert_eq!(std::fs::read(&path).unwrap(), b"jpeg");
+        assert_eq!(
+            storage.key_for_url(&url).as_deref(),
+            Some("profiles/1/avatar.jpg")
+        );
+
+        storage.delete("profiles/1/avatar.jpg").await.unwrap();
+        assert!(!path.exists());
+        // Deleting again is not an error
+        storage.delete("profiles/1/avatar.jpg").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_upload_file() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("export.csv");
+        std::fs::write(&source, b"id\n1\n").unwrap();
+        let storage = LocalObjectStorage::new(dir.path().join("store"), "file:///exports");
+
+        storage
+            .upload_file("exports/1/op.csv", &source, "text/csv")
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read(dir.path().join("store/exports/1/op.csv")).unwrap(),
+            b"id\n1\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_keys_outside_root() {
+        let dir = TempDir::new().unwrap();
+        let storage = LocalObjectStorage::new(dir.path(), "http://localhost:8000/uploads");
+
+        let result = storage
+            .upload("../escape.jpg", Bytes::new(), "image/jpeg")
+            .await;
+        assert!(matches!(result, Err(ObjectStorageError::InvalidKey(_))));
+        assert_eq!(
+            storage.key_for_url("http://localhost:8000/uploads/../secret"),
+            None
+        );
+        assert_eq!(storage.key_for_url("https://elsewhere.example/a.jpg"), None);
+    }
+}
@@ -0,0 +1,133 @@
+pub mod local_object_storage;
+pub mod s3_object_storage;
+
+pub use local_object_storage::LocalObjectStorage;
+pub use s3_object_storage::S3ObjectStorage;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::path::Path;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Object storage errors
+#[derive(Error, Debug)]
+pub enum ObjectStorageError {
+    #[error("Invalid object key: {0}")]
+    InvalidKey(String),
+
+    #[error("Object storage request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("Object storage responded with status {status}: {message}")]
+    UnexpectedStatus { status: u16, message: String },
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Store for uploaded files, addressed by slash-separated keys
+#[async_trait]
+pub trait ObjectStorage: Send + Sync {
+    /// Store `data` under `key` and return the URL it is publicly served from
+    async fn upload(
+        &self,
+        key: &str,
+        data: Bytes,
+        content_type: &str,
+    ) -> Result<String, ObjectStorageError>;
+
+    /// Store the contents of the file at `path` under `key` without reading it into memory
+    async fn upload_file(
+        &self,
+        key: &str,
+        path: &Path,
+        content_type: &str,
+    ) -> Result<(), ObjectStorageError>;
+
+    /// Remove the object stored under `key`; a missing object is not an error
+    async fn delete(&self, key: &str) -> Result<(), ObjectStorageError>;
+
+    /// URL the object stored under `key` is publicly served from
+    fn public_url(&self, key: &str) -> String;
+
+    /// URL the object stored under `key` can be downloaded from for the next `expires_in`
+    ///
+    /// Storages serving every object publicly give the public URL, which does not expire.
+    fn presigned_url(&self, key: &str, expires_in: Duration) -> Result<String, ObjectStorageError> {
+        let _ = expires_in;
+        validate_key(key)?;
+        Ok(self.public_url(key))
+    }
+
+    /// Key of the object served from `url`, if the URL belongs to this storage
+    fn key_for_url(&self, url: &str) -> Option<String> {
+        let key = url.strip_prefix(&self.public_url(""))?;
+        validate_key(key).ok()?;
+        Some(key.to_string())
+    }
+}
+
+/// Key of a newly uploaded profile picture: `profiles/<user_id>/<uuid>.<ext>`
+pub fn profile_picture_key(user_id: i32, extension: &str) -> String {
+    format!(
+        "profiles/{}/{}.{}",
+        user_id,
+        uuid::Uuid::new_v4(),
+        extension
+    )
+}
+
+/// Key of an analytics export file: `exports/<user_id>/<export_id>.<ext>`
+pub fn analytics_export_key(user_id: i32, export_id: &str, extension: &str) -> String {
+    format!("exports/{}/{}.{}", user_id, export_id, extension)
+}
+
+/// Reject keys that are empty or could escape their prefix
+pub(crate) fn validate_key(key: &str) -> Result<(), ObjectStorageError> {
+    let invalid = key.is_empty()
+        || key.starts_with('/')
+        || key.contains('\\')
+        || key
+            .split('/')
+            .any(|segment| segment.is_empty() || segment == "." || segment == "..");
+    if invalid {
+        return Err(ObjectStorageError::InvalidKey(key.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_picture_key() {
+        let key = profile_picture_key(42, "jpg");
+        let file = key.strip_prefix("profiles/42/").unwrap();
+        let (id, extension) = file.split_once('.').unwrap();
+        assert!(uuid::Uuid::parse_str(id).is_ok());
+        assert_eq!(extension, "jpg");
+        assert!(validate_key(&key).is_ok());
+    }
+
+    #[test]
+    fn test_analytics_export_key() {
+        let key = analytics_export_key(42, "op-1", "parquet");
+        assert_eq!(key, "exports/42/op-1.parquet");
+        assert!(validate_key(&key).is_ok());
+    }
+
+    #[test]
+    fn test_validate_key() {
+        for key in [
+            "",
+            "/profiles/1/a.jpg",
+            "profiles/../a.jpg",
+            "profiles//a.jpg",
+        ] {
+            assert!(validate_key(key).is_err(), "{:?} accepted", key);
+        }
+        assert!(validate_key("profiles/1/a.jpg").is_ok());
+    }
+}
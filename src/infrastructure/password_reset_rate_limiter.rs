@@ -1,4 +1,6 @@
+use crate::domain::repositories::user_repository::normalize_email;
 use chrono::{DateTime, Duration, Utc};
+use dashmap::DashMap;
 use governor::{
     clock::{Clock, DefaultClock},
     state::keyed::DefaultKeyedStateStore,
@@ -7,6 +9,7 @@ use governor::{
 use std::collections::HashMap;
 use std::num::NonZeroU32;
 use std::sync::Arc;
+use std::time::Instant;
 use thiserror::Error;
 use tokio::sync::Mutex;
 
@@ -15,8 +18,10 @@ use tokio::sync::Mutex;
 pub struct PasswordResetRateLimitConfig {
     /// Maximum requests per IP per hour
     pub requests_per_hour_per_ip: u32,
-    /// Maximum requests per email per hour
-    pub requests_per_hour_per_email: u32,
+    /// Maximum requests per email address within `per_email_window_seconds`, from any IP
+    pub per_email_max_requests: u32,
+    /// Length of the per email window; the count starts over once it has passed
+    pub per_email_window_seconds: u64,
     /// Cooldown period between requests (in minutes)
     pub cooldown_minutes: i64,
     /// Maximum active tokens per user
@@ -28,7 +33,8 @@ impl Default for PasswordResetRateLimitConfig {
     fn default() -> Self {
         Self {
             requests_per_hour_per_ip: 5,    // 5 requests per hour per IP
-            requests_per_hour_per_email: 3, // 3 requests per hour per email
+            per_email_max_requests: 3,      // 3 requests per email...
+            per_email_window_seconds: 3600, // ...per hour
             cooldown_minutes: 5,            // 5 minutes between requests
             max_active_tokens_per_user: 5,  // Max 5 active tokens per user
         }
@@ -39,7 +45,8 @@ impl Default for PasswordResetRateLimitConfig {
 pub struct PasswordResetRateLimiter {
     config: PasswordResetRateLimitConfig,
     ip_limiter: Arc<RateLimiter<String, DefaultKeyedStateStore<String>, DefaultClock>>,
-    email_limiter: Arc<RateLimiter<String, DefaultKeyedStateStore<String>, DefaultClock>>,
+    /// Requests per normalized email and when their window started
+    email_requests: Arc<DashMap<String, (u32, Instant)>>,
     last_request_times: Arc<Mutex<HashMap<String, DateTime<Utc>>>>,
 }
 
@@ -65,13 +72,24 @@ pub enum PasswordResetRateLimitError {
     Internal(String),
 }
 
+impl PasswordResetRateLimitError {
+    /// Seconds the client should wait before trying again, for the `Retry-After` header
+    pub fn retry_after_seconds(&self) -> Option<u64> {
+        match self {
+            Self::IpRateLimitExceeded(seconds) | Self::EmailRateLimitExceeded(seconds) => {
+                Some((*seconds).max(1))
+            }
+            Self::CooldownPeriodActive(minutes) => Some((*minutes).max(1) as u64 * 60),
+            Self::TooManyActiveTokens | Self::Internal(_) => None,
+        }
+    }
+}
+
 #[allow(dead_code)]
 impl PasswordResetRateLimiter {
     /// Create a new password reset rate limiter
     pub fn new(config: PasswordResetRateLimitConfig) -> Self {
         let ip_quota = Quota::per_hour(NonZeroU32::new(config.requests_per_hour_per_ip).unwrap());
-        let email_quota =
-            Quota::per_hour(NonZeroU32::new(config.requests_per_hour_per_email).unwrap());
 
         let ip_limiter = Arc::new(RateLimiter::new(
             ip_quota,
@@ -79,16 +97,10 @@ impl PasswordResetRateLimiter {
             &DefaultClock::default(),
         ));
 
-        let email_limiter = Arc::new(RateLimiter::new(
-            email_quota,
-            DefaultKeyedStateStore::new(),
-            &DefaultClock::default(),
-        ));
-
         Self {
             config,
             ip_limiter,
-            email_limiter,
+            email_requests: Arc::new(DashMap::new()),
             last_request_times: Arc::new(Mutex::new(HashMap::new())),
         }
     }
@@ -114,18 +126,35 @@ impl PasswordResetRateLimiter {
     }
 
     /// Check email rate limit
+    ///
+    /// Emails are compared case-insensitively, so changing the case does not get around it.
     pub fn check_email_limit(&self, email: &str) -> Result<(), PasswordResetRateLimitError> {
-        match self.email_limiter.check_key(&email.to_string()) {
-            Ok(_) => Ok(()),
-            Err(negative) => {
-                let retry_after = negative
-                    .wait_time_from(DefaultClock::default().now())
-                    .as_secs();
-                Err(PasswordResetRateLimitError::EmailRateLimitExceeded(
-                    retry_after,
-                ))
-            }
+        self.check_email_limit_at(email, Instant::now())
+    }
+
+    fn check_email_limit_at(
+        &self,
+        email: &str,
+        now: Instant,
+    ) -> Result<(), PasswordResetRateLimitError> {
+        let window = std::time::Duration::from_secs(self.config.per_email_window_seconds);
+        let mut entry = self
+            .email_requests
+            .entry(normalize_email(email))
+            .or_insert((0, now));
+        let (count, window_start) = entry.value_mut();
+        let elapsed = now.saturating_duration_since(*window_start);
+        if elapsed >= window {
+            *count = 0;
+            *window_start = now;
+        } else if *count >= self.config.per_email_max_requests {
+            let remaining = window - elapsed;
+            return Err(PasswordResetRateLimitError::EmailRateLimitExceeded(
+                remaining.as_secs_f64().ceil() as u64,
+            ));
         }
+        *count += 1;
+        Ok(())
     }
 
     /// Check cooldown period
@@ -175,6 +204,10 @@ impl PasswordResetRateLimiter {
         let cutoff_time = Utc::now() - Duration::hours(24);
 
         last_times.retain(|_, time| *time > cutoff_time);
+
+        let window = std::time::Duration::from_secs(self.config.per_email_window_seconds);
+        self.email_requests
+            .retain(|_, (_, window_start)| window_start.elapsed() < window);
     }
 
     /// Get rate limit info for debugging
@@ -198,7 +231,8 @@ impl PasswordResetRateLimiter {
 
         RateLimitInfo {
             requests_per_hour_per_ip: self.config.requests_per_hour_per_ip,
-            requests_per_hour_per_email: self.config.requests_per_hour_per_email,
+            per_email_max_requests: self.config.per_email_max_requests,
+            per_email_window_seconds: self.config.per_email_window_seconds,
             cooldown_minutes: self.config.cooldown_minutes,
             cooldown_remaining_minutes: cooldown_remaining,
             last_request,
@@ -211,7 +245,8 @@ impl PasswordResetRateLimiter {
 #[derive(Debug, Clone)]
 pub struct RateLimitInfo {
     pub requests_per_hour_per_ip: u32,
-    pub requests_per_hour_per_email: u32,
+    pub per_email_max_requests: u32,
+    pub per_email_window_seconds: u64,
     pub cooldown_minutes: i64,
     pub cooldown_remaining_minutes: Option<i64>,
     pub last_request: Option<DateTime<Utc>>,
@@ -225,7 +260,8 @@ mod tests {
     fn test_rate_limit_config() {
         let config = PasswordResetRateLimitConfig::default();
         assert_eq!(config.requests_per_hour_per_ip, 5);
-        assert_eq!(config.requests_per_hour_per_email, 3);
+        assert_eq!(config.per_email_max_requests, 3);
+        assert_eq!(config.per_email_window_seconds, 3600);
         assert_eq!(config.cooldown_minutes, 5);
         assert_eq!(config.max_active_tokens_per_user, 5);
     }
@@ -264,6 +300,65 @@ mod tests {
         assert!(last_times.contains_key("test@example.com"));
     }
 
+    fn limiter_without_cooldown() -> PasswordResetRateLimiter {
+        PasswordResetRateLimiter::new(PasswordResetRateLimitConfig {
+            cooldown_minutes: 0,
+            ..PasswordResetRateLimitConfig::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_email_limit_applies_across_ips() {
+        let limiter = limiter_without_cooldown();
+        for ip in ["10.0.0.1", "10.0.0.2", "10.0.0.3"] {
+            assert!(limiter
+                .check_all_limits(ip, "user@example.com")
+                .await
+                .is_ok());
+        }
+
+        // A fourth IP is still turned away, whatever the case of the address
+        let result = limiter
+            .check_all_limits("10.0.0.4", "User@Example.com")
+            .await;
+        let Err(error) = result else {
+            panic!("fourth request for the same email should be limited");
+        };
+        assert!(matches!(
+            error,
+            PasswordResetRateLimitError::EmailRateLimitExceeded(_)
+        ));
+        assert!(error.retry_after_seconds().unwrap() <= 3600);
+        assert!(limiter.check_email_limit("other@example.com").is_ok());
+    }
+
+    #[test]
+    fn test_email_limit_resets_after_window() {
+        let limiter = limiter_without_cooldown();
+        let start = Instant::now();
+        for _ in 0..3 {
+            assert!(limiter
+                .check_email_limit_at("user@example.com", start)
+                .is_ok());
+        }
+
+        let almost = start + std::time::Duration::from_secs(3599);
+        assert!(matches!(
+            limiter.check_email_limit_at("user@example.com", almost),
+            Err(PasswordResetRateLimitError::EmailRateLimitExceeded(1))
+        ));
+
+        let later = start + std::time::Duration::from_secs(3600);
+        for _ in 0..3 {
+            assert!(limiter
+                .check_email_limit_at("user@example.com", later)
+                .is_ok());
+        }
+        assert!(limiter
+            .check_email_limit_at("user@example.com", later)
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_get_rate_limit_info() {
         let limiter = PasswordResetRateLimiter::new_default();
@@ -271,7 +366,7 @@ mod tests {
 
         let info = limiter.get_rate_limit_info("test@example.com").await;
         assert_eq!(info.requests_per_hour_per_ip, 5);
-        assert_eq!(info.requests_per_hour_per_email, 3);
+        assert_eq!(info.per_email_max_requests, 3);
         assert!(info.last_request.is_some());
     }
 }
@@ -0,0 +1,287 @@
+use crate::domain::repositories::PushOutboxRepository;
+use crate::infrastructure::config::FcmConfig;
+use crate::infrastructure::push::{
+    drain_push_outbox, PushError, PushMessage, PushNotificationSender, PUSH_OUTBOX_POLL_INTERVAL,
+};
+use async_trait::async_trait;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// OAuth scope needed to send messages through FCM
+const FCM_SCOPE: &str = "https://www.googleapis.com/auth/firebase.messaging";
+
+/// Lifetime requested for the signed service account assertion
+const ASSERTION_LIFETIME_SECONDS: i64 = 3600;
+
+/// An access token is renewed this long before it expires
+const TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+/// Claims of the JWT exchanged for an access token
+#[derive(Serialize)]
+struct AssertionClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+/// Body of the token endpoint
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+/// Error body of the FCM send endpoint, reduced to the error code
+#[derive(Deserialize)]
+struct FcmErrorResponse {
+    error: FcmErrorStatus,
+}
+
+#[derive(Deserialize)]
+struct FcmErrorStatus {
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    details: Vec<FcmErrorDetail>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FcmErrorDetail {
+    #[serde(default)]
+    error_code: Option<String>,
+}
+
+/// Access token and when it stops being usable
+struct CachedToken {
+    access_token: String,
+    expires_at: Instant,
+}
+
+/// Push notification sender for Firebase Cloud Messaging (HTTP v1 API)
+///
+/// Requests are authorized with an OAuth access token obtained by signing a JWT with the
+/// service account key; the token is reused until shortly before it expires.
+pub struct FcmPushSender {
+    client: reqwest::Client,
+    config: FcmConfig,
+    encoding_key: EncodingKey,
+    token: Mutex<Option<CachedToken>>,
+}
+
+impl FcmPushSender {
+    /// Create a sender for the service account in `config`
+    pub fn new(config: FcmConfig) -> Result<Self, PushError> {
+        // Keys pasted into environment variables usually have escaped newlines
+        let private_key = config.private_key.replace("\\n", "\n");
+        let encoding_key = EncodingKey::from_rsa_pem(private_key.as_bytes()).map_err(|e| {
+            PushError::Authentication(format!("Invalid service account private key: {}", e))
+        })?;
+        Ok(Self {
+            client: reqwest::Client::new(),
+            config: FcmConfig {
+                token_url: config.token_url.trim_end_matches('/').to_string(),
+                api_url: config.api_url.trim_end_matches('/').to_string(),
+                ..config
+            },
+            encoding_key,
+            token: Mutex::new(None),
+        })
+    }
+
+    /// Send the pushes queued in `outbox` every [`PUSH_OUTBOX_POLL_INTERVAL`] until the runtime stops
+    ///
+    /// Failed pushes are retried like outbox emails; pushes to unregistered devices are given
+    /// up at once.
+    pub fn spawn_outbox_poller(
+        self: Arc<Self>,
+        outbox: Arc<dyn PushOutboxRepository>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(PUSH_OUTBOX_POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                match drain_push_outbox(outbox.as_ref(), self.as_ref(), chrono::Utc::now()).await {
+                    Ok(summary) if summary.sent + summary.retried + summary.given_up > 0 => {
+                        tracing::info!(
+                            "Push outbox: {} sent, {} to retry, {} given up",
+                            summary.sent,
+                            summary.retried,
+                            summary.given_up
+                        );
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Failed to drain the push outbox: {}", e),
+                }
+            }
+        })
+    }
+
+    /// Current access token, fetching a new one when there is none or it is about to expire
+    async fn access_token(&self) -> Result<String, PushError> {
+        let mut token = self.token.lock().await;
+        if let Some(cached) = token.as_ref() {
+            if cached.expires_at > Instant::now() + TOKEN_EXPIRY_MARGIN {
+                return Ok(cached.access_token.clone());
+            }
+        }
+
+        let fetched = self.fetch_access_token().await?;
+        let access_token = fetched.access_token.clone();
+        *token = Some(CachedToken {
+            access_token: fetched.access_token,
+            expires_at: Instant::now() + Duration::from_secs(fetched.expires_in),
+        });
+        Ok(access_token)
+    }
+
+    /// Exchange a signed service account assertion for an access token
+    async fn fetch_access_token(&self) -> Result<TokenResponse, PushError> {
+        let now = chrono::Utc::now().timestamp();
+        let claims = AssertionClaims {
+            iss: &self.config.client_email,
+            scope: FCM_SCOPE,
+            aud: &self.config.token_url,
+            iat: now,
+            exp: now + ASSERTION_LIFETIME_SECONDS,
+        };
+        let assertion = encode(&Header::new(Algorithm::RS256), &claims, &self.encoding_key)
+            .map_err(|e| PushError::Authentication(format!("Failed to sign assertion: {}", e)))?;
+
+        let response = self
+            .client
+            .post(&self.config.token_url)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", assertion.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| PushError::Authentication(format!("Token request failed: {}", e)))?;
+        let status = response.status();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| PushError::Authentication(format!("Token request failed: {}", e)))?;
+        if !status.is_success() {
+            return Err(PushError::Authentication(format!(
+                "Token endpoint returned {}: {}",
+                status,
+                String::from_utf8_lossy(&body)
+            )));
+        }
+        serde_json::from_slice(&body)
+            .map_err(|e| PushError::Authentication(format!("Unexpected token response: {}", e)))
+    }
+}
+
+/// Whether an FCM error response means the device token is gone for good
+fn is_unregistered(status: reqwest::StatusCode, body: &[u8]) -> bool {
+    let Ok(response) = serde_json::from_slice::<FcmErrorResponse>(body) else {
+        return status == reqwest::StatusCode::NOT_FOUND;
+    };
+    response.error.status == "NOT_FOUND"
+        || response
+            .error
+            .details
+            .iter()
+            .any(|detail| detail.error_code.as_deref() == Some("UNREGISTERED"))
+}
+
+#[async_trait]
+impl PushNotificationSender for FcmPushSender {
+    async fn send_push(&self, message: PushMessage) -> Result<(), PushError> {
+        let access_token = self.access_token().await?;
+        let body = serde_json::json!({
+            "message": {
+                "token": message.device_token,
+                "notification": {
+                    "title": message.title,
+                    "body": message.body,
+                },
+                "data": {
+                    "deep_link": message.deep_link,
+                },
+            }
+        });
+
+        let response = self
+            .client
+            .post(format!(
+                "{}/v1/projects/{}/messages:send",
+                self.config.api_url, self.config.project_id
+            ))
+            .bearer_auth(access_token)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(|e| PushError::SendingFailed(e.to_string()))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body = response.bytes().await.unwrap_or_default();
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            // Fetch a new token on the next attempt
+            *self.token.lock().await = None;
+        }
+        if is_unregistered(status, &body) {
+            return Err(PushError::Unregistered);
+        }
+        Err(PushError::SendingFailed(format!(
+            "FCM returned {}: {}",
+            status,
+            String::from_utf8_lossy(&body)
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unregistered_errors() {
+        let unregistered = br#"{"error": {"code": 404, "status": "NOT_FOUND", "details": [
+            {"@type": "type.googleapis.com/google.firebase.fcm.v1.FcmError", "errorCode": "UNREGISTERED"}
+        ]}}"#;
+        assert!(is_unregistered(
+            reqwest::StatusCode::NOT_FOUND,
+            unregistered
+        ));
+
+        let invalid = br#"{"error": {"code": 400, "status": "INVALID_ARGUMENT", "details": [
+            {"errorCode": "UNREGISTERED"}
+        ]}}"#;
+        assert!(is_unregistered(reqwest::StatusCode::BAD_REQUEST, invalid));
+
+        let unavailable = br#"{"error": {"code": 503, "status": "UNAVAILABLE"}}"#;
+        assert!(!is_unregistered(
+            reqwest::StatusCode::SERVICE_UNAVAILABLE,
+            unavailable
+        ));
+    }
+
+    #[test]
+    fn test_invalid_private_key_is_rejected() {
+        let config = FcmConfig {
+            enabled: true,
+            project_id: "project".to_string(),
+            client_email: "push@project.iam.gserviceaccount.com".to_string(),
+            private_key: "not a key".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            FcmPushSender::new(config),
+            Err(PushError::Authentication(_))
+        ));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod fcm_push_sender;
+pub mod outbox_push_sender;
+pub mod push_sender;
+
+pub use fcm_push_sender::FcmPushSender;
+pub use outbox_push_sender::{drain_push_outbox, OutboxPushSender, PUSH_OUTBOX_POLL_INTERVAL};
+pub use push_sender::{
+    bulk_operation_deep_link, url_deep_link, PushError, PushMessage, PushNotificationSender,
+};
@@ -0,0 +1,178 @@
+use crate::domain::entities::{OutboxEmail, OutboxPush};
+use crate::domain::repositories::PushOutboxRepository;
+use crate::infrastructure::email::OutboxDrainSummary;
+use crate::infrastructure::push::{PushError, PushMessage, PushNotificationSender};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, warn};
+
+/// Time between two drains of the push outbox
+pub const PUSH_OUTBOX_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Pushes sent per drain at most
+const OUTBOX_BATCH_SIZE: usize = 100;
+
+/// How long a claimed push is kept from other pollers
+const OUTBOX_LEASE_MINUTES: i64 = 5;
+
+/// Push sender queueing notifications in the outbox instead of sending them
+///
+/// The poller started by [`FcmPushSender::spawn_outbox_poller`] sends them later. Pushes
+/// carrying a `dedup_key` already queued for the device are dropped.
+///
+/// [`FcmPushSender::spawn_outbox_poller`]: crate::infrastructure::push::FcmPushSender::spawn_outbox_poller
+pub struct OutboxPushSender {
+    outbox: Arc<dyn PushOutboxRepository>,
+}
+
+impl OutboxPushSender {
+    pub fn new(outbox: Arc<dyn PushOutboxRepository>) -> Self {
+        Self { outbox }
+    }
+}
+
+#[async_trait]
+impl PushNotificationSender for OutboxPushSender {
+    async fn send_push(&self, message: PushMessage) -> Result<(), PushError> {
+        self.outbox
+            .enqueue(&message)
+            .await
+            .map_err(|e| PushError::Internal(format!("Failed to queue push: {}", e)))?;
+        Ok(())
+    }
+}
+
+impl From<OutboxPush> for PushMessage {
+    fn from(push: OutboxPush) -> Self {
+        Self {
+            user_id: push.user_id,
+            device_token: push.device_token,
+            title: push.title,
+            body: push.body,
+            deep_link: push.deep_link,
+            dedup_key: push.dedup_key,
+        }
+    }
+}
+
+/// Send the outbox pushes due at `now` with `sender`, recording each result
+pub async fn drain_push_outbox(
+    outbox: &dyn PushOutboxRepository,
+    sender: &dyn PushNotificationSender,
+    now: DateTime<Utc>,
+) -> Result<OutboxDrainSummary, Box<dyn std::error::Error + Send + Sync>> {
+    let pushes = outbox
+        .claim_due(
+            now,
+            chrono::Duration::minutes(OUTBOX_LEASE_MINUTES),
+            OUTBOX_BATCH_SIZE,
+        )
+        .await?;
+
+    let mut summary = OutboxDrainSummary::default();
+    for push in pushes {
+        let (id, user_id, attempt_count) = (push.id, push.user_id, push.attempt_count);
+        match sender.send_push(push.into()).await {
+            Ok(()) => {
+                outbox.mark_sent(id, Utc::now()).await?;
+                summary.sent += 1;
+            }
+            Err(e) => {
+                let retry_at = if e.is_retryable() {
+                    OutboxEmail::retry_at(attempt_count + 1, now)
+                } else {
+                    None
+                };
+                outbox
+                    .mark_attempt_failed(id, &e.to_string(), Utc::now(), retry_at)
+                    .await?;
+                match retry_at {
+                    Some(retry_at) => {
+                        warn!(
+                            "Failed to send outbox push {} to user {}, retrying at {}: {}",
+                            id, user_id, retry_at, e
+                        );
+                        summary.retried += 1;
+                    }
+                    None if !e.is_retryable() => {
+                        warn!("Giving up on outbox push {} to user {}: {}", id, user_id, e);
+                        summary.given_up += 1;
+                    }
+                    None => {
+                        error!(
+                            "ALERT: giving up on outbox push {} to user {} after {} attempts: {}",
+                            id,
+                            user_id,
+                            attempt_count + 1,
+                            e
+                        );
+                        summary.given_up += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_utils::{MockPushOutboxRepository, MockPushSender};
+
+    fn message(device_token: &str, dedup_key: Option<&str>) -> PushMessage {
+        PushMessage {
+            user_id: 1,
+            device_token: device_token.to_string(),
+            title: "Title".to_string(),
+            body: "Body".to_string(),
+            deep_link: "shortener://urls/1".to_string(),
+            dedup_key: dedup_key.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_pushes_are_sent_once() {
+        let outbox = Arc::new(MockPushOutboxRepository::new());
+        let sender = MockPushSender::new();
+        let queue = OutboxPushSender::new(outbox.clone());
+        queue
+            .send_push(message("device-a", Some("milestone:1:100")))
+            .await
+            .unwrap();
+        queue
+            .send_push(message("device-a", Some("milestone:1:100")))
+            .await
+            .unwrap();
+        queue
+            .send_push(message("device-b", Some("milestone:1:100")))
+            .await
+            .unwrap();
+
+        let summary = drain_push_outbox(outbox.as_ref(), &sender, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(summary.sent, 2);
+        let devices: Vec<_> = sender.sent().into_iter().map(|m| m.device_token).collect();
+        assert_eq!(devices, ["device-a", "device-b"]);
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_devices_are_given_up_at_once() {
+        let outbox = MockPushOutboxRepository::new();
+        let sender = MockPushSender::new();
+        sender.set_unregistered(true);
+        outbox.enqueue(&message("gone", None)).await.unwrap();
+
+        let summary = drain_push_outbox(&outbox, &sender, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(summary.given_up, 1);
+        let push = outbox.pushes().remove(0);
+        assert_eq!(push.attempt_count, 1);
+        assert!(push.failed_at.is_some());
+    }
+}
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
+/// Scheme of the links the mobile app opens
+pub const DEEP_LINK_SCHEME: &str = "shortener://";
+
+/// Push notification to one device
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushMessage {
+    /// Owner of the device, so undelivered notifications go away with the account
+    pub user_id: i32,
+    /// Registration token of the device
+    pub device_token: String,
+    pub title: String,
+    pub body: String,
+    /// App screen the notification opens, e.g. `shortener://urls/42`
+    pub deep_link: String,
+    /// Notifications with the same key are delivered to a device only once
+    pub dedup_key: Option<String>,
+}
+
+/// Deep link to the app screen of a URL
+pub fn url_deep_link(url_id: i32) -> String {
+    format!("{}urls/{}", DEEP_LINK_SCHEME, url_id)
+}
+
+/// Deep link to the app screen of a bulk operation
+pub fn bulk_operation_deep_link(operation_id: &str) -> String {
+    format!("{}bulk-operations/{}", DEEP_LINK_SCHEME, operation_id)
+}
+
+/// Push notification sender trait, implemented per push service
+#[async_trait]
+pub trait PushNotificationSender: Send + Sync {
+    /// Send a push notification to its device
+    async fn send_push(&self, message: PushMessage) -> Result<(), PushError>;
+}
+
+/// Push sending errors
+#[derive(Error, Debug)]
+pub enum PushError {
+    /// The device uninstalled the app or its token expired; retrying will not help
+    #[error("Device token is no longer registered")]
+    Unregistered,
+
+    #[error("Push authentication failed: {0}")]
+    Authentication(String),
+
+    #[error("Push sending failed: {0}")]
+    SendingFailed(String),
+
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+impl PushError {
+    /// Whether sending the same notification again can succeed
+    pub fn is_retryable(&self) -> bool {
+        !matches!(self, PushError::Unregistered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deep_links() {
+        assert_eq!(url_deep_link(42), "shortener://urls/42");
+        assert_eq!(
+            bulk_operation_deep_link("op-1"),
+            "shortener://bulk-operations/op-1"
+        );
+    }
+}
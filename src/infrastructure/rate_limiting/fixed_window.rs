@@ -0,0 +1,88 @@
+use super::{RateLimitDecision, RequestRateLimiter};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use std::time::Duration;
+
+/// Keys tracked before counters of past windows are dropped
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// In-memory counter per key, reset at the start of every window
+///
+/// Windows are aligned to multiples of the window length, so a client can send the full
+/// limit just before a boundary and again just after it.
+pub struct FixedWindowLimiter {
+    limit: u32,
+    window_ms: i64,
+    /// Start of the current window and requests counted in it, per key
+    windows: DashMap<String, (i64, u32)>,
+}
+
+impl FixedWindowLimiter {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window_ms: (window.as_millis() as i64).max(1),
+            windows: DashMap::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl RequestRateLimiter for FixedWindowLimiter {
+    async fn check_at(&self, key: &str, now_ms: i64) -> RateLimitDecision {
+        let window_start = now_ms - now_ms.rem_euclid(self.window_ms);
+        if self.windows.len() > PRUNE_THRESHOLD {
+            self.windows.retain(|_, (start, _)| *start >= window_start);
+        }
+
+        let mut entry = self
+            .windows
+            .entry(key.to_string())
+            .or_insert((window_start, 0));
+        let (start, count) = entry.value_mut();
+        if *start != window_start {
+            *start = window_start;
+            *count = 0;
+        }
+
+        if *count < self.limit {
+            *count += 1;
+            RateLimitDecision::Allowed
+        } else {
+            RateLimitDecision::limited_for(window_start + self.window_ms - now_ms)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_counts_per_key_and_resets_each_window() {
+        let limiter = FixedWindowLimiter::new(2, Duration::from_secs(60));
+
+        assert_eq!(limiter.check_at("a", 0).await, RateLimitDecision::Allowed);
+        assert_eq!(
+            limiter.check_at("a", 1_000).await,
+            RateLimitDecision::Allowed
+        );
+        assert_eq!(
+            limiter.check_at("a", 30_000).await,
+            RateLimitDecision::Limited {
+                retry_after_secs: 30
+            }
+        );
+        // Other keys have their own counter
+        assert_eq!(
+            limiter.check_at("b", 30_000).await,
+            RateLimitDecision::Allowed
+        );
+
+        // The next window starts from zero
+        assert_eq!(
+            limiter.check_at("a", 60_000).await,
+            RateLimitDecision::Allowed
+        );
+    }
+}
@@ -0,0 +1,346 @@
+use crate::domain::entities::AuditLogEntry;
+use crate::domain::repositories::AuditLogRepository;
+use crate::infrastructure::config::AbuseIpDbConfig;
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use serde::Deserialize;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{info, warn};
+
+/// AbuseIPDB API used when no other base URL is given
+pub const ABUSEIPDB_API_URL: &str = "https://api.abuseipdb.com";
+
+/// Prefix of the Redis keys holding cached reputation scores
+const KEY_PREFIX: &str = "ip_reputation:";
+
+/// How long a score is reused before the IP is checked again
+pub const SCORE_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Longest a lookup may take before the IP is let through unchecked
+pub const LOOKUP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Reports older than this many days are ignored by the score
+const MAX_AGE_IN_DAYS: u32 = 90;
+
+/// Errors of the store caching reputation scores
+#[derive(Error, Debug)]
+pub enum IpReputationCacheError {
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+}
+
+/// Remembers the reputation scores of recently checked IPs
+#[async_trait]
+pub trait IpReputationCache: Send + Sync {
+    /// Cached score of `ip`, if it has not expired
+    async fn get(&self, ip: &str) -> Result<Option<u8>, IpReputationCacheError>;
+
+    /// Cache the score of `ip` for `ttl`
+    async fn set(&self, ip: &str, score: u8, ttl: Duration) -> Result<(), IpReputationCacheError>;
+}
+
+/// Reputation scores as expiring Redis keys, shared by every instance
+#[derive(Clone)]
+pub struct RedisIpReputationCache {
+    connection: ConnectionManager,
+}
+
+impl RedisIpReputationCache {
+    /// Connect to the Redis server at `url`
+    pub async fn connect(url: &str) -> Result<Self, IpReputationCacheError> {
+        let client = redis::Client::open(url)?;
+        let connection = ConnectionManager::new(client).await?;
+        Ok(Self { connection })
+    }
+}
+
+#[async_trait]
+impl IpReputationCache for RedisIpReputationCache {
+    async fn get(&self, ip: &str) -> Result<Option<u8>, IpReputationCacheError> {
+        let mut connection = self.connection.clone();
+        let score: Option<u8> = redis::cmd("GET")
+            .arg(format!("{}{}", KEY_PREFIX, ip))
+            .query_async(&mut connection)
+            .await?;
+        Ok(score)
+    }
+
+    async fn set(&self, ip: &str, score: u8, ttl: Duration) -> Result<(), IpReputationCacheError> {
+        let mut connection = self.connection.clone();
+        redis::cmd("SET")
+            .arg(format!("{}{}", KEY_PREFIX, ip))
+            .arg(score)
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .query_async::<_, ()>(&mut connection)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Body of the AbuseIPDB check endpoint, reduced to the score
+#[derive(Deserialize)]
+struct CheckResponse {
+    data: CheckData,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CheckData {
+    abuse_confidence_score: u8,
+}
+
+/// What to do with a click, given the reputation of its IP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpVerdict {
+    /// Score at or below the threshold, or no score could be obtained
+    Trusted,
+    /// Score over the threshold; the click is recorded as suspicious
+    Suspicious { score: u8 },
+    /// Score over the threshold and suspicious IPs are refused
+    Blocked { score: u8 },
+}
+
+/// Scores client IPs with the AbuseIPDB check endpoint
+///
+/// Scores are cached for a day, clean ones included, so each IP costs one API call a day.
+/// Lookups fail open: an IP whose score cannot be had within `LOOKUP_TIMEOUT` is trusted.
+#[derive(Clone)]
+pub struct IpReputationService {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    score_threshold: u8,
+    block_suspicious_ips: bool,
+    cache: Arc<dyn IpReputationCache>,
+    audit_log: Option<Arc<dyn AuditLogRepository>>,
+}
+
+impl IpReputationService {
+    pub fn new(config: &AbuseIpDbConfig, cache: Arc<dyn IpReputationCache>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: ABUSEIPDB_API_URL.to_string(),
+            api_key: config.api_key.clone(),
+            score_threshold: config.score_threshold,
+            block_suspicious_ips: false,
+            cache,
+            audit_log: None,
+        }
+    }
+
+    /// Send API requests to another server, such as a mock in tests
+    #[allow(dead_code)]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Refuse suspicious IPs instead of only flagging their clicks
+    pub fn with_block_suspicious_ips(mut self, block_suspicious_ips: bool) -> Self {
+        self.block_suspicious_ips = block_suspicious_ips;
+        self
+    }
+
+    /// Record refused IPs in `audit_log`
+    pub fn with_audit_log(mut self, audit_log: Arc<dyn AuditLogRepository>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Decide how to treat a click of `url_id` from `ip`
+    ///
+    /// Blocked IPs are recorded in the audit log.
+    pub async fn check(&self, ip: IpAddr, url_id: i32) -> IpVerdict {
+        let Some(score) = self.score(ip).await else {
+            return IpVerdict::Trusted;
+        };
+        if score <= self.score_threshold {
+            return IpVerdict::Trusted;
+        }
+        if !self.block_suspicious_ips {
+            return IpVerdict::Suspicious { score };
+        }
+
+        warn!(
+            "Blocked redirect of URL {} for {} (abuse score {})",
+            url_id, ip, score
+        );
+        if let Some(audit_log) = &self.audit_log {
+            let entry = AuditLogEntry::suspicious_ip_block(url_id, &ip.to_string(), score);
+            if let Err(e) = audit_log.record(&entry).await {
+                warn!("Failed to record IP block in the audit log: {}", e);
+            }
+        }
+        IpVerdict::Blocked { score }
+    }
+
+    /// Abuse confidence score of `ip` from 0 to 100, or `None` when it could not be had
+    ///
+    /// Private and loopback addresses are never looked up.
+    pub async fn score(&self, ip: IpAddr) -> Option<u8> {
+        if !is_public(ip) {
+            return None;
+        }
+        match tokio::time::timeout(LOOKUP_TIMEOUT, self.lookup(ip)).await {
+            Ok(score) => score,
+            Err(_) => {
+                warn!("Reputation lookup of {} timed out, trusting it", ip);
+                None
+            }
+        }
+    }
+
+    /// Score of `ip` from the cache, or else from AbuseIPDB
+    async fn lookup(&self, ip: IpAddr) -> Option<u8> {
+        let key = ip.to_string();
+        match self.cache.get(&key).await {
+            Ok(Some(score)) => return Some(score),
+            Ok(None) => {}
+            Err(e) => warn!("Failed to read cached reputation of {}: {}", ip, e),
+        }
+
+        let score = match self.fetch_score(&key).await {
+            Ok(score) => score,
+            Err(e) => {
+                warn!("AbuseIPDB lookup of {} failed, trusting it: {}", ip, e);
+                return None;
+            }
+        };
+        if let Err(e) = self.cache.set(&key, score, SCORE_CACHE_TTL).await {
+            warn!("Failed to cache reputation of {}: {}", ip, e);
+        }
+        Some(score)
+    }
+
+    async fn fetch_score(&self, ip: &str) -> Result<u8, reqwest::Error> {
+        let body = self
+            .client
+            .get(format!("{}/api/v2/check", self.base_url))
+            .query(&[
+                ("ipAddress", ip.to_string()),
+                ("maxAgeInDays", MAX_AGE_IN_DAYS.to_string()),
+            ])
+            .header("Key", &self.api_key)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+
+        match serde_json::from_slice::<CheckResponse>(&body) {
+            Ok(response) => Ok(response.data.abuse_confidence_score.min(100)),
+            Err(e) => {
+                // Treated like a clean IP, and cached so a broken API is not asked again
+                warn!("Unexpected AbuseIPDB response for {}: {}", ip, e);
+                Ok(0)
+            }
+        }
+    }
+}
+
+/// Whether AbuseIPDB can know anything about `ip`
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation())
+        }
+        IpAddr::V6(ip) => {
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                // Unique local (fc00::/7) and link-local (fe80::/10) addresses
+                || (ip.segments()[0] & 0xfe00) == 0xfc00
+                || (ip.segments()[0] & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+/// Create the IP reputation service when AbuseIPDB checks are configured
+///
+/// Scores are cached in Redis; without `redis_url`, or when Redis cannot be reached, the
+/// checks stay off rather than spend an API call on every redirect.
+pub async fn create_ip_reputation_service(
+    config: &AbuseIpDbConfig,
+    redis_url: Option<&str>,
+    block_suspicious_ips: bool,
+) -> Option<IpReputationService> {
+    if !config.is_active() {
+        info!("IP reputation checks disabled: abuse_ipdb is not enabled or has no api_key");
+        return None;
+    }
+    let Some(redis_url) = redis_url else {
+        warn!("IP reputation checks need rate_limit.redis_url, disabling them");
+        return None;
+    };
+    match RedisIpReputationCache::connect(redis_url).await {
+        Ok(cache) => {
+            info!(
+                "IP reputation checks: scores over {} are {}",
+                config.score_threshold,
+                if block_suspicious_ips {
+                    "blocked"
+                } else {
+                    "flagged"
+                }
+            );
+            Some(
+                IpReputationService::new(config, Arc::new(cache))
+                    .with_block_suspicious_ips(block_suspicious_ips),
+            )
+        }
+        Err(e) => {
+            warn!(
+                "Failed to connect to Redis, disabling IP reputation checks: {}",
+                e
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_private_addresses_are_not_looked_up() {
+        for ip in [
+            "10.1.2.3",
+            "192.168.0.1",
+            "127.0.0.1",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in ["8.8.8.8", "2606:4700::1111"] {
+            assert!(is_public(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    /// Needs a Redis server: `REDIS_URL=redis://localhost:6379 cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_cache() {
+        let url = std::env::var("REDIS_URL").unwrap_or("redis://localhost:6379".to_string());
+        let cache = RedisIpReputationCache::connect(&url).await.unwrap();
+        let ip = format!("test-{}", uuid::Uuid::new_v4());
+
+        assert_eq!(cache.get(&ip).await.unwrap(), None);
+        cache.set(&ip, 0, Duration::from_secs(1)).await.unwrap();
+        assert_eq!(cache.get(&ip).await.unwrap(), Some(0));
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        assert_eq!(cache.get(&ip).await.unwrap(), None);
+    }
+}
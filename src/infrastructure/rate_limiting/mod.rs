@@ -0,0 +1,539 @@
+pub mod fixed_window;
+pub mod ip_reputation;
+pub mod sliding_window;
+
+pub use fixed_window::FixedWindowLimiter;
+pub use ip_reputation::{
+    create_ip_reputation_service, IpReputationCache, IpReputationCacheError, IpReputationService,
+    IpVerdict,
+};
+pub use sliding_window::{
+    RateLimitStoreError, RedisSlidingWindowStore, SlidingWindowEntry, SlidingWindowLimiter,
+    SlidingWindowStore,
+};
+
+use crate::infrastructure::config::{RateLimitAlgorithm, RateLimitConfig};
+use crate::infrastructure::http::RealIpExtractor;
+use async_trait::async_trait;
+use axum::{
+    extract::{ConnectInfo, DefaultBodyLimit, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Router,
+};
+use governor::{clock::DefaultClock, state::keyed::DefaultKeyedStateStore, Quota, RateLimiter};
+use std::net::SocketAddr;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+use tower_http::{compression::CompressionLayer, limit::RequestBodyLimitLayer, trace::TraceLayer};
+use tracing::{info, warn};
+
+/// Whether a request may proceed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitDecision {
+    Allowed,
+    Limited { retry_after_secs: u64 },
+}
+
+impl RateLimitDecision {
+    /// Rejection until `wait_ms` milliseconds have passed, rounded up to whole seconds
+    fn limited_for(wait_ms: i64) -> Self {
+        Self::Limited {
+            retry_after_secs: (wait_ms.max(0) as u64).div_ceil(1000),
+        }
+    }
+}
+
+/// Counts requests per key, such as a client IP, against a limit
+#[async_trait]
+pub trait RequestRateLimiter: Send + Sync {
+    /// Count a request of `key` made at `now_ms` (Unix time in milliseconds)
+    async fn check_at(&self, key: &str, now_ms: i64) -> RateLimitDecision;
+
+    /// Count a request of `key` made now
+    async fn check(&self, key: &str) -> RateLimitDecision {
+        self.check_at(key, chrono::Utc::now().timestamp_millis())
+            .await
+    }
+}
+
+/// Create the request rate limiter for the configured algorithm
+///
+/// The sliding window needs Redis; without `redis_url`, or when Redis cannot be reached at
+/// startup, the fixed window is used instead.
+pub async fn create_request_rate_limiter(config: &RateLimitConfig) -> Arc<dyn RequestRateLimiter> {
+    let limit = config.requests_per_window();
+    let window = Duration::from_secs(config.window_size);
+
+    if config.algorithm == RateLimitAlgorithm::SlidingWindow {
+        match &config.redis_url {
+            Some(redis_url) => match RedisSlidingWindowStore::connect(redis_url).await {
+                Ok(store) => {
+                    info!(
+                        "Rate limiting: sliding window of {}s, {} requests",
+                        config.window_size, limit
+                    );
+                    return Arc::new(SlidingWindowLimiter::new(store, limit, window));
+                }
+                Err(e) => warn!(
+                    "Failed to connect to Redis, falling back to fixed window rate limiting: {}",
+                    e
+                ),
+            },
+            None => warn!(
+                "Sliding window rate limiting needs rate_limit.redis_url, falling back to fixed window"
+            ),
+        }
+    }
+
+    info!(
+        "Rate limiting: fixed window of {}s, {} requests",
+        config.window_size, limit
+    );
+    Arc::new(FixedWindowLimiter::new(limit, window))
+}
+
+/// State of the rate limiting middleware
+#[derive(Clone)]
+pub struct RateLimitState {
+    pub real_ip_extractor: RealIpExtractor,
+    pub limiter: Arc<dyn RequestRateLimiter>,
+}
+
+/// Requests per minute allowed for each service account
+pub const SERVICE_ACCOUNT_REQUESTS_PER_MINUTE: u32 = 1000;
+
+/// Rate limiter for service-to-service endpoints, keyed by service account ID
+pub type ServiceAccountRateLimiter = RateLimiter<i32, DefaultKeyedStateStore<i32>, DefaultClock>;
+
+/// Create the service account rate limiter
+pub fn create_service_account_rate_limiter() -> ServiceAccountRateLimiter {
+    let quota = Quota::per_minute(NonZeroU32::new(SERVICE_ACCOUNT_REQUESTS_PER_MINUTE).unwrap());
+    RateLimiter::new(
+        quota,
+        DefaultKeyedStateStore::new(),
+        &DefaultClock::default(),
+    )
+}
+
+/// Rate limiting middleware, keyed by client IP
+pub async fn rate_limit_middleware(
+    State(state): State<RateLimitState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, axum::Json<RateLimitError>)> {
+    // Forwarding headers only count when the connection comes from a trusted proxy
+    let client_ip = state
+        .real_ip_extractor
+        .extract(
+            connect_info.map(|ConnectInfo(addr)| addr.ip()),
+            request.headers(),
+        )
+        .map(|ip| ip.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    match state.limiter.check(&client_ip).await {
+        RateLimitDecision::Allowed => Ok(next.run(request).await),
+        RateLimitDecision::Limited { retry_after_secs } => {
+            warn!(
+                "Rate limit exceeded for IP: {}, retry after {} seconds",
+                client_ip, retry_after_secs
+            );
+            Err(handle_rate_limit_error(retry_after_secs))
+        }
+    }
+}
+
+/// Create request size limiting middleware
+pub fn create_request_size_limiter(max_size: usize) -> RequestBodyLimitLayer {
+    RequestBodyLimitLayer::new(max_size)
+}
+
+/// Limit request bodies on every route of `router` to `max_bytes`
+///
+/// Bodies with a larger `Content-Length` are rejected before the handler runs; streamed
+/// bodies fail once they pass the limit. Axum's own 2MB extractor limit is replaced so it
+/// neither caps larger upload limits nor sits below them.
+pub fn with_body_limit<S>(router: Router<S>, max_bytes: usize) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router
+        .layer(DefaultBodyLimit::max(max_bytes))
+        .layer(create_request_size_limiter(max_bytes))
+}
+
+/// RFC 7807 problem details for an oversized request body
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    pub code: String,
+}
+
+/// Replace the plain-text 413 responses of the body limit with problem details
+///
+/// Handlers that already answer 413 with JSON (e.g. an oversized avatar) are left alone.
+pub async fn body_too_large_middleware(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE {
+        return response;
+    }
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("json"));
+    if is_json {
+        return response;
+    }
+
+    warn!("Rejected request body over the size limit");
+    body_too_large_response()
+}
+
+fn body_too_large_response() -> Response {
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        [(header::CONTENT_TYPE, "application/problem+json")],
+        axum::Json(ProblemDetails {
+            problem_type: "about:blank".to_string(),
+            title: "Payload Too Large".to_string(),
+            status: StatusCode::PAYLOAD_TOO_LARGE.as_u16(),
+            detail: "Request body exceeds the maximum allowed size".to_string(),
+            code: "BODY_TOO_LARGE".to_string(),
+        }),
+    )
+        .into_response()
+}
+
+/// Create compression middleware
+pub fn create_compression_layer() -> CompressionLayer {
+    CompressionLayer::new().br(true).gzip(true).deflate(true)
+}
+
+/// Create tracing middleware
+pub fn create_tracing_layer(
+) -> TraceLayer<tower_http::classify::SharedClassifier<tower_http::classify::ServerErrorsAsFailures>>
+{
+    TraceLayer::new_for_http()
+}
+
+/// Security headers middleware
+pub async fn security_headers_middleware(
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let mut response = next.run(request).await;
+
+    let headers = response.headers_mut();
+
+    // Add security headers
+    headers.insert("X-Content-Type-Options", "nosniff".parse().unwrap());
+    headers.insert("X-Frame-Options", "DENY".parse().unwrap());
+    headers.insert("X-XSS-Protection", "1; mode=block".parse().unwrap());
+    headers.insert(
+        "Referrer-Policy",
+        "strict-origin-when-cross-origin".parse().unwrap(),
+    );
+    headers.insert(
+        "Permissions-Policy",
+        "geolocation=(), microphone=(), camera=()".parse().unwrap(),
+    );
+
+    // Add HSTS header for HTTPS
+    headers.insert(
+        "Strict-Transport-Security",
+        "max-age=31536000; includeSubDomains".parse().unwrap(),
+    );
+
+    // Add Content Security Policy
+    headers.insert(
+        "Content-Security-Policy",
+        "default-src 'self'; script-src 'self'; style-src 'self' 'unsafe-inline'; img-src 'self' data:; font-src 'self'".parse().unwrap(),
+    );
+
+    Ok(response)
+}
+
+/// Rate limiting error response
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct RateLimitError {
+    pub error: String,
+    pub message: String,
+    pub retry_after: u64,
+}
+
+/// Handle rate limiting errors
+pub fn handle_rate_limit_error(retry_after: u64) -> (StatusCode, axum::Json<RateLimitError>) {
+    warn!("Rate limit exceeded, retry after {} seconds", retry_after);
+
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        axum::Json(RateLimitError {
+            error: "rate_limit_exceeded".to_string(),
+            message: "Too many requests. Please try again later.".to_string(),
+            retry_after,
+        }),
+    )
+}
+
+/// Create middleware layers individually
+pub fn create_tracing_layer_simple(
+) -> TraceLayer<tower_http::classify::SharedClassifier<tower_http::classify::ServerErrorsAsFailures>>
+{
+    create_tracing_layer()
+}
+
+pub fn create_compression_layer_simple() -> CompressionLayer {
+    create_compression_layer()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_utils::MockSlidingWindowStore;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{get, post},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    fn body_limited_app(max_bytes: usize) -> Router {
+        let router = Router::new()
+            .route(
+                "/echo",
+                post(|body: axum::body::Bytes| async move { body.len().to_string() }),
+            )
+            .route(
+                "/upload",
+                post(|mut multipart: axum_extra::extract::Multipart| async move {
+                    let mut total = 0;
+                    while let Some(field) = multipart.next_field().await.unwrap() {
+                        total += field.bytes().await.unwrap().len();
+                    }
+                    total.to_string()
+                }),
+            );
+        with_body_limit(router, max_bytes)
+            .layer(axum::middleware::from_fn(body_too_large_middleware))
+    }
+
+    fn post_request(uri: &str, body: Vec<u8>, with_length: bool) -> Request<Body> {
+        let mut builder = Request::builder().method("POST").uri(uri);
+        if with_length {
+            builder = builder.header("content-length", body.len());
+        }
+        builder.body(Body::from(body)).unwrap()
+    }
+
+    async fn assert_body_too_large(response: axum::response::Response) {
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            response.headers()["content-type"],
+            "application/problem+json"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.code, "BODY_TOO_LARGE");
+        assert_eq!(problem.status, 413);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_config_default() {
+        let config = RateLimitConfig::default();
+        assert_eq!(config.requests_per_minute, 60);
+        assert_eq!(config.burst_size, 10);
+    }
+
+    #[tokio::test]
+    async fn test_security_headers_middleware() {
+        let app = Router::new()
+            .route("/", get(|| async { "test" }))
+            .layer(axum::middleware::from_fn(security_headers_middleware));
+
+        let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+
+        // Check security headers are present
+        assert!(headers.contains_key("X-Content-Type-Options"));
+        assert!(headers.contains_key("X-Frame-Options"));
+        assert!(headers.contains_key("X-XSS-Protection"));
+        assert!(headers.contains_key("Referrer-Policy"));
+        assert!(headers.contains_key("Permissions-Policy"));
+        assert!(headers.contains_key("Strict-Transport-Security"));
+        assert!(headers.contains_key("Content-Security-Policy"));
+    }
+
+    #[test]
+    fn test_rate_limit_error_response() {
+        let error = handle_rate_limit_error(60);
+        assert_eq!(error.0, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(error.1.retry_after, 60);
+        assert_eq!(error.1.error, "rate_limit_exceeded");
+    }
+
+    #[tokio::test]
+    async fn test_create_rate_limiter() {
+        let config = RateLimitConfig::default();
+        let rate_limiter = create_request_rate_limiter(&config).await;
+        assert_eq!(
+            rate_limiter.check("test-ip").await,
+            RateLimitDecision::Allowed
+        );
+
+        // Without Redis the sliding window falls back to the fixed window
+        let config = RateLimitConfig {
+            algorithm: RateLimitAlgorithm::SlidingWindow,
+            ..Default::default()
+        };
+        let rate_limiter = create_request_rate_limiter(&config).await;
+        assert_eq!(
+            rate_limiter.check("test-ip").await,
+            RateLimitDecision::Allowed
+        );
+    }
+
+    /// Requests of a client that sends `limit` requests in the last second of a window and
+    /// again in the first second of the next, as (time, allowed) pairs
+    async fn boundary_burst(
+        limiter: &dyn RequestRateLimiter,
+        limit: u32,
+        window_ms: i64,
+    ) -> Vec<(i64, bool)> {
+        let mut requests = Vec::new();
+        for i in 0..2 * i64::from(limit) {
+            // Evenly spread over the two seconds around the boundary
+            let now_ms = window_ms - 1_000 + i * 2_000 / (2 * i64::from(limit));
+            let allowed = limiter.check_at("client", now_ms).await == RateLimitDecision::Allowed;
+            requests.push((now_ms, allowed));
+        }
+        requests
+    }
+
+    /// Most allowed requests in any `window_ms` long span
+    fn max_in_any_window(requests: &[(i64, bool)], window_ms: i64) -> usize {
+        let allowed: Vec<i64> = requests
+            .iter()
+            .filter(|(_, allowed)| *allowed)
+            .map(|(time, _)| *time)
+            .collect();
+        allowed
+            .iter()
+            .map(|start| {
+                allowed
+                    .iter()
+                    .filter(|time| (*start..*start + window_ms).contains(*time))
+                    .count()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[tokio::test]
+    async fn test_burst_at_window_boundary() {
+        let window = Duration::from_secs(60);
+        let window_ms = 60_000;
+
+        // The fixed window lets twice the limit through in two seconds
+        let fixed = FixedWindowLimiter::new(100, window);
+        let requests = boundary_burst(&fixed, 100, window_ms).await;
+        assert_eq!(requests.iter().filter(|(_, allowed)| *allowed).count(), 200);
+        assert_eq!(max_in_any_window(&requests, window_ms), 200);
+
+        // The sliding window never exceeds the limit in any minute
+        let sliding = SlidingWindowLimiter::new(MockSlidingWindowStore::new(), 100, window);
+        let requests = boundary_burst(&sliding, 100, window_ms).await;
+        assert_eq!(requests.iter().filter(|(_, allowed)| *allowed).count(), 100);
+        assert_eq!(max_in_any_window(&requests, window_ms), 100);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_middleware() {
+        let state = RateLimitState {
+            real_ip_extractor: RealIpExtractor::default(),
+            limiter: Arc::new(FixedWindowLimiter::new(2, Duration::from_secs(60))),
+        };
+        let app = Router::new().route("/", get(|| async { "ok" })).layer(
+            axum::middleware::from_fn_with_state(state, rate_limit_middleware),
+        );
+
+        let request = || Request::builder().uri("/").body(Body::empty()).unwrap();
+        for _ in 0..2 {
+            let response = app.clone().oneshot(request()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+        let response = app.oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_body_at_limit_accepted_and_one_byte_over_rejected() {
+        for with_length in [true, false] {
+            let app = body_limited_app(1024);
+            let response = app
+                .clone()
+                .oneshot(post_request("/echo", vec![b'a'; 1024], with_length))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let response = app
+                .oneshot(post_request("/echo", vec![b'a'; 1025], with_length))
+                .await
+                .unwrap();
+            assert_body_too_large(response).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multipart_upload_at_limit() {
+        let boundary = "X-BOUNDARY";
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"avatar\"; filename=\"a.png\"\r\nContent-Type: image/png\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend(vec![0u8; 4096]);
+        body.extend(format!("\r\n--{boundary}--\r\n").into_bytes());
+
+        let multipart_request = |limit_body: Vec<u8>| {
+            Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header(
+                    "content-type",
+                    format!("multipart/form-data; boundary={boundary}"),
+                )
+                .header("content-length", limit_body.len())
+                .body(Body::from(limit_body))
+                .unwrap()
+        };
+
+        let response = body_limited_app(body.len())
+            .oneshot(multipart_request(body.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = body_limited_app(body.len() - 1)
+            .oneshot(multipart_request(body))
+            .await
+            .unwrap();
+        assert_body_too_large(response).await;
+    }
+}
@@ -0,0 +1,228 @@
+use super::{RateLimitDecision, RequestRateLimiter};
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::warn;
+
+/// Prefix of the Redis keys holding request logs
+const KEY_PREFIX: &str = "rate_limit:";
+
+/// Trims the log of a key to the window and records the request if the limit allows it
+///
+/// Runs as one script so concurrent requests of the same key cannot both take the last slot.
+/// Returns `{allowed, oldest}`, where `oldest` is the earliest time left in the log or -1.
+const SLIDING_WINDOW_SCRIPT: &str = r#"
+local key = KEYS[1]
+local now = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+local limit = tonumber(ARGV[3])
+
+redis.call('ZREMRANGEBYSCORE', key, 0, now - window)
+local allowed = 0
+if redis.call('ZCARD', key) < limit then
+    redis.call('ZADD', key, 'NX', now, ARGV[4])
+    allowed = 1
+end
+redis.call('PEXPIRE', key, window)
+
+local oldest = redis.call('ZRANGE', key, 0, 0, 'WITHSCORES')
+return {allowed, tonumber(oldest[2]) or -1}
+"#;
+
+/// Errors of the store holding request logs
+#[derive(Error, Debug)]
+pub enum RateLimitStoreError {
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+}
+
+/// Outcome of recording a request in a sliding window log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlidingWindowEntry {
+    /// Whether the request fit under the limit and was recorded
+    pub allowed: bool,
+    /// Earliest request time still in the window, in milliseconds
+    pub oldest_ms: Option<i64>,
+}
+
+/// Log of request times per key
+#[async_trait]
+pub trait SlidingWindowStore: Send + Sync {
+    /// Drop requests of `key` at or before `now_ms - window_ms`, then record one at `now_ms`
+    /// if fewer than `limit` remain
+    async fn record(
+        &self,
+        key: &str,
+        now_ms: i64,
+        window_ms: i64,
+        limit: u32,
+    ) -> Result<SlidingWindowEntry, RateLimitStoreError>;
+}
+
+/// Request logs in Redis sorted sets, scored by request time
+///
+/// Every instance shares the logs, so the limit holds across the whole deployment.
+#[derive(Clone)]
+pub struct RedisSlidingWindowStore {
+    connection: ConnectionManager,
+    script: redis::Script,
+}
+
+impl RedisSlidingWindowStore {
+    /// Connect to the Redis server at `url`
+    pub async fn connect(url: &str) -> Result<Self, RateLimitStoreError> {
+        let client = redis::Client::open(url)?;
+        let connection = ConnectionManager::new(client).await?;
+        Ok(Self {
+            connection,
+            script: redis::Script::new(SLIDING_WINDOW_SCRIPT),
+        })
+    }
+}
+
+#[async_trait]
+impl SlidingWindowStore for RedisSlidingWindowStore {
+    async fn record(
+        &self,
+        key: &str,
+        now_ms: i64,
+        window_ms: i64,
+        limit: u32,
+    ) -> Result<SlidingWindowEntry, RateLimitStoreError> {
+        // Members must be unique, or requests in the same millisecond would count once
+        let member = format!("{}-{}", now_ms, uuid::Uuid::new_v4());
+        let mut connection = self.connection.clone();
+        let (allowed, oldest_ms): (i64, i64) = self
+            .script
+            .key(format!("{}{}", KEY_PREFIX, key))
+            .arg(now_ms)
+            .arg(window_ms)
+            .arg(limit)
+            .arg(member)
+            .invoke_async(&mut connection)
+            .await?;
+        Ok(SlidingWindowEntry {
+            allowed: allowed == 1,
+            oldest_ms: (oldest_ms >= 0).then_some(oldest_ms),
+        })
+    }
+}
+
+/// Limits the requests of each key in any window ending now
+///
+/// Unlike the fixed window, a burst at a window boundary still counts against the next
+/// `window` of requests. When the store fails, requests are let through rather than
+/// rejecting all traffic.
+pub struct SlidingWindowLimiter<S: SlidingWindowStore> {
+    store: S,
+    limit: u32,
+    window_ms: i64,
+}
+
+impl<S: SlidingWindowStore> SlidingWindowLimiter<S> {
+    pub fn new(store: S, limit: u32, window: Duration) -> Self {
+        Self {
+            store,
+            limit,
+            window_ms: (window.as_millis() as i64).max(1),
+        }
+    }
+}
+
+#[async_trait]
+impl<S: SlidingWindowStore> RequestRateLimiter for SlidingWindowLimiter<S> {
+    async fn check_at(&self, key: &str, now_ms: i64) -> RateLimitDecision {
+        match self
+            .store
+            .record(key, now_ms, self.window_ms, self.limit)
+            .await
+        {
+            Ok(entry) if entry.allowed => RateLimitDecision::Allowed,
+            Ok(entry) => {
+                let oldest_ms = entry.oldest_ms.unwrap_or(now_ms);
+                RateLimitDecision::limited_for(oldest_ms + self.window_ms - now_ms)
+            }
+            Err(e) => {
+                warn!(
+                    "Rate limit store unavailable, letting request through: {}",
+                    e
+                );
+                RateLimitDecision::Allowed
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_utils::MockSlidingWindowStore;
+
+    #[tokio::test]
+    async fn test_limits_requests_in_any_window() {
+        let limiter =
+            SlidingWindowLimiter::new(MockSlidingWindowStore::new(), 2, Duration::from_secs(60));
+
+        assert_eq!(limiter.check_at("a", 0).await, RateLimitDecision::Allowed);
+        assert_eq!(
+            limiter.check_at("a", 40_000).await,
+            RateLimitDecision::Allowed
+        );
+        // The first request leaves the window at 60s
+        assert_eq!(
+            limiter.check_at("a", 50_000).await,
+            RateLimitDecision::Limited {
+                retry_after_secs: 10
+            }
+        );
+        assert_eq!(
+            limiter.check_at("b", 50_000).await,
+            RateLimitDecision::Allowed
+        );
+        assert_eq!(
+            limiter.check_at("a", 60_000).await,
+            RateLimitDecision::Allowed
+        );
+        // Rejected requests are not recorded, so they do not extend the wait
+        assert_eq!(
+            limiter.check_at("a", 70_000).await,
+            RateLimitDecision::Limited {
+                retry_after_secs: 30
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_store_failure_lets_requests_through() {
+        let store = MockSlidingWindowStore::new();
+        store.set_failing(true);
+        let limiter = SlidingWindowLimiter::new(store, 1, Duration::from_secs(60));
+
+        for _ in 0..3 {
+            assert_eq!(limiter.check_at("a", 0).await, RateLimitDecision::Allowed);
+        }
+    }
+
+    /// Needs a Redis server: `REDIS_URL=redis://localhost:6379 cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_store() {
+        let url = std::env::var("REDIS_URL").unwrap_or("redis://localhost:6379".to_string());
+        let store = RedisSlidingWindowStore::connect(&url).await.unwrap();
+        let key = format!("test-{}", uuid::Uuid::new_v4());
+
+        let first = store.record(&key, 1_000, 60_000, 2).await.unwrap();
+        assert!(first.allowed);
+        assert!(store.record(&key, 1_000, 60_000, 2).await.unwrap().allowed);
+        let third = store.record(&key, 2_000, 60_000, 2).await.unwrap();
+        assert_eq!(
+            third,
+            SlidingWindowEntry {
+                allowed: false,
+                oldest_ms: Some(1_000)
+            }
+        );
+        assert!(store.record(&key, 61_000, 60_000, 2).await.unwrap().allowed);
+    }
+}
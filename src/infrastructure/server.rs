@@ -2,64 +2,165 @@ use axum::{
     middleware,
     response::Html,
     routing::{delete, get, patch, post, put},
-    Json, Router,
+    Extension, Router,
 };
-use serde_json::json;
-use std::env;
-use tower_http::cors::{Any, CorsLayer};
+use sqlx::ConnectOptions;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tracing::{info, warn};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 // Clean Architecture imports
 use crate::application::dto::requests::BulkShortenUrlsRequest;
-use crate::application::{ShortenUrlRequest, ShortenUrlUseCase};
-use crate::domain::services::AuthService;
+use crate::application::{
+    GetUrlAnalyticsUseCase, ListUrlsUseCase, ShortenUrlRequest, ShortenUrlUseCase, UpdateUrlUseCase,
+};
+use crate::domain::entities::{OAuthProvider, ShortCodeValidator};
+use crate::domain::repositories::{
+    AccountDeletionTokenRepository, ClickRepository, MagicLinkRepository, OrganizationRepository,
+    PasswordResetRepository, UrlRepository, UserRepository,
+};
+use crate::domain::services::auth_service::VERIFIED_TOKEN_CACHE_TTL;
+use crate::domain::services::cleanup_service::CleanupService;
+use crate::domain::services::click_tracking_service::{ClickTrackingConfig, ClickTrackingService};
+use crate::domain::services::{
+    AnalyticsExportService, AuthService, DataExportService, DomainBlacklist, HashStrategy,
+    InterstitialService, LinkPreviewService, NotificationService, OAuthClientConfig, OAuthService,
+    OrgLimits, OrgService, RandomStrategy, SequentialStrategy, ServiceAccountService,
+    ShortCodeStrategy,
+};
 use crate::domain::UrlService;
+use crate::infrastructure::analytics_cache::{
+    AnalyticsCache, RedisAnalyticsCache, UrlAnalyticsCacheInvalidator,
+};
+use crate::infrastructure::cache_health_check::CacheHealthCheck;
+use crate::infrastructure::click_deduplication::{ClickDeduplicator, RedisClickDeduplicator};
+use crate::infrastructure::config::{env_var, AppConfig, ShortCodeStrategyType};
+use crate::infrastructure::error_tracking::init_sentry;
+use crate::infrastructure::event_bus::InProcessEventBus;
+use crate::infrastructure::http::middleware::error_middleware::sentry_error_middleware;
+use crate::infrastructure::http::middleware::idempotency_middleware::{
+    IdempotencyLayer, IdempotencyStore, InMemoryIdempotencyStore, RedisIdempotencyStore,
+};
+use crate::infrastructure::http::RealIpExtractor;
+use crate::infrastructure::object_storage::{LocalObjectStorage, ObjectStorage, S3ObjectStorage};
+use crate::infrastructure::push::{FcmPushSender, OutboxPushSender, PushNotificationSender};
+use crate::infrastructure::server_info::{
+    log_route_table, print_startup_banner, route_table, ServerInfo,
+};
+use crate::infrastructure::tls::TlsCertificate;
+use crate::infrastructure::trace_context::{opentelemetry_layer, TraceIdExtractor, TraceIdFormat};
+use crate::infrastructure::user_invalidation::RedisUserInvalidation;
 use crate::infrastructure::{
-    PasswordResetRateLimiter, PostgresAccountDeletionTokenRepository,
-    PostgresPasswordResetRepository, PostgresUrlRepository, PostgresUserRepository,
-    SmtpEmailSender,
+    metrics, DatabaseHealthCheck, OutboxEmailSender, PasswordResetRateLimitConfig,
+    PasswordResetRateLimiter, PostgresAccountDeletionTokenRepository, PostgresArchiveRepository,
+    PostgresAuditLogRepository, PostgresClickRepository, PostgresDomainBlacklistRepository,
+    PostgresEmailChangeRepository, PostgresEmailOutboxRepository, PostgresExportJobRepository,
+    PostgresMagicLinkRepository, PostgresNotificationPreferencesRepository,
+    PostgresOrganizationRepository, PostgresPasswordResetRepository, PostgresPushOutboxRepository,
+    PostgresServiceAccountRepository, PostgresSessionRepository,
+    PostgresShortCodeSequenceRepository, PostgresUrlMetadataRepository, PostgresUrlRepository,
+    PostgresUserRepository, PostgresUserUrlStatsRepository, ReplicaAwarePool, SmtpEmailSender,
 };
+use crate::presentation::graphql::GraphQLServices;
 use crate::presentation::{
+    add_blocked_domain_handler, add_organization_member_handler,
     async_batch_url_operations_handler, async_bulk_shorten_urls_handler,
-    batch_url_operations_handler, bulk_delete_handler, bulk_expiration_update_handler,
-    bulk_shorten_urls_handler, bulk_status_update_handler, cancel_account_deletion,
-    cancel_bulk_operation_handler, confirm_account_deletion, deactivate_url_handler,
-    delete_account, delete_profile_picture, extend_expiration_handler,
-    get_bulk_operation_progress_handler, get_expiration_info_handler, get_expiring_urls_handler,
-    get_my_profile, get_privacy_recommendations, get_privacy_settings, get_profile_by_username,
-    get_public_profile, get_user_operations_handler, login_handler, patch_my_profile,
-    reactivate_url_handler, redirect_handler, register_handler, request_account_deletion,
-    request_password_reset, reset_password, set_expiration_handler, shorten_url_handler,
-    update_my_profile, update_privacy_settings, upload_profile_picture, validate_reset_token,
-    AppState,
+    batch_url_operations_handler, bulk_delete_handler, bulk_expiration_clear_handler,
+    bulk_expiration_update_handler, bulk_shorten_urls_handler, bulk_status_update_handler,
+    cancel_account_deletion, cancel_bulk_operation_handler, chain_operation_handler,
+    change_password, confirm_account_deletion, confirm_email_change_new, confirm_email_change_old,
+    confirm_redirect_handler, create_conversion_goal_handler, create_organization_handler,
+    create_service_account_handler, deactivate_url_handler, delete_account,
+    delete_conversion_goal_handler, delete_organization_handler, delete_profile_picture,
+    download_analytics_export_handler, download_data_export, duplicate_url_handler, export_my_data,
+    export_user_data_admin_handler, extend_expiration_handler, get_bulk_operation_progress_handler,
+    get_cleanup_config_handler, get_click_dedup_ratio_handler, get_click_patterns_handler,
+    get_dashboard_handler, get_db_pool_stats_handler, get_expiration_info_handler,
+    get_expiring_urls_handler, get_features_handler, get_link_preview_handler, get_my_profile,
+    get_notification_preferences_handler, get_operation_chain_handler,
+    get_operation_results_handler, get_organization_handler, get_preview_settings_handler,
+    get_privacy_preview, get_privacy_recommendations, get_privacy_settings,
+    get_profile_by_username, get_public_profile, get_server_info_handler, get_slow_queries_handler,
+    get_top_urls_handler, get_url_analytics_handler, get_url_analytics_summary_handler,
+    get_url_config_handler, get_url_handler, get_user_operations_handler,
+    get_utm_attribution_handler, graphiql_handler, graphql_handler, head_redirect_handler,
+    health_handler, introspect_token_handler, list_analytics_exports_handler,
+    list_archive_records_handler, list_blocked_domains_handler, list_organization_members_handler,
+    list_organization_urls_handler, list_organizations_admin_handler, list_organizations_handler,
+    list_sessions_handler, list_suspicious_urls_handler, list_urls_admin_handler,
+    list_urls_handler, liveness_handler, login_handler, oauth_callback, patch_my_profile,
+    preview_cleanup_handler, reactivate_url_handler, readiness_handler, rebuild_user_stats_handler,
+    redirect_handler, reencode_short_codes_handler, register_device_token, register_handler,
+    reload_tls_handler, remove_blocked_domain_handler, remove_device_token,
+    remove_organization_member_handler, rename_short_code_handler, report_conversion_handler,
+    reprioritize_operation_handler, request_account_deletion, request_analytics_export_handler,
+    request_email_change, request_magic_link, request_password_reset, reset_password,
+    restore_url_handler, revoke_other_sessions_handler, revoke_session_handler,
+    run_cleanup_handler, search_users_handler, set_expiration_handler, shorten_url_handler,
+    start_oauth_login, suspend_user_handler, transfer_url_handler, trigger_digest_handler,
+    unsuspend_user_handler, update_my_profile, update_notification_preferences_handler,
+    update_organization_handler, update_preview_settings_handler, update_privacy_settings,
+    update_url_config_handler, update_url_handler, upload_profile_picture,
+    urls_by_original_handler, validate_reset_token, verify_email_handler, verify_magic_link,
+    AppStateBuilder, ConcreteAppState,
 };
 // Note: utoipa __path_* structs are generated by the macro and auto-included in OpenAPI docs
 // We don't need to explicitly import them anymore after refactoring
 use crate::infrastructure::rate_limiting::{
-    create_compression_layer_simple, create_request_size_layer, create_tracing_layer_simple,
-    rate_limit_middleware, security_headers_middleware, RateLimitConfig,
+    body_too_large_middleware, create_compression_layer_simple, create_ip_reputation_service,
+    create_request_rate_limiter, create_service_account_rate_limiter, create_tracing_layer_simple,
+    rate_limit_middleware, security_headers_middleware, with_body_limit, RateLimitState,
+    SERVICE_ACCOUNT_REQUESTS_PER_MINUTE,
 };
 
+/// Hours between runs of the background cleanup service
+const CLEANUP_INTERVAL_HOURS: u64 = 24;
+
+/// How long open HTTPS connections may take to finish after a shutdown signal
+const TLS_SHUTDOWN_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(30);
+
 pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
     // Load environment variables from .env file
     dotenv::dotenv().ok();
 
-    // Initialize tracing
-    tracing_subscriber::fmt::init();
+    // Initialize tracing; log lines within a request start with its trace ID. Errors are
+    // also sent to Sentry once it is configured, with the warnings and infos before them as
+    // breadcrumbs
+    tracing_subscriber::registry()
+        .with(LevelFilter::INFO)
+        .with(opentelemetry_layer())
+        .with(tracing_subscriber::fmt::layer().event_format(TraceIdFormat::default()))
+        .with(sentry::integrations::tracing::layer())
+        .init();
 
-    // Get database URL: prefer DATABASE_URL; otherwise, assemble from POSTGRES_* parts (shared with Docker)
-    let database_url = if let Ok(url) = env::var("DATABASE_URL") {
+    // Load configuration: defaults -> config file (CONFIG_FILE) -> environment variables
+    let app_config = AppConfig::load()?;
+    info!(
+        "Configuration loaded ({:?} environment)",
+        app_config.environment
+    );
+    print_startup_banner(&app_config);
+
+    // Report errors to Sentry when a DSN is configured; the guard flushes them on shutdown
+    let sentry_guard = init_sentry(&app_config);
+    if sentry_guard.is_some() {
+        info!("Sentry error tracking enabled");
+    }
+
+    // Get database URL: prefer database.url / DATABASE_URL; otherwise, assemble from POSTGRES_* parts (shared with Docker)
+    let database_url = if let Some(url) = app_config.database.url.clone() {
         url
     } else {
-        let host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".to_string());
-        let port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "5432".to_string());
+        let host = env_var("POSTGRES_HOST").unwrap_or_else(|| "localhost".to_string());
+        let port = env_var("POSTGRES_PORT").unwrap_or_else(|| "5432".to_string());
         let name =
-            env::var("POSTGRES_DB").expect("POSTGRES_DB must be set (or provide DATABASE_URL)");
+            env_var("POSTGRES_DB").expect("POSTGRES_DB must be set (or provide DATABASE_URL)");
         let user =
-            env::var("POSTGRES_USER").expect("POSTGRES_USER must be set (or provide DATABASE_URL)");
-        let password = env::var("POSTGRES_PASSWORD")
+            env_var("POSTGRES_USER").expect("POSTGRES_USER must be set (or provide DATABASE_URL)");
+        let password = env_var("POSTGRES_PASSWORD")
             .expect("POSTGRES_PASSWORD must be set (or provide DATABASE_URL)");
         format!(
             "postgresql://{}:{}@{}:{}/{}",
@@ -67,62 +168,249 @@ pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
         )
     };
 
+    // Slow query logging (on by default in development); sqlx keeps its own 1s warning otherwise
+    let mut connect_options = app_config
+        .database
+        .apply_ssl(database_url.parse::<sqlx::postgres::PgConnectOptions>()?);
+    if app_config.database.log_slow_queries {
+        connect_options = connect_options.log_slow_statements(
+            log::LevelFilter::Warn,
+            std::time::Duration::from_millis(app_config.database.slow_query_threshold_ms),
+        );
+        info!(
+            "Logging queries slower than {}ms",
+            app_config.database.slow_query_threshold_ms
+        );
+    }
+
     // Connect to database using new clean architecture
-    let pool = sqlx::PgPool::connect(&database_url).await?;
-    let url_repository = PostgresUrlRepository::new(pool.clone());
-    let user_repository = PostgresUserRepository::new(pool.clone());
-    let password_reset_repository = PostgresPasswordResetRepository::new(pool.clone());
-    let account_deletion_repository = PostgresAccountDeletionTokenRepository::new(pool);
+    let pool = sqlx::postgres::PgPoolOptions::new()
+        .max_connections(app_config.database.pool_max_connections)
+        .min_connections(app_config.database.pool_min_connections)
+        .acquire_timeout(std::time::Duration::from_millis(
+            app_config.database.pool_acquire_timeout_ms,
+        ))
+        .idle_timeout(std::time::Duration::from_millis(
+            app_config.database.pool_idle_timeout_ms,
+        ))
+        .connect_with(connect_options)
+        .await?;
+    let replica_url_repository = match &app_config.database.replica_url {
+        Some(replica_url) => {
+            let replica_pool = sqlx::postgres::PgPoolOptions::new()
+                .max_connections(app_config.database.pool_max_connections)
+                .min_connections(app_config.database.pool_min_connections)
+                .acquire_timeout(std::time::Duration::from_millis(
+                    app_config.database.pool_acquire_timeout_ms,
+                ))
+                .idle_timeout(std::time::Duration::from_millis(
+                    app_config.database.pool_idle_timeout_ms,
+                ))
+                .connect_with(
+                    app_config
+                        .database
+                        .apply_ssl(replica_url.parse::<sqlx::postgres::PgConnectOptions>()?),
+                )
+                .await?;
+            info!(
+                "Sending reads to the database replica while it lags less than {}ms",
+                app_config.database.replica_lag_max_ms
+            );
+            Some(
+                PostgresUrlRepository::new(replica_pool).with_query_timeouts(
+                    app_config.database.short_query_timeout_ms,
+                    app_config.database.long_query_timeout_ms,
+                ),
+            )
+        }
+        None => None,
+    };
+    // The application state holds repositories as trait objects
+    let url_repository: std::sync::Arc<dyn UrlRepository> = std::sync::Arc::new(
+        ReplicaAwarePool::new(
+            PostgresUrlRepository::new(pool.clone()).with_query_timeouts(
+                app_config.database.short_query_timeout_ms,
+                app_config.database.long_query_timeout_ms,
+            ),
+            replica_url_repository,
+        )
+        .with_replica_lag_max_ms(app_config.database.replica_lag_max_ms),
+    );
+    let user_repository: std::sync::Arc<dyn UserRepository> =
+        std::sync::Arc::new(PostgresUserRepository::new(pool.clone()));
+    let password_reset_repository: std::sync::Arc<dyn PasswordResetRepository> =
+        std::sync::Arc::new(PostgresPasswordResetRepository::new(pool.clone()));
+    let account_deletion_repository: std::sync::Arc<dyn AccountDeletionTokenRepository> =
+        std::sync::Arc::new(PostgresAccountDeletionTokenRepository::new(pool.clone()));
+    let click_repository: std::sync::Arc<dyn ClickRepository> =
+        std::sync::Arc::new(PostgresClickRepository::new(pool.clone()));
+    let organization_repository: std::sync::Arc<dyn OrganizationRepository> =
+        std::sync::Arc::new(PostgresOrganizationRepository::new(pool.clone()));
+    let magic_link_repository: std::sync::Arc<dyn MagicLinkRepository> =
+        std::sync::Arc::new(PostgresMagicLinkRepository::new(pool.clone()));
+    let email_change_repository: std::sync::Arc<
+        dyn crate::domain::repositories::EmailChangeRepository,
+    > = std::sync::Arc::new(PostgresEmailChangeRepository::new(pool.clone()));
+    let session_repository = PostgresSessionRepository::new(pool.clone());
+    let domain_blacklist_repository = PostgresDomainBlacklistRepository::new(pool.clone());
+    let service_account_repository = PostgresServiceAccountRepository::new(pool.clone());
+    let url_metadata_repository = PostgresUrlMetadataRepository::new(pool.clone());
+    let audit_log_repository = PostgresAuditLogRepository::new(pool.clone());
+    let user_url_stats_repository: std::sync::Arc<
+        dyn crate::domain::repositories::UserUrlStatsRepository,
+    > = std::sync::Arc::new(PostgresUserUrlStatsRepository::new(pool.clone()));
+    let archive_repository = PostgresArchiveRepository::new(pool.clone());
+    let export_job_repository = PostgresExportJobRepository::new(pool.clone());
+    let notification_preferences_repository =
+        PostgresNotificationPreferencesRepository::new(pool.clone());
+    let email_outbox_repository: std::sync::Arc<
+        dyn crate::domain::repositories::EmailOutboxRepository,
+    > = std::sync::Arc::new(PostgresEmailOutboxRepository::new(pool.clone()));
+    let push_outbox_repository: std::sync::Arc<
+        dyn crate::domain::repositories::PushOutboxRepository,
+    > = std::sync::Arc::new(PostgresPushOutboxRepository::new(pool.clone()));
+    let short_code_sequence_repository = PostgresShortCodeSequenceRepository::new(pool.clone());
+    let database_health = DatabaseHealthCheck::new(pool);
+    database_health.clone().spawn_pool_monitor();
     info!("Connected to PostgreSQL database with clean architecture");
+    // Readiness probes ping Redis too when it is configured
+    let cache_health =
+        app_config.rate_limit.redis_url.as_deref().and_then(
+            |redis_url| match CacheHealthCheck::new(redis_url) {
+                Ok(cache_health) => Some(cache_health),
+                Err(e) => {
+                    warn!("Invalid Redis URL, readiness probes skip the cache: {}", e);
+                    None
+                }
+            },
+        );
 
     // Configure rate limiting
-    let rate_limit_config = RateLimitConfig {
-        requests_per_minute: env::var("RATE_LIMIT_REQUESTS_PER_MINUTE")
-            .unwrap_or_else(|_| "60".to_string())
-            .parse()
-            .unwrap_or(60),
-        burst_size: env::var("RATE_LIMIT_BURST_SIZE")
-            .unwrap_or_else(|_| "10".to_string())
-            .parse()
-            .unwrap_or(10),
-        max_request_size: env::var("MAX_REQUEST_SIZE")
-            .unwrap_or_else(|_| "1048576".to_string()) // 1MB
-            .parse()
-            .unwrap_or(1024 * 1024),
-    };
-
+    let request_rate_limiter = create_request_rate_limiter(&app_config.rate_limit).await;
     info!(
-        "Rate limiting configured: {} req/min, burst: {}, max size: {} bytes",
-        rate_limit_config.requests_per_minute,
-        rate_limit_config.burst_size,
-        rate_limit_config.max_request_size
+        "Request body limit: {} bytes ({} bytes for uploads)",
+        app_config.max_request_body_bytes, app_config.max_upload_body_bytes
     );
 
     // Configure CORS
+    let allowed_origins = if app_config.cors.allowed_origins.is_empty() {
+        AllowOrigin::any()
+    } else {
+        let origins = app_config
+            .cors
+            .allowed_origins
+            .iter()
+            .map(|origin| origin.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+        AllowOrigin::list(origins)
+    };
     let cors = CorsLayer::new()
-        .allow_origin(Any)
+        .allow_origin(allowed_origins)
         .allow_methods(Any)
         .allow_headers(Any);
 
     // Create clean architecture components
-    let url_service = UrlService::new(url_repository.clone());
-    let base_url = env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8000".to_string());
-    let shorten_url_use_case = ShortenUrlUseCase::new(url_service.clone(), base_url);
+    // Services react to URL events through subscriptions made once they are created
+    let event_bus = InProcessEventBus::default();
+    let org_limits = OrgLimits::default();
+    let url_service = UrlService::new(url_repository.clone())
+        .with_short_code_length(app_config.short_code.length)
+        .with_short_code_alphabet(app_config.short_code.generated_alphabet.clone())
+        .with_short_code_strategy(create_short_code_strategy(
+            app_config.short_code_strategy,
+            short_code_sequence_repository,
+        ))
+        .with_min_phonetic_distance(app_config.short_code.min_phonetic_distance)
+        .with_user_repository(user_repository.clone())
+        .with_audit_log(std::sync::Arc::new(audit_log_repository.clone()))
+        .with_max_urls_per_user(app_config.max_urls_per_user)
+        .with_max_urls_per_organization(org_limits.max_urls_per_organization)
+        .with_event_bus(std::sync::Arc::new(event_bus.clone()))
+        .with_user_url_stats(user_url_stats_repository.clone());
+    let base_url = app_config.base_url.clone();
+
+    // Domains that may not be shortened, seeded from the optional blacklist file
+    let domain_blacklist = DomainBlacklist::new(std::sync::Arc::new(domain_blacklist_repository));
+    if let Some(path) = &app_config.domain_blacklist_file {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            format!(
+                "Failed to read domain blacklist file {}: {}",
+                path.display(),
+                e
+            )
+        })?;
+        let added = domain_blacklist.import(&contents).await?;
+        info!(
+            "Loaded domain blacklist from {} ({} new domain(s))",
+            path.display(),
+            added
+        );
+    }
+    let shorten_url_use_case = ShortenUrlUseCase::new(url_service.clone(), base_url)
+        .with_hateoas(app_config.hateoas_enabled)
+        .with_domain_blacklist(domain_blacklist.clone())
+        .with_allowed_ports(app_config.url_allowed_ports.clone())
+        .with_short_code_validator(ShortCodeValidator::new(
+            app_config.short_code.min_length,
+            app_config.short_code.max_length,
+            &app_config.short_code.alphabet,
+        ));
+    let update_url_use_case = UpdateUrlUseCase::new(shorten_url_use_case.clone());
+    let list_urls_use_case = ListUrlsUseCase::new(
+        url_service.clone(),
+        url_repository.clone(),
+        shorten_url_use_case.base_url().to_string(),
+    );
 
     // Create auth service
-    let jwt_secret = env::var("JWT_SECRET").unwrap_or_else(|_| "your-secret-key".to_string());
-    let auth_service = AuthService::new(user_repository.clone(), jwt_secret);
+    let jwt_secret = env_var("JWT_SECRET").unwrap_or_else(|| "your-secret-key".to_string());
+    let admin_user_ids: Vec<i32> = env_var("ADMIN_USER_IDS")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|id| id.trim().parse().ok())
+        .collect();
+    info!("Admin access granted to {} user(s)", admin_user_ids.len());
+    // Preview confirmations are signed with the JWT secret so no extra key has to be managed
+    let interstitial_service = InterstitialService::new(jwt_secret.clone())
+        .with_require_preview_for_unverified(app_config.require_preview_for_unverified);
+    // Verified tokens are trusted for a few seconds; with Redis, suspensions, password changes
+    // and revoked sessions evict them on every instance
+    let mut auth_service = AuthService::new(user_repository.clone(), jwt_secret)
+        .with_admin_user_ids(admin_user_ids)
+        .with_token_expiration_hours(app_config.jwt_expiration_hours)
+        .with_session_repository(std::sync::Arc::new(session_repository))
+        .with_token_cache_ttl(VERIFIED_TOKEN_CACHE_TTL);
+    if let Some(redis_url) = app_config.rate_limit.redis_url.as_deref() {
+        match RedisUserInvalidation::connect(redis_url).await {
+            Ok(user_invalidation) => {
+                let local = auth_service.clone();
+                if let Err(e) = user_invalidation
+                    .subscribe(move |user_id| local.evict_cached_user(user_id))
+                    .await
+                {
+                    warn!("Failed to subscribe to user invalidations: {}", e);
+                }
+                auth_service =
+                    auth_service.with_user_invalidation(std::sync::Arc::new(user_invalidation));
+                info!("Verified token cache invalidated across instances through Redis");
+            }
+            Err(e) => warn!(
+                "Redis unavailable, verified token cache invalidated on this instance only: {}",
+                e
+            ),
+        }
+    }
 
-    // Create email sender (optional)
-    let email_sender = if env::var("SMTP_ENABLED").unwrap_or_else(|_| "false".to_string()) == "true"
-    {
+    // Create email sender (optional); emails are queued in the outbox and sent by its poller
+    let email_sender = if app_config.email_enabled {
         match SmtpEmailSender::from_env() {
             Ok(sender) => {
                 info!("Email sender configured successfully");
-                Some(std::sync::Arc::new(sender)
-                    as std::sync::Arc<
-                        dyn crate::infrastructure::email::EmailSender,
-                    >)
+                std::sync::Arc::new(sender).spawn_outbox_poller(email_outbox_repository.clone());
+                Some(
+                    std::sync::Arc::new(OutboxEmailSender::new(email_outbox_repository.clone()))
+                        as std::sync::Arc<dyn crate::infrastructure::email::EmailSender>,
+                )
             }
             Err(e) => {
                 warn!("Failed to configure email sender: {}", e);
@@ -130,47 +418,410 @@ pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
     } else {
-        info!("Email sender disabled (SMTP_ENABLED=false)");
+        info!(
+            "Email sender disabled (SMTP_ENABLED=false or {:?} environment)",
+            app_config.environment
+        );
+        None
+    };
+
+    // Push notifications are queued in their own outbox and sent to FCM by its poller
+    let push_sender = if app_config.fcm.is_active() {
+        match FcmPushSender::new(app_config.fcm.clone()) {
+            Ok(sender) => {
+                info!(
+                    "Push notifications sent through FCM project {}",
+                    app_config.fcm.project_id
+                );
+                std::sync::Arc::new(sender).spawn_outbox_poller(push_outbox_repository.clone());
+                Some(
+                    std::sync::Arc::new(OutboxPushSender::new(push_outbox_repository.clone()))
+                        as std::sync::Arc<dyn PushNotificationSender>,
+                )
+            }
+            Err(e) => {
+                warn!("Failed to configure push notifications: {}", e);
+                None
+            }
+        }
+    } else {
+        info!("Push notifications disabled: fcm is not enabled or has no service account");
         None
     };
 
     // Create password reset rate limiter
-    let password_reset_rate_limiter = std::sync::Arc::new(PasswordResetRateLimiter::new_default());
-    info!("Password reset rate limiter configured: 5 req/hour per IP, 3 req/hour per email, 5 min cooldown");
+    let password_reset_rate_limit_config = PasswordResetRateLimitConfig::default();
+    info!(
+        "Password reset rate limiter configured: {} req/hour per IP, {} req per {}s per email, {} min cooldown",
+        password_reset_rate_limit_config.requests_per_hour_per_ip,
+        password_reset_rate_limit_config.per_email_max_requests,
+        password_reset_rate_limit_config.per_email_window_seconds,
+        password_reset_rate_limit_config.cooldown_minutes
+    );
+    let password_reset_rate_limiter = std::sync::Arc::new(PasswordResetRateLimiter::new(
+        password_reset_rate_limit_config,
+    ));
 
-    // Create application state
-    let app_state = AppState::new(
-        shorten_url_use_case,
-        url_repository,
-        url_service,
-        auth_service,
-        user_repository,
-        password_reset_repository,
-        account_deletion_repository,
-        email_sender,
-        password_reset_rate_limiter,
+    // Magic links share the governor-backed limiter, keyed by email only
+    let magic_link_rate_limiter = std::sync::Arc::new(PasswordResetRateLimiter::new(
+        PasswordResetRateLimitConfig {
+            per_email_max_requests: 3,
+            per_email_window_seconds: 3600,
+            ..PasswordResetRateLimitConfig::default()
+        },
+    ));
+    info!("Magic link rate limiter configured: 3 req/hour per email");
+
+    // Service accounts authenticate with API keys for service-to-service endpoints
+    let service_account_service =
+        ServiceAccountService::new(std::sync::Arc::new(service_account_repository));
+    let service_account_rate_limiter = std::sync::Arc::new(create_service_account_rate_limiter());
+    info!(
+        "Service account rate limiter configured: {} req/min per account",
+        SERVICE_ACCOUNT_REQUESTS_PER_MINUTE
+    );
+
+    // Clicks are buffered and written in batches off the redirect path; repeat clicks from
+    // the same IP are detected in Redis and every click is recorded without it
+    let click_deduplicator: Option<std::sync::Arc<dyn ClickDeduplicator>> =
+        match &app_config.rate_limit.redis_url {
+            Some(redis_url) => match RedisClickDeduplicator::connect(redis_url).await {
+                Ok(deduplicator) => {
+                    info!(
+                        "Click deduplication: {}s window unless a URL overrides it",
+                        app_config.click_dedup_window_seconds
+                    );
+                    Some(std::sync::Arc::new(deduplicator))
+                }
+                Err(e) => {
+                    warn!("Failed to connect to Redis, recording every click: {}", e);
+                    None
+                }
+            },
+            None => {
+                info!("Click deduplication disabled: rate_limit.redis_url is not set");
+                None
+            }
+        };
+    // Retries of create requests sent with an Idempotency-Key replay the first response; keys
+    // are shared by every instance through Redis and kept per process without it
+    let idempotency_store: std::sync::Arc<dyn IdempotencyStore> =
+        match &app_config.rate_limit.redis_url {
+            Some(redis_url) => match RedisIdempotencyStore::connect(redis_url).await {
+                Ok(store) => std::sync::Arc::new(store),
+                Err(e) => {
+                    warn!(
+                        "Failed to connect to Redis, keeping idempotency keys in memory: {}",
+                        e
+                    );
+                    std::sync::Arc::new(InMemoryIdempotencyStore::new())
+                }
+            },
+            None => std::sync::Arc::new(InMemoryIdempotencyStore::new()),
+        };
+    let idempotency =
+        IdempotencyLayer::new(idempotency_store, std::sync::Arc::new(auth_service.clone()));
+    // URL analytics are cached in Redis for a minute and dropped when the URL is clicked
+    let analytics_cache: Option<std::sync::Arc<dyn AnalyticsCache>> =
+        match &app_config.rate_limit.redis_url {
+            Some(redis_url) => match RedisAnalyticsCache::connect(redis_url).await {
+                Ok(cache) => Some(std::sync::Arc::new(cache)),
+                Err(e) => {
+                    warn!(
+                        "Failed to connect to Redis, URL analytics are not cached: {}",
+                        e
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+    let mut click_tracking_service = ClickTrackingService::with_deduplicator(
+        click_repository.clone(),
+        ClickTrackingConfig {
+            dedup_window: std::time::Duration::from_secs(
+                app_config.click_dedup_window_seconds.into(),
+            ),
+            enabled: app_config.features.enable_click_tracking,
+            ..ClickTrackingConfig::default()
+        },
+        click_deduplicator,
+    )
+    .with_event_bus(std::sync::Arc::new(event_bus.clone()))
+    .with_user_url_stats(user_url_stats_repository.clone());
+    let mut get_url_analytics_use_case =
+        GetUrlAnalyticsUseCase::new(url_repository.clone(), click_repository.clone());
+    if let Some(cache) = analytics_cache {
+        click_tracking_service = click_tracking_service.with_analytics_invalidator(
+            std::sync::Arc::new(UrlAnalyticsCacheInvalidator::new(cache.clone())),
+        );
+        get_url_analytics_use_case = get_url_analytics_use_case.with_cache(cache);
+    }
+    info!("Click tracking configured: buffer 1000 clicks, batches of 100, flushed every 500ms");
+
+    // Organization memberships, URL quota and URL creation rate limit
+    let org_service = OrgService::with_limits(organization_repository.clone(), org_limits);
+
+    // Client IPs for click tracking and rate limiting; forwarding headers need a trusted proxy
+    let real_ip_extractor = RealIpExtractor::new(app_config.trusted_proxies.clone());
+    info!(
+        "Trusting forwarded client IPs from {} proxy range(s)",
+        app_config.trusted_proxies.len()
+    );
+
+    // Redirecting IPs are scored by AbuseIPDB; scores are cached in Redis for a day
+    let ip_reputation_service = create_ip_reputation_service(
+        &app_config.abuse_ipdb,
+        app_config.rate_limit.redis_url.as_deref(),
+        app_config.block_suspicious_ips,
+    )
+    .await
+    .map(|service| service.with_audit_log(std::sync::Arc::new(audit_log_repository)));
+
+    // Large personal data exports wait on disk until their download link expires
+    let data_export_service = DataExportService::new(app_config.data_export_dir.clone());
+    info!(
+        "Data exports stored in {}",
+        app_config.data_export_dir.display()
     );
 
+    // Open Graph metadata of destination pages, fetched in the background after shortening
+    let link_preview_service =
+        LinkPreviewService::new(std::sync::Arc::new(url_metadata_repository));
+
+    // Weekly digest of URLs about to expire, sent on each user's chosen weekday, and pushes
+    // for click milestones, completed bulk operations and URLs about to expire
+    let notification_service = NotificationService::new()
+        .with_email_sender(email_sender.clone())
+        .with_push_sender(push_sender)
+        .with_preferences_repository(std::sync::Arc::new(notification_preferences_repository))
+        .with_base_url(app_config.base_url.clone());
+    let click_tracking_service =
+        click_tracking_service.with_notification_service(notification_service.clone());
+    let event_notifications = notification_service.clone();
+    event_bus.spawn_subscriber("notifications", move |event| {
+        let notification_service = event_notifications.clone();
+        async move { notification_service.handle_event(event).await }
+    });
+    event_bus.spawn_subscriber("metrics", |event| async move {
+        metrics::record_domain_event(&event)
+    });
+
+    // Social logins are enabled per provider once its client credentials are configured
+    let mut oauth_service = OAuthService::new(user_repository.clone(), app_config.base_url.clone());
+    for (provider, client_id, client_secret) in [
+        (
+            OAuthProvider::Google,
+            &app_config.google_client_id,
+            &app_config.google_client_secret,
+        ),
+        (
+            OAuthProvider::GitHub,
+            &app_config.github_client_id,
+            &app_config.github_client_secret,
+        ),
+    ] {
+        if let (Some(client_id), Some(client_secret)) = (client_id, client_secret) {
+            info!("{} login enabled", provider);
+            oauth_service = oauth_service.with_client(
+                provider,
+                OAuthClientConfig::new(provider, client_id.clone(), client_secret.clone()),
+            );
+        }
+    }
+
+    // HTTPS is served directly when a certificate is configured; HTTP/2 is offered over it
+    let tls_certificate = match (&app_config.tls_cert_path, &app_config.tls_key_path) {
+        (Some(cert_path), Some(key_path)) => Some(
+            TlsCertificate::load(cert_path.clone(), key_path.clone(), app_config.enable_http2)
+                .await?,
+        ),
+        _ => None,
+    };
+
+    // Profile pictures go to the S3-compatible bucket when one is configured
+    let storage_config = &app_config.object_storage;
+    let object_storage: std::sync::Arc<dyn ObjectStorage> =
+        match S3ObjectStorage::from_config(storage_config) {
+            Some(storage) => {
+                info!(
+                    "Storing uploads in bucket {} at {}",
+                    storage_config.bucket.as_deref().unwrap_or_default(),
+                    storage_config.resolved_endpoint()
+                );
+                std::sync::Arc::new(storage)
+            }
+            None => {
+                info!("Storing uploads in {}", storage_config.local_dir.display());
+                std::sync::Arc::new(LocalObjectStorage::new(
+                    storage_config.local_dir.clone(),
+                    format!("{}/uploads", app_config.base_url),
+                ))
+            }
+        };
+
+    // Analytics exports hold visitor IPs, so they go to private storage that is only handed
+    // out as pre-signed URLs, and are removed by the cleanup service
+    let export_storage: std::sync::Arc<dyn ObjectStorage> =
+        match S3ObjectStorage::exports_from_config(storage_config) {
+            Some(storage) => {
+                info!(
+                    "Storing analytics exports in bucket {}",
+                    storage_config.exports_bucket.as_deref().unwrap_or_default()
+                );
+                std::sync::Arc::new(storage)
+            }
+            None => {
+                let exports_dir = &storage_config.exports_dir;
+                info!("Storing analytics exports in {}", exports_dir.display());
+                std::sync::Arc::new(LocalObjectStorage::new(
+                    exports_dir.clone(),
+                    format!("file://{}", exports_dir.display()),
+                ))
+            }
+        };
+    let analytics_export_service = AnalyticsExportService::new(
+        click_repository.clone(),
+        std::sync::Arc::new(export_job_repository),
+        export_storage,
+    );
+
+    // Old data is removed in the background according to the configured retention periods
+    let cleanup_service = CleanupService::new(url_repository.clone(), app_config.retention)
+        .with_click_repository(click_repository)
+        .with_password_reset_repository(password_reset_repository.clone())
+        .with_magic_link_repository(magic_link_repository.clone())
+        .with_email_change_repository(email_change_repository.clone())
+        .with_email_outbox_repository(email_outbox_repository)
+        .with_archive_repository(
+            std::sync::Arc::new(archive_repository),
+            app_config.archive_sensitive_fields.clone(),
+        )
+        .with_notification_service(notification_service.clone())
+        .with_user_url_stats(user_url_stats_repository);
+    info!(
+        "Cleanup retention: URLs {}d after expiry, clicks {}d, bulk operations {}d (0 = kept)",
+        app_config.retention.expired_url_retention_days,
+        app_config.retention.click_data_retention_days,
+        app_config.retention.bulk_operation_retention_days
+    );
+
+    // Create application state
+    // The route table comes from the OpenAPI spec declared below
+    let server_info =
+        ServerInfo::from_config(&app_config).with_routes(route_table(&ApiDoc::openapi()));
+    log_route_table(&server_info.routes);
+
+    let app_state = AppStateBuilder::new()
+        .shorten_url_use_case(shorten_url_use_case)
+        .update_url_use_case(update_url_use_case)
+        .list_urls_use_case(list_urls_use_case)
+        .get_url_analytics_use_case(get_url_analytics_use_case)
+        .url_repository(url_repository)
+        .url_service(url_service)
+        .auth_service(auth_service)
+        .user_repository(user_repository)
+        .password_reset_repository(password_reset_repository)
+        .account_deletion_repository(account_deletion_repository)
+        .password_reset_rate_limiter(password_reset_rate_limiter)
+        .click_tracking_service(click_tracking_service.clone())
+        .database_health(database_health)
+        .cache_health(cache_health)
+        .organization_repository(organization_repository)
+        .org_service(org_service)
+        .real_ip_extractor(real_ip_extractor.clone())
+        .magic_link_repository(magic_link_repository)
+        .magic_link_rate_limiter(magic_link_rate_limiter)
+        .max_expiration_days(app_config.max_expiration_days)
+        .domain_blacklist(domain_blacklist)
+        .click_cookie(app_config.click_cookie.clone())
+        .data_export_service(data_export_service)
+        .analytics_export_service(analytics_export_service)
+        .service_account_service(service_account_service)
+        .service_account_rate_limiter(service_account_rate_limiter)
+        .link_preview_service(link_preview_service)
+        .retention(app_config.retention)
+        .cleanup_service(cleanup_service)
+        .notification_service(notification_service)
+        .oauth_service(oauth_service)
+        .interstitial_service(interstitial_service)
+        .email_sender(email_sender)
+        .tls_certificate(tls_certificate.clone())
+        .object_storage(object_storage)
+        .email_change_repository(email_change_repository)
+        .ip_reputation_service(ip_reputation_service)
+        .server_info(server_info)
+        .features(app_config.features.clone())
+        .build()?;
+
+    let cleanup_service = app_state.cleanup_service.clone();
+    tokio::spawn(async move {
+        cleanup_service
+            .start_cleanup_service(CLEANUP_INTERVAL_HOURS)
+            .await
+    });
+
     // OpenAPI documentation with feature-based grouping
     #[derive(OpenApi)]
     #[openapi(
         paths(
             // Health & System
-            health_check,
+            crate::presentation::handlers::health_handlers::health_handler,
+            crate::presentation::handlers::health_handlers::liveness_handler,
+            crate::presentation::handlers::health_handlers::readiness_handler,
             // Authentication
             crate::presentation::handlers::auth_handlers::register_handler,
             crate::presentation::handlers::auth_handlers::login_handler,
+            crate::presentation::handlers::auth_handlers::introspect_token_handler,
+            crate::presentation::handlers::auth_handlers::verify_email_handler,
+            crate::presentation::handlers::magic_link_handlers::request_magic_link,
+            crate::presentation::handlers::magic_link_handlers::verify_magic_link,
+            crate::presentation::handlers::auth_handlers::list_sessions_handler,
+            crate::presentation::handlers::auth_handlers::revoke_session_handler,
+            crate::presentation::handlers::auth_handlers::revoke_other_sessions_handler,
+            crate::presentation::handlers::oauth_handlers::start_oauth_login,
+            crate::presentation::handlers::oauth_handlers::oauth_callback,
             // URL Shortening
             crate::presentation::handlers::url_handlers::urls::shorten_url_handler::shorten_url_handler,
             crate::presentation::handlers::url_handlers::urls::redirect_handler::redirect_handler,
+            crate::presentation::handlers::url_handlers::urls::redirect_handler::confirm_redirect_handler,
+            crate::presentation::handlers::url_handlers::urls::redirect_handler::head_redirect_handler,
+            crate::presentation::handlers::url_handlers::urls::link_preview_handler::get_link_preview_handler,
             // URL Management
             crate::presentation::handlers::url_handlers::urls::deactivate_url_handler::deactivate_url_handler,
             crate::presentation::handlers::url_handlers::urls::reactivate_url_handler::reactivate_url_handler,
+            crate::presentation::handlers::url_handlers::urls::restore_url_handler::restore_url_handler,
+            crate::presentation::handlers::url_handlers::urls::update_url_handler::update_url_handler,
+            crate::presentation::handlers::url_handlers::urls::rename_short_code_handler::rename_short_code_handler,
+            crate::presentation::handlers::url_handlers::urls::duplicate_url_handler::duplicate_url_handler,
+            crate::presentation::handlers::url_handlers::urls::preview_settings_handler::get_preview_settings_handler,
+            crate::presentation::handlers::url_handlers::urls::preview_settings_handler::update_preview_settings_handler,
+            crate::presentation::handlers::url_handlers::urls::get_top_urls_handler::get_top_urls_handler,
+            crate::presentation::handlers::url_handlers::urls::get_url_handler::get_url_handler,
+            crate::presentation::handlers::url_handlers::urls::list_urls_handler::list_urls_handler,
+            crate::presentation::handlers::url_handlers::urls::get_url_analytics_summary_handler::get_url_analytics_summary_handler,
+            crate::presentation::handlers::url_handlers::urls::get_url_analytics_handler::get_url_analytics_handler,
+            crate::presentation::handlers::url_handlers::urls::click_dedup_ratio_handler::get_click_dedup_ratio_handler,
+            crate::presentation::handlers::url_handlers::urls::click_patterns_handler::get_click_patterns_handler,
+            crate::presentation::handlers::url_handlers::urls::utm_attribution_handler::get_utm_attribution_handler,
+            crate::presentation::handlers::url_handlers::urls::url_config_handler::get_url_config_handler,
+            crate::presentation::handlers::url_handlers::urls::url_config_handler::update_url_config_handler,
+            // Conversions
+            crate::presentation::handlers::conversion_handlers::create_conversion_goal_handler,
+            crate::presentation::handlers::conversion_handlers::delete_conversion_goal_handler,
+            crate::presentation::handlers::conversion_handlers::report_conversion_handler,
+            // Analytics Exports
+            crate::presentation::handlers::analytics_export_handlers::request_analytics_export_handler,
+            crate::presentation::handlers::analytics_export_handlers::list_analytics_exports_handler,
+            crate::presentation::handlers::analytics_export_handlers::download_analytics_export_handler,
+            // Dashboard
+            crate::presentation::handlers::dashboard_handlers::get_dashboard_handler,
             // Bulk Operations - Synchronous
             crate::presentation::handlers::url_handlers::urls::bulk_shorten_urls_handler::bulk_shorten_urls_handler,
             crate::presentation::handlers::url_handlers::urls::batch_url_operations_handler::batch_url_operations_handler,
             crate::presentation::handlers::url_handlers::urls::bulk_status_update_handler::bulk_status_update_handler,
             crate::presentation::handlers::url_handlers::urls::bulk_expiration_update_handler::bulk_expiration_update_handler,
+            crate::presentation::handlers::url_handlers::urls::bulk_expiration_update_handler::bulk_expiration_clear_handler,
             crate::presentation::handlers::url_handlers::urls::bulk_delete_handler::bulk_delete_handler,
             // Bulk Operations - Asynchronous with Progress
             crate::presentation::handlers::url_handlers::urls::async_bulk_shorten_urls_handler::async_bulk_shorten_urls_handler,
@@ -178,6 +829,9 @@ pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
             crate::presentation::handlers::progress_handlers::get_bulk_operation_progress_handler,
             crate::presentation::handlers::progress_handlers::cancel_bulk_operation_handler,
             crate::presentation::handlers::progress_handlers::get_user_operations_handler,
+            crate::presentation::handlers::progress_handlers::get_operation_results_handler,
+            crate::presentation::handlers::progress_handlers::chain_operation_handler,
+            crate::presentation::handlers::progress_handlers::get_operation_chain_handler,
             // Expiration Management
             crate::presentation::handlers::expiration_handlers::get_expiration_info_handler,
             crate::presentation::handlers::expiration_handlers::set_expiration_handler,
@@ -190,12 +844,21 @@ pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
             crate::presentation::handlers::profile_handlers::patch_my_profile,
             crate::presentation::handlers::profile_handlers::get_profile_by_username,
             crate::presentation::handlers::profile_handlers::delete_account,
+            crate::presentation::handlers::profile_handlers::change_password,
+            crate::presentation::handlers::profile_handlers::request_email_change,
+            crate::presentation::handlers::profile_handlers::confirm_email_change_old,
+            crate::presentation::handlers::profile_handlers::confirm_email_change_new,
+            crate::presentation::handlers::profile_handlers::register_device_token,
+            crate::presentation::handlers::profile_handlers::remove_device_token,
+            crate::presentation::handlers::profile_handlers::export_my_data,
+            crate::presentation::handlers::profile_handlers::download_data_export,
             crate::presentation::handlers::file_upload_handlers::upload_profile_picture,
             crate::presentation::handlers::file_upload_handlers::delete_profile_picture,
             // Privacy Settings
             crate::presentation::handlers::privacy_handlers::get_privacy_settings,
             crate::presentation::handlers::privacy_handlers::update_privacy_settings,
             crate::presentation::handlers::privacy_handlers::get_privacy_recommendations,
+            crate::presentation::handlers::privacy_handlers::get_privacy_preview,
             // Password Reset
             crate::presentation::handlers::password_reset_handlers::request_password_reset,
             crate::presentation::handlers::password_reset_handlers::reset_password,
@@ -204,6 +867,46 @@ pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
             crate::presentation::handlers::account_deletion_handlers::request_account_deletion,
             crate::presentation::handlers::account_deletion_handlers::confirm_account_deletion,
             crate::presentation::handlers::account_deletion_handlers::cancel_account_deletion,
+            // Notifications
+            crate::presentation::handlers::notification_handlers::get_notification_preferences_handler,
+            crate::presentation::handlers::notification_handlers::update_notification_preferences_handler,
+            // Administration
+            crate::presentation::handlers::admin_handlers::suspend_user_handler,
+            crate::presentation::handlers::admin_handlers::unsuspend_user_handler,
+            crate::presentation::handlers::admin_handlers::export_user_data_admin_handler,
+            crate::presentation::handlers::admin_handlers::create_service_account_handler,
+            crate::presentation::handlers::admin_handlers::list_blocked_domains_handler,
+            crate::presentation::handlers::admin_handlers::add_blocked_domain_handler,
+            crate::presentation::handlers::admin_handlers::remove_blocked_domain_handler,
+            crate::presentation::handlers::admin_handlers::reprioritize_operation_handler,
+            crate::presentation::handlers::admin_handlers::get_cleanup_config_handler,
+            crate::presentation::handlers::admin_handlers::preview_cleanup_handler,
+            crate::presentation::handlers::admin_handlers::run_cleanup_handler,
+            crate::presentation::handlers::admin_handlers::list_archive_records_handler,
+            crate::presentation::handlers::admin_handlers::get_db_pool_stats_handler,
+            crate::presentation::handlers::admin_handlers::get_slow_queries_handler,
+            crate::presentation::handlers::admin_handlers::trigger_digest_handler,
+            crate::presentation::handlers::admin_handlers::reload_tls_handler,
+            crate::presentation::handlers::admin_handlers::list_organizations_admin_handler,
+            crate::presentation::handlers::admin_handlers::urls_by_original_handler,
+            crate::presentation::handlers::admin_handlers::list_urls_admin_handler,
+            crate::presentation::handlers::admin_handlers::list_suspicious_urls_handler,
+            crate::presentation::handlers::admin_handlers::transfer_url_handler,
+            crate::presentation::handlers::admin_handlers::rebuild_user_stats_handler,
+            crate::presentation::handlers::admin_handlers::reencode_short_codes_handler,
+            crate::presentation::handlers::admin_handlers::search_users_handler,
+            crate::presentation::handlers::admin_handlers::get_server_info_handler,
+            crate::presentation::handlers::health_handlers::get_features_handler,
+            // Organizations
+            crate::presentation::handlers::organization_handlers::create_organization_handler,
+            crate::presentation::handlers::organization_handlers::list_organizations_handler,
+            crate::presentation::handlers::organization_handlers::get_organization_handler,
+            crate::presentation::handlers::organization_handlers::update_organization_handler,
+            crate::presentation::handlers::organization_handlers::delete_organization_handler,
+            crate::presentation::handlers::organization_handlers::list_organization_members_handler,
+            crate::presentation::handlers::organization_handlers::add_organization_member_handler,
+            crate::presentation::handlers::organization_handlers::remove_organization_member_handler,
+            crate::presentation::handlers::organization_handlers::list_organization_urls_handler,
         ),
         components(
             schemas(
@@ -211,28 +914,83 @@ pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
                 ShortenUrlRequest,
                 BulkShortenUrlsRequest,
                 crate::application::dto::requests::UpdateUrlRequest,
+                crate::application::dto::requests::RenameShortCodeRequest,
+                crate::application::dto::requests::DuplicateUrlRequest,
+                crate::application::dto::requests::RedirectQuery,
+                crate::application::dto::requests::ConfirmRedirectForm,
+                crate::application::dto::requests::UpdatePreviewSettingsRequest,
+                crate::application::dto::requests::UpdateUrlConfigRequest,
                 crate::application::dto::requests::SetExpirationRequest,
                 crate::application::dto::requests::ExtendExpirationRequest,
                 crate::application::dto::requests::BatchUrlOperationRequest,
-                crate::application::dto::requests::BatchOperationType,
+                crate::application::dto::requests::ChainedOperationRequest,
+                crate::application::dto::requests::OperationPriority,
+                crate::application::dto::requests::ReprioritizeOperationRequest,
                 crate::application::dto::requests::BatchOperationData,
                 crate::application::dto::requests::BulkStatusUpdateRequest,
                 crate::application::dto::requests::BulkExpirationUpdateRequest,
+                crate::application::dto::requests::BulkExpirationClearRequest,
                 crate::application::dto::requests::BulkDeleteRequest,
                 crate::application::dto::requests::UpdateProfileRequest,
                 crate::application::dto::requests::ProfilePrivacyRequest,
                 crate::application::dto::requests::DeleteAccountRequest,
+                crate::application::dto::requests::ChangePasswordRequest,
+                crate::application::dto::requests::ChangeEmailRequest,
+                crate::application::dto::requests::RegisterDeviceTokenRequest,
                 crate::application::dto::requests::ConfirmAccountDeletionRequest,
+                crate::application::dto::requests::ListLimitQuery,
+                crate::application::dto::requests::ListUrlsQuery,
+                crate::application::dto::requests::RestoreUrlRequest,
                 // Response DTOs
+                crate::application::dto::responses::HealthResponse,
                 crate::application::ShortenUrlResponse,
                 crate::application::dto::responses::UrlInfoResponse,
+                crate::application::dto::responses::UrlDetailResponse,
+                crate::application::dto::responses::LinkPreviewResponse,
+                crate::application::dto::responses::PreviewSettingsResponse,
                 crate::application::dto::responses::UserUrlsResponse,
+                crate::application::dto::responses::TopUrlsResponse,
+                crate::application::dto::responses::UrlSummaryResponse,
+                crate::application::dto::responses::UrlSummaryPage,
+                crate::application::dto::responses::BulkOperationProgressPage,
+                crate::application::dto::responses::UserSummary,
+                crate::application::dto::responses::UserSummaryPage,
+                crate::application::dto::requests::PaginationRequest,
+                crate::application::dto::responses::UserUrlStatsResponse,
+                crate::application::dto::responses::UrlAnalyticsSummaryResponse,
+                crate::application::dto::responses::ClickDedupRatioResponse,
+                crate::application::dto::responses::ClickPatternsResponse,
+                crate::application::dto::responses::UtmAttributionEntry,
+                crate::application::dto::responses::UtmAttributionResponse,
+                crate::application::dto::requests::ClickPatternsQuery,
+                crate::application::dto::responses::UrlConfigResponse,
+                crate::application::dto::responses::CountryClicks,
+                crate::application::dto::responses::ReferrerClicks,
+                crate::application::dto::responses::DeviceBreakdownResponse,
+                crate::application::dto::responses::ClickSummary,
+                crate::application::dto::requests::AnalyticsSummaryQuery,
+                crate::application::dto::requests::GetUrlAnalyticsRequest,
+                crate::application::dto::responses::UrlAnalyticsResponse,
+                crate::application::dto::responses::ClickTimeseriesPoint,
+                crate::application::dto::responses::DashboardResponse,
                 crate::application::dto::responses::UserProfileResponse,
+                crate::application::dto::responses::EmailChangeRequestResponse,
+                crate::application::dto::responses::EmailChangeConfirmResponse,
+                crate::application::dto::responses::DeviceTokensResponse,
                 crate::application::dto::responses::PublicUserProfileResponse,
+                crate::application::dto::responses::SocialLinksDto,
+                crate::application::dto::responses::CustomSocialLinkDto,
+                crate::application::dto::responses::PublicUrlResponse,
+                crate::application::dto::responses::UserDataExportResponse,
+                crate::application::dto::responses::ExportedUrlResponse,
+                crate::application::dto::responses::DataExportQueuedResponse,
                 crate::application::dto::responses::ProfilePrivacyResponse,
                 crate::application::dto::responses::BatchOperationResponse,
                 crate::application::dto::responses::BatchOperationResult,
                 crate::application::dto::responses::BulkOperationProgress,
+                crate::application::dto::responses::ChainedOperationResponse,
+                crate::application::dto::responses::OperationChainResponse,
+                crate::application::dto::responses::BulkItemResult,
                 crate::application::dto::responses::BulkOperationStatus,
                 crate::application::dto::responses::ExpirationInfoResponse,
                 crate::application::dto::responses::AccountDeletionRequestResponse,
@@ -240,17 +998,83 @@ pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
                 crate::application::dto::responses::AccountDeletionCancellationResponse,
                 // Error DTOs
                 crate::application::ErrorResponse,
+                crate::application::dto::responses::ShortCodeLengthErrorResponse,
+                crate::application::dto::responses::ValidationErrorResponse,
+                crate::application::dto::responses::FieldErrorResponse,
                 crate::infrastructure::rate_limiting::RateLimitError,
+                crate::infrastructure::rate_limiting::ProblemDetails,
                 // Authentication DTOs
                 crate::presentation::handlers::auth_handlers::RegisterRequest,
                 crate::presentation::handlers::auth_handlers::LoginRequest,
                 crate::presentation::handlers::auth_handlers::AuthResponse,
                 crate::presentation::handlers::auth_handlers::UserResponse,
+                crate::presentation::handlers::auth_handlers::IntrospectTokenRequest,
+                crate::presentation::handlers::auth_handlers::IntrospectTokenResponse,
+                crate::presentation::handlers::auth_handlers::VerifyEmailResponse,
+                crate::presentation::handlers::magic_link_handlers::RequestMagicLinkRequest,
+                crate::presentation::handlers::magic_link_handlers::RequestMagicLinkResponse,
+                crate::presentation::handlers::auth_handlers::SessionResponse,
+                crate::presentation::handlers::auth_handlers::SessionsResponse,
+                crate::presentation::handlers::auth_handlers::RevokeSessionsResponse,
                 // Password Reset DTOs
                 crate::presentation::handlers::password_reset_handlers::RequestPasswordResetRequest,
                 crate::presentation::handlers::password_reset_handlers::RequestPasswordResetResponse,
                 crate::presentation::handlers::password_reset_handlers::ResetPasswordRequest,
                 crate::presentation::handlers::password_reset_handlers::ResetPasswordResponse,
+                // Admin DTOs
+                crate::presentation::handlers::admin_handlers::SuspendUserRequest,
+                crate::presentation::handlers::admin_handlers::AccountStatusResponse,
+                crate::presentation::handlers::admin_handlers::AddBlockedDomainRequest,
+                crate::presentation::handlers::admin_handlers::BlockedDomainResponse,
+                crate::presentation::handlers::admin_handlers::BlockedDomainsResponse,
+                crate::presentation::handlers::admin_handlers::CreateServiceAccountRequest,
+                crate::presentation::handlers::admin_handlers::ServiceAccountCreatedResponse,
+                crate::presentation::handlers::admin_handlers::RetentionEntryResponse,
+                crate::presentation::handlers::admin_handlers::CleanupConfigResponse,
+                crate::presentation::handlers::admin_handlers::CleanupPreviewResponse,
+                crate::presentation::handlers::admin_handlers::ArchiveRecordResponse,
+                crate::presentation::handlers::admin_handlers::ArchiveRecordsResponse,
+                crate::presentation::handlers::admin_handlers::DbPoolStatsResponse,
+                crate::presentation::handlers::admin_handlers::SlowQueryResponse,
+                crate::presentation::handlers::admin_handlers::SlowQueriesResponse,
+                crate::presentation::handlers::admin_handlers::DigestRunResponse,
+                crate::presentation::handlers::admin_handlers::UrlsByOriginalResponse,
+                crate::presentation::handlers::admin_handlers::AdminUrlResponse,
+                crate::presentation::handlers::admin_handlers::AdminUrlsResponse,
+                crate::presentation::handlers::admin_handlers::SuspiciousUrlsResponse,
+                crate::presentation::handlers::admin_handlers::TransferUrlRequest,
+                crate::presentation::handlers::admin_handlers::ReencodeShortCodesResponse,
+                crate::presentation::handlers::admin_handlers::RebuildUserStatsResponse,
+                crate::infrastructure::server_info::ServerInfo,
+                crate::infrastructure::server_info::ServerFeatures,
+                crate::infrastructure::config::FeatureFlags,
+                crate::infrastructure::server_info::ShortCodeInfo,
+                crate::infrastructure::server_info::RateLimitInfo,
+                crate::infrastructure::server_info::RouteInfo,
+                // Notification DTOs
+                crate::presentation::handlers::notification_handlers::UpdateNotificationPreferencesRequest,
+                crate::presentation::handlers::notification_handlers::NotificationPreferencesResponse,
+                // Conversion DTOs
+                crate::presentation::handlers::conversion_handlers::CreateConversionGoalRequest,
+                crate::presentation::handlers::conversion_handlers::ConversionGoalResponse,
+                crate::presentation::handlers::conversion_handlers::ReportConversionRequest,
+                crate::presentation::handlers::conversion_handlers::ConversionEventResponse,
+                // Analytics export DTOs
+                crate::application::dto::requests::ExportFormat,
+                crate::presentation::handlers::analytics_export_handlers::RequestAnalyticsExportRequest,
+                crate::presentation::handlers::analytics_export_handlers::AnalyticsExportQueuedResponse,
+                crate::presentation::handlers::analytics_export_handlers::AnalyticsExportResponse,
+                crate::presentation::handlers::analytics_export_handlers::AnalyticsExportListResponse,
+                // Organization DTOs
+                crate::presentation::handlers::organization_handlers::CreateOrganizationRequest,
+                crate::presentation::handlers::organization_handlers::UpdateOrganizationRequest,
+                crate::presentation::handlers::organization_handlers::AddOrganizationMemberRequest,
+                crate::presentation::handlers::organization_handlers::OrganizationResponse,
+                crate::presentation::handlers::organization_handlers::OrganizationsResponse,
+                crate::presentation::handlers::organization_handlers::OrganizationMemberResponse,
+                crate::presentation::handlers::organization_handlers::OrganizationMembersResponse,
+                crate::presentation::handlers::organization_handlers::OrganizationSummaryResponse,
+                crate::presentation::handlers::organization_handlers::OrganizationSummariesResponse,
             )
         ),
         tags(
@@ -258,12 +1082,18 @@ pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
             (name = "authentication", description = "User Authentication & Authorization"),
             (name = "url-shortener", description = "URL Shortening & Redirection"),
             (name = "url-management", description = "URL Lifecycle Management"),
+            (name = "conversions", description = "Conversion Goals & Tracking"),
+            (name = "analytics", description = "Raw Click Data Exports"),
+            (name = "dashboard", description = "User Dashboard"),
             (name = "bulk-operations", description = "Bulk URL Operations (Sync & Async)"),
             (name = "expiration", description = "URL Expiration Management"),
             (name = "profile", description = "User Profile Management"),
             (name = "privacy", description = "Privacy Settings & Controls"),
             (name = "password-reset", description = "Password Reset & Recovery"),
-            (name = "account-deletion", description = "Account Deletion Management")
+            (name = "account-deletion", description = "Account Deletion Management"),
+            (name = "notifications", description = "Email Notification Preferences"),
+            (name = "admin", description = "Administrative User Management"),
+            (name = "organizations", description = "Organizations & Shared URLs")
         )
     )]
     struct ApiDoc;
@@ -271,24 +1101,188 @@ pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
     let openapi = ApiDoc::openapi();
 
     // Create router with routes and docs using clean architecture
-    let api_router = Router::new()
+    let api_router = api_routes(&app_state, idempotency);
+
+    // File uploads get a higher body limit than the rest of the API
+    let upload_router = Router::new()
+        .route("/profile/avatar", post(upload_profile_picture))
+        .route("/profile/avatar", delete(delete_profile_picture));
+    let api_router = with_body_limit(api_router, app_config.max_request_body_bytes).merge(
+        with_body_limit(upload_router, app_config.max_upload_body_bytes),
+    );
+
+    // Probes bypass rate limiting, request logging and the other layers below
+    let health_router = Router::new()
+        .route("/health", get(health_handler))
+        .route("/features", get(get_features_handler))
+        .route("/health/live", get(liveness_handler))
+        .route("/health/ready", get(readiness_handler))
+        .with_state(app_state.clone());
+
+    let app = api_router
+        .merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", openapi))
+        .with_state(app_state)
+        .layer(middleware::from_fn(sentry_error_middleware))
+        .layer(cors)
+        .layer(middleware::from_fn(security_headers_middleware))
+        .layer(middleware::from_fn_with_state(
+            RateLimitState {
+                real_ip_extractor,
+                limiter: request_rate_limiter,
+            },
+            rate_limit_middleware,
+        ))
+        .layer(middleware::from_fn(body_too_large_middleware))
+        .layer(create_tracing_layer_simple())
+        .layer(create_compression_layer_simple())
+        .layer(TraceIdExtractor::new());
+    let mut app = health_router.merge(app);
+
+    // GraphiQL loads its scripts from a CDN, which the security headers would block
+    if app_config.is_development() {
+        app = app.route("/graphiql", get(graphiql_handler));
+        info!("GraphiQL playground enabled at /graphiql");
+    }
+
+    // Get server configuration from environment variables
+    let host = app_config.host.clone();
+    let port = app_config.port;
+
+    // Create socket address
+    let addr: std::net::SocketAddr = format!("{}:{}", host, port).parse()?;
+
+    let scheme = if tls_certificate.is_some() {
+        "https"
+    } else {
+        "http"
+    };
+    info!("Starting server on {}", addr);
+    info!("Welcome to your app! Visit {}://{}:{}", scheme, host, port);
+    info!(
+        "Health check endpoint: GET {}://{}:{}/health",
+        scheme, host, port
+    );
+    info!(
+        "Liveness/readiness probes: GET {}://{}:{}/health/live, /health/ready",
+        scheme, host, port
+    );
+    info!(
+        "Metrics endpoint: GET {}://{}:{}/metrics",
+        scheme, host, port
+    );
+    info!(
+        "URL shortening endpoint: POST {}://{}:{}/shorten",
+        scheme, host, port
+    );
+    info!(
+        "Redirect endpoint: GET {}://{}:{}/{{short_code}}",
+        scheme, host, port
+    );
+    info!("API documentation: {}://{}:{}/docs", scheme, host, port);
+    info!("Security features enabled: rate limiting, security headers, compression");
+
+    // Start the server
+    let make_service = app.into_make_service_with_connect_info::<std::net::SocketAddr>();
+    match tls_certificate {
+        Some(certificate) => {
+            info!(
+                "TLS enabled (HTTP/2 {})",
+                if app_config.enable_http2 {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            );
+            let handle = axum_server::Handle::new();
+            let shutdown_handle = handle.clone();
+            tokio::spawn(async move {
+                shutdown_signal().await;
+                shutdown_handle.graceful_shutdown(Some(TLS_SHUTDOWN_GRACE_PERIOD));
+            });
+            axum_server::bind_rustls(addr, certificate.rustls_config())
+                .handle(handle)
+                .serve(make_service)
+                .await?;
+        }
+        None => {
+            let listener = tokio::net::TcpListener::bind(&addr).await?;
+            axum::serve(listener, make_service)
+                .with_graceful_shutdown(shutdown_signal())
+                .await?;
+        }
+    }
+
+    // Write any clicks still waiting in the buffer before exiting
+    info!("Flushing buffered clicks");
+    click_tracking_service.shutdown().await;
+
+    Ok(())
+}
+
+/// Routes of the API, before the state and the middleware of `start_server` are applied
+///
+/// Uploads, health probes and the documentation are added by `start_server`; tests serve
+/// these routes over mock repositories through `TestApp`.
+pub fn api_routes(
+    app_state: &ConcreteAppState,
+    idempotency: IdempotencyLayer,
+) -> Router<ConcreteAppState> {
+    let graphql_schema = GraphQLServices {
+        shorten_url_use_case: app_state.shorten_url_use_case.clone(),
+        url_service: app_state.url_service.clone(),
+        auth_service: app_state.auth_service.clone(),
+        click_tracking_service: app_state.click_tracking_service.clone(),
+        org_service: app_state.org_service.clone(),
+    }
+    .into_schema();
+
+    Router::new()
         .route("/", get(welcome_handler))
-        .route("/health", get(health_check))
+        .route("/metrics", get(metrics_handler))
         .route("/register", post(register_handler))
         .route("/login", post(login_handler))
-        .route("/shorten", post(shorten_url_handler))
-        .route("/:short_code", get(redirect_handler))
+        .route("/auth/verify-email", get(verify_email_handler))
+        .route("/auth/magic-link/request", post(request_magic_link))
+        .route("/auth/magic-link/verify", get(verify_magic_link))
+        .route(
+            "/auth/sessions",
+            get(list_sessions_handler).delete(revoke_other_sessions_handler),
+        )
+        .route("/auth/sessions/:id", delete(revoke_session_handler))
+        .route("/auth/oauth/:provider", get(start_oauth_login))
+        .route("/auth/oauth/:provider/callback", get(oauth_callback))
+        .route("/auth/introspect", post(introspect_token_handler))
+        .route(
+            "/shorten",
+            post(shorten_url_handler).layer(idempotency.clone()),
+        )
+        .route(
+            "/:short_code",
+            get(redirect_handler)
+                .head(head_redirect_handler)
+                .post(confirm_redirect_handler),
+        )
+        .route("/:short_code/preview", get(get_link_preview_handler))
         // Bulk operations (synchronous)
-        .route("/urls/bulk", post(bulk_shorten_urls_handler))
-        .route("/urls/batch", post(batch_url_operations_handler))
+        .route(
+            "/urls/bulk",
+            post(bulk_shorten_urls_handler).layer(idempotency.clone()),
+        )
+        .route(
+            "/urls/batch",
+            post(batch_url_operations_handler).layer(idempotency.clone()),
+        )
         .route("/urls/bulk/status", patch(bulk_status_update_handler))
         .route(
             "/urls/bulk/expiration",
-            patch(bulk_expiration_update_handler),
+            patch(bulk_expiration_update_handler).delete(bulk_expiration_clear_handler),
         )
         .route("/urls/bulk", delete(bulk_delete_handler))
         // Async bulk operations with progress tracking
-        .route("/urls/bulk/async", post(async_bulk_shorten_urls_handler))
+        .route(
+            "/urls/bulk/async",
+            post(async_bulk_shorten_urls_handler).layer(idempotency),
+        )
         .route(
             "/urls/batch/async",
             post(async_batch_url_operations_handler),
@@ -303,9 +1297,61 @@ pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
             delete(cancel_bulk_operation_handler),
         )
         .route("/urls/bulk/operations", get(get_user_operations_handler))
+        .route(
+            "/operations/:operation_id/results",
+            get(get_operation_results_handler),
+        )
+        .route(
+            "/operations/:operation_id/chain",
+            get(get_operation_chain_handler).post(chain_operation_handler),
+        )
         // URL management endpoints
+        .route("/urls/:id", get(get_url_handler))
         .route("/urls/:id", delete(deactivate_url_handler))
+        .route("/urls/:id", patch(update_url_handler))
+        .route("/urls/:id/short-code", patch(rename_short_code_handler))
         .route("/urls/:id/reactivate", patch(reactivate_url_handler))
+        .route("/urls/:id/restore", post(restore_url_handler))
+        .route("/urls/:id/duplicate", post(duplicate_url_handler))
+        .route(
+            "/urls/:id/preview-settings",
+            get(get_preview_settings_handler).put(update_preview_settings_handler),
+        )
+        .route("/urls", get(list_urls_handler))
+        .route("/urls/top", get(get_top_urls_handler))
+        .route("/urls/:id/analytics", get(get_url_analytics_handler))
+        .route(
+            "/urls/:id/analytics/summary",
+            get(get_url_analytics_summary_handler),
+        )
+        .route(
+            "/urls/:id/analytics/dedup-ratio",
+            get(get_click_dedup_ratio_handler),
+        )
+        .route(
+            "/urls/:id/analytics/patterns",
+            get(get_click_patterns_handler),
+        )
+        .route("/urls/:id/analytics/utm", get(get_utm_attribution_handler))
+        .route(
+            "/urls/:id/config",
+            get(get_url_config_handler).put(update_url_config_handler),
+        )
+        // Conversion tracking
+        .route("/urls/:id/goals", post(create_conversion_goal_handler))
+        .route("/goals/:id", delete(delete_conversion_goal_handler))
+        .route("/conversions/report", post(report_conversion_handler))
+        // Analytics exports
+        .route(
+            "/analytics/exports",
+            get(list_analytics_exports_handler).post(request_analytics_export_handler),
+        )
+        .route(
+            "/analytics/exports/:id/download",
+            get(download_analytics_export_handler),
+        )
+        // Dashboard
+        .route("/dashboard", get(get_dashboard_handler))
         // Expiration management endpoints
         .route(
             "/urls/:short_code/expiration",
@@ -321,16 +1367,38 @@ pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
         .route("/profile/:user_id", get(get_public_profile))
         .route("/profile/username/:username", get(get_profile_by_username))
         .route("/profile/delete", delete(delete_account))
-        // Profile picture upload endpoints
-        .route("/profile/avatar", post(upload_profile_picture))
-        .route("/profile/avatar", delete(delete_profile_picture))
+        .route("/profile/password", patch(change_password))
+        .route("/profile/change-email", post(request_email_change))
+        .route(
+            "/profile/change-email/confirm-old",
+            post(confirm_email_change_old),
+        )
+        .route(
+            "/profile/change-email/confirm-new",
+            post(confirm_email_change_new),
+        )
+        .route("/profile/device-tokens", post(register_device_token))
+        .route("/profile/device-tokens/:token", delete(remove_device_token))
+        .route("/profile/export", get(export_my_data))
+        .route("/profile/export/download/:token", get(download_data_export))
         // Privacy management endpoints
         .route("/profile/privacy", get(get_privacy_settings))
         .route("/profile/privacy", put(update_privacy_settings))
+        .route("/profile/privacy", patch(update_privacy_settings))
+        .route("/profile/privacy/preview", get(get_privacy_preview))
         .route(
             "/profile/privacy/recommendations",
             get(get_privacy_recommendations),
         )
+        // Notification preferences
+        .route(
+            "/notification-preferences",
+            get(get_notification_preferences_handler),
+        )
+        .route(
+            "/notification-preferences",
+            post(update_notification_preferences_handler),
+        )
         // Password reset endpoints
         .route("/auth/password-reset/request", post(request_password_reset))
         .route("/auth/password-reset/confirm", post(reset_password))
@@ -341,47 +1409,72 @@ pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
         // Account deletion endpoints
         .route("/account/deletion/request", post(request_account_deletion))
         .route("/account/deletion/confirm", post(confirm_account_deletion))
-        .route("/account/deletion/cancel", post(cancel_account_deletion));
-
-    let app = api_router
-        .merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", openapi))
-        .with_state(app_state)
-        .layer(cors)
-        .layer(middleware::from_fn(security_headers_middleware))
-        .layer(middleware::from_fn(rate_limit_middleware))
-        .layer(create_request_size_layer(&rate_limit_config))
-        .layer(create_tracing_layer_simple())
-        .layer(create_compression_layer_simple());
-
-    // Get server configuration from environment variables
-    let host = env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
-    let port = env::var("PORT")
-        .unwrap_or_else(|_| "8000".to_string())
-        .parse::<u16>()
-        .expect("PORT must be a valid number");
-
-    // Create socket address
-    let addr: std::net::SocketAddr = format!("{}:{}", host, port).parse()?;
-
-    info!("Starting server on {}", addr);
-    info!("Welcome to your app! Visit http://{}:{}", host, port);
-    info!("Health check endpoint: GET http://{}:{}/health", host, port);
-    info!(
-        "URL shortening endpoint: POST http://{}:{}/shorten",
-        host, port
-    );
-    info!(
-        "Redirect endpoint: GET http://{}:{}/{{short_code}}",
-        host, port
-    );
-    info!("API documentation: http://{}:{}/docs", host, port);
-    info!("Security features enabled: rate limiting, security headers, compression");
-
-    // Start the server
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
-
-    Ok(())
+        .route("/account/deletion/cancel", post(cancel_account_deletion))
+        // Admin endpoints
+        .route("/admin/users/:id/suspend", post(suspend_user_handler))
+        .route("/admin/users/:id/unsuspend", post(unsuspend_user_handler))
+        .route(
+            "/admin/users/:id/export",
+            get(export_user_data_admin_handler),
+        )
+        .route(
+            "/admin/service-accounts",
+            post(create_service_account_handler),
+        )
+        .route("/admin/domain-blacklist", get(list_blocked_domains_handler))
+        .route("/admin/domain-blacklist", post(add_blocked_domain_handler))
+        .route(
+            "/admin/domain-blacklist/:id",
+            delete(remove_blocked_domain_handler),
+        )
+        .route(
+            "/admin/operations/:id/reprioritize",
+            post(reprioritize_operation_handler),
+        )
+        .route("/admin/cleanup/config", get(get_cleanup_config_handler))
+        .route("/admin/cleanup/preview", get(preview_cleanup_handler))
+        .route("/admin/cleanup/run", post(run_cleanup_handler))
+        .route("/admin/archive-records", get(list_archive_records_handler))
+        .route("/admin/db/pool-stats", get(get_db_pool_stats_handler))
+        .route("/admin/db/slow-queries", get(get_slow_queries_handler))
+        .route(
+            "/admin/notifications/trigger-digest",
+            post(trigger_digest_handler),
+        )
+        .route("/admin/tls/reload", post(reload_tls_handler))
+        .route("/admin/urls", get(list_urls_admin_handler))
+        .route("/admin/urls/by-original", get(urls_by_original_handler))
+        .route("/admin/urls/suspicious", get(list_suspicious_urls_handler))
+        .route("/admin/urls/:id/transfer", post(transfer_url_handler))
+        .route(
+            "/admin/short-codes/reencode",
+            post(reencode_short_codes_handler),
+        )
+        .route("/admin/stats/rebuild", post(rebuild_user_stats_handler))
+        .route(
+            "/admin/organizations",
+            get(list_organizations_admin_handler),
+        )
+        .route("/admin/users/search", get(search_users_handler))
+        .route("/info", get(get_server_info_handler))
+        // Organization endpoints
+        .route("/orgs", post(create_organization_handler))
+        .route("/orgs", get(list_organizations_handler))
+        .route("/orgs/:id", get(get_organization_handler))
+        .route("/orgs/:id", put(update_organization_handler))
+        .route("/orgs/:id", delete(delete_organization_handler))
+        .route("/orgs/:id/members", get(list_organization_members_handler))
+        .route("/orgs/:id/members", post(add_organization_member_handler))
+        .route(
+            "/orgs/:id/members/:user_id",
+            delete(remove_organization_member_handler),
+        )
+        .route("/orgs/:id/urls", get(list_organization_urls_handler))
+        // GraphQL over the same services
+        .route(
+            "/graphql",
+            post(graphql_handler).layer(Extension(graphql_schema)),
+        )
 }
 
 pub async fn welcome_handler() -> Html<&'static str> {
@@ -463,23 +1556,68 @@ pub async fn welcome_handler() -> Html<&'static str> {
     )
 }
 
-#[utoipa::path(
-    get,
-    path = "/health",
-    responses(
-        (status = 200, description = "Health check successful", body = serde_json::Value)
-    ),
-    tag = "health"
-)]
-pub async fn health_check() -> Json<serde_json::Value> {
-    let health_status = json!({
-        "status": "healthy",
-        "timestamp": chrono::Utc::now().to_rfc3339(),
-        "service": "url-shortener",
-        "version": env!("CARGO_PKG_VERSION"),
-        "uptime": "running"
-    });
+/// Build the configured short code strategy
+fn create_short_code_strategy(
+    strategy: ShortCodeStrategyType,
+    sequence_repository: PostgresShortCodeSequenceRepository,
+) -> Box<dyn ShortCodeStrategy + Send + Sync> {
+    info!("Generating short codes with the {:?} strategy", strategy);
+    match strategy {
+        ShortCodeStrategyType::Random => Box::new(RandomStrategy),
+        ShortCodeStrategyType::Hash => Box::new(HashStrategy),
+        ShortCodeStrategyType::Sequential => Box::new(SequentialStrategy::new(
+            std::sync::Arc::new(sequence_repository),
+        )),
+    }
+}
+
+/// Resolves when the process receives Ctrl+C or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                warn!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
 
-    info!("Health check requested - service is healthy");
-    Json(health_status)
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    info!("Shutdown signal received, stopping server");
+}
+
+/// Prometheus metrics in the text exposition format
+pub async fn metrics_handler(
+    axum::extract::State(app_state): axum::extract::State<ConcreteAppState>,
+) -> impl axum::response::IntoResponse {
+    metrics::sync_clicks_dropped(app_state.click_tracking_service.dropped_clicks_total());
+    metrics::sync_bulk_queue_depth(&app_state.bulk_processor.queue_depths());
+    metrics::sync_db_pool(&app_state.database_health.pool_stats());
+    metrics::sync_verify_token_cache(app_state.auth_service.token_cache_stats());
+
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4",
+        )],
+        metrics::render(),
+    )
 }
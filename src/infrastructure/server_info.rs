@@ -0,0 +1,270 @@
+use crate::infrastructure::config::AppConfig;
+use serde::Serialize;
+use tracing::info;
+use utoipa::openapi::{OpenApi, PathItemType};
+use utoipa::ToSchema;
+
+/// What the running server was configured with, as shown by the startup banner and `GET /info`
+///
+/// Only holds presence indicators for credentials and connection URLs, never their values.
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct ServerInfo {
+    /// Address the server listens on
+    pub address: String,
+    pub environment: String,
+    pub version: String,
+    pub tls_enabled: bool,
+    pub http2_enabled: bool,
+    pub features: ServerFeatures,
+    pub short_code: ShortCodeInfo,
+    pub rate_limit: RateLimitInfo,
+    /// Largest number of connections kept in the database pool
+    pub database_pool_size: u32,
+    /// Routes of the API, as documented in the OpenAPI spec
+    pub routes: Vec<RouteInfo>,
+}
+
+/// Optional integrations and whether they are turned on
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct ServerFeatures {
+    /// Redis backs the rate limiter, click deduplication and the analytics cache
+    pub redis_cache: bool,
+    pub email: bool,
+    /// Not built into this server; always `false`
+    pub two_factor_auth: bool,
+    /// Uploads go to an S3 bucket instead of the local disk
+    pub s3_storage: bool,
+    /// Not built into this server; always `false`
+    pub geoip: bool,
+    pub push_notifications: bool,
+    pub ip_reputation: bool,
+    /// OAuth login providers with a configured client
+    pub oauth_providers: Vec<String>,
+}
+
+/// How generated short codes look
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct ShortCodeInfo {
+    pub alphabet: String,
+    pub length: usize,
+    pub strategy: String,
+}
+
+/// Limits of the request rate limiter
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct RateLimitInfo {
+    pub algorithm: String,
+    pub requests_per_minute: u32,
+    pub burst_size: u32,
+    pub window_seconds: u64,
+}
+
+/// Method and path of a route
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct RouteInfo {
+    pub method: String,
+    pub path: String,
+}
+
+impl ServerInfo {
+    /// Summarize `config`; routes are added with [`ServerInfo::with_routes`]
+    pub fn from_config(config: &AppConfig) -> Self {
+        let oauth_providers = [
+            (
+                "google",
+                &config.google_client_id,
+                &config.google_client_secret,
+            ),
+            (
+                "github",
+                &config.github_client_id,
+                &config.github_client_secret,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, id, secret)| id.is_some() && secret.is_some())
+        .map(|(provider, _, _)| provider.to_string())
+        .collect();
+
+        Self {
+            address: format!("{}:{}", config.host, config.port),
+            environment: format!("{:?}", config.environment).to_lowercase(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            tls_enabled: config.tls_cert_path.is_some() && config.tls_key_path.is_some(),
+            http2_enabled: config.enable_http2,
+            features: ServerFeatures {
+                redis_cache: config.rate_limit.redis_url.is_some(),
+                email: config.email_enabled,
+                two_factor_auth: false,
+                s3_storage: config.object_storage.s3_enabled(),
+                geoip: false,
+                push_notifications: config.fcm.is_active(),
+                ip_reputation: config.abuse_ipdb.is_active(),
+                oauth_providers,
+            },
+            short_code: ShortCodeInfo {
+                alphabet: String::from(config.short_code.generated_alphabet.clone()),
+                length: config.short_code.length,
+                strategy: format!("{:?}", config.short_code_strategy).to_lowercase(),
+            },
+            rate_limit: RateLimitInfo {
+                algorithm: format!("{:?}", config.rate_limit.algorithm),
+                requests_per_minute: config.rate_limit.requests_per_minute,
+                burst_size: config.rate_limit.burst_size,
+                window_seconds: config.rate_limit.window_size,
+            },
+            database_pool_size: config.database.pool_max_connections,
+            routes: Vec::new(),
+        }
+    }
+
+    pub fn with_routes(mut self, routes: Vec<RouteInfo>) -> Self {
+        self.routes = routes;
+        self
+    }
+}
+
+/// Every operation documented in `openapi`, sorted by path then method
+pub fn route_table(openapi: &OpenApi) -> Vec<RouteInfo> {
+    let mut routes: Vec<RouteInfo> = openapi
+        .paths
+        .paths
+        .iter()
+        .flat_map(|(path, item)| {
+            item.operations.keys().map(move |method| RouteInfo {
+                method: method_name(method).to_string(),
+                path: path.clone(),
+            })
+        })
+        .collect();
+    routes.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.method.cmp(&b.method)));
+    routes
+}
+
+fn method_name(method: &PathItemType) -> &'static str {
+    match method {
+        PathItemType::Get => "GET",
+        PathItemType::Post => "POST",
+        PathItemType::Put => "PUT",
+        PathItemType::Delete => "DELETE",
+        PathItemType::Options => "OPTIONS",
+        PathItemType::Head => "HEAD",
+        PathItemType::Patch => "PATCH",
+        PathItemType::Trace => "TRACE",
+        PathItemType::Connect => "CONNECT",
+    }
+}
+
+/// Log what the server is about to run with
+pub fn print_startup_banner(config: &AppConfig) {
+    let info = ServerInfo::from_config(config);
+    info!(
+        address = %info.address,
+        environment = %info.environment,
+        version = %info.version,
+        tls = info.tls_enabled,
+        http2 = info.http2_enabled,
+        "Starting URL shortener"
+    );
+    info!(
+        redis_cache = info.features.redis_cache,
+        email = info.features.email,
+        two_factor_auth = info.features.two_factor_auth,
+        s3_storage = info.features.s3_storage,
+        geoip = info.features.geoip,
+        push_notifications = info.features.push_notifications,
+        ip_reputation = info.features.ip_reputation,
+        oauth_providers = ?info.features.oauth_providers,
+        "Features"
+    );
+    info!(
+        alphabet = %info.short_code.alphabet,
+        length = info.short_code.length,
+        strategy = %info.short_code.strategy,
+        "Short codes"
+    );
+    info!(
+        algorithm = %info.rate_limit.algorithm,
+        requests_per_minute = info.rate_limit.requests_per_minute,
+        burst_size = info.rate_limit.burst_size,
+        window_seconds = info.rate_limit.window_seconds,
+        "Rate limit"
+    );
+    info!(
+        pool_size = info.database_pool_size,
+        ssl_mode = %config.database.ssl_mode,
+        "Database"
+    );
+}
+
+/// Log one line per route
+pub fn log_route_table(routes: &[RouteInfo]) {
+    info!(count = routes.len(), "Routes");
+    for route in routes {
+        info!(method = %route.method, path = %route.path, "Route");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utoipa::openapi::path::{OperationBuilder, PathItemBuilder, PathsBuilder};
+    use utoipa::openapi::OpenApiBuilder;
+
+    #[test]
+    fn test_server_info_hides_secrets() {
+        let mut config = AppConfig::default();
+        config.rate_limit.redis_url = Some("redis://:hunter2@cache:6379".to_string());
+        config.google_client_id = Some("client".to_string());
+        config.google_client_secret = Some("google-secret".to_string());
+        config.github_client_id = Some("client".to_string());
+
+        let info = ServerInfo::from_config(&config);
+        assert!(info.features.redis_cache);
+        assert_eq!(info.features.oauth_providers, ["google"]);
+        assert_eq!(
+            info.database_pool_size,
+            config.database.pool_max_connections
+        );
+
+        let json = serde_json::to_string(&info).unwrap();
+        assert!(!json.contains("hunter2"));
+        assert!(!json.contains("google-secret"));
+    }
+
+    #[test]
+    fn test_route_table() {
+        let openapi = OpenApiBuilder::new()
+            .paths(
+                PathsBuilder::new()
+                    .path(
+                        "/urls",
+                        PathItemBuilder::new()
+                            .operation(PathItemType::Post, OperationBuilder::new().build())
+                            .operation(PathItemType::Get, OperationBuilder::new().build())
+                            .build(),
+                    )
+                    .path(
+                        "/info",
+                        PathItemBuilder::new()
+                            .operation(PathItemType::Get, OperationBuilder::new().build())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build();
+
+        let routes: Vec<(String, String)> = route_table(&openapi)
+            .into_iter()
+            .map(|route| (route.method, route.path))
+            .collect();
+        assert_eq!(
+            routes,
+            [
+                ("GET".to_string(), "/info".to_string()),
+                ("GET".to_string(), "/urls".to_string()),
+                ("POST".to_string(), "/urls".to_string()),
+            ]
+        );
+    }
+}
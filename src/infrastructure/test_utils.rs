@@ -1,21 +1,98 @@
 #![allow(dead_code)]
 
 // Test utilities for integration tests
-use crate::domain::entities::{ShortCode, Url, UrlStatus};
-use crate::domain::repositories::{RepositoryError, UrlRepository};
+use crate::application::{
+    GetUrlAnalyticsUseCase, ListUrlsUseCase, ShortenUrlUseCase, UpdateUrlUseCase,
+};
+use crate::domain::entities::click::DeviceType;
+use crate::domain::entities::{
+    AccountDeletionToken, AccountStatus, ArchiveRecord, AuditLogEntry, BlockedDomain, Click,
+    ConversionEvent, ConversionGoal, EmailChangeSide, EmailChangeToken, ExportJob, MagicLinkToken,
+    NotificationPreferences, OAuthProvider, OrgRole, Organization, OrganizationMember,
+    OrganizationWithMemberCount, OutboxEmail, OutboxPush, PasswordResetToken, ProfilePrivacy,
+    ProfileVisibility, ServiceAccount, Session, SessionClient, ShortCode, SocialLinks, Url,
+    UrlConfig, UrlCountChange, UrlMetadata, UrlStatus, UrlWithClickCount, User, UserUrlStats,
+    MAX_DEVICE_TOKENS,
+};
+use crate::domain::repositories::click_repository::{
+    ClickCountTotal, ClickDedupRatio, ClickStats, DeviceBreakdown,
+    RepositoryError as ClickRepositoryError, UrlAnalyticsSummary, UtmAttributionRow,
+};
+use crate::domain::repositories::domain_blacklist_repository::RepositoryError as DomainBlacklistRepositoryError;
+use crate::domain::repositories::notification_preferences_repository::RepositoryError as NotificationPreferencesRepositoryError;
+use crate::domain::repositories::service_account_repository::RepositoryError as ServiceAccountRepositoryError;
+use crate::domain::repositories::url_metadata_repository::RepositoryError as UrlMetadataRepositoryError;
+use crate::domain::repositories::user_repository::{
+    normalize_email, RepositoryError as UserRepositoryError, UserDataExport,
+};
+use crate::domain::repositories::{
+    AccountDeletionTokenRepository, ArchiveRepository, AuditLogRepository, ClickRepository,
+    DigestRecipient, DomainBlacklistRepository, EmailChangeRepository, EmailOutboxRepository,
+    ExportJobRepository, MagicLinkRepository, NotificationPreferencesRepository,
+    OrganizationRepository, OrganizationRepositoryError, PasswordResetRepository,
+    PushOutboxRepository, RepositoryError, ServiceAccountRepository, SessionRepository,
+    SortDirection, UrlCursor, UrlFilter, UrlMetadataRepository, UrlPage, UrlRepository,
+    UrlSortField, UserRepository, UserUrlStatsRepository,
+};
+use crate::domain::services::cleanup_service::CleanupService;
+use crate::domain::services::click_tracking_service::ClickTrackingService;
+use crate::domain::services::{
+    AnalyticsExportService, AnonymizationService, AuthService, DataExportService, DomainBlacklist,
+    InterstitialService, LinkPreviewService, OAuthService, OrgLimits, OrgService,
+    ServiceAccountService, UrlService,
+};
+use crate::infrastructure::analytics_cache::{AnalyticsCache, AnalyticsCacheError, KEY_PREFIX};
+use crate::infrastructure::click_deduplication::{ClickDeduplicationError, ClickDeduplicator};
+use crate::infrastructure::config::RetentionConfig;
+use crate::infrastructure::database::{DatabaseHealthCheck, ReplicaAwarePool, ReplicaLag};
+use crate::infrastructure::email::{EmailError, EmailMessage, EmailSender};
+use crate::infrastructure::http::middleware::idempotency_middleware::{
+    IdempotencyLayer, InMemoryIdempotencyStore,
+};
+use crate::infrastructure::object_storage::LocalObjectStorage;
+use crate::infrastructure::push::{PushError, PushMessage, PushNotificationSender};
+use crate::infrastructure::rate_limiting::{
+    IpReputationCache, IpReputationCacheError, RateLimitStoreError, SlidingWindowEntry,
+    SlidingWindowStore,
+};
+use crate::infrastructure::server::api_routes;
+use crate::infrastructure::trace_context::TraceIdExtractor;
+use crate::presentation::handlers::{AppStateBuilder, ConcreteAppState};
 use async_trait::async_trait;
+use axum::Router;
+use chrono::{Datelike, Timelike};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Callback run after each successful URL creation with the number of URLs stored
+type CreateHook = Arc<dyn Fn(usize) + Send + Sync>;
 
 /// Mock repository for testing
 #[derive(Clone)]
 pub struct MockUrlRepository {
     urls: Arc<Mutex<Vec<Url>>>,
+    /// Replaced short codes and the ID of the URL they still resolve to
+    aliases: Arc<Mutex<HashMap<String, i32>>>,
+    failing_creates: Arc<Mutex<usize>>,
+    missed_lookups: Arc<Mutex<usize>>,
+    create_hook: Arc<Mutex<Option<CreateHook>>>,
+    /// Recorded clicks per URL ID, set with `set_click_count`
+    click_counts: Arc<Mutex<HashMap<i32, i64>>>,
+    /// Lag reported as a read replica, set with `set_replica_lag_ms`
+    replica_lag_ms: Arc<Mutex<Option<f64>>>,
 }
 
 impl Default for MockUrlRepository {
     fn default() -> Self {
         Self {
             urls: Arc::new(Mutex::new(Vec::new())),
+            aliases: Arc::new(Mutex::new(HashMap::new())),
+            failing_creates: Arc::new(Mutex::new(0)),
+            missed_lookups: Arc::new(Mutex::new(0)),
+            create_hook: Arc::new(Mutex::new(None)),
+            click_counts: Arc::new(Mutex::new(HashMap::new())),
+            replica_lag_ms: Arc::new(Mutex::new(Some(0.0))),
         }
     }
 }
@@ -24,6 +101,104 @@ impl MockUrlRepository {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Report this lag when used as a read replica; `None` fails the lag check
+    pub fn set_replica_lag_ms(&self, lag_ms: Option<f64>) {
+        *self.replica_lag_ms.lock().unwrap() = lag_ms;
+    }
+
+    /// Make the next `count` URL creations fail with a connection error
+    pub fn fail_next_creates(&self, count: usize) {
+        *self.failing_creates.lock().unwrap() = count;
+    }
+
+    /// Make the next `count` short code lookups find nothing, as if they ran just before a
+    /// concurrent request stored the URL
+    pub fn miss_next_lookups(&self, count: usize) {
+        *self.missed_lookups.lock().unwrap() = count;
+    }
+
+    /// Run `hook` after every successful URL creation with the number of URLs stored
+    pub fn on_create(&self, hook: impl Fn(usize) + Send + Sync + 'static) {
+        *self.create_hook.lock().unwrap() = Some(Arc::new(hook));
+    }
+
+    /// Report `count` recorded clicks for the URL with `url_id`
+    pub fn set_click_count(&self, url_id: i32, count: i64) {
+        self.click_counts.lock().unwrap().insert(url_id, count);
+    }
+
+    /// Store a URL unless its short code is taken; `idempotent` returns the taken row instead
+    ///
+    /// Like the database, counts the organization's URLs under the same lock as the insert.
+    #[allow(clippy::too_many_arguments)]
+    fn store_url(
+        &self,
+        short_code: &ShortCode,
+        original_url: &str,
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Option<i32>,
+        organization_id: Option<i32>,
+        max_organization_urls: Option<i64>,
+        status: UrlStatus,
+        idempotent: bool,
+    ) -> Result<(Url, bool), RepositoryError> {
+        {
+            let mut failing_creates = self.failing_creates.lock().unwrap();
+            if *failing_creates > 0 {
+                *failing_creates -= 1;
+                return Err(RepositoryError::Connection(sqlx::Error::PoolTimedOut));
+            }
+        }
+        let (url, stored) = {
+            let mut urls = self.urls.lock().unwrap();
+            if let (Some(org_id), Some(limit)) = (organization_id, max_organization_urls) {
+                let owned = urls
+                    .iter()
+                    .filter(|url| url.organization_id == Some(org_id))
+                    .count();
+                if owned as i64 >= limit {
+                    return Err(RepositoryError::OrganizationQuotaExceeded { limit });
+                }
+            }
+            if let Some(existing) = urls
+                .iter()
+                .find(|url| url.short_code == short_code.value() && !url.is_deleted())
+            {
+                return if idempotent {
+                    Ok((existing.clone(), false))
+                } else {
+                    Err(RepositoryError::DuplicateShortCode)
+                };
+            }
+            let url = Url::new_with_timestamp(
+                (urls.len() + 1) as i32,
+                short_code.value().to_string(),
+                original_url.to_string(),
+                expiration_date,
+                user_id,
+                status,
+            )
+            .with_organization(organization_id);
+            urls.push(url.clone());
+            (url, urls.len())
+        };
+        let hook = self.create_hook.lock().unwrap().clone();
+        if let Some(hook) = hook {
+            hook(stored);
+        }
+        Ok((url, true))
+    }
+}
+
+#[async_trait]
+impl ReplicaLag for MockUrlRepository {
+    async fn replica_lag_ms(&self) -> Result<Option<f64>, RepositoryError> {
+        match *self.replica_lag_ms.lock().unwrap() {
+            Some(lag_ms) => Ok(Some(lag_ms)),
+            None => Err(RepositoryError::Connection(sqlx::Error::PoolTimedOut)),
+        }
+    }
 }
 
 #[async_trait]
@@ -34,48 +209,275 @@ impl UrlRepository for MockUrlRepository {
         original_url: &str,
         expiration_date: Option<chrono::DateTime<chrono::Utc>>,
         user_id: Option<i32>,
+        organization_id: Option<i32>,
         status: UrlStatus,
     ) -> Result<Url, RepositoryError> {
-        let url = Url::new_with_timestamp(
-            (self.urls.lock().unwrap().len() + 1) as i32,
-            short_code.value().to_string(),
-            original_url.to_string(),
+        self.store_url(
+            short_code,
+            original_url,
             expiration_date,
             user_id,
+            organization_id,
+            None,
             status,
-        );
-        let mut urls = self.urls.lock().unwrap();
-        urls.push(url.clone());
-        Ok(url)
+            false,
+        )
+        .map(|(url, _)| url)
+    }
+
+    async fn create_url_idempotent(
+        &self,
+        short_code: &ShortCode,
+        original_url: &str,
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Option<i32>,
+        organization_id: Option<i32>,
+        max_organization_urls: Option<i64>,
+        status: UrlStatus,
+    ) -> Result<(Url, bool), RepositoryError> {
+        self.store_url(
+            short_code,
+            original_url,
+            expiration_date,
+            user_id,
+            organization_id,
+            max_organization_urls,
+            status,
+            true,
+        )
     }
 
     async fn exists_by_short_code(&self, short_code: &ShortCode) -> Result<bool, RepositoryError> {
         let urls = self.urls.lock().unwrap();
-        Ok(urls.iter().any(|url| url.short_code == short_code.value()))
+        Ok(urls
+            .iter()
+            .any(|url| url.short_code == short_code.value() && !url.is_deleted())
+            || self
+                .aliases
+                .lock()
+                .unwrap()
+                .contains_key(short_code.value()))
+    }
+
+    async fn find_phonetically_similar_codes(
+        &self,
+        generated: &ShortCode,
+        threshold: f32,
+    ) -> Result<Vec<ShortCode>, RepositoryError> {
+        let urls = self.urls.lock().unwrap();
+        let aliases = self.aliases.lock().unwrap();
+        Ok(urls
+            .iter()
+            .filter(|url| !url.is_deleted())
+            .map(|url| url.short_code.clone())
+            .chain(aliases.keys().cloned())
+            .map(ShortCode::from_string_unchecked)
+            .filter(|code| generated.phonetic_distance(code) < threshold)
+            .collect())
+    }
+
+    async fn replace_short_code(
+        &self,
+        url_id: i32,
+        new_short_code: &ShortCode,
+    ) -> Result<(), RepositoryError> {
+        let mut urls = self.urls.lock().unwrap();
+        let url = urls
+            .iter_mut()
+            .find(|url| url.id == url_id)
+            .ok_or(RepositoryError::NotFound)?;
+        let old_short_code =
+            std::mem::replace(&mut url.short_code, new_short_code.value().to_string());
+        url.version += 1;
+        self.aliases
+            .lock()
+            .unwrap()
+            .entry(old_short_code)
+            .or_insert(url_id);
+        Ok(())
+    }
+
+    async fn rename_short_code(
+        &self,
+        url_id: i32,
+        new_short_code: &ShortCode,
+        max_aliases: usize,
+    ) -> Result<Url, RepositoryError> {
+        let mut urls = self.urls.lock().unwrap();
+        let mut aliases = self.aliases.lock().unwrap();
+        if urls
+            .iter()
+            .any(|url| url.short_code == new_short_code.value() && !url.is_deleted())
+            || aliases.contains_key(new_short_code.value())
+        {
+            return Err(RepositoryError::DuplicateShortCode);
+        }
+        let url = urls
+            .iter_mut()
+            .find(|url| url.id == url_id && !url.is_deleted())
+            .ok_or(RepositoryError::NotFound)?;
+        if aliases.values().filter(|&&id| id == url_id).count() >= max_aliases {
+            return Err(RepositoryError::AliasLimitReached { limit: max_aliases });
+        }
+        let old_short_code =
+            std::mem::replace(&mut url.short_code, new_short_code.value().to_string());
+        url.version += 1;
+        aliases.insert(old_short_code, url_id);
+        Ok(url.clone())
     }
 
     async fn find_by_short_code(
         &self,
         short_code: &ShortCode,
+        _force_primary: bool,
     ) -> Result<Option<Url>, RepositoryError> {
+        {
+            let mut missed_lookups = self.missed_lookups.lock().unwrap();
+            if *missed_lookups > 0 {
+                *missed_lookups -= 1;
+                return Ok(None);
+            }
+        }
         let urls = self.urls.lock().unwrap();
+        let alias = self
+            .aliases
+            .lock()
+            .unwrap()
+            .get(short_code.value())
+            .copied();
         Ok(urls
             .iter()
+            .filter(|url| !url.is_deleted())
             .find(|url| url.short_code == short_code.value())
+            .or_else(|| {
+                alias.and_then(|id| urls.iter().find(|url| url.id == id && !url.is_deleted()))
+            })
+            .cloned())
+    }
+
+    async fn find_deleted_by_short_code(
+        &self,
+        short_code: &ShortCode,
+    ) -> Result<Option<Url>, RepositoryError> {
+        let urls = self.urls.lock().unwrap();
+        Ok(urls
+            .iter()
+            .filter(|url| url.is_deleted() && url.short_code == short_code.value())
+            .max_by_key(|url| url.deleted_at)
             .cloned())
     }
 
-    async fn find_by_user_id(&self, _user_id: i32) -> Result<Vec<Url>, RepositoryError> {
+    async fn find_by_user_id(
+        &self,
+        _user_id: i32,
+        _organization_id: Option<i32>,
+    ) -> Result<Vec<Url>, RepositoryError> {
         let urls = self.urls.lock().unwrap();
         Ok(urls.clone())
     }
 
-    async fn delete_by_id(&self, _id: i32, _user_id: Option<i32>) -> Result<bool, RepositoryError> {
-        Ok(true)
+    async fn find_by_original_url(
+        &self,
+        original_url: &str,
+        user_id: i32,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let urls = self.urls.lock().unwrap();
+        Ok(urls
+            .iter()
+            .rev()
+            .filter(|u| u.original_url == original_url && u.user_id == Some(user_id))
+            .cloned()
+            .collect())
     }
 
-    async fn update_url(&self, url: &Url) -> Result<Url, RepositoryError> {
-        Ok(url.clone())
+    async fn find_all_by_original_url(
+        &self,
+        original_url: &str,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let urls = self.urls.lock().unwrap();
+        Ok(urls
+            .iter()
+            .rev()
+            .filter(|u| u.original_url == original_url)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_by_id(&self, id: i32, user_id: Option<i32>) -> Result<bool, RepositoryError> {
+        let mut urls = self.urls.lock().unwrap();
+        match urls
+            .iter_mut()
+            .find(|u| u.id == id && u.user_id == user_id && !u.is_deleted())
+        {
+            Some(url) => {
+                url.mark_deleted(chrono::Utc::now());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn permanently_delete_by_id(&self, id: i32) -> Result<bool, RepositoryError> {
+        let mut urls = self.urls.lock().unwrap();
+        let before = urls.len();
+        urls.retain(|url| url.id != id);
+        Ok(urls.len() < before)
+    }
+
+    async fn find_deleted_before(
+        &self,
+        deleted_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let urls = self.urls.lock().unwrap();
+        Ok(urls
+            .iter()
+            .filter(|url| url.deleted_at.is_some_and(|d| d < deleted_before))
+            .cloned()
+            .collect())
+    }
+
+    async fn transfer_owner(
+        &self,
+        id: i32,
+        from_user_id: i32,
+        to_user_id: i32,
+    ) -> Result<Option<Url>, RepositoryError> {
+        let mut urls = self.urls.lock().unwrap();
+        Ok(urls
+            .iter_mut()
+            .find(|u| u.id == id && u.user_id == Some(from_user_id) && !u.is_deleted())
+            .map(|url| {
+                url.user_id = Some(to_user_id);
+                url.clone()
+            }))
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Url>, RepositoryError> {
+        let urls = self.urls.lock().unwrap();
+        Ok(urls
+            .iter()
+            .find(|url| url.id == id && !url.is_deleted())
+            .cloned())
+    }
+
+    async fn update_url(&self, url: &Url, expected_version: i64) -> Result<Url, RepositoryError> {
+        let mut urls = self.urls.lock().unwrap();
+        match urls.iter_mut().find(|existing| existing.id == url.id) {
+            Some(existing) if existing.version != expected_version => {
+                Err(RepositoryError::ConflictingUpdate {
+                    current_version: existing.version,
+                })
+            }
+            Some(existing) => {
+                *existing = url.clone();
+                existing.version = expected_version + 1;
+                existing.updated_at = chrono::Utc::now();
+                Ok(existing.clone())
+            }
+            None => Err(RepositoryError::NotFound),
+        }
     }
 
     async fn get_stats(
@@ -86,24 +488,70 @@ impl UrlRepository for MockUrlRepository {
             total_urls: 0,
             total_clicks: 0,
             unique_short_codes: 0,
+            estimated_unique_visitors: 0,
+            archived_url_count: 0,
         })
     }
 
     async fn find_urls_expiring_soon(
         &self,
-        _duration: chrono::Duration,
+        duration: chrono::Duration,
     ) -> Result<Vec<Url>, RepositoryError> {
-        Ok(vec![])
+        let now = chrono::Utc::now();
+        let mut urls: Vec<Url> = self
+            .urls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|url| {
+                url.expiration_date
+                    .is_some_and(|e| e > now && e <= now + duration)
+            })
+            .cloned()
+            .collect();
+        urls.sort_by_key(|url| url.expiration_date);
+        Ok(urls)
     }
 
     async fn find_expired_urls(&self) -> Result<Vec<Url>, RepositoryError> {
         Ok(vec![])
     }
 
-    async fn delete_expired_urls(&self) -> Result<u64, RepositoryError> {
+    async fn delete_expired_urls(
+        &self,
+        _expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
         Ok(0)
     }
 
+    async fn archive_expired_urls(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let mut urls = self.urls.lock().unwrap();
+        let mut archived = Vec::new();
+        for url in urls.iter_mut().filter(|url| {
+            !url.is_archived() && url.expiration_date.is_some_and(|e| e <= expired_before)
+        }) {
+            archived.push(url.clone());
+            url.status = UrlStatus::Archived;
+        }
+        Ok(archived)
+    }
+
+    async fn count_expired_urls_to_archive(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        let urls = self.urls.lock().unwrap();
+        Ok(urls
+            .iter()
+            .filter(|url| {
+                !url.is_archived() && url.expiration_date.is_some_and(|e| e <= expired_before)
+            })
+            .count() as u64)
+    }
+
     async fn soft_delete_by_id(
         &self,
         id: i32,
@@ -146,6 +594,50 @@ impl UrlRepository for MockUrlRepository {
         Ok(filtered_urls)
     }
 
+    async fn find_by_status_paginated(
+        &self,
+        status: UrlStatus,
+        after_id: Option<i32>,
+        limit: usize,
+    ) -> Result<(Vec<Url>, Option<i32>), RepositoryError> {
+        let urls = self.urls.lock().unwrap();
+        let mut page: Vec<Url> = urls
+            .iter()
+            .filter(|url| url.status == status && after_id.is_none_or(|after| url.id > after))
+            .cloned()
+            .collect();
+        page.sort_by_key(|url| url.id);
+        let has_more = page.len() > limit;
+        page.truncate(limit);
+        let next_after_id = if has_more {
+            page.last().map(|url| url.id)
+        } else {
+            None
+        };
+        Ok((page, next_after_id))
+    }
+
+    async fn find_suspicious_urls(
+        &self,
+        min_click_count: i64,
+        created_within_hours: u8,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let created_after =
+            chrono::Utc::now() - chrono::Duration::hours(i64::from(created_within_hours));
+        let click_counts = self.click_counts.lock().unwrap();
+        let urls = self.urls.lock().unwrap();
+        let mut suspicious: Vec<(i64, Url)> = urls
+            .iter()
+            .filter(|url| !url.is_deleted() && url.created_at > created_after)
+            .filter_map(|url| {
+                let clicks = click_counts.get(&url.id).copied().unwrap_or(0);
+                (clicks >= min_click_count).then(|| (clicks, url.clone()))
+            })
+            .collect();
+        suspicious.sort_by_key(|(clicks, url)| (std::cmp::Reverse(*clicks), url.id));
+        Ok(suspicious.into_iter().map(|(_, url)| url).collect())
+    }
+
     async fn batch_deactivate_urls(
         &self,
         url_ids: &[i32],
@@ -178,11 +670,10 @@ impl UrlRepository for MockUrlRepository {
         let mut results = Vec::new();
 
         for &url_id in url_ids {
-            if let Some(pos) = urls
-                .iter()
-                .position(|u| u.id == url_id && (user_id.is_none() || u.user_id == user_id))
-            {
-                urls.remove(pos);
+            if let Some(url) = urls.iter_mut().find(|u| {
+                u.id == url_id && (user_id.is_none() || u.user_id == user_id) && !u.is_deleted()
+            }) {
+                url.mark_deleted(chrono::Utc::now());
                 results.push(BatchItemResult {
                     url_id,
                     success: true,
@@ -293,4 +784,2624 @@ impl UrlRepository for MockUrlRepository {
             results,
         })
     }
+
+    async fn find_most_clicked(
+        &self,
+        user_id: i32,
+        limit: usize,
+    ) -> Result<Vec<UrlWithClickCount>, RepositoryError> {
+        let urls = self.urls.lock().unwrap();
+        Ok(urls
+            .iter()
+            .filter(|url| url.user_id == Some(user_id))
+            .take(limit)
+            .map(|url| UrlWithClickCount::new(url.clone(), 0))
+            .collect())
+    }
+
+    async fn find_recently_created(
+        &self,
+        user_id: i32,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let urls = self.urls.lock().unwrap();
+        let mut user_urls: Vec<_> = urls
+            .iter()
+            .filter(|url| url.user_id == Some(user_id))
+            .cloned()
+            .collect();
+        user_urls.sort_by_key(|url| std::cmp::Reverse(url.created_at));
+        user_urls.truncate(limit);
+        Ok(user_urls)
+    }
+
+    async fn find_recently_modified(
+        &self,
+        user_id: i32,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: usize,
+    ) -> Result<Vec<Url>, RepositoryError> {
+        let urls = self.urls.lock().unwrap();
+        let mut user_urls: Vec<_> = urls
+            .iter()
+            .filter(|url| url.user_id == Some(user_id) && url.updated_at > since)
+            .cloned()
+            .collect();
+        user_urls.sort_by_key(|url| std::cmp::Reverse(url.updated_at));
+        user_urls.truncate(limit);
+        Ok(user_urls)
+    }
+
+    async fn find_paginated(
+        &self,
+        user_id: Option<i32>,
+        status: Option<UrlStatus>,
+        sort: UrlSortField,
+        direction: SortDirection,
+        after_cursor: Option<&UrlCursor>,
+        limit: usize,
+    ) -> Result<UrlPage, RepositoryError> {
+        let urls = self.urls.lock().unwrap();
+        let urls = urls
+            .iter()
+            .filter(|url| user_id.is_none_or(|id| url.user_id == Some(id)))
+            .filter(|url| url.status.is_listed_under(status))
+            .cloned();
+        Ok(UrlPage::paginate(
+            urls,
+            sort,
+            direction,
+            after_cursor,
+            limit,
+        ))
+    }
+
+    async fn count_by_filter(
+        &self,
+        filter: &UrlFilter,
+        user_id: i32,
+    ) -> Result<i64, RepositoryError> {
+        let urls = self.urls.lock().unwrap();
+        Ok(urls
+            .iter()
+            .filter(|url| url.user_id == Some(user_id) && filter.matches(url))
+            .count() as i64)
+    }
+
+    async fn count_clicks_for_urls(
+        &self,
+        url_ids: &[i32],
+    ) -> Result<HashMap<i32, i64>, RepositoryError> {
+        let click_counts = self.click_counts.lock().unwrap();
+        Ok(url_ids
+            .iter()
+            .filter_map(|id| click_counts.get(id).map(|count| (*id, *count)))
+            .collect())
+    }
+}
+
+/// In-memory user repository for testing
+///
+/// Mirrors the PostgreSQL implementation's case-insensitive email handling.
+#[derive(Clone, Default)]
+pub struct MockUserRepository {
+    users: Arc<Mutex<Vec<User>>>,
+    outbox: MockEmailOutboxRepository,
+}
+
+impl MockUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emails queued together with new users
+    pub fn outbox(&self) -> &MockEmailOutboxRepository {
+        &self.outbox
+    }
+}
+
+#[async_trait]
+impl UserRepository for MockUserRepository {
+    async fn create_user(
+        &self,
+        username: &str,
+        email: &str,
+        password_hash: &str,
+    ) -> Result<User, UserRepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        let email = normalize_email(email);
+        if users.iter().any(|u| u.email == email) {
+            return Err(UserRepositoryError::DuplicateEmail);
+        }
+        if users.iter().any(|u| u.username == username) {
+            return Err(UserRepositoryError::DuplicateUsername);
+        }
+
+        let user = User::new_with_timestamp(
+            (users.len() + 1) as i32,
+            username.to_string(),
+            email,
+            password_hash.to_string(),
+        );
+        users.push(user.clone());
+        Ok(user)
+    }
+
+    async fn create_unverified_user(
+        &self,
+        username: &str,
+        email: &str,
+        password_hash: &str,
+        welcome_email: &EmailMessage,
+    ) -> Result<User, UserRepositoryError> {
+        let user = self.create_user(username, email, password_hash).await?;
+        let user = self
+            .update_account_status(user.id, &AccountStatus::PendingVerification)
+            .await?;
+        self.outbox.queue(welcome_email);
+        Ok(user)
+    }
+
+    async fn create_oauth_user(
+        &self,
+        username: &str,
+        email: &str,
+        provider: OAuthProvider,
+        provider_id: &str,
+    ) -> Result<User, UserRepositoryError> {
+        let mut user = self.create_user(username, email, "").await?;
+        user.oauth_provider = Some(provider);
+        user.oauth_provider_id = Some(provider_id.to_string());
+
+        let mut users = self.users.lock().unwrap();
+        if let Some(stored) = users.iter_mut().find(|u| u.id == user.id) {
+            *stored = user.clone();
+        }
+        Ok(user)
+    }
+
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, UserRepositoryError> {
+        let users = self.users.lock().unwrap();
+        Ok(users.iter().find(|u| u.username == username).cloned())
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, UserRepositoryError> {
+        let users = self.users.lock().unwrap();
+        let email = normalize_email(email);
+        Ok(users
+            .iter()
+            .find(|u| normalize_email(&u.email) == email)
+            .cloned())
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<User>, UserRepositoryError> {
+        let users = self.users.lock().unwrap();
+        Ok(users.iter().find(|u| u.id == id).cloned())
+    }
+
+    async fn find_usernames_by_ids(
+        &self,
+        ids: &[i32],
+    ) -> Result<HashMap<i32, String>, UserRepositoryError> {
+        let users = self.users.lock().unwrap();
+        Ok(users
+            .iter()
+            .filter(|u| ids.contains(&u.id))
+            .map(|u| (u.id, u.username.clone()))
+            .collect())
+    }
+
+    async fn find_by_oauth_account(
+        &self,
+        provider: OAuthProvider,
+        provider_id: &str,
+    ) -> Result<Option<User>, UserRepositoryError> {
+        let users = self.users.lock().unwrap();
+        Ok(users
+            .iter()
+            .find(|u| {
+                u.oauth_provider == Some(provider)
+                    && u.oauth_provider_id.as_deref() == Some(provider_id)
+            })
+            .cloned())
+    }
+
+    async fn link_oauth_account(
+        &self,
+        user_id: i32,
+        provider: OAuthProvider,
+        provider_id: &str,
+    ) -> Result<User, UserRepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        let user = users
+            .iter_mut()
+            .find(|u| u.id == user_id)
+            .ok_or(UserRepositoryError::NotFound)?;
+
+        user.oauth_provider = Some(provider);
+        user.oauth_provider_id = Some(provider_id.to_string());
+        Ok(user.clone())
+    }
+
+    /// Display names match when every query word is one of their words, like `plainto_tsquery`
+    async fn search_users(
+        &self,
+        query: &str,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<User>, UserRepositoryError> {
+        let needle = query.to_lowercase();
+        let words: Vec<&str> = needle.split_whitespace().collect();
+        let mut users: Vec<User> = self
+            .users
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|u| {
+                let name = u.full_name().unwrap_or_default().to_lowercase();
+                let name_words: Vec<&str> = name.split_whitespace().collect();
+                u.email.to_lowercase().contains(&needle)
+                    || u.username.to_lowercase().contains(&needle)
+                    || (!words.is_empty() && words.iter().all(|w| name_words.contains(w)))
+            })
+            .cloned()
+            .collect();
+        users.sort_by(|a, b| a.username.cmp(&b.username));
+        Ok(users.into_iter().skip(offset).take(limit).collect())
+    }
+
+    async fn exists_by_username(&self, username: &str) -> Result<bool, UserRepositoryError> {
+        Ok(self.find_by_username(username).await?.is_some())
+    }
+
+    async fn exists_by_email(&self, email: &str) -> Result<bool, UserRepositoryError> {
+        Ok(self.find_by_email(email).await?.is_some())
+    }
+
+    async fn update_profile(
+        &self,
+        user_id: i32,
+        first_name: Option<&str>,
+        last_name: Option<&str>,
+        bio: Option<&str>,
+        avatar_url: Option<&str>,
+        website: Option<&str>,
+        location: Option<&str>,
+        privacy: Option<ProfilePrivacy>,
+    ) -> Result<User, UserRepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        let user = users
+            .iter_mut()
+            .find(|u| u.id == user_id)
+            .ok_or(UserRepositoryError::NotFound)?;
+
+        user.update_profile(
+            first_name.map(str::to_string),
+            last_name.map(str::to_string),
+            bio.map(str::to_string),
+            avatar_url.map(str::to_string),
+            website.map(str::to_string),
+            location.map(str::to_string),
+            privacy,
+        );
+        Ok(user.clone())
+    }
+
+    async fn get_profile(&self, user_id: i32) -> Result<Option<User>, UserRepositoryError> {
+        self.find_by_id(user_id).await
+    }
+
+    async fn delete_account(&self, user_id: i32) -> Result<(), UserRepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        let initial_count = users.len();
+        users.retain(|u| u.id != user_id);
+        if users.len() == initial_count {
+            return Err(UserRepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn anonymize_user(&self, user_id: i32) -> Result<(), UserRepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        let user = users
+            .iter_mut()
+            .find(|u| u.id == user_id)
+            .ok_or(UserRepositoryError::NotFound)?;
+
+        let anonymized = AnonymizationService::new().anonymize_user_data(user);
+        *user = User::new(
+            user.id,
+            anonymized.username,
+            anonymized.email,
+            anonymized.password_hash,
+            user.created_at,
+        );
+        user.privacy = ProfilePrivacy::Private;
+        user.account_status = AccountStatus::Deactivated;
+        user.password_changed_at = Some(chrono::Utc::now());
+        Ok(())
+    }
+
+    async fn update_password(
+        &self,
+        user_id: i32,
+        password_hash: &str,
+    ) -> Result<User, UserRepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        let user = users
+            .iter_mut()
+            .find(|u| u.id == user_id)
+            .ok_or(UserRepositoryError::NotFound)?;
+
+        user.password_hash = password_hash.to_string();
+        user.password_changed_at = Some(chrono::Utc::now());
+        Ok(user.clone())
+    }
+
+    async fn update_profile_visibility(
+        &self,
+        user_id: i32,
+        visibility: &ProfileVisibility,
+    ) -> Result<User, UserRepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        let user = users
+            .iter_mut()
+            .find(|u| u.id == user_id)
+            .ok_or(UserRepositoryError::NotFound)?;
+
+        user.profile_visibility = *visibility;
+        Ok(user.clone())
+    }
+
+    async fn update_social_links(
+        &self,
+        user_id: i32,
+        social_links: Option<&SocialLinks>,
+    ) -> Result<User, UserRepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        let user = users
+            .iter_mut()
+            .find(|u| u.id == user_id)
+            .ok_or(UserRepositoryError::NotFound)?;
+
+        user.social_links = social_links.cloned();
+        Ok(user.clone())
+    }
+
+    async fn set_pending_email(
+        &self,
+        user_id: i32,
+        pending_email: Option<&str>,
+    ) -> Result<User, UserRepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        let user = users
+            .iter_mut()
+            .find(|u| u.id == user_id)
+            .ok_or(UserRepositoryError::NotFound)?;
+
+        user.pending_email = pending_email.map(normalize_email);
+        Ok(user.clone())
+    }
+
+    async fn update_email(&self, user_id: i32, email: &str) -> Result<User, UserRepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        let email = normalize_email(email);
+        if users.iter().any(|u| u.email == email && u.id != user_id) {
+            return Err(UserRepositoryError::DuplicateEmail);
+        }
+        let user = users
+            .iter_mut()
+            .find(|u| u.id == user_id)
+            .ok_or(UserRepositoryError::NotFound)?;
+
+        user.email = email;
+        user.pending_email = None;
+        Ok(user.clone())
+    }
+
+    async fn add_device_token(
+        &self,
+        user_id: i32,
+        token: &str,
+    ) -> Result<User, UserRepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        if !users.iter().any(|u| u.id == user_id) {
+            return Err(UserRepositoryError::NotFound);
+        }
+        for user in users.iter_mut() {
+            user.device_tokens.retain(|t| t != token);
+        }
+
+        let user = users.iter_mut().find(|u| u.id == user_id).unwrap();
+        user.device_tokens.push(token.to_string());
+        let excess = user.device_tokens.len().saturating_sub(MAX_DEVICE_TOKENS);
+        user.device_tokens.drain(..excess);
+        Ok(user.clone())
+    }
+
+    async fn remove_device_token(
+        &self,
+        user_id: i32,
+        token: &str,
+    ) -> Result<bool, UserRepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        let Some(user) = users.iter_mut().find(|u| u.id == user_id) else {
+            return Ok(false);
+        };
+        let before = user.device_tokens.len();
+        user.device_tokens.retain(|t| t != token);
+        Ok(user.device_tokens.len() < before)
+    }
+
+    async fn update_account_status(
+        &self,
+        user_id: i32,
+        status: &AccountStatus,
+    ) -> Result<User, UserRepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        let user = users
+            .iter_mut()
+            .find(|u| u.id == user_id)
+            .ok_or(UserRepositoryError::NotFound)?;
+
+        user.account_status = status.clone();
+        Ok(user.clone())
+    }
+
+    /// Users do not own URLs in this mock, so exports have an empty URL list
+    async fn export_user_data(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<UserDataExport>, UserRepositoryError> {
+        Ok(self.find_by_id(user_id).await?.map(|user| UserDataExport {
+            user,
+            urls: Vec::new(),
+        }))
+    }
+}
+
+/// In-memory session repository for testing
+#[derive(Clone, Default)]
+pub struct MockSessionRepository {
+    sessions: Arc<Mutex<Vec<Session>>>,
+}
+
+impl MockSessionRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SessionRepository for MockSessionRepository {
+    async fn create_session(
+        &self,
+        session: Session,
+    ) -> Result<Session, Box<dyn std::error::Error + Send + Sync>> {
+        self.sessions.lock().unwrap().push(session.clone());
+        Ok(session)
+    }
+
+    async fn find_by_id(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<Session>, Box<dyn std::error::Error + Send + Sync>> {
+        let sessions = self.sessions.lock().unwrap();
+        Ok(sessions.iter().find(|s| s.id == id).cloned())
+    }
+
+    async fn find_active_for_user(
+        &self,
+        user_id: i32,
+        created_after: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Session>, Box<dyn std::error::Error + Send + Sync>> {
+        let sessions = self.sessions.lock().unwrap();
+        let mut active: Vec<Session> = sessions
+            .iter()
+            .filter(|s| s.user_id == user_id && !s.revoked && s.created_at > created_after)
+            .cloned()
+            .collect();
+        active.sort_by_key(|s| std::cmp::Reverse(s.last_used_at));
+        Ok(active)
+    }
+
+    async fn touch(
+        &self,
+        id: Uuid,
+        last_used_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut sessions = self.sessions.lock().unwrap();
+        if let Some(session) = sessions.iter_mut().find(|s| s.id == id) {
+            session.last_used_at = session.last_used_at.max(last_used_at);
+        }
+        Ok(())
+    }
+
+    async fn revoke(
+        &self,
+        id: Uuid,
+        user_id: i32,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let mut sessions = self.sessions.lock().unwrap();
+        match sessions
+            .iter_mut()
+            .find(|s| s.id == id && s.user_id == user_id && !s.revoked)
+        {
+            Some(session) => {
+                session.revoked = true;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn revoke_all_except(
+        &self,
+        user_id: i32,
+        keep: Option<Uuid>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let mut revoked = 0;
+        for session in sessions
+            .iter_mut()
+            .filter(|s| s.user_id == user_id && !s.revoked && Some(s.id) != keep)
+        {
+            session.revoked = true;
+            revoked += 1;
+        }
+        Ok(revoked)
+    }
+}
+
+/// In-memory magic link token repository for testing
+#[derive(Clone, Default)]
+pub struct MockMagicLinkRepository {
+    tokens: Arc<Mutex<Vec<MagicLinkToken>>>,
+}
+
+impl MockMagicLinkRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl MagicLinkRepository for MockMagicLinkRepository {
+    async fn create_token(
+        &self,
+        mut token: MagicLinkToken,
+    ) -> Result<MagicLinkToken, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        token.id = (tokens.len() + 1) as i32;
+        tokens.push(token.clone());
+        Ok(token)
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<MagicLinkToken>, Box<dyn std::error::Error + Send + Sync>> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens.iter().find(|t| t.token_hash == token_hash).cloned())
+    }
+
+    async fn mark_used(&self, id: i32) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        match tokens.iter_mut().find(|t| t.id == id && !t.used) {
+            Some(token) => {
+                token.used = true;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn delete_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let initial_count = tokens.len();
+        tokens.retain(|t| t.expires_at >= expired_before);
+        Ok(initial_count - tokens.len())
+    }
+
+    async fn count_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens
+            .iter()
+            .filter(|t| t.expires_at < expired_before)
+            .count())
+    }
+}
+
+/// In-memory password reset token repository for testing
+#[derive(Clone, Default)]
+pub struct MockPasswordResetRepository {
+    tokens: Arc<Mutex<Vec<PasswordResetToken>>>,
+    outbox: MockEmailOutboxRepository,
+}
+
+impl MockPasswordResetRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emails queued together with tokens
+    pub fn outbox(&self) -> &MockEmailOutboxRepository {
+        &self.outbox
+    }
+}
+
+#[async_trait]
+impl PasswordResetRepository for MockPasswordResetRepository {
+    async fn create_token(
+        &self,
+        mut token: PasswordResetToken,
+        email: Option<&EmailMessage>,
+    ) -> Result<PasswordResetToken, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        for pending in tokens
+            .iter_mut()
+            .filter(|t| t.user_id == token.user_id && !t.is_used)
+        {
+            pending.mark_as_used();
+        }
+        token.id = (tokens.len() + 1) as i32;
+        tokens.push(token.clone());
+        if let Some(email) = email {
+            self.outbox.queue(email);
+        }
+        Ok(token)
+    }
+
+    async fn find_by_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<PasswordResetToken>, Box<dyn std::error::Error + Send + Sync>> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens.iter().find(|t| t.token == token).cloned())
+    }
+
+    async fn find_active_tokens_for_user(
+        &self,
+        user_id: i32,
+    ) -> Result<Vec<PasswordResetToken>, Box<dyn std::error::Error + Send + Sync>> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens
+            .iter()
+            .filter(|t| t.user_id == user_id && t.is_valid())
+            .cloned()
+            .collect())
+    }
+
+    async fn count_active_tokens_for_user(
+        &self,
+        user_id: i32,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.find_active_tokens_for_user(user_id).await?.len())
+    }
+
+    async fn update_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<PasswordResetToken, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let stored = tokens
+            .iter_mut()
+            .find(|t| t.id == token.id)
+            .ok_or("Token not found")?;
+        *stored = token.clone();
+        Ok(token)
+    }
+
+    async fn find_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<PasswordResetToken>, Box<dyn std::error::Error + Send + Sync>> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens
+            .iter()
+            .filter(|t| t.expires_at < expired_before)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let initial_count = tokens.len();
+        tokens.retain(|t| t.expires_at >= expired_before);
+        Ok(initial_count - tokens.len())
+    }
+
+    async fn count_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens
+            .iter()
+            .filter(|t| t.expires_at < expired_before)
+            .count())
+    }
+
+    async fn mark_token_used(
+        &self,
+        token_id: i32,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        match tokens.iter_mut().find(|t| t.id == token_id && !t.is_used) {
+            Some(token) => {
+                token.mark_as_used();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn invalidate_all_tokens_for_user(
+        &self,
+        user_id: i32,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let mut invalidated = 0;
+        for token in tokens
+            .iter_mut()
+            .filter(|t| t.user_id == user_id && !t.is_used)
+        {
+            token.mark_as_used();
+            invalidated += 1;
+        }
+        Ok(invalidated)
+    }
+}
+
+/// In-memory account deletion token repository for testing
+#[derive(Clone, Default)]
+pub struct MockAccountDeletionTokenRepository {
+    tokens: Arc<Mutex<Vec<AccountDeletionToken>>>,
+    outbox: MockEmailOutboxRepository,
+}
+
+impl MockAccountDeletionTokenRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emails queued together with tokens
+    pub fn outbox(&self) -> &MockEmailOutboxRepository {
+        &self.outbox
+    }
+}
+
+#[async_trait]
+impl AccountDeletionTokenRepository for MockAccountDeletionTokenRepository {
+    async fn create_token(
+        &self,
+        mut token: AccountDeletionToken,
+        email: Option<&EmailMessage>,
+    ) -> Result<AccountDeletionToken, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        token.id = (tokens.len() + 1) as i32;
+        tokens.push(token.clone());
+        if let Some(email) = email {
+            self.outbox.queue(email);
+        }
+        Ok(token)
+    }
+
+    async fn find_by_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<AccountDeletionToken>, Box<dyn std::error::Error + Send + Sync>> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens.iter().find(|t| t.token == token).cloned())
+    }
+
+    async fn find_active_token_for_user(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<AccountDeletionToken>, Box<dyn std::error::Error + Send + Sync>> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens
+            .iter()
+            .find(|t| t.user_id == user_id && t.is_valid())
+            .cloned())
+    }
+
+    async fn update_token(
+        &self,
+        token: AccountDeletionToken,
+    ) -> Result<AccountDeletionToken, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let stored = tokens
+            .iter_mut()
+            .find(|t| t.id == token.id)
+            .ok_or("Token not found")?;
+        *stored = token.clone();
+        Ok(token)
+    }
+
+    async fn delete_expired_tokens(
+        &self,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let before = tokens.len();
+        tokens.retain(|t| !t.is_expired());
+        Ok(before - tokens.len())
+    }
+
+    async fn cancel_all_tokens_for_user(
+        &self,
+        user_id: i32,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let mut cancelled = 0;
+        for token in tokens
+            .iter_mut()
+            .filter(|t| t.user_id == user_id && t.is_valid())
+        {
+            token.mark_as_cancelled();
+            cancelled += 1;
+        }
+        Ok(cancelled)
+    }
+}
+
+/// Mock organization repository for testing
+#[derive(Clone, Default)]
+pub struct MockOrganizationRepository {
+    organizations: Arc<Mutex<Vec<Organization>>>,
+    members: Arc<Mutex<Vec<OrganizationMember>>>,
+    url_counts: Arc<Mutex<HashMap<i32, i64>>>,
+}
+
+impl MockOrganizationRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of URLs `count_urls` reports for an organization
+    pub fn set_url_count(&self, org_id: i32, count: i64) {
+        self.url_counts.lock().unwrap().insert(org_id, count);
+    }
+}
+
+#[async_trait]
+impl OrganizationRepository for MockOrganizationRepository {
+    async fn create_organization(
+        &self,
+        name: &str,
+        slug: &str,
+        owner_id: i32,
+    ) -> Result<Organization, OrganizationRepositoryError> {
+        let mut organizations = self.organizations.lock().unwrap();
+        if organizations.iter().any(|o| o.slug == slug) {
+            return Err(OrganizationRepositoryError::DuplicateSlug);
+        }
+        let organization = Organization {
+            id: organizations.len() as i32 + 1,
+            name: name.to_string(),
+            slug: slug.to_string(),
+            owner_id,
+            created_at: chrono::Utc::now(),
+        };
+        organizations.push(organization.clone());
+        self.members.lock().unwrap().push(OrganizationMember {
+            org_id: organization.id,
+            user_id: owner_id,
+            role: OrgRole::Owner,
+            joined_at: chrono::Utc::now(),
+        });
+        Ok(organization)
+    }
+
+    async fn find_by_id(
+        &self,
+        id: i32,
+    ) -> Result<Option<Organization>, OrganizationRepositoryError> {
+        let organizations = self.organizations.lock().unwrap();
+        Ok(organizations.iter().find(|o| o.id == id).cloned())
+    }
+
+    async fn find_by_member(
+        &self,
+        user_id: i32,
+    ) -> Result<Vec<Organization>, OrganizationRepositoryError> {
+        let members = self.members.lock().unwrap();
+        let organizations = self.organizations.lock().unwrap();
+        Ok(organizations
+            .iter()
+            .filter(|o| {
+                members
+                    .iter()
+                    .any(|m| m.org_id == o.id && m.user_id == user_id)
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn update_organization(
+        &self,
+        id: i32,
+        name: &str,
+        slug: &str,
+    ) -> Result<Organization, OrganizationRepositoryError> {
+        let mut organizations = self.organizations.lock().unwrap();
+        let organization = organizations
+            .iter_mut()
+            .find(|o| o.id == id)
+            .ok_or(OrganizationRepositoryError::NotFound)?;
+        organization.name = name.to_string();
+        organization.slug = slug.to_string();
+        Ok(organization.clone())
+    }
+
+    async fn delete_organization(&self, id: i32) -> Result<bool, OrganizationRepositoryError> {
+        let mut organizations = self.organizations.lock().unwrap();
+        let before = organizations.len();
+        organizations.retain(|o| o.id != id);
+        self.members.lock().unwrap().retain(|m| m.org_id != id);
+        Ok(organizations.len() < before)
+    }
+
+    async fn find_member(
+        &self,
+        org_id: i32,
+        user_id: i32,
+    ) -> Result<Option<OrganizationMember>, OrganizationRepositoryError> {
+        let members = self.members.lock().unwrap();
+        Ok(members
+            .iter()
+            .find(|m| m.org_id == org_id && m.user_id == user_id)
+            .cloned())
+    }
+
+    async fn list_members(
+        &self,
+        org_id: i32,
+    ) -> Result<Vec<OrganizationMember>, OrganizationRepositoryError> {
+        let members = self.members.lock().unwrap();
+        Ok(members
+            .iter()
+            .filter(|m| m.org_id == org_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn add_member(
+        &self,
+        org_id: i32,
+        user_id: i32,
+        role: OrgRole,
+    ) -> Result<OrganizationMember, OrganizationRepositoryError> {
+        let mut members = self.members.lock().unwrap();
+        if members
+            .iter()
+            .any(|m| m.org_id == org_id && m.user_id == user_id)
+        {
+            return Err(OrganizationRepositoryError::AlreadyMember);
+        }
+        let member = OrganizationMember {
+            org_id,
+            user_id,
+            role,
+            joined_at: chrono::Utc::now(),
+        };
+        members.push(member.clone());
+        Ok(member)
+    }
+
+    async fn remove_member(
+        &self,
+        org_id: i32,
+        user_id: i32,
+    ) -> Result<bool, OrganizationRepositoryError> {
+        let mut members = self.members.lock().unwrap();
+        let before = members.len();
+        members.retain(|m| !(m.org_id == org_id && m.user_id == user_id));
+        Ok(members.len() < before)
+    }
+
+    async fn count_urls(&self, org_id: i32) -> Result<i64, OrganizationRepositoryError> {
+        Ok(*self.url_counts.lock().unwrap().get(&org_id).unwrap_or(&0))
+    }
+
+    async fn list_with_member_counts(
+        &self,
+    ) -> Result<Vec<OrganizationWithMemberCount>, OrganizationRepositoryError> {
+        let members = self.members.lock().unwrap();
+        let organizations = self.organizations.lock().unwrap();
+        Ok(organizations
+            .iter()
+            .map(|o| OrganizationWithMemberCount {
+                organization: o.clone(),
+                member_count: members.iter().filter(|m| m.org_id == o.id).count() as i64,
+            })
+            .collect())
+    }
+}
+
+/// In-memory domain blacklist repository for testing
+#[derive(Clone, Default)]
+pub struct MockDomainBlacklistRepository {
+    domains: Arc<Mutex<Vec<BlockedDomain>>>,
+}
+
+impl MockDomainBlacklistRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(
+        domains: &mut Vec<BlockedDomain>,
+        domain: &str,
+        reason: &str,
+        added_by: Option<i32>,
+    ) -> BlockedDomain {
+        let entry = BlockedDomain {
+            id: domains.iter().map(|d| d.id).max().unwrap_or(0) + 1,
+            domain: domain.to_string(),
+            reason: reason.to_string(),
+            added_by,
+            created_at: chrono::Utc::now(),
+        };
+        domains.push(entry.clone());
+        entry
+    }
+}
+
+#[async_trait]
+impl DomainBlacklistRepository for MockDomainBlacklistRepository {
+    async fn is_blocked(&self, domain: &str) -> Result<bool, DomainBlacklistRepositoryError> {
+        let domains = self.domains.lock().unwrap();
+        Ok(domains.iter().any(|d| d.matches(domain)))
+    }
+
+    async fn add_domain(
+        &self,
+        domain: &str,
+        reason: &str,
+        added_by: i32,
+    ) -> Result<BlockedDomain, DomainBlacklistRepositoryError> {
+        let mut domains = self.domains.lock().unwrap();
+        if domains.iter().any(|d| d.domain == domain) {
+            return Err(DomainBlacklistRepositoryError::DuplicateDomain);
+        }
+        Ok(Self::insert(&mut domains, domain, reason, Some(added_by)))
+    }
+
+    async fn import_domains(
+        &self,
+        entries: &[(String, String)],
+    ) -> Result<u64, DomainBlacklistRepositoryError> {
+        let mut domains = self.domains.lock().unwrap();
+        let mut added = 0;
+        for (domain, reason) in entries {
+            if !domains.iter().any(|d| &d.domain == domain) {
+                Self::insert(&mut domains, domain, reason, None);
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    async fn remove_domain(&self, id: i32) -> Result<bool, DomainBlacklistRepositoryError> {
+        let mut domains = self.domains.lock().unwrap();
+        let initial_count = domains.len();
+        domains.retain(|d| d.id != id);
+        Ok(domains.len() < initial_count)
+    }
+
+    async fn list_domains(&self) -> Result<Vec<BlockedDomain>, DomainBlacklistRepositoryError> {
+        let mut domains = self.domains.lock().unwrap().clone();
+        domains.sort_by(|a, b| a.domain.cmp(&b.domain));
+        Ok(domains)
+    }
+}
+
+/// In-memory service account repository for testing
+#[derive(Clone, Default)]
+pub struct MockServiceAccountRepository {
+    accounts: Arc<Mutex<Vec<ServiceAccount>>>,
+}
+
+impl MockServiceAccountRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ServiceAccountRepository for MockServiceAccountRepository {
+    async fn create_service_account(
+        &self,
+        name: &str,
+        api_key_hash: &str,
+        created_by: i32,
+    ) -> Result<ServiceAccount, ServiceAccountRepositoryError> {
+        let mut accounts = self.accounts.lock().unwrap();
+        if accounts.iter().any(|a| a.name == name) {
+            return Err(ServiceAccountRepositoryError::DuplicateName);
+        }
+
+        let account = ServiceAccount {
+            id: (accounts.len() + 1) as i32,
+            name: name.to_string(),
+            api_key_hash: api_key_hash.to_string(),
+            created_by: Some(created_by),
+            created_at: chrono::Utc::now(),
+        };
+        accounts.push(account.clone());
+        Ok(account)
+    }
+
+    async fn find_by_api_key_hash(
+        &self,
+        api_key_hash: &str,
+    ) -> Result<Option<ServiceAccount>, ServiceAccountRepositoryError> {
+        let accounts = self.accounts.lock().unwrap();
+        Ok(accounts
+            .iter()
+            .find(|a| a.api_key_hash == api_key_hash)
+            .cloned())
+    }
+}
+
+/// In-memory URL metadata repository for testing
+#[derive(Clone, Default)]
+pub struct MockUrlMetadataRepository {
+    metadata: Arc<Mutex<Vec<UrlMetadata>>>,
+}
+
+impl MockUrlMetadataRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl UrlMetadataRepository for MockUrlMetadataRepository {
+    async fn save_metadata(
+        &self,
+        metadata: &UrlMetadata,
+    ) -> Result<(), UrlMetadataRepositoryError> {
+        let mut stored = self.metadata.lock().unwrap();
+        stored.retain(|m| m.url_id != metadata.url_id);
+        stored.push(metadata.clone());
+        Ok(())
+    }
+
+    async fn find_by_url_id(
+        &self,
+        url_id: i32,
+    ) -> Result<Option<UrlMetadata>, UrlMetadataRepositoryError> {
+        let stored = self.metadata.lock().unwrap();
+        Ok(stored.iter().find(|m| m.url_id == url_id).cloned())
+    }
+}
+
+/// In-memory audit log for testing
+#[derive(Clone, Default)]
+pub struct MockAuditLogRepository {
+    pub entries: Arc<Mutex<Vec<AuditLogEntry>>>,
+}
+
+impl MockAuditLogRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for MockAuditLogRepository {
+    async fn record(&self, entry: &AuditLogEntry) -> Result<AuditLogEntry, RepositoryError> {
+        let mut entries = self.entries.lock().unwrap();
+        let stored = AuditLogEntry {
+            id: entries.len() as i64 + 1,
+            ..entry.clone()
+        };
+        entries.push(stored.clone());
+        Ok(stored)
+    }
+}
+
+/// In-memory archive of removed records for testing
+#[derive(Clone, Default)]
+pub struct MockArchiveRepository {
+    pub records: Arc<Mutex<Vec<ArchiveRecord>>>,
+}
+
+impl MockArchiveRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ArchiveRepository for MockArchiveRepository {
+    async fn archive(&self, record: &ArchiveRecord) -> Result<ArchiveRecord, RepositoryError> {
+        let mut records = self.records.lock().unwrap();
+        let stored = ArchiveRecord {
+            id: records.len() as i64 + 1,
+            ..record.clone()
+        };
+        records.push(stored.clone());
+        Ok(stored)
+    }
+
+    async fn find_records(
+        &self,
+        entity_type: Option<&str>,
+        before: Option<chrono::DateTime<chrono::Utc>>,
+        limit: i64,
+    ) -> Result<Vec<ArchiveRecord>, RepositoryError> {
+        let records = self.records.lock().unwrap();
+        let mut found: Vec<_> = records
+            .iter()
+            .filter(|r| entity_type.is_none_or(|t| r.entity_type == t))
+            .filter(|r| before.is_none_or(|b| r.deleted_at < b))
+            .cloned()
+            .collect();
+        found.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at).then(b.id.cmp(&a.id)));
+        found.truncate(limit.max(0) as usize);
+        Ok(found)
+    }
+
+    async fn count_archived_before(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        let records = self.records.lock().unwrap();
+        Ok(records.iter().filter(|r| r.deleted_at < cutoff).count() as u64)
+    }
+
+    async fn delete_archived_before(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        let mut records = self.records.lock().unwrap();
+        let before = records.len();
+        records.retain(|r| r.deleted_at >= cutoff);
+        Ok((before - records.len()) as u64)
+    }
+}
+
+/// In-memory analytics export job repository for testing
+#[derive(Clone, Default)]
+pub struct MockExportJobRepository {
+    pub jobs: Arc<Mutex<HashMap<String, ExportJob>>>,
+}
+
+impl MockExportJobRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ExportJobRepository for MockExportJobRepository {
+    async fn create(&self, job: &ExportJob) -> Result<(), RepositoryError> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .entry(job.id.clone())
+            .or_insert_with(|| job.clone());
+        Ok(())
+    }
+
+    async fn save(&self, job: &ExportJob) -> Result<(), RepositoryError> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .insert(job.id.clone(), job.clone());
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<ExportJob>, RepositoryError> {
+        Ok(self.jobs.lock().unwrap().get(id).cloned())
+    }
+
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<ExportJob>, RepositoryError> {
+        let mut jobs: Vec<ExportJob> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|job| job.user_id == user_id)
+            .cloned()
+            .collect();
+        jobs.sort_by_key(|job| std::cmp::Reverse(job.created_at));
+        Ok(jobs)
+    }
+
+    async fn find_expired(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<ExportJob>, RepositoryError> {
+        Ok(self
+            .jobs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|job| job.completed_at.unwrap_or(job.created_at) < cutoff)
+            .cloned()
+            .collect())
+    }
+
+    async fn count_expired(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepositoryError> {
+        Ok(self.find_expired(cutoff).await?.len() as u64)
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, RepositoryError> {
+        Ok(self.jobs.lock().unwrap().remove(id).is_some())
+    }
+}
+
+/// In-memory notification preferences repository for testing
+#[derive(Clone, Default)]
+pub struct MockNotificationPreferencesRepository {
+    emails: Arc<Mutex<Vec<(i32, String)>>>,
+    preferences: Arc<Mutex<Vec<NotificationPreferences>>>,
+    device_tokens: Arc<Mutex<HashMap<i32, Vec<String>>>>,
+    outbox: MockEmailOutboxRepository,
+}
+
+impl MockNotificationPreferencesRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Digests queued when they were claimed
+    pub fn outbox(&self) -> &MockEmailOutboxRepository {
+        &self.outbox
+    }
+
+    /// Register a user who can receive digests
+    pub fn add_user(&self, user_id: i32, email: &str) {
+        self.emails
+            .lock()
+            .unwrap()
+            .push((user_id, email.to_string()));
+    }
+
+    /// Register a device a user receives push notifications on
+    pub fn add_device_token(&self, user_id: i32, token: &str) {
+        self.device_tokens
+            .lock()
+            .unwrap()
+            .entry(user_id)
+            .or_default()
+            .push(token.to_string());
+    }
+
+    fn get(&self, user_id: i32) -> NotificationPreferences {
+        self.preferences
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|p| p.user_id == user_id)
+            .cloned()
+            .unwrap_or_else(|| NotificationPreferences::new(user_id))
+    }
+
+    fn put(&self, preferences: NotificationPreferences) {
+        let mut stored = self.preferences.lock().unwrap();
+        stored.retain(|p| p.user_id != preferences.user_id);
+        stored.push(preferences);
+    }
+}
+
+#[async_trait]
+impl NotificationPreferencesRepository for MockNotificationPreferencesRepository {
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Result<NotificationPreferences, NotificationPreferencesRepositoryError> {
+        Ok(self.get(user_id))
+    }
+
+    async fn save(
+        &self,
+        preferences: &NotificationPreferences,
+    ) -> Result<NotificationPreferences, NotificationPreferencesRepositoryError> {
+        let saved = NotificationPreferences {
+            last_digest_sent_at: self.get(preferences.user_id).last_digest_sent_at,
+            ..preferences.clone()
+        };
+        self.put(saved.clone());
+        Ok(saved)
+    }
+
+    async fn find_digest_recipients(
+        &self,
+        user_ids: &[i32],
+    ) -> Result<Vec<DigestRecipient>, NotificationPreferencesRepositoryError> {
+        let emails = self.emails.lock().unwrap().clone();
+        Ok(emails
+            .into_iter()
+            .filter(|(user_id, _)| user_ids.contains(user_id))
+            .map(|(user_id, email)| DigestRecipient {
+                username: email.split('@').next().unwrap_or_default().to_string(),
+                email,
+                preferences: self.get(user_id),
+            })
+            .collect())
+    }
+
+    async fn find_device_tokens(
+        &self,
+        user_id: i32,
+    ) -> Result<Vec<String>, NotificationPreferencesRepositoryError> {
+        Ok(self
+            .device_tokens
+            .lock()
+            .unwrap()
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn claim_digest(
+        &self,
+        user_id: i32,
+        sent_at: chrono::DateTime<chrono::Utc>,
+        sent_after: chrono::DateTime<chrono::Utc>,
+        digest: &EmailMessage,
+    ) -> Result<bool, NotificationPreferencesRepositoryError> {
+        let mut preferences = self.get(user_id);
+        if preferences
+            .last_digest_sent_at
+            .is_some_and(|last| last > sent_after)
+        {
+            return Ok(false);
+        }
+        preferences.last_digest_sent_at = Some(sent_at);
+        self.put(preferences);
+        self.outbox.queue(digest);
+        Ok(true)
+    }
+}
+
+/// In-memory click repository for testing; conversion goals are not supported
+#[derive(Clone, Default)]
+pub struct MockClickRepository {
+    clicks: Arc<Mutex<Vec<Click>>>,
+    deduplicated_click_counts: Arc<Mutex<HashMap<i32, i64>>>,
+    url_configs: Arc<Mutex<HashMap<i32, UrlConfig>>>,
+    /// Owner of each URL, for the queries by user
+    url_owners: Arc<Mutex<HashMap<i32, i32>>>,
+}
+
+impl MockClickRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make clicks on `url_id` count as clicks on `user_id`'s URLs
+    pub fn set_url_owner(&self, url_id: i32, user_id: i32) {
+        self.url_owners.lock().unwrap().insert(url_id, user_id);
+    }
+
+    fn clicks_of_url(&self, url_id: i32, include_bots: bool) -> Vec<Click> {
+        let clicks = self.clicks.lock().unwrap();
+        clicks
+            .iter()
+            .filter(|c| c.url_id == url_id)
+            .filter(|c| include_bots || c.device_type() != DeviceType::Bot)
+            .cloned()
+            .collect()
+    }
+
+    fn stats(clicks: &[Click]) -> ClickStats {
+        let unique_ips: std::collections::HashSet<_> = clicks
+            .iter()
+            .filter_map(|c| c.ip_address.as_deref())
+            .collect();
+        ClickStats {
+            total_clicks: clicks.len() as i64,
+            unique_ips: unique_ips.len() as i64,
+            clicks_today: clicks.len() as i64,
+            clicks_this_week: clicks.len() as i64,
+            clicks_this_month: clicks.len() as i64,
+            top_countries: vec![],
+            top_referers: vec![],
+        }
+    }
+}
+
+#[async_trait]
+impl ClickRepository for MockClickRepository {
+    async fn record_click(&self, click: &Click) -> Result<Click, ClickRepositoryError> {
+        let mut clicks = self.clicks.lock().unwrap();
+        let mut new_click = click.clone();
+        new_click.id = (clicks.len() + 1) as i32;
+        clicks.push(new_click.clone());
+        Ok(new_click)
+    }
+
+    async fn record_clicks(&self, batch: &[Click]) -> Result<u64, ClickRepositoryError> {
+        for click in batch {
+            self.record_click(click).await?;
+        }
+        Ok(batch.len() as u64)
+    }
+
+    async fn increment_deduplicated_click_counts(
+        &self,
+        counts: &[(i32, i64)],
+    ) -> Result<Vec<ClickCountTotal>, ClickRepositoryError> {
+        let mut totals = self.deduplicated_click_counts.lock().unwrap();
+        Ok(counts
+            .iter()
+            .map(|(url_id, clicks)| {
+                let total = totals.entry(*url_id).or_default();
+                *total += clicks;
+                // URLs are not stored in this mock, so they have no owner or short code
+                ClickCountTotal {
+                    url_id: *url_id,
+                    user_id: None,
+                    short_code: String::new(),
+                    total: *total,
+                }
+            })
+            .collect())
+    }
+
+    async fn get_click_count(&self, url_id: i32) -> Result<i64, ClickRepositoryError> {
+        Ok(self.clicks_of_url(url_id, true).len() as i64)
+    }
+
+    async fn get_clicks_for_url(
+        &self,
+        url_id: i32,
+        start_date: Option<chrono::DateTime<chrono::Utc>>,
+        end_date: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<Click>, ClickRepositoryError> {
+        Ok(self
+            .clicks_of_url(url_id, true)
+            .into_iter()
+            .filter(|c| start_date.is_none_or(|start| c.clicked_at >= start))
+            .filter(|c| end_date.is_none_or(|end| c.clicked_at <= end))
+            .collect())
+    }
+
+    async fn get_clicks_for_user(
+        &self,
+        _user_id: i32,
+        _start_date: Option<chrono::DateTime<chrono::Utc>>,
+        _end_date: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<Click>, ClickRepositoryError> {
+        Ok(vec![])
+    }
+
+    async fn get_clicks_for_user_page(
+        &self,
+        user_id: i32,
+        start_date: Option<chrono::DateTime<chrono::Utc>>,
+        end_date: Option<chrono::DateTime<chrono::Utc>>,
+        after_id: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<Click>, ClickRepositoryError> {
+        let url_owners = self.url_owners.lock().unwrap();
+        let mut clicks: Vec<Click> = self
+            .clicks
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| url_owners.get(&c.url_id) == Some(&user_id))
+            .filter(|c| start_date.is_none_or(|start| c.clicked_at >= start))
+            .filter(|c| end_date.is_none_or(|end| c.clicked_at <= end))
+            .filter(|c| after_id.is_none_or(|after_id| c.id > after_id))
+            .cloned()
+            .collect();
+        clicks.sort_by_key(|c| c.id);
+        clicks.truncate(limit.max(0) as usize);
+        Ok(clicks)
+    }
+
+    async fn get_url_click_stats(&self, url_id: i32) -> Result<ClickStats, ClickRepositoryError> {
+        Ok(Self::stats(&self.clicks_of_url(url_id, true)))
+    }
+
+    async fn get_user_click_stats(
+        &self,
+        _user_id: i32,
+    ) -> Result<ClickStats, ClickRepositoryError> {
+        Ok(Self::stats(&[]))
+    }
+
+    async fn get_unique_visitors_estimate(
+        &self,
+        url_id: i32,
+        start: chrono::NaiveDate,
+        end: chrono::NaiveDate,
+    ) -> Result<i64, ClickRepositoryError> {
+        let clicks = self.clicks_of_url(url_id, true);
+        let visitors: std::collections::HashSet<_> = clicks
+            .iter()
+            .filter(|c| (start..=end).contains(&c.clicked_at.date_naive()))
+            .filter_map(|c| c.ip_address.as_deref())
+            .collect();
+        Ok(visitors.len() as i64)
+    }
+
+    async fn get_url_analytics_summary(
+        &self,
+        url_id: i32,
+        include_bots: bool,
+    ) -> Result<UrlAnalyticsSummary, ClickRepositoryError> {
+        let clicks = self.clicks_of_url(url_id, include_bots);
+        let stats = Self::stats(&clicks);
+
+        let mut device_breakdown = DeviceBreakdown::default();
+        for click in &clicks {
+            match click.device_type() {
+                DeviceType::Desktop => device_breakdown.desktop += 1,
+                DeviceType::Mobile => device_breakdown.mobile += 1,
+                DeviceType::Tablet => device_breakdown.tablet += 1,
+                DeviceType::Bot => device_breakdown.bot += 1,
+            }
+        }
+
+        Ok(UrlAnalyticsSummary {
+            total_clicks: stats.total_clicks,
+            unique_visitors: stats.unique_ips,
+            clicks_today: stats.clicks_today,
+            clicks_this_week: stats.clicks_this_week,
+            clicks_this_month: stats.clicks_this_month,
+            top_countries: vec![],
+            top_referrers: vec![],
+            device_breakdown,
+            conversion_rate: 0.0,
+            recent_clicks: clicks.into_iter().rev().take(10).collect(),
+        })
+    }
+
+    async fn create_conversion_goal(
+        &self,
+        _url_id: i32,
+        _goal_url_pattern: &str,
+        _name: &str,
+    ) -> Result<ConversionGoal, ClickRepositoryError> {
+        Err(ClickRepositoryError::Database(
+            "conversion goals are not supported by the mock".to_string(),
+        ))
+    }
+
+    async fn find_conversion_goal(
+        &self,
+        _id: i32,
+    ) -> Result<Option<ConversionGoal>, ClickRepositoryError> {
+        Ok(None)
+    }
+
+    async fn delete_conversion_goal(&self, _id: i32) -> Result<bool, ClickRepositoryError> {
+        Ok(false)
+    }
+
+    async fn record_conversion(
+        &self,
+        _click_token: &str,
+        _goal_id: i32,
+    ) -> Result<Option<ConversionEvent>, ClickRepositoryError> {
+        Ok(None)
+    }
+
+    async fn get_conversion_rate(&self, _url_id: i32) -> Result<f64, ClickRepositoryError> {
+        Ok(0.0)
+    }
+
+    async fn get_click_dedup_ratio(
+        &self,
+        url_id: i32,
+    ) -> Result<ClickDedupRatio, ClickRepositoryError> {
+        let clicks = self.clicks_of_url(url_id, true);
+        let bot_clicks = clicks
+            .iter()
+            .filter(|c| c.device_type() == DeviceType::Bot)
+            .count() as i64;
+        let raw_clicks = self
+            .deduplicated_click_counts
+            .lock()
+            .unwrap()
+            .get(&url_id)
+            .copied()
+            .unwrap_or_default();
+        Ok(ClickDedupRatio {
+            raw_clicks,
+            unique_clicks: clicks.len() as i64 - bot_clicks,
+            bot_clicks,
+        })
+    }
+
+    async fn find_url_config(
+        &self,
+        url_id: i32,
+    ) -> Result<Option<UrlConfig>, ClickRepositoryError> {
+        Ok(self.url_configs.lock().unwrap().get(&url_id).cloned())
+    }
+
+    async fn save_url_config(&self, config: &UrlConfig) -> Result<UrlConfig, ClickRepositoryError> {
+        let mut saved = config.clone();
+        saved.updated_at = chrono::Utc::now();
+        self.url_configs
+            .lock()
+            .unwrap()
+            .insert(config.url_id, saved.clone());
+        Ok(saved)
+    }
+
+    async fn get_hourly_distribution(
+        &self,
+        url_id: i32,
+    ) -> Result<[u32; 24], ClickRepositoryError> {
+        let mut distribution = [0u32; 24];
+        for click in self.clicks_of_url(url_id, true) {
+            distribution[click.clicked_at.hour() as usize] += 1;
+        }
+        Ok(distribution)
+    }
+
+    async fn get_daily_distribution(&self, url_id: i32) -> Result<[u32; 7], ClickRepositoryError> {
+        let mut distribution = [0u32; 7];
+        for click in self.clicks_of_url(url_id, true) {
+            distribution[click.clicked_at.weekday().num_days_from_sunday() as usize] += 1;
+        }
+        Ok(distribution)
+    }
+
+    async fn get_peak_hour(&self, url_id: i32) -> Result<u8, ClickRepositoryError> {
+        let distribution = self.get_hourly_distribution(url_id).await?;
+        let peak = (0..24).fold(0, |peak, hour| {
+            if distribution[hour] > distribution[peak] {
+                hour
+            } else {
+                peak
+            }
+        });
+        Ok(peak as u8)
+    }
+
+    async fn get_utm_attribution_breakdown(
+        &self,
+        url_id: i32,
+    ) -> Result<Vec<UtmAttributionRow>, ClickRepositoryError> {
+        let mut counts: HashMap<(Option<String>, Option<String>, Option<String>), i64> =
+            HashMap::new();
+        for click in self.clicks_of_url(url_id, true) {
+            let utm = click.utm();
+            if !utm.is_empty() {
+                *counts
+                    .entry((utm.source, utm.medium, utm.campaign))
+                    .or_default() += 1;
+            }
+        }
+        let mut rows: Vec<UtmAttributionRow> = counts
+            .into_iter()
+            .map(|((source, medium, campaign), count)| UtmAttributionRow {
+                source,
+                medium,
+                campaign,
+                count,
+            })
+            .collect();
+        rows.sort_by_key(|row| std::cmp::Reverse(row.count));
+        Ok(rows)
+    }
+
+    async fn get_click_velocity(
+        &self,
+        url_id: i32,
+        window_minutes: u32,
+    ) -> Result<f64, ClickRepositoryError> {
+        if window_minutes == 0 {
+            return Ok(0.0);
+        }
+        let since = chrono::Utc::now() - chrono::Duration::minutes(window_minutes as i64);
+        let clicks = self
+            .clicks_of_url(url_id, true)
+            .iter()
+            .filter(|c| c.clicked_at >= since)
+            .count();
+        Ok(clicks as f64 / window_minutes as f64)
+    }
+
+    async fn delete_old_clicks(
+        &self,
+        older_than: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, ClickRepositoryError> {
+        let mut clicks = self.clicks.lock().unwrap();
+        let before = clicks.len();
+        clicks.retain(|c| c.clicked_at >= older_than);
+        Ok((before - clicks.len()) as u64)
+    }
+
+    async fn count_old_clicks(
+        &self,
+        older_than: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, ClickRepositoryError> {
+        let clicks = self.clicks.lock().unwrap();
+        Ok(clicks.iter().filter(|c| c.clicked_at < older_than).count() as u64)
+    }
+}
+
+/// Email sender recording messages instead of sending them
+#[derive(Clone, Default)]
+pub struct MockEmailSender {
+    sent: Arc<Mutex<Vec<EmailMessage>>>,
+    failing: Arc<Mutex<bool>>,
+}
+
+impl MockEmailSender {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make every send fail until reset
+    pub fn set_failing(&self, failing: bool) {
+        *self.failing.lock().unwrap() = failing;
+    }
+
+    /// Messages sent so far
+    pub fn sent(&self) -> Vec<EmailMessage> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl EmailSender for MockEmailSender {
+    async fn send_email(&self, message: EmailMessage) -> Result<(), EmailError> {
+        if *self.failing.lock().unwrap() {
+            return Err(EmailError::SendingFailed("mock failure".to_string()));
+        }
+        self.sent.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+/// Email outbox kept in memory
+#[derive(Clone, Default)]
+pub struct MockEmailOutboxRepository {
+    emails: Arc<Mutex<Vec<OutboxEmail>>>,
+}
+
+impl MockEmailOutboxRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emails in the outbox, in the order they were queued
+    pub fn emails(&self) -> Vec<OutboxEmail> {
+        self.emails.lock().unwrap().clone()
+    }
+
+    /// Queue an email, as repositories do in the transaction of their change
+    pub fn queue(&self, message: &EmailMessage) -> OutboxEmail {
+        let mut emails = self.emails.lock().unwrap();
+        let now = chrono::Utc::now();
+        let email = OutboxEmail {
+            id: emails.len() as i64 + 1,
+            recipient: message.to.clone(),
+            subject: message.subject.clone(),
+            body_html: message.html_body.clone(),
+            body_text: message.body.clone(),
+            created_at: now,
+            next_attempt_at: now,
+            sent_at: None,
+            failed_at: None,
+            attempt_count: 0,
+            error: None,
+        };
+        emails.push(email.clone());
+        email
+    }
+
+    fn update(&self, id: i64, change: impl FnOnce(&mut OutboxEmail)) {
+        if let Some(email) = self.emails.lock().unwrap().iter_mut().find(|e| e.id == id) {
+            change(email);
+        }
+    }
+}
+
+#[async_trait]
+impl EmailOutboxRepository for MockEmailOutboxRepository {
+    async fn enqueue(
+        &self,
+        message: &EmailMessage,
+    ) -> Result<OutboxEmail, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.queue(message))
+    }
+
+    async fn claim_due(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        lease: chrono::Duration,
+        limit: usize,
+    ) -> Result<Vec<OutboxEmail>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut emails = self.emails.lock().unwrap();
+        Ok(emails
+            .iter_mut()
+            .filter(|email| email.is_pending() && email.next_attempt_at <= now)
+            .take(limit)
+            .map(|email| {
+                email.next_attempt_at = now + lease;
+                email.clone()
+            })
+            .collect())
+    }
+
+    async fn mark_sent(
+        &self,
+        id: i64,
+        sent_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.update(id, |email| {
+            email.sent_at = Some(sent_at);
+            email.attempt_count += 1;
+            email.error = None;
+        });
+        Ok(())
+    }
+
+    async fn mark_attempt_failed(
+        &self,
+        id: i64,
+        error: &str,
+        failed_at: chrono::DateTime<chrono::Utc>,
+        retry_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.update(id, |email| {
+            email.attempt_count += 1;
+            email.error = Some(error.to_string());
+            match retry_at {
+                Some(retry_at) => email.next_attempt_at = retry_at,
+                None => email.failed_at = Some(failed_at),
+            }
+        });
+        Ok(())
+    }
+
+    async fn delete_sent_before(
+        &self,
+        sent_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let mut emails = self.emails.lock().unwrap();
+        let before = emails.len();
+        emails.retain(|email| email.sent_at.is_none_or(|sent_at| sent_at >= sent_before));
+        Ok(before - emails.len())
+    }
+
+    async fn count_sent_before(
+        &self,
+        sent_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let emails = self.emails.lock().unwrap();
+        Ok(emails
+            .iter()
+            .filter(|email| email.sent_at.is_some_and(|sent_at| sent_at < sent_before))
+            .count())
+    }
+}
+
+/// Push sender recording the notifications it is asked to send
+#[derive(Clone, Default)]
+pub struct MockPushSender {
+    sent: Arc<Mutex<Vec<PushMessage>>>,
+    unregistered: Arc<Mutex<bool>>,
+}
+
+impl MockPushSender {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report every device as unregistered until reset
+    pub fn set_unregistered(&self, unregistered: bool) {
+        *self.unregistered.lock().unwrap() = unregistered;
+    }
+
+    /// Notifications sent so far
+    pub fn sent(&self) -> Vec<PushMessage> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl PushNotificationSender for MockPushSender {
+    async fn send_push(&self, message: PushMessage) -> Result<(), PushError> {
+        if *self.unregistered.lock().unwrap() {
+            return Err(PushError::Unregistered);
+        }
+        self.sent.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+/// Push outbox kept in memory
+#[derive(Clone, Default)]
+pub struct MockPushOutboxRepository {
+    pushes: Arc<Mutex<Vec<OutboxPush>>>,
+}
+
+impl MockPushOutboxRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pushes in the outbox, in the order they were queued
+    pub fn pushes(&self) -> Vec<OutboxPush> {
+        self.pushes.lock().unwrap().clone()
+    }
+
+    fn update(&self, id: i64, change: impl FnOnce(&mut OutboxPush)) {
+        if let Some(push) = self.pushes.lock().unwrap().iter_mut().find(|p| p.id == id) {
+            change(push);
+        }
+    }
+}
+
+#[async_trait]
+impl PushOutboxRepository for MockPushOutboxRepository {
+    async fn enqueue(
+        &self,
+        message: &PushMessage,
+    ) -> Result<Option<OutboxPush>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut pushes = self.pushes.lock().unwrap();
+        if message.dedup_key.is_some()
+            && pushes.iter().any(|push| {
+                push.device_token == message.device_token && push.dedup_key == message.dedup_key
+            })
+        {
+            return Ok(None);
+        }
+        let now = chrono::Utc::now();
+        let push = OutboxPush {
+            id: pushes.len() as i64 + 1,
+            user_id: message.user_id,
+            device_token: message.device_token.clone(),
+            title: message.title.clone(),
+            body: message.body.clone(),
+            deep_link: message.deep_link.clone(),
+            dedup_key: message.dedup_key.clone(),
+            created_at: now,
+            next_attempt_at: now,
+            sent_at: None,
+            failed_at: None,
+            attempt_count: 0,
+            error: None,
+        };
+        pushes.push(push.clone());
+        Ok(Some(push))
+    }
+
+    async fn claim_due(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        lease: chrono::Duration,
+        limit: usize,
+    ) -> Result<Vec<OutboxPush>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut pushes = self.pushes.lock().unwrap();
+        Ok(pushes
+            .iter_mut()
+            .filter(|push| push.is_pending() && push.next_attempt_at <= now)
+            .take(limit)
+            .map(|push| {
+                push.next_attempt_at = now + lease;
+                push.clone()
+            })
+            .collect())
+    }
+
+    async fn mark_sent(
+        &self,
+        id: i64,
+        sent_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.update(id, |push| {
+            push.sent_at = Some(sent_at);
+            push.attempt_count += 1;
+            push.error = None;
+        });
+        Ok(())
+    }
+
+    async fn mark_attempt_failed(
+        &self,
+        id: i64,
+        error: &str,
+        failed_at: chrono::DateTime<chrono::Utc>,
+        retry_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.update(id, |push| {
+            push.attempt_count += 1;
+            push.error = Some(error.to_string());
+            match retry_at {
+                Some(retry_at) => push.next_attempt_at = retry_at,
+                None => push.failed_at = Some(failed_at),
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Click deduplicator keeping marks in memory, like the Redis one does with expiring keys
+#[derive(Clone, Default)]
+pub struct MockClickDeduplicator {
+    marks: Arc<Mutex<HashMap<(String, i32), std::time::Instant>>>,
+    failing: Arc<Mutex<bool>>,
+}
+
+impl MockClickDeduplicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make every check fail until reset
+    pub fn set_failing(&self, failing: bool) {
+        *self.failing.lock().unwrap() = failing;
+    }
+}
+
+#[async_trait]
+impl ClickDeduplicator for MockClickDeduplicator {
+    async fn first_click(
+        &self,
+        ip_address: &str,
+        url_id: i32,
+        window: std::time::Duration,
+    ) -> Result<bool, ClickDeduplicationError> {
+        if *self.failing.lock().unwrap() {
+            return Err(
+                redis::RedisError::from((redis::ErrorKind::IoError, "mock failure")).into(),
+            );
+        }
+        let now = std::time::Instant::now();
+        let mut marks = self.marks.lock().unwrap();
+        let key = (ip_address.to_string(), url_id);
+        if marks.get(&key).is_some_and(|expires_at| *expires_at > now) {
+            return Ok(false);
+        }
+        marks.insert(key, now + window);
+        Ok(true)
+    }
+}
+
+/// Analytics cache keeping entries in memory, like the Redis one does with expiring keys
+#[derive(Clone, Default)]
+pub struct MockAnalyticsCache {
+    pub entries: Arc<Mutex<HashMap<String, (String, std::time::Instant)>>>,
+}
+
+impl MockAnalyticsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AnalyticsCache for MockAnalyticsCache {
+    async fn get(&self, key: &str) -> Result<Option<String>, AnalyticsCacheError> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries
+            .get(key)
+            .filter(|(_, expires_at)| *expires_at > std::time::Instant::now())
+            .map(|(value, _)| value.clone()))
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: std::time::Duration,
+    ) -> Result<(), AnalyticsCacheError> {
+        let expires_at = std::time::Instant::now() + ttl;
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), (value.to_string(), expires_at));
+        Ok(())
+    }
+
+    async fn invalidate_url(&self, url_id: i32) -> Result<(), AnalyticsCacheError> {
+        let prefix = format!("{}{}:", KEY_PREFIX, url_id);
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|key, _| !key.starts_with(&prefix));
+        Ok(())
+    }
+}
+
+/// Sliding window store keeping request logs in memory, like the Redis store does
+#[derive(Clone, Default)]
+pub struct MockSlidingWindowStore {
+    logs: Arc<Mutex<HashMap<String, Vec<i64>>>>,
+    failing: Arc<Mutex<bool>>,
+}
+
+impl MockSlidingWindowStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make every request fail until reset
+    pub fn set_failing(&self, failing: bool) {
+        *self.failing.lock().unwrap() = failing;
+    }
+}
+
+#[async_trait]
+impl SlidingWindowStore for MockSlidingWindowStore {
+    async fn record(
+        &self,
+        key: &str,
+        now_ms: i64,
+        window_ms: i64,
+        limit: u32,
+    ) -> Result<SlidingWindowEntry, RateLimitStoreError> {
+        if *self.failing.lock().unwrap() {
+            return Err(
+                redis::RedisError::from((redis::ErrorKind::IoError, "mock failure")).into(),
+            );
+        }
+        let mut logs = self.logs.lock().unwrap();
+        let log = logs.entry(key.to_string()).or_default();
+        log.retain(|time| *time > now_ms - window_ms);
+        let allowed = log.len() < limit as usize;
+        if allowed {
+            log.push(now_ms);
+        }
+        Ok(SlidingWindowEntry {
+            allowed,
+            oldest_ms: log.iter().min().copied(),
+        })
+    }
+}
+
+/// Reputation score cache keeping scores in memory, like the Redis cache does
+#[derive(Clone, Default)]
+pub struct MockIpReputationCache {
+    pub scores: Arc<Mutex<HashMap<String, (u8, std::time::Instant)>>>,
+}
+
+impl MockIpReputationCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl IpReputationCache for MockIpReputationCache {
+    async fn get(&self, ip: &str) -> Result<Option<u8>, IpReputationCacheError> {
+        let scores = self.scores.lock().unwrap();
+        Ok(scores
+            .get(ip)
+            .filter(|(_, expires_at)| *expires_at > std::time::Instant::now())
+            .map(|(score, _)| *score))
+    }
+
+    async fn set(
+        &self,
+        ip: &str,
+        score: u8,
+        ttl: std::time::Duration,
+    ) -> Result<(), IpReputationCacheError> {
+        let expires_at = std::time::Instant::now() + ttl;
+        self.scores
+            .lock()
+            .unwrap()
+            .insert(ip.to_string(), (score, expires_at));
+        Ok(())
+    }
+}
+
+/// In-memory email change repository for testing
+#[derive(Clone, Default)]
+pub struct MockEmailChangeRepository {
+    tokens: Arc<Mutex<Vec<EmailChangeToken>>>,
+}
+
+impl MockEmailChangeRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl EmailChangeRepository for MockEmailChangeRepository {
+    async fn create_token(
+        &self,
+        mut token: EmailChangeToken,
+    ) -> Result<EmailChangeToken, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        tokens.retain(|t| t.user_id != token.user_id);
+        token.id = tokens.iter().map(|t| t.id).max().unwrap_or(0) + 1;
+        tokens.push(token.clone());
+        Ok(token)
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        side: EmailChangeSide,
+        token_hash: &str,
+    ) -> Result<Option<EmailChangeToken>, Box<dyn std::error::Error + Send + Sync>> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens
+            .iter()
+            .find(|t| match side {
+                EmailChangeSide::OldAddress => t.old_token_hash == token_hash,
+                EmailChangeSide::NewAddress => t.new_token_hash == token_hash,
+            })
+            .cloned())
+    }
+
+    async fn confirm(
+        &self,
+        id: i32,
+        side: EmailChangeSide,
+    ) -> Result<Option<EmailChangeToken>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let Some(token) = tokens.iter_mut().find(|t| t.id == id) else {
+            return Ok(None);
+        };
+        let confirmed_at = match side {
+            EmailChangeSide::OldAddress => &mut token.old_confirmed_at,
+            EmailChangeSide::NewAddress => &mut token.new_confirmed_at,
+        };
+        confirmed_at.get_or_insert_with(chrono::Utc::now);
+        Ok(Some(token.clone()))
+    }
+
+    async fn delete_token(&self, id: i32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.tokens.lock().unwrap().retain(|t| t.id != id);
+        Ok(())
+    }
+
+    async fn delete_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let initial_count = tokens.len();
+        tokens.retain(|t| t.expires_at >= expired_before);
+        Ok(initial_count - tokens.len())
+    }
+
+    async fn count_expired_tokens(
+        &self,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens
+            .iter()
+            .filter(|t| t.expires_at < expired_before)
+            .count())
+    }
+}
+
+/// In-memory per-user URL counters for testing
+#[derive(Clone, Default)]
+pub struct MockUserUrlStatsRepository {
+    pub stats: Arc<Mutex<HashMap<i32, UserUrlStats>>>,
+}
+
+impl MockUserUrlStatsRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl UserUrlStatsRepository for MockUserUrlStatsRepository {
+    async fn find_by_user_id(&self, user_id: i32) -> Result<Option<UserUrlStats>, RepositoryError> {
+        Ok(self.stats.lock().unwrap().get(&user_id).cloned())
+    }
+
+    async fn adjust_url_counts(
+        &self,
+        user_id: i32,
+        change: UrlCountChange,
+    ) -> Result<(), RepositoryError> {
+        let mut stats = self.stats.lock().unwrap();
+        let user_stats = stats
+            .entry(user_id)
+            .or_insert_with(|| UserUrlStats::empty(user_id));
+        user_stats.total_urls += change.total_urls;
+        user_stats.active_urls += change.active_urls;
+        user_stats.inactive_urls += change.inactive_urls;
+        user_stats.archived_urls += change.archived_urls;
+        user_stats.last_updated_at = chrono::Utc::now();
+        Ok(())
+    }
+
+    /// No visitors are recorded, so the estimate is always 0
+    async fn estimate_unique_visitors(&self, _user_id: i32) -> Result<i64, RepositoryError> {
+        Ok(0)
+    }
+
+    async fn increment_clicks(&self, clicks: &[(i32, i64)]) -> Result<(), RepositoryError> {
+        let mut stats = self.stats.lock().unwrap();
+        for (user_id, added) in clicks {
+            let user_stats = stats
+                .entry(*user_id)
+                .or_insert_with(|| UserUrlStats::empty(*user_id));
+            user_stats.total_clicks += added;
+            user_stats.last_updated_at = chrono::Utc::now();
+        }
+        Ok(())
+    }
+
+    /// Nothing to recompute from; returns the number of users with counters
+    async fn rebuild_all(&self) -> Result<u64, RepositoryError> {
+        Ok(self.stats.lock().unwrap().len() as u64)
+    }
+}
+
+/// Base URL of the short links created through a `TestApp`
+pub const TEST_APP_BASE_URL: &str = "http://localhost:8000";
+
+/// Secret a `TestApp` signs its tokens with
+pub const TEST_JWT_SECRET: &str = "test-secret";
+
+/// Application state over mock repositories, assembled with `AppStateBuilder`
+///
+/// `router` serves the routes of the real server, so tests exercise the real handlers,
+/// services and repository routing without a database. The URL repository sits behind a
+/// `ReplicaAwarePool` without a replica, as in a server without `database.replica_url`.
+/// Must be created inside a Tokio runtime, which the click tracking service runs on.
+pub struct TestApp {
+    pub state: ConcreteAppState,
+    pub url_repository: MockUrlRepository,
+    pub user_repository: MockUserRepository,
+    pub click_repository: MockClickRepository,
+    /// Holds exported files until the app is dropped
+    _export_dir: tempfile::TempDir,
+}
+
+impl TestApp {
+    pub fn new() -> Self {
+        Self::with_email_sender(None)
+    }
+
+    /// A `TestApp` that sends its e-mails through `email_sender`
+    pub fn with_email_sender(email_sender: Option<Arc<dyn EmailSender>>) -> Self {
+        let url_repository = MockUrlRepository::new();
+        let user_repository = MockUserRepository::new();
+        let click_repository = MockClickRepository::new();
+        let export_dir = tempfile::tempdir().expect("temporary export directory");
+
+        let urls: Arc<dyn UrlRepository> =
+            Arc::new(ReplicaAwarePool::new(url_repository.clone(), None));
+        let users: Arc<dyn UserRepository> = Arc::new(user_repository.clone());
+        let clicks: Arc<dyn ClickRepository> = Arc::new(click_repository.clone());
+        let password_resets: Arc<dyn PasswordResetRepository> =
+            Arc::new(MockPasswordResetRepository::new());
+        let account_deletions: Arc<dyn AccountDeletionTokenRepository> =
+            Arc::new(MockAccountDeletionTokenRepository::new());
+        let organizations: Arc<dyn OrganizationRepository> =
+            Arc::new(MockOrganizationRepository::new());
+        let magic_links: Arc<dyn MagicLinkRepository> = Arc::new(MockMagicLinkRepository::new());
+        let email_changes: Arc<dyn EmailChangeRepository> =
+            Arc::new(MockEmailChangeRepository::new());
+
+        let url_service = UrlService::new(urls.clone())
+            .with_user_repository(users.clone())
+            .with_max_urls_per_organization(OrgLimits::default().max_urls_per_organization);
+        let shorten_url_use_case =
+            ShortenUrlUseCase::new(url_service.clone(), TEST_APP_BASE_URL.to_string());
+        let update_url_use_case = UpdateUrlUseCase::new(shorten_url_use_case.clone());
+        let list_urls_use_case = ListUrlsUseCase::new(
+            url_service.clone(),
+            urls.clone(),
+            TEST_APP_BASE_URL.to_string(),
+        );
+        let storage = |dir: &str| {
+            let root = export_dir.path().join(dir);
+            let base_url = format!("file://{}", root.display());
+            Arc::new(LocalObjectStorage::new(root, base_url))
+        };
+        // Never connects: readiness checks report the database as unreachable
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(100))
+            .connect_lazy("postgres://localhost/url_shortener_test")
+            .expect("lazy pool");
+
+        let state = AppStateBuilder::new()
+            .shorten_url_use_case(shorten_url_use_case)
+            .update_url_use_case(update_url_use_case)
+            .list_urls_use_case(list_urls_use_case)
+            .get_url_analytics_use_case(GetUrlAnalyticsUseCase::new(urls.clone(), clicks.clone()))
+            .url_repository(urls.clone())
+            .url_service(url_service)
+            .auth_service(AuthService::new(users.clone(), TEST_JWT_SECRET.to_string()))
+            .user_repository(users.clone())
+            .password_reset_repository(password_resets)
+            .account_deletion_repository(account_deletions)
+            .click_tracking_service(ClickTrackingService::new(clicks.clone()))
+            .database_health(DatabaseHealthCheck::new(pool))
+            .org_service(OrgService::new(organizations.clone()))
+            .organization_repository(organizations)
+            .magic_link_repository(magic_links)
+            .domain_blacklist(DomainBlacklist::new(Arc::new(
+                MockDomainBlacklistRepository::new(),
+            )))
+            .data_export_service(DataExportService::new(export_dir.path().join("data")))
+            .analytics_export_service(AnalyticsExportService::new(
+                clicks,
+                Arc::new(MockExportJobRepository::new()),
+                storage("analytics"),
+            ))
+            .service_account_service(ServiceAccountService::new(Arc::new(
+                MockServiceAccountRepository::new(),
+            )))
+            .link_preview_service(LinkPreviewService::new(Arc::new(
+                MockUrlMetadataRepository::new(),
+            )))
+            .cleanup_service(CleanupService::new(urls, RetentionConfig::default()))
+            .oauth_service(OAuthService::new(users, TEST_APP_BASE_URL.to_string()))
+            .interstitial_service(InterstitialService::new(TEST_JWT_SECRET))
+            .object_storage(storage("uploads"))
+            .email_change_repository(email_changes)
+            .email_sender(email_sender)
+            .build()
+            .expect("every required dependency is set");
+
+        Self {
+            state,
+            url_repository,
+            user_repository,
+            click_repository,
+            _export_dir: export_dir,
+        }
+    }
+
+    /// The API routes of the server over this app's state
+    ///
+    /// Requests get the trace context of the server; rate limiting, CORS and body limits
+    /// are left out.
+    pub fn router(&self) -> Router {
+        let idempotency = IdempotencyLayer::new(
+            Arc::new(InMemoryIdempotencyStore::new()),
+            Arc::new(self.state.auth_service.clone()),
+        );
+        api_routes(&self.state, idempotency)
+            .with_state(self.state.clone())
+            .layer(TraceIdExtractor::new())
+    }
+
+    /// Register `username` with an example e-mail address and log in
+    ///
+    /// Returns the user with a Bearer token for requests to `router`.
+    pub async fn sign_up(&self, username: &str) -> (User, String) {
+        let auth_service = &self.state.auth_service;
+        let user = auth_service
+            .register(
+                username,
+                &format!("{}@example.com", username),
+                "password123",
+            )
+            .await
+            .expect("registration succeeds");
+        let token = auth_service
+            .login(username, "password123", &SessionClient::default())
+            .await
+            .expect("login succeeds");
+        (user, token)
+    }
+}
+
+impl Default for TestApp {
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -0,0 +1,263 @@
+use axum_server::tls_rustls::RustlsConfig;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Protocols offered through ALPN, most preferred first
+fn alpn_protocols(enable_http2: bool) -> Vec<Vec<u8>> {
+    if enable_http2 {
+        vec![b"h2".to_vec(), b"http/1.1".to_vec()]
+    } else {
+        vec![b"http/1.1".to_vec()]
+    }
+}
+
+/// Read a PEM certificate and key into a rustls config offering the given protocols
+async fn load_rustls_config(
+    cert_path: &Path,
+    key_path: &Path,
+    enable_http2: bool,
+) -> io::Result<RustlsConfig> {
+    // Only ring is compiled in; an already installed provider is fine too
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    let config = RustlsConfig::from_pem_file(cert_path, key_path).await?;
+    let mut server_config = (*config.get_inner()).clone();
+    server_config.alpn_protocols = alpn_protocols(enable_http2);
+    config.reload_from_config(Arc::new(server_config));
+    Ok(config)
+}
+
+/// Certificate of the HTTPS listener, reloadable without restarting the server
+///
+/// Reloading swaps the certificate for new connections only; established connections keep
+/// the one they were opened with.
+#[derive(Clone)]
+pub struct TlsCertificate {
+    config: RustlsConfig,
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    enable_http2: bool,
+}
+
+impl TlsCertificate {
+    pub async fn load(
+        cert_path: PathBuf,
+        key_path: PathBuf,
+        enable_http2: bool,
+    ) -> io::Result<Self> {
+        let config = load_rustls_config(&cert_path, &key_path, enable_http2).await?;
+        Ok(Self {
+            config,
+            cert_path,
+            key_path,
+            enable_http2,
+        })
+    }
+
+    /// Config to bind the listener with; it follows later reloads
+    pub fn rustls_config(&self) -> RustlsConfig {
+        self.config.clone()
+    }
+
+    /// Read the certificate and key files again, e.g. after certbot renewed them
+    ///
+    /// On error the current certificate stays in use.
+    pub async fn reload(&self) -> io::Result<()> {
+        let fresh = load_rustls_config(&self.cert_path, &self.key_path, self.enable_http2).await?;
+        self.config.reload_from_config(fresh.get_inner());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::dto::{requests::ShortenUrlRequest, responses::ShortenUrlResponse};
+    use crate::application::ShortenUrlUseCase;
+    use crate::domain::entities::ShortCode;
+    use crate::domain::services::UrlService;
+    use crate::infrastructure::test_utils::MockUrlRepository;
+    use axum::{
+        extract::{Path as UrlPath, State},
+        response::Redirect,
+        routing::{get, post},
+        Json, Router,
+    };
+    use axum_server::Handle;
+    use reqwest::{redirect::Policy, StatusCode, Version};
+    use std::net::SocketAddr;
+    use tempfile::TempDir;
+
+    type TestState = (
+        ShortenUrlUseCase<MockUrlRepository>,
+        UrlService<MockUrlRepository>,
+    );
+
+    /// Write a fresh self-signed certificate for localhost, returning its PEM
+    fn write_certificate(dir: &TempDir) -> String {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let cert_pem = certified.cert.pem();
+        std::fs::write(dir.path().join("cert.pem"), &cert_pem).unwrap();
+        std::fs::write(
+            dir.path().join("key.pem"),
+            certified.key_pair.serialize_pem(),
+        )
+        .unwrap();
+        cert_pem
+    }
+
+    /// Serve a shorten and redirect API over TLS on a free port
+    async fn serve(certificate: &TlsCertificate) -> SocketAddr {
+        let url_service = UrlService::new(MockUrlRepository::new());
+        let use_case = ShortenUrlUseCase::new(url_service.clone(), "https://localhost".to_string());
+        let app = Router::new()
+            .route(
+                "/shorten",
+                post(
+                    |State((use_case, _)): State<TestState>,
+                     Json(request): Json<ShortenUrlRequest>| async move {
+                        Json(use_case.execute(request, None).await.unwrap())
+                    },
+                ),
+            )
+            .route(
+                "/:short_code",
+                get(
+                    |State((_, url_service)): State<TestState>,
+                     UrlPath(short_code): UrlPath<String>| async move {
+                        let short_code = ShortCode::new(short_code).unwrap();
+                        let url = url_service
+                            .get_url_by_short_code(&short_code)
+                            .await
+                            .unwrap()
+                            .unwrap();
+                        Redirect::permanent(&url.original_url)
+                    },
+                ),
+            )
+            .with_state((use_case, url_service));
+
+        let handle = Handle::new();
+        let server =
+            axum_server::bind_rustls("127.0.0.1:0".parse().unwrap(), certificate.rustls_config())
+                .handle(handle.clone());
+        tokio::spawn(server.serve(app.into_make_service()));
+        handle.listening().await.unwrap()
+    }
+
+    fn client(cert_pem: &str, addr: SocketAddr) -> reqwest::Client {
+        reqwest::Client::builder()
+            .add_root_certificate(reqwest::Certificate::from_pem(cert_pem.as_bytes()).unwrap())
+            .resolve("localhost", addr)
+            .redirect(Policy::none())
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_http2_client_shortens_and_is_redirected() {
+        let dir = TempDir::new().unwrap();
+        let cert_pem = write_certificate(&dir);
+        let certificate = TlsCertificate::load(
+            dir.path().join("cert.pem"),
+            dir.path().join("key.pem"),
+            true,
+        )
+        .await
+        .unwrap();
+        let addr = serve(&certificate).await;
+        let client = client(&cert_pem, addr);
+        let base = format!("https://localhost:{}", addr.port());
+
+        let response = client
+            .post(format!("{}/shorten", base))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(r#"{"url": "https://example.com/http2"}"#)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.version(), Version::HTTP_2);
+        assert_eq!(response.status(), StatusCode::OK);
+        let shortened: ShortenUrlResponse =
+            serde_json::from_slice(&response.bytes().await.unwrap()).unwrap();
+
+        let response = client
+            .get(format!("{}/{}", base, shortened.short_code))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.version(), Version::HTTP_2);
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            response.headers()[reqwest::header::LOCATION],
+            "https://example.com/http2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_http1_only_when_http2_disabled() {
+        let dir = TempDir::new().unwrap();
+        let cert_pem = write_certificate(&dir);
+        let certificate = TlsCertificate::load(
+            dir.path().join("cert.pem"),
+            dir.path().join("key.pem"),
+            false,
+        )
+        .await
+        .unwrap();
+        let addr = serve(&certificate).await;
+
+        let response = client(&cert_pem, addr)
+            .post(format!("https://localhost:{}/shorten", addr.port()))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(r#"{"url": "https://example.com/http1"}"#)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.version(), Version::HTTP_11);
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_reload_serves_renewed_certificate() {
+        let dir = TempDir::new().unwrap();
+        let old_pem = write_certificate(&dir);
+        let certificate = TlsCertificate::load(
+            dir.path().join("cert.pem"),
+            dir.path().join("key.pem"),
+            true,
+        )
+        .await
+        .unwrap();
+        let addr = serve(&certificate).await;
+        let url = format!("https://localhost:{}/unknown", addr.port());
+
+        let new_pem = write_certificate(&dir);
+        assert!(client(&new_pem, addr).post(&url).send().await.is_err());
+
+        certificate.reload().await.unwrap();
+        assert!(client(&new_pem, addr).post(&url).send().await.is_ok());
+        assert!(client(&old_pem, addr).post(&url).send().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reload_keeps_certificate_when_files_are_invalid() {
+        let dir = TempDir::new().unwrap();
+        let cert_pem = write_certificate(&dir);
+        let certificate = TlsCertificate::load(
+            dir.path().join("cert.pem"),
+            dir.path().join("key.pem"),
+            true,
+        )
+        .await
+        .unwrap();
+        let addr = serve(&certificate).await;
+
+        std::fs::write(dir.path().join("key.pem"), "not a key").unwrap();
+        assert!(certificate.reload().await.is_err());
+
+        let url = format!("https://localhost:{}/unknown", addr.port());
+        assert!(client(&cert_pem, addr).post(&url).send().await.is_ok());
+    }
+}
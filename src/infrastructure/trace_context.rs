@@ -0,0 +1,338 @@
+use axum::{body::Body, http::HeaderMap, http::Request};
+use opentelemetry::trace::{
+    SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, TracerProvider,
+};
+use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+use std::fmt;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::{field, info_span, instrument::Instrumented, Event, Instrument, Subscriber};
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt, OtelData};
+use tracing_subscriber::fmt::format::{Format, Full, Writer};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+
+/// W3C trace context header: `{version}-{trace id}-{parent span id}-{flags}`
+const TRACEPARENT_HEADER: &str = "traceparent";
+const B3_TRACE_ID_HEADER: &str = "x-b3-traceid";
+const B3_SPAN_ID_HEADER: &str = "x-b3-spanid";
+const B3_SAMPLED_HEADER: &str = "x-b3-sampled";
+
+/// Layer giving every `tracing` span an OpenTelemetry trace and span ID
+///
+/// IDs are generated by an SDK tracer; spans are not exported anywhere yet.
+pub fn opentelemetry_layer<S>() -> OpenTelemetryLayer<S, SdkTracer>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let tracer = SdkTracerProvider::builder()
+        .build()
+        .tracer(env!("CARGO_PKG_NAME"));
+    tracing_opentelemetry::layer().with_tracer(tracer)
+}
+
+/// Trace context a request carries, from `traceparent` or else the B3 headers
+///
+/// B3 trace IDs may be 64 or 128 bits. A B3 trace ID without `X-B3-SpanId` gets a random
+/// parent span ID, as OpenTelemetry ignores parents without one.
+pub fn extract_span_context(headers: &HeaderMap) -> Option<SpanContext> {
+    match header_str(headers, TRACEPARENT_HEADER) {
+        Some(traceparent) => parse_traceparent(traceparent),
+        None => parse_b3(headers),
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name)?.to_str().ok().map(str::trim)
+}
+
+fn parse_traceparent(value: &str) -> Option<SpanContext> {
+    let mut parts = value.split('-');
+    let version = parts.next().filter(|version| is_hex(version, 2))?;
+    let trace_id = parts.next().filter(|id| is_hex(id, 32))?;
+    let span_id = parts.next().filter(|id| is_hex(id, 16))?;
+    let flags = parts.next().filter(|flags| is_hex(flags, 2))?;
+    // Later versions may append fields, but version 00 has exactly four
+    if version == "ff" || (version == "00" && parts.next().is_some()) {
+        return None;
+    }
+    let flags = u8::from_str_radix(flags, 16).ok()?;
+    remote_span_context(
+        TraceId::from_hex(trace_id).ok()?,
+        SpanId::from_hex(span_id).ok()?,
+        TraceFlags::new(flags) & TraceFlags::SAMPLED,
+    )
+}
+
+fn parse_b3(headers: &HeaderMap) -> Option<SpanContext> {
+    let trace_id =
+        header_str(headers, B3_TRACE_ID_HEADER).filter(|id| is_hex(id, 16) || is_hex(id, 32))?;
+    let span_id = match header_str(headers, B3_SPAN_ID_HEADER) {
+        Some(span_id) => SpanId::from_hex(span_id)
+            .ok()
+            .filter(|_| is_hex(span_id, 16))?,
+        None => SpanId::from_bytes(rand::random()),
+    };
+    let flags = match header_str(headers, B3_SAMPLED_HEADER) {
+        Some("0") | Some("false") => TraceFlags::NOT_SAMPLED,
+        _ => TraceFlags::SAMPLED,
+    };
+    remote_span_context(TraceId::from_hex(trace_id).ok()?, span_id, flags)
+}
+
+/// Span context of a remote parent, unless its IDs are the all-zero invalid ones
+fn remote_span_context(
+    trace_id: TraceId,
+    span_id: SpanId,
+    flags: TraceFlags,
+) -> Option<SpanContext> {
+    let span_context = SpanContext::new(trace_id, span_id, flags, true, TraceState::NONE);
+    span_context.is_valid().then_some(span_context)
+}
+
+fn is_hex(value: &str, len: usize) -> bool {
+    value.len() == len && value.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+/// Middleware running each request in a root `request` span carrying its trace ID
+///
+/// The span continues the trace of the `traceparent` or `X-B3-TraceId` header when there
+/// is one; otherwise it starts a new trace. Spans opened while handling the request,
+/// including those of `#[tracing::instrument]` functions, belong to the same trace.
+#[derive(Debug, Clone, Default)]
+pub struct TraceIdExtractor;
+
+impl TraceIdExtractor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for TraceIdExtractor {
+    type Service = TraceIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TraceIdService { inner }
+    }
+}
+
+/// Service applying [`TraceIdExtractor`] to the requests of `S`
+#[derive(Debug, Clone)]
+pub struct TraceIdService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for TraceIdService<S>
+where
+    S: Service<Request<Body>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Instrumented<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let span = info_span!(
+            "request",
+            trace_id = field::Empty,
+            method = %request.method(),
+            path = %request.uri().path(),
+        );
+        let trace_id = match extract_span_context(request.headers()) {
+            Some(parent) => {
+                // Fails only without an OpenTelemetry layer; the trace ID is still logged
+                let _ = span.set_parent(
+                    opentelemetry::Context::new().with_remote_span_context(parent.clone()),
+                );
+                parent.trace_id()
+            }
+            None => span.context().span().span_context().trace_id(),
+        };
+        if trace_id != TraceId::INVALID {
+            span.record("trace_id", field::display(trace_id));
+        }
+        self.inner.call(request).instrument(span)
+    }
+}
+
+/// Event format prefixing each log line with the trace ID of the span it was logged in
+///
+/// Lines logged outside any traced span are left as they are.
+#[derive(Debug, Clone)]
+pub struct TraceIdFormat<F = Format<Full>> {
+    inner: F,
+}
+
+impl<F> TraceIdFormat<F> {
+    pub fn new(inner: F) -> Self {
+        Self { inner }
+    }
+}
+
+impl Default for TraceIdFormat {
+    fn default() -> Self {
+        Self::new(Format::default())
+    }
+}
+
+impl<S, N, F> FormatEvent<S, N> for TraceIdFormat<F>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    N: for<'writer> FormatFields<'writer> + 'static,
+    F: FormatEvent<S, N>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let trace_id = ctx.event_scope().and_then(|scope| {
+            scope.into_iter().find_map(|span| {
+                span.extensions()
+                    .get::<OtelData>()
+                    .and_then(OtelData::trace_id)
+                    .filter(|trace_id| *trace_id != TraceId::INVALID)
+            })
+        });
+        if let Some(trace_id) = trace_id {
+            write!(writer, "trace_id={} ", trace_id)?;
+        }
+        self.inner.format_event(ctx, writer, event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_extract_traceparent() {
+        let span_context = extract_span_context(&headers(&[(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        )]))
+        .unwrap();
+        assert_eq!(span_context.trace_id().to_string(), TRACE_ID);
+        assert_eq!(span_context.span_id().to_string(), "00f067aa0ba902b7");
+        assert!(span_context.is_sampled());
+        assert!(span_context.is_remote());
+
+        for invalid in [
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "not a traceparent",
+        ] {
+            assert!(
+                extract_span_context(&headers(&[("traceparent", invalid)])).is_none(),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_extract_b3() {
+        let span_context = extract_span_context(&headers(&[
+            ("x-b3-traceid", TRACE_ID),
+            ("x-b3-spanid", "00f067aa0ba902b7"),
+            ("x-b3-sampled", "0"),
+        ]))
+        .unwrap();
+        assert_eq!(span_context.trace_id().to_string(), TRACE_ID);
+        assert_eq!(span_context.span_id().to_string(), "00f067aa0ba902b7");
+        assert!(!span_context.is_sampled());
+
+        // 64-bit trace IDs are left-padded; a missing span ID is made up
+        let span_context =
+            extract_span_context(&headers(&[("x-b3-traceid", "a3ce929d0e0e4736")])).unwrap();
+        assert_eq!(
+            span_context.trace_id().to_string(),
+            "0000000000000000a3ce929d0e0e4736"
+        );
+        assert!(span_context.is_valid());
+
+        // traceparent wins over B3
+        let span_context = extract_span_context(&headers(&[
+            ("x-b3-traceid", "a3ce929d0e0e4736"),
+            (
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            ),
+        ]))
+        .unwrap();
+        assert_eq!(span_context.trace_id().to_string(), TRACE_ID);
+
+        assert!(extract_span_context(&headers(&[("x-b3-traceid", "xyz")])).is_none());
+        assert!(extract_span_context(&HeaderMap::new()).is_none());
+    }
+
+    /// Writer collecting log lines in memory
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_log_lines_carry_the_trace_id() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry()
+            .with(opentelemetry_layer())
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .event_format(TraceIdFormat::default())
+                    .with_writer(move || writer.clone())
+                    .with_ansi(false),
+            );
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("outside any span");
+            let parent = extract_span_context(&headers(&[("x-b3-traceid", TRACE_ID)])).unwrap();
+            let span = info_span!("request");
+            span.set_parent(opentelemetry::Context::new().with_remote_span_context(parent))
+                .unwrap();
+            span.in_scope(|| {
+                tracing::info!("in the request span");
+                info_span!("child").in_scope(|| tracing::info!("in a child span"));
+            });
+        });
+
+        let logs = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = logs.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(!lines[0].contains("trace_id="));
+        let prefix = format!("trace_id={} ", TRACE_ID);
+        assert!(lines[1].starts_with(&prefix), "{}", lines[1]);
+        assert!(lines[2].starts_with(&prefix), "{}", lines[2]);
+    }
+}
@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt;
+use tracing::warn;
+
+/// Prefix of the Redis channels user invalidations are published on, followed by the user ID
+pub const USER_INVALIDATED_CHANNEL_PREFIX: &str = "user-invalidated:";
+
+/// Errors of the user invalidation broadcast
+#[derive(Error, Debug)]
+pub enum UserInvalidationError {
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+}
+
+/// Tells every server instance to forget what it cached about a user
+#[async_trait]
+pub trait UserInvalidationBroadcaster: Send + Sync {
+    async fn broadcast(&self, user_id: i32) -> Result<(), UserInvalidationError>;
+}
+
+/// Channel a user's invalidations are published on
+fn channel(user_id: i32) -> String {
+    format!("{}{}", USER_INVALIDATED_CHANNEL_PREFIX, user_id)
+}
+
+/// User invalidations sent over Redis pub/sub
+pub struct RedisUserInvalidation {
+    client: redis::Client,
+    connection: ConnectionManager,
+}
+
+impl RedisUserInvalidation {
+    pub async fn connect(url: &str) -> Result<Self, UserInvalidationError> {
+        let client = redis::Client::open(url)?;
+        let connection = ConnectionManager::new(client.clone()).await?;
+        Ok(Self { client, connection })
+    }
+
+    /// Call `on_invalidated` with the ID of every user invalidated by any instance
+    ///
+    /// Listens on a dedicated connection in a background task; invalidations published
+    /// while that connection is down are missed.
+    pub async fn subscribe<F>(
+        &self,
+        on_invalidated: F,
+    ) -> Result<JoinHandle<()>, UserInvalidationError>
+    where
+        F: Fn(i32) + Send + 'static,
+    {
+        let mut pubsub = self.client.get_async_pubsub().await?;
+        pubsub
+            .psubscribe(format!("{}*", USER_INVALIDATED_CHANNEL_PREFIX))
+            .await?;
+
+        Ok(tokio::spawn(async move {
+            let mut messages = pubsub.into_on_message();
+            while let Some(message) = messages.next().await {
+                let user_id = message
+                    .get_channel_name()
+                    .strip_prefix(USER_INVALIDATED_CHANNEL_PREFIX)
+                    .and_then(|user_id| user_id.parse().ok());
+                match user_id {
+                    Some(user_id) => on_invalidated(user_id),
+                    None => warn!(
+                        "Ignoring malformed user invalidation on {}",
+                        message.get_channel_name()
+                    ),
+                }
+            }
+            warn!("User invalidation subscription closed");
+        }))
+    }
+}
+
+#[async_trait]
+impl UserInvalidationBroadcaster for RedisUserInvalidation {
+    async fn broadcast(&self, user_id: i32) -> Result<(), UserInvalidationError> {
+        let mut connection = self.connection.clone();
+        redis::cmd("PUBLISH")
+            .arg(channel(user_id))
+            .arg(user_id)
+            .query_async::<_, i64>(&mut connection)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    /// Needs a Redis server: `REDIS_URL=redis://localhost:6379 cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_invalidations_reach_subscribers() {
+        let url = std::env::var("REDIS_URL").unwrap_or("redis://localhost:6379".to_string());
+        let publisher = RedisUserInvalidation::connect(&url).await.unwrap();
+        let subscriber = RedisUserInvalidation::connect(&url).await.unwrap();
+        let (sender, mut received) = mpsc::unbounded_channel();
+        subscriber
+            .subscribe(move |user_id| {
+                let _ = sender.send(user_id);
+            })
+            .await
+            .unwrap();
+
+        publisher.broadcast(4242).await.unwrap();
+        let user_id = tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await
+            .unwrap();
+        assert_eq!(user_id, Some(4242));
+    }
+}
@@ -0,0 +1,439 @@
+//! GraphQL API over the domain services
+//!
+//! Resolvers call the same services as the REST handlers, so both APIs enforce the same
+//! rules. Errors carry a `code` extension matching the REST error codes.
+
+pub mod mutation;
+pub mod query;
+pub mod types;
+
+pub use mutation::MutationRoot;
+pub use query::QueryRoot;
+
+use crate::application::ShortenUrlUseCase;
+use crate::domain::entities::User;
+use crate::domain::repositories::{
+    ClickRepository, OrganizationRepository, UrlRepository, UserRepository,
+};
+use crate::domain::services::click_tracking_service::ClickTrackingService;
+use crate::domain::services::{AuthService, AuthServiceError, OrgService, UrlService};
+use async_graphql::{Context, EmptySubscription, ErrorExtensions, Schema};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+/// Deepest query the schema accepts
+const MAX_QUERY_DEPTH: usize = 10;
+
+/// GraphQL schema over the given repositories
+pub type UrlShortenerSchema<R, U, C> =
+    Schema<QueryRoot<R, U, C>, MutationRoot<R, U, C>, EmptySubscription>;
+
+/// Services the resolvers delegate to
+#[derive(Clone)]
+pub struct GraphQLServices<R, U, C>
+where
+    R: UrlRepository + Clone + Send + Sync + 'static,
+    U: UserRepository + Clone + Send + Sync + 'static,
+    C: ClickRepository + Clone + Send + Sync + 'static,
+{
+    pub shorten_url_use_case: ShortenUrlUseCase<R>,
+    pub url_service: UrlService<R>,
+    pub auth_service: AuthService<U>,
+    pub click_tracking_service: ClickTrackingService<C>,
+    pub org_service: OrgService<Arc<dyn OrganizationRepository>>,
+}
+
+impl<R, U, C> GraphQLServices<R, U, C>
+where
+    R: UrlRepository + Clone + Send + Sync + 'static,
+    U: UserRepository + Clone + Send + Sync + 'static,
+    C: ClickRepository + Clone + Send + Sync + 'static,
+{
+    /// Build the schema served at `/graphql`
+    pub fn into_schema(self) -> UrlShortenerSchema<R, U, C> {
+        Schema::build(
+            QueryRoot::new(self.clone()),
+            MutationRoot::new(self),
+            EmptySubscription,
+        )
+        .limit_depth(MAX_QUERY_DEPTH)
+        .finish()
+    }
+
+    /// The user whose bearer token came with the request
+    ///
+    /// The token is verified on first use and the user reused by later resolvers.
+    pub async fn current_user<'a>(&self, ctx: &'a Context<'_>) -> async_graphql::Result<&'a User> {
+        let auth = ctx.data_opt::<GraphQLAuth>();
+        let Some((token, user)) = auth.and_then(|auth| Some((auth.token.as_deref()?, &auth.user)))
+        else {
+            return Err(graphql_error(
+                "UNAUTHORIZED",
+                "Missing or invalid Authorization header",
+            ));
+        };
+
+        user.get_or_try_init(|| async {
+            self.auth_service
+                .verify_token(token)
+                .await
+                .map_err(|e| token_error(&e))
+        })
+        .await
+    }
+}
+
+/// Bearer token of a GraphQL request, attached as request data
+#[derive(Debug, Default)]
+pub struct GraphQLAuth {
+    token: Option<String>,
+    user: OnceCell<User>,
+}
+
+impl GraphQLAuth {
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            token,
+            user: OnceCell::new(),
+        }
+    }
+}
+
+/// GraphQL error with a machine-readable `code` extension
+pub fn graphql_error(code: &'static str, message: impl Into<String>) -> async_graphql::Error {
+    async_graphql::Error::new(message).extend_with(|_, extensions| extensions.set("code", code))
+}
+
+/// Map a failed token verification like `token_error_response` does for REST
+fn token_error(error: &AuthServiceError) -> async_graphql::Error {
+    match error {
+        AuthServiceError::AccountSuspended { reason, .. } => graphql_error(
+            "ACCOUNT_SUSPENDED",
+            format!("Account suspended: {}", reason),
+        ),
+        _ => graphql_error("INVALID_TOKEN", "Invalid or expired token"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::SessionClient;
+    use crate::infrastructure::test_utils::{
+        MockClickRepository, MockOrganizationRepository, MockUrlRepository, MockUserRepository,
+    };
+    use async_graphql::{Request, Response, Variables};
+    use serde_json::{json, Value};
+
+    type TestSchema =
+        UrlShortenerSchema<MockUrlRepository, MockUserRepository, MockClickRepository>;
+
+    struct TestApi {
+        schema: TestSchema,
+        services: GraphQLServices<MockUrlRepository, MockUserRepository, MockClickRepository>,
+    }
+
+    impl TestApi {
+        fn new() -> Self {
+            let url_service = UrlService::new(MockUrlRepository::new());
+            let services = GraphQLServices {
+                shorten_url_use_case: ShortenUrlUseCase::new(
+                    url_service.clone(),
+                    "https://sho.rt".to_string(),
+                ),
+                url_service,
+                auth_service: AuthService::new(MockUserRepository::new(), "secret".to_string()),
+                click_tracking_service: ClickTrackingService::new(MockClickRepository::new()),
+                org_service: OrgService::new(Arc::new(MockOrganizationRepository::new())),
+            };
+            Self {
+                schema: services.clone().into_schema(),
+                services,
+            }
+        }
+
+        /// Register a user and return a session token for them
+        async fn token_for(&self, username: &str) -> String {
+            self.services
+                .auth_service
+                .register(
+                    username,
+                    &format!("{}@example.com", username),
+                    "password123",
+                )
+                .await
+                .unwrap();
+            self.services
+                .auth_service
+                .login(username, "password123", &SessionClient::default())
+                .await
+                .unwrap()
+        }
+
+        async fn execute(&self, token: Option<&str>, query: &str, variables: Value) -> Response {
+            let request = Request::new(query)
+                .variables(Variables::from_json(variables))
+                .data(GraphQLAuth::new(token.map(str::to_string)));
+            self.schema.execute(request).await
+        }
+    }
+
+    fn error_code(response: &Response) -> Option<String> {
+        let extensions = response.errors.first()?.extensions.as_ref()?;
+        match extensions.get("code")? {
+            async_graphql::Value::String(code) => Some(code.clone()),
+            _ => None,
+        }
+    }
+
+    fn data(response: Response) -> Value {
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()
+    }
+
+    const SHORTEN: &str = r#"
+        mutation Shorten($url: String!, $code: String) {
+            shortenUrl(input: { url: $url, customShortCode: $code }) {
+                id shortCode shortUrl originalUrl status version
+            }
+        }
+    "#;
+
+    #[tokio::test]
+    async fn test_me_requires_a_valid_token() {
+        let api = TestApi::new();
+
+        let response = api.execute(None, "{ me { username } }", json!({})).await;
+        assert_eq!(error_code(&response).as_deref(), Some("UNAUTHORIZED"));
+
+        let response = api
+            .execute(Some("not-a-token"), "{ me { username } }", json!({}))
+            .await;
+        assert_eq!(error_code(&response).as_deref(), Some("INVALID_TOKEN"));
+
+        let token = api.token_for("alice").await;
+        let response = api
+            .execute(Some(&token), "{ me { username email } }", json!({}))
+            .await;
+        assert_eq!(
+            data(response)["me"],
+            json!({ "username": "alice", "email": "alice@example.com" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shorten_then_list_and_look_up() {
+        let api = TestApi::new();
+        let token = api.token_for("alice").await;
+
+        let shortened = data(
+            api.execute(
+                Some(&token),
+                SHORTEN,
+                json!({ "url": "https://example.com/graphql", "code": "gql123" }),
+            )
+            .await,
+        );
+        assert_eq!(shortened["shortenUrl"]["shortUrl"], "https://sho.rt/gql123");
+        assert_eq!(shortened["shortenUrl"]["status"], "ACTIVE");
+
+        let response = api
+            .execute(
+                Some(&token),
+                r#"{
+                    url(shortCode: "gql123") { originalUrl }
+                    urls(pagination: { limit: 5 }) { items { shortCode } nextCursor prevCursor }
+                }"#,
+                json!({}),
+            )
+            .await;
+        let found = data(response);
+        assert_eq!(found["url"]["originalUrl"], "https://example.com/graphql");
+        assert_eq!(found["urls"]["items"], json!([{ "shortCode": "gql123" }]));
+        assert_eq!(found["urls"]["prevCursor"], Value::Null);
+
+        // Other users cannot see the URL
+        let other = api.token_for("bob").await;
+        let response = api
+            .execute(
+                Some(&other),
+                r#"{ url(shortCode: "gql123") { id } }"#,
+                json!({}),
+            )
+            .await;
+        assert_eq!(data(response)["url"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_shorten_rejects_invalid_url() {
+        let api = TestApi::new();
+        let token = api.token_for("alice").await;
+
+        let response = api
+            .execute(Some(&token), SHORTEN, json!({ "url": "ftp://example.com" }))
+            .await;
+        assert_eq!(error_code(&response).as_deref(), Some("SHORTEN_FAILED"));
+    }
+
+    #[tokio::test]
+    async fn test_shorten_applies_request_rules() {
+        let api = TestApi::new();
+        let token = api.token_for("alice").await;
+
+        let response = api
+            .execute(
+                Some(&token),
+                SHORTEN,
+                json!({ "url": "https://example.com", "code": "no spaces" }),
+            )
+            .await;
+        assert_eq!(error_code(&response).as_deref(), Some("VALIDATION_ERROR"));
+        assert!(response.errors[0]
+            .message
+            .starts_with("custom_short_code: "));
+    }
+
+    #[tokio::test]
+    async fn test_shorten_for_an_organization_requires_membership() {
+        let api = TestApi::new();
+        let owner_token = api.token_for("alice").await;
+        let owner = api
+            .services
+            .auth_service
+            .verify_token(&owner_token)
+            .await
+            .unwrap();
+        let org = api
+            .services
+            .org_service
+            .create_organization(owner.id, "Acme", None)
+            .await
+            .unwrap();
+        let query = r#"
+            mutation Shorten($url: String!, $org: ID) {
+                shortenUrl(input: { url: $url, organizationId: $org }) { shortCode }
+            }
+        "#;
+        let variables = json!({ "url": "https://example.com/acme", "org": org.id.to_string() });
+
+        let outsider_token = api.token_for("bob").await;
+        let response = api
+            .execute(Some(&outsider_token), query, variables.clone())
+            .await;
+        assert_eq!(
+            error_code(&response).as_deref(),
+            Some("NOT_ORGANIZATION_MEMBER")
+        );
+
+        data(api.execute(Some(&owner_token), query, variables).await);
+        let org_urls = api
+            .services
+            .url_service
+            .get_urls_for_organization(owner.id, org.id)
+            .await
+            .unwrap();
+        assert_eq!(org_urls.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_update_and_deactivate_url() {
+        let api = TestApi::new();
+        let token = api.token_for("alice").await;
+        let shortened = data(
+            api.execute(
+                Some(&token),
+                SHORTEN,
+                json!({ "url": "https://example.com/a" }),
+            )
+            .await,
+        );
+        let id = shortened["shortenUrl"]["id"].clone();
+
+        let update = r#"
+            mutation Update($id: ID!, $version: Int) {
+                updateUrl(id: $id, input: { originalUrl: "https://example.com/b", version: $version }) {
+                    originalUrl version
+                }
+            }
+        "#;
+        let response = api
+            .execute(Some(&token), update, json!({ "id": id, "version": 7 }))
+            .await;
+        assert_eq!(error_code(&response).as_deref(), Some("CONFLICT"));
+
+        let updated = data(
+            api.execute(Some(&token), update, json!({ "id": id, "version": 1 }))
+                .await,
+        );
+        assert_eq!(
+            updated["updateUrl"],
+            json!({ "originalUrl": "https://example.com/b", "version": 2 })
+        );
+
+        let deactivate = "mutation Deactivate($id: ID!) { deactivateUrl(id: $id) }";
+        let other = api.token_for("bob").await;
+        let response = api
+            .execute(Some(&other), deactivate, json!({ "id": id }))
+            .await;
+        assert_eq!(data(response)["deactivateUrl"], json!(false));
+
+        let response = api
+            .execute(Some(&token), deactivate, json!({ "id": id }))
+            .await;
+        assert_eq!(data(response)["deactivateUrl"], json!(true));
+    }
+
+    #[tokio::test]
+    async fn test_url_analytics_with_timeline() {
+        let api = TestApi::new();
+        let token = api.token_for("alice").await;
+        let shortened = data(
+            api.execute(
+                Some(&token),
+                SHORTEN,
+                json!({ "url": "https://example.com/a" }),
+            )
+            .await,
+        );
+        let id = shortened["shortenUrl"]["id"].clone();
+
+        let query = r#"
+            query Analytics($id: ID!) {
+                urlAnalytics(id: $id, granularity: HOUR) {
+                    totalClicks granularity timeline { start clicks }
+                }
+            }
+        "#;
+        let analytics = data(api.execute(Some(&token), query, json!({ "id": id })).await);
+        assert_eq!(analytics["urlAnalytics"]["totalClicks"], 0);
+        assert_eq!(analytics["urlAnalytics"]["granularity"], "HOUR");
+        assert_eq!(
+            analytics["urlAnalytics"]["timeline"]
+                .as_array()
+                .unwrap()
+                .len(),
+            24
+        );
+
+        let response = api
+            .execute(Some(&token), query, json!({ "id": "999" }))
+            .await;
+        assert_eq!(error_code(&response).as_deref(), Some("NOT_FOUND"));
+    }
+
+    #[tokio::test]
+    async fn test_schema_exposes_roots() {
+        let sdl = TestApi::new().schema.sdl();
+        for field in [
+            "url(shortCode: String!): Url",
+            "urls(pagination: PaginationInput): UrlPage!",
+            "me: User!",
+            "urlAnalytics(id: ID!, granularity: Granularity): AnalyticsData!",
+            "shortenUrl(input: ShortenUrlInput!): Url!",
+            "deactivateUrl(id: ID!): Boolean!",
+            "updateUrl(id: ID!, input: UpdateUrlInput!): Url!",
+        ] {
+            assert!(sdl.contains(field), "missing {}", field);
+        }
+    }
+}
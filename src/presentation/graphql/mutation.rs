@@ -0,0 +1,230 @@
+use super::query::internal_error;
+use super::types::{ShortenUrlInput, UpdateUrlInput, UrlObject};
+use super::{graphql_error, GraphQLServices};
+use crate::application::dto::requests::ShortenUrlRequest;
+use crate::application::use_cases::shorten_url::UseCaseError;
+use crate::domain::entities::{ShortCode, ShortCodeError};
+use crate::domain::repositories::{
+    ClickRepository, RepositoryError, UrlRepository, UserRepository,
+};
+use crate::domain::services::{OrgServiceError, ServiceError};
+use crate::presentation::handlers::{field_errors, org_error_code};
+use async_graphql::{Context, Object, ID};
+use tracing::{info, warn};
+use validator::{Validate, ValidationErrors};
+
+/// Map a failed shortening to the error code the REST API uses
+fn shorten_error(error: &UseCaseError) -> async_graphql::Error {
+    let code = match error {
+        UseCaseError::InvalidShortCode(ShortCodeError::TooShort { .. }) => "SHORT_CODE_TOO_SHORT",
+        UseCaseError::InvalidShortCode(ShortCodeError::TooLong { .. }) => "SHORT_CODE_TOO_LONG",
+        UseCaseError::BlockedDomain(_) => "BLOCKED_DOMAIN",
+        UseCaseError::Service(ServiceError::ShortCodeAlreadyExists) => "DUPLICATE_SHORT_CODE",
+        UseCaseError::Service(ServiceError::Repository(
+            RepositoryError::OrganizationQuotaExceeded { .. },
+        )) => "ORGANIZATION_QUOTA_EXCEEDED",
+        _ => "SHORTEN_FAILED",
+    };
+    graphql_error(code, error.to_string())
+}
+
+/// Map a refused organization URL creation to the error code the REST API uses
+fn org_error(error: &OrgServiceError) -> async_graphql::Error {
+    let (_, code) = org_error_code(error);
+    graphql_error(code, error.to_string())
+}
+
+/// Map failed request rules to one error listing every field, like the REST API reports them
+fn validation_error(errors: &ValidationErrors) -> async_graphql::Error {
+    let message = field_errors(errors)
+        .iter()
+        .map(|error| format!("{}: {}", error.field, error.message))
+        .collect::<Vec<_>>()
+        .join("; ");
+    graphql_error("VALIDATION_ERROR", message)
+}
+
+/// Entry points that change data
+pub struct MutationRoot<R, U, C>
+where
+    R: UrlRepository + Clone + Send + Sync + 'static,
+    U: UserRepository + Clone + Send + Sync + 'static,
+    C: ClickRepository + Clone + Send + Sync + 'static,
+{
+    services: GraphQLServices<R, U, C>,
+}
+
+impl<R, U, C> MutationRoot<R, U, C>
+where
+    R: UrlRepository + Clone + Send + Sync + 'static,
+    U: UserRepository + Clone + Send + Sync + 'static,
+    C: ClickRepository + Clone + Send + Sync + 'static,
+{
+    pub fn new(services: GraphQLServices<R, U, C>) -> Self {
+        Self { services }
+    }
+}
+
+#[Object(name = "Mutation")]
+impl<R, U, C> MutationRoot<R, U, C>
+where
+    R: UrlRepository + Clone + Send + Sync + 'static,
+    U: UserRepository + Clone + Send + Sync + 'static,
+    C: ClickRepository + Clone + Send + Sync + 'static,
+{
+    /// Shorten a URL for the authenticated user, optionally owned by one of their organizations
+    async fn shorten_url(
+        &self,
+        ctx: &Context<'_>,
+        input: ShortenUrlInput,
+    ) -> async_graphql::Result<UrlObject> {
+        let user = self.services.current_user(ctx).await?;
+        let organization_id = input
+            .organization_id
+            .map(|id| id.parse::<i32>())
+            .transpose()
+            .map_err(|_| org_error(&OrgServiceError::NotFound))?;
+        let request = ShortenUrlRequest {
+            url: input.url,
+            custom_short_code: input.custom_short_code,
+            expiration_date: input.expiration_date,
+            organization_id,
+        };
+        request.validate().map_err(|e| validation_error(&e))?;
+
+        // URLs created for an organization count towards its rate limit and quota
+        if let Some(org_id) = organization_id {
+            self.services
+                .org_service
+                .authorize_url_creation(org_id, user.id)
+                .await
+                .map_err(|e| {
+                    warn!(
+                        "User {} cannot create URLs for organization {}: {}",
+                        user.id, org_id, e
+                    );
+                    org_error(&e)
+                })?;
+        }
+
+        let url = self
+            .services
+            .shorten_url_use_case
+            .shorten(request, Some(user.id))
+            .await
+            .map_err(|e| {
+                warn!("Failed to shorten URL: {}", e);
+                shorten_error(&e)
+            })?;
+        info!(
+            "Shortened {} for user {} via GraphQL",
+            url.short_code, user.id
+        );
+
+        Ok(UrlObject::new(
+            url,
+            self.services.shorten_url_use_case.base_url(),
+        ))
+    }
+
+    /// Deactivate one of the authenticated user's URLs
+    ///
+    /// Returns false when the user has no such URL.
+    async fn deactivate_url(&self, ctx: &Context<'_>, id: ID) -> async_graphql::Result<bool> {
+        let user = self.services.current_user(ctx).await?;
+        let Ok(id) = id.parse::<i32>() else {
+            return Ok(false);
+        };
+
+        self.services
+            .url_service
+            .deactivate_url(id, Some(user.id))
+            .await
+            .map_err(|e| {
+                warn!("Failed to deactivate URL {}: {}", id, e);
+                internal_error()
+            })
+    }
+
+    /// Change the destination, short code or expiration of one of the user's URLs
+    async fn update_url(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        input: UpdateUrlInput,
+    ) -> async_graphql::Result<UrlObject> {
+        let user = self.services.current_user(ctx).await?;
+        let not_found = || graphql_error("NOT_FOUND", "URL not found");
+        let id: i32 = id.parse().map_err(|_| not_found())?;
+        let url_service = &self.services.url_service;
+
+        let mut url = match url_service.get_url_by_id(id).await {
+            Ok(Some(url)) if url.user_id == Some(user.id) => url,
+            Ok(_) => return Err(not_found()),
+            Err(e) => {
+                warn!("Failed to load URL {}: {}", id, e);
+                return Err(internal_error());
+            }
+        };
+
+        let conflict = |current_version: i64| {
+            graphql_error(
+                "CONFLICT",
+                format!("URL is at version {}; reload it and retry", current_version),
+            )
+        };
+        let expected_version = input.version.unwrap_or(url.version);
+        if url.version != expected_version {
+            return Err(conflict(url.version));
+        }
+
+        if let Some(original_url) = input.original_url {
+            self.services
+                .shorten_url_use_case
+                .check_destination(&original_url)
+                .await
+                .map_err(|e| shorten_error(&e))?;
+            url.original_url = original_url;
+        }
+
+        if let Some(custom_short_code) = input.custom_short_code {
+            let short_code = ShortCode::new(custom_short_code)
+                .map_err(|e| graphql_error("INVALID_SHORT_CODE", e.to_string()))?;
+            if short_code.value() != url.short_code {
+                let taken = url_service
+                    .short_code_exists(&short_code)
+                    .await
+                    .map_err(|e| {
+                        warn!("Failed to check short code availability: {}", e);
+                        internal_error()
+                    })?;
+                if taken {
+                    return Err(graphql_error(
+                        "DUPLICATE_SHORT_CODE",
+                        "Short code already exists",
+                    ));
+                }
+                url.short_code = short_code.value().to_string();
+            }
+        }
+
+        if let Some(expiration_date) = input.expiration_date {
+            url.expiration_date = Some(expiration_date);
+        }
+
+        match url_service.update_url(&url, expected_version).await {
+            Ok(updated) => Ok(UrlObject::new(
+                updated,
+                self.services.shorten_url_use_case.base_url(),
+            )),
+            Err(ServiceError::Repository(RepositoryError::ConflictingUpdate {
+                current_version,
+            })) => Err(conflict(current_version)),
+            Err(ServiceError::Repository(RepositoryError::NotFound)) => Err(not_found()),
+            Err(e) => {
+                warn!("Failed to update URL {}: {}", id, e);
+                Err(internal_error())
+            }
+        }
+    }
+}
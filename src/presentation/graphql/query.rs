@@ -0,0 +1,161 @@
+use super::types::{
+    AnalyticsData, Granularity, PaginationInput, UrlObject, UrlPageObject, UserObject,
+};
+use super::{graphql_error, GraphQLServices};
+use crate::domain::entities::{ShortCode, Url};
+use crate::domain::repositories::{
+    ClickRepository, SortDirection, UrlRepository, UrlSortField, UserRepository,
+};
+use crate::domain::services::url_service::DEFAULT_LISTING_LIMIT;
+use crate::domain::services::ServiceError;
+use async_graphql::{Context, Object, ID};
+use tracing::warn;
+
+/// Read-only entry points
+pub struct QueryRoot<R, U, C>
+where
+    R: UrlRepository + Clone + Send + Sync + 'static,
+    U: UserRepository + Clone + Send + Sync + 'static,
+    C: ClickRepository + Clone + Send + Sync + 'static,
+{
+    services: GraphQLServices<R, U, C>,
+}
+
+impl<R, U, C> QueryRoot<R, U, C>
+where
+    R: UrlRepository + Clone + Send + Sync + 'static,
+    U: UserRepository + Clone + Send + Sync + 'static,
+    C: ClickRepository + Clone + Send + Sync + 'static,
+{
+    pub fn new(services: GraphQLServices<R, U, C>) -> Self {
+        Self { services }
+    }
+
+    /// Load a URL of the given user, or fail with NOT_FOUND
+    async fn owned_url(&self, id: &ID, user_id: i32) -> async_graphql::Result<Url> {
+        let not_found = || graphql_error("NOT_FOUND", "URL not found");
+        let id: i32 = id.parse().map_err(|_| not_found())?;
+        match self.services.url_service.get_url_by_id(id).await {
+            Ok(Some(url)) if url.user_id == Some(user_id) => Ok(url),
+            Ok(_) => Err(not_found()),
+            Err(e) => {
+                warn!("Failed to load URL {}: {}", id, e);
+                Err(internal_error())
+            }
+        }
+    }
+}
+
+/// Error for failures the client cannot do anything about
+pub(super) fn internal_error() -> async_graphql::Error {
+    graphql_error("INTERNAL_ERROR", "Internal server error")
+}
+
+#[Object(name = "Query")]
+impl<R, U, C> QueryRoot<R, U, C>
+where
+    R: UrlRepository + Clone + Send + Sync + 'static,
+    U: UserRepository + Clone + Send + Sync + 'static,
+    C: ClickRepository + Clone + Send + Sync + 'static,
+{
+    /// The authenticated user
+    async fn me(&self, ctx: &Context<'_>) -> async_graphql::Result<UserObject> {
+        let user = self.services.current_user(ctx).await?;
+        Ok(UserObject::from(user.clone()))
+    }
+
+    /// One of the authenticated user's URLs by short code
+    async fn url(
+        &self,
+        ctx: &Context<'_>,
+        short_code: String,
+    ) -> async_graphql::Result<Option<UrlObject>> {
+        let user = self.services.current_user(ctx).await?;
+        let short_code = ShortCode::new(short_code)
+            .map_err(|e| graphql_error("INVALID_SHORT_CODE", e.to_string()))?;
+
+        match self
+            .services
+            .url_service
+            .get_url_by_short_code(&short_code)
+            .await
+        {
+            Ok(url) => Ok(url
+                .filter(|url| url.user_id == Some(user.id))
+                .map(|url| UrlObject::new(url, self.services.shorten_url_use_case.base_url()))),
+            Err(e) => {
+                warn!("Failed to look up short code {}: {}", short_code.value(), e);
+                Err(internal_error())
+            }
+        }
+    }
+
+    /// The authenticated user's URLs, newest first
+    async fn urls(
+        &self,
+        ctx: &Context<'_>,
+        pagination: Option<PaginationInput>,
+    ) -> async_graphql::Result<UrlPageObject> {
+        let user = self.services.current_user(ctx).await?;
+        let pagination = pagination.unwrap_or_default();
+        let limit = pagination
+            .limit
+            .map_or(DEFAULT_LISTING_LIMIT, |limit| limit.max(0) as usize);
+
+        let page = self
+            .services
+            .url_service
+            .list_urls(
+                user.id,
+                None,
+                UrlSortField::default(),
+                SortDirection::default(),
+                pagination.after.as_deref(),
+                pagination.before.as_deref(),
+                limit,
+            )
+            .await
+            .map_err(|e| match e {
+                ServiceError::InvalidData(message) => graphql_error("INVALID_CURSOR", message),
+                e => {
+                    warn!("Failed to list URLs for user {}: {}", user.id, e);
+                    internal_error()
+                }
+            })?;
+
+        let base_url = self.services.shorten_url_use_case.base_url();
+        Ok(UrlPageObject {
+            items: page
+                .urls
+                .into_iter()
+                .map(|url| UrlObject::new(url, base_url))
+                .collect(),
+            next_cursor: page.next_cursor,
+            prev_cursor: page.prev_cursor,
+        })
+    }
+
+    /// Click analytics of one of the authenticated user's URLs
+    async fn url_analytics(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        granularity: Option<Granularity>,
+    ) -> async_graphql::Result<AnalyticsData> {
+        let user = self.services.current_user(ctx).await?;
+        let url = self.owned_url(&id, user.id).await?;
+        let granularity = granularity.unwrap_or_default();
+
+        let click_tracking_service = &self.services.click_tracking_service;
+        let (summary, timeline) = tokio::try_join!(
+            click_tracking_service.get_url_analytics_summary(url.id, false),
+            click_tracking_service.get_click_timeline(url.id, granularity.into(), false),
+        )
+        .map_err(|e| {
+            warn!("Failed to load analytics for URL {}: {}", url.id, e);
+            graphql_error("ANALYTICS_ERROR", "Failed to load URL analytics")
+        })?;
+
+        Ok(AnalyticsData::new(url.id, summary, granularity, timeline))
+    }
+}
@@ -0,0 +1,245 @@
+use crate::domain::entities::{Url, UrlStatus, User};
+use crate::domain::repositories::UrlAnalyticsSummary;
+use crate::domain::services::click_tracking_service::{ClickTimelinePoint, TimelineGranularity};
+use async_graphql::{Enum, InputObject, SimpleObject, ID};
+use chrono::{DateTime, Utc};
+
+/// Whether a URL redirects, was deactivated, was archived after expiring or was deleted
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[graphql(name = "UrlStatus")]
+pub enum UrlStatusObject {
+    Active,
+    Inactive,
+    Archived,
+    Deleted,
+}
+
+impl From<UrlStatus> for UrlStatusObject {
+    fn from(status: UrlStatus) -> Self {
+        match status {
+            UrlStatus::Active => UrlStatusObject::Active,
+            UrlStatus::Inactive => UrlStatusObject::Inactive,
+            UrlStatus::Archived => UrlStatusObject::Archived,
+            UrlStatus::Deleted => UrlStatusObject::Deleted,
+        }
+    }
+}
+
+/// A shortened URL
+#[derive(SimpleObject, Debug, Clone)]
+#[graphql(name = "Url")]
+pub struct UrlObject {
+    pub id: ID,
+    pub short_code: String,
+    pub short_url: String,
+    pub original_url: String,
+    pub status: UrlStatusObject,
+    pub is_expired: bool,
+    pub expiration_date: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Version to pass to `updateUrl` to guard against concurrent changes
+    pub version: i64,
+}
+
+impl UrlObject {
+    pub fn new(url: Url, base_url: &str) -> Self {
+        Self {
+            id: ID::from(url.id),
+            short_url: url.short_url(base_url),
+            is_expired: url.is_expired(),
+            status: url.status.into(),
+            short_code: url.short_code,
+            original_url: url.original_url,
+            expiration_date: url.expiration_date,
+            created_at: url.created_at,
+            updated_at: url.updated_at,
+            version: url.version,
+        }
+    }
+}
+
+/// One page of the current user's URLs
+#[derive(SimpleObject, Debug, Clone)]
+#[graphql(name = "UrlPage")]
+pub struct UrlPageObject {
+    pub items: Vec<UrlObject>,
+    /// Pass as `after` to get the next page; null on the last page
+    pub next_cursor: Option<String>,
+    /// Pass as `before` to get the previous page; null on the first page
+    pub prev_cursor: Option<String>,
+}
+
+/// The authenticated user
+#[derive(SimpleObject, Debug, Clone)]
+#[graphql(name = "User")]
+pub struct UserObject {
+    pub id: ID,
+    pub username: String,
+    pub email: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+    pub website: Option<String>,
+    pub location: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<User> for UserObject {
+    fn from(user: User) -> Self {
+        Self {
+            id: ID::from(user.id),
+            username: user.username,
+            email: user.email,
+            first_name: user.first_name,
+            last_name: user.last_name,
+            bio: user.bio,
+            avatar_url: user.avatar_url,
+            website: user.website,
+            location: user.location,
+            created_at: user.created_at,
+        }
+    }
+}
+
+/// Width of the buckets of a click timeline
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Granularity {
+    /// The last 24 hours
+    Hour,
+    /// The last 30 days
+    #[default]
+    Day,
+    /// The last 12 weeks
+    Week,
+    /// The last 12 months
+    Month,
+}
+
+impl From<Granularity> for TimelineGranularity {
+    fn from(granularity: Granularity) -> Self {
+        match granularity {
+            Granularity::Hour => TimelineGranularity::Hour,
+            Granularity::Day => TimelineGranularity::Day,
+            Granularity::Week => TimelineGranularity::Week,
+            Granularity::Month => TimelineGranularity::Month,
+        }
+    }
+}
+
+/// Clicks counted under one key, e.g. a country or referrer
+#[derive(SimpleObject, Debug, Clone)]
+pub struct ClickCount {
+    pub key: String,
+    pub clicks: i64,
+}
+
+/// Clicks of one URL broken down by device type
+#[derive(SimpleObject, Debug, Clone)]
+pub struct DeviceBreakdownObject {
+    pub desktop: i64,
+    pub mobile: i64,
+    pub tablet: i64,
+    pub bot: i64,
+}
+
+/// Clicks in one bucket of a timeline
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TimelinePoint {
+    pub start: DateTime<Utc>,
+    pub clicks: i64,
+}
+
+impl From<ClickTimelinePoint> for TimelinePoint {
+    fn from(point: ClickTimelinePoint) -> Self {
+        Self {
+            start: point.start,
+            clicks: point.clicks,
+        }
+    }
+}
+
+/// Click analytics of one URL, excluding bots
+#[derive(SimpleObject, Debug, Clone)]
+pub struct AnalyticsData {
+    pub url_id: ID,
+    pub total_clicks: i64,
+    /// Approximate number of distinct visitors
+    pub unique_visitors: i64,
+    pub clicks_today: i64,
+    pub clicks_this_week: i64,
+    pub clicks_this_month: i64,
+    pub top_countries: Vec<ClickCount>,
+    pub top_referrers: Vec<ClickCount>,
+    pub device_breakdown: DeviceBreakdownObject,
+    /// Share of clicks that led to a conversion, from 0 to 1
+    pub conversion_rate: f64,
+    pub granularity: Granularity,
+    /// Clicks per bucket, oldest first
+    pub timeline: Vec<TimelinePoint>,
+}
+
+impl AnalyticsData {
+    pub fn new(
+        url_id: i32,
+        summary: UrlAnalyticsSummary,
+        granularity: Granularity,
+        timeline: Vec<ClickTimelinePoint>,
+    ) -> Self {
+        let counts = |pairs: Vec<(String, i64)>| {
+            pairs
+                .into_iter()
+                .map(|(key, clicks)| ClickCount { key, clicks })
+                .collect()
+        };
+        Self {
+            url_id: ID::from(url_id),
+            total_clicks: summary.total_clicks,
+            unique_visitors: summary.unique_visitors,
+            clicks_today: summary.clicks_today,
+            clicks_this_week: summary.clicks_this_week,
+            clicks_this_month: summary.clicks_this_month,
+            top_countries: counts(summary.top_countries),
+            top_referrers: counts(summary.top_referrers),
+            device_breakdown: DeviceBreakdownObject {
+                desktop: summary.device_breakdown.desktop,
+                mobile: summary.device_breakdown.mobile,
+                tablet: summary.device_breakdown.tablet,
+                bot: summary.device_breakdown.bot,
+            },
+            conversion_rate: summary.conversion_rate,
+            granularity,
+            timeline: timeline.into_iter().map(TimelinePoint::from).collect(),
+        }
+    }
+}
+
+/// Cursor pagination of a listing; give at most one of `after` and `before`
+#[derive(InputObject, Debug, Clone, Default)]
+pub struct PaginationInput {
+    pub after: Option<String>,
+    pub before: Option<String>,
+    /// Maximum number of items (default 10, max 100)
+    pub limit: Option<i32>,
+}
+
+/// A URL to shorten
+#[derive(InputObject, Debug, Clone)]
+pub struct ShortenUrlInput {
+    pub url: String,
+    pub custom_short_code: Option<String>,
+    pub expiration_date: Option<DateTime<Utc>>,
+    /// Organization to create the URL for; the user must be a member
+    pub organization_id: Option<ID>,
+}
+
+/// Changes to a URL; omitted fields are left as they are
+#[derive(InputObject, Debug, Clone, Default)]
+pub struct UpdateUrlInput {
+    pub original_url: Option<String>,
+    pub custom_short_code: Option<String>,
+    pub expiration_date: Option<DateTime<Utc>>,
+    /// Version the changes are based on; the update fails if the URL changed since
+    pub version: Option<i64>,
+}
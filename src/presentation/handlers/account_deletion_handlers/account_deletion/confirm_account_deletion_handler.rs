@@ -1,8 +1,7 @@
 use crate::application::dto::requests::ConfirmAccountDeletionRequest;
 use crate::application::dto::responses::{AccountDeletionConfirmationResponse, ErrorResponse};
 use crate::domain::repositories::{AccountDeletionTokenRepository, UserRepository};
-use crate::domain::services::AnonymizationService;
-use crate::presentation::handlers::ConcreteAppState;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
 use axum::{extract::State, http::StatusCode, response::Json};
 
 /// Confirm account deletion with token
@@ -21,7 +20,7 @@ use axum::{extract::State, http::StatusCode, response::Json};
 )]
 pub async fn confirm_account_deletion(
     State(state): State<ConcreteAppState>,
-    Json(request): Json<ConfirmAccountDeletionRequest>,
+    ValidatedJson(request): ValidatedJson<ConfirmAccountDeletionRequest>,
 ) -> Result<Json<AccountDeletionConfirmationResponse>, (StatusCode, Json<ErrorResponse>)> {
     let account_deletion_repo = &state.account_deletion_repository;
 
@@ -100,19 +99,10 @@ pub async fn confirm_account_deletion(
             )
         })?;
 
-    // Anonymize user data
-    let anonymization_service = AnonymizationService::new();
-    let anonymized_data = anonymization_service.anonymize_user_data(&user);
-
-    // Anonymize account using repository method
+    // Erase personal data; URLs and recent clicks are kept without it
     state
         .user_repository
-        .anonymize_account(
-            user_id,
-            &anonymized_data.username,
-            &anonymized_data.email,
-            &anonymized_data.password_hash,
-        )
+        .anonymize_user(user.id)
         .await
         .map_err(|e| {
             (
@@ -124,6 +114,7 @@ pub async fn confirm_account_deletion(
                 }),
             )
         })?;
+    state.auth_service.invalidate_user(user.id).await;
 
     // Mark token as confirmed
     deletion_token.mark_as_confirmed();
@@ -2,8 +2,9 @@ use crate::application::dto::requests::DeleteAccountRequest;
 use crate::application::dto::responses::{AccountDeletionRequestResponse, ErrorResponse};
 use crate::domain::entities::AccountDeletionToken;
 use crate::domain::repositories::{AccountDeletionTokenRepository, UserRepository};
-use crate::infrastructure::email::EmailMessage;
-use crate::presentation::handlers::ConcreteAppState;
+use crate::infrastructure::config::env_var;
+use crate::infrastructure::email::{AccountDeletionConfirmEmail, EmailMessage};
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
 use axum::{extract::State, http::StatusCode, response::Json};
 use bcrypt::verify;
 use chrono::{Duration, Utc};
@@ -26,7 +27,7 @@ use super::token_utils::generate_secure_token;
 )]
 pub async fn request_account_deletion(
     State(state): State<ConcreteAppState>,
-    Json(request): Json<DeleteAccountRequest>,
+    ValidatedJson(request): ValidatedJson<DeleteAccountRequest>,
 ) -> Result<Json<AccountDeletionRequestResponse>, (StatusCode, Json<ErrorResponse>)> {
     // TODO: Extract user_id from authentication token/session
     // For now, using a placeholder user_id
@@ -105,8 +106,19 @@ pub async fn request_account_deletion(
     // Create account deletion token
     let deletion_token = AccountDeletionToken::new(0, user_id, token.clone(), now, expires_at);
 
+    // Account deletion confirmation email, queued with the token (if email sender is configured)
+    let base_url = env_var("BASE_URL").unwrap_or_else(|| "http://localhost:8000".to_string());
+    let template = AccountDeletionConfirmEmail {
+        username: user.username.clone(),
+        confirm_url: format!("{}/account/deletion/confirm?token={}", base_url, token),
+        cancel_url: format!("{}/account/deletion/cancel?token={}", base_url, token),
+        expires_in_hours: 24,
+    };
+    let email_message = EmailMessage::from_template(user.email.clone(), &template);
+    let email_message = state.email_sender.is_some().then_some(&email_message);
+
     let created_token = account_deletion_repo
-        .create_token(deletion_token)
+        .create_token(deletion_token, email_message)
         .await
         .map_err(|e| {
             (
@@ -119,25 +131,7 @@ pub async fn request_account_deletion(
             )
         })?;
 
-    // Send account deletion confirmation email
-    let base_url =
-        std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8000".to_string());
-    let confirmation_link = format!("{}/account/deletion/confirm?token={}", base_url, token);
-
-    let email_message = EmailMessage::account_deletion_confirmation(
-        user.email.clone(),
-        confirmation_link,
-        24, // 24 hours expiration
-    );
-
-    // Send email (if email sender is configured)
-    if let Some(email_sender) = state.email_sender.as_ref() {
-        if let Err(e) = email_sender.send_email(email_message).await {
-            tracing::error!("Failed to send account deletion confirmation email: {}", e);
-            // Don't fail the request if email sending fails
-            // Log the error and continue
-        }
-    } else {
+    if email_message.is_none() {
         tracing::warn!("Email sender not configured, account deletion email not sent");
         tracing::info!("Account deletion token for user {}: {}", user_id, token);
     }
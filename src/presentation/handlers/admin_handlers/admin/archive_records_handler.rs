@@ -0,0 +1,91 @@
+use super::dtos::{ArchiveRecordResponse, ArchiveRecordsQuery, ArchiveRecordsResponse};
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use tracing::{info, warn};
+
+/// Records returned when the request does not ask for a limit
+const DEFAULT_ARCHIVE_RECORDS_LIMIT: i64 = 100;
+
+/// Most records returned by one request
+const MAX_ARCHIVE_RECORDS_LIMIT: i64 = 1000;
+
+/// Start of `date` in UTC, the exclusive upper bound of a `before` filter
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc()
+}
+
+/// Handler listing the archived copies of records removed for good
+#[utoipa::path(
+    get,
+    path = "/admin/archive-records",
+    params(
+        ("entity_type" = Option<String>, Query, description = "Only records of this kind, e.g. url"),
+        ("before" = Option<String>, Query, description = "Only records deleted before this date (YYYY-MM-DD, UTC)"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of records to return (default 100, max 1000)")
+    ),
+    responses(
+        (status = 200, description = "Archive records, most recently deleted first", body = ArchiveRecordsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 500, description = "Failed to list archive records", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn list_archive_records_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Query(query): Query<ArchiveRecordsQuery>,
+) -> Result<Json<ArchiveRecordsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    info!("Admin {} listing archive records", admin.id);
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_ARCHIVE_RECORDS_LIMIT)
+        .clamp(1, MAX_ARCHIVE_RECORDS_LIMIT);
+    let records = app_state
+        .cleanup_service
+        .list_archive_records(
+            query.entity_type.as_deref(),
+            query.before.map(start_of_day),
+            limit,
+        )
+        .await
+        .map_err(|e| {
+            warn!("Failed to list archive records: {}", e);
+            let error_response = ErrorResponse {
+                error: "DATABASE_ERROR".to_string(),
+                message: "Failed to list archive records".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response))
+        })?;
+
+    Ok(Json(ArchiveRecordsResponse {
+        records: records
+            .into_iter()
+            .map(ArchiveRecordResponse::from)
+            .collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_before_date_starts_at_midnight_utc() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        assert_eq!(start_of_day(date).to_rfc3339(), "2024-01-01T00:00:00+00:00");
+    }
+}
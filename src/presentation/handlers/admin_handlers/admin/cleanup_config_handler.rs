@@ -0,0 +1,152 @@
+use super::dtos::{CleanupConfigResponse, RetentionEntryResponse};
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::infrastructure::config::RetentionConfig;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::warn;
+
+/// Kinds of data with a retention period, with their retention and table
+///
+/// Deleted URLs share the `urls` table with expired ones; bulk operation progress lives in
+/// memory.
+fn retention_entries(
+    retention: &RetentionConfig,
+) -> [(&'static str, u32, Option<&'static str>); 8] {
+    [
+        (
+            "expired_url",
+            retention.expired_url_retention_days,
+            Some("urls"),
+        ),
+        (
+            "deleted_url",
+            retention.deleted_url_retention_days,
+            Some("urls"),
+        ),
+        (
+            "password_reset_token",
+            retention.password_reset_token_retention_days,
+            Some("password_reset_tokens"),
+        ),
+        (
+            "click_data",
+            retention.click_data_retention_days,
+            Some("clicks"),
+        ),
+        (
+            "audit_log",
+            retention.audit_log_retention_days,
+            Some("audit_log"),
+        ),
+        (
+            "bulk_operation",
+            retention.bulk_operation_retention_days,
+            None,
+        ),
+        (
+            "magic_link_token",
+            retention.magic_link_token_retention_days,
+            Some("magic_link_tokens"),
+        ),
+        (
+            "archive_record",
+            retention.archive_retention_days,
+            Some("archive_records"),
+        ),
+    ]
+}
+
+/// Build the response from the retention configuration and measured table sizes
+fn cleanup_config_response(
+    retention: &RetentionConfig,
+    table_sizes: &[(String, i64)],
+) -> CleanupConfigResponse {
+    let entries = retention_entries(retention)
+        .into_iter()
+        .map(|(entity, retention_days, table)| RetentionEntryResponse {
+            entity: entity.to_string(),
+            retention_days,
+            enabled: retention_days > 0,
+            table: table.map(str::to_string),
+            estimated_storage_bytes: table.and_then(|table| {
+                table_sizes
+                    .iter()
+                    .find(|(name, _)| name == table)
+                    .map(|(_, bytes)| *bytes)
+            }),
+        })
+        .collect();
+    CleanupConfigResponse { entries }
+}
+
+/// Handler showing how long the cleanup service keeps each kind of data
+///
+/// Storage estimates are per table, so kinds of data sharing a table report the same size.
+#[utoipa::path(
+    get,
+    path = "/admin/cleanup/config",
+    responses(
+        (status = 200, description = "Retention configuration", body = CleanupConfigResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn get_cleanup_config_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Json<CleanupConfigResponse>, (StatusCode, Json<ErrorResponse>)> {
+    authorize_admin(&app_state, &headers).await?;
+
+    let tables: Vec<&str> = retention_entries(&app_state.retention)
+        .iter()
+        .filter_map(|(_, _, table)| *table)
+        .collect();
+    let table_sizes = match app_state.database_health.table_sizes(&tables).await {
+        Ok(sizes) => sizes,
+        Err(e) => {
+            warn!("Failed to measure table sizes: {}", e);
+            Vec::new()
+        }
+    };
+
+    Ok(Json(cleanup_config_response(
+        &app_state.retention,
+        &table_sizes,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_config_response() {
+        let retention = RetentionConfig {
+            click_data_retention_days: 0,
+            ..RetentionConfig::default()
+        };
+        let sizes = vec![("clicks".to_string(), 8192), ("urls".to_string(), 4096)];
+
+        let response = cleanup_config_response(&retention, &sizes);
+        assert_eq!(response.entries.len(), 8);
+
+        let clicks = &response.entries[3];
+        assert_eq!(clicks.entity, "click_data");
+        assert!(!clicks.enabled);
+        assert_eq!(clicks.estimated_storage_bytes, Some(8192));
+
+        let bulk_operation = &response.entries[5];
+        assert_eq!(bulk_operation.table, None);
+        assert_eq!(bulk_operation.estimated_storage_bytes, None);
+
+        let password_reset = &response.entries[2];
+        assert!(password_reset.enabled);
+        assert_eq!(password_reset.estimated_storage_bytes, None);
+    }
+}
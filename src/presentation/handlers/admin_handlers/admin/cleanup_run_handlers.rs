@@ -0,0 +1,102 @@
+use super::dtos::{CleanupPreviewResponse, CleanupRunQuery};
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::cleanup_service::{CleanupError, CleanupMode};
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Error response for a cleanup run that failed part way
+fn cleanup_error_response(error: &CleanupError) -> (StatusCode, Json<ErrorResponse>) {
+    warn!("Cleanup run failed: {}", error);
+    let error_response = ErrorResponse {
+        error: "INTERNAL_ERROR".to_string(),
+        message: "Cleanup run failed".to_string(),
+        status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+    };
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response))
+}
+
+/// Handler counting what the next cleanup run would remove, without removing anything
+#[utoipa::path(
+    get,
+    path = "/admin/cleanup/preview",
+    responses(
+        (status = 200, description = "Rows each cleanup task would remove", body = CleanupPreviewResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 500, description = "Counting failed", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn preview_cleanup_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Json<CleanupPreviewResponse>, (StatusCode, Json<ErrorResponse>)> {
+    authorize_admin(&app_state, &headers).await?;
+
+    let preview = app_state
+        .cleanup_service
+        .preview()
+        .await
+        .map_err(|e| cleanup_error_response(&e))?;
+
+    Ok(Json(CleanupPreviewResponse::new(preview, true)))
+}
+
+/// Handler running every cleanup task now instead of waiting for the scheduler
+///
+/// With `dry_run=true` nothing is removed and the counts are those of a preview.
+#[utoipa::path(
+    post,
+    path = "/admin/cleanup/run",
+    params(
+        ("dry_run" = Option<bool>, Query, description = "Only count what would be removed (default false)")
+    ),
+    responses(
+        (status = 200, description = "Rows each cleanup task removed", body = CleanupPreviewResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 500, description = "Cleanup run failed", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn run_cleanup_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Query(query): Query<CleanupRunQuery>,
+) -> Result<Json<CleanupPreviewResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    let mode = if query.dry_run {
+        CleanupMode::DryRun
+    } else {
+        CleanupMode::Execute
+    };
+    let counts = app_state
+        .cleanup_service
+        .run(mode)
+        .await
+        .map_err(|e| cleanup_error_response(&e))?;
+    info!("Admin {} ran cleanup ({:?}): {:?}", admin.id, mode, counts);
+
+    Ok(Json(CleanupPreviewResponse::new(counts, query.dry_run)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_error_response_hides_details() {
+        let (status, Json(body)) = cleanup_error_response(&CleanupError::TaskError(
+            "Failed to delete clicks: connection reset".to_string(),
+        ));
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body.message, "Cleanup run failed");
+    }
+}
@@ -0,0 +1,92 @@
+use super::dtos::{CreateServiceAccountRequest, ServiceAccountCreatedResponse};
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::ServiceAccountError;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+fn service_account_error_response(
+    error: &ServiceAccountError,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code) = match error {
+        ServiceAccountError::InvalidInput(_) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR"),
+        ServiceAccountError::AlreadyExists(_) => {
+            (StatusCode::CONFLICT, "SERVICE_ACCOUNT_ALREADY_EXISTS")
+        }
+        ServiceAccountError::InvalidApiKey | ServiceAccountError::Repository(_) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR")
+        }
+    };
+    let error_response = ErrorResponse {
+        error: code.to_string(),
+        message: error.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Handler for creating a service account for service-to-service calls
+#[utoipa::path(
+    post,
+    path = "/admin/service-accounts",
+    request_body = CreateServiceAccountRequest,
+    responses(
+        (status = 201, description = "Service account created; the API key is only shown once", body = ServiceAccountCreatedResponse),
+        (status = 400, description = "Invalid name", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 409, description = "Name already in use", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn create_service_account_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<CreateServiceAccountRequest>,
+) -> Result<(StatusCode, Json<ServiceAccountCreatedResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    match app_state
+        .service_account_service
+        .create_account(&request.name, admin.id)
+        .await
+    {
+        Ok((account, api_key)) => {
+            info!(
+                "Admin {} created service account {} ({})",
+                admin.id, account.id, account.name
+            );
+            Ok((
+                StatusCode::CREATED,
+                Json(ServiceAccountCreatedResponse::new(account, api_key)),
+            ))
+        }
+        Err(error) => {
+            warn!("Failed to create service account: {}", error);
+            Err(service_account_error_response(&error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_account_error_status() {
+        let (status, Json(body)) = service_account_error_response(
+            &ServiceAccountError::AlreadyExists("analytics".to_string()),
+        );
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body.error, "SERVICE_ACCOUNT_ALREADY_EXISTS");
+
+        let (status, _) =
+            service_account_error_response(&ServiceAccountError::InvalidInput("x".to_string()));
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,85 @@
+use super::dtos::{DbPoolStatsResponse, SlowQueriesResponse, SlowQueryResponse};
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::warn;
+
+/// Number of statements listed by the slow query report
+const SLOW_QUERY_LIMIT: i64 = 10;
+
+/// Handler showing how many database connections are open, idle and in use
+///
+/// The same numbers are exported as the `db_pool_connections_*` metrics.
+#[utoipa::path(
+    get,
+    path = "/admin/db/pool-stats",
+    responses(
+        (status = 200, description = "Connection pool statistics", body = DbPoolStatsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn get_db_pool_stats_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Json<DbPoolStatsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    authorize_admin(&app_state, &headers).await?;
+
+    Ok(Json(DbPoolStatsResponse::from(
+        app_state.database_health.pool_stats(),
+    )))
+}
+
+/// Handler listing the 10 statements with the highest mean execution time
+///
+/// Needs the `pg_stat_statements` extension; without it the endpoint answers 503.
+#[utoipa::path(
+    get,
+    path = "/admin/db/slow-queries",
+    responses(
+        (status = 200, description = "Slowest statements, slowest first", body = SlowQueriesResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 503, description = "pg_stat_statements is not installed", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn get_slow_queries_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Json<SlowQueriesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    authorize_admin(&app_state, &headers).await?;
+
+    match app_state
+        .database_health
+        .slowest_queries(SLOW_QUERY_LIMIT)
+        .await
+    {
+        Ok(Some(queries)) => Ok(Json(SlowQueriesResponse {
+            queries: queries.into_iter().map(SlowQueryResponse::from).collect(),
+        })),
+        Ok(None) => {
+            let error_response = ErrorResponse {
+                error: "PG_STAT_STATEMENTS_UNAVAILABLE".to_string(),
+                message: "The pg_stat_statements extension is not installed".to_string(),
+                status_code: StatusCode::SERVICE_UNAVAILABLE.as_u16(),
+            };
+            Err((StatusCode::SERVICE_UNAVAILABLE, Json(error_response)))
+        }
+        Err(e) => {
+            warn!("Failed to read pg_stat_statements: {}", e);
+            let error_response = ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: "Failed to read query statistics".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use super::dtos::{AddBlockedDomainRequest, BlockedDomainResponse, BlockedDomainsResponse};
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::DomainBlacklistError;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+fn blacklist_error_response(error: &DomainBlacklistError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code) = match error {
+        DomainBlacklistError::InvalidDomain(_) => (StatusCode::BAD_REQUEST, "INVALID_DOMAIN"),
+        DomainBlacklistError::AlreadyBlocked(_) => (StatusCode::CONFLICT, "DOMAIN_ALREADY_BLOCKED"),
+        DomainBlacklistError::NotFound => (StatusCode::NOT_FOUND, "NOT_FOUND"),
+        DomainBlacklistError::Repository(_) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR")
+        }
+    };
+    let error_response = ErrorResponse {
+        error: code.to_string(),
+        message: error.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Handler for listing the domain blacklist
+#[utoipa::path(
+    get,
+    path = "/admin/domain-blacklist",
+    responses(
+        (status = 200, description = "Blacklisted domains retrieved", body = BlockedDomainsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn list_blocked_domains_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<BlockedDomainsResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    info!("Admin {} listing the domain blacklist", admin.id);
+
+    match app_state.domain_blacklist.list_domains().await {
+        Ok(domains) => Ok((
+            StatusCode::OK,
+            Json(BlockedDomainsResponse {
+                domains: domains
+                    .into_iter()
+                    .map(BlockedDomainResponse::from)
+                    .collect(),
+            }),
+        )),
+        Err(error) => {
+            warn!("Failed to list the domain blacklist: {}", error);
+            Err(blacklist_error_response(&error))
+        }
+    }
+}
+
+/// Handler for adding a domain to the blacklist
+#[utoipa::path(
+    post,
+    path = "/admin/domain-blacklist",
+    request_body = AddBlockedDomainRequest,
+    responses(
+        (status = 201, description = "Domain blacklisted", body = BlockedDomainResponse),
+        (status = 400, description = "Invalid domain", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 409, description = "Domain already blacklisted", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn add_blocked_domain_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<AddBlockedDomainRequest>,
+) -> Result<(StatusCode, Json<BlockedDomainResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    match app_state
+        .domain_blacklist
+        .add_domain(
+            &request.domain,
+            request.reason.as_deref().unwrap_or_default(),
+            admin.id,
+        )
+        .await
+    {
+        Ok(blocked) => {
+            info!("Admin {} blacklisted domain {}", admin.id, blocked.domain);
+            Ok((
+                StatusCode::CREATED,
+                Json(BlockedDomainResponse::from(blocked)),
+            ))
+        }
+        Err(error) => {
+            warn!("Failed to blacklist domain {}: {}", request.domain, error);
+            Err(blacklist_error_response(&error))
+        }
+    }
+}
+
+/// Handler for removing a domain from the blacklist
+#[utoipa::path(
+    delete,
+    path = "/admin/domain-blacklist/{id}",
+    params(
+        ("id" = i32, Path, description = "ID of the blacklist entry")
+    ),
+    responses(
+        (status = 204, description = "Domain removed from the blacklist"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 404, description = "Blacklist entry not found", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn remove_blocked_domain_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    match app_state.domain_blacklist.remove_domain(id).await {
+        Ok(()) => {
+            info!("Admin {} removed blacklist entry {}", admin.id, id);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(error) => {
+            warn!("Failed to remove blacklist entry {}: {}", id, error);
+            Err(blacklist_error_response(&error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blacklist_error_response() {
+        let (status, Json(body)) =
+            blacklist_error_response(&DomainBlacklistError::AlreadyBlocked("malware.com".into()));
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body.error, "DOMAIN_ALREADY_BLOCKED");
+
+        let (status, _) =
+            blacklist_error_response(&DomainBlacklistError::InvalidDomain("a b".into()));
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = blacklist_error_response(&DomainBlacklistError::NotFound);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,395 @@
+use crate::application::dto::responses::UrlInfoResponse;
+use crate::domain::entities::{AccountStatus, ArchiveRecord, BlockedDomain, ServiceAccount, User};
+use crate::domain::services::cleanup_service::CleanupPreview;
+use crate::domain::services::notification_service::DigestRunSummary;
+use crate::infrastructure::database::{PoolStats, SlowQuery};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Request DTO for suspending a user account
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct SuspendUserRequest {
+    pub reason: String,
+    /// End of the suspension; omit for an indefinite suspension
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Response DTO describing a user's account status
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountStatusResponse {
+    pub user_id: i32,
+    pub username: String,
+    pub account_status: String,
+    pub suspension_reason: Option<String>,
+    pub suspended_until: Option<String>,
+}
+
+impl From<User> for AccountStatusResponse {
+    fn from(user: User) -> Self {
+        let (suspension_reason, suspended_until) = match &user.account_status {
+            AccountStatus::Suspended { reason, until } => {
+                (Some(reason.clone()), until.map(|dt| dt.to_rfc3339()))
+            }
+            _ => (None, None),
+        };
+
+        Self {
+            user_id: user.id,
+            username: user.username,
+            account_status: user.account_status.as_str().to_string(),
+            suspension_reason,
+            suspended_until,
+        }
+    }
+}
+
+/// Request DTO for adding a domain to the blacklist
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct AddBlockedDomainRequest {
+    /// Domain to block; its subdomains are blocked too
+    pub domain: String,
+    pub reason: Option<String>,
+}
+
+/// Response DTO for a blacklisted domain
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlockedDomainResponse {
+    pub id: i32,
+    pub domain: String,
+    pub reason: String,
+    /// Administrator who added the entry; null for entries loaded from the blacklist file
+    pub added_by: Option<i32>,
+    pub created_at: String,
+}
+
+impl From<BlockedDomain> for BlockedDomainResponse {
+    fn from(blocked: BlockedDomain) -> Self {
+        Self {
+            id: blocked.id,
+            domain: blocked.domain,
+            reason: blocked.reason,
+            added_by: blocked.added_by,
+            created_at: blocked.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Response DTO listing the domain blacklist
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlockedDomainsResponse {
+    pub domains: Vec<BlockedDomainResponse>,
+}
+
+/// Request DTO for creating a service account
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct CreateServiceAccountRequest {
+    pub name: String,
+}
+
+/// Response DTO for a newly created service account
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ServiceAccountCreatedResponse {
+    pub id: i32,
+    pub name: String,
+    /// Send as `X-API-Key`; shown only once and cannot be retrieved later
+    pub api_key: String,
+    pub created_at: String,
+}
+
+impl ServiceAccountCreatedResponse {
+    pub fn new(account: ServiceAccount, api_key: String) -> Self {
+        Self {
+            id: account.id,
+            name: account.name,
+            api_key,
+            created_at: account.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Retention of one kind of data cleaned up in the background
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RetentionEntryResponse {
+    /// Kind of data, e.g. `click_data`
+    pub entity: String,
+    /// Days the data is kept; 0 means cleanup is disabled
+    pub retention_days: u32,
+    pub enabled: bool,
+    /// Table holding the data; null for data kept in memory or not stored yet
+    pub table: Option<String>,
+    /// Disk space used by the table, including indexes; null when unknown
+    pub estimated_storage_bytes: Option<i64>,
+}
+
+/// Response DTO describing the cleanup retention configuration
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CleanupConfigResponse {
+    pub entries: Vec<RetentionEntryResponse>,
+}
+
+/// Response DTO with the rows each cleanup task removed, or would remove in a dry run
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CleanupPreviewResponse {
+    /// Whether the counts are a preview and nothing was removed
+    pub dry_run: bool,
+    /// Expired URLs archived
+    pub expired_urls: u64,
+    /// Deleted URLs removed for good
+    pub deleted_urls: u64,
+    pub old_clicks: u64,
+    pub expired_password_reset_tokens: u64,
+    pub expired_magic_link_tokens: u64,
+    /// Email changes not confirmed in time; their pending email is cleared
+    pub expired_email_changes: u64,
+    pub finished_bulk_operations: u64,
+    pub sent_emails: u64,
+    /// Archive records older than the archive retention deleted
+    pub archive_records: u64,
+    /// Analytics exports whose file was deleted 24 hours after they finished
+    pub expired_analytics_exports: u64,
+}
+
+impl CleanupPreviewResponse {
+    pub fn new(preview: CleanupPreview, dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            expired_urls: preview.expired_urls,
+            deleted_urls: preview.deleted_urls,
+            old_clicks: preview.old_clicks,
+            expired_password_reset_tokens: preview.expired_password_reset_tokens,
+            expired_magic_link_tokens: preview.expired_magic_link_tokens,
+            expired_email_changes: preview.expired_email_changes,
+            finished_bulk_operations: preview.finished_bulk_operations,
+            sent_emails: preview.sent_emails,
+            archive_records: preview.archive_records,
+            expired_analytics_exports: preview.expired_analytics_exports,
+        }
+    }
+}
+
+/// Query parameters for listing archive records
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ArchiveRecordsQuery {
+    /// Only records of this kind, e.g. `url`
+    pub entity_type: Option<String>,
+    /// Only records deleted before the start of this day (UTC)
+    pub before: Option<NaiveDate>,
+    /// Maximum number of records to return (default 100, max 1000)
+    pub limit: Option<i64>,
+}
+
+/// Response DTO for the archived copy of a record removed for good
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ArchiveRecordResponse {
+    pub id: i64,
+    pub entity_type: String,
+    pub entity_id: i32,
+    /// The record as it was before removal, without sensitive fields
+    #[schema(value_type = Object)]
+    pub data_snapshot: serde_json::Value,
+    pub deleted_at: DateTime<Utc>,
+    pub deletion_reason: String,
+}
+
+impl From<ArchiveRecord> for ArchiveRecordResponse {
+    fn from(record: ArchiveRecord) -> Self {
+        Self {
+            id: record.id,
+            entity_type: record.entity_type,
+            entity_id: record.entity_id,
+            data_snapshot: record.data_snapshot,
+            deleted_at: record.deleted_at,
+            deletion_reason: record.deletion_reason,
+        }
+    }
+}
+
+/// Response DTO listing archive records
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ArchiveRecordsResponse {
+    pub records: Vec<ArchiveRecordResponse>,
+}
+
+/// Query parameters for a manual cleanup run
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CleanupRunQuery {
+    /// Only count what would be removed
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Response DTO with the database connection pool's current size
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DbPoolStatsResponse {
+    /// Open connections, idle or in use
+    pub connections_total: u32,
+    pub connections_idle: u32,
+    pub connections_active: u32,
+    pub max_connections: u32,
+    /// Active connections as a share of `max_connections`, from 0.0 to 1.0
+    pub utilization: f64,
+}
+
+impl From<PoolStats> for DbPoolStatsResponse {
+    fn from(stats: PoolStats) -> Self {
+        Self {
+            connections_total: stats.size,
+            connections_idle: stats.idle,
+            connections_active: stats.active(),
+            max_connections: stats.max_connections,
+            utilization: stats.utilization(),
+        }
+    }
+}
+
+/// A statement recorded by `pg_stat_statements`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SlowQueryResponse {
+    /// Normalized statement text, with parameters replaced by placeholders
+    pub query: String,
+    pub calls: i64,
+    pub mean_exec_time_ms: f64,
+    pub total_exec_time_ms: f64,
+}
+
+impl From<SlowQuery> for SlowQueryResponse {
+    fn from(query: SlowQuery) -> Self {
+        Self {
+            query: query.query,
+            calls: query.calls,
+            mean_exec_time_ms: query.mean_exec_time_ms,
+            total_exec_time_ms: query.total_exec_time_ms,
+        }
+    }
+}
+
+/// Response DTO listing the statements with the highest mean execution time, slowest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SlowQueriesResponse {
+    pub queries: Vec<SlowQueryResponse>,
+}
+
+/// Response DTO summarizing a manually triggered expiry digest run
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DigestRunResponse {
+    /// Users with URLs expiring within the digest window
+    pub recipients: usize,
+    pub sent: usize,
+    /// Digest disabled or already sent this week
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+impl From<DigestRunSummary> for DigestRunResponse {
+    fn from(summary: DigestRunSummary) -> Self {
+        Self {
+            recipients: summary.recipients,
+            sent: summary.sent,
+            skipped: summary.skipped,
+            failed: summary.failed,
+        }
+    }
+}
+
+/// Request DTO for moving a URL to another user
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct TransferUrlRequest {
+    /// User receiving the URL
+    pub to_user_id: i32,
+}
+
+/// Query parameters for finding URLs by destination
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UrlsByOriginalQuery {
+    /// Exact destination URL
+    pub url: String,
+    /// Maximum number of URLs to return (default 50, max 100)
+    pub limit: Option<usize>,
+}
+
+/// Query parameters for paging through the URLs of every user
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AdminUrlsQuery {
+    /// One of `active`, `inactive`, `archived` or `deleted` (default `active`)
+    pub status: Option<String>,
+    /// `next_cursor` of the previous page
+    pub after: Option<i32>,
+    /// Maximum number of URLs to return (default 50, max 100)
+    pub limit: Option<usize>,
+}
+
+/// Query parameters for finding new URLs with suspiciously many clicks
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SuspiciousUrlsQuery {
+    /// Fewest clicks a URL must have (default 1000)
+    pub min_clicks: Option<i64>,
+    /// Only URLs created within this many hours (default 24)
+    pub hours: Option<u8>,
+}
+
+/// Query parameters for searching users
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SearchUsersQuery {
+    /// Part of the username or email, or words of the display name
+    pub q: String,
+}
+
+/// Response DTO listing the URLs of every user that point at one destination
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UrlsByOriginalResponse {
+    pub original_url: String,
+    pub urls: Vec<UrlInfoResponse>,
+    /// Distinct owners among the returned URLs; anonymous URLs are not counted
+    pub user_count: usize,
+}
+
+/// Response DTO for a URL under moderation, with its owner
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminUrlResponse {
+    pub id: i32,
+    pub short_code: String,
+    pub short_url: String,
+    pub original_url: String,
+    /// One of `active`, `inactive`, `archived` or `deleted`
+    pub status: String,
+    pub created_at: String,
+    pub user_id: Option<i32>,
+    /// Username of the owner; missing for anonymous URLs
+    pub username: Option<String>,
+    /// Recorded clicks
+    pub click_count: i64,
+}
+
+/// Response DTO for one page of the URLs of every user, ordered by ID
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminUrlsResponse {
+    pub urls: Vec<AdminUrlResponse>,
+    /// Value of `after` for the next page; missing on the last page
+    pub next_cursor: Option<i32>,
+}
+
+/// Response DTO listing new URLs with suspiciously many clicks, most clicked first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SuspiciousUrlsResponse {
+    pub urls: Vec<AdminUrlResponse>,
+}
+
+/// Response DTO summarizing a re-encode of stored short codes
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReencodeShortCodesResponse {
+    /// Alphabet the new short codes were generated from
+    pub alphabet: String,
+    pub checked: usize,
+    /// URLs given a new short code; their old code keeps redirecting
+    pub reencoded: usize,
+    pub failed: usize,
+}
+
+/// Response DTO for a rebuild of the per-user URL stats
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RebuildUserStatsResponse {
+    /// Users whose counters were recomputed
+    pub users: u64,
+}
@@ -0,0 +1,74 @@
+use super::utils::authorize_admin;
+use crate::application::dto::responses::ErrorResponse;
+use crate::domain::repositories::UserRepository;
+use crate::domain::services::data_export_service::export_filename;
+use crate::presentation::handlers::profile_handlers::profile::utils::{
+    data_export_to_response, export_download_response,
+};
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Response,
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for exporting a user's data on their behalf (GDPR data portability)
+///
+/// Always generated synchronously and not subject to the per-user export limit.
+#[utoipa::path(
+    get,
+    path = "/admin/users/{id}/export",
+    params(
+        ("id" = i32, Path, description = "ID of the user to export")
+    ),
+    responses(
+        (status = 200, description = "Export file", body = UserDataExportResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn export_user_data_admin_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    info!("Admin {} exporting data of user {}", admin.id, id);
+
+    let export = match app_state.user_repository.export_user_data(id).await {
+        Ok(Some(export)) => export,
+        Ok(None) => {
+            let error_response = ErrorResponse {
+                error: "USER_NOT_FOUND".to_string(),
+                message: format!("User {} not found", id),
+                status_code: StatusCode::NOT_FOUND.as_u16(),
+            };
+            return Err((StatusCode::NOT_FOUND, Json(error_response)));
+        }
+        Err(error) => {
+            warn!("Failed to export data of user {}: {}", id, error);
+            let error_response = ErrorResponse {
+                error: "EXPORT_FAILED".to_string(),
+                message: error.to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)));
+        }
+    };
+
+    let filename = export_filename(&export.user.username, chrono::Utc::now().date_naive());
+    let contents = serde_json::to_vec_pretty(&data_export_to_response(export)).map_err(|e| {
+        let error_response = ErrorResponse {
+            error: "EXPORT_FAILED".to_string(),
+            message: e.to_string(),
+            status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+        };
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response))
+    })?;
+    Ok(export_download_response(&filename, contents))
+}
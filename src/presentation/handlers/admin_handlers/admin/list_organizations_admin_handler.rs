@@ -0,0 +1,76 @@
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::organization_handlers::{
+    OrganizationSummariesResponse, OrganizationSummaryResponse,
+};
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for listing all organizations with their member counts
+#[utoipa::path(
+    get,
+    path = "/admin/organizations",
+    responses(
+        (status = 200, description = "Organizations retrieved", body = OrganizationSummariesResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn list_organizations_admin_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<OrganizationSummariesResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    info!("Admin {} listing organizations", admin.id);
+
+    match app_state.org_service.list_with_member_counts().await {
+        Ok(organizations) => Ok((
+            StatusCode::OK,
+            Json(OrganizationSummariesResponse {
+                organizations: organizations
+                    .into_iter()
+                    .map(OrganizationSummaryResponse::from)
+                    .collect(),
+            }),
+        )),
+        Err(error) => {
+            warn!("Failed to list organizations: {}", error);
+            let error_response = ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: error.to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Organization, OrganizationWithMemberCount};
+
+    #[test]
+    fn test_summary_includes_member_count() {
+        let summary = OrganizationWithMemberCount {
+            organization: Organization {
+                id: 1,
+                name: "Acme Corp".to_string(),
+                slug: "acme-corp".to_string(),
+                owner_id: 7,
+                created_at: chrono::Utc::now(),
+            },
+            member_count: 3,
+        };
+        let response = OrganizationSummaryResponse::from(summary);
+        assert_eq!(response.slug, "acme-corp");
+        assert_eq!(response.member_count, 3);
+    }
+}
@@ -0,0 +1,46 @@
+// Re-export all admin handler functions and DTOs
+
+pub mod archive_records_handler;
+pub mod cleanup_config_handler;
+pub mod cleanup_run_handlers;
+pub mod create_service_account_handler;
+pub mod database_diagnostics_handlers;
+pub mod domain_blacklist_handlers;
+mod dtos;
+pub mod export_user_data_admin_handler;
+pub mod list_organizations_admin_handler;
+pub mod rebuild_user_stats_handler;
+pub mod reencode_short_codes_handler;
+pub mod reprioritize_operation_handler;
+pub mod search_users_handler;
+pub mod server_info_handler;
+pub mod suspend_user_handler;
+pub mod tls_reload_handler;
+pub mod transfer_url_handler;
+pub mod trigger_digest_handler;
+pub mod unsuspend_user_handler;
+pub mod url_moderation_handlers;
+pub mod urls_by_original_handler;
+mod utils;
+
+pub use archive_records_handler::*;
+pub use cleanup_config_handler::*;
+pub use cleanup_run_handlers::*;
+pub use create_service_account_handler::*;
+pub use database_diagnostics_handlers::*;
+pub use domain_blacklist_handlers::*;
+pub use dtos::*;
+pub use export_user_data_admin_handler::*;
+pub use list_organizations_admin_handler::*;
+pub use rebuild_user_stats_handler::*;
+pub use reencode_short_codes_handler::*;
+pub use reprioritize_operation_handler::*;
+pub use search_users_handler::*;
+pub use server_info_handler::*;
+pub use suspend_user_handler::*;
+pub use tls_reload_handler::*;
+pub use transfer_url_handler::*;
+pub use trigger_digest_handler::*;
+pub use unsuspend_user_handler::*;
+pub use url_moderation_handlers::*;
+pub use urls_by_original_handler::*;
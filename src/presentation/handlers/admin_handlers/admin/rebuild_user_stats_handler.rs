@@ -0,0 +1,50 @@
+use super::dtos::RebuildUserStatsResponse;
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler recomputing the URL and click counters of every user from scratch
+///
+/// The counters are adjusted as URLs change; this corrects any drift, e.g. from updates
+/// or clicks dropped while the counters could not be written.
+#[utoipa::path(
+    post,
+    path = "/admin/stats/rebuild",
+    responses(
+        (status = 200, description = "User stats rebuilt", body = RebuildUserStatsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn rebuild_user_stats_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Json<RebuildUserStatsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    match app_state.url_service.rebuild_user_url_stats().await {
+        Ok(users) => {
+            info!(
+                "Admin {} rebuilt the URL stats of {} users",
+                admin.id, users
+            );
+            Ok(Json(RebuildUserStatsResponse { users }))
+        }
+        Err(e) => {
+            warn!("User stats rebuild failed: {}", e);
+            let error_response = ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: "User stats rebuild failed".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
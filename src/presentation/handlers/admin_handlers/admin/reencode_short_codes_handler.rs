@@ -0,0 +1,57 @@
+use super::dtos::ReencodeShortCodesResponse;
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler giving every URL whose short code uses characters outside the configured alphabet a
+/// new generated code
+///
+/// Meant to be run once after changing `short_code.generated_alphabet`. Old codes are kept as
+/// aliases and keep redirecting.
+#[utoipa::path(
+    post,
+    path = "/admin/short-codes/reencode",
+    responses(
+        (status = 200, description = "Short codes re-encoded", body = ReencodeShortCodesResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn reencode_short_codes_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Json<ReencodeShortCodesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+    let alphabet = app_state.url_service.short_code_alphabet().to_string();
+
+    match app_state.url_service.reencode_short_codes().await {
+        Ok(summary) => {
+            info!(
+                "Admin {} re-encoded {} of {} short codes to {} ({} failed)",
+                admin.id, summary.reencoded, summary.checked, alphabet, summary.failed
+            );
+            Ok(Json(ReencodeShortCodesResponse {
+                alphabet,
+                checked: summary.checked,
+                reencoded: summary.reencoded,
+                failed: summary.failed,
+            }))
+        }
+        Err(e) => {
+            warn!("Short code re-encode failed: {}", e);
+            let error_response = ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: "Short code re-encode failed".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use super::utils::authorize_admin;
+use crate::application::dto::{
+    requests::ReprioritizeOperationRequest, responses::BulkOperationProgress, ErrorResponse,
+};
+use crate::domain::services::bulk_processor::BulkProcessorError;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+fn not_queued_response(id: &str) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: "OPERATION_NOT_QUEUED".to_string(),
+        message: format!("Operation {} is not waiting in the queue", id),
+        status_code: StatusCode::CONFLICT.as_u16(),
+    };
+    (StatusCode::CONFLICT, Json(error_response))
+}
+
+/// Handler for moving a queued bulk operation to another priority
+#[utoipa::path(
+    post,
+    path = "/admin/operations/{id}/reprioritize",
+    params(
+        ("id" = String, Path, description = "ID of the queued bulk operation")
+    ),
+    request_body = ReprioritizeOperationRequest,
+    responses(
+        (status = 200, description = "Operation reprioritized", body = BulkOperationProgress),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 404, description = "Operation not found", body = ErrorResponse),
+        (status = 409, description = "Operation already started", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn reprioritize_operation_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    ValidatedJson(request): ValidatedJson<ReprioritizeOperationRequest>,
+) -> Result<(StatusCode, Json<BulkOperationProgress>), (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    if app_state.progress_service.get_progress(&id).await.is_err() {
+        let error_response = ErrorResponse {
+            error: "OPERATION_NOT_FOUND".to_string(),
+            message: format!("Operation {} not found", id),
+            status_code: StatusCode::NOT_FOUND.as_u16(),
+        };
+        return Err((StatusCode::NOT_FOUND, Json(error_response)));
+    }
+
+    info!(
+        "Admin {} moving operation {} to {} priority",
+        admin.id,
+        id,
+        request.priority.as_str()
+    );
+
+    match app_state
+        .bulk_processor
+        .reprioritize(&id, request.priority)
+        .await
+    {
+        Ok(()) => match app_state.progress_service.get_progress(&id).await {
+            Ok(progress) => Ok((StatusCode::OK, Json(progress))),
+            Err(_) => Err(not_queued_response(&id)),
+        },
+        Err(BulkProcessorError::NotQueued(_)) => Err(not_queued_response(&id)),
+        Err(error) => {
+            warn!("Failed to reprioritize operation {}: {}", id, error);
+            let error_response = ErrorResponse {
+                error: "REPRIORITIZE_FAILED".to_string(),
+                message: error.to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_queued_response() {
+        let (status, Json(body)) = not_queued_response("op-1");
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body.error, "OPERATION_NOT_QUEUED");
+        assert_eq!(body.status_code, 409);
+    }
+}
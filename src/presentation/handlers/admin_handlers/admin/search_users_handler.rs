@@ -0,0 +1,195 @@
+use super::utils::authorize_admin;
+use super::SearchUsersQuery;
+use crate::application::dto::{
+    requests::PaginationRequest,
+    responses::{page_offset, Page, UserSummary},
+    ErrorResponse,
+};
+use crate::domain::entities::User;
+use crate::domain::repositories::UserRepository;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Users returned when the request does not ask for a limit
+const DEFAULT_USER_SEARCH_LIMIT: usize = 20;
+
+/// Handler for searching users by username, email or display name
+#[utoipa::path(
+    get,
+    path = "/admin/users/search",
+    params(
+        ("q" = String, Query, description = "Part of the username or email, or words of the display name"),
+        ("after" = Option<String>, Query, description = "next_cursor from the previous page"),
+        ("before" = Option<String>, Query, description = "prev_cursor from the following page"),
+        ("limit" = Option<usize>, Query, description = "Maximum number of users to return (default 20, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Matching users, ordered by username", body = UserSummaryPage),
+        (status = 400, description = "Empty query or invalid cursor", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn search_users_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Query(query): Query<SearchUsersQuery>,
+    Query(pagination): Query<PaginationRequest>,
+) -> Result<(StatusCode, Json<Page<UserSummary>>), (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    let search = query.q.trim();
+    if search.is_empty() {
+        let error_response = ErrorResponse {
+            error: "INVALID_QUERY".to_string(),
+            message: "Search query must not be empty".to_string(),
+            status_code: StatusCode::BAD_REQUEST.as_u16(),
+        };
+        return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+    }
+
+    let limit = pagination.limit_or(DEFAULT_USER_SEARCH_LIMIT);
+    let Some(offset) = page_offset(&pagination, limit) else {
+        let error_response = ErrorResponse {
+            error: "INVALID_CURSOR".to_string(),
+            message: "Invalid pagination cursor".to_string(),
+            status_code: StatusCode::BAD_REQUEST.as_u16(),
+        };
+        return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+    };
+
+    info!("Admin {} searching users", admin.id);
+
+    // One extra row tells whether there is a next page
+    match app_state
+        .user_repository
+        .search_users(search, limit + 1, offset)
+        .await
+    {
+        Ok(users) => {
+            let summaries = users.into_iter().map(user_to_summary).collect();
+            Ok((
+                StatusCode::OK,
+                Json(Page::from_offset(summaries, offset, limit)),
+            ))
+        }
+        Err(error) => {
+            warn!("Failed to search users: {}", error);
+            let error_response = ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: "Internal server error".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+fn user_to_summary(user: User) -> UserSummary {
+    UserSummary {
+        id: user.id,
+        display_name: user.full_name(),
+        account_status: user.account_status.as_str().to_string(),
+        created_at: user.created_at.to_rfc3339(),
+        username: user.username,
+        email: user.email,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_utils::MockUserRepository;
+
+    async fn repository_with_users() -> MockUserRepository {
+        let repository = MockUserRepository::new();
+        let alice = repository
+            .create_user("alice_w", "alice@example.com", "hash")
+            .await
+            .unwrap();
+        repository
+            .update_profile(
+                alice.id,
+                Some("Alice"),
+                Some("Wonder"),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        repository
+            .create_user("bob", "robert@corp.io", "hash")
+            .await
+            .unwrap();
+        repository
+    }
+
+    async fn usernames(repository: &MockUserRepository, query: &str) -> Vec<String> {
+        repository
+            .search_users(query, 10, 0)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|user| user.username)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_search_matches_email_username_and_display_name() {
+        let repository = repository_with_users().await;
+
+        assert_eq!(usernames(&repository, "CORP.io").await, vec!["bob"]);
+        assert_eq!(usernames(&repository, "ice_").await, vec!["alice_w"]);
+        assert_eq!(
+            usernames(&repository, "wonder alice").await,
+            vec!["alice_w"]
+        );
+        assert!(usernames(&repository, "won").await.is_empty());
+        assert_eq!(usernames(&repository, "example.com").await, vec!["alice_w"]);
+    }
+
+    #[test]
+    fn test_offset_pages_link_to_each_other() {
+        let pagination = |after: Option<String>, before: Option<String>| PaginationRequest {
+            after,
+            before,
+            limit: Some(2),
+        };
+
+        let first = Page::from_offset(vec![1, 2, 3], 0, 2);
+        assert_eq!(first.data, vec![1, 2]);
+        assert!(first.prev_cursor.is_none());
+
+        let offset = page_offset(&pagination(first.next_cursor, None), 2).unwrap();
+        assert_eq!(offset, 2);
+        let last = Page::from_offset(vec![3], offset, 2);
+        assert_eq!(last.data, vec![3]);
+        assert!(last.next_cursor.is_none());
+
+        assert_eq!(page_offset(&pagination(None, last.prev_cursor), 2), Some(0));
+        assert!(page_offset(&pagination(Some("!!".to_string()), None), 2).is_none());
+    }
+
+    #[test]
+    fn test_summary_uses_full_name() {
+        let mut user = User::new_with_timestamp(
+            3,
+            "carol".to_string(),
+            "carol@example.com".to_string(),
+            "hash".to_string(),
+        );
+        user.first_name = Some("Carol".to_string());
+        let summary = user_to_summary(user);
+        assert_eq!(summary.display_name.as_deref(), Some("Carol"));
+        assert_eq!(summary.account_status, "active");
+    }
+}
@@ -0,0 +1,31 @@
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::infrastructure::server_info::ServerInfo;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+
+/// Handler describing what the server runs with: enabled features, limits and routes
+///
+/// Same information as the startup banner; secrets are only reported as present or absent.
+#[utoipa::path(
+    get,
+    path = "/info",
+    responses(
+        (status = 200, description = "Server configuration summary", body = ServerInfo),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn get_server_info_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Json<ServerInfo>, (StatusCode, Json<ErrorResponse>)> {
+    authorize_admin(&app_state, &headers).await?;
+
+    Ok(Json(app_state.server_info.clone()))
+}
@@ -0,0 +1,102 @@
+use super::dtos::{AccountStatusResponse, SuspendUserRequest};
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::AuthServiceError;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for suspending a user account
+#[utoipa::path(
+    post,
+    path = "/admin/users/{id}/suspend",
+    params(
+        ("id" = i32, Path, description = "ID of the user to suspend")
+    ),
+    request_body = SuspendUserRequest,
+    responses(
+        (status = 200, description = "User suspended successfully", body = AccountStatusResponse),
+        (status = 400, description = "Invalid suspension request", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn suspend_user_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    ValidatedJson(request): ValidatedJson<SuspendUserRequest>,
+) -> Result<(StatusCode, Json<AccountStatusResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    info!("Admin {} suspending user {}", admin.id, id);
+
+    match app_state
+        .auth_service
+        .suspend_user(id, &request.reason, request.until)
+        .await
+    {
+        Ok(user) => {
+            info!("Suspended user {}", user.id);
+            Ok((StatusCode::OK, Json(AccountStatusResponse::from(user))))
+        }
+        Err(AuthServiceError::InvalidInput(message)) => {
+            let error_response = ErrorResponse {
+                error: "VALIDATION_ERROR".to_string(),
+                message,
+                status_code: StatusCode::BAD_REQUEST.as_u16(),
+            };
+            Err((StatusCode::BAD_REQUEST, Json(error_response)))
+        }
+        Err(AuthServiceError::UserNotFound) => {
+            let error_response = ErrorResponse {
+                error: "USER_NOT_FOUND".to_string(),
+                message: format!("User {} not found", id),
+                status_code: StatusCode::NOT_FOUND.as_u16(),
+            };
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        }
+        Err(error) => {
+            warn!("Failed to suspend user {}: {}", id, error);
+            let error_response = ErrorResponse {
+                error: "SUSPEND_FAILED".to_string(),
+                message: error.to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suspend_request_deserialize() {
+        let json = r#"{"reason":"Spam","until":"2030-01-01T00:00:00Z"}"#;
+        let request: SuspendUserRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.reason, "Spam");
+        assert!(request.until.is_some());
+
+        let json = r#"{"reason":"Spam"}"#;
+        let request: SuspendUserRequest = serde_json::from_str(json).unwrap();
+        assert!(request.until.is_none());
+    }
+
+    #[test]
+    fn test_user_not_found_error() {
+        let error = ErrorResponse {
+            error: "USER_NOT_FOUND".to_string(),
+            message: "User 1 not found".to_string(),
+            status_code: StatusCode::NOT_FOUND.as_u16(),
+        };
+        assert_eq!(error.status_code, 404);
+    }
+}
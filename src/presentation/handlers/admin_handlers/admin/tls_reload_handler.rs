@@ -0,0 +1,57 @@
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler re-reading the TLS certificate and key from disk
+///
+/// Lets renewed certificates (e.g. from certbot) take effect without a restart. New
+/// connections use the new certificate; if the files cannot be loaded the old one stays.
+#[utoipa::path(
+    post,
+    path = "/admin/tls/reload",
+    responses(
+        (status = 204, description = "Certificate reloaded"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 409, description = "Server is not serving TLS", body = ErrorResponse),
+        (status = 500, description = "Certificate or key could not be loaded", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn reload_tls_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    let Some(certificate) = &app_state.tls_certificate else {
+        let error_response = ErrorResponse {
+            error: "TLS_NOT_ENABLED".to_string(),
+            message: "The server is not configured for TLS".to_string(),
+            status_code: StatusCode::CONFLICT.as_u16(),
+        };
+        return Err((StatusCode::CONFLICT, Json(error_response)));
+    };
+
+    match certificate.reload().await {
+        Ok(()) => {
+            info!("TLS certificate reloaded by admin {}", admin.id);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            warn!("Failed to reload TLS certificate: {}", e);
+            let error_response = ErrorResponse {
+                error: "TLS_RELOAD_FAILED".to_string(),
+                message: format!("Failed to load certificate: {}", e),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use super::dtos::TransferUrlRequest;
+use super::utils::authorize_admin;
+use crate::application::dto::{responses::UrlInfoResponse, ErrorResponse};
+use crate::domain::repositories::{RepositoryError, UserRepository};
+use crate::domain::services::ServiceError;
+use crate::presentation::handlers::url_handlers::urls::url_utils::url_to_info_response;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: String,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message,
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Map a failed transfer to its HTTP response
+fn transfer_error_response(error: &ServiceError) -> (StatusCode, Json<ErrorResponse>) {
+    match error {
+        ServiceError::Repository(RepositoryError::NotFound) => error_response(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            "URL not found".to_string(),
+        ),
+        ServiceError::UserNotFound(_) => {
+            error_response(StatusCode::NOT_FOUND, "USER_NOT_FOUND", error.to_string())
+        }
+        ServiceError::UserSuspended(_) => {
+            error_response(StatusCode::CONFLICT, "USER_SUSPENDED", error.to_string())
+        }
+        ServiceError::UrlQuotaExceeded { .. } => error_response(
+            StatusCode::CONFLICT,
+            "URL_QUOTA_EXCEEDED",
+            error.to_string(),
+        ),
+        // The owner was read just before, so a mismatch means the URL moved in the meantime
+        ServiceError::PermissionDenied(_) => error_response(
+            StatusCode::CONFLICT,
+            "CONFLICT",
+            "URL changed owner while it was being transferred; retry".to_string(),
+        ),
+        ServiceError::InvalidData(message) => {
+            error_response(StatusCode::BAD_REQUEST, "INVALID_REQUEST", message.clone())
+        }
+        _ => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "TRANSFER_FAILED",
+            "Internal server error".to_string(),
+        ),
+    }
+}
+
+/// Handler moving a URL from its owner to another user
+///
+/// The URL keeps its short code, clicks and conversions. Both users are emailed when email is
+/// configured, and the transfer is recorded in the audit log.
+#[utoipa::path(
+    post,
+    path = "/admin/urls/{id}/transfer",
+    params(
+        ("id" = i32, Path, description = "URL ID to transfer")
+    ),
+    request_body = TransferUrlRequest,
+    responses(
+        (status = 200, description = "URL transferred", body = UrlInfoResponse),
+        (status = 400, description = "URL has no owner or already belongs to the user", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 404, description = "URL or user not found", body = ErrorResponse),
+        (status = 409, description = "User suspended or over their URL quota, or URL changed owner", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn transfer_url_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    ValidatedJson(request): ValidatedJson<TransferUrlRequest>,
+) -> Result<Json<UrlInfoResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    let url = match app_state.url_service.get_url_by_id(id).await {
+        Ok(Some(url)) => url,
+        Ok(None) => {
+            return Err(transfer_error_response(&ServiceError::Repository(
+                RepositoryError::NotFound,
+            )))
+        }
+        Err(error) => return Err(transfer_error_response(&error)),
+    };
+    let Some(from_user_id) = url.user_id else {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            "INVALID_REQUEST",
+            "URL has no owner to transfer it from".to_string(),
+        ));
+    };
+
+    let url = match app_state
+        .url_service
+        .transfer_url(id, from_user_id, request.to_user_id, admin.id)
+        .await
+    {
+        Ok(url) => url,
+        Err(error) => {
+            warn!("Failed to transfer URL {}: {}", id, error);
+            return Err(transfer_error_response(&error));
+        }
+    };
+    info!(
+        "Admin {} transferred URL {} from user {} to user {}",
+        admin.id, id, from_user_id, request.to_user_id
+    );
+
+    let users = &app_state.user_repository;
+    if let (Ok(Some(previous_owner)), Ok(Some(new_owner))) = (
+        users.find_by_id(from_user_id).await,
+        users.find_by_id(request.to_user_id).await,
+    ) {
+        app_state
+            .notification_service
+            .send_url_transfer_notifications(&url, &previous_owner, &new_owner)
+            .await;
+    }
+
+    let base_url = app_state.shorten_url_use_case.base_url();
+    Ok(Json(url_to_info_response(url, base_url, None)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_error_responses() {
+        let cases = [
+            (
+                ServiceError::Repository(RepositoryError::NotFound),
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+            ),
+            (
+                ServiceError::UserNotFound(2),
+                StatusCode::NOT_FOUND,
+                "USER_NOT_FOUND",
+            ),
+            (
+                ServiceError::UserSuspended(2),
+                StatusCode::CONFLICT,
+                "USER_SUSPENDED",
+            ),
+            (
+                ServiceError::UrlQuotaExceeded {
+                    user_id: 2,
+                    limit: 10,
+                },
+                StatusCode::CONFLICT,
+                "URL_QUOTA_EXCEEDED",
+            ),
+            (
+                ServiceError::PermissionDenied("moved".to_string()),
+                StatusCode::CONFLICT,
+                "CONFLICT",
+            ),
+        ];
+        for (error, status, code) in cases {
+            let (actual_status, Json(body)) = transfer_error_response(&error);
+            assert_eq!(actual_status, status);
+            assert_eq!(body.error, code);
+        }
+
+        let (_, Json(body)) = transfer_error_response(&ServiceError::UrlQuotaExceeded {
+            user_id: 2,
+            limit: 10,
+        });
+        assert!(body.message.contains("already owns 10 URLs"));
+    }
+}
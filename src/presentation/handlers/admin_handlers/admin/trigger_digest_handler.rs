@@ -0,0 +1,79 @@
+use super::dtos::DigestRunResponse;
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::domain::repositories::UrlRepository;
+use crate::domain::services::notification_service::{DigestSchedule, EXPIRY_DIGEST_WINDOW_DAYS};
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use chrono::Utc;
+use tracing::{info, warn};
+
+/// Handler sending the expiry digest now instead of waiting for the scheduler
+///
+/// Users' digest days are ignored, but nobody gets a second digest within the same week.
+#[utoipa::path(
+    post,
+    path = "/admin/notifications/trigger-digest",
+    responses(
+        (status = 200, description = "Expiry digest run finished", body = DigestRunResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 503, description = "Email is not configured", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn trigger_digest_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Json<DigestRunResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    let internal_error = |message: String| {
+        let error_response = ErrorResponse {
+            error: "INTERNAL_ERROR".to_string(),
+            message,
+            status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+        };
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response))
+    };
+
+    if !app_state.notification_service.can_send_digests() {
+        let error_response = ErrorResponse {
+            error: "SERVICE_UNAVAILABLE".to_string(),
+            message: "Email is not configured".to_string(),
+            status_code: StatusCode::SERVICE_UNAVAILABLE.as_u16(),
+        };
+        return Err((StatusCode::SERVICE_UNAVAILABLE, Json(error_response)));
+    }
+
+    let expiring_urls = app_state
+        .url_repository
+        .find_urls_expiring_soon(chrono::Duration::days(EXPIRY_DIGEST_WINDOW_DAYS))
+        .await
+        .map_err(|e| {
+            warn!("Failed to find expiring URLs: {}", e);
+            internal_error("Failed to find expiring URLs".to_string())
+        })?;
+
+    match app_state
+        .notification_service
+        .send_expiry_digest_for_all_users(&expiring_urls, DigestSchedule::Immediate, Utc::now())
+        .await
+    {
+        Ok(summary) => {
+            info!(
+                "Admin {} triggered the expiry digest: {} sent",
+                admin.id, summary.sent
+            );
+            Ok(Json(summary.into()))
+        }
+        Err(e) => {
+            warn!("Expiry digest run failed: {}", e);
+            Err(internal_error("Expiry digest run failed".to_string()))
+        }
+    }
+}
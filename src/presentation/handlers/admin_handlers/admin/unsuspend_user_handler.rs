@@ -0,0 +1,90 @@
+use super::dtos::AccountStatusResponse;
+use super::utils::authorize_admin;
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::AuthServiceError;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for lifting a user account suspension
+#[utoipa::path(
+    post,
+    path = "/admin/users/{id}/unsuspend",
+    params(
+        ("id" = i32, Path, description = "ID of the user to unsuspend")
+    ),
+    responses(
+        (status = 200, description = "User unsuspended successfully", body = AccountStatusResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn unsuspend_user_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<(StatusCode, Json<AccountStatusResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    info!("Admin {} unsuspending user {}", admin.id, id);
+
+    match app_state.auth_service.unsuspend_user(id).await {
+        Ok(user) => {
+            info!("Unsuspended user {}", user.id);
+            Ok((StatusCode::OK, Json(AccountStatusResponse::from(user))))
+        }
+        Err(AuthServiceError::UserNotFound) => {
+            let error_response = ErrorResponse {
+                error: "USER_NOT_FOUND".to_string(),
+                message: format!("User {} not found", id),
+                status_code: StatusCode::NOT_FOUND.as_u16(),
+            };
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        }
+        Err(error) => {
+            warn!("Failed to unsuspend user {}: {}", id, error);
+            let error_response = ErrorResponse {
+                error: "UNSUSPEND_FAILED".to_string(),
+                message: error.to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::User;
+
+    #[test]
+    fn test_account_status_response_from_active_user() {
+        let user = User::new_with_timestamp(
+            1,
+            "testuser".to_string(),
+            "test@example.com".to_string(),
+            "hash".to_string(),
+        );
+        let response = AccountStatusResponse::from(user);
+        assert_eq!(response.account_status, "active");
+        assert!(response.suspension_reason.is_none());
+        assert!(response.suspended_until.is_none());
+    }
+
+    #[test]
+    fn test_unsuspend_failed_error() {
+        let error = ErrorResponse {
+            error: "UNSUSPEND_FAILED".to_string(),
+            message: "Database error".to_string(),
+            status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+        };
+        assert_eq!(error.error, "UNSUSPEND_FAILED");
+    }
+}
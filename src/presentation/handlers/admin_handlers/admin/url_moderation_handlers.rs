@@ -0,0 +1,176 @@
+use super::utils::authorize_admin;
+use super::{
+    AdminUrlResponse, AdminUrlsQuery, AdminUrlsResponse, SuspiciousUrlsQuery,
+    SuspiciousUrlsResponse,
+};
+use crate::application::dto::ErrorResponse;
+use crate::domain::entities::{Url, UrlStatus};
+use crate::domain::repositories::UserRepository;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// URLs returned when the request does not ask for a limit
+const DEFAULT_ADMIN_URLS_LIMIT: usize = 50;
+
+/// Fewest clicks of a suspicious URL when the request does not say
+const DEFAULT_SUSPICIOUS_MIN_CLICKS: i64 = 1000;
+
+/// Age in hours of the newest URLs checked when the request does not say
+const DEFAULT_SUSPICIOUS_WITHIN_HOURS: u8 = 24;
+
+fn database_error_response(message: &str) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: "DATABASE_ERROR".to_string(),
+        message: message.to_string(),
+        status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+    };
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response))
+}
+
+/// Moderation view of `urls`, with the username and click count of each
+async fn admin_url_responses(
+    app_state: &ConcreteAppState,
+    urls: Vec<Url>,
+) -> Result<Vec<AdminUrlResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let url_ids: Vec<i32> = urls.iter().map(|url| url.id).collect();
+    let mut user_ids: Vec<i32> = urls.iter().filter_map(|url| url.user_id).collect();
+    user_ids.sort_unstable();
+    user_ids.dedup();
+
+    let click_counts = app_state
+        .url_service
+        .count_clicks_for_urls(&url_ids)
+        .await
+        .map_err(|error| {
+            warn!("Failed to count the clicks of moderated URLs: {}", error);
+            database_error_response("Failed to list URLs")
+        })?;
+    let usernames = app_state
+        .user_repository
+        .find_usernames_by_ids(&user_ids)
+        .await
+        .map_err(|error| {
+            warn!("Failed to look up the owners of moderated URLs: {}", error);
+            database_error_response("Failed to list URLs")
+        })?;
+
+    let base_url = app_state.shorten_url_use_case.base_url();
+    Ok(urls
+        .into_iter()
+        .map(|url| AdminUrlResponse {
+            id: url.id,
+            short_url: url.short_url(base_url),
+            short_code: url.short_code.clone(),
+            original_url: url.original_url.clone(),
+            status: url.status.to_string(),
+            created_at: url.created_at.to_rfc3339(),
+            user_id: url.user_id,
+            username: url.user_id.and_then(|id| usernames.get(&id).cloned()),
+            click_count: click_counts.get(&url.id).copied().unwrap_or(0),
+        })
+        .collect())
+}
+
+/// Handler for paging through the URLs of every user with one status, for moderation
+#[utoipa::path(
+    get,
+    path = "/admin/urls",
+    params(
+        ("status" = Option<String>, Query, description = "One of active, inactive, archived or deleted (default active)"),
+        ("after" = Option<i32>, Query, description = "next_cursor of the previous page"),
+        ("limit" = Option<usize>, Query, description = "Maximum number of URLs to return (default 50, max 100)")
+    ),
+    responses(
+        (status = 200, description = "One page of URLs, ordered by ID", body = AdminUrlsResponse),
+        (status = 400, description = "Invalid status", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn list_urls_admin_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Query(query): Query<AdminUrlsQuery>,
+) -> Result<Json<AdminUrlsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    let status = match query.status.as_deref().map(UrlStatus::parse) {
+        None => UrlStatus::Active,
+        Some(Some(status)) => status,
+        Some(None) => {
+            let error_response = ErrorResponse {
+                error: "INVALID_STATUS".to_string(),
+                message: "Invalid status. Must be 'active', 'inactive', 'archived' or 'deleted'"
+                    .to_string(),
+                status_code: StatusCode::BAD_REQUEST.as_u16(),
+            };
+            return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+        }
+    };
+
+    info!("Admin {} listing {} URLs", admin.id, status);
+
+    let limit = query.limit.unwrap_or(DEFAULT_ADMIN_URLS_LIMIT);
+    let (urls, next_cursor) = app_state
+        .url_service
+        .list_urls_by_status(status, query.after, limit)
+        .await
+        .map_err(|error| {
+            warn!("Failed to list {} URLs: {}", status, error);
+            database_error_response("Failed to list URLs")
+        })?;
+
+    Ok(Json(AdminUrlsResponse {
+        urls: admin_url_responses(&app_state, urls).await?,
+        next_cursor,
+    }))
+}
+
+/// Handler for finding new URLs with anomalously many clicks, a hint of bot traffic
+#[utoipa::path(
+    get,
+    path = "/admin/urls/suspicious",
+    params(
+        ("min_clicks" = Option<i64>, Query, description = "Fewest clicks a URL must have (default 1000)"),
+        ("hours" = Option<u8>, Query, description = "Only URLs created within this many hours (default 24)")
+    ),
+    responses(
+        (status = 200, description = "Suspicious URLs, most clicked first", body = SuspiciousUrlsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn list_suspicious_urls_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Query(query): Query<SuspiciousUrlsQuery>,
+) -> Result<Json<SuspiciousUrlsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    let min_clicks = query.min_clicks.unwrap_or(DEFAULT_SUSPICIOUS_MIN_CLICKS);
+    let hours = query.hours.unwrap_or(DEFAULT_SUSPICIOUS_WITHIN_HOURS);
+    info!(
+        "Admin {} looking for URLs with {}+ clicks created within {} hours",
+        admin.id, min_clicks, hours
+    );
+
+    let urls = app_state
+        .url_service
+        .find_suspicious_urls(min_clicks, hours)
+        .await
+        .map_err(|error| {
+            warn!("Failed to look for suspicious URLs: {}", error);
+            database_error_response("Failed to list URLs")
+        })?;
+
+    Ok(Json(SuspiciousUrlsResponse {
+        urls: admin_url_responses(&app_state, urls).await?,
+    }))
+}
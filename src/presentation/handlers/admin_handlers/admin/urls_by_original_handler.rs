@@ -0,0 +1,76 @@
+use super::utils::authorize_admin;
+use super::{UrlsByOriginalQuery, UrlsByOriginalResponse};
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::url_handlers::urls::url_utils::url_to_info_response;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use std::collections::HashSet;
+use tracing::{info, warn};
+
+/// URLs returned when the request does not ask for a limit
+const DEFAULT_URLS_BY_ORIGINAL_LIMIT: usize = 50;
+
+/// Handler for finding the URLs of every user that point at one destination
+#[utoipa::path(
+    get,
+    path = "/admin/urls/by-original",
+    params(
+        ("url" = String, Query, description = "Exact destination URL, percent-encoded"),
+        ("limit" = Option<usize>, Query, description = "Maximum number of URLs to return (default 50, max 100)")
+    ),
+    responses(
+        (status = 200, description = "URLs pointing at the destination, newest first", body = UrlsByOriginalResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Administrator access required", body = ErrorResponse),
+    ),
+    tag = "admin"
+)]
+pub async fn urls_by_original_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Query(query): Query<UrlsByOriginalQuery>,
+) -> Result<(StatusCode, Json<UrlsByOriginalResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let admin = authorize_admin(&app_state, &headers).await?;
+
+    info!("Admin {} looking up URLs by destination", admin.id);
+
+    let limit = query.limit.unwrap_or(DEFAULT_URLS_BY_ORIGINAL_LIMIT);
+    match app_state
+        .url_service
+        .find_all_urls_by_original_url(&query.url, limit)
+        .await
+    {
+        Ok(urls) => {
+            let user_count = urls
+                .iter()
+                .filter_map(|url| url.user_id)
+                .collect::<HashSet<_>>()
+                .len();
+            let base_url = app_state.shorten_url_use_case.base_url();
+            Ok((
+                StatusCode::OK,
+                Json(UrlsByOriginalResponse {
+                    original_url: query.url,
+                    urls: urls
+                        .into_iter()
+                        .map(|url| url_to_info_response(url, base_url, None))
+                        .collect(),
+                    user_count,
+                }),
+            ))
+        }
+        Err(error) => {
+            warn!("Failed to look up URLs by destination: {}", error);
+            let error_response = ErrorResponse {
+                error: "DATABASE_ERROR".to_string(),
+                message: "Failed to look up URLs".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
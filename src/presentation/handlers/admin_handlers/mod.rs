@@ -0,0 +1,4 @@
+// Re-export all admin handler functions from the admin module
+pub mod admin;
+
+pub use admin::*;
@@ -0,0 +1,64 @@
+use crate::application::dto::requests::ExportFormat;
+use crate::domain::entities::ExportJob;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Request DTO for exporting the raw click data of the user's URLs
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct RequestAnalyticsExportRequest {
+    pub format: ExportFormat,
+    /// Only export clicks from this time on
+    pub start_date: Option<DateTime<Utc>>,
+    /// Only export clicks up to this time
+    pub end_date: Option<DateTime<Utc>>,
+}
+
+/// Response DTO for a queued analytics export
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnalyticsExportQueuedResponse {
+    /// ID of the export, which is also the ID of the bulk operation producing it
+    pub operation_id: String,
+    pub message: String,
+}
+
+/// Response DTO describing an analytics export
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnalyticsExportResponse {
+    pub id: String,
+    pub format: ExportFormat,
+    /// One of `pending`, `processing`, `completed` or `failed`
+    pub status: String,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    /// Clicks in the export file, once completed
+    pub click_count: u64,
+    /// Why the export failed
+    pub error: Option<String>,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+}
+
+impl From<ExportJob> for AnalyticsExportResponse {
+    fn from(job: ExportJob) -> Self {
+        Self {
+            id: job.id,
+            format: job.format,
+            status: job.status.as_str().to_string(),
+            start_date: job.date_range.start.map(|date| date.to_rfc3339()),
+            end_date: job.date_range.end.map(|date| date.to_rfc3339()),
+            click_count: job.click_count,
+            error: job.error,
+            created_at: job.created_at.to_rfc3339(),
+            completed_at: job.completed_at.map(|date| date.to_rfc3339()),
+        }
+    }
+}
+
+/// Response DTO listing the user's analytics exports
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnalyticsExportListResponse {
+    /// Exports of the last 24 hours, newest first
+    pub exports: Vec<AnalyticsExportResponse>,
+}
@@ -0,0 +1,50 @@
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::AnalyticsExportError;
+use axum::{http::StatusCode, Json};
+
+/// Map an analytics export error to an HTTP error response
+pub fn analytics_export_error_response(
+    error: &AnalyticsExportError,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code) = match error {
+        AnalyticsExportError::NotFound => (StatusCode::NOT_FOUND, "EXPORT_NOT_FOUND"),
+        AnalyticsExportError::NotReady => (StatusCode::CONFLICT, "EXPORT_NOT_READY"),
+        AnalyticsExportError::Repository(_)
+        | AnalyticsExportError::Jobs(_)
+        | AnalyticsExportError::Serialization(_)
+        | AnalyticsExportError::Storage(_) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR"),
+    };
+
+    let message = match status {
+        StatusCode::INTERNAL_SERVER_ERROR => "Internal server error".to_string(),
+        _ => error.to_string(),
+    };
+    let error_response = ErrorResponse {
+        error: code.to_string(),
+        message,
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::object_storage::ObjectStorageError;
+
+    #[test]
+    fn test_analytics_export_error_status_codes() {
+        let (status, Json(body)) = analytics_export_error_response(&AnalyticsExportError::NotFound);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.error, "EXPORT_NOT_FOUND");
+
+        let (status, _) = analytics_export_error_response(&AnalyticsExportError::NotReady);
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let (status, Json(body)) = analytics_export_error_response(&AnalyticsExportError::Storage(
+            ObjectStorageError::InvalidKey("a/../b".to_string()),
+        ));
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(!body.message.contains("a/../b"));
+    }
+}
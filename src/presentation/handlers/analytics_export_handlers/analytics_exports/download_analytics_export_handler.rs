@@ -0,0 +1,49 @@
+use super::analytics_export_errors::analytics_export_error_response;
+use crate::application::dto::ErrorResponse;
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Redirect,
+    Json,
+};
+use tracing::warn;
+
+/// Handler for downloading a completed analytics export
+///
+/// Redirects to a pre-signed URL of the export file, valid for one hour.
+#[utoipa::path(
+    get,
+    path = "/analytics/exports/{id}/download",
+    params(
+        ("id" = String, Path, description = "Export ID")
+    ),
+    responses(
+        (status = 307, description = "Redirect to the export file"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "Export not found", body = ErrorResponse),
+        (status = 409, description = "Export not completed", body = ErrorResponse),
+    ),
+    tag = "analytics"
+)]
+pub async fn download_analytics_export_handler(
+    State(app_state): State<ConcreteAppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<String>,
+) -> Result<Redirect, (StatusCode, Json<ErrorResponse>)> {
+    match app_state
+        .analytics_export_service
+        .download_url(user.id, &id)
+        .await
+    {
+        Ok(url) => Ok(Redirect::temporary(&url)),
+        Err(error) => {
+            warn!(
+                "Failed to download analytics export {} of user {}: {}",
+                id, user.id, error
+            );
+            Err(analytics_export_error_response(&error))
+        }
+    }
+}
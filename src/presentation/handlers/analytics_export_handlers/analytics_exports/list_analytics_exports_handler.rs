@@ -0,0 +1,39 @@
+use super::analytics_export_dtos::AnalyticsExportListResponse;
+use super::analytics_export_errors::analytics_export_error_response;
+use crate::application::dto::ErrorResponse;
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{extract::State, http::StatusCode, Json};
+use tracing::warn;
+
+/// Handler for listing the authenticated user's analytics exports
+#[utoipa::path(
+    get,
+    path = "/analytics/exports",
+    responses(
+        (status = 200, description = "Analytics exports", body = AnalyticsExportListResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+    ),
+    tag = "analytics"
+)]
+pub async fn list_analytics_exports_handler(
+    State(app_state): State<ConcreteAppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<AnalyticsExportListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match app_state
+        .analytics_export_service
+        .list_exports(user.id)
+        .await
+    {
+        Ok(exports) => Ok(Json(AnalyticsExportListResponse {
+            exports: exports.into_iter().map(Into::into).collect(),
+        })),
+        Err(error) => {
+            warn!(
+                "Failed to list analytics exports of user {}: {}",
+                user.id, error
+            );
+            Err(analytics_export_error_response(&error))
+        }
+    }
+}
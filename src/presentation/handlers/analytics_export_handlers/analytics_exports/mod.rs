@@ -0,0 +1,12 @@
+// Re-export all analytics export handler functions and DTOs
+
+mod analytics_export_dtos;
+pub mod analytics_export_errors;
+pub mod download_analytics_export_handler;
+pub mod list_analytics_exports_handler;
+pub mod request_analytics_export_handler;
+
+pub use analytics_export_dtos::*;
+pub use download_analytics_export_handler::*;
+pub use list_analytics_exports_handler::*;
+pub use request_analytics_export_handler::*;
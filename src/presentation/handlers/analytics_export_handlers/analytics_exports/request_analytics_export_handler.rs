@@ -0,0 +1,73 @@
+use super::analytics_export_dtos::{AnalyticsExportQueuedResponse, RequestAnalyticsExportRequest};
+use crate::application::dto::ErrorResponse;
+use crate::domain::entities::DateRange;
+use crate::domain::services::ServiceError;
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{extract::State, http::StatusCode, Json};
+use tracing::{info, warn};
+
+/// Handler for exporting the raw click data of the authenticated user's URLs
+///
+/// The export runs in the background; its file is kept for 24 hours once it completes.
+#[utoipa::path(
+    post,
+    path = "/analytics/exports",
+    request_body = RequestAnalyticsExportRequest,
+    responses(
+        (status = 202, description = "Export queued", body = AnalyticsExportQueuedResponse),
+        (status = 400, description = "Invalid date range", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+    ),
+    tag = "analytics"
+)]
+pub async fn request_analytics_export_handler(
+    State(app_state): State<ConcreteAppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    ValidatedJson(request): ValidatedJson<RequestAnalyticsExportRequest>,
+) -> Result<(StatusCode, Json<AnalyticsExportQueuedResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let date_range = DateRange {
+        start: request.start_date,
+        end: request.end_date,
+    };
+
+    match app_state
+        .analytics_export_service
+        .request_export(user.id, request.format, date_range)
+        .await
+    {
+        Ok(operation_id) => {
+            info!(
+                "User {} requested analytics export {}",
+                user.id, operation_id
+            );
+            Ok((
+                StatusCode::ACCEPTED,
+                Json(AnalyticsExportQueuedResponse {
+                    operation_id,
+                    message: "Your export is being prepared".to_string(),
+                }),
+            ))
+        }
+        Err(ServiceError::InvalidData(message)) => {
+            let error_response = ErrorResponse {
+                error: "VALIDATION_ERROR".to_string(),
+                message,
+                status_code: StatusCode::BAD_REQUEST.as_u16(),
+            };
+            Err((StatusCode::BAD_REQUEST, Json(error_response)))
+        }
+        Err(e) => {
+            warn!(
+                "Failed to queue analytics export for user {}: {}",
+                user.id, e
+            );
+            let error_response = ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: "Internal server error".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
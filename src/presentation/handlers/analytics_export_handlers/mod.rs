@@ -0,0 +1,4 @@
+// Re-export all analytics export handler functions from the analytics_exports module
+pub mod analytics_exports;
+
+pub use analytics_exports::*;
@@ -1,22 +1,49 @@
-use crate::application::ShortenUrlUseCase;
+use crate::application::{
+    GetUrlAnalyticsUseCase, ListUrlsUseCase, ShortenUrlUseCase, UpdateUrlUseCase,
+};
 use crate::domain::repositories::{
-    AccountDeletionTokenRepository, PasswordResetRepository, UrlRepository, UserRepository,
+    AccountDeletionTokenRepository, ClickRepository, EmailChangeRepository, MagicLinkRepository,
+    OrganizationRepository, PasswordResetRepository, UrlRepository, UserRepository,
+};
+use crate::domain::services::cleanup_service::CleanupService;
+use crate::domain::services::click_tracking_service::ClickTrackingService;
+use crate::domain::services::{
+    AnalyticsExportService, AuthService, BulkProcessor, DataExportService, DomainBlacklist,
+    InterstitialService, LinkPreviewService, NotificationService, OAuthService, OrgService,
+    ProgressService, ServiceAccountService, UrlService,
 };
-use crate::domain::services::{AuthService, BulkProcessor, ProgressService, UrlService};
+use crate::infrastructure::cache_health_check::CacheHealthCheck;
+use crate::infrastructure::config::FeatureFlags;
+use crate::infrastructure::config::{ClickCookieConfig, RetentionConfig};
+use crate::infrastructure::database::DatabaseHealthCheck;
 use crate::infrastructure::email::EmailSender;
+use crate::infrastructure::http::RealIpExtractor;
+use crate::infrastructure::object_storage::ObjectStorage;
+use crate::infrastructure::rate_limiting::{
+    create_service_account_rate_limiter, IpReputationService, ServiceAccountRateLimiter,
+};
+use crate::infrastructure::server_info::ServerInfo;
+use crate::infrastructure::tls::TlsCertificate;
 use crate::infrastructure::PasswordResetRateLimiter;
 use std::sync::Arc;
+use thiserror::Error;
 
 /// Application state that contains both use cases and repositories
 #[derive(Clone)]
-pub struct AppState<R, U, P, A>
+pub struct AppState<R, U, P, A, C, O, M>
 where
     R: UrlRepository + Send + Sync + Clone + 'static,
     U: UserRepository + Send + Sync + Clone + 'static,
     P: PasswordResetRepository + Send + Sync + Clone,
     A: AccountDeletionTokenRepository + Send + Sync + Clone,
+    C: ClickRepository + Send + Sync + Clone + 'static,
+    O: OrganizationRepository + Send + Sync + Clone + 'static,
+    M: MagicLinkRepository + Send + Sync + Clone + 'static,
 {
     pub shorten_url_use_case: ShortenUrlUseCase<R>,
+    pub update_url_use_case: UpdateUrlUseCase<R>,
+    pub list_urls_use_case: ListUrlsUseCase<R>,
+    pub get_url_analytics_use_case: GetUrlAnalyticsUseCase<R, C>,
     pub url_repository: R,
     pub url_service: UrlService<R>,
     pub auth_service: AuthService<U>,
@@ -27,36 +54,534 @@ where
     pub account_deletion_repository: A,
     pub email_sender: Option<Arc<dyn EmailSender>>,
     pub password_reset_rate_limiter: Arc<PasswordResetRateLimiter>,
+    pub click_tracking_service: ClickTrackingService<C>,
+    pub database_health: DatabaseHealthCheck,
+    /// Pings Redis for readiness probes; `None` when Redis is not configured
+    pub cache_health: Option<CacheHealthCheck>,
+    pub organization_repository: O,
+    pub org_service: OrgService<O>,
+    pub real_ip_extractor: RealIpExtractor,
+    pub magic_link_repository: M,
+    pub magic_link_rate_limiter: Arc<PasswordResetRateLimiter>,
+    /// Furthest a URL expiration may be set into the future, in days
+    pub max_expiration_days: u32,
+    pub domain_blacklist: DomainBlacklist,
+    /// Cookie set on redirects so goal pages can report conversions
+    pub click_cookie: ClickCookieConfig,
+    pub data_export_service: DataExportService,
+    /// Exports of raw click data, run on the bulk processor
+    pub analytics_export_service: AnalyticsExportService,
+    pub service_account_service: ServiceAccountService,
+    pub service_account_rate_limiter: Arc<ServiceAccountRateLimiter>,
+    pub link_preview_service: LinkPreviewService,
+    /// How long the cleanup service keeps each kind of data
+    pub retention: RetentionConfig,
+    /// Removes data past its retention, on a schedule or when an administrator asks
+    pub cleanup_service: CleanupService<R>,
+    pub notification_service: NotificationService,
+    pub oauth_service: OAuthService,
+    /// Decides when redirects show the preview interstitial first
+    pub interstitial_service: InterstitialService,
+    /// Certificate of the HTTPS listener; `None` when serving plain HTTP
+    pub tls_certificate: Option<TlsCertificate>,
+    /// Where uploaded profile pictures are stored
+    pub object_storage: Arc<dyn ObjectStorage>,
+    /// Pending email changes awaiting confirmation from both addresses
+    pub email_change_repository: Arc<dyn EmailChangeRepository>,
+    /// Scores the IPs of redirecting clients; `None` when AbuseIPDB checks are off
+    pub ip_reputation_service: Option<IpReputationService>,
+    /// Configuration summary served by `GET /info`
+    pub server_info: ServerInfo,
+    /// Features enabled on this instance
+    pub features: FeatureFlags,
+}
+
+/// Furthest into the future a URL may expire when the builder is not given a limit
+const DEFAULT_MAX_EXPIRATION_DAYS: u32 = 3650;
+
+/// Error returned when an `AppState` cannot be built
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BuildError {
+    #[error("Missing dependency: {0}")]
+    MissingDependency(&'static str),
+}
+
+/// Assembles an `AppState` one dependency at a time
+///
+/// Repositories and services without a sensible default must be set; `build` names the first
+/// one missing. Everything else (rate limiters, retention, cookie settings, e-mail and TLS)
+/// falls back to its default, so variants such as a state without e-mail sending only set
+/// what they need.
+pub struct AppStateBuilder<R, U, P, A, C, O, M>
+where
+    R: UrlRepository + Send + Sync + Clone + 'static,
+    U: UserRepository + Send + Sync + Clone + 'static,
+    P: PasswordResetRepository + Send + Sync + Clone,
+    A: AccountDeletionTokenRepository + Send + Sync + Clone,
+    C: ClickRepository + Send + Sync + Clone + 'static,
+    O: OrganizationRepository + Send + Sync + Clone + 'static,
+    M: MagicLinkRepository + Send + Sync + Clone + 'static,
+{
+    shorten_url_use_case: Option<ShortenUrlUseCase<R>>,
+    update_url_use_case: Option<UpdateUrlUseCase<R>>,
+    list_urls_use_case: Option<ListUrlsUseCase<R>>,
+    get_url_analytics_use_case: Option<GetUrlAnalyticsUseCase<R, C>>,
+    url_repository: Option<R>,
+    url_service: Option<UrlService<R>>,
+    auth_service: Option<AuthService<U>>,
+    user_repository: Option<U>,
+    password_reset_repository: Option<P>,
+    account_deletion_repository: Option<A>,
+    email_sender: Option<Arc<dyn EmailSender>>,
+    password_reset_rate_limiter: Option<Arc<PasswordResetRateLimiter>>,
+    click_tracking_service: Option<ClickTrackingService<C>>,
+    database_health: Option<DatabaseHealthCheck>,
+    cache_health: Option<CacheHealthCheck>,
+    organization_repository: Option<O>,
+    org_service: Option<OrgService<O>>,
+    real_ip_extractor: Option<RealIpExtractor>,
+    magic_link_repository: Option<M>,
+    magic_link_rate_limiter: Option<Arc<PasswordResetRateLimiter>>,
+    max_expiration_days: Option<u32>,
+    domain_blacklist: Option<DomainBlacklist>,
+    click_cookie: Option<ClickCookieConfig>,
+    data_export_service: Option<DataExportService>,
+    analytics_export_service: Option<AnalyticsExportService>,
+    service_account_service: Option<ServiceAccountService>,
+    service_account_rate_limiter: Option<Arc<ServiceAccountRateLimiter>>,
+    link_preview_service: Option<LinkPreviewService>,
+    retention: Option<RetentionConfig>,
+    cleanup_service: Option<CleanupService<R>>,
+    notification_service: Option<NotificationService>,
+    oauth_service: Option<OAuthService>,
+    interstitial_service: Option<InterstitialService>,
+    tls_certificate: Option<TlsCertificate>,
+    object_storage: Option<Arc<dyn ObjectStorage>>,
+    email_change_repository: Option<Arc<dyn EmailChangeRepository>>,
+    ip_reputation_service: Option<IpReputationService>,
+    server_info: Option<ServerInfo>,
+    features: Option<FeatureFlags>,
+}
+
+impl<R, U, P, A, C, O, M> Default for AppStateBuilder<R, U, P, A, C, O, M>
+where
+    R: UrlRepository + Send + Sync + Clone + 'static,
+    U: UserRepository + Send + Sync + Clone + 'static,
+    P: PasswordResetRepository + Send + Sync + Clone,
+    A: AccountDeletionTokenRepository + Send + Sync + Clone,
+    C: ClickRepository + Send + Sync + Clone + 'static,
+    O: OrganizationRepository + Send + Sync + Clone + 'static,
+    M: MagicLinkRepository + Send + Sync + Clone + 'static,
+{
+    fn default() -> Self {
+        Self {
+            shorten_url_use_case: None,
+            update_url_use_case: None,
+            list_urls_use_case: None,
+            get_url_analytics_use_case: None,
+            url_repository: None,
+            url_service: None,
+            auth_service: None,
+            user_repository: None,
+            password_reset_repository: None,
+            account_deletion_repository: None,
+            email_sender: None,
+            password_reset_rate_limiter: None,
+            click_tracking_service: None,
+            database_health: None,
+            cache_health: None,
+            organization_repository: None,
+            org_service: None,
+            real_ip_extractor: None,
+            magic_link_repository: None,
+            magic_link_rate_limiter: None,
+            max_expiration_days: None,
+            domain_blacklist: None,
+            click_cookie: None,
+            data_export_service: None,
+            analytics_export_service: None,
+            service_account_service: None,
+            service_account_rate_limiter: None,
+            link_preview_service: None,
+            retention: None,
+            cleanup_service: None,
+            notification_service: None,
+            oauth_service: None,
+            interstitial_service: None,
+            tls_certificate: None,
+            object_storage: None,
+            email_change_repository: None,
+            ip_reputation_service: None,
+            server_info: None,
+            features: None,
+        }
+    }
 }
 
-impl<R, U, P, A> AppState<R, U, P, A>
+impl<R, U, P, A, C, O, M> AppStateBuilder<R, U, P, A, C, O, M>
 where
     R: UrlRepository + Send + Sync + Clone + 'static,
     U: UserRepository + Send + Sync + Clone + 'static,
     P: PasswordResetRepository + Send + Sync + Clone,
     A: AccountDeletionTokenRepository + Send + Sync + Clone,
+    C: ClickRepository + Send + Sync + Clone + 'static,
+    O: OrganizationRepository + Send + Sync + Clone + 'static,
+    M: MagicLinkRepository + Send + Sync + Clone + 'static,
 {
-    #[allow(clippy::too_many_arguments)]
-    pub fn new(
-        shorten_url_use_case: ShortenUrlUseCase<R>,
-        url_repository: R,
-        url_service: UrlService<R>,
-        auth_service: AuthService<U>,
-        user_repository: U,
-        password_reset_repository: P,
-        account_deletion_repository: A,
-        email_sender: Option<Arc<dyn EmailSender>>,
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shorten_url_use_case(mut self, shorten_url_use_case: ShortenUrlUseCase<R>) -> Self {
+        self.shorten_url_use_case = Some(shorten_url_use_case);
+        self
+    }
+
+    pub fn update_url_use_case(mut self, update_url_use_case: UpdateUrlUseCase<R>) -> Self {
+        self.update_url_use_case = Some(update_url_use_case);
+        self
+    }
+
+    pub fn list_urls_use_case(mut self, list_urls_use_case: ListUrlsUseCase<R>) -> Self {
+        self.list_urls_use_case = Some(list_urls_use_case);
+        self
+    }
+
+    pub fn get_url_analytics_use_case(
+        mut self,
+        get_url_analytics_use_case: GetUrlAnalyticsUseCase<R, C>,
+    ) -> Self {
+        self.get_url_analytics_use_case = Some(get_url_analytics_use_case);
+        self
+    }
+
+    pub fn url_repository(mut self, url_repository: R) -> Self {
+        self.url_repository = Some(url_repository);
+        self
+    }
+
+    pub fn url_service(mut self, url_service: UrlService<R>) -> Self {
+        self.url_service = Some(url_service);
+        self
+    }
+
+    pub fn auth_service(mut self, auth_service: AuthService<U>) -> Self {
+        self.auth_service = Some(auth_service);
+        self
+    }
+
+    pub fn user_repository(mut self, user_repository: U) -> Self {
+        self.user_repository = Some(user_repository);
+        self
+    }
+
+    pub fn password_reset_repository(mut self, password_reset_repository: P) -> Self {
+        self.password_reset_repository = Some(password_reset_repository);
+        self
+    }
+
+    pub fn account_deletion_repository(mut self, account_deletion_repository: A) -> Self {
+        self.account_deletion_repository = Some(account_deletion_repository);
+        self
+    }
+
+    /// Send e-mails through this sender; `None`, the default, sends none
+    pub fn email_sender(mut self, email_sender: Option<Arc<dyn EmailSender>>) -> Self {
+        self.email_sender = email_sender;
+        self
+    }
+
+    /// Defaults to `PasswordResetRateLimiter::new_default()`
+    pub fn password_reset_rate_limiter(
+        mut self,
         password_reset_rate_limiter: Arc<PasswordResetRateLimiter>,
     ) -> Self {
+        self.password_reset_rate_limiter = Some(password_reset_rate_limiter);
+        self
+    }
+
+    pub fn click_tracking_service(
+        mut self,
+        click_tracking_service: ClickTrackingService<C>,
+    ) -> Self {
+        self.click_tracking_service = Some(click_tracking_service);
+        self
+    }
+
+    pub fn database_health(mut self, database_health: DatabaseHealthCheck) -> Self {
+        self.database_health = Some(database_health);
+        self
+    }
+
+    /// Report Redis in readiness probes; `None`, the default, leaves it out
+    pub fn cache_health(mut self, cache_health: Option<CacheHealthCheck>) -> Self {
+        self.cache_health = cache_health;
+        self
+    }
+
+    pub fn organization_repository(mut self, organization_repository: O) -> Self {
+        self.organization_repository = Some(organization_repository);
+        self
+    }
+
+    pub fn org_service(mut self, org_service: OrgService<O>) -> Self {
+        self.org_service = Some(org_service);
+        self
+    }
+
+    /// Defaults to trusting no proxy's forwarding headers
+    pub fn real_ip_extractor(mut self, real_ip_extractor: RealIpExtractor) -> Self {
+        self.real_ip_extractor = Some(real_ip_extractor);
+        self
+    }
+
+    pub fn magic_link_repository(mut self, magic_link_repository: M) -> Self {
+        self.magic_link_repository = Some(magic_link_repository);
+        self
+    }
+
+    /// Defaults to `PasswordResetRateLimiter::new_default()`
+    pub fn magic_link_rate_limiter(
+        mut self,
+        magic_link_rate_limiter: Arc<PasswordResetRateLimiter>,
+    ) -> Self {
+        self.magic_link_rate_limiter = Some(magic_link_rate_limiter);
+        self
+    }
+
+    /// Furthest a URL expiration may be set into the future; defaults to 3650 days
+    pub fn max_expiration_days(mut self, max_expiration_days: u32) -> Self {
+        self.max_expiration_days = Some(max_expiration_days);
+        self
+    }
+
+    pub fn domain_blacklist(mut self, domain_blacklist: DomainBlacklist) -> Self {
+        self.domain_blacklist = Some(domain_blacklist);
+        self
+    }
+
+    /// Defaults to `ClickCookieConfig::default()`
+    pub fn click_cookie(mut self, click_cookie: ClickCookieConfig) -> Self {
+        self.click_cookie = Some(click_cookie);
+        self
+    }
+
+    pub fn data_export_service(mut self, data_export_service: DataExportService) -> Self {
+        self.data_export_service = Some(data_export_service);
+        self
+    }
+
+    /// Service for analytics exports; `build` has it run exports on the bulk processor
+    pub fn analytics_export_service(
+        mut self,
+        analytics_export_service: AnalyticsExportService,
+    ) -> Self {
+        self.analytics_export_service = Some(analytics_export_service);
+        self
+    }
+
+    pub fn service_account_service(
+        mut self,
+        service_account_service: ServiceAccountService,
+    ) -> Self {
+        self.service_account_service = Some(service_account_service);
+        self
+    }
+
+    /// Defaults to `create_service_account_rate_limiter()`
+    pub fn service_account_rate_limiter(
+        mut self,
+        service_account_rate_limiter: Arc<ServiceAccountRateLimiter>,
+    ) -> Self {
+        self.service_account_rate_limiter = Some(service_account_rate_limiter);
+        self
+    }
+
+    pub fn link_preview_service(mut self, link_preview_service: LinkPreviewService) -> Self {
+        self.link_preview_service = Some(link_preview_service);
+        self
+    }
+
+    /// Defaults to `RetentionConfig::default()`
+    pub fn retention(mut self, retention: RetentionConfig) -> Self {
+        self.retention = Some(retention);
+        self
+    }
+
+    /// The built state's bulk operation progress is cleaned up by it too
+    pub fn cleanup_service(mut self, cleanup_service: CleanupService<R>) -> Self {
+        self.cleanup_service = Some(cleanup_service);
+        self
+    }
+
+    /// Defaults to a notification service that sends no e-mails
+    pub fn notification_service(mut self, notification_service: NotificationService) -> Self {
+        self.notification_service = Some(notification_service);
+        self
+    }
+
+    pub fn oauth_service(mut self, oauth_service: OAuthService) -> Self {
+        self.oauth_service = Some(oauth_service);
+        self
+    }
+
+    pub fn interstitial_service(mut self, interstitial_service: InterstitialService) -> Self {
+        self.interstitial_service = Some(interstitial_service);
+        self
+    }
+
+    /// Certificate of the HTTPS listener; `None`, the default, serves plain HTTP
+    pub fn tls_certificate(mut self, tls_certificate: Option<TlsCertificate>) -> Self {
+        self.tls_certificate = tls_certificate;
+        self
+    }
+
+    pub fn object_storage(mut self, object_storage: Arc<dyn ObjectStorage>) -> Self {
+        self.object_storage = Some(object_storage);
+        self
+    }
+
+    pub fn email_change_repository(
+        mut self,
+        email_change_repository: Arc<dyn EmailChangeRepository>,
+    ) -> Self {
+        self.email_change_repository = Some(email_change_repository);
+        self
+    }
+
+    /// Check redirecting IPs with this service; `None`, the default, trusts every IP
+    pub fn ip_reputation_service(
+        mut self,
+        ip_reputation_service: Option<IpReputationService>,
+    ) -> Self {
+        self.ip_reputation_service = ip_reputation_service;
+        self
+    }
+
+    /// Defaults to an empty `ServerInfo`
+    pub fn server_info(mut self, server_info: ServerInfo) -> Self {
+        self.server_info = Some(server_info);
+        self
+    }
+
+    /// Defaults to `FeatureFlags::default()`
+    pub fn features(mut self, features: FeatureFlags) -> Self {
+        self.features = Some(features);
+        self
+    }
+
+    /// Build the state, or report the first required dependency that was not set
+    #[allow(clippy::type_complexity)]
+    pub fn build(self) -> Result<AppState<R, U, P, A, C, O, M>, BuildError> {
+        let shorten_url_use_case = self
+            .shorten_url_use_case
+            .ok_or(BuildError::MissingDependency("shorten_url_use_case"))?;
+        let update_url_use_case = self
+            .update_url_use_case
+            .ok_or(BuildError::MissingDependency("update_url_use_case"))?;
+        let list_urls_use_case = self
+            .list_urls_use_case
+            .ok_or(BuildError::MissingDependency("list_urls_use_case"))?;
+        let get_url_analytics_use_case = self
+            .get_url_analytics_use_case
+            .ok_or(BuildError::MissingDependency("get_url_analytics_use_case"))?;
+        let url_repository = self
+            .url_repository
+            .ok_or(BuildError::MissingDependency("url_repository"))?;
+        let url_service = self
+            .url_service
+            .ok_or(BuildError::MissingDependency("url_service"))?;
+        let auth_service = self
+            .auth_service
+            .ok_or(BuildError::MissingDependency("auth_service"))?;
+        let user_repository = self
+            .user_repository
+            .ok_or(BuildError::MissingDependency("user_repository"))?;
+        let password_reset_repository = self
+            .password_reset_repository
+            .ok_or(BuildError::MissingDependency("password_reset_repository"))?;
+        let account_deletion_repository = self
+            .account_deletion_repository
+            .ok_or(BuildError::MissingDependency("account_deletion_repository"))?;
+        let password_reset_rate_limiter = self
+            .password_reset_rate_limiter
+            .unwrap_or_else(|| Arc::new(PasswordResetRateLimiter::new_default()));
+        let click_tracking_service = self
+            .click_tracking_service
+            .ok_or(BuildError::MissingDependency("click_tracking_service"))?;
+        let database_health = self
+            .database_health
+            .ok_or(BuildError::MissingDependency("database_health"))?;
+        let organization_repository = self
+            .organization_repository
+            .ok_or(BuildError::MissingDependency("organization_repository"))?;
+        let org_service = self
+            .org_service
+            .ok_or(BuildError::MissingDependency("org_service"))?;
+        let real_ip_extractor = self.real_ip_extractor.unwrap_or_default();
+        let magic_link_repository = self
+            .magic_link_repository
+            .ok_or(BuildError::MissingDependency("magic_link_repository"))?;
+        let magic_link_rate_limiter = self
+            .magic_link_rate_limiter
+            .unwrap_or_else(|| Arc::new(PasswordResetRateLimiter::new_default()));
+        let max_expiration_days = self
+            .max_expiration_days
+            .unwrap_or(DEFAULT_MAX_EXPIRATION_DAYS);
+        let domain_blacklist = self
+            .domain_blacklist
+            .ok_or(BuildError::MissingDependency("domain_blacklist"))?;
+        let click_cookie = self.click_cookie.unwrap_or_default();
+        let data_export_service = self
+            .data_export_service
+            .ok_or(BuildError::MissingDependency("data_export_service"))?;
+        let analytics_export_service = self
+            .analytics_export_service
+            .ok_or(BuildError::MissingDependency("analytics_export_service"))?;
+        let service_account_service = self
+            .service_account_service
+            .ok_or(BuildError::MissingDependency("service_account_service"))?;
+        let service_account_rate_limiter = self
+            .service_account_rate_limiter
+            .unwrap_or_else(|| Arc::new(create_service_account_rate_limiter()));
+        let link_preview_service = self
+            .link_preview_service
+            .ok_or(BuildError::MissingDependency("link_preview_service"))?;
+        let retention = self.retention.unwrap_or_default();
+        let cleanup_service = self
+            .cleanup_service
+            .ok_or(BuildError::MissingDependency("cleanup_service"))?;
+        let notification_service = self.notification_service.unwrap_or_default();
+        let oauth_service = self
+            .oauth_service
+            .ok_or(BuildError::MissingDependency("oauth_service"))?;
+        let interstitial_service = self
+            .interstitial_service
+            .ok_or(BuildError::MissingDependency("interstitial_service"))?;
+        let object_storage = self
+            .object_storage
+            .ok_or(BuildError::MissingDependency("object_storage"))?;
+        let email_change_repository = self
+            .email_change_repository
+            .ok_or(BuildError::MissingDependency("email_change_repository"))?;
         let progress_service = ProgressService::new();
         let bulk_processor = BulkProcessor::new(
             url_service.clone(),
             progress_service.clone(),
             user_repository.clone(),
-        );
+        )
+        .with_notification_service(notification_service.clone());
+        let analytics_export_service =
+            analytics_export_service.with_task_queue(bulk_processor.task_queue());
+        let cleanup_service = cleanup_service
+            .with_progress_service(progress_service.clone())
+            .with_analytics_export_service(analytics_export_service.clone());
 
-        Self {
+        Ok(AppState {
             shorten_url_use_case,
+            update_url_use_case,
+            list_urls_use_case,
+            get_url_analytics_use_case,
             url_repository,
             url_service,
             auth_service,
@@ -65,8 +590,87 @@ where
             bulk_processor,
             password_reset_repository,
             account_deletion_repository,
-            email_sender,
+            email_sender: self.email_sender,
             password_reset_rate_limiter,
-        }
+            click_tracking_service,
+            database_health,
+            cache_health: self.cache_health,
+            organization_repository,
+            org_service,
+            real_ip_extractor,
+            magic_link_repository,
+            magic_link_rate_limiter,
+            max_expiration_days,
+            domain_blacklist,
+            click_cookie,
+            data_export_service,
+            analytics_export_service,
+            service_account_service,
+            service_account_rate_limiter,
+            link_preview_service,
+            retention,
+            cleanup_service,
+            notification_service,
+            oauth_service,
+            interstitial_service,
+            tls_certificate: self.tls_certificate,
+            object_storage,
+            email_change_repository,
+            ip_reputation_service: self.ip_reputation_service,
+            server_info: self.server_info.unwrap_or_default(),
+            features: self.features.unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_utils::TestApp;
+    use crate::presentation::handlers::ConcreteAppState;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_build_reports_first_missing_dependency() {
+        let result: Result<ConcreteAppState, BuildError> = AppStateBuilder::new().build();
+        let error = result.err().unwrap();
+        assert_eq!(error, BuildError::MissingDependency("shorten_url_use_case"));
+        assert_eq!(
+            error.to_string(),
+            "Missing dependency: shorten_url_use_case"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_test_app_serves_the_real_routes_over_mocks() {
+        let app = TestApp::new();
+        app.url_repository
+            .create_url(
+                &crate::domain::entities::ShortCode::new("mocked1".to_string()).unwrap(),
+                "https://example.com/mocked",
+                None,
+                None,
+                None,
+                crate::domain::entities::UrlStatus::Active,
+            )
+            .await
+            .unwrap();
+
+        let response = app
+            .router()
+            .oneshot(Request::get("/mocked1").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()["location"], "https://example.com/mocked");
+
+        let response = app
+            .router()
+            .oneshot(Request::get("/missing1").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 }
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 /// Request DTO for user registration
-#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[derive(Debug, Deserialize, utoipa::ToSchema, Validate)]
 pub struct RegisterRequest {
     pub username: String,
     pub email: String,
@@ -9,7 +10,7 @@ pub struct RegisterRequest {
 }
 
 /// Request DTO for user login
-#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[derive(Debug, Deserialize, utoipa::ToSchema, Validate)]
 pub struct LoginRequest {
     pub username: String,
     pub password: String,
@@ -31,6 +32,50 @@ pub struct UserResponse {
     pub created_at: String,
 }
 
+/// Query parameters of the email verification endpoint
+#[derive(Debug, Deserialize)]
+pub struct VerifyEmailQuery {
+    /// Token from the link in the welcome email
+    pub token: String,
+}
+
+/// Response DTO for a verified email address
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct VerifyEmailResponse {
+    pub message: String,
+    pub email: String,
+}
+
+/// Request DTO for token introspection (RFC 7662)
+#[derive(Debug, Deserialize, utoipa::ToSchema, Validate)]
+pub struct IntrospectTokenRequest {
+    pub token: String,
+    /// Accepted for RFC 7662 compatibility; only access tokens exist
+    #[allow(dead_code)]
+    pub token_type_hint: Option<String>,
+}
+
+/// Response DTO for token introspection (RFC 7662)
+///
+/// Inactive tokens only carry `active: false`.
+#[derive(Debug, Default, Serialize, utoipa::ToSchema)]
+pub struct IntrospectTokenResponse {
+    pub active: bool,
+    /// User ID the token was issued to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
+}
+
 /// Error response DTO
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
@@ -38,3 +83,28 @@ pub struct ErrorResponse {
     pub message: String,
     pub status_code: u16,
 }
+
+/// Response DTO describing one login session
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct SessionResponse {
+    pub id: String,
+    /// Readable description of the client, e.g. "Chrome on macOS"
+    pub device_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: String,
+    pub last_used_at: String,
+    /// Whether this is the session of the token making the request
+    pub current: bool,
+}
+
+/// Response DTO listing a user's active sessions
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct SessionsResponse {
+    pub sessions: Vec<SessionResponse>,
+}
+
+/// Response DTO for revoking all other sessions
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RevokeSessionsResponse {
+    pub revoked: usize,
+}
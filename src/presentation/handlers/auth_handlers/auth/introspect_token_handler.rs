@@ -0,0 +1,174 @@
+use super::dtos::{ErrorResponse, IntrospectTokenRequest, IntrospectTokenResponse};
+use crate::domain::services::auth_service::TokenIntrospection;
+use crate::domain::services::ServiceAccountError;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Header carrying a service account's API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+impl From<Option<TokenIntrospection>> for IntrospectTokenResponse {
+    fn from(introspection: Option<TokenIntrospection>) -> Self {
+        match introspection {
+            Some(token) => Self {
+                active: true,
+                sub: Some(token.user_id.to_string()),
+                username: Some(token.username),
+                email: Some(token.email),
+                exp: Some(token.exp),
+                iat: Some(token.iat),
+                scopes: Some(token.scopes),
+            },
+            None => Self::default(),
+        }
+    }
+}
+
+fn error_response(
+    status: StatusCode,
+    code: &str,
+    message: &str,
+) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error: code.to_string(),
+            message: message.to_string(),
+            status_code: status.as_u16(),
+        }),
+    )
+}
+
+/// Handler for token introspection by other services (RFC 7662)
+///
+/// Authenticated with a service account API key in `X-API-Key`, so services can check user
+/// tokens without sharing the JWT secret. Limited to 1000 requests per minute per account.
+#[utoipa::path(
+    post,
+    path = "/auth/introspect",
+    request_body = IntrospectTokenRequest,
+    params(
+        ("X-API-Key" = String, Header, description = "Service account API key")
+    ),
+    responses(
+        (status = 200, description = "Token state; inactive tokens only report `active: false`", body = IntrospectTokenResponse),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 429, description = "Service account rate limit exceeded", body = ErrorResponse)
+    ),
+    tag = "authentication"
+)]
+pub async fn introspect_token_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<IntrospectTokenRequest>,
+) -> Result<(StatusCode, Json<IntrospectTokenResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let api_key = headers
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+
+    let service_account = match app_state
+        .service_account_service
+        .authenticate(api_key)
+        .await
+    {
+        Ok(account) => account,
+        Err(ServiceAccountError::InvalidApiKey) => {
+            warn!("Token introspection with an invalid API key");
+            return Err(error_response(
+                StatusCode::UNAUTHORIZED,
+                "UNAUTHORIZED",
+                "Missing or invalid API key",
+            ));
+        }
+        Err(e) => {
+            warn!("Failed to authenticate service account: {}", e);
+            return Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_ERROR",
+                "Failed to authenticate service account",
+            ));
+        }
+    };
+
+    if app_state
+        .service_account_rate_limiter
+        .check_key(&service_account.id)
+        .is_err()
+    {
+        warn!(
+            "Service account {} exceeded the introspection rate limit",
+            service_account.id
+        );
+        return Err(error_response(
+            StatusCode::TOO_MANY_REQUESTS,
+            "RATE_LIMIT_EXCEEDED",
+            "Too many requests. Please try again later.",
+        ));
+    }
+
+    let introspection = match app_state
+        .auth_service
+        .introspect_token(&request.token)
+        .await
+    {
+        Ok(introspection) => introspection,
+        Err(e) => {
+            warn!("Token introspection failed: {}", e);
+            return Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_ERROR",
+                "Failed to introspect token",
+            ));
+        }
+    };
+
+    info!(
+        "Service account {} ({}) introspected a token: active={}, user={:?}",
+        service_account.id,
+        service_account.name,
+        introspection.is_some(),
+        introspection.as_ref().map(|token| token.user_id)
+    );
+
+    Ok((
+        StatusCode::OK,
+        Json(IntrospectTokenResponse::from(introspection)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inactive_response_only_reports_active() {
+        let response = IntrospectTokenResponse::from(None);
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({ "active": false })
+        );
+    }
+
+    #[test]
+    fn test_active_response() {
+        let response = IntrospectTokenResponse::from(Some(TokenIntrospection {
+            user_id: 7,
+            username: "alice".to_string(),
+            email: "alice@example.com".to_string(),
+            exp: 2000,
+            iat: 1000,
+            scopes: vec!["user".to_string()],
+        }));
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["active"], true);
+        assert_eq!(json["sub"], "7");
+        assert_eq!(json["email"], "alice@example.com");
+        assert_eq!(json["scopes"], serde_json::json!(["user"]));
+    }
+}
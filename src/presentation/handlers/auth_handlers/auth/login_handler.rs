@@ -1,6 +1,14 @@
 use super::dtos::{AuthResponse, ErrorResponse, LoginRequest, UserResponse};
-use crate::presentation::handlers::ConcreteAppState;
-use axum::{extract::State, http::StatusCode, Json};
+use super::sessions_handler::session_client;
+use super::token_errors::token_error_response;
+use crate::domain::services::AuthServiceError;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use std::net::SocketAddr;
 use tracing::{info, warn};
 
 /// Handler for user login
@@ -10,19 +18,27 @@ use tracing::{info, warn};
     request_body = LoginRequest,
     responses(
         (status = 200, description = "Login successful", body = AuthResponse),
-        (status = 401, description = "Invalid credentials", body = ErrorResponse)
+        (status = 401, description = "Invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse)
     ),
     tag = "authentication"
 )]
 pub async fn login_handler(
     State(app_state): State<ConcreteAppState>,
-    Json(request): Json<LoginRequest>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<LoginRequest>,
 ) -> Result<(StatusCode, Json<AuthResponse>), (StatusCode, Json<ErrorResponse>)> {
     info!("Received login request for username: {}", request.username);
 
+    let client = session_client(
+        &app_state,
+        connect_info.map(|ConnectInfo(addr)| addr.ip()),
+        &headers,
+    );
     match app_state
         .auth_service
-        .login(&request.username, &request.password)
+        .login(&request.username, &request.password, &client)
         .await
     {
         Ok(token) => {
@@ -52,6 +68,16 @@ pub async fn login_handler(
                 }
             }
         }
+        Err(error @ AuthServiceError::AccountSuspended { .. }) => {
+            warn!("Rejected login for suspended user: {}", request.username);
+            let (status, Json(body)) = token_error_response(&error);
+            let error_response = ErrorResponse {
+                error: body.error,
+                message: body.message,
+                status_code: body.status_code,
+            };
+            Err((status, Json(error_response)))
+        }
         Err(error) => {
             warn!("Failed to login user: {}", error);
             let error_response = ErrorResponse {
@@ -1,9 +1,17 @@
 // Re-export all authentication handler functions and DTOs
 
 mod dtos;
+pub mod introspect_token_handler;
 pub mod login_handler;
 pub mod register_handler;
+pub mod sessions_handler;
+pub mod token_errors;
+pub mod verify_email_handler;
 
 pub use dtos::*;
+pub use introspect_token_handler::*;
 pub use login_handler::*;
 pub use register_handler::*;
+pub use sessions_handler::*;
+pub use token_errors::*;
+pub use verify_email_handler::*;
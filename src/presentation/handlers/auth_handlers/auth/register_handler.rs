@@ -1,41 +1,100 @@
 use super::dtos::{AuthResponse, ErrorResponse, RegisterRequest, UserResponse};
+use super::sessions_handler::session_client;
+use crate::domain::repositories::UserRepository;
 use crate::domain::services::AuthServiceError;
-use crate::presentation::handlers::ConcreteAppState;
-use axum::{extract::State, http::StatusCode, Json};
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use std::net::SocketAddr;
 use tracing::{info, warn};
 
 /// Handler for user registration
+///
+/// When email is configured, the new account waits for its owner to open the verification
+/// link of the welcome email.
 #[utoipa::path(
     post,
     path = "/register",
     request_body = RegisterRequest,
     responses(
         (status = 201, description = "User registered successfully", body = AuthResponse),
-        (status = 400, description = "Invalid input or user already exists", body = ErrorResponse)
+        (status = 400, description = "Invalid input or user already exists", body = ErrorResponse),
+        (status = 409, description = "Email already registered (case-insensitive)", body = ErrorResponse)
     ),
     tag = "authentication"
 )]
 pub async fn register_handler(
     State(app_state): State<ConcreteAppState>,
-    Json(request): Json<RegisterRequest>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<RegisterRequest>,
 ) -> Result<(StatusCode, Json<AuthResponse>), (StatusCode, Json<ErrorResponse>)> {
     info!(
         "Received registration request for username: {}",
         request.username
     );
 
+    // Emails are unique regardless of case, reject duplicates before creating the user
     match app_state
-        .auth_service
-        .register(&request.username, &request.email, &request.password)
+        .user_repository
+        .find_by_email(&request.email)
         .await
     {
+        Ok(Some(_)) => {
+            warn!("Registration rejected: email already registered");
+            let error_response = ErrorResponse {
+                error: "DUPLICATE_EMAIL".to_string(),
+                message: "An account with this email already exists".to_string(),
+                status_code: StatusCode::CONFLICT.as_u16(),
+            };
+            return Err((StatusCode::CONFLICT, Json(error_response)));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            warn!("Failed to check email uniqueness: {}", e);
+            let error_response = ErrorResponse {
+                error: "REGISTRATION_FAILED".to_string(),
+                message: "Registration failed".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)));
+        }
+    }
+
+    // With email configured, the welcome email is queued with the new account
+    let registration = if app_state.email_sender.is_some() {
+        app_state
+            .auth_service
+            .register_unverified(
+                &request.username,
+                &request.email,
+                &request.password,
+                app_state.shorten_url_use_case.base_url(),
+            )
+            .await
+    } else {
+        app_state
+            .auth_service
+            .register(&request.username, &request.email, &request.password)
+            .await
+    };
+
+    match registration {
         Ok(user) => {
             info!("Successfully registered user: {}", user.username);
 
             // Generate token for the newly registered user
+            let client = session_client(
+                &app_state,
+                connect_info.map(|ConnectInfo(addr)| addr.ip()),
+                &headers,
+            );
             match app_state
                 .auth_service
-                .login(&user.username, &request.password)
+                .login(&user.username, &request.password, &client)
                 .await
             {
                 Ok(token) => {
@@ -87,6 +146,71 @@ pub async fn register_handler(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::entities::AccountStatus;
+    use crate::infrastructure::test_utils::{MockEmailSender, TestApp};
+    use axum::body::Body;
+    use axum::http::{header, Request};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_registration_waits_for_the_welcome_email_link() {
+        let email_sender = MockEmailSender::new();
+        let app = TestApp::with_email_sender(Some(Arc::new(email_sender.clone())));
+
+        let response = app
+            .router()
+            .oneshot(
+                Request::post("/register")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(
+                        r#"{"username":"welcomed","email":"welcomed@example.com","password":"password123"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let user = app
+            .state
+            .user_repository
+            .find_by_username("welcomed")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(user.account_status, AccountStatus::PendingVerification);
+
+        // Queued with the account rather than sent by the request
+        assert!(email_sender.sent().is_empty());
+        let queued = app.user_repository.outbox().emails();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].recipient, "welcomed@example.com");
+        let verify_path = queued[0]
+            .body_text
+            .lines()
+            .find_map(|line| {
+                line.split_once("/auth/verify-email?")
+                    .map(|(_, query)| query)
+            })
+            .map(|query| format!("/auth/verify-email?{}", query))
+            .unwrap();
+
+        let response = app
+            .router()
+            .oneshot(Request::get(verify_path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let user = app
+            .state
+            .user_repository
+            .find_by_id(user.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(user.account_status, AccountStatus::Active);
+    }
 
     #[test]
     fn test_register_request_deserialize() {
@@ -122,6 +246,17 @@ mod tests {
         assert_eq!(error_response.status_code, 400);
     }
 
+    #[test]
+    fn test_duplicate_email_error() {
+        let error_response = ErrorResponse {
+            error: "DUPLICATE_EMAIL".to_string(),
+            message: "An account with this email already exists".to_string(),
+            status_code: StatusCode::CONFLICT.as_u16(),
+        };
+        assert_eq!(error_response.error, "DUPLICATE_EMAIL");
+        assert_eq!(error_response.status_code, 409);
+    }
+
     #[test]
     fn test_invalid_input_error() {
         let error_response = ErrorResponse {
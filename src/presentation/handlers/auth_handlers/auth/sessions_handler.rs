@@ -0,0 +1,214 @@
+use super::dtos::{RevokeSessionsResponse, SessionResponse, SessionsResponse};
+use super::token_errors::token_error_response;
+use crate::application::dto::ErrorResponse;
+use crate::domain::entities::{Session, SessionClient, User};
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use std::net::IpAddr;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Describe the client of a login request for its new session
+pub fn session_client(
+    app_state: &ConcreteAppState,
+    peer: Option<IpAddr>,
+    headers: &HeaderMap,
+) -> SessionClient {
+    SessionClient {
+        ip_address: app_state
+            .real_ip_extractor
+            .extract(peer, headers)
+            .map(|ip| ip.to_string()),
+        user_agent: headers
+            .get(header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string),
+    }
+}
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: &str,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message: message.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Authenticate the request, returning its user and the session of its token
+async fn authenticate(
+    app_state: &ConcreteAppState,
+    headers: &HeaderMap,
+) -> Result<(User, Option<Uuid>), (StatusCode, Json<ErrorResponse>)> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::UNAUTHORIZED,
+                "UNAUTHORIZED",
+                "Missing or invalid Authorization header",
+            )
+        })?;
+
+    app_state
+        .auth_service
+        .verify_token_with_session(token)
+        .await
+        .map_err(|e| {
+            warn!("Token verification failed: {}", e);
+            token_error_response(&e)
+        })
+}
+
+fn session_response(session: Session, current: Option<Uuid>) -> SessionResponse {
+    SessionResponse {
+        current: current == Some(session.id),
+        id: session.id.to_string(),
+        device_name: session.device_name,
+        ip_address: session.ip_address,
+        created_at: session.created_at.to_rfc3339(),
+        last_used_at: session.last_used_at.to_rfc3339(),
+    }
+}
+
+fn session_store_error(error: impl std::fmt::Display) -> (StatusCode, Json<ErrorResponse>) {
+    warn!("Session store failure: {}", error);
+    error_response(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "INTERNAL_ERROR",
+        "Failed to access sessions",
+    )
+}
+
+/// Handler listing the caller's active login sessions
+#[utoipa::path(
+    get,
+    path = "/auth/sessions",
+    responses(
+        (status = 200, description = "Active sessions, most recently used first", body = SessionsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+    ),
+    tag = "authentication"
+)]
+pub async fn list_sessions_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Json<SessionsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let (user, current) = authenticate(&app_state, &headers).await?;
+
+    let sessions = app_state
+        .auth_service
+        .list_sessions(user.id)
+        .await
+        .map_err(session_store_error)?;
+
+    Ok(Json(SessionsResponse {
+        sessions: sessions
+            .into_iter()
+            .map(|session| session_response(session, current))
+            .collect(),
+    }))
+}
+
+/// Handler revoking one of the caller's sessions
+///
+/// Its token stops working immediately. Revoking the current session logs the caller out.
+#[utoipa::path(
+    delete,
+    path = "/auth/sessions/{id}",
+    params(
+        ("id" = String, Path, description = "Session ID")
+    ),
+    responses(
+        (status = 204, description = "Session revoked"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "No such active session", body = ErrorResponse),
+    ),
+    tag = "authentication"
+)]
+pub async fn revoke_session_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let (user, _) = authenticate(&app_state, &headers).await?;
+
+    let not_found = || error_response(StatusCode::NOT_FOUND, "NOT_FOUND", "Session not found");
+    let session_id = Uuid::parse_str(&id).map_err(|_| not_found())?;
+
+    match app_state
+        .auth_service
+        .revoke_session(user.id, session_id)
+        .await
+    {
+        Ok(true) => {
+            info!("User {} revoked session {}", user.id, session_id);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Ok(false) => Err(not_found()),
+        Err(e) => Err(session_store_error(e)),
+    }
+}
+
+/// Handler revoking every session of the caller except the current one
+#[utoipa::path(
+    delete,
+    path = "/auth/sessions",
+    responses(
+        (status = 200, description = "Other sessions revoked", body = RevokeSessionsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+    ),
+    tag = "authentication"
+)]
+pub async fn revoke_other_sessions_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Json<RevokeSessionsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let (user, current) = authenticate(&app_state, &headers).await?;
+
+    let revoked = app_state
+        .auth_service
+        .revoke_other_sessions(user.id, current)
+        .await
+        .map_err(session_store_error)?;
+    info!("User {} revoked {} other session(s)", user.id, revoked);
+
+    Ok(Json(RevokeSessionsResponse { revoked }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_session_response_marks_current_session() {
+        let session = Session {
+            id: Uuid::new_v4(),
+            user_id: 1,
+            device_name: Some("Chrome on macOS".to_string()),
+            ip_address: Some("203.0.113.7".to_string()),
+            user_agent: None,
+            created_at: Utc::now(),
+            last_used_at: Utc::now(),
+            revoked: false,
+        };
+        let id = session.id;
+
+        assert!(session_response(session.clone(), Some(id)).current);
+        let response = session_response(session, Some(Uuid::new_v4()));
+        assert!(!response.current);
+        assert_eq!(response.device_name.as_deref(), Some("Chrome on macOS"));
+    }
+}
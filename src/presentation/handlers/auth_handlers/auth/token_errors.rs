@@ -0,0 +1,63 @@
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::AuthServiceError;
+use axum::{http::StatusCode, Json};
+
+/// Map a failed token verification to the response returned by authenticated endpoints
+///
+/// Suspended accounts get `403 ACCOUNT_SUSPENDED` with the reason and end of the
+/// suspension; every other failure is treated as an invalid token.
+pub fn token_error_response(error: &AuthServiceError) -> (StatusCode, Json<ErrorResponse>) {
+    match error {
+        AuthServiceError::AccountSuspended { reason, until } => {
+            let message = match until {
+                Some(until) => {
+                    format!("Account suspended until {}: {}", until.to_rfc3339(), reason)
+                }
+                None => format!("Account suspended: {}", reason),
+            };
+            let error_response = ErrorResponse {
+                error: "ACCOUNT_SUSPENDED".to_string(),
+                message,
+                status_code: StatusCode::FORBIDDEN.as_u16(),
+            };
+            (StatusCode::FORBIDDEN, Json(error_response))
+        }
+        _ => {
+            let error_response = ErrorResponse {
+                error: "INVALID_TOKEN".to_string(),
+                message: "Invalid or expired token".to_string(),
+                status_code: StatusCode::UNAUTHORIZED.as_u16(),
+            };
+            (StatusCode::UNAUTHORIZED, Json(error_response))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_suspended_account_response() {
+        let until = Utc.with_ymd_and_hms(2030, 1, 1, 12, 0, 0).unwrap();
+        let (status, Json(body)) = token_error_response(&AuthServiceError::AccountSuspended {
+            reason: "Spam".to_string(),
+            until: Some(until),
+        });
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body.error, "ACCOUNT_SUSPENDED");
+        assert!(body.message.contains("Spam"));
+        assert!(body.message.contains("2030-01-01T12:00:00+00:00"));
+    }
+
+    #[test]
+    fn test_invalid_token_response() {
+        let (status, Json(body)) =
+            token_error_response(&AuthServiceError::TokenValidation("expired".to_string()));
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body.error, "INVALID_TOKEN");
+    }
+}
@@ -0,0 +1,72 @@
+use super::dtos::{ErrorResponse, VerifyEmailQuery, VerifyEmailResponse};
+use crate::domain::services::AuthServiceError;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for the link in the welcome email
+///
+/// Activates an account waiting for its owner to verify their email address.
+#[utoipa::path(
+    get,
+    path = "/auth/verify-email",
+    params(
+        ("token" = String, Query, description = "Token from the link in the welcome email")
+    ),
+    responses(
+        (status = 200, description = "Email address verified", body = VerifyEmailResponse),
+        (status = 400, description = "Invalid or expired link", body = ErrorResponse),
+        (status = 404, description = "No account uses the address anymore", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "authentication"
+)]
+pub async fn verify_email_handler(
+    State(app_state): State<ConcreteAppState>,
+    Query(query): Query<VerifyEmailQuery>,
+) -> Result<Json<VerifyEmailResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match app_state.auth_service.verify_email(&query.token).await {
+        Ok(user) => {
+            info!("User {} verified their email address", user.id);
+            Ok(Json(VerifyEmailResponse {
+                message: "Your email address has been verified.".to_string(),
+                email: user.email,
+            }))
+        }
+        Err(error) => {
+            let (status, error_code, message) = match error {
+                AuthServiceError::TokenValidation(e) => {
+                    warn!("Rejected email verification link: {}", e);
+                    (
+                        StatusCode::BAD_REQUEST,
+                        "INVALID_TOKEN",
+                        "Invalid or expired verification link",
+                    )
+                }
+                AuthServiceError::UserNotFound => {
+                    (StatusCode::NOT_FOUND, "NOT_FOUND", "User account not found")
+                }
+                e => {
+                    warn!("Failed to verify email address: {}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "INTERNAL_ERROR",
+                        "Failed to verify email address",
+                    )
+                }
+            };
+            Err((
+                status,
+                Json(ErrorResponse {
+                    error: error_code.to_string(),
+                    message: message.to_string(),
+                    status_code: status.as_u16(),
+                }),
+            ))
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crate::domain::entities::{ConversionEvent, ConversionGoal};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Request DTO for creating a conversion goal
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct CreateConversionGoalRequest {
+    /// URL of the goal page; `*` matches any run of characters
+    pub goal_url_pattern: String,
+    pub name: String,
+}
+
+/// Response DTO describing a conversion goal
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConversionGoalResponse {
+    pub id: i32,
+    pub url_id: i32,
+    pub goal_url_pattern: String,
+    pub name: String,
+    pub created_at: String,
+}
+
+impl From<ConversionGoal> for ConversionGoalResponse {
+    fn from(goal: ConversionGoal) -> Self {
+        Self {
+            id: goal.id,
+            url_id: goal.url_id,
+            goal_url_pattern: goal.goal_url_pattern,
+            name: goal.name,
+            created_at: goal.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Request DTO sent by a goal page when a visitor converts
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct ReportConversionRequest {
+    /// Value of the `url_shortener_click_id` cookie
+    pub click_cookie: String,
+    pub goal_id: i32,
+    /// URL of the reporting page; checked against the goal pattern when given
+    pub page_url: Option<String>,
+}
+
+/// Response DTO for a recorded conversion
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConversionEventResponse {
+    pub id: i32,
+    pub goal_id: i32,
+    pub converted_at: String,
+}
+
+impl From<ConversionEvent> for ConversionEventResponse {
+    fn from(event: ConversionEvent) -> Self {
+        Self {
+            id: event.id,
+            goal_id: event.goal_id,
+            converted_at: event.converted_at.to_rfc3339(),
+        }
+    }
+}
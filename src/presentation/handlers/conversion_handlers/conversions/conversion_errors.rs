@@ -0,0 +1,51 @@
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::click_tracking_service::ClickTrackingError;
+use axum::{http::StatusCode, Json};
+
+/// Map a click tracking error from a conversion endpoint to an HTTP error response
+pub fn conversion_error_response(error: &ClickTrackingError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code) = match error {
+        ClickTrackingError::GoalNotFound => (StatusCode::NOT_FOUND, "GOAL_NOT_FOUND"),
+        ClickTrackingError::UnknownClick => (StatusCode::NOT_FOUND, "CLICK_NOT_FOUND"),
+        ClickTrackingError::InvalidData(_) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR"),
+        ClickTrackingError::BufferFull | ClickTrackingError::ServiceUnavailable => {
+            (StatusCode::SERVICE_UNAVAILABLE, "SERVICE_UNAVAILABLE")
+        }
+        ClickTrackingError::Repository(_) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR"),
+    };
+
+    let message = match error {
+        ClickTrackingError::Repository(_) => "Internal server error".to_string(),
+        _ => error.to_string(),
+    };
+    let error_response = ErrorResponse {
+        error: code.to_string(),
+        message,
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::ClickRepositoryError;
+
+    #[test]
+    fn test_conversion_error_status_codes() {
+        let (status, Json(body)) = conversion_error_response(&ClickTrackingError::UnknownClick);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.error, "CLICK_NOT_FOUND");
+
+        let (status, Json(body)) =
+            conversion_error_response(&ClickTrackingError::InvalidData("bad".to_string()));
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.message, "Invalid data: bad");
+
+        let (status, Json(body)) = conversion_error_response(&ClickTrackingError::Repository(
+            ClickRepositoryError::Database("connection refused".to_string()),
+        ));
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(!body.message.contains("connection refused"));
+    }
+}
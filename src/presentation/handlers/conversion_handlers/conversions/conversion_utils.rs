@@ -0,0 +1,71 @@
+use crate::application::dto::ErrorResponse;
+use crate::domain::entities::{Url, User};
+use crate::infrastructure::error_tracking::set_sentry_user;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState};
+use axum::{
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use tracing::warn;
+
+/// Authenticate the caller of a conversion goal endpoint
+pub async fn authenticate_goal_user(
+    app_state: &ConcreteAppState,
+    headers: &HeaderMap,
+) -> Result<User, (StatusCode, Json<ErrorResponse>)> {
+    // Require Authorization: Bearer <token>
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(t) if !t.is_empty() => t,
+        _ => {
+            let error_response = ErrorResponse {
+                error: "UNAUTHORIZED".to_string(),
+                message: "Missing or invalid Authorization header".to_string(),
+                status_code: StatusCode::UNAUTHORIZED.as_u16(),
+            };
+            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
+        }
+    };
+
+    // Verify token and get user
+    match app_state.auth_service.verify_token(token).await {
+        Ok(u) => {
+            set_sentry_user(&u);
+            Ok(u)
+        }
+        Err(e) => {
+            warn!("Token verification failed: {}", e);
+            Err(token_error_response(&e))
+        }
+    }
+}
+
+/// Load a URL owned by the user; URLs of other users are reported as missing
+pub async fn find_owned_url(
+    app_state: &ConcreteAppState,
+    url_id: i32,
+    user_id: i32,
+) -> Result<Url, (StatusCode, Json<ErrorResponse>)> {
+    match app_state.url_service.get_url_by_id(url_id).await {
+        Ok(Some(url)) if url.user_id == Some(user_id) => Ok(url),
+        Ok(_) => {
+            let error_response = ErrorResponse {
+                error: "NOT_FOUND".to_string(),
+                message: "URL not found".to_string(),
+                status_code: StatusCode::NOT_FOUND.as_u16(),
+            };
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        }
+        Err(e) => {
+            warn!("Failed to load URL {}: {}", url_id, e);
+            let error_response = ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: "Internal server error".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use super::conversion_dtos::{ConversionGoalResponse, CreateConversionGoalRequest};
+use super::conversion_errors::conversion_error_response;
+use super::conversion_utils::{authenticate_goal_user, find_owned_url};
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for adding a conversion goal to one of the authenticated user's URLs
+#[utoipa::path(
+    post,
+    path = "/urls/{id}/goals",
+    params(
+        ("id" = i32, Path, description = "URL ID")
+    ),
+    request_body = CreateConversionGoalRequest,
+    responses(
+        (status = 201, description = "Conversion goal created", body = ConversionGoalResponse),
+        (status = 400, description = "Invalid goal", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+    ),
+    tag = "conversions"
+)]
+pub async fn create_conversion_goal_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    ValidatedJson(request): ValidatedJson<CreateConversionGoalRequest>,
+) -> Result<(StatusCode, Json<ConversionGoalResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_goal_user(&app_state, &headers).await?;
+    let url = find_owned_url(&app_state, id, user.id).await?;
+
+    match app_state
+        .click_tracking_service
+        .create_conversion_goal(url.id, &request.goal_url_pattern, &request.name)
+        .await
+    {
+        Ok(goal) => {
+            info!(
+                "User {} added conversion goal {} to URL {}",
+                user.id, goal.id, url.id
+            );
+            Ok((StatusCode::CREATED, Json(goal.into())))
+        }
+        Err(error) => {
+            warn!("Failed to create conversion goal for URL {}: {}", id, error);
+            Err(conversion_error_response(&error))
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use super::conversion_errors::conversion_error_response;
+use super::conversion_utils::{authenticate_goal_user, find_owned_url};
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::click_tracking_service::ClickTrackingError;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for deleting a conversion goal and its recorded conversions
+///
+/// Only the owner of the goal's URL may delete it.
+#[utoipa::path(
+    delete,
+    path = "/goals/{id}",
+    params(
+        ("id" = i32, Path, description = "Conversion goal ID")
+    ),
+    responses(
+        (status = 204, description = "Conversion goal deleted"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "Conversion goal not found", body = ErrorResponse),
+    ),
+    tag = "conversions"
+)]
+pub async fn delete_conversion_goal_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_goal_user(&app_state, &headers).await?;
+
+    let goal = match app_state
+        .click_tracking_service
+        .get_conversion_goal(id)
+        .await
+    {
+        Ok(Some(goal)) => goal,
+        Ok(None) => return Err(conversion_error_response(&ClickTrackingError::GoalNotFound)),
+        Err(error) => {
+            warn!("Failed to load conversion goal {}: {}", id, error);
+            return Err(conversion_error_response(&error));
+        }
+    };
+    // Goals of other users' URLs look the same as missing ones
+    find_owned_url(&app_state, goal.url_id, user.id)
+        .await
+        .map_err(|_| conversion_error_response(&ClickTrackingError::GoalNotFound))?;
+
+    match app_state
+        .click_tracking_service
+        .delete_conversion_goal(goal.id)
+        .await
+    {
+        Ok(()) => {
+            info!("User {} deleted conversion goal {}", user.id, goal.id);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(error) => {
+            warn!("Failed to delete conversion goal {}: {}", id, error);
+            Err(conversion_error_response(&error))
+        }
+    }
+}
@@ -0,0 +1,13 @@
+// Re-export all conversion handler functions and DTOs
+
+mod conversion_dtos;
+pub mod conversion_errors;
+mod conversion_utils;
+pub mod create_conversion_goal_handler;
+pub mod delete_conversion_goal_handler;
+pub mod report_conversion_handler;
+
+pub use conversion_dtos::*;
+pub use create_conversion_goal_handler::*;
+pub use delete_conversion_goal_handler::*;
+pub use report_conversion_handler::*;
@@ -0,0 +1,51 @@
+use super::conversion_dtos::{ConversionEventResponse, ReportConversionRequest};
+use super::conversion_errors::conversion_error_response;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{extract::State, http::StatusCode, Json};
+use tracing::{info, warn};
+
+/// Handler for goal pages reporting that a visitor converted
+///
+/// Unauthenticated: the click cookie value identifies the click the visitor arrived with.
+/// Reporting the same click and goal again returns the already recorded conversion.
+#[utoipa::path(
+    post,
+    path = "/conversions/report",
+    request_body = ReportConversionRequest,
+    responses(
+        (status = 200, description = "Conversion recorded", body = ConversionEventResponse),
+        (status = 400, description = "Page does not match the goal", body = ErrorResponse),
+        (status = 404, description = "Unknown goal or click", body = ErrorResponse),
+    ),
+    tag = "conversions"
+)]
+pub async fn report_conversion_handler(
+    State(app_state): State<ConcreteAppState>,
+    ValidatedJson(request): ValidatedJson<ReportConversionRequest>,
+) -> Result<(StatusCode, Json<ConversionEventResponse>), (StatusCode, Json<ErrorResponse>)> {
+    match app_state
+        .click_tracking_service
+        .report_conversion(
+            request.click_cookie.trim(),
+            request.goal_id,
+            request.page_url.as_deref(),
+        )
+        .await
+    {
+        Ok(event) => {
+            info!(
+                "Recorded conversion {} for goal {}",
+                event.id, event.goal_id
+            );
+            Ok((StatusCode::OK, Json(event.into())))
+        }
+        Err(error) => {
+            warn!(
+                "Rejected conversion report for goal {}: {}",
+                request.goal_id, error
+            );
+            Err(conversion_error_response(&error))
+        }
+    }
+}
@@ -0,0 +1,4 @@
+// Re-export all conversion handler functions from the conversions module
+pub mod conversions;
+
+pub use conversions::*;
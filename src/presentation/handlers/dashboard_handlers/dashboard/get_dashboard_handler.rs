@@ -0,0 +1,152 @@
+use crate::application::dto::{
+    requests::ListLimitQuery,
+    responses::{DashboardResponse, UserUrlStatsResponse},
+    ErrorResponse,
+};
+use crate::domain::services::url_service::DEFAULT_LISTING_LIMIT;
+use crate::presentation::handlers::url_handlers::urls::url_utils::url_to_info_response;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState};
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    http::{header, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for the user dashboard
+///
+/// Returns the most clicked URLs, the most recently created URLs and overall
+/// statistics in a single response. The statistics come from the user's counters rather
+/// than an aggregate over their URLs; only the visitor estimate merges per-URL sketches.
+#[utoipa::path(
+    get,
+    path = "/dashboard",
+    params(
+        ("limit" = Option<usize>, Query, description = "Maximum number of URLs per list (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Dashboard retrieved", body = DashboardResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
+    ),
+    tag = "dashboard"
+)]
+pub async fn get_dashboard_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Query(query): Query<ListLimitQuery>,
+) -> Result<(StatusCode, Json<DashboardResponse>), (StatusCode, Json<ErrorResponse>)> {
+    // Require Authorization: Bearer <token>
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(t) if !t.is_empty() => t,
+        _ => {
+            let error_response = ErrorResponse {
+                error: "UNAUTHORIZED".to_string(),
+                message: "Missing or invalid Authorization header".to_string(),
+                status_code: StatusCode::UNAUTHORIZED.as_u16(),
+            };
+            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
+        }
+    };
+
+    // Verify token and get user
+    let user = match app_state.auth_service.verify_token(token).await {
+        Ok(u) => u,
+        Err(e) => {
+            warn!("Token verification failed: {}", e);
+            return Err(token_error_response(&e));
+        }
+    };
+
+    let limit = query.limit.unwrap_or(DEFAULT_LISTING_LIMIT);
+    info!("Building dashboard for user: {}", user.id);
+
+    let url_service = &app_state.url_service;
+    let (top_urls, recent_urls, stats, unique_visitors) = tokio::join!(
+        url_service.get_most_clicked_urls(user.id, limit),
+        url_service.get_recent_urls(user.id, limit),
+        url_service.get_user_url_stats(user.id),
+        url_service.estimate_unique_visitors(user.id),
+    );
+
+    match (top_urls, recent_urls, stats, unique_visitors) {
+        (Ok(top_urls), Ok(recent_urls), Ok(stats), Ok(unique_visitors)) => {
+            let base_url = app_state.shorten_url_use_case.base_url();
+            let response = DashboardResponse {
+                top_urls: top_urls
+                    .into_iter()
+                    .map(|u| url_to_info_response(u.url, base_url, Some(u.click_count)))
+                    .collect(),
+                recent_urls: recent_urls
+                    .into_iter()
+                    .map(|url| url_to_info_response(url, base_url, None))
+                    .collect(),
+                stats: UserUrlStatsResponse {
+                    total_urls: stats.total_urls,
+                    active_urls: stats.active_urls,
+                    inactive_urls: stats.inactive_urls,
+                    total_clicks: stats.total_clicks,
+                    unique_short_codes: stats.total_urls,
+                    estimated_unique_visitors: unique_visitors,
+                    archived_url_count: stats.archived_urls,
+                    last_updated_at: stats.last_updated_at.to_rfc3339(),
+                },
+            };
+            Ok((StatusCode::OK, Json(response)))
+        }
+        (Err(error), _, _, _)
+        | (_, Err(error), _, _)
+        | (_, _, Err(error), _)
+        | (_, _, _, Err(error)) => {
+            warn!("Failed to build dashboard for user {}: {}", user.id, error);
+            let error_response = ErrorResponse {
+                error: "DATABASE_ERROR".to_string(),
+                message: "Internal server error".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dashboard_response_shape() {
+        let response = DashboardResponse {
+            top_urls: vec![],
+            recent_urls: vec![],
+            stats: UserUrlStatsResponse {
+                total_urls: 3,
+                active_urls: 2,
+                inactive_urls: 1,
+                total_clicks: 12,
+                unique_short_codes: 3,
+                estimated_unique_visitors: 8,
+                archived_url_count: 0,
+                last_updated_at: "2024-01-01T00:00:00+00:00".to_string(),
+            },
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("top_urls").is_some());
+        assert!(json.get("recent_urls").is_some());
+        assert_eq!(json["stats"]["total_clicks"], 12);
+        assert_eq!(json["stats"]["estimated_unique_visitors"], 8);
+    }
+
+    #[test]
+    fn test_unauthorized_error() {
+        let error = ErrorResponse {
+            error: "UNAUTHORIZED".to_string(),
+            message: "Missing or invalid Authorization header".to_string(),
+            status_code: StatusCode::UNAUTHORIZED.as_u16(),
+        };
+        assert_eq!(error.status_code, 401);
+    }
+}
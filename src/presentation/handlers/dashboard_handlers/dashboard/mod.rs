@@ -0,0 +1,5 @@
+// Re-export all dashboard handler functions
+
+pub mod get_dashboard_handler;
+
+pub use get_dashboard_handler::*;
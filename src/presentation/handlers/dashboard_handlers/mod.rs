@@ -0,0 +1,4 @@
+// Re-export all dashboard handler functions from the dashboard module
+pub mod dashboard;
+
+pub use dashboard::*;
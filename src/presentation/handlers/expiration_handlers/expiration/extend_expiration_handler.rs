@@ -2,11 +2,68 @@ use crate::application::dto::{
     requests::ExtendExpirationRequest,
     responses::{ErrorResponse, SuccessResponse},
 };
-use crate::domain::repositories::UrlRepository;
-use crate::presentation::handlers::ConcreteAppState;
+use crate::domain::expiration::{
+    check_max_expiration, extend_expiration, parse_relative_duration, ExpirationError,
+};
+use crate::domain::repositories::{RepositoryError, UrlRepository};
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
 use axum::{extract::Path, extract::State, http::StatusCode, Json};
+use chrono::{DateTime, Duration, Utc};
 use tracing::{info, warn};
 
+fn bad_request(error: &str, message: String) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message,
+        status_code: StatusCode::BAD_REQUEST.as_u16(),
+    };
+    (StatusCode::BAD_REQUEST, Json(error_response))
+}
+
+fn expiration_error_response(error: ExpirationError) -> (StatusCode, Json<ErrorResponse>) {
+    let code = match error {
+        ExpirationError::InvalidDuration(_) => "INVALID_DURATION",
+        ExpirationError::ExceedsMaximum(_) => "EXPIRATION_TOO_FAR",
+    };
+    bad_request(code, error.to_string())
+}
+
+/// Work out the new expiration date requested by an `ExtendExpirationRequest`
+fn resolve_new_expiration(
+    request: &ExtendExpirationRequest,
+    current: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    max_expiration_days: u32,
+) -> Result<DateTime<Utc>, (StatusCode, Json<ErrorResponse>)> {
+    match (
+        request.expiration_date,
+        request.extend_by.as_deref(),
+        request.additional_days,
+    ) {
+        (Some(expiration_date), None, None) => {
+            check_max_expiration(expiration_date, now, max_expiration_days)
+                .map_err(expiration_error_response)?;
+            Ok(expiration_date)
+        }
+        (None, Some(extend_by), None) => {
+            let duration = parse_relative_duration(extend_by).map_err(expiration_error_response)?;
+            extend_expiration(current, duration, now, max_expiration_days)
+                .map_err(expiration_error_response)
+        }
+        (None, None, Some(days)) => extend_expiration(
+            current,
+            Duration::days(days as i64),
+            now,
+            max_expiration_days,
+        )
+        .map_err(expiration_error_response),
+        _ => Err(bad_request(
+            "INVALID_REQUEST",
+            "Provide exactly one of expiration_date or extend_by".to_string(),
+        )),
+    }
+}
+
 /// Handler for extending URL expiration
 #[utoipa::path(
     post,
@@ -17,14 +74,16 @@ use tracing::{info, warn};
     request_body = ExtendExpirationRequest,
     responses(
         (status = 200, description = "Expiration extended successfully", body = SuccessResponse),
+        (status = 400, description = "Invalid duration or expiration too far in the future", body = ErrorResponse),
         (status = 404, description = "URL not found", body = ErrorResponse),
+        (status = 409, description = "URL was modified concurrently", body = ErrorResponse),
     ),
     tag = "expiration"
 )]
 pub async fn extend_expiration_handler(
     State(app_state): State<ConcreteAppState>,
     Path(short_code_str): Path<String>,
-    Json(request): Json<ExtendExpirationRequest>,
+    ValidatedJson(request): ValidatedJson<ExtendExpirationRequest>,
 ) -> Result<(StatusCode, Json<SuccessResponse>), (StatusCode, Json<ErrorResponse>)> {
     info!("Extending expiration for short code: {}", short_code_str);
 
@@ -43,28 +102,42 @@ pub async fn extend_expiration_handler(
 
     match app_state
         .url_repository
-        .find_by_short_code(&short_code)
+        .find_by_short_code(&short_code, false)
         .await
     {
         Ok(Some(mut url)) => {
-            let now = chrono::Utc::now();
-            let current_expiration = url.expiration_date.unwrap_or(now);
-            let new_expiration =
-                current_expiration + chrono::Duration::days(request.additional_days as i64);
+            let new_expiration = resolve_new_expiration(
+                &request,
+                url.expiration_date,
+                Utc::now(),
+                app_state.max_expiration_days,
+            )?;
             url.expiration_date = Some(new_expiration);
 
-            match app_state.url_repository.update_url(&url).await {
+            match app_state.url_repository.update_url(&url, url.version).await {
                 Ok(_) => {
                     info!(
                         "Expiration extended successfully for URL: {}",
                         url.short_code
                     );
                     let response = SuccessResponse {
-                        message: format!("Expiration extended by {} days", request.additional_days),
+                        message: format!("Expiration extended to {}", new_expiration.to_rfc3339()),
                         status_code: StatusCode::OK.as_u16(),
                     };
                     Ok((StatusCode::OK, Json(response)))
                 }
+                Err(RepositoryError::ConflictingUpdate { current_version }) => {
+                    warn!(
+                        "Concurrent update of URL {} (now at version {})",
+                        url.short_code, current_version
+                    );
+                    let error_response = ErrorResponse {
+                        error: "CONFLICT".to_string(),
+                        message: "URL was modified by another request, please retry".to_string(),
+                        status_code: StatusCode::CONFLICT.as_u16(),
+                    };
+                    Err((StatusCode::CONFLICT, Json(error_response)))
+                }
                 Err(error) => {
                     warn!("Failed to extend URL expiration: {}", error);
                     let error_response = ErrorResponse {
@@ -101,6 +174,69 @@ pub async fn extend_expiration_handler(
 mod tests {
     use super::*;
 
+    fn request(
+        expiration_date: Option<DateTime<Utc>>,
+        extend_by: Option<&str>,
+        additional_days: Option<u32>,
+    ) -> ExtendExpirationRequest {
+        ExtendExpirationRequest {
+            expiration_date,
+            extend_by: extend_by.map(str::to_string),
+            additional_days,
+        }
+    }
+
+    #[test]
+    fn test_resolve_relative_extension() {
+        let now = Utc::now();
+        let current = now + Duration::days(3);
+
+        let resolved =
+            resolve_new_expiration(&request(None, Some("+7d"), None), Some(current), now, 365);
+        assert_eq!(resolved.unwrap(), current + Duration::days(7));
+
+        let resolved = resolve_new_expiration(&request(None, Some("30m"), None), None, now, 365);
+        assert_eq!(resolved.unwrap(), now + Duration::minutes(30));
+
+        let resolved = resolve_new_expiration(&request(None, None, Some(2)), None, now, 365);
+        assert_eq!(resolved.unwrap(), now + Duration::days(2));
+    }
+
+    #[test]
+    fn test_resolve_rejects_invalid_duration() {
+        let now = Utc::now();
+        let (status, Json(body)) =
+            resolve_new_expiration(&request(None, Some("7x"), None), None, now, 365).unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.error, "INVALID_DURATION");
+    }
+
+    #[test]
+    fn test_resolve_rejects_expiration_beyond_maximum() {
+        let now = Utc::now();
+        let (_, Json(body)) =
+            resolve_new_expiration(&request(None, Some("2y"), None), None, now, 365).unwrap_err();
+        assert_eq!(body.error, "EXPIRATION_TOO_FAR");
+
+        let far = now + Duration::days(400);
+        let (_, Json(body)) =
+            resolve_new_expiration(&request(Some(far), None, None), None, now, 365).unwrap_err();
+        assert_eq!(body.error, "EXPIRATION_TOO_FAR");
+    }
+
+    #[test]
+    fn test_resolve_requires_exactly_one_field() {
+        let now = Utc::now();
+        let (_, Json(body)) =
+            resolve_new_expiration(&request(None, None, None), None, now, 365).unwrap_err();
+        assert_eq!(body.error, "INVALID_REQUEST");
+
+        let (_, Json(body)) =
+            resolve_new_expiration(&request(Some(now), Some("7d"), None), None, now, 365)
+                .unwrap_err();
+        assert_eq!(body.error, "INVALID_REQUEST");
+    }
+
     #[test]
     fn test_update_failed_error() {
         let error = ErrorResponse {
@@ -38,7 +38,7 @@ pub async fn get_expiration_info_handler(
 
     match app_state
         .url_repository
-        .find_by_short_code(&short_code)
+        .find_by_short_code(&short_code, false)
         .await
     {
         Ok(Some(url)) => {
@@ -52,6 +52,7 @@ pub async fn get_expiration_info_handler(
                 expiration_date: url.expiration_date.map(|d| d.to_rfc3339()),
                 is_expired: url.is_expired(),
                 expires_in_days,
+                version: url.version,
             };
 
             Ok((StatusCode::OK, Json(response)))
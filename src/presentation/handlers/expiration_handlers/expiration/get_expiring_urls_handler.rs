@@ -41,9 +41,11 @@ pub async fn get_expiring_urls_handler(
                         original_url: url.original_url.clone(),
                         short_url: url.short_url("https://short.ly"), // TODO: Get from config
                         created_at: url.created_at.to_rfc3339(),
+                        updated_at: url.updated_at.to_rfc3339(),
                         expiration_date: url.expiration_date.map(|d| d.to_rfc3339()),
                         is_expired: url.is_expired(),
                         click_count: None, // TODO: Add click tracking
+                        version: url.version,
                     }
                 })
                 .collect();
@@ -2,8 +2,8 @@ use crate::application::dto::{
     requests::SetExpirationRequest,
     responses::{ErrorResponse, SuccessResponse},
 };
-use crate::domain::repositories::UrlRepository;
-use crate::presentation::handlers::ConcreteAppState;
+use crate::domain::repositories::{RepositoryError, UrlRepository};
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
 use axum::{extract::Path, extract::State, http::StatusCode, Json};
 use tracing::{info, warn};
 
@@ -18,13 +18,14 @@ use tracing::{info, warn};
     responses(
         (status = 200, description = "Expiration set successfully", body = SuccessResponse),
         (status = 404, description = "URL not found", body = ErrorResponse),
+        (status = 409, description = "URL was modified concurrently", body = ErrorResponse),
     ),
     tag = "expiration"
 )]
 pub async fn set_expiration_handler(
     State(app_state): State<ConcreteAppState>,
     Path(short_code_str): Path<String>,
-    Json(request): Json<SetExpirationRequest>,
+    ValidatedJson(request): ValidatedJson<SetExpirationRequest>,
 ) -> Result<(StatusCode, Json<SuccessResponse>), (StatusCode, Json<ErrorResponse>)> {
     info!("Setting expiration for short code: {}", short_code_str);
 
@@ -43,13 +44,13 @@ pub async fn set_expiration_handler(
 
     match app_state
         .url_repository
-        .find_by_short_code(&short_code)
+        .find_by_short_code(&short_code, false)
         .await
     {
         Ok(Some(mut url)) => {
             url.expiration_date = Some(request.expiration_date);
 
-            match app_state.url_repository.update_url(&url).await {
+            match app_state.url_repository.update_url(&url, url.version).await {
                 Ok(_) => {
                     info!("Expiration set successfully for URL: {}", url.short_code);
                     let response = SuccessResponse {
@@ -58,6 +59,18 @@ pub async fn set_expiration_handler(
                     };
                     Ok((StatusCode::OK, Json(response)))
                 }
+                Err(RepositoryError::ConflictingUpdate { current_version }) => {
+                    warn!(
+                        "Concurrent update of URL {} (now at version {})",
+                        url.short_code, current_version
+                    );
+                    let error_response = ErrorResponse {
+                        error: "CONFLICT".to_string(),
+                        message: "URL was modified by another request, please retry".to_string(),
+                        status_code: StatusCode::CONFLICT.as_u16(),
+                    };
+                    Err((StatusCode::CONFLICT, Json(error_response)))
+                }
                 Err(error) => {
                     warn!("Failed to update URL expiration: {}", error);
                     let error_response = ErrorResponse {
@@ -0,0 +1,47 @@
+use crate::application::dto::responses::ErrorResponse;
+use crate::infrastructure::config::{Feature, FeatureFlags};
+use axum::{http::StatusCode, Json};
+
+/// Reject the request with `501 FEATURE_DISABLED` unless `feature` is enabled
+///
+/// Handlers of switchable features start with `require_feature(&state.features, Feature::X)?;`.
+pub fn require_feature(
+    features: &FeatureFlags,
+    feature: Feature,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if features.is_enabled(feature) {
+        return Ok(());
+    }
+    Err((
+        StatusCode::NOT_IMPLEMENTED,
+        Json(ErrorResponse {
+            error: "FEATURE_DISABLED".to_string(),
+            message: format!(
+                "{} is not available on this instance",
+                feature.description()
+            ),
+            status_code: StatusCode::NOT_IMPLEMENTED.as_u16(),
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_require_feature() {
+        let mut features = FeatureFlags::default();
+        assert!(require_feature(&features, Feature::MagicLink).is_ok());
+
+        features.enable_magic_link = false;
+        let (status, Json(body)) = require_feature(&features, Feature::MagicLink).unwrap_err();
+        assert_eq!(status, StatusCode::NOT_IMPLEMENTED);
+        assert_eq!(body.error, "FEATURE_DISABLED");
+        assert_eq!(
+            body.message,
+            "Magic link login is not available on this instance"
+        );
+        assert!(require_feature(&features, Feature::SocialLogin).is_ok());
+    }
+}
@@ -1,6 +1,5 @@
 use crate::application::dto::responses::ErrorResponse;
 use crate::domain::repositories::UserRepository;
-use crate::domain::services::FileUploadService;
 use crate::presentation::handlers::ConcreteAppState;
 use axum::{extract::State, http::StatusCode, response::Json};
 use serde_json::Value;
@@ -65,26 +64,12 @@ pub async fn delete_profile_picture(
         }
     };
 
-    // Extract filename from URL
-    let filename = avatar_url.split('/').next_back().ok_or_else(|| {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "Invalid avatar URL".to_string(),
-                message: "Cannot extract filename from avatar URL".to_string(),
-                status_code: 400,
-            }),
-        )
-    })?;
-
-    // Create file upload service
-    let upload_service =
-        FileUploadService::new_profile_picture_service("uploads/avatars".to_string());
-
-    // Delete file from filesystem
-    if let Err(e) = upload_service.delete_file(filename).await {
-        // Log error but don't fail the request
-        tracing::warn!("Failed to delete avatar file {}: {}", filename, e);
+    // Delete the stored object; avatars set elsewhere (e.g. OAuth) are not ours to delete
+    if let Some(key) = state.object_storage.key_for_url(&avatar_url) {
+        if let Err(e) = state.object_storage.delete(&key).await {
+            // Log error but don't fail the request
+            tracing::warn!("Failed to delete avatar object {}: {}", key, e);
+        }
     }
 
     // Update user profile to remove avatar URL
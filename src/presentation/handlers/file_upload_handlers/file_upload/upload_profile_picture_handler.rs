@@ -1,13 +1,18 @@
 use crate::application::dto::responses::ErrorResponse;
 use crate::domain::repositories::UserRepository;
 use crate::domain::services::{FileUploadError, FileUploadService};
+use crate::infrastructure::object_storage::profile_picture_key;
 use crate::presentation::handlers::ConcreteAppState;
 use axum::{extract::State, http::StatusCode, response::Json};
 use axum_extra::extract::Multipart;
+use bytes::Bytes;
 use serde_json::Value;
 
 /// Upload profile picture
 /// POST /api/profile/avatar
+///
+/// The picture is stored in object storage under `profiles/<user_id>/<uuid>.<ext>` and the
+/// profile's avatar URL points at it.
 #[utoipa::path(
     post,
     path = "/profile/avatar",
@@ -30,8 +35,7 @@ pub async fn upload_profile_picture(
     let user_id = 1; // Placeholder
 
     // Create file upload service
-    let upload_service =
-        FileUploadService::new_profile_picture_service("uploads/avatars".to_string());
+    let upload_service = FileUploadService::new_profile_picture_service();
 
     // Process multipart form
     while let Some(field) = multipart.next_field().await.map_err(|e| {
@@ -75,10 +79,9 @@ pub async fn upload_profile_picture(
                 )
             })?;
 
-            // Process and save file
-            let upload_result = upload_service
-                .process_and_save_file(&filename, &content_type, data.to_vec())
-                .await
+            // Validate and process file
+            let processed = upload_service
+                .process_file(&filename, &content_type, data.to_vec())
                 .map_err(|e| {
                     let (status, message) = match e {
                         FileUploadError::FileTooLarge(_, max) => (
@@ -101,9 +104,25 @@ pub async fn upload_profile_picture(
                     )
                 })?;
 
+            // Store file
+            let key = profile_picture_key(user_id, &processed.extension);
+            let avatar_url = state
+                .object_storage
+                .upload(&key, Bytes::from(processed.data), &processed.mime_type)
+                .await
+                .map_err(|e| {
+                    tracing::warn!("Failed to store avatar {}: {}", key, e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ErrorResponse {
+                            error: "Storage error".to_string(),
+                            message: "Failed to store the uploaded file".to_string(),
+                            status_code: 500,
+                        }),
+                    )
+                })?;
+
             // Update user's avatar URL
-            let avatar_url =
-                upload_service.get_file_url(&upload_result.filename, "http://localhost:8000");
 
             match state
                 .user_repository
@@ -123,15 +142,15 @@ pub async fn upload_profile_picture(
                     return Ok(Json(serde_json::json!({
                         "message": "Avatar uploaded successfully",
                         "avatar_url": avatar_url,
-                        "filename": upload_result.filename,
-                        "file_size": upload_result.file_size,
-                        "width": upload_result.width,
-                        "height": upload_result.height
+                        "filename": key,
+                        "file_size": processed.file_size,
+                        "width": processed.width,
+                        "height": processed.height
                     })));
                 }
                 Err(e) => {
                     // Clean up uploaded file on database error
-                    let _ = upload_service.delete_file(&upload_result.filename).await;
+                    let _ = state.object_storage.delete(&key).await;
                     return Err((
                         StatusCode::INTERNAL_SERVER_ERROR,
                         Json(ErrorResponse {
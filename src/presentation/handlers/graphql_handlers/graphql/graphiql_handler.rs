@@ -0,0 +1,7 @@
+use async_graphql::http::GraphiQLSource;
+use axum::response::Html;
+
+/// Handler for the GraphiQL playground, only routed in development
+pub async fn graphiql_handler() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
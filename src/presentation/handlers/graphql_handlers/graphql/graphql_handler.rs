@@ -0,0 +1,25 @@
+use crate::presentation::graphql::GraphQLAuth;
+use crate::presentation::handlers::ConcreteGraphQLSchema;
+use axum::{
+    http::{header, HeaderMap},
+    Extension, Json,
+};
+
+/// Handler for GraphQL queries and mutations
+///
+/// Takes the same `Authorization: Bearer <token>` header as the REST API. Errors are
+/// reported in the response body with a `code` extension, so the status is always 200.
+pub async fn graphql_handler(
+    Extension(schema): Extension<ConcreteGraphQLSchema>,
+    headers: HeaderMap,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .filter(|t| !t.is_empty())
+        .map(str::to_string);
+
+    Json(schema.execute(request.data(GraphQLAuth::new(token))).await)
+}
@@ -0,0 +1,7 @@
+// Re-export all GraphQL handler functions
+
+pub mod graphiql_handler;
+pub mod graphql_handler;
+
+pub use graphiql_handler::*;
+pub use graphql_handler::*;
@@ -0,0 +1,4 @@
+// Re-export all GraphQL handler functions from the graphql module
+pub mod graphql;
+
+pub use graphql::*;
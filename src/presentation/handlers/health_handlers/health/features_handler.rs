@@ -0,0 +1,17 @@
+use crate::infrastructure::config::FeatureFlags;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{extract::State, Json};
+
+/// Features enabled on this instance, so clients can hide what is unavailable
+/// GET /api/features
+#[utoipa::path(
+    get,
+    path = "/features",
+    responses(
+        (status = 200, description = "Enabled features", body = FeatureFlags),
+    ),
+    tag = "health"
+)]
+pub async fn get_features_handler(State(state): State<ConcreteAppState>) -> Json<FeatureFlags> {
+    Json(state.features.clone())
+}
@@ -0,0 +1,167 @@
+use crate::application::dto::responses::HealthResponse;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{extract::State, http::StatusCode, Json};
+use std::collections::BTreeMap;
+use tracing::warn;
+
+/// Run every dependency check, returning whether all passed and each result
+async fn run_readiness_checks(app_state: &ConcreteAppState) -> (bool, BTreeMap<String, String>) {
+    let mut checks = BTreeMap::new();
+    let mut all_ok = true;
+
+    match app_state.database_health.check().await {
+        Ok(()) => {
+            checks.insert("database".to_string(), "ok".to_string());
+        }
+        Err(e) => {
+            warn!("Readiness check failed for database: {}", e);
+            checks.insert("database".to_string(), format!("error: {}", e));
+            all_ok = false;
+        }
+    }
+
+    if let Some(cache_health) = &app_state.cache_health {
+        match cache_health.check().await {
+            Ok(()) => {
+                checks.insert("cache".to_string(), "ok".to_string());
+            }
+            Err(e) => {
+                warn!("Readiness check failed for cache: {}", e);
+                checks.insert("cache".to_string(), format!("error: {}", e));
+                all_ok = false;
+            }
+        }
+    }
+
+    (all_ok, checks)
+}
+
+/// Liveness probe: the server is running and able to answer
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    responses(
+        (status = 200, description = "Server is running", body = HealthResponse),
+    ),
+    tag = "health"
+)]
+pub async fn liveness_handler() -> (StatusCode, Json<HealthResponse>) {
+    (
+        StatusCode::OK,
+        Json(HealthResponse {
+            status: "ok".to_string(),
+            checks: None,
+        }),
+    )
+}
+
+/// Readiness probe: the server can reach the services it depends on
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    responses(
+        (status = 200, description = "Ready to serve traffic", body = HealthResponse),
+        (status = 503, description = "A dependency check failed", body = HealthResponse),
+    ),
+    tag = "health"
+)]
+pub async fn readiness_handler(
+    State(app_state): State<ConcreteAppState>,
+) -> (StatusCode, Json<HealthResponse>) {
+    let (ready, checks) = run_readiness_checks(&app_state).await;
+
+    let (status_code, status) = if ready {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "not_ready")
+    };
+
+    (
+        status_code,
+        Json(HealthResponse {
+            status: status.to_string(),
+            checks: Some(checks),
+        }),
+    )
+}
+
+/// Combined health status for load balancers and manual checks
+#[utoipa::path(
+    get,
+    path = "/health",
+    responses(
+        (status = 200, description = "Service is healthy", body = HealthResponse),
+        (status = 503, description = "Service is running but a dependency check failed", body = HealthResponse),
+    ),
+    tag = "health"
+)]
+pub async fn health_handler(
+    State(app_state): State<ConcreteAppState>,
+) -> (StatusCode, Json<HealthResponse>) {
+    let (ready, checks) = run_readiness_checks(&app_state).await;
+
+    let (status_code, status) = if ready {
+        (StatusCode::OK, "ok")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "degraded")
+    };
+
+    (
+        status_code,
+        Json(HealthResponse {
+            status: status.to_string(),
+            checks: Some(checks),
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::cache_health_check::CacheHealthCheck;
+    use crate::infrastructure::test_utils::TestApp;
+
+    #[tokio::test]
+    async fn test_liveness_handler() {
+        let (status, Json(body)) = liveness_handler().await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.status, "ok");
+        assert!(body.checks.is_none());
+    }
+
+    #[test]
+    fn test_not_ready_response_serialization() {
+        let mut checks = BTreeMap::new();
+        checks.insert(
+            "database".to_string(),
+            "error: connection refused".to_string(),
+        );
+        let response = HealthResponse {
+            status: "not_ready".to_string(),
+            checks: Some(checks),
+        };
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["status"], "not_ready");
+        assert_eq!(json["checks"]["database"], "error: connection refused");
+    }
+
+    #[tokio::test]
+    async fn test_readiness_checks_cache_only_when_configured() {
+        let app = TestApp::new();
+        let (_, checks) = run_readiness_checks(&app.state).await;
+        assert!(!checks.contains_key("cache"));
+    }
+
+    #[tokio::test]
+    async fn test_readiness_fails_when_cache_is_unreachable() {
+        let mut app = TestApp::new();
+        app.state.cache_health = Some(CacheHealthCheck::new("redis://127.0.0.1:1").unwrap());
+
+        let (status, Json(body)) = readiness_handler(State(app.state.clone())).await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        let checks = body.checks.unwrap();
+        assert!(checks["cache"].starts_with("error: "));
+    }
+}
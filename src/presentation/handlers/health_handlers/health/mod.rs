@@ -0,0 +1,7 @@
+// Re-export all health handler functions
+
+pub mod features_handler;
+pub mod health_handler;
+
+pub use features_handler::*;
+pub use health_handler::*;
@@ -0,0 +1,4 @@
+// Re-export all health handler functions from the health module
+pub mod health;
+
+pub use health::*;
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Request DTO for sending a magic login link
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct RequestMagicLinkRequest {
+    pub email: String,
+}
+
+/// Response DTO for a magic link request
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RequestMagicLinkResponse {
+    pub message: String,
+    pub email: String,
+}
+
+/// Query parameters of the magic link verification endpoint
+#[derive(Debug, Deserialize)]
+pub struct VerifyMagicLinkQuery {
+    /// Token from the emailed link
+    pub token: String,
+}
@@ -0,0 +1,116 @@
+use super::magic_link_dtos::{RequestMagicLinkRequest, RequestMagicLinkResponse};
+use crate::application::dto::responses::ErrorResponse;
+use crate::domain::repositories::user_repository::normalize_email;
+use crate::domain::services::magic_link_service::MAGIC_LINK_EXPIRATION_MINUTES;
+use crate::domain::services::{MagicLinkError, MagicLinkService};
+use crate::infrastructure::config::Feature;
+use crate::infrastructure::email::EmailMessage;
+use crate::presentation::handlers::{require_feature, ConcreteAppState, ValidatedJson};
+use axum::{extract::State, http::StatusCode, response::Json};
+
+const MAGIC_LINK_SENT_MESSAGE: &str =
+    "If the email exists in our system, a login link has been sent.";
+
+/// Request a passwordless login link
+/// POST /auth/magic-link/request
+#[utoipa::path(
+    post,
+    path = "/auth/magic-link/request",
+    request_body = RequestMagicLinkRequest,
+    responses(
+        (status = 200, description = "Login link sent if the account exists", body = RequestMagicLinkResponse),
+        (status = 429, description = "Too many requests", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+        (status = 501, description = "Magic links are disabled", body = ErrorResponse)
+    ),
+    tag = "authentication"
+)]
+pub async fn request_magic_link(
+    State(state): State<ConcreteAppState>,
+    ValidatedJson(request): ValidatedJson<RequestMagicLinkRequest>,
+) -> Result<Json<RequestMagicLinkResponse>, (StatusCode, Json<ErrorResponse>)> {
+    require_feature(&state.features, Feature::MagicLink)?;
+
+    // At most 3 links per email address per hour
+    state
+        .magic_link_rate_limiter
+        .check_email_limit(&normalize_email(&request.email))
+        .map_err(|e| {
+            let status = StatusCode::TOO_MANY_REQUESTS;
+            (
+                status,
+                Json(ErrorResponse {
+                    error: "Rate limit exceeded".to_string(),
+                    message: e.to_string(),
+                    status_code: status.as_u16(),
+                }),
+            )
+        })?;
+
+    let magic_link_service = MagicLinkService::new(
+        state.magic_link_repository.clone(),
+        state.user_repository.clone(),
+    );
+
+    let magic_link = match magic_link_service.create_link(&request.email).await {
+        Ok(magic_link) => magic_link,
+        Err(MagicLinkError::UserNotFound) => {
+            // For security, don't reveal if user exists or not
+            return Ok(Json(RequestMagicLinkResponse {
+                message: MAGIC_LINK_SENT_MESSAGE.to_string(),
+                email: request.email,
+            }));
+        }
+        Err(e) => {
+            tracing::error!("Failed to create magic link: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Magic link error".to_string(),
+                    message: "Failed to create login link".to_string(),
+                    status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+                }),
+            ));
+        }
+    };
+
+    let login_link = format!(
+        "{}/auth/magic-link/verify?token={}",
+        state.shorten_url_use_case.base_url(),
+        magic_link.token
+    );
+    let email_message = EmailMessage::magic_link(
+        magic_link.user.email.clone(),
+        login_link,
+        MAGIC_LINK_EXPIRATION_MINUTES,
+    );
+
+    // Send email (if email sender is configured)
+    if let Some(email_sender) = state.email_sender.as_ref() {
+        if let Err(e) = email_sender.send_email(email_message).await {
+            tracing::error!("Failed to send magic link email: {}", e);
+        }
+    } else {
+        tracing::warn!(
+            "Email sender not configured, magic link email for user {} not sent",
+            magic_link.user.id
+        );
+    }
+
+    Ok(Json(RequestMagicLinkResponse {
+        message: MAGIC_LINK_SENT_MESSAGE.to_string(),
+        email: request.email,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_magic_link_deserialize() {
+        let request: RequestMagicLinkRequest =
+            serde_json::from_str(r#"{"email":"user@example.com"}"#).unwrap();
+        assert_eq!(request.email, "user@example.com");
+    }
+}
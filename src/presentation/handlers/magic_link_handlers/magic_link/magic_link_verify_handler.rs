@@ -0,0 +1,124 @@
+use super::magic_link_dtos::VerifyMagicLinkQuery;
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::{MagicLinkError, MagicLinkService};
+use crate::infrastructure::config::Feature;
+use crate::presentation::handlers::{
+    require_feature, session_client, token_error_response, AuthResponse, ConcreteAppState,
+    UserResponse,
+};
+use axum::{
+    extract::{ConnectInfo, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use std::net::SocketAddr;
+use tracing::{info, warn};
+
+fn invalid_link_response(error: &MagicLinkError) -> (StatusCode, Json<ErrorResponse>) {
+    let (error, message) = match error {
+        MagicLinkError::TokenExpired => ("MAGIC_LINK_EXPIRED", "Login link has expired"),
+        MagicLinkError::TokenAlreadyUsed => ("MAGIC_LINK_USED", "Login link has already been used"),
+        _ => ("INVALID_MAGIC_LINK", "Invalid login link"),
+    };
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message: message.to_string(),
+        status_code: StatusCode::UNAUTHORIZED.as_u16(),
+    };
+    (StatusCode::UNAUTHORIZED, Json(error_response))
+}
+
+/// Handler for logging in with a magic link
+///
+/// Consumes the one-time token and returns the same response as a password login.
+#[utoipa::path(
+    get,
+    path = "/auth/magic-link/verify",
+    params(
+        ("token" = String, Query, description = "Token from the emailed login link")
+    ),
+    responses(
+        (status = 200, description = "Login successful", body = AuthResponse),
+        (status = 401, description = "Invalid, expired or used link", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
+        (status = 501, description = "Magic links are disabled", body = ErrorResponse)
+    ),
+    tag = "authentication"
+)]
+pub async fn verify_magic_link(
+    State(app_state): State<ConcreteAppState>,
+    Query(query): Query<VerifyMagicLinkQuery>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<AuthResponse>), (StatusCode, Json<ErrorResponse>)> {
+    require_feature(&app_state.features, Feature::MagicLink)?;
+
+    let magic_link_service = MagicLinkService::new(
+        app_state.magic_link_repository.clone(),
+        app_state.user_repository.clone(),
+    );
+
+    let user = match magic_link_service.verify_link(&query.token).await {
+        Ok(user) => user,
+        Err(MagicLinkError::RepositoryError(e)) => {
+            warn!("Failed to verify magic link: {}", e);
+            let error_response = ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: "Failed to verify login link".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)));
+        }
+        Err(e) => {
+            warn!("Rejected magic link: {}", e);
+            return Err(invalid_link_response(&e));
+        }
+    };
+
+    let client = session_client(
+        &app_state,
+        connect_info.map(|ConnectInfo(addr)| addr.ip()),
+        &headers,
+    );
+    let token = match app_state.auth_service.issue_token(&user, &client).await {
+        Ok(token) => token,
+        Err(e) => {
+            warn!("Rejected magic link login for user {}: {}", user.id, e);
+            return Err(token_error_response(&e));
+        }
+    };
+
+    info!(
+        "Successfully logged in user via magic link: {}",
+        user.username
+    );
+    let response = AuthResponse {
+        token,
+        user: UserResponse {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            created_at: user.created_at.to_rfc3339(),
+        },
+    };
+    Ok((StatusCode::OK, Json(response)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_link_responses() {
+        let (status, Json(body)) = invalid_link_response(&MagicLinkError::TokenExpired);
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body.error, "MAGIC_LINK_EXPIRED");
+
+        let (_, Json(body)) = invalid_link_response(&MagicLinkError::TokenAlreadyUsed);
+        assert_eq!(body.error, "MAGIC_LINK_USED");
+
+        let (_, Json(body)) = invalid_link_response(&MagicLinkError::InvalidToken);
+        assert_eq!(body.error, "INVALID_MAGIC_LINK");
+        assert_eq!(body.status_code, 401);
+    }
+}
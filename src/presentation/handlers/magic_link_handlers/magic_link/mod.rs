@@ -0,0 +1,9 @@
+// Re-export all magic link handler functions and DTOs
+
+mod magic_link_dtos;
+pub mod magic_link_request_handler;
+pub mod magic_link_verify_handler;
+
+pub use magic_link_dtos::*;
+pub use magic_link_request_handler::*;
+pub use magic_link_verify_handler::*;
@@ -0,0 +1,4 @@
+// Re-export all magic link handler functions from the magic_link module
+pub mod magic_link;
+
+pub use magic_link::*;
@@ -1,29 +1,71 @@
 pub mod account_deletion_handlers;
+pub mod admin_handlers;
+pub mod analytics_export_handlers;
 pub mod app_state;
 pub mod auth_handlers;
+pub mod conversion_handlers;
+pub mod dashboard_handlers;
 pub mod expiration_handlers;
+pub mod feature_guard;
 pub mod file_upload_handlers;
+pub mod graphql_handlers;
+pub mod health_handlers;
+pub mod magic_link_handlers;
+pub mod notification_handlers;
+pub mod oauth_handlers;
+pub mod organization_handlers;
 pub mod password_reset_handlers;
 pub mod privacy_handlers;
 pub mod profile_handlers;
 pub mod progress_handlers;
 pub mod url_handlers;
+pub mod validated_json;
+
+use crate::domain::repositories::{
+    AccountDeletionTokenRepository, ClickRepository, MagicLinkRepository, OrganizationRepository,
+    PasswordResetRepository, UrlRepository, UserRepository,
+};
+use std::sync::Arc;
 
 pub use account_deletion_handlers::*;
+pub use admin_handlers::*;
+pub use analytics_export_handlers::*;
 pub use app_state::*;
 pub use auth_handlers::*;
+pub use conversion_handlers::*;
+pub use dashboard_handlers::*;
 pub use expiration_handlers::*;
+pub use feature_guard::*;
 pub use file_upload_handlers::*;
+pub use graphql_handlers::*;
+pub use health_handlers::*;
+pub use magic_link_handlers::*;
+pub use notification_handlers::*;
+pub use oauth_handlers::*;
+pub use organization_handlers::*;
 pub use password_reset_handlers::*;
 pub use privacy_handlers::*;
 pub use profile_handlers::*;
 pub use progress_handlers::*;
 pub use url_handlers::*;
+pub use validated_json::*;
 
 // Type alias for the concrete AppState used in the application
+//
+// Repositories are trait objects so tests can serve the same router over mock repositories.
 pub type ConcreteAppState = app_state::AppState<
-    crate::infrastructure::database::PostgresUrlRepository,
-    crate::infrastructure::database::PostgresUserRepository,
-    crate::infrastructure::database::PostgresPasswordResetRepository,
-    crate::infrastructure::database::PostgresAccountDeletionTokenRepository,
+    Arc<dyn UrlRepository>,
+    Arc<dyn UserRepository>,
+    Arc<dyn PasswordResetRepository>,
+    Arc<dyn AccountDeletionTokenRepository>,
+    Arc<dyn ClickRepository>,
+    Arc<dyn OrganizationRepository>,
+    Arc<dyn MagicLinkRepository>,
+>;
+
+// GraphQL schema over the repositories of the concrete AppState
+pub type ConcreteGraphQLSchema = crate::presentation::graphql::UrlShortenerSchema<
+    Arc<dyn UrlRepository>,
+    Arc<dyn UserRepository>,
+    Arc<dyn ClickRepository>,
 >;
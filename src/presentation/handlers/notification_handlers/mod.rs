@@ -0,0 +1,4 @@
+// Re-export all notification handler functions from the notifications module
+pub mod notifications;
+
+pub use notifications::*;
@@ -0,0 +1,43 @@
+use super::notification_dtos::NotificationPreferencesResponse;
+use super::notification_errors::notification_error_response;
+use super::notification_utils::authenticate_notification_user;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::warn;
+
+/// Handler for reading the authenticated user's notification preferences
+#[utoipa::path(
+    get,
+    path = "/notification-preferences",
+    responses(
+        (status = 200, description = "Notification preferences", body = NotificationPreferencesResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+    ),
+    tag = "notifications"
+)]
+pub async fn get_notification_preferences_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Json<NotificationPreferencesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_notification_user(&app_state, &headers).await?;
+
+    match app_state
+        .notification_service
+        .get_preferences(user.id)
+        .await
+    {
+        Ok(preferences) => Ok(Json(preferences.into())),
+        Err(error) => {
+            warn!(
+                "Failed to load notification preferences of user {}: {}",
+                user.id, error
+            );
+            Err(notification_error_response(&error))
+        }
+    }
+}
@@ -0,0 +1,11 @@
+// Re-export all notification handler functions and DTOs
+
+pub mod get_notification_preferences_handler;
+mod notification_dtos;
+pub mod notification_errors;
+mod notification_utils;
+pub mod update_notification_preferences_handler;
+
+pub use get_notification_preferences_handler::*;
+pub use notification_dtos::*;
+pub use update_notification_preferences_handler::*;
@@ -0,0 +1,34 @@
+use crate::domain::entities::NotificationPreferences;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Request DTO for updating notification preferences; omitted fields are unchanged
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema, Validate)]
+pub struct UpdateNotificationPreferencesRequest {
+    pub digest_enabled: Option<bool>,
+    /// Day the weekly expiry digest is sent on, 0 = Sunday through 6 = Saturday
+    pub digest_day_of_week: Option<u8>,
+    /// Send push notifications to the devices registered with `/profile/device-tokens`
+    pub push_enabled: Option<bool>,
+}
+
+/// Response DTO describing a user's notification preferences
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationPreferencesResponse {
+    pub digest_enabled: bool,
+    pub digest_day_of_week: u8,
+    pub last_digest_sent_at: Option<String>,
+    pub push_enabled: bool,
+}
+
+impl From<NotificationPreferences> for NotificationPreferencesResponse {
+    fn from(preferences: NotificationPreferences) -> Self {
+        Self {
+            digest_enabled: preferences.digest_enabled,
+            digest_day_of_week: preferences.digest_day_of_week,
+            last_digest_sent_at: preferences.last_digest_sent_at.map(|dt| dt.to_rfc3339()),
+            push_enabled: preferences.push_enabled,
+        }
+    }
+}
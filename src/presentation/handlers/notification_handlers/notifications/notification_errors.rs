@@ -0,0 +1,41 @@
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::notification_service::NotificationError;
+use axum::{http::StatusCode, Json};
+
+/// Map a notification error from a preferences endpoint to an HTTP error response
+pub fn notification_error_response(error: &NotificationError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code) = match error {
+        NotificationError::InvalidPreferences(_) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR"),
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR"),
+    };
+
+    let message = match error {
+        NotificationError::InvalidPreferences(_) => error.to_string(),
+        _ => "Internal server error".to_string(),
+    };
+    let error_response = ErrorResponse {
+        error: code.to_string(),
+        message,
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_error_status_codes() {
+        let (status, Json(body)) = notification_error_response(
+            &NotificationError::InvalidPreferences("bad day".to_string()),
+        );
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.message, "Invalid notification preferences: bad day");
+
+        let (status, Json(body)) =
+            notification_error_response(&NotificationError::Internal("db down".to_string()));
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(!body.message.contains("db down"));
+    }
+}
@@ -0,0 +1,56 @@
+use super::notification_dtos::{
+    NotificationPreferencesResponse, UpdateNotificationPreferencesRequest,
+};
+use super::notification_errors::notification_error_response;
+use super::notification_utils::authenticate_notification_user;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for updating the authenticated user's notification preferences
+#[utoipa::path(
+    post,
+    path = "/notification-preferences",
+    request_body = UpdateNotificationPreferencesRequest,
+    responses(
+        (status = 200, description = "Notification preferences updated", body = NotificationPreferencesResponse),
+        (status = 400, description = "Invalid preferences", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+    ),
+    tag = "notifications"
+)]
+pub async fn update_notification_preferences_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<UpdateNotificationPreferencesRequest>,
+) -> Result<Json<NotificationPreferencesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_notification_user(&app_state, &headers).await?;
+
+    match app_state
+        .notification_service
+        .update_preferences(
+            user.id,
+            request.digest_enabled,
+            request.digest_day_of_week,
+            request.push_enabled,
+        )
+        .await
+    {
+        Ok(preferences) => {
+            info!("User {} updated notification preferences", user.id);
+            Ok(Json(preferences.into()))
+        }
+        Err(error) => {
+            warn!(
+                "Failed to update notification preferences of user {}: {}",
+                user.id, error
+            );
+            Err(notification_error_response(&error))
+        }
+    }
+}
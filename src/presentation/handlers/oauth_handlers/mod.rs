@@ -0,0 +1,4 @@
+// Re-export all OAuth handler functions from the oauth module
+pub mod oauth;
+
+pub use oauth::*;
@@ -0,0 +1,9 @@
+// Re-export all OAuth handler functions and DTOs
+
+pub mod oauth_callback_handler;
+mod oauth_dtos;
+pub mod oauth_login_handler;
+mod oauth_utils;
+
+pub use oauth_callback_handler::*;
+pub use oauth_login_handler::*;
@@ -0,0 +1,186 @@
+use super::oauth_dtos::OAuthCallbackQuery;
+use super::oauth_utils::{clear_state_cookie, parse_provider, state_from_cookies};
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::OAuthError;
+use crate::infrastructure::config::Feature;
+use crate::presentation::handlers::{
+    require_feature, session_client, token_error_response, AuthResponse, ConcreteAppState,
+    UserResponse,
+};
+use axum::{
+    extract::{ConnectInfo, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use std::net::SocketAddr;
+use tracing::{info, warn};
+
+fn oauth_error_response(error: &OAuthError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, error, message) = match error {
+        OAuthError::ProviderNotConfigured(_) => (
+            StatusCode::NOT_FOUND,
+            "OAUTH_PROVIDER_NOT_CONFIGURED",
+            "Login with this provider is not available",
+        ),
+        OAuthError::InvalidState => (
+            StatusCode::UNAUTHORIZED,
+            "INVALID_OAUTH_STATE",
+            "Login session is invalid or has expired; please start again",
+        ),
+        OAuthError::TokenExchange(_) => (
+            StatusCode::UNAUTHORIZED,
+            "OAUTH_CODE_REJECTED",
+            "The provider rejected the login",
+        ),
+        OAuthError::EmailUnavailable => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "OAUTH_EMAIL_UNAVAILABLE",
+            "The provider account has no verified email address",
+        ),
+        OAuthError::Profile(_) => (
+            StatusCode::BAD_GATEWAY,
+            "OAUTH_PROVIDER_ERROR",
+            "Failed to fetch the profile from the provider",
+        ),
+        OAuthError::UsernameUnavailable | OAuthError::Repository(_) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "INTERNAL_ERROR",
+            "Failed to complete login",
+        ),
+    };
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message: message.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Handler for the provider's redirect back after a Google or GitHub login
+///
+/// Logs in the account linked to the provider profile, linking an existing account with
+/// the same email or creating a new one, and returns the same response as a password login.
+#[utoipa::path(
+    get,
+    path = "/auth/oauth/{provider}/callback",
+    params(
+        ("provider" = String, Path, description = "Login provider: google or github"),
+        ("code" = Option<String>, Query, description = "Authorization code from the provider"),
+        ("state" = Option<String>, Query, description = "State issued when the login started"),
+        ("error" = Option<String>, Query, description = "Error reported by the provider")
+    ),
+    responses(
+        (status = 200, description = "Login successful", body = AuthResponse),
+        (status = 401, description = "Login denied, invalid state or rejected code", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
+        (status = 404, description = "Unknown or unconfigured provider", body = ErrorResponse),
+        (status = 422, description = "No verified email at the provider", body = ErrorResponse),
+        (status = 501, description = "Social login is disabled", body = ErrorResponse),
+        (status = 502, description = "Provider profile unavailable", body = ErrorResponse)
+    ),
+    tag = "authentication"
+)]
+pub async fn oauth_callback(
+    State(app_state): State<ConcreteAppState>,
+    Path(provider): Path<String>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Query(query): Query<OAuthCallbackQuery>,
+) -> Result<
+    (
+        StatusCode,
+        [(header::HeaderName, String); 1],
+        Json<AuthResponse>,
+    ),
+    (StatusCode, Json<ErrorResponse>),
+> {
+    require_feature(&app_state.features, Feature::SocialLogin)?;
+    let provider = parse_provider(&provider)?;
+
+    if let Some(error) = query.error {
+        info!("{} login was not completed: {}", provider, error);
+        let error_response = ErrorResponse {
+            error: "OAUTH_DENIED".to_string(),
+            message: "Login was cancelled at the provider".to_string(),
+            status_code: StatusCode::UNAUTHORIZED.as_u16(),
+        };
+        return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
+    }
+
+    // The state must come back both from the provider and from the browser that started
+    // the login, otherwise someone else's authorization code could be injected
+    let (Some(code), Some(state)) = (query.code, query.state) else {
+        return Err(oauth_error_response(&OAuthError::InvalidState));
+    };
+    if state_from_cookies(&headers) != Some(state.as_str()) {
+        warn!(
+            "{} login callback without a matching state cookie",
+            provider
+        );
+        return Err(oauth_error_response(&OAuthError::InvalidState));
+    }
+
+    let login = match app_state
+        .oauth_service
+        .complete_login(provider, &code, &state)
+        .await
+    {
+        Ok(login) => login,
+        Err(e) => {
+            warn!("Failed to complete {} login: {}", provider, e);
+            return Err(oauth_error_response(&e));
+        }
+    };
+    let user = login.user;
+
+    let client = session_client(
+        &app_state,
+        connect_info.map(|ConnectInfo(addr)| addr.ip()),
+        &headers,
+    );
+    let token = match app_state.auth_service.issue_token(&user, &client).await {
+        Ok(token) => token,
+        Err(e) => {
+            warn!("Rejected {} login for user {}: {}", provider, user.id, e);
+            return Err(token_error_response(&e));
+        }
+    };
+
+    info!(
+        "Successfully logged in user via {}: {} (new account: {})",
+        provider, user.username, login.created
+    );
+    let response = AuthResponse {
+        token,
+        user: UserResponse {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            created_at: user.created_at.to_rfc3339(),
+        },
+    };
+    Ok((
+        StatusCode::OK,
+        [(header::SET_COOKIE, clear_state_cookie())],
+        Json(response),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oauth_error_responses() {
+        let (status, Json(body)) = oauth_error_response(&OAuthError::InvalidState);
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body.error, "INVALID_OAUTH_STATE");
+
+        let (status, Json(body)) = oauth_error_response(&OAuthError::EmailUnavailable);
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body.status_code, 422);
+
+        let (status, _) = oauth_error_response(&OAuthError::Profile("timeout".to_string()));
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+    }
+}
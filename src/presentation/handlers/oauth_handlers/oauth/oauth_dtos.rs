@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+/// Query parameters the provider sends to the OAuth callback
+#[derive(Debug, Deserialize)]
+pub struct OAuthCallbackQuery {
+    /// Authorization code to exchange; absent when the user denied access
+    pub code: Option<String>,
+    /// State issued when the login started
+    pub state: Option<String>,
+    /// Error reported by the provider (e.g. `access_denied`)
+    pub error: Option<String>,
+}
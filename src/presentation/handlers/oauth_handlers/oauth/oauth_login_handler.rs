@@ -0,0 +1,72 @@
+use super::oauth_utils::{parse_provider, state_cookie};
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::OAuthError;
+use crate::infrastructure::config::Feature;
+use crate::presentation::handlers::{require_feature, ConcreteAppState};
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Redirect, Response},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for starting a Google or GitHub login
+///
+/// Redirects to the provider's consent page; the provider then redirects back to the
+/// callback endpoint.
+#[utoipa::path(
+    get,
+    path = "/auth/oauth/{provider}",
+    params(
+        ("provider" = String, Path, description = "Login provider: google or github")
+    ),
+    responses(
+        (status = 303, description = "Redirect to the provider's consent page"),
+        (status = 404, description = "Unknown or unconfigured provider", body = ErrorResponse),
+        (status = 501, description = "Social login is disabled", body = ErrorResponse)
+    ),
+    tag = "authentication"
+)]
+pub async fn start_oauth_login(
+    State(app_state): State<ConcreteAppState>,
+    Path(provider): Path<String>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    require_feature(&app_state.features, Feature::SocialLogin)?;
+    let provider = parse_provider(&provider)?;
+
+    let authorization = match app_state.oauth_service.start_login(provider) {
+        Ok(authorization) => authorization,
+        Err(OAuthError::ProviderNotConfigured(provider)) => {
+            let error_response = ErrorResponse {
+                error: "OAUTH_PROVIDER_NOT_CONFIGURED".to_string(),
+                message: format!("Login with {} is not available", provider),
+                status_code: StatusCode::NOT_FOUND.as_u16(),
+            };
+            return Err((StatusCode::NOT_FOUND, Json(error_response)));
+        }
+        Err(e) => {
+            warn!("Failed to start {} login: {}", provider, e);
+            let error_response = ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: "Failed to start login".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)));
+        }
+    };
+
+    info!("Starting {} login", provider);
+    let secure = app_state
+        .shorten_url_use_case
+        .base_url()
+        .starts_with("https://");
+    Ok((
+        [(
+            header::SET_COOKIE,
+            state_cookie(&authorization.state, secure),
+        )],
+        Redirect::to(&authorization.url),
+    )
+        .into_response())
+}
@@ -0,0 +1,91 @@
+use crate::application::dto::ErrorResponse;
+use crate::domain::entities::OAuthProvider;
+use crate::domain::services::oauth_service::OAUTH_STATE_EXPIRATION_MINUTES;
+use axum::{
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+
+/// Cookie binding a started login to the browser that started it
+pub(super) const OAUTH_STATE_COOKIE: &str = "oauth_state";
+
+/// Provider named in the request path, or a 404 for unknown ones
+pub(super) fn parse_provider(
+    provider: &str,
+) -> Result<OAuthProvider, (StatusCode, Json<ErrorResponse>)> {
+    OAuthProvider::parse(provider).ok_or_else(|| {
+        let error_response = ErrorResponse {
+            error: "UNKNOWN_OAUTH_PROVIDER".to_string(),
+            message: format!("Unknown login provider: {}", provider),
+            status_code: StatusCode::NOT_FOUND.as_u16(),
+        };
+        (StatusCode::NOT_FOUND, Json(error_response))
+    })
+}
+
+/// `Set-Cookie` value storing the state of a started login
+///
+/// `SameSite=Lax` so the cookie comes along on the provider's redirect back to us.
+pub(super) fn state_cookie(state: &str, secure: bool) -> String {
+    let mut cookie = format!(
+        "{}={}; Path=/auth/oauth; Max-Age={}; HttpOnly; SameSite=Lax",
+        OAUTH_STATE_COOKIE,
+        state,
+        OAUTH_STATE_EXPIRATION_MINUTES * 60
+    );
+    if secure {
+        cookie.push_str("; Secure");
+    }
+    cookie
+}
+
+/// `Set-Cookie` value removing the state cookie
+pub(super) fn clear_state_cookie() -> String {
+    format!(
+        "{}=; Path=/auth/oauth; Max-Age=0; HttpOnly; SameSite=Lax",
+        OAUTH_STATE_COOKIE
+    )
+}
+
+/// State stored in the request's cookie, if any
+pub(super) fn state_from_cookies(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| *name == OAUTH_STATE_COOKIE)
+        .map(|(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_cookie_round_trip() {
+        let cookie = state_cookie("abc123", true);
+        assert!(cookie.starts_with("oauth_state=abc123;"));
+        assert!(cookie.contains("HttpOnly"));
+        assert!(cookie.ends_with("; Secure"));
+        assert!(!state_cookie("abc123", false).contains("Secure"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            "theme=dark; oauth_state=abc123; other=1".parse().unwrap(),
+        );
+        assert_eq!(state_from_cookies(&headers), Some("abc123"));
+        assert_eq!(state_from_cookies(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_parse_provider() {
+        assert_eq!(parse_provider("google").unwrap(), OAuthProvider::Google);
+        assert_eq!(parse_provider("github").unwrap(), OAuthProvider::GitHub);
+        let (status, Json(body)) = parse_provider("myspace").unwrap_err();
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.error, "UNKNOWN_OAUTH_PROVIDER");
+    }
+}
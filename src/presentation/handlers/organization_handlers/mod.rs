@@ -0,0 +1,4 @@
+// Re-export all organization handler functions from the organizations module
+pub mod organizations;
+
+pub use organizations::*;
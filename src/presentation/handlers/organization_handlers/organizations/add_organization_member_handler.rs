@@ -0,0 +1,139 @@
+use super::org_dtos::{AddOrganizationMemberRequest, OrganizationMemberResponse};
+use super::org_errors::org_error_response;
+use super::org_utils::authenticate_org_user;
+use crate::application::dto::ErrorResponse;
+use crate::domain::entities::OrgRole;
+use crate::domain::repositories::{OrganizationRepository, UserRepository};
+use crate::infrastructure::email::EmailMessage;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for adding an existing user to an organization by email
+///
+/// Owners and admins can add members; only the owner can add admins. The new member is
+/// notified by email when an email sender is configured.
+#[utoipa::path(
+    post,
+    path = "/orgs/{id}/members",
+    params(
+        ("id" = i32, Path, description = "Organization ID")
+    ),
+    request_body = AddOrganizationMemberRequest,
+    responses(
+        (status = 201, description = "Member added", body = OrganizationMemberResponse),
+        (status = 400, description = "Invalid role", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Owner or admin role required", body = ErrorResponse),
+        (status = 404, description = "Organization or user not found", body = ErrorResponse),
+        (status = 409, description = "User is already a member", body = ErrorResponse),
+    ),
+    tag = "organizations"
+)]
+pub async fn add_organization_member_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    ValidatedJson(request): ValidatedJson<AddOrganizationMemberRequest>,
+) -> Result<(StatusCode, Json<OrganizationMemberResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_org_user(&app_state, &headers).await?;
+
+    let role = match request.role.as_deref() {
+        None => OrgRole::Member,
+        Some(value) => match OrgRole::parse(value) {
+            Some(role) => role,
+            None => {
+                let error_response = ErrorResponse {
+                    error: "VALIDATION_ERROR".to_string(),
+                    message: format!("Invalid role '{}': use admin or member", value),
+                    status_code: StatusCode::BAD_REQUEST.as_u16(),
+                };
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+            }
+        },
+    };
+
+    let invitee = match app_state
+        .user_repository
+        .find_by_email(&request.email)
+        .await
+    {
+        Ok(Some(invitee)) => invitee,
+        Ok(None) => {
+            let error_response = ErrorResponse {
+                error: "USER_NOT_FOUND".to_string(),
+                message: "No user is registered with this email".to_string(),
+                status_code: StatusCode::NOT_FOUND.as_u16(),
+            };
+            return Err((StatusCode::NOT_FOUND, Json(error_response)));
+        }
+        Err(error) => {
+            warn!("Failed to look up user by email: {}", error);
+            let error_response = ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: "Failed to look up user".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)));
+        }
+    };
+
+    let member = match app_state
+        .org_service
+        .add_member(id, user.id, invitee.id, role)
+        .await
+    {
+        Ok(member) => member,
+        Err(error) => {
+            warn!("Failed to add member to organization {}: {}", id, error);
+            return Err(org_error_response(&error));
+        }
+    };
+
+    info!(
+        "User {} added user {} to organization {} as {}",
+        user.id, invitee.id, id, role
+    );
+
+    // Notify the new member (if email sender is configured)
+    if let Some(email_sender) = app_state.email_sender.as_ref() {
+        if let Ok(Some(organization)) = app_state.organization_repository.find_by_id(id).await {
+            let email_message = EmailMessage::organization_invitation(
+                invitee.email.clone(),
+                &organization.name,
+                &user.username,
+                role.as_str(),
+            );
+            if let Err(e) = email_sender.send_email(email_message).await {
+                // Don't fail the request if email sending fails
+                tracing::error!("Failed to send organization invitation email: {}", e);
+            }
+        }
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(OrganizationMemberResponse::from(member)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_member_request_deserialize() {
+        let json = r#"{"email":"member@example.com","role":"admin"}"#;
+        let request: AddOrganizationMemberRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.email, "member@example.com");
+        assert_eq!(request.role, Some("admin".to_string()));
+
+        let json = r#"{"email":"member@example.com"}"#;
+        let request: AddOrganizationMemberRequest = serde_json::from_str(json).unwrap();
+        assert!(request.role.is_none());
+    }
+}
@@ -0,0 +1,70 @@
+use super::org_dtos::{CreateOrganizationRequest, OrganizationResponse};
+use super::org_errors::org_error_response;
+use super::org_utils::authenticate_org_user;
+use crate::application::dto::ErrorResponse;
+use crate::domain::entities::OrgRole;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for creating an organization owned by the caller
+#[utoipa::path(
+    post,
+    path = "/orgs",
+    request_body = CreateOrganizationRequest,
+    responses(
+        (status = 201, description = "Organization created", body = OrganizationResponse),
+        (status = 400, description = "Invalid name or slug", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 409, description = "Slug already taken", body = ErrorResponse),
+    ),
+    tag = "organizations"
+)]
+pub async fn create_organization_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<CreateOrganizationRequest>,
+) -> Result<(StatusCode, Json<OrganizationResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_org_user(&app_state, &headers).await?;
+
+    match app_state
+        .org_service
+        .create_organization(user.id, &request.name, request.slug.as_deref())
+        .await
+    {
+        Ok(organization) => {
+            info!(
+                "User {} created organization {} ({})",
+                user.id, organization.id, organization.slug
+            );
+            Ok((
+                StatusCode::CREATED,
+                Json(OrganizationResponse::new(
+                    organization,
+                    Some(OrgRole::Owner.as_str().to_string()),
+                )),
+            ))
+        }
+        Err(error) => {
+            warn!("Failed to create organization: {}", error);
+            Err(org_error_response(&error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_request_deserialize() {
+        let json = r#"{"name":"Acme Corp"}"#;
+        let request: CreateOrganizationRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.name, "Acme Corp");
+        assert!(request.slug.is_none());
+    }
+}
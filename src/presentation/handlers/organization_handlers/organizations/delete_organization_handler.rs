@@ -0,0 +1,61 @@
+use super::org_errors::org_error_response;
+use super::org_utils::authenticate_org_user;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for deleting an organization (owner only)
+///
+/// URLs owned by the organization stay with their creators.
+#[utoipa::path(
+    delete,
+    path = "/orgs/{id}",
+    params(
+        ("id" = i32, Path, description = "Organization ID")
+    ),
+    responses(
+        (status = 204, description = "Organization deleted"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Owner role required", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse),
+    ),
+    tag = "organizations"
+)]
+pub async fn delete_organization_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_org_user(&app_state, &headers).await?;
+
+    match app_state.org_service.delete_organization(id, user.id).await {
+        Ok(()) => {
+            info!("User {} deleted organization {}", user.id, id);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(error) => {
+            warn!("Failed to delete organization {}: {}", id, error);
+            Err(org_error_response(&error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insufficient_role_error_response() {
+        let error = ErrorResponse {
+            error: "INSUFFICIENT_ROLE".to_string(),
+            message: "Your role in this organization does not allow this action".to_string(),
+            status_code: StatusCode::FORBIDDEN.as_u16(),
+        };
+        assert_eq!(error.status_code, 403);
+    }
+}
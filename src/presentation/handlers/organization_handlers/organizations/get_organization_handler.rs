@@ -0,0 +1,63 @@
+use super::org_dtos::OrganizationResponse;
+use super::org_errors::org_error_response;
+use super::org_utils::authenticate_org_user;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::warn;
+
+/// Handler for getting an organization the caller belongs to
+#[utoipa::path(
+    get,
+    path = "/orgs/{id}",
+    params(
+        ("id" = i32, Path, description = "Organization ID")
+    ),
+    responses(
+        (status = 200, description = "Organization retrieved", body = OrganizationResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse),
+    ),
+    tag = "organizations"
+)]
+pub async fn get_organization_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<(StatusCode, Json<OrganizationResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_org_user(&app_state, &headers).await?;
+
+    match app_state.org_service.get_organization(id, user.id).await {
+        Ok((organization, membership)) => Ok((
+            StatusCode::OK,
+            Json(OrganizationResponse::new(
+                organization,
+                Some(membership.role.as_str().to_string()),
+            )),
+        )),
+        Err(error) => {
+            warn!("Failed to get organization {}: {}", id, error);
+            Err(org_error_response(&error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_found_error_response() {
+        let error = ErrorResponse {
+            error: "ORGANIZATION_NOT_FOUND".to_string(),
+            message: "Organization not found".to_string(),
+            status_code: StatusCode::NOT_FOUND.as_u16(),
+        };
+        assert_eq!(error.status_code, 404);
+    }
+}
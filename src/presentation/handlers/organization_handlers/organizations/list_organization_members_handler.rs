@@ -0,0 +1,67 @@
+use super::org_dtos::OrganizationMembersResponse;
+use super::org_errors::org_error_response;
+use super::org_utils::authenticate_org_user;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::warn;
+
+/// Handler for listing the members of an organization
+#[utoipa::path(
+    get,
+    path = "/orgs/{id}/members",
+    params(
+        ("id" = i32, Path, description = "Organization ID")
+    ),
+    responses(
+        (status = 200, description = "Members retrieved", body = OrganizationMembersResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse),
+    ),
+    tag = "organizations"
+)]
+pub async fn list_organization_members_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<(StatusCode, Json<OrganizationMembersResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_org_user(&app_state, &headers).await?;
+
+    match app_state.org_service.list_members(id, user.id).await {
+        Ok(members) => Ok((
+            StatusCode::OK,
+            Json(OrganizationMembersResponse {
+                org_id: id,
+                members: members.into_iter().map(Into::into).collect(),
+            }),
+        )),
+        Err(error) => {
+            warn!("Failed to list members of organization {}: {}", id, error);
+            Err(org_error_response(&error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::org_dtos::OrganizationMemberResponse;
+    use crate::domain::entities::{OrgRole, OrganizationMember};
+
+    #[test]
+    fn test_member_response_from_entity() {
+        let member = OrganizationMember {
+            org_id: 1,
+            user_id: 2,
+            role: OrgRole::Admin,
+            joined_at: chrono::Utc::now(),
+        };
+        let response = OrganizationMemberResponse::from(member);
+        assert_eq!(response.user_id, 2);
+        assert_eq!(response.role, "admin");
+    }
+}
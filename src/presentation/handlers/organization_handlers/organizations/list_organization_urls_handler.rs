@@ -0,0 +1,84 @@
+use super::org_errors::org_error_response;
+use super::org_utils::authenticate_org_user;
+use crate::application::dto::{responses::UserUrlsResponse, ErrorResponse};
+use crate::presentation::handlers::url_handlers::urls::url_utils::url_to_info_response;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::warn;
+
+/// Handler for listing the URLs shared within an organization
+#[utoipa::path(
+    get,
+    path = "/orgs/{id}/urls",
+    params(
+        ("id" = i32, Path, description = "Organization ID")
+    ),
+    responses(
+        (status = 200, description = "Organization URLs retrieved", body = UserUrlsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse),
+    ),
+    tag = "organizations"
+)]
+pub async fn list_organization_urls_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<(StatusCode, Json<UserUrlsResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_org_user(&app_state, &headers).await?;
+
+    // Every member can see the organization's URLs
+    if let Err(error) = app_state.org_service.get_organization(id, user.id).await {
+        return Err(org_error_response(&error));
+    }
+
+    match app_state
+        .url_service
+        .get_urls_for_organization(user.id, id)
+        .await
+    {
+        Ok(urls) => {
+            let base_url = app_state.shorten_url_use_case.base_url();
+            let urls: Vec<_> = urls
+                .into_iter()
+                .map(|url| url_to_info_response(url, base_url, None))
+                .collect();
+            Ok((
+                StatusCode::OK,
+                Json(UserUrlsResponse {
+                    total_count: urls.len() as i64,
+                    urls,
+                }),
+            ))
+        }
+        Err(error) => {
+            warn!("Failed to list URLs for organization {}: {}", id, error);
+            let error_response = ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: "Failed to list organization URLs".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_member_error_response() {
+        let error = ErrorResponse {
+            error: "NOT_ORGANIZATION_MEMBER".to_string(),
+            message: "You are not a member of this organization".to_string(),
+            status_code: StatusCode::FORBIDDEN.as_u16(),
+        };
+        assert_eq!(error.status_code, 403);
+    }
+}
@@ -0,0 +1,63 @@
+use super::org_dtos::{OrganizationResponse, OrganizationsResponse};
+use super::org_errors::org_error_response;
+use super::org_utils::authenticate_org_user;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::warn;
+
+/// Handler for listing the organizations the caller belongs to
+#[utoipa::path(
+    get,
+    path = "/orgs",
+    responses(
+        (status = 200, description = "Organizations retrieved", body = OrganizationsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+    ),
+    tag = "organizations"
+)]
+pub async fn list_organizations_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<OrganizationsResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_org_user(&app_state, &headers).await?;
+
+    match app_state.org_service.list_for_user(user.id).await {
+        Ok(organizations) => {
+            let organizations = organizations
+                .into_iter()
+                .map(|organization| OrganizationResponse::new(organization, None))
+                .collect();
+            Ok((
+                StatusCode::OK,
+                Json(OrganizationsResponse { organizations }),
+            ))
+        }
+        Err(error) => {
+            warn!(
+                "Failed to list organizations for user {}: {}",
+                user.id, error
+            );
+            Err(org_error_response(&error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unauthorized_error_response() {
+        let error = ErrorResponse {
+            error: "UNAUTHORIZED".to_string(),
+            message: "Missing or invalid Authorization header".to_string(),
+            status_code: StatusCode::UNAUTHORIZED.as_u16(),
+        };
+        assert_eq!(error.status_code, 401);
+    }
+}
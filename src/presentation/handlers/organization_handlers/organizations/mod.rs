@@ -0,0 +1,26 @@
+// Re-export all organization handler functions and DTOs
+
+pub mod add_organization_member_handler;
+pub mod create_organization_handler;
+pub mod delete_organization_handler;
+pub mod get_organization_handler;
+pub mod list_organization_members_handler;
+pub mod list_organization_urls_handler;
+pub mod list_organizations_handler;
+mod org_dtos;
+pub mod org_errors;
+mod org_utils;
+pub mod remove_organization_member_handler;
+pub mod update_organization_handler;
+
+pub use add_organization_member_handler::*;
+pub use create_organization_handler::*;
+pub use delete_organization_handler::*;
+pub use get_organization_handler::*;
+pub use list_organization_members_handler::*;
+pub use list_organization_urls_handler::*;
+pub use list_organizations_handler::*;
+pub use org_dtos::*;
+pub use org_errors::*;
+pub use remove_organization_member_handler::*;
+pub use update_organization_handler::*;
@@ -0,0 +1,115 @@
+use crate::domain::entities::{Organization, OrganizationMember, OrganizationWithMemberCount};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Request DTO for creating an organization
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct CreateOrganizationRequest {
+    pub name: String,
+    /// URL-friendly identifier; derived from the name when omitted
+    pub slug: Option<String>,
+}
+
+/// Request DTO for updating an organization
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct UpdateOrganizationRequest {
+    pub name: Option<String>,
+    pub slug: Option<String>,
+}
+
+/// Request DTO for adding a member to an organization
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct AddOrganizationMemberRequest {
+    /// Email address of an existing user
+    pub email: String,
+    /// `admin` or `member` (default)
+    pub role: Option<String>,
+}
+
+/// Response DTO describing an organization
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrganizationResponse {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub owner_id: i32,
+    pub created_at: String,
+    /// Role of the caller in the organization, when known
+    pub role: Option<String>,
+}
+
+impl OrganizationResponse {
+    pub fn new(organization: Organization, role: Option<String>) -> Self {
+        Self {
+            id: organization.id,
+            name: organization.name,
+            slug: organization.slug,
+            owner_id: organization.owner_id,
+            created_at: organization.created_at.to_rfc3339(),
+            role,
+        }
+    }
+}
+
+/// Response DTO for a list of organizations
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrganizationsResponse {
+    pub organizations: Vec<OrganizationResponse>,
+}
+
+/// Response DTO describing an organization member
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrganizationMemberResponse {
+    pub user_id: i32,
+    pub role: String,
+    pub joined_at: String,
+}
+
+impl From<OrganizationMember> for OrganizationMemberResponse {
+    fn from(member: OrganizationMember) -> Self {
+        Self {
+            user_id: member.user_id,
+            role: member.role.as_str().to_string(),
+            joined_at: member.joined_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Response DTO for the members of an organization
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrganizationMembersResponse {
+    pub org_id: i32,
+    pub members: Vec<OrganizationMemberResponse>,
+}
+
+/// Response DTO for an organization with its member count (administration)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrganizationSummaryResponse {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub owner_id: i32,
+    pub created_at: String,
+    pub member_count: i64,
+}
+
+impl From<OrganizationWithMemberCount> for OrganizationSummaryResponse {
+    fn from(summary: OrganizationWithMemberCount) -> Self {
+        let organization = summary.organization;
+        Self {
+            id: organization.id,
+            name: organization.name,
+            slug: organization.slug,
+            owner_id: organization.owner_id,
+            created_at: organization.created_at.to_rfc3339(),
+            member_count: summary.member_count,
+        }
+    }
+}
+
+/// Response DTO for all organizations (administration)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrganizationSummariesResponse {
+    pub organizations: Vec<OrganizationSummaryResponse>,
+}
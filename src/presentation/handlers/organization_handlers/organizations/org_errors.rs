@@ -0,0 +1,59 @@
+use crate::application::dto::ErrorResponse;
+use crate::domain::services::OrgServiceError;
+use axum::{http::StatusCode, Json};
+
+/// HTTP status and error code of an organization service error
+///
+/// Shared with the GraphQL API, which reports the same codes.
+pub fn org_error_code(error: &OrgServiceError) -> (StatusCode, &'static str) {
+    match error {
+        OrgServiceError::NotFound => (StatusCode::NOT_FOUND, "ORGANIZATION_NOT_FOUND"),
+        OrgServiceError::NotMember => (StatusCode::FORBIDDEN, "NOT_ORGANIZATION_MEMBER"),
+        OrgServiceError::InsufficientRole => (StatusCode::FORBIDDEN, "INSUFFICIENT_ROLE"),
+        OrgServiceError::InvalidName(_) | OrgServiceError::InvalidSlug(_) => {
+            (StatusCode::BAD_REQUEST, "VALIDATION_ERROR")
+        }
+        OrgServiceError::DuplicateSlug => (StatusCode::CONFLICT, "DUPLICATE_SLUG"),
+        OrgServiceError::AlreadyMember => (StatusCode::CONFLICT, "ALREADY_MEMBER"),
+        OrgServiceError::CannotRemoveOwner | OrgServiceError::CannotAssignOwner => {
+            (StatusCode::BAD_REQUEST, "INVALID_MEMBERSHIP_CHANGE")
+        }
+        OrgServiceError::QuotaExceeded { .. } => {
+            (StatusCode::FORBIDDEN, "ORGANIZATION_QUOTA_EXCEEDED")
+        }
+        OrgServiceError::RateLimited => (StatusCode::TOO_MANY_REQUESTS, "RATE_LIMIT_EXCEEDED"),
+        OrgServiceError::Repository(_) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR"),
+    }
+}
+
+/// Map an organization service error to an HTTP error response
+pub fn org_error_response(error: &OrgServiceError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code) = org_error_code(error);
+
+    let error_response = ErrorResponse {
+        error: code.to_string(),
+        message: error.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_org_error_status_codes() {
+        let (status, Json(body)) = org_error_response(&OrgServiceError::NotMember);
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body.error, "NOT_ORGANIZATION_MEMBER");
+
+        let (status, _) = org_error_response(&OrgServiceError::RateLimited);
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+        let (status, Json(body)) =
+            org_error_response(&OrgServiceError::QuotaExceeded { limit: 10 });
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(body.message.contains("10"));
+    }
+}
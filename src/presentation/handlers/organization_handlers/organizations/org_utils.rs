@@ -0,0 +1,43 @@
+use crate::application::dto::ErrorResponse;
+use crate::domain::entities::User;
+use crate::infrastructure::error_tracking::set_sentry_user;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState};
+use axum::{
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use tracing::warn;
+
+/// Authenticate the caller of an organization endpoint
+pub async fn authenticate_org_user(
+    app_state: &ConcreteAppState,
+    headers: &HeaderMap,
+) -> Result<User, (StatusCode, Json<ErrorResponse>)> {
+    // Require Authorization: Bearer <token>
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(t) if !t.is_empty() => t,
+        _ => {
+            let error_response = ErrorResponse {
+                error: "UNAUTHORIZED".to_string(),
+                message: "Missing or invalid Authorization header".to_string(),
+                status_code: StatusCode::UNAUTHORIZED.as_u16(),
+            };
+            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
+        }
+    };
+
+    // Verify token and get user
+    match app_state.auth_service.verify_token(token).await {
+        Ok(u) => {
+            set_sentry_user(&u);
+            Ok(u)
+        }
+        Err(e) => {
+            warn!("Token verification failed: {}", e);
+            Err(token_error_response(&e))
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use super::org_errors::org_error_response;
+use super::org_utils::authenticate_org_user;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for removing a member from an organization
+///
+/// Members can remove themselves to leave the organization.
+#[utoipa::path(
+    delete,
+    path = "/orgs/{id}/members/{user_id}",
+    params(
+        ("id" = i32, Path, description = "Organization ID"),
+        ("user_id" = i32, Path, description = "ID of the member to remove")
+    ),
+    responses(
+        (status = 204, description = "Member removed"),
+        (status = 400, description = "The owner cannot be removed", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Insufficient role", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse),
+    ),
+    tag = "organizations"
+)]
+pub async fn remove_organization_member_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path((id, member_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_org_user(&app_state, &headers).await?;
+
+    match app_state
+        .org_service
+        .remove_member(id, user.id, member_id)
+        .await
+    {
+        Ok(()) => {
+            info!(
+                "User {} removed user {} from organization {}",
+                user.id, member_id, id
+            );
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(error) => {
+            warn!(
+                "Failed to remove user {} from organization {}: {}",
+                member_id, id, error
+            );
+            Err(org_error_response(&error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cannot_remove_owner_error_response() {
+        let error = ErrorResponse {
+            error: "INVALID_MEMBERSHIP_CHANGE".to_string(),
+            message: "The organization owner cannot be removed".to_string(),
+            status_code: StatusCode::BAD_REQUEST.as_u16(),
+        };
+        assert_eq!(error.status_code, 400);
+    }
+}
@@ -0,0 +1,74 @@
+use super::org_dtos::{OrganizationResponse, UpdateOrganizationRequest};
+use super::org_errors::org_error_response;
+use super::org_utils::authenticate_org_user;
+use crate::application::dto::ErrorResponse;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for updating an organization's name or slug (owners and admins)
+#[utoipa::path(
+    put,
+    path = "/orgs/{id}",
+    params(
+        ("id" = i32, Path, description = "Organization ID")
+    ),
+    request_body = UpdateOrganizationRequest,
+    responses(
+        (status = 200, description = "Organization updated", body = OrganizationResponse),
+        (status = 400, description = "Invalid name or slug", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Owner or admin role required", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse),
+        (status = 409, description = "Slug already taken", body = ErrorResponse),
+    ),
+    tag = "organizations"
+)]
+pub async fn update_organization_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    ValidatedJson(request): ValidatedJson<UpdateOrganizationRequest>,
+) -> Result<(StatusCode, Json<OrganizationResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_org_user(&app_state, &headers).await?;
+
+    match app_state
+        .org_service
+        .update_organization(
+            id,
+            user.id,
+            request.name.as_deref(),
+            request.slug.as_deref(),
+        )
+        .await
+    {
+        Ok(organization) => {
+            info!("User {} updated organization {}", user.id, id);
+            Ok((
+                StatusCode::OK,
+                Json(OrganizationResponse::new(organization, None)),
+            ))
+        }
+        Err(error) => {
+            warn!("Failed to update organization {}: {}", id, error);
+            Err(org_error_response(&error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_request_deserialize() {
+        let json = r#"{"slug":"acme-inc"}"#;
+        let request: UpdateOrganizationRequest = serde_json::from_str(json).unwrap();
+        assert!(request.name.is_none());
+        assert_eq!(request.slug, Some("acme-inc".to_string()));
+    }
+}
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use validator::Validate;
 
 /// Request DTO for password reset request
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct RequestPasswordResetRequest {
     pub email: String,
 }
@@ -15,7 +16,7 @@ pub struct RequestPasswordResetResponse {
 }
 
 /// Request DTO for password reset confirmation
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
 pub struct ResetPasswordRequest {
     pub token: String,
     pub new_password: String,
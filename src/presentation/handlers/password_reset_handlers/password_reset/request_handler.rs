@@ -1,9 +1,32 @@
 use super::dtos::{RequestPasswordResetRequest, RequestPasswordResetResponse};
 use crate::application::dto::responses::ErrorResponse;
+use crate::domain::repositories::user_repository::normalize_email;
 use crate::domain::services::{PasswordResetError, PasswordResetService};
-use crate::infrastructure::email::EmailMessage;
-use crate::presentation::handlers::ConcreteAppState;
-use axum::{extract::State, http::StatusCode, response::Json};
+use crate::infrastructure::config::env_var;
+use crate::infrastructure::password_reset_rate_limiter::PasswordResetRateLimitError;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use std::net::SocketAddr;
+
+/// 429 response for a request turned away by the rate limiter, with `Retry-After` when known
+fn rate_limit_response(error: &PasswordResetRateLimitError) -> Response {
+    let status = StatusCode::TOO_MANY_REQUESTS;
+    let body = Json(ErrorResponse {
+        error: "RATE_LIMIT_EXCEEDED".to_string(),
+        message: error.to_string(),
+        status_code: status.as_u16(),
+    });
+    match error.retry_after_seconds() {
+        Some(seconds) => {
+            (status, [(header::RETRY_AFTER, seconds.to_string())], body).into_response()
+        }
+        None => (status, body).into_response(),
+    }
+}
 
 /// Request password reset (send reset email)
 /// POST /api/auth/password-reset/request
@@ -14,33 +37,35 @@ use axum::{extract::State, http::StatusCode, response::Json};
     responses(
         (status = 200, description = "Password reset email sent", body = RequestPasswordResetResponse),
         (status = 404, description = "User not found", body = ErrorResponse),
-        (status = 429, description = "Too many requests", body = ErrorResponse),
+        (status = 429, description = "Too many requests for this IP or email; see Retry-After", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "password-reset"
 )]
 pub async fn request_password_reset(
     State(state): State<ConcreteAppState>,
-    Json(request): Json<RequestPasswordResetRequest>,
-) -> Result<Json<RequestPasswordResetResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Get client IP (in production, extract from headers)
-    let client_ip = "127.0.0.1"; // Placeholder - should extract from request headers
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<RequestPasswordResetRequest>,
+) -> Result<Json<RequestPasswordResetResponse>, Response> {
+    let client_ip = state
+        .real_ip_extractor
+        .extract(connect_info.map(|ConnectInfo(addr)| addr.ip()), &headers)
+        .map(|ip| ip.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
 
-    // Check rate limits
+    // Both the IP and the email address must be within their limits
     state
         .password_reset_rate_limiter
-        .check_all_limits(client_ip, &request.email)
+        .check_all_limits(&client_ip, &normalize_email(&request.email))
         .await
         .map_err(|e| {
-            let status = StatusCode::TOO_MANY_REQUESTS;
-            (
-                status,
-                Json(ErrorResponse {
-                    error: "Rate limit exceeded".to_string(),
-                    message: e.to_string(),
-                    status_code: status.as_u16(),
-                }),
-            )
+            tracing::warn!(
+                "Password reset request from {} rate limited: {}",
+                client_ip,
+                e
+            );
+            rate_limit_response(&e)
         })?;
 
     // Create password reset service
@@ -49,9 +74,12 @@ pub async fn request_password_reset(
         state.user_repository.clone(),
     );
 
-    // Create reset request and generate token
+    // Create reset request and generate token; the reset email is queued with the token
+    // (if email sender is configured)
+    let base_url = env_var("BASE_URL").unwrap_or_else(|| "http://localhost:8000".to_string());
+    let reset_url_base = state.email_sender.is_some().then_some(base_url.as_str());
     let reset_request = match password_reset_service
-        .create_reset_request(&request.email)
+        .create_reset_request(&request.email, reset_url_base)
         .await
     {
         Ok(reset_req) => reset_req,
@@ -74,7 +102,8 @@ pub async fn request_password_reset(
                                 .to_string(),
                             status_code: StatusCode::TOO_MANY_REQUESTS.as_u16(),
                         }),
-                    ));
+                    )
+                        .into_response());
                 }
                 _ => {
                     return Err((
@@ -84,31 +113,14 @@ pub async fn request_password_reset(
                             message: e.to_string(),
                             status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
                         }),
-                    ));
+                    )
+                        .into_response());
                 }
             }
         }
     };
 
-    // Send password reset email
-    let base_url =
-        std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8000".to_string());
-    let reset_link = format!("{}/reset-password?token={}", base_url, reset_request.token);
-
-    let email_message = EmailMessage::password_reset(
-        reset_request.email.clone(),
-        reset_link,
-        24, // 24 hours expiration
-    );
-
-    // Send email (if email sender is configured)
-    if let Some(email_sender) = state.email_sender.as_ref() {
-        if let Err(e) = email_sender.send_email(email_message).await {
-            tracing::error!("Failed to send password reset email: {}", e);
-            // Don't fail the request if email sending fails
-            // Log the error and continue
-        }
-    } else {
+    if reset_url_base.is_none() {
         tracing::warn!("Email sender not configured, password reset email not sent");
         tracing::info!(
             "Password reset token for {}: {}",
@@ -129,13 +141,17 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_rate_limit_error() {
-        let error = ErrorResponse {
-            error: "Rate limit exceeded".to_string(),
-            message: "Too many requests".to_string(),
-            status_code: StatusCode::TOO_MANY_REQUESTS.as_u16(),
-        };
-        assert_eq!(error.status_code, 429);
+    fn test_rate_limit_response() {
+        let response =
+            rate_limit_response(&PasswordResetRateLimitError::EmailRateLimitExceeded(1800));
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "1800");
+
+        let response = rate_limit_response(&PasswordResetRateLimitError::CooldownPeriodActive(2));
+        assert_eq!(response.headers()[header::RETRY_AFTER], "120");
+
+        let response = rate_limit_response(&PasswordResetRateLimitError::TooManyActiveTokens);
+        assert!(response.headers().get(header::RETRY_AFTER).is_none());
     }
 
     #[test]
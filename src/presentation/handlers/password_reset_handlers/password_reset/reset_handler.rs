@@ -1,9 +1,43 @@
 use super::dtos::{ResetPasswordRequest, ResetPasswordResponse};
 use crate::application::dto::responses::ErrorResponse;
 use crate::domain::services::{PasswordResetError, PasswordResetService};
-use crate::presentation::handlers::ConcreteAppState;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
 use axum::{extract::State, http::StatusCode, response::Json};
 
+/// Map a failed reset to a response with a machine-readable error code
+fn reset_error_response(error: &PasswordResetError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code, message) = match error {
+        PasswordResetError::InvalidToken => (
+            StatusCode::BAD_REQUEST,
+            "INVALID_TOKEN",
+            "Invalid password reset token".to_string(),
+        ),
+        PasswordResetError::TokenExpired => (
+            StatusCode::BAD_REQUEST,
+            "TOKEN_EXPIRED",
+            "Password reset token has expired".to_string(),
+        ),
+        PasswordResetError::TokenAlreadyUsed => (
+            StatusCode::BAD_REQUEST,
+            "TOKEN_ALREADY_USED",
+            "Password reset token has already been used".to_string(),
+        ),
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "PASSWORD_RESET_ERROR",
+            error.to_string(),
+        ),
+    };
+    (
+        status,
+        Json(ErrorResponse {
+            error: code.to_string(),
+            message,
+            status_code: status.as_u16(),
+        }),
+    )
+}
+
 /// Reset password with token
 /// POST /api/auth/password-reset/confirm
 #[utoipa::path(
@@ -12,14 +46,14 @@ use axum::{extract::State, http::StatusCode, response::Json};
     request_body = ResetPasswordRequest,
     responses(
         (status = 200, description = "Password reset successful", body = ResetPasswordResponse),
-        (status = 400, description = "Invalid or expired token", body = ErrorResponse),
+        (status = 400, description = "Invalid, expired or already used token", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "password-reset"
 )]
 pub async fn reset_password(
     State(state): State<ConcreteAppState>,
-    Json(request): Json<ResetPasswordRequest>,
+    ValidatedJson(request): ValidatedJson<ResetPasswordRequest>,
 ) -> Result<Json<ResetPasswordResponse>, (StatusCode, Json<ErrorResponse>)> {
     // Validate password strength (basic validation)
     if request.new_password.len() < 8 {
@@ -40,34 +74,11 @@ pub async fn reset_password(
     );
 
     // Reset password
-    password_reset_service
+    let user = password_reset_service
         .reset_password(&request.token, &request.new_password)
         .await
-        .map_err(|e| {
-            let (status, message) = match e {
-                PasswordResetError::InvalidToken => (
-                    StatusCode::BAD_REQUEST,
-                    "Invalid password reset token".to_string(),
-                ),
-                PasswordResetError::TokenExpired => (
-                    StatusCode::BAD_REQUEST,
-                    "Password reset token has expired".to_string(),
-                ),
-                PasswordResetError::TokenAlreadyUsed => (
-                    StatusCode::BAD_REQUEST,
-                    "Password reset token has already been used".to_string(),
-                ),
-                _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
-            };
-            (
-                status,
-                Json(ErrorResponse {
-                    error: "Password reset error".to_string(),
-                    message,
-                    status_code: status.as_u16(),
-                }),
-            )
-        })?;
+        .map_err(|e| reset_error_response(&e))?;
+    state.auth_service.invalidate_user(user.id).await;
 
     Ok(Json(ResetPasswordResponse {
         message: "Password has been reset successfully".to_string(),
@@ -89,6 +100,19 @@ mod tests {
         assert_eq!(error.status_code, 400);
     }
 
+    #[test]
+    fn test_reused_token_error_code() {
+        let (status, Json(body)) = reset_error_response(&PasswordResetError::TokenAlreadyUsed);
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.error, "TOKEN_ALREADY_USED");
+
+        let (_, Json(body)) = reset_error_response(&PasswordResetError::TokenExpired);
+        assert_eq!(body.error, "TOKEN_EXPIRED");
+
+        let (status, _) = reset_error_response(&PasswordResetError::UserNotFound);
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
     #[test]
     fn test_password_reset_error() {
         let error = ErrorResponse {
@@ -1,12 +1,41 @@
-use crate::domain::entities::ProfilePrivacy;
-use crate::domain::services::DataPrivacyLevel;
+use crate::domain::entities::{ProfilePrivacy, ProfileVisibility};
+use crate::domain::services::{DataPrivacyLevel, VisibilityRecommendation};
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 /// Request DTO for updating privacy settings
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Validate)]
 pub struct UpdatePrivacyRequest {
     pub profile_privacy: Option<ProfilePrivacyRequest>,
     pub field_settings: Option<FieldPrivacySettingsRequest>,
+    pub visibility: Option<ProfileVisibilityRequest>,
+}
+
+/// Public profile visibility request; omitted settings keep their current value
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct ProfileVisibilityRequest {
+    pub show_url_count: Option<bool>,
+    pub show_click_count: Option<bool>,
+    pub show_join_date: Option<bool>,
+    pub show_bio: Option<bool>,
+    pub show_website: Option<bool>,
+    pub show_social_links: Option<bool>,
+    pub show_recent_urls: Option<bool>,
+}
+
+impl ProfileVisibilityRequest {
+    /// Apply the provided settings on top of the current visibility
+    pub fn apply(&self, current: ProfileVisibility) -> ProfileVisibility {
+        ProfileVisibility {
+            show_url_count: self.show_url_count.unwrap_or(current.show_url_count),
+            show_click_count: self.show_click_count.unwrap_or(current.show_click_count),
+            show_join_date: self.show_join_date.unwrap_or(current.show_join_date),
+            show_bio: self.show_bio.unwrap_or(current.show_bio),
+            show_website: self.show_website.unwrap_or(current.show_website),
+            show_social_links: self.show_social_links.unwrap_or(current.show_social_links),
+            show_recent_urls: self.show_recent_urls.unwrap_or(current.show_recent_urls),
+        }
+    }
 }
 
 /// Privacy settings for profile requests
@@ -48,10 +77,64 @@ pub enum DataPrivacyLevelRequest {
 pub struct PrivacySettingsResponse {
     pub profile_privacy: ProfilePrivacyResponse,
     pub field_settings: FieldPrivacySettingsResponse,
+    pub visibility: ProfileVisibilityResponse,
     pub is_searchable: bool,
     pub privacy_description: String,
 }
 
+/// Public profile visibility response
+#[derive(Debug, Serialize)]
+pub struct ProfileVisibilityResponse {
+    pub show_url_count: bool,
+    pub show_click_count: bool,
+    pub show_join_date: bool,
+    pub show_bio: bool,
+    pub show_website: bool,
+    pub show_social_links: bool,
+    pub show_recent_urls: bool,
+}
+
+impl From<ProfileVisibility> for ProfileVisibilityResponse {
+    fn from(visibility: ProfileVisibility) -> Self {
+        Self {
+            show_url_count: visibility.show_url_count,
+            show_click_count: visibility.show_click_count,
+            show_join_date: visibility.show_join_date,
+            show_bio: visibility.show_bio,
+            show_website: visibility.show_website,
+            show_social_links: visibility.show_social_links,
+            show_recent_urls: visibility.show_recent_urls,
+        }
+    }
+}
+
+/// Response DTO for privacy recommendations
+#[derive(Debug, Serialize)]
+pub struct PrivacyRecommendationsResponse {
+    /// Recommended settings, with the visibility suggestions already applied
+    #[serde(flatten)]
+    pub settings: PrivacySettingsResponse,
+    pub visibility_recommendations: Vec<VisibilityRecommendationResponse>,
+}
+
+/// A suggested change to a visibility setting
+#[derive(Debug, Serialize)]
+pub struct VisibilityRecommendationResponse {
+    pub setting: String,
+    pub recommended: bool,
+    pub reason: String,
+}
+
+impl From<VisibilityRecommendation> for VisibilityRecommendationResponse {
+    fn from(recommendation: VisibilityRecommendation) -> Self {
+        Self {
+            setting: recommendation.setting.to_string(),
+            recommended: recommendation.recommended,
+            reason: recommendation.reason.to_string(),
+        }
+    }
+}
+
 /// Profile privacy response
 #[derive(Debug, Serialize)]
 pub enum ProfilePrivacyResponse {
@@ -122,3 +205,21 @@ pub fn convert_data_privacy_response(level: DataPrivacyLevel) -> DataPrivacyLeve
         DataPrivacyLevel::FriendsOnly => DataPrivacyLevelResponse::FriendsOnly,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visibility_request_keeps_omitted_settings() {
+        let request: ProfileVisibilityRequest =
+            serde_json::from_str(r#"{"show_bio": false, "show_url_count": true}"#).unwrap();
+        let current = ProfileVisibility::default();
+
+        let updated = request.apply(current);
+        assert!(!updated.show_bio);
+        assert!(updated.show_url_count);
+        assert_eq!(updated.show_website, current.show_website);
+        assert_eq!(updated.show_recent_urls, current.show_recent_urls);
+    }
+}
@@ -0,0 +1,51 @@
+use super::utils::authenticate_privacy_user;
+use crate::application::dto::responses::{ErrorResponse, PublicUserProfileResponse};
+use crate::domain::repositories::UserRepository;
+use crate::presentation::handlers::profile_handlers::profile::utils::public_profile_response;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+
+/// Preview the current user's public profile as other visitors see it
+/// GET /api/profile/privacy/preview
+#[utoipa::path(
+    get,
+    path = "/profile/privacy/preview",
+    responses(
+        (status = 200, description = "Public profile preview generated successfully", body = PublicUserProfileResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "privacy"
+)]
+pub async fn get_privacy_preview(
+    State(state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Json<PublicUserProfileResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let user_id = authenticate_privacy_user(&state, &headers).await?.id;
+
+    // Private profiles are previewed too, so the owner can see what sharing would reveal
+    match state.user_repository.get_profile(user_id).await {
+        Ok(Some(user)) => Ok(Json(public_profile_response(&state, user).await?)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "User not found".to_string(),
+                message: "User profile not found".to_string(),
+                status_code: 404,
+            }),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Database error".to_string(),
+                message: e.to_string(),
+                status_code: 500,
+            }),
+        )),
+    }
+}
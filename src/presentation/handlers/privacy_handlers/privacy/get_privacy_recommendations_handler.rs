@@ -1,10 +1,13 @@
-use super::dtos::{
-    convert_data_privacy_response, FieldPrivacySettingsResponse, PrivacySettingsResponse,
-    ProfilePrivacyResponse,
-};
+use super::dtos::{PrivacyRecommendationsResponse, VisibilityRecommendationResponse};
+use super::utils::{authenticate_privacy_user, privacy_settings_response};
 use crate::application::dto::responses::ErrorResponse;
 use crate::domain::services::PrivacyService;
-use axum::{http::StatusCode, response::Json};
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
 
 /// Get recommended privacy settings
 /// GET /api/profile/privacy/recommendations
@@ -12,13 +15,17 @@ use axum::{http::StatusCode, response::Json};
     get,
     path = "/profile/privacy/recommendations",
     responses(
-        (status = 200, description = "Recommended privacy settings retrieved successfully", body = PrivacySettingsResponse),
+        (status = 200, description = "Recommended privacy settings retrieved successfully", body = PrivacyRecommendationsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "privacy"
 )]
-pub async fn get_privacy_recommendations(// In a real implementation, you would extract user preferences from JWT token or request
-) -> Result<Json<PrivacySettingsResponse>, (StatusCode, Json<ErrorResponse>)> {
+pub async fn get_privacy_recommendations(
+    State(state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Json<PrivacyRecommendationsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let user = authenticate_privacy_user(&state, &headers).await?;
     let privacy_service = PrivacyService::new();
 
     // For demo purposes, return business account recommendations
@@ -29,28 +36,35 @@ pub async fn get_privacy_recommendations(// In a real implementation, you would
         false, // is_personal_account
     );
 
-    Ok(Json(PrivacySettingsResponse {
-        profile_privacy: ProfilePrivacyResponse::Public,
-        field_settings: FieldPrivacySettingsResponse {
-            first_name: convert_data_privacy_response(field_settings.first_name),
-            last_name: convert_data_privacy_response(field_settings.last_name),
-            bio: convert_data_privacy_response(field_settings.bio),
-            avatar_url: convert_data_privacy_response(field_settings.avatar_url),
-            website: convert_data_privacy_response(field_settings.website),
-            location: convert_data_privacy_response(field_settings.location),
-            email: convert_data_privacy_response(field_settings.email),
-        },
-        is_searchable: true,
-        privacy_description: "Recommended settings for business accounts".to_string(),
+    // Suggest hiding the public profile details the user still shares by default
+    let recommendations = privacy_service.recommend_visibility_changes(&user.profile_visibility);
+    let mut visibility = user.profile_visibility;
+    for recommendation in &recommendations {
+        recommendation.apply(&mut visibility);
+    }
+
+    Ok(Json(PrivacyRecommendationsResponse {
+        settings: privacy_settings_response(
+            &user.privacy,
+            visibility,
+            field_settings,
+            "Recommended settings for business accounts".to_string(),
+        ),
+        visibility_recommendations: recommendations
+            .into_iter()
+            .map(VisibilityRecommendationResponse::from)
+            .collect(),
     }))
 }
 
 #[cfg(test)]
 mod tests {
     use super::super::dtos::{
-        DataPrivacyLevelResponse, FieldPrivacySettingsResponse, PrivacySettingsResponse,
-        ProfilePrivacyResponse,
+        DataPrivacyLevelResponse, FieldPrivacySettingsResponse, PrivacyRecommendationsResponse,
+        PrivacySettingsResponse, ProfilePrivacyResponse, ProfileVisibilityResponse,
+        VisibilityRecommendationResponse,
     };
+    use crate::domain::entities::ProfileVisibility;
 
     #[test]
     fn test_privacy_recommendations_response_structure() {
@@ -66,6 +80,7 @@ mod tests {
                 location: DataPrivacyLevelResponse::Public,
                 email: DataPrivacyLevelResponse::Private,
             },
+            visibility: ProfileVisibilityResponse::from(ProfileVisibility::default()),
             is_searchable: true,
             privacy_description: "Recommended settings for business accounts".to_string(),
         };
@@ -74,5 +89,17 @@ mod tests {
             response.privacy_description,
             "Recommended settings for business accounts"
         );
+
+        let response = PrivacyRecommendationsResponse {
+            settings: response,
+            visibility_recommendations: vec![VisibilityRecommendationResponse {
+                setting: "show_bio".to_string(),
+                recommended: false,
+                reason: "Your bio may contain personal details".to_string(),
+            }],
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["is_searchable"], true);
+        assert_eq!(json["visibility_recommendations"][0]["setting"], "show_bio");
     }
 }
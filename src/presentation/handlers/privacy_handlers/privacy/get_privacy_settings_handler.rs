@@ -1,12 +1,14 @@
-use super::dtos::{
-    convert_data_privacy_response, convert_privacy_response, FieldPrivacySettingsResponse,
-    PrivacySettingsResponse,
-};
+use super::dtos::PrivacySettingsResponse;
+use super::utils::{authenticate_privacy_user, privacy_settings_response};
 use crate::application::dto::responses::ErrorResponse;
 use crate::domain::repositories::UserRepository;
 use crate::domain::services::PrivacyService;
 use crate::presentation::handlers::ConcreteAppState;
-use axum::{extract::State, http::StatusCode, response::Json};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
 
 /// Get current user's privacy settings
 /// GET /api/profile/privacy
@@ -15,6 +17,7 @@ use axum::{extract::State, http::StatusCode, response::Json};
     path = "/profile/privacy",
     responses(
         (status = 200, description = "Privacy settings retrieved successfully", body = PrivacySettingsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 404, description = "User not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
@@ -22,11 +25,9 @@ use axum::{extract::State, http::StatusCode, response::Json};
 )]
 pub async fn get_privacy_settings(
     State(state): State<ConcreteAppState>,
-    // In a real implementation, you would extract user from JWT token
-    // For now, we'll use a placeholder user_id
+    headers: HeaderMap,
 ) -> Result<Json<PrivacySettingsResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // TODO: Extract user_id from JWT token
-    let user_id = 1; // Placeholder
+    let user_id = authenticate_privacy_user(&state, &headers).await?.id;
 
     let user = match state.user_repository.get_profile(user_id).await {
         Ok(Some(user)) => user,
@@ -53,24 +54,15 @@ pub async fn get_privacy_settings(
     };
 
     let privacy_service = PrivacyService::new();
-    let field_settings = PrivacyService::get_default_privacy_settings();
 
-    Ok(Json(PrivacySettingsResponse {
-        profile_privacy: convert_privacy_response(user.privacy.clone()),
-        field_settings: FieldPrivacySettingsResponse {
-            first_name: convert_data_privacy_response(field_settings.first_name),
-            last_name: convert_data_privacy_response(field_settings.last_name),
-            bio: convert_data_privacy_response(field_settings.bio),
-            avatar_url: convert_data_privacy_response(field_settings.avatar_url),
-            website: convert_data_privacy_response(field_settings.website),
-            location: convert_data_privacy_response(field_settings.location),
-            email: convert_data_privacy_response(field_settings.email),
-        },
-        is_searchable: privacy_service.is_profile_searchable(&user.privacy),
-        privacy_description: privacy_service
+    Ok(Json(privacy_settings_response(
+        &user.privacy,
+        user.profile_visibility,
+        PrivacyService::get_default_privacy_settings(),
+        privacy_service
             .get_privacy_description(&user.privacy)
             .to_string(),
-    }))
+    )))
 }
 
 #[cfg(test)]
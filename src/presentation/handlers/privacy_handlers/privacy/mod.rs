@@ -1,10 +1,13 @@
 // Re-export all privacy handler functions and DTOs
 
 mod dtos;
+pub mod get_privacy_preview_handler;
 pub mod get_privacy_recommendations_handler;
 pub mod get_privacy_settings_handler;
 pub mod update_privacy_settings_handler;
+mod utils;
 
+pub use get_privacy_preview_handler::*;
 pub use get_privacy_recommendations_handler::*;
 pub use get_privacy_settings_handler::*;
 pub use update_privacy_settings_handler::*;
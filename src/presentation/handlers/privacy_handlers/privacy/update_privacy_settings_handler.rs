@@ -1,15 +1,17 @@
-use super::dtos::{
-    convert_data_privacy_response, convert_privacy_request, convert_privacy_response,
-    FieldPrivacySettingsResponse, PrivacySettingsResponse, UpdatePrivacyRequest,
-};
+use super::dtos::{convert_privacy_request, PrivacySettingsResponse, UpdatePrivacyRequest};
+use super::utils::{authenticate_privacy_user, privacy_settings_response};
 use crate::application::dto::responses::ErrorResponse;
 use crate::domain::repositories::UserRepository;
 use crate::domain::services::PrivacyService;
-use crate::presentation::handlers::ConcreteAppState;
-use axum::{extract::State, http::StatusCode, response::Json};
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
 
-/// Update privacy settings
-/// PUT /api/profile/privacy
+/// Update privacy settings; settings left out of the request are unchanged
+/// PUT/PATCH /api/profile/privacy
 #[utoipa::path(
     put,
     path = "/profile/privacy",
@@ -17,18 +19,18 @@ use axum::{extract::State, http::StatusCode, response::Json};
     responses(
         (status = 200, description = "Privacy settings updated successfully", body = PrivacySettingsResponse),
         (status = 400, description = "Invalid privacy settings", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "privacy"
 )]
 pub async fn update_privacy_settings(
     State(state): State<ConcreteAppState>,
-    Json(request): Json<UpdatePrivacyRequest>,
-    // In a real implementation, you would extract user from JWT token
-    // For now, we'll use a placeholder user_id
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<UpdatePrivacyRequest>,
 ) -> Result<Json<PrivacySettingsResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // TODO: Extract user_id from JWT token
-    let user_id = 1; // Placeholder
+    let user = authenticate_privacy_user(&state, &headers).await?;
+    let user_id = user.id;
 
     let privacy_service = PrivacyService::new();
 
@@ -77,6 +79,25 @@ pub async fn update_privacy_settings(
         }
     }
 
+    // Update only the visibility settings provided
+    if let Some(visibility) = request.visibility {
+        let visibility = visibility.apply(user.profile_visibility);
+        if let Err(e) = state
+            .user_repository
+            .update_profile_visibility(user_id, &visibility)
+            .await
+        {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Database error".to_string(),
+                    message: e.to_string(),
+                    status_code: 500,
+                }),
+            ));
+        }
+    }
+
     // Get updated user profile
     let user = match state.user_repository.get_profile(user_id).await {
         Ok(Some(user)) => user,
@@ -102,24 +123,14 @@ pub async fn update_privacy_settings(
         }
     };
 
-    let field_settings = PrivacyService::get_default_privacy_settings();
-
-    Ok(Json(PrivacySettingsResponse {
-        profile_privacy: convert_privacy_response(user.privacy.clone()),
-        field_settings: FieldPrivacySettingsResponse {
-            first_name: convert_data_privacy_response(field_settings.first_name),
-            last_name: convert_data_privacy_response(field_settings.last_name),
-            bio: convert_data_privacy_response(field_settings.bio),
-            avatar_url: convert_data_privacy_response(field_settings.avatar_url),
-            website: convert_data_privacy_response(field_settings.website),
-            location: convert_data_privacy_response(field_settings.location),
-            email: convert_data_privacy_response(field_settings.email),
-        },
-        is_searchable: privacy_service.is_profile_searchable(&user.privacy),
-        privacy_description: privacy_service
+    Ok(Json(privacy_settings_response(
+        &user.privacy,
+        user.profile_visibility,
+        PrivacyService::get_default_privacy_settings(),
+        privacy_service
             .get_privacy_description(&user.privacy)
             .to_string(),
-    }))
+    )))
 }
 
 #[cfg(test)]
@@ -0,0 +1,74 @@
+use super::dtos::{
+    convert_data_privacy_response, convert_privacy_response, FieldPrivacySettingsResponse,
+    PrivacySettingsResponse,
+};
+use crate::application::dto::ErrorResponse;
+use crate::domain::entities::{ProfilePrivacy, ProfileVisibility, User};
+use crate::domain::services::privacy_service::FieldPrivacySettings;
+use crate::domain::services::PrivacyService;
+use crate::infrastructure::error_tracking::set_sentry_user;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState};
+use axum::{
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use tracing::warn;
+
+/// Authenticate the caller of a privacy endpoint
+pub async fn authenticate_privacy_user(
+    app_state: &ConcreteAppState,
+    headers: &HeaderMap,
+) -> Result<User, (StatusCode, Json<ErrorResponse>)> {
+    // Require Authorization: Bearer <token>
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(t) if !t.is_empty() => t,
+        _ => {
+            let error_response = ErrorResponse {
+                error: "UNAUTHORIZED".to_string(),
+                message: "Missing or invalid Authorization header".to_string(),
+                status_code: StatusCode::UNAUTHORIZED.as_u16(),
+            };
+            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
+        }
+    };
+
+    // Verify token and get user
+    match app_state.auth_service.verify_token(token).await {
+        Ok(u) => {
+            set_sentry_user(&u);
+            Ok(u)
+        }
+        Err(e) => {
+            warn!("Token verification failed: {}", e);
+            Err(token_error_response(&e))
+        }
+    }
+}
+
+/// Build the privacy settings response for a profile
+pub fn privacy_settings_response(
+    privacy: &ProfilePrivacy,
+    visibility: ProfileVisibility,
+    field_settings: FieldPrivacySettings,
+    privacy_description: String,
+) -> PrivacySettingsResponse {
+    let privacy_service = PrivacyService::new();
+    PrivacySettingsResponse {
+        profile_privacy: convert_privacy_response(privacy.clone()),
+        field_settings: FieldPrivacySettingsResponse {
+            first_name: convert_data_privacy_response(field_settings.first_name),
+            last_name: convert_data_privacy_response(field_settings.last_name),
+            bio: convert_data_privacy_response(field_settings.bio),
+            avatar_url: convert_data_privacy_response(field_settings.avatar_url),
+            website: convert_data_privacy_response(field_settings.website),
+            location: convert_data_privacy_response(field_settings.location),
+            email: convert_data_privacy_response(field_settings.email),
+        },
+        visibility: visibility.into(),
+        is_searchable: privacy_service.is_profile_searchable(privacy),
+        privacy_description,
+    }
+}
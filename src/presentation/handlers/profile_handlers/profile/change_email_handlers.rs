@@ -0,0 +1,259 @@
+use crate::application::dto::requests::{ChangeEmailRequest, EmailChangeConfirmQuery};
+use crate::application::dto::responses::{
+    EmailChangeConfirmResponse, EmailChangeRequestResponse, ErrorResponse,
+};
+use crate::domain::entities::EmailChangeSide;
+use crate::domain::services::email_change_service::{
+    EmailChangeConfirmation, EmailChangeError, EmailChangeService, EMAIL_CHANGE_EXPIRATION_HOURS,
+};
+use crate::infrastructure::email::{EmailChangeConfirmEmail, EmailMessage};
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{info, warn};
+
+fn email_change_error_response(error: &EmailChangeError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code, message) = match error {
+        EmailChangeError::SameEmail => (
+            StatusCode::BAD_REQUEST,
+            "SAME_EMAIL",
+            "The new email address is the current one",
+        ),
+        EmailChangeError::EmailTaken => (
+            StatusCode::CONFLICT,
+            "EMAIL_TAKEN",
+            "The email address is already in use",
+        ),
+        EmailChangeError::InvalidToken => (
+            StatusCode::BAD_REQUEST,
+            "INVALID_TOKEN",
+            "Invalid email change link",
+        ),
+        EmailChangeError::TokenExpired => (
+            StatusCode::GONE,
+            "TOKEN_EXPIRED",
+            "The email change has expired; request it again",
+        ),
+        EmailChangeError::UserNotFound => {
+            (StatusCode::NOT_FOUND, "NOT_FOUND", "User account not found")
+        }
+        EmailChangeError::RepositoryError(e) => {
+            warn!("Email change failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_ERROR",
+                "Failed to change email",
+            )
+        }
+    };
+    (
+        status,
+        Json(ErrorResponse {
+            error: code.to_string(),
+            message: message.to_string(),
+            status_code: status.as_u16(),
+        }),
+    )
+}
+
+/// Start changing the current user's email address
+/// POST /api/profile/change-email
+///
+/// Sends a confirmation link to both the current and the new address. The email only
+/// changes once both links were opened within 24 hours; until then the new address is
+/// shown as the pending email.
+#[utoipa::path(
+    post,
+    path = "/profile/change-email",
+    request_body = ChangeEmailRequest,
+    responses(
+        (status = 202, description = "Confirmation emails sent", body = EmailChangeRequestResponse),
+        (status = 400, description = "Invalid or unchanged email address", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 409, description = "Email address already in use", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "profile"
+)]
+pub async fn request_email_change(
+    State(state): State<ConcreteAppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    ValidatedJson(request): ValidatedJson<ChangeEmailRequest>,
+) -> Result<(StatusCode, Json<EmailChangeRequestResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let service = EmailChangeService::new(
+        state.email_change_repository.clone(),
+        state.user_repository.clone(),
+    );
+    let change = service
+        .request_change(user.id, &request.new_email)
+        .await
+        .map_err(|e| email_change_error_response(&e))?;
+    let pending_email = change.user.pending_email.clone().unwrap_or_default();
+
+    let base_url = state.shorten_url_use_case.base_url();
+    let emails = [
+        (
+            change.user.email.clone(),
+            format!(
+                "{}/profile/change-email/confirm-old?token={}",
+                base_url, change.old_address_token
+            ),
+            false,
+        ),
+        (
+            pending_email.clone(),
+            format!(
+                "{}/profile/change-email/confirm-new?token={}",
+                base_url, change.new_address_token
+            ),
+            true,
+        ),
+    ];
+    for (recipient, confirm_url, to_new_address) in emails {
+        let template = EmailChangeConfirmEmail {
+            username: change.user.username.clone(),
+            new_email: pending_email.clone(),
+            confirm_url,
+            expires_in_hours: EMAIL_CHANGE_EXPIRATION_HOURS as u32,
+            to_new_address,
+        };
+        let email_message = EmailMessage::from_template(recipient, &template);
+
+        // Send email (if email sender is configured)
+        if let Some(email_sender) = state.email_sender.as_ref() {
+            if let Err(e) = email_sender.send_email(email_message).await {
+                tracing::error!("Failed to send email change confirmation: {}", e);
+            }
+        } else {
+            warn!(
+                "Email sender not configured, email change confirmation for user {} not sent",
+                user.id
+            );
+        }
+    }
+
+    info!("User {} requested an email change", user.id);
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(EmailChangeRequestResponse {
+            message: "Confirmation links have been sent to your current and your new address."
+                .to_string(),
+            pending_email,
+            expires_at: change.expires_at.to_rfc3339(),
+        }),
+    ))
+}
+
+/// Confirm one side of an email change and apply it once both sides confirmed
+async fn confirm_email_change(
+    state: &ConcreteAppState,
+    side: EmailChangeSide,
+    token: &str,
+) -> Result<Json<EmailChangeConfirmResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let service = EmailChangeService::new(
+        state.email_change_repository.clone(),
+        state.user_repository.clone(),
+    );
+    let confirmation = service
+        .confirm(side, token)
+        .await
+        .map_err(|e| email_change_error_response(&e))?;
+
+    let response = match confirmation {
+        EmailChangeConfirmation::Pending(change) => EmailChangeConfirmResponse {
+            message: match side {
+                EmailChangeSide::OldAddress => {
+                    "Confirmed. Open the link sent to your new address to finish the change."
+                }
+                EmailChangeSide::NewAddress => {
+                    "Confirmed. Open the link sent to your current address to finish the change."
+                }
+            }
+            .to_string(),
+            completed: false,
+            pending_email: Some(change.new_email),
+        },
+        EmailChangeConfirmation::Completed(user) => {
+            // Cached sessions still carry the old address
+            state.auth_service.invalidate_user(user.id).await;
+            info!("User {} changed their email", user.id);
+            EmailChangeConfirmResponse {
+                message: format!("Your email address is now {}.", user.email),
+                completed: true,
+                pending_email: None,
+            }
+        }
+    };
+    Ok(Json(response))
+}
+
+/// Confirm an email change from the current address
+/// POST /api/profile/change-email/confirm-old?token=X
+#[utoipa::path(
+    post,
+    path = "/profile/change-email/confirm-old",
+    params(
+        ("token" = String, Query, description = "Token from the link sent to the current address")
+    ),
+    responses(
+        (status = 200, description = "Confirmed; `completed` tells whether the email changed", body = EmailChangeConfirmResponse),
+        (status = 400, description = "Invalid token", body = ErrorResponse),
+        (status = 409, description = "Email address taken in the meantime", body = ErrorResponse),
+        (status = 410, description = "Email change expired", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "profile"
+)]
+pub async fn confirm_email_change_old(
+    State(state): State<ConcreteAppState>,
+    Query(query): Query<EmailChangeConfirmQuery>,
+) -> Result<Json<EmailChangeConfirmResponse>, (StatusCode, Json<ErrorResponse>)> {
+    confirm_email_change(&state, EmailChangeSide::OldAddress, &query.token).await
+}
+
+/// Confirm an email change from the new address
+/// POST /api/profile/change-email/confirm-new?token=Y
+#[utoipa::path(
+    post,
+    path = "/profile/change-email/confirm-new",
+    params(
+        ("token" = String, Query, description = "Token from the link sent to the new address")
+    ),
+    responses(
+        (status = 200, description = "Confirmed; `completed` tells whether the email changed", body = EmailChangeConfirmResponse),
+        (status = 400, description = "Invalid token", body = ErrorResponse),
+        (status = 409, description = "Email address taken in the meantime", body = ErrorResponse),
+        (status = 410, description = "Email change expired", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "profile"
+)]
+pub async fn confirm_email_change_new(
+    State(state): State<ConcreteAppState>,
+    Query(query): Query<EmailChangeConfirmQuery>,
+) -> Result<Json<EmailChangeConfirmResponse>, (StatusCode, Json<ErrorResponse>)> {
+    confirm_email_change(&state, EmailChangeSide::NewAddress, &query.token).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_change_error_responses() {
+        let (status, Json(body)) = email_change_error_response(&EmailChangeError::TokenExpired);
+        assert_eq!(status, StatusCode::GONE);
+        assert_eq!(body.error, "TOKEN_EXPIRED");
+
+        let (status, Json(body)) = email_change_error_response(&EmailChangeError::EmailTaken);
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body.status_code, 409);
+
+        let (status, _) = email_change_error_response(&EmailChangeError::InvalidToken);
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::application::dto::requests::ChangePasswordRequest;
+use crate::application::dto::responses::ErrorResponse;
+use crate::domain::services::{AuthServiceError, PasswordResetService};
+use crate::presentation::handlers::{token_error_response, ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::Json,
+};
+use tracing::{info, warn};
+
+fn change_password_error_response(error: &AuthServiceError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code, message) = match error {
+        AuthServiceError::InvalidCredentials => (
+            StatusCode::UNAUTHORIZED,
+            "INVALID_PASSWORD",
+            "The current password is incorrect".to_string(),
+        ),
+        AuthServiceError::InvalidInput(message) => {
+            (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", message.clone())
+        }
+        AuthServiceError::UserNotFound => (
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            "User account not found".to_string(),
+        ),
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "INTERNAL_ERROR",
+            "Failed to change password".to_string(),
+        ),
+    };
+    (
+        status,
+        Json(ErrorResponse {
+            error: code.to_string(),
+            message,
+            status_code: status.as_u16(),
+        }),
+    )
+}
+
+/// Change the current user's password
+/// PATCH /api/profile/password
+///
+/// Signs out every existing session and invalidates pending password reset links.
+#[utoipa::path(
+    patch,
+    path = "/profile/password",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 204, description = "Password changed successfully"),
+        (status = 400, description = "New password is invalid", body = ErrorResponse),
+        (status = 401, description = "Unauthorized or incorrect current password", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "profile"
+)]
+pub async fn change_password(
+    State(state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<ChangePasswordRequest>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    // Require Authorization: Bearer <token>
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(t) if !t.is_empty() => t,
+        _ => {
+            let error_response = ErrorResponse {
+                error: "UNAUTHORIZED".to_string(),
+                message: "Missing or invalid Authorization header".to_string(),
+                status_code: StatusCode::UNAUTHORIZED.as_u16(),
+            };
+            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
+        }
+    };
+
+    let user = match state.auth_service.verify_token(token).await {
+        Ok(u) => u,
+        Err(e) => {
+            warn!("Token verification failed: {}", e);
+            return Err(token_error_response(&e));
+        }
+    };
+
+    let user = state
+        .auth_service
+        .change_password(&user, &request.current_password, &request.new_password)
+        .await
+        .map_err(|e| {
+            warn!("Password change failed for user {}: {}", user.id, e);
+            change_password_error_response(&e)
+        })?;
+
+    // A reset link requested before the change must not be able to undo it
+    let password_reset_service = PasswordResetService::new_default(
+        state.password_reset_repository.clone(),
+        state.user_repository.clone(),
+    );
+    if let Err(e) = password_reset_service
+        .invalidate_all_tokens_for_user(user.id)
+        .await
+    {
+        warn!(
+            "Failed to invalidate reset tokens for user {}: {}",
+            user.id, e
+        );
+    }
+
+    info!("Password changed for user {}", user.id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_password_error_response() {
+        let (status, Json(body)) =
+            change_password_error_response(&AuthServiceError::InvalidCredentials);
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body.error, "INVALID_PASSWORD");
+
+        let (status, Json(body)) = change_password_error_response(&AuthServiceError::InvalidInput(
+            "Password must be at least 8 characters long".to_string(),
+        ));
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.status_code, 400);
+    }
+}
@@ -1,8 +1,7 @@
 use crate::application::dto::requests::DeleteAccountRequest;
 use crate::application::dto::responses::ErrorResponse;
 use crate::domain::repositories::UserRepository;
-use crate::domain::services::AnonymizationService;
-use crate::presentation::handlers::ConcreteAppState;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
 use axum::{extract::State, http::StatusCode, response::Json};
 use bcrypt::verify;
 
@@ -22,7 +21,7 @@ use bcrypt::verify;
 )]
 pub async fn delete_account(
     State(state): State<ConcreteAppState>,
-    Json(request): Json<DeleteAccountRequest>,
+    ValidatedJson(request): ValidatedJson<DeleteAccountRequest>,
 ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
     // TODO: Extract user_id from JWT token
     let user_id = 1; // Placeholder
@@ -79,20 +78,11 @@ pub async fn delete_account(
     }
 
     // Anonymize account data instead of hard deletion
-    let anonymization_service = AnonymizationService::new();
-    let anonymized_data = anonymization_service.anonymize_user_data(&user);
-
-    match state
-        .user_repository
-        .anonymize_account(
-            user_id,
-            &anonymized_data.username,
-            &anonymized_data.email,
-            &anonymized_data.password_hash,
-        )
-        .await
-    {
-        Ok(()) => Ok(StatusCode::NO_CONTENT),
+    match state.user_repository.anonymize_user(user_id).await {
+        Ok(()) => {
+            state.auth_service.invalidate_user(user_id).await;
+            Ok(StatusCode::NO_CONTENT)
+        }
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
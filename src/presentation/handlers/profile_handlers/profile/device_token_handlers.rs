@@ -0,0 +1,145 @@
+use crate::application::dto::requests::RegisterDeviceTokenRequest;
+use crate::application::dto::responses::{DeviceTokensResponse, ErrorResponse};
+use crate::domain::repositories::user_repository::RepositoryError;
+use crate::domain::repositories::UserRepository;
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{info, warn};
+
+fn device_token_error_response(error: &RepositoryError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code, message) = match error {
+        RepositoryError::NotFound => (
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            "User account not found".to_string(),
+        ),
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "INTERNAL_ERROR",
+            "Failed to update device tokens".to_string(),
+        ),
+    };
+    (
+        status,
+        Json(ErrorResponse {
+            error: code.to_string(),
+            message,
+            status_code: status.as_u16(),
+        }),
+    )
+}
+
+/// Register a device of the current user for push notifications
+/// POST /api/profile/device-tokens
+///
+/// Registering a token again keeps it; a token registered by another account moves here.
+#[utoipa::path(
+    post,
+    path = "/profile/device-tokens",
+    request_body = RegisterDeviceTokenRequest,
+    responses(
+        (status = 200, description = "Device registered", body = DeviceTokensResponse),
+        (status = 400, description = "Invalid token", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "profile"
+)]
+pub async fn register_device_token(
+    State(state): State<ConcreteAppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    ValidatedJson(request): ValidatedJson<RegisterDeviceTokenRequest>,
+) -> Result<Json<DeviceTokensResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let user = state
+        .user_repository
+        .add_device_token(user.id, request.token.trim())
+        .await
+        .map_err(|e| {
+            warn!("Failed to register device for user {}: {}", user.id, e);
+            device_token_error_response(&e)
+        })?;
+
+    info!("Registered a push notification device for user {}", user.id);
+    Ok(Json(DeviceTokensResponse {
+        device_tokens: user.device_tokens,
+    }))
+}
+
+/// Stop sending push notifications to a device of the current user
+/// DELETE /api/profile/device-tokens/{token}
+#[utoipa::path(
+    delete,
+    path = "/profile/device-tokens/{token}",
+    params(
+        ("token" = String, Path, description = "Registration token of the device")
+    ),
+    responses(
+        (status = 204, description = "Device removed"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "Device not registered", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "profile"
+)]
+pub async fn remove_device_token(
+    State(state): State<ConcreteAppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(token): Path<String>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let removed = state
+        .user_repository
+        .remove_device_token(user.id, &token)
+        .await
+        .map_err(|e| {
+            warn!("Failed to remove device for user {}: {}", user.id, e);
+            device_token_error_response(&e)
+        })?;
+
+    if !removed {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "NOT_FOUND".to_string(),
+                message: "Device token is not registered".to_string(),
+                status_code: StatusCode::NOT_FOUND.as_u16(),
+            }),
+        ));
+    }
+    info!("Removed a push notification device of user {}", user.id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::Validate;
+
+    #[test]
+    fn test_register_request_validation() {
+        let request = RegisterDeviceTokenRequest {
+            token: "fcm-token".to_string(),
+        };
+        assert!(request.validate().is_ok());
+
+        let request = RegisterDeviceTokenRequest {
+            token: String::new(),
+        };
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_device_token_error_response() {
+        let (status, Json(body)) = device_token_error_response(&RepositoryError::NotFound);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.error, "NOT_FOUND");
+
+        let (status, _) =
+            device_token_error_response(&RepositoryError::Internal("boom".to_string()));
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,38 @@
+use super::utils::{data_export_error_response, export_download_response};
+use crate::application::dto::responses::ErrorResponse;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{Json, Response},
+};
+
+/// Download a data export generated in the background
+/// GET /api/profile/export/download/{token}
+///
+/// The token from the emailed link is the only credential; links expire after 48 hours.
+#[utoipa::path(
+    get,
+    path = "/profile/export/download/{token}",
+    params(
+        ("token" = String, Path, description = "Download token from the export email")
+    ),
+    responses(
+        (status = 200, description = "Export file", body = UserDataExportResponse),
+        (status = 404, description = "Export not found or link expired", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "profile"
+)]
+pub async fn download_data_export(
+    State(state): State<ConcreteAppState>,
+    Path(token): Path<String>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    match state.data_export_service.load(&token).await {
+        Ok((filename, contents)) => Ok(export_download_response(&filename, contents)),
+        Err(e) => {
+            tracing::warn!("Data export download failed: {}", e);
+            Err(data_export_error_response(&e))
+        }
+    }
+}
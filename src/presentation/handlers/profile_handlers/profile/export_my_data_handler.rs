@@ -0,0 +1,245 @@
+use super::utils::{data_export_error_response, data_export_to_response, export_download_response};
+use crate::application::dto::requests::OperationPriority;
+use crate::application::dto::responses::{DataExportQueuedResponse, ErrorResponse};
+use crate::domain::entities::User;
+use crate::domain::repositories::{UserDataExport, UserRepository};
+use crate::domain::services::bulk_processor::BulkProcessorError;
+use crate::domain::services::data_export_service::{
+    export_filename, EXPORT_LINK_TTL_HOURS, LARGE_EXPORT_URL_THRESHOLD,
+};
+use crate::domain::services::DataExportService;
+use crate::infrastructure::email::{EmailMessage, EmailSender};
+use crate::presentation::handlers::{token_error_response, ConcreteAppState};
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use std::sync::Arc;
+use tokio::sync::oneshot;
+use tracing::{error, info, warn};
+
+fn internal_error(message: &str) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: "EXPORT_FAILED".to_string(),
+            message: message.to_string(),
+            status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+        }),
+    )
+}
+
+/// Export all of the current user's data (GDPR data portability)
+/// GET /api/profile/export
+///
+/// Returns the export as a JSON file download. Accounts with more than 1000 URLs are
+/// exported in the background instead and the download link is sent by email. Limited to
+/// one export per user every 24 hours.
+#[utoipa::path(
+    get,
+    path = "/profile/export",
+    responses(
+        (status = 200, description = "Export file", body = UserDataExportResponse),
+        (status = 202, description = "Export queued; a download link will be emailed", body = DataExportQueuedResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 429, description = "An export was already requested in the last 24 hours", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "profile"
+)]
+pub async fn export_my_data(
+    State(state): State<ConcreteAppState>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    // Require Authorization: Bearer <token>
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(t) if !t.is_empty() => t,
+        _ => {
+            let error_response = ErrorResponse {
+                error: "UNAUTHORIZED".to_string(),
+                message: "Missing or invalid Authorization header".to_string(),
+                status_code: StatusCode::UNAUTHORIZED.as_u16(),
+            };
+            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
+        }
+    };
+
+    let user = match state.auth_service.verify_token(token).await {
+        Ok(u) => u,
+        Err(e) => {
+            warn!("Token verification failed: {}", e);
+            return Err(token_error_response(&e));
+        }
+    };
+
+    state
+        .data_export_service
+        .reserve(user.id)
+        .map_err(|e| data_export_error_response(&e))?;
+
+    let total_urls = match state.url_service.get_stats(Some(user.id)).await {
+        Ok(stats) => stats.total_urls,
+        Err(e) => {
+            error!("Failed to count URLs for export of user {}: {}", user.id, e);
+            state.data_export_service.release(user.id);
+            return Err(internal_error("Failed to export account data"));
+        }
+    };
+
+    if total_urls > LARGE_EXPORT_URL_THRESHOLD {
+        return queue_export(&state, user, total_urls).await;
+    }
+
+    let export = match state.user_repository.export_user_data(user.id).await {
+        Ok(Some(export)) => export,
+        Ok(None) => {
+            state.data_export_service.release(user.id);
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "USER_NOT_FOUND".to_string(),
+                    message: "User account not found".to_string(),
+                    status_code: StatusCode::NOT_FOUND.as_u16(),
+                }),
+            ));
+        }
+        Err(e) => {
+            error!("Failed to export data of user {}: {}", user.id, e);
+            state.data_export_service.release(user.id);
+            return Err(internal_error("Failed to export account data"));
+        }
+    };
+
+    info!("Exported data of user {} ({} URLs)", user.id, total_urls);
+    let filename = export_filename(&user.username, chrono::Utc::now().date_naive());
+    let contents = serde_json::to_vec_pretty(&data_export_to_response(export))
+        .map_err(|_| internal_error("Failed to export account data"))?;
+    Ok(export_download_response(&filename, contents))
+}
+
+/// Generate the export through the bulk processor and email the download link when done
+async fn queue_export(
+    state: &ConcreteAppState,
+    user: User,
+    total_urls: i64,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let priority = state
+        .bulk_processor
+        .effective_priority(OperationPriority::Low, &user);
+    let operation_id = state.progress_service.create_operation(1).await;
+
+    let receiver = match state
+        .bulk_processor
+        .process_data_export(operation_id.clone(), user.id, priority)
+        .await
+    {
+        Ok(receiver) => receiver,
+        Err(e) => {
+            error!("Failed to queue data export for user {}: {}", user.id, e);
+            state.data_export_service.release(user.id);
+            return Err(internal_error("Failed to queue the data export"));
+        }
+    };
+
+    info!(
+        "Queued data export {} for user {} ({} URLs)",
+        operation_id, user.id, total_urls
+    );
+    tokio::spawn(deliver_export(
+        state.data_export_service.clone(),
+        state.email_sender.clone(),
+        state.shorten_url_use_case.base_url().to_string(),
+        user,
+        receiver,
+    ));
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(DataExportQueuedResponse {
+            operation_id,
+            message: "Your export is being prepared; a download link will be emailed to you"
+                .to_string(),
+        }),
+    )
+        .into_response())
+}
+
+/// Store a finished background export and email its download link
+async fn deliver_export(
+    data_export_service: DataExportService,
+    email_sender: Option<Arc<dyn EmailSender>>,
+    base_url: String,
+    user: User,
+    receiver: oneshot::Receiver<Result<UserDataExport, BulkProcessorError>>,
+) {
+    let export = match receiver.await {
+        Ok(Ok(export)) => export,
+        Ok(Err(e)) => {
+            error!("Data export for user {} failed: {}", user.id, e);
+            data_export_service.release(user.id);
+            return;
+        }
+        Err(_) => {
+            warn!("Data export for user {} was cancelled", user.id);
+            data_export_service.release(user.id);
+            return;
+        }
+    };
+
+    let filename = export_filename(&user.username, chrono::Utc::now().date_naive());
+    let stored = match serde_json::to_vec_pretty(&data_export_to_response(export)) {
+        Ok(contents) => data_export_service.store(filename, contents).await,
+        Err(e) => {
+            error!(
+                "Failed to serialize data export for user {}: {}",
+                user.id, e
+            );
+            data_export_service.release(user.id);
+            return;
+        }
+    };
+    let stored = match stored {
+        Ok(stored) => stored,
+        Err(e) => {
+            error!("Failed to store data export for user {}: {}", user.id, e);
+            data_export_service.release(user.id);
+            return;
+        }
+    };
+
+    info!(
+        "Stored data export for user {}, link valid until {}",
+        user.id, stored.expires_at
+    );
+    let download_link = format!("{}/profile/export/download/{}", base_url, stored.token);
+    let email_message =
+        EmailMessage::data_export_ready(user.email.clone(), download_link, EXPORT_LINK_TTL_HOURS);
+
+    // Send email (if email sender is configured)
+    if let Some(email_sender) = email_sender.as_ref() {
+        if let Err(e) = email_sender.send_email(email_message).await {
+            error!("Failed to send data export email: {}", e);
+        }
+    } else {
+        warn!(
+            "Email sender not configured, data export email for user {} not sent",
+            user.id
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_internal_error_response() {
+        let (status, Json(body)) = internal_error("Failed to export account data");
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body.error, "EXPORT_FAILED");
+    }
+}
@@ -1,4 +1,4 @@
-use super::utils::user_to_public_profile_response;
+use super::utils::public_profile_response;
 use crate::application::dto::responses::{ErrorResponse, PublicUserProfileResponse};
 use crate::domain::repositories::UserRepository;
 use crate::presentation::handlers::ConcreteAppState;
@@ -37,7 +37,7 @@ pub async fn get_profile_by_username(
                     }),
                 ));
             }
-            Ok(Json(user_to_public_profile_response(user)))
+            Ok(Json(public_profile_response(&state, user).await?))
         }
         Ok(None) => Err((
             StatusCode::NOT_FOUND,
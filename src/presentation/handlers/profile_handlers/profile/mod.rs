@@ -1,6 +1,11 @@
 // Re-export all profile handler functions and utilities
 
+pub mod change_email_handlers;
+pub mod change_password_handler;
 pub mod delete_account_handler;
+pub mod device_token_handlers;
+pub mod download_data_export_handler;
+pub mod export_my_data_handler;
 pub mod get_my_profile_handler;
 pub mod get_profile_by_username_handler;
 pub mod get_public_profile_handler;
@@ -8,7 +13,12 @@ pub mod patch_my_profile_handler;
 pub mod update_my_profile_handler;
 pub mod utils;
 
+pub use change_email_handlers::*;
+pub use change_password_handler::*;
 pub use delete_account_handler::*;
+pub use device_token_handlers::*;
+pub use download_data_export_handler::*;
+pub use export_my_data_handler::*;
 pub use get_my_profile_handler::*;
 pub use get_profile_by_username_handler::*;
 pub use get_public_profile_handler::*;
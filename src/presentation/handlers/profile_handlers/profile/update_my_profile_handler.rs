@@ -1,4 +1,4 @@
-use super::utils::{convert_privacy_request, user_to_profile_response};
+use super::utils::{convert_privacy_request, patched_social_links, user_to_profile_response};
 use crate::application::dto::{
     requests::UpdateProfileRequest,
     responses::{ErrorResponse, UserProfileResponse},
@@ -6,7 +6,7 @@ use crate::application::dto::{
 use crate::domain::entities::ProfilePrivacy;
 use crate::domain::repositories::UserRepository;
 use crate::domain::services::ProfileValidationService;
-use crate::presentation::handlers::ConcreteAppState;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
 use axum::{extract::State, http::StatusCode, response::Json};
 
 /// Update current user's profile
@@ -23,7 +23,7 @@ use axum::{extract::State, http::StatusCode, response::Json};
 )]
 pub async fn update_my_profile(
     State(state): State<ConcreteAppState>,
-    Json(request): Json<UpdateProfileRequest>,
+    ValidatedJson(request): ValidatedJson<UpdateProfileRequest>,
     // In a real implementation, you would extract user from JWT token
     // For now, we'll use a placeholder user_id
 ) -> Result<Json<UserProfileResponse>, (StatusCode, Json<ErrorResponse>)> {
@@ -59,7 +59,14 @@ pub async fn update_my_profile(
             )
         })?;
 
-    match state
+    let social_links = match request.social_links {
+        Some(patch) => {
+            Some(patched_social_links(&state, &validation_service, user_id, patch).await?)
+        }
+        None => None,
+    };
+
+    let updated = state
         .user_repository
         .update_profile(
             user_id,
@@ -71,8 +78,18 @@ pub async fn update_my_profile(
             validated_data.location.as_deref(),
             Some(validated_data.privacy),
         )
-        .await
-    {
+        .await;
+    let updated = match (updated, social_links) {
+        (Ok(_), Some(links)) => {
+            state
+                .user_repository
+                .update_social_links(user_id, links.as_ref())
+                .await
+        }
+        (updated, _) => updated,
+    };
+
+    match updated {
         Ok(user) => Ok(Json(user_to_profile_response(user))),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
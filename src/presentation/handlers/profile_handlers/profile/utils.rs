@@ -1,8 +1,18 @@
 use crate::application::dto::{
     requests::ProfilePrivacyRequest,
-    responses::{ProfilePrivacyResponse, PublicUserProfileResponse, UserProfileResponse},
+    responses::{
+        ErrorResponse, ExportedUrlResponse, ProfilePrivacyResponse, PublicUrlResponse,
+        PublicUserProfileResponse, SocialLinksDto, UserDataExportResponse, UserProfileResponse,
+    },
+};
+use crate::domain::entities::{ProfilePrivacy, SocialLinks, Url, User, UserTier};
+use crate::domain::repositories::{UrlStats, UserDataExport, UserRepository};
+use crate::domain::services::{DataExportError, ProfileValidationService};
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
-use crate::domain::entities::{ProfilePrivacy, User};
 
 /// Convert ProfilePrivacyRequest to ProfilePrivacy
 pub fn convert_privacy_request(privacy: ProfilePrivacyRequest) -> ProfilePrivacy {
@@ -37,24 +47,431 @@ pub fn user_to_profile_response(user: User) -> UserProfileResponse {
         website: user.website,
         location: user.location,
         privacy: convert_privacy_response(user.privacy),
+        social_links: user.social_links.map(SocialLinksDto::from),
+        pending_email: user.pending_email,
         created_at: user.created_at.to_rfc3339(),
         updated_at: user.updated_at.map(|dt| dt.to_rfc3339()),
     }
 }
 
-/// Convert User entity to PublicUserProfileResponse
-pub fn user_to_public_profile_response(user: User) -> PublicUserProfileResponse {
+/// Apply a JSON Merge Patch (RFC 7386) to `target`
+fn json_merge_patch(target: &mut serde_json::Value, patch: serde_json::Value) {
+    let serde_json::Value::Object(patch) = patch else {
+        *target = patch;
+        return;
+    };
+    if !target.is_object() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+    let target = target.as_object_mut().expect("target was made an object");
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(&key);
+        } else {
+            json_merge_patch(target.entry(key).or_insert(serde_json::Value::Null), value);
+        }
+    }
+}
+
+/// Merge a patch of the social links into the stored ones; `None` means no links
+pub fn merge_social_links(
+    current: Option<SocialLinks>,
+    patch: serde_json::Value,
+) -> Result<Option<SocialLinks>, serde_json::Error> {
+    let mut merged = serde_json::to_value(current.map(SocialLinksDto::from))?;
+    json_merge_patch(&mut merged, patch);
+    let links: Option<SocialLinksDto> = serde_json::from_value(merged)?;
+    Ok(links.map(SocialLinks::from))
+}
+
+/// Resolve the social links a profile update leaves the user with
+///
+/// The patch is merged into the stored links and validated before anything is written;
+/// an update removing every link stores none.
+pub async fn patched_social_links(
+    state: &ConcreteAppState,
+    validation_service: &ProfileValidationService,
+    user_id: i32,
+    patch: serde_json::Value,
+) -> Result<Option<SocialLinks>, (StatusCode, Json<ErrorResponse>)> {
+    let validation_error = |message: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Validation error".to_string(),
+                message,
+                status_code: 400,
+            }),
+        )
+    };
+
+    let current = state
+        .user_repository
+        .find_by_id(user_id)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Database error".to_string(),
+                    message: e.to_string(),
+                    status_code: 500,
+                }),
+            )
+        })?
+        .and_then(|user| user.social_links);
+    let merged = merge_social_links(current, patch)
+        .map_err(|e| validation_error(format!("Invalid social links: {}", e)))?;
+    let links = merged
+        .map(|links| validation_service.validate_social_links(links))
+        .transpose()
+        .map_err(|e| validation_error(e.to_string()))?;
+    Ok(links.filter(|links| !links.is_empty()))
+}
+
+/// Number of URLs listed on a public profile that shows recent URLs
+pub const PUBLIC_PROFILE_RECENT_URLS: usize = 5;
+
+/// Link activity that may appear on a public profile
+#[derive(Debug, Default)]
+pub struct PublicProfileActivity {
+    pub stats: Option<UrlStats>,
+    pub recent_urls: Option<Vec<Url>>,
+}
+
+/// Convert User entity to PublicUserProfileResponse, leaving out hidden fields
+pub fn user_to_public_profile_response(
+    user: User,
+    activity: PublicProfileActivity,
+    base_url: &str,
+) -> PublicUserProfileResponse {
     let full_name = user.full_name();
+    let visibility = user.profile_visibility;
+    let stats = activity.stats.as_ref();
     PublicUserProfileResponse {
         id: user.id,
         username: user.username,
         first_name: user.first_name,
         last_name: user.last_name,
         full_name,
-        bio: user.bio,
+        bio: user.bio.filter(|_| visibility.show_bio),
         avatar_url: user.avatar_url,
-        website: user.website,
+        website: user.website.filter(|_| visibility.show_website),
         location: user.location,
-        created_at: user.created_at.to_rfc3339(),
+        social_links: user
+            .social_links
+            .filter(|_| visibility.show_social_links)
+            .map(SocialLinksDto::from),
+        created_at: visibility
+            .show_join_date
+            .then(|| user.created_at.to_rfc3339()),
+        url_count: stats
+            .filter(|_| visibility.show_url_count)
+            .map(|s| s.total_urls),
+        click_count: stats
+            .filter(|_| visibility.show_click_count)
+            .map(|s| s.total_clicks),
+        recent_urls: activity
+            .recent_urls
+            .filter(|_| visibility.show_recent_urls)
+            .map(|urls| {
+                urls.into_iter()
+                    .map(|url| PublicUrlResponse {
+                        short_url: url.short_url(base_url),
+                        short_code: url.short_code,
+                        original_url: url.original_url,
+                        created_at: url.created_at.to_rfc3339(),
+                    })
+                    .collect()
+            }),
+    }
+}
+
+/// Build a user's public profile, loading only the link activity it shows
+pub async fn public_profile_response(
+    state: &ConcreteAppState,
+    user: User,
+) -> Result<PublicUserProfileResponse, (StatusCode, Json<ErrorResponse>)> {
+    let visibility = user.profile_visibility;
+    let database_error = |e: crate::domain::services::ServiceError| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Database error".to_string(),
+                message: e.to_string(),
+                status_code: 500,
+            }),
+        )
+    };
+
+    let mut activity = PublicProfileActivity::default();
+    if visibility.show_url_count || visibility.show_click_count {
+        activity.stats = Some(
+            state
+                .url_service
+                .get_stats(Some(user.id))
+                .await
+                .map_err(database_error)?,
+        );
+    }
+    if visibility.show_recent_urls {
+        activity.recent_urls = Some(
+            state
+                .url_service
+                .get_recent_urls(user.id, PUBLIC_PROFILE_RECENT_URLS)
+                .await
+                .map_err(database_error)?,
+        );
+    }
+
+    let base_url = state.shorten_url_use_case.base_url();
+    Ok(user_to_public_profile_response(user, activity, base_url))
+}
+
+/// Convert a user data export to its response; the password hash is never included
+pub fn data_export_to_response(export: UserDataExport) -> UserDataExportResponse {
+    let UserDataExport { user, urls } = export;
+    let account_status = user.account_status.as_str().to_string();
+    let tier = match user.tier {
+        UserTier::Free => "free",
+        UserTier::Premium => "premium",
+    }
+    .to_string();
+    let profile_picture_url = user.avatar_url.clone();
+
+    let urls: Vec<ExportedUrlResponse> = urls
+        .into_iter()
+        .map(|entry| ExportedUrlResponse {
+            id: entry.url.id,
+            short_code: entry.url.short_code,
+            original_url: entry.url.original_url,
+            status: entry.url.status.to_string(),
+            created_at: entry.url.created_at.to_rfc3339(),
+            updated_at: entry.url.updated_at.to_rfc3339(),
+            expiration_date: entry.url.expiration_date.map(|dt| dt.to_rfc3339()),
+            organization_id: entry.url.organization_id,
+            click_count: entry.click_count,
+        })
+        .collect();
+    let total_clicks = urls.iter().map(|url| url.click_count).sum();
+
+    UserDataExportResponse {
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        profile: user_to_profile_response(user),
+        account_status,
+        tier,
+        profile_picture_url,
+        urls,
+        total_clicks,
+    }
+}
+
+/// Serve an export as a JSON file download
+pub fn export_download_response(filename: &str, contents: Vec<u8>) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        contents,
+    )
+        .into_response()
+}
+
+/// Map a data export error to an HTTP error response
+pub fn data_export_error_response(error: &DataExportError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code) = match error {
+        DataExportError::RateLimited { .. } => {
+            (StatusCode::TOO_MANY_REQUESTS, "RATE_LIMIT_EXCEEDED")
+        }
+        DataExportError::NotFound => (StatusCode::NOT_FOUND, "EXPORT_NOT_FOUND"),
+        DataExportError::IoError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "EXPORT_FAILED"),
+    };
+    let message = match error {
+        DataExportError::IoError(_) => "Failed to store the data export".to_string(),
+        _ => error.to_string(),
+    };
+    (
+        status,
+        Json(ErrorResponse {
+            error: code.to_string(),
+            message,
+            status_code: status.as_u16(),
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Url, UrlStatus, UrlWithClickCount};
+
+    #[test]
+    fn test_data_export_response_aggregates_clicks() {
+        let mut user = User::new_with_timestamp(
+            1,
+            "alice".to_string(),
+            "alice@example.com".to_string(),
+            "secret-hash".to_string(),
+        );
+        user.avatar_url = Some("/uploads/avatars/alice.png".to_string());
+        let url = |id| {
+            Url::new_with_timestamp(
+                id,
+                format!("code{}", id),
+                "https://example.com".to_string(),
+                None,
+                Some(1),
+                UrlStatus::Active,
+            )
+        };
+        let export = UserDataExport {
+            user,
+            urls: vec![
+                UrlWithClickCount::new(url(1), 3),
+                UrlWithClickCount::new(url(2), 4),
+            ],
+        };
+
+        let response = data_export_to_response(export);
+        assert_eq!(response.profile.username, "alice");
+        assert_eq!(response.account_status, "active");
+        assert_eq!(response.tier, "free");
+        assert_eq!(
+            response.profile_picture_url.as_deref(),
+            Some("/uploads/avatars/alice.png")
+        );
+        assert_eq!(response.urls.len(), 2);
+        assert_eq!(response.total_clicks, 7);
+        assert!(!serde_json::to_string(&response)
+            .unwrap()
+            .contains("secret-hash"));
+    }
+
+    #[test]
+    fn test_public_profile_omits_hidden_fields() {
+        let mut user = User::new_with_timestamp(
+            1,
+            "alice".to_string(),
+            "alice@example.com".to_string(),
+            "hash".to_string(),
+        );
+        user.bio = Some("Hello".to_string());
+        user.website = Some("https://alice.dev".to_string());
+        user.profile_visibility.show_bio = false;
+        user.profile_visibility.show_join_date = false;
+        user.profile_visibility.show_click_count = true;
+        let activity = PublicProfileActivity {
+            stats: Some(UrlStats {
+                total_urls: 4,
+                total_clicks: 12,
+                unique_short_codes: 4,
+                estimated_unique_visitors: 9,
+                archived_url_count: 0,
+            }),
+            recent_urls: None,
+        };
+
+        let response = user_to_public_profile_response(user, activity, "https://short.ly");
+        assert_eq!(response.bio, None);
+        assert_eq!(response.website.as_deref(), Some("https://alice.dev"));
+        assert_eq!(response.url_count, None);
+        assert_eq!(response.click_count, Some(12));
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("bio").is_none());
+        assert!(json.get("created_at").is_none());
+        assert!(json.get("recent_urls").is_none());
+    }
+
+    #[test]
+    fn test_merge_social_links_updates_only_patched_links() {
+        let current = SocialLinks {
+            twitter: Some("https://twitter.com/alice".to_string()),
+            github: Some("https://github.com/alice".to_string()),
+            custom: vec![("Blog".to_string(), "https://alice.dev".to_string())],
+            ..SocialLinks::default()
+        };
+
+        let merged = merge_social_links(
+            Some(current.clone()),
+            serde_json::json!({ "github": "https://github.com/alice-dev", "twitter": null }),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(merged.twitter, None);
+        assert_eq!(
+            merged.github.as_deref(),
+            Some("https://github.com/alice-dev")
+        );
+        assert_eq!(merged.custom, current.custom);
+
+        let merged = merge_social_links(Some(current.clone()), serde_json::json!({ "custom": [] }))
+            .unwrap()
+            .unwrap();
+        assert!(merged.custom.is_empty());
+        assert_eq!(merged.github, current.github);
+
+        assert_eq!(
+            merge_social_links(Some(current), serde_json::Value::Null).unwrap(),
+            None
+        );
+        let created = merge_social_links(
+            None,
+            serde_json::json!({ "linkedin": "https://linkedin.com/in/a" }),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            created.linkedin.as_deref(),
+            Some("https://linkedin.com/in/a")
+        );
+    }
+
+    #[test]
+    fn test_public_profile_respects_show_social_links() {
+        let mut user = User::new_with_timestamp(
+            1,
+            "alice".to_string(),
+            "alice@example.com".to_string(),
+            "hash".to_string(),
+        );
+        user.social_links = Some(SocialLinks {
+            github: Some("https://github.com/alice".to_string()),
+            ..SocialLinks::default()
+        });
+
+        let response = user_to_public_profile_response(
+            user.clone(),
+            PublicProfileActivity::default(),
+            "https://short.ly",
+        );
+        assert_eq!(
+            response.social_links.unwrap().github.as_deref(),
+            Some("https://github.com/alice")
+        );
+
+        user.profile_visibility.show_social_links = false;
+        let response = user_to_public_profile_response(
+            user,
+            PublicProfileActivity::default(),
+            "https://short.ly",
+        );
+        assert!(response.social_links.is_none());
+    }
+
+    #[test]
+    fn test_data_export_error_status() {
+        let (status, Json(body)) = data_export_error_response(&DataExportError::RateLimited {
+            retry_after_seconds: 60,
+        });
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body.error, "RATE_LIMIT_EXCEEDED");
+
+        let (status, _) = data_export_error_response(&DataExportError::NotFound);
+        assert_eq!(status, StatusCode::NOT_FOUND);
     }
 }
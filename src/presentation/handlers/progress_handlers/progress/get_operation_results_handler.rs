@@ -0,0 +1,130 @@
+use crate::application::dto::responses::{BulkItemResult, ErrorResponse};
+use crate::domain::services::ProgressServiceError;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+    Json,
+};
+use std::pin::Pin;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tokio_stream::{Stream, StreamExt};
+use tracing::{info, warn};
+
+/// SSE event carrying one item result
+fn item_event(result: &BulkItemResult) -> Result<Event, axum::Error> {
+    Event::default().event("item").json_data(result)
+}
+
+/// Handler streaming the item results of a bulk operation as server-sent events
+///
+/// Results recorded so far are replayed first. While the operation is still running, new
+/// results follow as items finish. A final `done` event is sent once the operation has
+/// finished, so clients know not to reconnect.
+#[utoipa::path(
+    get,
+    path = "/operations/{operation_id}/results",
+    params(
+        ("operation_id" = String, Path, description = "Operation ID to stream results for")
+    ),
+    responses(
+        (status = 200, description = "Stream of `item` events, each holding a BulkItemResult, followed by a `done` event", content_type = "text/event-stream", body = BulkItemResult),
+        (status = 404, description = "Operation not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    tag = "bulk-operations"
+)]
+pub async fn get_operation_results_handler(
+    State(app_state): State<ConcreteAppState>,
+    Path(operation_id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, (StatusCode, Json<ErrorResponse>)>
+{
+    let subscription = match app_state
+        .progress_service
+        .subscribe_item_results(&operation_id)
+        .await
+    {
+        Ok(subscription) => subscription,
+        Err(ProgressServiceError::OperationNotFound) => {
+            warn!("Operation not found for result stream: {}", operation_id);
+            let error_response = ErrorResponse {
+                error: "OPERATION_NOT_FOUND".to_string(),
+                message: "Operation not found or may have expired".to_string(),
+                status_code: StatusCode::NOT_FOUND.as_u16(),
+            };
+            return Err((StatusCode::NOT_FOUND, Json(error_response)));
+        }
+        Err(error) => {
+            warn!(
+                "Error subscribing to results of operation {}: {}",
+                operation_id, error
+            );
+            let error_response = ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: "Failed to stream operation results".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)));
+        }
+    };
+    info!(
+        "Streaming results of operation {} ({} recorded, {})",
+        operation_id,
+        subscription.recorded.len(),
+        if subscription.live.is_some() {
+            "following"
+        } else {
+            "finished"
+        }
+    );
+
+    let live: Pin<Box<dyn Stream<Item = BulkItemResult> + Send>> = match subscription.live {
+        Some(receiver) => Box::pin(BroadcastStream::new(receiver).filter_map(move |result| {
+            result
+                .map_err(|BroadcastStreamRecvError::Lagged(skipped)| {
+                    warn!(
+                        "Result stream of operation {} fell behind, skipped {} results",
+                        operation_id, skipped
+                    );
+                })
+                .ok()
+        })),
+        None => Box::pin(tokio_stream::empty()),
+    };
+    let events = tokio_stream::iter(subscription.recorded)
+        .chain(live)
+        .map(|result| item_event(&result))
+        .chain(tokio_stream::once(Ok(Event::default()
+            .event("done")
+            .data("{}"))));
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operation_not_found_error() {
+        let error = ErrorResponse {
+            error: "OPERATION_NOT_FOUND".to_string(),
+            message: "Operation not found or may have expired".to_string(),
+            status_code: StatusCode::NOT_FOUND.as_u16(),
+        };
+        assert_eq!(error.status_code, 404);
+    }
+
+    #[test]
+    fn test_item_event_is_built_from_result() {
+        let result = BulkItemResult {
+            index: 3,
+            url: "https://example.com".to_string(),
+            success: false,
+            short_code: None,
+            error: Some("Short code already exists".to_string()),
+        };
+        assert!(item_event(&result).is_ok());
+    }
+}
@@ -1,35 +1,62 @@
-use crate::application::dto::responses::{BulkOperationProgress, ErrorResponse};
+use crate::application::dto::requests::PaginationRequest;
+use crate::application::dto::responses::{BulkOperationProgress, ErrorResponse, Page};
 use crate::presentation::handlers::ConcreteAppState;
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
 use tracing::{info, warn};
 
+/// Operations returned per page when the client does not ask for a limit
+const DEFAULT_OPERATIONS_LIMIT: usize = 20;
+
 /// Handler for getting all operations for a user
 #[utoipa::path(
     get,
     path = "/urls/bulk/operations",
+    params(
+        ("after" = Option<String>, Query, description = "next_cursor from the previous page"),
+        ("before" = Option<String>, Query, description = "prev_cursor from the following page"),
+        ("limit" = Option<usize>, Query, description = "Maximum number of operations to return (default 20, max 100)")
+    ),
     responses(
-        (status = 200, description = "Operations retrieved successfully", body = [BulkOperationProgress]),
+        (status = 200, description = "Operations retrieved successfully", body = BulkOperationProgressPage),
+        (status = 400, description = "Invalid cursor", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
     ),
     tag = "bulk-operations"
 )]
 pub async fn get_user_operations_handler(
     State(app_state): State<ConcreteAppState>,
+    Query(pagination): Query<PaginationRequest>,
     // In a real implementation, you'd extract user_id from the auth token
     // For now, we'll use a placeholder user_id
-) -> Result<(StatusCode, Json<Vec<BulkOperationProgress>>), (StatusCode, Json<ErrorResponse>)> {
+) -> Result<(StatusCode, Json<Page<BulkOperationProgress>>), (StatusCode, Json<ErrorResponse>)> {
     let progress_service = &app_state.progress_service;
     let user_id = 1; // This should come from authentication
     info!("Getting operations for user: {}", user_id);
 
     match progress_service.get_user_operations(user_id).await {
-        Ok(operations) => {
+        Ok(mut operations) => {
             info!(
                 "Retrieved {} operations for user {}",
                 operations.len(),
                 user_id
             );
-            Ok((StatusCode::OK, Json(operations)))
+            // Operations are tracked in a map; give pages a stable order
+            operations.sort_by(|a, b| a.operation_id.cmp(&b.operation_id));
+            match Page::paginate_in_memory(operations, &pagination, DEFAULT_OPERATIONS_LIMIT) {
+                Some(page) => Ok((StatusCode::OK, Json(page))),
+                None => {
+                    let error_response = ErrorResponse {
+                        error: "INVALID_CURSOR".to_string(),
+                        message: "Malformed pagination cursor".to_string(),
+                        status_code: StatusCode::BAD_REQUEST.as_u16(),
+                    };
+                    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+                }
+            }
         }
         Err(error) => {
             warn!(
@@ -59,4 +86,60 @@ mod tests {
         };
         assert_eq!(error.status_code, 500);
     }
+
+    fn pagination(after: Option<&str>, before: Option<&str>, limit: usize) -> PaginationRequest {
+        PaginationRequest {
+            after: after.map(str::to_string),
+            before: before.map(str::to_string),
+            limit: Some(limit),
+        }
+    }
+
+    #[test]
+    fn test_in_memory_pages_walk_both_ways() {
+        let items: Vec<i32> = (0..25).collect();
+
+        let first =
+            Page::paginate_in_memory(items.clone(), &pagination(None, None, 10), 20).unwrap();
+        assert_eq!(first.data, (0..10).collect::<Vec<_>>());
+        assert_eq!(first.total, Some(25));
+        assert!(first.prev_cursor.is_none());
+
+        let second = Page::paginate_in_memory(
+            items.clone(),
+            &pagination(first.next_cursor.as_deref(), None, 10),
+            20,
+        )
+        .unwrap();
+        let last = Page::paginate_in_memory(
+            items.clone(),
+            &pagination(second.next_cursor.as_deref(), None, 10),
+            20,
+        )
+        .unwrap();
+        assert_eq!(last.data, (20..25).collect::<Vec<_>>());
+        assert!(last.next_cursor.is_none());
+
+        let back = Page::paginate_in_memory(
+            items.clone(),
+            &pagination(None, last.prev_cursor.as_deref(), 10),
+            20,
+        )
+        .unwrap();
+        assert_eq!(back.data, second.data);
+        assert_eq!(back.prev_cursor, second.prev_cursor);
+    }
+
+    #[test]
+    fn test_in_memory_pagination_rejects_bad_cursors() {
+        let items: Vec<i32> = (0..5).collect();
+        assert!(
+            Page::paginate_in_memory(items.clone(), &pagination(Some("!!"), None, 10), 20)
+                .is_none()
+        );
+
+        let page = Page::paginate_in_memory(items.clone(), &pagination(None, None, 2), 20).unwrap();
+        let cursor = page.next_cursor.as_deref();
+        assert!(Page::paginate_in_memory(items, &pagination(cursor, cursor, 2), 20).is_none());
+    }
 }
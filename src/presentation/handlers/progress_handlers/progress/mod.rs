@@ -1,9 +1,13 @@
 // Re-export all progress handler functions
 
 pub mod cancel_operation_handler;
+pub mod get_operation_results_handler;
 pub mod get_progress_handler;
 pub mod get_user_operations_handler;
+pub mod operation_chain_handlers;
 
 pub use cancel_operation_handler::*;
+pub use get_operation_results_handler::*;
 pub use get_progress_handler::*;
 pub use get_user_operations_handler::*;
+pub use operation_chain_handlers::*;
@@ -0,0 +1,173 @@
+use crate::application::dto::requests::ChainedOperationRequest;
+use crate::application::dto::responses::{
+    ChainedOperationResponse, ErrorResponse, OperationChainResponse,
+};
+use crate::domain::services::bulk_processor::BulkProcessorError;
+use crate::domain::services::ProgressServiceError;
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use tracing::{info, warn};
+
+fn operation_not_found() -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: "OPERATION_NOT_FOUND".to_string(),
+        message: "Operation not found or may have expired".to_string(),
+        status_code: StatusCode::NOT_FOUND.as_u16(),
+    };
+    (StatusCode::NOT_FOUND, Json(error_response))
+}
+
+fn chain_error_response(error: &BulkProcessorError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code, message) = match error {
+        BulkProcessorError::ChainRejected(ProgressServiceError::OperationNotFound) => {
+            return operation_not_found();
+        }
+        BulkProcessorError::ChainRejected(ProgressServiceError::InvalidOperationState) => (
+            StatusCode::CONFLICT,
+            "INVALID_OPERATION_STATE",
+            "The operation has finished or already has a next operation".to_string(),
+        ),
+        BulkProcessorError::ChainRejected(e @ ProgressServiceError::ChainTooLong(_)) => {
+            (StatusCode::BAD_REQUEST, "CHAIN_TOO_LONG", e.to_string())
+        }
+        BulkProcessorError::InvalidData(message) => {
+            (StatusCode::BAD_REQUEST, "INVALID_DATA", message.clone())
+        }
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "INTERNAL_ERROR",
+            "Failed to chain the operation".to_string(),
+        ),
+    };
+    let error_response = ErrorResponse {
+        error: code.to_string(),
+        message,
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Handler running a batch operation once another operation has completed
+///
+/// The new operation waits as `pending` and is cancelled if the operation it depends on
+/// fails or is cancelled. Without `url_ids` it applies to the URLs that operation created.
+#[utoipa::path(
+    post,
+    path = "/operations/{operation_id}/chain",
+    params(
+        ("operation_id" = String, Path, description = "Operation the new operation waits for")
+    ),
+    request_body = ChainedOperationRequest,
+    responses(
+        (status = 202, description = "Operation chained", body = ChainedOperationResponse),
+        (status = 400, description = "Invalid data or chain too long", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "Operation not found", body = ErrorResponse),
+        (status = 409, description = "Operation finished or already chained", body = ErrorResponse),
+    ),
+    tag = "bulk-operations"
+)]
+pub async fn chain_operation_handler(
+    State(app_state): State<ConcreteAppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(operation_id): Path<String>,
+    ValidatedJson(request): ValidatedJson<ChainedOperationRequest>,
+) -> Result<(StatusCode, Json<ChainedOperationResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let child_operation_id = app_state
+        .bulk_processor
+        .chain_bulk_operation(
+            operation_id.clone(),
+            request.operation,
+            request.url_ids,
+            request.data,
+            Some(user.id),
+        )
+        .await
+        .map_err(|e| {
+            warn!(
+                "Failed to chain an operation to {} for user {}: {}",
+                operation_id, user.id, e
+            );
+            chain_error_response(&e)
+        })?;
+
+    info!(
+        "User {} chained operation {} to {}",
+        user.id, child_operation_id, operation_id
+    );
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ChainedOperationResponse {
+            parent_operation_id: operation_id,
+            child_operation_id,
+        }),
+    ))
+}
+
+/// Handler for the progress of every operation in the chain of an operation
+#[utoipa::path(
+    get,
+    path = "/operations/{operation_id}/chain",
+    params(
+        ("operation_id" = String, Path, description = "Any operation of the chain")
+    ),
+    responses(
+        (status = 200, description = "Operations of the chain, first to last", body = OperationChainResponse),
+        (status = 404, description = "Operation not found", body = ErrorResponse),
+    ),
+    tag = "bulk-operations"
+)]
+pub async fn get_operation_chain_handler(
+    State(app_state): State<ConcreteAppState>,
+    Path(operation_id): Path<String>,
+) -> Result<Json<OperationChainResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match app_state.progress_service.get_chain(&operation_id).await {
+        Ok(operations) => Ok(Json(OperationChainResponse { operations })),
+        Err(ProgressServiceError::OperationNotFound) => {
+            warn!("Operation not found for chain: {}", operation_id);
+            Err(operation_not_found())
+        }
+        Err(error) => {
+            warn!(
+                "Error retrieving the chain of operation {}: {}",
+                operation_id, error
+            );
+            let error_response = ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: "Failed to retrieve the operation chain".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_error_response() {
+        let (status, Json(body)) = chain_error_response(&BulkProcessorError::ChainRejected(
+            ProgressServiceError::OperationNotFound,
+        ));
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.error, "OPERATION_NOT_FOUND");
+
+        let (status, _) = chain_error_response(&BulkProcessorError::ChainRejected(
+            ProgressServiceError::InvalidOperationState,
+        ));
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let (status, Json(body)) = chain_error_response(&BulkProcessorError::ChainRejected(
+            ProgressServiceError::ChainTooLong(5),
+        ));
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.error, "CHAIN_TOO_LONG");
+    }
+}
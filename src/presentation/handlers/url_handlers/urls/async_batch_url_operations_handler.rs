@@ -1,7 +1,7 @@
 use crate::application::dto::{
     requests::BatchUrlOperationRequest, responses::BulkOperationProgress, ErrorResponse,
 };
-use crate::presentation::handlers::ConcreteAppState;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState, ValidatedJson};
 use axum::{
     extract::State,
     http::HeaderMap,
@@ -19,13 +19,14 @@ use tracing::{info, warn};
         (status = 202, description = "Batch operation started", body = BulkOperationProgress),
         (status = 400, description = "Bad request", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
     ),
     tag = "bulk-operations"
 )]
 pub async fn async_batch_url_operations_handler(
     State(app_state): State<ConcreteAppState>,
     headers: HeaderMap,
-    Json(request): Json<BatchUrlOperationRequest>,
+    ValidatedJson(request): ValidatedJson<BatchUrlOperationRequest>,
 ) -> Result<(StatusCode, Json<BulkOperationProgress>), (StatusCode, Json<ErrorResponse>)> {
     // Require Authorization: Bearer <token>
     let auth_header = headers
@@ -48,15 +49,13 @@ pub async fn async_batch_url_operations_handler(
         Ok(u) => u,
         Err(e) => {
             warn!("Token verification failed: {}", e);
-            let error_response = ErrorResponse {
-                error: "INVALID_TOKEN".to_string(),
-                message: "Invalid or expired token".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
+            return Err(token_error_response(&e));
         }
     };
 
+    let priority = app_state
+        .bulk_processor
+        .effective_priority(request.priority, &user);
     let total_items = request.url_ids.len();
 
     // Create operation for progress tracking
@@ -79,6 +78,7 @@ pub async fn async_batch_url_operations_handler(
             request.url_ids,
             request.data,
             Some(user.id),
+            priority,
         )
         .await
     {
@@ -1,7 +1,8 @@
 use crate::application::dto::{
     requests::BulkShortenUrlsRequest, responses::BulkOperationProgress, ErrorResponse,
 };
-use crate::presentation::handlers::ConcreteAppState;
+use crate::presentation::handlers::url_handlers::urls::url_utils::authorize_organization_urls;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState, ValidatedJson};
 use axum::{
     extract::State,
     http::HeaderMap,
@@ -11,6 +12,9 @@ use axum::{
 use tracing::{info, warn};
 
 /// Handler for async bulk URL shortening with progress tracking
+///
+/// Items for an organization are checked like `/shorten` requests before the operation is
+/// queued.
 #[utoipa::path(
     post,
     path = "/urls/bulk/async",
@@ -19,13 +23,17 @@ use tracing::{info, warn};
         (status = 202, description = "Bulk operation started", body = BulkOperationProgress),
         (status = 400, description = "Bad request", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended, not an organization member or organization quota reached", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse),
+        (status = 422, description = "More than 1000 items or an invalid item", body = ValidationErrorResponse),
+        (status = 429, description = "Organization rate limit exceeded", body = ErrorResponse),
     ),
     tag = "bulk-operations"
 )]
 pub async fn async_bulk_shorten_urls_handler(
     State(app_state): State<ConcreteAppState>,
     headers: HeaderMap,
-    Json(request): Json<BulkShortenUrlsRequest>,
+    ValidatedJson(request): ValidatedJson<BulkShortenUrlsRequest>,
 ) -> Result<(StatusCode, Json<BulkOperationProgress>), (StatusCode, Json<ErrorResponse>)> {
     // Require Authorization: Bearer <token>
     let auth_header = headers
@@ -48,16 +56,16 @@ pub async fn async_bulk_shorten_urls_handler(
         Ok(u) => u,
         Err(e) => {
             warn!("Token verification failed: {}", e);
-            let error_response = ErrorResponse {
-                error: "INVALID_TOKEN".to_string(),
-                message: "Invalid or expired token".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
+            return Err(token_error_response(&e));
         }
     };
 
+    authorize_organization_urls(&app_state, &request.items, user.id).await?;
+
     let user_id = Some(user.id);
+    let priority = app_state
+        .bulk_processor
+        .effective_priority(request.priority, &user);
     let total_items = request.items.len();
 
     // Create operation for progress tracking
@@ -74,7 +82,7 @@ pub async fn async_bulk_shorten_urls_handler(
     // Start background processing
     match app_state
         .bulk_processor
-        .process_bulk_url_creation(operation_id.clone(), request.items, user_id)
+        .process_bulk_url_creation(operation_id.clone(), request.items, user_id, priority)
         .await
     {
         Ok(_) => {
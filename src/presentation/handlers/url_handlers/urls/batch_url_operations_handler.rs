@@ -3,13 +3,9 @@ use crate::application::dto::{
     responses::{BatchOperationResponse, BatchOperationResult},
     ErrorResponse,
 };
-use crate::presentation::handlers::ConcreteAppState;
-use axum::{
-    extract::State,
-    http::HeaderMap,
-    http::{header, StatusCode},
-    Json,
-};
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{extract::State, http::StatusCode, Json};
 use tracing::{info, warn};
 
 /// Handler for batch URL operations
@@ -21,44 +17,15 @@ use tracing::{info, warn};
         (status = 200, description = "Batch operation completed", body = BatchOperationResponse),
         (status = 400, description = "Bad request", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
     ),
     tag = "bulk-operations"
 )]
 pub async fn batch_url_operations_handler(
     State(app_state): State<ConcreteAppState>,
-    headers: HeaderMap,
-    Json(request): Json<BatchUrlOperationRequest>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    ValidatedJson(request): ValidatedJson<BatchUrlOperationRequest>,
 ) -> Result<(StatusCode, Json<BatchOperationResponse>), (StatusCode, Json<ErrorResponse>)> {
-    // Require Authorization: Bearer <token>
-    let auth_header = headers
-        .get(header::AUTHORIZATION)
-        .and_then(|v| v.to_str().ok());
-    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
-        Some(t) if !t.is_empty() => t,
-        _ => {
-            let error_response = ErrorResponse {
-                error: "UNAUTHORIZED".to_string(),
-                message: "Missing or invalid Authorization header".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
-        }
-    };
-
-    // Verify token and get user
-    let user = match app_state.auth_service.verify_token(token).await {
-        Ok(u) => u,
-        Err(e) => {
-            warn!("Token verification failed: {}", e);
-            let error_response = ErrorResponse {
-                error: "INVALID_TOKEN".to_string(),
-                message: "Invalid or expired token".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
-        }
-    };
-
     info!(
         "Received batch operation request: {:?} for {} URLs (user: {})",
         request.operation,
@@ -69,7 +36,7 @@ pub async fn batch_url_operations_handler(
     match app_state
         .url_service
         .process_batch_operations(
-            &request.operation,
+            request.operation.as_ref(),
             &request.url_ids,
             request.data.as_ref(),
             Some(user.id),
@@ -78,7 +45,7 @@ pub async fn batch_url_operations_handler(
     {
         Ok(result) => {
             let response = BatchOperationResponse {
-                operation: format!("{:?}", request.operation),
+                operation: request.operation.name().to_string(),
                 total_processed: result.total_processed,
                 successful: result.successful,
                 failed: result.failed,
@@ -118,7 +85,11 @@ mod tests {
     fn test_batch_operation_request_deserialize() {
         let json = r#"{"operation":"deactivate","url_ids":[1,2,3]}"#;
         let request: Result<BatchUrlOperationRequest, _> = serde_json::from_str(json);
-        assert!(request.is_ok());
+        assert_eq!(request.unwrap().operation.name(), "deactivate");
+
+        let json = r#"{"operation":"archive","url_ids":[1]}"#;
+        let error = serde_json::from_str::<BatchUrlOperationRequest>(json).unwrap_err();
+        assert!(error.to_string().contains("unknown variant `archive`"));
     }
 
     #[test]
@@ -3,13 +3,9 @@ use crate::application::dto::{
     responses::{BatchOperationResponse, BatchOperationResult},
     ErrorResponse,
 };
-use crate::presentation::handlers::ConcreteAppState;
-use axum::{
-    extract::State,
-    http::HeaderMap,
-    http::{header, StatusCode},
-    Json,
-};
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{extract::State, http::StatusCode, Json};
 use tracing::{info, warn};
 
 /// Handler for bulk URL deletion
@@ -21,44 +17,15 @@ use tracing::{info, warn};
         (status = 200, description = "URLs deleted successfully", body = BatchOperationResponse),
         (status = 400, description = "Bad request", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
     ),
     tag = "bulk-operations"
 )]
 pub async fn bulk_delete_handler(
     State(app_state): State<ConcreteAppState>,
-    headers: HeaderMap,
-    Json(request): Json<BulkDeleteRequest>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    ValidatedJson(request): ValidatedJson<BulkDeleteRequest>,
 ) -> Result<(StatusCode, Json<BatchOperationResponse>), (StatusCode, Json<ErrorResponse>)> {
-    // Require Authorization: Bearer <token>
-    let auth_header = headers
-        .get(header::AUTHORIZATION)
-        .and_then(|v| v.to_str().ok());
-    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
-        Some(t) if !t.is_empty() => t,
-        _ => {
-            let error_response = ErrorResponse {
-                error: "UNAUTHORIZED".to_string(),
-                message: "Missing or invalid Authorization header".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
-        }
-    };
-
-    // Verify token and get user
-    let user = match app_state.auth_service.verify_token(token).await {
-        Ok(u) => u,
-        Err(e) => {
-            warn!("Token verification failed: {}", e);
-            let error_response = ErrorResponse {
-                error: "INVALID_TOKEN".to_string(),
-                message: "Invalid or expired token".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
-        }
-    };
-
     info!(
         "Received bulk delete request for {} URLs (user: {}, force: {:?})",
         request.url_ids.len(),
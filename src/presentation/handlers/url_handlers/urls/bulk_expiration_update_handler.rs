@@ -1,18 +1,17 @@
 use crate::application::dto::{
-    requests::BulkExpirationUpdateRequest,
+    requests::{BulkExpirationClearRequest, BulkExpirationUpdateRequest},
     responses::{BatchOperationResponse, BatchOperationResult},
     ErrorResponse,
 };
-use crate::presentation::handlers::ConcreteAppState;
-use axum::{
-    extract::State,
-    http::HeaderMap,
-    http::{header, StatusCode},
-    Json,
-};
+use crate::domain::entities::User;
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{extract::State, http::StatusCode, Json};
 use tracing::{info, warn};
 
 /// Handler for bulk expiration updates
+///
+/// A `null` expiration date removes the expiration of every listed URL.
 #[utoipa::path(
     patch,
     path = "/urls/bulk/expiration",
@@ -21,62 +20,66 @@ use tracing::{info, warn};
         (status = 200, description = "Expiration updated successfully", body = BatchOperationResponse),
         (status = 400, description = "Bad request", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
     ),
     tag = "bulk-operations"
 )]
 pub async fn bulk_expiration_update_handler(
     State(app_state): State<ConcreteAppState>,
-    headers: HeaderMap,
-    Json(request): Json<BulkExpirationUpdateRequest>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    ValidatedJson(request): ValidatedJson<BulkExpirationUpdateRequest>,
 ) -> Result<(StatusCode, Json<BatchOperationResponse>), (StatusCode, Json<ErrorResponse>)> {
-    // Require Authorization: Bearer <token>
-    let auth_header = headers
-        .get(header::AUTHORIZATION)
-        .and_then(|v| v.to_str().ok());
-    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
-        Some(t) if !t.is_empty() => t,
-        _ => {
-            let error_response = ErrorResponse {
-                error: "UNAUTHORIZED".to_string(),
-                message: "Missing or invalid Authorization header".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
-        }
-    };
+    update_expirations(&app_state, &user, &request.url_ids, request.expiration_date).await
+}
 
-    // Verify token and get user
-    let user = match app_state.auth_service.verify_token(token).await {
-        Ok(u) => u,
-        Err(e) => {
-            warn!("Token verification failed: {}", e);
-            let error_response = ErrorResponse {
-                error: "INVALID_TOKEN".to_string(),
-                message: "Invalid or expired token".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
-        }
-    };
+/// Handler removing the expiration of several URLs
+#[utoipa::path(
+    delete,
+    path = "/urls/bulk/expiration",
+    request_body = BulkExpirationClearRequest,
+    responses(
+        (status = 200, description = "Expiration removed successfully", body = BatchOperationResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
+    ),
+    tag = "bulk-operations"
+)]
+pub async fn bulk_expiration_clear_handler(
+    State(app_state): State<ConcreteAppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    ValidatedJson(request): ValidatedJson<BulkExpirationClearRequest>,
+) -> Result<(StatusCode, Json<BatchOperationResponse>), (StatusCode, Json<ErrorResponse>)> {
+    update_expirations(&app_state, &user, &request.url_ids, None).await
+}
 
+/// Set or, with `None`, remove the expiration of the authenticated user's URLs
+async fn update_expirations(
+    app_state: &ConcreteAppState,
+    user: &User,
+    url_ids: &[i32],
+    expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<(StatusCode, Json<BatchOperationResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let operation = if expiration_date.is_some() {
+        "update_expiration"
+    } else {
+        "clear_expiration"
+    };
     info!(
-        "Received bulk expiration update request for {} URLs (user: {})",
-        request.url_ids.len(),
+        "Received bulk {} request for {} URLs (user: {})",
+        operation,
+        url_ids.len(),
         user.id
     );
 
     match app_state
         .url_service
-        .batch_update_expiration(
-            &request.url_ids,
-            Some(request.expiration_date),
-            Some(user.id),
-        )
+        .batch_update_expiration(url_ids, expiration_date, Some(user.id))
         .await
     {
         Ok(result) => {
             let response = BatchOperationResponse {
-                operation: "update_expiration".to_string(),
+                operation: operation.to_string(),
                 total_processed: result.total_processed,
                 successful: result.successful,
                 failed: result.failed,
@@ -119,6 +122,17 @@ mod tests {
         assert!(request.is_ok());
     }
 
+    #[test]
+    fn test_null_expiration_date_clears_but_missing_is_rejected() {
+        let json = r#"{"url_ids":[1,2,3],"expiration_date":null}"#;
+        let request: BulkExpirationUpdateRequest = serde_json::from_str(json).unwrap();
+        assert!(request.expiration_date.is_none());
+
+        let json = r#"{"url_ids":[1,2,3]}"#;
+        let request: Result<BulkExpirationUpdateRequest, _> = serde_json::from_str(json);
+        assert!(request.is_err());
+    }
+
     #[test]
     fn test_unauthorized_error() {
         let error = ErrorResponse {
@@ -1,74 +1,134 @@
 use crate::application::dto::{
     requests::{BulkShortenUrlsRequest, ShortenUrlRequest},
-    responses::ShortenUrlResponse,
+    responses::{BulkItemResult, ShortenUrlResponse},
     ErrorResponse,
 };
-use crate::presentation::handlers::ConcreteAppState;
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::url_handlers::urls::url_utils::authorize_organization_urls;
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
 use axum::{
     extract::State,
-    http::HeaderMap,
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     Json,
 };
-use tracing::warn;
+use tokio_stream::StreamExt;
+use tracing::{error, info};
+
+/// Whether the client asked for results as server-sent events
+fn wants_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/event-stream"))
+}
+
+/// Create the URLs concurrently, sending each item's result as an SSE event once it finishes
+///
+/// The items are tracked as a bulk operation, so its progress and results can also be read
+/// and it can be cancelled like a queued one.
+async fn stream_bulk_creation(
+    app_state: ConcreteAppState,
+    items: Vec<ShortenUrlRequest>,
+    user_id: i32,
+) -> Response {
+    let progress_service = app_state.progress_service.clone();
+    let operation_id = progress_service.create_operation(items.len()).await;
+    info!(
+        "Streaming bulk URL shortening for {} URLs (user: {}, operation: {})",
+        items.len(),
+        user_id,
+        operation_id
+    );
+
+    let results = app_state
+        .bulk_processor
+        .process_bulk_url_creation_streaming(operation_id.clone(), items, Some(user_id));
+    let (mut processed, mut successful, mut failed) = (0, 0, 0);
+    let events = results
+        .then(move |result: BulkItemResult| {
+            processed += 1;
+            if result.success {
+                successful += 1;
+            } else {
+                failed += 1;
+            }
+            let progress_service = progress_service.clone();
+            let operation_id = operation_id.clone();
+            async move {
+                let event = Event::default().event("item").json_data(&result);
+                if let Err(e) = progress_service
+                    .record_item_result(&operation_id, result)
+                    .await
+                {
+                    error!(
+                        "Failed to record item result for operation {}: {}",
+                        operation_id, e
+                    );
+                }
+                if let Err(e) = progress_service
+                    .update_progress(&operation_id, processed, successful, failed)
+                    .await
+                {
+                    error!(
+                        "Failed to update progress for operation {}: {}",
+                        operation_id, e
+                    );
+                }
+                event
+            }
+        })
+        .chain(tokio_stream::once(Ok(Event::default()
+            .event("done")
+            .data("{}"))));
+
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
 
 /// Handler for bulk shortening URLs
+///
+/// With `Accept: text/event-stream` the URLs are created concurrently and every item's
+/// outcome is streamed as an `item` event holding a `BulkItemResult`, failures included,
+/// followed by a `done` event. Otherwise the first failure stops the request.
+///
+/// Items for an organization are checked like `/shorten` requests before any URL is created.
 #[utoipa::path(
     post,
     path = "/urls/bulk",
     request_body = BulkShortenUrlsRequest,
     responses(
         (status = 201, description = "URLs shortened successfully", body = [ShortenUrlResponse]),
+        (status = 200, description = "Stream of `item` events, each holding a BulkItemResult, followed by a `done` event; sent for `Accept: text/event-stream`", content_type = "text/event-stream", body = BulkItemResult),
         (status = 400, description = "Bad request", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended, not an organization member or organization quota reached", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse),
+        (status = 422, description = "More than 1000 items or an invalid item", body = ValidationErrorResponse),
+        (status = 429, description = "Organization rate limit exceeded", body = ErrorResponse),
     ),
     tag = "bulk-operations"
 )]
 pub async fn bulk_shorten_urls_handler(
     State(app_state): State<ConcreteAppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
     headers: HeaderMap,
-    Json(request): Json<BulkShortenUrlsRequest>,
-) -> Result<(StatusCode, Json<Vec<ShortenUrlResponse>>), (StatusCode, Json<ErrorResponse>)> {
-    // Require Authorization: Bearer <token>
-    let auth_header = headers
-        .get(header::AUTHORIZATION)
-        .and_then(|v| v.to_str().ok());
-    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
-        Some(t) if !t.is_empty() => t,
-        _ => {
-            let error_response = ErrorResponse {
-                error: "UNAUTHORIZED".to_string(),
-                message: "Missing or invalid Authorization header".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
-        }
-    };
-
-    // Verify token and get user
-    let user = match app_state.auth_service.verify_token(token).await {
-        Ok(u) => u,
-        Err(e) => {
-            warn!("Token verification failed: {}", e);
-            let error_response = ErrorResponse {
-                error: "INVALID_TOKEN".to_string(),
-                message: "Invalid or expired token".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
-        }
-    };
+    ValidatedJson(request): ValidatedJson<BulkShortenUrlsRequest>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    authorize_organization_urls(&app_state, &request.items, user.id).await?;
+    if wants_event_stream(&headers) {
+        return Ok(stream_bulk_creation(app_state, request.items, user.id).await);
+    }
 
     let user_id = Some(user.id);
     let mut responses: Vec<ShortenUrlResponse> = Vec::with_capacity(request.items.len());
 
     for item in request.items {
-        let req = ShortenUrlRequest {
-            url: item.url,
-            custom_short_code: item.custom_short_code,
-            expiration_date: item.expiration_date,
-        };
-        match app_state.shorten_url_use_case.execute(req, user_id).await {
+        match app_state.shorten_url_use_case.execute(item, user_id).await {
             Ok(resp) => responses.push(resp),
             Err(err) => {
                 let error_response = ErrorResponse {
@@ -81,12 +141,18 @@ pub async fn bulk_shorten_urls_handler(
         }
     }
 
-    Ok((StatusCode::CREATED, Json(responses)))
+    Ok((StatusCode::CREATED, Json(responses)).into_response())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::entities::SessionClient;
+    use crate::domain::repositories::UrlRepository;
+    use crate::infrastructure::test_utils::TestApp;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
 
     #[test]
     fn test_bulk_shorten_request_deserialize() {
@@ -116,4 +182,95 @@ mod tests {
         };
         assert_eq!(error.error, "SHORTEN_FAILED");
     }
+
+    #[tokio::test]
+    async fn test_results_are_streamed_when_asked_for() {
+        let app = TestApp::new();
+        app.state
+            .auth_service
+            .register("streamer", "streamer@example.com", "password123")
+            .await
+            .unwrap();
+        let token = app
+            .state
+            .auth_service
+            .login("streamer", "password123", &SessionClient::default())
+            .await
+            .unwrap();
+        let body = r#"{"items":[
+            {"url":"https://example1.com","custom_short_code":"taken1"},
+            {"url":"https://example2.com","custom_short_code":"taken1"},
+            {"url":"https://example3.com"}
+        ]}"#;
+
+        let response = app
+            .router()
+            .oneshot(
+                Request::post("/urls/bulk")
+                    .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header(header::ACCEPT, "text/event-stream")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        let results: Vec<BulkItemResult> = body
+            .split("\n\n")
+            .filter(|event| event.starts_with("event: item"))
+            .map(|event| serde_json::from_str(event.split("data: ").nth(1).unwrap()).unwrap())
+            .collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results.iter().filter(|r| r.success).count(), 2);
+        assert!(body.trim_end().ends_with("event: done\ndata: {}"));
+
+        let operations = app
+            .state
+            .progress_service
+            .get_user_operations(0)
+            .await
+            .unwrap();
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].processed_items, 3);
+        assert_eq!(operations[0].successful_items, 2);
+    }
+
+    #[tokio::test]
+    async fn test_organization_items_need_membership() {
+        let app = TestApp::new();
+        let (owner, owner_token) = app.sign_up("orgowner").await;
+        let (_, outsider_token) = app.sign_up("outsider").await;
+        let org = app
+            .state
+            .org_service
+            .create_organization(owner.id, "Acme", None)
+            .await
+            .unwrap();
+        let body = format!(
+            r#"{{"items":[{{"url":"https://example1.com","organization_id":{}}}]}}"#,
+            org.id
+        );
+        let bulk = |token: &str| {
+            Request::post("/urls/bulk")
+                .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.clone()))
+                .unwrap()
+        };
+
+        let response = app.router().oneshot(bulk(&outsider_token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let org_urls = || app.url_repository.find_by_user_id(owner.id, Some(org.id));
+        assert!(org_urls().await.unwrap().is_empty());
+
+        let response = app.router().oneshot(bulk(&owner_token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(org_urls().await.unwrap().len(), 1);
+    }
 }
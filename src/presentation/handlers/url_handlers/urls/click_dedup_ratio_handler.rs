@@ -0,0 +1,92 @@
+use crate::application::dto::{responses::ClickDedupRatioResponse, ErrorResponse};
+use crate::domain::repositories::ClickDedupRatio;
+use crate::presentation::handlers::url_handlers::urls::url_utils::find_accessible_url;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState};
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use tracing::warn;
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: &str,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message: message.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+fn ratio_to_response(ratio: ClickDedupRatio) -> ClickDedupRatioResponse {
+    ClickDedupRatioResponse {
+        raw_clicks: ratio.raw_clicks,
+        unique_clicks: ratio.unique_clicks,
+        bot_clicks: ratio.bot_clicks,
+    }
+}
+
+/// Handler comparing the clicks of a URL the authenticated user can access before and after
+/// deduplication
+#[utoipa::path(
+    get,
+    path = "/urls/{id}/analytics/dedup-ratio",
+    params(
+        ("id" = i32, Path, description = "URL ID")
+    ),
+    responses(
+        (status = 200, description = "Raw, unique and bot clicks", body = ClickDedupRatioResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn get_click_dedup_ratio_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<Json<ClickDedupRatioResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::UNAUTHORIZED,
+                "UNAUTHORIZED",
+                "Missing or invalid Authorization header",
+            )
+        })?;
+
+    let user = app_state
+        .auth_service
+        .verify_token(token)
+        .await
+        .map_err(|e| {
+            warn!("Token verification failed: {}", e);
+            token_error_response(&e)
+        })?;
+
+    let url = find_accessible_url(&app_state, id, user.id).await?;
+
+    match app_state
+        .click_tracking_service
+        .get_click_dedup_ratio(url.id)
+        .await
+    {
+        Ok(ratio) => Ok(Json(ratio_to_response(ratio))),
+        Err(error) => {
+            warn!("Failed to load dedup ratio of URL {}: {}", id, error);
+            Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "ANALYTICS_ERROR",
+                "Failed to load URL analytics",
+            ))
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use crate::application::dto::{
+    requests::ClickPatternsQuery, responses::ClickPatternsResponse, ErrorResponse,
+};
+use crate::domain::services::click_tracking_service::{
+    ClickPatterns, ClickTrackingError, DEFAULT_CLICK_VELOCITY_WINDOW_MINUTES,
+};
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::url_handlers::urls::url_utils::find_accessible_url;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use tracing::warn;
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: &str,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message: message.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+fn patterns_to_response(
+    url_id: i32,
+    patterns: ClickPatterns,
+    window_minutes: u32,
+) -> ClickPatternsResponse {
+    ClickPatternsResponse {
+        url_id,
+        hourly_distribution: patterns.hourly_distribution.to_vec(),
+        daily_distribution: patterns.daily_distribution.to_vec(),
+        peak_hour: patterns.peak_hour,
+        click_velocity: patterns.click_velocity,
+        velocity_window_minutes: window_minutes,
+    }
+}
+
+/// Handler for the hourly and daily click patterns of a URL the authenticated user can access
+#[utoipa::path(
+    get,
+    path = "/urls/{id}/analytics/patterns",
+    params(
+        ("id" = i32, Path, description = "URL ID"),
+        ("window_minutes" = Option<u32>, Query, description = "Minutes the click velocity is measured over (default 60, at most 10080)")
+    ),
+    responses(
+        (status = 200, description = "Click distributions, peak hour and velocity", body = ClickPatternsResponse),
+        (status = 400, description = "Invalid velocity window", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn get_click_patterns_handler(
+    State(app_state): State<ConcreteAppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<i32>,
+    Query(query): Query<ClickPatternsQuery>,
+) -> Result<Json<ClickPatternsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let url = find_accessible_url(&app_state, id, user.id).await?;
+
+    let window_minutes = query
+        .window_minutes
+        .unwrap_or(DEFAULT_CLICK_VELOCITY_WINDOW_MINUTES);
+    match app_state
+        .click_tracking_service
+        .get_click_patterns(url.id, window_minutes)
+        .await
+    {
+        Ok(patterns) => Ok(Json(patterns_to_response(url.id, patterns, window_minutes))),
+        Err(ClickTrackingError::InvalidData(message)) => Err(error_response(
+            StatusCode::BAD_REQUEST,
+            "INVALID_WINDOW",
+            &message,
+        )),
+        Err(error) => {
+            warn!("Failed to load click patterns of URL {}: {}", id, error);
+            Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "ANALYTICS_ERROR",
+                "Failed to load URL analytics",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patterns_to_response() {
+        let mut hourly_distribution = [0; 24];
+        hourly_distribution[9] = 3;
+        let patterns = ClickPatterns {
+            hourly_distribution,
+            daily_distribution: [0, 3, 0, 0, 0, 0, 0],
+            peak_hour: 9,
+            click_velocity: 0.5,
+        };
+
+        let response = patterns_to_response(7, patterns, 60);
+        assert_eq!(response.url_id, 7);
+        assert_eq!(response.hourly_distribution.len(), 24);
+        assert_eq!(response.hourly_distribution[9], 3);
+        assert_eq!(response.daily_distribution, vec![0, 3, 0, 0, 0, 0, 0]);
+        assert_eq!(response.peak_hour, 9);
+        assert_eq!(response.velocity_window_minutes, 60);
+    }
+}
@@ -1,11 +1,7 @@
 use crate::application::dto::ErrorResponse;
+use crate::infrastructure::http::AuthenticatedUser;
 use crate::presentation::handlers::ConcreteAppState;
-use axum::{
-    extract::State,
-    http::HeaderMap,
-    http::{header, StatusCode},
-    Json,
-};
+use axum::{extract::State, http::StatusCode, Json};
 use tracing::{info, warn};
 
 /// Handler for deactivating a URL (soft delete)
@@ -19,44 +15,15 @@ use tracing::{info, warn};
         (status = 204, description = "URL deactivated successfully"),
         (status = 404, description = "URL not found", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
     ),
     tag = "url-management"
 )]
 pub async fn deactivate_url_handler(
     State(app_state): State<ConcreteAppState>,
-    headers: HeaderMap,
+    AuthenticatedUser(user): AuthenticatedUser,
     axum::extract::Path(id): axum::extract::Path<i32>,
 ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
-    // Require Authorization: Bearer <token>
-    let auth_header = headers
-        .get(header::AUTHORIZATION)
-        .and_then(|v| v.to_str().ok());
-    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
-        Some(t) if !t.is_empty() => t,
-        _ => {
-            let error_response = ErrorResponse {
-                error: "UNAUTHORIZED".to_string(),
-                message: "Missing or invalid Authorization header".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
-        }
-    };
-
-    // Verify token and get user
-    let user = match app_state.auth_service.verify_token(token).await {
-        Ok(u) => u,
-        Err(e) => {
-            warn!("Token verification failed: {}", e);
-            let error_response = ErrorResponse {
-                error: "INVALID_TOKEN".to_string(),
-                message: "Invalid or expired token".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
-        }
-    };
-
     info!(
         "Received deactivate URL request for ID: {} (user: {})",
         id, user.id
@@ -0,0 +1,168 @@
+use crate::application::dto::{
+    requests::DuplicateUrlRequest, responses::ShortenUrlResponse, ErrorResponse,
+};
+use crate::application::use_cases::shorten_url::UseCaseError;
+use crate::domain::repositories::RepositoryError;
+use crate::domain::services::ServiceError;
+use crate::presentation::handlers::url_handlers::urls::url_utils::schedule_link_preview;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    http::{header, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: String,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message,
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Map a failed duplication to an HTTP error
+///
+/// URLs owned by someone else are reported as missing so their IDs don't leak.
+fn duplicate_error_response(error: &UseCaseError) -> (StatusCode, Json<ErrorResponse>) {
+    match error {
+        UseCaseError::BlockedDomain(_) => {
+            error_response(StatusCode::BAD_REQUEST, "BLOCKED_DOMAIN", error.to_string())
+        }
+        UseCaseError::Validation(_)
+        | UseCaseError::InvalidShortCode(_)
+        | UseCaseError::Service(ServiceError::InvalidShortCode(_)) => error_response(
+            StatusCode::BAD_REQUEST,
+            "DUPLICATE_FAILED",
+            error.to_string(),
+        ),
+        UseCaseError::Service(ServiceError::PermissionDenied(_))
+        | UseCaseError::Service(ServiceError::Repository(RepositoryError::NotFound)) => {
+            error_response(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                "URL not found or you don't have permission to duplicate it".to_string(),
+            )
+        }
+        UseCaseError::Service(ServiceError::ShortCodeAlreadyExists) => error_response(
+            StatusCode::CONFLICT,
+            "DUPLICATE_SHORT_CODE",
+            "Short code already exists".to_string(),
+        ),
+        _ => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DUPLICATE_FAILED",
+            "Failed to duplicate URL".to_string(),
+        ),
+    }
+}
+
+/// Handler for duplicating a URL
+///
+/// Creates a new short URL with the same settings as an existing one. The destination and
+/// short code can be overridden; the copy starts without clicks.
+#[utoipa::path(
+    post,
+    path = "/urls/{id}/duplicate",
+    params(
+        ("id" = i32, Path, description = "ID of the URL to duplicate")
+    ),
+    request_body = DuplicateUrlRequest,
+    responses(
+        (status = 201, description = "URL duplicated successfully", body = ShortenUrlResponse),
+        (status = 400, description = "Invalid override or blacklisted domain (BLOCKED_DOMAIN)", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+        (status = 409, description = "Short code already exists", body = ErrorResponse),
+        (status = 422, description = "Request body breaks a validation rule", body = ValidationErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn duplicate_url_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    ValidatedJson(request): ValidatedJson<DuplicateUrlRequest>,
+) -> Result<(StatusCode, Json<ShortenUrlResponse>), (StatusCode, Json<ErrorResponse>)> {
+    // Require Authorization: Bearer <token>
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(t) if !t.is_empty() => t,
+        _ => {
+            return Err(error_response(
+                StatusCode::UNAUTHORIZED,
+                "UNAUTHORIZED",
+                "Missing or invalid Authorization header".to_string(),
+            ));
+        }
+    };
+
+    // Verify token and get user
+    let user = match app_state.auth_service.verify_token(token).await {
+        Ok(u) => u,
+        Err(e) => {
+            warn!("Token verification failed: {}", e);
+            return Err(token_error_response(&e));
+        }
+    };
+
+    info!(
+        "Received duplicate URL request for ID: {} (user: {})",
+        id, user.id
+    );
+
+    match app_state
+        .shorten_url_use_case
+        .duplicate(id, request, user.id)
+        .await
+    {
+        Ok(response) => {
+            info!(
+                "Duplicated URL {} as {} -> {}",
+                id, response.short_url, response.original_url
+            );
+            schedule_link_preview(&app_state, &response.short_code);
+            Ok((StatusCode::CREATED, Json(response)))
+        }
+        Err(error) => {
+            warn!("Failed to duplicate URL {}: {}", id, error);
+            Err(duplicate_error_response(&error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_request_fields_are_optional() {
+        let request: DuplicateUrlRequest = serde_json::from_str("{}").unwrap();
+        assert!(request.original_url.is_none());
+        assert!(request.custom_short_code.is_none());
+    }
+
+    #[test]
+    fn test_foreign_url_is_reported_as_not_found() {
+        let error = UseCaseError::Service(ServiceError::PermissionDenied("x".to_string()));
+        let (status, Json(body)) = duplicate_error_response(&error);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.error, "NOT_FOUND");
+    }
+
+    #[test]
+    fn test_taken_short_code_is_conflict() {
+        let error = UseCaseError::Service(ServiceError::ShortCodeAlreadyExists);
+        let (status, _) = duplicate_error_response(&error);
+        assert_eq!(status, StatusCode::CONFLICT);
+    }
+}
@@ -0,0 +1,115 @@
+use super::url_utils::url_to_info_response;
+use crate::application::dto::{
+    requests::ListLimitQuery,
+    responses::{TopUrlsResponse, UrlInfoResponse},
+    ErrorResponse,
+};
+use crate::domain::services::url_service::DEFAULT_LISTING_LIMIT;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState};
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    http::{header, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for getting the authenticated user's most clicked URLs
+#[utoipa::path(
+    get,
+    path = "/urls/top",
+    params(
+        ("limit" = Option<usize>, Query, description = "Maximum number of URLs to return (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Most clicked URLs retrieved", body = TopUrlsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn get_top_urls_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Query(query): Query<ListLimitQuery>,
+) -> Result<(StatusCode, Json<TopUrlsResponse>), (StatusCode, Json<ErrorResponse>)> {
+    // Require Authorization: Bearer <token>
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(t) if !t.is_empty() => t,
+        _ => {
+            let error_response = ErrorResponse {
+                error: "UNAUTHORIZED".to_string(),
+                message: "Missing or invalid Authorization header".to_string(),
+                status_code: StatusCode::UNAUTHORIZED.as_u16(),
+            };
+            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
+        }
+    };
+
+    // Verify token and get user
+    let user = match app_state.auth_service.verify_token(token).await {
+        Ok(u) => u,
+        Err(e) => {
+            warn!("Token verification failed: {}", e);
+            return Err(token_error_response(&e));
+        }
+    };
+
+    let limit = query.limit.unwrap_or(DEFAULT_LISTING_LIMIT);
+    info!("Getting top {} URLs for user: {}", limit, user.id);
+
+    match app_state
+        .url_service
+        .get_most_clicked_urls(user.id, limit)
+        .await
+    {
+        Ok(urls) => {
+            let base_url = app_state.shorten_url_use_case.base_url();
+            let urls: Vec<UrlInfoResponse> = urls
+                .into_iter()
+                .map(|u| url_to_info_response(u.url, base_url, Some(u.click_count)))
+                .collect();
+            let response = TopUrlsResponse {
+                total_count: urls.len() as i64,
+                urls,
+            };
+            Ok((StatusCode::OK, Json(response)))
+        }
+        Err(error) => {
+            warn!("Failed to get top URLs for user {}: {}", user.id, error);
+            let error_response = ErrorResponse {
+                error: "DATABASE_ERROR".to_string(),
+                message: "Internal server error".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_limit_query_deserialize() {
+        let query: ListLimitQuery = serde_json::from_str(r#"{"limit":5}"#).unwrap();
+        assert_eq!(query.limit, Some(5));
+
+        let query: ListLimitQuery = serde_json::from_str("{}").unwrap();
+        assert!(query.limit.is_none());
+    }
+
+    #[test]
+    fn test_database_error() {
+        let error = ErrorResponse {
+            error: "DATABASE_ERROR".to_string(),
+            message: "Internal server error".to_string(),
+            status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+        };
+        assert_eq!(error.status_code, 500);
+    }
+}
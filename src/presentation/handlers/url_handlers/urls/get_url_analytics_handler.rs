@@ -0,0 +1,144 @@
+use crate::application::dto::{
+    requests::GetUrlAnalyticsRequest, responses::UrlAnalyticsResponse, ErrorResponse,
+};
+use crate::application::GetUrlAnalyticsError;
+use crate::presentation::handlers::url_handlers::urls::url_utils::find_accessible_url;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState};
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use tracing::warn;
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: String,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message,
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Map a failed analytics request to its HTTP response
+fn analytics_error_response(error: GetUrlAnalyticsError) -> (StatusCode, Json<ErrorResponse>) {
+    match error {
+        GetUrlAnalyticsError::NotFound => error_response(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            "URL not found".to_string(),
+        ),
+        GetUrlAnalyticsError::InvalidRange(message) => {
+            error_response(StatusCode::BAD_REQUEST, "INVALID_RANGE", message)
+        }
+        GetUrlAnalyticsError::Url(_) | GetUrlAnalyticsError::Click(_) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "ANALYTICS_ERROR",
+            "Failed to load URL analytics".to_string(),
+        ),
+    }
+}
+
+/// Handler for the analytics of a URL over a time range
+///
+/// Available to the URL's creator and the members of its organization. Results are cached
+/// for a minute when Redis is configured, and dropped once the URL is clicked again.
+#[utoipa::path(
+    get,
+    path = "/urls/{id}/analytics",
+    params(
+        ("id" = i32, Path, description = "URL ID"),
+        ("granularity" = Option<String>, Query, description = "Timeseries bucket width: hour, day (default), week or month"),
+        ("start" = Option<String>, Query, description = "Start of the range (RFC 3339); defaults to the usual window of the granularity"),
+        ("end" = Option<String>, Query, description = "End of the range (RFC 3339); defaults to now")
+    ),
+    responses(
+        (status = 200, description = "URL analytics retrieved", body = UrlAnalyticsResponse),
+        (status = 400, description = "Invalid or too long range", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn get_url_analytics_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    Query(request): Query<GetUrlAnalyticsRequest>,
+) -> Result<Json<UrlAnalyticsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::UNAUTHORIZED,
+                "UNAUTHORIZED",
+                "Missing or invalid Authorization header".to_string(),
+            )
+        })?;
+
+    let user = app_state
+        .auth_service
+        .verify_token(token)
+        .await
+        .map_err(|e| {
+            warn!("Token verification failed: {}", e);
+            token_error_response(&e)
+        })?;
+    find_accessible_url(&app_state, id, user.id).await?;
+
+    match app_state
+        .get_url_analytics_use_case
+        .execute(id, request)
+        .await
+    {
+        Ok(response) => Ok(Json(response)),
+        Err(error) => {
+            if matches!(
+                error,
+                GetUrlAnalyticsError::Url(_) | GetUrlAnalyticsError::Click(_)
+            ) {
+                warn!("Failed to load analytics for URL {}: {}", id, error);
+            }
+            Err(analytics_error_response(error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analytics_error_responses() {
+        let (status, Json(body)) = analytics_error_response(GetUrlAnalyticsError::NotFound);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.error, "NOT_FOUND");
+
+        let (status, Json(body)) = analytics_error_response(GetUrlAnalyticsError::InvalidRange(
+            "start must not be after end".to_string(),
+        ));
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.message, "start must not be after end");
+    }
+
+    #[test]
+    fn test_request_parses_from_query_string() {
+        let Query(request): Query<GetUrlAnalyticsRequest> = Query::try_from_uri(
+            &"/urls/1/analytics?granularity=week&start=2024-01-01T00:00:00Z"
+                .parse()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(request.granularity.as_str(), "week");
+        assert!(request.start.is_some());
+        assert!(request.end.is_none());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::application::dto::{
+    requests::AnalyticsSummaryQuery,
+    responses::{
+        ClickSummary, CountryClicks, DeviceBreakdownResponse, ReferrerClicks,
+        UrlAnalyticsSummaryResponse,
+    },
+    ErrorResponse,
+};
+use crate::domain::repositories::UrlAnalyticsSummary;
+use crate::presentation::handlers::url_handlers::urls::url_utils::find_accessible_url;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState};
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    http::{header, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+fn analytics_error_response() -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: "ANALYTICS_ERROR".to_string(),
+        message: "Failed to load URL analytics".to_string(),
+        status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+    };
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response))
+}
+
+fn summary_to_response(url_id: i32, summary: UrlAnalyticsSummary) -> UrlAnalyticsSummaryResponse {
+    UrlAnalyticsSummaryResponse {
+        url_id,
+        total_clicks: summary.total_clicks,
+        unique_visitors: summary.unique_visitors,
+        clicks_today: summary.clicks_today,
+        clicks_this_week: summary.clicks_this_week,
+        clicks_this_month: summary.clicks_this_month,
+        top_countries: summary
+            .top_countries
+            .into_iter()
+            .map(|(country_code, count)| CountryClicks {
+                country_code,
+                count,
+            })
+            .collect(),
+        top_referrers: summary
+            .top_referrers
+            .into_iter()
+            .map(|(referrer, count)| ReferrerClicks { referrer, count })
+            .collect(),
+        device_breakdown: DeviceBreakdownResponse {
+            desktop: summary.device_breakdown.desktop,
+            mobile: summary.device_breakdown.mobile,
+            tablet: summary.device_breakdown.tablet,
+            bot: summary.device_breakdown.bot,
+        },
+        conversion_rate: summary.conversion_rate,
+        recent_clicks: summary
+            .recent_clicks
+            .into_iter()
+            .map(|click| ClickSummary {
+                clicked_at: click.clicked_at.to_rfc3339(),
+                device: click.device_type().as_str().to_string(),
+                country_code: click.country_code,
+                referrer: click.referer,
+            })
+            .collect(),
+    }
+}
+
+/// Handler for the analytics summary of a URL the authenticated user can access
+#[utoipa::path(
+    get,
+    path = "/urls/{id}/analytics/summary",
+    params(
+        ("id" = i32, Path, description = "URL ID"),
+        ("include_bots" = Option<bool>, Query, description = "Count clicks from crawlers and scripted clients (default false)")
+    ),
+    responses(
+        (status = 200, description = "Analytics summary retrieved", body = UrlAnalyticsSummaryResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn get_url_analytics_summary_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    Query(query): Query<AnalyticsSummaryQuery>,
+) -> Result<(StatusCode, Json<UrlAnalyticsSummaryResponse>), (StatusCode, Json<ErrorResponse>)> {
+    // Require Authorization: Bearer <token>
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(t) if !t.is_empty() => t,
+        _ => {
+            let error_response = ErrorResponse {
+                error: "UNAUTHORIZED".to_string(),
+                message: "Missing or invalid Authorization header".to_string(),
+                status_code: StatusCode::UNAUTHORIZED.as_u16(),
+            };
+            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
+        }
+    };
+
+    // Verify token and get user
+    let user = match app_state.auth_service.verify_token(token).await {
+        Ok(u) => u,
+        Err(e) => {
+            warn!("Token verification failed: {}", e);
+            return Err(token_error_response(&e));
+        }
+    };
+
+    let url = find_accessible_url(&app_state, id, user.id).await?;
+
+    info!(
+        "Building analytics summary for URL {} (user: {})",
+        id, user.id
+    );
+
+    match app_state
+        .click_tracking_service
+        .get_url_analytics_summary(url.id, query.include_bots)
+        .await
+    {
+        Ok(summary) => Ok((StatusCode::OK, Json(summary_to_response(url.id, summary)))),
+        Err(e) => {
+            warn!("Failed to load analytics for URL {}: {}", id, e);
+            Err(analytics_error_response())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analytics_error_response() {
+        let (status, Json(body)) = analytics_error_response();
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body.error, "ANALYTICS_ERROR");
+        assert_eq!(body.status_code, 500);
+    }
+
+    #[test]
+    fn test_summary_to_response_hides_ip_addresses() {
+        use crate::domain::entities::Click;
+        use crate::domain::repositories::click_repository::DeviceBreakdown;
+
+        let summary = UrlAnalyticsSummary {
+            total_clicks: 1,
+            unique_visitors: 1,
+            clicks_today: 1,
+            clicks_this_week: 1,
+            clicks_this_month: 1,
+            top_countries: vec![("PT".to_string(), 1)],
+            top_referrers: vec![],
+            device_breakdown: DeviceBreakdown {
+                mobile: 1,
+                ..Default::default()
+            },
+            conversion_rate: 0.25,
+            recent_clicks: vec![Click::new_for_tracking(
+                9,
+                Some("10.0.0.1".to_string()),
+                Some("Mozilla/5.0 (iPhone) Mobile".to_string()),
+                Some("https://example.com".to_string()),
+                Some("PT".to_string()),
+            )],
+        };
+
+        let response = summary_to_response(9, summary);
+        assert_eq!(response.top_countries[0].country_code, "PT");
+        assert_eq!(response.recent_clicks[0].device, "mobile");
+        assert_eq!(response.conversion_rate, 0.25);
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(!json.contains("10.0.0.1"));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::application::dto::{responses::UrlDetailResponse, ErrorResponse};
+use crate::presentation::handlers::url_handlers::urls::url_utils::url_to_detail_response;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState};
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use tracing::warn;
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: &str,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message: message.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Handler returning the details of a single URL
+///
+/// Only the URL's owner, members of its organization and administrators may read it.
+#[utoipa::path(
+    get,
+    path = "/urls/{id}",
+    params(
+        ("id" = i32, Path, description = "URL ID")
+    ),
+    responses(
+        (status = 200, description = "URL details", body = UrlDetailResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "URL belongs to another user", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn get_url_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<Json<UrlDetailResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::UNAUTHORIZED,
+                "UNAUTHORIZED",
+                "Missing or invalid Authorization header",
+            )
+        })?;
+
+    let user = app_state
+        .auth_service
+        .verify_token(token)
+        .await
+        .map_err(|e| {
+            warn!("Token verification failed: {}", e);
+            token_error_response(&e)
+        })?;
+
+    let url = match app_state.url_service.get_url_by_id(id).await {
+        Ok(Some(url)) => url,
+        Ok(None) => {
+            return Err(error_response(
+                StatusCode::NOT_FOUND,
+                "URL_NOT_FOUND",
+                "URL not found",
+            ))
+        }
+        Err(error) => {
+            warn!("Failed to load URL {}: {}", id, error);
+            return Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                "Failed to load URL",
+            ));
+        }
+    };
+
+    let can_access = match app_state.org_service.can_access_url(user.id, &url).await {
+        Ok(can_access) => can_access || app_state.auth_service.is_admin(&user),
+        Err(error) => {
+            warn!("Failed to check access to URL {}: {}", id, error);
+            return Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                "Failed to load URL",
+            ));
+        }
+    };
+    if !can_access {
+        warn!(
+            "User {} attempted to read URL {} of another user",
+            user.id, id
+        );
+        return Err(error_response(
+            StatusCode::FORBIDDEN,
+            "FORBIDDEN",
+            "URL belongs to another user",
+        ));
+    }
+
+    let click_count = match app_state.click_tracking_service.get_click_count(id).await {
+        Ok(count) => count,
+        Err(error) => {
+            warn!("Failed to count clicks of URL {}: {}", id, error);
+            return Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                "Failed to load URL",
+            ));
+        }
+    };
+
+    let use_case = &app_state.shorten_url_use_case;
+    let links = use_case.links(&url);
+    Ok(Json(url_to_detail_response(
+        url,
+        use_case.base_url(),
+        click_count,
+        links,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{OrgRole, ShortCode, UrlStatus};
+    use crate::domain::repositories::UrlRepository;
+    use crate::infrastructure::test_utils::TestApp;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_organization_members_can_read_its_urls() {
+        let app = TestApp::new();
+        let (owner, _) = app.sign_up("orgowner").await;
+        let (member, member_token) = app.sign_up("orgmember").await;
+        let (_, outsider_token) = app.sign_up("outsider").await;
+        let org_service = &app.state.org_service;
+        let org = org_service
+            .create_organization(owner.id, "Acme", None)
+            .await
+            .unwrap();
+        org_service
+            .add_member(org.id, owner.id, member.id, OrgRole::Member)
+            .await
+            .unwrap();
+        let url = app
+            .url_repository
+            .create_url(
+                &ShortCode::new("orgurl1".to_string()).unwrap(),
+                "https://example.com/acme",
+                None,
+                Some(owner.id),
+                Some(org.id),
+                UrlStatus::Active,
+            )
+            .await
+            .unwrap();
+
+        let get = |token: &str| {
+            Request::get(format!("/urls/{}", url.id))
+                .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let response = app.router().oneshot(get(&member_token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = app.router().oneshot(get(&outsider_token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::application::dto::{responses::LinkPreviewResponse, ErrorResponse};
+use crate::domain::entities::{ShortCode, Url, UrlMetadata};
+use crate::presentation::handlers::url_handlers::urls::url_utils::{
+    short_code_lookup_error_response, url_to_preview_response,
+};
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{Html, IntoResponse, Response},
+    Json,
+};
+use tracing::warn;
+
+/// Seconds the HTML preview page waits before following the short link
+const PREVIEW_REDIRECT_DELAY_SECONDS: u32 = 3;
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: &str,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message: message.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Look up an accessible URL and its fetched metadata
+///
+/// Metadata lookup failures are logged and treated as "not fetched yet".
+pub(super) async fn find_url_with_metadata(
+    app_state: &ConcreteAppState,
+    short_code: String,
+) -> Result<(Url, Option<UrlMetadata>), (StatusCode, Json<ErrorResponse>)> {
+    let short_code = ShortCode::new(short_code).map_err(|error| {
+        error_response(
+            StatusCode::BAD_REQUEST,
+            "INVALID_SHORT_CODE",
+            &error.to_string(),
+        )
+    })?;
+
+    let url = match app_state
+        .url_service
+        .get_url_by_short_code_with_validation(&short_code)
+        .await
+    {
+        Ok(Some(url)) => url,
+        Ok(None) => {
+            return Err(error_response(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                "Short code not found or no longer available",
+            ));
+        }
+        Err(error) => {
+            warn!("Database error while looking up short code: {}", error);
+            return Err(short_code_lookup_error_response(&error));
+        }
+    };
+
+    let metadata = match app_state.link_preview_service.get_metadata(url.id).await {
+        Ok(metadata) => metadata,
+        Err(error) => {
+            warn!("Failed to load link preview of URL {}: {}", url.id, error);
+            None
+        }
+    };
+    Ok((url, metadata))
+}
+
+/// Handler returning the link preview metadata of a short URL without redirecting
+#[utoipa::path(
+    get,
+    path = "/{short_code}/preview",
+    params(
+        ("short_code" = String, Path, description = "Short code to preview")
+    ),
+    responses(
+        (status = 200, description = "Link preview metadata", body = LinkPreviewResponse),
+        (status = 400, description = "Invalid short code", body = ErrorResponse),
+        (status = 404, description = "Short code not found", body = ErrorResponse),
+    ),
+    tag = "url-shortener"
+)]
+pub async fn get_link_preview_handler(
+    State(app_state): State<ConcreteAppState>,
+    Path(short_code): Path<String>,
+) -> Result<Json<LinkPreviewResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let (url, metadata) = find_url_with_metadata(&app_state, short_code).await?;
+    let base_url = app_state.shorten_url_use_case.base_url();
+    Ok(Json(url_to_preview_response(url, metadata, base_url)))
+}
+
+/// HTML page showing where a short link goes before following it
+///
+/// The page refreshes to the short link itself rather than the destination, so the click
+/// is still recorded.
+pub(super) fn preview_page(preview: &LinkPreviewResponse) -> Response {
+    let title = preview.title.as_deref().unwrap_or(&preview.original_url);
+    let description = preview
+        .description
+        .as_deref()
+        .map(|d| format!("<p>{}</p>", escape_html(d)))
+        .unwrap_or_default();
+    let image = preview
+        .image_url
+        .as_deref()
+        .map(|i| format!("<img src=\"{}\" alt=\"\">", escape_html(i)))
+        .unwrap_or_default();
+
+    let page = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta http-equiv=\"refresh\" content=\"{delay}; url={target}\">\n\
+         <title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n{description}\n{image}\n\
+         <p>You will be redirected to <a href=\"{target}\">{destination}</a> in {delay} seconds.</p>\n\
+         </body>\n</html>\n",
+        delay = PREVIEW_REDIRECT_DELAY_SECONDS,
+        target = escape_html(&preview.short_url),
+        destination = escape_html(&preview.original_url),
+        title = escape_html(title),
+    );
+    ([(header::CACHE_CONTROL, "no-store")], Html(page)).into_response()
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preview(title: Option<&str>) -> LinkPreviewResponse {
+        LinkPreviewResponse {
+            short_code: "abc123".to_string(),
+            short_url: "https://short.ly/abc123".to_string(),
+            original_url: "https://example.com/?a=1&b=2".to_string(),
+            title: title.map(str::to_string),
+            description: None,
+            image_url: None,
+            favicon_url: None,
+            fetched_at: None,
+        }
+    }
+
+    async fn body_text(response: Response) -> String {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_preview_page_redirects_through_short_link() {
+        let body = body_text(preview_page(&preview(Some("Example")))).await;
+        assert!(body.contains("content=\"3; url=https://short.ly/abc123\""));
+        assert!(body.contains("<title>Example</title>"));
+        assert!(body.contains("https://example.com/?a=1&amp;b=2"));
+    }
+
+    #[tokio::test]
+    async fn test_preview_page_escapes_metadata() {
+        let body = body_text(preview_page(&preview(Some("<script>alert(1)</script>")))).await;
+        assert!(!body.contains("<script>"));
+        assert!(body.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+    }
+}
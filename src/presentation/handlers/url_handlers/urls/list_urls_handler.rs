@@ -0,0 +1,154 @@
+use crate::application::dto::{
+    requests::{ListUrlsQuery, PaginationRequest},
+    responses::{Page, UrlSummaryResponse},
+    ErrorResponse,
+};
+use crate::application::ListUrlsRequest;
+use crate::domain::entities::UrlStatus;
+use crate::domain::services::url_service::{ServiceError, DEFAULT_LISTING_LIMIT};
+use crate::presentation::handlers::{token_error_response, ConcreteAppState};
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    http::{header, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Handler for listing the authenticated user's URLs with cursor pagination
+///
+/// Each URL comes with its click count and tags; `total` counts the URLs on every page.
+#[utoipa::path(
+    get,
+    path = "/urls",
+    params(
+        ("sort" = Option<String>, Query, description = "Sort field: created_at (default) or short_code"),
+        ("direction" = Option<String>, Query, description = "Sort direction: asc or desc (default)"),
+        ("status" = Option<String>, Query, description = "Only list URLs with this status: active, inactive or archived (archived URLs are hidden by default)"),
+        ("after" = Option<String>, Query, description = "next_cursor from the previous page"),
+        ("before" = Option<String>, Query, description = "prev_cursor from the following page"),
+        ("limit" = Option<usize>, Query, description = "Maximum number of URLs to return (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Page of URLs retrieved", body = UrlSummaryPage),
+        (status = 400, description = "Invalid cursor or status", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn list_urls_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Query(query): Query<ListUrlsQuery>,
+    Query(pagination): Query<PaginationRequest>,
+) -> Result<(StatusCode, Json<Page<UrlSummaryResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    // Require Authorization: Bearer <token>
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(t) if !t.is_empty() => t,
+        _ => {
+            let error_response = ErrorResponse {
+                error: "UNAUTHORIZED".to_string(),
+                message: "Missing or invalid Authorization header".to_string(),
+                status_code: StatusCode::UNAUTHORIZED.as_u16(),
+            };
+            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
+        }
+    };
+
+    // Verify token and get user
+    let user = match app_state.auth_service.verify_token(token).await {
+        Ok(u) => u,
+        Err(e) => {
+            warn!("Token verification failed: {}", e);
+            return Err(token_error_response(&e));
+        }
+    };
+
+    let status = match query.status.as_deref().map(UrlStatus::parse) {
+        None => None,
+        Some(Some(status)) => Some(status),
+        Some(None) => {
+            let error_response = ErrorResponse {
+                error: "INVALID_STATUS".to_string(),
+                message: "Invalid status. Must be 'active', 'inactive' or 'archived'".to_string(),
+                status_code: StatusCode::BAD_REQUEST.as_u16(),
+            };
+            return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+        }
+    };
+
+    let limit = pagination.limit_or(DEFAULT_LISTING_LIMIT);
+    info!("Listing URLs for user: {} (limit {})", user.id, limit);
+
+    let request = ListUrlsRequest {
+        user_id: user.id,
+        status,
+        sort: query.sort,
+        direction: query.direction,
+        after: pagination.after,
+        before: pagination.before,
+        limit,
+    };
+    match app_state.list_urls_use_case.execute(request).await {
+        Ok(page) => Ok((StatusCode::OK, Json(page))),
+        Err(ServiceError::InvalidData(message)) => {
+            let error_response = ErrorResponse {
+                error: "INVALID_CURSOR".to_string(),
+                message,
+                status_code: StatusCode::BAD_REQUEST.as_u16(),
+            };
+            Err((StatusCode::BAD_REQUEST, Json(error_response)))
+        }
+        Err(error) => {
+            warn!("Failed to list URLs for user {}: {}", user.id, error);
+            let error_response = ErrorResponse {
+                error: "DATABASE_ERROR".to_string(),
+                message: "Internal server error".to_string(),
+                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            };
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::{SortDirection, UrlSortField};
+
+    #[test]
+    fn test_list_urls_query_defaults() {
+        let query: ListUrlsQuery = serde_json::from_str("{}").unwrap();
+        assert_eq!(query.sort, UrlSortField::CreatedAt);
+        assert_eq!(query.direction, SortDirection::Desc);
+
+        let query: ListUrlsQuery =
+            serde_json::from_str(r#"{"sort":"short_code","direction":"asc"}"#).unwrap();
+        assert_eq!(query.sort, UrlSortField::ShortCode);
+        assert_eq!(query.direction, SortDirection::Asc);
+
+        let query: ListUrlsQuery = serde_json::from_str(r#"{"status":"archived"}"#).unwrap();
+        assert_eq!(
+            query.status.as_deref().and_then(UrlStatus::parse),
+            Some(UrlStatus::Archived)
+        );
+    }
+
+    #[test]
+    fn test_pagination_accepts_legacy_cursor_and_clamps_limit() {
+        let pagination: PaginationRequest =
+            serde_json::from_str(r#"{"cursor":"abc","limit":500}"#).unwrap();
+        assert_eq!(pagination.after.as_deref(), Some("abc"));
+        assert_eq!(pagination.limit_or(DEFAULT_LISTING_LIMIT), 100);
+
+        let pagination = PaginationRequest::default();
+        assert_eq!(
+            pagination.limit_or(DEFAULT_LISTING_LIMIT),
+            DEFAULT_LISTING_LIMIT
+        );
+    }
+}
@@ -7,10 +7,26 @@ pub mod bulk_delete_handler;
 pub mod bulk_expiration_update_handler;
 pub mod bulk_shorten_urls_handler;
 pub mod bulk_status_update_handler;
+pub mod click_dedup_ratio_handler;
+pub mod click_patterns_handler;
 pub mod deactivate_url_handler;
+pub mod duplicate_url_handler;
+pub mod get_top_urls_handler;
+pub mod get_url_analytics_handler;
+pub mod get_url_analytics_summary_handler;
+pub mod get_url_handler;
+pub mod link_preview_handler;
+pub mod list_urls_handler;
+pub mod preview_settings_handler;
 pub mod reactivate_url_handler;
 pub mod redirect_handler;
+pub mod rename_short_code_handler;
+pub mod restore_url_handler;
 pub mod shorten_url_handler;
+pub mod update_url_handler;
+pub mod url_config_handler;
+pub mod url_utils;
+pub mod utm_attribution_handler;
 
 pub use async_batch_url_operations_handler::*;
 pub use async_bulk_shorten_urls_handler::*;
@@ -19,7 +35,22 @@ pub use bulk_delete_handler::*;
 pub use bulk_expiration_update_handler::*;
 pub use bulk_shorten_urls_handler::*;
 pub use bulk_status_update_handler::*;
+pub use click_dedup_ratio_handler::*;
+pub use click_patterns_handler::*;
 pub use deactivate_url_handler::*;
+pub use duplicate_url_handler::*;
+pub use get_top_urls_handler::*;
+pub use get_url_analytics_handler::*;
+pub use get_url_analytics_summary_handler::*;
+pub use get_url_handler::*;
+pub use link_preview_handler::*;
+pub use list_urls_handler::*;
+pub use preview_settings_handler::*;
 pub use reactivate_url_handler::*;
 pub use redirect_handler::*;
+pub use rename_short_code_handler::*;
+pub use restore_url_handler::*;
 pub use shorten_url_handler::*;
+pub use update_url_handler::*;
+pub use url_config_handler::*;
+pub use utm_attribution_handler::*;
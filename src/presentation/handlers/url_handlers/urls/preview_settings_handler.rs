@@ -0,0 +1,164 @@
+use crate::application::dto::{
+    requests::UpdatePreviewSettingsRequest, responses::PreviewSettingsResponse, ErrorResponse,
+};
+use crate::domain::entities::{PreviewMode, Url};
+use crate::domain::repositories::RepositoryError;
+use crate::domain::services::ServiceError;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: &str,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message: message.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Load a URL owned by the user behind the request's bearer token
+async fn find_owned_url(
+    app_state: &ConcreteAppState,
+    headers: &HeaderMap,
+    id: i32,
+) -> Result<Url, (StatusCode, Json<ErrorResponse>)> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::UNAUTHORIZED,
+                "UNAUTHORIZED",
+                "Missing or invalid Authorization header",
+            )
+        })?;
+
+    let user = app_state
+        .auth_service
+        .verify_token(token)
+        .await
+        .map_err(|e| {
+            warn!("Token verification failed: {}", e);
+            token_error_response(&e)
+        })?;
+
+    match app_state.url_service.get_url_by_id(id).await {
+        Ok(Some(url)) if url.user_id == Some(user.id) => Ok(url),
+        Ok(_) => Err(error_response(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            "URL not found or you don't have permission to access it",
+        )),
+        Err(error) => {
+            warn!("Failed to load URL {}: {}", id, error);
+            Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                "Failed to load URL",
+            ))
+        }
+    }
+}
+
+fn settings_response(app_state: &ConcreteAppState, url: &Url) -> PreviewSettingsResponse {
+    PreviewSettingsResponse {
+        url_id: url.id,
+        preview_mode: url.preview_mode.to_string(),
+        preview_required: app_state.interstitial_service.requires_preview(url),
+    }
+}
+
+/// Handler returning when a URL shows the preview interstitial
+#[utoipa::path(
+    get,
+    path = "/urls/{id}/preview-settings",
+    params(
+        ("id" = i32, Path, description = "URL ID")
+    ),
+    responses(
+        (status = 200, description = "Preview settings", body = PreviewSettingsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn get_preview_settings_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<Json<PreviewSettingsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let url = find_owned_url(&app_state, &headers, id).await?;
+    Ok(Json(settings_response(&app_state, &url)))
+}
+
+/// Handler changing when a URL shows the preview interstitial
+#[utoipa::path(
+    put,
+    path = "/urls/{id}/preview-settings",
+    params(
+        ("id" = i32, Path, description = "URL ID")
+    ),
+    request_body = UpdatePreviewSettingsRequest,
+    responses(
+        (status = 200, description = "Preview settings updated", body = PreviewSettingsResponse),
+        (status = 400, description = "Unknown preview mode", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+        (status = 409, description = "URL was modified concurrently; retry", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn update_preview_settings_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    ValidatedJson(request): ValidatedJson<UpdatePreviewSettingsRequest>,
+) -> Result<Json<PreviewSettingsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let preview_mode = PreviewMode::parse(&request.preview_mode).ok_or_else(|| {
+        error_response(
+            StatusCode::BAD_REQUEST,
+            "INVALID_PREVIEW_MODE",
+            "preview_mode must be one of none, always or high_risk",
+        )
+    })?;
+
+    let mut url = find_owned_url(&app_state, &headers, id).await?;
+    if url.preview_mode == preview_mode {
+        return Ok(Json(settings_response(&app_state, &url)));
+    }
+
+    let version = url.version;
+    url.preview_mode = preview_mode;
+    match app_state.url_service.update_url(&url, version).await {
+        Ok(updated) => {
+            info!("Set preview mode of URL {} to {}", id, preview_mode);
+            Ok(Json(settings_response(&app_state, &updated)))
+        }
+        Err(ServiceError::Repository(RepositoryError::ConflictingUpdate { .. })) => {
+            Err(error_response(
+                StatusCode::CONFLICT,
+                "CONFLICT",
+                "URL was modified concurrently; retry",
+            ))
+        }
+        Err(error) => {
+            warn!("Failed to update preview mode of URL {}: {}", id, error);
+            Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "UPDATE_FAILED",
+                "Failed to update preview settings",
+            ))
+        }
+    }
+}
@@ -1,83 +1,583 @@
-use crate::application::dto::ErrorResponse;
+use super::link_preview_handler::{find_url_with_metadata, preview_page};
+use crate::application::dto::{
+    requests::{ConfirmRedirectForm, RedirectQuery},
+    ErrorResponse,
+};
+use crate::domain::entities::{AccessibilityStatus, RedirectType, ShortCode, Url, UtmParameters};
+use crate::domain::services::click_tracking_service::ClickInfo;
+use crate::infrastructure::http::RealIpExtractor;
+use crate::infrastructure::rate_limiting::IpVerdict;
+use crate::presentation::handlers::url_handlers::urls::url_utils::{
+    short_code_lookup_error_response, url_to_preview_response,
+};
 use crate::presentation::handlers::ConcreteAppState;
-use axum::{extract::State, http::StatusCode, response::Redirect, Json};
+use askama::Template;
+use axum::{
+    extract::{ConnectInfo, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Redirect, Response},
+    Form, Json,
+};
+use std::net::{IpAddr, SocketAddr};
 use tracing::{info, warn};
 
+/// Read a header value as an owned string
+fn header_string(headers: &HeaderMap, name: impl header::AsHeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+/// Build click tracking information from the connection and request headers
+///
+/// Each click gets a fresh random token for the conversion cookie. The click is attributed
+/// to the UTM parameters of the destination, or else to those of the referring page so
+/// campaigns survive a chain of short links.
+fn click_info_from_request(
+    real_ip_extractor: &RealIpExtractor,
+    peer: Option<IpAddr>,
+    headers: &HeaderMap,
+    destination: &str,
+) -> ClickInfo {
+    let referer = header_string(headers, header::REFERER);
+    let referer_utm = referer
+        .as_deref()
+        .map(UtmParameters::from_url)
+        .unwrap_or_default();
+    ClickInfo {
+        ip_address: real_ip_extractor
+            .extract(peer, headers)
+            .map(|ip| ip.to_string()),
+        user_agent: header_string(headers, header::USER_AGENT),
+        referer,
+        country_code: None,
+        click_token: Some(uuid::Uuid::new_v4().simple().to_string()),
+        suspicious: false,
+        utm: UtmParameters::from_url(destination).or(referer_utm),
+    }
+}
+
+/// Seconds the preview interstitial waits before proceeding on its own
+const INTERSTITIAL_DELAY_SECONDS: u32 = 5;
+
+/// "You are leaving" page shown before redirecting to links that require a preview
+#[derive(Template)]
+#[template(path = "preview.html")]
+struct InterstitialTemplate<'a> {
+    host: &'a str,
+    destination: &'a str,
+    short_code: &'a str,
+    token: &'a str,
+    delay_seconds: u32,
+}
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: &str,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message: message.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Error sent instead of redirecting to a URL that is not accessible
+fn inaccessible_response(reason: AccessibilityStatus) -> (StatusCode, Json<ErrorResponse>) {
+    match reason {
+        // Expired and deleted links are gone for good, so clients and crawlers may forget them
+        AccessibilityStatus::Expired => error_response(
+            StatusCode::GONE,
+            "URL_EXPIRED",
+            "This short link has expired",
+        ),
+        AccessibilityStatus::Deleted => error_response(
+            StatusCode::GONE,
+            "URL_DELETED",
+            "This short link has been deleted",
+        ),
+        AccessibilityStatus::ClickLimitReached => error_response(
+            StatusCode::NOT_FOUND,
+            "CLICK_LIMIT_REACHED",
+            "This short link has reached its click limit",
+        ),
+        AccessibilityStatus::PasswordRequired => error_response(
+            StatusCode::FORBIDDEN,
+            "INVALID_PASSWORD",
+            "Wrong password for this short link",
+        ),
+        AccessibilityStatus::Inactive | AccessibilityStatus::Accessible => error_response(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            "Short code not found or no longer available",
+        ),
+    }
+}
+
+/// Look up the URL a short code redirects to, checking expiration, status and click limit
+///
+/// Password protected URLs are returned; `check_password` asks for the password.
+async fn find_redirect_url(
+    app_state: &ConcreteAppState,
+    short_code_str: String,
+) -> Result<Url, (StatusCode, Json<ErrorResponse>)> {
+    // Parse and validate short code
+    let short_code = match ShortCode::new(short_code_str) {
+        Ok(code) => code,
+        Err(error) => {
+            warn!("Invalid short code format: {}", error);
+            return Err(error_response(
+                StatusCode::BAD_REQUEST,
+                "INVALID_SHORT_CODE",
+                &error.to_string(),
+            ));
+        }
+    };
+
+    match app_state
+        .url_service
+        .get_url_for_redirect(&short_code)
+        .await
+    {
+        Ok(Some(url)) => match url.accessibility_reason() {
+            AccessibilityStatus::Accessible | AccessibilityStatus::PasswordRequired => Ok(url),
+            reason => {
+                warn!(
+                    "Short code {} is not accessible: {:?}",
+                    short_code.value(),
+                    reason
+                );
+                Err(inaccessible_response(reason))
+            }
+        },
+        Ok(None) => {
+            warn!("Short code not found: {}", short_code.value());
+            Err(error_response(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                "Short code not found or no longer available",
+            ))
+        }
+        Err(error) => {
+            warn!("Database error while looking up short code: {}", error);
+            Err(short_code_lookup_error_response(&error))
+        }
+    }
+}
+
+/// Header visitors of a password protected URL send the password in
+pub const LINK_PASSWORD_HEADER: &str = "x-link-password";
+
+/// Require the URL's password, if it has one, in the `X-Link-Password` header
+fn check_password(url: &Url, headers: &HeaderMap) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if !url.is_password_protected() {
+        return Ok(());
+    }
+    match headers
+        .get(LINK_PASSWORD_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        None => Err(error_response(
+            StatusCode::UNAUTHORIZED,
+            "PASSWORD_REQUIRED",
+            "This short link is password protected",
+        )),
+        Some(password) if url.verify_password(password) => Ok(()),
+        Some(_) => {
+            warn!("Wrong password given for {}", url.short_code);
+            Err(inaccessible_response(AccessibilityStatus::PasswordRequired))
+        }
+    }
+}
+
+/// The redirect a URL's redirect type calls for
+fn redirect_to(url: &Url) -> Redirect {
+    match url.redirect_type {
+        RedirectType::Permanent => Redirect::permanent(&url.original_url),
+        RedirectType::Temporary => Redirect::temporary(&url.original_url),
+    }
+}
+
+/// Check the reputation of the clicking IP, flagging the click as suspicious when it is bad
+///
+/// IPs are trusted when reputation checks are off or no score could be had; with
+/// `block_suspicious_ips` a bad IP gets a 403 instead.
+async fn screen_click(
+    app_state: &ConcreteAppState,
+    url: &Url,
+    click_info: &mut ClickInfo,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let Some(reputation) = &app_state.ip_reputation_service else {
+        return Ok(());
+    };
+    let Some(ip) = click_info
+        .ip_address
+        .as_deref()
+        .and_then(|ip| ip.parse::<IpAddr>().ok())
+    else {
+        return Ok(());
+    };
+
+    match reputation.check(ip, url.id).await {
+        IpVerdict::Trusted => Ok(()),
+        IpVerdict::Suspicious { score } => {
+            info!(
+                "Click on {} from {} is suspicious (abuse score {})",
+                url.short_code, ip, score
+            );
+            click_info.suspicious = true;
+            Ok(())
+        }
+        IpVerdict::Blocked { .. } => Err(error_response(
+            StatusCode::FORBIDDEN,
+            "SUSPICIOUS_IP",
+            "Requests from your IP address are not allowed",
+        )),
+    }
+}
+
+/// Record the click and send the visitor on to the destination
+fn follow_redirect(
+    app_state: &ConcreteAppState,
+    url: &Url,
+    click_info: ClickInfo,
+    redirect: Redirect,
+) -> Response {
+    info!("Redirecting {} to {}", url.short_code, url.original_url);
+    // Buffered write; a dropped click must never fail the redirect
+    let click_token = click_info.click_token.clone();
+    let recorded = app_state
+        .click_tracking_service
+        .record_click(url.id, click_info)
+        .is_ok();
+
+    // Only hand out a token for a click that will actually be stored
+    match click_token.filter(|_| recorded) {
+        Some(token) => (
+            [(
+                header::SET_COOKIE,
+                app_state.click_cookie.set_cookie_value(&token),
+            )],
+            redirect,
+        )
+            .into_response(),
+        None => redirect.into_response(),
+    }
+}
+
+/// Render the interstitial asking the visitor to confirm the destination
+///
+/// No click is recorded here; that happens once the visitor proceeds.
+fn interstitial_page(app_state: &ConcreteAppState, url: &Url) -> Response {
+    let base_url = app_state.shorten_url_use_case.base_url();
+    let host = url::Url::parse(base_url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_string))
+        .unwrap_or_else(|| base_url.to_string());
+    let token = app_state
+        .interstitial_service
+        .confirmation_token(&url.short_code);
+    let template = InterstitialTemplate {
+        host: &host,
+        destination: &url.original_url,
+        short_code: &url.short_code,
+        token: &token,
+        delay_seconds: INTERSTITIAL_DELAY_SECONDS,
+    };
+
+    match template.render() {
+        Ok(page) => ([(header::CACHE_CONTROL, "no-store")], Html(page)).into_response(),
+        Err(error) => {
+            warn!("Failed to render preview interstitial: {}", error);
+            error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_ERROR",
+                "Failed to render preview page",
+            )
+            .into_response()
+        }
+    }
+}
+
 /// Handler for redirecting to original URL
+///
+/// Sets the `url_shortener_click_id` cookie so goal pages can report conversions. With
+/// `?preview=true` an HTML page describing the destination is shown first instead. Links
+/// whose preview mode (or the `require_preview_for_unverified` policy) calls for it get an
+/// interstitial page that visitors confirm before being redirected. Clicks from IPs with a
+/// bad AbuseIPDB reputation are recorded as suspicious, or refused when so configured.
+/// Password protected links need the password in the `X-Link-Password` header.
 #[utoipa::path(
     get,
     path = "/{short_code}",
     params(
-        ("short_code" = String, Path, description = "Short code to redirect")
+        ("short_code" = String, Path, description = "Short code to redirect"),
+        ("preview" = Option<bool>, Query, description = "Show a preview page that redirects after 3 seconds"),
+        ("X-Link-Password" = Option<String>, Header, description = "Password of a password protected link")
     ),
     responses(
-        (status = 301, description = "Redirect to original URL"),
+        (status = 200, description = "Preview page (with preview=true) or confirmation interstitial", content_type = "text/html"),
+        (status = 307, description = "Temporary redirect to original URL; sets the url_shortener_click_id cookie"),
+        (status = 308, description = "Permanent redirect to original URL; sets the url_shortener_click_id cookie"),
         (status = 400, description = "Bad request", body = ErrorResponse),
-        (status = 404, description = "Short code not found", body = ErrorResponse),
+        (status = 401, description = "Link is password protected and no password was given", body = ErrorResponse),
+        (status = 403, description = "Wrong password, or client IP refused for its abuse score", body = ErrorResponse),
+        (status = 404, description = "Short code not found, deactivated or past its click limit", body = ErrorResponse),
+        (status = 410, description = "Short link expired or deleted", body = ErrorResponse),
+        (status = 503, description = "Lookup timed out; safe to retry", body = ErrorResponse),
     ),
     tag = "url-shortener"
 )]
+#[tracing::instrument(
+    skip(app_state, short_code_str, query, connect_info, headers),
+    fields(short_code = %short_code_str)
+)]
 pub async fn redirect_handler(
     State(app_state): State<ConcreteAppState>,
     axum::extract::Path(short_code_str): axum::extract::Path<String>,
-) -> Result<Redirect, (StatusCode, Json<ErrorResponse>)> {
+    Query(query): Query<RedirectQuery>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    // The preview page links back here without the flag, so the click is counted then
+    if query.preview {
+        let (url, metadata) = find_url_with_metadata(&app_state, short_code_str).await?;
+        check_password(&url, &headers)?;
+        let base_url = app_state.shorten_url_use_case.base_url();
+        return Ok(preview_page(&url_to_preview_response(
+            url, metadata, base_url,
+        )));
+    }
+
     info!(
         "Received redirect request for short code: {}",
         short_code_str
     );
 
-    // Parse and validate short code
-    let short_code = match crate::domain::entities::ShortCode::new(short_code_str) {
-        Ok(code) => code,
-        Err(error) => {
-            warn!("Invalid short code format: {}", error);
-            let error_response = ErrorResponse {
-                error: "INVALID_SHORT_CODE".to_string(),
-                message: error.to_string(),
-                status_code: StatusCode::BAD_REQUEST.as_u16(),
-            };
-            return Err((StatusCode::BAD_REQUEST, Json(error_response)));
-        }
-    };
+    let url = find_redirect_url(&app_state, short_code_str).await?;
+    check_password(&url, &headers)?;
+    let mut click_info = click_info_from_request(
+        &app_state.real_ip_extractor,
+        connect_info.map(|ConnectInfo(addr)| addr.ip()),
+        &headers,
+        &url.original_url,
+    );
+    screen_click(&app_state, &url, &mut click_info).await?;
+    if app_state.interstitial_service.requires_preview(&url) {
+        return Ok(interstitial_page(&app_state, &url));
+    }
 
-    // Find the URL with validation (checks expiration and status)
-    match app_state
-        .url_service
-        .get_url_by_short_code_with_validation(&short_code)
+    Ok(follow_redirect(
+        &app_state,
+        &url,
+        click_info,
+        redirect_to(&url),
+    ))
+}
+
+/// Bodiless answer to a `HEAD` request for a URL that would be redirected to
+///
+/// `Content-Length` is the length of the `Location` target, as link checkers use it to
+/// tell destinations apart without following them.
+fn head_response(app_state: &ConcreteAppState, url: &Url) -> Response {
+    if app_state.interstitial_service.requires_preview(url) {
+        return (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+        )
+            .into_response();
+    }
+    head_redirect_response(url)
+}
+
+/// The redirect of [`redirect_to`] without a body
+fn head_redirect_response(url: &Url) -> Response {
+    let mut response = redirect_to(url).into_response();
+    response.headers_mut().insert(
+        header::CONTENT_LENGTH,
+        header::HeaderValue::from(url.original_url.len()),
+    );
+    response
+}
+
+/// Handler for `HEAD` requests to a short link, as sent by link checkers and monitors
+///
+/// Answers with the status and headers a `GET` would get, without a body. No click is
+/// recorded and no IP reputation lookup is made, since these are not visits.
+#[utoipa::path(
+    head,
+    path = "/{short_code}",
+    params(
+        ("short_code" = String, Path, description = "Short code to check"),
+        ("X-Link-Password" = Option<String>, Header, description = "Password of a password protected link")
+    ),
+    responses(
+        (status = 200, description = "Link shows a confirmation interstitial first"),
+        (status = 307, description = "Temporary redirect; Content-Length is the length of the Location target"),
+        (status = 308, description = "Permanent redirect; Content-Length is the length of the Location target"),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Link is password protected and no password was given"),
+        (status = 403, description = "Wrong password"),
+        (status = 404, description = "Short code not found, deactivated or past its click limit"),
+        (status = 410, description = "Short link expired or deleted"),
+        (status = 503, description = "Lookup timed out; safe to retry"),
+    ),
+    tag = "url-shortener"
+)]
+pub async fn head_redirect_handler(
+    State(app_state): State<ConcreteAppState>,
+    axum::extract::Path(short_code_str): axum::extract::Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let url = find_redirect_url(&app_state, short_code_str)
         .await
+        .map_err(|(status, _)| status)?;
+    check_password(&url, &headers).map_err(|(status, _)| status)?;
+
+    Ok(head_response(&app_state, &url))
+}
+
+/// Handler for the "Proceed" button of the preview interstitial
+///
+/// Requires `?confirm=true` and the signed token from the interstitial, so the page cannot
+/// be skipped by posting directly.
+#[utoipa::path(
+    post,
+    path = "/{short_code}",
+    params(
+        ("short_code" = String, Path, description = "Short code to redirect"),
+        ("confirm" = bool, Query, description = "Must be true")
+    ),
+    request_body(content = ConfirmRedirectForm, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 303, description = "Redirect to original URL; sets the url_shortener_click_id cookie"),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 403, description = "Missing, invalid or expired confirmation, or client IP refused for its abuse score", body = ErrorResponse),
+        (status = 404, description = "Short code not found or deactivated", body = ErrorResponse),
+        (status = 410, description = "Short link expired or deleted", body = ErrorResponse),
+        (status = 503, description = "Lookup timed out; safe to retry", body = ErrorResponse),
+    ),
+    tag = "url-shortener"
+)]
+pub async fn confirm_redirect_handler(
+    State(app_state): State<ConcreteAppState>,
+    axum::extract::Path(short_code_str): axum::extract::Path<String>,
+    Query(query): Query<RedirectQuery>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Form(form): Form<ConfirmRedirectForm>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    if !query.confirm
+        || !app_state
+            .interstitial_service
+            .verify_confirmation(&short_code_str, &form.token)
     {
-        Ok(Some(url)) => {
-            info!("Redirecting {} to {}", short_code.value(), url.original_url);
-            Ok(Redirect::permanent(&url.original_url))
-        }
-        Ok(None) => {
-            warn!(
-                "Short code not found or not accessible: {}",
-                short_code.value()
-            );
-            let error_response = ErrorResponse {
-                error: "NOT_FOUND".to_string(),
-                message: "Short code not found or no longer available".to_string(),
-                status_code: StatusCode::NOT_FOUND.as_u16(),
-            };
-            Err((StatusCode::NOT_FOUND, Json(error_response)))
-        }
-        Err(error) => {
-            warn!("Database error while looking up short code: {}", error);
-            let error_response = ErrorResponse {
-                error: "DATABASE_ERROR".to_string(),
-                message: "Internal server error".to_string(),
-                status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
-            };
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-        }
+        warn!("Rejected preview confirmation for {}", short_code_str);
+        return Err(error_response(
+            StatusCode::FORBIDDEN,
+            "INVALID_CONFIRMATION",
+            "Confirmation is missing or expired; reload the link",
+        ));
     }
+
+    let url = find_redirect_url(&app_state, short_code_str).await?;
+    let mut click_info = click_info_from_request(
+        &app_state.real_ip_extractor,
+        connect_info.map(|ConnectInfo(addr)| addr.ip()),
+        &headers,
+        &url.original_url,
+    );
+    screen_click(&app_state, &url, &mut click_info).await?;
+    // 303 so the browser follows with a GET
+    Ok(follow_redirect(
+        &app_state,
+        &url,
+        click_info,
+        Redirect::to(&url.original_url),
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::entities::ShortCode;
+    use crate::domain::repositories::{ClickRepository, UrlRepository};
+    use crate::infrastructure::test_utils::TestApp;
+    use axum::http::Method;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_head_redirect_response_has_no_body() {
+        let mut url = Url::new_with_timestamp(
+            1,
+            "abc123".to_string(),
+            "https://example.com/landing".to_string(),
+            None,
+            None,
+            crate::domain::entities::UrlStatus::Active,
+        );
+        let response = head_redirect_response(&url);
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            response.headers()[header::LOCATION],
+            "https://example.com/landing"
+        );
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], "27");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(body.is_empty());
+
+        url.redirect_type = RedirectType::Temporary;
+        let response = head_redirect_response(&url);
+        assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+    }
+
+    #[tokio::test]
+    async fn test_only_get_requests_count_clicks() {
+        let app = TestApp::new();
+        let url = app
+            .url_repository
+            .create_url(
+                &ShortCode::new("headed1".to_string()).unwrap(),
+                "https://example.com/landing",
+                None,
+                None,
+                None,
+                crate::domain::entities::UrlStatus::Active,
+            )
+            .await
+            .unwrap();
+        let router = app.router();
+        let request = |method: Method| {
+            axum::http::Request::builder()
+                .method(method)
+                .uri("/headed1")
+                .body(axum::body::Body::empty())
+                .unwrap()
+        };
+
+        for _ in 0..2 {
+            let response = router.clone().oneshot(request(Method::HEAD)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+            assert_eq!(response.headers()[header::CONTENT_LENGTH], "27");
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert!(body.is_empty());
+        }
+        let response = router.oneshot(request(Method::GET)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+
+        // Writes the buffered clicks
+        app.state.click_tracking_service.shutdown().await;
+        assert_eq!(
+            app.click_repository.get_click_count(url.id).await.unwrap(),
+            1
+        );
+    }
 
     #[test]
     fn test_short_code_validation() {
@@ -85,6 +585,85 @@ mod tests {
         assert!(valid_code.is_ok());
     }
 
+    #[test]
+    fn test_click_info_from_trusted_proxy() {
+        let extractor = RealIpExtractor::new(vec!["10.0.0.0/8".parse().unwrap()]);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "203.0.113.7, 10.0.0.1".parse().unwrap());
+        headers.insert(header::USER_AGENT, "curl/8.0".parse().unwrap());
+
+        let info = click_info_from_request(
+            &extractor,
+            Some("10.0.0.2".parse().unwrap()),
+            &headers,
+            "https://example.com",
+        );
+        assert_eq!(info.ip_address.as_deref(), Some("203.0.113.7"));
+        assert_eq!(info.user_agent.as_deref(), Some("curl/8.0"));
+        assert_eq!(info.referer, None);
+        assert_eq!(info.click_token.as_deref().map(str::len), Some(32));
+        assert!(info.utm.is_empty());
+    }
+
+    #[test]
+    fn test_click_info_takes_utm_from_destination_then_referer() {
+        let extractor = RealIpExtractor::new(vec![]);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::REFERER,
+            "https://short.ly/promo?utm_source=partner&utm_medium=link"
+                .parse()
+                .unwrap(),
+        );
+
+        let info = click_info_from_request(
+            &extractor,
+            None,
+            &headers,
+            "https://example.com?utm_source=newsletter",
+        );
+        assert_eq!(info.utm.source.as_deref(), Some("newsletter"));
+        assert_eq!(info.utm.medium, None);
+
+        let info = click_info_from_request(&extractor, None, &headers, "https://example.com");
+        assert_eq!(info.utm.source.as_deref(), Some("partner"));
+        assert_eq!(info.utm.medium.as_deref(), Some("link"));
+    }
+
+    #[test]
+    fn test_click_info_ignores_forwarded_header_from_untrusted_client() {
+        let extractor = RealIpExtractor::new(vec!["10.0.0.0/8".parse().unwrap()]);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "203.0.113.7".parse().unwrap());
+
+        let info = click_info_from_request(
+            &extractor,
+            Some("198.51.100.9".parse().unwrap()),
+            &headers,
+            "https://example.com",
+        );
+        assert_eq!(info.ip_address.as_deref(), Some("198.51.100.9"));
+    }
+
+    #[test]
+    fn test_interstitial_template_escapes_destination() {
+        let page = InterstitialTemplate {
+            host: "short.ly",
+            destination: "https://example.com/?a=1&b=\"><script>alert(1)</script>",
+            short_code: "abc123",
+            token: "123.sig",
+            delay_seconds: 5,
+        }
+        .render()
+        .unwrap();
+
+        assert!(page.contains("You are leaving short.ly"));
+        assert!(page.contains("action=\"/abc123?confirm=true\""));
+        assert!(page.contains("value=\"123.sig\""));
+        assert!(page.contains("var remaining = 5;"));
+        assert!(!page.contains("<script>alert(1)"));
+    }
+
     #[test]
     fn test_invalid_short_code_error() {
         let error = ErrorResponse {
@@ -105,6 +684,34 @@ mod tests {
         assert_eq!(error.status_code, 404);
     }
 
+    #[test]
+    fn test_inaccessible_urls_map_to_status() {
+        let (status, Json(body)) = inaccessible_response(AccessibilityStatus::Expired);
+        assert_eq!(status, StatusCode::GONE);
+        assert_eq!(body.error, "URL_EXPIRED");
+
+        let (status, Json(body)) = inaccessible_response(AccessibilityStatus::Deleted);
+        assert_eq!(status, StatusCode::GONE);
+        assert_eq!(body.error, "URL_DELETED");
+
+        let (status, Json(body)) = inaccessible_response(AccessibilityStatus::Inactive);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.error, "NOT_FOUND");
+
+        let (status, Json(body)) = inaccessible_response(AccessibilityStatus::ClickLimitReached);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.error, "CLICK_LIMIT_REACHED");
+
+        let (status, Json(body)) = inaccessible_response(AccessibilityStatus::PasswordRequired);
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body.error, "INVALID_PASSWORD");
+
+        // Never sent for an accessible URL, but answered like an unknown short code
+        let (status, Json(body)) = inaccessible_response(AccessibilityStatus::Accessible);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.error, "NOT_FOUND");
+    }
+
     #[test]
     fn test_database_error_response() {
         let error = ErrorResponse {
@@ -114,4 +721,49 @@ mod tests {
         };
         assert_eq!(error.status_code, 500);
     }
+
+    #[test]
+    fn test_password_protected_urls_need_the_password() {
+        let mut url = Url::new_with_timestamp(
+            1,
+            "secret1".to_string(),
+            "https://example.com".to_string(),
+            None,
+            None,
+            crate::domain::entities::UrlStatus::Active,
+        );
+        let mut headers = HeaderMap::new();
+        assert!(check_password(&url, &headers).is_ok());
+
+        url.password_hash = Some(bcrypt::hash("s3cret", 4).unwrap());
+        let (status, Json(body)) = check_password(&url, &headers).unwrap_err();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body.error, "PASSWORD_REQUIRED");
+
+        headers.insert(LINK_PASSWORD_HEADER, "wrong".parse().unwrap());
+        let (status, Json(body)) = check_password(&url, &headers).unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body.error, "INVALID_PASSWORD");
+
+        headers.insert(LINK_PASSWORD_HEADER, "s3cret".parse().unwrap());
+        assert!(check_password(&url, &headers).is_ok());
+    }
+
+    #[test]
+    fn test_redirect_status_follows_redirect_type() {
+        let mut url = Url::new_with_timestamp(
+            1,
+            "temp1".to_string(),
+            "https://example.com".to_string(),
+            None,
+            None,
+            crate::domain::entities::UrlStatus::Active,
+        );
+        let response = redirect_to(&url).into_response();
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+
+        url.redirect_type = RedirectType::Temporary;
+        let response = redirect_to(&url).into_response();
+        assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+    }
 }
@@ -0,0 +1,149 @@
+use crate::application::dto::{
+    requests::RenameShortCodeRequest, responses::UrlDetailResponse, ErrorResponse,
+};
+use crate::application::use_cases::shorten_url::UseCaseError;
+use crate::domain::repositories::RepositoryError;
+use crate::domain::services::ServiceError;
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::url_handlers::urls::url_utils::{
+    find_accessible_url, url_to_detail_response,
+};
+use crate::presentation::handlers::{ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use tracing::{info, warn};
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: String,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message,
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Map a failed rename to an HTTP error
+fn rename_error_response(error: &UseCaseError) -> (StatusCode, Json<ErrorResponse>) {
+    match error {
+        UseCaseError::InvalidShortCode(_)
+        | UseCaseError::Service(ServiceError::InvalidShortCode(_)) => error_response(
+            StatusCode::BAD_REQUEST,
+            "INVALID_SHORT_CODE",
+            error.to_string(),
+        ),
+        UseCaseError::Service(ServiceError::Repository(RepositoryError::NotFound)) => {
+            error_response(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                "URL not found".to_string(),
+            )
+        }
+        UseCaseError::Service(ServiceError::ShortCodeAlreadyExists) => error_response(
+            StatusCode::CONFLICT,
+            "DUPLICATE_SHORT_CODE",
+            "Short code already exists".to_string(),
+        ),
+        UseCaseError::Service(ServiceError::Repository(
+            e @ RepositoryError::AliasLimitReached { .. },
+        )) => error_response(StatusCode::CONFLICT, "ALIAS_LIMIT_REACHED", e.to_string()),
+        _ => {
+            warn!("Failed to rename short code: {}", error);
+            error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "RENAME_FAILED",
+                "Failed to rename short code".to_string(),
+            )
+        }
+    }
+}
+
+/// Handler giving a URL a new short code
+///
+/// The old short code becomes an alias that keeps redirecting to the URL, so links already
+/// shared stay valid. A URL keeps at most `MAX_SHORT_CODE_ALIASES` aliases.
+#[utoipa::path(
+    patch,
+    path = "/urls/{id}/short-code",
+    params(
+        ("id" = i32, Path, description = "URL ID to rename")
+    ),
+    request_body = RenameShortCodeRequest,
+    responses(
+        (status = 200, description = "Short code renamed", body = UrlDetailResponse),
+        (status = 400, description = "Invalid short code", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+        (status = 409, description = "Short code taken or alias limit reached", body = ErrorResponse),
+        (status = 422, description = "Request body breaks a validation rule", body = ValidationErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn rename_short_code_handler(
+    State(app_state): State<ConcreteAppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<i32>,
+    ValidatedJson(request): ValidatedJson<RenameShortCodeRequest>,
+) -> Result<Json<UrlDetailResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let url = find_accessible_url(&app_state, id, user.id).await?;
+    let url = app_state
+        .update_url_use_case
+        .rename_short_code(&url, request.short_code)
+        .await
+        .map_err(|e| rename_error_response(&e))?;
+    info!(
+        "User {} renamed the short code of URL {} to {}",
+        user.id, id, url.short_code
+    );
+
+    let click_count = match app_state.click_tracking_service.get_click_count(id).await {
+        Ok(count) => count,
+        Err(error) => {
+            warn!("Failed to count clicks of URL {}: {}", id, error);
+            return Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                "Failed to load URL".to_string(),
+            ));
+        }
+    };
+
+    let use_case = &app_state.shorten_url_use_case;
+    let links = use_case.links(&url);
+    Ok(Json(url_to_detail_response(
+        url,
+        use_case.base_url(),
+        click_count,
+        links,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_error_responses() {
+        let full = UseCaseError::Service(ServiceError::Repository(
+            RepositoryError::AliasLimitReached { limit: 3 },
+        ));
+        let (status, Json(body)) = rename_error_response(&full);
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body.error, "ALIAS_LIMIT_REACHED");
+
+        let taken = UseCaseError::Service(ServiceError::ShortCodeAlreadyExists);
+        let (status, Json(body)) = rename_error_response(&taken);
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body.error, "DUPLICATE_SHORT_CODE");
+
+        let foreign = UseCaseError::Service(ServiceError::Repository(RepositoryError::NotFound));
+        let (status, _) = rename_error_response(&foreign);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
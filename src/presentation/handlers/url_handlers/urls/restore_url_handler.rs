@@ -0,0 +1,142 @@
+use crate::application::dto::{
+    requests::RestoreUrlRequest, responses::UrlInfoResponse, ErrorResponse,
+};
+use crate::domain::repositories::RepositoryError;
+use crate::domain::services::ServiceError;
+use crate::presentation::handlers::url_handlers::urls::url_utils::url_to_info_response;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    http::{header, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: String,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message,
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Handler for restoring an archived URL
+///
+/// The URL redirects again and expires at the given `expiration_date`, or never when it is
+/// omitted.
+#[utoipa::path(
+    post,
+    path = "/urls/{id}/restore",
+    params(
+        ("id" = i32, Path, description = "URL ID to restore")
+    ),
+    request_body = RestoreUrlRequest,
+    responses(
+        (status = 200, description = "URL restored", body = UrlInfoResponse),
+        (status = 400, description = "URL is not archived or expiration date is in the past", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+        (status = 409, description = "URL changed while it was being restored", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn restore_url_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    axum::extract::Path(id): axum::extract::Path<i32>,
+    ValidatedJson(request): ValidatedJson<RestoreUrlRequest>,
+) -> Result<(StatusCode, Json<UrlInfoResponse>), (StatusCode, Json<ErrorResponse>)> {
+    // Require Authorization: Bearer <token>
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(t) if !t.is_empty() => t,
+        _ => {
+            return Err(error_response(
+                StatusCode::UNAUTHORIZED,
+                "UNAUTHORIZED",
+                "Missing or invalid Authorization header".to_string(),
+            ));
+        }
+    };
+
+    // Verify token and get user
+    let user = match app_state.auth_service.verify_token(token).await {
+        Ok(u) => u,
+        Err(e) => {
+            warn!("Token verification failed: {}", e);
+            return Err(token_error_response(&e));
+        }
+    };
+
+    info!(
+        "Received restore URL request for ID: {} (user: {})",
+        id, user.id
+    );
+
+    match app_state
+        .url_service
+        .restore_url(id, user.id, request.expiration_date)
+        .await
+    {
+        Ok(Some(url)) => {
+            info!("Successfully restored URL ID: {}", id);
+            let base_url = app_state.shorten_url_use_case.base_url();
+            Ok((
+                StatusCode::OK,
+                Json(url_to_info_response(url, base_url, None)),
+            ))
+        }
+        Ok(None) => {
+            warn!("URL not found or not owned by user: {}", id);
+            Err(error_response(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                "URL not found or you don't have permission to restore it".to_string(),
+            ))
+        }
+        Err(ServiceError::InvalidData(message)) => Err(error_response(
+            StatusCode::BAD_REQUEST,
+            "INVALID_REQUEST",
+            message,
+        )),
+        Err(ServiceError::Repository(RepositoryError::ConflictingUpdate { .. })) => {
+            Err(error_response(
+                StatusCode::CONFLICT,
+                "CONFLICT",
+                "URL changed while it was being restored; retry".to_string(),
+            ))
+        }
+        Err(error) => {
+            warn!("Failed to restore URL {}: {}", id, error);
+            Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "RESTORE_FAILED",
+                "Internal server error".to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_request_defaults_to_no_expiration() {
+        let request: RestoreUrlRequest = serde_json::from_str("{}").unwrap();
+        assert!(request.expiration_date.is_none());
+
+        let request: RestoreUrlRequest =
+            serde_json::from_str(r#"{"expiration_date":"2030-01-01T00:00:00Z"}"#).unwrap();
+        assert!(request.expiration_date.is_some());
+    }
+}
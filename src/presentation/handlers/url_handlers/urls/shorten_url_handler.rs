@@ -1,15 +1,74 @@
 use crate::application::dto::{
-    requests::ShortenUrlRequest, responses::ShortenUrlResponse, ErrorResponse,
+    requests::ShortenUrlRequest,
+    responses::{ShortCodeLengthErrorResponse, ShortenUrlResponse},
+    ErrorResponse,
 };
-use crate::presentation::handlers::ConcreteAppState;
+use crate::application::use_cases::shorten_url::UseCaseError;
+use crate::domain::entities::ShortCodeError;
+use crate::domain::repositories::RepositoryError;
+use crate::domain::services::ServiceError;
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::url_handlers::urls::url_utils::schedule_link_preview;
+use crate::presentation::handlers::{org_error_response, ConcreteAppState, ValidatedJson};
 use axum::{
     extract::State,
-    http::HeaderMap,
-    http::{header, StatusCode},
+    http::StatusCode,
+    response::{IntoResponse, Response},
     Json,
 };
 use tracing::{info, warn};
 
+/// Map a failed shortening to an HTTP error
+///
+/// Custom short codes of the wrong length report the limit and the actual length.
+fn shorten_error_response(error: &UseCaseError) -> Response {
+    let (code, min_length, max_length, actual_length) = match error {
+        UseCaseError::InvalidShortCode(ShortCodeError::TooShort {
+            min_length,
+            actual_length,
+        }) => (
+            "SHORT_CODE_TOO_SHORT",
+            Some(*min_length),
+            None,
+            *actual_length,
+        ),
+        UseCaseError::InvalidShortCode(ShortCodeError::TooLong {
+            max_length,
+            actual_length,
+        }) => (
+            "SHORT_CODE_TOO_LONG",
+            None,
+            Some(*max_length),
+            *actual_length,
+        ),
+        _ => {
+            let (status, code) = match error {
+                UseCaseError::BlockedDomain(_) => (StatusCode::BAD_REQUEST, "BLOCKED_DOMAIN"),
+                UseCaseError::Service(ServiceError::Repository(
+                    RepositoryError::OrganizationQuotaExceeded { .. },
+                )) => (StatusCode::FORBIDDEN, "ORGANIZATION_QUOTA_EXCEEDED"),
+                _ => (StatusCode::BAD_REQUEST, "SHORTEN_FAILED"),
+            };
+            let error_response = ErrorResponse {
+                error: code.to_string(),
+                message: error.to_string(),
+                status_code: status.as_u16(),
+            };
+            return (status, Json(error_response)).into_response();
+        }
+    };
+
+    let error_response = ShortCodeLengthErrorResponse {
+        error: code.to_string(),
+        message: error.to_string(),
+        status_code: StatusCode::BAD_REQUEST.as_u16(),
+        min_length,
+        max_length,
+        actual_length,
+    };
+    (StatusCode::BAD_REQUEST, Json(error_response)).into_response()
+}
+
 /// Handler for shortening URLs
 #[utoipa::path(
     post,
@@ -17,44 +76,35 @@ use tracing::{info, warn};
     request_body = ShortenUrlRequest,
     responses(
         (status = 201, description = "URL shortened successfully", body = ShortenUrlResponse),
-        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 400, description = "Bad request or blacklisted domain (BLOCKED_DOMAIN)", body = ErrorResponse),
+        (status = 400, description = "Custom short code too short or too long (SHORT_CODE_TOO_SHORT, SHORT_CODE_TOO_LONG)", body = ShortCodeLengthErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended, not an organization member or organization quota reached", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse),
+        (status = 422, description = "Request body breaks a validation rule", body = ValidationErrorResponse),
+        (status = 429, description = "Organization rate limit exceeded", body = ErrorResponse),
     ),
     tag = "url-shortener"
 )]
 pub async fn shorten_url_handler(
     State(app_state): State<ConcreteAppState>,
-    headers: HeaderMap,
-    Json(request): Json<ShortenUrlRequest>,
-) -> Result<(StatusCode, Json<ShortenUrlResponse>), (StatusCode, Json<ErrorResponse>)> {
-    // Require Authorization: Bearer <token>
-    let auth_header = headers
-        .get(header::AUTHORIZATION)
-        .and_then(|v| v.to_str().ok());
-    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
-        Some(t) if !t.is_empty() => t,
-        _ => {
-            let error_response = ErrorResponse {
-                error: "UNAUTHORIZED".to_string(),
-                message: "Missing or invalid Authorization header".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
-        }
-    };
-
-    // Verify token and get user
-    let user = match app_state.auth_service.verify_token(token).await {
-        Ok(u) => u,
-        Err(e) => {
-            warn!("Token verification failed: {}", e);
-            let error_response = ErrorResponse {
-                error: "INVALID_TOKEN".to_string(),
-                message: "Invalid or expired token".to_string(),
-                status_code: StatusCode::UNAUTHORIZED.as_u16(),
-            };
-            return Err((StatusCode::UNAUTHORIZED, Json(error_response)));
+    AuthenticatedUser(user): AuthenticatedUser,
+    ValidatedJson(request): ValidatedJson<ShortenUrlRequest>,
+) -> Result<(StatusCode, Json<ShortenUrlResponse>), Response> {
+    // URLs created for an organization count towards its rate limit and quota
+    if let Some(org_id) = request.organization_id {
+        if let Err(error) = app_state
+            .org_service
+            .authorize_url_creation(org_id, user.id)
+            .await
+        {
+            warn!(
+                "User {} cannot create URLs for organization {}: {}",
+                user.id, org_id, error
+            );
+            return Err(org_error_response(&error).into_response());
         }
-    };
+    }
 
     let user_id = Some(user.id);
     info!(
@@ -72,16 +122,12 @@ pub async fn shorten_url_handler(
                 "Successfully shortened URL: {} -> {}",
                 response.original_url, response.short_url
             );
+            schedule_link_preview(&app_state, &response.short_code);
             Ok((StatusCode::CREATED, Json(response)))
         }
         Err(error) => {
             warn!("Failed to shorten URL: {}", error);
-            let error_response = ErrorResponse {
-                error: "SHORTEN_FAILED".to_string(),
-                message: error.to_string(),
-                status_code: StatusCode::BAD_REQUEST.as_u16(),
-            };
-            Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            Err(shorten_error_response(&error))
         }
     }
 }
@@ -138,6 +184,25 @@ mod tests {
         assert_eq!(error.status_code, 400);
     }
 
+    #[tokio::test]
+    async fn test_short_code_length_error_response() {
+        let response =
+            shorten_error_response(&UseCaseError::InvalidShortCode(ShortCodeError::TooShort {
+                min_length: 4,
+                actual_length: 3,
+            }));
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"], "SHORT_CODE_TOO_SHORT");
+        assert_eq!(json["min_length"], 4);
+        assert_eq!(json["actual_length"], 3);
+        assert!(json.get("max_length").is_none());
+    }
+
     #[test]
     fn test_shorten_response_serialization() {
         use chrono::Utc;
@@ -146,7 +211,10 @@ mod tests {
             original_url: "https://example.com".to_string(),
             short_code: "abc123".to_string(),
             created_at: Utc::now().to_rfc3339(),
+            updated_at: Utc::now().to_rfc3339(),
             expiration_date: None,
+            version: 1,
+            links: std::collections::HashMap::new(),
         };
         let json = serde_json::to_string(&response);
         assert!(json.is_ok());
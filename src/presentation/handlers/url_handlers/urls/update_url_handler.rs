@@ -0,0 +1,320 @@
+use crate::application::dto::{
+    requests::UpdateUrlRequest, responses::UpdateUrlResponse, ErrorResponse,
+};
+use crate::application::use_cases::shorten_url::UseCaseError;
+use crate::domain::repositories::RepositoryError;
+use crate::domain::services::ServiceError;
+use crate::presentation::handlers::url_handlers::urls::url_utils::find_accessible_url;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    http::{header, StatusCode},
+    Json,
+};
+use tracing::{info, warn};
+
+/// Parse the URL version from an `If-Match` header value
+///
+/// Accepts `3`, `"3"` and weak validators such as `W/"3"`.
+fn parse_if_match(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let value = value.strip_prefix("W/").unwrap_or(value);
+    value.trim_matches('"').parse().ok()
+}
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: String,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message,
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+fn version_conflict(
+    expected_version: i64,
+    current_version: i64,
+) -> (StatusCode, Json<ErrorResponse>) {
+    error_response(
+        StatusCode::PRECONDITION_FAILED,
+        "CONFLICT",
+        format!(
+            "URL is at version {}, not {}; reload it and retry",
+            current_version, expected_version
+        ),
+    )
+}
+
+/// Map a failed update to an HTTP error
+fn update_error_response(
+    error: &UseCaseError,
+    expected_version: i64,
+) -> (StatusCode, Json<ErrorResponse>) {
+    match error {
+        UseCaseError::Service(ServiceError::Repository(RepositoryError::ConflictingUpdate {
+            current_version,
+        })) => version_conflict(expected_version, *current_version),
+        UseCaseError::BlockedDomain(_) => {
+            error_response(StatusCode::BAD_REQUEST, "BLOCKED_DOMAIN", error.to_string())
+        }
+        UseCaseError::InvalidShortCode(_)
+        | UseCaseError::Service(ServiceError::InvalidShortCode(_)) => error_response(
+            StatusCode::BAD_REQUEST,
+            "INVALID_SHORT_CODE",
+            error.to_string(),
+        ),
+        UseCaseError::Validation(_) => {
+            error_response(StatusCode::BAD_REQUEST, "INVALID_UPDATE", error.to_string())
+        }
+        UseCaseError::Service(ServiceError::Repository(RepositoryError::NotFound)) => {
+            error_response(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                "URL not found".to_string(),
+            )
+        }
+        UseCaseError::Service(ServiceError::ShortCodeAlreadyExists) => error_response(
+            StatusCode::CONFLICT,
+            "DUPLICATE_SHORT_CODE",
+            "Short code already exists".to_string(),
+        ),
+        _ => {
+            warn!("Failed to update URL: {}", error);
+            error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "UPDATE_FAILED",
+                "Failed to update URL".to_string(),
+            )
+        }
+    }
+}
+
+/// Handler for updating a URL with optimistic concurrency control
+///
+/// Only the fields present in the body are changed; `null` clears the expiration date or
+/// the password. Requires an `If-Match` header with the version last read by the client; the update is
+/// rejected if the URL changed since then. Members of the URL's organization can update it
+/// like its creator.
+#[utoipa::path(
+    patch,
+    path = "/urls/{id}",
+    params(
+        ("id" = i32, Path, description = "URL ID to update"),
+        ("If-Match" = String, Header, description = "Version of the URL the update is based on")
+    ),
+    request_body = UpdateUrlRequest,
+    responses(
+        (status = 200, description = "URL updated successfully", body = ShortenUrlResponse),
+        (status = 400, description = "Invalid destination, short code or expiration date", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+        (status = 409, description = "Short code already exists", body = ErrorResponse),
+        (status = 412, description = "URL was modified since the given version", body = ErrorResponse),
+        (status = 422, description = "Request body breaks a validation rule", body = ValidationErrorResponse),
+        (status = 428, description = "If-Match header required", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn update_url_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    axum::extract::Path(id): axum::extract::Path<i32>,
+    ValidatedJson(request): ValidatedJson<UpdateUrlRequest>,
+) -> Result<
+    (
+        StatusCode,
+        [(header::HeaderName, String); 1],
+        Json<UpdateUrlResponse>,
+    ),
+    (StatusCode, Json<ErrorResponse>),
+> {
+    // Require Authorization: Bearer <token>
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(t) if !t.is_empty() => t,
+        _ => {
+            return Err(error_response(
+                StatusCode::UNAUTHORIZED,
+                "UNAUTHORIZED",
+                "Missing or invalid Authorization header".to_string(),
+            ));
+        }
+    };
+
+    // Verify token and get user
+    let user = match app_state.auth_service.verify_token(token).await {
+        Ok(u) => u,
+        Err(e) => {
+            warn!("Token verification failed: {}", e);
+            return Err(token_error_response(&e));
+        }
+    };
+
+    let expected_version = match headers.get(header::IF_MATCH).and_then(|v| v.to_str().ok()) {
+        Some(value) => match parse_if_match(value) {
+            Some(version) => version,
+            None => {
+                return Err(error_response(
+                    StatusCode::BAD_REQUEST,
+                    "INVALID_IF_MATCH",
+                    "If-Match must contain the URL version".to_string(),
+                ));
+            }
+        },
+        None => {
+            return Err(error_response(
+                StatusCode::PRECONDITION_REQUIRED,
+                "PRECONDITION_REQUIRED",
+                "If-Match header with the URL version is required".to_string(),
+            ));
+        }
+    };
+
+    info!(
+        "Received update URL request for ID: {} (user: {}, version: {})",
+        id, user.id, expected_version
+    );
+
+    let url = find_accessible_url(&app_state, id, user.id).await?;
+    match app_state
+        .update_url_use_case
+        .execute(url, request, expected_version)
+        .await
+    {
+        Ok(response) => {
+            info!("Updated URL {} to version {}", id, response.version);
+            let etag = format!("\"{}\"", response.version);
+            Ok((StatusCode::OK, [(header::ETAG, etag)], Json(response)))
+        }
+        Err(error) => {
+            if let UseCaseError::Service(ServiceError::Repository(
+                RepositoryError::ConflictingUpdate { current_version },
+            )) = &error
+            {
+                warn!(
+                    "Concurrent update of URL {}: expected version {}, found {}",
+                    id, expected_version, current_version
+                );
+            }
+            Err(update_error_response(&error, expected_version))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{OrgRole, ShortCode, UrlStatus};
+    use crate::domain::repositories::UrlRepository;
+    use crate::infrastructure::test_utils::TestApp;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_parse_if_match() {
+        assert_eq!(parse_if_match("3"), Some(3));
+        assert_eq!(parse_if_match("\"12\""), Some(12));
+        assert_eq!(parse_if_match("W/\"7\""), Some(7));
+        assert_eq!(parse_if_match("*"), None);
+        assert_eq!(parse_if_match("abc"), None);
+    }
+
+    #[test]
+    fn test_version_conflict_response() {
+        let (status, Json(body)) = version_conflict(2, 3);
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+        assert_eq!(body.error, "CONFLICT");
+        assert_eq!(body.status_code, 412);
+    }
+
+    #[test]
+    fn test_update_error_responses() {
+        let conflict = UseCaseError::Service(ServiceError::Repository(
+            RepositoryError::ConflictingUpdate { current_version: 4 },
+        ));
+        let (status, Json(body)) = update_error_response(&conflict, 3);
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+        assert_eq!(body.error, "CONFLICT");
+
+        let foreign = UseCaseError::Service(ServiceError::Repository(RepositoryError::NotFound));
+        let (status, _) = update_error_response(&foreign, 3);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let taken = UseCaseError::Service(ServiceError::ShortCodeAlreadyExists);
+        let (status, _) = update_error_response(&taken, 3);
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let past = UseCaseError::Validation("Expiration date must be in the future".to_string());
+        let (status, _) = update_error_response(&past, 3);
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_precondition_required_response() {
+        let (status, Json(body)) = error_response(
+            StatusCode::PRECONDITION_REQUIRED,
+            "PRECONDITION_REQUIRED",
+            "If-Match header with the URL version is required".to_string(),
+        );
+        assert_eq!(status, StatusCode::PRECONDITION_REQUIRED);
+        assert_eq!(body.status_code, 428);
+    }
+
+    #[tokio::test]
+    async fn test_organization_members_can_update_its_urls() {
+        let app = TestApp::new();
+        let (owner, _) = app.sign_up("orgowner").await;
+        let (member, member_token) = app.sign_up("orgmember").await;
+        let (_, outsider_token) = app.sign_up("outsider").await;
+        let org_service = &app.state.org_service;
+        let org = org_service
+            .create_organization(owner.id, "Acme", None)
+            .await
+            .unwrap();
+        org_service
+            .add_member(org.id, owner.id, member.id, OrgRole::Member)
+            .await
+            .unwrap();
+        let url = app
+            .url_repository
+            .create_url(
+                &ShortCode::new("orgurl1".to_string()).unwrap(),
+                "https://example.com/acme",
+                None,
+                Some(owner.id),
+                Some(org.id),
+                UrlStatus::Active,
+            )
+            .await
+            .unwrap();
+
+        let update = |token: &str| {
+            Request::patch(format!("/urls/{}", url.id))
+                .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::IF_MATCH, "\"1\"")
+                .body(Body::from(r#"{"title":"Launch"}"#))
+                .unwrap()
+        };
+        let response = app.router().oneshot(update(&outsider_token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = app.router().oneshot(update(&member_token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let url = app
+            .url_repository
+            .find_by_id(url.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(url.title.as_deref(), Some("Launch"));
+    }
+}
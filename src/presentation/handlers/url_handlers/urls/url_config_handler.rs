@@ -0,0 +1,208 @@
+use crate::application::dto::{
+    requests::UpdateUrlConfigRequest, responses::UrlConfigResponse, ErrorResponse,
+};
+use crate::domain::entities::{Url, UrlConfig};
+use crate::domain::services::click_tracking_service::ClickTrackingError;
+use crate::presentation::handlers::{token_error_response, ConcreteAppState, ValidatedJson};
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use std::time::Duration;
+use tracing::{info, warn};
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: &str,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message: message.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Load a URL owned by the user behind the request's bearer token
+async fn find_owned_url(
+    app_state: &ConcreteAppState,
+    headers: &HeaderMap,
+    id: i32,
+) -> Result<Url, (StatusCode, Json<ErrorResponse>)> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::UNAUTHORIZED,
+                "UNAUTHORIZED",
+                "Missing or invalid Authorization header",
+            )
+        })?;
+
+    let user = app_state
+        .auth_service
+        .verify_token(token)
+        .await
+        .map_err(|e| {
+            warn!("Token verification failed: {}", e);
+            token_error_response(&e)
+        })?;
+
+    match app_state.url_service.get_url_by_id(id).await {
+        Ok(Some(url)) if url.user_id == Some(user.id) => Ok(url),
+        Ok(_) => Err(error_response(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            "URL not found or you don't have permission to access it",
+        )),
+        Err(error) => {
+            warn!("Failed to load URL {}: {}", id, error);
+            Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                "Failed to load URL",
+            ))
+        }
+    }
+}
+
+fn config_response(config: UrlConfig, default_dedup_window: Duration) -> UrlConfigResponse {
+    UrlConfigResponse {
+        url_id: config.url_id,
+        effective_click_dedup_window_seconds: config
+            .click_dedup_window_seconds
+            .map_or(default_dedup_window.as_secs(), |seconds| {
+                seconds.max(0) as u64
+            }),
+        click_dedup_window_seconds: config.click_dedup_window_seconds,
+    }
+}
+
+/// Handler returning the click tracking settings of a URL
+#[utoipa::path(
+    get,
+    path = "/urls/{id}/config",
+    params(
+        ("id" = i32, Path, description = "URL ID")
+    ),
+    responses(
+        (status = 200, description = "URL settings", body = UrlConfigResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn get_url_config_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<Json<UrlConfigResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let url = find_owned_url(&app_state, &headers, id).await?;
+    let click_tracking = &app_state.click_tracking_service;
+
+    match click_tracking.get_url_config(url.id).await {
+        Ok(config) => Ok(Json(config_response(
+            config,
+            click_tracking.default_click_dedup_window(),
+        ))),
+        Err(error) => {
+            warn!("Failed to load settings of URL {}: {}", id, error);
+            Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                "Failed to load URL settings",
+            ))
+        }
+    }
+}
+
+/// Handler changing the click tracking settings of a URL
+#[utoipa::path(
+    put,
+    path = "/urls/{id}/config",
+    params(
+        ("id" = i32, Path, description = "URL ID")
+    ),
+    request_body = UpdateUrlConfigRequest,
+    responses(
+        (status = 200, description = "URL settings updated", body = UrlConfigResponse),
+        (status = 400, description = "Deduplication window out of range", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn update_url_config_handler(
+    State(app_state): State<ConcreteAppState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    ValidatedJson(request): ValidatedJson<UpdateUrlConfigRequest>,
+) -> Result<Json<UrlConfigResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let url = find_owned_url(&app_state, &headers, id).await?;
+    let click_tracking = &app_state.click_tracking_service;
+
+    match click_tracking
+        .set_click_dedup_window(url.id, request.click_dedup_window_seconds)
+        .await
+    {
+        Ok(config) => {
+            info!(
+                "Set click deduplication window of URL {} to {:?}",
+                id, config.click_dedup_window_seconds
+            );
+            Ok(Json(config_response(
+                config,
+                click_tracking.default_click_dedup_window(),
+            )))
+        }
+        Err(ClickTrackingError::InvalidData(message)) => Err(error_response(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_ERROR",
+            &message,
+        )),
+        Err(error) => {
+            warn!("Failed to update settings of URL {}: {}", id, error);
+            Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "UPDATE_FAILED",
+                "Failed to update URL settings",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_response_falls_back_to_default_window() {
+        let default_window = Duration::from_secs(60);
+
+        let response = config_response(UrlConfig::default_for(3), default_window);
+        assert_eq!(response.click_dedup_window_seconds, None);
+        assert_eq!(response.effective_click_dedup_window_seconds, 60);
+
+        let config = UrlConfig {
+            click_dedup_window_seconds: Some(0),
+            ..UrlConfig::default_for(3)
+        };
+        let response = config_response(config, default_window);
+        assert_eq!(response.click_dedup_window_seconds, Some(0));
+        assert_eq!(response.effective_click_dedup_window_seconds, 0);
+    }
+
+    #[test]
+    fn test_update_request_requires_window_field() {
+        let request: UpdateUrlConfigRequest =
+            serde_json::from_str(r#"{"click_dedup_window_seconds": null}"#).unwrap();
+        assert_eq!(request.click_dedup_window_seconds, None);
+
+        assert!(serde_json::from_str::<UpdateUrlConfigRequest>("{}").is_err());
+    }
+}
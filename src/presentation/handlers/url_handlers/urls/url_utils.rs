@@ -0,0 +1,266 @@
+use crate::application::dto::requests::ShortenUrlRequest;
+use crate::application::dto::responses::{LinkPreviewResponse, UrlDetailResponse, UrlInfoResponse};
+use crate::application::dto::ErrorResponse;
+use crate::domain::entities::{ShortCode, Url, UrlMetadata};
+use crate::domain::repositories::RepositoryError;
+use crate::domain::services::ServiceError;
+use crate::presentation::handlers::{org_error_response, ConcreteAppState};
+use axum::{http::StatusCode, Json};
+use std::collections::HashMap;
+
+/// Convert a Url entity to UrlInfoResponse
+pub fn url_to_info_response(url: Url, base_url: &str, click_count: Option<i64>) -> UrlInfoResponse {
+    UrlInfoResponse {
+        id: url.id,
+        short_url: url.short_url(base_url),
+        is_expired: url.is_expired(),
+        short_code: url.short_code,
+        original_url: url.original_url,
+        created_at: url.created_at.to_rfc3339(),
+        updated_at: url.updated_at.to_rfc3339(),
+        expiration_date: url.expiration_date.map(|d| d.to_rfc3339()),
+        click_count,
+        version: url.version,
+    }
+}
+
+/// Convert a Url entity and its recorded click count to UrlDetailResponse
+pub fn url_to_detail_response(
+    url: Url,
+    base_url: &str,
+    click_count: i64,
+    links: HashMap<String, String>,
+) -> UrlDetailResponse {
+    UrlDetailResponse {
+        id: url.id,
+        short_url: url.short_url(base_url),
+        is_expired: url.is_expired(),
+        status: url.status.to_string(),
+        preview_mode: url.preview_mode.as_str().to_string(),
+        short_code: url.short_code,
+        original_url: url.original_url,
+        created_at: url.created_at.to_rfc3339(),
+        updated_at: url.updated_at.to_rfc3339(),
+        expiration_date: url.expiration_date.map(|d| d.to_rfc3339()),
+        user_id: url.user_id,
+        organization_id: url.organization_id,
+        click_count,
+        deduplicated_click_count: url.deduplicated_click_count,
+        version: url.version,
+        links,
+    }
+}
+
+/// Convert a Url and its fetched metadata to LinkPreviewResponse
+pub fn url_to_preview_response(
+    url: Url,
+    metadata: Option<UrlMetadata>,
+    base_url: &str,
+) -> LinkPreviewResponse {
+    let short_url = url.short_url(base_url);
+    let metadata = metadata.as_ref();
+    LinkPreviewResponse {
+        short_url,
+        short_code: url.short_code,
+        original_url: url.original_url,
+        title: metadata.and_then(|m| m.title.clone()),
+        description: metadata.and_then(|m| m.description.clone()),
+        image_url: metadata.and_then(|m| m.image_url.clone()),
+        favicon_url: metadata.and_then(|m| m.favicon_url.clone()),
+        fetched_at: metadata.map(|m| m.fetched_at.to_rfc3339()),
+    }
+}
+
+/// Map a failed short code lookup to an HTTP error
+///
+/// A lookup cancelled by the statement timeout is reported as 503 so load balancers retry
+/// on another instance; other failures are 500.
+pub fn short_code_lookup_error_response(error: &ServiceError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, code, message) = match error {
+        ServiceError::Repository(RepositoryError::QueryTimeout) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "SERVICE_UNAVAILABLE",
+            "Lookup timed out, please retry",
+        ),
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            "Internal server error",
+        ),
+    };
+    let error_response = ErrorResponse {
+        error: code.to_string(),
+        message: message.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+/// Load a URL the user may manage: one they created or one of an organization they belong to
+///
+/// URLs the user cannot access are reported as not found.
+pub async fn find_accessible_url(
+    app_state: &ConcreteAppState,
+    url_id: i32,
+    user_id: i32,
+) -> Result<Url, (StatusCode, Json<ErrorResponse>)> {
+    let url = match app_state.url_service.get_url_by_id(url_id).await {
+        Ok(url) => url,
+        Err(e) => {
+            tracing::warn!("Failed to load URL {}: {}", url_id, e);
+            return Err(internal_error_response());
+        }
+    };
+    let Some(url) = url else {
+        return Err(url_not_found_response());
+    };
+    match app_state.org_service.can_access_url(user_id, &url).await {
+        Ok(true) => Ok(url),
+        Ok(false) => Err(url_not_found_response()),
+        Err(e) => {
+            tracing::warn!(
+                "Failed to check access of user {} to URL {}: {}",
+                user_id,
+                url_id,
+                e
+            );
+            Err(internal_error_response())
+        }
+    }
+}
+
+/// Check that the user may create the URLs of `requests` for their organizations
+///
+/// Every request for an organization counts towards its rate limit.
+pub async fn authorize_organization_urls(
+    app_state: &ConcreteAppState,
+    requests: &[ShortenUrlRequest],
+    user_id: i32,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    for org_id in requests
+        .iter()
+        .filter_map(|request| request.organization_id)
+    {
+        if let Err(error) = app_state
+            .org_service
+            .authorize_url_creation(org_id, user_id)
+            .await
+        {
+            tracing::warn!(
+                "User {} cannot create URLs for organization {}: {}",
+                user_id,
+                org_id,
+                error
+            );
+            return Err(org_error_response(&error));
+        }
+    }
+    Ok(())
+}
+
+fn url_not_found_response() -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: "NOT_FOUND".to_string(),
+        message: "URL not found".to_string(),
+        status_code: StatusCode::NOT_FOUND.as_u16(),
+    };
+    (StatusCode::NOT_FOUND, Json(error_response))
+}
+
+fn internal_error_response() -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: "INTERNAL_ERROR".to_string(),
+        message: "Internal server error".to_string(),
+        status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+    };
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response))
+}
+
+/// Fetch the link preview metadata of a newly created URL in the background
+///
+/// Failures are only logged: a URL without a preview works like any other.
+pub fn schedule_link_preview(app_state: &ConcreteAppState, short_code: &str) {
+    let Ok(short_code) = ShortCode::new(short_code.to_string()) else {
+        return;
+    };
+    let url_service = app_state.url_service.clone();
+    let link_preview_service = app_state.link_preview_service.clone();
+    tokio::spawn(async move {
+        let url = match url_service.get_url_by_short_code(&short_code).await {
+            Ok(Some(url)) => url,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load URL {} for preview: {}",
+                    short_code.value(),
+                    e
+                );
+                return;
+            }
+        };
+        match link_preview_service
+            .fetch_and_store(url.id, &url.original_url)
+            .await
+        {
+            Ok(_) => tracing::debug!("Stored link preview of URL {}", url.id),
+            Err(e) => tracing::info!("No link preview for URL {}: {}", url.id, e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::UrlStatus;
+
+    #[test]
+    fn test_lookup_timeout_is_service_unavailable() {
+        let error = ServiceError::Repository(RepositoryError::QueryTimeout);
+        let (status, Json(body)) = short_code_lookup_error_response(&error);
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body.status_code, 503);
+
+        let error = ServiceError::Repository(RepositoryError::NotFound);
+        let (status, _) = short_code_lookup_error_response(&error);
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn test_url_to_info_response() {
+        let url = Url::new_with_timestamp(
+            7,
+            "abc123".to_string(),
+            "https://example.com".to_string(),
+            None,
+            Some(1),
+            UrlStatus::Active,
+        );
+
+        let response = url_to_info_response(url, "http://localhost:8000/", Some(42));
+        assert_eq!(response.id, 7);
+        assert_eq!(response.short_url, "http://localhost:8000/abc123");
+        assert_eq!(response.click_count, Some(42));
+        assert!(!response.is_expired);
+    }
+
+    #[test]
+    fn test_url_to_detail_response() {
+        let url = Url::new_with_timestamp(
+            7,
+            "abc123".to_string(),
+            "https://example.com".to_string(),
+            None,
+            Some(1),
+            UrlStatus::Inactive,
+        );
+
+        let links = HashMap::from([("self".to_string(), "/urls/7".to_string())]);
+        let response = url_to_detail_response(url, "http://localhost:8000/", 42, links);
+        assert_eq!(response.short_url, "http://localhost:8000/abc123");
+        assert_eq!(response.status, "inactive");
+        assert_eq!(response.preview_mode, "none");
+        assert_eq!(response.user_id, Some(1));
+        assert_eq!(response.click_count, 42);
+        assert_eq!(response.links["self"], "/urls/7");
+    }
+}
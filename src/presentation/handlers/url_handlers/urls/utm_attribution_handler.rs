@@ -0,0 +1,109 @@
+use crate::application::dto::{
+    responses::{UtmAttributionEntry, UtmAttributionResponse},
+    ErrorResponse,
+};
+use crate::domain::repositories::UtmAttributionRow;
+use crate::infrastructure::http::AuthenticatedUser;
+use crate::presentation::handlers::url_handlers::urls::url_utils::find_accessible_url;
+use crate::presentation::handlers::ConcreteAppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use tracing::warn;
+
+fn error_response(
+    status: StatusCode,
+    error: &str,
+    message: &str,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: error.to_string(),
+        message: message.to_string(),
+        status_code: status.as_u16(),
+    };
+    (status, Json(error_response))
+}
+
+fn breakdown_to_response(url_id: i32, rows: Vec<UtmAttributionRow>) -> UtmAttributionResponse {
+    UtmAttributionResponse {
+        url_id,
+        attributions: rows
+            .into_iter()
+            .map(|row| UtmAttributionEntry {
+                source: row.source,
+                medium: row.medium,
+                campaign: row.campaign,
+                count: row.count,
+            })
+            .collect(),
+    }
+}
+
+/// Handler for the clicks by UTM campaign of a URL the authenticated user can access
+///
+/// Clicks carry the UTM parameters of the destination URL or, failing that, of the page
+/// the visitor came from. Clicks without any are left out.
+#[utoipa::path(
+    get,
+    path = "/urls/{id}/analytics/utm",
+    params(
+        ("id" = i32, Path, description = "URL ID")
+    ),
+    responses(
+        (status = 200, description = "Clicks by UTM source, medium and campaign", body = UtmAttributionResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Account suspended", body = ErrorResponse),
+        (status = 404, description = "URL not found", body = ErrorResponse),
+    ),
+    tag = "url-management"
+)]
+pub async fn get_utm_attribution_handler(
+    State(app_state): State<ConcreteAppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<i32>,
+) -> Result<Json<UtmAttributionResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let url = find_accessible_url(&app_state, id, user.id).await?;
+
+    match app_state
+        .click_tracking_service
+        .get_utm_attribution_breakdown(url.id)
+        .await
+    {
+        Ok(rows) => Ok(Json(breakdown_to_response(url.id, rows))),
+        Err(error) => {
+            warn!("Failed to load UTM attribution of URL {}: {}", id, error);
+            Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "ANALYTICS_ERROR",
+                "Failed to load URL analytics",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakdown_to_response() {
+        let rows = vec![UtmAttributionRow {
+            source: Some("newsletter".to_string()),
+            medium: Some("email".to_string()),
+            campaign: None,
+            count: 4,
+        }];
+
+        let response = breakdown_to_response(7, rows);
+        assert_eq!(response.url_id, 7);
+        assert_eq!(response.attributions.len(), 1);
+        assert_eq!(
+            response.attributions[0].source.as_deref(),
+            Some("newsletter")
+        );
+        assert_eq!(response.attributions[0].campaign, None);
+        assert_eq!(response.attributions[0].count, 4);
+    }
+}
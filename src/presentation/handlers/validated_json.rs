@@ -0,0 +1,201 @@
+use crate::application::dto::responses::{FieldErrorResponse, ValidationErrorResponse};
+use axum::{
+    async_trait,
+    extract::{FromRequest, Request},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationErrors, ValidationErrorsKind};
+
+/// JSON request body that passed its `Validate` rules
+///
+/// Bodies that cannot be parsed are rejected like with [`Json`]; bodies breaking a rule get a
+/// `422 VALIDATION_ERROR` listing every failed field.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        value
+            .validate()
+            .map_err(|errors| validation_error_response(&errors).into_response())?;
+        Ok(Self(value))
+    }
+}
+
+/// Map failed rules to the `422` response sent to clients
+pub fn validation_error_response(
+    errors: &ValidationErrors,
+) -> (StatusCode, Json<ValidationErrorResponse>) {
+    let error_response = ValidationErrorResponse {
+        error: "VALIDATION_ERROR".to_string(),
+        message: "Request validation failed".to_string(),
+        status_code: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+        errors: field_errors(errors),
+    };
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error_response))
+}
+
+/// Flatten failed rules, nested ones included, into one entry per message sorted by field
+pub fn field_errors(errors: &ValidationErrors) -> Vec<FieldErrorResponse> {
+    let mut field_errors = Vec::new();
+    collect_field_errors(errors, "", &mut field_errors);
+    field_errors.sort_by(|a, b| a.field.cmp(&b.field));
+    field_errors
+}
+
+fn collect_field_errors(
+    errors: &ValidationErrors,
+    prefix: &str,
+    field_errors: &mut Vec<FieldErrorResponse>,
+) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                field_errors.extend(errors.iter().map(|error| {
+                    FieldErrorResponse {
+                        field: path.clone(),
+                        message: error
+                            .message
+                            .as_deref()
+                            .map_or_else(|| format!("is invalid ({})", error.code), str::to_string),
+                    }
+                }));
+            }
+            ValidationErrorsKind::Struct(errors) => {
+                collect_field_errors(errors, &path, field_errors);
+            }
+            ValidationErrorsKind::List(items) => {
+                for (index, errors) in items {
+                    collect_field_errors(errors, &format!("{}[{}]", path, index), field_errors);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::dto::requests::{BulkShortenUrlsRequest, ShortenUrlRequest};
+    use axum::{body::Body, routing::post, Router};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/shorten",
+                post(
+                    |ValidatedJson(request): ValidatedJson<ShortenUrlRequest>| async move {
+                        request.url
+                    },
+                ),
+            )
+            .route(
+                "/bulk",
+                post(
+                    |ValidatedJson(request): ValidatedJson<BulkShortenUrlsRequest>| async move {
+                        request.items.len().to_string()
+                    },
+                ),
+            )
+    }
+
+    async fn post_json(uri: &str, body: Value) -> (StatusCode, Vec<u8>) {
+        let request = Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, body.to_vec())
+    }
+
+    #[tokio::test]
+    async fn test_valid_body_reaches_handler() {
+        let (status, body) = post_json("/shorten", json!({ "url": "https://example.com" })).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, b"https://example.com");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_body_lists_failed_fields() {
+        let (status, body) = post_json(
+            "/shorten",
+            json!({ "url": "example", "custom_short_code": "a b" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response: ValidationErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response.error, "VALIDATION_ERROR");
+        assert_eq!(response.status_code, 422);
+        let fields: Vec<_> = response
+            .errors
+            .iter()
+            .map(|e| (e.field.as_str(), e.message.as_str()))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("custom_short_code", "must be between 4 and 50 characters"),
+                (
+                    "custom_short_code",
+                    "must contain only letters, digits, '-' and '_'"
+                ),
+                ("url", "must be a valid URL"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_nested_errors_report_item_path() {
+        let (status, body) = post_json(
+            "/bulk",
+            json!({ "items": [{ "url": "https://example.com" }, { "url": "nope" }] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response: ValidationErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(response.errors[0].field, "items[1].url");
+    }
+
+    #[tokio::test]
+    async fn test_malformed_body_rejected_like_json() {
+        let (status, _) = post_json("/shorten", json!({ "custom_short_code": "abcd" })).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/shorten")
+            .body(Body::from(r#"{"url":"https://example.com"}"#))
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+}
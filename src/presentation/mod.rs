@@ -1,3 +1,4 @@
+pub mod graphql;
 pub mod handlers;
 
 pub use handlers::*;
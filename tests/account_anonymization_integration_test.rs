@@ -0,0 +1,78 @@
+use url_shortner::domain::entities::{AccountStatus, ProfilePrivacy, SessionClient};
+use url_shortner::domain::repositories::user_repository::RepositoryError;
+use url_shortner::domain::repositories::UserRepository;
+use url_shortner::domain::services::{AuthService, AuthServiceError};
+use url_shortner::infrastructure::test_utils::MockUserRepository;
+
+/// Integration test for the right to erasure
+/// Covers that no personal data remains on the user and that the account is unusable
+#[tokio::test]
+async fn test_anonymized_user_keeps_no_personal_data() {
+    let user_repository = MockUserRepository::new();
+    let auth_service = AuthService::new(user_repository.clone(), "test-secret".to_string());
+
+    let user = auth_service
+        .register("alice", "alice@example.com", "password123")
+        .await
+        .unwrap();
+    user_repository
+        .update_profile(
+            user.id,
+            Some("Alice"),
+            Some("Liddell"),
+            Some("Down the rabbit hole"),
+            Some("https://example.com/alice.png"),
+            Some("https://alice.example.com"),
+            Some("Oxford"),
+            Some(ProfilePrivacy::Public),
+        )
+        .await
+        .unwrap();
+
+    user_repository.anonymize_user(user.id).await.unwrap();
+
+    let erased = user_repository.find_by_id(user.id).await.unwrap().unwrap();
+    assert_eq!(erased.username, format!("deleted-{}", user.id));
+    assert_eq!(
+        erased.email,
+        format!("deleted-{}@anonymized.invalid", user.id)
+    );
+    assert!(erased.password_hash.is_empty());
+    assert_eq!(erased.first_name, None);
+    assert_eq!(erased.last_name, None);
+    assert_eq!(erased.bio, None);
+    assert_eq!(erased.avatar_url, None);
+    assert_eq!(erased.website, None);
+    assert_eq!(erased.location, None);
+    assert_eq!(erased.privacy, ProfilePrivacy::Private);
+    assert_eq!(erased.account_status, AccountStatus::Deactivated);
+
+    // Nothing that identified the user can still be found or used to log in
+    let serialized = serde_json::to_string(&erased).unwrap();
+    for pii in ["alice", "Alice", "Liddell", "rabbit", "Oxford"] {
+        assert!(!serialized.contains(pii), "{} survived anonymization", pii);
+    }
+    assert!(user_repository
+        .find_by_email("alice@example.com")
+        .await
+        .unwrap()
+        .is_none());
+    assert!(matches!(
+        auth_service
+            .login("alice", "password123", &SessionClient::default())
+            .await,
+        Err(AuthServiceError::InvalidCredentials)
+    ));
+    assert!(matches!(
+        auth_service
+            .login(&erased.username, "", &SessionClient::default())
+            .await,
+        Err(AuthServiceError::InvalidCredentials)
+    ));
+
+    // Unknown users are reported as such
+    assert!(matches!(
+        user_repository.anonymize_user(9999).await,
+        Err(RepositoryError::NotFound)
+    ));
+}
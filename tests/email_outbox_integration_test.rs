@@ -0,0 +1,85 @@
+//! Checks, against a real database, that emails are queued in the transaction of their change
+//!
+//! Needs a database created from init.sql:
+//! `TEST_DATABASE_URL=postgres://... cargo test --test email_outbox_integration_test -- --ignored`
+
+use chrono::{Duration, Utc};
+use url_shortner::domain::repositories::{NotificationPreferencesRepository, UserRepository};
+use url_shortner::infrastructure::database::{
+    PostgresNotificationPreferencesRepository, PostgresUserRepository,
+};
+use url_shortner::infrastructure::email::EmailMessage;
+
+async fn pool() -> sqlx::PgPool {
+    let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+    sqlx::PgPool::connect(&url).await.unwrap()
+}
+
+/// Outbox emails queued for a recipient
+async fn queued_for(pool: &sqlx::PgPool, recipient: &str) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM email_outbox WHERE recipient = $1")
+        .bind(recipient)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+/// Name unique to this run, so the test can run again on the same database
+fn unique(prefix: &str) -> String {
+    format!("{}{}", prefix, Utc::now().timestamp_micros())
+}
+
+fn message(to: &str) -> EmailMessage {
+    EmailMessage::new_with_html(
+        to.to_string(),
+        "Subject".to_string(),
+        "Text".to_string(),
+        "<p>HTML</p>".to_string(),
+    )
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_rolled_back_registration_leaves_no_outbox_email() {
+    let pool = pool().await;
+    let repository = PostgresUserRepository::new(pool.clone());
+    let username = unique("outbox");
+
+    let first = format!("{}@example.com", username);
+    repository
+        .create_unverified_user(&username, &first, "hash", &message(&first))
+        .await
+        .unwrap();
+    assert_eq!(queued_for(&pool, &first).await, 1);
+
+    // The welcome email is queued before the insert fails on the taken username
+    let second = format!("{}-second@example.com", username);
+    assert!(repository
+        .create_unverified_user(&username, &second, "hash", &message(&second))
+        .await
+        .is_err());
+    assert_eq!(queued_for(&pool, &second).await, 0);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_digest_is_queued_only_when_claimed() {
+    let pool = pool().await;
+    let users = PostgresUserRepository::new(pool.clone());
+    let username = unique("digest");
+    let email = format!("{}@example.com", username);
+    let user = users.create_user(&username, &email, "hash").await.unwrap();
+    let repository = PostgresNotificationPreferencesRepository::new(pool.clone());
+
+    let now = Utc::now();
+    let week_ago = now - Duration::days(7);
+    assert!(repository
+        .claim_digest(user.id, now, week_ago, &message(&email))
+        .await
+        .unwrap());
+    assert!(!repository
+        .claim_digest(user.id, now, week_ago, &message(&email))
+        .await
+        .unwrap());
+    assert_eq!(queued_for(&pool, &email).await, 1);
+}
@@ -0,0 +1,60 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use std::time::Duration;
+use tower::ServiceExt;
+use url_shortner::infrastructure::config::Environment;
+use url_shortner::infrastructure::error_tracking::client_options;
+use url_shortner::infrastructure::http::middleware::error_middleware::sentry_error_middleware;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const ENVELOPE_PATH: &str = "/api/42/envelope/";
+
+/// The Sentry client is global, so every scenario lives in this single test
+#[tokio::test(flavor = "multi_thread")]
+async fn test_server_errors_are_sent_to_sentry() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(ENVELOPE_PATH))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let dsn = format!("http://public@{}/42", server.address());
+    let _guard = sentry::init(client_options(&dsn, &Environment::Test));
+
+    let app = Router::new()
+        .route(
+            "/broken/:id",
+            get(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+        )
+        .route("/invalid", get(|| async { StatusCode::BAD_REQUEST }))
+        .layer(middleware::from_fn(sentry_error_middleware));
+
+    for uri in ["/broken/secret-token", "/invalid"] {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        app.clone().oneshot(request).await.unwrap();
+    }
+    assert!(sentry::Hub::main()
+        .client()
+        .unwrap()
+        .flush(Some(Duration::from_secs(5))));
+
+    // Only the 500 is reported, under its route rather than the path holding the token
+    let requests = server.received_requests().await.unwrap();
+    let envelopes: Vec<String> = requests
+        .iter()
+        .filter(|request| request.url.path() == ENVELOPE_PATH)
+        .map(|request| String::from_utf8_lossy(&request.body).into_owned())
+        .collect();
+    assert_eq!(envelopes.len(), 1);
+    assert!(envelopes[0].contains("GET /broken/:id responded with 500 Internal Server Error"));
+    assert!(!envelopes[0].contains("secret-token"));
+    assert!(envelopes[0].contains(r#""environment":"test""#));
+    assert!(envelopes[0].contains(env!("CARGO_PKG_VERSION")));
+}
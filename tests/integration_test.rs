@@ -1,12 +1,14 @@
 use chrono::Utc;
+use std::collections::HashMap;
 use url_shortner::application::dto::{requests::ShortenUrlRequest, responses::ShortenUrlResponse};
-use url_shortner::domain::services::UrlService;
+use url_shortner::domain::services::{HashStrategy, UrlService};
 use url_shortner::infrastructure::test_utils::MockUrlRepository;
 
-// Helper function to generate short codes using the new architecture
+// Helper function to generate short codes using the new architecture, with the
+// deterministic hash strategy
 async fn generate_short_code(url: &str) -> String {
     let mock_repo = MockUrlRepository::new();
-    let url_service = UrlService::new(mock_repo);
+    let url_service = UrlService::new(mock_repo).with_short_code_strategy(Box::new(HashStrategy));
     url_service
         .generate_short_code(url)
         .await
@@ -51,6 +53,7 @@ async fn test_url_shortener_integration() {
             url: url.to_string(),
             custom_short_code: None,
             expiration_date: None,
+            organization_id: None,
         };
         assert_eq!(request.url, url);
 
@@ -62,7 +65,10 @@ async fn test_url_shortener_integration() {
             original_url: url.to_string(),
             short_code: short_code.clone(),
             created_at: now.to_rfc3339(),
+            updated_at: now.to_rfc3339(),
             expiration_date: None,
+            version: 1,
+            links: HashMap::new(),
         };
         assert_eq!(response.short_url, short_url);
         assert_eq!(response.original_url, url);
@@ -190,6 +196,7 @@ async fn test_url_shortener_api_contract() {
         url: test_url.to_string(),
         custom_short_code: None,
         expiration_date: None,
+        organization_id: None,
     };
     let request_json = serde_json::to_string(&request).unwrap();
     assert!(request_json.contains("url"));
@@ -202,7 +209,10 @@ async fn test_url_shortener_api_contract() {
         original_url: test_url.to_string(),
         short_code: short_code.clone(),
         created_at: Utc::now().to_rfc3339(),
+        updated_at: Utc::now().to_rfc3339(),
         expiration_date: None,
+        version: 1,
+        links: HashMap::new(),
     };
     let response_json = serde_json::to_string(&response).unwrap();
     assert!(response_json.contains("short_url"));
@@ -241,6 +251,7 @@ async fn test_url_shortener_data_integrity() {
         url: original_url.to_string(),
         custom_short_code: None,
         expiration_date: None,
+        organization_id: None,
     };
 
     // Create response
@@ -249,7 +260,10 @@ async fn test_url_shortener_data_integrity() {
         original_url: original_url.to_string(),
         short_code: short_code.clone(),
         created_at: Utc::now().to_rfc3339(),
+        updated_at: Utc::now().to_rfc3339(),
         expiration_date: None,
+        version: 1,
+        links: HashMap::new(),
     };
 
     // Test data integrity
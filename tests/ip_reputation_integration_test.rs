@@ -0,0 +1,133 @@
+use std::sync::Arc;
+use std::time::Duration;
+use url_shortner::domain::entities::audit_log_entry::SUSPICIOUS_IP_BLOCK_ACTION;
+use url_shortner::infrastructure::config::AbuseIpDbConfig;
+use url_shortner::infrastructure::rate_limiting::{IpReputationService, IpVerdict};
+use url_shortner::infrastructure::test_utils::{MockAuditLogRepository, MockIpReputationCache};
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const BAD_IP: &str = "185.220.101.1";
+const GOOD_IP: &str = "8.8.8.8";
+
+fn service_for(server: &MockServer, cache: &MockIpReputationCache) -> IpReputationService {
+    let config = AbuseIpDbConfig {
+        api_key: "test-key".to_string(),
+        score_threshold: 50,
+        enabled: true,
+    };
+    IpReputationService::new(&config, Arc::new(cache.clone())).with_base_url(&server.uri())
+}
+
+async fn mount_score(server: &MockServer, ip: &str, score: u8) {
+    Mock::given(method("GET"))
+        .and(path("/api/v2/check"))
+        .and(query_param("ipAddress", ip))
+        .and(header("Key", "test-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "ipAddress": ip, "abuseConfidenceScore": score }
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+/// Integration test for scoring redirecting IPs with AbuseIPDB
+/// Covers high and low scores and reusing cached scores, clean ones included
+#[tokio::test]
+async fn test_high_and_low_scores_are_cached() {
+    let server = MockServer::start().await;
+    mount_score(&server, BAD_IP, 100).await;
+    mount_score(&server, GOOD_IP, 0).await;
+    let cache = MockIpReputationCache::new();
+    let service = service_for(&server, &cache);
+
+    // 1. An IP over the threshold is suspicious, one at 0 is trusted
+    for _ in 0..2 {
+        assert_eq!(
+            service.check(BAD_IP.parse().unwrap(), 1).await,
+            IpVerdict::Suspicious { score: 100 }
+        );
+        assert_eq!(
+            service.check(GOOD_IP.parse().unwrap(), 1).await,
+            IpVerdict::Trusted
+        );
+    }
+
+    // 2. Both scores were cached, so each IP was only looked up once
+    let scores = cache.scores.lock().unwrap();
+    assert_eq!(scores.get(BAD_IP).map(|(score, _)| *score), Some(100));
+    assert_eq!(scores.get(GOOD_IP).map(|(score, _)| *score), Some(0));
+}
+
+#[tokio::test]
+async fn test_blocked_ips_are_audited() {
+    let server = MockServer::start().await;
+    mount_score(&server, BAD_IP, 90).await;
+    let audit_log = MockAuditLogRepository::new();
+    let service = service_for(&server, &MockIpReputationCache::new())
+        .with_block_suspicious_ips(true)
+        .with_audit_log(Arc::new(audit_log.clone()));
+
+    assert_eq!(
+        service.check(BAD_IP.parse().unwrap(), 7).await,
+        IpVerdict::Blocked { score: 90 }
+    );
+
+    let entries = audit_log.entries.lock().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].action, SUSPICIOUS_IP_BLOCK_ACTION);
+    assert_eq!(entries[0].entity_id, 7);
+    assert_eq!(entries[0].details["ip_address"], BAD_IP);
+    assert_eq!(entries[0].details["abuse_score"], 90);
+}
+
+#[tokio::test]
+async fn test_failed_lookups_fail_open() {
+    let server = MockServer::start().await;
+    let cache = MockIpReputationCache::new();
+    let service = service_for(&server, &cache);
+
+    // An API error trusts the IP without caching anything
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    assert_eq!(
+        service.check(BAD_IP.parse().unwrap(), 1).await,
+        IpVerdict::Trusted
+    );
+    assert!(cache.scores.lock().unwrap().is_empty());
+
+    // So does an API slower than the timeout
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "data": { "abuseConfidenceScore": 100 } }))
+                .set_delay(Duration::from_secs(5)),
+        )
+        .mount(&server)
+        .await;
+    let started = std::time::Instant::now();
+    assert_eq!(
+        service.check(BAD_IP.parse().unwrap(), 1).await,
+        IpVerdict::Trusted
+    );
+    assert!(started.elapsed() < Duration::from_secs(3));
+}
+
+#[tokio::test]
+async fn test_private_ips_are_not_looked_up() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+    let service = service_for(&server, &MockIpReputationCache::new());
+
+    for ip in ["10.0.0.1", "127.0.0.1", "::1"] {
+        assert_eq!(service.score(ip.parse().unwrap()).await, None);
+    }
+}
@@ -0,0 +1,56 @@
+use url_shortner::domain::entities::SessionClient;
+use url_shortner::domain::services::{AuthService, MagicLinkError, MagicLinkService};
+use url_shortner::infrastructure::test_utils::{MockMagicLinkRepository, MockUserRepository};
+
+/// Integration test for passwordless login
+/// Covers requesting a link, logging in with it and rejecting its reuse
+#[tokio::test]
+async fn test_magic_link_login_flow() {
+    let user_repository = MockUserRepository::new();
+    let magic_link_repository = MockMagicLinkRepository::new();
+    let auth_service = AuthService::new(user_repository.clone(), "test-secret".to_string());
+
+    // 1. A regular password account needs no extra setup for magic links
+    let registered = auth_service
+        .register("alice", "alice@example.com", "password123")
+        .await
+        .unwrap();
+
+    // 2. Request a link; the lookup is case-insensitive like the rest of the email handling
+    let magic_link_service = MagicLinkService::new(magic_link_repository, user_repository);
+    let magic_link = magic_link_service
+        .create_link("Alice@Example.com")
+        .await
+        .unwrap();
+    assert_eq!(magic_link.user.id, registered.id);
+    assert_ne!(
+        magic_link.token,
+        MagicLinkService::<MockMagicLinkRepository, MockUserRepository>::hash_token(
+            &magic_link.token
+        )
+    );
+
+    // 3. Following the link logs the user in with a regular session token
+    let user = magic_link_service
+        .verify_link(&magic_link.token)
+        .await
+        .unwrap();
+    let token = auth_service
+        .issue_token(&user, &SessionClient::default())
+        .await
+        .unwrap();
+    let authenticated = auth_service.verify_token(&token).await.unwrap();
+    assert_eq!(authenticated.id, registered.id);
+
+    // 4. The password login keeps working alongside magic links
+    assert!(auth_service
+        .login("alice", "password123", &SessionClient::default())
+        .await
+        .is_ok());
+
+    // 5. Links are single-use
+    assert!(matches!(
+        magic_link_service.verify_link(&magic_link.token).await,
+        Err(MagicLinkError::TokenAlreadyUsed)
+    ));
+}
@@ -0,0 +1,147 @@
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+use url_shortner::infrastructure::object_storage::{
+    profile_picture_key, ObjectStorage, ObjectStorageError, S3ObjectStorage,
+};
+use wiremock::matchers::{body_bytes, header, header_regex, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn storage_for(server: &MockServer) -> S3ObjectStorage {
+    S3ObjectStorage::new(
+        &server.uri(),
+        "avatars",
+        "us-east-1",
+        "minio",
+        "minio-secret",
+    )
+}
+
+/// Integration test for storing profile pictures in an S3-compatible bucket
+/// Covers the signed upload, the returned public URL and deleting the object again
+#[tokio::test]
+async fn test_s3_upload_and_delete() {
+    let server = MockServer::start().await;
+    let storage = storage_for(&server);
+    let key = profile_picture_key(7, "jpg");
+    let object_path = format!("/avatars/{}", key);
+    let data = Bytes::from_static(b"jpeg bytes");
+
+    // 1. The upload is a signed path-style PUT carrying the file and its content type
+    Mock::given(method("PUT"))
+        .and(path(object_path.as_str()))
+        .and(header("content-type", "image/jpeg"))
+        .and(header(
+            "x-amz-content-sha256",
+            format!("{:x}", Sha256::digest(&data)).as_str(),
+        ))
+        .and(header_regex(
+            "authorization",
+            r"^AWS4-HMAC-SHA256 Credential=minio/\d{8}/us-east-1/s3/aws4_request, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature=[0-9a-f]{64}$",
+        ))
+        .and(body_bytes(data.to_vec()))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let url = storage
+        .upload(&key, data.clone(), "image/jpeg")
+        .await
+        .unwrap();
+    assert_eq!(url, format!("{}{}", server.uri(), object_path));
+    assert_eq!(storage.key_for_url(&url).as_deref(), Some(key.as_str()));
+
+    // 2. Deleting sends a signed DELETE for the same object
+    Mock::given(method("DELETE"))
+        .and(path(object_path.as_str()))
+        .and(header_regex("authorization", "^AWS4-HMAC-SHA256 "))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    storage.delete(&key).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_s3_delete_of_missing_object_succeeds() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    storage_for(&server)
+        .delete("profiles/1/missing.jpg")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_s3_upload_rejected_by_server() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(403).set_body_string("<Error>AccessDenied</Error>"))
+        .mount(&server)
+        .await;
+
+    let result = storage_for(&server)
+        .upload(
+            "profiles/1/a.jpg",
+            Bytes::from_static(b"jpeg"),
+            "image/jpeg",
+        )
+        .await;
+    match result {
+        Err(ObjectStorageError::UnexpectedStatus { status, message }) => {
+            assert_eq!(status, 403);
+            assert!(message.contains("AccessDenied"));
+        }
+        other => panic!("expected an unexpected status error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_public_url_override() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let storage = storage_for(&server).with_public_url("https://cdn.example.com/avatars");
+    let url = storage
+        .upload("profiles/1/a.jpg", Bytes::new(), "image/jpeg")
+        .await
+        .unwrap();
+    assert_eq!(url, "https://cdn.example.com/avatars/profiles/1/a.jpg");
+}
+
+/// Analytics exports are uploaded from a file, streamed with its length and hash
+#[tokio::test]
+async fn test_s3_upload_file_streams_with_content_length() {
+    let server = MockServer::start().await;
+    let dir = tempfile::TempDir::new().unwrap();
+    let source = dir.path().join("export.csv");
+    let data = "id,url_id\n1,1\n".repeat(10_000);
+    std::fs::write(&source, &data).unwrap();
+
+    Mock::given(method("PUT"))
+        .and(path("/avatars/exports/7/op-1.csv"))
+        .and(header("content-type", "text/csv"))
+        .and(header("content-length", data.len().to_string().as_str()))
+        .and(header(
+            "x-amz-content-sha256",
+            format!("{:x}", Sha256::digest(data.as_bytes())).as_str(),
+        ))
+        .and(body_bytes(data.as_bytes().to_vec()))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    storage_for(&server)
+        .upload_file("exports/7/op-1.csv", &source, "text/csv")
+        .await
+        .unwrap();
+}
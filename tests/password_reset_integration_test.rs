@@ -0,0 +1,238 @@
+use url_shortner::domain::entities::SessionClient;
+use url_shortner::domain::services::{
+    AuthService, AuthServiceError, PasswordResetError, PasswordResetService,
+};
+use url_shortner::infrastructure::test_utils::{MockPasswordResetRepository, MockUserRepository};
+
+fn services() -> (
+    AuthService<MockUserRepository>,
+    PasswordResetService<MockPasswordResetRepository, MockUserRepository>,
+) {
+    let user_repository = MockUserRepository::new();
+    let auth_service = AuthService::new(user_repository.clone(), "test-secret".to_string());
+    let reset_service =
+        PasswordResetService::new_default(MockPasswordResetRepository::new(), user_repository);
+    (auth_service, reset_service)
+}
+
+/// A reset token works exactly once
+#[tokio::test]
+async fn test_reset_token_cannot_be_reused() {
+    let (auth_service, reset_service) = services();
+    auth_service
+        .register("alice", "alice@example.com", "password123")
+        .await
+        .unwrap();
+
+    let request = reset_service
+        .create_reset_request("alice@example.com", None)
+        .await
+        .unwrap();
+    reset_service
+        .reset_password(&request.token, "new-password-1")
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        reset_service
+            .reset_password(&request.token, "attacker-password")
+            .await,
+        Err(PasswordResetError::TokenAlreadyUsed)
+    ));
+    assert!(auth_service
+        .login("alice", "new-password-1", &SessionClient::default())
+        .await
+        .is_ok());
+    assert!(auth_service
+        .login("alice", "attacker-password", &SessionClient::default())
+        .await
+        .is_err());
+}
+
+/// An intercepted link stops working once the victim resets with another link
+#[tokio::test]
+async fn test_intercepted_link_is_invalidated_by_reset() {
+    let (auth_service, reset_service) = services();
+    auth_service
+        .register("alice", "alice@example.com", "password123")
+        .await
+        .unwrap();
+
+    let intercepted = reset_service
+        .create_reset_request("alice@example.com", None)
+        .await
+        .unwrap();
+    let victims = reset_service
+        .create_reset_request("alice@example.com", None)
+        .await
+        .unwrap();
+
+    reset_service
+        .reset_password(&victims.token, "victims-new-password")
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        reset_service
+            .reset_password(&intercepted.token, "attacker-password")
+            .await,
+        Err(PasswordResetError::TokenAlreadyUsed)
+    ));
+}
+
+/// Requesting a new link invalidates the previous one before anyone uses it
+#[tokio::test]
+async fn test_second_token_invalidates_first() {
+    let (auth_service, reset_service) = services();
+    auth_service
+        .register("alice", "alice@example.com", "password123")
+        .await
+        .unwrap();
+
+    let first = reset_service
+        .create_reset_request("alice@example.com", None)
+        .await
+        .unwrap();
+    let second = reset_service
+        .create_reset_request("alice@example.com", None)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        reset_service.validate_token(&first.token).await,
+        Err(PasswordResetError::TokenAlreadyUsed)
+    ));
+    assert!(reset_service.validate_token(&second.token).await.is_ok());
+    assert_eq!(
+        reset_service
+            .get_active_tokens_for_user(second.user_id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+/// Concurrent resets with the same token: only one may change the password
+#[tokio::test]
+async fn test_concurrent_resets_with_same_token() {
+    let (auth_service, reset_service) = services();
+    auth_service
+        .register("alice", "alice@example.com", "password123")
+        .await
+        .unwrap();
+    let request = reset_service
+        .create_reset_request("alice@example.com", None)
+        .await
+        .unwrap();
+
+    let (first, second) = tokio::join!(
+        reset_service.reset_password(&request.token, "first-password"),
+        reset_service.reset_password(&request.token, "second-password"),
+    );
+
+    let results = [first, second];
+    assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+    assert!(results
+        .iter()
+        .any(|r| matches!(r, Err(PasswordResetError::TokenAlreadyUsed))));
+}
+
+/// Sessions issued before a reset are rejected afterwards
+#[tokio::test]
+async fn test_reset_invalidates_existing_sessions() {
+    let (auth_service, reset_service) = services();
+    auth_service
+        .register("alice", "alice@example.com", "password123")
+        .await
+        .unwrap();
+    let old_session = auth_service
+        .login("alice", "password123", &SessionClient::default())
+        .await
+        .unwrap();
+    assert!(auth_service.verify_token(&old_session).await.is_ok());
+
+    let request = reset_service
+        .create_reset_request("alice@example.com", None)
+        .await
+        .unwrap();
+    reset_service
+        .reset_password(&request.token, "new-password-1")
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        auth_service.verify_token(&old_session).await,
+        Err(AuthServiceError::TokenValidation(_))
+    ));
+    let new_session = auth_service
+        .login("alice", "new-password-1", &SessionClient::default())
+        .await
+        .unwrap();
+    assert!(auth_service.verify_token(&new_session).await.is_ok());
+}
+
+/// Changing the password also invalidates pending reset links
+#[tokio::test]
+async fn test_password_change_invalidates_reset_tokens() {
+    let (auth_service, reset_service) = services();
+    let user = auth_service
+        .register("alice", "alice@example.com", "password123")
+        .await
+        .unwrap();
+    let request = reset_service
+        .create_reset_request("alice@example.com", None)
+        .await
+        .unwrap();
+
+    auth_service
+        .change_password(&user, "password123", "changed-password")
+        .await
+        .unwrap();
+    let invalidated = reset_service
+        .invalidate_all_tokens_for_user(user.id)
+        .await
+        .unwrap();
+    assert_eq!(invalidated, 1);
+
+    assert!(matches!(
+        reset_service
+            .reset_password(&request.token, "attacker-password")
+            .await,
+        Err(PasswordResetError::TokenAlreadyUsed)
+    ));
+}
+
+/// The reset email is queued together with its token, and only for a created token
+#[tokio::test]
+async fn test_reset_email_is_queued_with_the_token() {
+    let user_repository = MockUserRepository::new();
+    let reset_repository = MockPasswordResetRepository::new();
+    let auth_service = AuthService::new(user_repository.clone(), "test-secret".to_string());
+    let reset_service =
+        PasswordResetService::new_default(reset_repository.clone(), user_repository);
+    auth_service
+        .register("alice", "alice@example.com", "password123")
+        .await
+        .unwrap();
+
+    let request = reset_service
+        .create_reset_request("alice@example.com", Some("https://short.ly"))
+        .await
+        .unwrap();
+    let queued = reset_repository.outbox().emails();
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0].recipient, "alice@example.com");
+    assert!(queued[0].body_text.contains(&format!(
+        "https://short.ly/reset-password?token={}",
+        request.token
+    )));
+
+    assert!(matches!(
+        reset_service
+            .create_reset_request("nobody@example.com", Some("https://short.ly"))
+            .await,
+        Err(PasswordResetError::UserNotFound)
+    ));
+    assert_eq!(reset_repository.outbox().emails().len(), 1);
+}
@@ -0,0 +1,132 @@
+//! Checks of the index migration and, against a real database, of the query plans it targets
+
+const INIT_SQL: &str = include_str!("../init.sql");
+const ADD_MISSING_INDEXES: &str = include_str!("../migrations/add_missing_indexes.sql");
+
+/// SQL statements of a script, without comment lines
+fn statements(sql: &str) -> Vec<String> {
+    sql.lines()
+        .filter(|line| !line.trim_start().starts_with("--"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .split(';')
+        .map(|statement| statement.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|statement| !statement.is_empty())
+        .collect()
+}
+
+/// Name of the index created by a `CREATE INDEX` statement
+fn created_index_name(statement: &str) -> Option<&str> {
+    let rest = statement.split(" IF NOT EXISTS ").nth(1)?;
+    rest.split_whitespace().next()
+}
+
+#[test]
+fn test_migration_is_idempotent() {
+    for statement in statements(ADD_MISSING_INDEXES) {
+        if statement.starts_with("CREATE") {
+            assert!(statement.contains(" IF NOT EXISTS "), "{}", statement);
+        } else {
+            assert!(statement.starts_with("DROP INDEX"), "{}", statement);
+            assert!(statement.contains(" IF EXISTS "), "{}", statement);
+        }
+    }
+}
+
+#[test]
+fn test_fresh_schema_matches_migration() {
+    let init = statements(INIT_SQL);
+    for statement in statements(ADD_MISSING_INDEXES) {
+        if let Some(name) = created_index_name(&statement) {
+            assert!(
+                init.iter()
+                    .any(|s| s.starts_with("CREATE") && created_index_name(s) == Some(name)),
+                "init.sql is missing index {}",
+                name
+            );
+        }
+    }
+    assert!(!INIT_SQL.contains("idx_urls_status ON"));
+    assert!(!INIT_SQL.contains("idx_urls_user_created_at ON"));
+}
+
+/// Plan of a query with sequential scans disabled, so small tables still show the index used
+async fn explain(pool: &sqlx::PgPool, query: &str) -> String {
+    let mut connection = pool.acquire().await.unwrap();
+    sqlx::query("SET enable_seqscan = off")
+        .execute(&mut *connection)
+        .await
+        .unwrap();
+    let lines: Vec<String> = sqlx::query_scalar(&format!("EXPLAIN ANALYZE {}", query))
+        .fetch_all(&mut *connection)
+        .await
+        .unwrap();
+    lines.join("\n")
+}
+
+/// Documents the expected plans of the most common queries
+///
+/// Needs a database created from init.sql:
+/// `TEST_DATABASE_URL=postgres://... cargo test --test schema_indexes_test -- --ignored`
+#[tokio::test]
+#[ignore]
+async fn test_common_queries_use_indexes() {
+    let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+    let pool = sqlx::PgPool::connect(&url).await.unwrap();
+
+    let cases = [
+        (
+            "SELECT id FROM urls WHERE user_id = 1 ORDER BY created_at DESC LIMIT 20",
+            "idx_urls_user_created_at_id",
+        ),
+        (
+            "SELECT id FROM urls
+             WHERE user_id = 1 AND (created_at, id) < (NOW(), 50)
+             ORDER BY created_at DESC, id DESC LIMIT 20",
+            "idx_urls_user_created_at_id",
+        ),
+        (
+            "SELECT id FROM urls
+             WHERE user_id = 1 AND (short_code, id) > ('abc', 50)
+             ORDER BY short_code ASC, id ASC LIMIT 20",
+            "idx_urls_user_short_code_id",
+        ),
+        (
+            "SELECT id FROM urls WHERE status = 'active' AND user_id = 1 ORDER BY created_at DESC",
+            "idx_urls_status_user_id",
+        ),
+        (
+            "SELECT id FROM urls WHERE expiration_date IS NOT NULL AND expiration_date <= NOW()",
+            "idx_urls_expiration_date",
+        ),
+        (
+            "SELECT id FROM urls
+             WHERE url_hash = md5('https://example.com') AND original_url = 'https://example.com'
+             ORDER BY created_at DESC, id DESC LIMIT 50",
+            "idx_urls_url_hash",
+        ),
+        (
+            "SELECT id FROM clicks
+             WHERE url_id = 1 AND clicked_at >= NOW() - INTERVAL '7 days'
+             ORDER BY clicked_at DESC LIMIT 10",
+            // With few clicks per URL either (url_id, ...) index is as cheap
+            "idx_clicks_url_",
+        ),
+        (
+            "SELECT country_code, COUNT(*) FROM clicks
+             WHERE url_id = 1 AND country_code IS NOT NULL GROUP BY country_code",
+            "idx_clicks_url_country",
+        ),
+    ];
+
+    for (query, index) in cases {
+        let plan = explain(&pool, query).await;
+        println!("{}\n{}\n", query, plan);
+        assert!(
+            plan.contains(index),
+            "expected {} in plan:\n{}",
+            index,
+            plan
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use tower::ServiceExt;
+use tracing_test::traced_test;
+use url_shortner::domain::entities::{ShortCode, UrlStatus};
+use url_shortner::domain::repositories::UrlRepository;
+use url_shortner::infrastructure::test_utils::TestApp;
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+#[traced_test]
+#[tokio::test]
+async fn test_redirect_logs_share_the_request_trace_id() {
+    let app = TestApp::new();
+    app.url_repository
+        .create_url(
+            &ShortCode::new("trace1".to_string()).unwrap(),
+            "https://example.com/traced",
+            None,
+            None,
+            None,
+            UrlStatus::Active,
+        )
+        .await
+        .unwrap();
+
+    let request = Request::builder()
+        .uri("/trace1")
+        .header(
+            "traceparent",
+            format!("00-{}-00f067aa0ba902b7-01", TRACE_ID),
+        )
+        .body(Body::empty())
+        .unwrap();
+    let response = app.router().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+
+    let trace_id = format!("trace_id={}", TRACE_ID);
+    logs_assert(|lines: &[&str]| {
+        // One line from the span of each layer of the redirect, all in the request's trace
+        for (span, needle) in [
+            ("redirect_handler{", "Received redirect request"),
+            ("get_url_for_redirect{", "Looking up URL for redirect"),
+            ("find_by_short_code{", "Routing query to the primary"),
+            ("record_click{", "Recording click"),
+        ] {
+            let line = lines
+                .iter()
+                .find(|line| line.contains(needle))
+                .ok_or_else(|| format!("no log line containing {:?}", needle))?;
+            if !line.contains(span) {
+                return Err(format!("{:?} is not in the {} span", line, span));
+            }
+            if !line.contains(&trace_id) {
+                return Err(format!("{:?} is not in the request trace", line));
+            }
+        }
+        Ok(())
+    });
+}